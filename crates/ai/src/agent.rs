@@ -0,0 +1,155 @@
+//! Behavior tree components and their fixed-rate update.
+//!
+//! Entities with AI carry a [`BehaviorAgent`] naming the tree they run and
+//! a [`Blackboard`] component. The [`AiRunner`] ticks every agent a whole
+//! number of fixed steps per frame, so behavior does not depend on the
+//! frame rate.
+
+use crate::behavior_tree::{BehaviorTree, TreeState};
+use crate::blackboard::Blackboard;
+use crate::task::{Status, TaskRegistry};
+use scheduler::FixedTimestep;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default AI tick rate, in ticks per second.
+pub const DEFAULT_TICK_RATE: u32 = 20;
+
+/// Component running a behavior tree.
+#[derive(Debug, Clone)]
+pub struct BehaviorAgent {
+    /// Tree shared with other agents.
+    tree: Arc<BehaviorTree>,
+    /// This agent's progress through the tree.
+    state: TreeState,
+    /// Result of the last tick.
+    status: Option<Status>,
+}
+
+impl BehaviorAgent {
+    /// Creates an agent starting `tree` from the root.
+    #[must_use]
+    pub fn new(tree: Arc<BehaviorTree>) -> Self {
+        let state = TreeState::new(&tree);
+        Self {
+            tree,
+            state,
+            status: None,
+        }
+    }
+
+    /// Returns the tree the agent runs.
+    #[must_use]
+    #[inline]
+    pub fn tree(&self) -> &Arc<BehaviorTree> {
+        &self.tree
+    }
+
+    /// Returns the result of the last tick, or `None` before the first.
+    #[must_use]
+    #[inline]
+    pub fn status(&self) -> Option<Status> {
+        self.status
+    }
+
+    /// Switches to another tree, starting it from the root.
+    pub fn set_tree(&mut self, tree: Arc<BehaviorTree>) {
+        self.state = TreeState::new(&tree);
+        self.tree = tree;
+        self.status = None;
+    }
+
+    /// Ticks the tree once.
+    pub fn tick<E: Copy>(
+        &mut self,
+        entity: E,
+        blackboard: &mut Blackboard,
+        delta: Duration,
+        tasks: &mut TaskRegistry<E>,
+    ) -> Status {
+        let status = self
+            .tree
+            .tick(&mut self.state, entity, blackboard, delta, tasks);
+        self.status = Some(status);
+        status
+    }
+}
+
+/// Ticks behavior agents at a fixed rate.
+///
+/// # Example
+///
+/// ```
+/// use ai::{AiRunner, BehaviorAgent, BehaviorTree, Node, Status, TaskContext, TaskRegistry};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// let mut tasks = TaskRegistry::new();
+/// tasks.register("count", |ctx: &mut TaskContext<'_, u32>| {
+///     let ticks = ctx.blackboard.get_int("ticks").unwrap_or(0);
+///     ctx.blackboard.set("ticks", ticks + 1);
+///     Status::Success
+/// });
+/// let mut runner = AiRunner::new(tasks, 10);
+///
+/// let tree = Arc::new(BehaviorTree::new(Node::Task("count".to_owned())));
+/// let mut agent = BehaviorAgent::new(tree.clone());
+/// let mut blackboard = tree.blackboard.clone();
+///
+/// // Half a second at 10 ticks per second.
+/// runner.update(Duration::from_millis(500), [(1, &mut agent, &mut blackboard)]);
+/// assert_eq!(blackboard.get_int("ticks"), Some(5));
+/// ```
+#[derive(Debug)]
+pub struct AiRunner<E> {
+    /// Tasks the trees can run.
+    tasks: TaskRegistry<E>,
+    /// Converts frame time into ticks.
+    timestep: FixedTimestep,
+}
+
+impl<E: Copy> AiRunner<E> {
+    /// Creates a runner ticking `tick_rate` times per second.
+    #[must_use]
+    pub fn new(tasks: TaskRegistry<E>, tick_rate: u32) -> Self {
+        Self {
+            tasks,
+            timestep: FixedTimestep::from_hz(tick_rate),
+        }
+    }
+
+    /// Returns the registered tasks.
+    #[must_use]
+    #[inline]
+    pub fn tasks(&self) -> &TaskRegistry<E> {
+        &self.tasks
+    }
+
+    /// Returns the registered tasks for adding more.
+    #[inline]
+    pub fn tasks_mut(&mut self) -> &mut TaskRegistry<E> {
+        &mut self.tasks
+    }
+
+    /// Adds a frame's time and ticks every agent once per fixed step due.
+    ///
+    /// Returns the number of steps run.
+    pub fn update<'a>(
+        &mut self,
+        delta: Duration,
+        agents: impl IntoIterator<Item = (E, &'a mut BehaviorAgent, &'a mut Blackboard)>,
+    ) -> u32 {
+        let steps = self.timestep.advance(delta);
+        if steps == 0 {
+            return 0;
+        }
+        let step = self.timestep.step();
+        let mut agents: Vec<_> = agents.into_iter().collect();
+        for _ in 0..steps {
+            for (entity, agent, blackboard) in &mut agents {
+                agent.tick(*entity, blackboard, step, &mut self.tasks);
+            }
+        }
+        steps
+    }
+}
@@ -0,0 +1,64 @@
+//! Unit tests for behavior agents and fixed-rate ticking.
+
+use crate::agent::{AiRunner, BehaviorAgent};
+use crate::behavior_tree::{BehaviorTree, Node};
+use crate::blackboard::Blackboard;
+use crate::task::{Status, TaskContext, TaskRegistry};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn counting_runner() -> AiRunner<u32> {
+    let mut tasks = TaskRegistry::new();
+    tasks.register("count", |ctx: &mut TaskContext<'_, u32>| {
+        let ticks = ctx.blackboard.get_int("ticks").unwrap_or(0);
+        ctx.blackboard.set("ticks", ticks + 1);
+        Status::Success
+    });
+    AiRunner::new(tasks, 20)
+}
+
+/// Verifies agents tick once per fixed step, not once per frame.
+#[test]
+fn ticks_at_fixed_rate() {
+    let mut runner = counting_runner();
+    let tree = Arc::new(BehaviorTree::new(Node::Task("count".to_owned())));
+    let mut agents = [
+        (BehaviorAgent::new(tree.clone()), Blackboard::new()),
+        (BehaviorAgent::new(tree), Blackboard::new()),
+    ];
+
+    for _ in 0..4 {
+        runner.update(
+            Duration::from_millis(25),
+            (0..)
+                .zip(&mut agents)
+                .map(|(entity, (agent, blackboard))| (entity, agent, blackboard)),
+        );
+    }
+
+    for (agent, blackboard) in &agents {
+        assert_eq!(blackboard.get_int("ticks"), Some(2));
+        assert_eq!(agent.status(), Some(Status::Success));
+    }
+}
+
+/// Verifies switching trees restarts the agent.
+#[test]
+fn set_tree_restarts() {
+    let mut runner = counting_runner();
+    let mut agent = BehaviorAgent::new(Arc::new(BehaviorTree::new(Node::Wait(10.0))));
+    let mut blackboard = Blackboard::new();
+    runner.update(
+        Duration::from_millis(50),
+        [(0, &mut agent, &mut blackboard)],
+    );
+    assert_eq!(agent.status(), Some(Status::Running));
+
+    agent.set_tree(Arc::new(BehaviorTree::new(Node::Task("count".to_owned()))));
+    assert_eq!(agent.status(), None);
+    runner.update(
+        Duration::from_millis(50),
+        [(0, &mut agent, &mut blackboard)],
+    );
+    assert_eq!(blackboard.get_int("ticks"), Some(1));
+}
@@ -0,0 +1,75 @@
+//! AI subsystem implementation.
+//!
+//! Provides the [`RustgineAi`] system, which owns the AI tick rate.
+
+use crate::agent::DEFAULT_TICK_RATE;
+use rustgine_core::{RustgineError, RustgineSystem};
+use tracing::info;
+
+/// AI subsystem for the Rustgine engine.
+///
+/// Manages:
+/// - Behavior tree assets and the tasks they run
+/// - Per-entity blackboards
+/// - Fixed-rate ticking of behavior agents
+///
+/// # Example
+///
+/// ```ignore
+/// use ai::RustgineAi;
+/// use rustgine_core::RustgineSystem;
+///
+/// let mut ai = RustgineAi::default();
+/// ai.startup()?;
+/// ```
+#[derive(Debug)]
+pub struct RustgineAi {
+    /// Behavior tree ticks per second.
+    tick_rate: u32,
+}
+
+impl Default for RustgineAi {
+    fn default() -> Self {
+        Self {
+            tick_rate: DEFAULT_TICK_RATE,
+        }
+    }
+}
+
+impl RustgineAi {
+    /// Sets how many times per second behavior trees are ticked.
+    #[must_use]
+    pub fn with_tick_rate(mut self, tick_rate: u32) -> Self {
+        self.tick_rate = tick_rate.max(1);
+        self
+    }
+
+    /// Returns how many times per second behavior trees are ticked.
+    #[must_use]
+    #[inline]
+    pub fn tick_rate(&self) -> u32 {
+        self.tick_rate
+    }
+}
+
+impl RustgineSystem for RustgineAi {
+    /// Initializes the AI subsystem.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if AI initialization fails.
+    fn startup(&mut self) -> Result<(), RustgineError> {
+        info!(tick_rate = self.tick_rate, "ai started");
+        Ok(())
+    }
+
+    /// Shuts down the AI subsystem.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if cleanup fails.
+    #[inline]
+    fn shutdown(&mut self) -> Result<(), RustgineError> {
+        Ok(())
+    }
+}
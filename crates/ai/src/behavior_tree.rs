@@ -0,0 +1,332 @@
+//! Data-driven behavior trees.
+//!
+//! A [`BehaviorTree`] is an asset describing a tree of [`Node`]s and the
+//! initial blackboard values its entities start with:
+//!
+//! ```toml
+//! [blackboard]
+//! patrol_speed = 2.5
+//!
+//! [root]
+//! selector = [
+//!     { sequence = [{ condition = { key = "has_target" } }, { task = "chase" }] },
+//!     { sequence = [{ task = "next_waypoint" }, { task = "walk" }, { wait = 1.0 }] },
+//! ]
+//! ```
+//!
+//! Composites remember the child that was running, so a sequence resumes
+//! where it left off on the next tick instead of re-running earlier
+//! children. That progress lives in a per-entity [`TreeState`], which lets
+//! many entities share one tree asset.
+
+use crate::blackboard::{Blackboard, BlackboardValue};
+use crate::task::{Status, TaskContext, TaskRegistry};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// A behavior tree node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Node {
+    /// Runs children in order until one fails.
+    Sequence(Vec<Node>),
+    /// Runs children in order until one succeeds.
+    Selector(Vec<Node>),
+    /// Swaps the child's success and failure.
+    Inverter(Box<Node>),
+    /// Succeeds once the child finishes, whatever its result.
+    Succeeder(Box<Node>),
+    /// Runs the child repeatedly until it fails.
+    Repeat {
+        /// Successful runs before the node succeeds; zero repeats forever.
+        #[serde(default)]
+        times: u32,
+        /// Repeated node.
+        child: Box<Node>,
+    },
+    /// Runs for the given number of seconds, then succeeds.
+    Wait(f64),
+    /// Succeeds if a blackboard value is set and matches.
+    Condition {
+        /// Blackboard key.
+        key: String,
+        /// Value to compare against; without one, the value must be truthy.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        equals: Option<BlackboardValue>,
+    },
+    /// Runs a task registered in the [`TaskRegistry`].
+    Task(String),
+    /// Runs a script through the registry's script runner.
+    Script(String),
+}
+
+impl Node {
+    /// Returns the number of nodes in this subtree.
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        1 + self.children().map(Node::node_count).sum::<usize>()
+    }
+
+    /// Iterates over the direct children.
+    pub fn children(&self) -> impl Iterator<Item = &Node> {
+        let children: &[Node] = match self {
+            Self::Sequence(children) | Self::Selector(children) => children,
+            Self::Inverter(child) | Self::Succeeder(child) | Self::Repeat { child, .. } => {
+                std::slice::from_ref(&**child)
+            }
+            Self::Wait(_) | Self::Condition { .. } | Self::Task(_) | Self::Script(_) => &[],
+        };
+        children.iter()
+    }
+
+    /// Calls `visit` for every node in this subtree, parents first.
+    fn walk<'a>(&'a self, visit: &mut impl FnMut(&'a Node)) {
+        visit(self);
+        for child in self.children() {
+            child.walk(visit);
+        }
+    }
+}
+
+/// A behavior tree asset.
+///
+/// # Example
+///
+/// ```
+/// use ai::{BehaviorTree, Blackboard, Status, TaskContext, TaskRegistry, TreeState};
+/// use std::time::Duration;
+///
+/// let tree = BehaviorTree::from_toml(r#"
+///     [root]
+///     sequence = [{ task = "aim" }, { wait = 0.5 }, { task = "fire" }]
+/// "#).unwrap();
+///
+/// let mut tasks = TaskRegistry::new();
+/// tasks.register("aim", |_: &mut TaskContext<'_, ()>| Status::Success);
+/// tasks.register("fire", |_: &mut TaskContext<'_, ()>| Status::Success);
+/// tree.validate(&tasks).unwrap();
+///
+/// let mut state = TreeState::new(&tree);
+/// let mut blackboard = tree.blackboard.clone();
+/// let step = Duration::from_millis(250);
+/// assert_eq!(tree.tick(&mut state, (), &mut blackboard, step, &mut tasks), Status::Running);
+/// assert_eq!(tree.tick(&mut state, (), &mut blackboard, step, &mut tasks), Status::Success);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BehaviorTree {
+    /// Initial blackboard values for entities running the tree.
+    #[serde(default, skip_serializing_if = "Blackboard::is_empty")]
+    pub blackboard: Blackboard,
+    /// Root node.
+    pub root: Node,
+}
+
+impl BehaviorTree {
+    /// Creates a tree with an empty initial blackboard.
+    #[must_use]
+    pub fn new(root: Node) -> Self {
+        Self {
+            blackboard: Blackboard::new(),
+            root,
+        }
+    }
+
+    /// Parses a tree asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is not a valid behavior tree.
+    pub fn from_toml(text: &str) -> anyhow::Result<Self> {
+        toml::from_str(text).map_err(|e| anyhow::anyhow!("invalid behavior tree: {e}"))
+    }
+
+    /// Serializes the tree as an asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a node cannot be represented in TOML.
+    pub fn to_toml(&self) -> anyhow::Result<String> {
+        toml::to_string(self).map_err(|e| anyhow::anyhow!("failed to serialize behavior tree: {e}"))
+    }
+
+    /// Checks that every task the tree runs is registered, and that a
+    /// script runner is set if the tree runs scripts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first missing task or script runner.
+    pub fn validate<E>(&self, tasks: &TaskRegistry<E>) -> anyhow::Result<()> {
+        let mut result = Ok(());
+        self.root.walk(&mut |node| {
+            if result.is_err() {
+                return;
+            }
+            match node {
+                Node::Task(name) if !tasks.contains(name) => {
+                    result = Err(anyhow::anyhow!("unknown behavior tree task {name:?}"));
+                }
+                Node::Script(path) if !tasks.runs_scripts() => {
+                    result = Err(anyhow::anyhow!(
+                        "behavior tree runs script {path:?} but no script runner is set"
+                    ));
+                }
+                _ => {}
+            }
+        });
+        result
+    }
+
+    /// Ticks the tree once for `entity`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `state` was created for a different tree.
+    pub fn tick<E: Copy>(
+        &self,
+        state: &mut TreeState,
+        entity: E,
+        blackboard: &mut Blackboard,
+        delta: Duration,
+        tasks: &mut TaskRegistry<E>,
+    ) -> Status {
+        assert_eq!(
+            state.nodes.len(),
+            self.root.node_count(),
+            "tree state belongs to another tree"
+        );
+        let mut ctx = TaskContext {
+            entity,
+            blackboard,
+            delta,
+        };
+        tick_node(&self.root, 0, state, &mut ctx, tasks)
+    }
+}
+
+/// Progress of one node between ticks.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct NodeState {
+    /// Running child of a composite, or completed runs of a repeat.
+    counter: u32,
+    /// Time spent in a wait.
+    elapsed: Duration,
+}
+
+/// Per-entity progress through a [`BehaviorTree`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeState {
+    /// State of every node, in depth-first order.
+    nodes: Vec<NodeState>,
+}
+
+impl TreeState {
+    /// Creates the state for an entity starting `tree`.
+    #[must_use]
+    pub fn new(tree: &BehaviorTree) -> Self {
+        Self {
+            nodes: vec![NodeState::default(); tree.root.node_count()],
+        }
+    }
+
+    /// Restarts the tree from the root on the next tick.
+    pub fn reset(&mut self) {
+        self.nodes.fill(NodeState::default());
+    }
+}
+
+/// Ticks `node`, whose depth-first index is `id`.
+fn tick_node<E: Copy>(
+    node: &Node,
+    id: usize,
+    state: &mut TreeState,
+    ctx: &mut TaskContext<'_, E>,
+    tasks: &mut TaskRegistry<E>,
+) -> Status {
+    match node {
+        Node::Sequence(children) => {
+            tick_composite(children, Status::Success, id, state, ctx, tasks)
+        }
+        Node::Selector(children) => {
+            tick_composite(children, Status::Failure, id, state, ctx, tasks)
+        }
+        Node::Inverter(child) => match tick_node(child, id + 1, state, ctx, tasks) {
+            Status::Success => Status::Failure,
+            Status::Failure => Status::Success,
+            Status::Running => Status::Running,
+        },
+        Node::Succeeder(child) => match tick_node(child, id + 1, state, ctx, tasks) {
+            Status::Running => Status::Running,
+            Status::Success | Status::Failure => Status::Success,
+        },
+        Node::Repeat { times, child } => match tick_node(child, id + 1, state, ctx, tasks) {
+            Status::Running => Status::Running,
+            Status::Failure => {
+                state.nodes[id].counter = 0;
+                Status::Failure
+            }
+            Status::Success => {
+                state.nodes[id].counter += 1;
+                if *times > 0 && state.nodes[id].counter >= *times {
+                    state.nodes[id].counter = 0;
+                    Status::Success
+                } else {
+                    Status::Running
+                }
+            }
+        },
+        Node::Wait(seconds) => {
+            let elapsed = state.nodes[id].elapsed + ctx.delta;
+            if elapsed.as_secs_f64() >= *seconds {
+                state.nodes[id].elapsed = Duration::ZERO;
+                Status::Success
+            } else {
+                state.nodes[id].elapsed = elapsed;
+                Status::Running
+            }
+        }
+        Node::Condition { key, equals } => {
+            let value = ctx.blackboard.get(key);
+            let holds = match equals {
+                Some(expected) => value == Some(expected),
+                None => value.is_some_and(BlackboardValue::is_truthy),
+            };
+            if holds {
+                Status::Success
+            } else {
+                Status::Failure
+            }
+        }
+        Node::Task(name) => tasks.run(name, ctx),
+        Node::Script(path) => tasks.run_script(path, ctx),
+    }
+}
+
+/// Ticks a sequence (`done` is success) or selector (`done` is failure),
+/// resuming at the child that was running.
+fn tick_composite<E: Copy>(
+    children: &[Node],
+    done: Status,
+    id: usize,
+    state: &mut TreeState,
+    ctx: &mut TaskContext<'_, E>,
+    tasks: &mut TaskRegistry<E>,
+) -> Status {
+    let resume = state.nodes[id].counter as usize;
+    let mut child_id = id + 1;
+    for (index, child) in children.iter().enumerate() {
+        if index >= resume {
+            let status = tick_node(child, child_id, state, ctx, tasks);
+            if status == Status::Running {
+                state.nodes[id].counter = u32::try_from(index).unwrap_or(u32::MAX);
+                return Status::Running;
+            }
+            if status != done {
+                state.nodes[id].counter = 0;
+                return status;
+            }
+        }
+        child_id += child.node_count();
+    }
+    state.nodes[id].counter = 0;
+    done
+}
@@ -0,0 +1,176 @@
+//! Unit tests for behavior tree assets and ticking.
+
+use crate::behavior_tree::{BehaviorTree, Node, TreeState};
+use crate::blackboard::{Blackboard, BlackboardValue};
+use crate::task::{Status, TaskContext, TaskRegistry};
+use std::time::Duration;
+
+const STEP: Duration = Duration::from_millis(100);
+
+/// Registry whose tasks append their name to the blackboard's `log`.
+fn logging_tasks() -> TaskRegistry<()> {
+    let mut tasks = TaskRegistry::new();
+    for (name, status) in [
+        ("ok", Status::Success),
+        ("fail", Status::Failure),
+        ("busy", Status::Running),
+    ] {
+        tasks.register(name, move |ctx: &mut TaskContext<'_, ()>| {
+            let log = ctx
+                .blackboard
+                .get_text("log")
+                .unwrap_or_default()
+                .to_owned();
+            ctx.blackboard.set("log", log + name + ";");
+            status
+        });
+    }
+    tasks
+}
+
+fn task(name: &str) -> Node {
+    Node::Task(name.to_owned())
+}
+
+/// Ticks `tree` `ticks` times and returns the last status and the log.
+fn run(tree: &BehaviorTree, ticks: usize) -> (Status, String) {
+    let mut tasks = logging_tasks();
+    let mut state = TreeState::new(tree);
+    let mut blackboard = tree.blackboard.clone();
+    let mut status = Status::Running;
+    for _ in 0..ticks {
+        status = tree.tick(&mut state, (), &mut blackboard, STEP, &mut tasks);
+    }
+    let log = blackboard.get_text("log").unwrap_or_default().to_owned();
+    (status, log)
+}
+
+/// Verifies sequences stop at the first failure and selectors at the first
+/// success.
+#[test]
+fn composites_short_circuit() {
+    let sequence = BehaviorTree::new(Node::Sequence(vec![task("ok"), task("fail"), task("ok")]));
+    let selector = BehaviorTree::new(Node::Selector(vec![task("fail"), task("ok"), task("fail")]));
+
+    assert_eq!(run(&sequence, 1), (Status::Failure, "ok;fail;".to_owned()));
+    assert_eq!(run(&selector, 1), (Status::Success, "fail;ok;".to_owned()));
+}
+
+/// Verifies a running child is resumed without re-running earlier ones.
+#[test]
+fn running_child_resumes() {
+    let tree = BehaviorTree::new(Node::Sequence(vec![
+        task("ok"),
+        Node::Wait(0.25),
+        task("ok"),
+    ]));
+
+    assert_eq!(run(&tree, 2), (Status::Running, "ok;".to_owned()));
+    assert_eq!(run(&tree, 3), (Status::Success, "ok;ok;".to_owned()));
+    // Restarts from the first child after finishing.
+    assert_eq!(run(&tree, 4), (Status::Running, "ok;ok;ok;".to_owned()));
+}
+
+/// Verifies decorators transform their child's result.
+#[test]
+fn decorators() {
+    let inverted = BehaviorTree::new(Node::Inverter(Box::new(task("fail"))));
+    let succeeded = BehaviorTree::new(Node::Succeeder(Box::new(task("fail"))));
+    let repeated = BehaviorTree::new(Node::Repeat {
+        times: 3,
+        child: Box::new(task("ok")),
+    });
+
+    assert_eq!(run(&inverted, 1).0, Status::Success);
+    assert_eq!(run(&succeeded, 1).0, Status::Success);
+    assert_eq!(run(&repeated, 2), (Status::Running, "ok;ok;".to_owned()));
+    assert_eq!(run(&repeated, 3).0, Status::Success);
+}
+
+/// Verifies conditions read the blackboard.
+#[test]
+fn conditions_read_blackboard() {
+    let mut tree = BehaviorTree::new(Node::Sequence(vec![
+        Node::Condition {
+            key: "alert".to_owned(),
+            equals: None,
+        },
+        Node::Condition {
+            key: "mode".to_owned(),
+            equals: Some(BlackboardValue::Text("hunt".to_owned())),
+        },
+        task("ok"),
+    ]));
+    assert_eq!(run(&tree, 1).0, Status::Failure);
+
+    tree.blackboard.set("alert", true);
+    tree.blackboard.set("mode", "hunt");
+    assert_eq!(run(&tree, 1).0, Status::Success);
+}
+
+/// Verifies scripts go through the script runner and fail without one.
+#[test]
+fn scripts_use_runner() {
+    let tree = BehaviorTree::new(Node::Script("scripts/patrol.lua".to_owned()));
+    let mut tasks = TaskRegistry::new();
+    let mut state = TreeState::new(&tree);
+    let mut blackboard = Blackboard::new();
+
+    assert!(tree.validate(&tasks).is_err());
+    assert_eq!(
+        tree.tick(&mut state, 5_u32, &mut blackboard, STEP, &mut tasks),
+        Status::Failure
+    );
+    tasks.set_script_runner(|path, ctx: &mut TaskContext<'_, u32>| {
+        ctx.blackboard.set("ran", format!("{path}@{}", ctx.entity));
+        Status::Success
+    });
+    assert!(tree.validate(&tasks).is_ok());
+    assert_eq!(
+        tree.tick(&mut state, 5, &mut blackboard, STEP, &mut tasks),
+        Status::Success
+    );
+    assert_eq!(blackboard.get_text("ran"), Some("scripts/patrol.lua@5"));
+}
+
+/// Verifies trees load from and round-trip through their asset form.
+#[test]
+fn parses_tree_asset() {
+    let tree = BehaviorTree::from_toml(
+        r#"
+        [blackboard]
+        speed = 2.5
+
+        [root]
+        selector = [
+            { sequence = [{ condition = { key = "target", equals = 3 } }, { task = "chase" }] },
+            { repeat = { times = 2, child = { wait = 1.0 } } },
+            { inverter = { script = "idle.lua" } },
+        ]
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(tree.blackboard.get_float("speed"), Some(2.5));
+    assert_eq!(tree.root.node_count(), 8);
+    let Node::Selector(children) = &tree.root else {
+        panic!("root is not a selector");
+    };
+    assert_eq!(
+        children[0],
+        Node::Sequence(vec![
+            Node::Condition {
+                key: "target".to_owned(),
+                equals: Some(BlackboardValue::Int(3)),
+            },
+            task("chase"),
+        ])
+    );
+    assert_eq!(
+        BehaviorTree::from_toml(&tree.to_toml().unwrap()).unwrap(),
+        tree
+    );
+    assert!(logging_tasks().contains("ok"));
+    assert!(tree.validate(&logging_tasks()).is_err());
+    assert!(BehaviorTree::from_toml("[root]\nteleport = 1").is_err());
+}
@@ -0,0 +1,170 @@
+//! Per-entity AI memory.
+//!
+//! A [`Blackboard`] is a component holding named values that behavior tree
+//! tasks read and write, such as the current target or a patrol waypoint.
+//! Trees declare initial values in their asset.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A value stored on a [`Blackboard`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BlackboardValue {
+    /// A flag.
+    Bool(bool),
+    /// An integer, also used for entity ids.
+    Int(i64),
+    /// A number.
+    Float(f64),
+    /// A string.
+    Text(String),
+}
+
+impl BlackboardValue {
+    /// Returns `true` for `true`, non-zero numbers, and non-empty text.
+    #[must_use]
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Self::Bool(value) => *value,
+            Self::Int(value) => *value != 0,
+            Self::Float(value) => *value != 0.0,
+            Self::Text(value) => !value.is_empty(),
+        }
+    }
+}
+
+impl From<bool> for BlackboardValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for BlackboardValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<f64> for BlackboardValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<&str> for BlackboardValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_owned())
+    }
+}
+
+impl From<String> for BlackboardValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+/// Named values shared by the tasks of one entity's behavior tree.
+///
+/// # Example
+///
+/// ```
+/// use ai::Blackboard;
+///
+/// let mut blackboard = Blackboard::new();
+/// blackboard.set("target", 42);
+/// blackboard.set("alert", true);
+///
+/// assert_eq!(blackboard.get_int("target"), Some(42));
+/// assert_eq!(blackboard.get_bool("alert"), Some(true));
+/// assert_eq!(blackboard.get_float("target"), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Blackboard {
+    /// Values by key.
+    values: BTreeMap<String, BlackboardValue>,
+}
+
+impl Blackboard {
+    /// Creates an empty blackboard.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a value, returning the previous one.
+    pub fn set(&mut self, key: &str, value: impl Into<BlackboardValue>) -> Option<BlackboardValue> {
+        self.values.insert(key.to_owned(), value.into())
+    }
+
+    /// Returns a value.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&BlackboardValue> {
+        self.values.get(key)
+    }
+
+    /// Returns a flag, or `None` if missing or not a flag.
+    #[must_use]
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.get(key)? {
+            BlackboardValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns an integer, or `None` if missing or not an integer.
+    #[must_use]
+    pub fn get_int(&self, key: &str) -> Option<i64> {
+        match self.get(key)? {
+            BlackboardValue::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns a number, or `None` if missing or not a number.
+    #[must_use]
+    pub fn get_float(&self, key: &str) -> Option<f64> {
+        match self.get(key)? {
+            BlackboardValue::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns a string, or `None` if missing or not a string.
+    #[must_use]
+    pub fn get_text(&self, key: &str) -> Option<&str> {
+        match self.get(key)? {
+            BlackboardValue::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Removes a value, returning it.
+    pub fn remove(&mut self, key: &str) -> Option<BlackboardValue> {
+        self.values.remove(key)
+    }
+
+    /// Returns `true` if `key` has a value.
+    #[must_use]
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    /// Returns the number of values.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if there are no values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Iterates over keys and values in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &BlackboardValue)> {
+        self.values.iter().map(|(key, value)| (key.as_str(), value))
+    }
+}
@@ -0,0 +1,37 @@
+//! Unit tests for per-entity blackboards.
+
+use crate::blackboard::{Blackboard, BlackboardValue};
+
+/// Verifies typed getters only return values of their type.
+#[test]
+fn typed_getters_check_type() {
+    let mut blackboard = Blackboard::new();
+    blackboard.set("speed", 2.5);
+    blackboard.set("name", "guard");
+
+    assert_eq!(blackboard.get_float("speed"), Some(2.5));
+    assert_eq!(blackboard.get_text("name"), Some("guard"));
+    assert_eq!(blackboard.get_int("speed"), None);
+    assert_eq!(blackboard.get_bool("missing"), None);
+}
+
+/// Verifies setting replaces values and removal empties the blackboard.
+#[test]
+fn set_and_remove() {
+    let mut blackboard = Blackboard::new();
+
+    assert_eq!(blackboard.set("ammo", 3), None);
+    assert_eq!(blackboard.set("ammo", 2), Some(BlackboardValue::Int(3)));
+    assert_eq!(blackboard.len(), 1);
+    assert_eq!(blackboard.remove("ammo"), Some(BlackboardValue::Int(2)));
+    assert!(blackboard.is_empty());
+}
+
+/// Verifies truthiness of every value type.
+#[test]
+fn truthiness() {
+    assert!(BlackboardValue::Bool(true).is_truthy());
+    assert!(!BlackboardValue::Int(0).is_truthy());
+    assert!(BlackboardValue::Float(0.5).is_truthy());
+    assert!(!BlackboardValue::Text(String::new()).is_truthy());
+}
@@ -0,0 +1,44 @@
+//! AI subsystem for the Rustgine game engine.
+//!
+//! This crate provides data-driven behavior trees for game characters.
+//!
+//! # Overview
+//!
+//! The AI crate handles:
+//! - Behavior tree assets with sequence, selector, decorator, and leaf
+//!   nodes ([`BehaviorTree`])
+//! - Leaf tasks implemented by game systems or scripts ([`TaskRegistry`])
+//! - Per-entity memory stored as a component ([`Blackboard`])
+//! - Fixed-rate ticking of behavior agents ([`AiRunner`])
+//!
+//! # Example
+//!
+//! ```ignore
+//! use ai::RustgineAi;
+//! use rustgine_core::RustgineSystem;
+//!
+//! let mut ai = RustgineAi::default();
+//! ai.startup()?;
+//! ```
+
+#![warn(missing_docs)]
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+pub mod agent;
+#[cfg(test)]
+mod agent_test;
+pub mod ai;
+pub mod behavior_tree;
+#[cfg(test)]
+mod behavior_tree_test;
+pub mod blackboard;
+#[cfg(test)]
+mod blackboard_test;
+pub mod task;
+
+pub use agent::{AiRunner, BehaviorAgent};
+pub use ai::RustgineAi;
+pub use behavior_tree::{BehaviorTree, Node, TreeState};
+pub use blackboard::{Blackboard, BlackboardValue};
+pub use task::{Status, TaskContext, TaskRegistry};
@@ -0,0 +1,135 @@
+//! Leaf tasks of behavior trees.
+//!
+//! Game code registers the actions its trees can run by name in a
+//! [`TaskRegistry`]. Tree assets refer to them with `task` nodes, or run a
+//! script through the registry's script runner with `script` nodes.
+
+use crate::blackboard::Blackboard;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+/// Result of ticking a behavior tree node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The node finished and achieved its goal.
+    Success,
+    /// The node finished without achieving its goal.
+    Failure,
+    /// The node needs more ticks.
+    Running,
+}
+
+/// What a task sees while it runs.
+#[derive(Debug)]
+pub struct TaskContext<'a, E> {
+    /// Entity whose tree is ticked.
+    pub entity: E,
+    /// The entity's blackboard.
+    pub blackboard: &'a mut Blackboard,
+    /// Length of the fixed step being ticked.
+    pub delta: Duration,
+}
+
+/// A registered task.
+type TaskFn<E> = Box<dyn FnMut(&mut TaskContext<'_, E>) -> Status + Send>;
+
+/// The script runner, called with the script asset path.
+type ScriptFn<E> = Box<dyn FnMut(&str, &mut TaskContext<'_, E>) -> Status + Send>;
+
+/// Tasks available to behavior trees, by name.
+///
+/// # Example
+///
+/// ```
+/// use ai::{Blackboard, Status, TaskContext, TaskRegistry};
+/// use std::time::Duration;
+///
+/// let mut tasks = TaskRegistry::new();
+/// tasks.register("flee", |ctx: &mut TaskContext<'_, u32>| {
+///     ctx.blackboard.set("fleeing", true);
+///     Status::Success
+/// });
+///
+/// let mut blackboard = Blackboard::new();
+/// let mut ctx = TaskContext { entity: 7, blackboard: &mut blackboard, delta: Duration::ZERO };
+/// assert_eq!(tasks.run("flee", &mut ctx), Status::Success);
+/// assert_eq!(blackboard.get_bool("fleeing"), Some(true));
+/// ```
+pub struct TaskRegistry<E> {
+    /// Tasks by name.
+    tasks: HashMap<String, TaskFn<E>>,
+    /// Runner for `script` nodes.
+    scripts: Option<ScriptFn<E>>,
+}
+
+impl<E> Default for TaskRegistry<E> {
+    fn default() -> Self {
+        Self {
+            tasks: HashMap::new(),
+            scripts: None,
+        }
+    }
+}
+
+impl<E> fmt::Debug for TaskRegistry<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<_> = self.tasks.keys().collect();
+        names.sort();
+        f.debug_struct("TaskRegistry")
+            .field("tasks", &names)
+            .field("scripts", &self.scripts.is_some())
+            .finish()
+    }
+}
+
+impl<E> TaskRegistry<E> {
+    /// Creates a registry without tasks.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a task, replacing any task of the same name.
+    pub fn register(
+        &mut self,
+        name: &str,
+        task: impl FnMut(&mut TaskContext<'_, E>) -> Status + Send + 'static,
+    ) {
+        self.tasks.insert(name.to_owned(), Box::new(task));
+    }
+
+    /// Sets the runner `script` nodes call with their script path.
+    pub fn set_script_runner(
+        &mut self,
+        runner: impl FnMut(&str, &mut TaskContext<'_, E>) -> Status + Send + 'static,
+    ) {
+        self.scripts = Some(Box::new(runner));
+    }
+
+    /// Returns `true` if a task named `name` is registered.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.tasks.contains_key(name)
+    }
+
+    /// Returns `true` if a script runner is set.
+    #[must_use]
+    pub fn runs_scripts(&self) -> bool {
+        self.scripts.is_some()
+    }
+
+    /// Runs the task named `name`; unknown tasks fail.
+    pub fn run(&mut self, name: &str, ctx: &mut TaskContext<'_, E>) -> Status {
+        self.tasks
+            .get_mut(name)
+            .map_or(Status::Failure, |task| task(ctx))
+    }
+
+    /// Runs the script at `path`; scripts fail without a runner.
+    pub fn run_script(&mut self, path: &str, ctx: &mut TaskContext<'_, E>) -> Status {
+        self.scripts
+            .as_mut()
+            .map_or(Status::Failure, |runner| runner(path, ctx))
+    }
+}
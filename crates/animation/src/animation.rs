@@ -0,0 +1,69 @@
+//! Animation subsystem implementation.
+//!
+//! Provides the [`RustgineAnimation`] system, which owns the running tweens.
+
+use crate::tween::Tweener;
+use rustgine_core::{RustgineError, RustgineSystem};
+use tracing::info;
+
+/// Animation subsystem for the Rustgine engine.
+///
+/// Manages:
+/// - The properties available to animation
+/// - Running tweens and their completion events
+/// - Cutscene timelines played by `TimelinePlayer` components
+///
+/// # Example
+///
+/// ```ignore
+/// use animation::RustgineAnimation;
+/// use rustgine_core::RustgineSystem;
+///
+/// let mut animation = RustgineAnimation::default();
+/// animation.startup()?;
+/// ```
+#[derive(Debug, Default)]
+pub struct RustgineAnimation {
+    /// Running tweens.
+    tweener: Tweener,
+}
+
+impl RustgineAnimation {
+    /// Returns the tweens.
+    #[must_use]
+    #[inline]
+    pub fn tweener(&self) -> &Tweener {
+        &self.tweener
+    }
+
+    /// Returns the tweens for changes.
+    #[inline]
+    pub fn tweener_mut(&mut self) -> &mut Tweener {
+        &mut self.tweener
+    }
+}
+
+impl RustgineSystem for RustgineAnimation {
+    /// Initializes the animation subsystem.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if initialization fails.
+    fn startup(&mut self) -> Result<(), RustgineError> {
+        info!(
+            properties = self.tweener.properties().len(),
+            "animation started"
+        );
+        Ok(())
+    }
+
+    /// Shuts down the animation subsystem.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if cleanup fails.
+    #[inline]
+    fn shutdown(&mut self) -> Result<(), RustgineError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+//! Easing curves.
+//!
+//! An easing curve maps linear progress `t` in `0..=1` to eased progress.
+//! Every curve starts at 0 and ends at 1; curves such as
+//! [`Ease::OutBack`] and [`Ease::OutElastic`] overshoot in between. The
+//! formulas follow the widely used set at <https://easings.net>.
+
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// Overshoot of the back curves.
+const BACK: f32 = 1.701_58;
+
+/// An easing curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Ease {
+    /// Constant speed.
+    #[default]
+    Linear,
+    /// Accelerates from rest.
+    InQuad,
+    /// Decelerates to rest.
+    OutQuad,
+    /// Accelerates, then decelerates.
+    InOutQuad,
+    /// Accelerates from rest, more sharply than quadratic.
+    InCubic,
+    /// Decelerates to rest, more sharply than quadratic.
+    OutCubic,
+    /// Accelerates, then decelerates, more sharply than quadratic.
+    InOutCubic,
+    /// Accelerates gently from rest.
+    InSine,
+    /// Decelerates gently to rest.
+    OutSine,
+    /// Accelerates, then decelerates, gently.
+    InOutSine,
+    /// Accelerates from nearly still.
+    InExpo,
+    /// Decelerates to nearly still.
+    OutExpo,
+    /// Pulls back before moving.
+    InBack,
+    /// Overshoots the target, then settles.
+    OutBack,
+    /// Pulls back, then overshoots.
+    InOutBack,
+    /// Springs past the target and oscillates to rest.
+    OutElastic,
+    /// Bounces against the target like a dropped ball.
+    OutBounce,
+}
+
+impl Ease {
+    /// Returns eased progress for linear progress `t`, clamped to `0..=1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use animation::Ease;
+    ///
+    /// assert_eq!(Ease::Linear.apply(0.25), 0.25);
+    /// assert!(Ease::OutBack.apply(0.7) > 1.0);
+    /// assert_eq!(Ease::OutBack.apply(1.0), 1.0);
+    /// ```
+    #[must_use]
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        if t <= 0.0 || t >= 1.0 {
+            return t;
+        }
+        match self {
+            Self::Linear => t,
+            Self::InQuad => t * t,
+            Self::OutQuad => 1.0 - (1.0 - t) * (1.0 - t),
+            Self::InOutQuad => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Self::InCubic => t * t * t,
+            Self::OutCubic => 1.0 - (1.0 - t).powi(3),
+            Self::InOutCubic => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Self::InSine => 1.0 - (t * PI / 2.0).cos(),
+            Self::OutSine => (t * PI / 2.0).sin(),
+            Self::InOutSine => -((PI * t).cos() - 1.0) / 2.0,
+            Self::InExpo => 2.0_f32.powf(10.0 * t - 10.0),
+            Self::OutExpo => 1.0 - 2.0_f32.powf(-10.0 * t),
+            Self::InBack => (BACK + 1.0) * t * t * t - BACK * t * t,
+            Self::OutBack => {
+                let u = t - 1.0;
+                1.0 + (BACK + 1.0) * u * u * u + BACK * u * u
+            }
+            Self::InOutBack => {
+                let c = BACK * 1.525;
+                if t < 0.5 {
+                    (2.0 * t).powi(2) * ((c + 1.0) * 2.0 * t - c) / 2.0
+                } else {
+                    (2.0 * t - 2.0).powi(2) * ((c + 1.0) * (t * 2.0 - 2.0) + c) / 2.0 + 1.0
+                }
+            }
+            Self::OutElastic => {
+                2.0_f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+            }
+            Self::OutBounce => out_bounce(t),
+        }
+    }
+}
+
+fn out_bounce(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984_375
+    }
+}
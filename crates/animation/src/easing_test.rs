@@ -0,0 +1,52 @@
+//! Unit tests for easing curves.
+
+use crate::easing::Ease;
+
+const ALL: [Ease; 17] = [
+    Ease::Linear,
+    Ease::InQuad,
+    Ease::OutQuad,
+    Ease::InOutQuad,
+    Ease::InCubic,
+    Ease::OutCubic,
+    Ease::InOutCubic,
+    Ease::InSine,
+    Ease::OutSine,
+    Ease::InOutSine,
+    Ease::InExpo,
+    Ease::OutExpo,
+    Ease::InBack,
+    Ease::OutBack,
+    Ease::InOutBack,
+    Ease::OutElastic,
+    Ease::OutBounce,
+];
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-3
+}
+
+/// Verifies that every curve starts at 0, ends at 1, and clamps input.
+#[test]
+fn curves_hit_endpoints() {
+    for ease in ALL {
+        assert!(close(ease.apply(0.0), 0.0), "{ease:?} at 0");
+        assert!(close(ease.apply(1.0), 1.0), "{ease:?} at 1");
+        assert!(close(ease.apply(-1.0), 0.0), "{ease:?} below 0");
+        assert!(close(ease.apply(2.0), 1.0), "{ease:?} above 1");
+    }
+}
+
+/// Verifies the shape of representative curves.
+#[test]
+fn curves_have_expected_shape() {
+    assert!(close(Ease::Linear.apply(0.3), 0.3));
+    assert!(close(Ease::InQuad.apply(0.5), 0.25));
+    assert!(close(Ease::OutQuad.apply(0.5), 0.75));
+    assert!(close(Ease::InOutCubic.apply(0.5), 0.5));
+    assert!(close(Ease::InOutSine.apply(0.5), 0.5));
+    assert!(Ease::InBack.apply(0.2) < 0.0);
+    assert!(Ease::OutBack.apply(0.8) > 1.0);
+    assert!(Ease::OutElastic.apply(0.2) > 1.0);
+    assert!((0.0..=1.0).contains(&Ease::OutBounce.apply(0.5)));
+}
@@ -0,0 +1,44 @@
+//! Animation subsystem for the Rustgine game engine.
+//!
+//! This crate animates component data over time.
+//!
+//! # Overview
+//!
+//! The animation crate handles:
+//! - Easing curves for smooth, springy, or bouncy motion
+//! - Named properties that expose component fields to animation
+//! - Tweens of properties, in sequences that loop or ping-pong
+//! - Timelines sequencing animation, camera cuts, audio, events, and
+//!   subtitles into cutscenes
+//!
+//! # Example
+//!
+//! ```ignore
+//! use animation::RustgineAnimation;
+//! use rustgine_core::RustgineSystem;
+//!
+//! let mut animation = RustgineAnimation::default();
+//! animation.startup()?;
+//! ```
+
+#![warn(missing_docs)]
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+pub mod animation;
+pub mod easing;
+#[cfg(test)]
+mod easing_test;
+pub mod property;
+pub mod timeline;
+#[cfg(test)]
+mod timeline_test;
+pub mod tween;
+#[cfg(test)]
+mod tween_test;
+
+pub use animation::RustgineAnimation;
+pub use easing::Ease;
+pub use property::PropertyRegistry;
+pub use timeline::{update_timelines, Timeline, TimelineFrame, TimelinePlayer, Track};
+pub use tween::{Repeat, TweenBuilder, TweenEvent, TweenId, Tweener};
@@ -0,0 +1,122 @@
+//! Named component properties that animation can read and write.
+//!
+//! The engine has no runtime reflection, so a component field becomes
+//! animatable by registering it once under a dotted name with a getter and
+//! setter. Tweens and timelines then refer to it by name, which keeps them
+//! serializable:
+//!
+//! ```
+//! use animation::PropertyRegistry;
+//!
+//! struct Transform {
+//!     translation: [f32; 3],
+//! }
+//!
+//! let mut properties = PropertyRegistry::default();
+//! properties.register(
+//!     "Transform.translation.y",
+//!     |t: &Transform| t.translation[1],
+//!     |t: &mut Transform, y| t.translation[1] = y,
+//! );
+//! assert!(properties.contains("Transform.translation.y"));
+//! ```
+
+use ecs::{Entity, World};
+use std::collections::BTreeMap;
+
+/// Reads and writes one property on an entity.
+trait Property: Send + Sync {
+    /// Returns the property's value, if the entity has the component.
+    fn get(&self, world: &World, entity: Entity) -> Option<f32>;
+    /// Sets the property's value, returning `false` if the entity does not
+    /// have the component.
+    fn set(&self, world: &mut World, entity: Entity, value: f32) -> bool;
+}
+
+/// A property backed by a getter and setter on component `C`.
+struct Field<C> {
+    /// Reads the field.
+    get: fn(&C) -> f32,
+    /// Writes the field.
+    set: fn(&mut C, f32),
+}
+
+impl<C: Send + Sync + 'static> Property for Field<C> {
+    fn get(&self, world: &World, entity: Entity) -> Option<f32> {
+        world.get::<C>(entity).map(self.get)
+    }
+
+    fn set(&self, world: &mut World, entity: Entity, value: f32) -> bool {
+        world
+            .get_mut::<C>(entity)
+            .map(|component| (self.set)(component, value))
+            .is_some()
+    }
+}
+
+/// Animatable properties by name.
+#[derive(Default)]
+pub struct PropertyRegistry {
+    /// Properties by dotted name.
+    properties: BTreeMap<String, Box<dyn Property>>,
+}
+
+impl std::fmt::Debug for PropertyRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.properties.keys()).finish()
+    }
+}
+
+impl PropertyRegistry {
+    /// Registers a field of component `C` under `name`, replacing any
+    /// property registered under it before.
+    ///
+    /// By convention the name is the component type followed by the field
+    /// path, such as `Transform.translation.y`.
+    pub fn register<C: Send + Sync + 'static>(
+        &mut self,
+        name: &str,
+        get: fn(&C) -> f32,
+        set: fn(&mut C, f32),
+    ) {
+        self.properties
+            .insert(name.to_owned(), Box::new(Field { get, set }));
+    }
+
+    /// Returns `true` if a property is registered under `name`.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.properties.contains_key(name)
+    }
+
+    /// Returns the registered property names in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.properties.keys().map(String::as_str)
+    }
+
+    /// Returns the number of registered properties.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.properties.len()
+    }
+
+    /// Returns `true` if no properties are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+
+    /// Returns a property's value on an entity.
+    #[must_use]
+    pub fn get(&self, world: &World, entity: Entity, name: &str) -> Option<f32> {
+        self.properties.get(name)?.get(world, entity)
+    }
+
+    /// Sets a property's value on an entity, returning `false` if the
+    /// property is unknown or the entity lacks its component.
+    pub fn set(&self, world: &mut World, entity: Entity, name: &str, value: f32) -> bool {
+        self.properties
+            .get(name)
+            .is_some_and(|property| property.set(world, entity, value))
+    }
+}
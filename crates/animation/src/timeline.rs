@@ -0,0 +1,505 @@
+//! Timelines: cutscenes and scripted sequences.
+//!
+//! A [`Timeline`] is an asset of tracks laid out over time: property
+//! animation, camera cuts, audio cues, gameplay events, and subtitles. It
+//! names the entities it animates by role ("door", "hero") rather than by
+//! id, so one timeline plays against any set of entities bound to a
+//! [`TimelinePlayer`]. Timelines are stored as RON, which reads well in
+//! diffs and is easy to tweak by hand:
+//!
+//! ```ron
+//! (
+//!     duration: 4.0,
+//!     tracks: [
+//!         Property(
+//!             target: Some("door"),
+//!             property: "Transform.rotation.y",
+//!             keys: [(time: 0.0, value: 0.0), (time: 1.5, value: 1.57, ease: OutBack)],
+//!         ),
+//!         Camera(cuts: [(time: 0.0, camera: "wide"), (time: 2.0, camera: "closeup")]),
+//!         Audio(cues: [(time: 0.2, sound: "sfx/door_creak.ogg")]),
+//!         Event(keys: [(time: 3.5, name: "door_opened")]),
+//!         Subtitle(lines: [(start: 2.0, end: 3.5, text: "cutscene.door.line1")]),
+//!     ],
+//! )
+//! ```
+//!
+//! [`update_timelines`] advances every player in a world, writes animated
+//! properties, and returns the camera cuts, cues, events, and subtitles for
+//! the systems that present them.
+
+use crate::easing::Ease;
+use crate::property::PropertyRegistry;
+use ecs::{Entity, World};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A property value at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Keyframe {
+    /// Time in seconds.
+    pub time: f32,
+    /// Property value.
+    pub value: f32,
+    /// Curve from the previous keyframe to this one.
+    #[serde(default)]
+    pub ease: Ease,
+}
+
+/// A switch to another camera.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraCut {
+    /// Time in seconds.
+    pub time: f32,
+    /// Role of the camera entity.
+    pub camera: String,
+}
+
+/// A sound started at a point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioCue {
+    /// Time in seconds.
+    pub time: f32,
+    /// Asset path of the sound.
+    pub sound: String,
+    /// Volume, where 1 is unchanged.
+    #[serde(default = "unit_volume")]
+    pub volume: f32,
+}
+
+fn unit_volume() -> f32 {
+    1.0
+}
+
+/// A named gameplay event fired at a point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventKey {
+    /// Time in seconds.
+    pub time: f32,
+    /// Event name.
+    pub name: String,
+}
+
+/// A subtitle line shown for a span of time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubtitleLine {
+    /// Time the line appears, in seconds.
+    pub start: f32,
+    /// Time the line disappears, in seconds.
+    pub end: f32,
+    /// Text, or a localization key the UI looks up.
+    pub text: String,
+}
+
+/// One track of a timeline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Track {
+    /// Animates a named property of an entity.
+    Property {
+        /// Role of the animated entity; the player's own entity if `None`.
+        #[serde(default)]
+        target: Option<String>,
+        /// Property name in the [`PropertyRegistry`].
+        property: String,
+        /// Keyframes in time order.
+        keys: Vec<Keyframe>,
+    },
+    /// Switches the active camera.
+    Camera {
+        /// Cuts in time order.
+        cuts: Vec<CameraCut>,
+    },
+    /// Starts sounds.
+    Audio {
+        /// Cues in time order.
+        cues: Vec<AudioCue>,
+    },
+    /// Fires gameplay events.
+    Event {
+        /// Events in time order.
+        keys: Vec<EventKey>,
+    },
+    /// Shows subtitle lines.
+    Subtitle {
+        /// Lines in time order.
+        lines: Vec<SubtitleLine>,
+    },
+}
+
+/// A sequence of tracks over time.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Timeline {
+    /// Length in seconds.
+    pub duration: f32,
+    /// Tracks, all starting at time 0.
+    #[serde(default)]
+    pub tracks: Vec<Track>,
+}
+
+impl Timeline {
+    /// Parses a timeline from RON, sorting keys into time order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a valid timeline, or the
+    /// duration is negative or not finite.
+    pub fn from_ron(text: &str) -> anyhow::Result<Self> {
+        let mut timeline: Self =
+            ron::from_str(text).map_err(|e| anyhow::anyhow!("invalid timeline: {e}"))?;
+        if !timeline.duration.is_finite() || timeline.duration < 0.0 {
+            anyhow::bail!("timeline duration {} is invalid", timeline.duration);
+        }
+        timeline.sort();
+        Ok(timeline)
+    }
+
+    /// Writes the timeline as pretty-printed RON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_ron(&self) -> anyhow::Result<String> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| anyhow::anyhow!("failed to write timeline: {e}"))
+    }
+
+    /// Sorts every track's keys into time order.
+    pub fn sort(&mut self) {
+        for track in &mut self.tracks {
+            match track {
+                Track::Property { keys, .. } => keys.sort_by(|a, b| a.time.total_cmp(&b.time)),
+                Track::Camera { cuts } => cuts.sort_by(|a, b| a.time.total_cmp(&b.time)),
+                Track::Audio { cues } => cues.sort_by(|a, b| a.time.total_cmp(&b.time)),
+                Track::Event { keys } => keys.sort_by(|a, b| a.time.total_cmp(&b.time)),
+                Track::Subtitle { lines } => lines.sort_by(|a, b| a.start.total_cmp(&b.start)),
+            }
+        }
+    }
+}
+
+/// Returns a property track's value at `time`.
+fn sample(keys: &[Keyframe], time: f32) -> Option<f32> {
+    let next = keys.iter().position(|key| key.time > time);
+    match next {
+        None => keys.last().map(|key| key.value),
+        Some(0) => Some(keys[0].value),
+        Some(index) => {
+            let (a, b) = (keys[index - 1], keys[index]);
+            let t = (time - a.time) / (b.time - a.time);
+            Some(a.value + (b.value - a.value) * b.ease.apply(t))
+        }
+    }
+}
+
+/// Playback state of a [`TimelinePlayer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlaybackState {
+    /// Not started, or stopped.
+    #[default]
+    Stopped,
+    /// Advancing.
+    Playing,
+    /// Holding the current time.
+    Paused,
+    /// Reached the end without looping.
+    Finished,
+}
+
+/// A property value to write.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertySample {
+    /// Entity the property belongs to.
+    pub entity: Entity,
+    /// Property name.
+    pub property: String,
+    /// Value at the current time.
+    pub value: f32,
+}
+
+/// What a timeline produced during one advance.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TimelineFrame {
+    /// Playback time after the advance, in seconds.
+    pub time: f32,
+    /// Property values at that time.
+    pub samples: Vec<PropertySample>,
+    /// Active camera, if a camera track has started.
+    pub camera: Option<Entity>,
+    /// Whether the active camera changed during the advance.
+    pub camera_cut: bool,
+    /// Sounds to start.
+    pub audio: Vec<AudioCue>,
+    /// Gameplay events fired.
+    pub events: Vec<String>,
+    /// Subtitle lines shown at that time.
+    pub subtitles: Vec<String>,
+    /// Whether the timeline reached its end without looping.
+    pub finished: bool,
+}
+
+/// Component playing a timeline on an entity.
+///
+/// # Example
+///
+/// ```
+/// use animation::timeline::{PlaybackState, Timeline, TimelinePlayer};
+/// use ecs::Entity;
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// let timeline = Timeline::from_ron(r#"(
+///     duration: 2.0,
+///     tracks: [Event(keys: [(time: 1.0, name: "explode")])],
+/// )"#)?;
+/// let mut player = TimelinePlayer::new(Arc::new(timeline));
+/// player.play();
+///
+/// let frame = player.advance(Entity(0), Duration::from_millis(1500));
+/// assert_eq!(frame.events, ["explode"]);
+///
+/// player.seek(0.5);
+/// player.pause();
+/// assert_eq!(player.state(), PlaybackState::Paused);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimelinePlayer {
+    /// Timeline played.
+    timeline: Arc<Timeline>,
+    /// Entities by the roles the timeline names.
+    bindings: BTreeMap<String, Entity>,
+    /// Playback time in seconds.
+    time: f32,
+    /// Playback state.
+    state: PlaybackState,
+    /// Playback speed, where 1 is normal.
+    speed: f32,
+    /// Whether playback wraps to the start at the end.
+    looping: bool,
+    /// Whether properties must be written even though time did not move.
+    dirty: bool,
+    /// Camera active at the last advance.
+    camera: Option<Entity>,
+}
+
+impl TimelinePlayer {
+    /// Creates a stopped player at the start of `timeline`.
+    #[must_use]
+    pub fn new(timeline: Arc<Timeline>) -> Self {
+        Self {
+            timeline,
+            bindings: BTreeMap::new(),
+            time: 0.0,
+            state: PlaybackState::Stopped,
+            speed: 1.0,
+            looping: false,
+            dirty: true,
+            camera: None,
+        }
+    }
+
+    /// Binds a role the timeline names to an entity.
+    #[must_use]
+    pub fn with_binding(mut self, role: &str, entity: Entity) -> Self {
+        self.bind(role, entity);
+        self
+    }
+
+    /// Makes playback wrap to the start at the end.
+    #[must_use]
+    pub fn looping(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+
+    /// Binds a role the timeline names to an entity.
+    pub fn bind(&mut self, role: &str, entity: Entity) {
+        self.bindings.insert(role.to_owned(), entity);
+        self.dirty = true;
+    }
+
+    /// Returns the timeline played.
+    #[must_use]
+    pub fn timeline(&self) -> &Timeline {
+        &self.timeline
+    }
+
+    /// Returns the playback time in seconds.
+    #[must_use]
+    #[inline]
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Returns the playback state.
+    #[must_use]
+    #[inline]
+    pub fn state(&self) -> PlaybackState {
+        self.state
+    }
+
+    /// Sets the playback speed, where 1 is normal; negative speeds stop.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.0);
+    }
+
+    /// Starts or resumes playback, restarting a finished timeline.
+    pub fn play(&mut self) {
+        if self.state == PlaybackState::Finished {
+            self.seek(0.0);
+        }
+        self.state = PlaybackState::Playing;
+    }
+
+    /// Holds the current time.
+    pub fn pause(&mut self) {
+        if self.state == PlaybackState::Playing {
+            self.state = PlaybackState::Paused;
+        }
+    }
+
+    /// Stops playback and rewinds to the start.
+    pub fn stop(&mut self) {
+        self.state = PlaybackState::Stopped;
+        self.seek(0.0);
+    }
+
+    /// Jumps to `time` seconds without firing the cues and events skipped.
+    pub fn seek(&mut self, time: f32) {
+        self.time = time.clamp(0.0, self.timeline.duration);
+        self.dirty = true;
+        if self.state == PlaybackState::Finished {
+            self.state = PlaybackState::Paused;
+        }
+    }
+
+    /// Advances playback by `delta` and returns what happened.
+    ///
+    /// `entity` is the player's own entity, which property tracks without
+    /// a target animate. Properties are only sampled when time moved or
+    /// after a seek or rebind.
+    pub fn advance(&mut self, entity: Entity, delta: Duration) -> TimelineFrame {
+        let duration = self.timeline.duration;
+        let start = self.time;
+        // Time spans covered, in play order, with whether each includes its
+        // start: only the very start of the timeline, so keys at 0 fire too.
+        let mut ranges = Vec::new();
+        let mut finished = false;
+        if self.state == PlaybackState::Playing {
+            let from_start = start <= 0.0;
+            let mut end = start + delta.as_secs_f32() * self.speed;
+            if end >= duration {
+                if self.looping && duration > 0.0 {
+                    ranges.push((start, duration, from_start));
+                    end = (end - duration) % duration;
+                    ranges.push((0.0, end, true));
+                } else {
+                    end = duration;
+                    ranges.push((start, end, from_start));
+                    finished = true;
+                    self.state = PlaybackState::Finished;
+                }
+            } else if end > start {
+                ranges.push((start, end, from_start));
+            }
+            self.time = end;
+        }
+        let moved = !ranges.is_empty();
+
+        let mut frame = TimelineFrame {
+            time: self.time,
+            finished,
+            ..TimelineFrame::default()
+        };
+        // Keys fire once, in the advance that reaches their time.
+        let in_range = |at: f32, &(from, to, inclusive): &(f32, f32, bool)| {
+            (at > from || (inclusive && at >= from)) && at <= to
+        };
+        for track in &self.timeline.tracks {
+            match track {
+                Track::Property {
+                    target,
+                    property,
+                    keys,
+                } => {
+                    if !(moved || self.dirty) {
+                        continue;
+                    }
+                    let target = match target {
+                        Some(role) => self.bindings.get(role).copied(),
+                        None => Some(entity),
+                    };
+                    if let (Some(target), Some(value)) = (target, sample(keys, self.time)) {
+                        frame.samples.push(PropertySample {
+                            entity: target,
+                            property: property.clone(),
+                            value,
+                        });
+                    }
+                }
+                Track::Camera { cuts } => {
+                    let active = cuts.iter().rev().find(|cut| cut.time <= self.time);
+                    if let Some(cut) = active {
+                        frame.camera = self.bindings.get(&cut.camera).copied();
+                    }
+                }
+                Track::Audio { cues } => {
+                    for range in &ranges {
+                        frame
+                            .audio
+                            .extend(cues.iter().filter(|cue| in_range(cue.time, range)).cloned());
+                    }
+                }
+                Track::Event { keys } => {
+                    for range in &ranges {
+                        frame.events.extend(
+                            keys.iter()
+                                .filter(|key| in_range(key.time, range))
+                                .map(|key| key.name.clone()),
+                        );
+                    }
+                }
+                Track::Subtitle { lines } => {
+                    frame.subtitles.extend(
+                        lines
+                            .iter()
+                            .filter(|line| line.start <= self.time && self.time < line.end)
+                            .map(|line| line.text.clone()),
+                    );
+                }
+            }
+        }
+        frame.camera_cut = frame.camera != self.camera;
+        self.camera = frame.camera;
+        self.dirty = false;
+        frame
+    }
+}
+
+/// Advances every [`TimelinePlayer`] in `world` by `delta`, writes the
+/// animated properties, and returns each player's frame.
+pub fn update_timelines(
+    world: &mut World,
+    properties: &PropertyRegistry,
+    delta: Duration,
+) -> Vec<(Entity, TimelineFrame)> {
+    let mut players: Vec<Entity> = world
+        .query::<TimelinePlayer>()
+        .map(|(entity, _)| entity)
+        .collect();
+    players.sort_unstable();
+    let mut frames = Vec::with_capacity(players.len());
+    for entity in players {
+        let Some(player) = world.get_mut::<TimelinePlayer>(entity) else {
+            continue;
+        };
+        let frame = player.advance(entity, delta);
+        for sample in &frame.samples {
+            properties.set(world, sample.entity, &sample.property, sample.value);
+        }
+        frames.push((entity, frame));
+    }
+    frames
+}
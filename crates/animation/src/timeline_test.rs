@@ -0,0 +1,170 @@
+//! Unit tests for timelines.
+
+use crate::easing::Ease;
+use crate::property::PropertyRegistry;
+use crate::timeline::{update_timelines, PlaybackState, Timeline, TimelinePlayer, Track};
+use ecs::{Entity, World};
+use std::sync::Arc;
+use std::time::Duration;
+
+const CUTSCENE: &str = r#"(
+    duration: 4.0,
+    tracks: [
+        Property(
+            target: Some("door"),
+            property: "Door.angle",
+            keys: [(time: 2.0, value: 90.0), (time: 0.0, value: 0.0)],
+        ),
+        Property(property: "Door.angle", keys: [(time: 0.0, value: 5.0)]),
+        Camera(cuts: [(time: 0.0, camera: "wide"), (time: 2.0, camera: "closeup")]),
+        Audio(cues: [(time: 0.5, sound: "sfx/creak.ogg", volume: 0.5)]),
+        Event(keys: [(time: 1.0, name: "opened"), (time: 4.0, name: "done")]),
+        Subtitle(lines: [(start: 1.0, end: 3.0, text: "door.line1")]),
+    ],
+)"#;
+
+struct Door {
+    angle: f32,
+}
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-4
+}
+
+fn player() -> TimelinePlayer {
+    TimelinePlayer::new(Arc::new(Timeline::from_ron(CUTSCENE).unwrap()))
+        .with_binding("door", Entity(10))
+        .with_binding("wide", Entity(20))
+        .with_binding("closeup", Entity(21))
+}
+
+/// Verifies RON parsing, key sorting, defaults, and round trips.
+#[test]
+fn parses_and_writes_ron() {
+    let timeline = Timeline::from_ron(CUTSCENE).unwrap();
+    assert_eq!(timeline.tracks.len(), 6);
+    let Track::Property { keys, .. } = &timeline.tracks[0] else {
+        panic!("expected a property track");
+    };
+    assert_eq!(keys[0].value.to_bits(), 0.0_f32.to_bits());
+    assert_eq!(keys[1].ease, Ease::Linear);
+
+    let written = timeline.to_ron().unwrap();
+    assert_eq!(Timeline::from_ron(&written).unwrap(), timeline);
+
+    assert!(Timeline::from_ron("(duration: -1.0)").is_err());
+    assert!(Timeline::from_ron("(duration: 1.0, tracks: [Unknown])").is_err());
+}
+
+/// Verifies tracks fire once and in the right frames.
+#[test]
+fn plays_tracks() {
+    let mut player = player();
+    let own = Entity(1);
+    player.play();
+
+    let frame = player.advance(own, ms(600));
+    assert_eq!(frame.audio.len(), 1);
+    assert!(frame.events.is_empty());
+    assert_eq!(frame.camera, Some(Entity(20)));
+    assert!(frame.camera_cut);
+    assert_eq!(frame.samples.len(), 2);
+    assert_eq!(frame.samples[0].entity, Entity(10));
+    assert!(close(frame.samples[0].value, 27.0));
+    assert_eq!(frame.samples[1].entity, own);
+
+    let frame = player.advance(own, ms(1000));
+    assert!(frame.audio.is_empty());
+    assert_eq!(frame.events, ["opened"]);
+    assert_eq!(frame.subtitles, ["door.line1"]);
+    assert!(!frame.camera_cut);
+
+    let frame = player.advance(own, ms(1000));
+    assert_eq!(frame.camera, Some(Entity(21)));
+    assert!(frame.camera_cut);
+
+    let frame = player.advance(own, ms(5000));
+    assert_eq!(frame.events, ["done"]);
+    assert!(frame.finished);
+    assert!(frame.subtitles.is_empty());
+    assert_eq!(player.state(), PlaybackState::Finished);
+    assert!(close(player.time(), 4.0));
+}
+
+/// Verifies pause, seek, and stop.
+#[test]
+fn pauses_and_seeks() {
+    let mut player = player();
+    let own = Entity(1);
+    player.play();
+    player.advance(own, ms(500));
+
+    player.pause();
+    let frame = player.advance(own, ms(1000));
+    assert!(close(frame.time, 0.5));
+    assert!(frame.samples.is_empty());
+
+    // Seeking skips the event at 1.0 but re-samples properties.
+    player.seek(1.5);
+    let frame = player.advance(own, ms(1000));
+    assert!(frame.events.is_empty());
+    assert!(close(frame.samples[0].value, 67.5));
+
+    player.play();
+    player.set_speed(2.0);
+    let frame = player.advance(own, ms(250));
+    assert!(close(frame.time, 2.0));
+
+    player.stop();
+    assert_eq!(player.state(), PlaybackState::Stopped);
+    assert!(close(player.time(), 0.0));
+}
+
+/// Verifies that looping wraps and fires keys on both sides of the wrap.
+#[test]
+fn loops() {
+    let timeline = Timeline::from_ron(
+        r#"(duration: 1.0, tracks: [Event(keys: [(time: 0.0, name: "start"), (time: 0.9, name: "end")])])"#,
+    )
+    .unwrap();
+    let mut player = TimelinePlayer::new(Arc::new(timeline)).looping();
+    player.play();
+
+    let frame = player.advance(Entity(0), ms(500));
+    assert_eq!(frame.events, ["start"]);
+    let frame = player.advance(Entity(0), ms(700));
+    assert_eq!(frame.events, ["end", "start"]);
+    assert!(close(frame.time, 0.2));
+    assert_eq!(player.state(), PlaybackState::Playing);
+}
+
+/// Verifies that players in a world write their properties.
+#[test]
+fn updates_world() {
+    let mut properties = PropertyRegistry::default();
+    properties.register(
+        "Door.angle",
+        |d: &Door| d.angle,
+        |d: &mut Door, a| d.angle = a,
+    );
+
+    let mut world = World::default();
+    let director = world.spawn();
+    let door = world.spawn();
+    world.insert(director, Door { angle: 0.0 });
+    world.insert(door, Door { angle: 0.0 });
+    let mut player = TimelinePlayer::new(Arc::new(Timeline::from_ron(CUTSCENE).unwrap()))
+        .with_binding("door", door);
+    player.play();
+    world.insert(director, player);
+
+    let frames = update_timelines(&mut world, &properties, ms(1000));
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].1.events, ["opened"]);
+    assert!(close(world.get::<Door>(door).unwrap().angle, 45.0));
+    assert!(close(world.get::<Door>(director).unwrap().angle, 5.0));
+}
@@ -0,0 +1,511 @@
+//! Tweens: property animations along easing curves.
+//!
+//! A tween moves one property of an entity from its current value (or a
+//! given start) to a target over a duration, shaped by an [`Ease`]. Tweens
+//! chain into sequences, which can wait between steps, repeat, and play
+//! back and forth:
+//!
+//! ```
+//! use animation::{Ease, Tweener};
+//! use ecs::World;
+//! use std::time::Duration;
+//!
+//! struct Transform {
+//!     translation: [f32; 3],
+//! }
+//!
+//! let mut tweener = Tweener::default();
+//! tweener.properties_mut().register(
+//!     "Transform.translation.y",
+//!     |t: &Transform| t.translation[1],
+//!     |t: &mut Transform, y| t.translation[1] = y,
+//! );
+//!
+//! let mut world = World::default();
+//! let crate_entity = world.spawn();
+//! world.insert(crate_entity, Transform { translation: [0.0; 3] });
+//!
+//! tweener
+//!     .tween(crate_entity)
+//!     .field("Transform.translation.y")
+//!     .to(5.0)
+//!     .over(0.3)
+//!     .ease(Ease::OutBack)
+//!     .start()?;
+//!
+//! tweener.update(&mut world, Duration::from_millis(300));
+//! assert_eq!(world.get::<Transform>(crate_entity).unwrap().translation[1], 5.0);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! Completion, loop, and cancellation are reported as [`TweenEvent`]s.
+
+use crate::easing::Ease;
+use crate::property::PropertyRegistry;
+use ecs::{Entity, Events, World};
+use std::time::Duration;
+
+/// Identifies a running tween sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TweenId(pub u64);
+
+/// How many times a sequence plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Repeat {
+    /// Plays once.
+    #[default]
+    Once,
+    /// Plays this many times in total.
+    Times(u32),
+    /// Plays until cancelled.
+    Forever,
+}
+
+/// Something that happened to a tween sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TweenEvent {
+    /// A repeating sequence finished a play and started the next.
+    Looped {
+        /// The sequence.
+        id: TweenId,
+        /// The animated entity.
+        entity: Entity,
+    },
+    /// The sequence finished its last play.
+    Completed {
+        /// The sequence.
+        id: TweenId,
+        /// The animated entity.
+        entity: Entity,
+    },
+    /// The sequence was cancelled, or stopped because its entity or
+    /// component went away.
+    Cancelled {
+        /// The sequence.
+        id: TweenId,
+        /// The animated entity.
+        entity: Entity,
+    },
+}
+
+/// One step of a sequence.
+#[derive(Debug, Clone)]
+struct Step {
+    /// Animated property, or `None` for a wait.
+    property: Option<String>,
+    /// Start value; the property's value when the step first starts if
+    /// `None`.
+    from: Option<f32>,
+    /// Target value.
+    to: Option<f32>,
+    /// Length of the step.
+    duration: Duration,
+    /// Easing curve.
+    ease: Ease,
+}
+
+impl Step {
+    fn tween(property: &str) -> Self {
+        Self {
+            property: Some(property.to_owned()),
+            from: None,
+            to: None,
+            duration: Duration::ZERO,
+            ease: Ease::Linear,
+        }
+    }
+
+    fn wait(duration: Duration) -> Self {
+        Self {
+            property: None,
+            from: None,
+            to: None,
+            duration,
+            ease: Ease::Linear,
+        }
+    }
+}
+
+/// A running sequence.
+#[derive(Debug)]
+struct Sequence {
+    /// The sequence's id.
+    id: TweenId,
+    /// Animated entity.
+    entity: Entity,
+    /// Steps in play order.
+    steps: Vec<Step>,
+    /// Position in the current pass, counted in play direction.
+    index: usize,
+    /// Time spent in the current step.
+    elapsed: Duration,
+    /// How many times to play.
+    repeat: Repeat,
+    /// Whether each play runs forwards and then backwards.
+    ping_pong: bool,
+    /// Whether the current pass runs backwards.
+    reversed: bool,
+    /// Completed plays.
+    plays: u32,
+}
+
+/// What advancing a sequence led to.
+enum Outcome {
+    /// Still running.
+    Running,
+    /// Finished its last play.
+    Completed,
+    /// Lost its entity or component.
+    Cancelled,
+}
+
+impl Sequence {
+    fn total_duration(&self) -> Duration {
+        self.steps.iter().map(|step| step.duration).sum()
+    }
+
+    /// Advances by `delta`, writing property values and sending loop events.
+    fn advance(
+        &mut self,
+        world: &mut World,
+        properties: &PropertyRegistry,
+        mut delta: Duration,
+        events: &mut Events<TweenEvent>,
+    ) -> Outcome {
+        let zero_length = self.total_duration().is_zero();
+        loop {
+            let position = if self.reversed {
+                self.steps.len() - 1 - self.index
+            } else {
+                self.index
+            };
+            let step = &mut self.steps[position];
+            if let Some(property) = &step.property {
+                if step.from.is_none() {
+                    step.from = properties.get(world, self.entity, property);
+                    if step.from.is_none() {
+                        return Outcome::Cancelled;
+                    }
+                }
+            }
+            let remaining = step.duration.saturating_sub(self.elapsed);
+            let finished = delta >= remaining;
+            if finished {
+                delta -= remaining;
+                self.elapsed = step.duration;
+            } else {
+                self.elapsed += delta;
+            }
+            if !sample(
+                step,
+                self.elapsed,
+                self.reversed,
+                world,
+                properties,
+                self.entity,
+            ) {
+                return Outcome::Cancelled;
+            }
+            if !finished {
+                return Outcome::Running;
+            }
+            self.index += 1;
+            self.elapsed = Duration::ZERO;
+            if self.index < self.steps.len() {
+                continue;
+            }
+            self.index = 0;
+            if self.ping_pong && !self.reversed {
+                self.reversed = true;
+                continue;
+            }
+            self.reversed = false;
+            self.plays += 1;
+            let done = match self.repeat {
+                Repeat::Once => true,
+                Repeat::Times(times) => self.plays >= times,
+                Repeat::Forever => false,
+            };
+            if done {
+                return Outcome::Completed;
+            }
+            events.send(TweenEvent::Looped {
+                id: self.id,
+                entity: self.entity,
+            });
+            // Without this, a sequence of instant steps would loop forever
+            // within one update.
+            if zero_length {
+                return Outcome::Running;
+            }
+        }
+    }
+}
+
+/// Writes a step's value at `elapsed`, returning `false` if the entity lost
+/// the property's component.
+fn sample(
+    step: &Step,
+    elapsed: Duration,
+    reversed: bool,
+    world: &mut World,
+    properties: &PropertyRegistry,
+    entity: Entity,
+) -> bool {
+    let (Some(property), Some(from), Some(to)) = (&step.property, step.from, step.to) else {
+        return true;
+    };
+    let t = if step.duration.is_zero() {
+        1.0
+    } else {
+        elapsed.as_secs_f32() / step.duration.as_secs_f32()
+    };
+    let t = if reversed { 1.0 - t } else { t };
+    let value = if t >= 1.0 {
+        to
+    } else if t <= 0.0 {
+        from
+    } else {
+        from + (to - from) * step.ease.apply(t)
+    };
+    properties.set(world, entity, property, value)
+}
+
+/// Runs tween sequences.
+#[derive(Debug, Default)]
+pub struct Tweener {
+    /// Properties tweens may animate.
+    properties: PropertyRegistry,
+    /// Running sequences, in start order.
+    sequences: Vec<Sequence>,
+    /// Id of the next sequence.
+    next_id: u64,
+    /// Loop, completion, and cancellation events.
+    events: Events<TweenEvent>,
+}
+
+impl Tweener {
+    /// Returns the properties tweens may animate.
+    #[must_use]
+    #[inline]
+    pub fn properties(&self) -> &PropertyRegistry {
+        &self.properties
+    }
+
+    /// Returns the properties tweens may animate, for registration.
+    #[inline]
+    pub fn properties_mut(&mut self) -> &mut PropertyRegistry {
+        &mut self.properties
+    }
+
+    /// Starts describing a tween sequence on `entity`.
+    pub fn tween(&mut self, entity: Entity) -> TweenBuilder<'_> {
+        TweenBuilder {
+            tweener: self,
+            entity,
+            steps: Vec::new(),
+            repeat: Repeat::Once,
+            ping_pong: false,
+        }
+    }
+
+    /// Stops a sequence where it is, returning `false` if it was not
+    /// running.
+    pub fn cancel(&mut self, id: TweenId) -> bool {
+        let Some(index) = self.sequences.iter().position(|s| s.id == id) else {
+            return false;
+        };
+        let sequence = self.sequences.remove(index);
+        self.events.send(TweenEvent::Cancelled {
+            id,
+            entity: sequence.entity,
+        });
+        true
+    }
+
+    /// Stops every sequence on an entity, returning how many were running.
+    pub fn cancel_entity(&mut self, entity: Entity) -> usize {
+        let ids: Vec<TweenId> = self
+            .sequences
+            .iter()
+            .filter(|s| s.entity == entity)
+            .map(|s| s.id)
+            .collect();
+        for id in &ids {
+            self.cancel(*id);
+        }
+        ids.len()
+    }
+
+    /// Returns `true` if a sequence is running.
+    #[must_use]
+    pub fn is_active(&self, id: TweenId) -> bool {
+        self.sequences.iter().any(|s| s.id == id)
+    }
+
+    /// Returns the number of running sequences.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    /// Returns `true` if no sequences are running.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sequences.is_empty()
+    }
+
+    /// Returns loop, completion, and cancellation events from this frame
+    /// and last.
+    #[must_use]
+    #[inline]
+    pub fn events(&self) -> &Events<TweenEvent> {
+        &self.events
+    }
+
+    /// Advances every sequence by `delta`, writing animated properties.
+    ///
+    /// Call once per frame; it also advances the event queue.
+    pub fn update(&mut self, world: &mut World, delta: Duration) {
+        self.events.update();
+        let Self {
+            properties,
+            sequences,
+            events,
+            ..
+        } = self;
+        sequences.retain_mut(|sequence| {
+            let event = match sequence.advance(world, properties, delta, events) {
+                Outcome::Running => return true,
+                Outcome::Completed => TweenEvent::Completed {
+                    id: sequence.id,
+                    entity: sequence.entity,
+                },
+                Outcome::Cancelled => TweenEvent::Cancelled {
+                    id: sequence.id,
+                    entity: sequence.entity,
+                },
+            };
+            events.send(event);
+            false
+        });
+    }
+}
+
+/// Describes a tween sequence; finish with [`start`](Self::start).
+///
+/// [`field`](Self::field) and [`wait`](Self::wait) add steps, which play
+/// one after another. [`from`](Self::from), [`to`](Self::to),
+/// [`over`](Self::over), and [`ease`](Self::ease) configure the latest
+/// step.
+#[derive(Debug)]
+#[must_use = "the tween does nothing until started"]
+pub struct TweenBuilder<'a> {
+    /// Tweener the sequence starts on.
+    tweener: &'a mut Tweener,
+    /// Animated entity.
+    entity: Entity,
+    /// Steps so far.
+    steps: Vec<Step>,
+    /// How many times to play.
+    repeat: Repeat,
+    /// Whether each play runs forwards and then backwards.
+    ping_pong: bool,
+}
+
+impl TweenBuilder<'_> {
+    /// Adds a step animating the named property.
+    pub fn field(mut self, property: &str) -> Self {
+        self.steps.push(Step::tween(property));
+        self
+    }
+
+    /// Sets the latest step's start value, instead of the property's value
+    /// when the step starts.
+    pub fn from(mut self, value: f32) -> Self {
+        if let Some(step) = self.steps.last_mut() {
+            step.from = Some(value);
+        }
+        self
+    }
+
+    /// Sets the latest step's target value.
+    pub fn to(mut self, value: f32) -> Self {
+        if let Some(step) = self.steps.last_mut() {
+            step.to = Some(value);
+        }
+        self
+    }
+
+    /// Sets the latest step's length in seconds.
+    pub fn over(mut self, seconds: f32) -> Self {
+        if let Some(step) = self.steps.last_mut() {
+            step.duration = Duration::from_secs_f32(seconds.max(0.0));
+        }
+        self
+    }
+
+    /// Sets the latest step's easing curve.
+    pub fn ease(mut self, ease: Ease) -> Self {
+        if let Some(step) = self.steps.last_mut() {
+            step.ease = ease;
+        }
+        self
+    }
+
+    /// Adds a pause of `seconds`.
+    pub fn wait(mut self, seconds: f32) -> Self {
+        self.steps
+            .push(Step::wait(Duration::from_secs_f32(seconds.max(0.0))));
+        self
+    }
+
+    /// Sets how many times the sequence plays.
+    pub fn repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Plays each repetition forwards and then backwards.
+    pub fn ping_pong(mut self) -> Self {
+        self.ping_pong = true;
+        self
+    }
+
+    /// Starts the sequence on the next update.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no steps, a step names an unregistered
+    /// property, or a step has no target.
+    pub fn start(self) -> anyhow::Result<TweenId> {
+        if self.steps.is_empty() {
+            anyhow::bail!("tween on {} has no steps", self.entity);
+        }
+        for (index, step) in self.steps.iter().enumerate() {
+            let Some(property) = &step.property else {
+                continue;
+            };
+            if !self.tweener.properties.contains(property) {
+                anyhow::bail!("tween step {index} animates unknown property {property:?}");
+            }
+            if step.to.is_none() {
+                anyhow::bail!("tween step {index} on {property:?} has no target");
+            }
+        }
+        let id = TweenId(self.tweener.next_id);
+        self.tweener.next_id += 1;
+        self.tweener.sequences.push(Sequence {
+            id,
+            entity: self.entity,
+            steps: self.steps,
+            index: 0,
+            elapsed: Duration::ZERO,
+            repeat: self.repeat,
+            ping_pong: self.ping_pong,
+            reversed: false,
+            plays: 0,
+        });
+        Ok(id)
+    }
+}
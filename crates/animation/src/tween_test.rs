@@ -0,0 +1,242 @@
+//! Unit tests for tweens.
+
+use crate::easing::Ease;
+use crate::tween::{Repeat, TweenEvent, Tweener};
+use ecs::{Entity, World};
+use std::time::Duration;
+
+struct Transform {
+    translation: [f32; 3],
+}
+
+struct Sprite {
+    alpha: f32,
+}
+
+fn setup() -> (Tweener, World, Entity) {
+    let mut tweener = Tweener::default();
+    let properties = tweener.properties_mut();
+    properties.register(
+        "Transform.translation.x",
+        |t: &Transform| t.translation[0],
+        |t: &mut Transform, x| t.translation[0] = x,
+    );
+    properties.register(
+        "Transform.translation.y",
+        |t: &Transform| t.translation[1],
+        |t: &mut Transform, y| t.translation[1] = y,
+    );
+    properties.register(
+        "Sprite.alpha",
+        |s: &Sprite| s.alpha,
+        |s: &mut Sprite, a| s.alpha = a,
+    );
+
+    let mut world = World::default();
+    let entity = world.spawn();
+    world.insert(
+        entity,
+        Transform {
+            translation: [0.0; 3],
+        },
+    );
+    (tweener, world, entity)
+}
+
+fn y(world: &World, entity: Entity) -> f32 {
+    world.get::<Transform>(entity).unwrap().translation[1]
+}
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-4
+}
+
+/// Verifies interpolation from the current value, easing, and completion.
+#[test]
+fn tweens_property() {
+    let (mut tweener, mut world, entity) = setup();
+    let id = tweener
+        .tween(entity)
+        .field("Transform.translation.y")
+        .to(10.0)
+        .over(1.0)
+        .start()
+        .unwrap();
+
+    tweener.update(&mut world, ms(250));
+    assert!(close(y(&world, entity), 2.5));
+    assert!(tweener.is_active(id));
+
+    tweener.update(&mut world, ms(1000));
+    assert!(close(y(&world, entity), 10.0));
+    assert!(!tweener.is_active(id));
+    assert_eq!(
+        tweener.events().iter().copied().collect::<Vec<_>>(),
+        [TweenEvent::Completed { id, entity }]
+    );
+
+    tweener
+        .tween(entity)
+        .field("Transform.translation.y")
+        .from(0.0)
+        .to(1.0)
+        .over(1.0)
+        .ease(Ease::InQuad)
+        .start()
+        .unwrap();
+    tweener.update(&mut world, ms(500));
+    assert!(close(y(&world, entity), 0.25));
+}
+
+/// Verifies that steps, waits, and leftover time chain in order.
+#[test]
+fn sequences_steps() {
+    let (mut tweener, mut world, entity) = setup();
+    tweener
+        .tween(entity)
+        .field("Transform.translation.x")
+        .to(4.0)
+        .over(0.5)
+        .wait(0.25)
+        .field("Transform.translation.y")
+        .to(-2.0)
+        .over(0.25)
+        .start()
+        .unwrap();
+
+    tweener.update(&mut world, ms(600));
+    let translation = world.get::<Transform>(entity).unwrap().translation;
+    assert!(close(translation[0], 4.0));
+    assert!(close(translation[1], 0.0));
+
+    tweener.update(&mut world, ms(300));
+    assert!(close(y(&world, entity), -1.2));
+    tweener.update(&mut world, ms(100));
+    assert!(close(y(&world, entity), -2.0));
+    assert!(tweener.is_empty());
+}
+
+/// Verifies repeat counts, ping-pong, and loop events.
+#[test]
+fn repeats_and_ping_pongs() {
+    let (mut tweener, mut world, entity) = setup();
+    let id = tweener
+        .tween(entity)
+        .field("Transform.translation.y")
+        .from(0.0)
+        .to(1.0)
+        .over(1.0)
+        .repeat(Repeat::Times(2))
+        .ping_pong()
+        .start()
+        .unwrap();
+
+    tweener.update(&mut world, ms(1500));
+    assert!(close(y(&world, entity), 0.5));
+    tweener.update(&mut world, ms(600));
+    assert!(close(y(&world, entity), 0.1));
+    assert!(tweener
+        .events()
+        .iter()
+        .any(|event| *event == TweenEvent::Looped { id, entity }));
+
+    tweener.update(&mut world, ms(2000));
+    assert!(close(y(&world, entity), 0.0));
+    assert!(!tweener.is_active(id));
+}
+
+/// Verifies that forever loops restart from the captured start value.
+#[test]
+fn loops_forever() {
+    let (mut tweener, mut world, entity) = setup();
+    world.get_mut::<Transform>(entity).unwrap().translation[1] = 2.0;
+    let id = tweener
+        .tween(entity)
+        .field("Transform.translation.y")
+        .to(4.0)
+        .over(1.0)
+        .repeat(Repeat::Forever)
+        .start()
+        .unwrap();
+
+    tweener.update(&mut world, ms(10_250));
+    assert!(close(y(&world, entity), 2.5));
+    assert!(tweener.is_active(id));
+
+    assert!(tweener.cancel(id));
+    assert!(!tweener.cancel(id));
+}
+
+/// Verifies that instant steps on repeat advance once per update.
+#[test]
+fn zero_length_loop_terminates() {
+    let (mut tweener, mut world, entity) = setup();
+    tweener
+        .tween(entity)
+        .field("Transform.translation.y")
+        .to(1.0)
+        .repeat(Repeat::Forever)
+        .start()
+        .unwrap();
+    tweener.update(&mut world, ms(16));
+    assert!(close(y(&world, entity), 1.0));
+    assert_eq!(tweener.len(), 1);
+}
+
+/// Verifies that tweens stop when their component goes away.
+#[test]
+fn cancels_when_component_missing() {
+    let (mut tweener, mut world, entity) = setup();
+    let faded = tweener
+        .tween(entity)
+        .field("Sprite.alpha")
+        .to(0.0)
+        .over(1.0)
+        .start()
+        .unwrap();
+    let moved = tweener
+        .tween(entity)
+        .field("Transform.translation.y")
+        .to(1.0)
+        .over(1.0)
+        .start()
+        .unwrap();
+
+    tweener.update(&mut world, ms(100));
+    world.despawn(entity);
+    tweener.update(&mut world, ms(100));
+
+    let events: Vec<_> = tweener.events().iter().copied().collect();
+    assert_eq!(
+        events,
+        [
+            TweenEvent::Cancelled { id: faded, entity },
+            TweenEvent::Cancelled { id: moved, entity },
+        ]
+    );
+    assert!(tweener.is_empty());
+}
+
+/// Verifies that invalid sequences are rejected at start.
+#[test]
+fn rejects_invalid_sequences() {
+    let (mut tweener, _, entity) = setup();
+    assert!(tweener.tween(entity).start().is_err());
+    assert!(tweener
+        .tween(entity)
+        .field("Transform.scale")
+        .to(1.0)
+        .start()
+        .is_err());
+    assert!(tweener
+        .tween(entity)
+        .field("Transform.translation.y")
+        .over(1.0)
+        .start()
+        .is_err());
+    assert!(tweener.is_empty());
+}
@@ -0,0 +1,178 @@
+//! 10 000 boids flocking in a box.
+//!
+//! Each boid is an entity with a position and velocity. A spatial index
+//! finds its neighbours for separation, alignment, and cohesion, and the
+//! flock is drawn as instanced cones.
+//!
+//! ```text
+//! cargo run -p app --release --example boids_10k -- --frames 600
+//! ```
+
+mod common;
+
+use common::{Random, Scene};
+use ecs::{Entity, SpatialIndex, World};
+use math::{Aabb, Vec3};
+use render::{RenderStats, RenderStatsRecorder};
+use std::time::Duration;
+
+const BOIDS: usize = 10_000;
+/// Half the side of the box the flock lives in.
+const BOUNDS: f32 = 30.0;
+/// Distance within which boids see each other.
+const VIEW_RADIUS: f32 = 2.5;
+/// Distance below which boids steer apart.
+const SEPARATION_RADIUS: f32 = 1.0;
+const MIN_SPEED: f32 = 4.0;
+const MAX_SPEED: f32 = 10.0;
+/// Most instances drawn by one draw call.
+const MAX_BATCH_INSTANCES: usize = 4096;
+/// Triangles in the cone mesh each boid is drawn with.
+const CONE_TRIANGLES: u32 = 16;
+/// Bytes per instance: a 4x4 transform.
+const INSTANCE_BYTES: u64 = 64;
+
+#[derive(Debug, Clone, Copy)]
+struct Boid {
+    position: [f32; 3],
+    velocity: [f32; 3],
+}
+
+struct Boids {
+    world: World,
+    boids: Vec<Entity>,
+    index: SpatialIndex,
+    recorder: RenderStatsRecorder,
+    neighbours: usize,
+}
+
+impl Boids {
+    fn new(count: usize) -> Self {
+        let mut random = Random::new(0xb01d);
+        let mut world = World::default();
+        let boids = (0..count)
+            .map(|_| {
+                let entity = world.spawn();
+                let position = [(); 3].map(|()| random.range(-BOUNDS, BOUNDS));
+                let velocity = [(); 3].map(|()| random.range(-MIN_SPEED, MIN_SPEED));
+                world.insert(entity, Boid { position, velocity });
+                entity
+            })
+            .collect();
+        let mut recorder = RenderStatsRecorder::default();
+        recorder.allocate_buffer(count as u64 * INSTANCE_BYTES);
+        Self {
+            world,
+            boids,
+            index: SpatialIndex::with_margin(0.5),
+            recorder,
+            neighbours: 0,
+        }
+    }
+
+    /// Computes each boid's new velocity from its neighbours.
+    fn steer(&mut self, seconds: f32) -> Vec<(Entity, [f32; 3])> {
+        self.neighbours = 0;
+        let mut steered = Vec::with_capacity(self.boids.len());
+        for &entity in &self.boids {
+            let Some(&boid) = self.world.get::<Boid>(entity) else {
+                continue;
+            };
+            let mut separation = [0.0; 3];
+            let mut alignment = [0.0; 3];
+            let mut cohesion = [0.0; 3];
+            let mut seen = 0.0;
+            let view =
+                Aabb::from_center_half_extents(Vec3::from(boid.position), Vec3::splat(VIEW_RADIUS));
+            for other in self.index.query_aabb(&view) {
+                if other == entity {
+                    continue;
+                }
+                let Some(other) = self.world.get::<Boid>(other) else {
+                    continue;
+                };
+                let offset = sub(other.position, boid.position);
+                let distance = length(offset);
+                if distance > VIEW_RADIUS {
+                    continue;
+                }
+                seen += 1.0;
+                for axis in 0..3 {
+                    alignment[axis] += other.velocity[axis];
+                    cohesion[axis] += offset[axis];
+                    if distance < SEPARATION_RADIUS {
+                        separation[axis] -= offset[axis] / distance.max(0.01);
+                    }
+                }
+            }
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            {
+                self.neighbours += seen as usize;
+            }
+
+            let mut velocity = boid.velocity;
+            for axis in 0..3 {
+                let mut acceleration = 3.0 * separation[axis];
+                if seen > 0.0 {
+                    acceleration += 0.8 * (alignment[axis] / seen - boid.velocity[axis]);
+                    acceleration += 0.6 * cohesion[axis] / seen;
+                }
+                // Turn back towards the middle near the walls.
+                if boid.position[axis].abs() > BOUNDS * 0.9 {
+                    acceleration -= boid.position[axis].signum() * 8.0;
+                }
+                velocity[axis] += acceleration * seconds;
+            }
+            let speed = length(velocity).max(f32::EPSILON);
+            let clamped = speed.clamp(MIN_SPEED, MAX_SPEED);
+            steered.push((entity, velocity.map(|v| v / speed * clamped)));
+        }
+        steered
+    }
+}
+
+impl Scene for Boids {
+    fn step(&mut self, delta: Duration) -> RenderStats {
+        let seconds = delta.as_secs_f32();
+        self.index.sync::<Boid>(&self.world, |boid| {
+            Aabb::from_center_half_extents(Vec3::from(boid.position), Vec3::splat(0.1))
+        });
+        for (entity, velocity) in self.steer(seconds) {
+            if let Some(boid) = self.world.get_mut::<Boid>(entity) {
+                boid.velocity = velocity;
+                for (position, velocity) in boid.position.iter_mut().zip(velocity) {
+                    *position = (*position + velocity * seconds).clamp(-BOUNDS, BOUNDS);
+                }
+            }
+        }
+
+        self.recorder.begin_frame();
+        for chunk in self.boids.chunks(MAX_BATCH_INSTANCES) {
+            #[allow(clippy::cast_possible_truncation)]
+            self.recorder.draw(0, chunk.len() as u32, CONE_TRIANGLES);
+        }
+        self.recorder.end_frame()
+    }
+
+    fn overlay(&self) -> Vec<String> {
+        #[allow(clippy::cast_precision_loss)]
+        let average = self.neighbours as f32 / self.boids.len().max(1) as f32;
+        vec![format!(
+            "boids       {} ({average:.1} neighbours each)",
+            self.boids.len()
+        )]
+    }
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn length(v: [f32; 3]) -> f32 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    common::run_scene("boids_10k", Boids::new(BOIDS)).await
+}
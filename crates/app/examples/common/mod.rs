@@ -0,0 +1,200 @@
+//! Frame-loop harness shared by the stress-test examples.
+//!
+//! Each example is a [`Scene`] stepped once per frame by the engine's main
+//! loop, through a main-thread task that re-queues itself. The scene's
+//! renderer statistics feed the same frame stats, metrics, and remote
+//! `/status` as a normal run, and once a second the debug overlay's lines
+//! are printed to the terminal.
+//!
+//! Examples run until Ctrl+C, or for a fixed number of frames when given
+//! `--frames N`, which makes them usable as soak tests in CI.
+
+use app::resources::{run, AppState};
+use render::RenderStats;
+use rustgine_core::{init_tracing_from_config, Config};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Longest step handed to a scene, so a stall does not explode the
+/// simulation.
+const MAX_DELTA: Duration = Duration::from_millis(100);
+
+/// Interval between overlay reports.
+const REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A workload stepped once per frame.
+pub trait Scene: Send + 'static {
+    /// Advances the scene by `delta`, returning the frame's draw statistics.
+    fn step(&mut self, delta: Duration) -> RenderStats;
+
+    /// Returns scene-specific overlay lines, such as entity counts.
+    fn overlay(&self) -> Vec<String>;
+}
+
+/// Step timings across the run.
+#[derive(Debug, Default)]
+struct Timings {
+    /// Frames stepped.
+    frames: u64,
+    /// Total time spent stepping.
+    total: Duration,
+    /// Slowest step.
+    max: Duration,
+}
+
+/// Per-run state carried from frame to frame.
+struct Runner<S> {
+    name: &'static str,
+    state: Arc<AppState>,
+    scene: S,
+    frame_limit: Option<u64>,
+    timings: Timings,
+    last_frame: Instant,
+    last_report: Instant,
+}
+
+/// Runs `scene` in the engine's main loop until Ctrl+C or the frame limit.
+///
+/// # Errors
+///
+/// Returns an error if the arguments, configuration, or runtime fail.
+pub async fn run_scene(name: &'static str, scene: impl Scene) -> anyhow::Result<()> {
+    let frame_limit = frame_limit(std::env::args().skip(1))?;
+    let config = Config::load()?;
+    let state = AppState::initialize(&config)?;
+    let _tracing =
+        init_tracing_from_config(&config, Some(&state.console), Some(&state.log_buffer))?;
+
+    // Debug builds are far slower, so say which build the numbers are for
+    println!("[{name}] {}", state.build);
+    let now = Instant::now();
+    let runner = Runner {
+        name,
+        state: Arc::clone(&state),
+        scene,
+        frame_limit,
+        timings: Timings::default(),
+        last_frame: now,
+        last_report: now,
+    };
+    state.main_thread.enqueue(move || runner.frame());
+    run(state).await?;
+    Ok(())
+}
+
+/// Parses `--frames N` or `--frames=N`.
+fn frame_limit(mut args: impl Iterator<Item = String>) -> anyhow::Result<Option<u64>> {
+    while let Some(arg) = args.next() {
+        let value = match arg.strip_prefix("--frames") {
+            Some("") => args.next(),
+            Some(rest) => rest.strip_prefix('=').map(str::to_owned),
+            None => continue,
+        };
+        let value = value.ok_or_else(|| anyhow::anyhow!("--frames expects a frame count"))?;
+        return Ok(Some(value.parse().map_err(|_| {
+            anyhow::anyhow!("--frames expects a frame count, got `{value}`")
+        })?));
+    }
+    Ok(None)
+}
+
+impl<S: Scene> Runner<S> {
+    /// Steps the scene, reports, and queues the next frame.
+    fn frame(mut self) {
+        let now = Instant::now();
+        let delta = now.duration_since(self.last_frame).min(MAX_DELTA);
+        self.last_frame = now;
+
+        let stats = self.scene.step(delta);
+        let elapsed = now.elapsed();
+        self.state.frame_stats.record_render_stats(stats);
+        self.timings.frames += 1;
+        self.timings.total += elapsed;
+        self.timings.max = self.timings.max.max(elapsed);
+
+        if now.duration_since(self.last_report) >= REPORT_INTERVAL {
+            self.last_report = now;
+            self.report(&stats, elapsed);
+        }
+
+        if self
+            .frame_limit
+            .is_some_and(|limit| self.timings.frames >= limit)
+        {
+            self.summary();
+            self.state.shutdown.trigger();
+            return;
+        }
+        let queue = self.state.main_thread.clone();
+        queue.enqueue(move || self.frame());
+    }
+
+    /// Prints the overlay lines.
+    fn report(&self, stats: &RenderStats, step: Duration) {
+        println!(
+            "[{}] frame {}  {:.1} fps  step {:.2} ms",
+            self.name,
+            self.timings.frames,
+            self.state.frame_stats.fps(),
+            ms(step)
+        );
+        for line in stats
+            .overlay_lines()
+            .into_iter()
+            .chain(self.scene.overlay())
+        {
+            println!("    {line}");
+        }
+    }
+
+    /// Prints step timings for the whole run.
+    fn summary(&self) {
+        let frames = u32::try_from(self.timings.frames)
+            .unwrap_or(u32::MAX)
+            .max(1);
+        println!(
+            "[{}] {} frames, step avg {:.2} ms, max {:.2} ms",
+            self.name,
+            self.timings.frames,
+            ms(self.timings.total / frames),
+            ms(self.timings.max)
+        );
+    }
+}
+
+/// Returns `duration` in milliseconds.
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Small deterministic generator for placing scene objects.
+///
+/// Unused by examples whose scene comes from elsewhere, such as a game
+/// library.
+#[derive(Debug)]
+#[allow(dead_code)]
+pub struct Random(u64);
+
+#[allow(dead_code)]
+impl Random {
+    /// Creates a generator from `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self(seed | 1)
+    }
+
+    /// Returns the next value in `0.0..1.0`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn next(&mut self) -> f32 {
+        // xorshift64*
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        let bits = self.0.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 40;
+        bits as f32 / (1u64 << 24) as f32
+    }
+
+    /// Returns the next value in `min..max`.
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + self.next() * (max - min)
+    }
+}
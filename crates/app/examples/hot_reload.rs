@@ -0,0 +1,55 @@
+//! Game logic hot-reloaded from a `cdylib` while the engine runs.
+//!
+//! Loads the game library given as the first argument and steps it in the
+//! engine's main loop. Rebuilding the game crate swaps the new build in
+//! between two frames, moving the state it saves to the new build.
+//!
+//! ```text
+//! cargo run -p app --features hot-reload --example hot_reload -- target/debug/libmy_game.so
+//! ```
+
+mod common;
+
+use app::resources::GameLibrary;
+use common::Scene;
+use ecs::World;
+use render::RenderStats;
+use std::time::Duration;
+use tracing::warn;
+
+struct HotReload {
+    world: World,
+    game: GameLibrary,
+}
+
+impl Scene for HotReload {
+    fn step(&mut self, delta: Duration) -> RenderStats {
+        // A failed build keeps the running logic until the next rebuild
+        if let Err(e) = self.game.poll_reload(&mut self.world) {
+            warn!(error = %e, "game reload failed");
+        }
+        self.game.update(&mut self.world, delta);
+        RenderStats::default()
+    }
+
+    fn overlay(&self) -> Vec<String> {
+        vec![
+            format!("library {}", self.game.path().display()),
+            format!("reloads {}", self.game.reloads()),
+            format!("entities {}", self.world.len()),
+        ]
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let path = std::env::args()
+        .nth(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .ok_or_else(|| anyhow::anyhow!("usage: hot_reload <game library> [--frames N]"))?;
+    let scene = HotReload {
+        world: World::default(),
+        game: GameLibrary::load(path)?,
+    };
+    common::run_scene("hot_reload", scene).await
+}
@@ -0,0 +1,190 @@
+//! Thousands of rigid bodies piling up in a bin.
+//!
+//! Boxes and balls rain into a walled bin until 2 000 bodies are resting on
+//! each other, exercising the physics step's contact solving and sleeping.
+//! Each body is an entity linked to its rapier body, drawn as instanced
+//! cubes and spheres.
+//!
+//! ```text
+//! cargo run -p app --release --example physics_pile -- --frames 1200
+//! ```
+
+mod common;
+
+use common::{Random, Scene};
+use ecs::{Entity, World};
+use physics::rapier3d::prelude::*;
+use physics::PhysicsWorld;
+use render::{RenderStats, RenderStatsRecorder};
+use std::time::Duration;
+
+const MAX_BODIES: usize = 2_000;
+/// Bodies dropped per second.
+const DROP_RATE: f32 = 200.0;
+/// Fixed physics step.
+const FIXED_STEP: Duration = Duration::from_micros(16_667);
+/// Most fixed steps run per frame before the simulation falls behind.
+const MAX_STEPS_PER_FRAME: u32 = 2;
+/// Half the width of the bin.
+const BIN: f32 = 15.0;
+const CUBE_TRIANGLES: u32 = 12;
+const SPHERE_TRIANGLES: u32 = 320;
+/// Most instances drawn by one draw call.
+const MAX_BATCH_INSTANCES: usize = 4096;
+/// Bytes per instance: a 4x4 transform.
+const INSTANCE_BYTES: u64 = 64;
+
+/// Shape of a body, which decides the mesh it is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    Cube,
+    Sphere,
+}
+
+/// Links an entity to its rapier body.
+#[derive(Debug, Clone, Copy)]
+struct Body {
+    handle: RigidBodyHandle,
+    shape: Shape,
+}
+
+struct PhysicsPile {
+    world: World,
+    physics: PhysicsWorld,
+    bodies: Vec<Entity>,
+    recorder: RenderStatsRecorder,
+    random: Random,
+    /// Time not yet simulated.
+    accumulator: Duration,
+    /// Fractional bodies owed to the next frame.
+    pending: f32,
+    steps: u64,
+}
+
+impl PhysicsPile {
+    fn new() -> Self {
+        let mut physics = PhysicsWorld::default();
+        physics.insert_collider(
+            ColliderBuilder::cuboid(BIN * 2.0, 0.5, BIN * 2.0).translation(vector![0.0, -0.5, 0.0]),
+            None,
+        );
+        for (x, z, half_x, half_z) in [
+            (BIN, 0.0, 0.5, BIN),
+            (-BIN, 0.0, 0.5, BIN),
+            (0.0, BIN, BIN, 0.5),
+            (0.0, -BIN, BIN, 0.5),
+        ] {
+            physics.insert_collider(
+                ColliderBuilder::cuboid(half_x, 10.0, half_z).translation(vector![x, 10.0, z]),
+                None,
+            );
+        }
+        let mut recorder = RenderStatsRecorder::default();
+        recorder.allocate_buffer(MAX_BODIES as u64 * INSTANCE_BYTES);
+        Self {
+            world: World::default(),
+            physics,
+            bodies: Vec::new(),
+            recorder,
+            random: Random::new(0x9113),
+            accumulator: Duration::ZERO,
+            pending: 0.0,
+            steps: 0,
+        }
+    }
+
+    /// Drops new bodies above the bin until the pile is full.
+    fn drop_bodies(&mut self, seconds: f32) {
+        self.pending += DROP_RATE * seconds;
+        while self.pending >= 1.0 && self.bodies.len() < MAX_BODIES {
+            self.pending -= 1.0;
+            let entity = self.world.spawn();
+            let position = vector![
+                self.random.range(-BIN + 1.0, BIN - 1.0),
+                self.random.range(20.0, 30.0),
+                self.random.range(-BIN + 1.0, BIN - 1.0)
+            ];
+            let handle = self
+                .physics
+                .insert_body(RigidBodyBuilder::dynamic().translation(position));
+            let (shape, collider) = if self.bodies.len().is_multiple_of(2) {
+                (Shape::Cube, ColliderBuilder::cuboid(0.3, 0.3, 0.3))
+            } else {
+                (Shape::Sphere, ColliderBuilder::ball(0.3))
+            };
+            let collider = self
+                .physics
+                .insert_collider(collider.restitution(0.2), Some(handle));
+            self.physics.set_entity(collider, entity.0);
+            self.world.insert(entity, Body { handle, shape });
+            self.bodies.push(entity);
+        }
+        self.pending = self.pending.min(1.0);
+    }
+
+    /// Gathers body transforms into one instanced draw call per shape.
+    fn draw(&mut self) -> RenderStats {
+        let mut cubes = Vec::new();
+        let mut spheres = Vec::new();
+        for (_, body) in self.world.query::<Body>() {
+            let Some(position) = self.physics.translation(body.handle) else {
+                continue;
+            };
+            match body.shape {
+                Shape::Cube => cubes.push(position),
+                Shape::Sphere => spheres.push(position),
+            }
+        }
+
+        self.recorder.begin_frame();
+        // Ground and walls.
+        self.recorder.draw(0, 5, CUBE_TRIANGLES);
+        for (pipeline, instances, triangles) in
+            [(0, &cubes, CUBE_TRIANGLES), (1, &spheres, SPHERE_TRIANGLES)]
+        {
+            for chunk in instances.chunks(MAX_BATCH_INSTANCES) {
+                #[allow(clippy::cast_possible_truncation)]
+                self.recorder.draw(pipeline, chunk.len() as u32, triangles);
+            }
+        }
+        self.recorder.end_frame()
+    }
+}
+
+impl Scene for PhysicsPile {
+    fn step(&mut self, delta: Duration) -> RenderStats {
+        self.drop_bodies(delta.as_secs_f32());
+        self.accumulator += delta;
+        let mut steps = 0;
+        while self.accumulator >= FIXED_STEP && steps < MAX_STEPS_PER_FRAME {
+            self.physics.step(FIXED_STEP);
+            self.accumulator -= FIXED_STEP;
+            steps += 1;
+        }
+        // Drop time the simulation cannot catch up on.
+        if steps == MAX_STEPS_PER_FRAME {
+            self.accumulator = Duration::ZERO;
+        }
+        self.steps += u64::from(steps);
+        self.draw()
+    }
+
+    fn overlay(&self) -> Vec<String> {
+        let sleeping = self
+            .physics
+            .bodies()
+            .iter()
+            .filter(|(_, body)| body.is_sleeping())
+            .count();
+        vec![format!(
+            "bodies      {} ({sleeping} sleeping, {} steps)",
+            self.bodies.len(),
+            self.steps
+        )]
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    common::run_scene("physics_pile", PhysicsPile::new()).await
+}
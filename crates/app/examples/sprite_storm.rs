@@ -0,0 +1,235 @@
+//! A storm of short-lived animated sprites.
+//!
+//! Emitters burst sprites that fall, animate, and expire, keeping around
+//! 20 000 alive. Every frame spawns and despawns hundreds of entities, then
+//! batches the survivors per atlas, which stresses entity churn as well as
+//! sprite batching. Expired sprites go back to an entity pool for reuse,
+//! and the per-atlas batch lists are built in a frame arena.
+//!
+//! ```text
+//! cargo run -p app --release --example sprite_storm -- --frames 600
+//! ```
+
+mod common;
+
+use common::{Random, Scene};
+use ecs::{Entity, EntityPool, Events, World};
+use render::sprite::{
+    animate_sprites, AnimationClip, AnimationEvent, SpriteAnimation, TextureAtlas,
+};
+use render::{RenderStats, RenderStatsRecorder};
+use scheduler::{FrameArena, SystemProfiler};
+use std::time::Duration;
+
+/// Sprites spawned per second.
+const SPAWN_RATE: f32 = 10_000.0;
+/// Seconds a sprite lives.
+const LIFETIME: f32 = 2.0;
+/// Sprites pre-spawned into the pool, about as many as are alive at once.
+const POOLED_SPRITES: usize = 20_000;
+const GRAVITY: f32 = -600.0;
+/// Most instances drawn by one draw call.
+const MAX_BATCH_INSTANCES: usize = 4096;
+/// Bytes per instance: position, size, and atlas rectangle.
+const INSTANCE_BYTES: u64 = 32;
+
+#[derive(Debug, Clone, Copy)]
+struct Particle {
+    position: [f32; 2],
+    velocity: [f32; 2],
+    atlas: usize,
+    age: f32,
+}
+
+struct SpriteStorm {
+    world: World,
+    atlases: Vec<TextureAtlas>,
+    sprites: EntityPool,
+    events: Events<AnimationEvent<Entity>>,
+    recorder: RenderStatsRecorder,
+    /// Per-frame batch lists, released at the start of each frame.
+    arena: FrameArena,
+    profiler: SystemProfiler,
+    random: Random,
+    /// Fractional sprites owed to the next frame.
+    pending: f32,
+    spawned: usize,
+    expired: usize,
+    /// Instance buffer capacity, grown as the storm builds.
+    capacity: usize,
+}
+
+impl SpriteStorm {
+    fn new() -> Self {
+        let atlases = vec![
+            TextureAtlas::grid("sparks.png", (16, 16), 8, 1)
+                .with_clip("burn", AnimationClip::new([0, 1, 2, 3, 4, 5, 6, 7], 12.0)),
+            TextureAtlas::grid("leaves.png", (32, 32), 4, 4).with_clip(
+                "burn",
+                AnimationClip::new([0, 1, 2, 3, 4, 5, 6, 7, 8, 9], 8.0),
+            ),
+            TextureAtlas::grid("smoke.png", (64, 64), 4, 2)
+                .with_clip("burn", AnimationClip::new([0, 1, 2, 3, 4, 5, 6, 7], 4.0)),
+        ];
+        let mut recorder = RenderStatsRecorder::default();
+        for atlas in &atlases {
+            recorder.allocate_texture(u64::from(atlas.width * atlas.height) * 4);
+        }
+        let mut world = World::default();
+        let mut sprites = EntityPool::new(|world, entity| {
+            world.insert(entity, SpriteAnimation::new("burn"));
+        });
+        sprites.warm_up(&mut world, POOLED_SPRITES);
+        Self {
+            world,
+            atlases,
+            sprites,
+            events: Events::default(),
+            recorder,
+            arena: FrameArena::new(),
+            profiler: SystemProfiler::new(),
+            random: Random::new(0x5707),
+            pending: 0.0,
+            spawned: 0,
+            expired: 0,
+            capacity: 0,
+        }
+    }
+
+    /// Bursts new sprites from emitters along the top of the screen.
+    fn spawn(&mut self, seconds: f32) {
+        self.pending += SPAWN_RATE * seconds;
+        while self.pending >= 1.0 {
+            self.pending -= 1.0;
+            let emitter = (self.random.next() * 8.0).floor();
+            let entity = self.sprites.acquire(&mut self.world);
+            self.world.insert(
+                entity,
+                Particle {
+                    position: [emitter * 240.0 + 120.0, 1080.0],
+                    velocity: [
+                        self.random.range(-300.0, 300.0),
+                        self.random.range(0.0, 400.0),
+                    ],
+                    atlas: self.spawned % self.atlases.len(),
+                    age: 0.0,
+                },
+            );
+            if let Some(animation) = self.world.get_mut::<SpriteAnimation>(entity) {
+                animation.speed = self.random.range(0.5, 1.5);
+            }
+            self.spawned += 1;
+        }
+    }
+
+    /// Moves and ages sprites, returning the expired ones to the pool.
+    fn simulate(&mut self, seconds: f32) {
+        let mut expired = self.arena.vec();
+        for &entity in self.sprites.active() {
+            let Some(particle) = self.world.get_mut::<Particle>(entity) else {
+                continue;
+            };
+            particle.age += seconds;
+            particle.velocity[1] += GRAVITY * seconds;
+            for axis in 0..2 {
+                particle.position[axis] += particle.velocity[axis] * seconds;
+            }
+            if particle.age >= LIFETIME || particle.position[1] <= 0.0 {
+                expired.push(entity);
+            }
+        }
+        for &entity in &expired {
+            self.sprites.release(&mut self.world, entity);
+        }
+        self.expired += expired.len();
+    }
+
+    /// Advances every sprite's animation.
+    fn animate(&mut self, delta: Duration) {
+        self.events.update();
+        for &entity in self.sprites.active() {
+            let Some(&Particle { atlas, .. }) = self.world.get::<Particle>(entity) else {
+                continue;
+            };
+            if let Some(animation) = self.world.get_mut::<SpriteAnimation>(entity) {
+                animate_sprites(
+                    &self.atlases[atlas],
+                    [(entity, animation)],
+                    delta,
+                    &mut self.events,
+                );
+            }
+        }
+    }
+
+    /// Groups sprites per atlas into instanced draws.
+    fn draw(&mut self) -> RenderStats {
+        let arena = &self.arena;
+        let mut per_atlas = arena.vec_with_capacity(self.atlases.len());
+        per_atlas.extend(self.atlases.iter().map(|_| arena.vec()));
+        for &entity in self.sprites.active() {
+            let (Some(particle), Some(animation)) = (
+                self.world.get::<Particle>(entity),
+                self.world.get::<SpriteAnimation>(entity),
+            ) else {
+                continue;
+            };
+            let atlas = &self.atlases[particle.atlas];
+            if let Some(uv) = animation.frame(atlas).and_then(|frame| atlas.uv(frame)) {
+                per_atlas[particle.atlas].push((particle.position, uv));
+            }
+        }
+
+        if self.sprites.active().len() > self.capacity {
+            // Grow the instance buffer by doubling, like a real upload would.
+            let grown = self.sprites.active().len().next_power_of_two();
+            self.recorder
+                .free_buffer(self.capacity as u64 * INSTANCE_BYTES);
+            self.recorder.allocate_buffer(grown as u64 * INSTANCE_BYTES);
+            self.capacity = grown;
+        }
+        self.recorder.begin_frame();
+        for (atlas, instances) in per_atlas.iter().enumerate() {
+            for chunk in instances.chunks(MAX_BATCH_INSTANCES) {
+                #[allow(clippy::cast_possible_truncation)]
+                self.recorder.draw(atlas as u64, chunk.len() as u32, 2);
+            }
+        }
+        self.recorder.end_frame()
+    }
+}
+
+impl Scene for SpriteStorm {
+    fn step(&mut self, delta: Duration) -> RenderStats {
+        self.profiler.record_arena(self.arena.reset());
+        let seconds = delta.as_secs_f32();
+        self.spawn(seconds);
+        self.simulate(seconds);
+        self.animate(delta);
+        self.draw()
+    }
+
+    fn overlay(&self) -> Vec<String> {
+        let arena = self.profiler.arena_usage();
+        vec![
+            format!(
+                "sprites     {} alive ({} spawned, {} expired, {} pooled entities)",
+                self.sprites.active().len(),
+                self.spawned,
+                self.expired,
+                self.sprites.spawned()
+            ),
+            format!(
+                "arena       {} KiB held, {} KiB peak, {} frames grew",
+                arena.capacity_bytes / 1024,
+                arena.peak_capacity_bytes / 1024,
+                arena.grown_frames
+            ),
+        ]
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    common::run_scene("sprite_storm", SpriteStorm::new()).await
+}
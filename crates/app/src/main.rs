@@ -3,53 +3,112 @@
 //! Initializes the engine configuration, tracing infrastructure, and
 //! runs the main event loop until shutdown.
 //!
+//! # Flags
+//!
+//! - `--dump-schedule[=dot|mermaid]` - Print the subsystem schedule and exit
+//! - `--config key=value` - Override one configuration key, e.g.
+//!   `--config output.surface_format=hdr10`
+//! - `--print-config` - Print the merged configuration, annotated with the
+//!   layer that set each key, and exit
+//!
 //! # Exit Codes
 //!
 //! - `0` - Clean shutdown
 //! - `1` - Error during initialization or runtime
+//!
+//! # Web
+//!
+//! Built for `wasm32` with the `wasm` feature, `main` returns right away and
+//! the browser drives the engine from animation frames; errors are logged
+//! instead of setting an exit code.
 
-use app::resources::{run, AppState};
-use platform::RustginePlatform;
-use render::RustgineRender;
-use rustgine_core::{init_tracing, Config};
-use scheduler::RustgineScheduler;
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+compile_error!("building the app for wasm32 requires the `wasm` feature");
+
+use app::resources::{dump_schedule_format, run, AppBuilder, AppState};
+use rustgine_core::config_layers::PRINT_CONFIG_FLAG;
+use rustgine_core::{init_tracing_from_config, ConfigLoader};
 use tracing::info;
 
 /// Application entry point.
 ///
 /// Performs the following initialization sequence:
 ///
-/// 1. Load configuration from environment
-/// 2. Initialize structured logging/tracing
-/// 3. Create application state
-/// 4. Run the main event loop
-/// 5. Log shutdown and exit
+/// 1. Load configuration from files, environment, and command line,
+///    printing it instead of running if `--print-config` was given
+/// 2. Create application state
+/// 3. Initialize structured logging/tracing, mirrored into the console
+/// 4. Register subsystems, printing their schedule instead of running if
+///    `--dump-schedule` was given
+/// 5. Run the main event loop
+/// 6. Log shutdown and exit
+#[cfg(not(target_arch = "wasm32"))]
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    start().await
+}
+
+/// Browser entry point: spawns [`start`] on the page's executor, since the
+/// browser's thread must not block.
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    wasm_bindgen_futures::spawn_local(async {
+        if let Err(e) = start().await {
+            tracing::error!(error = %e, "engine failed");
+        }
+    });
+}
+
+/// Runs the initialization sequence described on `main`, then the engine
+/// until shutdown.
+async fn start() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let dump_schedule = dump_schedule_format(args.iter().cloned())?;
+
     // Load configuration first (before tracing, as it may affect log levels)
-    let config = Config::load()?;
+    let layered = ConfigLoader::new()
+        .with_args(args.iter().cloned())?
+        .load()?;
+    if args.iter().any(|arg| arg == PRINT_CONFIG_FLAG) {
+        print!("{layered}");
+        return Ok(());
+    }
+    let config = layered.into_config();
 
-    // Initialize tracing with environment-appropriate defaults
-    init_tracing(&config.log_level);
+    // Create application state before tracing so the console and log buffer
+    // capture log output
+    let state = AppState::initialize(&config)?;
+
+    // Initialize tracing with environment-appropriate defaults; the guard
+    // flushes span export on exit. Schedule dumps skip it to keep stdout
+    // clean.
+    let _tracing = match dump_schedule {
+        Some(_) => None,
+        None => Some(init_tracing_from_config(
+            &config,
+            Some(&state.console),
+            Some(&state.log_buffer),
+        )?),
+    };
 
     info!(
         environment = %config.environment,
         log_level = %config.log_level,
         service = "rustgine",
+        version = state.build.version,
+        git_hash = state.build.git_hash,
+        profile = state.build.profile,
+        target = state.build.target,
         "engine starting"
     );
 
-    // Initialize application state and run
-    let state = AppState::initialize(&config)?;
-
-    // Initialize subsystems in dependency order
-    let platform = RustginePlatform;
-    let render = RustgineRender;
-    let scheduler = RustgineScheduler;
+    // Register the subsystems enabled at compile time, in dependency order
+    let state = AppBuilder::from_state(state).add_default_systems()?.build();
 
-    state.register_system("platform", platform)?;
-    state.register_system("render", render)?;
-    state.register_system("scheduler", scheduler)?;
+    if let Some(format) = dump_schedule {
+        print!("{}", state.schedule()?.plan()?.render(format, &[]));
+        return Ok(());
+    }
 
     // Run the main event loop
     run(state).await?;
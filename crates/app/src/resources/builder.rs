@@ -0,0 +1,129 @@
+//! Application assembly.
+//!
+//! An [`AppBuilder`] wraps a fresh [`AppState`] and registers subsystems on
+//! it. [`add_default_systems`](AppBuilder::add_default_systems) registers
+//! the engine's own subsystems in dependency order, skipping those whose
+//! Cargo feature is disabled, so a headless server built without `render`
+//! or `audio` neither compiles nor starts them.
+
+#[cfg(feature = "render")]
+use crate::resources::state::RENDER_SYSTEM;
+use crate::resources::{run, AppState};
+#[cfg(feature = "audio")]
+use audio::RustgineAudio;
+#[cfg(feature = "net")]
+use net::RustgineNet;
+#[cfg(feature = "physics")]
+use physics::RustginePhysics;
+#[cfg(feature = "render")]
+use render::RustgineRender;
+use rustgine_core::{Config, RustgineError, RustgineSystem};
+use std::sync::Arc;
+
+/// Builds an [`AppState`] with its subsystems registered.
+///
+/// # Example
+///
+/// ```
+/// use app::resources::AppBuilder;
+/// use rustgine_core::Config;
+///
+/// let state = AppBuilder::new(&Config::default())?
+///     .add_default_systems()?
+///     .build();
+/// assert!(state.system_count() >= 5);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct AppBuilder {
+    state: Arc<AppState>,
+}
+
+impl AppBuilder {
+    /// Creates a builder with state initialized from `config`.
+    ///
+    /// Must be called from the main thread; see [`AppState::initialize`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cvar file cannot be read or parsed.
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        Ok(Self::from_state(AppState::initialize(config)?))
+    }
+
+    /// Creates a builder around already initialized state.
+    #[must_use]
+    pub fn from_state(state: Arc<AppState>) -> Self {
+        Self { state }
+    }
+
+    /// Returns the state being built.
+    #[must_use]
+    #[inline]
+    pub fn state(&self) -> &Arc<AppState> {
+        &self.state
+    }
+
+    /// Registers a subsystem; see [`AppState::register_system`].
+    ///
+    /// # Errors
+    ///
+    /// Registration currently always succeeds; see
+    /// [`AppState::register_system`].
+    pub fn add_system<S>(self, alias: &str, system: S) -> Result<Self, RustgineError>
+    where
+        S: RustgineSystem + Send + Sync + 'static,
+    {
+        self.state.register_system(alias, system)?;
+        Ok(self)
+    }
+
+    /// Registers the engine subsystems in dependency order: platform,
+    /// render, scheduler, physics, net, audio, ai, ui, and animation.
+    ///
+    /// Render, physics, net, and audio are only registered when their
+    /// Cargo feature is enabled.
+    ///
+    /// # Errors
+    ///
+    /// Registration currently always succeeds; see
+    /// [`AppState::register_system`].
+    pub fn add_default_systems(self) -> Result<Self, RustgineError> {
+        let builder = self.add_system("platform", platform::RustginePlatform)?;
+        #[cfg(feature = "render")]
+        let builder = {
+            let render = RustgineRender::new(builder.state.rendering_paused.clone())
+                .with_output_config(builder.state.config.output.clone());
+            builder.add_system(RENDER_SYSTEM, render)?
+        };
+        let builder = builder.add_system("scheduler", scheduler::RustgineScheduler)?;
+        #[cfg(feature = "physics")]
+        let builder = builder.add_system("physics", RustginePhysics::default())?;
+        #[cfg(feature = "net")]
+        let builder = {
+            let net = RustgineNet::default().with_cvars(builder.state.cvars.clone());
+            builder.add_system("net", net)?
+        };
+        #[cfg(feature = "audio")]
+        let builder = builder.add_system("audio", RustgineAudio)?;
+        builder
+            .add_system("ai", ai::RustgineAi::default())?
+            .add_system("ui", ui::RustgineUi::default())?
+            .add_system("animation", animation::RustgineAnimation::default())
+    }
+
+    /// Returns the built state, ready for [`run`].
+    #[must_use]
+    pub fn build(self) -> Arc<AppState> {
+        self.state
+    }
+
+    /// Runs the application until shutdown; see [`run`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a subsystem fails to start or shut down.
+    pub async fn run(self) -> Result<(), RustgineError> {
+        run(self.state).await
+    }
+}
@@ -0,0 +1,48 @@
+//! Unit tests for application assembly.
+
+use crate::resources::AppBuilder;
+use rustgine_core::{Config, Label};
+
+/// Verifies the default subsystems follow the enabled features, in
+/// dependency order.
+#[test]
+fn default_systems_respect_features() {
+    let state = AppBuilder::new(&Config::default())
+        .unwrap()
+        .add_default_systems()
+        .unwrap()
+        .build();
+    let names: Vec<Label> = state.systems().iter().map(|system| system.name).collect();
+
+    let expected: Vec<&str> = [
+        ("platform", true),
+        ("render", cfg!(feature = "render")),
+        ("scheduler", true),
+        ("physics", cfg!(feature = "physics")),
+        ("net", cfg!(feature = "net")),
+        ("audio", cfg!(feature = "audio")),
+        ("ai", true),
+        ("ui", true),
+        ("animation", true),
+    ]
+    .into_iter()
+    .filter_map(|(name, enabled)| enabled.then_some(name))
+    .collect();
+    assert_eq!(names, expected);
+}
+
+/// Verifies custom systems are added after the defaults and scheduled.
+#[test]
+fn adds_custom_systems() {
+    let state = AppBuilder::new(&Config::default())
+        .unwrap()
+        .add_default_systems()
+        .unwrap()
+        .add_system("game", platform::RustginePlatform)
+        .unwrap()
+        .build();
+    let plan = state.schedule().unwrap().plan().unwrap();
+    let batches = &plan.stages[0].batches;
+    assert_eq!(batches.last().unwrap(), &["game"]);
+    assert_eq!(batches.len(), state.system_count());
+}
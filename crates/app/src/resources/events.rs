@@ -0,0 +1,101 @@
+//! Engine lifecycle events.
+//!
+//! [`AppEvents`] broadcasts notable changes in the engine's own state, such
+//! as subsystem restarts or recovering from a subsystem panic, to any
+//! number of listeners: the game, tools watching a dedicated server, or
+//! tests.
+
+use crate::resources::HangReport;
+use rustgine_core::Label;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// Events kept for subscribers that fall behind.
+const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+/// A change in the engine's state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AppEvent {
+    /// A subsystem panicked while the subsystem registry was locked, and
+    /// the registry was recovered as it was left.
+    ///
+    /// The panicking subsystem may be half started or half shut down.
+    SystemRegistryCorrupted,
+
+    /// A subsystem is about to be shut down and started again by
+    /// [`AppState::restart_system`](crate::resources::AppState::restart_system).
+    SystemRestarting {
+        /// Name the subsystem was registered under.
+        name: Label,
+    },
+
+    /// A subsystem was shut down and started again.
+    SystemRestarted {
+        /// Name the subsystem was registered under.
+        name: Label,
+    },
+
+    /// Shutting a subsystem down or starting it again failed, leaving it
+    /// stopped.
+    SystemRestartFailed {
+        /// Name the subsystem was registered under.
+        name: Label,
+    },
+
+    /// No frame completed within the watchdog timeout; see
+    /// [`Watchdog`](crate::resources::Watchdog).
+    FrameHang(HangReport),
+}
+
+/// Broadcaster of [`AppEvent`]s.
+///
+/// Events sent while nobody is subscribed are dropped, and a subscriber
+/// that falls more than 64 events behind skips the oldest.
+///
+/// # Example
+///
+/// ```
+/// use app::resources::{AppEvent, AppEvents};
+///
+/// let events = AppEvents::new();
+/// let mut rx = events.subscribe();
+/// events.send(AppEvent::SystemRegistryCorrupted);
+/// assert_eq!(rx.try_recv(), Ok(AppEvent::SystemRegistryCorrupted));
+/// ```
+#[derive(Clone, Debug)]
+pub struct AppEvents {
+    sender: Arc<broadcast::Sender<AppEvent>>,
+}
+
+impl Default for AppEvents {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AppEvents {
+    /// Creates a broadcaster without subscribers.
+    #[must_use]
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self {
+            sender: Arc::new(sender),
+        }
+    }
+
+    /// Sends `event` to every current subscriber.
+    #[inline]
+    pub fn send(&self, event: AppEvent) {
+        // No receivers is fine: nobody is listening
+        let _ = self.sender.send(event);
+    }
+
+    /// Subscribes to events sent from now on.
+    #[must_use]
+    #[inline]
+    pub fn subscribe(&self) -> broadcast::Receiver<AppEvent> {
+        self.sender.subscribe()
+    }
+}
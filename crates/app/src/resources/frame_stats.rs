@@ -0,0 +1,185 @@
+//! Frame counting and frame-rate measurement.
+
+#[cfg(feature = "render")]
+use render::{PassTiming, RenderStats};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use web_time::Instant;
+
+/// Length of the window over which the frame rate is averaged.
+const FPS_WINDOW: Duration = Duration::from_secs(1);
+
+/// Frame counter, frame rate, entity count, GPU pass timings, and renderer
+/// statistics, updated by the main loop and the renderer. The last two
+/// require the `render` feature.
+///
+/// # Thread Safety
+///
+/// Updated from the main loop and readable from any thread, for example by
+/// the remote control server.
+///
+/// # Example
+///
+/// ```
+/// use app::resources::FrameStats;
+/// use std::time::{Duration, Instant};
+///
+/// let start = Instant::now();
+/// let stats = FrameStats::new(start);
+/// for i in 1..=60 {
+///     stats.record_frame(start + Duration::from_millis(i * 1000 / 60));
+/// }
+/// assert_eq!(stats.frames(), 60);
+/// assert!((stats.fps() - 60.0).abs() < 0.5);
+/// ```
+#[derive(Debug)]
+pub struct FrameStats {
+    started: Instant,
+    inner: Mutex<Window>,
+    entities: AtomicUsize,
+    #[cfg(feature = "render")]
+    gpu_passes: Mutex<Vec<PassTiming>>,
+    #[cfg(feature = "render")]
+    render: Mutex<RenderStats>,
+}
+
+/// Counters for the current averaging window.
+#[derive(Debug)]
+struct Window {
+    frames: u64,
+    start: Instant,
+    count: u32,
+    fps: f64,
+}
+
+impl Default for FrameStats {
+    fn default() -> Self {
+        Self::new(Instant::now())
+    }
+}
+
+impl FrameStats {
+    /// Creates statistics starting at `now`.
+    #[must_use]
+    pub fn new(now: Instant) -> Self {
+        Self {
+            started: now,
+            inner: Mutex::new(Window {
+                frames: 0,
+                start: now,
+                count: 0,
+                fps: 0.0,
+            }),
+            entities: AtomicUsize::new(0),
+            #[cfg(feature = "render")]
+            gpu_passes: Mutex::new(Vec::new()),
+            #[cfg(feature = "render")]
+            render: Mutex::new(RenderStats::default()),
+        }
+    }
+
+    /// Records a completed frame at `now`.
+    ///
+    /// The frame rate is recomputed once per second.
+    pub fn record_frame(&self, now: Instant) {
+        let mut window = self.window();
+        window.frames += 1;
+        window.count += 1;
+        let elapsed = now.saturating_duration_since(window.start);
+        if elapsed >= FPS_WINDOW {
+            window.fps = f64::from(window.count) / elapsed.as_secs_f64();
+            window.start = now;
+            window.count = 0;
+        }
+    }
+
+    /// Returns the number of frames recorded so far.
+    #[must_use]
+    pub fn frames(&self) -> u64 {
+        self.window().frames
+    }
+
+    /// Returns the frame rate averaged over the last full second.
+    #[must_use]
+    pub fn fps(&self) -> f64 {
+        self.window().fps
+    }
+
+    /// Stores the number of entities in the world after the latest frame.
+    pub fn record_entities(&self, count: usize) {
+        self.entities.store(count, Ordering::Relaxed);
+    }
+
+    /// Returns the number of entities in the world after the latest frame.
+    #[must_use]
+    pub fn entities(&self) -> usize {
+        self.entities.load(Ordering::Relaxed)
+    }
+
+    /// Stores the per-pass GPU timings of the latest resolved frame.
+    ///
+    /// Comparing [`gpu_ms`](Self::gpu_ms) with the frame interval tells
+    /// GPU-bound frames apart from CPU-bound ones.
+    #[cfg(feature = "render")]
+    pub fn record_gpu_timings(&self, timings: &[PassTiming]) {
+        let mut passes = self
+            .gpu_passes
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        passes.clear();
+        passes.extend_from_slice(timings);
+    }
+
+    /// Returns the per-pass GPU timings of the latest resolved frame.
+    ///
+    /// Empty if the adapter does not support timestamp queries.
+    #[cfg(feature = "render")]
+    #[must_use]
+    pub fn gpu_timings(&self) -> Vec<PassTiming> {
+        self.gpu_passes
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Returns the total GPU time of the latest resolved frame, if timed.
+    #[cfg(feature = "render")]
+    #[must_use]
+    pub fn gpu_ms(&self) -> Option<f64> {
+        let passes = self.gpu_timings();
+        (!passes.is_empty()).then(|| passes.iter().map(|pass| pass.gpu_ms).sum())
+    }
+
+    /// Stores the renderer statistics of the latest frame, for the debug
+    /// overlay and metrics.
+    #[cfg(feature = "render")]
+    pub fn record_render_stats(&self, stats: RenderStats) {
+        *self
+            .render
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = stats;
+    }
+
+    /// Returns the renderer statistics of the latest frame.
+    #[cfg(feature = "render")]
+    #[must_use]
+    pub fn render_stats(&self) -> RenderStats {
+        *self
+            .render
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Returns the time elapsed since the statistics were created.
+    #[must_use]
+    pub fn uptime(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    fn window(&self) -> std::sync::MutexGuard<'_, Window> {
+        self.inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,152 @@
+//! Game logic loadable from a dynamic library.
+//!
+//! A game crate built as a `cdylib` implements [`GameLogic`] and exports it
+//! with [`export_game!`](crate::export_game), so that a development build
+//! of the engine can load it with `GameLibrary` (`hot-reload` feature) and
+//! swap it for a rebuilt version without a restart:
+//!
+//! ```toml
+//! [lib]
+//! crate-type = ["cdylib"]
+//! ```
+//!
+//! Building the game library needs no engine features; only the host
+//! loading it does.
+
+use ecs::World;
+use rustgine_core::{RustgineError, RustgineSystem};
+use std::time::Duration;
+
+/// Version of the contract between [`export_game!`](crate::export_game) and
+/// the loader, bumped whenever either side changes.
+///
+/// Libraries exporting another version are refused.
+pub const GAME_ABI_VERSION: u32 = 1;
+
+/// Symbol of the function returning the library's [`GAME_ABI_VERSION`].
+pub const GAME_ABI_SYMBOL: &str = "rustgine_game_abi";
+
+/// Symbol of the function creating the library's [`GameLogic`].
+pub const GAME_ENTRY_SYMBOL: &str = "rustgine_game_create";
+
+/// Game systems run by the engine on the ECS [`World`].
+///
+/// [`startup`](RustgineSystem::startup) runs after the library is loaded and
+/// [`shutdown`](RustgineSystem::shutdown) before it is unloaded, including
+/// around every reload.
+///
+/// # State across reloads
+///
+/// The world outlives the library, but code does not: components and
+/// resources whose types are defined in the library are dropped through the
+/// library's code, and a rebuilt library may change their layout. Such state
+/// must be moved out of the world by [`save_state`](Self::save_state) and
+/// back in by [`restore_state`](Self::restore_state) of the new library,
+/// typically serialized with serde. State made of engine types can stay in
+/// the world.
+///
+/// The logic must be [`Send`] so the engine can step it from its main-thread
+/// queue.
+///
+/// # Example
+///
+/// ```
+/// use app::resources::GameLogic;
+/// use ecs::World;
+/// use rustgine_core::{RustgineError, RustgineSystem};
+/// use std::time::Duration;
+///
+/// #[derive(Debug, Default)]
+/// struct Score(u32);
+///
+/// #[derive(Debug, Default)]
+/// struct Game;
+///
+/// impl RustgineSystem for Game {
+///     fn startup(&mut self) -> Result<(), RustgineError> {
+///         Ok(())
+///     }
+///
+///     fn shutdown(&mut self) -> Result<(), RustgineError> {
+///         Ok(())
+///     }
+/// }
+///
+/// impl GameLogic for Game {
+///     fn update(&mut self, world: &mut World, _delta: Duration) {
+///         if let Some(score) = world.resource_mut::<Score>() {
+///             score.0 += 1;
+///         }
+///     }
+///
+///     fn save_state(&mut self, world: &mut World) -> Result<Vec<u8>, RustgineError> {
+///         let score = world.remove_resource::<Score>().unwrap_or_default();
+///         Ok(score.0.to_le_bytes().to_vec())
+///     }
+///
+///     fn restore_state(&mut self, world: &mut World, state: &[u8]) -> Result<(), RustgineError> {
+///         let bytes = state.try_into().map_err(|_| anyhow::anyhow!("bad score"))?;
+///         world.insert_resource(Score(u32::from_le_bytes(bytes)));
+///         Ok(())
+///     }
+/// }
+///
+/// app::export_game!(Game);
+/// ```
+pub trait GameLogic: RustgineSystem + Send {
+    /// Runs one frame on `world`, `delta` after the previous one.
+    fn update(&mut self, world: &mut World, delta: Duration);
+
+    /// Moves the state the library owns out of `world` and serializes it,
+    /// before the library is unloaded.
+    ///
+    /// Saves nothing by default.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state cannot be serialized; the library then
+    /// stays loaded.
+    fn save_state(&mut self, world: &mut World) -> Result<Vec<u8>, RustgineError> {
+        let _ = world;
+        Ok(Vec::new())
+    }
+
+    /// Restores `state` saved by the previous library into `world`, after
+    /// [`startup`](RustgineSystem::startup).
+    ///
+    /// Ignores the state by default.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `state` cannot be deserialized, e.g. because its
+    /// format changed.
+    fn restore_state(&mut self, world: &mut World, state: &[u8]) -> Result<(), RustgineError> {
+        let _ = (world, state);
+        Ok(())
+    }
+}
+
+/// Exports a [`GameLogic`] type implementing [`Default`] from a `cdylib`
+/// for the hot-reload loader.
+///
+/// Defines the [`GAME_ABI_SYMBOL`] and [`GAME_ENTRY_SYMBOL`] functions; use
+/// it once per library. The library and the engine must be built with the
+/// same compiler and engine version.
+///
+/// [`GameLogic`]: crate::resources::GameLogic
+/// [`GAME_ABI_SYMBOL`]: crate::resources::GAME_ABI_SYMBOL
+/// [`GAME_ENTRY_SYMBOL`]: crate::resources::GAME_ENTRY_SYMBOL
+#[macro_export]
+macro_rules! export_game {
+    ($game:ty) => {
+        #[no_mangle]
+        pub extern "C" fn rustgine_game_abi() -> u32 {
+            $crate::resources::GAME_ABI_VERSION
+        }
+
+        #[no_mangle]
+        pub fn rustgine_game_create() -> ::std::boxed::Box<dyn $crate::resources::GameLogic> {
+            ::std::boxed::Box::new(<$game as ::std::default::Default>::default())
+        }
+    };
+}
@@ -0,0 +1,163 @@
+//! Liveness and readiness probes for containerized servers.
+//!
+//! Both HTTP servers, the metrics endpoint and the remote control server,
+//! answer the paths orchestrators such as Kubernetes probe:
+//!
+//! | Path | `200 OK` | `503 Service Unavailable` |
+//! |------|----------|---------------------------|
+//! | `/healthz` | The frame loop ticked within the stall budget, or has not started | The frame loop stalled |
+//! | `/readyz` | Every enabled subsystem started and the engine is not shutting down | Starting up or shutting down |
+//!
+//! Bodies are JSON, e.g. `{"status":"stalled","since_last_frame_ms":7012}`.
+
+#[cfg(not(target_arch = "wasm32"))]
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+#[cfg(not(target_arch = "wasm32"))]
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+use web_time::Instant;
+
+/// Time without a frame after which the engine reports itself unhealthy.
+///
+/// Far above the frame interval, so loading hitches do not get a server
+/// restarted.
+pub const DEFAULT_STALL_BUDGET: Duration = Duration::from_secs(5);
+
+/// Readiness and frame loop liveness, updated by [`run`](crate::resources::run).
+///
+/// # Example
+///
+/// ```
+/// use app::resources::Health;
+/// use std::time::{Duration, Instant};
+///
+/// let health = Health::new(Duration::from_secs(1));
+/// let start = Instant::now();
+/// assert!(!health.is_ready());
+/// assert!(health.is_live(start));
+///
+/// health.set_ready(true);
+/// health.record_frame(start);
+/// assert!(health.is_live(start + Duration::from_millis(500)));
+/// assert!(!health.is_live(start + Duration::from_secs(2)));
+/// ```
+#[derive(Debug)]
+pub struct Health {
+    ready: AtomicBool,
+    last_frame: Mutex<Option<Instant>>,
+    stall_budget: Duration,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self::new(DEFAULT_STALL_BUDGET)
+    }
+}
+
+impl Health {
+    /// Creates a probe state that is not ready, reporting a stall once no
+    /// frame ticked for `stall_budget`.
+    #[must_use]
+    pub fn new(stall_budget: Duration) -> Self {
+        Self {
+            ready: AtomicBool::new(false),
+            last_frame: Mutex::new(None),
+            stall_budget,
+        }
+    }
+
+    /// Marks the engine ready to serve, or not.
+    pub fn set_ready(&self, ready: bool) {
+        self.ready.store(ready, Ordering::Release);
+    }
+
+    /// Returns `true` if every subsystem started and the engine is not
+    /// shutting down.
+    #[must_use]
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+
+    /// Records a frame loop tick at `now`.
+    pub fn record_frame(&self, now: Instant) {
+        *self
+            .last_frame
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(now);
+    }
+
+    /// Returns the time from the last frame to `now`, or `None` before the
+    /// first frame.
+    #[must_use]
+    pub fn since_last_frame(&self, now: Instant) -> Option<Duration> {
+        self.last_frame
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .map(|last| now.saturating_duration_since(last))
+    }
+
+    /// Returns `false` if the frame loop has not ticked for longer than the
+    /// stall budget at `now`.
+    ///
+    /// Startup is covered by readiness, so the engine is live until its
+    /// first frame.
+    #[must_use]
+    pub fn is_live(&self, now: Instant) -> bool {
+        self.since_last_frame(now)
+            .is_none_or(|elapsed| elapsed <= self.stall_budget)
+    }
+
+    /// Returns the stall budget.
+    #[must_use]
+    #[inline]
+    pub fn stall_budget(&self) -> Duration {
+        self.stall_budget
+    }
+}
+
+/// Builds the `/healthz` and `/readyz` routes.
+///
+/// Not available on `wasm32`, where the engine cannot listen for requests.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn health_routes(health: Arc<Health>) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .with_state(health)
+}
+
+/// `GET /healthz`
+#[cfg(not(target_arch = "wasm32"))]
+async fn healthz(State(health): State<Arc<Health>>) -> (StatusCode, Json<Value>) {
+    let now = Instant::now();
+    let Some(elapsed) = health.since_last_frame(now) else {
+        return (StatusCode::OK, Json(json!({ "status": "starting" })));
+    };
+    let (code, status) = if health.is_live(now) {
+        (StatusCode::OK, "ok")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "stalled")
+    };
+    let body = json!({
+        "status": status,
+        "since_last_frame_ms": u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+    });
+    (code, Json(body))
+}
+
+/// `GET /readyz`
+#[cfg(not(target_arch = "wasm32"))]
+async fn readyz(State(health): State<Arc<Health>>) -> (StatusCode, Json<Value>) {
+    if health.is_ready() {
+        (StatusCode::OK, Json(json!({ "status": "ready" })))
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({ "status": "not ready" })),
+        )
+    }
+}
@@ -0,0 +1,35 @@
+//! Unit tests for health and readiness probes.
+
+use crate::resources::Health;
+use std::time::{Duration, Instant};
+
+/// Verifies readiness follows the flag set by the runtime.
+#[test]
+fn readiness_follows_flag() {
+    let health = Health::default();
+    assert!(!health.is_ready());
+    health.set_ready(true);
+    assert!(health.is_ready());
+    health.set_ready(false);
+    assert!(!health.is_ready());
+}
+
+/// Verifies liveness fails only once a ticking loop exceeds the budget.
+#[test]
+fn liveness_tracks_stalls() {
+    let health = Health::new(Duration::from_millis(100));
+    let start = Instant::now();
+    assert!(health.is_live(start + Duration::from_mins(1)));
+    assert_eq!(health.since_last_frame(start), None);
+
+    health.record_frame(start);
+    assert!(health.is_live(start + Duration::from_millis(100)));
+    assert!(!health.is_live(start + Duration::from_millis(101)));
+    assert_eq!(
+        health.since_last_frame(start + Duration::from_millis(40)),
+        Some(Duration::from_millis(40))
+    );
+
+    health.record_frame(start + Duration::from_millis(150));
+    assert!(health.is_live(start + Duration::from_millis(200)));
+}
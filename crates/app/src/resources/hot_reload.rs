@@ -0,0 +1,390 @@
+//! Hot reloading of game logic from a dynamic library.
+//!
+//! A [`GameLibrary`] loads a [`GameLogic`] exported by a `cdylib` game
+//! crate and watches the library file. When the game is rebuilt,
+//! [`poll_reload`](GameLibrary::poll_reload) loads the new build, has the
+//! old one save its state from the world, shuts it down and unloads it,
+//! then starts the new one and restores the state, all between two frames.
+//!
+//! The library is loaded from a numbered copy in the temporary directory,
+//! so the build can overwrite the original while it is loaded.
+//!
+//! Development only: loading a library runs its code with the engine's
+//! privileges, and the two must be built with the same compiler and engine
+//! version for the loaded [`GameLogic`] to be valid.
+
+use crate::resources::{GameLogic, GAME_ABI_SYMBOL, GAME_ABI_VERSION, GAME_ENTRY_SYMBOL};
+use ecs::World;
+use libloading::{Library, Symbol};
+use rustgine_core::RustgineError;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::{debug, info, warn};
+
+/// Age the library file must reach before it is loaded, so a build still
+/// writing it is not picked up.
+const RELOAD_SETTLE_TIME: Duration = Duration::from_millis(250);
+
+/// Name the game logic is reported under in subsystem errors.
+const GAME_SYSTEM_NAME: &str = "game";
+
+/// Game logic loaded from a dynamic library, reloaded when it is rebuilt.
+///
+/// # Example
+///
+/// ```no_run
+/// use app::resources::{GameLibrary, TestApp};
+///
+/// let mut game = GameLibrary::load("target/debug/libmy_game.so")?;
+/// let mut app = TestApp::new()?.add_update(move |world, delta| {
+///     if let Err(e) = game.poll_reload(world) {
+///         tracing::warn!(error = %e, "game reload failed");
+///     }
+///     game.update(world, delta);
+/// });
+/// app.tick_n(600)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct GameLibrary {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    reloads: u64,
+    loaded: Option<Loaded>,
+    /// State saved by logic that was unloaded for a build that failed to
+    /// start or to restore it, kept for the next build.
+    saved: Option<Vec<u8>>,
+}
+
+/// A running [`GameLogic`] and the library holding its code.
+struct Loaded {
+    logic: Box<dyn GameLogic>,
+    /// `None` for logic compiled into the engine, in tests.
+    library: Option<Library>,
+    /// Copy the library was loaded from.
+    copy: Option<PathBuf>,
+}
+
+impl fmt::Debug for GameLibrary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GameLibrary")
+            .field("path", &self.path)
+            .field("modified", &self.modified)
+            .field("reloads", &self.reloads)
+            .field("loaded", &self.loaded.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl GameLibrary {
+    /// Loads the game library at `path` and starts its logic.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the library cannot be copied or loaded, exports
+    /// another [`GAME_ABI_VERSION`], or lacks the [`export_game!`]
+    /// functions, or [`RustgineError::SystemStartupError`] if the logic
+    /// fails to start.
+    ///
+    /// [`export_game!`]: crate::export_game
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, RustgineError> {
+        let path = path.into();
+        let modified = modified(&path);
+        let mut loaded = Loaded::open(&path, 0)?;
+        if let Err(e) = loaded.start() {
+            loaded.close();
+            return Err(e);
+        }
+        info!(path = %path.display(), "game library loaded");
+        Ok(Self {
+            path,
+            modified,
+            reloads: 0,
+            loaded: Some(loaded),
+            saved: None,
+        })
+    }
+
+    /// Starts logic compiled into the engine, watching `path`, for testing
+    /// reloads without a library.
+    #[cfg(test)]
+    pub(crate) fn in_process(
+        path: PathBuf,
+        logic: Box<dyn GameLogic>,
+    ) -> Result<Self, RustgineError> {
+        let mut loaded = Loaded::in_process(logic);
+        loaded.start()?;
+        Ok(Self {
+            modified: modified(&path),
+            path,
+            reloads: 0,
+            loaded: Some(loaded),
+            saved: None,
+        })
+    }
+
+    /// Swaps the running logic for `logic` compiled into the engine, as
+    /// [`reload`](Self::reload) does for a new build.
+    #[cfg(test)]
+    pub(crate) fn reload_in_process(
+        &mut self,
+        world: &mut World,
+        logic: Box<dyn GameLogic>,
+    ) -> Result<(), RustgineError> {
+        self.swap(world, Loaded::in_process(logic))
+    }
+
+    /// Like [`reload_in_process`](Self::reload_in_process), for logic
+    /// loaded from the library copy at `copy`.
+    #[cfg(test)]
+    pub(crate) fn reload_in_process_copied(
+        &mut self,
+        world: &mut World,
+        logic: Box<dyn GameLogic>,
+        copy: PathBuf,
+    ) -> Result<(), RustgineError> {
+        let mut next = Loaded::in_process(logic);
+        next.copy = Some(copy);
+        self.swap(world, next)
+    }
+
+    /// Returns the path of the watched library.
+    #[must_use]
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns how many times the library was reloaded.
+    #[must_use]
+    #[inline]
+    pub fn reloads(&self) -> u64 {
+        self.reloads
+    }
+
+    /// Runs one frame of the game logic.
+    ///
+    /// Does nothing if a failed reload left no logic running.
+    pub fn update(&mut self, world: &mut World, delta: Duration) {
+        if let Some(loaded) = &mut self.loaded {
+            loaded.logic.update(world, delta);
+        }
+    }
+
+    /// Reloads the library if it was rebuilt since it was last loaded, and
+    /// the build finished writing it.
+    ///
+    /// Returns `true` if the library was reloaded.
+    ///
+    /// # Errors
+    ///
+    /// See [`reload`](Self::reload). A failed build is not retried until the
+    /// library changes again.
+    pub fn poll_reload(&mut self, world: &mut World) -> Result<bool, RustgineError> {
+        let modified = modified(&self.path);
+        if modified == self.modified
+            || modified
+                .and_then(|time| time.elapsed().ok())
+                .is_none_or(|age| age < RELOAD_SETTLE_TIME)
+        {
+            return Ok(false);
+        }
+        self.modified = modified;
+        self.reload(world)?;
+        Ok(true)
+    }
+
+    /// Swaps the running logic for the library's current build, moving its
+    /// state across through `world`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new build cannot be loaded or the old logic
+    /// fails to save its state, in which case the old logic keeps running.
+    /// Returns [`RustgineError::SystemStartupError`] if the new logic fails
+    /// to start, leaving no logic running until the next successful reload,
+    /// which gets the saved state instead. Returns an error if the new logic
+    /// fails to restore the saved state; it keeps running, and the next
+    /// reload gets the saved state instead of saving its own.
+    pub fn reload(&mut self, world: &mut World) -> Result<(), RustgineError> {
+        let next = Loaded::open(&self.path, self.reloads + 1)?;
+        self.swap(world, next)
+    }
+
+    /// Replaces the running logic with `next`.
+    fn swap(&mut self, world: &mut World, mut next: Loaded) -> Result<(), RustgineError> {
+        // Logic that never got the saved state has nothing newer to save
+        let state = match (&mut self.loaded, self.saved.take()) {
+            (_, Some(saved)) => saved,
+            (Some(current), None) => match current.logic.save_state(world) {
+                Ok(state) => state,
+                Err(e) => {
+                    warn!(error = %e, "game logic failed to save its state, keeping it loaded");
+                    next.close();
+                    return Err(e);
+                }
+            },
+            (None, None) => Vec::new(),
+        };
+        if let Some(current) = self.loaded.take() {
+            current.unload();
+        }
+
+        self.reloads += 1;
+        if let Err(e) = next.start() {
+            warn!(error = %e, "reloaded game logic failed to start");
+            next.close();
+            self.saved = Some(state);
+            return Err(e);
+        }
+        let loaded = self.loaded.insert(next);
+        if let Err(e) = loaded.logic.restore_state(world, &state) {
+            warn!(error = %e, "reloaded game logic failed to restore the saved state");
+            self.saved = Some(state);
+            return Err(e);
+        }
+        info!(
+            path = %self.path.display(),
+            reloads = self.reloads,
+            state_bytes = state.len(),
+            "game library reloaded"
+        );
+        Ok(())
+    }
+}
+
+impl Drop for GameLibrary {
+    fn drop(&mut self) {
+        if let Some(loaded) = self.loaded.take() {
+            loaded.unload();
+        }
+    }
+}
+
+impl Loaded {
+    /// Copies the library at `path` as build `generation` and loads the
+    /// copy, without starting the logic.
+    fn open(path: &Path, generation: u64) -> Result<Self, RustgineError> {
+        let copy = copy_path(path, generation);
+        if let Some(dir) = copy.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::copy(path, &copy)
+            .map_err(|e| anyhow::anyhow!("failed to copy game library {}: {e}", path.display()))?;
+
+        let opened = open_library(&copy);
+        match opened {
+            Ok((library, logic)) => Ok(Self {
+                logic,
+                library: Some(library),
+                copy: Some(copy),
+            }),
+            Err(e) => {
+                let _ = fs::remove_file(&copy);
+                Err(e)
+            }
+        }
+    }
+
+    /// Wraps logic compiled into the engine.
+    #[cfg(test)]
+    fn in_process(logic: Box<dyn GameLogic>) -> Self {
+        Self {
+            logic,
+            library: None,
+            copy: None,
+        }
+    }
+
+    /// Starts the logic.
+    fn start(&mut self) -> Result<(), RustgineError> {
+        self.logic
+            .startup()
+            .map_err(|e| RustgineError::system_startup(GAME_SYSTEM_NAME, e))
+    }
+
+    /// Shuts the logic down, then [closes](Self::close) it.
+    fn unload(mut self) {
+        if let Err(e) = self.logic.shutdown() {
+            warn!(error = %e, "game logic failed to shut down");
+        }
+        self.close();
+    }
+
+    /// Drops the logic without shutting it down, then unloads its library
+    /// and deletes the copy.
+    fn close(self) {
+        let Self {
+            logic,
+            library,
+            copy,
+        } = self;
+        // The logic's code lives in the library, so it goes first
+        drop(logic);
+        if let Some(library) = library {
+            if let Err(e) = library.close() {
+                warn!(error = %e, "failed to unload game library");
+            }
+        }
+        if let Some(copy) = copy {
+            let _ = fs::remove_file(&copy);
+            // Only succeeds once no other copy is left
+            if let Some(dir) = copy.parent() {
+                let _ = fs::remove_dir(dir);
+            }
+        }
+        debug!("game library unloaded");
+    }
+}
+
+/// Loads the library at `path` and creates its logic.
+fn open_library(path: &Path) -> Result<(Library, Box<dyn GameLogic>), RustgineError> {
+    let display = path.display();
+    // SAFETY: loading runs the library's initializers. Game libraries are
+    // trusted development builds, as documented on the module.
+    let library = unsafe { Library::new(path) }
+        .map_err(|e| anyhow::anyhow!("failed to load game library {display}: {e}"))?;
+
+    // SAFETY: `export_game!` defines the symbol with this signature.
+    let abi: Symbol<'_, extern "C" fn() -> u32> =
+        unsafe { library.get(GAME_ABI_SYMBOL.as_bytes()) }
+            .map_err(|e| anyhow::anyhow!("{display} does not export a game: {e}"))?;
+    let version = abi();
+    if version != GAME_ABI_VERSION {
+        return Err(anyhow::anyhow!(
+            "{display} exports game ABI version {version}, expected {GAME_ABI_VERSION}"
+        )
+        .into());
+    }
+
+    // SAFETY: `export_game!` defines the symbol with this signature, and the
+    // matching ABI version means it was built against this engine.
+    let logic = {
+        let create: Symbol<'_, fn() -> Box<dyn GameLogic>> =
+            unsafe { library.get(GAME_ENTRY_SYMBOL.as_bytes()) }
+                .map_err(|e| anyhow::anyhow!("{display} does not export a game: {e}"))?;
+        create()
+    };
+    Ok((library, logic))
+}
+
+/// Returns where build `generation` of the library at `path` is copied.
+fn copy_path(path: &Path, generation: u64) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map_or_else(|| "game".into(), |stem| stem.to_string_lossy());
+    let mut name = format!("{stem}-{generation}");
+    if let Some(extension) = path.extension() {
+        name.push('.');
+        name.push_str(&extension.to_string_lossy());
+    }
+    std::env::temp_dir()
+        .join(format!("rustgine-hot-reload-{}", std::process::id()))
+        .join(name)
+}
+
+/// Returns the modification time of `path`, or `None` if it cannot be read.
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
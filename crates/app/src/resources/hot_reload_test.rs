@@ -0,0 +1,268 @@
+//! Unit tests for game logic hot reloading.
+
+use super::GameLibrary;
+use crate::resources::GameLogic;
+use ecs::World;
+use rustgine_core::testing::{CallLog, Phase, RecordingSystem};
+use rustgine_core::{RustgineError, RustgineSystem};
+use std::fs::File;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// Score owned by the game library.
+#[derive(Debug, Default)]
+struct Score(u32);
+
+/// Game logic adding `step` to the score every frame.
+#[derive(Debug)]
+struct Counter {
+    step: u32,
+    system: RecordingSystem,
+    fail_save: bool,
+    fail_restore: bool,
+}
+
+impl Counter {
+    fn boxed(step: u32, system: RecordingSystem) -> Box<dyn GameLogic> {
+        Box::new(Self {
+            step,
+            system,
+            fail_save: false,
+            fail_restore: false,
+        })
+    }
+}
+
+impl RustgineSystem for Counter {
+    fn startup(&mut self) -> Result<(), RustgineError> {
+        self.system.startup()
+    }
+
+    fn shutdown(&mut self) -> Result<(), RustgineError> {
+        self.system.shutdown()
+    }
+}
+
+impl GameLogic for Counter {
+    fn update(&mut self, world: &mut World, _delta: Duration) {
+        world.resource_mut::<Score>().unwrap().0 += self.step;
+    }
+
+    fn save_state(&mut self, world: &mut World) -> Result<Vec<u8>, RustgineError> {
+        if self.fail_save {
+            return Err(anyhow::anyhow!("cannot save").into());
+        }
+        let score = world.remove_resource::<Score>().unwrap_or_default();
+        Ok(score.0.to_le_bytes().to_vec())
+    }
+
+    fn restore_state(&mut self, world: &mut World, state: &[u8]) -> Result<(), RustgineError> {
+        if self.fail_restore {
+            return Err(anyhow::anyhow!("cannot restore").into());
+        }
+        let bytes = state.try_into().map_err(|_| anyhow::anyhow!("bad score"))?;
+        world.insert_resource(Score(u32::from_le_bytes(bytes)));
+        Ok(())
+    }
+}
+
+/// Returns a path unique to `name` in the temporary directory.
+fn library_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("rustgine-game-{name}-{}.so", std::process::id()))
+}
+
+fn world_with_score() -> World {
+    let mut world = World::default();
+    world.insert_resource(Score::default());
+    world
+}
+
+/// Verifies a reload shuts the old logic down, starts the new one, and
+/// moves the state across.
+#[test]
+fn reload_moves_state() {
+    let log = CallLog::new();
+    let mut world = world_with_score();
+    let mut game = GameLibrary::in_process(
+        library_path("state"),
+        Counter::boxed(1, RecordingSystem::new("v1", &log)),
+    )
+    .unwrap();
+    for _ in 0..3 {
+        game.update(&mut world, Duration::ZERO);
+    }
+
+    game.reload_in_process(
+        &mut world,
+        Counter::boxed(10, RecordingSystem::new("v2", &log)),
+    )
+    .unwrap();
+    assert_eq!(game.reloads(), 1);
+    assert_eq!(world.resource::<Score>().unwrap().0, 3);
+    game.update(&mut world, Duration::ZERO);
+    assert_eq!(world.resource::<Score>().unwrap().0, 13);
+
+    assert_eq!(log.systems(Phase::Startup), ["v1", "v2"]);
+    assert_eq!(log.systems(Phase::Shutdown), ["v1"]);
+    drop(game);
+    assert_eq!(log.systems(Phase::Shutdown), ["v1", "v2"]);
+}
+
+/// Verifies logic that cannot save its state keeps running.
+#[test]
+fn failed_save_keeps_old_logic() {
+    let log = CallLog::new();
+    let mut world = world_with_score();
+    let v1 = Box::new(Counter {
+        step: 1,
+        system: RecordingSystem::new("v1", &log),
+        fail_save: true,
+        fail_restore: false,
+    });
+    let mut game = GameLibrary::in_process(library_path("save"), v1).unwrap();
+
+    assert!(game
+        .reload_in_process(
+            &mut world,
+            Counter::boxed(10, RecordingSystem::new("v2", &log))
+        )
+        .is_err());
+    assert_eq!(game.reloads(), 0);
+    game.update(&mut world, Duration::ZERO);
+    assert_eq!(world.resource::<Score>().unwrap().0, 1);
+    assert!(log.systems(Phase::Shutdown).is_empty());
+}
+
+/// Verifies a build swapped in for logic that cannot save its state has its
+/// library copy deleted.
+#[test]
+fn failed_save_deletes_new_copy() {
+    let log = CallLog::new();
+    let mut world = world_with_score();
+    let v1 = Box::new(Counter {
+        step: 1,
+        system: RecordingSystem::new("v1", &log),
+        fail_save: true,
+        fail_restore: false,
+    });
+    let mut game = GameLibrary::in_process(library_path("save-copy"), v1).unwrap();
+    let dir = std::env::temp_dir().join(format!("rustgine-game-copy-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let copy = dir.join("game-1.so");
+    File::create(&copy).unwrap();
+
+    assert!(game
+        .reload_in_process_copied(
+            &mut world,
+            Counter::boxed(10, RecordingSystem::new("v2", &log)),
+            copy.clone(),
+        )
+        .is_err());
+    assert!(!copy.exists());
+    assert_eq!(log.systems(Phase::Startup), ["v1"]);
+}
+
+/// Verifies a build that fails to start leaves no logic running, and the
+/// next build gets the saved state.
+#[test]
+fn failed_start_keeps_state_for_next_build() {
+    let log = CallLog::new();
+    let mut world = world_with_score();
+    let mut game = GameLibrary::in_process(
+        library_path("start"),
+        Counter::boxed(1, RecordingSystem::new("v1", &log)),
+    )
+    .unwrap();
+    game.update(&mut world, Duration::ZERO);
+
+    let broken = RecordingSystem::new("v2", &log).failing_on(Phase::Startup);
+    assert!(matches!(
+        game.reload_in_process(&mut world, Counter::boxed(10, broken)),
+        Err(RustgineError::SystemStartupError { .. })
+    ));
+    assert!(world.resource::<Score>().is_none());
+    game.update(&mut world, Duration::ZERO);
+
+    game.reload_in_process(
+        &mut world,
+        Counter::boxed(100, RecordingSystem::new("v3", &log)),
+    )
+    .unwrap();
+    game.update(&mut world, Duration::ZERO);
+    assert_eq!(world.resource::<Score>().unwrap().0, 101);
+}
+
+/// Verifies a build that fails to restore the state keeps running, and the
+/// next build gets the saved state.
+#[test]
+fn failed_restore_keeps_state_for_next_build() {
+    let log = CallLog::new();
+    let mut world = world_with_score();
+    let mut game = GameLibrary::in_process(
+        library_path("restore"),
+        Counter::boxed(1, RecordingSystem::new("v1", &log)),
+    )
+    .unwrap();
+    game.update(&mut world, Duration::ZERO);
+    game.update(&mut world, Duration::ZERO);
+
+    let broken = Box::new(Counter {
+        step: 10,
+        system: RecordingSystem::new("v2", &log),
+        fail_save: false,
+        fail_restore: true,
+    });
+    let error = game.reload_in_process(&mut world, broken).unwrap_err();
+    assert!(error.to_string().contains("cannot restore"), "{error}");
+    assert!(world.resource::<Score>().is_none());
+
+    game.reload_in_process(
+        &mut world,
+        Counter::boxed(100, RecordingSystem::new("v3", &log)),
+    )
+    .unwrap();
+    game.update(&mut world, Duration::ZERO);
+    assert_eq!(world.resource::<Score>().unwrap().0, 102);
+    assert_eq!(log.systems(Phase::Shutdown), ["v1", "v2"]);
+}
+
+/// Verifies a changed file that is not a game library is reported once and
+/// the running logic is kept.
+#[test]
+fn poll_reload_reports_invalid_build_once() {
+    let log = CallLog::new();
+    let path = library_path("poll");
+    std::fs::write(&path, b"first build").unwrap();
+    let mut world = world_with_score();
+    let mut game = GameLibrary::in_process(
+        path.clone(),
+        Counter::boxed(1, RecordingSystem::new("v1", &log)),
+    )
+    .unwrap();
+    assert!(!game.poll_reload(&mut world).unwrap());
+
+    std::fs::write(&path, b"not a library").unwrap();
+    File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(SystemTime::now() - Duration::from_secs(5))
+        .unwrap();
+    let error = game.poll_reload(&mut world).unwrap_err().to_string();
+    assert!(error.contains("failed to load game library"), "{error}");
+    assert!(!game.poll_reload(&mut world).unwrap());
+
+    game.update(&mut world, Duration::ZERO);
+    assert_eq!(world.resource::<Score>().unwrap().0, 1);
+    assert_eq!(game.reloads(), 0);
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// Verifies loading a missing library fails.
+#[test]
+fn load_missing_library_fails() {
+    let error = GameLibrary::load(library_path("missing"))
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("failed to copy game library"), "{error}");
+}
@@ -0,0 +1,159 @@
+//! Main-thread task dispatching.
+//!
+//! Windowing and some GPU operations must be performed on the main thread,
+//! while systems execute on the scheduler's worker pool. The
+//! [`MainThreadQueue`] lets any thread hand closures to the main thread,
+//! which the runtime drains once per frame.
+
+use std::collections::VecDeque;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::{self, ThreadId};
+
+/// A unit of work queued for execution on the main thread.
+type MainThreadTask = Box<dyn FnOnce() + Send + 'static>;
+
+/// Queue of closures to be executed on the main thread.
+///
+/// The queue records the thread it was created on as the main thread.
+/// Tasks may be enqueued from any thread and are executed in FIFO order
+/// when the runtime calls [`drain`](Self::drain).
+///
+/// # Thread Safety
+///
+/// `MainThreadQueue` is [`Clone`] and [`Send`] + [`Sync`]; clones share
+/// the same underlying queue.
+///
+/// # Example
+///
+/// ```
+/// use app::resources::MainThreadQueue;
+///
+/// let queue = MainThreadQueue::new();
+/// queue.enqueue(|| println!("running on the main thread"));
+///
+/// // Typically performed by the runtime once per frame:
+/// assert_eq!(queue.drain(), 1);
+/// ```
+#[derive(Clone)]
+pub struct MainThreadQueue {
+    /// Thread that owns the queue and executes tasks.
+    main_thread: ThreadId,
+
+    /// Pending tasks in submission order.
+    tasks: Arc<Mutex<VecDeque<MainThreadTask>>>,
+}
+
+impl std::fmt::Debug for MainThreadQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MainThreadQueue")
+            .field("main_thread", &self.main_thread)
+            .field("pending", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for MainThreadQueue {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MainThreadQueue {
+    /// Creates a queue bound to the calling thread.
+    ///
+    /// Must be called from the main thread.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            main_thread: thread::current().id(),
+            tasks: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// Returns `true` if the calling thread is the main thread.
+    #[must_use]
+    #[inline]
+    pub fn is_main_thread(&self) -> bool {
+        thread::current().id() == self.main_thread
+    }
+
+    /// Queues `task` for execution during the next [`drain`](Self::drain).
+    pub fn enqueue<F>(&self, task: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.lock_tasks().push_back(Box::new(task));
+    }
+
+    /// Runs `task` on the main thread and blocks until it returns.
+    ///
+    /// Intended for startup paths that need a result from an OS-bound call.
+    /// If called from the main thread, the task runs inline to avoid
+    /// deadlocking the queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the task was dropped without completing, for
+    /// example because it panicked on the main thread.
+    pub fn run_on_main<F, R>(&self, task: F) -> anyhow::Result<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        if self.is_main_thread() {
+            return Ok(task());
+        }
+
+        let (sender, receiver) = mpsc::sync_channel(1);
+        self.enqueue(move || {
+            // Ignore send errors: the caller stopped waiting for the result.
+            let _ = sender.send(task());
+        });
+
+        receiver
+            .recv()
+            .map_err(|_| anyhow::anyhow!("main thread task dropped before completion"))
+    }
+
+    /// Executes all queued tasks on the calling thread.
+    ///
+    /// Tasks enqueued while draining are executed on the next call.
+    ///
+    /// # Returns
+    ///
+    /// The number of tasks executed.
+    #[allow(clippy::must_use_candidate)]
+    pub fn drain(&self) -> usize {
+        let tasks = std::mem::take(&mut *self.lock_tasks());
+        let count = tasks.len();
+        for task in tasks {
+            task();
+        }
+        count
+    }
+
+    /// Returns the number of tasks waiting to run.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.lock_tasks().len()
+    }
+
+    /// Returns `true` if no tasks are waiting to run.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Locks the task queue, recovering from poisoning.
+    ///
+    /// A poisoned lock only means a task panicked while enqueuing; the
+    /// queue itself is always in a consistent state.
+    fn lock_tasks(&self) -> std::sync::MutexGuard<'_, VecDeque<MainThreadTask>> {
+        self.tasks
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
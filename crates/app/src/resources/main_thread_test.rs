@@ -0,0 +1,80 @@
+//! Unit tests for the main-thread task queue.
+
+use super::MainThreadQueue;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Verifies that a new queue is empty and bound to the current thread.
+#[test]
+fn new_queue_is_empty() {
+    let queue = MainThreadQueue::new();
+    assert!(queue.is_empty());
+    assert!(queue.is_main_thread());
+}
+
+/// Verifies that drained tasks run in FIFO order.
+#[test]
+fn drain_runs_tasks_in_order() {
+    let queue = MainThreadQueue::new();
+    let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    for i in 0..3 {
+        let order = Arc::clone(&order);
+        queue.enqueue(move || order.lock().unwrap().push(i));
+    }
+
+    assert_eq!(queue.len(), 3);
+    assert_eq!(queue.drain(), 3);
+    assert!(queue.is_empty());
+    assert_eq!(*order.lock().unwrap(), [0, 1, 2]);
+}
+
+/// Verifies that tasks enqueued from worker threads run on the main thread.
+#[test]
+fn worker_tasks_run_on_main_thread() {
+    let queue = MainThreadQueue::new();
+    let main_id = thread::current().id();
+    let ran_on_main = Arc::new(AtomicUsize::new(0));
+
+    let worker_queue = queue.clone();
+    let flag = Arc::clone(&ran_on_main);
+    thread::spawn(move || {
+        assert!(!worker_queue.is_main_thread());
+        worker_queue.enqueue(move || {
+            if thread::current().id() == main_id {
+                flag.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+    })
+    .join()
+    .unwrap();
+
+    queue.drain();
+    assert_eq!(ran_on_main.load(Ordering::SeqCst), 1);
+}
+
+/// Verifies that `run_on_main` blocks a worker until the main thread drains.
+#[test]
+fn run_on_main_returns_result_to_worker() {
+    let queue = MainThreadQueue::new();
+    let worker_queue = queue.clone();
+
+    let worker = thread::spawn(move || worker_queue.run_on_main(|| 6 * 7));
+
+    while queue.is_empty() {
+        thread::sleep(Duration::from_millis(1));
+    }
+    queue.drain();
+
+    assert_eq!(worker.join().unwrap().unwrap(), 42);
+}
+
+/// Verifies that `run_on_main` executes inline when already on the main thread.
+#[test]
+fn run_on_main_inline_on_main_thread() {
+    let queue = MainThreadQueue::new();
+    assert_eq!(queue.run_on_main(|| "inline").unwrap(), "inline");
+    assert!(queue.is_empty());
+}
@@ -0,0 +1,189 @@
+//! Engine metrics collection and export.
+//!
+//! The runtime records frame metrics into [`AppState::metrics`] every frame.
+//! They can be exported two ways, both safe to enable in production:
+//!
+//! - A read-only HTTP endpoint serving `GET /metrics` for Prometheus to
+//!   scrape ([`Config::metrics_addr`](rustgine_core::Config::metrics_addr)),
+//!   along with the [`health`](crate::resources::Health) probes
+//! - A file rewritten periodically, for node-exporter's textfile collector
+//!   or log shipping ([`Config::metrics_file`](rustgine_core::Config::metrics_file))
+//!
+//! Neither exporter is available on `wasm32`, where there are no sockets or
+//! files; the metrics are still recorded and readable in-process.
+
+use crate::resources::AppState;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::resources::{health_routes, Health, Shutdown};
+#[cfg(not(target_arch = "wasm32"))]
+use axum::{routing::get, Router};
+use rustgine_core::metrics::{Counter, Gauge, Histogram, Metrics, FRAME_TIME_BUCKETS};
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::{net::TcpListener, task::JoinHandle};
+#[cfg(not(target_arch = "wasm32"))]
+use tracing::{info, warn};
+
+/// Interval between metrics file dumps.
+pub const METRICS_DUMP_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Metrics the runtime updates every frame.
+#[derive(Debug, Clone)]
+pub struct EngineMetrics {
+    frames: Counter,
+    frame_time: Histogram,
+    fps: Gauge,
+    systems: Gauge,
+    #[cfg(feature = "render")]
+    draw_calls: Gauge,
+    #[cfg(feature = "render")]
+    triangles: Gauge,
+    #[cfg(feature = "render")]
+    pipeline_switches: Gauge,
+    #[cfg(feature = "render")]
+    ui_draw_calls: Gauge,
+    #[cfg(feature = "render")]
+    bind_group_hit_rate: Gauge,
+    #[cfg(feature = "render")]
+    gpu_memory: Gauge,
+}
+
+impl EngineMetrics {
+    /// Registers the engine metrics on `metrics`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if one of the names is already registered with
+    /// another metric type.
+    pub fn register(metrics: &Metrics) -> anyhow::Result<Self> {
+        Ok(Self {
+            frames: metrics.counter("rustgine_frames_total", "Frames ticked by the main loop")?,
+            frame_time: metrics.histogram(
+                "rustgine_frame_time_seconds",
+                "Time between main loop frames",
+                FRAME_TIME_BUCKETS,
+            )?,
+            fps: metrics.gauge("rustgine_fps", "Frames per second over the last second")?,
+            systems: metrics.gauge("rustgine_systems", "Registered engine subsystems")?,
+            #[cfg(feature = "render")]
+            draw_calls: metrics
+                .gauge("rustgine_render_draw_calls", "Draw calls in the last frame")?,
+            #[cfg(feature = "render")]
+            triangles: metrics.gauge(
+                "rustgine_render_triangles",
+                "Triangles submitted in the last frame",
+            )?,
+            #[cfg(feature = "render")]
+            pipeline_switches: metrics.gauge(
+                "rustgine_render_pipeline_switches",
+                "Pipeline switches in the last frame",
+            )?,
+            #[cfg(feature = "render")]
+            ui_draw_calls: metrics.gauge(
+                "rustgine_render_ui_draw_calls",
+                "UI draw calls in the last frame",
+            )?,
+            #[cfg(feature = "render")]
+            bind_group_hit_rate: metrics.gauge(
+                "rustgine_render_bind_group_hit_rate",
+                "Fraction of bind group lookups answered from the cache in the last frame",
+            )?,
+            #[cfg(feature = "render")]
+            gpu_memory: metrics.gauge(
+                "rustgine_render_gpu_memory_bytes",
+                "GPU buffer and texture memory allocated",
+            )?,
+        })
+    }
+
+    /// Records one frame.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn record_frame(&self, state: &AppState, frame_time: Duration) {
+        self.frames.inc();
+        self.frame_time.observe(frame_time.as_secs_f64());
+        self.fps.set(state.frame_stats.fps());
+        self.systems.set(state.system_count() as f64);
+        #[cfg(feature = "render")]
+        {
+            let render = state.frame_stats.render_stats();
+            self.draw_calls.set(render.draw_calls as f64);
+            self.triangles.set(render.triangles as f64);
+            self.pipeline_switches.set(render.pipeline_switches as f64);
+            self.ui_draw_calls.set(render.ui_draw_calls as f64);
+            self.bind_group_hit_rate.set(render.bind_group_hit_rate());
+            self.gpu_memory.set(render.memory_bytes() as f64);
+        }
+    }
+}
+
+/// Starts a read-only HTTP server answering `GET /metrics`, and the
+/// `/healthz` and `/readyz` probes from `health`.
+///
+/// The server stops when `shutdown` fires.
+///
+/// # Returns
+///
+/// The bound address (useful when `addr` uses port 0) and the server task.
+///
+/// # Errors
+///
+/// Returns an error if the address cannot be bound.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn spawn_metrics_server(
+    metrics: Metrics,
+    health: Arc<Health>,
+    addr: SocketAddr,
+    shutdown: &Shutdown,
+) -> anyhow::Result<(SocketAddr, JoinHandle<()>)> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to bind metrics endpoint on {addr}: {e}"))?;
+    let local_addr = listener.local_addr()?;
+
+    let mut shutdown = shutdown.subscribe();
+    let router = Router::new()
+        .route("/metrics", get(move || async move { metrics.render() }))
+        .merge(health_routes(health));
+    let task = tokio::spawn(async move {
+        let server = axum::serve(listener, router).with_graceful_shutdown(async move {
+            shutdown.recv().await;
+        });
+        if let Err(e) = server.await {
+            warn!(error = %e, "metrics endpoint failed");
+        }
+    });
+
+    info!(addr = %local_addr, "metrics endpoint listening");
+    Ok((local_addr, task))
+}
+
+/// Rewrites `path` with the current metrics every `interval` until
+/// `shutdown` fires, then writes a final dump.
+#[cfg(not(target_arch = "wasm32"))]
+#[allow(clippy::must_use_candidate)]
+pub fn spawn_metrics_dump(
+    metrics: Metrics,
+    path: PathBuf,
+    interval: Duration,
+    shutdown: &Shutdown,
+) -> JoinHandle<()> {
+    let mut shutdown = shutdown.subscribe();
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            tokio::select! {
+                () = shutdown.recv() => break,
+                _ = ticker.tick() => {
+                    if let Err(e) = metrics.write_to_file(&path) {
+                        warn!(error = %e, "failed to dump metrics");
+                    }
+                }
+            }
+        }
+        if let Err(e) = metrics.write_to_file(&path) {
+            warn!(error = %e, "failed to dump metrics");
+        }
+    })
+}
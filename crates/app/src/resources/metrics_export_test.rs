@@ -0,0 +1,147 @@
+//! Unit tests for metrics collection and export.
+
+use crate::resources::{
+    spawn_metrics_dump, spawn_metrics_server, AppState, EngineMetrics, Health, Shutdown,
+};
+#[cfg(feature = "render")]
+use render::RenderStats;
+use rustgine_core::{Config, Metrics};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Verifies that frame metrics are recorded.
+#[test]
+fn engine_metrics_record_frames() {
+    let state = AppState::initialize(&Config::default()).unwrap();
+    let engine = EngineMetrics::register(&state.metrics).unwrap();
+    #[cfg(feature = "render")]
+    state.frame_stats.record_render_stats(RenderStats {
+        draw_calls: 12,
+        ui_draw_calls: 2,
+        bind_group_hits: 3,
+        bind_group_misses: 1,
+        texture_bytes: 2048,
+        ..RenderStats::default()
+    });
+    engine.record_frame(&state, Duration::from_millis(16));
+    engine.record_frame(&state, Duration::from_millis(20));
+
+    let text = state.metrics.render();
+    assert!(text.contains("rustgine_frames_total 2"), "{text}");
+    assert!(
+        text.contains("rustgine_frame_time_seconds_count 2"),
+        "{text}"
+    );
+    assert!(
+        text.contains("rustgine_frame_time_seconds_bucket{le=\"0.016\"} 1"),
+        "{text}"
+    );
+    assert!(text.contains("rustgine_systems 0"), "{text}");
+    if cfg!(feature = "render") {
+        assert!(text.contains("rustgine_render_draw_calls 12"), "{text}");
+        assert!(text.contains("rustgine_render_ui_draw_calls 2"), "{text}");
+        assert!(
+            text.contains("rustgine_render_bind_group_hit_rate 0.75"),
+            "{text}"
+        );
+        assert!(
+            text.contains("rustgine_render_gpu_memory_bytes 2048"),
+            "{text}"
+        );
+    } else {
+        assert!(!text.contains("rustgine_render"), "{text}");
+    }
+}
+
+/// Sends `GET path` and returns the whole response.
+async fn get(addr: SocketAddr, path: &str) -> String {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let request = format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+/// Verifies the scrape endpoint.
+#[tokio::test]
+async fn metrics_endpoint_serves_text() {
+    let metrics = Metrics::new();
+    metrics
+        .counter("rustgine_frames_total", "Frames")
+        .unwrap()
+        .add(5);
+    let shutdown = Shutdown::new();
+    let health = Arc::new(Health::default());
+    let (addr, server) =
+        spawn_metrics_server(metrics, health, "127.0.0.1:0".parse().unwrap(), &shutdown)
+            .await
+            .unwrap();
+
+    let response = get(addr, "/metrics").await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    assert!(
+        response.ends_with("rustgine_frames_total 5\n"),
+        "{response}"
+    );
+
+    shutdown.trigger();
+    server.await.unwrap();
+}
+
+/// Verifies the probes report readiness and frame loop stalls.
+#[tokio::test]
+async fn metrics_endpoint_serves_probes() {
+    let shutdown = Shutdown::new();
+    let health = Arc::new(Health::new(Duration::from_millis(50)));
+    let (addr, server) = spawn_metrics_server(
+        Metrics::new(),
+        Arc::clone(&health),
+        "127.0.0.1:0".parse().unwrap(),
+        &shutdown,
+    )
+    .await
+    .unwrap();
+
+    let response = get(addr, "/readyz").await;
+    assert!(response.starts_with("HTTP/1.1 503"), "{response}");
+    let response = get(addr, "/healthz").await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    assert!(response.ends_with(r#"{"status":"starting"}"#), "{response}");
+
+    health.set_ready(true);
+    health.record_frame(Instant::now());
+    let response = get(addr, "/readyz").await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    let response = get(addr, "/healthz").await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+
+    health.record_frame(Instant::now().checked_sub(Duration::from_secs(1)).unwrap());
+    let response = get(addr, "/healthz").await;
+    assert!(response.starts_with("HTTP/1.1 503"), "{response}");
+    assert!(response.contains(r#""status":"stalled""#), "{response}");
+
+    shutdown.trigger();
+    server.await.unwrap();
+}
+
+/// Verifies that the dump task writes a final snapshot on shutdown.
+#[tokio::test]
+async fn metrics_dump_writes_file() {
+    let path = std::env::temp_dir().join(format!("rustgine-dump-{}.prom", std::process::id()));
+    let metrics = Metrics::new();
+    let gauge = metrics.gauge("rustgine_entities", "Live entities").unwrap();
+    let shutdown = Shutdown::new();
+    let task = spawn_metrics_dump(metrics, path.clone(), Duration::from_hours(1), &shutdown);
+
+    gauge.set(42.0);
+    shutdown.trigger();
+    task.await.unwrap();
+    assert!(std::fs::read_to_string(&path)
+        .unwrap()
+        .ends_with("rustgine_entities 42\n"));
+    std::fs::remove_file(path).unwrap();
+}
@@ -3,15 +3,84 @@
 //! This module contains the core building blocks for the application:
 //!
 //! - [`AppState`] - Global state container for configuration and subsystems
+//! - [`AppBuilder`] - Creates the state and registers the default subsystems
 //! - [`Shutdown`] - Graceful shutdown signal broadcaster
-//! - [`run`] - Main event loop execution
+//! - [`AppEvents`] - Engine lifecycle events, such as registry recovery
+//! - [`MainThreadQueue`] - Closures dispatched to the main thread each frame
+//! - [`FrameStats`] - Frame counter and frame rate
+//! - [`Health`] - Readiness and liveness for `/readyz` and `/healthz` probes
+//! - [`Watchdog`] - Reports a main loop that stops completing frames
+//! - `spawn_remote_server` - Development-only HTTP remote control (`devui`
+//!   feature, not on `wasm32`)
+//! - [`EngineMetrics`] - Per-frame metrics and their exporters
+//! - [`dump_schedule_format`] - The `--dump-schedule` command-line flag
+//! - [`run`] - Main event loop execution, driven by `requestAnimationFrame`
+//!   on `wasm32` (`wasm` feature)
+//! - [`TestApp`] - Headless engine stepped frame by frame in tests
+//! - [`GameLogic`] - Game systems loadable from a dynamic library, and
+//!   `GameLibrary` hot-reloading them (`hot-reload` feature)
 
+mod builder;
+#[cfg(test)]
+mod builder_test;
+mod events;
+mod frame_stats;
+mod game_logic;
+mod health;
+#[cfg(test)]
+mod health_test;
+#[cfg(feature = "hot-reload")]
+mod hot_reload;
+#[cfg(all(test, feature = "hot-reload"))]
+mod hot_reload_test;
+mod main_thread;
+#[cfg(test)]
+mod main_thread_test;
+mod metrics_export;
+#[cfg(test)]
+mod metrics_export_test;
+#[cfg(all(feature = "devui", not(target_arch = "wasm32")))]
+mod remote;
+#[cfg(all(test, feature = "devui", feature = "render"))]
+mod remote_test;
 mod runtime;
+#[cfg(test)]
+mod runtime_test;
+mod schedule_dump;
+#[cfg(test)]
+mod schedule_dump_test;
 mod shutdown;
 #[cfg(test)]
 mod shutdown_test;
 mod state;
+#[cfg(test)]
+mod state_test;
+mod test_app;
+#[cfg(test)]
+mod test_app_test;
+mod watchdog;
+#[cfg(test)]
+mod watchdog_test;
 
+pub use builder::AppBuilder;
+pub use events::{AppEvent, AppEvents};
+pub use frame_stats::FrameStats;
+pub use game_logic::{GameLogic, GAME_ABI_SYMBOL, GAME_ABI_VERSION, GAME_ENTRY_SYMBOL};
+#[cfg(not(target_arch = "wasm32"))]
+pub use health::health_routes;
+pub use health::{Health, DEFAULT_STALL_BUDGET};
+#[cfg(feature = "hot-reload")]
+pub use hot_reload::GameLibrary;
+pub use main_thread::MainThreadQueue;
+#[cfg(not(target_arch = "wasm32"))]
+pub use metrics_export::{spawn_metrics_dump, spawn_metrics_server};
+pub use metrics_export::{EngineMetrics, METRICS_DUMP_INTERVAL};
+#[cfg(all(feature = "devui", not(target_arch = "wasm32")))]
+pub use remote::{remote_token, spawn_remote_server, REMOTE_TOKEN_SECRET};
+#[cfg(any(not(target_arch = "wasm32"), feature = "wasm"))]
 pub use runtime::run;
+pub use schedule_dump::{dump_schedule_format, DUMP_SCHEDULE_FLAG};
 pub use shutdown::{Shutdown, ShutdownRx};
 pub use state::AppState;
+pub use test_app::TestApp;
+pub use watchdog::{FrameStage, HangReport, Heartbeat, Watchdog};
@@ -0,0 +1,323 @@
+//! Remote control and telemetry server for development builds.
+//!
+//! Exposes engine status, the developer console, and console variables over
+//! HTTP so external tools and automated tests can drive a running engine:
+//!
+//! | Method | Path | Body | Response |
+//! |--------|------|------|----------|
+//! | `GET` | `/status` | | Build, frame rate, entity count, GPU pass times, asset memory, uptime, and subsystems |
+//! | `POST` | `/console` | Command line | `{"output": ...}` |
+//! | `GET` | `/cvars` | | All cvars with values and defaults |
+//! | `PUT` | `/cvars/{name}` | New value | `{"name": ..., "value": ...}` |
+//! | `GET` | `/metrics` | | Metrics in the Prometheus text format |
+//! | `GET` | `/healthz`, `/readyz` | | [Health](crate::resources::Health) probes |
+//!
+//! Errors are returned as `{"error": ...}` with a 4xx status.
+//!
+//! [`run`](crate::resources::run) only starts the server when
+//! [`Config::remote_addr`](rustgine_core::Config::remote_addr) is set in a
+//! development environment, and only on a loopback address unless
+//! [`Config::remote_allow_public`](rustgine_core::Config::remote_allow_public)
+//! is set. Every request but the health probes must carry
+//! `Authorization: Bearer <token>`, where the token is the
+//! [`REMOTE_TOKEN_SECRET`] secret, or a random one logged at startup. So
+//! that web pages open in the developer's browser cannot reach the server
+//! through DNS rebinding or cross-site requests, requests whose `Host` or
+//! `Origin` names another address are refused.
+
+use crate::resources::{health_routes, AppState};
+use assets::AssetMemoryReport;
+use axum::extract::{Path, Request, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post, put};
+use axum::{Json, Router};
+#[cfg(feature = "render")]
+use render::RenderStats;
+use rustgine_core::console::ArgValue;
+use rustgine_core::{BuildInfo, Label, Secret};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// Name of the [secret](rustgine_core::Config::secrets) holding the
+/// bearer token of the remote control server.
+pub const REMOTE_TOKEN_SECRET: &str = "remote_token";
+
+/// Handler result: JSON on success, status and message on failure.
+type ApiResult = Result<Json<Value>, (StatusCode, Json<Value>)>;
+
+/// Engine status reported by `GET /status`.
+#[derive(Debug, Serialize)]
+struct Status {
+    environment: String,
+    build: BuildInfo,
+    uptime_secs: f64,
+    frames: u64,
+    fps: f64,
+    entities: usize,
+    #[cfg(feature = "render")]
+    gpu_ms: Option<f64>,
+    #[cfg(feature = "render")]
+    gpu_passes: Vec<PassStatus>,
+    #[cfg(feature = "render")]
+    render: RenderStats,
+    #[cfg(feature = "render")]
+    rendering_paused: bool,
+    assets: AssetMemoryReport,
+    systems: Vec<SystemStatus>,
+}
+
+/// GPU time of one render pass in the status report.
+#[derive(Debug, Serialize)]
+struct PassStatus {
+    name: String,
+    gpu_ms: f64,
+}
+
+/// One subsystem in the status report.
+#[derive(Debug, Serialize)]
+struct SystemStatus {
+    name: Label,
+    enabled: bool,
+}
+
+/// Who may use the remote control server.
+#[derive(Debug)]
+struct Access {
+    /// The bearer token requests must carry.
+    token: Secret,
+    /// The address the server is bound to.
+    addr: SocketAddr,
+}
+
+/// Returns the remote control token from `config`'s
+/// [`REMOTE_TOKEN_SECRET`], or a random one for this session, which is
+/// logged so the developer can copy it, if it is not set.
+///
+/// # Errors
+///
+/// Returns an error if the operating system provides no randomness.
+pub fn remote_token(config: &rustgine_core::Config) -> anyhow::Result<Secret> {
+    if let Some(token) = config.secret(REMOTE_TOKEN_SECRET) {
+        return Ok(token.clone());
+    }
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes).map_err(|e| anyhow::anyhow!("failed to generate token: {e}"))?;
+    let token = format!("{:032x}", u128::from_le_bytes(bytes));
+    info!(token = %token, "remote control token for this session");
+    Ok(Secret::new(token))
+}
+
+/// Starts the remote control server on `addr`, accepting requests that
+/// carry `token`.
+///
+/// The server stops when the application shutdown signal fires.
+///
+/// # Returns
+///
+/// The bound address (useful when `addr` uses port 0) and the server task.
+///
+/// # Errors
+///
+/// Returns an error if the address cannot be bound.
+pub async fn spawn_remote_server(
+    state: Arc<AppState>,
+    addr: SocketAddr,
+    token: Secret,
+) -> anyhow::Result<(SocketAddr, JoinHandle<()>)> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to bind remote control server on {addr}: {e}"))?;
+    let local_addr = listener.local_addr()?;
+
+    let mut shutdown = state.shutdown.subscribe();
+    let access = Arc::new(Access {
+        token,
+        addr: local_addr,
+    });
+    let router = router(Arc::clone(&state))
+        .layer(middleware::from_fn_with_state(access, check_access))
+        .merge(health_routes(Arc::clone(&state.health)));
+    let task = tokio::spawn(async move {
+        let server = axum::serve(listener, router).with_graceful_shutdown(async move {
+            shutdown.recv().await;
+        });
+        if let Err(e) = server.await {
+            warn!(error = %e, "remote control server failed");
+        }
+    });
+
+    info!(addr = %local_addr, "remote control server listening");
+    Ok((local_addr, task))
+}
+
+/// Builds the HTTP routes.
+fn router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/status", get(status))
+        .route("/console", post(console))
+        .route("/cvars", get(cvars))
+        .route("/cvars/{name}", put(set_cvar))
+        .route("/metrics", get(metrics))
+        .with_state(state)
+}
+
+/// Refuses requests without the bearer token or from another site.
+async fn check_access(State(access): State<Arc<Access>>, request: Request, next: Next) -> Response {
+    let headers = request.headers();
+    if !host_allowed(headers, access.addr) {
+        return error(StatusCode::FORBIDDEN, "unexpected host or origin").into_response();
+    }
+    let authorized = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| constant_time_eq(token.as_bytes(), access.token.expose().as_bytes()));
+    if !authorized {
+        return error(StatusCode::UNAUTHORIZED, "missing or invalid bearer token").into_response();
+    }
+    next.run(request).await
+}
+
+/// Returns `true` if the `Host` and any `Origin` of a request name the
+/// address the server is bound to.
+///
+/// Loopback addresses also answer to `localhost`; servers bound to every
+/// interface only check the port, since their address is not known.
+fn host_allowed(headers: &HeaderMap, addr: SocketAddr) -> bool {
+    let names_server = |authority: &str| {
+        let Some((host, port)) = authority.rsplit_once(':') else {
+            return false;
+        };
+        if port.parse() != Ok(addr.port()) {
+            return false;
+        }
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        addr.ip().is_unspecified()
+            || host.parse() == Ok(addr.ip())
+            || (addr.ip().is_loopback() && host.eq_ignore_ascii_case("localhost"))
+    };
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+    let host_ok = header(header::HOST).is_some_and(names_server);
+    let origin_ok = header(header::ORIGIN)
+        .is_none_or(|origin| origin.strip_prefix("http://").is_some_and(names_server));
+    host_ok && origin_ok
+}
+
+/// Compares two byte strings in time independent of where they differ.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// `GET /status`
+async fn status(State(state): State<Arc<AppState>>) -> ApiResult {
+    let systems = state
+        .systems()
+        .iter()
+        .map(|system| SystemStatus {
+            name: system.name,
+            enabled: system.enabled,
+        })
+        .collect();
+
+    let status = Status {
+        environment: state.config.environment.clone(),
+        build: state.build,
+        uptime_secs: state.frame_stats.uptime().as_secs_f64(),
+        frames: state.frame_stats.frames(),
+        fps: state.frame_stats.fps(),
+        entities: state.frame_stats.entities(),
+        #[cfg(feature = "render")]
+        gpu_ms: state.frame_stats.gpu_ms(),
+        #[cfg(feature = "render")]
+        gpu_passes: state
+            .frame_stats
+            .gpu_timings()
+            .into_iter()
+            .map(|pass| PassStatus {
+                name: pass.name,
+                gpu_ms: pass.gpu_ms,
+            })
+            .collect(),
+        #[cfg(feature = "render")]
+        render: state.frame_stats.render_stats(),
+        #[cfg(feature = "render")]
+        rendering_paused: state.rendering_paused.is_paused(),
+        assets: state.assets().report(),
+        systems,
+    };
+    Ok(Json(json!(status)))
+}
+
+/// `POST /console`
+async fn console(State(state): State<Arc<AppState>>, line: String) -> ApiResult {
+    state
+        .console
+        .execute(&line)
+        .map(|output| Json(json!({ "output": output })))
+        .map_err(|e| error(StatusCode::BAD_REQUEST, &e.to_string()))
+}
+
+/// `GET /cvars`
+async fn cvars(State(state): State<Arc<AppState>>) -> ApiResult {
+    let list: Vec<Value> = state
+        .cvars
+        .list()
+        .into_iter()
+        .map(|info| {
+            json!({
+                "name": info.name,
+                "description": info.description,
+                "value": to_json(&info.value),
+                "default": to_json(&info.default),
+            })
+        })
+        .collect();
+    Ok(Json(Value::Array(list)))
+}
+
+/// `PUT /cvars/{name}`
+async fn set_cvar(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    value: String,
+) -> ApiResult {
+    if state.cvars.get(&name).is_none() {
+        return Err(error(
+            StatusCode::NOT_FOUND,
+            &format!("unknown cvar `{name}`"),
+        ));
+    }
+    state
+        .cvars
+        .set(&name, value.trim())
+        .map_err(|e| error(StatusCode::BAD_REQUEST, &e.to_string()))?;
+    let value = state.cvars.get(&name).map_or(Value::Null, |v| to_json(&v));
+    Ok(Json(json!({ "name": name, "value": value })))
+}
+
+/// `GET /metrics`
+async fn metrics(State(state): State<Arc<AppState>>) -> String {
+    state.metrics.render()
+}
+
+/// Builds an error response.
+fn error(status: StatusCode, message: &str) -> (StatusCode, Json<Value>) {
+    (status, Json(json!({ "error": message })))
+}
+
+/// Converts a cvar value to JSON, keeping its type.
+fn to_json(value: &ArgValue) -> Value {
+    match value {
+        ArgValue::Int(value) => json!(value),
+        ArgValue::Float(value) => json!(value),
+        ArgValue::Bool(value) => json!(value),
+        ArgValue::Str(value) => json!(value),
+    }
+}
@@ -0,0 +1,183 @@
+//! Unit tests for the remote control server.
+
+use crate::resources::{remote_token, spawn_remote_server, AppState, REMOTE_TOKEN_SECRET};
+use render::{PassTiming, RenderStats};
+use rustgine_core::{Config, Secret};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::task::JoinHandle;
+
+/// Token the test servers accept.
+const TOKEN: &str = "test-token";
+
+/// Sends one authorized HTTP request and returns the status code and body.
+async fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, String) {
+    let headers = format!(
+        "Host: localhost:{}\r\nAuthorization: Bearer {TOKEN}\r\n",
+        addr.port()
+    );
+    raw_request(addr, method, path, &headers, body).await
+}
+
+/// Sends one HTTP request with `headers` and returns the status code and
+/// body.
+async fn raw_request(
+    addr: SocketAddr,
+    method: &str,
+    path: &str,
+    headers: &str,
+    body: &str,
+) -> (u16, String) {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let request = format!(
+        "{method} {path} HTTP/1.1\r\n{headers}Connection: close\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    let status = response[9..12].parse().unwrap();
+    let body = response
+        .split_once("\r\n\r\n")
+        .map(|(_, body)| body.to_owned())
+        .unwrap_or_default();
+    (status, body)
+}
+
+/// Starts a server for `state` on a free loopback port.
+async fn spawn(state: &Arc<AppState>) -> (SocketAddr, JoinHandle<()>) {
+    let token = Secret::new(TOKEN.to_owned());
+    spawn_remote_server(Arc::clone(state), "127.0.0.1:0".parse().unwrap(), token)
+        .await
+        .unwrap()
+}
+
+/// Verifies status, console, and cvar endpoints.
+#[tokio::test]
+async fn remote_endpoints() {
+    let state = AppState::initialize(&Config::default()).unwrap();
+    let vsync = state
+        .cvars
+        .register("r.vsync", true, "Wait for vblank")
+        .unwrap();
+    state.frame_stats.record_gpu_timings(&[PassTiming {
+        name: "main".to_owned(),
+        gpu_ms: 4.5,
+    }]);
+    state.frame_stats.record_render_stats(RenderStats {
+        draw_calls: 3,
+        ..RenderStats::default()
+    });
+    let _intro = state.assets().insert("intro.txt", String::from("hello"));
+    state.frame_stats.record_entities(12);
+    let (addr, server) = spawn(&state).await;
+
+    let (code, body) = request(addr, "GET", "/status", "").await;
+    assert_eq!(code, 200);
+    assert!(body.contains("\"environment\":\"development\""), "{body}");
+    assert!(body.contains("\"entities\":12"), "{body}");
+    assert!(body.contains("\"systems\":[]"), "{body}");
+    assert!(
+        body.contains(&format!("\"version\":\"{}\"", state.build.version)),
+        "{body}"
+    );
+    assert!(
+        body.contains("\"gpu_passes\":[{\"gpu_ms\":4.5,\"name\":\"main\"}]"),
+        "{body}"
+    );
+    assert!(body.contains("\"draw_calls\":3"), "{body}");
+    assert!(
+        body.contains("\"types\":[{\"assets\":1,\"bytes\":5,\"kind\":\"text\",\"strong\":1}]"),
+        "{body}"
+    );
+
+    let (code, body) = request(addr, "POST", "/console", "assets").await;
+    assert_eq!(code, 200);
+    assert!(
+        body.contains("text       5 B (1 assets, 1 strong)"),
+        "{body}"
+    );
+
+    let (code, body) = request(addr, "POST", "/console", "r.vsync off").await;
+    assert_eq!(code, 200);
+    assert_eq!(body, r#"{"output":"r.vsync = false"}"#);
+    assert!(!vsync.get());
+
+    let (code, body) = request(addr, "POST", "/console", "bogus").await;
+    assert_eq!(code, 400);
+    assert!(body.contains("unknown command"), "{body}");
+
+    let (code, body) = request(addr, "PUT", "/cvars/r.vsync", "on").await;
+    assert_eq!(code, 200);
+    assert_eq!(body, r#"{"name":"r.vsync","value":true}"#);
+
+    let (code, _) = request(addr, "PUT", "/cvars/r.missing", "1").await;
+    assert_eq!(code, 404);
+
+    let (code, body) = request(addr, "GET", "/cvars", "").await;
+    assert_eq!(code, 200);
+    assert!(body.contains(r#""name":"r.vsync""#), "{body}");
+
+    let (code, _) = request(addr, "GET", "/readyz", "").await;
+    assert_eq!(code, 503);
+    state.health.set_ready(true);
+    let (code, body) = request(addr, "GET", "/readyz", "").await;
+    assert_eq!(code, 200);
+    assert_eq!(body, r#"{"status":"ready"}"#);
+    let (code, _) = request(addr, "GET", "/healthz", "").await;
+    assert_eq!(code, 200);
+
+    state.shutdown.trigger();
+    server.await.unwrap();
+}
+
+/// Verifies requests without the token, or naming another host or origin
+/// as DNS rebinding and cross-site requests do, are refused, while health
+/// probes stay open.
+#[tokio::test]
+async fn refuses_unauthorized_requests() {
+    let state = AppState::initialize(&Config::default()).unwrap();
+    let (addr, server) = spawn(&state).await;
+    let host = format!("Host: 127.0.0.1:{}\r\n", addr.port());
+    let auth = format!("Authorization: Bearer {TOKEN}\r\n");
+
+    let (code, _) = raw_request(addr, "POST", "/console", &host, "quit").await;
+    assert_eq!(code, 401);
+    let wrong = format!("{host}Authorization: Bearer wrong\r\n");
+    let (code, _) = raw_request(addr, "POST", "/console", &wrong, "quit").await;
+    assert_eq!(code, 401);
+
+    let rebound = format!("Host: evil.example:{}\r\n{auth}", addr.port());
+    let (code, _) = raw_request(addr, "GET", "/status", &rebound, "").await;
+    assert_eq!(code, 403);
+    let cross_site = format!("{host}Origin: http://evil.example\r\n{auth}");
+    let (code, _) = raw_request(addr, "PUT", "/cvars/r.vsync", &cross_site, "off").await;
+    assert_eq!(code, 403);
+    let same_site = format!("{host}Origin: http://localhost:{}\r\n{auth}", addr.port());
+    let (code, _) = raw_request(addr, "GET", "/status", &same_site, "").await;
+    assert_eq!(code, 200);
+
+    let (code, _) = raw_request(addr, "GET", "/healthz", &host, "").await;
+    assert_eq!(code, 200);
+
+    state.shutdown.trigger();
+    server.await.unwrap();
+}
+
+/// Verifies the token is read from the config's secret, or generated.
+#[test]
+fn token_comes_from_config() {
+    let mut config = Config::default();
+    let generated = remote_token(&config).unwrap();
+    assert_eq!(generated.expose().len(), 32);
+    assert_ne!(generated.expose(), remote_token(&config).unwrap().expose());
+
+    config.secrets.insert(
+        REMOTE_TOKEN_SECRET.to_owned(),
+        Secret::new(TOKEN.to_owned()),
+    );
+    assert_eq!(remote_token(&config).unwrap().expose(), TOKEN);
+}
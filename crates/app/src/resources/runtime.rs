@@ -2,18 +2,44 @@
 //!
 //! Provides the main execution loop that coordinates all engine subsystems
 //! and handles graceful shutdown on OS signals.
+//!
+//! Native builds tick frames on a timer. In the browser (`wasm32` with the
+//! `wasm` feature) frames are driven by `requestAnimationFrame` instead, and
+//! the HTTP servers, OS signal handling, and watchdog thread are unavailable.
 
-use crate::resources::AppState;
+#[cfg(all(feature = "devui", not(target_arch = "wasm32")))]
+use crate::resources::{remote_token, spawn_remote_server};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::resources::{spawn_metrics_dump, spawn_metrics_server, Watchdog, METRICS_DUMP_INTERVAL};
+use crate::resources::{AppState, EngineMetrics, FrameStage};
+use rustgine_core::RustgineError;
 use std::sync::Arc;
-use tracing::{debug, warn};
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+use tracing::{debug, trace, warn};
+use web_time::Instant;
+
+/// Interval between main loop frames.
+pub(crate) const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Interval between main loop frames while rendering is paused and
+/// [`Config::throttle_when_paused`](rustgine_core::Config::throttle_when_paused) is set.
+#[cfg(not(target_arch = "wasm32"))]
+const PAUSED_FRAME_INTERVAL: Duration = Duration::from_millis(100);
 
 /// Runs the main application event loop.
 ///
 /// This function orchestrates the engine lifecycle:
 ///
 /// 1. **Startup**: Initializes all subsystems in dependency order
-/// 2. **Run**: Waits for shutdown signal (Ctrl+C or internal trigger)
-/// 3. **Shutdown**: Cleanly terminates subsystems in reverse order
+/// 2. **Run**: Reports ready on [`AppState::health`], then ticks the frame
+///    loop, draining the main-thread queue, until a shutdown signal is
+///    received (Ctrl+C or internal trigger). A [`Watchdog`] reports hangs
+///    meanwhile if [`Config::watchdog`](rustgine_core::Config::watchdog) is
+///    set
+/// 3. **Shutdown**: Reports not ready, saves cvar overrides, and cleanly
+///    terminates subsystems in reverse order
 ///
 /// # Arguments
 ///
@@ -39,63 +65,247 @@ use tracing::{debug, warn};
 /// async fn main() -> anyhow::Result<()> {
 ///     let config = Config::load()?;
 ///     let state = AppState::initialize(&config)?;
-///     run(state).await
+///     run(state).await?;
+///     Ok(())
 /// }
 /// ```
 ///
 /// # Errors
 ///
-/// Returns an error if:
-/// - Any subsystem fails during startup
-/// - Any subsystem fails during shutdown
-pub async fn run(state: Arc<AppState>) -> anyhow::Result<()> {
-    {
-        let mut systems = state
-            .rustgine_systems
-            .lock()
-            .map_err(|_| anyhow::anyhow!("rustgine systems lock poisoned"))?;
-
-        for system in systems.iter_mut() {
-            if !system.enabled {
-                debug!(system = %system.name, "subsystem disabled, skipping startup");
-                continue;
-            }
-            debug!(system = %system.name, "starting subsystem");
-            if let Err(e) = system.system.startup() {
-                warn!(system = %system.name, error = %e, "failed to start subsystem");
-                return Err(e);
+/// Returns:
+/// - [`RustgineError::SystemStartupError`] if any subsystem fails during startup
+/// - [`RustgineError::SystemShutdownError`] if any subsystem fails during shutdown
+/// - [`RustgineError::Other`] if the engine metrics cannot be registered; the
+///   subsystems already started are shut down first
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn run(state: Arc<AppState>) -> Result<(), RustgineError> {
+    start_systems(&state)?;
+    debug!(systems = ?state.system_count(), "all subsystems initialized, entering main loop");
+
+    let (engine_metrics, metrics_dump) = match start_services(&state).await {
+        Ok(services) => services,
+        Err(e) => {
+            warn!(error = %e, "failed to start services, shutting down subsystems");
+            if let Err(shutdown_error) = shutdown_systems(&state) {
+                warn!(error = %shutdown_error, "failed to shut down after startup failure");
             }
-            debug!(system = %system.name, "subsystem started");
+            return Err(e.into());
         }
-    }
-    debug!(systems = ?state.system_count(), "all subsystems initialized, entering main loop");
+    };
+    state.health.set_ready(true);
+
+    let watchdog = state.config.watchdog.clone().and_then(|config| {
+        Watchdog::spawn(Arc::clone(&state), config)
+            .inspect_err(|e| warn!(error = %e, "failed to start watchdog"))
+            .ok()
+    });
 
     // Subscribe to shutdown signal for coordinated termination
     let mut shutdown_rx = state.shutdown.subscribe();
     let mut shutdown_fut = Box::pin(shutdown_rx.recv());
+    let mut ctrl_c = Box::pin(os_shutdown_signal());
 
-    // Wait for shutdown trigger (OS signal or internal)
-    tokio::select! {
-        result = tokio::signal::ctrl_c() => {
-            match result {
-                Ok(()) => debug!("received Ctrl+C, initiating shutdown"),
-                Err(e) => warn!(error = %e, "failed to listen for Ctrl+C signal"),
+    let mut throttled = false;
+    let mut frame = frame_interval(FRAME_INTERVAL);
+    let mut last_frame = Instant::now();
+
+    // Tick frames until a shutdown trigger arrives (OS signal or internal)
+    loop {
+        tokio::select! {
+            result = &mut ctrl_c => {
+                match result {
+                    Ok(()) => debug!("received Ctrl+C, initiating shutdown"),
+                    Err(e) => warn!(error = %e, "failed to listen for Ctrl+C signal"),
+                }
+                state.shutdown.trigger();
+                break;
+            }
+            () = &mut shutdown_fut => {
+                // Internal shutdown already triggered elsewhere; no need to re-trigger here.
+                debug!("internal shutdown signal received");
+                break;
             }
-            state.shutdown.trigger();
+            _ = frame.tick() => {
+                let now = Instant::now();
+                step_frame(&state, &engine_metrics, now, now - last_frame);
+                last_frame = now;
+
+                let throttle = state.config.throttle_when_paused && rendering_paused(&state);
+                if throttle != throttled {
+                    throttled = throttle;
+                    debug!(throttled, "adjusting frame rate for rendering suspension");
+                    frame = frame_interval(if throttle { PAUSED_FRAME_INTERVAL } else { FRAME_INTERVAL });
+                }
+            }
+        }
+    }
+
+    // Stop taking traffic while tearing down; teardown has no frames to watch
+    state.health.set_ready(false);
+    drop(watchdog);
+
+    // Run anything queued during the final frame before tearing down
+    state.main_thread.drain();
+
+    // Let the metrics dump write its final snapshot
+    if let Some(task) = metrics_dump {
+        let _ = task.await;
+    }
+
+    if let Some(path) = &state.config.cvar_file {
+        match state.cvars.save_file(path) {
+            Ok(()) => debug!(path = %path.display(), "saved cvar overrides"),
+            Err(e) => warn!(error = %e, "failed to save cvar overrides"),
         }
-        () = &mut shutdown_fut => {
-            // Internal shutdown already triggered elsewhere; no need to re-trigger here.
+    }
+
+    shutdown_systems(&state)
+}
+
+/// Runs the main application loop in the browser.
+///
+/// The lifecycle matches the native `run`: subsystems start, frames are
+/// stepped until [`Shutdown::trigger`](crate::resources::Shutdown::trigger)
+/// is called, and subsystems shut down in reverse order. Frames are driven
+/// by `requestAnimationFrame`, so they pause with hidden tabs, and there is
+/// no Ctrl+C, watchdog, remote control, or metrics export.
+///
+/// Spawn the returned future on the page's executor, e.g. with
+/// `wasm_bindgen_futures::spawn_local`; it completes after shutdown.
+///
+/// # Errors
+///
+/// Returns:
+/// - [`RustgineError::SystemStartupError`] if any subsystem fails during startup
+/// - [`RustgineError::SystemShutdownError`] if any subsystem fails during shutdown
+/// - [`RustgineError::Other`] if the animation frame loop cannot be started
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub async fn run(state: Arc<AppState>) -> Result<(), RustgineError> {
+    start_systems(&state)?;
+    debug!(systems = ?state.system_count(), "all subsystems initialized, entering main loop");
+
+    let engine_metrics = match EngineMetrics::register(&state.metrics) {
+        Ok(metrics) => metrics,
+        Err(e) => {
+            warn!(error = %e, "failed to register metrics, shutting down subsystems");
+            if let Err(shutdown_error) = shutdown_systems(&state) {
+                warn!(error = %shutdown_error, "failed to shut down after startup failure");
+            }
+            return Err(e.into());
+        }
+    };
+    state.health.set_ready(true);
+
+    // The browser calls the frame callback; it reports back once shutdown
+    // is observed, instead of stepping another frame
+    let (stopped_tx, stopped_rx) = tokio::sync::oneshot::channel();
+    let mut stopped_tx = Some(stopped_tx);
+    let mut shutdown_rx = state.shutdown.subscribe();
+    let frame_state = Arc::clone(&state);
+    let started = platform::run_animation_frames(move |delta| {
+        if shutdown_rx.is_triggered() {
             debug!("internal shutdown signal received");
+            if let Some(stopped) = stopped_tx.take() {
+                let _ = stopped.send(());
+            }
+            return false;
+        }
+        step_frame(&frame_state, &engine_metrics, Instant::now(), delta);
+        true
+    });
+    if let Err(e) = started {
+        state.health.set_ready(false);
+        let _ = shutdown_systems(&state);
+        return Err(e.into());
+    }
+    let _ = stopped_rx.await;
+
+    state.health.set_ready(false);
+    state.main_thread.drain();
+
+    if let Some(path) = &state.config.cvar_file {
+        match state.cvars.save_file(path) {
+            Ok(()) => debug!(path = %path.display(), "saved cvar overrides"),
+            Err(e) => warn!(error = %e, "failed to save cvar overrides"),
+        }
+    }
+
+    shutdown_systems(&state)
+}
+
+/// Starts the enabled subsystems in registration order.
+///
+/// # Errors
+///
+/// Returns [`RustgineError::SystemStartupError`] for the first subsystem
+/// that fails to start, after shutting the subsystems already started down
+/// in reverse order.
+pub(crate) fn start_systems(state: &AppState) -> Result<(), RustgineError> {
+    state.heartbeat.enter(FrameStage::Startup);
+    let mut systems = state.systems();
+
+    for index in 0..systems.len() {
+        let system = &mut systems[index];
+        if !system.enabled {
+            debug!(system = %system.name, "subsystem disabled, skipping startup");
+            continue;
         }
+        debug!(system = %system.name, "starting subsystem");
+        state.heartbeat.set_system(Some(system.name));
+        let result = system.system.startup();
+        state.heartbeat.set_system(None);
+        if let Err(e) = result {
+            warn!(system = %system.name, error = %e, "failed to start subsystem");
+            let error = RustgineError::system_startup(system.name, e);
+            state.heartbeat.enter(FrameStage::Shutdown);
+            for started in systems[..index].iter_mut().rev() {
+                if !started.enabled {
+                    continue;
+                }
+                debug!(system = %started.name, "shutting down subsystem");
+                state.heartbeat.set_system(Some(started.name));
+                let result = started.system.shutdown();
+                state.heartbeat.set_system(None);
+                if let Err(e) = result {
+                    warn!(system = %started.name, error = %e, "failed to shut down subsystem");
+                }
+            }
+            return Err(error);
+        }
+        debug!(system = %system.name, "subsystem started");
+    }
+    Ok(())
+}
+
+/// Runs one main loop frame at `now`, `delta` after the previous one:
+/// records frame statistics, metrics, and liveness, then drains the
+/// main-thread queue. Each stage is reported on [`AppState::heartbeat`].
+pub(crate) fn step_frame(state: &AppState, metrics: &EngineMetrics, now: Instant, delta: Duration) {
+    state.heartbeat.enter(FrameStage::Metrics);
+    state.frame_stats.record_frame(now);
+    state.health.record_frame(now);
+    metrics.record_frame(state, delta);
+
+    state.heartbeat.enter(FrameStage::MainThreadQueue);
+    let executed = state.main_thread.drain();
+    if executed > 0 {
+        trace!(tasks = executed, "drained main thread queue");
     }
+    state.heartbeat.beat(now);
+}
 
+/// Shuts the enabled subsystems down in reverse registration order.
+///
+/// # Errors
+///
+/// Returns [`RustgineError::SystemShutdownError`] for the first subsystem
+/// that fails to shut down.
+pub(crate) fn shutdown_systems(state: &AppState) -> Result<(), RustgineError> {
     debug!("shutting down subsystems");
+    state.heartbeat.enter(FrameStage::Shutdown);
 
     // Shutdown in reverse dependency order
-    let mut systems = state
-        .rustgine_systems
-        .lock()
-        .map_err(|_| anyhow::anyhow!("rustgine systems lock poisoned"))?;
+    let mut systems = state.systems();
 
     for system in systems.iter_mut().rev() {
         if !system.enabled {
@@ -103,9 +313,12 @@ pub async fn run(state: Arc<AppState>) -> anyhow::Result<()> {
             continue;
         }
         debug!(system = %system.name, "shutting down subsystem");
-        if let Err(e) = system.system.shutdown() {
+        state.heartbeat.set_system(Some(system.name));
+        let result = system.system.shutdown();
+        state.heartbeat.set_system(None);
+        if let Err(e) = result {
             warn!(system = %system.name, error = %e, "failed to shut down subsystem");
-            return Err(e);
+            return Err(RustgineError::system_shutdown(system.name, e));
         }
         debug!(system = %system.name, "subsystem shut down");
     }
@@ -113,3 +326,81 @@ pub async fn run(state: Arc<AppState>) -> anyhow::Result<()> {
     debug!("all subsystems shut down");
     Ok(())
 }
+
+/// Starts the optional remote control and metrics services.
+///
+/// Returns the per-frame engine metrics and the metrics dump task, if any.
+#[cfg(not(target_arch = "wasm32"))]
+async fn start_services(
+    state: &Arc<AppState>,
+) -> anyhow::Result<(EngineMetrics, Option<JoinHandle<()>>)> {
+    let engine_metrics = EngineMetrics::register(&state.metrics)?;
+
+    // The remote server is a development aid, so failing to start it is not fatal
+    #[cfg(feature = "devui")]
+    if let Some(addr) = state.config.remote_control_addr() {
+        let started = match remote_token(&state.config) {
+            Ok(token) => spawn_remote_server(Arc::clone(state), addr, token).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = started {
+            warn!(error = %e, "remote control server unavailable");
+        }
+    } else if state.config.remote_addr.is_some() {
+        warn!(
+            "remote control is only available in development environments, on loopback \
+             addresses unless `remote_allow_public` is set"
+        );
+    }
+    #[cfg(not(feature = "devui"))]
+    if state.config.remote_addr.is_some() {
+        warn!("remote control requires the `devui` feature");
+    }
+
+    if let Some(addr) = state.config.metrics_addr {
+        let metrics = state.metrics.clone();
+        let health = Arc::clone(&state.health);
+        if let Err(e) = spawn_metrics_server(metrics, health, addr, &state.shutdown).await {
+            warn!(error = %e, "metrics endpoint unavailable");
+        }
+    }
+    let metrics_dump = state.config.metrics_file.as_ref().map(|path| {
+        spawn_metrics_dump(
+            state.metrics.clone(),
+            path.clone(),
+            METRICS_DUMP_INTERVAL,
+            &state.shutdown,
+        )
+    });
+
+    Ok((engine_metrics, metrics_dump))
+}
+
+/// Returns `true` if rendering is suspended.
+#[cfg(all(feature = "render", not(target_arch = "wasm32")))]
+fn rendering_paused(state: &AppState) -> bool {
+    state.rendering_paused.is_paused()
+}
+
+/// Returns `false`; without a renderer there is nothing to suspend.
+#[cfg(all(not(feature = "render"), not(target_arch = "wasm32")))]
+fn rendering_paused(_state: &AppState) -> bool {
+    false
+}
+
+/// Creates a frame ticker that skips missed ticks instead of bursting.
+#[cfg(not(target_arch = "wasm32"))]
+fn frame_interval(period: Duration) -> tokio::time::Interval {
+    let mut interval = tokio::time::interval(period);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    interval
+}
+
+/// Waits for the OS shutdown signal (Ctrl+C).
+///
+/// Browsers have no process signals, so on `wasm32` shutdown is driven solely
+/// by the internal trigger.
+#[cfg(not(target_arch = "wasm32"))]
+async fn os_shutdown_signal() -> std::io::Result<()> {
+    tokio::signal::ctrl_c().await
+}
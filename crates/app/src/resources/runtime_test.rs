@@ -0,0 +1,30 @@
+//! Unit tests for the application runtime.
+
+use crate::resources::{run, AppBuilder};
+use rustgine_core::testing::{CallLog, Phase, RecordingSystem};
+use rustgine_core::{Config, RustgineError};
+
+/// Verifies that subsystems started before a service failed are shut down
+/// in reverse order.
+#[tokio::test]
+async fn failed_services_shut_systems_down() {
+    let log = CallLog::new();
+    let state = AppBuilder::new(&Config::default())
+        .unwrap()
+        .add_system("first", RecordingSystem::new("first", &log))
+        .unwrap()
+        .add_system("second", RecordingSystem::new("second", &log))
+        .unwrap()
+        .build();
+    // Taking the frame counter's name makes the engine metrics fail to register
+    state
+        .metrics
+        .gauge("rustgine_frames_total", "Not a counter")
+        .unwrap();
+
+    let err = run(state).await.unwrap_err();
+
+    assert!(matches!(err, RustgineError::Other(_)), "{err}");
+    assert_eq!(log.systems(Phase::Startup), ["first", "second"]);
+    assert_eq!(log.systems(Phase::Shutdown), ["second", "first"]);
+}
@@ -0,0 +1,34 @@
+//! Schedule dumps requested on the command line.
+//!
+//! Running the engine with `--dump-schedule` prints the resolved subsystem
+//! schedule as Mermaid and exits; `--dump-schedule=dot` prints Graphviz DOT
+//! instead. The same output is available in-game from the `schedule`
+//! console command.
+
+use scheduler::ScheduleFormat;
+
+/// Command-line flag that dumps the schedule instead of running.
+pub const DUMP_SCHEDULE_FLAG: &str = "--dump-schedule";
+
+/// Returns the format requested with [`DUMP_SCHEDULE_FLAG`], or `None` if
+/// the flag is absent. Other arguments are ignored.
+///
+/// # Errors
+///
+/// Returns an error if the flag names an unknown format.
+pub fn dump_schedule_format(
+    args: impl IntoIterator<Item = String>,
+) -> anyhow::Result<Option<ScheduleFormat>> {
+    for arg in args {
+        if arg == DUMP_SCHEDULE_FLAG {
+            return Ok(Some(ScheduleFormat::default()));
+        }
+        if let Some(format) = arg
+            .strip_prefix(DUMP_SCHEDULE_FLAG)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            return format.parse().map(Some);
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,46 @@
+//! Unit tests for the schedule dump flag and the subsystem schedule.
+
+use super::{dump_schedule_format, AppState};
+use platform::RustginePlatform;
+use rustgine_core::Config;
+use scheduler::ScheduleFormat;
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(ToString::to_string).collect()
+}
+
+/// Verifies the flag is found among other arguments, with or without a
+/// format.
+#[test]
+fn parses_dump_schedule_flag() {
+    assert_eq!(dump_schedule_format(args(&["--verbose"])).unwrap(), None);
+    assert_eq!(
+        dump_schedule_format(args(&["--verbose", "--dump-schedule"])).unwrap(),
+        Some(ScheduleFormat::Mermaid)
+    );
+    assert_eq!(
+        dump_schedule_format(args(&["--dump-schedule=dot"])).unwrap(),
+        Some(ScheduleFormat::Dot)
+    );
+    assert!(dump_schedule_format(args(&["--dump-schedule=svg"])).is_err());
+}
+
+/// Verifies subsystems are scheduled in registration order and the
+/// console command renders the plan.
+#[test]
+fn schedules_registered_subsystems() {
+    let state = AppState::initialize(&Config::default()).unwrap();
+    state.register_system("platform", RustginePlatform).unwrap();
+    state.register_system("input", RustginePlatform).unwrap();
+
+    let plan = state.schedule().unwrap().plan().unwrap();
+    assert_eq!(plan.stages[0].batches, [vec!["platform"], vec!["input"]]);
+
+    let dot = state.console.execute("schedule dot").unwrap();
+    assert!(dot.starts_with("digraph schedule {"));
+    assert!(state
+        .console
+        .execute("schedule")
+        .unwrap()
+        .starts_with("flowchart"));
+}
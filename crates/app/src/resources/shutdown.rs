@@ -113,6 +113,7 @@ impl Shutdown {
     pub fn subscribe(&self) -> ShutdownRx {
         ShutdownRx {
             receiver: self.sender.subscribe(),
+            triggered: false,
         }
     }
 
@@ -138,6 +139,7 @@ impl Shutdown {
 #[derive(Debug)]
 pub struct ShutdownRx {
     receiver: broadcast::Receiver<()>,
+    triggered: bool,
 }
 
 impl ShutdownRx {
@@ -156,5 +158,21 @@ impl ShutdownRx {
         // - RecvError::Closed: sender dropped, shutdown implied
         // - RecvError::Lagged: missed messages, treat as shutdown
         let _ = self.receiver.recv().await;
+        self.triggered = true;
+    }
+
+    /// Returns `true` if the shutdown signal has been received, without
+    /// waiting.
+    ///
+    /// For loops that cannot await, such as the browser's animation frame
+    /// callbacks. Like [`recv`](Self::recv), stays `true` once triggered.
+    pub fn is_triggered(&mut self) -> bool {
+        if !self.triggered {
+            self.triggered = !matches!(
+                self.receiver.try_recv(),
+                Err(broadcast::error::TryRecvError::Empty)
+            );
+        }
+        self.triggered
     }
 }
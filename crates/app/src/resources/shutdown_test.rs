@@ -142,3 +142,16 @@ async fn cloned_shutdown_can_trigger() {
         "trigger from clone should notify original's subscriber"
     );
 }
+
+/// Verifies that polling for the signal reports it once triggered, and keeps
+/// reporting it.
+#[test]
+fn is_triggered_polls_without_waiting() {
+    let shutdown = Shutdown::new();
+    let mut rx = shutdown.subscribe();
+    assert!(!rx.is_triggered());
+
+    shutdown.trigger();
+    assert!(rx.is_triggered());
+    assert!(rx.is_triggered());
+}
@@ -3,15 +3,35 @@
 //! Provides the central state container that holds configuration,
 //! subsystem references, and shutdown coordination.
 
-use crate::resources::Shutdown;
-use rustgine_core::{Config, RustgineSystem};
-use std::sync::{Arc, Mutex};
+use crate::resources::{
+    AppEvent, AppEvents, FrameStats, Health, Heartbeat, MainThreadQueue, Shutdown,
+};
+use assets::AssetCache;
+#[cfg(feature = "render")]
+use platform::{LifecycleAction, LifecycleState, WindowVisibility};
+use platform::{LifecycleTracker, VisibilityTracker};
+#[cfg(feature = "render")]
+use render::{GpuDeviceLost, RenderingPaused};
+use rustgine_core::console::Arg;
+use rustgine_core::{
+    BuildInfo, CVars, Config, Console, Label, LogBuffer, Metrics, RustgineError, RustgineSystem,
+};
+use scheduler::{Schedule, ScheduleFormat, SystemDescriptor};
+#[cfg(feature = "render")]
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
+#[cfg(feature = "render")]
+use tracing::debug;
+use tracing::{info, warn, Level};
+use winit::event::{Event, WindowEvent};
 
 /// Global application state shared across all engine tasks.
 ///
 /// `AppState` serves as the central hub for:
 /// - Application configuration
 /// - Graceful shutdown coordination
+/// - Main-thread task dispatching
+/// - The developer console and console variables
 /// - Registered engine subsystems
 ///
 /// # Thread Safety
@@ -43,18 +63,97 @@ pub struct AppState {
     /// Wrapped in [`Arc`] to allow cheap cloning to subsystems.
     pub config: Arc<Config>,
 
+    /// Version, git commit, profile, and target of the running build.
+    ///
+    /// Logged at startup and reported by the `version` console command and
+    /// the remote `/status` endpoint.
+    pub build: BuildInfo,
+
     /// Graceful shutdown signal broadcaster.
     ///
     /// Used to coordinate shutdown across all engine tasks.
     pub shutdown: Shutdown,
 
+    /// Engine lifecycle events.
+    pub events: AppEvents,
+
+    /// Queue of closures executed on the main thread once per frame.
+    ///
+    /// Bound to the thread that called [`initialize`](Self::initialize).
+    pub main_thread: MainThreadQueue,
+
+    /// Rendering suspension flag shared with the renderer.
+    ///
+    /// Set from window visibility changes via
+    /// [`set_window_visibility`](Self::set_window_visibility), and while the
+    /// application is suspended via
+    /// [`handle_lifecycle_event`](Self::handle_lifecycle_event).
+    #[cfg(feature = "render")]
+    pub rendering_paused: RenderingPaused,
+
+    /// Window visibility, fed by the platform event loop through
+    /// [`handle_window_event`](Self::handle_window_event).
+    pub window_visibility: Mutex<VisibilityTracker>,
+
+    /// Suspend and resume state, fed by the platform event loop through
+    /// [`handle_lifecycle_event`](Self::handle_lifecycle_event).
+    pub lifecycle: Mutex<LifecycleTracker>,
+
+    /// Set while the renderer is shut down because the application was
+    /// suspended and its window, with the render surface, destroyed.
+    #[cfg(feature = "render")]
+    surface_dropped: AtomicBool,
+
+    /// Developer console shared with all subsystems.
+    ///
+    /// Comes with the engine commands `quit`, `systems`, `restart`,
+    /// `schedule`, `warnings`, `assets`, and `version`; subsystems register their own
+    /// commands on a clone.
+    pub console: Console,
+
+    /// Console variables, exposed as console commands.
+    ///
+    /// Seeded from [`Config::cvar_file`] at initialization; overrides are
+    /// written back on shutdown.
+    pub cvars: CVars,
+
+    /// Frame counter and frame rate, updated by the main loop.
+    pub frame_stats: FrameStats,
+
+    /// Readiness and frame loop liveness, served as `/readyz` and
+    /// `/healthz` by the metrics endpoint and the remote control server.
+    pub health: Arc<Health>,
+
+    /// Main loop progress, checked for hangs by the
+    /// [`Watchdog`](crate::resources::Watchdog) when
+    /// [`Config::watchdog`] is set.
+    pub heartbeat: Heartbeat,
+
+    /// Engine metrics registry, exported in the Prometheus text format.
+    pub metrics: Metrics,
+
+    /// Most recent log records, for debug overlays.
+    ///
+    /// Filled by the tracing subscriber once installed with
+    /// [`init_tracing_from_config`](rustgine_core::init_tracing_from_config).
+    pub log_buffer: LogBuffer,
+
+    /// Loaded assets and their CPU memory budget.
+    ///
+    /// The memory breakdown by asset type is reported by the `assets`
+    /// console command and the remote `/status` endpoint. Lock it through
+    /// [`assets`](Self::assets).
+    pub asset_cache: Mutex<AssetCache>,
+
     /// Registered engine subsystems.
     ///
     /// Systems are stored as trait objects to allow heterogeneous collections.
     /// They are started in registration order and shut down in reverse order.
     ///
     /// A mutex provides interior mutability so subsystems can be registered
-    /// without requiring a mutable reference to `AppState`.
+    /// without requiring a mutable reference to `AppState`. Lock it through
+    /// [`systems`](Self::systems), which recovers it if a subsystem
+    /// panicked while it was held.
     // rustgine_systems: Vec<Box<dyn RustgineSystem + Send + Sync>>,
     pub rustgine_systems: Mutex<Vec<NamedSystem>>,
 }
@@ -65,7 +164,7 @@ pub struct AppState {
 /// and management purposes.
 #[derive(Debug)]
 pub struct NamedSystem {
-    pub name: String,
+    pub name: Label,
     pub enabled: bool,
     pub system: Box<dyn RustgineSystem + Send + Sync>,
 }
@@ -74,6 +173,8 @@ impl AppState {
     /// Initializes the application state with the given configuration.
     ///
     /// Creates a new `AppState` wrapped in [`Arc`] for sharing across tasks.
+    /// Must be called from the main thread, which becomes the owner of the
+    /// [`MainThreadQueue`].
     ///
     /// # Arguments
     ///
@@ -85,8 +186,7 @@ impl AppState {
     ///
     /// # Errors
     ///
-    /// Currently infallible, but returns `Result` to allow for future
-    /// initialization steps that may fail.
+    /// Returns an error if the cvar file cannot be read or parsed.
     ///
     /// # Example
     ///
@@ -98,11 +198,39 @@ impl AppState {
     /// let state = AppState::initialize(&config)?;
     /// ```
     pub fn initialize(config: &Config) -> anyhow::Result<Arc<Self>> {
-        Ok(Arc::new(Self {
-            config: Arc::new(config.clone()),
-            shutdown: Shutdown::new(),
-            rustgine_systems: Mutex::new(Vec::new()),
-        }))
+        let cvars = CVars::new();
+        if let Some(path) = &config.cvar_file {
+            cvars.load_file(path)?;
+        }
+
+        let state = Arc::new_cyclic(|weak: &Weak<Self>| {
+            let console = Console::new();
+            register_engine_commands(&console, weak);
+            Self {
+                config: Arc::new(config.clone()),
+                build: BuildInfo::current(),
+                shutdown: Shutdown::new(),
+                events: AppEvents::new(),
+                main_thread: MainThreadQueue::new(),
+                #[cfg(feature = "render")]
+                rendering_paused: RenderingPaused::default(),
+                window_visibility: Mutex::default(),
+                lifecycle: Mutex::default(),
+                #[cfg(feature = "render")]
+                surface_dropped: AtomicBool::new(false),
+                console,
+                cvars,
+                frame_stats: FrameStats::default(),
+                health: Arc::default(),
+                heartbeat: Heartbeat::default(),
+                metrics: Metrics::new(),
+                log_buffer: LogBuffer::default(),
+                asset_cache: Mutex::new(AssetCache::default()),
+                rustgine_systems: Mutex::new(Vec::new()),
+            }
+        });
+        state.cvars.attach_console(&state.console)?;
+        Ok(state)
     }
 
     /// Registers an engine subsystem for lifecycle management.
@@ -128,18 +256,14 @@ impl AppState {
     ///
     /// # Errors
     ///
-    /// Returns an error if the subsystem registry lock is poisoned.
-    pub fn register_system<S>(&self, alias: &str, system: S) -> anyhow::Result<()>
+    /// Registration currently always succeeds; a registry poisoned by a
+    /// panicking subsystem is recovered by [`systems`](Self::systems).
+    pub fn register_system<S>(&self, alias: &str, system: S) -> Result<(), RustgineError>
     where
         S: RustgineSystem + Send + Sync + 'static,
     {
-        let mut systems = self
-            .rustgine_systems
-            .lock()
-            .map_err(|_| anyhow::anyhow!("rustgine systems lock poisoned"))?;
-
-        systems.push(NamedSystem {
-            name: alias.to_string(),
+        self.systems().push(NamedSystem {
+            name: Label::new(alias),
             enabled: true,
             system: Box::new(system),
         });
@@ -147,15 +271,391 @@ impl AppState {
         Ok(())
     }
 
-    /// Returns the number of registered subsystems.
+    /// Shuts down and starts again the subsystem registered as `name`, while
+    /// the rest of the engine keeps running.
+    ///
+    /// Recovers a subsystem whose device went away, such as the renderer
+    /// after device loss or audio after the default output changed.
+    /// [`AppEvent::SystemRestarting`] is sent before, and
+    /// [`AppEvent::SystemRestarted`] or [`AppEvent::SystemRestartFailed`]
+    /// after. Other users of the registry wait until the restart completes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RustgineError::SystemShutdownError`] or
+    /// [`RustgineError::SystemStartupError`] if the subsystem fails to stop
+    /// or start, or [`RustgineError::Other`] if no enabled subsystem is
+    /// named `name`.
+    pub fn restart_system(&self, name: &str) -> Result<(), RustgineError> {
+        let mut systems = self.systems();
+        let system = systems
+            .iter_mut()
+            .find(|system| system.name == name)
+            .ok_or_else(|| anyhow::anyhow!("unknown subsystem `{name}`"))?;
+        if !system.enabled {
+            return Err(anyhow::anyhow!("subsystem `{name}` is disabled").into());
+        }
+
+        let name = system.name;
+        info!(system = %name, "restarting subsystem");
+        self.events.send(AppEvent::SystemRestarting { name });
+        self.heartbeat.set_system(Some(name));
+        let result = system
+            .system
+            .shutdown()
+            .map_err(|e| RustgineError::system_shutdown(name, e))
+            .and_then(|()| {
+                system
+                    .system
+                    .startup()
+                    .map_err(|e| RustgineError::system_startup(name, e))
+            });
+        self.heartbeat.set_system(None);
+        match &result {
+            Ok(()) => {
+                info!(system = %name, "subsystem restarted");
+                self.events.send(AppEvent::SystemRestarted { name });
+            }
+            Err(e) => {
+                warn!(system = %name, error = %e, "failed to restart subsystem");
+                self.events.send(AppEvent::SystemRestartFailed { name });
+            }
+        }
+        result
+    }
+
+    /// Recovers from a [`GpuDeviceLost`] raised by the renderer by
+    /// restarting the `render` subsystem, which recreates the device.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RustgineError::DeviceLost`] if the renderer cannot be
+    /// restarted; the device stays lost and nothing is rendered.
+    #[cfg(feature = "render")]
+    pub fn handle_device_lost(&self, lost: GpuDeviceLost) -> Result<(), RustgineError> {
+        warn!(reason = %lost.reason, "graphics device lost, restarting the renderer");
+        self.restart_system(RENDER_SYSTEM).map_err(|e| {
+            warn!(error = %e, "failed to recover from graphics device loss");
+            RustgineError::from(lost)
+        })
+    }
+
+    /// Records a window event from the platform event loop, pausing or
+    /// resuming rendering when it changes the window's visibility.
+    pub fn handle_window_event(&self, event: &WindowEvent) {
+        let changed = self
+            .window_visibility
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .handle_window_event(event);
+        #[cfg(feature = "render")]
+        if let Some(visibility) = changed {
+            self.set_window_visibility(visibility);
+        }
+        #[cfg(not(feature = "render"))]
+        let _ = changed;
+    }
+
+    /// Pauses or resumes rendering based on the window's visibility.
+    ///
+    /// Rendering is paused while the window is minimized or occluded and
+    /// resumes as soon as it becomes visible again, unless the application
+    /// is suspended.
+    #[cfg(feature = "render")]
+    pub fn set_window_visibility(&self, visibility: WindowVisibility) {
+        let suspended = matches!(
+            self.lifecycle
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .state(),
+            LifecycleState::Paused | LifecycleState::Terminated
+        );
+        let paused = !visibility.is_visible() || suspended;
+        if self.rendering_paused.set(paused) {
+            debug!(?visibility, paused, "rendering suspension changed");
+        }
+    }
+
+    /// Records a suspend or resume event from the platform event loop.
+    ///
+    /// On suspend, rendering is paused and the `render` subsystem is shut
+    /// down, dropping the render surface before the OS destroys the window.
+    /// On resume, the renderer is started again, recreating the surface,
+    /// and rendering resumes if the window is visible. Subsystem startup and
+    /// shutdown are left to [`run`](crate::resources::run).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RustgineError::SystemShutdownError`] or
+    /// [`RustgineError::SystemStartupError`] if the renderer fails to stop
+    /// or start; a renderer that failed to start is retried on the next
+    /// resume.
+    pub fn handle_lifecycle_event<T>(&self, event: &Event<T>) -> Result<(), RustgineError> {
+        let actions = self
+            .lifecycle
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .handle_winit_event(event);
+        #[cfg(feature = "render")]
+        for action in actions {
+            self.apply_lifecycle_action(action)?;
+        }
+        #[cfg(not(feature = "render"))]
+        let _ = actions;
+        Ok(())
+    }
+
+    /// Pauses, resumes, or moves the renderer across a surface loss.
+    #[cfg(feature = "render")]
+    fn apply_lifecycle_action(&self, action: LifecycleAction) -> Result<(), RustgineError> {
+        match action {
+            LifecycleAction::Pause => {
+                if self.rendering_paused.set(true) {
+                    debug!("rendering suspended with the application");
+                }
+            }
+            LifecycleAction::Resume => {
+                let visibility = self
+                    .window_visibility
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .visibility();
+                self.set_window_visibility(visibility);
+            }
+            LifecycleAction::DestroySurface => {
+                if self.suspend_system(RENDER_SYSTEM)? {
+                    self.surface_dropped.store(true, Ordering::Release);
+                }
+            }
+            LifecycleAction::RecreateSurface => {
+                if self.surface_dropped.swap(false, Ordering::AcqRel) {
+                    self.resume_system(RENDER_SYSTEM).inspect_err(|_| {
+                        self.surface_dropped.store(true, Ordering::Release);
+                    })?;
+                }
+            }
+            LifecycleAction::Startup
+            | LifecycleAction::Shutdown
+            | LifecycleAction::Reorient(_)
+            | LifecycleAction::TrimMemory => {
+                debug!(?action, "ignoring lifecycle action");
+            }
+        }
+        Ok(())
+    }
+
+    /// Shuts the enabled subsystem registered as `name` down and disables
+    /// it until [`resume_system`](Self::resume_system).
     ///
-    /// Returns `0` if the subsystem registry lock is poisoned.
+    /// Returns `false` if no enabled subsystem is named `name`.
+    #[cfg(feature = "render")]
+    fn suspend_system(&self, name: &str) -> Result<bool, RustgineError> {
+        let mut systems = self.systems();
+        let Some(system) = systems
+            .iter_mut()
+            .find(|system| system.enabled && system.name == name)
+        else {
+            return Ok(false);
+        };
+        info!(system = %system.name, "suspending subsystem");
+        self.heartbeat.set_system(Some(system.name));
+        let result = system.system.shutdown();
+        self.heartbeat.set_system(None);
+        result.map_err(|e| RustgineError::system_shutdown(system.name, e))?;
+        system.enabled = false;
+        Ok(true)
+    }
+
+    /// Starts the subsystem registered as `name` again and enables it.
+    #[cfg(feature = "render")]
+    fn resume_system(&self, name: &str) -> Result<(), RustgineError> {
+        let mut systems = self.systems();
+        let system = systems
+            .iter_mut()
+            .find(|system| system.name == name)
+            .ok_or_else(|| anyhow::anyhow!("unknown subsystem `{name}`"))?;
+        info!(system = %system.name, "resuming subsystem");
+        self.heartbeat.set_system(Some(system.name));
+        let result = system.system.startup();
+        self.heartbeat.set_system(None);
+        result.map_err(|e| RustgineError::system_startup(system.name, e))?;
+        system.enabled = true;
+        Ok(())
+    }
+
+    /// Returns the schedule of the enabled subsystems.
+    ///
+    /// Subsystems run one after another in registration order, in a single
+    /// `frame` stage.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if two subsystems share a name.
+    pub fn schedule(&self) -> Result<Schedule, RustgineError> {
+        let systems = self.systems();
+        let mut schedule = Schedule::default();
+        schedule.add_stage(FRAME_STAGE);
+        let mut previous: Option<&str> = None;
+        for system in systems.iter().filter(|s| s.enabled) {
+            let mut descriptor = SystemDescriptor::new(FRAME_STAGE, &system.name);
+            if let Some(previous) = previous {
+                descriptor = descriptor.after(previous);
+            }
+            schedule.add_system(descriptor)?;
+            previous = Some(system.name.as_str());
+        }
+        Ok(schedule)
+    }
+
+    /// Returns the number of registered subsystems.
     #[must_use]
     #[inline]
     pub fn system_count(&self) -> usize {
-        self.rustgine_systems
+        self.systems().len()
+    }
+
+    /// Locks the registered subsystems.
+    ///
+    /// A subsystem that panics during startup or shutdown poisons the
+    /// lock. Rather than failing every later caller, the registry is
+    /// recovered as the panic left it, with a warning and an
+    /// [`AppEvent::SystemRegistryCorrupted`].
+    pub fn systems(&self) -> MutexGuard<'_, Vec<NamedSystem>> {
+        self.rustgine_systems.lock().unwrap_or_else(|poisoned| {
+            warn!("a subsystem panicked while the registry was locked, recovering it");
+            self.rustgine_systems.clear_poison();
+            self.events.send(AppEvent::SystemRegistryCorrupted);
+            poisoned.into_inner()
+        })
+    }
+
+    /// Locks the asset cache.
+    ///
+    /// A panic while the cache was locked leaves it consistent, so the
+    /// lock is recovered without a warning.
+    pub fn assets(&self) -> MutexGuard<'_, AssetCache> {
+        self.asset_cache
             .lock()
-            .map(|systems| systems.len())
-            .unwrap_or(0)
+            .unwrap_or_else(PoisonError::into_inner)
     }
 }
+
+/// Stage the registered subsystems run in.
+const FRAME_STAGE: &str = "frame";
+
+/// Name the renderer is registered under by
+/// [`AppBuilder::add_default_systems`](crate::resources::AppBuilder::add_default_systems).
+#[cfg(feature = "render")]
+pub(crate) const RENDER_SYSTEM: &str = "render";
+
+/// Returns the application state a console command was registered on.
+fn upgrade(state: &Weak<AppState>) -> anyhow::Result<Arc<AppState>> {
+    state
+        .upgrade()
+        .ok_or_else(|| anyhow::anyhow!("application state dropped"))
+}
+
+/// Registers the console commands that operate on the application itself.
+///
+/// Handlers hold a [`Weak`] reference so the console does not keep the
+/// state alive.
+fn register_engine_commands(console: &Console, state: &Weak<AppState>) {
+    let weak = state.clone();
+    let quit = console.register("quit", "Shuts the engine down", &[], move |_| {
+        if let Some(state) = weak.upgrade() {
+            state.shutdown.trigger();
+        }
+        Ok("shutting down".to_owned())
+    });
+
+    let weak = state.clone();
+    let systems = console.register("systems", "Lists registered subsystems", &[], move |_| {
+        let state = upgrade(&weak)?;
+        let systems = state.systems();
+        Ok(systems
+            .iter()
+            .map(|s| {
+                format!(
+                    "{} ({})",
+                    s.name,
+                    if s.enabled { "enabled" } else { "disabled" }
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"))
+    });
+
+    let weak = state.clone();
+    let restart = console.register(
+        "restart",
+        "Shuts a subsystem down and starts it again",
+        &[Arg::str("system")],
+        move |args| {
+            let state = upgrade(&weak)?;
+            let name = args.str(0)?;
+            state.restart_system(name)?;
+            Ok(format!("restarted {name}"))
+        },
+    );
+
+    let weak = state.clone();
+    let warnings = console.register(
+        "warnings",
+        "Shows recent warnings and errors",
+        &[],
+        move |_| {
+            let state = upgrade(&weak)?;
+            Ok(state
+                .log_buffer
+                .at_least(Level::WARN)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n"))
+        },
+    );
+
+    let weak = state.clone();
+    let assets = console.register(
+        "assets",
+        "Shows asset memory by type against the budget",
+        &[],
+        move |_| {
+            let state = upgrade(&weak)?;
+            let report = state.assets().report();
+            Ok(report.to_string())
+        },
+    );
+
+    let weak = state.clone();
+    let schedule = console.register(
+        "schedule",
+        "Shows the subsystem schedule as Mermaid, or as DOT with `schedule dot`",
+        &[Arg::str("format").optional()],
+        move |args| {
+            let state = upgrade(&weak)?;
+            let format = match args.get(0) {
+                Some(_) => args.str(0)?.parse()?,
+                None => ScheduleFormat::default(),
+            };
+            Ok(state.schedule()?.plan()?.render(format, &[]))
+        },
+    );
+
+    let weak = state.clone();
+    let version = console.register("version", "Shows the engine build", &[], move |_| {
+        let state = upgrade(&weak)?;
+        Ok(state.build.to_string())
+    });
+
+    // Registration only fails on name clashes, which a fresh console cannot have.
+    debug_assert!(
+        quit.is_ok()
+            && systems.is_ok()
+            && restart.is_ok()
+            && warnings.is_ok()
+            && assets.is_ok()
+            && schedule.is_ok()
+            && version.is_ok()
+    );
+}
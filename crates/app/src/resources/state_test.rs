@@ -0,0 +1,66 @@
+//! Unit tests for the global application state.
+
+use crate::resources::AppState;
+use platform::WindowVisibility;
+#[cfg(feature = "render")]
+use render::GpuDeviceLost;
+#[cfg(feature = "render")]
+use rustgine_core::testing::{CallLog, Phase, RecordingSystem};
+use rustgine_core::Config;
+#[cfg(feature = "render")]
+use rustgine_core::RustgineError;
+use winit::dpi::PhysicalSize;
+use winit::event::WindowEvent;
+
+/// Verifies that window events from the platform pause rendering while the
+/// window is hidden and resume it once visible.
+#[test]
+fn window_events_pause_rendering() {
+    let state = AppState::initialize(&Config::default()).unwrap();
+    let visibility = || state.window_visibility.lock().unwrap().visibility();
+
+    state.handle_window_event(&WindowEvent::Occluded(true));
+    assert_eq!(visibility(), WindowVisibility::Occluded);
+    #[cfg(feature = "render")]
+    assert!(state.rendering_paused.is_paused());
+
+    state.handle_window_event(&WindowEvent::Occluded(false));
+    state.handle_window_event(&WindowEvent::Resized(PhysicalSize::new(0, 0)));
+    assert_eq!(visibility(), WindowVisibility::Minimized);
+    #[cfg(feature = "render")]
+    assert!(state.rendering_paused.is_paused());
+
+    state.handle_window_event(&WindowEvent::Resized(PhysicalSize::new(1280, 720)));
+    assert_eq!(visibility(), WindowVisibility::Visible);
+    #[cfg(feature = "render")]
+    assert!(!state.rendering_paused.is_paused());
+}
+
+/// Verifies that a lost graphics device restarts the renderer, and is
+/// reported as [`RustgineError::DeviceLost`] when the restart fails.
+#[cfg(feature = "render")]
+#[test]
+fn device_loss_restarts_the_renderer() {
+    let log = CallLog::new();
+    let state = AppState::initialize(&Config::default()).unwrap();
+    state
+        .register_system("render", RecordingSystem::new("render", &log))
+        .unwrap();
+    let lost = || GpuDeviceLost {
+        reason: "out of GPU memory".to_owned(),
+    };
+
+    state.handle_device_lost(lost()).unwrap();
+    assert_eq!(log.systems(Phase::Shutdown), ["render"]);
+    assert_eq!(log.systems(Phase::Startup), ["render"]);
+
+    let broken = AppState::initialize(&Config::default()).unwrap();
+    broken
+        .register_system(
+            "render",
+            RecordingSystem::new("render", &log).failing_on(Phase::Startup),
+        )
+        .unwrap();
+    let err = broken.handle_device_lost(lost()).unwrap_err();
+    assert!(matches!(err, RustgineError::DeviceLost), "{err}");
+}
@@ -0,0 +1,236 @@
+//! Deterministic engine harness for integration tests.
+//!
+//! A [`TestApp`] is a headless engine that never spawns windows, servers,
+//! or timers. Its frame loop runs only when the test calls
+//! [`tick`](TestApp::tick) or [`tick_n`](TestApp::tick_n), on a virtual
+//! clock advanced by a fixed interval, so tests neither sleep nor depend on
+//! the machine's speed. Each frame runs the same steps as
+//! [`run`](crate::resources::run), then the test's update functions on an
+//! ECS [`World`].
+//!
+//! Instances share nothing, so one test can run several, e.g. a server and
+//! its clients.
+
+use crate::resources::runtime::{shutdown_systems, start_systems, step_frame, FRAME_INTERVAL};
+use crate::resources::{AppState, EngineMetrics};
+use ecs::World;
+use rustgine_core::{Config, RustgineError, RustgineSystem};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use web_time::Instant;
+
+/// Per-frame update run on the world, given the frame's delta.
+type Update = Box<dyn FnMut(&mut World, Duration)>;
+
+/// A headless engine stepped manually by tests.
+///
+/// Subsystems start on the first tick, or on
+/// [`startup`](Self::startup), and shut down on
+/// [`shutdown`](Self::shutdown). Like [`AppState`], a `TestApp` belongs to
+/// the thread that created it, which runs its main-thread queue.
+///
+/// # Example
+///
+/// ```
+/// use app::resources::TestApp;
+///
+/// #[derive(Debug, Default)]
+/// struct Score(u32);
+///
+/// let mut app = TestApp::new()?.add_update(|world, _delta| {
+///     if let Some(score) = world.resource_mut::<Score>() {
+///         score.0 += 1;
+///     }
+/// });
+/// app.world_mut().insert_resource(Score::default());
+///
+/// app.tick_n(10)?;
+/// assert_eq!(app.world().resource::<Score>().unwrap().0, 10);
+/// assert_eq!(app.state().frame_stats.frames(), 10);
+/// app.shutdown()?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct TestApp {
+    state: Arc<AppState>,
+    world: World,
+    updates: Vec<Update>,
+    metrics: EngineMetrics,
+    frame_interval: Duration,
+    /// Virtual time of the last frame.
+    now: Instant,
+    started: bool,
+}
+
+impl fmt::Debug for TestApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TestApp")
+            .field("state", &self.state)
+            .field("world", &self.world)
+            .field("updates", &self.updates.len())
+            .field("frame_interval", &self.frame_interval)
+            .field("started", &self.started)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TestApp {
+    /// Creates an app with the default configuration and no subsystems.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the engine metrics cannot be registered.
+    pub fn new() -> anyhow::Result<Self> {
+        Self::with_config(&Config::default())
+    }
+
+    /// Creates an app with `config` and no subsystems.
+    ///
+    /// Servers in `config` are not started.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cvar file cannot be read or the engine
+    /// metrics cannot be registered.
+    pub fn with_config(config: &Config) -> anyhow::Result<Self> {
+        Self::from_state(AppState::initialize(config)?)
+    }
+
+    /// Creates an app around already initialized state, e.g. one from
+    /// [`AppBuilder`](crate::resources::AppBuilder).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the engine metrics are already registered on the
+    /// state's registry with other types.
+    pub fn from_state(state: Arc<AppState>) -> anyhow::Result<Self> {
+        Ok(Self {
+            metrics: EngineMetrics::register(&state.metrics)?,
+            state,
+            world: World::default(),
+            updates: Vec::new(),
+            frame_interval: FRAME_INTERVAL,
+            now: Instant::now(),
+            started: false,
+        })
+    }
+
+    /// Registers a subsystem; see [`AppState::register_system`].
+    ///
+    /// # Errors
+    ///
+    /// Registration currently always succeeds; see
+    /// [`AppState::register_system`].
+    pub fn add_system<S>(self, alias: &str, system: S) -> Result<Self, RustgineError>
+    where
+        S: RustgineSystem + Send + Sync + 'static,
+    {
+        self.state.register_system(alias, system)?;
+        Ok(self)
+    }
+
+    /// Adds a function run on the world every frame, after the engine's
+    /// own frame steps, in the order added.
+    #[must_use]
+    pub fn add_update(mut self, update: impl FnMut(&mut World, Duration) + 'static) -> Self {
+        self.updates.push(Box::new(update));
+        self
+    }
+
+    /// Sets the virtual time between frames, 16 ms by default.
+    #[must_use]
+    pub fn with_frame_interval(mut self, interval: Duration) -> Self {
+        self.frame_interval = interval;
+        self
+    }
+
+    /// Returns the engine state.
+    #[must_use]
+    #[inline]
+    pub fn state(&self) -> &Arc<AppState> {
+        &self.state
+    }
+
+    /// Returns the world.
+    #[must_use]
+    #[inline]
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Returns the world mutably, e.g. to spawn fixtures.
+    #[inline]
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// Returns the virtual time of the last frame.
+    #[must_use]
+    #[inline]
+    pub fn now(&self) -> Instant {
+        self.now
+    }
+
+    /// Starts the subsystems if they are not running, and marks the engine
+    /// ready.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RustgineError::SystemStartupError`] if a subsystem fails to
+    /// start.
+    pub fn startup(&mut self) -> Result<(), RustgineError> {
+        if !self.started {
+            start_systems(&self.state)?;
+            self.started = true;
+            self.state.health.set_ready(true);
+        }
+        Ok(())
+    }
+
+    /// Runs one frame, starting the subsystems first if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RustgineError::SystemStartupError`] if a subsystem fails to
+    /// start.
+    pub fn tick(&mut self) -> Result<(), RustgineError> {
+        self.startup()?;
+        self.now += self.frame_interval;
+        step_frame(&self.state, &self.metrics, self.now, self.frame_interval);
+        for update in &mut self.updates {
+            update(&mut self.world, self.frame_interval);
+        }
+        self.state.frame_stats.record_entities(self.world.len());
+        Ok(())
+    }
+
+    /// Runs `frames` frames.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RustgineError::SystemStartupError`] if a subsystem fails to
+    /// start.
+    pub fn tick_n(&mut self, frames: usize) -> Result<(), RustgineError> {
+        for _ in 0..frames {
+            self.tick()?;
+        }
+        Ok(())
+    }
+
+    /// Shuts the subsystems down if they are running, as
+    /// [`run`](crate::resources::run) does after its loop.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RustgineError::SystemShutdownError`] if a subsystem fails
+    /// to shut down.
+    pub fn shutdown(&mut self) -> Result<(), RustgineError> {
+        if !self.started {
+            return Ok(());
+        }
+        self.started = false;
+        self.state.health.set_ready(false);
+        self.state.main_thread.drain();
+        shutdown_systems(&self.state)
+    }
+}
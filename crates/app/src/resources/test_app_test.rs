@@ -0,0 +1,89 @@
+//! Unit tests for the integration test harness.
+
+use crate::resources::TestApp;
+use rustgine_core::testing::{CallLog, Phase, RecordingSystem};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Frame counter resource.
+#[derive(Debug, Default)]
+struct Frames(u32);
+
+/// Verifies subsystems start on the first tick and stop in reverse order.
+#[test]
+fn runs_system_lifecycle() {
+    let log = CallLog::new();
+    let mut app = TestApp::new()
+        .unwrap()
+        .add_system("a", RecordingSystem::new("a", &log))
+        .unwrap()
+        .add_system("b", RecordingSystem::new("b", &log))
+        .unwrap();
+    assert!(log.calls().is_empty());
+    assert!(!app.state().health.is_ready());
+
+    app.tick_n(3).unwrap();
+    assert!(app.state().health.is_ready());
+    app.shutdown().unwrap();
+    app.shutdown().unwrap();
+    assert_eq!(log.systems(Phase::Startup), ["a", "b"]);
+    assert_eq!(log.systems(Phase::Shutdown), ["b", "a"]);
+    assert!(!app.state().health.is_ready());
+}
+
+/// Verifies frames advance a virtual clock and run updates on the world.
+#[test]
+fn steps_world_on_virtual_clock() {
+    let mut app = TestApp::new()
+        .unwrap()
+        .with_frame_interval(Duration::from_millis(10))
+        .add_update(|world, delta| {
+            assert_eq!(delta, Duration::from_millis(10));
+            if let Some(frames) = world.resource_mut::<Frames>() {
+                frames.0 += 1;
+            }
+        });
+    app.world_mut().insert_resource(Frames::default());
+    app.world_mut().spawn();
+    let start = app.now();
+
+    app.tick_n(10).unwrap();
+    assert_eq!(app.world().resource::<Frames>().unwrap().0, 10);
+    assert_eq!(app.now() - start, Duration::from_millis(100));
+    assert_eq!(app.state().frame_stats.frames(), 10);
+    assert_eq!(app.state().frame_stats.entities(), 1);
+    assert!(app
+        .state()
+        .metrics
+        .render()
+        .contains("rustgine_frames_total 10"));
+}
+
+/// Verifies main-thread tasks run during the next tick.
+#[test]
+fn drains_main_thread_queue() {
+    let mut app = TestApp::new().unwrap();
+    let ran = Arc::new(Mutex::new(false));
+    let flag = Arc::clone(&ran);
+    app.state()
+        .main_thread
+        .enqueue(move || *flag.lock().unwrap() = true);
+    assert!(!*ran.lock().unwrap());
+    app.tick().unwrap();
+    assert!(*ran.lock().unwrap());
+}
+
+/// Verifies instances are independent of each other.
+#[test]
+fn instances_are_isolated() {
+    let mut server = TestApp::new().unwrap();
+    let mut client = TestApp::new().unwrap();
+    server.world_mut().spawn();
+
+    server.tick_n(5).unwrap();
+    client.tick_n(2).unwrap();
+    assert_eq!(server.state().frame_stats.frames(), 5);
+    assert_eq!(client.state().frame_stats.frames(), 2);
+    assert_eq!(server.world().len(), 1);
+    assert!(client.world().is_empty());
+}
@@ -0,0 +1,256 @@
+//! Main loop hang detection.
+//!
+//! The frame loop reports its progress on a [`Heartbeat`]: the stage it is
+//! in, the subsystem it is calling, and when it last completed a frame. A
+//! [`Watchdog`] thread checks the heartbeat and, when no frame completes
+//! within [`WatchdogConfig::timeout`], logs a [`HangReport`], sends
+//! [`AppEvent::FrameHang`], and optionally triggers shutdown.
+//!
+//! The watchdog runs on its own OS thread rather than as a task, so it keeps
+//! running when the hung loop blocks the async runtime.
+
+use crate::resources::{AppEvent, AppState};
+use rustgine_core::{Label, WatchdogConfig};
+use std::fmt;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+use web_time::Instant;
+
+/// Longest time between two heartbeat checks.
+const MAX_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Part of the engine lifecycle the main loop is in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FrameStage {
+    /// Starting the subsystems.
+    #[default]
+    Startup,
+    /// Recording frame statistics, liveness, and metrics.
+    Metrics,
+    /// Running closures from the main-thread queue.
+    MainThreadQueue,
+    /// Between frames, waiting for the next tick.
+    Idle,
+    /// Shutting the subsystems down.
+    Shutdown,
+}
+
+impl fmt::Display for FrameStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Startup => "startup",
+            Self::Metrics => "metrics",
+            Self::MainThreadQueue => "main-thread queue",
+            Self::Idle => "idle",
+            Self::Shutdown => "shutdown",
+        })
+    }
+}
+
+/// Progress of the main loop, as last reported.
+#[derive(Debug, Clone, Copy, Default)]
+struct Pulse {
+    stage: FrameStage,
+    system: Option<Label>,
+    last_system: Option<Label>,
+    last_frame: Option<Instant>,
+    frames: u64,
+}
+
+/// Main loop progress, updated by [`run`](crate::resources::run) and read by
+/// the [`Watchdog`].
+///
+/// # Example
+///
+/// ```
+/// use app::resources::{FrameStage, Heartbeat};
+/// use std::time::{Duration, Instant};
+///
+/// let heartbeat = Heartbeat::default();
+/// let start = Instant::now();
+/// heartbeat.beat(start);
+/// heartbeat.enter(FrameStage::MainThreadQueue);
+///
+/// let report = heartbeat.check(start + Duration::from_secs(3), Duration::from_secs(2)).unwrap();
+/// assert_eq!(report.stage, FrameStage::MainThreadQueue);
+/// assert_eq!(report.stalled_for, Duration::from_secs(3));
+/// ```
+#[derive(Debug, Default)]
+pub struct Heartbeat {
+    pulse: Mutex<Pulse>,
+}
+
+impl Heartbeat {
+    /// Records that the main loop entered `stage`.
+    pub fn enter(&self, stage: FrameStage) {
+        self.lock().stage = stage;
+    }
+
+    /// Records that the main loop is calling `system`, or no subsystem.
+    pub fn set_system(&self, system: Option<Label>) {
+        let mut pulse = self.lock();
+        if system.is_some() {
+            pulse.last_system = system;
+        }
+        pulse.system = system;
+    }
+
+    /// Records a frame completed at `now`, and the loop going idle.
+    pub fn beat(&self, now: Instant) {
+        let mut pulse = self.lock();
+        pulse.stage = FrameStage::Idle;
+        pulse.last_frame = Some(now);
+        pulse.frames += 1;
+    }
+
+    /// Returns the time of the last completed frame, or `None` before the
+    /// first.
+    #[must_use]
+    pub fn last_frame(&self) -> Option<Instant> {
+        self.lock().last_frame
+    }
+
+    /// Returns a report if no frame completed within `timeout` before `now`.
+    ///
+    /// Nothing is reported before the first frame.
+    #[must_use]
+    pub fn check(&self, now: Instant, timeout: Duration) -> Option<HangReport> {
+        self.check_since(now, timeout, None)
+    }
+
+    /// Like [`check`](Self::check), but measures from `armed` until the
+    /// first frame.
+    fn check_since(
+        &self,
+        now: Instant,
+        timeout: Duration,
+        armed: Option<Instant>,
+    ) -> Option<HangReport> {
+        let pulse = *self.lock();
+        let stalled_for = now.saturating_duration_since(pulse.last_frame.or(armed)?);
+        (stalled_for > timeout).then_some(HangReport {
+            stalled_for,
+            stage: pulse.stage,
+            system: pulse.system,
+            last_system: pulse.last_system,
+            frames: pulse.frames,
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Pulse> {
+        self.pulse.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// What the main loop was doing when it hung.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HangReport {
+    /// Time since the last completed frame.
+    pub stalled_for: Duration,
+    /// Stage the loop is stuck in.
+    pub stage: FrameStage,
+    /// Subsystem being called, if any.
+    pub system: Option<Label>,
+    /// Subsystem called most recently, possibly the one still running.
+    pub last_system: Option<Label>,
+    /// Frames completed before the hang.
+    pub frames: u64,
+}
+
+impl fmt::Display for HangReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "main loop hung for {:.1?} in stage {} after {} frames",
+            self.stalled_for, self.stage, self.frames
+        )?;
+        match (self.system, self.last_system) {
+            (Some(system), _) => write!(f, ", calling subsystem {system}"),
+            (None, Some(last)) => write!(f, ", last subsystem called: {last}"),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Thread watching a [`Heartbeat`] for hangs.
+///
+/// Each hang is reported once; the watchdog re-arms when the next frame
+/// completes. Dropping the watchdog stops its thread.
+#[derive(Debug)]
+pub struct Watchdog {
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Watchdog {
+    /// Starts watching `state`'s heartbeat with `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the thread cannot be spawned.
+    pub fn spawn(state: Arc<AppState>, config: WatchdogConfig) -> std::io::Result<Self> {
+        let (stop, stopped) = mpsc::channel();
+        let thread = std::thread::Builder::new()
+            .name("rustgine-watchdog".to_owned())
+            .spawn(move || watch(&state, &config, &stopped))?;
+        Ok(Self {
+            stop: Some(stop),
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        // Disconnecting the channel wakes the thread
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Checks the heartbeat until `stopped` disconnects.
+fn watch(state: &AppState, config: &WatchdogConfig, stopped: &mpsc::Receiver<()>) {
+    debug!(timeout = ?config.timeout, "watchdog started");
+    let armed = Instant::now();
+    let interval = (config.timeout / 4).min(MAX_CHECK_INTERVAL);
+    // Frame the last reported hang followed, `None` before the first frame
+    let mut reported: Option<Option<Instant>> = None;
+
+    while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+        let last_frame = state.heartbeat.last_frame();
+        if reported == Some(last_frame) {
+            continue;
+        }
+        if let Some(report) =
+            state
+                .heartbeat
+                .check_since(Instant::now(), config.timeout, Some(armed))
+        {
+            reported = Some(last_frame);
+            report_hang(state, config, report);
+        }
+    }
+    debug!("watchdog stopped");
+}
+
+/// Logs `report`, sends it as an event, and shuts down if configured to.
+fn report_hang(state: &AppState, config: &WatchdogConfig, report: HangReport) {
+    error!(
+        stalled_ms = u64::try_from(report.stalled_for.as_millis()).unwrap_or(u64::MAX),
+        stage = %report.stage,
+        system = report.system.map(tracing::field::display),
+        last_system = report.last_system.map(tracing::field::display),
+        frames = report.frames,
+        "{report}"
+    );
+    state.events.send(AppEvent::FrameHang(report));
+    if config.shutdown_on_hang {
+        warn!("shutting down after main loop hang");
+        state.shutdown.trigger();
+    }
+}
@@ -0,0 +1,101 @@
+//! Unit tests for main loop hang detection.
+
+use super::{FrameStage, HangReport, Heartbeat, Watchdog};
+use crate::resources::{AppEvent, AppState};
+use rustgine_core::{Config, WatchdogConfig};
+use std::time::{Duration, Instant};
+use tokio::time::timeout;
+
+/// Verifies a hang is reported with the stage and subsystem it is stuck in,
+/// and only once the timeout passes.
+#[test]
+fn check_reports_stage_and_system() {
+    let heartbeat = Heartbeat::default();
+    let start = Instant::now();
+    let budget = Duration::from_secs(1);
+    assert_eq!(
+        heartbeat.check(start + Duration::from_mins(1), budget),
+        None
+    );
+
+    heartbeat.beat(start);
+    heartbeat.beat(start);
+    heartbeat.enter(FrameStage::Shutdown);
+    heartbeat.set_system(Some("audio".into()));
+    assert_eq!(heartbeat.check(start + budget, budget), None);
+
+    let report = heartbeat
+        .check(start + Duration::from_secs(2), budget)
+        .unwrap();
+    assert_eq!(
+        report,
+        HangReport {
+            stalled_for: Duration::from_secs(2),
+            stage: FrameStage::Shutdown,
+            system: Some("audio".into()),
+            last_system: Some("audio".into()),
+            frames: 2,
+        }
+    );
+    assert_eq!(
+        report.to_string(),
+        "main loop hung for 2.0s in stage shutdown after 2 frames, calling subsystem audio"
+    );
+}
+
+/// Verifies a completed frame leaves the loop idle but remembers the last
+/// subsystem called.
+#[test]
+fn beat_resets_stage() {
+    let heartbeat = Heartbeat::default();
+    let start = Instant::now();
+    heartbeat.enter(FrameStage::Startup);
+    heartbeat.set_system(Some("render".into()));
+    heartbeat.set_system(None);
+    heartbeat.enter(FrameStage::MainThreadQueue);
+    heartbeat.beat(start);
+
+    let report = heartbeat
+        .check(start + Duration::from_secs(2), Duration::from_secs(1))
+        .unwrap();
+    assert_eq!(report.stage, FrameStage::Idle);
+    assert_eq!(report.system, None);
+    assert!(report
+        .to_string()
+        .ends_with("last subsystem called: render"));
+}
+
+/// Verifies the watchdog thread sends `FrameHang` once per hang and
+/// triggers shutdown when configured to.
+#[tokio::test]
+async fn watchdog_reports_hang_and_shuts_down() {
+    let state = AppState::initialize(&Config::default()).unwrap();
+    state.heartbeat.beat(Instant::now());
+    state.heartbeat.enter(FrameStage::MainThreadQueue);
+    let mut events = state.events.subscribe();
+    let mut shutdown = state.shutdown.subscribe();
+
+    let config = WatchdogConfig {
+        timeout: Duration::from_millis(20),
+        shutdown_on_hang: true,
+    };
+    let watchdog = Watchdog::spawn(std::sync::Arc::clone(&state), config).unwrap();
+
+    let event = timeout(Duration::from_secs(5), events.recv())
+        .await
+        .expect("no hang reported")
+        .unwrap();
+    let AppEvent::FrameHang(report) = event else {
+        panic!("unexpected event {event:?}");
+    };
+    assert_eq!(report.stage, FrameStage::MainThreadQueue);
+    assert_eq!(report.frames, 1);
+    timeout(Duration::from_secs(5), shutdown.recv())
+        .await
+        .expect("shutdown not triggered");
+
+    // Still hung on the same frame, so nothing more is reported
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    drop(watchdog);
+    assert!(events.try_recv().is_err());
+}
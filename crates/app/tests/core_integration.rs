@@ -1,8 +1,25 @@
 use app::resources::Shutdown;
+use app::resources::{AppEvent, AppState, TestApp};
+use platform::RustginePlatform;
 use rustgine_core::init_tracing;
+use rustgine_core::testing::{CallLog, Phase, RecordingSystem};
 use rustgine_core::Config;
+use rustgine_core::{RustgineError, RustgineSystem};
 use tracing::info;
 
+#[derive(Debug)]
+struct LostDevice;
+
+impl RustgineSystem for LostDevice {
+    fn startup(&mut self) -> Result<(), RustgineError> {
+        Err(RustgineError::DeviceLost)
+    }
+
+    fn shutdown(&mut self) -> Result<(), RustgineError> {
+        Ok(())
+    }
+}
+
 #[test]
 fn test_config_load() {
     // Should load default config or error if missing
@@ -26,3 +43,266 @@ async fn test_shutdown_trigger_and_subscribe() {
     // Should complete without hanging
     rx.recv().await;
 }
+
+#[tokio::test]
+async fn test_console_engine_commands() {
+    let state = AppState::initialize(&Config::default()).unwrap();
+    state.register_system("platform", RustginePlatform).unwrap();
+    assert_eq!(
+        state.console.execute("systems").unwrap(),
+        "platform (enabled)"
+    );
+    assert_eq!(
+        state.console.execute("version").unwrap(),
+        state.build.to_string()
+    );
+
+    let mut rx = state.shutdown.subscribe();
+    state.console.execute("quit").unwrap();
+    // Should complete without hanging
+    rx.recv().await;
+}
+
+#[tokio::test]
+async fn test_startup_failure_reports_system() {
+    let state = AppState::initialize(&Config::default()).unwrap();
+    state.register_system("render", LostDevice).unwrap();
+
+    let error = app::resources::run(state).await.unwrap_err();
+    assert_eq!(error.system_name(), Some("render"));
+    let RustgineError::SystemStartupError { source, .. } = error else {
+        panic!("expected a startup error, got {error:?}");
+    };
+    assert!(matches!(
+        source.downcast_ref::<RustgineError>(),
+        Some(RustgineError::DeviceLost)
+    ));
+}
+
+#[tokio::test]
+async fn test_run_starts_in_order_and_stops_in_reverse() {
+    let log = CallLog::new();
+    let state = AppState::initialize(&Config::default()).unwrap();
+    for name in ["platform", "render", "audio"] {
+        state
+            .register_system(name, RecordingSystem::new(name, &log))
+            .unwrap();
+    }
+    let shutdown = state.shutdown.clone();
+    state.main_thread.enqueue(move || shutdown.trigger());
+
+    app::resources::run(state).await.unwrap();
+    assert_eq!(log.systems(Phase::Startup), ["platform", "render", "audio"]);
+    assert_eq!(
+        log.systems(Phase::Shutdown),
+        ["audio", "render", "platform"]
+    );
+}
+
+#[tokio::test]
+async fn test_startup_failure_skips_later_systems() {
+    let log = CallLog::new();
+    let state = AppState::initialize(&Config::default()).unwrap();
+    state
+        .register_system("platform", RecordingSystem::new("platform", &log))
+        .unwrap();
+    state
+        .register_system(
+            "render",
+            RecordingSystem::new("render", &log).failing_on(Phase::Startup),
+        )
+        .unwrap();
+    state
+        .register_system("audio", RecordingSystem::new("audio", &log))
+        .unwrap();
+
+    let error = app::resources::run(state).await.unwrap_err();
+    assert_eq!(error.system_name(), Some("render"));
+    assert_eq!(log.systems(Phase::Startup), ["platform", "render"]);
+    assert_eq!(log.count("audio", Phase::Startup), 0);
+    assert_eq!(log.systems(Phase::Shutdown), ["platform"]);
+}
+
+#[tokio::test]
+async fn test_panicking_startup_recovers_registry() {
+    let log = CallLog::new();
+    let state = AppState::initialize(&Config::default()).unwrap();
+    state
+        .register_system("platform", RecordingSystem::new("platform", &log))
+        .unwrap();
+    state
+        .register_system(
+            "render",
+            RecordingSystem::new("render", &log).panicking_on(Phase::Startup),
+        )
+        .unwrap();
+    let mut events = state.events.subscribe();
+
+    let result = tokio::spawn(app::resources::run(std::sync::Arc::clone(&state))).await;
+    assert!(result.unwrap_err().is_panic());
+    assert!(state.rustgine_systems.is_poisoned());
+
+    // The registry is recovered once, then keeps working
+    assert_eq!(state.system_count(), 2);
+    assert!(!state.rustgine_systems.is_poisoned());
+    assert_eq!(events.try_recv(), Ok(AppEvent::SystemRegistryCorrupted));
+    assert_eq!(
+        state.console.execute("systems").unwrap(),
+        "platform (enabled)\nrender (enabled)"
+    );
+    assert!(events.try_recv().is_err());
+    assert_eq!(log.systems(Phase::Startup), ["platform", "render"]);
+}
+
+#[test]
+fn test_restart_system_cycles_one_system() {
+    let log = CallLog::new();
+    let mut app = TestApp::new()
+        .unwrap()
+        .add_system("platform", RecordingSystem::new("platform", &log))
+        .unwrap()
+        .add_system("render", RecordingSystem::new("render", &log))
+        .unwrap();
+    app.tick().unwrap();
+    log.clear();
+    let mut events = app.state().events.subscribe();
+
+    assert_eq!(
+        app.state().console.execute("restart render").unwrap(),
+        "restarted render"
+    );
+    assert_eq!(log.systems(Phase::Shutdown), ["render"]);
+    assert_eq!(log.systems(Phase::Startup), ["render"]);
+    assert_eq!(
+        events.try_recv(),
+        Ok(AppEvent::SystemRestarting {
+            name: "render".into()
+        })
+    );
+    assert_eq!(
+        events.try_recv(),
+        Ok(AppEvent::SystemRestarted {
+            name: "render".into()
+        })
+    );
+
+    // The engine keeps running and shuts the restarted system down once
+    app.tick_n(3).unwrap();
+    assert_eq!(app.state().frame_stats.frames(), 4);
+    app.shutdown().unwrap();
+    assert_eq!(log.count("render", Phase::Shutdown), 2);
+    assert_eq!(log.count("platform", Phase::Shutdown), 1);
+}
+
+#[test]
+fn test_restart_system_reports_failure() {
+    let log = CallLog::new();
+    let mut app = TestApp::new()
+        .unwrap()
+        .add_system(
+            "audio",
+            RecordingSystem::new("audio", &log).failing_on(Phase::Shutdown),
+        )
+        .unwrap();
+    app.tick().unwrap();
+    let mut events = app.state().events.subscribe();
+
+    assert!(matches!(
+        app.state().restart_system("audio"),
+        Err(RustgineError::SystemShutdownError { .. })
+    ));
+    assert_eq!(log.count("audio", Phase::Startup), 1);
+    assert_eq!(
+        events.try_recv(),
+        Ok(AppEvent::SystemRestarting {
+            name: "audio".into()
+        })
+    );
+    assert_eq!(
+        events.try_recv(),
+        Ok(AppEvent::SystemRestartFailed {
+            name: "audio".into()
+        })
+    );
+
+    assert!(app.state().restart_system("physics").is_err());
+    assert!(events.try_recv().is_err());
+}
+
+#[test]
+fn test_warnings_command_reads_log_buffer() {
+    let state = AppState::initialize(&Config::default()).unwrap();
+    state
+        .log_buffer
+        .push(tracing::Level::INFO, "app", "frame ticked", "");
+    state
+        .log_buffer
+        .push(tracing::Level::WARN, "render", "slow frame", " ms=40");
+    assert_eq!(
+        state.console.execute("warnings").unwrap(),
+        "WARN render: slow frame ms=40"
+    );
+}
+
+#[test]
+fn test_cvars_seeded_from_config_file() {
+    let path = std::env::temp_dir().join(format!("rustgine-cvars-{}.toml", std::process::id()));
+    std::fs::write(&path, "\"r.vsync\" = false\n").unwrap();
+    let config = Config {
+        cvar_file: Some(path.clone()),
+        ..Config::default()
+    };
+
+    let state = AppState::initialize(&config).unwrap();
+    let vsync = state
+        .cvars
+        .register("r.vsync", true, "Wait for vblank")
+        .unwrap();
+    assert!(!vsync.get());
+    assert_eq!(
+        state.console.execute("r.vsync on").unwrap(),
+        "r.vsync = true"
+    );
+
+    state.cvars.save_file(&path).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+    std::fs::remove_file(path).unwrap();
+}
+
+#[cfg(feature = "render")]
+#[test]
+fn test_suspend_pauses_rendering_and_resume_recreates_surface() {
+    use winit::event::Event;
+
+    let log = CallLog::new();
+    let mut app = TestApp::new()
+        .unwrap()
+        .add_system("platform", RecordingSystem::new("platform", &log))
+        .unwrap()
+        .add_system("render", RecordingSystem::new("render", &log))
+        .unwrap();
+    app.tick().unwrap();
+    let state = app.state().clone();
+
+    // The launch resume finds the surface the renderer started with
+    state.handle_lifecycle_event(&Event::<()>::Resumed).unwrap();
+    assert!(!state.rendering_paused.is_paused());
+    log.clear();
+
+    state
+        .handle_lifecycle_event(&Event::<()>::Suspended)
+        .unwrap();
+    assert!(state.rendering_paused.is_paused());
+    assert_eq!(log.systems(Phase::Shutdown), ["render"]);
+    app.tick_n(2).unwrap();
+
+    state.handle_lifecycle_event(&Event::<()>::Resumed).unwrap();
+    assert!(!state.rendering_paused.is_paused());
+    assert_eq!(log.systems(Phase::Startup), ["render"]);
+
+    app.shutdown().unwrap();
+    assert_eq!(
+        log.systems(Phase::Shutdown),
+        ["render", "render", "platform"]
+    );
+}
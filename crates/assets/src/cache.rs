@@ -0,0 +1,352 @@
+//! The asset cache and its memory budget.
+
+use crate::handle::{Asset, Handle, LoadedAsset, WeakHandle};
+use crate::report::{AssetMemoryReport, AssetTypeMemory};
+use anyhow::Context;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Default CPU memory budget of an [`AssetCache`], 512 MiB.
+pub const DEFAULT_BUDGET_BYTES: u64 = 512 << 20;
+
+/// How an [`AssetCache`] frees memory when over budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EvictionPolicy {
+    /// Evicts the least recently used assets not held by a strong handle.
+    #[default]
+    Lru,
+    /// Never evicts; assets stay cached until removed.
+    Never,
+}
+
+/// One cached asset.
+struct Entry {
+    asset: Arc<dyn Any + Send + Sync>,
+    kind: &'static str,
+    bytes: u64,
+    last_used: u64,
+}
+
+impl Entry {
+    /// Returns whether a handle outside the cache keeps the asset loaded.
+    fn is_strong(&self) -> bool {
+        Arc::strong_count(&self.asset) > 1
+    }
+}
+
+/// Loaded assets keyed by path, with their CPU memory counted against a
+/// budget.
+///
+/// Strong [`Handle`]s keep an asset loaded. Once only [`WeakHandle`]s
+/// remain, the asset stays cached for fast reuse until the cache runs over
+/// budget, when such assets are evicted least recently used first.
+///
+/// # Example
+///
+/// ```
+/// use assets::AssetCache;
+///
+/// let mut cache = AssetCache::new(1000);
+/// let level = cache.insert("levels/1.txt", "x".repeat(800));
+/// let weak = level.downgrade();
+/// drop(level);
+///
+/// // Over budget: the weak-only level is evicted to make room.
+/// let _menu = cache.insert("ui/menu.txt", "y".repeat(400));
+/// assert!(!weak.is_loaded());
+/// assert_eq!(cache.memory_bytes(), 400);
+/// ```
+pub struct AssetCache {
+    entries: HashMap<Arc<str>, Entry>,
+    budget: u64,
+    policy: EvictionPolicy,
+    used: u64,
+    clock: u64,
+    evicted: u64,
+}
+
+impl Default for AssetCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_BUDGET_BYTES)
+    }
+}
+
+impl AssetCache {
+    /// Creates an empty cache with a budget of `budget` bytes.
+    #[must_use]
+    pub fn new(budget: u64) -> Self {
+        Self {
+            entries: HashMap::new(),
+            budget,
+            policy: EvictionPolicy::default(),
+            used: 0,
+            clock: 0,
+            evicted: 0,
+        }
+    }
+
+    /// Sets how memory is freed when over budget.
+    #[must_use]
+    pub fn with_policy(mut self, policy: EvictionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns the budget in bytes.
+    #[must_use]
+    pub fn budget(&self) -> u64 {
+        self.budget
+    }
+
+    /// Changes the budget, evicting assets if it shrank below the memory
+    /// in use.
+    pub fn set_budget(&mut self, budget: u64) {
+        self.budget = budget;
+        self.enforce_budget();
+    }
+
+    /// Returns the CPU memory of every cached asset, in bytes.
+    #[must_use]
+    pub fn memory_bytes(&self) -> u64 {
+        self.used
+    }
+
+    /// Returns whether the cached assets exceed the budget.
+    #[must_use]
+    pub fn is_over_budget(&self) -> bool {
+        self.used > self.budget
+    }
+
+    /// Returns the number of cached assets.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether no asset is cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns whether an asset is cached under `path`.
+    #[must_use]
+    pub fn contains(&self, path: &str) -> bool {
+        self.entries.contains_key(path)
+    }
+
+    /// Caches `asset` under `path`, replacing any asset cached there, and
+    /// returns a strong handle to it.
+    ///
+    /// Handles to a replaced asset keep it alive, but it no longer counts
+    /// against the budget.
+    pub fn insert<T: Asset>(&mut self, path: &str, asset: T) -> Handle<T> {
+        let bytes = asset.memory_bytes();
+        let asset = Arc::new(asset);
+        let path: Arc<str> = Arc::from(path);
+        let erased = Arc::clone(&asset) as Arc<dyn Any + Send + Sync>;
+        self.insert_entry(Arc::clone(&path), erased, T::kind(), bytes);
+        Handle::new(path, asset)
+    }
+
+    /// Caches an asset loaded without its type being known, returning the
+    /// reference that keeps it loaded.
+    pub(crate) fn insert_loaded(
+        &mut self,
+        path: &str,
+        loaded: LoadedAsset,
+    ) -> Arc<dyn Any + Send + Sync> {
+        let asset = Arc::clone(&loaded.asset);
+        self.insert_entry(Arc::from(path), loaded.asset, loaded.kind, loaded.bytes);
+        asset
+    }
+
+    /// Returns the reference that keeps the asset under `path` loaded,
+    /// marking it as recently used.
+    pub(crate) fn get_loaded(&mut self, path: &str) -> Option<Arc<dyn Any + Send + Sync>> {
+        let asset = Arc::clone(&self.entries.get(path)?.asset);
+        self.touch(path);
+        Some(asset)
+    }
+
+    /// Removes the asset under `path` if no handle outside the cache holds
+    /// it, returning whether it was removed.
+    pub(crate) fn unload_unused(&mut self, path: &str) -> bool {
+        if self
+            .entries
+            .get(path)
+            .is_some_and(|entry| !entry.is_strong())
+        {
+            self.remove(path)
+        } else {
+            false
+        }
+    }
+
+    /// Adds an entry and enforces the budget, keeping `asset` itself.
+    fn insert_entry(
+        &mut self,
+        path: Arc<str>,
+        asset: Arc<dyn Any + Send + Sync>,
+        kind: &'static str,
+        bytes: u64,
+    ) {
+        self.clock += 1;
+        let entry = Entry {
+            asset,
+            kind,
+            bytes,
+            last_used: self.clock,
+        };
+        if let Some(old) = self.entries.insert(path, entry) {
+            self.used -= old.bytes;
+        }
+        self.used += bytes;
+        self.enforce_budget();
+    }
+
+    /// Returns a strong handle to the asset cached under `path`, marking it
+    /// as recently used.
+    ///
+    /// `None` if nothing is cached there or the asset is not a `T`.
+    pub fn get<T: Asset>(&mut self, path: &str) -> Option<Handle<T>> {
+        let (path, entry) = self.entries.get_key_value(path)?;
+        let asset = Arc::clone(&entry.asset).downcast::<T>().ok()?;
+        let path = Arc::clone(path);
+        self.touch(&path);
+        Some(Handle::new(path, asset))
+    }
+
+    /// Returns a weak handle to the asset cached under `path`, without
+    /// marking it as used.
+    #[must_use]
+    pub fn get_weak<T: Asset>(&self, path: &str) -> Option<WeakHandle<T>> {
+        let (path, entry) = self.entries.get_key_value(path)?;
+        let asset = Arc::clone(&entry.asset).downcast::<T>().ok()?;
+        Some(Handle::new(Arc::clone(path), asset).downgrade())
+    }
+
+    /// Returns the asset cached under `path`, loading and caching it with
+    /// `load` if absent.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `load`, or an error if the cached asset is not
+    /// a `T`.
+    pub fn get_or_load<T: Asset>(
+        &mut self,
+        path: &str,
+        load: impl FnOnce() -> anyhow::Result<T>,
+    ) -> anyhow::Result<Handle<T>> {
+        if let Some(handle) = self.get(path) {
+            return Ok(handle);
+        }
+        if let Some(entry) = self.entries.get(path) {
+            anyhow::bail!(
+                "asset `{path}` is cached as {}, not {}",
+                entry.kind,
+                T::kind()
+            );
+        }
+        let asset = load().with_context(|| format!("failed to load asset `{path}`"))?;
+        Ok(self.insert(path, asset))
+    }
+
+    /// Removes the asset cached under `path`.
+    ///
+    /// Strong handles keep the asset alive, untracked. Returns whether an
+    /// asset was cached.
+    pub fn remove(&mut self, path: &str) -> bool {
+        let Some(entry) = self.entries.remove(path) else {
+            return false;
+        };
+        self.used -= entry.bytes;
+        true
+    }
+
+    /// Evicts assets held by no strong handle, least recently used first,
+    /// until the cache is within budget. Runs after every insertion.
+    ///
+    /// Returns the evicted paths. Does nothing with
+    /// [`EvictionPolicy::Never`].
+    pub fn enforce_budget(&mut self) -> Vec<String> {
+        if !self.is_over_budget() || self.policy == EvictionPolicy::Never {
+            return Vec::new();
+        }
+        let mut candidates: Vec<(u64, Arc<str>)> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| !entry.is_strong())
+            .map(|(path, entry)| (entry.last_used, Arc::clone(path)))
+            .collect();
+        candidates.sort_unstable();
+
+        let mut evicted = Vec::new();
+        for (_, path) in candidates {
+            if !self.is_over_budget() {
+                break;
+            }
+            if let Some(entry) = self.entries.remove(&path) {
+                self.used -= entry.bytes;
+                self.evicted += 1;
+                debug!(target: "assets", %path, bytes = entry.bytes, "evicted asset");
+                evicted.push(path.to_string());
+            }
+        }
+        if self.is_over_budget() {
+            warn!(
+                target: "assets",
+                used = self.used,
+                budget = self.budget,
+                "asset memory over budget with every remaining asset strongly held"
+            );
+        }
+        evicted
+    }
+
+    /// Returns the memory breakdown by asset type.
+    #[must_use]
+    pub fn report(&self) -> AssetMemoryReport {
+        let mut types: HashMap<&'static str, AssetTypeMemory> = HashMap::new();
+        for entry in self.entries.values() {
+            let memory = types.entry(entry.kind).or_insert_with(|| AssetTypeMemory {
+                kind: entry.kind.to_owned(),
+                assets: 0,
+                strong: 0,
+                bytes: 0,
+            });
+            memory.assets += 1;
+            memory.strong += u32::from(entry.is_strong());
+            memory.bytes += entry.bytes;
+        }
+        let mut types: Vec<AssetTypeMemory> = types.into_values().collect();
+        types.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.kind.cmp(&b.kind)));
+        AssetMemoryReport {
+            used_bytes: self.used,
+            budget_bytes: self.budget,
+            evicted: self.evicted,
+            types,
+        }
+    }
+
+    /// Marks the asset under `path` as used now.
+    fn touch(&mut self, path: &str) {
+        self.clock += 1;
+        if let Some(entry) = self.entries.get_mut(path) {
+            entry.last_used = self.clock;
+        }
+    }
+}
+
+impl std::fmt::Debug for AssetCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AssetCache")
+            .field("assets", &self.entries.len())
+            .field("used", &self.used)
+            .field("budget", &self.budget)
+            .field("policy", &self.policy)
+            .finish_non_exhaustive()
+    }
+}
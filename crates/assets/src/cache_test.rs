@@ -0,0 +1,125 @@
+//! Unit tests for the asset cache.
+
+use crate::cache::{AssetCache, EvictionPolicy};
+use crate::handle::Asset;
+
+/// A test asset of a fixed size.
+struct Blob(u64);
+
+impl Asset for Blob {
+    fn memory_bytes(&self) -> u64 {
+        self.0
+    }
+
+    fn kind() -> &'static str {
+        "blob"
+    }
+}
+
+/// Verifies cached assets are shared and their memory tracked.
+#[test]
+fn insert_and_get_share_assets() {
+    let mut cache = AssetCache::new(1000);
+    let a = cache.insert("a", Blob(100));
+    let b = cache.insert("b", Blob(200));
+    assert_eq!(cache.memory_bytes(), 300);
+    assert_eq!(cache.len(), 2);
+
+    let again = cache.get::<Blob>("a").unwrap();
+    assert!(again.ptr_eq(&a));
+    assert_eq!(again.path(), "a");
+    assert!(cache.get::<String>("a").is_none());
+    assert!(cache.get::<Blob>("missing").is_none());
+
+    cache.insert("b", Blob(50));
+    assert_eq!(cache.memory_bytes(), 150);
+    assert_eq!(b.0, 200);
+    assert!(cache.remove("a"));
+    assert!(!cache.remove("a"));
+    assert_eq!(cache.memory_bytes(), 50);
+}
+
+/// Verifies only weakly held assets are evicted, least recently used first.
+#[test]
+fn evicts_weak_assets_in_lru_order() {
+    let mut cache = AssetCache::new(300);
+    let old = cache.insert("old", Blob(100)).downgrade();
+    let used = cache.insert("used", Blob(100)).downgrade();
+    let held = cache.insert("held", Blob(100));
+    assert!(old.is_loaded() && used.is_loaded());
+
+    // Touching `used` makes `old` the least recently used.
+    drop(cache.get::<Blob>("used"));
+    let _new = cache.insert("new", Blob(100));
+    assert!(!old.is_loaded());
+    assert!(old.upgrade().is_none());
+    assert!(used.upgrade().is_some());
+    assert!(cache.contains("held"));
+    assert_eq!(cache.memory_bytes(), 300);
+    assert_eq!(cache.report().evicted, 1);
+    drop(held);
+}
+
+/// Verifies strongly held assets are kept even over budget.
+#[test]
+fn strong_handles_are_never_evicted() {
+    let mut cache = AssetCache::new(100);
+    let a = cache.insert("a", Blob(80));
+    let b = cache.insert("b", Blob(80));
+    assert!(cache.is_over_budget());
+    assert_eq!(cache.len(), 2);
+
+    drop(a);
+    assert_eq!(cache.enforce_budget(), ["a"]);
+    assert!(!cache.is_over_budget());
+    assert!(cache.get_weak::<Blob>("b").unwrap().is_loaded());
+    drop(b);
+}
+
+/// Verifies shrinking the budget evicts and the `Never` policy keeps all.
+#[test]
+fn set_budget_and_never_policy() {
+    let mut cache = AssetCache::new(1000);
+    for path in ["a", "b", "c"] {
+        cache.insert(path, Blob(100));
+    }
+    cache.set_budget(150);
+    assert_eq!(cache.len(), 1);
+    assert!(cache.contains("c"));
+
+    let mut cache = AssetCache::new(150).with_policy(EvictionPolicy::Never);
+    for path in ["a", "b", "c"] {
+        cache.insert(path, Blob(100));
+    }
+    assert_eq!(cache.len(), 3);
+    assert!(cache.enforce_budget().is_empty());
+}
+
+/// Verifies assets load once and load errors name the path.
+#[test]
+fn get_or_load_caches_results() {
+    let mut cache = AssetCache::default();
+    let mut loads = 0;
+    for _ in 0..2 {
+        cache
+            .get_or_load("a", || {
+                loads += 1;
+                Ok(Blob(10))
+            })
+            .unwrap();
+    }
+    assert_eq!(loads, 1);
+
+    let error = cache
+        .get_or_load::<Blob>("broken", || anyhow::bail!("bad header"))
+        .unwrap_err();
+    assert!(
+        format!("{error:#}").contains("`broken`: bad header"),
+        "{error:#}"
+    );
+    let error = cache.get_or_load("a", || Ok(String::new())).unwrap_err();
+    assert!(
+        error.to_string().contains("cached as blob, not text"),
+        "{error}"
+    );
+}
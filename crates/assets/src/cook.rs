@@ -0,0 +1,306 @@
+//! Incremental cooking of source assets into a build cache.
+
+use crate::database::AssetDatabase;
+use crate::id::AssetId;
+use crate::meta::AssetMeta;
+use anyhow::Context;
+use scheduler::JobGraph;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+/// File in the build directory recording the content hash of every cooked
+/// asset.
+pub const CACHE_MANIFEST: &str = "cook-cache.toml";
+
+/// Extension of cooked files, named by asset ID in the build directory.
+pub const COOKED_EXTENSION: &str = "cooked";
+
+/// FNV-1a 64-bit offset basis.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a 64-bit prime.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Turns the source of one kind of asset into its cooked form.
+pub trait Cooker: Send + Sync {
+    /// Returns the version of the cooked output.
+    ///
+    /// The version is part of every content hash, so bumping it after
+    /// changing the output cooks every asset of the kind again.
+    fn version(&self) -> u32;
+
+    /// Cooks the `source` bytes of an asset with the importer settings of
+    /// `meta`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source is invalid.
+    fn cook(&self, source: &[u8], meta: &AssetMeta) -> anyhow::Result<Vec<u8>>;
+}
+
+/// Copies sources unchanged, for importers without a cooker.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CopyCooker;
+
+impl Cooker for CopyCooker {
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn cook(&self, source: &[u8], _meta: &AssetMeta) -> anyhow::Result<Vec<u8>> {
+        Ok(source.to_vec())
+    }
+}
+
+/// What a [`AssetCooker::cook`] run did.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CookReport {
+    /// Assets cooked because they were new or changed, in path order.
+    pub cooked: Vec<PathBuf>,
+    /// Assets whose cached output was reused.
+    pub up_to_date: usize,
+    /// Cached outputs deleted because their asset is gone.
+    pub removed: usize,
+    /// Assets that failed to cook, with the error, in path order.
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+impl fmt::Display for CookReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cooked {}, up to date {}, removed {}, failed {}",
+            self.cooked.len(),
+            self.up_to_date,
+            self.removed,
+            self.failed.len()
+        )
+    }
+}
+
+/// The build cache manifest.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    assets: BTreeMap<AssetId, ManifestEntry>,
+}
+
+/// Cached output of one asset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManifestEntry {
+    /// Source path when cooked, for reading the manifest.
+    source: String,
+    /// Content hash, as 16 hex digits.
+    hash: String,
+}
+
+/// Result of cooking one asset.
+enum Outcome {
+    Cooked,
+    UpToDate,
+    Failed(String),
+}
+
+/// Cooks the assets of an [`AssetDatabase`] into a build directory,
+/// skipping assets whose output is up to date.
+///
+/// Each asset is keyed by a content hash of its source bytes, its meta file
+/// (ID and importer settings), and the version of its cooker, recorded in
+/// the [`CACHE_MANIFEST`] of the build directory. Only assets whose hash
+/// changed or whose output is missing are cooked, in parallel on the
+/// scheduler's thread pool; one asset failing does not stop the others.
+///
+/// # Example
+///
+/// ```
+/// use assets::{AssetCooker, AssetDatabase};
+///
+/// let root = std::env::temp_dir().join(format!("rustgine-doc-cook-{}", std::process::id()));
+/// std::fs::create_dir_all(root.join("src"))?;
+/// std::fs::write(root.join("src/intro.txt"), "Once upon a time")?;
+///
+/// let database = AssetDatabase::scan(root.join("src"))?;
+/// let cooker = AssetCooker::new(root.join("build"));
+/// assert_eq!(cooker.cook(&database)?.cooked.len(), 1);
+/// assert_eq!(cooker.cook(&database)?.up_to_date, 1);
+/// # std::fs::remove_dir_all(&root)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct AssetCooker {
+    build_dir: PathBuf,
+    cookers: HashMap<&'static str, Box<dyn Cooker>>,
+    fallback: Box<dyn Cooker>,
+}
+
+impl fmt::Debug for AssetCooker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut kinds: Vec<_> = self.cookers.keys().collect();
+        kinds.sort_unstable();
+        f.debug_struct("AssetCooker")
+            .field("build_dir", &self.build_dir)
+            .field("cookers", &kinds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AssetCooker {
+    /// Creates a cooker writing to `build_dir` that copies every asset
+    /// until cookers are added.
+    #[must_use]
+    pub fn new(build_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            build_dir: build_dir.into(),
+            cookers: HashMap::new(),
+            fallback: Box::new(CopyCooker),
+        }
+    }
+
+    /// Cooks assets whose importer is `kind`, such as `"texture"`, with
+    /// `cooker`.
+    #[must_use]
+    pub fn with_cooker(mut self, kind: &'static str, cooker: impl Cooker + 'static) -> Self {
+        self.cookers.insert(kind, Box::new(cooker));
+        self
+    }
+
+    /// Returns the build directory.
+    #[must_use]
+    pub fn build_dir(&self) -> &Path {
+        &self.build_dir
+    }
+
+    /// Returns the cooked file of `id`.
+    #[must_use]
+    pub fn output_path(&self, id: AssetId) -> PathBuf {
+        self.build_dir.join(format!("{id}.{COOKED_EXTENSION}"))
+    }
+
+    /// Cooks the new and changed assets of `database`, deletes the outputs
+    /// of removed assets, and updates the manifest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the build directory or its manifest cannot be
+    /// written. Assets that fail to cook are listed in the report instead.
+    pub fn cook(&self, database: &AssetDatabase) -> anyhow::Result<CookReport> {
+        std::fs::create_dir_all(&self.build_dir)
+            .with_context(|| format!("failed to create {}", self.build_dir.display()))?;
+        let previous = self.load_manifest();
+
+        let results = Mutex::new(Vec::with_capacity(database.len()));
+        let mut graph = JobGraph::new();
+        for (path, meta) in database.iter() {
+            let previous = previous.assets.get(&meta.id);
+            let results = &results;
+            graph.add_job(&path.display().to_string(), &[], move || {
+                let (outcome, hash) = match self.cook_asset(database, path, meta, previous) {
+                    Ok((outcome, hash)) => (outcome, Some(hash)),
+                    Err(error) => (Outcome::Failed(format!("{error:#}")), None),
+                };
+                results
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    .push((path, meta.id, outcome, hash));
+                Ok(())
+            })?;
+        }
+        graph.run()?;
+
+        let mut results = results
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        results.sort_by(|a, b| a.0.cmp(b.0));
+        let mut report = CookReport::default();
+        let mut manifest = Manifest::default();
+        for (path, id, outcome, hash) in results {
+            match outcome {
+                Outcome::Cooked => report.cooked.push(path.to_owned()),
+                Outcome::UpToDate => report.up_to_date += 1,
+                Outcome::Failed(error) => {
+                    warn!(target: "assets", path = %path.display(), %error, "failed to cook asset");
+                    report.failed.push((path.to_owned(), error));
+                }
+            }
+            if let Some(hash) = hash {
+                let source = path.display().to_string();
+                let hash = format!("{hash:016x}");
+                manifest.assets.insert(id, ManifestEntry { source, hash });
+            }
+        }
+        for id in previous.assets.keys() {
+            if database.path(*id).is_none() {
+                let _ = std::fs::remove_file(self.output_path(*id));
+                report.removed += 1;
+            }
+        }
+
+        let path = self.build_dir.join(CACHE_MANIFEST);
+        let text = toml::to_string(&manifest)
+            .map_err(|e| anyhow::anyhow!("failed to serialize cook manifest: {e}"))?;
+        std::fs::write(&path, text)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        info!(target: "assets", %report, "cooked assets");
+        Ok(report)
+    }
+
+    /// Cooks one asset unless its cached output matches, returning the
+    /// content hash.
+    fn cook_asset(
+        &self,
+        database: &AssetDatabase,
+        path: &Path,
+        meta: &AssetMeta,
+        previous: Option<&ManifestEntry>,
+    ) -> anyhow::Result<(Outcome, u64)> {
+        let full = database.root().join(path);
+        let source =
+            std::fs::read(&full).with_context(|| format!("failed to read {}", full.display()))?;
+        let cooker = self
+            .cookers
+            .get(meta.importer.kind())
+            .unwrap_or(&self.fallback);
+        let hash = [
+            source.as_slice(),
+            meta.to_toml()?.as_bytes(),
+            &cooker.version().to_le_bytes(),
+        ]
+        .iter()
+        .fold(FNV_OFFSET, |hash, part| {
+            // The length keeps the parts from running into each other.
+            fnv1a(fnv1a(hash, &(part.len() as u64).to_le_bytes()), part)
+        });
+
+        let output = self.output_path(meta.id);
+        let unchanged = previous.is_some_and(|entry| entry.hash == format!("{hash:016x}"));
+        if unchanged && output.exists() {
+            return Ok((Outcome::UpToDate, hash));
+        }
+        std::fs::write(&output, cooker.cook(&source, meta)?)
+            .with_context(|| format!("failed to write {}", output.display()))?;
+        Ok((Outcome::Cooked, hash))
+    }
+
+    /// Reads the manifest of the previous run, starting over if it is
+    /// missing or unreadable.
+    fn load_manifest(&self) -> Manifest {
+        let path = self.build_dir.join(CACHE_MANIFEST);
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            return Manifest::default();
+        };
+        toml::from_str(&text).unwrap_or_else(|error| {
+            warn!(target: "assets", path = %path.display(), %error, "invalid cook manifest, cooking everything");
+            Manifest::default()
+        })
+    }
+}
+
+/// Continues the FNV-1a hash `hash` over `bytes`.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
@@ -0,0 +1,185 @@
+//! Unit tests for incremental cooking.
+
+use crate::cook::{AssetCooker, Cooker, CACHE_MANIFEST};
+use crate::database::AssetDatabase;
+use crate::meta::{AssetMeta, ImporterSettings, TextureCompression, TextureSettings};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Creates a project unique to `name` with `files` in its `src` directory.
+fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("rustgine-cook-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    for (file, contents) in files {
+        let path = root.join("src").join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+    root
+}
+
+/// Upper-cases text, failing on sources containing `!`.
+struct Shout(u32);
+
+impl Cooker for Shout {
+    fn version(&self) -> u32 {
+        self.0
+    }
+
+    fn cook(&self, source: &[u8], _meta: &AssetMeta) -> anyhow::Result<Vec<u8>> {
+        anyhow::ensure!(!source.contains(&b'!'), "no shouting twice");
+        Ok(source.to_ascii_uppercase())
+    }
+}
+
+/// Verifies only new and changed sources and settings are cooked again,
+/// and outputs of deleted sources are removed.
+#[test]
+fn cooks_only_changes() {
+    let root = project("changes", &[("a.txt", "a"), ("b.png", "b"), ("c.txt", "c")]);
+    let mut database = AssetDatabase::scan(root.join("src")).unwrap();
+    let cooker = AssetCooker::new(root.join("build"));
+
+    let report = cooker.cook(&database).unwrap();
+    assert_eq!(
+        report.to_string(),
+        "cooked 3, up to date 0, removed 0, failed 0"
+    );
+    let a = database.id(Path::new("a.txt")).unwrap();
+    assert_eq!(std::fs::read(cooker.output_path(a)).unwrap(), b"a");
+    assert_eq!(cooker.cook(&database).unwrap().up_to_date, 3);
+
+    std::fs::write(root.join("src/a.txt"), "changed").unwrap();
+    let b = database.id(Path::new("b.png")).unwrap();
+    let settings = TextureSettings {
+        compression: TextureCompression::Bc1,
+        ..TextureSettings::default()
+    };
+    database
+        .set_importer(b, ImporterSettings::Texture(settings))
+        .unwrap();
+    let report = cooker.cook(&database).unwrap();
+    assert_eq!(
+        report.cooked,
+        [PathBuf::from("a.txt"), PathBuf::from("b.png")]
+    );
+    assert_eq!(report.up_to_date, 1);
+    assert_eq!(std::fs::read(cooker.output_path(a)).unwrap(), b"changed");
+
+    std::fs::remove_file(root.join("src/a.txt")).unwrap();
+    database.refresh().unwrap();
+    let report = cooker.cook(&database).unwrap();
+    assert_eq!(report.removed, 1);
+    assert_eq!(report.up_to_date, 2);
+    assert!(!cooker.output_path(a).exists());
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+/// Verifies cookers are picked by importer and a version bump cooks their
+/// assets again.
+#[test]
+fn cooker_version_invalidates_its_assets() {
+    let root = project("version", &[("a.txt", "a"), ("b.png", "b")]);
+    let database = AssetDatabase::scan(root.join("src")).unwrap();
+    let a = database.id(Path::new("a.txt")).unwrap();
+    let b = database.id(Path::new("b.png")).unwrap();
+
+    let cooker = AssetCooker::new(root.join("build")).with_cooker("raw", Shout(1));
+    assert_eq!(cooker.cook(&database).unwrap().cooked.len(), 2);
+    assert_eq!(std::fs::read(cooker.output_path(a)).unwrap(), b"A");
+    assert_eq!(std::fs::read(cooker.output_path(b)).unwrap(), b"b");
+
+    let cooker = AssetCooker::new(root.join("build")).with_cooker("raw", Shout(2));
+    let report = cooker.cook(&database).unwrap();
+    assert_eq!(report.cooked, [PathBuf::from("a.txt")]);
+    assert_eq!(report.up_to_date, 1);
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+/// Verifies a failing asset is reported without stopping the others and is
+/// retried on the next run.
+#[test]
+fn failures_are_reported_and_retried() {
+    let root = project("failures", &[("bad.txt", "hey!"), ("good.txt", "hi")]);
+    let database = AssetDatabase::scan(root.join("src")).unwrap();
+    let cooker = AssetCooker::new(root.join("build")).with_cooker("raw", Shout(1));
+
+    let report = cooker.cook(&database).unwrap();
+    assert_eq!(report.cooked, [PathBuf::from("good.txt")]);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, Path::new("bad.txt"));
+    assert!(
+        report.failed[0].1.contains("no shouting twice"),
+        "{report:?}"
+    );
+
+    std::fs::write(root.join("src/bad.txt"), "hey").unwrap();
+    let report = cooker.cook(&database).unwrap();
+    assert_eq!(report.cooked, [PathBuf::from("bad.txt")]);
+    assert!(report.failed.is_empty());
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+/// Verifies missing outputs and an unreadable manifest cause a re-cook.
+#[test]
+fn missing_cache_cooks_again() {
+    let root = project("missing", &[("a.txt", "a"), ("b.txt", "b")]);
+    let database = AssetDatabase::scan(root.join("src")).unwrap();
+    let cooker = AssetCooker::new(root.join("build"));
+    cooker.cook(&database).unwrap();
+
+    let a = database.id(Path::new("a.txt")).unwrap();
+    std::fs::remove_file(cooker.output_path(a)).unwrap();
+    let report = cooker.cook(&database).unwrap();
+    assert_eq!(report.cooked, [PathBuf::from("a.txt")]);
+
+    std::fs::write(root.join("build").join(CACHE_MANIFEST), "assets = 3").unwrap();
+    assert_eq!(cooker.cook(&database).unwrap().cooked.len(), 2);
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+/// Records how many cooks run at once.
+struct Concurrency {
+    active: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl Cooker for Arc<Concurrency> {
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn cook(&self, source: &[u8], _meta: &AssetMeta) -> anyhow::Result<Vec<u8>> {
+        let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(active, Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(20));
+        self.active.fetch_sub(1, Ordering::SeqCst);
+        Ok(source.to_vec())
+    }
+}
+
+/// Verifies assets are cooked in parallel on the thread pool.
+#[test]
+fn cooks_in_parallel() {
+    let files: Vec<(String, &str)> = (0..8).map(|i| (format!("{i}.txt"), "x")).collect();
+    let files: Vec<(&str, &str)> = files.iter().map(|(f, c)| (f.as_str(), *c)).collect();
+    let root = project("parallel", &files);
+    let database = AssetDatabase::scan(root.join("src")).unwrap();
+    let concurrency = Arc::new(Concurrency {
+        active: AtomicUsize::new(0),
+        peak: AtomicUsize::new(0),
+    });
+    let cooker = AssetCooker::new(root.join("build")).with_cooker("raw", Arc::clone(&concurrency));
+
+    let report = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .unwrap()
+        .install(|| cooker.cook(&database))
+        .unwrap();
+    assert_eq!(report.cooked.len(), 8);
+    assert!(concurrency.peak.load(Ordering::SeqCst) >= 2);
+    std::fs::remove_dir_all(&root).unwrap();
+}
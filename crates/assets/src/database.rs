@@ -0,0 +1,277 @@
+//! The asset database: source files of a project by stable ID.
+
+use crate::cache::AssetCache;
+use crate::handle::{Asset, Handle};
+use crate::id::AssetId;
+use crate::meta::{meta_path, AssetMeta, ImporterSettings, META_EXTENSION};
+use anyhow::Context;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// What a [`AssetDatabase::refresh`] changed on disk.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ScanReport {
+    /// Source files imported for the first time, with a new meta file.
+    pub imported: Vec<PathBuf>,
+    /// Source files whose meta file repeated another asset's ID, usually
+    /// after copying a file with its meta; they were given a new ID.
+    pub reassigned: Vec<PathBuf>,
+    /// Meta files without a source file, left in place.
+    pub orphaned: Vec<PathBuf>,
+}
+
+/// The source files under an asset directory, indexed by [`AssetId`].
+///
+/// Scanning generates a `.meta` file for every source file without one, so
+/// each asset keeps its ID when it is renamed or moved along with its meta
+/// file. Paths are relative to the asset directory.
+///
+/// # Example
+///
+/// ```
+/// use assets::AssetDatabase;
+/// use std::path::Path;
+///
+/// let root = std::env::temp_dir().join(format!("rustgine-doc-assets-{}", std::process::id()));
+/// std::fs::create_dir_all(&root)?;
+/// std::fs::write(root.join("hero.png"), b"")?;
+///
+/// let mut database = AssetDatabase::scan(&root)?;
+/// let hero = database.id(Path::new("hero.png")).unwrap();
+/// assert!(root.join("hero.png.meta").exists());
+///
+/// database.move_asset(hero, Path::new("characters/hero.png"))?;
+/// let database = AssetDatabase::scan(&root)?;
+/// assert_eq!(database.path(hero), Some(Path::new("characters/hero.png")));
+/// # std::fs::remove_dir_all(&root)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct AssetDatabase {
+    root: PathBuf,
+    assets: BTreeMap<PathBuf, AssetMeta>,
+    paths: HashMap<AssetId, PathBuf>,
+}
+
+impl AssetDatabase {
+    /// Scans the asset directory `root`, importing new source files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read, a meta file is
+    /// invalid, or a new meta file cannot be written.
+    pub fn scan(root: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let mut database = Self {
+            root: root.into(),
+            assets: BTreeMap::new(),
+            paths: HashMap::new(),
+        };
+        database.refresh()?;
+        Ok(database)
+    }
+
+    /// Rescans the asset directory for added, moved, and removed files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read, a meta file is
+    /// invalid, or a new meta file cannot be written. The database is left
+    /// unchanged on error.
+    pub fn refresh(&mut self) -> anyhow::Result<ScanReport> {
+        let mut files = Vec::new();
+        collect_files(&self.root, Path::new(""), &mut files)?;
+        files.sort();
+        let (metas, sources): (Vec<PathBuf>, Vec<PathBuf>) = files.into_iter().partition(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == META_EXTENSION)
+        });
+
+        let mut report = ScanReport::default();
+        let mut assets = BTreeMap::new();
+        let mut paths = HashMap::new();
+        for source in sources {
+            let meta_file = self.root.join(meta_path(&source));
+            let mut meta = if meta_file.exists() {
+                AssetMeta::load(&meta_file)?
+            } else {
+                let meta = AssetMeta::new(&source);
+                meta.save(&meta_file)?;
+                report.imported.push(source.clone());
+                meta
+            };
+            if paths.contains_key(&meta.id) {
+                warn!(
+                    target: "assets",
+                    path = %source.display(),
+                    id = %meta.id,
+                    "duplicate asset ID, assigning a new one"
+                );
+                meta.id = AssetId::random();
+                meta.save(&meta_file)?;
+                report.reassigned.push(source.clone());
+            }
+            paths.insert(meta.id, source.clone());
+            assets.insert(source, meta);
+        }
+        for meta in metas {
+            if !assets.contains_key(&meta.with_extension("")) {
+                warn!(target: "assets", path = %meta.display(), "meta file without a source file");
+                report.orphaned.push(meta);
+            }
+        }
+
+        if !report.imported.is_empty() {
+            info!(target: "assets", count = report.imported.len(), "imported new assets");
+        }
+        self.assets = assets;
+        self.paths = paths;
+        Ok(report)
+    }
+
+    /// Returns the asset directory.
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the number of assets.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.assets.len()
+    }
+
+    /// Returns whether the directory holds no assets.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.assets.is_empty()
+    }
+
+    /// Returns the ID of the source file at `path`, relative to the root.
+    #[must_use]
+    pub fn id(&self, path: &Path) -> Option<AssetId> {
+        self.assets.get(path).map(|meta| meta.id)
+    }
+
+    /// Returns the source file of `id`, relative to the root.
+    #[must_use]
+    pub fn path(&self, id: AssetId) -> Option<&Path> {
+        self.paths.get(&id).map(PathBuf::as_path)
+    }
+
+    /// Returns the meta of `id`.
+    #[must_use]
+    pub fn meta(&self, id: AssetId) -> Option<&AssetMeta> {
+        self.assets.get(self.paths.get(&id)?)
+    }
+
+    /// Returns every asset path with its meta, in path order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Path, &AssetMeta)> + '_ {
+        self.assets
+            .iter()
+            .map(|(path, meta)| (path.as_path(), meta))
+    }
+
+    /// Changes the importer settings of `id` and saves its meta file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `id` is unknown or the meta file cannot be
+    /// written.
+    pub fn set_importer(&mut self, id: AssetId, importer: ImporterSettings) -> anyhow::Result<()> {
+        let path = self
+            .paths
+            .get(&id)
+            .with_context(|| format!("unknown asset {id}"))?;
+        let meta = self
+            .assets
+            .get_mut(path)
+            .with_context(|| format!("unknown asset {id}"))?;
+        meta.importer = importer;
+        meta.save(&self.root.join(meta_path(path)))
+    }
+
+    /// Moves or renames the source file of `id` to `to`, relative to the
+    /// root, together with its meta file, keeping its ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `id` is unknown, `to` already exists, or a file
+    /// cannot be moved.
+    pub fn move_asset(&mut self, id: AssetId, to: &Path) -> anyhow::Result<()> {
+        let from = self
+            .paths
+            .get(&id)
+            .cloned()
+            .with_context(|| format!("unknown asset {id}"))?;
+        let target = self.root.join(to);
+        anyhow::ensure!(!target.exists(), "{} already exists", target.display());
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        for (source, destination) in [
+            (from.clone(), to.to_owned()),
+            (meta_path(&from), meta_path(to)),
+        ] {
+            let (source, destination) = (self.root.join(source), self.root.join(destination));
+            std::fs::rename(&source, &destination).with_context(|| {
+                format!(
+                    "failed to move {} to {}",
+                    source.display(),
+                    destination.display()
+                )
+            })?;
+        }
+        if let Some(meta) = self.assets.remove(&from) {
+            self.assets.insert(to.to_owned(), meta);
+        }
+        self.paths.insert(id, to.to_owned());
+        Ok(())
+    }
+
+    /// Returns the asset `id` from `cache`, loading it from its source file
+    /// with `load` if absent.
+    ///
+    /// The asset is cached by ID, so moving its file does not load it
+    /// twice.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `id` is unknown or `load` fails.
+    pub fn load<T: Asset>(
+        &self,
+        cache: &mut AssetCache,
+        id: AssetId,
+        load: impl FnOnce(&Path, &AssetMeta) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Handle<T>> {
+        let path = self
+            .paths
+            .get(&id)
+            .with_context(|| format!("unknown asset {id}"))?;
+        let meta = &self.assets[path];
+        cache.get_or_load(&id.to_string(), || load(&self.root.join(path), meta))
+    }
+}
+
+/// Appends the files under `root.join(dir)` to `files`, relative to
+/// `root`, skipping hidden entries.
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    let full = root.join(dir);
+    let entries =
+        std::fs::read_dir(&full).with_context(|| format!("failed to read {}", full.display()))?;
+    for entry in entries {
+        let entry = entry.with_context(|| format!("failed to read {}", full.display()))?;
+        let name = entry.file_name();
+        if name.to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = dir.join(&name);
+        if entry.file_type()?.is_dir() {
+            collect_files(root, &path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,129 @@
+//! Unit tests for the asset database.
+
+use crate::cache::AssetCache;
+use crate::database::AssetDatabase;
+use crate::meta::{AssetMeta, ImporterSettings, SpriteSettings};
+use std::path::{Path, PathBuf};
+
+/// Creates an asset directory unique to `name` holding `files`.
+fn asset_dir(name: &str, files: &[&str]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rustgine-assets-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    for file in files {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, file.as_bytes()).unwrap();
+    }
+    dir
+}
+
+/// Verifies the first scan writes meta files and later scans keep IDs.
+#[test]
+fn scan_generates_stable_ids() {
+    let root = asset_dir("stable", &["hero.png", "levels/1.toml", ".git/HEAD"]);
+    let mut database = AssetDatabase::scan(&root).unwrap();
+    assert_eq!(database.len(), 2);
+    let hero = database.id(Path::new("hero.png")).unwrap();
+    assert!(root.join("hero.png.meta").exists());
+    assert!(root.join("levels/1.toml.meta").exists());
+    assert!(!root.join(".git/HEAD.meta").exists());
+
+    let report = database.refresh().unwrap();
+    assert!(report.imported.is_empty());
+    assert_eq!(database.id(Path::new("hero.png")), Some(hero));
+    assert_eq!(database.path(hero), Some(Path::new("hero.png")));
+
+    std::fs::write(root.join("intro.ogg"), b"").unwrap();
+    let report = database.refresh().unwrap();
+    assert_eq!(report.imported, [PathBuf::from("intro.ogg")]);
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+/// Verifies moving a file with its meta keeps its ID, both through the
+/// database and on disk.
+#[test]
+fn moves_keep_ids() {
+    let root = asset_dir("moves", &["hero.png", "tree.png"]);
+    let mut database = AssetDatabase::scan(&root).unwrap();
+    let hero = database.id(Path::new("hero.png")).unwrap();
+    let tree = database.id(Path::new("tree.png")).unwrap();
+
+    database
+        .move_asset(hero, Path::new("characters/player.png"))
+        .unwrap();
+    assert_eq!(
+        database.path(hero),
+        Some(Path::new("characters/player.png"))
+    );
+    assert!(root.join("characters/player.png.meta").exists());
+    assert!(database
+        .move_asset(tree, Path::new("characters/player.png"))
+        .is_err());
+
+    std::fs::create_dir_all(root.join("props")).unwrap();
+    std::fs::rename(root.join("tree.png"), root.join("props/tree.png")).unwrap();
+    std::fs::rename(root.join("tree.png.meta"), root.join("props/tree.png.meta")).unwrap();
+    database.refresh().unwrap();
+    assert_eq!(database.path(tree), Some(Path::new("props/tree.png")));
+    assert_eq!(database.id(Path::new("tree.png")), None);
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+/// Verifies copied meta files get a new ID and orphaned ones are reported.
+#[test]
+fn duplicates_and_orphans() {
+    let root = asset_dir("duplicates", &["a.png", "b.png", "gone.png"]);
+    let mut database = AssetDatabase::scan(&root).unwrap();
+    let a = database.id(Path::new("a.png")).unwrap();
+    std::fs::copy(root.join("a.png.meta"), root.join("b.png.meta")).unwrap();
+    std::fs::remove_file(root.join("gone.png")).unwrap();
+
+    let report = database.refresh().unwrap();
+    assert_eq!(report.reassigned, [PathBuf::from("b.png")]);
+    assert_eq!(report.orphaned, [PathBuf::from("gone.png.meta")]);
+    assert_eq!(database.id(Path::new("a.png")), Some(a));
+    let b = database.id(Path::new("b.png")).unwrap();
+    assert_ne!(a, b);
+    assert_eq!(AssetMeta::load(&root.join("b.png.meta")).unwrap().id, b);
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+/// Verifies importer settings are saved to the meta file.
+#[test]
+fn set_importer_persists() {
+    let root = asset_dir("importer", &["hero.png"]);
+    let mut database = AssetDatabase::scan(&root).unwrap();
+    let hero = database.id(Path::new("hero.png")).unwrap();
+    let sprite = ImporterSettings::Sprite(SpriteSettings {
+        pivot: [0.5, 0.0],
+        ..SpriteSettings::default()
+    });
+    database.set_importer(hero, sprite).unwrap();
+
+    let database = AssetDatabase::scan(&root).unwrap();
+    assert_eq!(database.meta(hero).unwrap().importer, sprite);
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+/// Verifies assets load by ID once, wherever their file moved.
+#[test]
+fn load_caches_by_id() {
+    let root = asset_dir("load", &["intro.txt"]);
+    let mut database = AssetDatabase::scan(&root).unwrap();
+    let intro = database.id(Path::new("intro.txt")).unwrap();
+    let mut cache = AssetCache::default();
+    let read = |path: &Path, _: &AssetMeta| Ok(std::fs::read_to_string(path)?);
+
+    let text = database.load(&mut cache, intro, read).unwrap();
+    assert_eq!(&*text, "intro.txt");
+    database
+        .move_asset(intro, Path::new("story/intro.txt"))
+        .unwrap();
+    let again = database
+        .load(&mut cache, intro, |_, _| -> anyhow::Result<String> {
+            panic!("loaded twice")
+        })
+        .unwrap();
+    assert!(again.ptr_eq(&text));
+    std::fs::remove_dir_all(&root).unwrap();
+}
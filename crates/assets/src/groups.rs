@@ -0,0 +1,480 @@
+//! Addressable asset groups, preloaded and released as a whole.
+//!
+//! Groups are named sets of assets defined in a manifest, usually
+//! `asset-groups.toml` in the asset directory. Assets are listed by path
+//! relative to the asset directory or by [`AssetId`]. Scenes list the
+//! groups they need:
+//!
+//! ```toml
+//! [groups.ui_common]
+//! assets = ["ui/font.ttf", "ui/buttons.png"]
+//! # Kept loaded across scene transitions.
+//! persistent = true
+//!
+//! [groups.level2]
+//! assets = ["levels/2.toml", "0f8fad5b-d9cb-469f-a165-70867728950e"]
+//!
+//! [scenes.level2]
+//! groups = ["ui_common", "level2"]
+//! ```
+
+use crate::cache::AssetCache;
+use crate::database::AssetDatabase;
+use crate::handle::LoadedAsset;
+use crate::id::AssetId;
+use crate::meta::AssetMeta;
+use crate::report::Bytes;
+use anyhow::Context;
+use rustgine_core::RustgineError;
+use scheduler::spawn_background;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Conventional name of the group manifest in the asset directory.
+pub const GROUPS_MANIFEST: &str = "asset-groups.toml";
+
+/// Loads one asset of a group on a background thread, from its source
+/// file and meta.
+pub type GroupLoadFn = dyn Fn(&Path, &AssetMeta) -> anyhow::Result<LoadedAsset> + Send + Sync;
+
+/// A named set of assets loaded and released together.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct GroupDef {
+    /// Asset paths relative to the asset directory, or asset IDs.
+    pub assets: Vec<String>,
+    /// Whether the group stays loaded across scene transitions.
+    #[serde(default)]
+    pub persistent: bool,
+}
+
+/// The groups a scene needs.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SceneDef {
+    /// Names of the groups loaded while the scene is active.
+    pub groups: Vec<String>,
+}
+
+/// Asset groups and the scenes using them, as defined in a manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct GroupManifest {
+    /// Groups by name.
+    #[serde(default)]
+    pub groups: BTreeMap<String, GroupDef>,
+    /// Scenes by name.
+    #[serde(default)]
+    pub scenes: BTreeMap<String, SceneDef>,
+}
+
+impl GroupManifest {
+    /// Parses a manifest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is invalid or a scene names an unknown
+    /// group.
+    pub fn from_toml(text: &str) -> anyhow::Result<Self> {
+        let manifest: Self =
+            toml::from_str(text).map_err(|e| anyhow::anyhow!("invalid group manifest: {e}"))?;
+        for (scene, def) in &manifest.scenes {
+            if let Some(group) = def
+                .groups
+                .iter()
+                .find(|group| !manifest.groups.contains_key(*group))
+            {
+                anyhow::bail!("scene `{scene}` uses unknown asset group `{group}`");
+            }
+        }
+        Ok(manifest)
+    }
+
+    /// Reads the manifest at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Self::from_toml(&text).with_context(|| path.display().to_string())
+    }
+}
+
+/// Loading progress of a group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct GroupProgress {
+    /// Assets loaded or failed so far.
+    pub items_loaded: u32,
+    /// Assets in the group.
+    pub items_total: u32,
+    /// Source bytes of the assets loaded so far.
+    pub bytes_loaded: u64,
+    /// Source bytes of every asset in the group.
+    pub bytes_total: u64,
+    /// Assets that failed to load.
+    pub failed: u32,
+}
+
+impl GroupProgress {
+    /// Returns whether every asset has loaded or failed.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.items_loaded == self.items_total
+    }
+
+    /// Returns the loaded fraction in `[0.0, 1.0]`, by bytes, or by items
+    /// if the sources are empty.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fraction(&self) -> f32 {
+        if self.bytes_total > 0 {
+            self.bytes_loaded as f32 / self.bytes_total as f32
+        } else if self.items_total > 0 {
+            self.items_loaded as f32 / self.items_total as f32
+        } else {
+            1.0
+        }
+    }
+}
+
+impl fmt::Display for GroupProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} assets, {} / {}",
+            self.items_loaded,
+            self.items_total,
+            Bytes(self.bytes_loaded),
+            Bytes(self.bytes_total)
+        )?;
+        if self.failed > 0 {
+            write!(f, ", {} failed", self.failed)?;
+        }
+        Ok(())
+    }
+}
+
+/// A preloaded or loading group.
+struct ActiveGroup {
+    /// Distinguishes this load from earlier ones of the same group.
+    generation: u64,
+    assets: Vec<AssetId>,
+    /// Keeps the loaded assets from being evicted.
+    held: Vec<Arc<dyn Any + Send + Sync>>,
+    progress: GroupProgress,
+}
+
+/// An asset finished on a background thread.
+struct Finished {
+    group: String,
+    generation: u64,
+    id: AssetId,
+    bytes: u64,
+    result: anyhow::Result<LoadedAsset>,
+}
+
+/// Preloads and releases the groups of a [`GroupManifest`] into an
+/// [`AssetCache`].
+///
+/// Assets are loaded on scheduler worker threads and added to the cache,
+/// keyed by ID like [`AssetDatabase::load`], by [`update`](Self::update),
+/// which the game calls once per frame. A preloaded group holds its assets
+/// so the cache never evicts them; releasing it unloads the assets no
+/// other group or handle uses. [`enter_scene`](Self::enter_scene) does
+/// both on a scene transition, keeping memory bounded to the groups the
+/// active scene needs.
+///
+/// # Example
+///
+/// ```
+/// use assets::{AssetCache, AssetDatabase, AssetGroups, GroupManifest, LoadedAsset};
+/// use std::sync::Arc;
+///
+/// let root = std::env::temp_dir().join(format!("rustgine-doc-groups-{}", std::process::id()));
+/// std::fs::create_dir_all(&root)?;
+/// std::fs::write(root.join("intro.txt"), "Once upon a time")?;
+/// let manifest = GroupManifest::from_toml(r#"groups.intro.assets = ["intro.txt"]"#)?;
+///
+/// let database = Arc::new(AssetDatabase::scan(&root)?);
+/// let mut groups = AssetGroups::new(manifest, database, |path, _meta| {
+///     Ok(LoadedAsset::new(std::fs::read_to_string(path)?))
+/// });
+/// let mut cache = AssetCache::default();
+/// groups.preload(&mut cache, "intro")?;
+/// while !groups.is_loaded("intro") {
+///     std::thread::yield_now();
+///     groups.update(&mut cache);
+/// }
+/// assert_eq!(groups.progress("intro").unwrap().bytes_loaded, 16);
+///
+/// groups.release(&mut cache, "intro");
+/// assert!(cache.is_empty());
+/// # std::fs::remove_dir_all(&root)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct AssetGroups {
+    manifest: GroupManifest,
+    database: Arc<AssetDatabase>,
+    load: Arc<GroupLoadFn>,
+    active: HashMap<String, ActiveGroup>,
+    scene: Option<String>,
+    generation: u64,
+    sender: Sender<Finished>,
+    receiver: Receiver<Finished>,
+}
+
+impl fmt::Debug for AssetGroups {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut active: Vec<_> = self.active.keys().collect();
+        active.sort_unstable();
+        f.debug_struct("AssetGroups")
+            .field("groups", &self.manifest.groups.len())
+            .field("active", &active)
+            .field("scene", &self.scene)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AssetGroups {
+    /// Creates a loader for the groups of `manifest`, resolving assets in
+    /// `database` and loading each with `load`.
+    pub fn new(
+        manifest: GroupManifest,
+        database: Arc<AssetDatabase>,
+        load: impl Fn(&Path, &AssetMeta) -> anyhow::Result<LoadedAsset> + Send + Sync + 'static,
+    ) -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            manifest,
+            database,
+            load: Arc::new(load),
+            active: HashMap::new(),
+            scene: None,
+            generation: 0,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Returns the group manifest.
+    #[must_use]
+    pub fn manifest(&self) -> &GroupManifest {
+        &self.manifest
+    }
+
+    /// Returns the active scene, if one was entered.
+    #[must_use]
+    pub fn scene(&self) -> Option<&str> {
+        self.scene.as_deref()
+    }
+
+    /// Returns the names of the preloaded and loading groups, sorted.
+    #[must_use]
+    pub fn active_groups(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.active.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Returns the progress of group `name`, `None` unless preloaded or
+    /// loading.
+    #[must_use]
+    pub fn progress(&self, name: &str) -> Option<GroupProgress> {
+        self.active.get(name).map(|group| group.progress)
+    }
+
+    /// Returns whether group `name` is preloaded and every asset has
+    /// loaded or failed.
+    #[must_use]
+    pub fn is_loaded(&self, name: &str) -> bool {
+        self.progress(name)
+            .is_some_and(|progress| progress.is_complete())
+    }
+
+    /// Starts loading the assets of group `name` in the background.
+    ///
+    /// Assets already in `cache` count as loaded at once. Does nothing if
+    /// the group is already preloaded or loading.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the group is unknown or lists an asset missing
+    /// from the database.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn preload(&mut self, cache: &mut AssetCache, name: &str) -> anyhow::Result<()> {
+        if self.active.contains_key(name) {
+            return Ok(());
+        }
+        let def = self
+            .manifest
+            .groups
+            .get(name)
+            .with_context(|| format!("unknown asset group `{name}`"))?;
+        let assets = def
+            .assets
+            .iter()
+            .map(|asset| self.resolve(asset))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .with_context(|| format!("invalid asset group `{name}`"))?;
+
+        self.generation += 1;
+        let mut group = ActiveGroup {
+            generation: self.generation,
+            assets: assets.iter().map(|(id, _)| *id).collect(),
+            held: Vec::with_capacity(assets.len()),
+            progress: GroupProgress {
+                items_total: assets.len() as u32,
+                ..GroupProgress::default()
+            },
+        };
+        for (id, path) in assets {
+            let bytes = std::fs::metadata(&path).map_or(0, |metadata| metadata.len());
+            group.progress.bytes_total += bytes;
+            if let Some(asset) = cache.get_loaded(&id.to_string()) {
+                group.held.push(asset);
+                group.progress.items_loaded += 1;
+                group.progress.bytes_loaded += bytes;
+                continue;
+            }
+            let meta = self
+                .database
+                .meta(id)
+                .cloned()
+                .with_context(|| format!("unknown asset {id}"))?;
+            let load = Arc::clone(&self.load);
+            let sender = self.sender.clone();
+            let (group_name, generation) = (name.to_owned(), group.generation);
+            spawn_background(&format!("load {}", path.display()), move || {
+                let result = load(&path, &meta);
+                // The receiver is gone once the loader is dropped.
+                let _ = sender.send(Finished {
+                    group: group_name,
+                    generation,
+                    id,
+                    bytes,
+                    result,
+                });
+            });
+        }
+        debug!(target: "assets", group = name, progress = %group.progress, "preloading asset group");
+        self.active.insert(name.to_owned(), group);
+        Ok(())
+    }
+
+    /// Adds the assets loaded in the background since the last call to
+    /// `cache` and updates the progress of their groups.
+    pub fn update(&mut self, cache: &mut AssetCache) {
+        while let Ok(finished) = self.receiver.try_recv() {
+            let Some(group) = self
+                .active
+                .get_mut(&finished.group)
+                .filter(|group| group.generation == finished.generation)
+            else {
+                // Released while loading.
+                continue;
+            };
+            group.progress.items_loaded += 1;
+            group.progress.bytes_loaded += finished.bytes;
+            let key = finished.id.to_string();
+            match finished.result {
+                Ok(loaded) => {
+                    // Another group may have loaded it first.
+                    let asset = cache
+                        .get_loaded(&key)
+                        .unwrap_or_else(|| cache.insert_loaded(&key, loaded));
+                    group.held.push(asset);
+                }
+                Err(error) => {
+                    group.progress.failed += 1;
+                    warn!(
+                        target: "assets",
+                        group = %finished.group,
+                        id = %finished.id,
+                        error = %format!("{error:#}"),
+                        "failed to load asset"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Releases group `name`, unloading its assets from `cache` unless
+    /// another group or a handle still uses them.
+    ///
+    /// Assets still loading are dropped when they finish. Returns whether
+    /// the group was preloaded or loading.
+    pub fn release(&mut self, cache: &mut AssetCache, name: &str) -> bool {
+        let Some(group) = self.active.remove(name) else {
+            return false;
+        };
+        drop(group.held);
+        let unloaded = group
+            .assets
+            .iter()
+            .filter(|id| cache.unload_unused(&id.to_string()))
+            .count();
+        debug!(target: "assets", group = name, unloaded, "released asset group");
+        true
+    }
+
+    /// Switches to `scene`: preloads the groups it uses and releases every
+    /// other group, except persistent ones.
+    ///
+    /// Groups are preloaded first, so assets shared with a released group
+    /// stay loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scene is unknown or a group fails to
+    /// preload.
+    pub fn enter_scene(&mut self, cache: &mut AssetCache, scene: &str) -> anyhow::Result<()> {
+        let needed = self
+            .manifest
+            .scenes
+            .get(scene)
+            .with_context(|| format!("unknown scene `{scene}`"))?
+            .groups
+            .clone();
+        let unused: Vec<String> = self
+            .active
+            .keys()
+            .filter(|name| {
+                !needed.contains(name)
+                    && !self
+                        .manifest
+                        .groups
+                        .get(*name)
+                        .is_some_and(|def| def.persistent)
+            })
+            .cloned()
+            .collect();
+        for name in &needed {
+            self.preload(cache, name)?;
+        }
+        for name in unused {
+            self.release(cache, &name);
+        }
+        self.scene = Some(scene.to_owned());
+        Ok(())
+    }
+
+    /// Resolves a manifest entry, an ID or a path, to an asset and its
+    /// source file.
+    ///
+    /// Fails with [`RustgineError::AssetNotFound`] for entries the database
+    /// does not know.
+    fn resolve(&self, asset: &str) -> anyhow::Result<(AssetId, PathBuf)> {
+        let not_found = || RustgineError::AssetNotFound { path: asset.into() };
+        let id = match asset.parse::<AssetId>() {
+            Ok(id) => id,
+            Err(_) => self.database.id(Path::new(asset)).ok_or_else(not_found)?,
+        };
+        let path = self.database.path(id).ok_or_else(not_found)?;
+        Ok((id, self.database.root().join(path)))
+    }
+}
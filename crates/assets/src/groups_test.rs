@@ -0,0 +1,213 @@
+//! Unit tests for asset groups.
+
+use crate::cache::AssetCache;
+use crate::database::AssetDatabase;
+use crate::groups::{AssetGroups, GroupManifest};
+use crate::handle::LoadedAsset;
+use rustgine_core::RustgineError;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const MANIFEST: &str = r#"
+[groups.ui_common]
+assets = ["ui/font.txt"]
+persistent = true
+
+[groups.level1]
+assets = ["levels/1.txt", "shared.txt"]
+
+[groups.level2]
+assets = ["levels/2.txt", "shared.txt"]
+
+[groups.broken]
+assets = ["levels/1.txt", "bad.txt"]
+
+[scenes.level1]
+groups = ["ui_common", "level1"]
+
+[scenes.level2]
+groups = ["ui_common", "level2"]
+"#;
+
+/// Creates an asset directory unique to `name` with the manifest's files.
+fn asset_dir(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("rustgine-groups-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    for (file, contents) in [
+        ("ui/font.txt", "font"),
+        ("levels/1.txt", "level one"),
+        ("levels/2.txt", "level two"),
+        ("shared.txt", "shared"),
+        ("bad.txt", "!"),
+    ] {
+        let path = root.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+    root
+}
+
+/// Creates groups over `root` loading text files, failing on `!`.
+fn groups(root: &Path, delay: Duration) -> AssetGroups {
+    let database = Arc::new(AssetDatabase::scan(root).unwrap());
+    AssetGroups::new(
+        GroupManifest::from_toml(MANIFEST).unwrap(),
+        database,
+        move |path, _| {
+            std::thread::sleep(delay);
+            let text = std::fs::read_to_string(path)?;
+            anyhow::ensure!(!text.contains('!'), "corrupt asset");
+            Ok(LoadedAsset::new(text))
+        },
+    )
+}
+
+/// Updates `groups` until `names` have loaded.
+fn wait(groups: &mut AssetGroups, cache: &mut AssetCache, names: &[&str]) {
+    let start = Instant::now();
+    while !names.iter().all(|name| groups.is_loaded(name)) {
+        assert!(start.elapsed() < Duration::from_secs(10), "{groups:?}");
+        std::thread::sleep(Duration::from_millis(1));
+        groups.update(cache);
+    }
+}
+
+/// Verifies scenes naming unknown groups are rejected.
+#[test]
+fn manifest_validates_scenes() {
+    let manifest = GroupManifest::from_toml(MANIFEST).unwrap();
+    assert_eq!(manifest.groups.len(), 4);
+    assert!(manifest.groups["ui_common"].persistent);
+    let error = GroupManifest::from_toml("[scenes.menu]\ngroups = [\"ui\"]").unwrap_err();
+    assert!(
+        error.to_string().contains("unknown asset group `ui`"),
+        "{error}"
+    );
+}
+
+/// Verifies preloading reports item and byte progress and keeps the
+/// assets from being evicted until released.
+#[test]
+fn preload_and_release() {
+    let root = asset_dir("preload");
+    let mut groups = groups(&root, Duration::ZERO);
+    let mut cache = AssetCache::new(1);
+
+    groups.preload(&mut cache, "level1").unwrap();
+    let progress = groups.progress("level1").unwrap();
+    assert_eq!((progress.items_total, progress.bytes_total), (2, 15));
+    wait(&mut groups, &mut cache, &["level1"]);
+    let progress = groups.progress("level1").unwrap();
+    assert_eq!(progress.to_string(), "2/2 assets, 15 B / 15 B");
+    assert!((progress.fraction() - 1.0).abs() < 1e-6);
+    // Over budget, but held by the group.
+    assert_eq!(cache.len(), 2);
+    assert!(cache.enforce_budget().is_empty());
+
+    assert!(groups.release(&mut cache, "level1"));
+    assert!(!groups.release(&mut cache, "level1"));
+    assert!(cache.is_empty());
+    assert_eq!(groups.progress("level1"), None);
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+/// Verifies assets shared with another group or a handle stay loaded when
+/// a group is released.
+#[test]
+fn release_keeps_shared_assets() {
+    let root = asset_dir("shared");
+    let mut groups = groups(&root, Duration::ZERO);
+    let mut cache = AssetCache::default();
+    groups.preload(&mut cache, "level1").unwrap();
+    wait(&mut groups, &mut cache, &["level1"]);
+    groups.preload(&mut cache, "level2").unwrap();
+    wait(&mut groups, &mut cache, &["level2"]);
+    assert_eq!(cache.len(), 3);
+
+    groups.release(&mut cache, "level1");
+    assert_eq!(cache.len(), 2);
+    let database = AssetDatabase::scan(&root).unwrap();
+    let level2 = database.id(Path::new("levels/2.txt")).unwrap();
+    let handle = cache.get::<String>(&level2.to_string()).unwrap();
+    groups.release(&mut cache, "level2");
+    assert_eq!(cache.len(), 1);
+    assert_eq!(&*handle, "level two");
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+/// Verifies scene transitions unload unused groups but keep persistent
+/// ones.
+#[test]
+fn scene_transition_unloads_groups() {
+    let root = asset_dir("scenes");
+    let mut groups = groups(&root, Duration::ZERO);
+    let mut cache = AssetCache::default();
+
+    groups.enter_scene(&mut cache, "level1").unwrap();
+    wait(&mut groups, &mut cache, &["ui_common", "level1"]);
+    assert_eq!(cache.len(), 3);
+
+    groups.enter_scene(&mut cache, "level2").unwrap();
+    assert_eq!(groups.scene(), Some("level2"));
+    assert_eq!(groups.active_groups(), ["level2", "ui_common"]);
+    // The font and the shared asset are reused at once.
+    assert_eq!(groups.progress("level2").unwrap().items_loaded, 1);
+    assert!(groups.is_loaded("ui_common"));
+    wait(&mut groups, &mut cache, &["level2"]);
+    assert_eq!(cache.len(), 3);
+    assert!(groups.enter_scene(&mut cache, "credits").is_err());
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+/// Verifies failed assets complete the group and are counted.
+#[test]
+fn failures_complete_the_group() {
+    let root = asset_dir("failures");
+    let mut groups = groups(&root, Duration::ZERO);
+    let mut cache = AssetCache::default();
+    groups.preload(&mut cache, "broken").unwrap();
+    wait(&mut groups, &mut cache, &["broken"]);
+    let progress = groups.progress("broken").unwrap();
+    assert_eq!((progress.items_loaded, progress.failed), (2, 1));
+    assert!(progress.to_string().ends_with(", 1 failed"), "{progress}");
+    assert_eq!(cache.len(), 1);
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+/// Verifies assets finishing after their group was released are dropped.
+#[test]
+fn release_while_loading_drops_results() {
+    let root = asset_dir("cancel");
+    let mut groups = groups(&root, Duration::from_millis(20));
+    let mut cache = AssetCache::default();
+    groups.preload(&mut cache, "level1").unwrap();
+    assert!(!groups.is_loaded("level1"));
+    groups.release(&mut cache, "level1");
+
+    std::thread::sleep(Duration::from_millis(200));
+    groups.update(&mut cache);
+    assert!(cache.is_empty());
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+/// Verifies unknown groups and assets are rejected.
+#[test]
+fn preload_rejects_unknown() {
+    let root = asset_dir("unknown");
+    std::fs::remove_file(root.join("shared.txt")).unwrap();
+    let mut groups = groups(&root, Duration::ZERO);
+    let mut cache = AssetCache::default();
+    assert!(groups.preload(&mut cache, "level9").is_err());
+    let error = groups.preload(&mut cache, "level1").unwrap_err();
+    assert!(
+        format!("{error:#}").contains("asset not found: shared.txt"),
+        "{error:#}"
+    );
+    assert!(matches!(
+        error.root_cause().downcast_ref::<RustgineError>(),
+        Some(RustgineError::AssetNotFound { .. })
+    ));
+    assert!(groups.active_groups().is_empty());
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,200 @@
+//! Strong and weak asset handles.
+
+use std::any::Any;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{Arc, Weak};
+
+/// A loaded asset that tracks its CPU memory.
+///
+/// # Example
+///
+/// ```
+/// use assets::Asset;
+///
+/// struct Texture {
+///     pixels: Vec<u8>,
+/// }
+///
+/// impl Asset for Texture {
+///     fn memory_bytes(&self) -> u64 {
+///         self.pixels.len() as u64
+///     }
+///
+///     fn kind() -> &'static str {
+///         "texture"
+///     }
+/// }
+/// ```
+pub trait Asset: Send + Sync + 'static {
+    /// Returns the CPU memory held by the asset, in bytes.
+    ///
+    /// Measured once when the asset enters the
+    /// [`AssetCache`](crate::AssetCache) and counted against its budget.
+    fn memory_bytes(&self) -> u64;
+
+    /// Returns the type name the memory breakdown groups the asset under.
+    ///
+    /// Defaults to the Rust type name.
+    #[must_use]
+    fn kind() -> &'static str
+    where
+        Self: Sized,
+    {
+        std::any::type_name::<Self>()
+    }
+}
+
+impl Asset for Vec<u8> {
+    fn memory_bytes(&self) -> u64 {
+        self.capacity() as u64
+    }
+
+    fn kind() -> &'static str {
+        "bytes"
+    }
+}
+
+impl Asset for String {
+    fn memory_bytes(&self) -> u64 {
+        self.capacity() as u64
+    }
+
+    fn kind() -> &'static str {
+        "text"
+    }
+}
+
+/// A loaded asset of any type, as returned by the loader of an
+/// [`AssetGroups`](crate::AssetGroups) background load.
+pub struct LoadedAsset {
+    pub(crate) asset: Arc<dyn Any + Send + Sync>,
+    pub(crate) kind: &'static str,
+    pub(crate) bytes: u64,
+}
+
+impl LoadedAsset {
+    /// Wraps `asset`, measuring its memory.
+    #[must_use]
+    pub fn new<T: Asset>(asset: T) -> Self {
+        Self {
+            bytes: asset.memory_bytes(),
+            kind: T::kind(),
+            asset: Arc::new(asset),
+        }
+    }
+}
+
+impl fmt::Debug for LoadedAsset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoadedAsset")
+            .field("kind", &self.kind)
+            .field("bytes", &self.bytes)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A strong reference to a cached asset.
+///
+/// While any strong handle exists the cache never evicts the asset. Store
+/// a [`WeakHandle`] instead for assets that may be reloaded on demand.
+pub struct Handle<T> {
+    path: Arc<str>,
+    asset: Arc<T>,
+}
+
+impl<T> Handle<T> {
+    pub(crate) fn new(path: Arc<str>, asset: Arc<T>) -> Self {
+        Self { path, asset }
+    }
+
+    /// Returns the path the asset was cached under.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns a weak handle to the same asset.
+    #[must_use]
+    pub fn downgrade(&self) -> WeakHandle<T> {
+        WeakHandle {
+            path: Arc::clone(&self.path),
+            asset: Arc::downgrade(&self.asset),
+        }
+    }
+
+    /// Returns whether both handles refer to the same loaded asset.
+    #[must_use]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.asset, &other.asset)
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            path: Arc::clone(&self.path),
+            asset: Arc::clone(&self.asset),
+        }
+    }
+}
+
+impl<T> Deref for Handle<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.asset
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Handle").field(&self.path).finish()
+    }
+}
+
+/// A reference to a cached asset that does not keep it loaded.
+///
+/// Assets only held weakly stay cached until the cache runs over budget,
+/// and are then evicted least recently used first.
+pub struct WeakHandle<T> {
+    path: Arc<str>,
+    asset: Weak<T>,
+}
+
+impl<T> WeakHandle<T> {
+    /// Returns the path the asset was cached under.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns a strong handle if the asset is still loaded.
+    #[must_use]
+    pub fn upgrade(&self) -> Option<Handle<T>> {
+        self.asset
+            .upgrade()
+            .map(|asset| Handle::new(Arc::clone(&self.path), asset))
+    }
+
+    /// Returns whether the asset is still loaded.
+    #[must_use]
+    pub fn is_loaded(&self) -> bool {
+        self.asset.strong_count() > 0
+    }
+}
+
+impl<T> Clone for WeakHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            path: Arc::clone(&self.path),
+            asset: Weak::clone(&self.asset),
+        }
+    }
+}
+
+impl<T> fmt::Debug for WeakHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("WeakHandle").field(&self.path).finish()
+    }
+}
@@ -0,0 +1,100 @@
+//! Stable asset identifiers.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// A stable asset identifier: a random (version 4) UUID assigned on first
+/// import and kept in the asset's `.meta` file.
+///
+/// Scenes and other assets reference assets by ID, so renaming or moving a
+/// file together with its `.meta` file keeps references intact. Serializes
+/// as the hyphenated UUID string.
+///
+/// # Example
+///
+/// ```
+/// use assets::AssetId;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Prop {
+///     mesh: AssetId,
+/// }
+///
+/// let prop: Prop = toml::from_str(r#"mesh = "0f8fad5b-d9cb-469f-a165-70867728950e""#)?;
+/// assert_eq!(prop.mesh.to_string(), "0f8fad5b-d9cb-469f-a165-70867728950e");
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AssetId(u128);
+
+impl AssetId {
+    /// Generates a new random ID.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system has no random source.
+    #[must_use]
+    pub fn random() -> Self {
+        let mut bytes = [0_u8; 16];
+        getrandom::fill(&mut bytes).expect("no operating system random source");
+        // Version 4, variant 1.
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+        Self(u128::from_be_bytes(bytes))
+    }
+
+    /// Creates an ID from its 128-bit value.
+    #[must_use]
+    pub const fn from_u128(value: u128) -> Self {
+        Self(value)
+    }
+
+    /// Returns the 128-bit value of the ID.
+    #[must_use]
+    pub const fn as_u128(self) -> u128 {
+        self.0
+    }
+}
+
+impl fmt::Display for AssetId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = format!("{:032x}", self.0);
+        write!(
+            f,
+            "{}-{}-{}-{}-{}",
+            &hex[..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..]
+        )
+    }
+}
+
+impl FromStr for AssetId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let groups: Vec<&str> = s.split('-').collect();
+        let hyphenated = groups.iter().map(|group| group.len()).eq([8, 4, 4, 4, 12]);
+        if !hyphenated || !groups.concat().bytes().all(|b| b.is_ascii_hexdigit()) {
+            anyhow::bail!("invalid asset ID `{s}`, expected a hyphenated UUID");
+        }
+        Ok(Self(u128::from_str_radix(&groups.concat(), 16)?))
+    }
+}
+
+impl Serialize for AssetId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for AssetId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
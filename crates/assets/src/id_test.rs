@@ -0,0 +1,42 @@
+//! Unit tests for asset IDs.
+
+use crate::id::AssetId;
+
+/// Verifies IDs format as hyphenated UUIDs and parse back.
+#[test]
+fn display_round_trips() {
+    let id = AssetId::from_u128(0x0f8f_ad5b_d9cb_469f_a165_7086_7728_950e);
+    assert_eq!(id.to_string(), "0f8fad5b-d9cb-469f-a165-70867728950e");
+    assert_eq!(
+        "0F8FAD5B-D9CB-469F-A165-70867728950E"
+            .parse::<AssetId>()
+            .unwrap(),
+        id
+    );
+}
+
+/// Verifies random IDs are distinct version 4 UUIDs.
+#[test]
+fn random_ids_are_v4() {
+    let a = AssetId::random();
+    let b = AssetId::random();
+    assert_ne!(a, b);
+    let text = a.to_string();
+    assert_eq!(&text[14..15], "4");
+    assert!(matches!(&text[19..20], "8" | "9" | "a" | "b"), "{text}");
+}
+
+/// Verifies malformed IDs are rejected.
+#[test]
+fn parse_rejects_malformed() {
+    for text in [
+        "",
+        "0f8fad5bd9cb469fa16570867728950e",
+        "0f8fad5b-d9cb-469f-a165-70867728950",
+        "0f8fad5b-d9cb-469f-a165-70867728950g",
+        "+f8fad5b-d9cb-469f-a165-70867728950e",
+    ] {
+        let error = text.parse::<AssetId>().unwrap_err();
+        assert!(error.to_string().contains("hyphenated UUID"), "{error}");
+    }
+}
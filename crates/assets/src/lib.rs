@@ -0,0 +1,75 @@
+//! Asset subsystem for the Rustgine game engine.
+//!
+//! This crate tracks the source assets of a project and caches loaded
+//! assets within a memory budget.
+//!
+//! # Overview
+//!
+//! The assets crate handles:
+//! - Strong handles that keep an asset loaded and weak handles that do not
+//!   ([`Handle`], [`WeakHandle`])
+//! - Per-asset memory tracking against a budget, with least-recently-used
+//!   eviction of weakly held assets ([`AssetCache`])
+//! - A memory breakdown by asset type for the dev UI
+//!   ([`AssetMemoryReport`])
+//! - Stable asset IDs and importer settings kept in `.meta` sidecar files,
+//!   so references survive renames and moves ([`AssetId`],
+//!   [`AssetDatabase`])
+//! - Incremental cooking into a build cache, re-cooking only assets whose
+//!   source, importer settings, or cooker changed ([`AssetCooker`])
+//! - Named asset groups preloaded in the background with progress and
+//!   released on scene transitions ([`AssetGroups`])
+//! - Reading asset bytes from a directory, or with `fetch` in the browser
+//!   (enable the `wasm` feature for `wasm32-unknown-unknown`)
+//!   ([`AssetSource`])
+//!
+//! # Example
+//!
+//! ```
+//! use assets::AssetCache;
+//!
+//! let mut cache = AssetCache::default();
+//! let text = cache.get_or_load("intro.txt", || Ok(String::from("Once upon a time")))?;
+//! assert_eq!(&*text, "Once upon a time");
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+#![warn(missing_docs)]
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+pub mod cache;
+#[cfg(test)]
+mod cache_test;
+pub mod cook;
+#[cfg(test)]
+mod cook_test;
+pub mod database;
+#[cfg(test)]
+mod database_test;
+pub mod groups;
+#[cfg(test)]
+mod groups_test;
+pub mod handle;
+pub mod id;
+#[cfg(test)]
+mod id_test;
+pub mod meta;
+#[cfg(test)]
+mod meta_test;
+pub mod report;
+#[cfg(test)]
+mod report_test;
+pub mod source;
+#[cfg(test)]
+mod source_test;
+
+pub use cache::{AssetCache, EvictionPolicy, DEFAULT_BUDGET_BYTES};
+pub use cook::{AssetCooker, CookReport, Cooker, CopyCooker};
+pub use database::{AssetDatabase, ScanReport};
+pub use groups::{AssetGroups, GroupDef, GroupManifest, GroupProgress, SceneDef, GROUPS_MANIFEST};
+pub use handle::{Asset, Handle, LoadedAsset, WeakHandle};
+pub use id::AssetId;
+pub use meta::{AssetMeta, ImporterSettings, SpriteSettings, TextureCompression, TextureSettings};
+pub use report::{AssetMemoryReport, AssetTypeMemory};
+pub use source::AssetSource;
@@ -0,0 +1,206 @@
+//! Sidecar `.meta` files holding asset IDs and importer settings.
+//!
+//! Every source file `hero.png` gets a `hero.png.meta` next to it on first
+//! import:
+//!
+//! ```toml
+//! id = "0f8fad5b-d9cb-469f-a165-70867728950e"
+//!
+//! [importer]
+//! type = "sprite"
+//! pivot = [0.5, 0.0]
+//! pixels_per_unit = 100.0
+//!
+//! [importer.texture]
+//! compression = "bc7"
+//! srgb = true
+//! mipmaps = false
+//! ```
+//!
+//! Meta files are meant to be committed and moved along with their source.
+
+use crate::id::AssetId;
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Extension appended to a source file name to get its meta file.
+pub const META_EXTENSION: &str = "meta";
+
+/// Returns the meta file of the source file `source`.
+#[must_use]
+pub fn meta_path(source: &Path) -> PathBuf {
+    let mut name = source.as_os_str().to_owned();
+    name.push(".");
+    name.push(META_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Block compression of a cooked texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextureCompression {
+    /// Uncompressed RGBA8.
+    None,
+    /// BC1, RGB with 1-bit alpha at 4 bits per pixel.
+    Bc1,
+    /// BC3, RGBA at 8 bits per pixel.
+    Bc3,
+    /// BC5, two channels for normal maps.
+    Bc5,
+    /// BC7, high quality RGBA at 8 bits per pixel.
+    #[default]
+    Bc7,
+    /// ASTC 4x4 for mobile GPUs.
+    Astc4x4,
+}
+
+/// Importer settings of a texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TextureSettings {
+    /// Block compression of the cooked texture.
+    pub compression: TextureCompression,
+    /// Whether the pixels are sRGB-encoded colors rather than linear data.
+    pub srgb: bool,
+    /// Whether a mip chain is generated.
+    pub mipmaps: bool,
+}
+
+impl Default for TextureSettings {
+    fn default() -> Self {
+        Self {
+            compression: TextureCompression::default(),
+            srgb: true,
+            mipmaps: true,
+        }
+    }
+}
+
+/// Importer settings of a sprite image.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpriteSettings {
+    /// Origin of the sprite, from `[0.0, 0.0]` at the bottom left to
+    /// `[1.0, 1.0]` at the top right.
+    pub pivot: [f32; 2],
+    /// Source pixels per world unit.
+    pub pixels_per_unit: f32,
+    /// How the sprite texture is cooked.
+    pub texture: TextureSettings,
+}
+
+impl Default for SpriteSettings {
+    fn default() -> Self {
+        Self {
+            pivot: [0.5, 0.5],
+            pixels_per_unit: 100.0,
+            texture: TextureSettings {
+                mipmaps: false,
+                ..TextureSettings::default()
+            },
+        }
+    }
+}
+
+/// How a source file is imported.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ImporterSettings {
+    /// Copied as is.
+    #[default]
+    Raw,
+    /// Imported as a texture.
+    Texture(TextureSettings),
+    /// Imported as a sprite.
+    Sprite(SpriteSettings),
+}
+
+impl ImporterSettings {
+    /// Returns the default settings for a source file with `extension`:
+    /// image formats are textures and anything else is raw.
+    #[must_use]
+    pub fn for_extension(extension: &str) -> Self {
+        match extension.to_ascii_lowercase().as_str() {
+            "png" | "jpg" | "jpeg" | "tga" | "bmp" | "hdr" | "exr" | "dds" | "ktx2" => {
+                Self::Texture(TextureSettings::default())
+            }
+            _ => Self::Raw,
+        }
+    }
+
+    /// Returns the importer name, the `type` key of the meta file.
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Raw => "raw",
+            Self::Texture(_) => "texture",
+            Self::Sprite(_) => "sprite",
+        }
+    }
+}
+
+/// Contents of a `.meta` file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetMeta {
+    /// The stable ID of the asset.
+    pub id: AssetId,
+    /// How the source file is imported.
+    #[serde(default)]
+    pub importer: ImporterSettings,
+}
+
+impl AssetMeta {
+    /// Creates the meta of a newly imported `source` file, with a new ID
+    /// and the default importer for its extension.
+    #[must_use]
+    pub fn new(source: &Path) -> Self {
+        let extension = source
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+        Self {
+            id: AssetId::random(),
+            importer: ImporterSettings::for_extension(extension),
+        }
+    }
+
+    /// Parses meta file contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is not a valid meta file.
+    pub fn from_toml(text: &str) -> anyhow::Result<Self> {
+        toml::from_str(text).map_err(|e| anyhow::anyhow!("invalid meta file: {e}"))
+    }
+
+    /// Serializes the meta to TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_toml(&self) -> anyhow::Result<String> {
+        toml::to_string(self).map_err(|e| anyhow::anyhow!("failed to serialize meta file: {e}"))
+    }
+
+    /// Reads the meta file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Self::from_toml(&text).with_context(|| path.display().to_string())
+    }
+
+    /// Writes the meta file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, self.to_toml()?)
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+}
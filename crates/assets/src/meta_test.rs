@@ -0,0 +1,79 @@
+//! Unit tests for meta files.
+
+use crate::id::AssetId;
+use crate::meta::{
+    meta_path, AssetMeta, ImporterSettings, SpriteSettings, TextureCompression, TextureSettings,
+};
+use std::path::Path;
+
+/// Verifies the meta file sits next to its source.
+#[test]
+fn meta_path_appends_extension() {
+    assert_eq!(
+        meta_path(Path::new("sprites/hero.png")),
+        Path::new("sprites/hero.png.meta")
+    );
+}
+
+/// Verifies images default to the texture importer and others to raw.
+#[test]
+fn importer_defaults_by_extension() {
+    assert_eq!(
+        AssetMeta::new(Path::new("a/b.PNG")).importer,
+        ImporterSettings::Texture(TextureSettings::default())
+    );
+    assert_eq!(
+        AssetMeta::new(Path::new("level.toml")).importer,
+        ImporterSettings::Raw
+    );
+    assert_eq!(
+        AssetMeta::new(Path::new("README")).importer,
+        ImporterSettings::Raw
+    );
+}
+
+/// Verifies importer settings survive a TOML round trip.
+#[test]
+fn toml_round_trips() {
+    let meta = AssetMeta {
+        id: AssetId::from_u128(7),
+        importer: ImporterSettings::Sprite(SpriteSettings {
+            pivot: [0.5, 0.0],
+            texture: TextureSettings {
+                compression: TextureCompression::Astc4x4,
+                ..TextureSettings::default()
+            },
+            ..SpriteSettings::default()
+        }),
+    };
+    let text = meta.to_toml().unwrap();
+    assert!(text.contains("type = \"sprite\""), "{text}");
+    assert!(text.contains("compression = \"astc4x4\""), "{text}");
+    assert_eq!(AssetMeta::from_toml(&text).unwrap(), meta);
+}
+
+/// Verifies omitted settings take their defaults.
+#[test]
+fn missing_settings_default() {
+    let meta = AssetMeta::from_toml(
+        r#"
+        id = "00000000-0000-4000-8000-000000000001"
+
+        [importer]
+        type = "texture"
+        compression = "none"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        meta.importer,
+        ImporterSettings::Texture(TextureSettings {
+            compression: TextureCompression::None,
+            ..TextureSettings::default()
+        })
+    );
+
+    let raw = AssetMeta::from_toml(r#"id = "00000000-0000-4000-8000-000000000001""#).unwrap();
+    assert_eq!(raw.importer, ImporterSettings::Raw);
+    assert!(AssetMeta::from_toml("id = \"nope\"").is_err());
+}
@@ -0,0 +1,82 @@
+//! Memory breakdown of the asset cache for the dev UI.
+
+use serde::Serialize;
+use std::fmt;
+
+/// Memory of the cached assets of one type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AssetTypeMemory {
+    /// The [`Asset::kind`](crate::Asset::kind) of the assets.
+    pub kind: String,
+    /// Cached assets.
+    pub assets: u32,
+    /// Assets held by at least one strong handle, which cannot be evicted.
+    pub strong: u32,
+    /// CPU memory of the assets, in bytes.
+    pub bytes: u64,
+}
+
+/// Memory used by an [`AssetCache`](crate::AssetCache), by asset type.
+///
+/// A type whose strongly held count only grows between snapshots usually
+/// points at a leaked handle.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct AssetMemoryReport {
+    /// CPU memory of every cached asset, in bytes.
+    pub used_bytes: u64,
+    /// The cache budget, in bytes.
+    pub budget_bytes: u64,
+    /// Assets evicted since the cache was created.
+    pub evicted: u64,
+    /// Memory per asset type, largest first.
+    pub types: Vec<AssetTypeMemory>,
+}
+
+impl AssetMemoryReport {
+    /// Returns the lines shown in the dev UI.
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "assets      {} / {} ({} evicted)",
+            Bytes(self.used_bytes),
+            Bytes(self.budget_bytes),
+            self.evicted
+        )];
+        lines.extend(self.types.iter().map(|memory| {
+            format!(
+                "  {:<10} {} ({} assets, {} strong)",
+                memory.kind,
+                Bytes(memory.bytes),
+                memory.assets,
+                memory.strong
+            )
+        }));
+        lines
+    }
+}
+
+impl fmt::Display for AssetMemoryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.lines().join("\n"))
+    }
+}
+
+/// Formats a byte count with a binary unit, such as `1.5 MiB`.
+pub(crate) struct Bytes(pub(crate) u64);
+
+impl fmt::Display for Bytes {
+    #[allow(clippy::cast_precision_loss)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut value = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while value >= 1024.0 && unit + 1 < UNITS.len() {
+            value /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{value:.1} {}", UNITS[unit])
+    }
+}
@@ -0,0 +1,30 @@
+//! Unit tests for the asset memory report.
+
+use crate::cache::AssetCache;
+
+/// Verifies memory is broken down by type with strongly held counts.
+#[test]
+fn report_groups_by_kind() {
+    let mut cache = AssetCache::new(4 << 20);
+    let texture = cache.insert("hero.png", vec![0_u8; 3 << 20]);
+    cache.insert("intro.txt", String::from("hello"));
+    let script = cache.insert("ai.txt", String::with_capacity(2048));
+
+    let report = cache.report();
+    assert_eq!(report.used_bytes, (3 << 20) + 5 + 2048);
+    let types: Vec<_> = report
+        .types
+        .iter()
+        .map(|memory| (memory.kind.as_str(), memory.assets, memory.strong))
+        .collect();
+    assert_eq!(types, [("bytes", 1, 1), ("text", 2, 1)]);
+    assert_eq!(
+        report.lines(),
+        [
+            "assets      3.0 MiB / 4.0 MiB (0 evicted)",
+            "  bytes      3.0 MiB (1 assets, 1 strong)",
+            "  text       2.0 KiB (2 assets, 1 strong)",
+        ]
+    );
+    drop((texture, script));
+}
@@ -0,0 +1,125 @@
+//! Reading asset bytes on every target.
+//!
+//! Native builds read assets from a directory. Browsers have no file system,
+//! so with the `wasm` feature on `wasm32` assets are fetched over HTTP from a
+//! URL prefix instead. Fetching never blocks, so [`AssetSource::read`] is an
+//! `async fn` on every target.
+
+use rustgine_core::RustgineError;
+
+/// Where asset bytes are read from: a directory on native targets, a URL
+/// prefix in the browser.
+///
+/// # Example
+///
+/// ```
+/// use assets::AssetSource;
+///
+/// let source = AssetSource::new("assets");
+/// assert_eq!(source.resolve("textures/hero.png")?, "assets/textures/hero.png");
+/// assert!(source.resolve("../secrets.toml").is_err());
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetSource {
+    root: String,
+}
+
+impl AssetSource {
+    /// Creates a source reading from `root`, a directory or URL prefix.
+    #[must_use]
+    pub fn new(root: impl Into<String>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Returns the directory or URL prefix assets are read from.
+    #[must_use]
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+
+    /// Returns the file path or URL of the asset at `path`, relative to the
+    /// root and separated by `/`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` is empty, absolute, contains a backslash,
+    /// or has a `.` or `..` component, so assets cannot escape the root.
+    pub fn resolve(&self, path: &str) -> anyhow::Result<String> {
+        let valid = !path.is_empty()
+            && !path.contains('\\')
+            && path
+                .split('/')
+                .all(|component| !matches!(component, "" | "." | ".."));
+        anyhow::ensure!(valid, "invalid asset path `{path}`");
+
+        let root = self.root.trim_end_matches('/');
+        Ok(if root.is_empty() {
+            path.to_owned()
+        } else {
+            format!("{root}/{path}")
+        })
+    }
+
+    /// Reads the asset at `path`; see [`resolve`](Self::resolve).
+    ///
+    /// Native targets read the file synchronously, so the returned future is
+    /// ready on its first poll.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RustgineError::AssetNotFound`] if there is no asset at
+    /// `path`, or another error if the path is invalid or the asset cannot
+    /// be read, including other HTTP error statuses in the browser.
+    #[cfg_attr(
+        not(all(target_arch = "wasm32", feature = "wasm")),
+        allow(clippy::unused_async)
+    )]
+    pub async fn read(&self, path: &str) -> anyhow::Result<Vec<u8>> {
+        let location = self.resolve(path)?;
+        #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+        {
+            fetch(path, &location).await
+        }
+        #[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+        {
+            std::fs::read(&location).map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    RustgineError::AssetNotFound { path: path.into() }.into()
+                } else {
+                    anyhow::anyhow!("failed to read `{location}`: {e}")
+                }
+            })
+        }
+    }
+}
+
+/// Fetches the asset at `path` from `url` and returns the response body.
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+async fn fetch(path: &str, url: &str) -> anyhow::Result<Vec<u8>> {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+
+    let window = web_sys::window().ok_or_else(|| anyhow::anyhow!("no browser window"))?;
+    let response = JsFuture::from(window.fetch_with_str(url))
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to fetch `{url}`: {e:?}"))?
+        .dyn_into::<web_sys::Response>()
+        .map_err(|_| anyhow::anyhow!("fetching `{url}` did not return a response"))?;
+    if response.status() == 404 {
+        return Err(RustgineError::AssetNotFound { path: path.into() }.into());
+    }
+    anyhow::ensure!(
+        response.ok(),
+        "failed to fetch `{url}`: HTTP {}",
+        response.status()
+    );
+
+    let body = response
+        .array_buffer()
+        .map_err(|e| anyhow::anyhow!("failed to read `{url}`: {e:?}"))?;
+    let body = JsFuture::from(body)
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to read `{url}`: {e:?}"))?;
+    Ok(js_sys::Uint8Array::new(&body).to_vec())
+}
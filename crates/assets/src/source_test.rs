@@ -0,0 +1,69 @@
+//! Unit tests for reading asset bytes.
+
+use crate::source::AssetSource;
+use rustgine_core::RustgineError;
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+/// Polls `future`, which must be ready on its first poll.
+fn ready<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("native reads complete immediately"),
+    }
+}
+
+/// Verifies that paths resolve below the root, with or without a trailing
+/// separator.
+#[test]
+fn resolves_below_root() {
+    let source = AssetSource::new("https://example.com/game/");
+    assert_eq!(
+        source.resolve("levels/1.toml").unwrap(),
+        "https://example.com/game/levels/1.toml"
+    );
+    assert_eq!(
+        AssetSource::new("").resolve("hero.png").unwrap(),
+        "hero.png"
+    );
+}
+
+/// Verifies that paths cannot escape the root.
+#[test]
+fn rejects_escaping_paths() {
+    let source = AssetSource::new("assets");
+    for path in [
+        "",
+        "/etc/passwd",
+        "../config.toml",
+        "a/../../b",
+        "a//b",
+        "./a",
+        "a\\b",
+    ] {
+        assert!(source.resolve(path).is_err(), "{path:?} was accepted");
+    }
+}
+
+/// Verifies that native builds read assets from the root directory.
+#[test]
+fn reads_files() {
+    let root = std::env::temp_dir().join(format!("rustgine-source-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("text")).unwrap();
+    std::fs::write(root.join("text/intro.txt"), "Once upon a time").unwrap();
+    let source = AssetSource::new(root.to_str().unwrap());
+
+    assert_eq!(
+        ready(source.read("text/intro.txt")).unwrap(),
+        b"Once upon a time"
+    );
+    let err = ready(source.read("text/missing.txt")).unwrap_err();
+    assert_eq!(err.to_string(), "asset not found: text/missing.txt");
+    assert!(matches!(
+        err.downcast_ref::<RustgineError>(),
+        Some(RustgineError::AssetNotFound { .. })
+    ));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,47 @@
+//! Audio subsystem for the Rustgine game engine.
+//!
+//! This crate provides sound playback and mixing for games.
+//!
+//! # Overview
+//!
+//! The audio crate handles:
+//! - Spatial sound with attenuation, panning, and doppler ([`Emitter`])
+//! - Occlusion muffling from world raycasts ([`SoundOcclusion`])
+//! - Reverb zones blending environment presets ([`ReverbZone`])
+//! - Mixer buses with effects, snapshots, and ducking ([`Mixer`])
+//! - Streaming Ogg Vorbis music with loop points and cross-fades
+//!   ([`MusicPlayer`])
+//!
+//! # Example
+//!
+//! ```ignore
+//! use audio::RustgineAudio;
+//! use rustgine_core::RustgineSystem;
+//!
+//! let mut audio = RustgineAudio::default();
+//! audio.startup()?;
+//! ```
+
+#![warn(missing_docs)]
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+pub mod audio;
+pub mod mixer;
+#[cfg(test)]
+mod mixer_test;
+pub mod reverb;
+#[cfg(test)]
+mod reverb_test;
+pub mod spatial;
+#[cfg(test)]
+mod spatial_test;
+pub mod stream;
+#[cfg(test)]
+mod stream_test;
+
+pub use audio::RustgineAudio;
+pub use mixer::{BusId, Mixer, MixerSnapshot};
+pub use reverb::{ReverbPreset, ReverbZone};
+pub use spatial::{Emitter, Listener, SoundOcclusion, SpatialParams};
+pub use stream::{MusicPlayer, MusicStream};
@@ -0,0 +1,543 @@
+//! Mixer bus graph, snapshots, and side-chain ducking.
+//!
+//! Every voice plays into a bus. Buses form a tree under `master`, with
+//! `music`, `sfx`, and `voice` created by default, and each bus applies its
+//! volume, a low-pass filter, and an optional [`Compressor`] before passing
+//! its signal to its parent.
+//!
+//! A [`MixerSnapshot`] stores bus settings for a game state such as
+//! "paused" or "underwater" and is faded in with
+//! [`Mixer::apply_snapshot`]. [`DuckRule`]s lower one bus while another is
+//! loud, so music dips under dialog.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Low-pass cutoffs at or above this are treated as an open filter.
+pub const OPEN_LOW_PASS_HZ: f32 = 20_000.0;
+
+/// Level floor used when converting silence to decibels.
+const SILENCE_DB: f32 = -120.0;
+
+/// Converts decibels to a linear gain.
+#[must_use]
+pub fn db_to_gain(db: f32) -> f32 {
+    10.0_f32.powf(db / 20.0)
+}
+
+/// Converts a linear gain to decibels.
+#[must_use]
+pub fn gain_to_db(gain: f32) -> f32 {
+    if gain <= 0.0 {
+        SILENCE_DB
+    } else {
+        (20.0 * gain.log10()).max(SILENCE_DB)
+    }
+}
+
+/// Index of a bus in a [`Mixer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BusId(usize);
+
+impl BusId {
+    /// The root bus feeding the output device.
+    pub const MASTER: Self = Self(0);
+    /// Background music.
+    pub const MUSIC: Self = Self(1);
+    /// Sound effects.
+    pub const SFX: Self = Self(2);
+    /// Dialog.
+    pub const VOICE: Self = Self(3);
+}
+
+/// Settings of a bus that snapshots control.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BusSettings {
+    /// Linear volume.
+    pub volume: f32,
+    /// Low-pass filter cutoff in hertz.
+    pub low_pass_hz: f32,
+}
+
+impl Default for BusSettings {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            low_pass_hz: OPEN_LOW_PASS_HZ,
+        }
+    }
+}
+
+impl BusSettings {
+    /// Returns a blend of `self` and `other`; the cutoff is blended on a
+    /// logarithmic scale so sweeps sound even.
+    #[must_use]
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            volume: self.volume + (other.volume - self.volume) * t,
+            low_pass_hz: self.low_pass_hz * (other.low_pass_hz / self.low_pass_hz).powf(t),
+        }
+    }
+}
+
+/// Dynamic range compressor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Compressor {
+    /// Level above which gain is reduced, in decibels.
+    pub threshold_db: f32,
+    /// Input decibels above the threshold per output decibel.
+    pub ratio: f32,
+    /// Time to react to rising levels.
+    pub attack: Duration,
+    /// Time to recover once levels fall.
+    pub release: Duration,
+}
+
+impl Default for Compressor {
+    fn default() -> Self {
+        Self {
+            threshold_db: -12.0,
+            ratio: 4.0,
+            attack: Duration::from_millis(5),
+            release: Duration::from_millis(100),
+        }
+    }
+}
+
+impl Compressor {
+    /// Returns the gain change in decibels for a signal at `level_db`.
+    #[must_use]
+    pub fn gain_db(&self, level_db: f32) -> f32 {
+        let over = level_db - self.threshold_db;
+        if over <= 0.0 {
+            0.0
+        } else {
+            -over * (1.0 - 1.0 / self.ratio.max(1.0))
+        }
+    }
+}
+
+/// Lowers `target` while `trigger` is louder than `threshold_db`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DuckRule {
+    /// Bus whose level triggers ducking, e.g. [`BusId::VOICE`].
+    pub trigger: BusId,
+    /// Bus that is lowered, e.g. [`BusId::MUSIC`].
+    pub target: BusId,
+    /// Trigger level that starts ducking, in decibels.
+    pub threshold_db: f32,
+    /// Gain change at full ducking, in decibels (negative).
+    pub depth_db: f32,
+    /// Time to duck once the trigger starts.
+    pub attack: Duration,
+    /// Time to recover once the trigger stops.
+    pub release: Duration,
+}
+
+impl DuckRule {
+    /// Ducks `target` by `depth_db` under `trigger` with typical dialog
+    /// timings.
+    #[must_use]
+    pub fn new(trigger: BusId, target: BusId, depth_db: f32) -> Self {
+        Self {
+            trigger,
+            target,
+            threshold_db: -40.0,
+            depth_db: -depth_db.abs(),
+            attack: Duration::from_millis(50),
+            release: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Bus settings for a game state.
+///
+/// # Example
+///
+/// ```
+/// use audio::mixer::{BusId, BusSettings, Mixer, MixerSnapshot};
+/// use std::time::Duration;
+///
+/// let underwater = MixerSnapshot::new().with_bus(
+///     "sfx",
+///     BusSettings { volume: 0.6, low_pass_hz: 800.0 },
+/// );
+///
+/// let mut mixer = Mixer::new(48_000, 2);
+/// mixer.apply_snapshot(&underwater, Duration::from_millis(500)).unwrap();
+/// mixer.update(Duration::from_millis(500));
+/// assert_eq!(mixer.settings(BusId::SFX).low_pass_hz, 800.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MixerSnapshot {
+    /// Settings by bus name; buses not listed keep their current settings.
+    pub buses: BTreeMap<String, BusSettings>,
+}
+
+impl MixerSnapshot {
+    /// Creates a snapshot changing no buses.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the settings of bus `name`.
+    #[must_use]
+    pub fn with_bus(mut self, name: &str, settings: BusSettings) -> Self {
+        self.buses.insert(name.to_owned(), settings);
+        self
+    }
+}
+
+/// A settings fade in progress.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Transition {
+    /// Settings at the start of the fade.
+    from: BusSettings,
+    /// Settings at the end of the fade.
+    to: BusSettings,
+    /// Time faded so far.
+    elapsed: Duration,
+    /// Length of the fade.
+    duration: Duration,
+}
+
+/// One bus of the graph.
+#[derive(Debug, Clone)]
+struct Bus {
+    /// Unique name.
+    name: String,
+    /// Bus receiving this bus's output; `None` for master.
+    parent: Option<BusId>,
+    /// Current settings.
+    settings: BusSettings,
+    /// Optional compressor.
+    compressor: Option<Compressor>,
+    /// Snapshot fade in progress.
+    transition: Option<Transition>,
+    /// Low-pass filter memory per channel.
+    filter: Vec<f32>,
+    /// Compressor level envelope in decibels.
+    envelope_db: f32,
+    /// Peak output level of the last mixed block, in decibels.
+    level_db: f32,
+    /// Gain change from ducking, in decibels.
+    duck_db: f32,
+}
+
+/// The bus graph.
+///
+/// # Example
+///
+/// ```
+/// use audio::mixer::{BusId, Mixer};
+///
+/// let mut mixer = Mixer::new(48_000, 1);
+/// mixer.set_volume(BusId::MUSIC, 0.5);
+///
+/// let music = [1.0; 4];
+/// let mut output = [0.0; 4];
+/// mixer.mix(&[(BusId::MUSIC, &music)], &mut output);
+/// assert_eq!(output, [0.5; 4]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Mixer {
+    /// Buses, parents before children.
+    buses: Vec<Bus>,
+    /// Side-chain ducking rules.
+    ducking: Vec<DuckRule>,
+    /// Output sample rate in hertz.
+    sample_rate: u32,
+    /// Interleaved channels per frame.
+    channels: usize,
+}
+
+impl Mixer {
+    /// Creates the default graph: master with music, sfx, and voice.
+    #[must_use]
+    pub fn new(sample_rate: u32, channels: usize) -> Self {
+        let mut mixer = Self {
+            buses: Vec::new(),
+            ducking: Vec::new(),
+            sample_rate: sample_rate.max(1),
+            channels: channels.max(1),
+        };
+        mixer.push_bus("master", None);
+        for name in ["music", "sfx", "voice"] {
+            mixer.push_bus(name, Some(BusId::MASTER));
+        }
+        mixer
+    }
+
+    /// Adds a bus under `parent`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is taken or `parent` does not exist.
+    pub fn add_bus(&mut self, name: &str, parent: BusId) -> anyhow::Result<BusId> {
+        if self.bus(name).is_some() {
+            anyhow::bail!("mixer bus {name:?} already exists");
+        }
+        if parent.0 >= self.buses.len() {
+            anyhow::bail!("mixer bus {name:?} has an unknown parent");
+        }
+        Ok(self.push_bus(name, Some(parent)))
+    }
+
+    fn push_bus(&mut self, name: &str, parent: Option<BusId>) -> BusId {
+        self.buses.push(Bus {
+            name: name.to_owned(),
+            parent,
+            settings: BusSettings::default(),
+            compressor: None,
+            transition: None,
+            filter: vec![0.0; self.channels],
+            envelope_db: SILENCE_DB,
+            level_db: SILENCE_DB,
+            duck_db: 0.0,
+        });
+        BusId(self.buses.len() - 1)
+    }
+
+    /// Returns the bus named `name`.
+    #[must_use]
+    pub fn bus(&self, name: &str) -> Option<BusId> {
+        self.buses
+            .iter()
+            .position(|bus| bus.name == name)
+            .map(BusId)
+    }
+
+    /// Returns the name of a bus.
+    #[must_use]
+    pub fn name(&self, bus: BusId) -> &str {
+        &self.buses[bus.0].name
+    }
+
+    /// Returns the parent of a bus; `None` for master.
+    #[must_use]
+    pub fn parent(&self, bus: BusId) -> Option<BusId> {
+        self.buses[bus.0].parent
+    }
+
+    /// Returns the current settings of a bus.
+    #[must_use]
+    pub fn settings(&self, bus: BusId) -> BusSettings {
+        self.buses[bus.0].settings
+    }
+
+    /// Sets the settings of a bus, cancelling any snapshot fade on it.
+    pub fn set_settings(&mut self, bus: BusId, settings: BusSettings) {
+        let bus = &mut self.buses[bus.0];
+        bus.settings = settings;
+        bus.transition = None;
+    }
+
+    /// Sets the linear volume of a bus.
+    pub fn set_volume(&mut self, bus: BusId, volume: f32) {
+        let settings = BusSettings {
+            volume: volume.max(0.0),
+            ..self.settings(bus)
+        };
+        self.set_settings(bus, settings);
+    }
+
+    /// Sets or removes the compressor of a bus.
+    pub fn set_compressor(&mut self, bus: BusId, compressor: Option<Compressor>) {
+        self.buses[bus.0].compressor = compressor;
+    }
+
+    /// Adds a side-chain ducking rule.
+    pub fn add_ducking(&mut self, rule: DuckRule) {
+        self.ducking.push(rule);
+    }
+
+    /// Returns the current gain change from ducking on a bus, in decibels.
+    #[must_use]
+    pub fn duck_db(&self, bus: BusId) -> f32 {
+        self.buses[bus.0].duck_db
+    }
+
+    /// Returns the peak level of a bus in the last mixed block, in decibels.
+    #[must_use]
+    pub fn level_db(&self, bus: BusId) -> f32 {
+        self.buses[bus.0].level_db
+    }
+
+    /// Captures the current settings of every bus.
+    #[must_use]
+    pub fn snapshot(&self) -> MixerSnapshot {
+        MixerSnapshot {
+            buses: self
+                .buses
+                .iter()
+                .map(|bus| (bus.name.clone(), bus.settings))
+                .collect(),
+        }
+    }
+
+    /// Fades the buses named in `snapshot` to its settings over `fade`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, without changing any bus, if the snapshot names a
+    /// bus that does not exist.
+    pub fn apply_snapshot(
+        &mut self,
+        snapshot: &MixerSnapshot,
+        fade: Duration,
+    ) -> anyhow::Result<()> {
+        let targets = snapshot
+            .buses
+            .iter()
+            .map(|(name, settings)| {
+                self.bus(name)
+                    .map(|bus| (bus, *settings))
+                    .ok_or_else(|| anyhow::anyhow!("mixer snapshot names unknown bus {name:?}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        for (bus, to) in targets {
+            let bus = &mut self.buses[bus.0];
+            if fade.is_zero() {
+                bus.settings = to;
+                bus.transition = None;
+            } else {
+                bus.transition = Some(Transition {
+                    from: bus.settings,
+                    to,
+                    elapsed: Duration::ZERO,
+                    duration: fade,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Advances snapshot fades.
+    pub fn update(&mut self, delta: Duration) {
+        for bus in &mut self.buses {
+            if let Some(transition) = &mut bus.transition {
+                transition.elapsed += delta;
+                let t =
+                    (transition.elapsed.as_secs_f32() / transition.duration.as_secs_f32()).min(1.0);
+                bus.settings = transition.from.lerp(&transition.to, t);
+                if t >= 1.0 {
+                    bus.settings = transition.to;
+                    bus.transition = None;
+                }
+            }
+        }
+    }
+
+    /// Mixes one block of interleaved samples.
+    ///
+    /// `inputs` are the voices already summed per bus. Ducking reacts to
+    /// trigger levels of the previous block.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mix(&mut self, inputs: &[(BusId, &[f32])], output: &mut [f32]) {
+        let len = output.len();
+        let block = Duration::from_secs_f32((len / self.channels) as f32 / self.sample_rate as f32);
+        self.update_ducking(block);
+
+        let mut buffers = vec![vec![0.0_f32; len]; self.buses.len()];
+        for (bus, samples) in inputs {
+            for (mixed, sample) in buffers[bus.0].iter_mut().zip(*samples) {
+                *mixed += sample;
+            }
+        }
+        // Children come after their parents, so walk backwards.
+        for index in (0..self.buses.len()).rev() {
+            let mut samples = std::mem::take(&mut buffers[index]);
+            self.process_bus(index, &mut samples);
+            match self.buses[index].parent {
+                Some(parent) => {
+                    for (mixed, sample) in buffers[parent.0].iter_mut().zip(&samples) {
+                        *mixed += sample;
+                    }
+                }
+                None => output.copy_from_slice(&samples),
+            }
+        }
+    }
+
+    /// Applies a bus's volume, ducking, filter, and compressor in place.
+    #[allow(clippy::cast_precision_loss)]
+    fn process_bus(&mut self, index: usize, samples: &mut [f32]) {
+        let sample_rate = self.sample_rate as f32;
+        let channels = self.channels;
+        let bus = &mut self.buses[index];
+        let gain = bus.settings.volume * db_to_gain(bus.duck_db);
+        let filter = (bus.settings.low_pass_hz < OPEN_LOW_PASS_HZ)
+            .then(|| 1.0 - (-std::f32::consts::TAU * bus.settings.low_pass_hz / sample_rate).exp());
+        let compressor = bus.compressor.map(|compressor| {
+            (
+                compressor,
+                smoothing(compressor.attack, sample_rate),
+                smoothing(compressor.release, sample_rate),
+            )
+        });
+
+        let mut peak = 0.0_f32;
+        for (i, sample) in samples.iter_mut().enumerate() {
+            let mut value = *sample * gain;
+            if let Some(coefficient) = filter {
+                let state = &mut bus.filter[i % channels];
+                *state += coefficient * (value - *state);
+                value = *state;
+            }
+            if let Some((compressor, attack, release)) = compressor {
+                let level_db = gain_to_db(value.abs());
+                let coefficient = if level_db > bus.envelope_db {
+                    attack
+                } else {
+                    release
+                };
+                bus.envelope_db += coefficient * (level_db - bus.envelope_db);
+                value *= db_to_gain(compressor.gain_db(bus.envelope_db));
+            }
+            peak = peak.max(value.abs());
+            *sample = value;
+        }
+        bus.level_db = gain_to_db(peak);
+    }
+
+    /// Moves each ducked bus toward its target gain for a block of length
+    /// `block`.
+    fn update_ducking(&mut self, block: Duration) {
+        // Target gain and timings per ducked bus; the deepest active rule
+        // wins and the first rule's timings apply.
+        let mut targets: BTreeMap<usize, (f32, Duration, Duration)> = BTreeMap::new();
+        for rule in &self.ducking {
+            let (target, _, _) =
+                targets
+                    .entry(rule.target.0)
+                    .or_insert((0.0, rule.attack, rule.release));
+            if self.buses[rule.trigger.0].level_db > rule.threshold_db {
+                *target = target.min(rule.depth_db);
+            }
+        }
+        for (index, (target, attack, release)) in targets {
+            let bus = &mut self.buses[index];
+            let time = if target < bus.duck_db {
+                attack
+            } else {
+                release
+            };
+            let t = if time.is_zero() {
+                1.0
+            } else {
+                (block.as_secs_f32() / time.as_secs_f32()).min(1.0)
+            };
+            bus.duck_db += (target - bus.duck_db) * t;
+        }
+    }
+}
+
+/// Returns the per-sample smoothing coefficient for a time constant.
+fn smoothing(time: Duration, sample_rate: f32) -> f32 {
+    if time.is_zero() {
+        1.0
+    } else {
+        1.0 - (-1.0 / (time.as_secs_f32() * sample_rate)).exp()
+    }
+}
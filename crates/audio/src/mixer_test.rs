@@ -0,0 +1,124 @@
+//! Unit tests for the mixer bus graph.
+
+use crate::mixer::{
+    db_to_gain, BusId, BusSettings, Compressor, DuckRule, Mixer, MixerSnapshot, OPEN_LOW_PASS_HZ,
+};
+use std::time::Duration;
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+}
+
+/// Verifies the default graph and that child buses feed their parents.
+#[test]
+fn buses_feed_parents() {
+    let mut mixer = Mixer::new(48_000, 1);
+    let ui = mixer.add_bus("ui", BusId::SFX).unwrap();
+    mixer.set_volume(BusId::SFX, 0.5);
+    mixer.set_volume(BusId::MASTER, 0.5);
+
+    assert_eq!(mixer.bus("voice"), Some(BusId::VOICE));
+    assert_eq!(mixer.parent(ui), Some(BusId::SFX));
+    assert!(mixer.add_bus("ui", BusId::MASTER).is_err());
+
+    let mut output = [0.0; 2];
+    mixer.mix(
+        &[(ui, &[1.0, 1.0]), (BusId::MUSIC, &[1.0, 0.0])],
+        &mut output,
+    );
+    assert_close(output[0], 0.75);
+    assert_close(output[1], 0.25);
+}
+
+/// Verifies the low-pass filter lets through DC and damps alternation.
+#[test]
+fn low_pass_filters() {
+    let mut mixer = Mixer::new(48_000, 1);
+    mixer.set_settings(
+        BusId::MUSIC,
+        BusSettings {
+            volume: 1.0,
+            low_pass_hz: 500.0,
+        },
+    );
+    let alternating: Vec<f32> = (0..480)
+        .map(|i| if i % 2 == 0 { 1.0 } else { -1.0 })
+        .collect();
+    let mut output = vec![0.0; 480];
+
+    mixer.mix(&[(BusId::MUSIC, &alternating)], &mut output);
+    assert!(mixer.level_db(BusId::MUSIC) < -10.0);
+    mixer.mix(&[(BusId::MUSIC, &[1.0; 480])], &mut output);
+    assert_close(output[479], 1.0);
+}
+
+/// Verifies the compressor reduces loud signals by its ratio.
+#[test]
+fn compressor_reduces_loud_signals() {
+    let compressor = Compressor {
+        threshold_db: -20.0,
+        ratio: 4.0,
+        attack: Duration::ZERO,
+        release: Duration::ZERO,
+    };
+    assert_close(compressor.gain_db(-30.0), 0.0);
+    assert_close(compressor.gain_db(0.0), -15.0);
+
+    let mut mixer = Mixer::new(48_000, 1);
+    mixer.set_compressor(BusId::SFX, Some(compressor));
+    let mut output = [0.0; 8];
+    mixer.mix(&[(BusId::SFX, &[1.0; 8])], &mut output);
+    assert_close(output[7], db_to_gain(-15.0));
+}
+
+/// Verifies snapshots fade bus settings and reject unknown buses.
+#[test]
+fn snapshots_fade() {
+    let mut mixer = Mixer::new(48_000, 2);
+    let normal = mixer.snapshot();
+    let paused = MixerSnapshot::new().with_bus(
+        "sfx",
+        BusSettings {
+            volume: 0.0,
+            low_pass_hz: OPEN_LOW_PASS_HZ,
+        },
+    );
+
+    mixer
+        .apply_snapshot(&paused, Duration::from_secs(1))
+        .unwrap();
+    mixer.update(Duration::from_millis(250));
+    assert_close(mixer.settings(BusId::SFX).volume, 0.75);
+    mixer.update(Duration::from_secs(1));
+    assert_close(mixer.settings(BusId::SFX).volume, 0.0);
+
+    mixer.apply_snapshot(&normal, Duration::ZERO).unwrap();
+    assert_close(mixer.settings(BusId::SFX).volume, 1.0);
+    let unknown = MixerSnapshot::new().with_bus("ambience", BusSettings::default());
+    assert!(mixer.apply_snapshot(&unknown, Duration::ZERO).is_err());
+}
+
+/// Verifies music ducks while dialog plays and recovers afterwards.
+#[test]
+fn music_ducks_under_voice() {
+    let mut mixer = Mixer::new(1_000, 1);
+    let mut rule = DuckRule::new(BusId::VOICE, BusId::MUSIC, 12.0);
+    rule.attack = Duration::from_millis(100);
+    rule.release = Duration::from_millis(100);
+    mixer.add_ducking(rule);
+    let mut output = [0.0; 100];
+
+    // The first block only measures the voice level.
+    mixer.mix(&[(BusId::VOICE, &[0.5; 100])], &mut output);
+    assert_close(mixer.duck_db(BusId::MUSIC), 0.0);
+    mixer.mix(
+        &[(BusId::VOICE, &[0.5; 100]), (BusId::MUSIC, &[1.0; 100])],
+        &mut output,
+    );
+    assert_close(mixer.duck_db(BusId::MUSIC), -12.0);
+    assert_close(output[0], 0.5 + db_to_gain(-12.0));
+
+    mixer.mix(&[], &mut output);
+    mixer.mix(&[], &mut output);
+    assert_close(mixer.duck_db(BusId::MUSIC), 0.0);
+}
@@ -0,0 +1,144 @@
+//! Environmental reverb from zone volumes.
+//!
+//! Levels place [`ReverbZone`] components as boxes around caves, halls, or
+//! corridors. The listener's position selects a blend of the zones it is in
+//! or near, fading over each zone's blend distance, so walking from a tunnel
+//! into a cathedral changes the reverb smoothly instead of switching it.
+
+/// Parameters of the reverb effect.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReverbPreset {
+    /// Time for the reverb tail to decay by 60 dB, in seconds.
+    pub decay_time: f32,
+    /// Delay before the first reflections, in seconds.
+    pub pre_delay: f32,
+    /// Share of reverberated signal in the output, from 0 to 1.
+    pub wet: f32,
+    /// High-frequency damping of the tail, from 0 to 1.
+    pub damping: f32,
+}
+
+impl ReverbPreset {
+    /// Open air: no reverb.
+    pub const OUTDOORS: Self = Self {
+        decay_time: 0.3,
+        pre_delay: 0.0,
+        wet: 0.0,
+        damping: 0.5,
+    };
+
+    /// A furnished room.
+    pub const ROOM: Self = Self {
+        decay_time: 0.6,
+        pre_delay: 0.005,
+        wet: 0.2,
+        damping: 0.6,
+    };
+
+    /// A large hall with a long, bright tail.
+    pub const HALL: Self = Self {
+        decay_time: 2.5,
+        pre_delay: 0.02,
+        wet: 0.35,
+        damping: 0.3,
+    };
+
+    /// A cave with strong, dark reflections.
+    pub const CAVE: Self = Self {
+        decay_time: 3.5,
+        pre_delay: 0.03,
+        wet: 0.5,
+        damping: 0.7,
+    };
+
+    /// Returns a weighted blend of `self` and `other`.
+    #[must_use]
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        Self {
+            decay_time: mix(self.decay_time, other.decay_time),
+            pre_delay: mix(self.pre_delay, other.pre_delay),
+            wet: mix(self.wet, other.wet),
+            damping: mix(self.damping, other.damping),
+        }
+    }
+}
+
+/// Component applying a reverb preset inside a box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReverbZone {
+    /// Center of the box.
+    pub center: [f32; 3],
+    /// Half the box size along each axis.
+    pub half_extents: [f32; 3],
+    /// Distance outside the box over which the zone fades out.
+    pub blend_distance: f32,
+    /// Reverb applied inside the box.
+    pub preset: ReverbPreset,
+}
+
+impl ReverbZone {
+    /// Returns the zone's influence at `position`: 1 inside the box,
+    /// falling to 0 at the blend distance.
+    #[must_use]
+    pub fn weight(&self, position: [f32; 3]) -> f32 {
+        let outside = (0..3)
+            .map(|axis| {
+                let gap = (position[axis] - self.center[axis]).abs() - self.half_extents[axis];
+                gap.max(0.0).powi(2)
+            })
+            .sum::<f32>()
+            .sqrt();
+        if outside <= 0.0 {
+            1.0
+        } else if self.blend_distance <= 0.0 {
+            0.0
+        } else {
+            (1.0 - outside / self.blend_distance).max(0.0)
+        }
+    }
+}
+
+/// Returns the reverb heard at `listener`: the zones' presets weighted by
+/// their influence, with `ambient` filling in where they add up to less
+/// than one.
+///
+/// # Example
+///
+/// ```
+/// use audio::reverb::{blend_reverb, ReverbPreset, ReverbZone};
+///
+/// let cave = ReverbZone {
+///     center: [0.0; 3],
+///     half_extents: [10.0; 3],
+///     blend_distance: 4.0,
+///     preset: ReverbPreset::CAVE,
+/// };
+///
+/// assert_eq!(blend_reverb(&[cave], [0.0; 3], ReverbPreset::OUTDOORS), ReverbPreset::CAVE);
+/// assert_eq!(blend_reverb(&[cave], [20.0, 0.0, 0.0], ReverbPreset::OUTDOORS), ReverbPreset::OUTDOORS);
+/// ```
+#[must_use]
+pub fn blend_reverb<'a>(
+    zones: impl IntoIterator<Item = &'a ReverbZone>,
+    listener: [f32; 3],
+    ambient: ReverbPreset,
+) -> ReverbPreset {
+    let weighted: Vec<_> = zones
+        .into_iter()
+        .map(|zone| (zone.weight(listener), zone.preset))
+        .filter(|(weight, _)| *weight > 0.0)
+        .collect();
+    let total: f32 = weighted.iter().map(|(weight, _)| weight).sum();
+    if total <= 0.0 {
+        return ambient;
+    }
+    // Overlapping zones share the mix; a lone partial zone fades to ambient.
+    let mut blended = weighted[0].1;
+    let mut accumulated = weighted[0].0;
+    for (weight, preset) in &weighted[1..] {
+        accumulated += weight;
+        blended = blended.lerp(preset, weight / accumulated);
+    }
+    ambient.lerp(&blended, total.min(1.0))
+}
@@ -0,0 +1,41 @@
+//! Unit tests for reverb zones.
+
+use crate::reverb::{blend_reverb, ReverbPreset, ReverbZone};
+
+fn zone(center: [f32; 3], preset: ReverbPreset) -> ReverbZone {
+    ReverbZone {
+        center,
+        half_extents: [5.0; 3],
+        blend_distance: 2.0,
+        preset,
+    }
+}
+
+/// Verifies zone influence fades over the blend distance.
+#[test]
+fn weight_fades_outside_box() {
+    let hall = zone([0.0; 3], ReverbPreset::HALL);
+
+    assert!((hall.weight([4.0, 4.0, 4.0]) - 1.0).abs() < 1e-6);
+    assert!((hall.weight([6.0, 0.0, 0.0]) - 0.5).abs() < 1e-6);
+    assert!(hall.weight([8.0, 0.0, 0.0]).abs() < 1e-6);
+}
+
+/// Verifies a partially entered zone blends with the ambient preset.
+#[test]
+fn blends_with_ambient() {
+    let cave = zone([0.0; 3], ReverbPreset::CAVE);
+
+    let edge = blend_reverb(&[cave], [6.0, 0.0, 0.0], ReverbPreset::OUTDOORS);
+    assert_eq!(edge, ReverbPreset::OUTDOORS.lerp(&ReverbPreset::CAVE, 0.5));
+}
+
+/// Verifies overlapping zones share the mix by weight.
+#[test]
+fn overlapping_zones_share_mix() {
+    let room = zone([0.0; 3], ReverbPreset::ROOM);
+    let hall = zone([4.0, 0.0, 0.0], ReverbPreset::HALL);
+
+    let overlap = blend_reverb(&[room, hall], [2.0, 0.0, 0.0], ReverbPreset::OUTDOORS);
+    assert_eq!(overlap, ReverbPreset::ROOM.lerp(&ReverbPreset::HALL, 0.5));
+}
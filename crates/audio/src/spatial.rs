@@ -0,0 +1,245 @@
+//! Spatial sound parameters.
+//!
+//! Every frame, each playing [`Emitter`] is evaluated against the
+//! [`Listener`] to produce the [`SpatialParams`] its voice is mixed with:
+//! distance attenuation, stereo pan, a low-pass filter cutoff, and a doppler
+//! pitch shift from the relative motion of source and listener.
+//!
+//! Sounds behind walls are muffled. A [`SoundOcclusion`] implementation
+//! casts rays through the world, usually the physics world, and reports how
+//! much sound passes; occluded sounds get quieter and lose high
+//! frequencies. 2D games use the same model with every `z` set to zero.
+
+use std::time::Duration;
+
+/// Speed of sound in air, in world units (meters) per second.
+pub const SPEED_OF_SOUND: f32 = 343.0;
+
+/// Low-pass cutoff of an unobstructed sound, in hertz.
+pub const OPEN_CUTOFF_HZ: f32 = 22_000.0;
+
+/// Low-pass cutoff of a fully occluded sound, in hertz.
+pub const OCCLUDED_CUTOFF_HZ: f32 = 600.0;
+
+/// Gain of a fully occluded sound.
+pub const OCCLUDED_GAIN: f32 = 0.3;
+
+/// Time occlusion takes to follow a change, so walls fade sounds instead of
+/// cutting them.
+pub const OCCLUSION_SMOOTHING: Duration = Duration::from_millis(150);
+
+/// Doppler pitch shifts are clamped to this factor in either direction.
+const MAX_DOPPLER_SHIFT: f32 = 2.0;
+
+/// Reports how much sound passes between two points.
+pub trait SoundOcclusion {
+    /// Returns the fraction of sound reaching `to` from `from`: 1 when the
+    /// path is clear, 0 when fully blocked.
+    fn transmission(&self, from: [f32; 3], to: [f32; 3]) -> f32;
+}
+
+/// Occlusion from a raycast that counts the surfaces between two points.
+///
+/// Each surface lets `per_hit` of the sound through.
+#[derive(Debug, Clone, Copy)]
+pub struct RaycastOcclusion<F> {
+    /// Returns the number of surfaces hit between two points.
+    raycast: F,
+    /// Fraction of sound passing one surface.
+    per_hit: f32,
+}
+
+impl<F: Fn([f32; 3], [f32; 3]) -> usize> RaycastOcclusion<F> {
+    /// Creates occlusion from a raycast counting surface hits.
+    #[must_use]
+    pub fn new(raycast: F, per_hit: f32) -> Self {
+        Self {
+            raycast,
+            per_hit: per_hit.clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl<F: Fn([f32; 3], [f32; 3]) -> usize> SoundOcclusion for RaycastOcclusion<F> {
+    fn transmission(&self, from: [f32; 3], to: [f32; 3]) -> f32 {
+        let hits = (self.raycast)(from, to);
+        (0..hits).fold(1.0, |transmission, _| transmission * self.per_hit)
+    }
+}
+
+/// The ears sounds are heard from, usually on the active camera.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Listener {
+    /// World position.
+    pub position: [f32; 3],
+    /// Velocity in units per second.
+    pub velocity: [f32; 3],
+    /// Unit vector pointing to the listener's right.
+    pub right: [f32; 3],
+}
+
+impl Default for Listener {
+    fn default() -> Self {
+        Self {
+            position: [0.0; 3],
+            velocity: [0.0; 3],
+            right: [1.0, 0.0, 0.0],
+        }
+    }
+}
+
+/// Component positioning a sound source in the world.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Emitter {
+    /// World position.
+    pub position: [f32; 3],
+    /// Velocity in units per second.
+    pub velocity: [f32; 3],
+    /// Distance up to which the sound plays at full volume.
+    pub min_distance: f32,
+    /// Distance beyond which the sound is silent.
+    pub max_distance: f32,
+    /// Scale of the doppler effect; 0 disables it.
+    pub doppler: f32,
+    /// Whether walls muffle the sound.
+    pub occludable: bool,
+    /// Smoothed transmission from the last update.
+    transmission: f32,
+}
+
+impl Default for Emitter {
+    fn default() -> Self {
+        Self {
+            position: [0.0; 3],
+            velocity: [0.0; 3],
+            min_distance: 1.0,
+            max_distance: 100.0,
+            doppler: 1.0,
+            occludable: true,
+            transmission: 1.0,
+        }
+    }
+}
+
+/// Mixing parameters of one voice.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpatialParams {
+    /// Volume multiplier.
+    pub gain: f32,
+    /// Stereo position, from -1 (left) to 1 (right).
+    pub pan: f32,
+    /// Low-pass filter cutoff in hertz.
+    pub low_pass_hz: f32,
+    /// Playback rate multiplier from the doppler effect.
+    pub pitch: f32,
+}
+
+impl Default for SpatialParams {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            pan: 0.0,
+            low_pass_hz: OPEN_CUTOFF_HZ,
+            pitch: 1.0,
+        }
+    }
+}
+
+impl Emitter {
+    /// Creates an emitter at `position` with default ranges.
+    #[must_use]
+    pub fn at(position: [f32; 3]) -> Self {
+        Self {
+            position,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the distances of full volume and silence.
+    #[must_use]
+    pub fn with_range(mut self, min_distance: f32, max_distance: f32) -> Self {
+        self.min_distance = min_distance.max(f32::EPSILON);
+        self.max_distance = max_distance.max(self.min_distance);
+        self
+    }
+
+    /// Returns the smoothed fraction of sound reaching the listener.
+    #[must_use]
+    #[inline]
+    pub fn transmission(&self) -> f32 {
+        self.transmission
+    }
+
+    /// Updates occlusion and returns the voice's parameters.
+    ///
+    /// `occlusion` is only queried for occludable emitters within range.
+    pub fn update(
+        &mut self,
+        listener: &Listener,
+        occlusion: Option<&dyn SoundOcclusion>,
+        delta: Duration,
+    ) -> SpatialParams {
+        let offset = sub(self.position, listener.position);
+        let distance = length(offset);
+        if distance >= self.max_distance {
+            return SpatialParams {
+                gain: 0.0,
+                ..SpatialParams::default()
+            };
+        }
+
+        let target = match occlusion {
+            Some(occlusion) if self.occludable => occlusion
+                .transmission(listener.position, self.position)
+                .clamp(0.0, 1.0),
+            _ => 1.0,
+        };
+        let blend = (delta.as_secs_f32() / OCCLUSION_SMOOTHING.as_secs_f32()).min(1.0);
+        self.transmission += (target - self.transmission) * blend;
+
+        let attenuation = self.min_distance / distance.max(self.min_distance);
+        let direction = if distance > f32::EPSILON {
+            offset.map(|component| component / distance)
+        } else {
+            [0.0; 3]
+        };
+        SpatialParams {
+            gain: attenuation * lerp(OCCLUDED_GAIN, 1.0, self.transmission),
+            pan: dot(direction, listener.right).clamp(-1.0, 1.0),
+            low_pass_hz: OCCLUDED_CUTOFF_HZ
+                * (OPEN_CUTOFF_HZ / OCCLUDED_CUTOFF_HZ).powf(self.transmission),
+            pitch: self.doppler_pitch(listener, direction),
+        }
+    }
+
+    /// Returns the doppler pitch factor for sound travelling along
+    /// `direction` from the listener to the emitter.
+    fn doppler_pitch(&self, listener: &Listener, direction: [f32; 3]) -> f32 {
+        if self.doppler <= 0.0 {
+            return 1.0;
+        }
+        // Closing speeds: positive when listener and source approach.
+        let listener_speed = dot(listener.velocity, direction) * self.doppler;
+        let source_speed = -dot(self.velocity, direction) * self.doppler;
+        let limit = SPEED_OF_SOUND * 0.9;
+        ((SPEED_OF_SOUND + listener_speed.clamp(-limit, limit))
+            / (SPEED_OF_SOUND - source_speed.clamp(-limit, limit)))
+        .clamp(1.0 / MAX_DOPPLER_SHIFT, MAX_DOPPLER_SHIFT)
+    }
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn length(a: [f32; 3]) -> f32 {
+    dot(a, a).sqrt()
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
@@ -0,0 +1,85 @@
+//! Unit tests for spatial sound parameters.
+
+use crate::spatial::{
+    Emitter, Listener, RaycastOcclusion, SoundOcclusion, OCCLUDED_CUTOFF_HZ, OPEN_CUTOFF_HZ,
+};
+use std::time::Duration;
+
+const FRAME: Duration = Duration::from_millis(16);
+
+/// Verifies volume falls off with distance and stops at the maximum range.
+#[test]
+fn attenuates_with_distance() {
+    let listener = Listener::default();
+    let mut near = Emitter::at([0.5, 0.0, 0.0]).with_range(1.0, 50.0);
+    let mut mid = Emitter::at([4.0, 0.0, 0.0]).with_range(1.0, 50.0);
+    let mut far = Emitter::at([60.0, 0.0, 0.0]).with_range(1.0, 50.0);
+
+    assert!((near.update(&listener, None, FRAME).gain - 1.0).abs() < 1e-6);
+    assert!((mid.update(&listener, None, FRAME).gain - 0.25).abs() < 1e-6);
+    assert_eq!(
+        far.update(&listener, None, FRAME).gain.to_bits(),
+        0.0_f32.to_bits()
+    );
+}
+
+/// Verifies sounds pan toward the side they come from.
+#[test]
+fn pans_by_direction() {
+    let listener = Listener::default();
+
+    let right = Emitter::at([3.0, 0.0, 0.0]).update(&listener, None, FRAME);
+    let left = Emitter::at([-3.0, 0.0, 0.0]).update(&listener, None, FRAME);
+    let ahead = Emitter::at([0.0, 0.0, -3.0]).update(&listener, None, FRAME);
+    assert!((right.pan - 1.0).abs() < 1e-6);
+    assert!((left.pan + 1.0).abs() < 1e-6);
+    assert!(ahead.pan.abs() < 1e-6);
+}
+
+/// Verifies approaching sources are pitched up and receding ones down.
+#[test]
+fn doppler_follows_motion() {
+    let listener = Listener::default();
+    let mut approaching = Emitter::at([10.0, 0.0, 0.0]);
+    approaching.velocity = [-34.3, 0.0, 0.0];
+    let mut receding = Emitter::at([10.0, 0.0, 0.0]);
+    receding.velocity = [34.3, 0.0, 0.0];
+    let mut disabled = approaching;
+    disabled.doppler = 0.0;
+
+    assert!((approaching.update(&listener, None, FRAME).pitch - 1.0 / 0.9).abs() < 1e-5);
+    assert!((receding.update(&listener, None, FRAME).pitch - 1.0 / 1.1).abs() < 1e-5);
+    assert_eq!(
+        disabled.update(&listener, None, FRAME).pitch.to_bits(),
+        1.0_f32.to_bits()
+    );
+}
+
+/// Verifies occlusion fades in and muffles the sound.
+#[test]
+fn occlusion_muffles_over_time() {
+    let wall = RaycastOcclusion::new(|_, _| 1, 0.0);
+    let listener = Listener::default();
+    let mut emitter = Emitter::at([2.0, 0.0, 0.0]);
+
+    let first = emitter.update(&listener, Some(&wall), Duration::from_millis(75));
+    assert!((emitter.transmission() - 0.5).abs() < 1e-6);
+    assert!(first.low_pass_hz < OPEN_CUTOFF_HZ && first.low_pass_hz > OCCLUDED_CUTOFF_HZ);
+
+    let settled = emitter.update(&listener, Some(&wall), Duration::from_secs(1));
+    assert!((settled.low_pass_hz - OCCLUDED_CUTOFF_HZ).abs() < 1e-2);
+    assert!((settled.gain - 0.15).abs() < 1e-6);
+
+    emitter.occludable = false;
+    emitter.update(&listener, Some(&wall), Duration::from_secs(1));
+    assert!((emitter.transmission() - 1.0).abs() < 1e-6);
+}
+
+/// Verifies each surface hit lets through its share of the sound.
+#[test]
+fn raycast_occlusion_compounds_hits() {
+    let occlusion = RaycastOcclusion::new(|_, to: [f32; 3]| if to[0] > 0.0 { 2 } else { 0 }, 0.5);
+
+    assert!((occlusion.transmission([0.0; 3], [0.0; 3]) - 1.0).abs() < 1e-6);
+    assert!((occlusion.transmission([0.0; 3], [2.0, 0.0, 0.0]) - 0.25).abs() < 1e-6);
+}
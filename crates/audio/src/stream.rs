@@ -0,0 +1,504 @@
+//! Streaming music playback.
+//!
+//! Music tracks are too large to decode into memory up front. A
+//! [`MusicStream`] decodes a few seconds ahead into a ring buffer on a
+//! scheduler background thread, and the output device drains it. Tracks
+//! can loop between [`LoopPoints`] without a gap, since decoding simply
+//! continues from the loop start into the same buffer.
+//!
+//! The [`MusicPlayer`] owns the playing stream and cross-fades to the next
+//! track with equal-power gains.
+
+use scheduler::spawn_background;
+use std::collections::VecDeque;
+use std::io::{Read, Seek};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tracing::warn;
+
+/// Default amount of audio decoded ahead of playback.
+pub const DEFAULT_STREAM_BUFFER: Duration = Duration::from_secs(2);
+
+/// Produces interleaved samples from an encoded track.
+pub trait Decoder: Send {
+    /// Returns the number of interleaved channels.
+    fn channels(&self) -> usize;
+
+    /// Returns the sample rate in hertz.
+    fn sample_rate(&self) -> u32;
+
+    /// Decodes the next block of interleaved samples, or returns `None` at
+    /// the end of the track.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the track data is corrupt.
+    fn next_block(&mut self) -> anyhow::Result<Option<Vec<f32>>>;
+
+    /// Restarts decoding from the first frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying reader cannot seek.
+    fn rewind(&mut self) -> anyhow::Result<()>;
+}
+
+/// Decoder for Ogg Vorbis tracks.
+pub struct VorbisDecoder<R: Read + Seek> {
+    /// The lewton stream reader.
+    reader: lewton::inside_ogg::OggStreamReader<R>,
+}
+
+impl<R: Read + Seek> std::fmt::Debug for VorbisDecoder<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VorbisDecoder")
+            .field("channels", &self.channels())
+            .field("sample_rate", &self.sample_rate())
+            .finish()
+    }
+}
+
+impl<R: Read + Seek> VorbisDecoder<R> {
+    /// Reads the stream headers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `reader` does not hold an Ogg Vorbis stream.
+    pub fn new(reader: R) -> anyhow::Result<Self> {
+        let reader = lewton::inside_ogg::OggStreamReader::new(reader)
+            .map_err(|e| anyhow::anyhow!("invalid ogg vorbis stream: {e}"))?;
+        Ok(Self { reader })
+    }
+
+    fn channels(&self) -> usize {
+        usize::from(self.reader.ident_hdr.audio_channels)
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.reader.ident_hdr.audio_sample_rate
+    }
+}
+
+impl<R: Read + Seek + Send> Decoder for VorbisDecoder<R> {
+    fn channels(&self) -> usize {
+        Self::channels(self)
+    }
+
+    fn sample_rate(&self) -> u32 {
+        Self::sample_rate(self)
+    }
+
+    fn next_block(&mut self) -> anyhow::Result<Option<Vec<f32>>> {
+        let block = self
+            .reader
+            .read_dec_packet_generic::<lewton::samples::InterleavedSamples<f32>>()
+            .map_err(|e| anyhow::anyhow!("failed to decode ogg vorbis packet: {e}"))?;
+        Ok(block.map(|block| block.samples))
+    }
+
+    fn rewind(&mut self) -> anyhow::Result<()> {
+        self.reader
+            .seek_absgp_pg(0)
+            .map_err(|e| anyhow::anyhow!("failed to rewind ogg vorbis stream: {e}"))
+    }
+}
+
+/// Frames between which a track loops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LoopPoints {
+    /// First frame played when the track wraps around.
+    pub start: u64,
+    /// Frame at which the track wraps around; `None` for the end of the
+    /// track.
+    pub end: Option<u64>,
+}
+
+/// Decoding state, only touched by whoever refills the buffer.
+struct Feed {
+    /// The track decoder.
+    decoder: Box<dyn Decoder>,
+    /// Looping, if the track loops.
+    loop_points: Option<LoopPoints>,
+    /// Frames decoded since the start of the track.
+    position: u64,
+    /// Frames before this are dropped after wrapping around.
+    skip_until: u64,
+    /// Whether any samples were produced since the last wrap.
+    produced: bool,
+    /// Decoded samples that did not fit into the buffer.
+    pending: Vec<f32>,
+    /// Whether the track has ended.
+    finished: bool,
+}
+
+impl Feed {
+    /// Returns the next samples to play, honoring loop points.
+    fn next_samples(&mut self, channels: usize) -> anyhow::Result<Option<Vec<f32>>> {
+        loop {
+            let end = self.loop_points.and_then(|points| points.end);
+            if end.is_some_and(|end| self.position >= end) {
+                self.wrap()?;
+            }
+            let Some(block) = self.decoder.next_block()? else {
+                if self.loop_points.is_some() && self.produced {
+                    self.wrap()?;
+                    continue;
+                }
+                return Ok(None);
+            };
+            let first = self.position;
+            let frames = (block.len() / channels) as u64;
+            self.position += frames;
+            let keep_from = first.max(self.skip_until);
+            let keep_to = end.map_or(self.position, |end| self.position.min(end));
+            if keep_from >= keep_to {
+                continue;
+            }
+            self.produced = true;
+            let offset =
+                |frame: u64| usize::try_from(frame - first).unwrap_or(usize::MAX) * channels;
+            return Ok(Some(block[offset(keep_from)..offset(keep_to)].to_vec()));
+        }
+    }
+
+    /// Restarts decoding at the loop start.
+    fn wrap(&mut self) -> anyhow::Result<()> {
+        self.decoder.rewind()?;
+        self.position = 0;
+        self.skip_until = self.loop_points.map_or(0, |points| points.start);
+        self.produced = false;
+        Ok(())
+    }
+}
+
+/// State shared between the stream and its background refills.
+struct Shared {
+    /// Decoded samples waiting to be played.
+    ring: Mutex<VecDeque<f32>>,
+    /// Ring capacity in samples.
+    capacity: usize,
+    /// Decoder state.
+    feed: Mutex<Feed>,
+    /// Set while a background refill is queued or running.
+    refilling: AtomicBool,
+    /// Interleaved channels.
+    channels: usize,
+}
+
+impl Shared {
+    fn refill(&self) -> anyhow::Result<()> {
+        let mut feed = self.feed.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            let space = {
+                let ring = self.ring.lock().unwrap_or_else(PoisonError::into_inner);
+                self.capacity - ring.len()
+            };
+            if space == 0 {
+                return Ok(());
+            }
+            if feed.pending.is_empty() {
+                if feed.finished {
+                    return Ok(());
+                }
+                let Some(samples) = feed.next_samples(self.channels)? else {
+                    feed.finished = true;
+                    return Ok(());
+                };
+                feed.pending = samples;
+            }
+            let count = space.min(feed.pending.len());
+            let mut ring = self.ring.lock().unwrap_or_else(PoisonError::into_inner);
+            ring.extend(feed.pending.drain(..count));
+        }
+    }
+}
+
+/// A track decoded incrementally into a ring buffer.
+///
+/// # Example
+///
+/// ```no_run
+/// use audio::stream::{LoopPoints, MusicStream, VorbisDecoder};
+/// use std::fs::File;
+///
+/// let decoder = VorbisDecoder::new(File::open("music/theme.ogg")?)?;
+/// let stream = MusicStream::new(Box::new(decoder))
+///     .looping(LoopPoints { start: 44_100, end: None });
+/// stream.request_refill();
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct MusicStream {
+    /// Buffer and decoder.
+    shared: Arc<Shared>,
+    /// Sample rate in hertz.
+    sample_rate: u32,
+}
+
+impl std::fmt::Debug for MusicStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MusicStream")
+            .field("channels", &self.shared.channels)
+            .field("sample_rate", &self.sample_rate)
+            .field("buffered", &self.buffered())
+            .finish_non_exhaustive()
+    }
+}
+
+impl MusicStream {
+    /// Creates a stream buffering [`DEFAULT_STREAM_BUFFER`] ahead.
+    #[must_use]
+    pub fn new(decoder: Box<dyn Decoder>) -> Self {
+        Self::with_buffer(decoder, DEFAULT_STREAM_BUFFER)
+    }
+
+    /// Creates a stream buffering `buffer` of audio ahead.
+    #[must_use]
+    pub fn with_buffer(decoder: Box<dyn Decoder>, buffer: Duration) -> Self {
+        let channels = decoder.channels().max(1);
+        let sample_rate = decoder.sample_rate();
+        let frames = (buffer.as_secs_f64() * f64::from(sample_rate)).ceil();
+        // Saturating float to integer conversion; at least one frame.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let capacity = (frames as usize).max(1) * channels;
+        Self {
+            shared: Arc::new(Shared {
+                ring: Mutex::new(VecDeque::with_capacity(capacity)),
+                capacity,
+                feed: Mutex::new(Feed {
+                    decoder,
+                    loop_points: None,
+                    position: 0,
+                    skip_until: 0,
+                    produced: false,
+                    pending: Vec::new(),
+                    finished: false,
+                }),
+                refilling: AtomicBool::new(false),
+                channels,
+            }),
+            sample_rate,
+        }
+    }
+
+    /// Loops the track between `points`.
+    #[must_use]
+    pub fn looping(self, points: LoopPoints) -> Self {
+        self.shared
+            .feed
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .loop_points = Some(points);
+        self
+    }
+
+    /// Returns the number of interleaved channels.
+    #[must_use]
+    pub fn channels(&self) -> usize {
+        self.shared.channels
+    }
+
+    /// Returns the sample rate in hertz.
+    #[must_use]
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns the number of decoded samples waiting to be played.
+    #[must_use]
+    pub fn buffered(&self) -> usize {
+        self.shared
+            .ring
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Returns `true` once the track has ended and every sample was played.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.buffered() == 0
+            && self
+                .shared
+                .feed
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .finished
+    }
+
+    /// Decodes until the buffer is full, on the calling thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if decoding fails.
+    pub fn refill(&self) -> anyhow::Result<()> {
+        self.shared.refill()
+    }
+
+    /// Queues a background refill if the buffer is less than half full and
+    /// none is queued yet.
+    pub fn request_refill(&self) {
+        if self.buffered() >= self.shared.capacity / 2
+            || self.shared.refilling.swap(true, Ordering::AcqRel)
+        {
+            return;
+        }
+        let shared = Arc::clone(&self.shared);
+        spawn_background("music stream refill", move || {
+            if let Err(e) = shared.refill() {
+                warn!(error = %e, "music stream decoding failed");
+                shared
+                    .feed
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .finished = true;
+            }
+            shared.refilling.store(false, Ordering::Release);
+        });
+    }
+
+    /// Moves buffered samples into `out`, returning how many were written.
+    ///
+    /// The rest of `out` is left untouched; a short read means the decoder
+    /// fell behind or the track ended.
+    pub fn read(&self, out: &mut [f32]) -> usize {
+        let mut ring = self
+            .shared
+            .ring
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let count = out.len().min(ring.len());
+        for (slot, sample) in out.iter_mut().zip(ring.drain(..count)) {
+            *slot = sample;
+        }
+        count
+    }
+}
+
+/// Plays one music track at a time, cross-fading between tracks.
+#[derive(Debug)]
+pub struct MusicPlayer {
+    /// Output channels.
+    channels: usize,
+    /// Output sample rate in hertz.
+    sample_rate: u32,
+    /// Track fading in or playing.
+    current: Option<MusicStream>,
+    /// Track fading out.
+    outgoing: Option<MusicStream>,
+    /// Length of the fade in frames.
+    fade_frames: u64,
+    /// Frames of the fade done.
+    faded: u64,
+    /// Scratch buffer for reading streams.
+    scratch: Vec<f32>,
+}
+
+impl MusicPlayer {
+    /// Creates a player for an output of the given format.
+    #[must_use]
+    pub fn new(sample_rate: u32, channels: usize) -> Self {
+        Self {
+            channels: channels.max(1),
+            sample_rate,
+            current: None,
+            outgoing: None,
+            fade_frames: 0,
+            faded: 0,
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Returns the playing track.
+    #[must_use]
+    pub fn current(&self) -> Option<&MusicStream> {
+        self.current.as_ref()
+    }
+
+    /// Returns `true` while a cross-fade is in progress.
+    #[must_use]
+    pub fn is_fading(&self) -> bool {
+        self.outgoing.is_some()
+    }
+
+    /// Starts `stream`, cross-fading from the playing track over `fade`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream's format differs from the output's.
+    pub fn play(&mut self, stream: MusicStream, fade: Duration) -> anyhow::Result<()> {
+        if stream.channels() != self.channels || stream.sample_rate() != self.sample_rate {
+            anyhow::bail!(
+                "music stream is {} Hz with {} channels, output is {} Hz with {} channels",
+                stream.sample_rate(),
+                stream.channels(),
+                self.sample_rate,
+                self.channels
+            );
+        }
+        stream.request_refill();
+        self.start_fade(Some(stream), fade);
+        Ok(())
+    }
+
+    /// Fades out the playing track over `fade`.
+    pub fn stop(&mut self, fade: Duration) {
+        self.start_fade(None, fade);
+    }
+
+    fn start_fade(&mut self, next: Option<MusicStream>, fade: Duration) {
+        let previous = std::mem::replace(&mut self.current, next);
+        // A fade interrupted by another drops the older outgoing track.
+        self.outgoing = previous.filter(|_| !fade.is_zero());
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let frames = (fade.as_secs_f64() * f64::from(self.sample_rate)).round() as u64;
+        self.fade_frames = frames.max(1);
+        self.faded = 0;
+    }
+
+    /// Fills `out` with the next interleaved samples, mixing the tracks of a
+    /// cross-fade, and queues background refills.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fill(&mut self, out: &mut [f32]) {
+        out.fill(0.0);
+        let fading = self.outgoing.is_some();
+        let start = self.faded;
+        let channels = self.channels;
+        let fade_frames = self.fade_frames;
+        // Equal-power gains of the incoming (`true`) and outgoing track.
+        let gain = |frame: usize, incoming: bool| {
+            if !fading {
+                return 1.0;
+            }
+            let t = ((start + frame as u64) as f32 / fade_frames as f32).min(1.0);
+            let angle = t * std::f32::consts::FRAC_PI_2;
+            if incoming {
+                angle.sin()
+            } else {
+                angle.cos()
+            }
+        };
+
+        self.scratch.resize(out.len(), 0.0);
+        for (stream, incoming) in [(&self.current, true), (&self.outgoing, false)] {
+            let Some(stream) = stream else {
+                continue;
+            };
+            let read = stream.read(&mut self.scratch);
+            for (i, (slot, sample)) in out.iter_mut().zip(&self.scratch[..read]).enumerate() {
+                *slot += sample * gain(i / channels, incoming);
+            }
+            stream.request_refill();
+        }
+
+        if fading {
+            self.faded += (out.len() / channels) as u64;
+            if self.faded >= self.fade_frames {
+                self.outgoing = None;
+            }
+        }
+        if self.current.as_ref().is_some_and(MusicStream::is_finished) {
+            self.current = None;
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! Unit tests for streaming music playback.
+
+use crate::stream::{Decoder, LoopPoints, MusicPlayer, MusicStream, VorbisDecoder};
+use std::io::Cursor;
+use std::time::{Duration, Instant};
+
+/// Mono track whose samples are their frame index, decoded `block` frames
+/// at a time.
+struct Ramp {
+    frames: u32,
+    block: u32,
+    position: u32,
+}
+
+impl Decoder for Ramp {
+    fn channels(&self) -> usize {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        10
+    }
+
+    fn next_block(&mut self) -> anyhow::Result<Option<Vec<f32>>> {
+        if self.position >= self.frames {
+            return Ok(None);
+        }
+        let end = (self.position + self.block).min(self.frames);
+        let block = (self.position..end)
+            .map(|frame| f32::from(u16::try_from(frame).unwrap()))
+            .collect();
+        self.position = end;
+        Ok(Some(block))
+    }
+
+    fn rewind(&mut self) -> anyhow::Result<()> {
+        self.position = 0;
+        Ok(())
+    }
+}
+
+/// Stream over a ramp of `frames` frames buffering one second (10 frames).
+fn ramp(frames: u32) -> MusicStream {
+    MusicStream::with_buffer(
+        Box::new(Ramp {
+            frames,
+            block: 3,
+            position: 0,
+        }),
+        Duration::from_secs(1),
+    )
+}
+
+/// Reads `count` samples, refilling as needed.
+fn play(stream: &MusicStream, count: usize) -> Vec<f32> {
+    let mut samples = Vec::new();
+    let mut chunk = [0.0; 4];
+    while samples.len() < count {
+        stream.refill().unwrap();
+        let read = stream.read(&mut chunk);
+        if read == 0 {
+            break;
+        }
+        samples.extend_from_slice(&chunk[..read]);
+    }
+    samples.truncate(count);
+    samples
+}
+
+/// Verifies the buffer holds at most its capacity and the track ends.
+#[test]
+fn buffers_ahead_and_finishes() {
+    let stream = ramp(25);
+    stream.refill().unwrap();
+    assert_eq!(stream.buffered(), 10);
+
+    let samples = play(&stream, 100);
+    assert_eq!(samples.len(), 25);
+    assert_eq!(samples[24].to_bits(), 24.0_f32.to_bits());
+    assert!(stream.is_finished());
+}
+
+/// Verifies loops wrap seamlessly between the loop points.
+#[test]
+fn loops_between_points() {
+    let bounded = ramp(8).looping(LoopPoints {
+        start: 2,
+        end: Some(6),
+    });
+    let to_end = ramp(8).looping(LoopPoints {
+        start: 5,
+        end: None,
+    });
+
+    assert_eq!(
+        play(&bounded, 14),
+        [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 2.0, 3.0, 4.0, 5.0, 2.0, 3.0, 4.0, 5.0]
+    );
+    assert_eq!(
+        play(&to_end, 12),
+        [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 5.0, 6.0, 7.0, 5.0]
+    );
+    assert!(!to_end.is_finished());
+}
+
+/// Verifies refills requested by playback run in the background.
+#[test]
+fn refills_in_background() {
+    let stream = ramp(100);
+    stream.request_refill();
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while stream.buffered() < 10 {
+        assert!(Instant::now() < deadline, "background refill did not run");
+        std::thread::yield_now();
+    }
+}
+
+/// Verifies tracks cross-fade with equal power and the old track stops.
+#[test]
+fn cross_fades_tracks() {
+    let mut player = MusicPlayer::new(10, 1);
+    let first = ramp(1000);
+    first.refill().unwrap();
+    player.play(first, Duration::ZERO).unwrap();
+    let mut out = [0.0; 2];
+    player.fill(&mut out);
+    assert_eq!(
+        out.map(f32::to_bits),
+        [0.0_f32.to_bits(), 1.0_f32.to_bits()]
+    );
+
+    let second = ramp(1000);
+    second.refill().unwrap();
+    player.play(second, Duration::from_millis(400)).unwrap();
+    assert!(player.is_fading());
+    player.current().unwrap().refill().unwrap();
+    let mut out = [0.0; 4];
+    player.fill(&mut out);
+    let half = std::f32::consts::FRAC_PI_4;
+    // Halfway through, frame 4 of the old track meets frame 2 of the new one.
+    assert!((out[2] - (4.0 * half.cos() + 2.0 * half.sin())).abs() < 1e-5);
+    assert!(!player.is_fading());
+}
+
+/// Verifies unsupported input is rejected.
+#[test]
+fn rejects_bad_input() {
+    let mut player = MusicPlayer::new(48_000, 2);
+
+    assert!(player.play(ramp(10), Duration::ZERO).is_err());
+    assert!(VorbisDecoder::new(Cursor::new(b"not an ogg file".to_vec())).is_err());
+}
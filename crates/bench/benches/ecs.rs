@@ -0,0 +1,94 @@
+//! ECS hot paths: component iteration, component insertion and removal, and
+//! event dispatch.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use ecs::{Entity, Events, World};
+
+const ENTITIES: u64 = 10_000;
+
+#[derive(Debug, Clone, Copy)]
+struct Position([f32; 3]);
+
+#[derive(Debug, Clone, Copy)]
+struct Velocity([f32; 3]);
+
+#[derive(Debug, Clone, Copy)]
+struct Frozen;
+
+fn populated_world() -> (World, Vec<Entity>) {
+    let mut world = World::default();
+    let entities = (0..ENTITIES)
+        .map(|i| {
+            let entity = world.spawn();
+            #[allow(clippy::cast_precision_loss)]
+            let x = i as f32;
+            world.insert(entity, Position([x, 0.0, 0.0]));
+            world.insert(entity, Velocity([1.0, 0.5, 0.0]));
+            entity
+        })
+        .collect();
+    (world, entities)
+}
+
+fn iteration(c: &mut Criterion) {
+    let (mut world, entities) = populated_world();
+    c.bench_function("ecs/query_10k", |b| {
+        b.iter(|| {
+            let sum: f32 = world.query::<Position>().map(|(_, p)| p.0[0]).sum();
+            black_box(sum)
+        });
+    });
+    c.bench_function("ecs/integrate_10k", |b| {
+        b.iter(|| {
+            for &entity in &entities {
+                let Some(&Velocity(velocity)) = world.get::<Velocity>(entity) else {
+                    continue;
+                };
+                if let Some(Position(position)) = world.get_mut::<Position>(entity) {
+                    for axis in 0..3 {
+                        position[axis] += velocity[axis] / 60.0;
+                    }
+                }
+            }
+        });
+    });
+}
+
+fn component_moves(c: &mut Criterion) {
+    c.bench_function("ecs/insert_remove_10k", |b| {
+        b.iter_batched_ref(
+            populated_world,
+            |(world, entities)| {
+                for &entity in entities.iter() {
+                    world.insert(entity, Frozen);
+                }
+                for &entity in entities.iter() {
+                    black_box(world.remove::<Frozen>(entity));
+                }
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    c.bench_function("ecs/spawn_despawn_10k", |b| {
+        b.iter(|| {
+            let (mut world, entities) = populated_world();
+            for entity in entities {
+                world.despawn(entity);
+            }
+        });
+    });
+}
+
+fn events(c: &mut Criterion) {
+    let mut events = Events::<u64>::default();
+    c.bench_function("ecs/events_10k", |b| {
+        b.iter(|| {
+            events.update();
+            events.send_batch(0..ENTITIES);
+            black_box(events.iter().sum::<u64>())
+        });
+    });
+}
+
+criterion_group!(benches, iteration, component_moves, events);
+criterion_main!(benches);
@@ -0,0 +1,46 @@
+//! GPU-driven path hot paths: building and culling indirect scenes.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use math::{Aabb, Mat4, Vec3};
+use render::indirect::{IndirectScene, InstanceDesc};
+use render::mesh_pool::MeshPool;
+
+/// Builds a scene of `count` instances of 16 meshes on a grid around the
+/// view, about half of them on screen.
+#[allow(clippy::cast_precision_loss)]
+fn scene(count: u32) -> IndirectScene {
+    let mut pool = MeshPool::default();
+    let meshes: Vec<_> = (0..16)
+        .map(|_| pool.insert(24, 36).expect("meshes fit a block"))
+        .collect();
+    let bounds = Aabb::new(Vec3::splat(-0.01), Vec3::splat(0.01));
+    let instances = (0..count).map(|i| InstanceDesc {
+        mesh: meshes[i as usize % meshes.len()],
+        model: Mat4::from_translation(Vec3::new(
+            (i % 512) as f32 / 128.0 - 2.0,
+            (i / 512 % 512) as f32 / 128.0 - 2.0,
+            0.5,
+        )),
+        bounds,
+        id: i,
+    });
+    IndirectScene::build(&pool, instances).expect("meshes are in the pool")
+}
+
+fn gpu_culling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("indirect/cull");
+    for count in [10_000, 100_000] {
+        let scene = scene(count);
+        group.bench_with_input(BenchmarkId::from_parameter(count), &scene, |b, scene| {
+            b.iter(|| black_box(scene.cull(&Mat4::IDENTITY, None)));
+        });
+    }
+    group.finish();
+
+    c.bench_function("indirect/build/100000", |b| {
+        b.iter(|| black_box(scene(100_000)));
+    });
+}
+
+criterion_group!(benches, gpu_culling);
+criterion_main!(benches);
@@ -0,0 +1,44 @@
+//! Render graph hot paths: serial versus parallel command recording.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use render::graph::{RecordMode, RenderGraph};
+
+/// Records `passes` independent passes, each encoding 2048 draw commands.
+fn record(passes: usize, mode: RecordMode) -> Vec<Vec<u64>> {
+    let mut graph = RenderGraph::new();
+    for pass in 0..passes {
+        graph
+            .add_pass(&format!("pass{pass}"), &[], move || {
+                Ok((0..2048u64)
+                    .map(|draw| black_box(draw.wrapping_mul(31) ^ pass as u64))
+                    .collect())
+            })
+            .expect("pass handles are valid");
+    }
+    graph
+        .record(mode)
+        .expect("recording failed")
+        .into_command_buffers()
+}
+
+fn command_recording(c: &mut Criterion) {
+    for (name, mode) in [
+        ("render_graph/serial", RecordMode::Serial),
+        ("render_graph/parallel", RecordMode::Parallel),
+    ] {
+        let mut group = c.benchmark_group(name);
+        for passes in [4, 16, 64] {
+            group.bench_with_input(
+                BenchmarkId::from_parameter(passes),
+                &passes,
+                |b, &passes| {
+                    b.iter(|| record(passes, mode));
+                },
+            );
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, command_recording);
+criterion_main!(benches);
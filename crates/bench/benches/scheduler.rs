@@ -0,0 +1,47 @@
+//! Scheduler hot paths: job graph throughput.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use scheduler::JobGraph;
+
+/// Runs `width` independent jobs between a start and a finish job.
+fn fan_out_fan_in(width: usize) -> anyhow::Result<()> {
+    let mut graph = JobGraph::new();
+    let start = graph.add_job("start", &[], || Ok(()))?;
+    let workers = (0..width)
+        .map(|i| {
+            graph.add_job(&format!("work{i}"), &[start], move || {
+                black_box((0..256u64).fold(i as u64, |acc, x| acc.wrapping_mul(31) ^ x));
+                Ok(())
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    graph.add_job("finish", &workers, || Ok(()))?;
+    graph.run()
+}
+
+/// Runs `length` jobs, each depending on the previous one.
+fn chain(length: usize) -> anyhow::Result<()> {
+    let mut graph = JobGraph::new();
+    let mut previous = None;
+    for i in 0..length {
+        let dependencies: Vec<_> = previous.into_iter().collect();
+        previous = Some(graph.add_job(&format!("step{i}"), &dependencies, || Ok(()))?);
+    }
+    graph.run()
+}
+
+fn job_throughput(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scheduler/fan_out");
+    for width in [16, 256, 1024] {
+        group.bench_with_input(BenchmarkId::from_parameter(width), &width, |b, &width| {
+            b.iter(|| fan_out_fan_in(width).expect("job graph failed"));
+        });
+    }
+    group.finish();
+    c.bench_function("scheduler/chain_256", |b| {
+        b.iter(|| chain(256).expect("job graph failed"));
+    });
+}
+
+criterion_group!(benches, job_throughput);
+criterion_main!(benches);
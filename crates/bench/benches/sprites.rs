@@ -0,0 +1,22 @@
+//! Sprite hot paths: the moving sprites scenario and its phases.
+
+use bench::MovingSprites;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::time::Duration;
+
+const FRAME: Duration = Duration::from_micros(16_667);
+
+fn moving_sprites(c: &mut Criterion) {
+    let mut scenario = MovingSprites::new(10_000, 1);
+    c.bench_function("sprites/frame_10k", |b| b.iter(|| scenario.step(FRAME)));
+    c.bench_function("sprites/move_10k", |b| {
+        b.iter(|| scenario.move_sprites(FRAME))
+    });
+    c.bench_function("sprites/animate_10k", |b| {
+        b.iter(|| scenario.animate(FRAME))
+    });
+    c.bench_function("sprites/batch_10k", |b| b.iter(|| scenario.batch()));
+}
+
+criterion_group!(benches, moving_sprites);
+criterion_main!(benches);
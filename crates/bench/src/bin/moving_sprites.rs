@@ -0,0 +1,51 @@
+//! Runs the moving sprites scenario headless and prints its timing.
+//!
+//! Usage: `moving_sprites [sprites] [frames]`, defaulting to 10 000 sprites
+//! for 600 frames.
+
+use anyhow::Context;
+use bench::MovingSprites;
+use std::time::{Duration, Instant};
+
+const FRAME: Duration = Duration::from_micros(16_667);
+
+fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let sprites: usize = args
+        .next()
+        .map_or(Ok(10_000), |arg| arg.parse())
+        .context("sprite count must be a number")?;
+    let frames: u32 = args
+        .next()
+        .map_or(Ok(600), |arg| arg.parse())
+        .context("frame count must be a number")?;
+
+    let started = Instant::now();
+    let mut scenario = MovingSprites::new(sprites, 1);
+    let spawned = started.elapsed();
+
+    let mut times = Vec::with_capacity(frames as usize);
+    let mut stats = render::RenderStats::default();
+    for _ in 0..frames {
+        let frame = Instant::now();
+        stats = scenario.step(FRAME);
+        times.push(frame.elapsed());
+    }
+    times.sort_unstable();
+
+    println!("moving sprites: {sprites} sprites, {frames} frames");
+    println!("spawn       {:.2} ms", ms(spawned));
+    if let (Some(first), Some(last)) = (times.first(), times.last()) {
+        let total: Duration = times.iter().sum();
+        println!("frame avg   {:.3} ms", ms(total) / f64::from(frames));
+        println!("frame p50   {:.3} ms", ms(times[times.len() / 2]));
+        println!("frame p99   {:.3} ms", ms(times[times.len() * 99 / 100]));
+        println!("frame min   {:.3} ms, max {:.3} ms", ms(*first), ms(*last));
+    }
+    println!("{stats}");
+    Ok(())
+}
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
@@ -0,0 +1,27 @@
+//! Benchmarks and headless performance scenarios for the Rustgine game
+//! engine.
+//!
+//! Criterion suites under `benches/` measure the engine's hot paths, so
+//! performance regressions show up as numbers rather than hunches:
+//!
+//! - `ecs`: component iteration, component insertion and removal, and
+//!   event dispatch
+//! - `scheduler`: job graph throughput
+//! - `sprites`: moving, animating, and batching sprites
+//!
+//! Run them with `cargo bench -p bench`. The [`sprites::MovingSprites`]
+//! scenario also runs without a window via the `moving_sprites` binary:
+//!
+//! ```text
+//! cargo run -p bench --release --bin moving_sprites -- 10000 600
+//! ```
+
+#![warn(missing_docs)]
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+pub mod sprites;
+#[cfg(test)]
+mod sprites_test;
+
+pub use sprites::MovingSprites;
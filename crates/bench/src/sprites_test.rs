@@ -0,0 +1,57 @@
+//! Unit tests for the moving sprites scenario.
+
+use crate::sprites::{MovingSprites, Position, AREA, MAX_BATCH_INSTANCES};
+use std::time::Duration;
+
+const FRAME: Duration = Duration::from_millis(16);
+
+/// Verifies sprites stay inside the play area while moving.
+#[test]
+fn test_sprites_stay_in_area() {
+    let mut scenario = MovingSprites::new(500, 3);
+    for _ in 0..300 {
+        scenario.step(FRAME);
+    }
+    for (_, Position([x, y])) in scenario.world().query::<Position>() {
+        assert!((0.0..=AREA[0]).contains(x) && (0.0..=AREA[1]).contains(y));
+    }
+}
+
+/// Verifies batches split per atlas and at the instance limit.
+#[test]
+fn test_batches_split_by_atlas_and_size() {
+    let mut scenario = MovingSprites::new(10_000, 1);
+    let stats = scenario.step(FRAME);
+    // 5 000 sprites per atlas need two draws each.
+    assert_eq!(stats.draw_calls, 4);
+    assert_eq!(stats.instances, 10_000);
+    assert_eq!(stats.pipeline_switches, 1);
+    let sizes: Vec<(usize, usize)> = scenario
+        .batches()
+        .iter()
+        .map(|batch| (batch.atlas, batch.instances.len()))
+        .collect();
+    assert_eq!(
+        sizes,
+        [
+            (0, MAX_BATCH_INSTANCES),
+            (0, 5_000 - MAX_BATCH_INSTANCES),
+            (1, MAX_BATCH_INSTANCES),
+            (1, 5_000 - MAX_BATCH_INSTANCES),
+        ]
+    );
+}
+
+/// Verifies the same seed gives the same run.
+#[test]
+fn test_runs_are_deterministic() {
+    let mut first = MovingSprites::new(100, 42);
+    let mut second = MovingSprites::new(100, 42);
+    for _ in 0..10 {
+        first.step(FRAME);
+        second.step(FRAME);
+    }
+    assert_eq!(first.batches(), second.batches());
+    assert!(!first.is_empty());
+    assert_eq!(first.len(), 100);
+}
@@ -0,0 +1,26 @@
+//! Project scaffolding for the Rustgine game engine.
+//!
+//! Backs the `cargo-rustgine` binary, which cargo runs as a subcommand:
+//!
+//! ```text
+//! cargo install --path crates/cli
+//! cargo rustgine new my-game
+//! cd my-game && cargo run
+//! ```
+//!
+//! [`NewProject`] generates a game crate with a `main.rs` built on
+//! `AppBuilder`, engine configuration, an asset folder layout, and an
+//! example scene; see [`scaffold`] for the layout.
+//!
+//! `cargo rustgine cook` cooks a project's assets incrementally with
+//! [`assets::AssetCooker`].
+
+#![warn(missing_docs)]
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+pub mod scaffold;
+#[cfg(test)]
+mod scaffold_test;
+
+pub use scaffold::{EngineSource, NewProject, ENGINE_GIT};
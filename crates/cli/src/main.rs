@@ -0,0 +1,97 @@
+//! `cargo rustgine`: generates rustgine game projects and cooks their
+//! assets.
+//!
+//! # Usage
+//!
+//! - `cargo rustgine new <name> [--path <engine>]` - Create a game project in
+//!   `./<name>`, depending on the engine's git repository or, with
+//!   `--path`, on a local checkout of the `rustgine` crate
+//! - `cargo rustgine cook [<assets>] [--out <dir>]` - Cook the new and
+//!   changed assets of `<assets>` (default `assets`) into the build cache
+//!   `<dir>` (default `target/assets`)
+//!
+//! # Exit Codes
+//!
+//! - `0` - Project created or assets cooked
+//! - `1` - Invalid arguments, the project could not be written, or an
+//!   asset failed to cook
+
+use anyhow::{bail, Context};
+use assets::{AssetCooker, AssetDatabase};
+use cli::{EngineSource, NewProject};
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: cargo rustgine new <name> [--path <engine>]
+       cargo rustgine cook [<assets>] [--out <dir>]";
+
+fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1).peekable();
+    // Cargo passes the subcommand name on to the binary
+    if args.peek().map(String::as_str) == Some("rustgine") {
+        args.next();
+    }
+
+    match args.next().as_deref() {
+        Some("new") => new(args),
+        Some("cook") => cook(args),
+        Some("-h" | "--help") => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        _ => bail!("{USAGE}"),
+    }
+}
+
+/// Creates a game project.
+fn new(mut args: impl Iterator<Item = String>) -> anyhow::Result<()> {
+    let mut name = None;
+    let mut engine = EngineSource::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--path" => {
+                let path = PathBuf::from(args.next().context("--path requires a directory")?);
+                let path = path
+                    .canonicalize()
+                    .with_context(|| format!("engine path `{}` not found", path.display()))?;
+                engine = EngineSource::Path(path);
+            }
+            _ if name.is_none() && !arg.starts_with('-') => name = Some(arg),
+            _ => bail!("unexpected argument `{arg}`\n{USAGE}"),
+        }
+    }
+
+    let project = NewProject::new(&name.context(USAGE)?)?.with_engine(engine);
+    let root = project.create(&std::env::current_dir()?)?;
+    println!("Created `{}` in {}", project.name(), root.display());
+    println!("Run it with: cd {} && cargo run", project.name());
+    Ok(())
+}
+
+/// Cooks the assets of a project incrementally.
+fn cook(mut args: impl Iterator<Item = String>) -> anyhow::Result<()> {
+    let mut source = None;
+    let mut out = PathBuf::from("target/assets");
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => out = PathBuf::from(args.next().context("--out requires a directory")?),
+            _ if source.is_none() && !arg.starts_with('-') => source = Some(PathBuf::from(arg)),
+            _ => bail!("unexpected argument `{arg}`\n{USAGE}"),
+        }
+    }
+
+    let source = source.unwrap_or_else(|| PathBuf::from("assets"));
+    let database = AssetDatabase::scan(&source)
+        .with_context(|| format!("failed to scan {}", source.display()))?;
+    let report = AssetCooker::new(out).cook(&database)?;
+    for path in &report.cooked {
+        println!("cooked {}", path.display());
+    }
+    for (path, error) in &report.failed {
+        eprintln!("failed {}: {error}", path.display());
+    }
+    println!("{report}");
+    if !report.failed.is_empty() {
+        bail!("{} assets failed to cook", report.failed.len());
+    }
+    Ok(())
+}
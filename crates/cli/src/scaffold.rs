@@ -0,0 +1,185 @@
+//! Game project generation.
+//!
+//! A [`NewProject`] renders the templates under `templates/` into the files
+//! of a fresh game crate:
+//!
+//! ```text
+//! my-game/
+//! ├── .cargo/config.toml        # RUSTGINE_ENV for `cargo run`
+//! ├── .gitignore
+//! ├── Cargo.toml                # depends on the `rustgine` umbrella crate
+//! ├── cvars.toml                # cvar overrides
+//! ├── rustgine.toml             # engine configuration
+//! ├── assets/
+//! │   ├── audio/
+//! │   ├── materials/default.toml
+//! │   ├── shaders/
+//! │   ├── textures/
+//! │   └── timelines/
+//! └── src/
+//!     ├── main.rs               # AppBuilder with the game registered
+//!     └── scene.rs              # example scene
+//! ```
+//!
+//! Templates substitute `{{name}}` with the package name and
+//! `{{rustgine}}` with the engine dependency.
+
+use anyhow::{bail, Context};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Git repository new projects depend on unless a local engine is given.
+pub const ENGINE_GIT: &str = "https://github.com/diamondbo/rustgine";
+
+/// Package names that would clash with the engine or the standard library.
+const RESERVED_NAMES: &[&str] = &["rustgine", "core", "std", "alloc", "test", "proc_macro"];
+
+/// Asset folders created empty, keeping the layout visible from the start.
+const ASSET_DIRS: &[&str] = &["audio", "shaders", "textures", "timelines"];
+
+/// Template files, as (path in the project, template).
+const TEMPLATES: &[(&str, &str)] = &[
+    ("Cargo.toml", include_str!("../templates/Cargo.toml.tmpl")),
+    (
+        ".cargo/config.toml",
+        include_str!("../templates/config.toml.tmpl"),
+    ),
+    (".gitignore", include_str!("../templates/gitignore")),
+    ("rustgine.toml", include_str!("../templates/rustgine.toml")),
+    ("cvars.toml", include_str!("../templates/cvars.toml")),
+    (
+        "assets/materials/default.toml",
+        include_str!("../templates/material.toml"),
+    ),
+    ("src/main.rs", include_str!("../templates/main.rs.tmpl")),
+    ("src/scene.rs", include_str!("../templates/scene.rs.tmpl")),
+];
+
+/// Where a generated project gets the engine from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EngineSource {
+    /// The engine's git repository.
+    Git(String),
+    /// A local checkout of the `rustgine` umbrella crate.
+    Path(PathBuf),
+}
+
+impl EngineSource {
+    /// Returns the dependency as an inline TOML table.
+    fn dependency(&self) -> String {
+        match self {
+            Self::Git(url) => format!("{{ git = {url:?} }}"),
+            Self::Path(path) => format!("{{ path = {:?} }}", path.display().to_string()),
+        }
+    }
+}
+
+impl Default for EngineSource {
+    fn default() -> Self {
+        Self::Git(ENGINE_GIT.to_string())
+    }
+}
+
+/// A game project to generate.
+///
+/// # Example
+///
+/// ```
+/// use cli::NewProject;
+///
+/// let project = NewProject::new("my-game")?;
+/// let files = project.files();
+/// assert!(files.iter().any(|(path, _)| path.ends_with("main.rs")));
+/// assert!(NewProject::new("2fast").is_err());
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewProject {
+    name: String,
+    engine: EngineSource,
+}
+
+impl NewProject {
+    /// Creates a project named `name`, depending on the engine's git
+    /// repository.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `name` is not a valid package name: it must
+    /// start with a letter, contain only ASCII letters, digits, `-`, and
+    /// `_`, and not be reserved (such as `rustgine` or `std`).
+    pub fn new(name: &str) -> anyhow::Result<Self> {
+        let mut chars = name.chars();
+        if !chars.next().is_some_and(|c| c.is_ascii_alphabetic()) {
+            bail!("invalid project name `{name}`: must start with a letter");
+        }
+        if let Some(c) = chars.find(|&c| !(c.is_ascii_alphanumeric() || c == '-' || c == '_')) {
+            bail!("invalid project name `{name}`: `{c}` is not allowed");
+        }
+        if RESERVED_NAMES.contains(&name.replace('-', "_").as_str()) {
+            bail!("invalid project name `{name}`: the name is reserved");
+        }
+        Ok(Self {
+            name: name.to_string(),
+            engine: EngineSource::default(),
+        })
+    }
+
+    /// Sets where the project gets the engine from.
+    #[must_use]
+    pub fn with_engine(mut self, engine: EngineSource) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    /// Returns the package name.
+    #[must_use]
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the rendered files, as (path in the project, contents).
+    #[must_use]
+    pub fn files(&self) -> Vec<(PathBuf, String)> {
+        let dependency = self.engine.dependency();
+        TEMPLATES
+            .iter()
+            .map(|(path, template)| {
+                let contents = template
+                    .replace("{{name}}", &self.name)
+                    .replace("{{rustgine}}", &dependency);
+                (PathBuf::from(path), contents)
+            })
+            .collect()
+    }
+
+    /// Writes the project into a new directory named after it under
+    /// `parent`, returning that directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory already exists or a file cannot
+    /// be written.
+    pub fn create(&self, parent: &Path) -> anyhow::Result<PathBuf> {
+        let root = parent.join(&self.name);
+        if root.exists() {
+            bail!("destination `{}` already exists", root.display());
+        }
+        for dir in ASSET_DIRS {
+            let dir = root.join("assets").join(dir);
+            fs::create_dir_all(&dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+        for (path, contents) in self.files() {
+            let path = root.join(path);
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)
+                    .with_context(|| format!("failed to create {}", dir.display()))?;
+            }
+            fs::write(&path, contents)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+        }
+        Ok(root)
+    }
+}
@@ -0,0 +1,83 @@
+//! Unit tests for project generation.
+
+use crate::{EngineSource, NewProject};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn file(project: &NewProject, path: &str) -> String {
+    project
+        .files()
+        .into_iter()
+        .find(|(file, _)| file == Path::new(path))
+        .map_or_else(|| panic!("missing {path}"), |(_, contents)| contents)
+}
+
+/// Verifies package names are validated.
+#[test]
+fn rejects_invalid_names() {
+    assert!(NewProject::new("my-game").is_ok());
+    assert!(NewProject::new("space_shooter2").is_ok());
+    for name in [
+        "", "2fast", "-game", "my game", "game/src", "rustgine", "std",
+    ] {
+        assert!(NewProject::new(name).is_err(), "{name:?} accepted");
+    }
+}
+
+/// Verifies templates are filled in, leaving no placeholders.
+#[test]
+fn renders_templates() {
+    let project = NewProject::new("my-game").unwrap();
+    for (path, contents) in project.files() {
+        assert!(!contents.contains("{{"), "{}: {contents}", path.display());
+    }
+    let manifest = file(&project, "Cargo.toml");
+    assert!(manifest.contains(r#"name = "my-game""#), "{manifest}");
+    assert!(
+        manifest.contains(r#"rustgine = { git = "https://github.com/diamondbo/rustgine" }"#),
+        "{manifest}"
+    );
+    assert!(file(&project, "src/main.rs").contains(r#".add_system("my-game""#));
+}
+
+/// Verifies a local engine becomes a path dependency.
+#[test]
+fn depends_on_local_engine() {
+    let project = NewProject::new("my-game")
+        .unwrap()
+        .with_engine(EngineSource::Path(PathBuf::from(
+            "/src/rustgine/crates/rustgine",
+        )));
+    let manifest = file(&project, "Cargo.toml");
+    assert!(
+        manifest.contains(r#"rustgine = { path = "/src/rustgine/crates/rustgine" }"#),
+        "{manifest}"
+    );
+}
+
+/// Verifies the project is written to disk and never overwrites.
+#[test]
+fn creates_project_directory() {
+    let parent = std::env::temp_dir().join(format!("rustgine-new-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&parent);
+    fs::create_dir_all(&parent).unwrap();
+
+    let project = NewProject::new("my-game").unwrap();
+    let root = project.create(&parent).unwrap();
+    assert_eq!(root, parent.join("my-game"));
+    for path in [
+        "Cargo.toml",
+        ".cargo/config.toml",
+        "rustgine.toml",
+        "cvars.toml",
+        "src/main.rs",
+        "src/scene.rs",
+        "assets/materials/default.toml",
+    ] {
+        assert!(root.join(path).is_file(), "missing {path}");
+    }
+    assert!(root.join("assets/textures").is_dir());
+    assert!(project.create(&parent).is_err());
+
+    fs::remove_dir_all(&parent).unwrap();
+}
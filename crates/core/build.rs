@@ -0,0 +1,52 @@
+//! Captures build metadata for `BuildInfo`.
+//!
+//! Cargo only exposes the build profile and target triple to build
+//! scripts, and the git commit has to be asked of git, so they are
+//! forwarded to the crate as `RUSTGINE_BUILD_*` environment variables.
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let profile = std::env::var("PROFILE").unwrap_or_else(|_| "unknown".to_owned());
+    let target = std::env::var("TARGET").unwrap_or_else(|_| "unknown".to_owned());
+    println!("cargo:rustc-env=RUSTGINE_BUILD_PROFILE={profile}");
+    println!("cargo:rustc-env=RUSTGINE_BUILD_TARGET={target}");
+
+    // Builds without a checkout (vendored sources, Docker) can pass the hash in
+    println!("cargo:rerun-if-env-changed=RUSTGINE_GIT_HASH");
+    let hash = std::env::var("RUSTGINE_GIT_HASH")
+        .ok()
+        .or_else(git_hash)
+        .unwrap_or_else(|| "unknown".to_owned());
+    println!("cargo:rustc-env=RUSTGINE_BUILD_GIT_HASH={hash}");
+}
+
+/// Returns the abbreviated commit hash, suffixed with `-dirty` if the
+/// working tree has uncommitted changes.
+fn git_hash() -> Option<String> {
+    let git_dir = git(&["rev-parse", "--git-dir"])?;
+    // Rebuild when HEAD moves or a branch ref is updated
+    for path in ["HEAD", "index", "refs"] {
+        let path = Path::new(&git_dir).join(path);
+        if path.exists() {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+    }
+
+    let hash = git(&["rev-parse", "--short=12", "HEAD"])?;
+    let dirty = git(&["status", "--porcelain", "--untracked-files=no"])
+        .is_some_and(|status| !status.is_empty());
+    Some(if dirty { format!("{hash}-dirty") } else { hash })
+}
+
+/// Runs git, returning its trimmed output if it succeeded.
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|out| out.trim().to_owned())
+}
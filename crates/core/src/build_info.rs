@@ -0,0 +1,108 @@
+//! Build metadata captured at compile time.
+//!
+//! [`BuildInfo::current`] describes the engine binary: its version, the git
+//! commit it was built from, the Cargo profile, and the target triple. It
+//! is logged at startup, shown by the `version` console command and the
+//! remote `/status` endpoint, and attached to bug reports so a report can
+//! be matched to the exact build.
+//!
+//! Peers built from different engine versions must not connect to each
+//! other; [`BuildInfo::protocol_id`] derives the handshake protocol id from
+//! the game name and engine version, so mismatched builds are rejected
+//! before any game data is exchanged.
+
+use serde::Serialize;
+use std::fmt;
+
+/// FNV-1a 64-bit offset basis.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a 64-bit prime.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Version, commit, profile, and target of an engine build.
+///
+/// # Example
+///
+/// ```
+/// use rustgine_core::BuildInfo;
+///
+/// let build = BuildInfo::current();
+/// assert_eq!(build.version, env!("CARGO_PKG_VERSION"));
+/// println!("{build}"); // rustgine 0.1.0 (3f2a9c81d0e4, release, x86_64-unknown-linux-gnu)
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct BuildInfo {
+    /// Engine crate version.
+    pub version: &'static str,
+    /// Abbreviated git commit hash, suffixed with `-dirty` for builds with
+    /// uncommitted changes, or `unknown` outside a git checkout.
+    ///
+    /// Set `RUSTGINE_GIT_HASH` at build time to override it.
+    pub git_hash: &'static str,
+    /// Cargo profile, `debug` or `release`.
+    pub profile: &'static str,
+    /// Target triple, such as `x86_64-unknown-linux-gnu`.
+    pub target: &'static str,
+}
+
+impl BuildInfo {
+    /// Returns the metadata of the running build.
+    #[must_use]
+    pub const fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            git_hash: env!("RUSTGINE_BUILD_GIT_HASH"),
+            profile: env!("RUSTGINE_BUILD_PROFILE"),
+            target: env!("RUSTGINE_BUILD_TARGET"),
+        }
+    }
+
+    /// Returns `true` for optimized release builds.
+    #[must_use]
+    #[inline]
+    pub fn is_release(&self) -> bool {
+        self.profile == "release"
+    }
+
+    /// Returns the network protocol id for `game` on this engine version.
+    ///
+    /// Two builds get the same id only if both the game name and the engine
+    /// version match, so handshakes between mismatched builds fail instead
+    /// of exchanging data neither side can decode.
+    ///
+    /// ```
+    /// use rustgine_core::BuildInfo;
+    ///
+    /// let build = BuildInfo::current();
+    /// let older = BuildInfo { version: "0.0.1", ..build };
+    /// assert_eq!(build.protocol_id("arena"), build.protocol_id("arena"));
+    /// assert_ne!(build.protocol_id("arena"), older.protocol_id("arena"));
+    /// ```
+    #[must_use]
+    pub fn protocol_id(&self, game: &str) -> u64 {
+        // A separator keeps ("ab", "c") and ("a", "bc") apart
+        [game.as_bytes(), &[0], self.version.as_bytes()]
+            .concat()
+            .iter()
+            .fold(FNV_OFFSET, |hash, &byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+            })
+    }
+}
+
+impl Default for BuildInfo {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
+impl fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rustgine {} ({}, {}, {})",
+            self.version, self.git_hash, self.profile, self.target
+        )
+    }
+}
@@ -0,0 +1,60 @@
+//! Unit tests for build metadata.
+
+use crate::BuildInfo;
+
+/// Verifies the build script filled in every field.
+#[test]
+fn current_build_is_populated() {
+    let build = BuildInfo::current();
+    assert_eq!(build.version, env!("CARGO_PKG_VERSION"));
+    assert!(!build.git_hash.is_empty());
+    assert_eq!(build.is_release(), !cfg!(debug_assertions));
+    assert!(build.target.contains(std::env::consts::ARCH), "{build}");
+}
+
+/// Verifies the display format used in logs and reports.
+#[test]
+fn displays_all_fields() {
+    let build = BuildInfo {
+        version: "1.2.3",
+        git_hash: "3f2a9c81d0e4",
+        profile: "release",
+        target: "x86_64-unknown-linux-gnu",
+    };
+    assert_eq!(
+        build.to_string(),
+        "rustgine 1.2.3 (3f2a9c81d0e4, release, x86_64-unknown-linux-gnu)"
+    );
+}
+
+/// Verifies protocol ids depend on the game and engine version only.
+#[test]
+fn protocol_id_tracks_game_and_version() {
+    let build = BuildInfo {
+        version: "1.2.3",
+        git_hash: "3f2a9c81d0e4",
+        profile: "release",
+        target: "x86_64-unknown-linux-gnu",
+    };
+    let debug = BuildInfo {
+        profile: "debug",
+        git_hash: "unknown",
+        ..build
+    };
+    assert_eq!(build.protocol_id("arena"), debug.protocol_id("arena"));
+    assert_ne!(build.protocol_id("arena"), build.protocol_id("racer"));
+    assert_ne!(
+        build.protocol_id("arena"),
+        BuildInfo {
+            version: "1.2.4",
+            ..build
+        }
+        .protocol_id("arena")
+    );
+    // The game name cannot run into the version
+    let shifted = BuildInfo {
+        version: "31.2.3",
+        ..build
+    };
+    assert_ne!(build.protocol_id("arena3"), shifted.protocol_id("arena"));
+}
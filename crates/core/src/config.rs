@@ -1,15 +1,27 @@
 //! Application configuration management.
 //!
 //! Provides environment-aware configuration loading with sensible defaults
-//! for development and production environments.
+//! for development and production environments. Settings are merged from
+//! configuration files, environment variables, and the command line; see
+//! [`config_layers`](crate::config_layers).
 
-use std::env;
-
-/// Environment variable name for specifying the runtime environment.
-const ENV_VAR_NAME: &str = "RUSTGINE_ENV";
+use crate::config_layers::{ConfigLoader, LayeredConfig};
+use crate::error::RustgineError;
+use crate::secret::Secret;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
 
 /// Default environment when none is specified.
-const DEFAULT_ENVIRONMENT: &str = "development";
+pub(crate) const DEFAULT_ENVIRONMENT: &str = "development";
+
+/// Brightness of SDR white on HDR displays when none is configured.
+pub(crate) const DEFAULT_PAPER_WHITE_NITS: f32 = 200.0;
+
+/// Service name reported to the collector when none is configured.
+pub(crate) const DEFAULT_SERVICE_NAME: &str = "rustgine";
 
 /// Application configuration settings.
 ///
@@ -25,12 +37,12 @@ const DEFAULT_ENVIRONMENT: &str = "development";
 /// # Example
 ///
 /// ```
-/// use core::Config;
+/// use rustgine_core::Config;
 ///
 /// let config = Config::load().expect("Failed to load config");
 /// println!("Running in {} mode", config.environment);
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Config {
     /// The runtime environment (e.g., "development", "staging", "production").
     ///
@@ -41,6 +53,181 @@ pub struct Config {
     ///
     /// Common values: "trace", "debug", "info", "warn", "error".
     pub log_level: String,
+
+    /// Whether the main loop slows down while rendering is paused.
+    ///
+    /// When the window is minimized or occluded, frames are still ticked
+    /// for simulation, but at a reduced rate to save CPU and battery.
+    pub throttle_when_paused: bool,
+
+    /// TOML file that seeds console variables at startup and receives user
+    /// overrides at shutdown.
+    ///
+    /// `None` keeps cvars in memory only.
+    pub cvar_file: Option<PathBuf>,
+
+    /// Address for the remote control server, e.g. `127.0.0.1:7878`.
+    ///
+    /// The server exposes the console, so it is only started in
+    /// development environments and on loopback addresses; see
+    /// [`remote_control_addr`](Self::remote_control_addr). Requests must
+    /// carry the `remote_token` [secret](Self::secrets) as a bearer token.
+    pub remote_addr: Option<SocketAddr>,
+
+    /// Whether the remote control server may listen on a non-loopback
+    /// address, reachable from other machines.
+    pub remote_allow_public: bool,
+
+    /// Address serving `GET /metrics` in the Prometheus text format, and
+    /// the `/healthz` and `/readyz` probes.
+    ///
+    /// Read-only, so unlike the remote control server it is available in
+    /// every environment.
+    pub metrics_addr: Option<SocketAddr>,
+
+    /// File periodically rewritten with the current metrics.
+    pub metrics_file: Option<PathBuf>,
+
+    /// OpenTelemetry trace export settings.
+    ///
+    /// Only used when the `otlp` feature is enabled.
+    pub otlp: Option<OtlpConfig>,
+
+    /// Surface format and HDR output settings.
+    pub output: OutputConfig,
+
+    /// Main loop hang detection settings.
+    ///
+    /// `None` disables the watchdog.
+    pub watchdog: Option<WatchdogConfig>,
+
+    /// Credentials such as database passwords and auth tokens, by name.
+    ///
+    /// Loaded from the `[secrets]` table, secret files, or
+    /// `RUSTGINE_SECRET_*` variables, and never printed; see
+    /// [`config_layers`](crate::config_layers).
+    pub secrets: BTreeMap<String, Secret>,
+}
+
+/// Which kind of surface the renderer presents to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SurfaceFormatPreference {
+    /// 8-bit sRGB, supported everywhere.
+    #[default]
+    Sdr,
+    /// 10-bit HDR10 (PQ, Rec. 2020) if the display supports HDR.
+    Hdr10,
+    /// 16-bit float scRGB (linear, extended range) if the display supports
+    /// HDR.
+    ScRgb,
+    /// The best HDR format the display supports, otherwise SDR.
+    Auto,
+}
+
+impl std::str::FromStr for SurfaceFormatPreference {
+    type Err = RustgineError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "sdr" | "srgb" => Ok(Self::Sdr),
+            "hdr10" => Ok(Self::Hdr10),
+            "scrgb" => Ok(Self::ScRgb),
+            "auto" => Ok(Self::Auto),
+            _ => Err(RustgineError::ConfigError(format!(
+                "invalid surface format `{value}`: expected sdr, hdr10, scrgb, or auto"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for SurfaceFormatPreference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Sdr => "sdr",
+            Self::Hdr10 => "hdr10",
+            Self::ScRgb => "scrgb",
+            Self::Auto => "auto",
+        })
+    }
+}
+
+/// Surface format and HDR output settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputConfig {
+    /// Preferred surface format; HDR preferences fall back to SDR on
+    /// displays without HDR.
+    pub surface_format: SurfaceFormatPreference,
+
+    /// Brightness in nits that SDR white, such as UI, is shown at on HDR
+    /// displays.
+    pub paper_white_nits: f32,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            surface_format: SurfaceFormatPreference::Sdr,
+            paper_white_nits: DEFAULT_PAPER_WHITE_NITS,
+        }
+    }
+}
+
+/// Main loop hang detection settings.
+///
+/// A watchdog thread reports a hang when no frame completes within
+/// [`timeout`](Self::timeout), e.g. because of a deadlock in the scheduler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchdogConfig {
+    /// Time without a completed frame after which the main loop is
+    /// reported hung.
+    pub timeout: Duration,
+
+    /// Whether a hang also triggers a graceful shutdown, so a supervisor
+    /// can restart a dedicated server.
+    pub shutdown_on_hang: bool,
+}
+
+impl WatchdogConfig {
+    /// Creates settings reporting hangs longer than `timeout`, without
+    /// shutting down.
+    #[must_use]
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            shutdown_on_hang: false,
+        }
+    }
+}
+
+/// OpenTelemetry (OTLP) trace export settings.
+///
+/// Lets distributed deployments, such as dedicated servers behind a
+/// matchmaker, correlate engine traces in Jaeger, Tempo, or any other
+/// OTLP-compatible backend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtlpConfig {
+    /// Collector traces endpoint, e.g. `http://localhost:4318/v1/traces`.
+    pub endpoint: String,
+
+    /// Service name attached to every exported span.
+    pub service_name: String,
+
+    /// Fraction of root traces to export, from `0.0` to `1.0`.
+    ///
+    /// Child spans follow their parent's sampling decision.
+    pub sample_ratio: f64,
+}
+
+impl OtlpConfig {
+    /// Creates settings exporting every trace to `endpoint`.
+    #[must_use]
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            service_name: DEFAULT_SERVICE_NAME.to_owned(),
+            sample_ratio: 1.0,
+        }
+    }
 }
 
 impl Default for Config {
@@ -48,16 +235,31 @@ impl Default for Config {
         Self {
             environment: DEFAULT_ENVIRONMENT.to_owned(),
             log_level: "debug".to_owned(),
+            throttle_when_paused: true,
+            cvar_file: None,
+            remote_addr: None,
+            remote_allow_public: false,
+            metrics_addr: None,
+            metrics_file: None,
+            otlp: None,
+            output: OutputConfig::default(),
+            watchdog: None,
+            secrets: BTreeMap::new(),
         }
     }
 }
 
 impl Config {
-    /// Loads configuration from environment variables.
+    /// Loads configuration from files and environment variables.
+    ///
+    /// Merges `rustgine.toml`, `rustgine.{environment}.toml`, and
+    /// `rustgine.local.toml` with the `RUSTGINE_*` environment variables;
+    /// see [`config_layers`](crate::config_layers) for the order. Use
+    /// [`ConfigLoader`] directly to apply command-line overrides or to see
+    /// which layer set each value.
     ///
-    /// Reads the `RUSTGINE_ENV` environment variable to determine the
-    /// runtime environment. Log level is automatically set based on
-    /// the environment:
+    /// `RUSTGINE_ENV` selects the environment. Unless a layer sets
+    /// `log_level`, it follows the environment:
     ///
     /// | Environment | Log Level |
     /// |-------------|----------|
@@ -65,34 +267,46 @@ impl Config {
     /// | staging     | info     |
     /// | production  | warn     |
     ///
+    /// Every other field has a variable named after it:
+    /// `RUSTGINE_LOG_LEVEL`, `RUSTGINE_THROTTLE_WHEN_PAUSED` (`0` or
+    /// `false` keeps the main loop at full rate while rendering is paused),
+    /// `RUSTGINE_CVAR_FILE`, `RUSTGINE_REMOTE_ADDR`,
+    /// `RUSTGINE_REMOTE_ALLOW_PUBLIC`, `RUSTGINE_METRICS_ADDR`, and
+    /// `RUSTGINE_METRICS_FILE`. Setting `RUSTGINE_OTLP_ENDPOINT` enables
+    /// [`otlp`](Self::otlp), tuned by `RUSTGINE_OTLP_SERVICE_NAME` and
+    /// `RUSTGINE_OTLP_SAMPLE_RATIO`.
+    /// `RUSTGINE_SURFACE_FORMAT` (`sdr`, `hdr10`, `scrgb`, or `auto`) and
+    /// `RUSTGINE_PAPER_WHITE_NITS` set [`output`](Self::output).
+    /// Setting `RUSTGINE_WATCHDOG_TIMEOUT_MS` enables
+    /// [`watchdog`](Self::watchdog), and `RUSTGINE_WATCHDOG_SHUTDOWN` makes
+    /// a hang shut the engine down.
+    /// `RUSTGINE_SECRET_{NAME}` sets the secret `name`.
+    ///
     /// # Errors
     ///
-    /// Currently this function is infallible, but returns `Result` to
-    /// allow for future configuration sources that may fail (e.g., file I/O).
+    /// Returns [`RustgineError::ConfigError`] if a configuration file is
+    /// invalid, an address is not a valid socket address, the OTLP sample
+    /// ratio is not between 0 and 1, the output settings are invalid, the
+    /// watchdog timeout is not positive, or a secret cannot be read or is
+    /// readable by other users in production.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// use core::Config;
+    /// use rustgine_core::Config;
     ///
-    /// // With RUSTGINE_ENV unset, defaults to "development"
+    /// // With RUSTGINE_ENV unset and no configuration files, defaults to
+    /// // "development"
     /// let config = Config::load().unwrap();
     /// assert_eq!(config.environment, "development");
     /// ```
-    pub fn load() -> anyhow::Result<Self> {
-        let environment = env::var(ENV_VAR_NAME).unwrap_or_else(|_| DEFAULT_ENVIRONMENT.to_owned());
-
-        let log_level = Self::log_level_for_environment(&environment);
-
-        Ok(Self {
-            environment,
-            log_level,
-        })
+    pub fn load() -> Result<Self, RustgineError> {
+        ConfigLoader::new().load().map(LayeredConfig::into_config)
     }
 
     /// Determines the appropriate log level for the given environment.
     #[must_use]
-    fn log_level_for_environment(env: &str) -> String {
+    pub(crate) fn log_level_for_environment(env: &str) -> String {
         match env.to_ascii_lowercase().as_str() {
             "dev" | "development" => "debug",
             "prod" | "production" => "warn",
@@ -111,13 +325,34 @@ impl Config {
         )
     }
 
+    /// Returns the remote control address if the server may run.
+    ///
+    /// Always `None` outside development environments, even when
+    /// [`remote_addr`](Self::remote_addr) is set, and for non-loopback
+    /// addresses unless [`remote_allow_public`](Self::remote_allow_public)
+    /// is set.
+    #[must_use]
+    pub fn remote_control_addr(&self) -> Option<SocketAddr> {
+        self.remote_addr.filter(|addr| {
+            self.is_development() && (addr.ip().is_loopback() || self.remote_allow_public)
+        })
+    }
+
     /// Returns `true` if running in a production environment.
     #[must_use]
     #[inline]
     pub fn is_production(&self) -> bool {
-        matches!(
-            self.environment.to_ascii_lowercase().as_str(),
-            "production" | "prod"
-        )
+        Self::is_production_environment(&self.environment)
+    }
+
+    /// Returns `true` if `env` names a production environment.
+    pub(crate) fn is_production_environment(env: &str) -> bool {
+        matches!(env.to_ascii_lowercase().as_str(), "production" | "prod")
+    }
+
+    /// Returns the secret `name`, or `None` if it is not configured.
+    #[must_use]
+    pub fn secret(&self, name: &str) -> Option<&Secret> {
+        self.secrets.get(name)
     }
 }
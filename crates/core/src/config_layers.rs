@@ -0,0 +1,883 @@
+//! Layered configuration files.
+//!
+//! [`Config::load`] resolves every setting from up to six layers, each
+//! overriding the ones before it:
+//!
+//! 1. Built-in defaults, which depend on the environment
+//! 2. `rustgine.toml`, the project's checked-in defaults
+//! 3. `rustgine.{environment}.toml`, e.g. `rustgine.production.toml`
+//! 4. `rustgine.local.toml`, per-machine overrides kept out of version
+//!    control
+//! 5. `RUSTGINE_*` environment variables
+//! 6. `--config key=value` command-line overrides
+//!
+//! Files are read from `RUSTGINE_CONFIG_DIR`, or the working directory if
+//! it is unset, and missing files are skipped. Files use the field names of
+//! [`Config`], and relative paths in them are relative to the file:
+//!
+//! ```toml
+//! log_level = "info"
+//! cvar_file = "cvars.toml"
+//! remote_addr = "127.0.0.1:7878"
+//!
+//! [otlp]
+//! endpoint = "http://localhost:4318/v1/traces"
+//!
+//! [output]
+//! surface_format = "auto"
+//! ```
+//!
+//! The environment selecting layer 3 is taken from the command line,
+//! `RUSTGINE_ENV`, the local file, or the base file, whichever sets
+//! `environment` first. A [`LayeredConfig`] remembers which layer set each
+//! key, and displays as the merged configuration annotated with it, which
+//! is what the engine prints for [`PRINT_CONFIG_FLAG`].
+//!
+//! # Secrets
+//!
+//! The `[secrets]` table holds credentials as [`Secret`]s. Each entry is
+//! the value itself, the environment variable to read it from, or a file
+//! holding it, relative to the configuration file; trailing newlines are
+//! trimmed. `RUSTGINE_SECRET_{NAME}` sets the secret `name` directly:
+//!
+//! ```toml
+//! [secrets]
+//! db_password = { file = "secrets/db_password" }
+//! auth_token = { env = "AUTH_TOKEN" }
+//! ```
+//!
+//! Secret values are printed as [`REDACTED`]. In production, configuration
+//! files holding secret values and secret files must not be readable by
+//! every user.
+
+use crate::config::{
+    Config, OtlpConfig, OutputConfig, WatchdogConfig, DEFAULT_ENVIRONMENT,
+    DEFAULT_PAPER_WHITE_NITS, DEFAULT_SERVICE_NAME,
+};
+use crate::error::RustgineError;
+use crate::secret::{Secret, REDACTED};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use toml::{Table, Value};
+
+/// Project configuration file, shared by every environment and machine.
+pub const BASE_CONFIG_FILE: &str = "rustgine.toml";
+
+/// Per-machine configuration file, overriding the project's.
+pub const LOCAL_CONFIG_FILE: &str = "rustgine.local.toml";
+
+/// Command-line flag overriding one key, as `--config key=value`.
+pub const CONFIG_FLAG: &str = "--config";
+
+/// Command-line flag printing the merged configuration instead of running.
+pub const PRINT_CONFIG_FLAG: &str = "--print-config";
+
+/// Environment variable naming the directory configuration files are read
+/// from.
+const CONFIG_DIR_VAR: &str = "RUSTGINE_CONFIG_DIR";
+
+/// Keys holding paths, which are relative to the file that sets them.
+const PATH_KEYS: [&str; 2] = ["cvar_file", "metrics_file"];
+
+/// Table of [`Secret`]s, whose entries merge as single values.
+const SECRETS_KEY: &str = "secrets";
+
+/// Prefix of the environment variables setting a secret.
+const SECRET_VAR_PREFIX: &str = "RUSTGINE_SECRET_";
+
+/// How an environment variable's value is read.
+#[derive(Debug, Clone, Copy)]
+enum VarKind {
+    /// Taken as is.
+    Text,
+    /// `0`, `false`, or `no` disable; anything else enables.
+    Flag,
+    /// A socket address.
+    Addr,
+    /// A floating-point number.
+    Number,
+}
+
+/// Environment variables and the keys they set.
+const ENV_VARS: [(&str, &str, VarKind); 15] = [
+    ("RUSTGINE_ENV", "environment", VarKind::Text),
+    ("RUSTGINE_LOG_LEVEL", "log_level", VarKind::Text),
+    (
+        "RUSTGINE_THROTTLE_WHEN_PAUSED",
+        "throttle_when_paused",
+        VarKind::Flag,
+    ),
+    ("RUSTGINE_CVAR_FILE", "cvar_file", VarKind::Text),
+    ("RUSTGINE_REMOTE_ADDR", "remote_addr", VarKind::Addr),
+    (
+        "RUSTGINE_REMOTE_ALLOW_PUBLIC",
+        "remote_allow_public",
+        VarKind::Flag,
+    ),
+    ("RUSTGINE_METRICS_ADDR", "metrics_addr", VarKind::Addr),
+    ("RUSTGINE_METRICS_FILE", "metrics_file", VarKind::Text),
+    ("RUSTGINE_OTLP_ENDPOINT", "otlp.endpoint", VarKind::Text),
+    (
+        "RUSTGINE_OTLP_SERVICE_NAME",
+        "otlp.service_name",
+        VarKind::Text,
+    ),
+    (
+        "RUSTGINE_OTLP_SAMPLE_RATIO",
+        "otlp.sample_ratio",
+        VarKind::Number,
+    ),
+    (
+        "RUSTGINE_SURFACE_FORMAT",
+        "output.surface_format",
+        VarKind::Text,
+    ),
+    (
+        "RUSTGINE_PAPER_WHITE_NITS",
+        "output.paper_white_nits",
+        VarKind::Number,
+    ),
+    (
+        "RUSTGINE_WATCHDOG_TIMEOUT_MS",
+        "watchdog.timeout_ms",
+        VarKind::Number,
+    ),
+    (
+        "RUSTGINE_WATCHDOG_SHUTDOWN",
+        "watchdog.shutdown_on_hang",
+        VarKind::Flag,
+    ),
+];
+
+/// The keys a configuration layer may set, checked before layers merge so
+/// typos are reported with the file that contains them.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    environment: Option<String>,
+    log_level: Option<String>,
+    throttle_when_paused: Option<bool>,
+    cvar_file: Option<PathBuf>,
+    remote_addr: Option<SocketAddr>,
+    remote_allow_public: Option<bool>,
+    metrics_addr: Option<SocketAddr>,
+    metrics_file: Option<PathBuf>,
+    otlp: Option<OtlpFile>,
+    output: Option<OutputFile>,
+    watchdog: Option<WatchdogFile>,
+    secrets: Option<BTreeMap<String, SecretFile>>,
+}
+
+/// The `[otlp]` table of a [`ConfigFile`].
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OtlpFile {
+    endpoint: Option<String>,
+    service_name: Option<String>,
+    sample_ratio: Option<f64>,
+}
+
+/// The `[output]` table of a [`ConfigFile`].
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OutputFile {
+    surface_format: Option<String>,
+    paper_white_nits: Option<f32>,
+}
+
+/// The `[watchdog]` table of a [`ConfigFile`].
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WatchdogFile {
+    timeout_ms: Option<f64>,
+    shutdown_on_hang: Option<bool>,
+}
+
+/// An entry of the `[secrets]` table of a [`ConfigFile`].
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SecretFile {
+    /// The secret value.
+    Value(Secret),
+    /// Where to read the secret value from.
+    Reference(SecretReference),
+}
+
+/// A [`SecretFile`] read from elsewhere; exactly one field is set.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SecretReference {
+    env: Option<String>,
+    file: Option<PathBuf>,
+}
+
+impl ConfigFile {
+    /// Checks that `table` only sets known keys to values of the right
+    /// type, naming `origin` in the error.
+    fn validate(table: &Table, origin: impl fmt::Display) -> Result<Self, RustgineError> {
+        Value::Table(table.clone())
+            .try_into()
+            .map_err(|e| RustgineError::ConfigError(format!("invalid {origin}: {e}")))
+    }
+}
+
+/// The layer that set a configuration key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// The built-in default.
+    Default,
+    /// A configuration file.
+    File(PathBuf),
+    /// An environment variable.
+    Env(String),
+    /// A [`CONFIG_FLAG`] command-line override.
+    CommandLine,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => f.write_str("default"),
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Env(name) => write!(f, "${name}"),
+            Self::CommandLine => write!(f, "{CONFIG_FLAG}"),
+        }
+    }
+}
+
+/// Resolves a [`Config`] from its layers.
+///
+/// # Example
+///
+/// ```
+/// use rustgine_core::config_layers::{ConfigLoader, ConfigSource};
+///
+/// let dir = std::env::temp_dir().join(format!("rustgine-doc-layers-{}", std::process::id()));
+/// std::fs::create_dir_all(&dir)?;
+/// std::fs::write(dir.join("rustgine.toml"), "log_level = \"info\"\n")?;
+/// std::fs::write(dir.join("rustgine.staging.toml"), "log_level = \"trace\"\n")?;
+///
+/// let layered = ConfigLoader::from_vars([("RUSTGINE_ENV".to_owned(), "staging".to_owned())])
+///     .with_dir(&dir)
+///     .with_args(["--config".to_owned(), "output.surface_format=hdr10".to_owned()])?
+///     .load()?;
+/// assert_eq!(layered.config().log_level, "trace");
+/// assert_eq!(
+///     layered.source("log_level"),
+///     Some(&ConfigSource::File(dir.join("rustgine.staging.toml")))
+/// );
+/// assert_eq!(layered.source("output.surface_format"), Some(&ConfigSource::CommandLine));
+/// # std::fs::remove_dir_all(&dir)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLoader {
+    /// Directory holding the configuration files, if set explicitly.
+    dir: Option<PathBuf>,
+    /// Environment variables.
+    vars: BTreeMap<String, String>,
+    /// Command-line overrides.
+    overrides: Table,
+}
+
+impl ConfigLoader {
+    /// Creates a loader reading the process environment, without
+    /// command-line overrides.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::from_vars(std::env::vars_os().filter_map(|(name, value)| {
+            Some((name.into_string().ok()?, value.into_string().ok()?))
+        }))
+    }
+
+    /// Creates a loader reading `vars` instead of the process environment.
+    #[must_use]
+    pub fn from_vars(vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        Self {
+            vars: vars.into_iter().collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Reads configuration files from `dir` instead of `RUSTGINE_CONFIG_DIR`
+    /// or the working directory.
+    #[must_use]
+    pub fn with_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// Overrides the dotted `key`, e.g. `output.surface_format`, as the
+    /// command line does.
+    ///
+    /// `value` is parsed as a TOML value, so `true` and `300` are a boolean
+    /// and a number; anything that does not parse is taken as a string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RustgineError::ConfigError`] if `key` is empty or nests
+    /// under a key that was overridden with a value.
+    pub fn with_override(mut self, key: &str, value: &str) -> Result<Self, RustgineError> {
+        let value = toml::from_str::<Table>(&format!("value = {value}"))
+            .ok()
+            .and_then(|mut table| table.remove("value"))
+            .unwrap_or_else(|| Value::String(value.to_owned()));
+        insert_dotted(&mut self.overrides, key, value)?;
+        Ok(self)
+    }
+
+    /// Applies the `--config key=value` and `--config=key=value` arguments
+    /// in `args`, ignoring all others.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RustgineError::ConfigError`] if a [`CONFIG_FLAG`] is not
+    /// followed by `key=value`.
+    pub fn with_args(
+        mut self,
+        args: impl IntoIterator<Item = String>,
+    ) -> Result<Self, RustgineError> {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let setting = if arg == CONFIG_FLAG {
+                args.next()
+            } else if let Some(setting) = arg
+                .strip_prefix(CONFIG_FLAG)
+                .and_then(|rest| rest.strip_prefix('='))
+            {
+                Some(setting.to_owned())
+            } else {
+                continue;
+            };
+            let Some((key, value)) = setting.as_deref().and_then(|s| s.split_once('=')) else {
+                return Err(RustgineError::ConfigError(format!(
+                    "{CONFIG_FLAG} expects key=value, got `{}`",
+                    setting.unwrap_or_default()
+                )));
+            };
+            self = self.with_override(key.trim(), value.trim())?;
+        }
+        Ok(self)
+    }
+
+    /// Reads and merges every layer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RustgineError::ConfigError`] if a file cannot be read or
+    /// parsed, a layer sets an unknown key or a value of the wrong type, an
+    /// environment-specific file sets `environment`, or a merged value is
+    /// out of range.
+    pub fn load(&self) -> Result<LayeredConfig, RustgineError> {
+        let dir = self
+            .dir
+            .clone()
+            .or_else(|| {
+                self.vars
+                    .get(CONFIG_DIR_VAR)
+                    .filter(|dir| !dir.is_empty())
+                    .map(PathBuf::from)
+            })
+            .unwrap_or_default();
+
+        ConfigFile::validate(&self.overrides, ConfigSource::CommandLine)?;
+        let vars = self.env_layer()?;
+        let base_path = dir.join(BASE_CONFIG_FILE);
+        let base = read_layer(&base_path)?;
+        let local_path = dir.join(LOCAL_CONFIG_FILE);
+        let local = read_layer(&local_path)?;
+
+        let env_var = vars
+            .iter()
+            .find(|(_, key, _)| key == "environment")
+            .map(|(_, _, value)| value);
+        let environment = [
+            self.overrides.get("environment"),
+            env_var,
+            local.as_ref().and_then(|table| table.get("environment")),
+            base.as_ref().and_then(|table| table.get("environment")),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(Value::as_str)
+        .unwrap_or(DEFAULT_ENVIRONMENT)
+        .to_owned();
+
+        let env_path = dir.join(format!("rustgine.{environment}.toml"));
+        let env_file = read_layer(&env_path)?;
+        if env_file
+            .as_ref()
+            .is_some_and(|table| table.contains_key("environment"))
+        {
+            return Err(RustgineError::ConfigError(format!(
+                "{} cannot set `environment`, which selects the file",
+                env_path.display()
+            )));
+        }
+
+        let production = Config::is_production_environment(&environment);
+        for (path, table) in [
+            (&base_path, &base),
+            (&env_path, &env_file),
+            (&local_path, &local),
+        ] {
+            if production && table.as_ref().is_some_and(holds_secrets) {
+                ensure_private(path, "holds secrets")?;
+            }
+        }
+
+        let mut layered = LayeredConfig {
+            config: Config::default(),
+            environment: environment.clone(),
+            table: Table::new(),
+            sources: BTreeMap::new(),
+            files: Vec::new(),
+        };
+        layered.merge(defaults(&environment), &ConfigSource::Default);
+        for (path, table) in [(base_path, base), (env_path, env_file), (local_path, local)] {
+            if let Some(table) = table {
+                layered.merge(table, &ConfigSource::File(path.clone()));
+                layered.files.push(path);
+            }
+        }
+        for (name, key, value) in vars {
+            let mut table = Table::new();
+            insert_dotted(&mut table, &key, value)?;
+            layered.merge(table, &ConfigSource::Env(name));
+        }
+        layered.merge(self.overrides.clone(), &ConfigSource::CommandLine);
+        layered.config = layered.build(&self.vars)?;
+        Ok(layered)
+    }
+
+    /// Returns the values of the set `RUSTGINE_*` variables, as (variable,
+    /// key, value).
+    fn env_layer(&self) -> Result<Vec<(String, String, Value)>, RustgineError> {
+        let mut layer = Vec::new();
+        for (name, key, kind) in ENV_VARS {
+            let Some(text) = self.vars.get(name).filter(|text| !text.is_empty()) else {
+                continue;
+            };
+            let value = match kind {
+                VarKind::Text => Value::String(text.clone()),
+                VarKind::Flag => Value::Boolean(!matches!(
+                    text.to_ascii_lowercase().as_str(),
+                    "0" | "false" | "no"
+                )),
+                VarKind::Addr => text
+                    .parse::<SocketAddr>()
+                    .map(|_| Value::String(text.clone()))
+                    .map_err(|e| {
+                        RustgineError::ConfigError(format!("invalid {name} `{text}`: {e}"))
+                    })?,
+                VarKind::Number => text.parse().map(Value::Float).map_err(|_| {
+                    RustgineError::ConfigError(format!(
+                        "invalid {name} `{text}`: expected a number"
+                    ))
+                })?,
+            };
+            layer.push((name.to_owned(), key.to_owned(), value));
+        }
+        for (name, value) in &self.vars {
+            if let Some(secret) = name
+                .strip_prefix(SECRET_VAR_PREFIX)
+                .filter(|secret| !secret.is_empty() && !value.is_empty())
+            {
+                let key = format!("{SECRETS_KEY}.{}", secret.to_ascii_lowercase());
+                layer.push((name.clone(), key, Value::String(value.clone())));
+            }
+        }
+        Ok(layer)
+    }
+}
+
+/// A [`Config`] with the layer each of its settings came from.
+///
+/// Displays as TOML, each key annotated with its source.
+#[derive(Debug, Clone)]
+pub struct LayeredConfig {
+    /// The resolved configuration.
+    config: Config,
+    /// Environment that selected the environment-specific file.
+    environment: String,
+    /// Merged keys.
+    table: Table,
+    /// Layer that last set each dotted key.
+    sources: BTreeMap<String, ConfigSource>,
+    /// Files that were found and merged, lowest priority first.
+    files: Vec<PathBuf>,
+}
+
+impl LayeredConfig {
+    /// Returns the resolved configuration.
+    #[must_use]
+    #[inline]
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns the resolved configuration, dropping the sources.
+    #[must_use]
+    #[inline]
+    pub fn into_config(self) -> Config {
+        self.config
+    }
+
+    /// Returns the layer that set the dotted `key`, or `None` if no layer
+    /// did.
+    #[must_use]
+    pub fn source(&self, key: &str) -> Option<&ConfigSource> {
+        self.sources.get(key)
+    }
+
+    /// Returns the configuration files that were merged, lowest priority
+    /// first.
+    #[must_use]
+    #[inline]
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Merges `layer` over the keys so far.
+    fn merge(&mut self, layer: Table, source: &ConfigSource) {
+        merge_table(&mut self.table, layer, "", source, &mut self.sources);
+    }
+
+    /// Converts the merged keys into a [`Config`], checking value ranges and
+    /// reading secrets, with `vars` as the environment.
+    fn build(&self, vars: &BTreeMap<String, String>) -> Result<Config, RustgineError> {
+        let file = ConfigFile::validate(&self.table, "configuration")?;
+        let environment = file.environment.unwrap_or_else(|| self.environment.clone());
+        let log_level = file
+            .log_level
+            .unwrap_or_else(|| Config::log_level_for_environment(&environment));
+
+        let otlp = match file.otlp.unwrap_or_default() {
+            OtlpFile {
+                endpoint: Some(endpoint),
+                service_name,
+                sample_ratio,
+            } if !endpoint.is_empty() => {
+                let sample_ratio = sample_ratio.unwrap_or(1.0);
+                if !(0.0..=1.0).contains(&sample_ratio) {
+                    return Err(self.out_of_range("otlp.sample_ratio", sample_ratio, "0.0 to 1.0"));
+                }
+                Some(OtlpConfig {
+                    endpoint,
+                    service_name: service_name
+                        .filter(|name| !name.is_empty())
+                        .unwrap_or_else(|| DEFAULT_SERVICE_NAME.to_owned()),
+                    sample_ratio,
+                })
+            }
+            _ => None,
+        };
+
+        let output_file = file.output.unwrap_or_default();
+        let mut output = OutputConfig::default();
+        if let Some(format) = output_file.surface_format {
+            output.surface_format = format.parse()?;
+        }
+        if let Some(nits) = output_file.paper_white_nits {
+            if !(80.0..=10_000.0).contains(&nits) {
+                return Err(self.out_of_range("output.paper_white_nits", nits, "80 to 10000"));
+            }
+            output.paper_white_nits = nits;
+        }
+
+        let watchdog = self.watchdog(&file.watchdog.unwrap_or_default())?;
+
+        let production = Config::is_production_environment(&environment);
+        let mut secrets = BTreeMap::new();
+        for (name, entry) in file.secrets.unwrap_or_default() {
+            let secret = match entry {
+                SecretFile::Value(secret) => secret,
+                SecretFile::Reference(SecretReference {
+                    env: Some(var),
+                    file: None,
+                }) => vars
+                    .get(&var)
+                    .map(|value| Secret::new(value.clone()))
+                    .ok_or_else(|| {
+                        RustgineError::ConfigError(format!(
+                            "secret `{name}` reads ${var}, which is not set"
+                        ))
+                    })?,
+                SecretFile::Reference(SecretReference {
+                    env: None,
+                    file: Some(path),
+                }) => {
+                    if production {
+                        ensure_private(&path, &format!("holds secret `{name}`"))?;
+                    }
+                    let text = std::fs::read_to_string(&path).map_err(|e| {
+                        RustgineError::ConfigError(format!(
+                            "failed to read secret `{name}` from {}: {e}",
+                            path.display()
+                        ))
+                    })?;
+                    Secret::new(text.trim_end_matches(['\n', '\r']).to_owned())
+                }
+                SecretFile::Reference(_) => {
+                    let source = self
+                        .source(&dotted(SECRETS_KEY, &name))
+                        .unwrap_or(&ConfigSource::Default);
+                    return Err(RustgineError::ConfigError(format!(
+                        "secret `{name}` from {source} must set one of `env` or `file`"
+                    )));
+                }
+            };
+            secrets.insert(name, secret);
+        }
+
+        Ok(Config {
+            environment,
+            log_level,
+            throttle_when_paused: file.throttle_when_paused.unwrap_or(true),
+            cvar_file: file.cvar_file,
+            remote_addr: file.remote_addr,
+            remote_allow_public: file.remote_allow_public.unwrap_or(false),
+            metrics_addr: file.metrics_addr,
+            metrics_file: file.metrics_file,
+            otlp,
+            output,
+            watchdog,
+            secrets,
+        })
+    }
+
+    /// Converts the `[watchdog]` table, which enables the watchdog if it
+    /// sets a timeout.
+    fn watchdog(&self, file: &WatchdogFile) -> Result<Option<WatchdogConfig>, RustgineError> {
+        match file.timeout_ms {
+            Some(timeout_ms) if timeout_ms > 0.0 && timeout_ms.is_finite() => {
+                Ok(Some(WatchdogConfig {
+                    timeout: Duration::from_secs_f64(timeout_ms / 1000.0),
+                    shutdown_on_hang: file.shutdown_on_hang.unwrap_or(false),
+                }))
+            }
+            Some(timeout_ms) => Err(self.out_of_range(
+                "watchdog.timeout_ms",
+                timeout_ms,
+                "a positive number of milliseconds",
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the error for a value outside its valid range.
+    fn out_of_range(&self, key: &str, value: impl fmt::Display, expected: &str) -> RustgineError {
+        let source = self.source(key).unwrap_or(&ConfigSource::Default);
+        RustgineError::ConfigError(format!(
+            "invalid {key} `{value}` from {source}: expected {expected}"
+        ))
+    }
+
+    /// Writes the keys of `table`, nested under `prefix`.
+    ///
+    /// Secret values are redacted; references to them are shown.
+    fn write_table(&self, f: &mut fmt::Formatter<'_>, table: &Table, prefix: &str) -> fmt::Result {
+        let secrets = prefix == SECRETS_KEY;
+        for (key, value) in table
+            .iter()
+            .filter(|(_, value)| secrets || !value.is_table())
+        {
+            let path = dotted(prefix, key);
+            let source = self.source(&path).unwrap_or(&ConfigSource::Default);
+            if secrets && value.is_str() {
+                writeln!(f, "{key} = {}  # {source}", Value::from(REDACTED))?;
+            } else {
+                writeln!(f, "{key} = {value}  # {source}")?;
+            }
+        }
+        if secrets {
+            return Ok(());
+        }
+        for (key, value) in table {
+            if let Value::Table(nested) = value {
+                let path = dotted(prefix, key);
+                writeln!(f, "\n[{path}]")?;
+                self.write_table(f, nested, &path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for LayeredConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# environment: {}", self.environment)?;
+        if self.files.is_empty() {
+            writeln!(f, "# files: none")?;
+        } else {
+            let files: Vec<String> = self
+                .files
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            writeln!(f, "# files: {}", files.join(", "))?;
+        }
+        self.write_table(f, &self.table, "")
+    }
+}
+
+/// Returns the built-in defaults for `environment` as a layer.
+fn defaults(environment: &str) -> Table {
+    let output = OutputConfig::default();
+    let mut table = Table::new();
+    table.insert("environment".into(), environment.into());
+    table.insert(
+        "log_level".into(),
+        Config::log_level_for_environment(environment).into(),
+    );
+    table.insert("throttle_when_paused".into(), true.into());
+    let mut output_table = Table::new();
+    output_table.insert(
+        "surface_format".into(),
+        output.surface_format.to_string().into(),
+    );
+    output_table.insert(
+        "paper_white_nits".into(),
+        f64::from(DEFAULT_PAPER_WHITE_NITS).into(),
+    );
+    table.insert("output".into(), output_table.into());
+    table
+}
+
+/// Reads the configuration file at `path`, or `None` if it does not exist.
+fn read_layer(path: &Path) -> Result<Option<Table>, RustgineError> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(RustgineError::ConfigError(format!(
+                "failed to read {}: {e}",
+                path.display()
+            )))
+        }
+    };
+    let mut table: Table = toml::from_str(&text)
+        .map_err(|e| RustgineError::ConfigError(format!("invalid {}: {e}", path.display())))?;
+    ConfigFile::validate(&table, path.display())?;
+
+    let dir = path.parent().unwrap_or(Path::new(""));
+    for key in PATH_KEYS {
+        if let Some(Value::String(relative)) = table.get(key) {
+            let resolved = dir.join(relative).display().to_string();
+            table.insert(key.to_owned(), Value::String(resolved));
+        }
+    }
+    if let Some(Value::Table(secrets)) = table.get_mut(SECRETS_KEY) {
+        for (_, entry) in secrets.iter_mut() {
+            if let Some(Value::String(relative)) = entry.get_mut("file") {
+                *relative = dir.join(relative.as_str()).display().to_string();
+            }
+        }
+    }
+    Ok(Some(table))
+}
+
+/// Returns `true` if the layer `table` holds secret values rather than
+/// references to them.
+fn holds_secrets(table: &Table) -> bool {
+    table
+        .get(SECRETS_KEY)
+        .and_then(Value::as_table)
+        .is_some_and(|secrets| secrets.values().any(Value::is_str))
+}
+
+/// Fails if every user may read `path`, which `what` describes.
+///
+/// Only checked on Unix, where permissions have an "other" class.
+#[cfg(unix)]
+fn ensure_private(path: &Path, what: &str) -> Result<(), RustgineError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = std::fs::metadata(path)
+        .map_err(|e| RustgineError::ConfigError(format!("failed to read {}: {e}", path.display())))?
+        .permissions()
+        .mode();
+    if mode & 0o004 == 0 {
+        Ok(())
+    } else {
+        Err(RustgineError::ConfigError(format!(
+            "{} {what} but is readable by every user; restrict it with `chmod o-r` \
+             before using it in production",
+            path.display()
+        )))
+    }
+}
+
+/// Fails if every user may read `path`, which `what` describes.
+///
+/// Only checked on Unix, where permissions have an "other" class.
+#[cfg(not(unix))]
+#[allow(clippy::unnecessary_wraps)]
+fn ensure_private(_path: &Path, _what: &str) -> Result<(), RustgineError> {
+    Ok(())
+}
+
+/// Sets the dotted `key` in `table`, creating intermediate tables.
+fn insert_dotted(table: &mut Table, key: &str, value: Value) -> Result<(), RustgineError> {
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let last = parts
+        .pop()
+        .filter(|last| !last.is_empty())
+        .ok_or_else(|| RustgineError::ConfigError(format!("invalid configuration key `{key}`")))?;
+    let mut table = table;
+    for part in parts {
+        let entry = table
+            .entry(part)
+            .or_insert_with(|| Value::Table(Table::new()));
+        table = entry.as_table_mut().ok_or_else(|| {
+            RustgineError::ConfigError(format!("configuration key `{part}` is not a table"))
+        })?;
+    }
+    table.insert(last.to_owned(), value);
+    Ok(())
+}
+
+/// Merges `layer` into `target`, recording `source` for every key it sets.
+fn merge_table(
+    target: &mut Table,
+    layer: Table,
+    prefix: &str,
+    source: &ConfigSource,
+    sources: &mut BTreeMap<String, ConfigSource>,
+) {
+    for (key, value) in layer {
+        let path = dotted(prefix, &key);
+        match value {
+            // A secret is replaced as a whole, so a file reference never
+            // merges with an environment one
+            Value::Table(nested) if prefix != SECRETS_KEY => {
+                let entry = target
+                    .entry(key)
+                    .or_insert_with(|| Value::Table(Table::new()));
+                if !entry.is_table() {
+                    *entry = Value::Table(Table::new());
+                }
+                if let Value::Table(existing) = entry {
+                    merge_table(existing, nested, &path, source, sources);
+                }
+            }
+            value => {
+                sources.insert(path, source.clone());
+                target.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Joins a table path and a key.
+fn dotted(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{prefix}.{key}")
+    }
+}
@@ -0,0 +1,397 @@
+//! Unit tests for layered configuration.
+
+use crate::config::{SurfaceFormatPreference, WatchdogConfig};
+use crate::config_layers::{ConfigLoader, ConfigSource};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Creates an empty configuration directory unique to `name`.
+fn config_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rustgine-config-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn vars(vars: &[(&str, &str)]) -> ConfigLoader {
+    ConfigLoader::from_vars(
+        vars.iter()
+            .map(|(name, value)| ((*name).to_owned(), (*value).to_owned())),
+    )
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(ToString::to_string).collect()
+}
+
+/// Verifies that without files, variables, or overrides the defaults apply.
+#[test]
+fn defaults_without_layers() {
+    let dir = config_dir("defaults");
+    let layered = vars(&[]).with_dir(&dir).load().unwrap();
+
+    assert_eq!(layered.config().environment, "development");
+    assert_eq!(layered.config().log_level, "debug");
+    assert!(layered.config().throttle_when_paused);
+    assert!(layered.files().is_empty());
+    assert_eq!(layered.source("log_level"), Some(&ConfigSource::Default));
+    assert_eq!(layered.source("remote_addr"), None);
+}
+
+/// Verifies each layer overrides the ones before it.
+#[test]
+fn later_layers_override_earlier_ones() {
+    let dir = config_dir("order");
+    std::fs::write(
+        dir.join("rustgine.toml"),
+        "log_level = \"info\"\nthrottle_when_paused = false\nmetrics_addr = \"127.0.0.1:9000\"\n\
+         [output]\nsurface_format = \"auto\"\npaper_white_nits = 300\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("rustgine.development.toml"),
+        "log_level = \"trace\"\nremote_addr = \"127.0.0.1:7878\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("rustgine.local.toml"),
+        "remote_addr = \"127.0.0.1:7879\"\n[output]\nsurface_format = \"hdr10\"\n",
+    )
+    .unwrap();
+
+    let layered = vars(&[("RUSTGINE_PAPER_WHITE_NITS", "250")])
+        .with_dir(&dir)
+        .with_args(args(&["--config", "output.surface_format=scrgb"]))
+        .unwrap()
+        .load()
+        .unwrap();
+    let config = layered.config();
+
+    assert_eq!(config.log_level, "trace");
+    assert!(!config.throttle_when_paused);
+    assert_eq!(config.metrics_addr, Some("127.0.0.1:9000".parse().unwrap()));
+    assert_eq!(config.remote_addr, Some("127.0.0.1:7879".parse().unwrap()));
+    assert_eq!(config.output.surface_format, SurfaceFormatPreference::ScRgb);
+    assert!((config.output.paper_white_nits - 250.0).abs() < f32::EPSILON);
+
+    let file = |name: &str| Some(ConfigSource::File(dir.join(name)));
+    assert_eq!(
+        layered.source("log_level").cloned(),
+        file("rustgine.development.toml")
+    );
+    assert_eq!(
+        layered.source("metrics_addr").cloned(),
+        file("rustgine.toml")
+    );
+    assert_eq!(
+        layered.source("remote_addr").cloned(),
+        file("rustgine.local.toml")
+    );
+    assert_eq!(
+        layered.source("output.paper_white_nits"),
+        Some(&ConfigSource::Env("RUSTGINE_PAPER_WHITE_NITS".to_owned()))
+    );
+    assert_eq!(
+        layered.source("output.surface_format"),
+        Some(&ConfigSource::CommandLine)
+    );
+    assert_eq!(layered.files().len(), 3);
+}
+
+/// Verifies the environment selects its file, and that the file cannot
+/// change the environment.
+#[test]
+fn environment_selects_file() {
+    let dir = config_dir("environment");
+    std::fs::write(dir.join("rustgine.toml"), "environment = \"staging\"\n").unwrap();
+    std::fs::write(dir.join("rustgine.staging.toml"), "log_level = \"error\"\n").unwrap();
+    std::fs::write(
+        dir.join("rustgine.production.toml"),
+        "log_level = \"off\"\n",
+    )
+    .unwrap();
+
+    let staging = vars(&[]).with_dir(&dir).load().unwrap();
+    assert_eq!(staging.config().environment, "staging");
+    assert_eq!(staging.config().log_level, "error");
+
+    let production = vars(&[("RUSTGINE_ENV", "production")])
+        .with_dir(&dir)
+        .load()
+        .unwrap();
+    assert_eq!(production.config().log_level, "off");
+
+    // The command line outranks the environment variable
+    let development = vars(&[("RUSTGINE_ENV", "production")])
+        .with_dir(&dir)
+        .with_override("environment", "development")
+        .unwrap()
+        .load()
+        .unwrap();
+    assert_eq!(development.config().log_level, "debug");
+
+    std::fs::write(dir.join("rustgine.staging.toml"), "environment = \"qa\"\n").unwrap();
+    let error = vars(&[]).with_dir(&dir).load().unwrap_err().to_string();
+    assert!(error.contains("cannot set `environment`"), "{error}");
+}
+
+/// Verifies relative paths in a file are relative to the file, and paths
+/// from variables are kept as given.
+#[test]
+fn resolves_paths_relative_to_file() {
+    let dir = config_dir("paths");
+    std::fs::write(
+        dir.join("rustgine.toml"),
+        "cvar_file = \"cvars.toml\"\nmetrics_file = \"/tmp/metrics.prom\"\n",
+    )
+    .unwrap();
+
+    let config = vars(&[]).with_dir(&dir).load().unwrap().into_config();
+    assert_eq!(config.cvar_file, Some(dir.join("cvars.toml")));
+    assert_eq!(
+        config.metrics_file,
+        Some(PathBuf::from("/tmp/metrics.prom"))
+    );
+
+    let config = vars(&[("RUSTGINE_CVAR_FILE", "other.toml")])
+        .with_dir(&dir)
+        .load()
+        .unwrap()
+        .into_config();
+    assert_eq!(config.cvar_file, Some(PathBuf::from("other.toml")));
+}
+
+/// Verifies invalid layers are reported with their source.
+#[test]
+fn reports_invalid_layers() {
+    let dir = config_dir("invalid");
+    std::fs::write(dir.join("rustgine.local.toml"), "log_levle = \"info\"\n").unwrap();
+    let error = vars(&[]).with_dir(&dir).load().unwrap_err().to_string();
+    assert!(error.contains("rustgine.local.toml"), "{error}");
+    assert!(error.contains("log_levle"), "{error}");
+    std::fs::remove_file(dir.join("rustgine.local.toml")).unwrap();
+
+    let error = vars(&[("RUSTGINE_REMOTE_ADDR", "localhost")])
+        .with_dir(&dir)
+        .load()
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("RUSTGINE_REMOTE_ADDR"),
+        "{error}"
+    );
+
+    let error = vars(&[])
+        .with_dir(&dir)
+        .with_override("otlp.endpoint", "http://localhost:4318")
+        .unwrap()
+        .with_override("otlp.sample_ratio", "2")
+        .unwrap()
+        .load()
+        .unwrap_err();
+    assert!(error.to_string().contains("from --config"), "{error}");
+
+    assert!(vars(&[])
+        .with_dir(&dir)
+        .with_override("throttle_when_paused", "sometimes")
+        .unwrap()
+        .load()
+        .is_err());
+}
+
+/// Verifies `--config` arguments are parsed among other arguments.
+#[test]
+fn parses_config_arguments() {
+    let dir = config_dir("args");
+    let config = vars(&[])
+        .with_dir(&dir)
+        .with_args(args(&[
+            "--dump-schedule",
+            "--config",
+            "throttle_when_paused=false",
+            "--config=otlp.endpoint=http://collector:4318/v1/traces",
+        ]))
+        .unwrap()
+        .load()
+        .unwrap()
+        .into_config();
+
+    assert!(!config.throttle_when_paused);
+    let otlp = config.otlp.unwrap();
+    assert_eq!(otlp.endpoint, "http://collector:4318/v1/traces");
+    assert_eq!(otlp.service_name, "rustgine");
+
+    assert!(vars(&[]).with_args(args(&["--config"])).is_err());
+    assert!(vars(&[]).with_args(args(&["--config=log_level"])).is_err());
+}
+
+/// Verifies the watchdog is enabled by a timeout and rejects non-positive
+/// ones.
+#[test]
+fn reads_watchdog() {
+    let dir = config_dir("watchdog");
+    assert_eq!(
+        vars(&[]).with_dir(&dir).load().unwrap().config().watchdog,
+        None
+    );
+
+    std::fs::write(dir.join("rustgine.toml"), "[watchdog]\ntimeout_ms = 2000\n").unwrap();
+    let config = vars(&[("RUSTGINE_WATCHDOG_SHUTDOWN", "1")])
+        .with_dir(&dir)
+        .load()
+        .unwrap()
+        .into_config();
+    assert_eq!(
+        config.watchdog,
+        Some(WatchdogConfig {
+            timeout: Duration::from_secs(2),
+            shutdown_on_hang: true,
+        })
+    );
+
+    let error = vars(&[("RUSTGINE_WATCHDOG_TIMEOUT_MS", "0")])
+        .with_dir(&dir)
+        .load()
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("$RUSTGINE_WATCHDOG_TIMEOUT_MS"), "{error}");
+}
+
+/// Verifies the printed configuration names the layer of every key.
+#[test]
+fn displays_sources() {
+    let dir = config_dir("display");
+    std::fs::write(dir.join("rustgine.toml"), "log_level = \"info\"\n").unwrap();
+
+    let printed = vars(&[("RUSTGINE_METRICS_ADDR", "127.0.0.1:9000")])
+        .with_dir(&dir)
+        .load()
+        .unwrap()
+        .to_string();
+
+    assert!(
+        printed.starts_with("# environment: development\n"),
+        "{printed}"
+    );
+    let base = dir.join("rustgine.toml").display().to_string();
+    assert!(
+        printed.contains(&format!("log_level = \"info\"  # {base}")),
+        "{printed}"
+    );
+    assert!(
+        printed.contains("metrics_addr = \"127.0.0.1:9000\"  # $RUSTGINE_METRICS_ADDR"),
+        "{printed}"
+    );
+    let output = printed.split_once("\n[output]\n").unwrap().1;
+    assert!(
+        output.contains("surface_format = \"sdr\"  # default\n"),
+        "{printed}"
+    );
+}
+
+/// Verifies secrets are read from files, variables, and secret files, and
+/// redacted when printed.
+#[test]
+fn loads_and_redacts_secrets() {
+    let dir = config_dir("secrets");
+    std::fs::write(dir.join("db_password"), "hunter2\n").unwrap();
+    std::fs::write(
+        dir.join("rustgine.toml"),
+        "[secrets]\nlobby_key = \"swordfish\"\ndb_password = { file = \"db_password\" }\n\
+         auth_token = { env = \"AUTH_TOKEN\" }\n",
+    )
+    .unwrap();
+
+    let layered = vars(&[
+        ("AUTH_TOKEN", "opensesame"),
+        ("RUSTGINE_SECRET_API_KEY", "xyzzy"),
+    ])
+    .with_dir(&dir)
+    .load()
+    .unwrap();
+    let config = layered.config();
+    let secret = |name: &str| config.secret(name).map(|secret| secret.expose().as_str());
+    assert_eq!(secret("lobby_key"), Some("swordfish"));
+    assert_eq!(secret("db_password"), Some("hunter2"));
+    assert_eq!(secret("auth_token"), Some("opensesame"));
+    assert_eq!(secret("api_key"), Some("xyzzy"));
+    assert_eq!(secret("missing"), None);
+
+    let printed = layered.to_string();
+    for value in ["swordfish", "hunter2", "opensesame", "xyzzy"] {
+        assert!(!printed.contains(value), "{printed}");
+    }
+    assert!(!format!("{config:?}").contains("swordfish"));
+    assert!(
+        printed.contains("api_key = \"[redacted]\"  # $RUSTGINE_SECRET_API_KEY"),
+        "{printed}"
+    );
+    assert!(
+        printed.contains("auth_token = { env = \"AUTH_TOKEN\" }"),
+        "{printed}"
+    );
+
+    let error = vars(&[]).with_dir(&dir).load().unwrap_err().to_string();
+    assert!(error.contains("$AUTH_TOKEN"), "{error}");
+
+    // A later layer replaces the whole entry rather than merging into it
+    let config = vars(&[("AUTH_TOKEN", "opensesame")])
+        .with_dir(&dir)
+        .with_override("secrets.db_password.env", "\"AUTH_TOKEN\"")
+        .unwrap()
+        .load()
+        .unwrap()
+        .into_config();
+    assert_eq!(config.secret("db_password").unwrap().expose(), "opensesame");
+
+    let error = vars(&[("AUTH_TOKEN", "opensesame")])
+        .with_dir(&dir)
+        .with_override(
+            "secrets.db_password",
+            "{ env = \"AUTH_TOKEN\", file = \"db_password\" }",
+        )
+        .unwrap()
+        .load()
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("one of `env` or `file`"), "{error}");
+}
+
+/// Verifies production refuses secrets in files every user may read.
+#[cfg(unix)]
+#[test]
+fn production_requires_private_secret_files() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let chmod = |path: &std::path::Path, mode| {
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode)).unwrap();
+    };
+    let dir = config_dir("secret-permissions");
+    let base = dir.join("rustgine.toml");
+    std::fs::write(&base, "[secrets]\nlobby_key = \"swordfish\"\n").unwrap();
+    chmod(&base, 0o644);
+
+    let production = || {
+        vars(&[("RUSTGINE_ENV", "production")])
+            .with_dir(&dir)
+            .load()
+    };
+    let error = production().unwrap_err().to_string();
+    assert!(error.contains("readable by every user"), "{error}");
+    // Development does not check
+    assert!(vars(&[]).with_dir(&dir).load().is_ok());
+
+    chmod(&base, 0o600);
+    assert!(production().is_ok());
+
+    let secret = dir.join("lobby_key");
+    std::fs::write(&secret, "swordfish").unwrap();
+    chmod(&secret, 0o644);
+    std::fs::write(&base, "[secrets]\nlobby_key = { file = \"lobby_key\" }\n").unwrap();
+    chmod(&base, 0o644);
+    let error = production().unwrap_err().to_string();
+    assert!(error.contains("holds secret `lobby_key`"), "{error}");
+
+    chmod(&secret, 0o600);
+    let config = production().unwrap().into_config();
+    assert_eq!(config.secret("lobby_key").unwrap().expose(), "swordfish");
+}
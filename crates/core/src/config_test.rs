@@ -1,7 +1,59 @@
-use crate::config::Config as CoreConfig;
+use crate::config::{Config as CoreConfig, OtlpConfig, SurfaceFormatPreference};
 
 #[test]
 fn config_loads_successfully() {
     let config = CoreConfig::load();
     assert!(config.is_ok(), "Config should load without error");
 }
+
+#[test]
+fn remote_control_is_development_only() {
+    let addr = "127.0.0.1:7878".parse().unwrap();
+    let mut config = CoreConfig {
+        remote_addr: Some(addr),
+        ..CoreConfig::default()
+    };
+    assert_eq!(config.remote_control_addr(), Some(addr));
+
+    config.environment = "production".to_owned();
+    assert_eq!(config.remote_control_addr(), None);
+}
+
+#[test]
+fn remote_control_is_loopback_only_unless_allowed() {
+    let addr = "0.0.0.0:7878".parse().unwrap();
+    let mut config = CoreConfig {
+        remote_addr: Some(addr),
+        ..CoreConfig::default()
+    };
+    assert_eq!(config.remote_control_addr(), None);
+
+    config.remote_allow_public = true;
+    assert_eq!(config.remote_control_addr(), Some(addr));
+}
+
+#[test]
+fn otlp_config_defaults_to_full_sampling() {
+    let otlp = OtlpConfig::new("http://localhost:4318/v1/traces");
+    assert_eq!(otlp.endpoint, "http://localhost:4318/v1/traces");
+    assert_eq!(otlp.service_name, "rustgine");
+    assert!((otlp.sample_ratio - 1.0).abs() < f64::EPSILON);
+    assert!(CoreConfig::default().otlp.is_none());
+}
+
+#[test]
+fn surface_format_preference_parses() {
+    assert_eq!(
+        "HDR10".parse::<SurfaceFormatPreference>().unwrap(),
+        SurfaceFormatPreference::Hdr10
+    );
+    assert_eq!(
+        "srgb".parse::<SurfaceFormatPreference>().unwrap(),
+        SurfaceFormatPreference::Sdr
+    );
+    assert!("hdr".parse::<SurfaceFormatPreference>().is_err());
+
+    let output = CoreConfig::default().output;
+    assert_eq!(output.surface_format, SurfaceFormatPreference::Sdr);
+    assert!((output.paper_white_nits - 200.0).abs() < f32::EPSILON);
+}
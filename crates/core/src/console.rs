@@ -0,0 +1,728 @@
+//! In-engine developer console.
+//!
+//! The [`Console`] is a registry of named commands with typed arguments that
+//! any crate can extend, plus the state an overlay needs to present it:
+//! input history, autocompletion, and a scrollback of command output and
+//! captured log lines.
+//!
+//! ```text
+//! > set_timescale 0.5
+//! timescale = 0.5
+//! > r.vsync off
+//! ```
+//!
+//! Log output reaches the scrollback through [`ConsoleLayer`], installed by
+//! [`init_tracing_with_console`](crate::trace::init_tracing_with_console).
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::{self, Write as _};
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+/// Maximum number of entered lines kept in the history.
+const MAX_HISTORY: usize = 100;
+
+/// Maximum number of lines kept in the scrollback.
+const MAX_OUTPUT: usize = 1000;
+
+/// Type of a command argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgKind {
+    /// A signed integer, e.g. `42`.
+    Int,
+    /// A floating-point number, e.g. `0.5`.
+    Float,
+    /// A boolean: `1`/`0`, `true`/`false`, `on`/`off`, or `yes`/`no`.
+    Bool,
+    /// Any text. Quote it to include spaces.
+    Str,
+}
+
+impl ArgKind {
+    /// Parses a token as a value of this type.
+    #[must_use]
+    pub fn parse(self, token: &str) -> Option<ArgValue> {
+        match self {
+            Self::Int => token.parse().ok().map(ArgValue::Int),
+            Self::Float => token.parse().ok().map(ArgValue::Float),
+            Self::Bool => parse_bool(token).map(ArgValue::Bool),
+            Self::Str => Some(ArgValue::Str(token.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for ArgKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Int => "int",
+            Self::Float => "float",
+            Self::Bool => "bool",
+            Self::Str => "string",
+        })
+    }
+}
+
+/// Declaration of one command argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arg {
+    /// Name shown in usage messages.
+    pub name: &'static str,
+    /// Expected type.
+    pub kind: ArgKind,
+    /// Whether the argument may be omitted.
+    ///
+    /// Optional arguments must come after all required ones.
+    pub optional: bool,
+}
+
+impl Arg {
+    /// Declares a required integer argument.
+    #[must_use]
+    pub const fn int(name: &'static str) -> Self {
+        Self::new(name, ArgKind::Int)
+    }
+
+    /// Declares a required float argument.
+    #[must_use]
+    pub const fn float(name: &'static str) -> Self {
+        Self::new(name, ArgKind::Float)
+    }
+
+    /// Declares a required boolean argument.
+    #[must_use]
+    pub const fn bool(name: &'static str) -> Self {
+        Self::new(name, ArgKind::Bool)
+    }
+
+    /// Declares a required string argument.
+    #[must_use]
+    pub const fn str(name: &'static str) -> Self {
+        Self::new(name, ArgKind::Str)
+    }
+
+    /// Makes the argument optional.
+    #[must_use]
+    pub const fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    const fn new(name: &'static str, kind: ArgKind) -> Self {
+        Self {
+            name,
+            kind,
+            optional: false,
+        }
+    }
+
+    /// Parses a token as this argument's type.
+    fn parse(&self, token: &str) -> anyhow::Result<ArgValue> {
+        self.kind
+            .parse(token)
+            .ok_or_else(|| anyhow::anyhow!("`{}` expects {}, got `{token}`", self.name, self.kind))
+    }
+}
+
+/// Parses a console boolean.
+///
+/// Accepts `1`/`0`, `true`/`false`, `on`/`off`, and `yes`/`no`, ignoring case.
+#[must_use]
+pub fn parse_bool(token: &str) -> Option<bool> {
+    match token.to_ascii_lowercase().as_str() {
+        "1" | "true" | "on" | "yes" => Some(true),
+        "0" | "false" | "off" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// A parsed argument value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgValue {
+    /// An integer argument.
+    Int(i64),
+    /// A float argument.
+    Float(f64),
+    /// A boolean argument.
+    Bool(bool),
+    /// A string argument.
+    Str(String),
+}
+
+impl ArgValue {
+    /// Returns the type of the value.
+    #[must_use]
+    pub fn kind(&self) -> ArgKind {
+        match self {
+            Self::Int(_) => ArgKind::Int,
+            Self::Float(_) => ArgKind::Float,
+            Self::Bool(_) => ArgKind::Bool,
+            Self::Str(_) => ArgKind::Str,
+        }
+    }
+}
+
+impl fmt::Display for ArgValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Float(value) => write!(f, "{value}"),
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Str(value) => f.write_str(value),
+        }
+    }
+}
+
+/// Arguments passed to a command handler, already checked against the
+/// command's declaration.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommandArgs {
+    values: Vec<ArgValue>,
+}
+
+impl CommandArgs {
+    /// Returns the number of arguments given, including optional ones.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no arguments were given.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the raw value at `index`, if given.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&ArgValue> {
+        self.values.get(index)
+    }
+
+    /// Returns the integer argument at `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the argument is missing or not an integer.
+    pub fn int(&self, index: usize) -> anyhow::Result<i64> {
+        match self.get(index) {
+            Some(ArgValue::Int(value)) => Ok(*value),
+            other => Err(mismatch(index, ArgKind::Int, other)),
+        }
+    }
+
+    /// Returns the float argument at `index`. Integer arguments are widened.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the argument is missing or not a number.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn float(&self, index: usize) -> anyhow::Result<f64> {
+        match self.get(index) {
+            Some(ArgValue::Float(value)) => Ok(*value),
+            Some(ArgValue::Int(value)) => Ok(*value as f64),
+            other => Err(mismatch(index, ArgKind::Float, other)),
+        }
+    }
+
+    /// Returns the boolean argument at `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the argument is missing or not a boolean.
+    pub fn bool(&self, index: usize) -> anyhow::Result<bool> {
+        match self.get(index) {
+            Some(ArgValue::Bool(value)) => Ok(*value),
+            other => Err(mismatch(index, ArgKind::Bool, other)),
+        }
+    }
+
+    /// Returns the string argument at `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the argument is missing or not a string.
+    pub fn str(&self, index: usize) -> anyhow::Result<&str> {
+        match self.get(index) {
+            Some(ArgValue::Str(value)) => Ok(value),
+            other => Err(mismatch(index, ArgKind::Str, other)),
+        }
+    }
+}
+
+/// Builds the error for a handler reading an argument with the wrong type.
+fn mismatch(index: usize, expected: ArgKind, found: Option<&ArgValue>) -> anyhow::Error {
+    match found {
+        Some(value) => anyhow::anyhow!("argument {index} is {value:?}, not {expected}"),
+        None => anyhow::anyhow!("argument {index} ({expected}) was not given"),
+    }
+}
+
+/// Command handler. Returns text to print, which may be empty.
+type Handler = dyn Fn(&CommandArgs) -> anyhow::Result<String> + Send + Sync;
+
+/// A registered command.
+struct Command {
+    description: String,
+    args: Vec<Arg>,
+    handler: Arc<Handler>,
+}
+
+impl Command {
+    /// Formats the usage line, e.g. `spawn_entity <prefab:string> [count:int]`.
+    fn usage(&self, name: &str) -> String {
+        let mut usage = name.to_owned();
+        for arg in &self.args {
+            let _ = if arg.optional {
+                write!(usage, " [{}:{}]", arg.name, arg.kind)
+            } else {
+                write!(usage, " <{}:{}>", arg.name, arg.kind)
+            };
+        }
+        usage
+    }
+
+    /// Parses tokens against the declared arguments.
+    fn parse_args(&self, name: &str, tokens: &[String]) -> anyhow::Result<CommandArgs> {
+        let required = self.args.iter().filter(|arg| !arg.optional).count();
+        if tokens.len() < required || tokens.len() > self.args.len() {
+            return Err(anyhow::anyhow!("usage: {}", self.usage(name)));
+        }
+        let values = self
+            .args
+            .iter()
+            .zip(tokens)
+            .map(|(arg, token)| arg.parse(token))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(CommandArgs { values })
+    }
+}
+
+/// Kind of a scrollback line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    /// A line the user entered.
+    Input,
+    /// Output of a command.
+    Output,
+    /// A command error.
+    Error,
+    /// A captured log event.
+    Log(Level),
+}
+
+/// One line of console scrollback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsoleLine {
+    /// Where the line came from.
+    pub kind: LineKind,
+    /// The line text.
+    pub text: String,
+}
+
+/// Shared console state.
+#[derive(Default)]
+struct ConsoleState {
+    commands: BTreeMap<String, Command>,
+    history: VecDeque<String>,
+    output: VecDeque<ConsoleLine>,
+    open: bool,
+}
+
+impl ConsoleState {
+    fn push_line(&mut self, kind: LineKind, text: String) {
+        if self.output.len() == MAX_OUTPUT {
+            self.output.pop_front();
+        }
+        self.output.push_back(ConsoleLine { kind, text });
+    }
+}
+
+/// Developer console with a shared command registry.
+///
+/// `Console` is a cheap handle; clones share the same registry and
+/// scrollback, so subsystems can keep a clone to register their commands.
+///
+/// Commands run on the calling thread without the console lock held, so a
+/// handler may itself use the console (for example to print extra lines).
+///
+/// # Thread Safety
+///
+/// All methods take `&self` and synchronize internally. A poisoned lock is
+/// recovered, since the console is diagnostic state.
+///
+/// # Example
+///
+/// ```
+/// use rustgine_core::console::{Arg, Console};
+///
+/// let console = Console::new();
+/// console
+///     .register(
+///         "set_timescale",
+///         "Scales simulation time",
+///         &[Arg::float("scale")],
+///         |args| Ok(format!("timescale = {}", args.float(0)?)),
+///     )
+///     .unwrap();
+///
+/// assert_eq!(console.execute("set_timescale 0.5").unwrap(), "timescale = 0.5");
+/// assert!(console.execute("set_timescale fast").is_err());
+/// assert_eq!(console.complete("set_"), ["set_timescale"]);
+/// ```
+#[derive(Clone)]
+pub struct Console {
+    inner: Arc<Mutex<ConsoleState>>,
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Console {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state();
+        f.debug_struct("Console")
+            .field("commands", &state.commands.keys().collect::<Vec<_>>())
+            .field("history", &state.history.len())
+            .field("output", &state.output.len())
+            .field("open", &state.open)
+            .finish()
+    }
+}
+
+impl Console {
+    /// Creates a console with the built-in `help` and `clear` commands.
+    #[must_use]
+    pub fn new() -> Self {
+        let console = Self {
+            inner: Arc::new(Mutex::new(ConsoleState::default())),
+        };
+
+        let weak = Arc::downgrade(&console.inner);
+        console.insert(
+            "help",
+            "Lists commands, or shows the usage of one command",
+            &[Arg::str("command").optional()],
+            move |args| Ok(help(&weak, args.str(0).ok())),
+        );
+
+        let weak = Arc::downgrade(&console.inner);
+        console.insert("clear", "Clears the console output", &[], move |_| {
+            if let Some(inner) = weak.upgrade() {
+                lock(&inner).output.clear();
+            }
+            Ok(String::new())
+        });
+
+        console
+    }
+
+    /// Registers a command.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Command name, without whitespace (e.g. `"r.vsync"`)
+    /// * `description` - One-line summary shown by `help`
+    /// * `args` - Argument declarations, checked before the handler runs
+    /// * `handler` - Runs the command and returns text to print
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is empty, contains whitespace, or is
+    /// already registered, or if a required argument follows an optional one.
+    pub fn register<F>(
+        &self,
+        name: &str,
+        description: &str,
+        args: &[Arg],
+        handler: F,
+    ) -> anyhow::Result<()>
+    where
+        F: Fn(&CommandArgs) -> anyhow::Result<String> + Send + Sync + 'static,
+    {
+        if name.is_empty() || name.chars().any(char::is_whitespace) {
+            return Err(anyhow::anyhow!("invalid command name `{name}`"));
+        }
+        if args
+            .windows(2)
+            .any(|pair| pair[0].optional && !pair[1].optional)
+        {
+            return Err(anyhow::anyhow!(
+                "command `{name}` declares a required argument after an optional one"
+            ));
+        }
+        if self.state().commands.contains_key(name) {
+            return Err(anyhow::anyhow!("command `{name}` is already registered"));
+        }
+        self.insert(name, description, args, handler);
+        Ok(())
+    }
+
+    /// Removes a command. Returns `true` if it was registered.
+    #[allow(clippy::must_use_candidate)]
+    pub fn unregister(&self, name: &str) -> bool {
+        self.state().commands.remove(name).is_some()
+    }
+
+    /// Returns `true` if a command is registered under `name`.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.state().commands.contains_key(name)
+    }
+
+    /// Parses and runs a command line.
+    ///
+    /// The line is added to the history and scrollback along with the
+    /// command's output or error. Blank lines do nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the line cannot be tokenized, the command is
+    /// unknown, the arguments do not match, or the handler fails.
+    pub fn execute(&self, line: &str) -> anyhow::Result<String> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(String::new());
+        }
+
+        let handler_and_args = {
+            let mut state = self.state();
+            if state.history.back().map(String::as_str) != Some(line) {
+                if state.history.len() == MAX_HISTORY {
+                    state.history.pop_front();
+                }
+                state.history.push_back(line.to_owned());
+            }
+            state.push_line(LineKind::Input, format!("> {line}"));
+
+            tokenize(line).and_then(|tokens| {
+                let (name, rest) = tokens
+                    .split_first()
+                    .ok_or_else(|| anyhow::anyhow!("empty command"))?;
+                let command = state
+                    .commands
+                    .get(name)
+                    .ok_or_else(|| anyhow::anyhow!("unknown command `{name}`"))?;
+                Ok((
+                    Arc::clone(&command.handler),
+                    command.parse_args(name, rest)?,
+                ))
+            })
+        };
+
+        let result = handler_and_args.and_then(|(handler, args)| handler(&args));
+
+        let mut state = self.state();
+        match &result {
+            Ok(output) => {
+                for text in output.lines() {
+                    state.push_line(LineKind::Output, text.to_owned());
+                }
+            }
+            Err(e) => state.push_line(LineKind::Error, e.to_string()),
+        }
+        result
+    }
+
+    /// Prints a line to the scrollback.
+    pub fn print(&self, text: impl Into<String>) {
+        self.state().push_line(LineKind::Output, text.into());
+    }
+
+    /// Returns registered command names starting with `prefix`, sorted.
+    #[must_use]
+    pub fn complete(&self, prefix: &str) -> Vec<String> {
+        self.state()
+            .commands
+            .range(prefix.to_owned()..)
+            .take_while(|(name, _)| name.starts_with(prefix))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Returns all commands as `(name, description)` pairs, sorted by name.
+    #[must_use]
+    pub fn commands(&self) -> Vec<(String, String)> {
+        self.state()
+            .commands
+            .iter()
+            .map(|(name, command)| (name.clone(), command.description.clone()))
+            .collect()
+    }
+
+    /// Returns previously entered lines, oldest first.
+    #[must_use]
+    pub fn history(&self) -> Vec<String> {
+        self.state().history.iter().cloned().collect()
+    }
+
+    /// Returns the scrollback, oldest first.
+    #[must_use]
+    pub fn output(&self) -> Vec<ConsoleLine> {
+        self.state().output.iter().cloned().collect()
+    }
+
+    /// Shows or hides the console overlay.
+    pub fn set_open(&self, open: bool) {
+        self.state().open = open;
+    }
+
+    /// Toggles the console overlay and returns the new state.
+    #[allow(clippy::must_use_candidate)]
+    pub fn toggle(&self) -> bool {
+        let mut state = self.state();
+        state.open = !state.open;
+        state.open
+    }
+
+    /// Returns `true` while the console overlay is shown.
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.state().open
+    }
+
+    /// Returns a tracing layer that copies log events into the scrollback.
+    #[must_use]
+    pub fn tracing_layer(&self) -> ConsoleLayer {
+        ConsoleLayer {
+            inner: Arc::downgrade(&self.inner),
+        }
+    }
+
+    /// Registers a command without validation.
+    fn insert<F>(&self, name: &str, description: &str, args: &[Arg], handler: F)
+    where
+        F: Fn(&CommandArgs) -> anyhow::Result<String> + Send + Sync + 'static,
+    {
+        self.state().commands.insert(
+            name.to_owned(),
+            Command {
+                description: description.to_owned(),
+                args: args.to_vec(),
+                handler: Arc::new(handler),
+            },
+        );
+    }
+
+    fn state(&self) -> MutexGuard<'_, ConsoleState> {
+        lock(&self.inner)
+    }
+}
+
+/// Locks the console state, recovering from poisoning.
+fn lock(inner: &Mutex<ConsoleState>) -> MutexGuard<'_, ConsoleState> {
+    inner
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Implements the built-in `help` command.
+fn help(inner: &Weak<Mutex<ConsoleState>>, command: Option<&str>) -> String {
+    let Some(inner) = inner.upgrade() else {
+        return String::new();
+    };
+    let state = lock(&inner);
+    if let Some(name) = command {
+        return state.commands.get(name).map_or_else(
+            || format!("unknown command `{name}`"),
+            |command| format!("{}\n  {}", command.usage(name), command.description),
+        );
+    }
+    let mut text = String::new();
+    for (name, command) in &state.commands {
+        let _ = writeln!(text, "{name} - {}", command.description);
+    }
+    text
+}
+
+/// Splits a command line on whitespace, honouring double quotes.
+fn tokenize(line: &str) -> anyhow::Result<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut quoted = false;
+
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_token = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_token = true;
+            }
+        }
+    }
+    if quoted {
+        return Err(anyhow::anyhow!("unterminated quote"));
+    }
+    if in_token {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+/// Tracing layer that mirrors log events into a [`Console`] scrollback.
+///
+/// Holds a weak reference, so it stops capturing once every console
+/// handle is dropped.
+#[derive(Debug, Clone)]
+pub struct ConsoleLayer {
+    inner: Weak<Mutex<ConsoleState>>,
+}
+
+impl<S: Subscriber> Layer<S> for ConsoleLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let Some(inner) = self.inner.upgrade() else {
+            return;
+        };
+        let mut visitor = LineVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        let text = format!(
+            "{}: {}{}",
+            metadata.target(),
+            visitor.message,
+            visitor.fields
+        );
+        lock(&inner).push_line(LineKind::Log(*metadata.level()), text);
+    }
+}
+
+/// Formats an event's message followed by its `key=value` fields.
+#[derive(Default)]
+pub(crate) struct LineVisitor {
+    pub(crate) message: String,
+    pub(crate) fields: String,
+}
+
+impl Visit for LineVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={value}", field.name());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! Unit tests for the developer console.
+
+use crate::console::{Arg, ArgValue, Console, LineKind};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use tracing::subscriber::with_default;
+use tracing_subscriber::layer::SubscriberExt;
+
+/// Verifies typed argument parsing, including optional arguments.
+#[test]
+fn typed_arguments_are_checked() {
+    let console = Console::new();
+    let spawned = Arc::new(AtomicI64::new(0));
+    let counter = Arc::clone(&spawned);
+    console
+        .register(
+            "spawn_entity",
+            "Spawns entities from a prefab",
+            &[Arg::str("prefab"), Arg::int("count").optional()],
+            move |args| {
+                let count = args.int(1).unwrap_or(1);
+                counter.fetch_add(count, Ordering::SeqCst);
+                Ok(format!("spawned {count} {}", args.str(0)?))
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        console.execute("spawn_entity crate").unwrap(),
+        "spawned 1 crate"
+    );
+    assert_eq!(
+        console.execute("spawn_entity \"big crate\" 3").unwrap(),
+        "spawned 3 big crate"
+    );
+    assert_eq!(spawned.load(Ordering::SeqCst), 4);
+
+    let err = console.execute("spawn_entity crate many").unwrap_err();
+    assert!(err.to_string().contains("expects int"), "{err}");
+    let err = console.execute("spawn_entity").unwrap_err();
+    assert!(err.to_string().contains("usage"), "{err}");
+    assert_eq!(spawned.load(Ordering::SeqCst), 4);
+}
+
+/// Verifies that booleans accept the usual console spellings.
+#[test]
+fn bool_arguments_accept_on_off() {
+    let console = Console::new();
+    console
+        .register(
+            "r.vsync",
+            "Toggles vsync",
+            &[Arg::bool("enabled")],
+            |args| Ok(args.bool(0)?.to_string()),
+        )
+        .unwrap();
+
+    assert_eq!(console.execute("r.vsync off").unwrap(), "false");
+    assert_eq!(console.execute("r.vsync ON").unwrap(), "true");
+    assert!(console.execute("r.vsync maybe").is_err());
+}
+
+/// Verifies registration validation.
+#[test]
+fn register_rejects_invalid_commands() {
+    let console = Console::new();
+    let noop = |_: &_| Ok(String::new());
+    assert!(console.register("has space", "", &[], noop).is_err());
+    assert!(console.register("help", "", &[], noop).is_err());
+    assert!(console
+        .register("bad", "", &[Arg::int("a").optional(), Arg::int("b")], noop)
+        .is_err());
+
+    assert!(console.execute("nope").is_err());
+    assert!(console.execute("say \"unterminated").is_err());
+}
+
+/// Verifies history, scrollback, and autocompletion.
+#[test]
+fn history_output_and_completion() {
+    let console = Console::new();
+    let noop = |_: &_| Ok("done".to_owned());
+    console.register("phys.substeps", "", &[], noop).unwrap();
+    console.register("phys.gravity", "", &[], noop).unwrap();
+    console.register("r.vsync", "", &[], noop).unwrap();
+
+    assert_eq!(console.complete("phys."), ["phys.gravity", "phys.substeps"]);
+    assert!(console.complete("x").is_empty());
+
+    console.execute("r.vsync").unwrap();
+    console.execute("r.vsync").unwrap();
+    let _ = console.execute("missing");
+    assert_eq!(console.history(), ["r.vsync", "missing"]);
+
+    let kinds: Vec<_> = console.output().iter().map(|line| line.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            LineKind::Input,
+            LineKind::Output,
+            LineKind::Input,
+            LineKind::Output,
+            LineKind::Input,
+            LineKind::Error,
+        ]
+    );
+
+    console.execute("clear").unwrap();
+    assert!(console.output().is_empty());
+}
+
+/// Verifies that `help` lists commands and shows usage.
+#[test]
+fn help_shows_usage() {
+    let console = Console::new();
+    console
+        .register(
+            "set_timescale",
+            "Scales time",
+            &[Arg::float("scale")],
+            |_| Ok(String::new()),
+        )
+        .unwrap();
+
+    assert!(console
+        .execute("help")
+        .unwrap()
+        .contains("set_timescale - Scales time"));
+    assert!(console
+        .execute("help set_timescale")
+        .unwrap()
+        .starts_with("set_timescale <scale:float>"));
+}
+
+/// Verifies that log events are mirrored into the scrollback.
+#[test]
+fn tracing_layer_captures_logs() {
+    let console = Console::new();
+    let subscriber = tracing_subscriber::registry().with(console.tracing_layer());
+    with_default(subscriber, || {
+        tracing::warn!(entities = 3, "budget exceeded");
+    });
+
+    let output = console.output();
+    assert_eq!(output.len(), 1);
+    assert_eq!(output[0].kind, LineKind::Log(tracing::Level::WARN));
+    assert!(
+        output[0].text.ends_with("budget exceeded entities=3"),
+        "{}",
+        output[0].text
+    );
+}
+
+/// Verifies that integer arguments widen to floats and strings stay raw.
+#[test]
+fn accessors_convert_or_reject() {
+    let console = Console::new();
+    console
+        .register(
+            "scale",
+            "",
+            &[Arg::int("factor"), Arg::str("raw")],
+            |args| {
+                assert!((args.float(0)? - 2.0).abs() < f64::EPSILON);
+                assert_eq!(args.get(1), Some(&ArgValue::Str("3".to_owned())));
+                assert!(args.int(1).is_err());
+                assert!(args.bool(2).is_err());
+                Ok(String::new())
+            },
+        )
+        .unwrap();
+    console.execute("scale 2 3").unwrap();
+}
+
+/// Verifies toggling the overlay.
+#[test]
+fn toggle_flips_open_state() {
+    let console = Console::new();
+    assert!(!console.is_open());
+    assert!(console.toggle());
+    assert!(console.is_open());
+    console.set_open(false);
+    assert!(!console.is_open());
+}
@@ -0,0 +1,618 @@
+//! Console variables: runtime-tunable engine settings.
+//!
+//! A cvar is a named, typed value with a default, such as
+//! `r.shadow_resolution` or `phys.substeps`. Subsystems register the cvars
+//! they read and keep a typed [`CVar`] handle; values can then be changed
+//! from the developer console, a config file, or remote tooling without
+//! recompiling.
+//!
+//! Values that differ from their default are user overrides. They can be
+//! saved to a TOML file and loaded back on the next start. Values loaded
+//! before a cvar is registered are kept and applied on registration, so the
+//! file can be read before subsystems start.
+//!
+//! ```toml
+//! "r.vsync" = false
+//! "phys.substeps" = 8
+//!
+//! # Nested tables are flattened, so this is `r.shadow_resolution`.
+//! [r]
+//! shadow_resolution = 4096
+//! ```
+
+use crate::console::{Arg, ArgKind, ArgValue, Console};
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Change callback, invoked with the cvar name and its new value.
+type Callback = dyn Fn(&str, &ArgValue) + Send + Sync;
+
+/// Rust types that can be stored in a cvar.
+///
+/// Values share the console's argument types, so anything typed into the
+/// console maps directly onto a cvar.
+pub trait CVarType: Sized {
+    /// Storage type of the cvar.
+    const KIND: ArgKind;
+
+    /// Wraps the value.
+    fn into_value(self) -> ArgValue;
+
+    /// Unwraps a value of kind [`KIND`](Self::KIND).
+    fn from_value(value: &ArgValue) -> Option<Self>;
+}
+
+impl CVarType for bool {
+    const KIND: ArgKind = ArgKind::Bool;
+
+    fn into_value(self) -> ArgValue {
+        ArgValue::Bool(self)
+    }
+
+    fn from_value(value: &ArgValue) -> Option<Self> {
+        match value {
+            ArgValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl CVarType for i64 {
+    const KIND: ArgKind = ArgKind::Int;
+
+    fn into_value(self) -> ArgValue {
+        ArgValue::Int(self)
+    }
+
+    fn from_value(value: &ArgValue) -> Option<Self> {
+        match value {
+            ArgValue::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl CVarType for f64 {
+    const KIND: ArgKind = ArgKind::Float;
+
+    fn into_value(self) -> ArgValue {
+        ArgValue::Float(self)
+    }
+
+    fn from_value(value: &ArgValue) -> Option<Self> {
+        match value {
+            ArgValue::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl CVarType for String {
+    const KIND: ArgKind = ArgKind::Str;
+
+    fn into_value(self) -> ArgValue {
+        ArgValue::Str(self)
+    }
+
+    fn from_value(value: &ArgValue) -> Option<Self> {
+        match value {
+            ArgValue::Str(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Description of a registered cvar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CVarInfo {
+    /// Full name, e.g. `r.vsync`.
+    pub name: String,
+    /// One-line summary.
+    pub description: String,
+    /// Current value.
+    pub value: ArgValue,
+    /// Default value.
+    pub default: ArgValue,
+}
+
+/// A registered cvar.
+struct Entry {
+    description: String,
+    value: ArgValue,
+    default: ArgValue,
+    callbacks: Vec<Arc<Callback>>,
+}
+
+/// Shared cvar state.
+#[derive(Default)]
+struct Registry {
+    entries: BTreeMap<String, Entry>,
+    /// Values loaded for cvars that are not registered yet.
+    pending: BTreeMap<String, ArgValue>,
+    console: Option<Console>,
+}
+
+/// Registry of console variables.
+///
+/// `CVars` is a cheap handle; clones share the same variables.
+///
+/// # Thread Safety
+///
+/// All methods take `&self` and synchronize internally. Change callbacks run
+/// on the thread that made the change, without the registry lock held.
+///
+/// # Example
+///
+/// ```
+/// use rustgine_core::cvar::CVars;
+///
+/// let cvars = CVars::new();
+/// let substeps = cvars
+///     .register("phys.substeps", 4_i64, "Physics substeps per frame")
+///     .unwrap();
+/// assert_eq!(substeps.get(), 4);
+///
+/// cvars.set("phys.substeps", "8").unwrap();
+/// assert_eq!(substeps.get(), 8);
+/// assert!(cvars.set("phys.substeps", "lots").is_err());
+/// ```
+#[derive(Clone, Default)]
+pub struct CVars {
+    inner: Arc<Mutex<Registry>>,
+}
+
+impl fmt::Debug for CVars {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let registry = self.registry();
+        f.debug_struct("CVars")
+            .field("entries", &registry.entries.keys().collect::<Vec<_>>())
+            .field("pending", &registry.pending)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CVars {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a cvar and returns a typed handle to it.
+    ///
+    /// If a value for `name` was loaded earlier, it replaces the default.
+    /// When a console is attached, a command named after the cvar is added
+    /// that prints the value, or sets it when given an argument.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is empty, contains whitespace, or is
+    /// already registered.
+    pub fn register<T: CVarType>(
+        &self,
+        name: &str,
+        default: T,
+        description: &str,
+    ) -> anyhow::Result<CVar<T>> {
+        if name.is_empty() || name.chars().any(char::is_whitespace) {
+            return Err(anyhow::anyhow!("invalid cvar name `{name}`"));
+        }
+        let console = {
+            let mut registry = self.registry();
+            if registry.entries.contains_key(name) {
+                return Err(anyhow::anyhow!("cvar `{name}` is already registered"));
+            }
+            let default = default.into_value();
+            let value = registry
+                .pending
+                .remove(name)
+                .and_then(|value| coerce(value, T::KIND))
+                .unwrap_or_else(|| default.clone());
+            registry.entries.insert(
+                name.to_owned(),
+                Entry {
+                    description: description.to_owned(),
+                    value,
+                    default,
+                    callbacks: Vec::new(),
+                },
+            );
+            registry.console.clone()
+        };
+        if let Some(console) = console {
+            self.register_command(&console, name, description)?;
+        }
+        Ok(CVar {
+            name: Arc::from(name),
+            cvars: self.clone(),
+            _type: PhantomData,
+        })
+    }
+
+    /// Returns the current value of a cvar.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<ArgValue> {
+        self.registry()
+            .entries
+            .get(name)
+            .map(|entry| entry.value.clone())
+    }
+
+    /// Parses `text` as the cvar's type and assigns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cvar is unknown or `text` does not parse.
+    pub fn set(&self, name: &str, text: &str) -> anyhow::Result<()> {
+        let kind = self.kind(name)?;
+        let value = kind
+            .parse(text)
+            .ok_or_else(|| anyhow::anyhow!("`{name}` expects {kind}, got `{text}`"))?;
+        self.set_value(name, value)
+    }
+
+    /// Assigns a value, widening integers for float cvars.
+    ///
+    /// Callbacks run only if the value actually changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cvar is unknown or the value has the wrong type.
+    pub fn set_value(&self, name: &str, value: ArgValue) -> anyhow::Result<()> {
+        let (callbacks, value) = {
+            let mut registry = self.registry();
+            let entry = registry
+                .entries
+                .get_mut(name)
+                .ok_or_else(|| anyhow::anyhow!("unknown cvar `{name}`"))?;
+            let kind = entry.default.kind();
+            let found = value.kind();
+            let value = coerce(value, kind)
+                .ok_or_else(|| anyhow::anyhow!("`{name}` expects {kind}, got {found}"))?;
+            if entry.value == value {
+                return Ok(());
+            }
+            entry.value = value.clone();
+            (entry.callbacks.clone(), value)
+        };
+        for callback in callbacks {
+            callback(name, &value);
+        }
+        Ok(())
+    }
+
+    /// Restores a cvar to its default value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cvar is unknown.
+    pub fn reset(&self, name: &str) -> anyhow::Result<()> {
+        let default = self
+            .registry()
+            .entries
+            .get(name)
+            .map(|entry| entry.default.clone())
+            .ok_or_else(|| anyhow::anyhow!("unknown cvar `{name}`"))?;
+        self.set_value(name, default)
+    }
+
+    /// Calls `callback` with the new value whenever the cvar changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cvar is unknown.
+    pub fn on_change<F>(&self, name: &str, callback: F) -> anyhow::Result<()>
+    where
+        F: Fn(&str, &ArgValue) + Send + Sync + 'static,
+    {
+        self.registry()
+            .entries
+            .get_mut(name)
+            .ok_or_else(|| anyhow::anyhow!("unknown cvar `{name}`"))?
+            .callbacks
+            .push(Arc::new(callback));
+        Ok(())
+    }
+
+    /// Returns all registered cvars, sorted by name.
+    #[must_use]
+    pub fn list(&self) -> Vec<CVarInfo> {
+        self.registry()
+            .entries
+            .iter()
+            .map(|(name, entry)| CVarInfo {
+                name: name.clone(),
+                description: entry.description.clone(),
+                value: entry.value.clone(),
+                default: entry.default.clone(),
+            })
+            .collect()
+    }
+
+    /// Applies values from TOML text.
+    ///
+    /// Nested tables are flattened into dotted names. Values for cvars that
+    /// are not registered yet are kept until they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not valid TOML or a value does not
+    /// match its cvar's type. Valid entries are still applied.
+    pub fn load_str(&self, text: &str) -> anyhow::Result<()> {
+        let table: toml::Table = text
+            .parse()
+            .map_err(|e| anyhow::anyhow!("invalid cvar file: {e}"))?;
+        let mut values = Vec::new();
+        flatten("", table, &mut values)?;
+
+        let mut errors = Vec::new();
+        for (name, value) in values {
+            let registered = self.registry().entries.contains_key(&name);
+            if registered {
+                if let Err(e) = self.set_value(&name, value) {
+                    errors.push(e.to_string());
+                }
+            } else {
+                self.registry().pending.insert(name, value);
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(errors.join("; ")))
+        }
+    }
+
+    /// Applies values from a TOML file. A missing file is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed; see
+    /// [`load_str`](Self::load_str).
+    pub fn load_file(&self, path: &Path) -> anyhow::Result<()> {
+        match fs::read_to_string(path) {
+            Ok(text) => self
+                .load_str(&text)
+                .map_err(|e| anyhow::anyhow!("{}: {e}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(anyhow::anyhow!("failed to read {}: {e}", path.display())),
+        }
+    }
+
+    /// Serializes user overrides as TOML.
+    ///
+    /// Includes every cvar whose value differs from its default, plus loaded
+    /// values for cvars that were never registered, so they survive a
+    /// round trip.
+    #[must_use]
+    pub fn overrides_to_string(&self) -> String {
+        let registry = self.registry();
+        let overrides = registry
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.value != entry.default)
+            .map(|(name, entry)| (name, &entry.value))
+            .chain(registry.pending.iter());
+
+        let mut sorted: Vec<_> = overrides.collect();
+        sorted.sort_by_key(|(name, _)| name.as_str());
+
+        let mut text = String::new();
+        for (name, value) in sorted {
+            let _ = writeln!(
+                text,
+                "{} = {}",
+                toml::Value::String(name.clone()),
+                to_toml(value)
+            );
+        }
+        text
+    }
+
+    /// Writes user overrides to a TOML file, creating parent directories.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save_file(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                anyhow::anyhow!("failed to create directory {}: {e}", parent.display())
+            })?;
+        }
+        fs::write(path, self.overrides_to_string())
+            .map_err(|e| anyhow::anyhow!("failed to write {}: {e}", path.display()))
+    }
+
+    /// Exposes cvars as console commands.
+    ///
+    /// Every cvar, present and future, gets a command named after it:
+    /// `r.vsync` prints the value and `r.vsync off` sets it. A `cvars`
+    /// command lists all variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a cvar name clashes with an existing command.
+    pub fn attach_console(&self, console: &Console) -> anyhow::Result<()> {
+        let names: Vec<(String, String)> = {
+            let mut registry = self.registry();
+            registry.console = Some(console.clone());
+            registry
+                .entries
+                .iter()
+                .map(|(name, entry)| (name.clone(), entry.description.clone()))
+                .collect()
+        };
+        for (name, description) in names {
+            self.register_command(console, &name, &description)?;
+        }
+
+        let cvars = self.clone();
+        console.register("cvars", "Lists console variables", &[], move |_| {
+            let mut text = String::new();
+            for info in cvars.list() {
+                let _ = writeln!(
+                    text,
+                    "{} = {} (default {}) - {}",
+                    info.name, info.value, info.default, info.description
+                );
+            }
+            Ok(text)
+        })
+    }
+
+    /// Adds the console command for one cvar.
+    fn register_command(
+        &self,
+        console: &Console,
+        name: &str,
+        description: &str,
+    ) -> anyhow::Result<()> {
+        let cvars = self.clone();
+        let cvar = name.to_owned();
+        console.register(
+            name,
+            description,
+            &[Arg::str("value").optional()],
+            move |args| {
+                if let Ok(text) = args.str(0) {
+                    cvars.set(&cvar, text)?;
+                }
+                let value = cvars
+                    .get(&cvar)
+                    .ok_or_else(|| anyhow::anyhow!("unknown cvar `{cvar}`"))?;
+                Ok(format!("{cvar} = {value}"))
+            },
+        )
+    }
+
+    /// Returns the type of a registered cvar.
+    fn kind(&self, name: &str) -> anyhow::Result<ArgKind> {
+        self.registry()
+            .entries
+            .get(name)
+            .map(|entry| entry.default.kind())
+            .ok_or_else(|| anyhow::anyhow!("unknown cvar `{name}`"))
+    }
+
+    fn registry(&self) -> MutexGuard<'_, Registry> {
+        self.inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Typed handle to a registered cvar.
+///
+/// Cheap to clone; reads always see the latest value.
+pub struct CVar<T> {
+    name: Arc<str>,
+    cvars: CVars,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for CVar<T> {
+    fn clone(&self) -> Self {
+        Self {
+            name: Arc::clone(&self.name),
+            cvars: self.cvars.clone(),
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for CVar<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CVar")
+            .field("name", &self.name)
+            .field("value", &self.cvars.get(&self.name))
+            .finish()
+    }
+}
+
+impl<T: CVarType> CVar<T> {
+    /// Returns the cvar name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the current value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the registry holds a value of another type, which
+    /// registration and assignment rule out.
+    #[must_use]
+    pub fn get(&self) -> T {
+        self.cvars
+            .get(&self.name)
+            .and_then(|value| T::from_value(&value))
+            .expect("cvar type is fixed at registration")
+    }
+
+    /// Assigns a new value, running change callbacks if it differs.
+    pub fn set(&self, value: T) {
+        // Cannot fail: the cvar exists and the value has the registered type.
+        let _ = self.cvars.set_value(&self.name, value.into_value());
+    }
+}
+
+/// Converts a value to `kind`, widening integers to floats and parsing
+/// strings.
+#[allow(clippy::cast_precision_loss)]
+fn coerce(value: ArgValue, kind: ArgKind) -> Option<ArgValue> {
+    match (value, kind) {
+        (value, kind) if value.kind() == kind => Some(value),
+        (ArgValue::Int(value), ArgKind::Float) => Some(ArgValue::Float(value as f64)),
+        (ArgValue::Str(text), kind) => kind.parse(&text),
+        _ => None,
+    }
+}
+
+/// Flattens a TOML table into dotted names and cvar values.
+fn flatten(
+    prefix: &str,
+    table: toml::Table,
+    out: &mut Vec<(String, ArgValue)>,
+) -> anyhow::Result<()> {
+    for (key, value) in table {
+        let name = if prefix.is_empty() {
+            key
+        } else {
+            format!("{prefix}.{key}")
+        };
+        let value = match value {
+            toml::Value::Table(table) => {
+                flatten(&name, table, out)?;
+                continue;
+            }
+            toml::Value::Boolean(value) => ArgValue::Bool(value),
+            toml::Value::Integer(value) => ArgValue::Int(value),
+            toml::Value::Float(value) => ArgValue::Float(value),
+            toml::Value::String(value) => ArgValue::Str(value),
+            other => {
+                return Err(anyhow::anyhow!(
+                    "`{name}` has unsupported type {}",
+                    other.type_str()
+                ))
+            }
+        };
+        out.push((name, value));
+    }
+    Ok(())
+}
+
+/// Converts a cvar value to TOML.
+fn to_toml(value: &ArgValue) -> toml::Value {
+    match value {
+        ArgValue::Int(value) => toml::Value::Integer(*value),
+        ArgValue::Float(value) => toml::Value::Float(*value),
+        ArgValue::Bool(value) => toml::Value::Boolean(*value),
+        ArgValue::Str(value) => toml::Value::String(value.clone()),
+    }
+}
@@ -0,0 +1,122 @@
+//! Unit tests for console variables.
+
+use crate::console::{ArgValue, Console};
+use crate::cvar::CVars;
+use std::sync::{Arc, Mutex};
+
+/// Verifies typed access and parsing through the registry.
+#[test]
+fn typed_get_and_set() {
+    let cvars = CVars::new();
+    let vsync = cvars.register("r.vsync", true, "Wait for vblank").unwrap();
+    let scale = cvars.register("time.scale", 1.0, "Time scale").unwrap();
+    let name = cvars
+        .register("net.name", "player".to_owned(), "Player name")
+        .unwrap();
+
+    cvars.set("r.vsync", "off").unwrap();
+    assert!(!vsync.get());
+
+    // Integers widen to floats.
+    cvars.set_value("time.scale", ArgValue::Int(2)).unwrap();
+    assert!((scale.get() - 2.0).abs() < f64::EPSILON);
+
+    name.set("ferris".to_owned());
+    assert_eq!(name.get(), "ferris");
+
+    assert!(cvars.set("r.vsync", "sometimes").is_err());
+    assert!(cvars.set_value("r.vsync", ArgValue::Float(1.0)).is_err());
+    assert!(cvars.set("r.missing", "1").is_err());
+    assert!(cvars.register("r.vsync", false, "").is_err());
+}
+
+/// Verifies that callbacks fire only on actual changes.
+#[test]
+fn change_callbacks() {
+    let cvars = CVars::new();
+    let substeps = cvars.register("phys.substeps", 4_i64, "").unwrap();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&seen);
+    cvars
+        .on_change("phys.substeps", move |name, value| {
+            log.lock().unwrap().push(format!("{name}={value}"));
+        })
+        .unwrap();
+
+    substeps.set(8);
+    substeps.set(8);
+    cvars.reset("phys.substeps").unwrap();
+    assert_eq!(
+        *seen.lock().unwrap(),
+        ["phys.substeps=8", "phys.substeps=4"]
+    );
+}
+
+/// Verifies seeding before registration and saving only overrides.
+#[test]
+fn load_and_save_overrides() {
+    let cvars = CVars::new();
+    cvars
+        .load_str("\"r.vsync\" = false\n[r]\nshadow_resolution = 4096\n[mod]\nunknown = \"kept\"\n")
+        .unwrap();
+
+    let vsync = cvars.register("r.vsync", true, "").unwrap();
+    let shadows = cvars.register("r.shadow_resolution", 2048_i64, "").unwrap();
+    let _substeps = cvars.register("phys.substeps", 4_i64, "").unwrap();
+    assert!(!vsync.get());
+    assert_eq!(shadows.get(), 4096);
+
+    let saved = cvars.overrides_to_string();
+    assert_eq!(
+        saved,
+        "\"mod.unknown\" = \"kept\"\n\"r.shadow_resolution\" = 4096\n\"r.vsync\" = false\n"
+    );
+
+    let reloaded = CVars::new();
+    reloaded.load_str(&saved).unwrap();
+    let vsync = reloaded.register("r.vsync", true, "").unwrap();
+    assert!(!vsync.get());
+}
+
+/// Verifies that bad values are reported without blocking valid ones.
+#[test]
+fn load_reports_type_errors() {
+    let cvars = CVars::new();
+    let vsync = cvars.register("r.vsync", true, "").unwrap();
+    let substeps = cvars.register("phys.substeps", 4_i64, "").unwrap();
+
+    let err = cvars
+        .load_str("\"r.vsync\" = 3\n\"phys.substeps\" = 2\n")
+        .unwrap_err();
+    assert!(err.to_string().contains("r.vsync"), "{err}");
+    assert!(vsync.get());
+    assert_eq!(substeps.get(), 2);
+    assert!(cvars.load_str("not toml =").is_err());
+}
+
+/// Verifies that cvars are reachable from the console.
+#[test]
+fn console_integration() {
+    let console = Console::new();
+    let cvars = CVars::new();
+    let vsync = cvars.register("r.vsync", true, "Wait for vblank").unwrap();
+    cvars.attach_console(&console).unwrap();
+    let substeps = cvars.register("phys.substeps", 4_i64, "Substeps").unwrap();
+
+    assert_eq!(console.execute("r.vsync").unwrap(), "r.vsync = true");
+    assert_eq!(console.execute("r.vsync off").unwrap(), "r.vsync = false");
+    assert!(!vsync.get());
+
+    assert_eq!(
+        console.execute("phys.substeps 6").unwrap(),
+        "phys.substeps = 6"
+    );
+    assert_eq!(substeps.get(), 6);
+    assert!(console.execute("phys.substeps many").is_err());
+
+    assert!(console
+        .execute("cvars")
+        .unwrap()
+        .contains("phys.substeps = 6 (default 4) - Substeps"));
+    assert_eq!(console.complete("r."), ["r.vsync"]);
+}
@@ -0,0 +1,108 @@
+//! Structured engine errors.
+//!
+//! [`RustgineError`] names the failure kinds callers are expected to handle,
+//! so they can match on them instead of inspecting error messages. Anything
+//! else is carried as [`RustgineError::Other`].
+
+use std::error::Error;
+use std::path::PathBuf;
+
+/// Boxed error used as the cause of a [`RustgineError`].
+pub type BoxError = Box<dyn Error + Send + Sync + 'static>;
+
+/// Result type defaulting to [`RustgineError`].
+pub type Result<T, E = RustgineError> = std::result::Result<T, E>;
+
+/// Failure kinds reported by the engine.
+///
+/// # Example
+///
+/// ```
+/// use rustgine_core::RustgineError;
+///
+/// fn describe(error: &RustgineError) -> &'static str {
+///     match error {
+///         RustgineError::DeviceLost => "recreate the renderer",
+///         RustgineError::SystemStartupError { .. } => "abort startup",
+///         _ => "log and continue",
+///     }
+/// }
+///
+/// assert_eq!(describe(&RustgineError::DeviceLost), "recreate the renderer");
+/// ```
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+#[allow(clippy::enum_variant_names)]
+pub enum RustgineError {
+    /// The configuration is missing or invalid.
+    #[error("invalid configuration: {0}")]
+    ConfigError(String),
+
+    /// A subsystem failed to start.
+    #[error("subsystem `{name}` failed to start")]
+    SystemStartupError {
+        /// Name the subsystem was registered under.
+        name: String,
+        /// Error returned by [`RustgineSystem::startup`](crate::RustgineSystem::startup).
+        #[source]
+        source: BoxError,
+    },
+
+    /// A subsystem failed to shut down.
+    #[error("subsystem `{name}` failed to shut down")]
+    SystemShutdownError {
+        /// Name the subsystem was registered under.
+        name: String,
+        /// Error returned by [`RustgineSystem::shutdown`](crate::RustgineSystem::shutdown).
+        #[source]
+        source: BoxError,
+    },
+
+    /// The graphics device was lost and must be recreated.
+    #[error("graphics device lost")]
+    DeviceLost,
+
+    /// An asset could not be found.
+    #[error("asset not found: {}", path.display())]
+    AssetNotFound {
+        /// Path the asset was requested from.
+        path: PathBuf,
+    },
+
+    /// An I/O operation failed.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// Any other failure.
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl RustgineError {
+    /// Creates a [`SystemStartupError`](Self::SystemStartupError).
+    pub fn system_startup(name: impl Into<String>, source: impl Into<BoxError>) -> Self {
+        Self::SystemStartupError {
+            name: name.into(),
+            source: source.into(),
+        }
+    }
+
+    /// Creates a [`SystemShutdownError`](Self::SystemShutdownError).
+    pub fn system_shutdown(name: impl Into<String>, source: impl Into<BoxError>) -> Self {
+        Self::SystemShutdownError {
+            name: name.into(),
+            source: source.into(),
+        }
+    }
+
+    /// Returns the name of the subsystem that failed, if any.
+    #[must_use]
+    pub fn system_name(&self) -> Option<&str> {
+        match self {
+            Self::SystemStartupError { name, .. } | Self::SystemShutdownError { name, .. } => {
+                Some(name)
+            }
+            _ => None,
+        }
+    }
+}
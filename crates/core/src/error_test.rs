@@ -0,0 +1,41 @@
+//! Unit tests for the structured engine error type.
+
+use crate::error::RustgineError;
+use std::error::Error;
+
+/// Verifies startup failures keep the subsystem name and the cause.
+#[test]
+fn system_startup_keeps_name_and_source() {
+    let error = RustgineError::system_startup("render", RustgineError::DeviceLost);
+
+    assert_eq!(error.to_string(), "subsystem `render` failed to start");
+    assert_eq!(error.system_name(), Some("render"));
+    let source = error.source().unwrap();
+    assert!(matches!(
+        source.downcast_ref::<RustgineError>(),
+        Some(RustgineError::DeviceLost)
+    ));
+}
+
+/// Verifies anyhow errors are wrapped transparently.
+#[test]
+fn other_is_transparent() {
+    let error = RustgineError::from(anyhow::anyhow!("boom"));
+
+    assert_eq!(error.to_string(), "boom");
+    assert_eq!(error.system_name(), None);
+}
+
+/// Verifies the error survives a round trip through anyhow for downcasting.
+#[test]
+fn downcasts_from_anyhow() {
+    let error = anyhow::Error::from(RustgineError::AssetNotFound {
+        path: "textures/missing.png".into(),
+    });
+
+    assert_eq!(error.to_string(), "asset not found: textures/missing.png");
+    assert!(matches!(
+        error.downcast_ref::<RustgineError>(),
+        Some(RustgineError::AssetNotFound { .. })
+    ));
+}
@@ -0,0 +1,222 @@
+//! Interned strings for names and asset keys.
+//!
+//! System names, layer names, animation clips, and asset paths are drawn
+//! from a small vocabulary but were cloned as `String`s into every
+//! component, table row, and map key that mentioned them. A [`Label`] is a
+//! `Copy` handle to a string stored once in a global interner: copying it
+//! never allocates, and comparing or hashing it looks at a pointer instead
+//! of the text.
+//!
+//! Interned strings live for the rest of the process, so labels are meant
+//! for bounded vocabularies, not for arbitrary user input such as chat
+//! messages.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+/// Interned strings, each leaked once.
+static INTERNER: OnceLock<RwLock<HashSet<&'static str>>> = OnceLock::new();
+
+/// An interned string.
+///
+/// Equal labels share one allocation, so equality and hashing compare
+/// pointers. Ordering compares the text, keeping sorted output stable
+/// across runs. `Label` deliberately does not implement `Borrow<str>`,
+/// because its hash differs from the string's; look labels up by label.
+///
+/// # Example
+///
+/// ```
+/// use rustgine_core::Label;
+///
+/// let a = Label::new("physics");
+/// let b = Label::from(String::from("physics"));
+/// assert_eq!(a, b);
+/// assert_eq!(a.as_str().as_ptr(), b.as_str().as_ptr());
+/// assert_eq!(a, "physics");
+/// assert!(Label::new("ai") < a);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Label(&'static str);
+
+impl Label {
+    /// Interns `text`, returning its label.
+    ///
+    /// Interning a known string only takes a shared lock; the first use of
+    /// a string allocates it once.
+    #[must_use]
+    pub fn new(text: &str) -> Self {
+        let interner = INTERNER.get_or_init(RwLock::default);
+        if let Some(interned) = interner
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(text)
+        {
+            return Self(interned);
+        }
+        let mut strings = interner.write().unwrap_or_else(PoisonError::into_inner);
+        // Another thread may have interned it between the two locks
+        if let Some(interned) = strings.get(text) {
+            return Self(interned);
+        }
+        let leaked: &'static str = Box::leak(text.into());
+        strings.insert(leaked);
+        Self(leaked)
+    }
+
+    /// Returns the label's text.
+    #[must_use]
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        self.0
+    }
+}
+
+impl Default for Label {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl PartialEq for Label {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.0, other.0)
+    }
+}
+
+impl Eq for Label {}
+
+impl Hash for Label {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.as_ptr().hash(state);
+    }
+}
+
+impl PartialOrd for Label {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Label {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self == other {
+            Ordering::Equal
+        } else {
+            self.0.cmp(other.0)
+        }
+    }
+}
+
+impl PartialEq<str> for Label {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Label {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<String> for Label {
+    #[inline]
+    fn eq(&self, other: &String) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<Label> for str {
+    #[inline]
+    fn eq(&self, other: &Label) -> bool {
+        self == other.0
+    }
+}
+
+impl PartialEq<Label> for &str {
+    #[inline]
+    fn eq(&self, other: &Label) -> bool {
+        *self == other.0
+    }
+}
+
+impl Deref for Label {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
+impl AsRef<str> for Label {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl From<&str> for Label {
+    #[inline]
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<&String> for Label {
+    #[inline]
+    fn from(text: &String) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for Label {
+    #[inline]
+    fn from(text: String) -> Self {
+        Self::new(&text)
+    }
+}
+
+impl From<Label> for String {
+    #[inline]
+    fn from(label: Label) -> Self {
+        label.0.to_owned()
+    }
+}
+
+impl fmt::Debug for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.0, f)
+    }
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl Serialize for Label {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Label {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Borrowing avoids a temporary `String` for formats that allow it
+        let text = std::borrow::Cow::<'de, str>::deserialize(deserializer)?;
+        Ok(Self::new(&text))
+    }
+}
@@ -0,0 +1,78 @@
+//! Unit tests for interned labels.
+
+use crate::Label;
+use std::collections::HashMap;
+
+/// Verifies equal strings intern to the same allocation.
+#[test]
+fn interns_equal_strings_once() {
+    let a = Label::new("label-test-interned");
+    let b = Label::from(String::from("label-test-interned"));
+    assert_eq!(a, b);
+    assert!(std::ptr::eq(a.as_str(), b.as_str()));
+    assert_ne!(a, Label::new("label-test-other"));
+}
+
+/// Verifies labels compare with plain strings in both directions.
+#[test]
+fn compares_with_str() {
+    let label = Label::new("render");
+    assert_eq!(label, "render");
+    assert_eq!("render", label);
+    assert_eq!(label.len(), 6);
+    assert_eq!(label.to_string(), "render");
+    assert_eq!(format!("{label:?}"), "\"render\"");
+    assert_eq!(Label::default(), "");
+}
+
+/// Verifies ordering follows the text, not interning order.
+#[test]
+fn orders_by_text() {
+    let mut labels = [Label::new("zeta"), Label::new("alpha"), Label::new("mu")];
+    labels.sort();
+    let text: Vec<&str> = labels.iter().map(|label| label.as_str()).collect();
+    assert_eq!(text, ["alpha", "mu", "zeta"]);
+}
+
+/// Verifies labels work as map keys.
+#[test]
+fn hashes_as_map_key() {
+    let mut counts = HashMap::new();
+    for name in ["physics", "ai", "physics"] {
+        *counts.entry(Label::new(name)).or_insert(0) += 1;
+    }
+    assert_eq!(counts[&Label::new("physics")], 2);
+    assert_eq!(counts[&Label::new("ai")], 1);
+}
+
+/// Verifies labels serialize as their text.
+#[test]
+fn round_trips_through_serde() {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Sprite {
+        texture: Label,
+    }
+
+    let sprite = Sprite {
+        texture: Label::new("textures/hero.png"),
+    };
+    let text = toml::to_string(&sprite).unwrap();
+    assert_eq!(text.trim(), "texture = \"textures/hero.png\"");
+    let back: Sprite = toml::from_str(&text).unwrap();
+    assert_eq!(back.texture, sprite.texture);
+}
+
+/// Verifies concurrent interning agrees on one label per string.
+#[test]
+fn interns_consistently_across_threads() {
+    let labels: Vec<Label> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..8)
+            .map(|_| scope.spawn(|| Label::new("label-test-threads")))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+    assert!(labels.windows(2).all(|pair| pair[0] == pair[1]));
+}
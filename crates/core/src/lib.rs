@@ -6,14 +6,26 @@
 //!
 //! # Overview
 //!
-//! - [`Config`] - Application configuration loaded from environment variables
+//! - [`BuildInfo`] - Version, git commit, profile, and target of the build
+//! - [`Config`] - Application configuration loaded from files and environment variables
+//! - [`ConfigLoader`] - Layered configuration resolution with per-key sources
+//! - [`Console`] - Developer console with a shared command registry
+//! - [`CVars`] - Runtime-tunable console variables with persisted overrides
+//! - [`Label`] - Interned `Copy` strings for names and asset keys
+//! - [`RustgineError`] - Structured failure kinds callers can match on
+//! - [`LogBuffer`] - Ring buffer of recent log records for debug overlays
+//! - [`Metrics`] - Counters, gauges, and histograms in the Prometheus format
+//! - [`Secret`] - Credentials redacted from debug output and serialization
 //! - [`RustgineSystem`] - Trait defining the lifecycle of engine subsystems
 //! - [`init_tracing`] - Initializes structured logging with environment-based filtering
 //!
+//! The `test-support` feature adds `testing` doubles such as a
+//! `RecordingSystem` that logs its lifecycle calls.
+//!
 //! # Example
 //!
 //! ```
-//! use core::{Config, init_tracing};
+//! use rustgine_core::{Config, init_tracing};
 //!
 //! let config = Config::load().expect("Failed to load config");
 //! init_tracing(&config.environment);
@@ -23,14 +35,54 @@
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+pub mod build_info;
+#[cfg(test)]
+mod build_info_test;
 pub mod config;
+pub mod config_layers;
+#[cfg(test)]
+mod config_layers_test;
 #[cfg(test)]
 mod config_test;
+pub mod console;
+#[cfg(test)]
+mod console_test;
+pub mod cvar;
+#[cfg(test)]
+mod cvar_test;
+pub mod error;
+#[cfg(test)]
+mod error_test;
+pub mod label;
+#[cfg(test)]
+mod label_test;
+pub mod log_buffer;
+#[cfg(test)]
+mod log_buffer_test;
+pub mod metrics;
+#[cfg(test)]
+mod metrics_test;
+pub mod secret;
+#[cfg(test)]
+mod secret_test;
 pub mod system;
+#[cfg(any(test, feature = "test-support"))]
+pub mod testing;
+#[cfg(test)]
+mod testing_test;
 pub mod trace;
 #[cfg(test)]
 mod trace_test;
 
-pub use config::Config;
+pub use build_info::BuildInfo;
+pub use config::{Config, OtlpConfig, OutputConfig, SurfaceFormatPreference, WatchdogConfig};
+pub use config_layers::{ConfigLoader, ConfigSource, LayeredConfig};
+pub use console::Console;
+pub use cvar::{CVar, CVars};
+pub use error::RustgineError;
+pub use label::Label;
+pub use log_buffer::LogBuffer;
+pub use metrics::Metrics;
+pub use secret::Secret;
 pub use system::RustgineSystem;
-pub use trace::init_tracing;
+pub use trace::{init_tracing, init_tracing_from_config, init_tracing_with_console, TracingGuard};
@@ -0,0 +1,209 @@
+//! In-memory ring buffer of recent log records.
+//!
+//! [`LogBuffer`] keeps the last N records emitted through `tracing` so
+//! debug overlays and the console can show recent warnings and errors
+//! without reading the log file. Records reach it through
+//! [`LogBufferLayer`], installed by
+//! [`init_tracing_from_config`](crate::init_tracing_from_config).
+//!
+//! Writers never block: each record claims a slot with an atomic counter
+//! and swaps it in, overwriting the oldest record once the buffer is full.
+
+use crate::console::LineVisitor;
+use arc_swap::ArcSwapOption;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+/// Number of records kept by [`LogBuffer::default`].
+pub const DEFAULT_LOG_BUFFER_CAPACITY: usize = 256;
+
+/// A captured log event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    /// Position in the stream of all records pushed to the buffer.
+    pub sequence: u64,
+    /// When the event was recorded.
+    pub timestamp: SystemTime,
+    /// Event severity.
+    pub level: Level,
+    /// Module path or explicit target of the event.
+    pub target: String,
+    /// The event message.
+    pub message: String,
+    /// Structured fields formatted as ` key=value` pairs.
+    pub fields: String,
+}
+
+impl fmt::Display for LogRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: {}{}",
+            self.level, self.target, self.message, self.fields
+        )
+    }
+}
+
+/// Fixed-capacity, lock-free ring buffer of recent [`LogRecord`]s.
+///
+/// `LogBuffer` is a cheap handle; clones share the same buffer.
+///
+/// # Example
+///
+/// ```
+/// use rustgine_core::log_buffer::LogBuffer;
+/// use tracing::Level;
+///
+/// let buffer = LogBuffer::new(2);
+/// buffer.push(Level::INFO, "app", "first", "");
+/// buffer.push(Level::WARN, "app", "second", "");
+/// buffer.push(Level::ERROR, "app", "third", "");
+///
+/// let messages: Vec<_> = buffer.records().into_iter().map(|r| r.message).collect();
+/// assert_eq!(messages, ["second", "third"]);
+/// assert_eq!(buffer.at_least(Level::ERROR).len(), 1);
+/// ```
+#[derive(Clone)]
+pub struct LogBuffer {
+    inner: Arc<Ring>,
+}
+
+/// Slots shared by every [`LogBuffer`] handle.
+struct Ring {
+    slots: Box<[ArcSwapOption<LogRecord>]>,
+    /// Sequence number of the next record.
+    next: AtomicU64,
+}
+
+impl LogBuffer {
+    /// Creates a buffer keeping the last `capacity` records.
+    ///
+    /// A capacity of zero is raised to one.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        let slots = (0..capacity.max(1))
+            .map(|_| ArcSwapOption::empty())
+            .collect();
+        Self {
+            inner: Arc::new(Ring {
+                slots,
+                next: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Returns the maximum number of records kept.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.inner.slots.len()
+    }
+
+    /// Returns the number of records pushed since creation, including
+    /// those already overwritten.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.inner.next.load(Ordering::Acquire)
+    }
+
+    /// Appends a record, overwriting the oldest one if the buffer is full.
+    pub fn push(&self, level: Level, target: &str, message: &str, fields: &str) {
+        let sequence = self.inner.next.fetch_add(1, Ordering::AcqRel);
+        let record = LogRecord {
+            sequence,
+            timestamp: SystemTime::now(),
+            level,
+            target: target.to_owned(),
+            message: message.to_owned(),
+            fields: fields.to_owned(),
+        };
+        self.slot(sequence).store(Some(Arc::new(record)));
+    }
+
+    /// Returns the buffered records, oldest first.
+    ///
+    /// Records being written concurrently may be missing from the result.
+    #[must_use]
+    pub fn records(&self) -> Vec<LogRecord> {
+        let end = self.total();
+        let start = end.saturating_sub(self.capacity() as u64);
+        (start..end)
+            .filter_map(|sequence| {
+                self.slot(sequence)
+                    .load_full()
+                    .filter(|record| record.sequence == sequence)
+            })
+            .map(|record| (*record).clone())
+            .collect()
+    }
+
+    /// Returns the buffered records at `level` or more severe, oldest first.
+    ///
+    /// `LogBuffer::at_least(Level::WARN)` yields warnings and errors.
+    #[must_use]
+    pub fn at_least(&self, level: Level) -> Vec<LogRecord> {
+        let mut records = self.records();
+        records.retain(|record| record.level <= level);
+        records
+    }
+
+    /// Removes all buffered records.
+    pub fn clear(&self) {
+        for slot in &*self.inner.slots {
+            slot.store(None);
+        }
+    }
+
+    /// Returns a tracing layer that pushes every event into this buffer.
+    #[must_use]
+    pub fn tracing_layer(&self) -> LogBufferLayer {
+        LogBufferLayer {
+            buffer: self.clone(),
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn slot(&self, sequence: u64) -> &ArcSwapOption<LogRecord> {
+        &self.inner.slots[(sequence % self.capacity() as u64) as usize]
+    }
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOG_BUFFER_CAPACITY)
+    }
+}
+
+impl fmt::Debug for LogBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogBuffer")
+            .field("capacity", &self.capacity())
+            .field("total", &self.total())
+            .finish()
+    }
+}
+
+/// Tracing layer that records events into a [`LogBuffer`].
+#[derive(Debug, Clone)]
+pub struct LogBufferLayer {
+    buffer: LogBuffer,
+}
+
+impl<S: Subscriber> Layer<S> for LogBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = LineVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        self.buffer.push(
+            *metadata.level(),
+            metadata.target(),
+            &visitor.message,
+            &visitor.fields,
+        );
+    }
+}
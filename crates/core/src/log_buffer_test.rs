@@ -0,0 +1,103 @@
+//! Unit tests for the in-memory log ring buffer.
+
+use crate::log_buffer::LogBuffer;
+use std::thread;
+use tracing::Level;
+use tracing_subscriber::layer::SubscriberExt;
+
+/// Verifies the oldest records are overwritten once the buffer is full.
+#[test]
+fn keeps_last_records_in_order() {
+    let buffer = LogBuffer::new(3);
+    for i in 0..5 {
+        buffer.push(Level::INFO, "test", &format!("message {i}"), "");
+    }
+
+    let records = buffer.records();
+    let messages: Vec<_> = records.iter().map(|r| r.message.as_str()).collect();
+    assert_eq!(messages, ["message 2", "message 3", "message 4"]);
+    assert_eq!(records[0].sequence, 2);
+    assert_eq!(buffer.total(), 5);
+}
+
+/// Verifies level filtering keeps only records at or above the severity.
+#[test]
+fn filters_by_minimum_level() {
+    let buffer = LogBuffer::new(8);
+    buffer.push(Level::DEBUG, "test", "debug", "");
+    buffer.push(Level::WARN, "test", "warn", "");
+    buffer.push(Level::INFO, "test", "info", "");
+    buffer.push(Level::ERROR, "test", "error", "");
+
+    let messages: Vec<_> = buffer
+        .at_least(Level::WARN)
+        .into_iter()
+        .map(|r| r.message)
+        .collect();
+    assert_eq!(messages, ["warn", "error"]);
+}
+
+/// Verifies clearing empties the buffer but keeps the running total.
+#[test]
+fn clear_removes_records() {
+    let buffer = LogBuffer::new(4);
+    buffer.push(Level::INFO, "test", "one", "");
+    buffer.clear();
+
+    assert!(buffer.records().is_empty());
+    assert_eq!(buffer.total(), 1);
+}
+
+/// Verifies a zero capacity still keeps the latest record.
+#[test]
+fn zero_capacity_keeps_one_record() {
+    let buffer = LogBuffer::new(0);
+    buffer.push(Level::INFO, "test", "one", "");
+    buffer.push(Level::INFO, "test", "two", "");
+
+    assert_eq!(buffer.capacity(), 1);
+    assert_eq!(buffer.records()[0].message, "two");
+}
+
+/// Verifies concurrent writers never lose the buffer's consistency.
+#[test]
+fn concurrent_pushes_are_recorded() {
+    let buffer = LogBuffer::new(64);
+    let handles: Vec<_> = (0..4)
+        .map(|t| {
+            let buffer = buffer.clone();
+            thread::spawn(move || {
+                for i in 0..100 {
+                    buffer.push(Level::INFO, "test", &format!("{t}:{i}"), "");
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let records = buffer.records();
+    assert_eq!(buffer.total(), 400);
+    assert_eq!(records.len(), 64);
+    assert!(records.windows(2).all(|w| w[0].sequence < w[1].sequence));
+}
+
+/// Verifies the tracing layer captures the message, target, and fields.
+#[test]
+fn layer_captures_events() {
+    let buffer = LogBuffer::new(4);
+    let subscriber = tracing_subscriber::registry().with(buffer.tracing_layer());
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::warn!(target: "render", frame = 7, "slow frame");
+    });
+
+    let records = buffer.records();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].level, Level::WARN);
+    assert_eq!(records[0].target, "render");
+    assert_eq!(records[0].message, "slow frame");
+    assert_eq!(records[0].fields, " frame=7");
+    assert_eq!(records[0].to_string(), "WARN render: slow frame frame=7");
+}
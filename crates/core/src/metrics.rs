@@ -0,0 +1,322 @@
+//! Engine metrics in the Prometheus text format.
+//!
+//! Subsystems register [`Counter`]s, [`Gauge`]s, and [`Histogram`]s on the
+//! shared [`Metrics`] registry and update them on the hot path; updates are
+//! lock-free for counters and gauges. The registry renders everything in the
+//! [Prometheus text exposition format] for scraping or periodic dumps, so
+//! long-running dedicated servers can be monitored with standard tooling.
+//!
+//! [Prometheus text exposition format]: https://prometheus.io/docs/instrumenting/exposition_formats/
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// Default histogram buckets for durations in seconds, tuned for frame times.
+pub const FRAME_TIME_BUCKETS: &[f64] = &[
+    0.001, 0.002, 0.004, 0.008, 0.016, 0.033, 0.05, 0.1, 0.25, 0.5, 1.0,
+];
+
+/// A monotonically increasing count, such as frames rendered.
+#[derive(Debug, Clone, Default)]
+pub struct Counter(Arc<AtomicU64>);
+
+impl Counter {
+    /// Increments the counter by one.
+    #[inline]
+    pub fn inc(&self) {
+        self.add(1);
+    }
+
+    /// Increments the counter by `n`.
+    #[inline]
+    pub fn add(&self, n: u64) {
+        self.0.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Returns the current count.
+    #[must_use]
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// A value that can go up and down, such as entity count or cache size.
+#[derive(Debug, Clone, Default)]
+pub struct Gauge(Arc<AtomicU64>);
+
+impl Gauge {
+    /// Sets the gauge.
+    #[inline]
+    pub fn set(&self, value: f64) {
+        self.0.store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Adds `delta` to the gauge, which may be negative.
+    pub fn add(&self, delta: f64) {
+        let _ = self
+            .0
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                Some((f64::from_bits(bits) + delta).to_bits())
+            });
+    }
+
+    /// Returns the current value.
+    #[must_use]
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.0.load(Ordering::Relaxed))
+    }
+}
+
+/// Samples sorted into cumulative buckets, such as frame times.
+#[derive(Debug, Clone)]
+pub struct Histogram(Arc<Mutex<HistogramState>>);
+
+/// Bucket counts and totals of a histogram.
+#[derive(Debug)]
+struct HistogramState {
+    /// Upper bounds, ascending.
+    bounds: Vec<f64>,
+    /// Per-bucket (non-cumulative) counts; the last entry is `+Inf`.
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(buckets: &[f64]) -> Self {
+        let mut bounds: Vec<f64> = buckets.iter().copied().filter(|b| b.is_finite()).collect();
+        bounds.sort_by(f64::total_cmp);
+        bounds.dedup();
+        let counts = vec![0; bounds.len() + 1];
+        Self(Arc::new(Mutex::new(HistogramState {
+            bounds,
+            counts,
+            sum: 0.0,
+            count: 0,
+        })))
+    }
+
+    /// Records one sample.
+    pub fn observe(&self, value: f64) {
+        let mut state = self.state();
+        let bucket = state.bounds.partition_point(|bound| *bound < value);
+        state.counts[bucket] += 1;
+        state.sum += value;
+        state.count += 1;
+    }
+
+    /// Returns the number of samples recorded.
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.state().count
+    }
+
+    /// Returns the sum of all samples.
+    #[must_use]
+    pub fn sum(&self) -> f64 {
+        self.state().sum
+    }
+
+    fn state(&self) -> MutexGuard<'_, HistogramState> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A registered metric.
+#[derive(Debug, Clone)]
+enum Metric {
+    Counter(Counter),
+    Gauge(Gauge),
+    Histogram(Histogram),
+}
+
+impl Metric {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::Counter(_) => "counter",
+            Self::Gauge(_) => "gauge",
+            Self::Histogram(_) => "histogram",
+        }
+    }
+}
+
+/// A registered metric with its help text.
+#[derive(Debug)]
+struct Entry {
+    help: String,
+    metric: Metric,
+}
+
+/// Registry of engine metrics.
+///
+/// `Metrics` is a cheap handle; clones share the same registry. Requesting
+/// a metric that already exists returns the existing one, so independent
+/// code can share a metric by name.
+///
+/// # Example
+///
+/// ```
+/// use rustgine_core::metrics::Metrics;
+///
+/// let metrics = Metrics::new();
+/// let draw_calls = metrics.gauge("rustgine_draw_calls", "Draw calls last frame").unwrap();
+/// draw_calls.set(120.0);
+///
+/// let text = metrics.render();
+/// assert!(text.contains("# TYPE rustgine_draw_calls gauge"));
+/// assert!(text.contains("rustgine_draw_calls 120"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    inner: Arc<Mutex<BTreeMap<String, Entry>>>,
+}
+
+impl Metrics {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the counter named `name`, registering it if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is not a valid Prometheus metric name or
+    /// is registered as another metric type.
+    pub fn counter(&self, name: &str, help: &str) -> anyhow::Result<Counter> {
+        match self.get_or_insert(name, help, || Metric::Counter(Counter::default()))? {
+            Metric::Counter(counter) => Ok(counter),
+            other => Err(type_clash(name, "counter", &other)),
+        }
+    }
+
+    /// Returns the gauge named `name`, registering it if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is not a valid Prometheus metric name or
+    /// is registered as another metric type.
+    pub fn gauge(&self, name: &str, help: &str) -> anyhow::Result<Gauge> {
+        match self.get_or_insert(name, help, || Metric::Gauge(Gauge::default()))? {
+            Metric::Gauge(gauge) => Ok(gauge),
+            other => Err(type_clash(name, "gauge", &other)),
+        }
+    }
+
+    /// Returns the histogram named `name`, registering it with `buckets`
+    /// (upper bounds) if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is not a valid Prometheus metric name or
+    /// is registered as another metric type.
+    pub fn histogram(&self, name: &str, help: &str, buckets: &[f64]) -> anyhow::Result<Histogram> {
+        match self.get_or_insert(name, help, || Metric::Histogram(Histogram::new(buckets)))? {
+            Metric::Histogram(histogram) => Ok(histogram),
+            other => Err(type_clash(name, "histogram", &other)),
+        }
+    }
+
+    /// Renders all metrics in the Prometheus text exposition format.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut text = String::new();
+        for (name, entry) in self.entries().iter() {
+            let _ = render_metric(&mut text, name, entry);
+        }
+        text
+    }
+
+    /// Writes [`render`](Self::render) output to `path`, replacing it
+    /// atomically so readers never see a partial dump.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn write_to_file(&self, path: &Path) -> anyhow::Result<()> {
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, self.render())
+            .and_then(|()| fs::rename(&tmp, path))
+            .map_err(|e| anyhow::anyhow!("failed to write metrics to {}: {e}", path.display()))
+    }
+
+    fn get_or_insert(
+        &self,
+        name: &str,
+        help: &str,
+        make: impl FnOnce() -> Metric,
+    ) -> anyhow::Result<Metric> {
+        if !is_valid_name(name) {
+            return Err(anyhow::anyhow!("invalid metric name `{name}`"));
+        }
+        let mut entries = self.entries();
+        let entry = entries.entry(name.to_owned()).or_insert_with(|| Entry {
+            help: help.to_owned(),
+            metric: make(),
+        });
+        Ok(entry.metric.clone())
+    }
+
+    fn entries(&self) -> MutexGuard<'_, BTreeMap<String, Entry>> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Builds the error for requesting a metric as the wrong type.
+fn type_clash(name: &str, requested: &str, existing: &Metric) -> anyhow::Error {
+    anyhow::anyhow!(
+        "metric `{name}` is a {}, not a {requested}",
+        existing.type_name()
+    )
+}
+
+/// Returns `true` for names matching `[a-zA-Z_:][a-zA-Z0-9_:]*`.
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == ':')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+}
+
+/// Appends one metric family to `out`.
+fn render_metric(out: &mut String, name: &str, entry: &Entry) -> fmt::Result {
+    let help = entry.help.replace('\\', "\\\\").replace('\n', "\\n");
+    writeln!(out, "# HELP {name} {help}")?;
+    writeln!(out, "# TYPE {name} {}", entry.metric.type_name())?;
+    match &entry.metric {
+        Metric::Counter(counter) => writeln!(out, "{name} {}", counter.get()),
+        Metric::Gauge(gauge) => writeln!(out, "{name} {}", format_value(gauge.get())),
+        Metric::Histogram(histogram) => {
+            let state = histogram.state();
+            let mut cumulative = 0;
+            for (bound, count) in state.bounds.iter().zip(&state.counts) {
+                cumulative += count;
+                writeln!(
+                    out,
+                    "{name}_bucket{{le=\"{}\"}} {cumulative}",
+                    format_value(*bound)
+                )?;
+            }
+            writeln!(out, "{name}_bucket{{le=\"+Inf\"}} {}", state.count)?;
+            writeln!(out, "{name}_sum {}", format_value(state.sum))?;
+            writeln!(out, "{name}_count {}", state.count)
+        }
+    }
+}
+
+/// Formats a sample value the way Prometheus expects.
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_owned()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_owned()
+    } else {
+        value.to_string()
+    }
+}
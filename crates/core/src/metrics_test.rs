@@ -0,0 +1,71 @@
+//! Unit tests for the metrics registry.
+
+use crate::metrics::Metrics;
+
+/// Verifies counter and gauge updates and sharing by name.
+#[test]
+fn counters_and_gauges() {
+    let metrics = Metrics::new();
+    let frames = metrics.counter("rustgine_frames_total", "Frames").unwrap();
+    frames.inc();
+    frames.add(2);
+    assert_eq!(
+        metrics.counter("rustgine_frames_total", "").unwrap().get(),
+        3
+    );
+
+    let entities = metrics.gauge("rustgine_entities", "Live entities").unwrap();
+    entities.set(10.0);
+    entities.add(-2.5);
+    assert!((entities.get() - 7.5).abs() < f64::EPSILON);
+}
+
+/// Verifies name validation and type clashes.
+#[test]
+fn rejects_invalid_names_and_type_clashes() {
+    let metrics = Metrics::new();
+    assert!(metrics.counter("9lives", "").is_err());
+    assert!(metrics.counter("has-dash", "").is_err());
+    metrics.gauge("rustgine_cache_bytes", "").unwrap();
+    let err = metrics.counter("rustgine_cache_bytes", "").unwrap_err();
+    assert!(err.to_string().contains("is a gauge"), "{err}");
+}
+
+/// Verifies the text exposition format, including histogram buckets.
+#[test]
+fn renders_prometheus_text() {
+    let metrics = Metrics::new();
+    metrics.counter("b_total", "Second\nline").unwrap().inc();
+    let frame_time = metrics
+        .histogram("a_seconds", "Frame time", &[0.1, 0.01])
+        .unwrap();
+    frame_time.observe(0.005);
+    frame_time.observe(0.05);
+    frame_time.observe(0.5);
+    assert_eq!(frame_time.count(), 3);
+
+    assert_eq!(
+        metrics.render(),
+        "# HELP a_seconds Frame time\n\
+         # TYPE a_seconds histogram\n\
+         a_seconds_bucket{le=\"0.01\"} 1\n\
+         a_seconds_bucket{le=\"0.1\"} 2\n\
+         a_seconds_bucket{le=\"+Inf\"} 3\n\
+         a_seconds_sum 0.555\n\
+         a_seconds_count 3\n\
+         # HELP b_total Second\\nline\n\
+         # TYPE b_total counter\n\
+         b_total 1\n"
+    );
+}
+
+/// Verifies dumping to a file.
+#[test]
+fn writes_to_file() {
+    let path = std::env::temp_dir().join(format!("rustgine-metrics-{}.prom", std::process::id()));
+    let metrics = Metrics::new();
+    metrics.gauge("up", "Engine running").unwrap().set(1.0);
+    metrics.write_to_file(&path).unwrap();
+    assert!(std::fs::read_to_string(&path).unwrap().ends_with("up 1\n"));
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,85 @@
+//! Redacted credentials.
+//!
+//! Dedicated servers need database passwords and auth tokens, and those
+//! end up in the same structs as everything else that gets logged, dumped
+//! to the console, or sent to a debug endpoint. A [`Secret`] wraps such a
+//! value so that [`Debug`](fmt::Debug) and serialization print
+//! [`REDACTED`] instead, and reading it takes a call to
+//! [`expose`](Secret::expose) that is easy to find in review.
+//!
+//! Secrets are configured in the `[secrets]` table of the configuration
+//! files, or from the environment; see
+//! [`config_layers`](crate::config_layers).
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Text shown in place of a secret value.
+pub const REDACTED: &str = "[redacted]";
+
+/// A value that is never printed or serialized.
+///
+/// Deserializes from the plain value, so secrets can be read from files
+/// and requests, but serializes as [`REDACTED`].
+///
+/// # Example
+///
+/// ```
+/// use rustgine_core::Secret;
+///
+/// let token = Secret::new("hunter2".to_owned());
+/// assert_eq!(format!("{token:?}"), "Secret([redacted])");
+/// assert_eq!(token.expose(), "hunter2");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Secret<T = String>(T);
+
+impl<T> Secret<T> {
+    /// Wraps `value`.
+    #[must_use]
+    #[inline]
+    pub const fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns the secret value.
+    ///
+    /// Keep the result out of logs and error messages.
+    #[must_use]
+    #[inline]
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwraps the secret value.
+    #[must_use]
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Secret<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret({REDACTED})")
+    }
+}
+
+impl<T> Serialize for Secret<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(REDACTED)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Secret<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self)
+    }
+}
@@ -0,0 +1,55 @@
+//! Unit tests for redacted secrets.
+
+use crate::secret::{Secret, REDACTED};
+
+/// Verifies debug output never contains the value.
+#[test]
+fn debug_is_redacted() {
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Credentials {
+        user: String,
+        password: Secret,
+    }
+
+    let secret = Secret::new("hunter2".to_owned());
+    let debug = format!("{secret:?}");
+    assert!(!debug.contains("hunter2"), "{debug}");
+    assert!(debug.contains(REDACTED), "{debug}");
+
+    let credentials = Credentials {
+        user: "admin".to_owned(),
+        password: "hunter2".to_owned().into(),
+    };
+    assert!(!format!("{credentials:#?}").contains("hunter2"));
+}
+
+/// Verifies secrets deserialize from the plain value but serialize redacted.
+#[test]
+fn serializes_redacted() {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Database {
+        url: String,
+        password: Secret,
+    }
+
+    let database: Database =
+        toml::from_str("url = \"postgres://db\"\npassword = \"hunter2\"\n").unwrap();
+    assert_eq!(database.password.expose(), "hunter2");
+
+    let text = toml::to_string(&database).unwrap();
+    assert!(!text.contains("hunter2"), "{text}");
+    assert!(
+        text.contains(&format!("password = \"{REDACTED}\"")),
+        "{text}"
+    );
+}
+
+/// Verifies the wrapped value is reachable explicitly.
+#[test]
+fn exposes_value() {
+    let secret = Secret::from(42_u64);
+    assert_eq!(*secret.expose(), 42);
+    assert_eq!(secret.clone(), Secret::new(42));
+    assert_eq!(secret.into_inner(), 42);
+}
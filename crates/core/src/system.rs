@@ -3,6 +3,7 @@
 //! Defines the [`RustgineSystem`] trait that all engine subsystems must implement
 //! for proper initialization and cleanup.
 
+use crate::error::RustgineError;
 use std::fmt::Debug;
 
 /// Trait defining the lifecycle of an engine subsystem.
@@ -28,7 +29,7 @@ use std::fmt::Debug;
 /// # Example
 ///
 /// ```
-/// use core::RustgineSystem;
+/// use rustgine_core::{RustgineError, RustgineSystem};
 ///
 /// #[derive(Debug)]
 /// struct AudioSystem {
@@ -36,13 +37,13 @@ use std::fmt::Debug;
 /// }
 ///
 /// impl RustgineSystem for AudioSystem {
-///     fn startup(&mut self) -> anyhow::Result<()> {
+///     fn startup(&mut self) -> Result<(), RustgineError> {
 ///         // Initialize audio device, load banks, etc.
 ///         self.initialized = true;
 ///         Ok(())
 ///     }
 ///
-///     fn shutdown(&mut self) -> anyhow::Result<()> {
+///     fn shutdown(&mut self) -> Result<(), RustgineError> {
 ///         // Stop playback, release audio device
 ///         self.initialized = false;
 ///         Ok(())
@@ -57,9 +58,9 @@ pub trait RustgineSystem: Debug {
     ///
     /// # Errors
     ///
-    /// Returns an error if initialization fails. The engine will typically
-    /// abort startup if any critical subsystem fails to initialize.
-    fn startup(&mut self) -> anyhow::Result<()>;
+    /// Returns an error if initialization fails. The runtime aborts startup
+    /// and reports it as [`RustgineError::SystemStartupError`].
+    fn startup(&mut self) -> Result<(), RustgineError>;
 
     /// Shuts down the subsystem and releases resources.
     ///
@@ -69,6 +70,7 @@ pub trait RustgineSystem: Debug {
     /// # Errors
     ///
     /// Returns an error if cleanup fails. Errors during shutdown are
-    /// typically logged but may not prevent engine termination.
-    fn shutdown(&mut self) -> anyhow::Result<()>;
+    /// typically logged but may not prevent engine termination. The runtime
+    /// reports them as [`RustgineError::SystemShutdownError`].
+    fn shutdown(&mut self) -> Result<(), RustgineError>;
 }
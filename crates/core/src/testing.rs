@@ -0,0 +1,243 @@
+//! Test doubles for engine subsystems.
+//!
+//! Compiled with the `test-support` feature, which crates enable in their
+//! `[dev-dependencies]`. A [`RecordingSystem`] appends every lifecycle call
+//! to a [`CallLog`] shared with the test, and can be told to fail, panic,
+//! or stall at chosen phases, so runtime ordering, failure handling, and
+//! slow shutdowns can be tested without real devices.
+
+use crate::error::RustgineError;
+use crate::label::Label;
+use crate::system::RustgineSystem;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+/// A lifecycle call of a [`RustgineSystem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// [`RustgineSystem::startup`].
+    Startup,
+    /// A per-frame update, driven by the test through
+    /// [`RecordingSystem::update`].
+    Update,
+    /// [`RustgineSystem::shutdown`].
+    Shutdown,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Startup => "startup",
+            Self::Update => "update",
+            Self::Shutdown => "shutdown",
+        })
+    }
+}
+
+/// One recorded call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Call {
+    /// Name of the system called.
+    pub system: Label,
+    /// Lifecycle phase called.
+    pub phase: Phase,
+}
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.phase, self.system)
+    }
+}
+
+/// Calls recorded by any number of [`RecordingSystem`]s, in call order.
+///
+/// Clones share the same log.
+#[derive(Debug, Clone, Default)]
+pub struct CallLog(Arc<Mutex<Vec<Call>>>);
+
+impl CallLog {
+    /// Creates an empty log.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns every call so far.
+    #[must_use]
+    pub fn calls(&self) -> Vec<Call> {
+        self.lock().clone()
+    }
+
+    /// Returns the systems called in `phase`, in call order.
+    #[must_use]
+    pub fn systems(&self, phase: Phase) -> Vec<Label> {
+        self.lock()
+            .iter()
+            .filter(|call| call.phase == phase)
+            .map(|call| call.system)
+            .collect()
+    }
+
+    /// Returns how often `system` was called in `phase`.
+    #[must_use]
+    pub fn count(&self, system: &str, phase: Phase) -> usize {
+        self.lock()
+            .iter()
+            .filter(|call| call.phase == phase && call.system == system)
+            .count()
+    }
+
+    /// Forgets every call.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn push(&self, call: Call) {
+        self.lock().push(call);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Call>> {
+        // A recording system may panic on purpose while others record
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// What a [`RecordingSystem`] does when called in a phase.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Outcome {
+    #[default]
+    Succeed,
+    Fail,
+    Panic,
+}
+
+/// Injected behavior for one phase.
+#[derive(Debug, Clone, Copy, Default)]
+struct Behavior {
+    outcome: Outcome,
+    delay: Duration,
+}
+
+/// A [`RustgineSystem`] that records its calls.
+///
+/// Every call is recorded before the injected delay and outcome apply, so
+/// failed and panicking calls appear in the log too.
+///
+/// # Example
+///
+/// ```
+/// use rustgine_core::testing::{CallLog, Phase, RecordingSystem};
+/// use rustgine_core::RustgineSystem;
+///
+/// let log = CallLog::new();
+/// let mut audio = RecordingSystem::new("audio", &log).failing_on(Phase::Shutdown);
+///
+/// audio.startup()?;
+/// assert!(audio.shutdown().is_err());
+/// assert_eq!(log.systems(Phase::Shutdown), ["audio"]);
+/// # Ok::<(), rustgine_core::RustgineError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RecordingSystem {
+    name: Label,
+    log: CallLog,
+    startup: Behavior,
+    update: Behavior,
+    shutdown: Behavior,
+}
+
+impl RecordingSystem {
+    /// Creates a system named `name` that succeeds at once in every phase,
+    /// recording into `log`.
+    #[must_use]
+    pub fn new(name: impl Into<Label>, log: &CallLog) -> Self {
+        Self {
+            name: name.into(),
+            log: log.clone(),
+            startup: Behavior::default(),
+            update: Behavior::default(),
+            shutdown: Behavior::default(),
+        }
+    }
+
+    /// Returns an error from every call in `phase`.
+    #[must_use]
+    pub fn failing_on(mut self, phase: Phase) -> Self {
+        self.behavior(phase).outcome = Outcome::Fail;
+        self
+    }
+
+    /// Panics in every call in `phase`.
+    #[must_use]
+    pub fn panicking_on(mut self, phase: Phase) -> Self {
+        self.behavior(phase).outcome = Outcome::Panic;
+        self
+    }
+
+    /// Blocks the calling thread for `delay` in every call in `phase`, e.g.
+    /// to simulate a slow device shutdown.
+    #[must_use]
+    pub fn with_delay(mut self, phase: Phase, delay: Duration) -> Self {
+        self.behavior(phase).delay = delay;
+        self
+    }
+
+    /// Returns the system's name.
+    #[must_use]
+    #[inline]
+    pub fn name(&self) -> Label {
+        self.name
+    }
+
+    /// Records a per-frame update.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RustgineError::Other`] if updates were set to fail.
+    ///
+    /// # Panics
+    ///
+    /// Panics if updates were set to panic.
+    pub fn update(&mut self) -> Result<(), RustgineError> {
+        self.call(Phase::Update)
+    }
+
+    fn behavior(&mut self, phase: Phase) -> &mut Behavior {
+        match phase {
+            Phase::Startup => &mut self.startup,
+            Phase::Update => &mut self.update,
+            Phase::Shutdown => &mut self.shutdown,
+        }
+    }
+
+    /// Records a call in `phase`, then applies its behavior.
+    fn call(&mut self, phase: Phase) -> Result<(), RustgineError> {
+        let name = self.name;
+        self.log.push(Call {
+            system: name,
+            phase,
+        });
+        let behavior = *self.behavior(phase);
+        if !behavior.delay.is_zero() {
+            std::thread::sleep(behavior.delay);
+        }
+        match behavior.outcome {
+            Outcome::Succeed => Ok(()),
+            Outcome::Fail => Err(RustgineError::Other(anyhow::anyhow!(
+                "{name} failed on {phase} as configured"
+            ))),
+            Outcome::Panic => panic!("{name} panicked on {phase} as configured"),
+        }
+    }
+}
+
+impl RustgineSystem for RecordingSystem {
+    fn startup(&mut self) -> Result<(), RustgineError> {
+        self.call(Phase::Startup)
+    }
+
+    fn shutdown(&mut self) -> Result<(), RustgineError> {
+        self.call(Phase::Shutdown)
+    }
+}
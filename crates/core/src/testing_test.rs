@@ -0,0 +1,65 @@
+//! Unit tests for subsystem test doubles.
+
+use crate::testing::{CallLog, Phase, RecordingSystem};
+use crate::RustgineSystem;
+use std::time::{Duration, Instant};
+
+/// Verifies calls from several systems are recorded in call order.
+#[test]
+fn records_calls_in_order() {
+    let log = CallLog::new();
+    let mut render = RecordingSystem::new("render", &log);
+    let mut audio = RecordingSystem::new("audio", &log);
+
+    render.startup().unwrap();
+    audio.startup().unwrap();
+    render.update().unwrap();
+    render.update().unwrap();
+    audio.shutdown().unwrap();
+    render.shutdown().unwrap();
+
+    assert_eq!(log.systems(Phase::Startup), ["render", "audio"]);
+    assert_eq!(log.systems(Phase::Shutdown), ["audio", "render"]);
+    assert_eq!(log.count("render", Phase::Update), 2);
+    assert_eq!(log.count("audio", Phase::Update), 0);
+    let calls: Vec<String> = log.calls().iter().map(ToString::to_string).collect();
+    assert_eq!(calls[..2], ["startup render", "startup audio"]);
+
+    log.clear();
+    assert!(log.calls().is_empty());
+}
+
+/// Verifies configured failures and panics apply only to their phase.
+#[test]
+fn fails_and_panics_at_configured_phases() {
+    let log = CallLog::new();
+    let mut system = RecordingSystem::new("net", &log)
+        .failing_on(Phase::Update)
+        .panicking_on(Phase::Shutdown);
+
+    system.startup().unwrap();
+    let error = system.update().unwrap_err();
+    assert!(
+        error.to_string().contains("net failed on update"),
+        "{error}"
+    );
+
+    let panic = std::panic::catch_unwind(move || system.shutdown());
+    assert!(panic.is_err());
+    // The panicking call was still recorded
+    assert_eq!(log.count("net", Phase::Shutdown), 1);
+}
+
+/// Verifies injected delays block the call.
+#[test]
+fn delays_configured_phases() {
+    let log = CallLog::new();
+    let mut system =
+        RecordingSystem::new("audio", &log).with_delay(Phase::Shutdown, Duration::from_millis(20));
+
+    let start = Instant::now();
+    system.startup().unwrap();
+    assert!(start.elapsed() < Duration::from_millis(20));
+    system.shutdown().unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(20));
+}
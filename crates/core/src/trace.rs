@@ -1,9 +1,20 @@
 //! Structured logging and tracing infrastructure.
 //!
 //! Provides logging initialization using the [`tracing`] ecosystem for
-//! structured, high-performance observability.
+//! structured, high-performance observability. With the `otlp` feature,
+//! spans can also be exported to an OpenTelemetry collector.
+use crate::config::Config;
+#[cfg(feature = "otlp")]
+use crate::config::OtlpConfig;
+use crate::console::{Console, ConsoleLayer};
+use crate::log_buffer::{LogBuffer, LogBufferLayer};
 use std::sync::Once;
-use tracing_subscriber::{fmt, EnvFilter};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter, Layer, Registry};
+
+/// A layer installed directly on the registry.
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
 
 static INIT_TRACING: Once = Once::new();
 /// Initializes the global tracing subscriber with the given default log level.
@@ -21,20 +32,158 @@ static INIT_TRACING: Once = Once::new();
 /// # Example
 ///
 /// ```ignore
-/// use core::init_tracing;
+/// use rustgine_core::init_tracing;
 ///
 /// // Initialize with info level; RUST_LOG=debug overrides at runtime
 /// init_tracing("info");
 /// ```
 pub fn init_tracing(log_level: &str) {
+    install(log_level, None, None, None);
+}
+
+/// Initializes the global tracing subscriber and mirrors log output into
+/// the developer console.
+///
+/// Behaves like [`init_tracing`], additionally copying every event that
+/// passes the filter into the [`Console`] scrollback.
+///
+/// # Arguments
+///
+/// * `log_level` - Default log level filter (e.g., `"info"`, `"debug"`, `"warn"`)
+/// * `console` - Console whose scrollback receives log lines
+pub fn init_tracing_with_console(log_level: &str, console: &Console) {
+    install(log_level, Some(console.tracing_layer()), None, None);
+}
+
+/// Initializes the global tracing subscriber from the engine configuration.
+///
+/// Uses [`Config::log_level`] as the default filter, mirrors log output into
+/// `console` and `log_buffer` if given, and, when [`Config::otlp`] is set and the `otlp`
+/// feature is enabled, exports spans to an OpenTelemetry collector.
+///
+/// Keep the returned guard alive for the lifetime of the process; dropping
+/// it flushes and shuts down the exporter.
+///
+/// # Errors
+///
+/// Returns an error if the OTLP exporter cannot be created.
+///
+/// # Example
+///
+/// ```ignore
+/// use rustgine_core::{init_tracing_from_config, Config};
+///
+/// let config = Config::load()?;
+/// let _tracing = init_tracing_from_config(&config, None, None)?;
+/// ```
+pub fn init_tracing_from_config(
+    config: &Config,
+    console: Option<&Console>,
+    log_buffer: Option<&LogBuffer>,
+) -> anyhow::Result<TracingGuard> {
+    #[cfg(feature = "otlp")]
+    let (otel, guard) = match &config.otlp {
+        Some(otlp) => otlp_layer(otlp)?,
+        None => (None, TracingGuard::default()),
+    };
+    #[cfg(not(feature = "otlp"))]
+    let (otel, guard) = (None, TracingGuard::default());
+
+    install(
+        &config.log_level,
+        console.map(Console::tracing_layer),
+        log_buffer.map(LogBuffer::tracing_layer),
+        otel,
+    );
+
+    #[cfg(not(feature = "otlp"))]
+    if let Some(otlp) = &config.otlp {
+        tracing::warn!(
+            endpoint = %otlp.endpoint,
+            "OTLP endpoint configured but the `otlp` feature is disabled; spans will not be exported"
+        );
+    }
+    Ok(guard)
+}
+
+/// Flushes and shuts down trace exporters when dropped.
+///
+/// Returned by [`init_tracing_from_config`].
+#[derive(Debug, Default)]
+#[must_use = "dropping the guard shuts down trace export"]
+pub struct TracingGuard {
+    #[cfg(feature = "otlp")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otlp")]
+        if let Some(provider) = self.provider.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("failed to shut down OTLP trace export: {e}");
+            }
+        }
+    }
+}
+
+/// Builds the OpenTelemetry layer exporting spans over OTLP/HTTP.
+#[cfg(feature = "otlp")]
+fn otlp_layer(otlp: &OtlpConfig) -> anyhow::Result<(Option<BoxedLayer>, TracingGuard)> {
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+    use opentelemetry_sdk::trace::{Sampler, SdkTracerProvider};
+    use opentelemetry_sdk::Resource;
+
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(&otlp.endpoint)
+        .build()
+        .map_err(|e| {
+            anyhow::anyhow!("failed to create OTLP exporter for {}: {e}", otlp.endpoint)
+        })?;
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            otlp.sample_ratio,
+        ))))
+        .with_resource(
+            Resource::builder()
+                .with_service_name(otlp.service_name.clone())
+                .build(),
+        )
+        .build();
+    let tracer = provider.tracer("rustgine");
+    let layer = tracing_opentelemetry::layer().with_tracer(tracer).boxed();
+    Ok((
+        Some(layer),
+        TracingGuard {
+            provider: Some(provider),
+        },
+    ))
+}
+
+/// Installs the global subscriber once.
+fn install(
+    log_level: &str,
+    console: Option<ConsoleLayer>,
+    log_buffer: Option<LogBufferLayer>,
+    otel: Option<BoxedLayer>,
+) {
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(log_level));
 
     INIT_TRACING.call_once(|| {
-        fmt()
-            .with_env_filter(filter)
-            .with_target(true)
-            .with_thread_ids(false)
-            .with_thread_names(false)
+        tracing_subscriber::registry()
+            .with(otel)
+            .with(filter)
+            .with(
+                fmt::layer()
+                    .with_target(true)
+                    .with_thread_ids(false)
+                    .with_thread_names(false),
+            )
+            .with(console)
+            .with(log_buffer)
             .init();
     });
 }
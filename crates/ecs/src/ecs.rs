@@ -3,7 +3,7 @@
 //! Provides the [`RustgineEcs`] system for managing entities, components,
 //! and system execution.
 
-use rustgine_core::RustgineSystem;
+use rustgine_core::{RustgineError, RustgineSystem};
 
 /// Entity Component System subsystem for the Rustgine engine.
 ///
@@ -31,7 +31,7 @@ impl RustgineSystem for RustgineEcs {
     ///
     /// Returns an error if ECS initialization fails.
     #[inline]
-    fn startup(&mut self) -> anyhow::Result<()> {
+    fn startup(&mut self) -> Result<(), RustgineError> {
         Ok(())
     }
 
@@ -41,7 +41,7 @@ impl RustgineSystem for RustgineEcs {
     ///
     /// Returns an error if cleanup fails.
     #[inline]
-    fn shutdown(&mut self) -> anyhow::Result<()> {
+    fn shutdown(&mut self) -> Result<(), RustgineError> {
         Ok(())
     }
 }
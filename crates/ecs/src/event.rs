@@ -0,0 +1,94 @@
+//! Double-buffered event queues.
+//!
+//! Provides [`Events`], the channel through which subsystems publish
+//! per-frame events (input, lifecycle, gameplay) for systems to read.
+
+/// A double-buffered queue of events of type `T`.
+///
+/// Events sent during a frame remain readable for that frame and the next,
+/// so systems that run before the sender in a frame still observe them.
+/// Call [`update`](Self::update) once per frame to drop events older than
+/// that.
+///
+/// # Example
+///
+/// ```
+/// use ecs::Events;
+///
+/// let mut events = Events::default();
+/// events.send("jump");
+/// assert_eq!(events.iter().collect::<Vec<_>>(), [&"jump"]);
+///
+/// events.update(); // still visible for one more frame
+/// assert_eq!(events.len(), 1);
+/// events.update();
+/// assert!(events.is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Events<T> {
+    previous: Vec<T>,
+    current: Vec<T>,
+}
+
+impl<T> Default for Events<T> {
+    fn default() -> Self {
+        Self {
+            previous: Vec::new(),
+            current: Vec::new(),
+        }
+    }
+}
+
+impl<T> Events<T> {
+    /// Publishes an event.
+    #[inline]
+    pub fn send(&mut self, event: T) {
+        self.current.push(event);
+    }
+
+    /// Publishes every event from `events`.
+    pub fn send_batch(&mut self, events: impl IntoIterator<Item = T>) {
+        self.current.extend(events);
+    }
+
+    /// Advances the queue by one frame, dropping events from two frames ago.
+    pub fn update(&mut self) {
+        std::mem::swap(&mut self.previous, &mut self.current);
+        self.current.clear();
+    }
+
+    /// Iterates over all readable events, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.previous.iter().chain(self.current.iter())
+    }
+
+    /// Iterates over events sent since the last [`update`](Self::update).
+    pub fn iter_current(&self) -> impl Iterator<Item = &T> {
+        self.current.iter()
+    }
+
+    /// Removes and returns all readable events, oldest first.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.previous.drain(..).chain(self.current.drain(..))
+    }
+
+    /// Returns the number of readable events.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.previous.len() + self.current.len()
+    }
+
+    /// Returns `true` if there are no readable events.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all events.
+    pub fn clear(&mut self) {
+        self.previous.clear();
+        self.current.clear();
+    }
+}
@@ -0,0 +1,64 @@
+//! Entity names and parent links.
+//!
+//! Entities form a tree through [`Parent`] components. Tools show the tree
+//! and label entities with their [`Name`]; despawning a parent with
+//! [`despawn_recursive`] removes its children too.
+
+use crate::world::{Entity, World};
+
+/// Human-readable label of an entity, shown by tools.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Name(pub String);
+
+impl Name {
+    /// Creates a name.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+}
+
+/// The entity an entity is attached under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Parent(pub Entity);
+
+/// Returns the children of `parent`, in entity order.
+#[must_use]
+pub fn children(world: &World, parent: Entity) -> Vec<Entity> {
+    let mut children: Vec<Entity> = world
+        .query::<Parent>()
+        .filter(|(_, link)| link.0 == parent)
+        .map(|(entity, _)| entity)
+        .collect();
+    children.sort_unstable();
+    children
+}
+
+/// Returns the entities without a living parent, in entity order.
+#[must_use]
+pub fn roots(world: &World) -> Vec<Entity> {
+    let mut roots: Vec<Entity> = world
+        .entities()
+        .filter(|&entity| {
+            world
+                .get::<Parent>(entity)
+                .is_none_or(|parent| !world.contains(parent.0))
+        })
+        .collect();
+    roots.sort_unstable();
+    roots
+}
+
+/// Despawns an entity and all its descendants, returning how many were
+/// despawned.
+pub fn despawn_recursive(world: &mut World, entity: Entity) -> usize {
+    let mut pending = vec![entity];
+    let mut count = 0;
+    while let Some(entity) = pending.pop() {
+        pending.extend(children(world, entity));
+        if world.despawn(entity) {
+            count += 1;
+        }
+    }
+    count
+}
@@ -0,0 +1,229 @@
+//! World inspector state for the dev UI.
+//!
+//! A [`WorldInspector`] holds everything an inspector panel presents: the
+//! entity list filtered by a search string and laid out as the [`Parent`]
+//! tree, the selected entity with the fields of its registered components,
+//! and the actions behind the panel's buttons. The overlay hosting the
+//! panel draws the rows and forwards edits; the inspector does not depend
+//! on a particular UI library.
+//!
+//! Components appear once registered in a [`TypeRegistry`]; floats, vectors,
+//! and colors are then editable live.
+
+use crate::hierarchy::{children, despawn_recursive, roots, Name, Parent};
+use crate::reflect::{FieldValue, TypeRegistry};
+use crate::world::{Entity, World};
+use std::collections::HashSet;
+
+/// One line of the entity list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityRow {
+    /// The entity.
+    pub entity: Entity,
+    /// Its [`Name`], or its id when unnamed.
+    pub label: String,
+    /// Nesting depth in the tree, 0 for roots.
+    pub depth: usize,
+    /// Whether the entity has children.
+    pub has_children: bool,
+    /// Whether its children are shown.
+    pub expanded: bool,
+    /// Whether the row is the selected entity.
+    pub selected: bool,
+}
+
+/// A registered component of the selected entity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentView {
+    /// Registered type name.
+    pub name: &'static str,
+    /// Field names and current values, in registration order.
+    pub fields: Vec<(&'static str, FieldValue)>,
+}
+
+/// State of the world inspector panel.
+///
+/// # Example
+///
+/// ```
+/// use ecs::inspector::WorldInspector;
+/// use ecs::World;
+///
+/// let mut world = World::default();
+/// let mut inspector = WorldInspector::default();
+/// let player = inspector.spawn(&mut world, None);
+/// inspector.spawn(&mut world, Some(player));
+///
+/// inspector.set_search("entity 1");
+/// let rows = inspector.rows(&world);
+/// // The match is shown under its parent.
+/// assert_eq!(rows.len(), 2);
+/// assert_eq!(rows[1].depth, 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WorldInspector {
+    /// Case-insensitive filter on entity labels.
+    search: String,
+    /// The selected entity.
+    selected: Option<Entity>,
+    /// Entities whose children are hidden.
+    collapsed: HashSet<Entity>,
+}
+
+impl WorldInspector {
+    /// Returns the search string.
+    #[must_use]
+    pub fn search(&self) -> &str {
+        &self.search
+    }
+
+    /// Filters the entity list to labels containing `search`, ignoring
+    /// case. Ancestors of matches stay visible so the tree keeps its shape.
+    pub fn set_search(&mut self, search: impl Into<String>) {
+        self.search = search.into();
+    }
+
+    /// Returns the selected entity.
+    #[must_use]
+    #[inline]
+    pub fn selected(&self) -> Option<Entity> {
+        self.selected
+    }
+
+    /// Selects an entity, or clears the selection.
+    pub fn select(&mut self, entity: Option<Entity>) {
+        self.selected = entity;
+    }
+
+    /// Shows or hides an entity's children, returning whether they are now
+    /// shown.
+    pub fn toggle_expanded(&mut self, entity: Entity) -> bool {
+        if self.collapsed.remove(&entity) {
+            true
+        } else {
+            self.collapsed.insert(entity);
+            false
+        }
+    }
+
+    /// Returns the label of an entity: its [`Name`], or its id.
+    #[must_use]
+    pub fn label(world: &World, entity: Entity) -> String {
+        world
+            .get::<Name>(entity)
+            .map_or_else(|| entity.to_string(), |name| name.0.clone())
+    }
+
+    /// Returns the visible rows of the entity list, depth first.
+    ///
+    /// While searching, collapsed entities are expanded so matches are
+    /// never hidden.
+    #[must_use]
+    pub fn rows(&self, world: &World) -> Vec<EntityRow> {
+        let search = self.search.to_lowercase();
+        let mut rows = Vec::new();
+        for root in roots(world) {
+            self.collect(world, root, 0, &search, &mut rows);
+        }
+        rows
+    }
+
+    /// Appends the rows of `entity`'s subtree, returning whether any
+    /// matched the search.
+    fn collect(
+        &self,
+        world: &World,
+        entity: Entity,
+        depth: usize,
+        search: &str,
+        rows: &mut Vec<EntityRow>,
+    ) -> bool {
+        let label = Self::label(world, entity);
+        let matched = search.is_empty() || label.to_lowercase().contains(search);
+        let kids = children(world, entity);
+        let expanded = !search.is_empty() || !self.collapsed.contains(&entity);
+
+        let index = rows.len();
+        rows.push(EntityRow {
+            entity,
+            label,
+            depth,
+            has_children: !kids.is_empty(),
+            expanded,
+            selected: self.selected == Some(entity),
+        });
+        let mut any_child = false;
+        if expanded {
+            for child in kids {
+                any_child |= self.collect(world, child, depth + 1, search, rows);
+            }
+        }
+        if !matched && !any_child {
+            rows.truncate(index);
+            return false;
+        }
+        true
+    }
+
+    /// Returns the registered components of the selected entity.
+    #[must_use]
+    pub fn components(&self, world: &World, registry: &TypeRegistry) -> Vec<ComponentView> {
+        let Some(entity) = self.selected.filter(|&entity| world.contains(entity)) else {
+            return Vec::new();
+        };
+        registry
+            .components_of(world, entity)
+            .into_iter()
+            .filter_map(|name| {
+                let fields = registry.values(world, entity, name)?;
+                Some(ComponentView { name, fields })
+            })
+            .collect()
+    }
+
+    /// Writes a field of the selected entity's component.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if nothing is selected or the registry rejects the
+    /// edit.
+    pub fn edit(
+        &self,
+        world: &mut World,
+        registry: &TypeRegistry,
+        component: &str,
+        field: &str,
+        value: FieldValue,
+    ) -> anyhow::Result<()> {
+        let entity = self
+            .selected
+            .ok_or_else(|| anyhow::anyhow!("no entity selected"))?;
+        registry.set(world, entity, component, field, value)
+    }
+
+    /// Spawns a named entity, under `parent` if given, and selects it.
+    pub fn spawn(&mut self, world: &mut World, parent: Option<Entity>) -> Entity {
+        let entity = world.spawn();
+        world.insert(entity, Name(entity.to_string()));
+        if let Some(parent) = parent {
+            world.insert(entity, Parent(parent));
+            self.collapsed.remove(&parent);
+        }
+        self.selected = Some(entity);
+        entity
+    }
+
+    /// Despawns an entity and its descendants, clearing the selection if
+    /// it was among them. Returns how many entities were despawned.
+    pub fn despawn(&mut self, world: &mut World, entity: Entity) -> usize {
+        let count = despawn_recursive(world, entity);
+        if self
+            .selected
+            .is_some_and(|selected| !world.contains(selected))
+        {
+            self.selected = None;
+        }
+        self.collapsed.retain(|&entity| world.contains(entity));
+        count
+    }
+}
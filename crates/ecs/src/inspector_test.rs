@@ -0,0 +1,137 @@
+//! Unit tests for the world inspector.
+
+use crate::hierarchy::{Name, Parent};
+use crate::inspector::{ComponentView, WorldInspector};
+use crate::reflect::{FieldValue, TypeRegistry};
+use crate::world::{Entity, World};
+
+struct Health(f32);
+
+/// Builds `level` with children `door` and `player`, and `player` with
+/// child `sword`.
+fn scene() -> (World, [Entity; 4]) {
+    let mut world = World::default();
+    let entities = ["level", "door", "player", "sword"].map(|name| {
+        let entity = world.spawn();
+        world.insert(entity, Name::new(name));
+        entity
+    });
+    let [level, door, player, sword] = entities;
+    world.insert(door, Parent(level));
+    world.insert(player, Parent(level));
+    world.insert(sword, Parent(player));
+    (world, entities)
+}
+
+fn labels(inspector: &WorldInspector, world: &World) -> Vec<(String, usize)> {
+    inspector
+        .rows(world)
+        .into_iter()
+        .map(|row| (row.label, row.depth))
+        .collect()
+}
+
+/// Verifies rows follow the hierarchy depth first and collapse on toggle.
+#[test]
+fn test_rows_follow_hierarchy() {
+    let (world, [_, _, player, _]) = scene();
+    let mut inspector = WorldInspector::default();
+    assert_eq!(
+        labels(&inspector, &world),
+        [
+            ("level".to_owned(), 0),
+            ("door".to_owned(), 1),
+            ("player".to_owned(), 1),
+            ("sword".to_owned(), 2),
+        ]
+    );
+
+    assert!(!inspector.toggle_expanded(player));
+    let rows = inspector.rows(&world);
+    assert_eq!(rows.len(), 3);
+    assert!(rows[2].has_children && !rows[2].expanded);
+    assert!(inspector.toggle_expanded(player));
+    assert_eq!(inspector.rows(&world).len(), 4);
+}
+
+/// Verifies searching keeps matches and their ancestors, even collapsed.
+#[test]
+fn test_search_keeps_ancestors() {
+    let (world, [level, ..]) = scene();
+    let mut inspector = WorldInspector::default();
+    inspector.toggle_expanded(level);
+    inspector.set_search("SWO");
+    assert_eq!(
+        labels(&inspector, &world),
+        [
+            ("level".to_owned(), 0),
+            ("player".to_owned(), 1),
+            ("sword".to_owned(), 2),
+        ]
+    );
+    inspector.set_search("nothing");
+    assert!(inspector.rows(&world).is_empty());
+}
+
+/// Verifies the selected entity's registered components are listed and
+/// editable.
+#[test]
+fn test_edit_selected_components() {
+    let (mut world, [_, _, player, _]) = scene();
+    world.insert(player, Health(80.0));
+    let mut registry = TypeRegistry::default();
+    registry
+        .register::<Health>("Health")
+        .float("value", |h| h.0, |h, v| h.0 = v);
+
+    let mut inspector = WorldInspector::default();
+    assert!(inspector.components(&world, &registry).is_empty());
+    assert!(inspector
+        .edit(
+            &mut world,
+            &registry,
+            "Health",
+            "value",
+            FieldValue::Float(1.0)
+        )
+        .is_err());
+
+    inspector.select(Some(player));
+    assert!(inspector.rows(&world)[2].selected);
+    assert_eq!(
+        inspector.components(&world, &registry),
+        [ComponentView {
+            name: "Health",
+            fields: vec![("value", FieldValue::Float(80.0))],
+        }]
+    );
+    inspector
+        .edit(
+            &mut world,
+            &registry,
+            "Health",
+            "value",
+            FieldValue::Float(25.0),
+        )
+        .unwrap();
+    assert!((world.get::<Health>(player).unwrap().0 - 25.0).abs() < f32::EPSILON);
+}
+
+/// Verifies spawning selects the new entity and despawning removes the
+/// subtree and clears a stale selection.
+#[test]
+fn test_spawn_and_despawn() {
+    let (mut world, [level, door, player, sword]) = scene();
+    let mut inspector = WorldInspector::default();
+
+    let torch = inspector.spawn(&mut world, Some(door));
+    assert_eq!(inspector.selected(), Some(torch));
+    assert_eq!(world.get::<Parent>(torch), Some(&Parent(door)));
+    assert_eq!(WorldInspector::label(&world, torch), torch.to_string());
+
+    inspector.select(Some(sword));
+    assert_eq!(inspector.despawn(&mut world, player), 2);
+    assert_eq!(inspector.selected(), None);
+    assert!(world.contains(level) && !world.contains(sword));
+    assert_eq!(inspector.rows(&world).len(), 3);
+}
@@ -9,6 +9,14 @@
 //! - Entity lifecycle management (creation, destruction, queries)
 //! - Component storage with cache-friendly memory layouts
 //! - System execution with automatic parallelization
+//! - Double-buffered [`Events`] queues for per-frame messages
+//! - A [`World`] of components and resources with change ticks
+//! - Entity names and parent links ([`Name`], [`Parent`])
+//! - Component registration for tools ([`TypeRegistry`]) and the world
+//!   inspector state behind the dev UI panel ([`WorldInspector`])
+//! - A [`SpatialIndex`] of entity bounds for box, ray, and nearest queries
+//! - [`EntityPool`]s recycling short-lived entities such as bullets and
+//!   particles
 //!
 //! # Example
 //!
@@ -25,5 +33,29 @@
 #![allow(clippy::module_name_repetitions)]
 
 pub mod ecs;
+pub mod event;
+pub mod hierarchy;
+pub mod inspector;
+#[cfg(test)]
+mod inspector_test;
+pub mod pool;
+#[cfg(test)]
+mod pool_test;
+pub mod reflect;
+#[cfg(test)]
+mod reflect_test;
+pub mod spatial;
+#[cfg(test)]
+mod spatial_test;
+pub mod world;
+#[cfg(test)]
+mod world_test;
 
 pub use ecs::RustgineEcs;
+pub use event::Events;
+pub use hierarchy::{Name, Parent};
+pub use inspector::WorldInspector;
+pub use pool::{Disabled, EntityPool};
+pub use reflect::{FieldValue, TypeRegistry};
+pub use spatial::{RayHit, SpatialIndex};
+pub use world::{Entity, Tick, World};
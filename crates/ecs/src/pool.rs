@@ -0,0 +1,180 @@
+//! Entity pools for short-lived gameplay objects.
+//!
+//! Bullets, particles, and pickups are spawned and despawned by the
+//! hundred every frame. Each despawn walks every component storage, and
+//! each spawn grows them again. An [`EntityPool`] keeps released entities
+//! alive instead, marked [`Disabled`], and hands them out again on the next
+//! [`acquire`](EntityPool::acquire), so steady churn neither allocates nor
+//! frees.
+//!
+//! Systems iterate the live objects with [`EntityPool::active`]. Systems
+//! that query a pooled component type across the whole world skip
+//! entities with a [`Disabled`] component.
+
+use crate::world::{Entity, World};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Marker component on pooled entities that are not in use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Disabled;
+
+/// Prepares a fresh or recycled entity for use.
+type Setup = Box<dyn Fn(&mut World, Entity) + Send + Sync>;
+
+/// A pool of recycled entities sharing a set of components.
+///
+/// # Example
+///
+/// ```
+/// use ecs::pool::{Disabled, EntityPool};
+/// use ecs::World;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Bullet {
+///     age: f32,
+/// }
+///
+/// let mut world = World::default();
+/// let mut bullets = EntityPool::new(|world, bullet| {
+///     world.insert(bullet, Bullet { age: 0.0 });
+/// });
+/// bullets.warm_up(&mut world, 64);
+///
+/// let bullet = bullets.acquire(&mut world);
+/// world.get_mut::<Bullet>(bullet).unwrap().age = 1.5;
+/// assert!(bullets.release(&mut world, bullet));
+/// assert!(world.get::<Disabled>(bullet).is_some());
+///
+/// // The same entity comes back, reset by the setup closure
+/// let again = bullets.acquire(&mut world);
+/// assert_eq!(world.get::<Bullet>(again), Some(&Bullet { age: 0.0 }));
+/// assert_eq!(bullets.spawned(), 64);
+/// ```
+pub struct EntityPool {
+    /// Initializes entities on every acquire and warm-up.
+    setup: Setup,
+    /// Disabled entities ready for reuse.
+    free: Vec<Entity>,
+    /// Entities in use.
+    active: Vec<Entity>,
+    /// Position of each active entity in `active`.
+    index: HashMap<Entity, usize>,
+    /// Entities spawned by the pool over its lifetime.
+    spawned: usize,
+}
+
+impl fmt::Debug for EntityPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EntityPool")
+            .field("active", &self.active.len())
+            .field("free", &self.free.len())
+            .field("spawned", &self.spawned)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EntityPool {
+    /// Creates an empty pool whose entities are initialized by `setup`.
+    ///
+    /// `setup` runs whenever an entity is handed out, whether freshly
+    /// spawned or recycled, so it should insert every component the object
+    /// starts with.
+    pub fn new(setup: impl Fn(&mut World, Entity) + Send + Sync + 'static) -> Self {
+        Self {
+            setup: Box::new(setup),
+            free: Vec::new(),
+            active: Vec::new(),
+            index: HashMap::new(),
+            spawned: 0,
+        }
+    }
+
+    /// Spawns disabled entities until at least `count` are free, so the
+    /// first frames of play do not spawn at all.
+    pub fn warm_up(&mut self, world: &mut World, count: usize) {
+        self.free.reserve(count.saturating_sub(self.free.len()));
+        while self.free.len() < count {
+            let entity = world.spawn();
+            (self.setup)(world, entity);
+            world.insert(entity, Disabled);
+            self.free.push(entity);
+            self.spawned += 1;
+        }
+        self.active.reserve(self.free.len());
+        self.index.reserve(self.free.len());
+    }
+
+    /// Returns an initialized entity, recycling a free one if possible.
+    pub fn acquire(&mut self, world: &mut World) -> Entity {
+        let entity = loop {
+            match self.free.pop() {
+                Some(entity) if world.remove::<Disabled>(entity).is_some() => break entity,
+                // Despawned by someone else while free
+                Some(_) => {}
+                None => {
+                    self.spawned += 1;
+                    break world.spawn();
+                }
+            }
+        };
+        (self.setup)(world, entity);
+        self.index.insert(entity, self.active.len());
+        self.active.push(entity);
+        entity
+    }
+
+    /// Disables an entity and returns it to the pool.
+    ///
+    /// Returns `false` if the entity is not active in this pool, e.g. when
+    /// released twice.
+    pub fn release(&mut self, world: &mut World, entity: Entity) -> bool {
+        let Some(position) = self.index.remove(&entity) else {
+            return false;
+        };
+        self.active.swap_remove(position);
+        if let Some(&moved) = self.active.get(position) {
+            self.index.insert(moved, position);
+        }
+        if world.insert(entity, Disabled) {
+            self.free.push(entity);
+        }
+        true
+    }
+
+    /// Returns the entities in use, in no particular order.
+    #[must_use]
+    #[inline]
+    pub fn active(&self) -> &[Entity] {
+        &self.active
+    }
+
+    /// Returns `true` if `entity` is in use from this pool.
+    #[must_use]
+    #[inline]
+    pub fn is_active(&self, entity: Entity) -> bool {
+        self.index.contains_key(&entity)
+    }
+
+    /// Returns the number of free entities ready for reuse.
+    #[must_use]
+    #[inline]
+    pub fn available(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Returns the number of entities the pool has spawned, a measure of
+    /// how well it is sized.
+    #[must_use]
+    #[inline]
+    pub fn spawned(&self) -> usize {
+        self.spawned
+    }
+
+    /// Despawns every free entity, shrinking the pool to its active set.
+    pub fn clear_free(&mut self, world: &mut World) {
+        for entity in self.free.drain(..) {
+            world.despawn(entity);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Unit tests for entity pools.
+
+use crate::pool::{Disabled, EntityPool};
+use crate::world::World;
+
+#[derive(Debug, PartialEq)]
+struct Particle {
+    age: f32,
+}
+
+fn particles() -> EntityPool {
+    EntityPool::new(|world, entity| {
+        world.insert(entity, Particle { age: 0.0 });
+    })
+}
+
+/// Verifies warm-up pre-spawns disabled entities that acquire reuses.
+#[test]
+fn warm_up_preallocates() {
+    let mut world = World::default();
+    let mut pool = particles();
+    pool.warm_up(&mut world, 8);
+    assert_eq!(pool.available(), 8);
+    assert_eq!(world.query::<Disabled>().count(), 8);
+
+    let acquired: Vec<_> = (0..8).map(|_| pool.acquire(&mut world)).collect();
+    assert_eq!(pool.spawned(), 8);
+    assert_eq!(world.len(), 8);
+    assert_eq!(world.query::<Disabled>().count(), 0);
+    assert_eq!(pool.active().len(), 8);
+    assert!(acquired.iter().all(|&entity| pool.is_active(entity)));
+
+    // Exhausted pools grow
+    pool.acquire(&mut world);
+    assert_eq!(pool.spawned(), 9);
+
+    // Warming up again only tops up the free list
+    pool.warm_up(&mut world, 2);
+    pool.warm_up(&mut world, 2);
+    assert_eq!(pool.spawned(), 11);
+}
+
+/// Verifies steady churn recycles entities instead of spawning.
+#[test]
+fn recycles_released_entities() {
+    let mut world = World::default();
+    let mut pool = particles();
+    for _ in 0..100 {
+        let entity = pool.acquire(&mut world);
+        assert_eq!(world.get::<Particle>(entity), Some(&Particle { age: 0.0 }));
+        world.get_mut::<Particle>(entity).unwrap().age = 3.0;
+        assert!(pool.release(&mut world, entity));
+        assert!(!pool.release(&mut world, entity));
+    }
+    assert_eq!(pool.spawned(), 1);
+    assert_eq!(world.len(), 1);
+}
+
+/// Verifies releases keep the active set consistent.
+#[test]
+fn release_keeps_active_set() {
+    let mut world = World::default();
+    let mut pool = particles();
+    let entities: Vec<_> = (0..5).map(|_| pool.acquire(&mut world)).collect();
+
+    assert!(pool.release(&mut world, entities[1]));
+    assert!(pool.release(&mut world, entities[4]));
+    let mut active = pool.active().to_vec();
+    active.sort_unstable();
+    assert_eq!(active, [entities[0], entities[2], entities[3]]);
+    for &entity in &active {
+        assert!(pool.release(&mut world, entity));
+    }
+    assert!(pool.active().is_empty());
+    assert_eq!(pool.available(), 5);
+}
+
+/// Verifies entities despawned elsewhere are not handed out.
+#[test]
+fn skips_despawned_entities() {
+    let mut world = World::default();
+    let mut pool = particles();
+    pool.warm_up(&mut world, 2);
+    let acquired = pool.acquire(&mut world);
+    let free = pool.acquire(&mut world);
+    pool.release(&mut world, free);
+    world.despawn(free);
+    world.despawn(acquired);
+
+    // Releasing a despawned entity does not put it back
+    assert!(pool.release(&mut world, acquired));
+    assert_eq!(pool.available(), 1);
+    let fresh = pool.acquire(&mut world);
+    assert!(world.contains(fresh));
+    assert_ne!(fresh, free);
+    assert_eq!(pool.spawned(), 3);
+
+    pool.release(&mut world, fresh);
+    pool.clear_free(&mut world);
+    assert!(world.is_empty());
+}
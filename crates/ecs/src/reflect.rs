@@ -0,0 +1,394 @@
+//! Registered component types whose fields tools can read and edit.
+//!
+//! The engine has no runtime reflection, so a component becomes visible to
+//! the inspector by registering it once with its editable fields. Each field
+//! is a float, a vector, or a color, read and written through plain
+//! functions:
+//!
+//! ```
+//! use ecs::reflect::{FieldValue, TypeRegistry};
+//! use ecs::World;
+//! use math::Color;
+//!
+//! #[derive(Default)]
+//! struct Light {
+//!     intensity: f32,
+//!     color: Color,
+//! }
+//!
+//! let mut registry = TypeRegistry::default();
+//! registry
+//!     .register::<Light>("Light")
+//!     .float("intensity", |l| l.intensity, |l, v| l.intensity = v)
+//!     .color("color", |l| l.color, |l, v| l.color = v);
+//!
+//! let mut world = World::default();
+//! let lamp = world.spawn();
+//! world.insert(lamp, Light::default());
+//! registry.set(&mut world, lamp, "Light", "intensity", FieldValue::Float(2.5))?;
+//! assert_eq!(world.get::<Light>(lamp).unwrap().intensity, 2.5);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use crate::world::{Entity, World};
+use math::Color;
+use std::any::TypeId;
+use std::collections::BTreeMap;
+
+/// Value of a registered field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldValue {
+    /// A scalar, edited with a drag box.
+    Float(f32),
+    /// A 3D vector, such as a position or scale.
+    Vec3([f32; 3]),
+    /// A color, edited with a color picker.
+    Color(Color),
+}
+
+impl FieldValue {
+    /// Returns the kind of value.
+    #[must_use]
+    pub fn kind(&self) -> FieldKind {
+        match self {
+            Self::Float(_) => FieldKind::Float,
+            Self::Vec3(_) => FieldKind::Vec3,
+            Self::Color(_) => FieldKind::Color,
+        }
+    }
+}
+
+/// Kind of a registered field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldKind {
+    /// See [`FieldValue::Float`].
+    Float,
+    /// See [`FieldValue::Vec3`].
+    Vec3,
+    /// See [`FieldValue::Color`].
+    Color,
+}
+
+/// Reads and writes one field of component `C`.
+enum Accessor<C> {
+    Float(fn(&C) -> f32, fn(&mut C, f32)),
+    Vec3(fn(&C) -> [f32; 3], fn(&mut C, [f32; 3])),
+    Color(fn(&C) -> Color, fn(&mut C, Color)),
+}
+
+impl<C> Accessor<C> {
+    fn get(&self, component: &C) -> FieldValue {
+        match self {
+            Self::Float(get, _) => FieldValue::Float(get(component)),
+            Self::Vec3(get, _) => FieldValue::Vec3(get(component)),
+            Self::Color(get, _) => FieldValue::Color(get(component)),
+        }
+    }
+
+    /// Writes `value`, returning `false` if it is the wrong kind.
+    fn set(&self, component: &mut C, value: FieldValue) -> bool {
+        match (self, value) {
+            (Self::Float(_, set), FieldValue::Float(v)) => set(component, v),
+            (Self::Vec3(_, set), FieldValue::Vec3(v)) => set(component, v),
+            (Self::Color(_, set), FieldValue::Color(v)) => set(component, v),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// Type-erased access to one registered component type.
+trait Reflect: Send + Sync {
+    /// Returns the component's field names, in registration order.
+    fn fields(&self) -> Vec<&'static str>;
+    /// Returns whether the entity has the component.
+    fn has(&self, world: &World, entity: Entity) -> bool;
+    /// Returns the entity's field values, if it has the component.
+    fn values(&self, world: &World, entity: Entity) -> Option<Vec<(&'static str, FieldValue)>>;
+    /// Writes one field.
+    fn set(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        field: &str,
+        value: FieldValue,
+    ) -> anyhow::Result<()>;
+    /// Adds a default component, if the type has a default.
+    fn insert_default(&self, world: &mut World, entity: Entity) -> bool;
+    /// Removes the component.
+    fn remove(&self, world: &mut World, entity: Entity) -> bool;
+}
+
+/// Registration of component `C`.
+struct Registration<C> {
+    /// Type name shown to users.
+    name: &'static str,
+    /// Fields in registration order.
+    fields: Vec<(&'static str, Accessor<C>)>,
+    /// Creates the component for "add component" buttons.
+    default: Option<fn() -> C>,
+}
+
+impl<C: Send + Sync + 'static> Reflect for Registration<C> {
+    fn fields(&self) -> Vec<&'static str> {
+        self.fields.iter().map(|(name, _)| *name).collect()
+    }
+
+    fn has(&self, world: &World, entity: Entity) -> bool {
+        world.get::<C>(entity).is_some()
+    }
+
+    fn values(&self, world: &World, entity: Entity) -> Option<Vec<(&'static str, FieldValue)>> {
+        let component = world.get::<C>(entity)?;
+        Some(
+            self.fields
+                .iter()
+                .map(|(name, accessor)| (*name, accessor.get(component)))
+                .collect(),
+        )
+    }
+
+    fn set(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        field: &str,
+        value: FieldValue,
+    ) -> anyhow::Result<()> {
+        let accessor = self
+            .fields
+            .iter()
+            .find(|(name, _)| *name == field)
+            .map(|(_, accessor)| accessor)
+            .ok_or_else(|| anyhow::anyhow!("{} has no field {field}", self.name))?;
+        let component = world
+            .get_mut::<C>(entity)
+            .ok_or_else(|| anyhow::anyhow!("{entity} has no {}", self.name))?;
+        if !accessor.set(component, value) {
+            anyhow::bail!("{}.{field} is not a {:?}", self.name, value.kind());
+        }
+        Ok(())
+    }
+
+    fn insert_default(&self, world: &mut World, entity: Entity) -> bool {
+        self.default
+            .is_some_and(|default| world.insert(entity, default()))
+    }
+
+    fn remove(&self, world: &mut World, entity: Entity) -> bool {
+        world.remove::<C>(entity).is_some()
+    }
+}
+
+/// Component types registered for inspection, by name.
+#[derive(Default)]
+pub struct TypeRegistry {
+    /// Registrations by type name.
+    types: BTreeMap<&'static str, Box<dyn Reflect>>,
+    /// Type name of each registered type.
+    names: BTreeMap<TypeId, &'static str>,
+}
+
+impl std::fmt::Debug for TypeRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.types.keys()).finish()
+    }
+}
+
+impl TypeRegistry {
+    /// Registers component `C` under `name`, replacing any earlier
+    /// registration of either, and returns a builder for its fields.
+    pub fn register<C: Send + Sync + 'static>(&mut self, name: &'static str) -> TypeBuilder<'_, C> {
+        if let Some(old) = self.names.insert(TypeId::of::<C>(), name) {
+            self.types.remove(old);
+        }
+        TypeBuilder {
+            registry: self,
+            registration: Some(Registration {
+                name,
+                fields: Vec::new(),
+                default: None,
+            }),
+        }
+    }
+
+    /// Returns the registered type names in order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.types.keys().copied()
+    }
+
+    /// Returns the name `C` is registered under.
+    #[must_use]
+    pub fn name_of<C: 'static>(&self) -> Option<&'static str> {
+        self.names.get(&TypeId::of::<C>()).copied()
+    }
+
+    /// Returns the number of registered types.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    /// Returns `true` if no types are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// Returns the field names of a registered type.
+    #[must_use]
+    pub fn fields(&self, type_name: &str) -> Option<Vec<&'static str>> {
+        self.types.get(type_name).map(|reflect| reflect.fields())
+    }
+
+    /// Returns the names of the registered components an entity has.
+    #[must_use]
+    pub fn components_of(&self, world: &World, entity: Entity) -> Vec<&'static str> {
+        self.types
+            .iter()
+            .filter(|(_, reflect)| reflect.has(world, entity))
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    /// Returns the field values of an entity's component.
+    #[must_use]
+    pub fn values(
+        &self,
+        world: &World,
+        entity: Entity,
+        type_name: &str,
+    ) -> Option<Vec<(&'static str, FieldValue)>> {
+        self.types.get(type_name)?.values(world, entity)
+    }
+
+    /// Returns one field value of an entity's component.
+    #[must_use]
+    pub fn get(
+        &self,
+        world: &World,
+        entity: Entity,
+        type_name: &str,
+        field: &str,
+    ) -> Option<FieldValue> {
+        self.values(world, entity, type_name)?
+            .into_iter()
+            .find(|(name, _)| *name == field)
+            .map(|(_, value)| value)
+    }
+
+    /// Writes one field of an entity's component.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type or field is unknown, the entity lacks
+    /// the component, or `value` is the wrong kind for the field.
+    pub fn set(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        type_name: &str,
+        field: &str,
+        value: FieldValue,
+    ) -> anyhow::Result<()> {
+        self.types
+            .get(type_name)
+            .ok_or_else(|| anyhow::anyhow!("unregistered component type {type_name}"))?
+            .set(world, entity, field, value)
+    }
+
+    /// Adds a default component to an entity, returning `false` if the type
+    /// is unknown, has no default, or the entity does not exist.
+    pub fn insert_default(&self, world: &mut World, entity: Entity, type_name: &str) -> bool {
+        self.types
+            .get(type_name)
+            .is_some_and(|reflect| reflect.insert_default(world, entity))
+    }
+
+    /// Removes a component from an entity, returning whether it had one.
+    pub fn remove(&self, world: &mut World, entity: Entity, type_name: &str) -> bool {
+        self.types
+            .get(type_name)
+            .is_some_and(|reflect| reflect.remove(world, entity))
+    }
+}
+
+/// Adds fields to a type being registered; the registration completes when
+/// the builder is dropped.
+pub struct TypeBuilder<'a, C: Send + Sync + 'static> {
+    /// Registry receiving the type.
+    registry: &'a mut TypeRegistry,
+    /// The registration, taken on drop.
+    registration: Option<Registration<C>>,
+}
+
+impl<C: Send + Sync + 'static> TypeBuilder<'_, C> {
+    fn field(&mut self, name: &'static str, accessor: Accessor<C>) -> &mut Self {
+        if let Some(registration) = &mut self.registration {
+            registration
+                .fields
+                .retain(|(existing, _)| *existing != name);
+            registration.fields.push((name, accessor));
+        }
+        self
+    }
+
+    /// Adds a float field.
+    pub fn float(
+        &mut self,
+        name: &'static str,
+        get: fn(&C) -> f32,
+        set: fn(&mut C, f32),
+    ) -> &mut Self {
+        self.field(name, Accessor::Float(get, set))
+    }
+
+    /// Adds a 3D vector field.
+    pub fn vec3(
+        &mut self,
+        name: &'static str,
+        get: fn(&C) -> [f32; 3],
+        set: fn(&mut C, [f32; 3]),
+    ) -> &mut Self {
+        self.field(name, Accessor::Vec3(get, set))
+    }
+
+    /// Adds a color field.
+    pub fn color(
+        &mut self,
+        name: &'static str,
+        get: fn(&C) -> Color,
+        set: fn(&mut C, Color),
+    ) -> &mut Self {
+        self.field(name, Accessor::Color(get, set))
+    }
+
+    /// Lets tools add the component with `C::default()`.
+    pub fn with_default(&mut self) -> &mut Self
+    where
+        C: Default,
+    {
+        if let Some(registration) = &mut self.registration {
+            registration.default = Some(C::default);
+        }
+        self
+    }
+}
+
+impl<C: Send + Sync + 'static> Drop for TypeBuilder<'_, C> {
+    fn drop(&mut self) {
+        if let Some(registration) = self.registration.take() {
+            self.registry
+                .types
+                .insert(registration.name, Box::new(registration));
+        }
+    }
+}
+
+impl<C: Send + Sync + 'static> std::fmt::Debug for TypeBuilder<'_, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TypeBuilder")
+            .field("name", &self.registration.as_ref().map(|r| r.name))
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,123 @@
+//! Unit tests for the component type registry.
+
+use crate::reflect::{FieldValue, TypeRegistry};
+use crate::world::World;
+use math::Color;
+
+#[derive(Debug, Default, PartialEq)]
+struct Light {
+    intensity: f32,
+    position: [f32; 3],
+    color: Color,
+}
+
+fn registry() -> TypeRegistry {
+    let mut registry = TypeRegistry::default();
+    registry
+        .register::<Light>("Light")
+        .float("intensity", |l| l.intensity, |l, v| l.intensity = v)
+        .vec3("position", |l| l.position, |l, v| l.position = v)
+        .color("color", |l| l.color, |l, v| l.color = v)
+        .with_default();
+    registry
+}
+
+/// Verifies registered fields are listed in order and read from the world.
+#[test]
+fn test_register_and_read_fields() {
+    let registry = registry();
+    assert_eq!(registry.names().collect::<Vec<_>>(), ["Light"]);
+    assert_eq!(registry.name_of::<Light>(), Some("Light"));
+    assert_eq!(
+        registry.fields("Light"),
+        Some(vec!["intensity", "position", "color"])
+    );
+
+    let mut world = World::default();
+    let lamp = world.spawn();
+    assert!(registry.components_of(&world, lamp).is_empty());
+    world.insert(
+        lamp,
+        Light {
+            intensity: 2.0,
+            ..Light::default()
+        },
+    );
+    assert_eq!(registry.components_of(&world, lamp), ["Light"]);
+    assert_eq!(
+        registry.get(&world, lamp, "Light", "intensity"),
+        Some(FieldValue::Float(2.0))
+    );
+}
+
+/// Verifies edits write through and reject unknown names and wrong kinds.
+#[test]
+fn test_set_validates_edits() {
+    let registry = registry();
+    let mut world = World::default();
+    let lamp = world.spawn();
+    let bare = world.spawn();
+    assert!(registry.insert_default(&mut world, lamp, "Light"));
+
+    registry
+        .set(
+            &mut world,
+            lamp,
+            "Light",
+            "color",
+            FieldValue::Color(Color::linear_rgb(1.0, 0.5, 0.0)),
+        )
+        .unwrap();
+    registry
+        .set(
+            &mut world,
+            lamp,
+            "Light",
+            "position",
+            FieldValue::Vec3([0.0, 3.0, 0.0]),
+        )
+        .unwrap();
+    let light = world.get::<Light>(lamp).unwrap();
+    assert_eq!(
+        light.color.to_array().map(f32::to_bits),
+        [1.0, 0.5, 0.0, 1.0].map(f32::to_bits)
+    );
+    assert_eq!(
+        light.position.map(f32::to_bits),
+        [0.0, 3.0, 0.0].map(f32::to_bits)
+    );
+
+    let float = FieldValue::Float(1.0);
+    assert!(registry
+        .set(&mut world, lamp, "Light", "color", float)
+        .is_err());
+    assert!(registry
+        .set(&mut world, lamp, "Light", "range", float)
+        .is_err());
+    assert!(registry
+        .set(&mut world, lamp, "Camera", "fov", float)
+        .is_err());
+    assert!(registry
+        .set(&mut world, bare, "Light", "intensity", float)
+        .is_err());
+
+    assert!(registry.remove(&mut world, lamp, "Light"));
+    assert!(!registry.remove(&mut world, lamp, "Light"));
+}
+
+/// Verifies registering a type again replaces its earlier registration.
+#[test]
+fn test_reregister_replaces() {
+    let mut registry = registry();
+    registry.register::<Light>("PointLight").float(
+        "intensity",
+        |l| l.intensity,
+        |l, v| l.intensity = v,
+    );
+    assert_eq!(registry.names().collect::<Vec<_>>(), ["PointLight"]);
+    assert_eq!(registry.fields("PointLight"), Some(vec!["intensity"]));
+
+    let mut world = World::default();
+    let lamp = world.spawn();
+    assert!(!registry.insert_default(&mut world, lamp, "PointLight"));
+}
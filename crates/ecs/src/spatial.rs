@@ -0,0 +1,475 @@
+//! Spatial index over entity bounds.
+//!
+//! A [`SpatialIndex`] answers "what is near here" for every system that
+//! asks: render culling, audio occlusion, AI sensing, and gameplay
+//! proximity checks all query the same index instead of each scanning every
+//! entity.
+//!
+//! The index is a dynamic bounding volume hierarchy. Each entity's box is
+//! stored enlarged by a margin, so an entity that moves a little stays in
+//! its leaf and only entities that leave their enlarged box are reinserted.
+//! [`SpatialIndex::sync`] keeps the index in step with a component using the
+//! world's change ticks, touching only entities whose component changed.
+
+use crate::world::{Entity, Tick, World};
+use math::{Aabb, Ray, Vec3};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// An entity hit by [`SpatialIndex::query_ray`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    /// The entity hit.
+    pub entity: Entity,
+    /// Distance along the ray to where it enters the entity's box.
+    pub distance: f32,
+}
+
+/// Marks the absence of a node.
+const NONE: usize = usize::MAX;
+
+/// A node of the tree.
+#[derive(Debug, Clone)]
+struct Node {
+    /// Box holding everything below this node; enlarged for leaves.
+    aabb: Aabb,
+    /// Parent node, or [`NONE`] for the root.
+    parent: usize,
+    /// What the node holds.
+    kind: NodeKind,
+}
+
+#[derive(Debug, Clone)]
+enum NodeKind {
+    /// An entity with its exact box.
+    Leaf { entity: Entity, bounds: Aabb },
+    /// Two child nodes.
+    Branch { children: [usize; 2] },
+    /// An unused slot.
+    Free,
+}
+
+/// Dynamic bounding volume hierarchy over entity boxes.
+///
+/// # Example
+///
+/// ```
+/// use ecs::{SpatialIndex, World};
+/// use math::{Aabb, Ray, Vec3};
+///
+/// let mut world = World::default();
+/// let crate_box = world.spawn();
+/// world.insert(
+///     crate_box,
+///     Aabb::from_center_half_extents(Vec3::new(4.0, 0.0, 0.0), Vec3::splat(0.5)),
+/// );
+///
+/// let mut index = SpatialIndex::default();
+/// index.sync::<Aabb>(&world, |bounds| *bounds);
+///
+/// let hits = index.query_ray(&Ray::new(Vec3::ZERO, Vec3::X), 10.0);
+/// assert_eq!(hits[0].entity, crate_box);
+/// assert_eq!(index.k_nearest(Vec3::ZERO, 1)[0].0, crate_box);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpatialIndex {
+    /// Node slots, including free ones.
+    nodes: Vec<Node>,
+    /// Free node slots.
+    free: Vec<usize>,
+    /// Root node, or [`NONE`] when empty.
+    root: usize,
+    /// Leaf of each entity.
+    leaves: HashMap<Entity, usize>,
+    /// How far leaf boxes are enlarged.
+    margin: f32,
+    /// World tick of the last [`sync`](Self::sync).
+    synced: Option<Tick>,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::with_margin(0.1)
+    }
+}
+
+impl SpatialIndex {
+    /// Creates an index that enlarges each box by `margin` on every side.
+    ///
+    /// Larger margins reinsert moving entities less often but make queries
+    /// visit more of the tree.
+    #[must_use]
+    pub fn with_margin(margin: f32) -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: NONE,
+            leaves: HashMap::new(),
+            margin: margin.max(0.0),
+            synced: None,
+        }
+    }
+
+    /// Returns the number of entities indexed.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Returns whether no entities are indexed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Returns whether `entity` is indexed.
+    #[must_use]
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.leaves.contains_key(&entity)
+    }
+
+    /// Returns the box `entity` is indexed with.
+    #[must_use]
+    pub fn get(&self, entity: Entity) -> Option<Aabb> {
+        match self.nodes[*self.leaves.get(&entity)?].kind {
+            NodeKind::Leaf { bounds, .. } => Some(bounds),
+            _ => None,
+        }
+    }
+
+    /// Removes every entity.
+    pub fn clear(&mut self) {
+        *self = Self::with_margin(self.margin);
+    }
+
+    /// Indexes `entity` with `bounds`, or moves it there if already indexed.
+    pub fn insert(&mut self, entity: Entity, bounds: Aabb) {
+        if let Some(&leaf) = self.leaves.get(&entity) {
+            if self.nodes[leaf].aabb.contains_aabb(&bounds) {
+                // Still inside its enlarged box; the tree needs no change.
+                self.nodes[leaf].kind = NodeKind::Leaf { entity, bounds };
+                return;
+            }
+            self.detach(leaf);
+            self.nodes[leaf].aabb = bounds.expanded(self.margin);
+            self.nodes[leaf].kind = NodeKind::Leaf { entity, bounds };
+            self.attach(leaf);
+            return;
+        }
+        let leaf = self.allocate(Node {
+            aabb: bounds.expanded(self.margin),
+            parent: NONE,
+            kind: NodeKind::Leaf { entity, bounds },
+        });
+        self.leaves.insert(entity, leaf);
+        self.attach(leaf);
+    }
+
+    /// Removes `entity`, returning whether it was indexed.
+    pub fn remove(&mut self, entity: Entity) -> bool {
+        let Some(leaf) = self.leaves.remove(&entity) else {
+            return false;
+        };
+        self.detach(leaf);
+        self.release(leaf);
+        true
+    }
+
+    /// Brings the index in step with every `T` in `world`, boxed by `bounds`.
+    ///
+    /// Entities whose `T` changed since the last sync are moved, entities
+    /// that gained one are added, and entities that lost it or were
+    /// despawned are removed. Sync each index from one component type only;
+    /// storing an [`Aabb`] component and passing `|bounds| *bounds` is the
+    /// simplest setup.
+    pub fn sync<T: Send + Sync + 'static>(
+        &mut self,
+        world: &World,
+        mut bounds: impl FnMut(&T) -> Aabb,
+    ) {
+        let since = self.synced;
+        for (entity, component) in world.query::<T>() {
+            let changed = since.is_none_or(|since| world.is_changed_since::<T>(entity, since));
+            if changed || !self.contains(entity) {
+                self.insert(entity, bounds(component));
+            }
+        }
+        let stale: Vec<Entity> = self
+            .leaves
+            .keys()
+            .filter(|&&entity| world.get::<T>(entity).is_none())
+            .copied()
+            .collect();
+        for entity in stale {
+            self.remove(entity);
+        }
+        self.synced = Some(world.change_tick());
+    }
+
+    /// Returns the entities whose boxes overlap `area`.
+    #[must_use]
+    pub fn query_aabb(&self, area: &Aabb) -> Vec<Entity> {
+        let mut found = Vec::new();
+        let mut stack = vec![self.root];
+        while let Some(index) = stack.pop() {
+            let Some(node) = self.nodes.get(index) else {
+                continue;
+            };
+            if !node.aabb.intersects(area) {
+                continue;
+            }
+            match node.kind {
+                NodeKind::Leaf { entity, bounds } if bounds.intersects(area) => found.push(entity),
+                NodeKind::Branch { children } => stack.extend(children),
+                _ => {}
+            }
+        }
+        found
+    }
+
+    /// Returns the entities whose boxes `ray` enters within `max_distance`,
+    /// nearest first.
+    #[must_use]
+    pub fn query_ray(&self, ray: &Ray, max_distance: f32) -> Vec<RayHit> {
+        if ray.direction == Vec3::ZERO {
+            return Vec::new();
+        }
+        let within = |aabb: &Aabb| {
+            ray.intersect_aabb(aabb)
+                .filter(|&distance| distance <= max_distance)
+        };
+        let mut hits = Vec::new();
+        let mut stack = vec![self.root];
+        while let Some(index) = stack.pop() {
+            let Some(node) = self.nodes.get(index) else {
+                continue;
+            };
+            if within(&node.aabb).is_none() {
+                continue;
+            }
+            match node.kind {
+                NodeKind::Leaf { entity, bounds } => {
+                    if let Some(distance) = within(&bounds) {
+                        hits.push(RayHit { entity, distance });
+                    }
+                }
+                NodeKind::Branch { children } => stack.extend(children),
+                NodeKind::Free => {}
+            }
+        }
+        hits.sort_by(|a, b| {
+            a.distance
+                .total_cmp(&b.distance)
+                .then(a.entity.cmp(&b.entity))
+        });
+        hits
+    }
+
+    /// Returns up to `k` entities nearest to `point` with their distances,
+    /// nearest first. Distances are to the entities' boxes, 0 inside them.
+    #[must_use]
+    pub fn k_nearest(&self, point: Vec3, k: usize) -> Vec<(Entity, f32)> {
+        let mut nearest = Vec::with_capacity(k.min(self.len()));
+        let mut queue = BinaryHeap::new();
+        if self.root != NONE {
+            queue.push(Candidate {
+                distance: self.nodes[self.root].aabb.distance_to(point),
+                node: self.root,
+                exact: false,
+            });
+        }
+        // Best first: every box holds the boxes below it, so no unvisited
+        // entity is nearer than the front of the queue.
+        while let Some(candidate) = queue.pop() {
+            if nearest.len() == k {
+                break;
+            }
+            match self.nodes[candidate.node].kind {
+                NodeKind::Leaf { entity, .. } if candidate.exact => {
+                    nearest.push((entity, candidate.distance));
+                }
+                NodeKind::Leaf { bounds, .. } => queue.push(Candidate {
+                    distance: bounds.distance_to(point),
+                    exact: true,
+                    ..candidate
+                }),
+                NodeKind::Branch { children } => {
+                    queue.extend(children.map(|child| Candidate {
+                        distance: self.nodes[child].aabb.distance_to(point),
+                        node: child,
+                        exact: false,
+                    }));
+                }
+                NodeKind::Free => {}
+            }
+        }
+        nearest
+    }
+
+    fn allocate(&mut self, node: Node) -> usize {
+        if let Some(index) = self.free.pop() {
+            self.nodes[index] = node;
+            index
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        }
+    }
+
+    fn release(&mut self, index: usize) {
+        self.nodes[index].kind = NodeKind::Free;
+        self.free.push(index);
+    }
+
+    fn children(&self, index: usize) -> Option<[usize; 2]> {
+        match self.nodes[index].kind {
+            NodeKind::Branch { children } => Some(children),
+            _ => None,
+        }
+    }
+
+    /// Links a detached leaf into the tree beside the sibling that grows
+    /// the tree's surface area least.
+    fn attach(&mut self, leaf: usize) {
+        if self.root == NONE {
+            self.root = leaf;
+            self.nodes[leaf].parent = NONE;
+            return;
+        }
+        let aabb = self.nodes[leaf].aabb;
+        let mut sibling = self.root;
+        while let Some(children) = self.children(sibling) {
+            let area = half_area(&self.nodes[sibling].aabb);
+            let combined = half_area(&self.nodes[sibling].aabb.union(&aabb));
+            // Cost of pairing with this node, and of the growth every
+            // ancestor pays if the leaf goes further down.
+            let here = 2.0 * combined;
+            let inherited = 2.0 * (combined - area);
+            let cost = |child: usize| {
+                let node = &self.nodes[child];
+                let grown = half_area(&node.aabb.union(&aabb));
+                match node.kind {
+                    NodeKind::Leaf { .. } => grown + inherited,
+                    _ => grown - half_area(&node.aabb) + inherited,
+                }
+            };
+            let [left, right] = children.map(cost);
+            if here < left && here < right {
+                break;
+            }
+            sibling = if left <= right {
+                children[0]
+            } else {
+                children[1]
+            };
+        }
+
+        let old_parent = self.nodes[sibling].parent;
+        let parent = self.allocate(Node {
+            aabb: self.nodes[sibling].aabb.union(&aabb),
+            parent: old_parent,
+            kind: NodeKind::Branch {
+                children: [sibling, leaf],
+            },
+        });
+        self.nodes[sibling].parent = parent;
+        self.nodes[leaf].parent = parent;
+        if old_parent == NONE {
+            self.root = parent;
+        } else {
+            self.replace_child(old_parent, sibling, parent);
+        }
+        self.refit(old_parent);
+    }
+
+    /// Unlinks a leaf from the tree, keeping its slot.
+    fn detach(&mut self, leaf: usize) {
+        if leaf == self.root {
+            self.root = NONE;
+            return;
+        }
+        let parent = self.nodes[leaf].parent;
+        let grandparent = self.nodes[parent].parent;
+        let sibling = match self.children(parent) {
+            Some([a, b]) => {
+                if a == leaf {
+                    b
+                } else {
+                    a
+                }
+            }
+            None => unreachable!("leaf parent is a branch"),
+        };
+        self.nodes[sibling].parent = grandparent;
+        if grandparent == NONE {
+            self.root = sibling;
+        } else {
+            self.replace_child(grandparent, parent, sibling);
+        }
+        self.release(parent);
+        self.refit(grandparent);
+    }
+
+    fn replace_child(&mut self, parent: usize, old: usize, new: usize) {
+        if let NodeKind::Branch { children } = &mut self.nodes[parent].kind {
+            for child in children {
+                if *child == old {
+                    *child = new;
+                }
+            }
+        }
+    }
+
+    /// Recomputes the boxes of `index` and its ancestors.
+    fn refit(&mut self, mut index: usize) {
+        while index != NONE {
+            if let Some([a, b]) = self.children(index) {
+                self.nodes[index].aabb = self.nodes[a].aabb.union(&self.nodes[b].aabb);
+            }
+            index = self.nodes[index].parent;
+        }
+    }
+}
+
+/// Returns half the surface area of `aabb`, the cost the tree minimizes.
+fn half_area(aabb: &Aabb) -> f32 {
+    let size = aabb.max - aabb.min;
+    size.x * size.y + size.y * size.z + size.z * size.x
+}
+
+/// A node waiting in [`SpatialIndex::k_nearest`]'s queue, nearest first.
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    /// Distance from the query point to the node's box.
+    distance: f32,
+    /// The node.
+    node: usize,
+    /// Whether `distance` is to a leaf's exact box.
+    exact: bool,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, so the max-heap pops the nearest. Exact leaves go first
+        // at equal distance, settling ties before expanding more nodes.
+        other
+            .distance
+            .total_cmp(&self.distance)
+            .then(self.exact.cmp(&other.exact))
+            .then(other.node.cmp(&self.node))
+    }
+}
@@ -0,0 +1,194 @@
+//! Unit tests for the spatial index.
+
+use crate::spatial::SpatialIndex;
+use crate::world::{Entity, World};
+use math::{Aabb, Ray, Vec3};
+
+/// Returns `count` scattered unit-ish boxes from a fixed seed.
+fn scattered(count: u64) -> Vec<(Entity, Aabb)> {
+    let mut state = 0x2545_f491_u32;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        #[allow(clippy::cast_precision_loss)]
+        let unit = (state % 10_000) as f32 / 10_000.0;
+        unit
+    };
+    (0..count)
+        .map(|id| {
+            let center = Vec3::new(next() * 100.0, next() * 20.0, next() * 100.0);
+            let half = Vec3::new(0.2 + next(), 0.2 + next(), 0.2 + next());
+            (Entity(id), Aabb::from_center_half_extents(center, half))
+        })
+        .collect()
+}
+
+fn index_of(boxes: &[(Entity, Aabb)]) -> SpatialIndex {
+    let mut index = SpatialIndex::default();
+    for &(entity, bounds) in boxes {
+        index.insert(entity, bounds);
+    }
+    index
+}
+
+fn sorted(mut entities: Vec<Entity>) -> Vec<Entity> {
+    entities.sort_unstable();
+    entities
+}
+
+/// Verifies box queries find exactly the overlapping entities.
+#[test]
+fn test_query_aabb_matches_brute_force() {
+    let boxes = scattered(500);
+    let index = index_of(&boxes);
+    assert_eq!(index.len(), 500);
+
+    for area in [
+        Aabb::new(Vec3::new(10.0, 0.0, 10.0), Vec3::new(30.0, 20.0, 30.0)),
+        Aabb::from_center_half_extents(Vec3::new(50.0, 10.0, 50.0), Vec3::splat(5.0)),
+        Aabb::new(Vec3::splat(-10.0), Vec3::splat(-5.0)),
+    ] {
+        let expected: Vec<Entity> = boxes
+            .iter()
+            .filter(|(_, bounds)| bounds.intersects(&area))
+            .map(|&(entity, _)| entity)
+            .collect();
+        assert_eq!(sorted(index.query_aabb(&area)), sorted(expected));
+    }
+}
+
+/// Verifies ray queries return hits nearest first and stop at the maximum
+/// distance.
+#[test]
+fn test_query_ray_sorts_hits() {
+    let mut index = SpatialIndex::default();
+    index.insert(
+        Entity(1),
+        Aabb::from_center_half_extents(Vec3::new(8.0, 0.0, 0.0), Vec3::ONE),
+    );
+    index.insert(
+        Entity(2),
+        Aabb::from_center_half_extents(Vec3::new(3.0, 0.0, 0.0), Vec3::ONE),
+    );
+    index.insert(
+        Entity(3),
+        Aabb::from_center_half_extents(Vec3::new(3.0, 5.0, 0.0), Vec3::ONE),
+    );
+    index.insert(
+        Entity(4),
+        Aabb::from_center_half_extents(Vec3::new(20.0, 0.0, 0.0), Vec3::ONE),
+    );
+
+    let hits = index.query_ray(&Ray::new(Vec3::ZERO, Vec3::new(2.0, 0.0, 0.0)), 10.0);
+    let entities: Vec<Entity> = hits.iter().map(|hit| hit.entity).collect();
+    assert_eq!(entities, [Entity(2), Entity(1)]);
+    assert!((hits[0].distance - 2.0).abs() < 1e-5);
+    assert!((hits[1].distance - 7.0).abs() < 1e-5);
+
+    // A ray starting inside a box hits it at distance 0.
+    let inside = index.query_ray(&Ray::new(Vec3::new(3.0, 5.0, 0.0), Vec3::Z), 1.0);
+    assert_eq!(inside.len(), 1);
+    assert!(inside[0].distance.abs() < 1e-6);
+}
+
+/// Verifies nearest-neighbor queries match sorting every box by distance.
+#[test]
+fn test_k_nearest_matches_brute_force() {
+    let boxes = scattered(300);
+    let index = index_of(&boxes);
+    let point = Vec3::new(40.0, 5.0, 60.0);
+
+    let mut expected: Vec<(Entity, f32)> = boxes
+        .iter()
+        .map(|&(entity, bounds)| (entity, bounds.distance_to(point)))
+        .collect();
+    expected.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let nearest = index.k_nearest(point, 10);
+    assert_eq!(nearest.len(), 10);
+    for (found, expected) in nearest.iter().zip(&expected) {
+        assert!((found.1 - expected.1).abs() < 1e-5);
+    }
+    assert_eq!(index.k_nearest(point, 1000).len(), 300);
+    assert!(SpatialIndex::default().k_nearest(point, 3).is_empty());
+}
+
+/// Verifies moving and removing entities keeps queries correct.
+#[test]
+fn test_insert_moves_and_remove() {
+    let boxes = scattered(200);
+    let mut index = index_of(&boxes);
+
+    // Small moves stay inside the enlarged box; large ones reinsert.
+    let nudged = Aabb::from_center_half_extents(Vec3::new(0.05, 0.0, 0.0), Vec3::splat(0.1));
+    index.insert(
+        Entity(0),
+        Aabb::from_center_half_extents(Vec3::ZERO, Vec3::splat(0.1)),
+    );
+    index.insert(Entity(0), nudged);
+    assert_eq!(index.get(Entity(0)), Some(nudged));
+    index.insert(
+        Entity(1),
+        Aabb::from_center_half_extents(Vec3::splat(500.0), Vec3::ONE),
+    );
+    assert_eq!(
+        index.query_aabb(&Aabb::from_center_half_extents(
+            Vec3::splat(500.0),
+            Vec3::splat(2.0)
+        )),
+        [Entity(1)]
+    );
+
+    for id in 2..100 {
+        assert!(index.remove(Entity(id)));
+    }
+    assert!(!index.remove(Entity(2)));
+    assert_eq!(index.len(), 102);
+    let everything = Aabb::new(Vec3::splat(-1000.0), Vec3::splat(1000.0));
+    assert_eq!(index.query_aabb(&everything).len(), 102);
+
+    index.clear();
+    assert!(index.is_empty());
+    assert!(index.query_aabb(&everything).is_empty());
+}
+
+/// Verifies syncing follows added, changed, removed, and despawned
+/// components.
+#[test]
+fn test_sync_tracks_world_changes() {
+    let mut world = World::default();
+    let a = world.spawn();
+    let b = world.spawn();
+    world.insert(a, Aabb::from_center_half_extents(Vec3::ZERO, Vec3::ONE));
+    world.insert(
+        b,
+        Aabb::from_center_half_extents(Vec3::new(10.0, 0.0, 0.0), Vec3::ONE),
+    );
+
+    let mut index = SpatialIndex::default();
+    index.sync::<Aabb>(&world, |bounds| *bounds);
+    assert_eq!(index.len(), 2);
+
+    *world.get_mut::<Aabb>(a).unwrap() =
+        Aabb::from_center_half_extents(Vec3::new(50.0, 0.0, 0.0), Vec3::ONE);
+    let c = world.spawn();
+    world.insert(
+        c,
+        Aabb::from_center_half_extents(Vec3::new(-10.0, 0.0, 0.0), Vec3::ONE),
+    );
+    world.despawn(b);
+    index.sync::<Aabb>(&world, |bounds| *bounds);
+
+    assert_eq!(index.len(), 2);
+    assert!(!index.contains(b));
+    assert_eq!(index.k_nearest(Vec3::new(49.0, 0.0, 0.0), 1)[0].0, a);
+    assert_eq!(index.k_nearest(Vec3::new(-9.0, 0.0, 0.0), 1)[0].0, c);
+
+    world.remove::<Aabb>(c);
+    index.sync::<Aabb>(&world, |bounds| *bounds);
+    assert_eq!(
+        index.query_aabb(&Aabb::new(Vec3::splat(-100.0), Vec3::splat(100.0))),
+        [a]
+    );
+}
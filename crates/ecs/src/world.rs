@@ -0,0 +1,308 @@
+//! Entities, components, and resources with change detection.
+//!
+//! A [`World`] stores components per entity and resources (components that
+//! exist once, like the score or the game clock), keyed by type. Every write
+//! stamps the value with a new [`Tick`], so a consumer that remembers the
+//! tick it last looked at can tell cheaply whether a value changed since,
+//! without comparing or copying it. UI bindings and network replication use
+//! this to skip work for data that stayed the same.
+
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Identifies an entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Entity(pub u64);
+
+impl fmt::Display for Entity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "entity {}", self.0)
+    }
+}
+
+/// A point in a world's history of writes.
+///
+/// Ticks only grow, so a value changed after tick `t` if its change tick is
+/// greater than `t`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Tick(pub u64);
+
+/// A value with the tick it last changed at.
+#[derive(Debug)]
+struct Stamped<T> {
+    /// The value.
+    value: T,
+    /// Tick of the last write.
+    changed: Tick,
+}
+
+/// Type-erased storage of one component type.
+trait Storage: Send + Sync {
+    /// Removes the component of a despawned entity.
+    fn remove_entity(&mut self, entity: Entity);
+    /// Returns the storage for downcasting.
+    fn as_any(&self) -> &dyn Any;
+    /// Returns the storage for downcasting.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Send + Sync + 'static> Storage for HashMap<Entity, Stamped<T>> {
+    fn remove_entity(&mut self, entity: Entity) {
+        self.remove(&entity);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Entities with their components, and resources.
+///
+/// # Example
+///
+/// ```
+/// use ecs::World;
+///
+/// struct Health(u32);
+///
+/// let mut world = World::default();
+/// let player = world.spawn();
+/// world.insert(player, Health(100));
+///
+/// let seen = world.change_tick();
+/// assert!(!world.is_changed_since::<Health>(player, seen));
+/// world.get_mut::<Health>(player).unwrap().0 -= 10;
+/// assert!(world.is_changed_since::<Health>(player, seen));
+/// ```
+#[derive(Default)]
+pub struct World {
+    /// Id of the next spawned entity.
+    next_entity: u64,
+    /// Live entities.
+    entities: HashSet<Entity>,
+    /// Component storages by component type.
+    components: HashMap<TypeId, Box<dyn Storage>>,
+    /// Resources by type, each a `Stamped<R>`.
+    resources: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    /// Tick of the latest write.
+    tick: Tick,
+}
+
+impl fmt::Debug for World {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("World")
+            .field("entities", &self.entities.len())
+            .field("component_types", &self.components.len())
+            .field("resources", &self.resources.len())
+            .field("tick", &self.tick)
+            .finish_non_exhaustive()
+    }
+}
+
+impl World {
+    /// Creates an entity without components.
+    pub fn spawn(&mut self) -> Entity {
+        let entity = Entity(self.next_entity);
+        self.next_entity += 1;
+        self.entities.insert(entity);
+        entity
+    }
+
+    /// Removes an entity and its components, returning `false` if it did
+    /// not exist.
+    pub fn despawn(&mut self, entity: Entity) -> bool {
+        if !self.entities.remove(&entity) {
+            return false;
+        }
+        for storage in self.components.values_mut() {
+            storage.remove_entity(entity);
+        }
+        true
+    }
+
+    /// Returns `true` if the entity exists.
+    #[must_use]
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities.contains(&entity)
+    }
+
+    /// Returns the number of entities.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if there are no entities.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Iterates over the entities, in no particular order.
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.entities.iter().copied()
+    }
+
+    /// Returns the tick of the latest write. Values written later have a
+    /// greater change tick.
+    #[must_use]
+    #[inline]
+    pub fn change_tick(&self) -> Tick {
+        self.tick
+    }
+
+    /// Adds or replaces a component, returning `false` if the entity does
+    /// not exist.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, entity: Entity, component: T) -> bool {
+        if !self.contains(entity) {
+            return false;
+        }
+        let changed = self.next_tick();
+        self.storage_mut::<T>().insert(
+            entity,
+            Stamped {
+                value: component,
+                changed,
+            },
+        );
+        true
+    }
+
+    /// Removes a component, returning it.
+    pub fn remove<T: Send + Sync + 'static>(&mut self, entity: Entity) -> Option<T> {
+        self.components
+            .get_mut(&TypeId::of::<T>())?
+            .as_any_mut()
+            .downcast_mut::<HashMap<Entity, Stamped<T>>>()?
+            .remove(&entity)
+            .map(|stamped| stamped.value)
+    }
+
+    /// Returns a component.
+    #[must_use]
+    pub fn get<T: Send + Sync + 'static>(&self, entity: Entity) -> Option<&T> {
+        self.stamped::<T>(entity).map(|stamped| &stamped.value)
+    }
+
+    /// Returns a component for writing, marking it changed.
+    pub fn get_mut<T: Send + Sync + 'static>(&mut self, entity: Entity) -> Option<&mut T> {
+        let changed = Tick(self.tick.0 + 1);
+        let stamped = self
+            .components
+            .get_mut(&TypeId::of::<T>())?
+            .as_any_mut()
+            .downcast_mut::<HashMap<Entity, Stamped<T>>>()?
+            .get_mut(&entity)?;
+        self.tick = changed;
+        stamped.changed = changed;
+        Some(&mut stamped.value)
+    }
+
+    /// Returns the tick a component last changed at.
+    #[must_use]
+    pub fn changed_tick<T: Send + Sync + 'static>(&self, entity: Entity) -> Option<Tick> {
+        self.stamped::<T>(entity).map(|stamped| stamped.changed)
+    }
+
+    /// Returns `true` if a component was written after `since`.
+    #[must_use]
+    pub fn is_changed_since<T: Send + Sync + 'static>(&self, entity: Entity, since: Tick) -> bool {
+        self.changed_tick::<T>(entity)
+            .is_some_and(|tick| tick > since)
+    }
+
+    /// Iterates over the entities with a component, in no particular order.
+    pub fn query<T: Send + Sync + 'static>(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.storage::<T>().into_iter().flat_map(|storage| {
+            storage
+                .iter()
+                .map(|(entity, stamped)| (*entity, &stamped.value))
+        })
+    }
+
+    /// Adds or replaces a resource.
+    pub fn insert_resource<R: Send + Sync + 'static>(&mut self, resource: R) {
+        let changed = self.next_tick();
+        self.resources.insert(
+            TypeId::of::<R>(),
+            Box::new(Stamped {
+                value: resource,
+                changed,
+            }),
+        );
+    }
+
+    /// Removes a resource, returning it.
+    pub fn remove_resource<R: Send + Sync + 'static>(&mut self) -> Option<R> {
+        let resource = self.resources.remove(&TypeId::of::<R>())?;
+        resource
+            .downcast::<Stamped<R>>()
+            .ok()
+            .map(|stamped| stamped.value)
+    }
+
+    /// Returns a resource.
+    #[must_use]
+    pub fn resource<R: Send + Sync + 'static>(&self) -> Option<&R> {
+        self.stamped_resource::<R>().map(|stamped| &stamped.value)
+    }
+
+    /// Returns a resource for writing, marking it changed.
+    pub fn resource_mut<R: Send + Sync + 'static>(&mut self) -> Option<&mut R> {
+        let changed = Tick(self.tick.0 + 1);
+        let stamped = self
+            .resources
+            .get_mut(&TypeId::of::<R>())?
+            .downcast_mut::<Stamped<R>>()?;
+        self.tick = changed;
+        stamped.changed = changed;
+        Some(&mut stamped.value)
+    }
+
+    /// Returns the tick a resource last changed at.
+    #[must_use]
+    pub fn resource_changed_tick<R: Send + Sync + 'static>(&self) -> Option<Tick> {
+        self.stamped_resource::<R>().map(|stamped| stamped.changed)
+    }
+
+    /// Returns `true` if a resource was written after `since`.
+    #[must_use]
+    pub fn is_resource_changed_since<R: Send + Sync + 'static>(&self, since: Tick) -> bool {
+        self.resource_changed_tick::<R>()
+            .is_some_and(|tick| tick > since)
+    }
+
+    fn next_tick(&mut self) -> Tick {
+        self.tick.0 += 1;
+        self.tick
+    }
+
+    fn storage<T: Send + Sync + 'static>(&self) -> Option<&HashMap<Entity, Stamped<T>>> {
+        self.components
+            .get(&TypeId::of::<T>())?
+            .as_any()
+            .downcast_ref()
+    }
+
+    fn storage_mut<T: Send + Sync + 'static>(&mut self) -> &mut HashMap<Entity, Stamped<T>> {
+        self.components
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(HashMap::<Entity, Stamped<T>>::new()))
+            .as_any_mut()
+            .downcast_mut()
+            .expect("component storage is keyed by its type")
+    }
+
+    fn stamped<T: Send + Sync + 'static>(&self, entity: Entity) -> Option<&Stamped<T>> {
+        self.storage::<T>()?.get(&entity)
+    }
+
+    fn stamped_resource<R: Send + Sync + 'static>(&self) -> Option<&Stamped<R>> {
+        self.resources.get(&TypeId::of::<R>())?.downcast_ref()
+    }
+}
@@ -0,0 +1,73 @@
+//! Unit tests for the world and change detection.
+
+use crate::world::{Entity, Tick, World};
+
+#[derive(Debug, PartialEq)]
+struct Health(u32);
+
+#[derive(Debug, PartialEq)]
+struct Score(u64);
+
+/// Verifies spawning, component access, and despawning.
+#[test]
+fn stores_components() {
+    let mut world = World::default();
+    let a = world.spawn();
+    let b = world.spawn();
+    assert_ne!(a, b);
+
+    assert!(world.insert(a, Health(10)));
+    assert!(world.insert(b, Health(20)));
+    assert!(!world.insert(Entity(99), Health(0)));
+    assert_eq!(world.get::<Health>(a), Some(&Health(10)));
+    assert_eq!(world.get::<Score>(a), None);
+
+    let mut all: Vec<_> = world.query::<Health>().map(|(e, h)| (e, h.0)).collect();
+    all.sort_unstable();
+    assert_eq!(all, [(a, 10), (b, 20)]);
+
+    assert_eq!(world.remove::<Health>(b), Some(Health(20)));
+    assert!(world.despawn(a));
+    assert!(!world.despawn(a));
+    assert_eq!(world.get::<Health>(a), None);
+    assert_eq!(world.len(), 1);
+}
+
+/// Verifies that writes, and only writes, advance change ticks.
+#[test]
+fn detects_component_changes() {
+    let mut world = World::default();
+    let player = world.spawn();
+    let other = world.spawn();
+    world.insert(player, Health(100));
+    world.insert(other, Health(50));
+
+    let seen = world.change_tick();
+    let _ = world.get::<Health>(player);
+    assert!(!world.is_changed_since::<Health>(player, seen));
+
+    world.get_mut::<Health>(player).unwrap().0 = 90;
+    assert!(world.is_changed_since::<Health>(player, seen));
+    assert!(!world.is_changed_since::<Health>(other, seen));
+    assert!(!world.is_changed_since::<Score>(player, Tick::default()));
+    assert_eq!(
+        world.changed_tick::<Health>(player),
+        Some(world.change_tick())
+    );
+}
+
+/// Verifies resources and their change ticks.
+#[test]
+fn detects_resource_changes() {
+    let mut world = World::default();
+    assert!(world.resource::<Score>().is_none());
+    world.insert_resource(Score(0));
+
+    let seen = world.change_tick();
+    assert!(!world.is_resource_changed_since::<Score>(seen));
+    world.resource_mut::<Score>().unwrap().0 += 5;
+    assert!(world.is_resource_changed_since::<Score>(seen));
+    assert_eq!(world.resource::<Score>(), Some(&Score(5)));
+    assert_eq!(world.remove_resource::<Score>(), Some(Score(5)));
+    assert!(!world.is_resource_changed_since::<Score>(seen));
+}
@@ -0,0 +1,168 @@
+//! Abilities with cast times, cooldowns, and costs.
+//!
+//! An [`AbilityDef`] asset describes an ability; the [`Abilities`] component
+//! holds the abilities an entity knows, their cooldowns, and the cast in
+//! progress. Casts start with a [`CastRequest`](crate::CastRequest) event.
+
+use ecs::Entity;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+/// An ability asset.
+///
+/// ```toml
+/// [abilities.fireball]
+/// cast_time = 1.5
+/// cooldown = 4.0
+/// cost = { mana = 20 }
+/// target_effects = ["burning"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AbilityDef {
+    /// Seconds from the start of the cast until it takes effect; 0 for
+    /// instant abilities.
+    #[serde(default)]
+    pub cast_time: f32,
+    /// Seconds after the cast completes before it can start again.
+    #[serde(default)]
+    pub cooldown: f32,
+    /// Amount taken from each pool when the cast completes.
+    #[serde(default)]
+    pub cost: BTreeMap<String, f32>,
+    /// Effects applied to the target.
+    #[serde(default)]
+    pub target_effects: Vec<String>,
+    /// Effects applied to the caster.
+    #[serde(default)]
+    pub self_effects: Vec<String>,
+}
+
+/// A cast in progress.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cast {
+    /// Ability id in the library.
+    pub ability: String,
+    /// Entity the ability is cast at.
+    pub target: Entity,
+    /// Time until the cast completes.
+    pub remaining: Duration,
+}
+
+/// Why a cast did not start or complete.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CastError {
+    /// The library has no such ability.
+    UnknownAbility,
+    /// The caster has no [`Abilities`] component or does not know the
+    /// ability.
+    NotLearned,
+    /// The ability is cooling down for this long.
+    OnCooldown(Duration),
+    /// The caster is already casting.
+    Busy,
+    /// The named pool has less than the ability costs.
+    CannotAfford(String),
+    /// The cast was interrupted.
+    Interrupted,
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownAbility => write!(f, "unknown ability"),
+            Self::NotLearned => write!(f, "ability not learned"),
+            Self::OnCooldown(remaining) => {
+                write!(f, "on cooldown for {:.1}s", remaining.as_secs_f32())
+            }
+            Self::Busy => write!(f, "already casting"),
+            Self::CannotAfford(stat) => write!(f, "not enough {stat}"),
+            Self::Interrupted => write!(f, "interrupted"),
+        }
+    }
+}
+
+/// Component with the abilities an entity knows.
+///
+/// # Example
+///
+/// ```
+/// use gameplay::Abilities;
+///
+/// let abilities = Abilities::default().with("fireball").with("blink");
+/// assert!(abilities.knows("fireball"));
+/// assert!(abilities.is_ready("blink"));
+/// assert!(abilities.casting().is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Abilities {
+    /// Known abilities and their remaining cooldowns.
+    pub(crate) cooldowns: BTreeMap<String, Duration>,
+    pub(crate) casting: Option<Cast>,
+}
+
+impl Abilities {
+    /// Learns `ability`.
+    #[must_use]
+    pub fn with(mut self, ability: impl Into<String>) -> Self {
+        self.learn(ability);
+        self
+    }
+
+    /// Learns `ability`, ready to cast.
+    pub fn learn(&mut self, ability: impl Into<String>) {
+        self.cooldowns.entry(ability.into()).or_default();
+    }
+
+    /// Forgets `ability`, returning `false` if it was not known. A cast of
+    /// it continues.
+    pub fn forget(&mut self, ability: &str) -> bool {
+        self.cooldowns.remove(ability).is_some()
+    }
+
+    /// Returns `true` if the entity knows `ability`.
+    #[must_use]
+    pub fn knows(&self, ability: &str) -> bool {
+        self.cooldowns.contains_key(ability)
+    }
+
+    /// Iterates over the known abilities in id order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.cooldowns.keys().map(String::as_str)
+    }
+
+    /// Returns the time until `ability` can be cast again, or `None` if it
+    /// is not known.
+    #[must_use]
+    pub fn cooldown(&self, ability: &str) -> Option<Duration> {
+        self.cooldowns.get(ability).copied()
+    }
+
+    /// Returns `true` if `ability` is known and not cooling down.
+    #[must_use]
+    pub fn is_ready(&self, ability: &str) -> bool {
+        self.cooldown(ability)
+            .is_some_and(|cooldown| cooldown.is_zero())
+    }
+
+    /// Returns the cast in progress, if any.
+    #[must_use]
+    #[inline]
+    pub fn casting(&self) -> Option<&Cast> {
+        self.casting.as_ref()
+    }
+
+    /// Checks that `ability` can start casting now.
+    pub(crate) fn can_start(&self, ability: &str) -> Result<(), CastError> {
+        let cooldown = self.cooldown(ability).ok_or(CastError::NotLearned)?;
+        if self.casting.is_some() {
+            return Err(CastError::Busy);
+        }
+        if !cooldown.is_zero() {
+            return Err(CastError::OnCooldown(cooldown));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+//! Unit tests for the abilities component.
+
+use crate::ability::{Abilities, Cast, CastError};
+use ecs::Entity;
+use std::time::Duration;
+
+/// Verifies learning and forgetting abilities.
+#[test]
+fn learns_and_forgets_abilities() {
+    let mut abilities = Abilities::default().with("fireball");
+    abilities.learn("blink");
+    abilities.learn("blink");
+    assert_eq!(abilities.iter().collect::<Vec<_>>(), ["blink", "fireball"]);
+    assert_eq!(abilities.cooldown("blink"), Some(Duration::ZERO));
+    assert!(abilities.forget("blink"));
+    assert!(!abilities.forget("blink"));
+    assert!(!abilities.knows("blink"));
+    assert_eq!(abilities.cooldown("blink"), None);
+}
+
+/// Verifies a cast cannot start while unknown, casting, or cooling down.
+#[test]
+fn can_start_checks_cooldown_and_cast() {
+    let mut abilities = Abilities::default().with("fireball");
+    assert_eq!(abilities.can_start("fireball"), Ok(()));
+    assert_eq!(abilities.can_start("blink"), Err(CastError::NotLearned));
+
+    abilities
+        .cooldowns
+        .insert("fireball".to_owned(), Duration::from_secs(2));
+    assert!(!abilities.is_ready("fireball"));
+    assert_eq!(
+        abilities.can_start("fireball"),
+        Err(CastError::OnCooldown(Duration::from_secs(2)))
+    );
+
+    abilities.learn("blink");
+    abilities.casting = Some(Cast {
+        ability: "fireball".to_owned(),
+        target: Entity(1),
+        remaining: Duration::from_secs(1),
+    });
+    assert_eq!(abilities.can_start("blink"), Err(CastError::Busy));
+}
+
+/// Verifies cast errors read well in logs and UI.
+#[test]
+fn cast_errors_display() {
+    assert_eq!(
+        CastError::OnCooldown(Duration::from_millis(1500)).to_string(),
+        "on cooldown for 1.5s"
+    );
+    assert_eq!(
+        CastError::CannotAfford("mana".to_owned()).to_string(),
+        "not enough mana"
+    );
+}
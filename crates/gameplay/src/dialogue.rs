@@ -0,0 +1,570 @@
+//! Branching dialogue.
+//!
+//! A [`Dialogue`] asset is a graph of nodes, each showing one line and then
+//! moving to the next node or offering choices. Nodes can run script
+//! commands when their line is shown, and choices can be hidden behind
+//! conditions; both are callbacks game code registers on the
+//! [`DialogueRunner`] by name. Every line, speaker, and choice text goes
+//! through the runner's localizer before it reaches the UI.
+//!
+//! Game code controls conversations by sending [`DialogueRequest`]s, and
+//! [`update_dialogue`] answers with [`DialogueEvent`]s for the UI.
+
+use ecs::{Events, World};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::Arc;
+use tracing::warn;
+
+/// A dialogue asset.
+///
+/// # Example
+///
+/// ```
+/// use gameplay::Dialogue;
+///
+/// let dialogue = Dialogue::from_toml(r#"
+///     start = "greet"
+///
+///     [nodes.greet]
+///     speaker = "npc.smith"
+///     text = "smith.greet"
+///     choices = [
+///         { text = "smith.buy", next = "shop", condition = "has_gold 10" },
+///         { text = "smith.bye" },
+///     ]
+///
+///     [nodes.shop]
+///     speaker = "npc.smith"
+///     text = "smith.shop"
+///     commands = ["open_shop smith"]
+/// "#)?;
+/// assert_eq!(dialogue.node("greet").unwrap().choices.len(), 2);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Dialogue {
+    /// Id of the first node.
+    pub start: String,
+    /// Nodes by id.
+    pub nodes: BTreeMap<String, DialogueNode>,
+}
+
+/// One line of a dialogue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DialogueNode {
+    /// Who speaks, as a localization key or name.
+    #[serde(default)]
+    pub speaker: Option<String>,
+    /// The line, as a localization key or text.
+    pub text: String,
+    /// Script commands run when the line is shown, e.g. `give_item potion 2`.
+    #[serde(default)]
+    pub commands: Vec<String>,
+    /// Node shown after this one, or `None` to end the dialogue. Not used
+    /// by nodes with choices.
+    #[serde(default)]
+    pub next: Option<String>,
+    /// Choices offered after the line.
+    #[serde(default)]
+    pub choices: Vec<DialogueChoice>,
+}
+
+/// A choice offered after a line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DialogueChoice {
+    /// The choice, as a localization key or text.
+    pub text: String,
+    /// Node shown when chosen, or `None` to end the dialogue.
+    #[serde(default)]
+    pub next: Option<String>,
+    /// Condition that must hold for the choice to be offered, e.g.
+    /// `has_item key`.
+    #[serde(default)]
+    pub condition: Option<String>,
+}
+
+impl Dialogue {
+    /// Parses a dialogue from TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TOML is malformed or the dialogue is invalid.
+    pub fn from_toml(source: &str) -> anyhow::Result<Self> {
+        let dialogue: Self =
+            toml::from_str(source).map_err(|e| anyhow::anyhow!("invalid dialogue: {e}"))?;
+        dialogue.validate()?;
+        Ok(dialogue)
+    }
+
+    /// Serializes the dialogue to TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_toml(&self) -> anyhow::Result<String> {
+        toml::to_string(self).map_err(|e| anyhow::anyhow!("failed to serialize dialogue: {e}"))
+    }
+
+    /// Returns node `id`.
+    #[must_use]
+    pub fn node(&self, id: &str) -> Option<&DialogueNode> {
+        self.nodes.get(id)
+    }
+
+    /// Checks that the start node and every node linked to exist, and that
+    /// nodes with choices have no `next`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first problem found.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !self.nodes.contains_key(&self.start) {
+            anyhow::bail!("start node `{}` does not exist", self.start);
+        }
+        for (id, node) in &self.nodes {
+            if node.next.is_some() && !node.choices.is_empty() {
+                anyhow::bail!("node `{id}` has both `next` and choices");
+            }
+            let links = node.next.iter().chain(
+                node.choices
+                    .iter()
+                    .filter_map(|choice| choice.next.as_ref()),
+            );
+            for next in links {
+                if !self.nodes.contains_key(next) {
+                    anyhow::bail!("node `{id}` links to missing node `{next}`");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Asks the [`DialogueRunner`] to change the conversation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogueRequest {
+    /// Starts a dialogue added to the runner.
+    Start {
+        /// Dialogue id.
+        dialogue: String,
+    },
+    /// Moves on from a line without choices.
+    Advance,
+    /// Picks a choice by its index in the last [`DialogueEvent::Choices`].
+    Choose(usize),
+    /// Ends the conversation.
+    Stop,
+}
+
+/// Why a [`DialogueRequest`] was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogueError {
+    /// No dialogue with this id was added.
+    UnknownDialogue(String),
+    /// Another dialogue is running.
+    Busy,
+    /// No dialogue is running.
+    NotRunning,
+    /// The line offers choices, so it cannot be advanced.
+    ChoiceRequired,
+    /// The line offers no choices.
+    NoChoices,
+    /// The choice index is out of range.
+    InvalidChoice(usize),
+}
+
+impl fmt::Display for DialogueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownDialogue(id) => write!(f, "unknown dialogue `{id}`"),
+            Self::Busy => write!(f, "another dialogue is running"),
+            Self::NotRunning => write!(f, "no dialogue is running"),
+            Self::ChoiceRequired => write!(f, "the line needs a choice"),
+            Self::NoChoices => write!(f, "the line offers no choices"),
+            Self::InvalidChoice(index) => write!(f, "no choice {index}"),
+        }
+    }
+}
+
+impl std::error::Error for DialogueError {}
+
+/// What happened in the conversation, for the UI.
+///
+/// Texts are localized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogueEvent {
+    /// A dialogue started.
+    Started {
+        /// Dialogue id.
+        dialogue: String,
+    },
+    /// A line is shown.
+    Line {
+        /// Node id.
+        node: String,
+        /// Who speaks.
+        speaker: Option<String>,
+        /// The line.
+        text: String,
+    },
+    /// Choices are offered for the line just shown, answered with
+    /// [`DialogueRequest::Choose`].
+    Choices {
+        /// Node id.
+        node: String,
+        /// Choice texts, in index order.
+        choices: Vec<String>,
+    },
+    /// The dialogue ended.
+    Ended {
+        /// Dialogue id.
+        dialogue: String,
+    },
+    /// The request was refused and nothing changed.
+    Failed {
+        /// The refused request.
+        request: DialogueRequest,
+        /// Why.
+        error: DialogueError,
+    },
+}
+
+/// A registered script command, called with its arguments.
+type CommandFn = Box<dyn FnMut(&[&str], &mut World) + Send + Sync>;
+
+/// A registered condition, called with its arguments.
+type ConditionFn = Box<dyn Fn(&[&str], &World) -> bool + Send + Sync>;
+
+/// The localizer, returning `None` for unknown keys.
+type LocalizeFn = Box<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+/// The running conversation.
+#[derive(Debug)]
+struct Conversation {
+    /// Dialogue id.
+    id: String,
+    dialogue: Arc<Dialogue>,
+    /// Node whose line is shown.
+    node: String,
+    /// Indices into the node's choices of the ones offered.
+    offered: Vec<usize>,
+}
+
+/// World resource running one conversation at a time.
+///
+/// # Example
+///
+/// ```
+/// use ecs::{Events, World};
+/// use gameplay::{update_dialogue, Dialogue, DialogueEvent, DialogueRequest, DialogueRunner};
+///
+/// let mut runner = DialogueRunner::new();
+/// runner.add("intro", Dialogue::from_toml(r#"
+///     start = "hello"
+///     [nodes.hello]
+///     text = "intro.hello"
+/// "#)?);
+/// runner.set_localizer(|key| (key == "intro.hello").then(|| "Hello!".to_owned()));
+///
+/// let mut world = World::default();
+/// world.insert_resource(runner);
+/// let mut requests = Events::default();
+/// requests.send(DialogueRequest::Start { dialogue: "intro".to_owned() });
+/// world.insert_resource(requests);
+/// update_dialogue(&mut world);
+///
+/// let events = world.resource::<Events<DialogueEvent>>().unwrap();
+/// assert!(events.iter().any(|event| matches!(
+///     event,
+///     DialogueEvent::Line { text, .. } if text == "Hello!"
+/// )));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Default)]
+pub struct DialogueRunner {
+    dialogues: HashMap<String, Arc<Dialogue>>,
+    commands: HashMap<String, CommandFn>,
+    conditions: HashMap<String, ConditionFn>,
+    localizer: Option<LocalizeFn>,
+    conversation: Option<Conversation>,
+}
+
+impl fmt::Debug for DialogueRunner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut dialogues: Vec<_> = self.dialogues.keys().collect();
+        dialogues.sort();
+        let mut commands: Vec<_> = self.commands.keys().collect();
+        commands.sort();
+        let mut conditions: Vec<_> = self.conditions.keys().collect();
+        conditions.sort();
+        f.debug_struct("DialogueRunner")
+            .field("dialogues", &dialogues)
+            .field("commands", &commands)
+            .field("conditions", &conditions)
+            .field("localizer", &self.localizer.is_some())
+            .field("conversation", &self.conversation)
+            .finish()
+    }
+}
+
+impl DialogueRunner {
+    /// Creates a runner without dialogues or callbacks.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds dialogue `id`, replacing any dialogue of the same id. A running
+    /// conversation keeps the version it started with.
+    pub fn add(&mut self, id: impl Into<String>, dialogue: Dialogue) {
+        self.dialogues.insert(id.into(), Arc::new(dialogue));
+    }
+
+    /// Returns dialogue `id`.
+    #[must_use]
+    pub fn dialogue(&self, id: &str) -> Option<&Dialogue> {
+        self.dialogues.get(id).map(AsRef::as_ref)
+    }
+
+    /// Registers script command `name`, replacing any command of the same
+    /// name. It is called with the words after the name.
+    pub fn register_command(
+        &mut self,
+        name: &str,
+        command: impl FnMut(&[&str], &mut World) + Send + Sync + 'static,
+    ) {
+        self.commands.insert(name.to_owned(), Box::new(command));
+    }
+
+    /// Registers condition `name`, replacing any condition of the same
+    /// name. It is called with the words after the name.
+    pub fn register_condition(
+        &mut self,
+        name: &str,
+        condition: impl Fn(&[&str], &World) -> bool + Send + Sync + 'static,
+    ) {
+        self.conditions.insert(name.to_owned(), Box::new(condition));
+    }
+
+    /// Sets the function looking up localized text by key. Keys it does not
+    /// know are shown as written.
+    pub fn set_localizer(
+        &mut self,
+        localizer: impl Fn(&str) -> Option<String> + Send + Sync + 'static,
+    ) {
+        self.localizer = Some(Box::new(localizer));
+    }
+
+    /// Returns the localized text for `key`, or `key` itself.
+    #[must_use]
+    pub fn localize(&self, key: &str) -> String {
+        self.localizer
+            .as_ref()
+            .and_then(|localizer| localizer(key))
+            .unwrap_or_else(|| key.to_owned())
+    }
+
+    /// Returns `true` while a conversation runs.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.conversation.is_some()
+    }
+
+    /// Returns the ids of the running dialogue and its current node.
+    #[must_use]
+    pub fn current(&self) -> Option<(&str, &str)> {
+        self.conversation
+            .as_ref()
+            .map(|conversation| (conversation.id.as_str(), conversation.node.as_str()))
+    }
+
+    /// Handles one request.
+    fn handle(
+        &mut self,
+        world: &mut World,
+        request: &DialogueRequest,
+        out: &mut Vec<DialogueEvent>,
+    ) -> Result<(), DialogueError> {
+        match request {
+            DialogueRequest::Start { dialogue } => {
+                if self.conversation.is_some() {
+                    return Err(DialogueError::Busy);
+                }
+                let asset = self
+                    .dialogues
+                    .get(dialogue)
+                    .cloned()
+                    .ok_or_else(|| DialogueError::UnknownDialogue(dialogue.clone()))?;
+                out.push(DialogueEvent::Started {
+                    dialogue: dialogue.clone(),
+                });
+                let start = asset.start.clone();
+                self.conversation = Some(Conversation {
+                    id: dialogue.clone(),
+                    dialogue: asset,
+                    node: String::new(),
+                    offered: Vec::new(),
+                });
+                self.enter(world, Some(start), out);
+            }
+            DialogueRequest::Advance => {
+                let conversation = self
+                    .conversation
+                    .as_ref()
+                    .ok_or(DialogueError::NotRunning)?;
+                if !conversation.offered.is_empty() {
+                    return Err(DialogueError::ChoiceRequired);
+                }
+                let next = conversation
+                    .dialogue
+                    .node(&conversation.node)
+                    .and_then(|node| node.next.clone());
+                self.enter(world, next, out);
+            }
+            DialogueRequest::Choose(index) => {
+                let conversation = self
+                    .conversation
+                    .as_ref()
+                    .ok_or(DialogueError::NotRunning)?;
+                if conversation.offered.is_empty() {
+                    return Err(DialogueError::NoChoices);
+                }
+                let choice = conversation
+                    .offered
+                    .get(*index)
+                    .ok_or(DialogueError::InvalidChoice(*index))?;
+                let next = conversation
+                    .dialogue
+                    .node(&conversation.node)
+                    .and_then(|node| node.choices.get(*choice))
+                    .and_then(|choice| choice.next.clone());
+                self.enter(world, next, out);
+            }
+            DialogueRequest::Stop => {
+                if self.conversation.is_none() {
+                    return Err(DialogueError::NotRunning);
+                }
+                self.enter(world, None, out);
+            }
+        }
+        Ok(())
+    }
+
+    /// Shows node `next`, or ends the conversation if `None`.
+    fn enter(&mut self, world: &mut World, next: Option<String>, out: &mut Vec<DialogueEvent>) {
+        let Some(conversation) = self.conversation.as_mut() else {
+            return;
+        };
+        let Some(node) = next.and_then(|id| {
+            let node = conversation.dialogue.node(&id)?.clone();
+            conversation.node = id;
+            Some(node)
+        }) else {
+            if let Some(conversation) = self.conversation.take() {
+                out.push(DialogueEvent::Ended {
+                    dialogue: conversation.id,
+                });
+            }
+            return;
+        };
+
+        for command in &node.commands {
+            let mut words = command.split_whitespace();
+            let name = words.next().unwrap_or_default();
+            let args: Vec<&str> = words.collect();
+            if let Some(run) = self.commands.get_mut(name) {
+                run(&args, world);
+            } else {
+                warn!("Dialogue command `{name}` is not registered");
+            }
+        }
+
+        let offered: Vec<usize> = node
+            .choices
+            .iter()
+            .enumerate()
+            .filter(|(_, choice)| {
+                choice
+                    .condition
+                    .as_deref()
+                    .is_none_or(|condition| self.check(condition, world))
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        let id = self
+            .current()
+            .map(|(_, node)| node.to_owned())
+            .unwrap_or_default();
+        out.push(DialogueEvent::Line {
+            node: id.clone(),
+            speaker: node
+                .speaker
+                .as_deref()
+                .map(|speaker| self.localize(speaker)),
+            text: self.localize(&node.text),
+        });
+        if !offered.is_empty() {
+            out.push(DialogueEvent::Choices {
+                node: id,
+                choices: offered
+                    .iter()
+                    .map(|&index| self.localize(&node.choices[index].text))
+                    .collect(),
+            });
+        }
+        if let Some(conversation) = self.conversation.as_mut() {
+            conversation.offered = offered;
+        }
+    }
+
+    /// Evaluates a condition; unregistered conditions fail.
+    fn check(&self, condition: &str, world: &World) -> bool {
+        let mut words = condition.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let args: Vec<&str> = words.collect();
+        if let Some(check) = self.conditions.get(name) {
+            check(&args, world)
+        } else {
+            warn!("Dialogue condition `{name}` is not registered");
+            false
+        }
+    }
+}
+
+/// Handles the [`DialogueRequest`]s sent since the last update, in order,
+/// and sends the outcome to the world's `Events<DialogueEvent>`, created if
+/// missing, which is advanced a frame first.
+///
+/// Does nothing without a [`DialogueRunner`] resource. The runner is taken
+/// out of the world while it runs, so commands and conditions can use the
+/// whole world.
+pub fn update_dialogue(world: &mut World) {
+    let Some(mut runner) = world.remove_resource::<DialogueRunner>() else {
+        return;
+    };
+    let requests: Vec<DialogueRequest> = world
+        .resource_mut::<Events<DialogueRequest>>()
+        .map(|events| events.drain().collect())
+        .unwrap_or_default();
+    let mut out = Vec::new();
+    for request in requests {
+        if let Err(error) = runner.handle(world, &request, &mut out) {
+            out.push(DialogueEvent::Failed { request, error });
+        }
+    }
+    world.insert_resource(runner);
+
+    if world.resource::<Events<DialogueEvent>>().is_none() {
+        world.insert_resource(Events::<DialogueEvent>::default());
+    }
+    if let Some(events) = world.resource_mut::<Events<DialogueEvent>>() {
+        events.update();
+        events.send_batch(out);
+    }
+}
@@ -0,0 +1,208 @@
+//! Unit tests for dialogues.
+
+use crate::dialogue::{
+    update_dialogue, Dialogue, DialogueError, DialogueEvent, DialogueRequest, DialogueRunner,
+};
+use ecs::{Events, World};
+
+const SMITH: &str = r#"
+start = "greet"
+
+[nodes.greet]
+speaker = "npc.smith"
+text = "smith.greet"
+choices = [
+    { text = "smith.buy", next = "shop", condition = "has_gold 10" },
+    { text = "smith.rumors", next = "rumors" },
+    { text = "smith.bye" },
+]
+
+[nodes.shop]
+speaker = "npc.smith"
+text = "smith.shop"
+commands = ["open_shop weapons", "missing_command"]
+
+[nodes.rumors]
+text = "Heard about the dragon?"
+next = "shop"
+"#;
+
+/// Gold the `has_gold` condition compares against.
+struct Gold(u32);
+
+/// Shops opened by the `open_shop` command.
+#[derive(Default)]
+struct OpenedShops(Vec<String>);
+
+fn world(gold: u32) -> World {
+    let mut runner = DialogueRunner::new();
+    runner.add("smith", Dialogue::from_toml(SMITH).unwrap());
+    runner.register_condition("has_gold", |args, world| {
+        let needed: u32 = args[0].parse().unwrap();
+        world
+            .resource::<Gold>()
+            .is_some_and(|gold| gold.0 >= needed)
+    });
+    runner.register_command("open_shop", |args, world| {
+        if let Some(shops) = world.resource_mut::<OpenedShops>() {
+            shops.0.push(args.join(" "));
+        }
+    });
+    runner.set_localizer(|key| match key {
+        "npc.smith" => Some("Smith".to_owned()),
+        "smith.greet" => Some("Welcome!".to_owned()),
+        "smith.buy" => Some("Show me your wares.".to_owned()),
+        "smith.rumors" => Some("Any news?".to_owned()),
+        "smith.bye" => Some("Goodbye.".to_owned()),
+        _ => None,
+    });
+
+    let mut world = World::default();
+    world.insert_resource(runner);
+    world.insert_resource(Gold(gold));
+    world.insert_resource(OpenedShops::default());
+    world
+}
+
+/// Sends `requests`, runs an update, and returns the events it sent.
+fn run(
+    world: &mut World,
+    requests: impl IntoIterator<Item = DialogueRequest>,
+) -> Vec<DialogueEvent> {
+    let mut events = Events::default();
+    events.send_batch(requests);
+    world.insert_resource(events);
+    update_dialogue(world);
+    world
+        .resource::<Events<DialogueEvent>>()
+        .unwrap()
+        .iter_current()
+        .cloned()
+        .collect()
+}
+
+fn start() -> DialogueRequest {
+    DialogueRequest::Start {
+        dialogue: "smith".to_owned(),
+    }
+}
+
+/// Verifies a started dialogue shows its first line with localized text
+/// and offers only the choices whose conditions hold.
+#[test]
+fn start_shows_localized_line_and_choices() {
+    let mut world = world(5);
+    assert_eq!(
+        run(&mut world, [start()]),
+        [
+            DialogueEvent::Started {
+                dialogue: "smith".to_owned()
+            },
+            DialogueEvent::Line {
+                node: "greet".to_owned(),
+                speaker: Some("Smith".to_owned()),
+                text: "Welcome!".to_owned(),
+            },
+            DialogueEvent::Choices {
+                node: "greet".to_owned(),
+                choices: vec!["Any news?".to_owned(), "Goodbye.".to_owned()],
+            },
+        ]
+    );
+    let runner = world.resource::<DialogueRunner>().unwrap();
+    assert_eq!(runner.current(), Some(("smith", "greet")));
+}
+
+/// Verifies choices and advancing follow the graph, run the commands of
+/// each line shown, and end the dialogue after the last line.
+#[test]
+fn choices_and_advancing_follow_the_graph() {
+    let mut world = world(20);
+    run(&mut world, [start()]);
+
+    let events = run(&mut world, [DialogueRequest::Choose(1)]);
+    assert_eq!(
+        events,
+        [DialogueEvent::Line {
+            node: "rumors".to_owned(),
+            speaker: None,
+            text: "Heard about the dragon?".to_owned(),
+        }]
+    );
+
+    let events = run(&mut world, [DialogueRequest::Advance]);
+    assert!(matches!(&events[..], [DialogueEvent::Line { node, .. }] if node == "shop"));
+    assert_eq!(world.resource::<OpenedShops>().unwrap().0, ["weapons"]);
+
+    assert_eq!(
+        run(&mut world, [DialogueRequest::Advance]),
+        [DialogueEvent::Ended {
+            dialogue: "smith".to_owned()
+        }]
+    );
+    assert!(!world.resource::<DialogueRunner>().unwrap().is_running());
+}
+
+/// Verifies requests that do not fit the conversation are refused.
+#[test]
+fn refuses_requests_out_of_turn() {
+    let mut world = world(20);
+    let unknown = DialogueRequest::Start {
+        dialogue: "queen".to_owned(),
+    };
+    let events = run(
+        &mut world,
+        [
+            DialogueRequest::Advance,
+            unknown.clone(),
+            start(),
+            start(),
+            DialogueRequest::Advance,
+            DialogueRequest::Choose(3),
+        ],
+    );
+    let failures: Vec<_> = events
+        .into_iter()
+        .filter_map(|event| match event {
+            DialogueEvent::Failed { request, error } => Some((request, error)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        failures,
+        [
+            (DialogueRequest::Advance, DialogueError::NotRunning),
+            (unknown, DialogueError::UnknownDialogue("queen".to_owned())),
+            (start(), DialogueError::Busy),
+            (DialogueRequest::Advance, DialogueError::ChoiceRequired),
+            (DialogueRequest::Choose(3), DialogueError::InvalidChoice(3)),
+        ]
+    );
+
+    run(&mut world, [DialogueRequest::Choose(2)]);
+    assert_eq!(
+        run(&mut world, [DialogueRequest::Stop]),
+        [DialogueEvent::Failed {
+            request: DialogueRequest::Stop,
+            error: DialogueError::NotRunning,
+        }]
+    );
+}
+
+/// Verifies dialogues linking to missing nodes are refused.
+#[test]
+fn refuses_invalid_dialogues() {
+    for source in [
+        "start = \"a\"\n[nodes.b]\ntext = \"b\"",
+        "start = \"a\"\n[nodes.a]\ntext = \"a\"\nnext = \"b\"",
+        "start = \"a\"\n[nodes.a]\ntext = \"a\"\nchoices = [{ text = \"c\", next = \"b\" }]",
+        "start = \"a\"\n[nodes.a]\ntext = \"a\"\nnext = \"a\"\nchoices = [{ text = \"c\" }]",
+    ] {
+        assert!(Dialogue::from_toml(source).is_err(), "{source}");
+    }
+    let dialogue = Dialogue::from_toml(SMITH).unwrap();
+    assert_eq!(
+        Dialogue::from_toml(&dialogue.to_toml().unwrap()).unwrap(),
+        dialogue
+    );
+}
@@ -0,0 +1,114 @@
+//! Status effects: timed buffs, debuffs, and damage over time.
+//!
+//! An [`EffectDef`] asset describes what an effect does; the
+//! [`StatusEffects`] component tracks the effects on an entity. Effects are
+//! applied and removed through the [`ApplyEffect`](crate::ApplyEffect) and
+//! [`RemoveEffect`](crate::RemoveEffect) events.
+
+use crate::stats::StatModifier;
+use ecs::Entity;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// A status effect asset.
+///
+/// ```toml
+/// [effects.burning]
+/// duration = 3.0
+/// max_stacks = 3
+/// modifiers = [{ stat = "armor", percent = -10 }]
+/// periodic = { stat = "health", amount = -5, interval = 1.0 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EffectDef {
+    /// Seconds the effect lasts, or `None` until removed.
+    #[serde(default)]
+    pub duration: Option<f32>,
+    /// Most stacks at once. Applying the effect again adds a stack, up to
+    /// this many, and restarts its duration.
+    #[serde(default = "one_stack")]
+    pub max_stacks: u32,
+    /// Stat modifiers, applied once per stack while the effect lasts.
+    #[serde(default)]
+    pub modifiers: Vec<StatModifier>,
+    /// Change to a pool at a fixed interval, such as damage over time.
+    #[serde(default)]
+    pub periodic: Option<Periodic>,
+}
+
+fn one_stack() -> u32 {
+    1
+}
+
+/// A change to a pool at a fixed interval.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Periodic {
+    /// Name of the changed pool.
+    pub stat: String,
+    /// Amount added per stack each interval; negative for damage.
+    pub amount: f32,
+    /// Seconds between changes.
+    pub interval: f32,
+}
+
+/// An effect on an entity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveEffect {
+    /// Effect id in the library.
+    pub id: String,
+    /// Entity that applied the effect last.
+    pub source: Entity,
+    /// Number of stacks, at least 1.
+    pub stacks: u32,
+    /// Time left, or `None` until removed.
+    pub remaining: Option<Duration>,
+    /// Time since the last periodic change.
+    pub(crate) since_periodic: Duration,
+}
+
+/// Component with the status effects on an entity.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatusEffects {
+    pub(crate) active: Vec<ActiveEffect>,
+}
+
+impl StatusEffects {
+    /// Returns the active effect `id`, if any.
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&ActiveEffect> {
+        self.active.iter().find(|effect| effect.id == id)
+    }
+
+    /// Returns `true` if effect `id` is active.
+    #[must_use]
+    pub fn contains(&self, id: &str) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Returns the stacks of effect `id`, or 0 if it is not active.
+    #[must_use]
+    pub fn stacks(&self, id: &str) -> u32 {
+        self.get(id).map_or(0, |effect| effect.stacks)
+    }
+
+    /// Iterates over the active effects in application order.
+    pub fn iter(&self) -> impl Iterator<Item = &ActiveEffect> {
+        self.active.iter()
+    }
+
+    /// Returns the number of active effects.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Returns `true` if no effect is active.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+}
@@ -0,0 +1,87 @@
+//! Gameplay subsystem implementation.
+//!
+//! Provides the [`RustgineGameplay`] system, which owns the game's ability
+//! and effect assets.
+
+use crate::library::GameplayLibrary;
+use crate::update::update_gameplay;
+use ecs::World;
+use rustgine_core::{RustgineError, RustgineSystem};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+/// Gameplay subsystem for the Rustgine engine.
+///
+/// Manages:
+/// - The ability and status effect assets
+/// - Stats and their modifiers
+/// - Casts, cooldowns, costs, and status effects each frame
+///
+/// # Example
+///
+/// ```ignore
+/// use gameplay::RustgineGameplay;
+/// use rustgine_core::RustgineSystem;
+///
+/// let mut gameplay = RustgineGameplay::new(Arc::new(library));
+/// gameplay.startup()?;
+/// ```
+#[derive(Debug, Default)]
+pub struct RustgineGameplay {
+    /// Ability and effect assets.
+    library: Arc<GameplayLibrary>,
+}
+
+impl RustgineGameplay {
+    /// Creates the subsystem with the game's assets.
+    #[must_use]
+    pub fn new(library: Arc<GameplayLibrary>) -> Self {
+        Self { library }
+    }
+
+    /// Returns the ability and effect assets.
+    #[must_use]
+    #[inline]
+    pub fn library(&self) -> &Arc<GameplayLibrary> {
+        &self.library
+    }
+
+    /// Replaces the assets, e.g. after designers edit them. Active effects
+    /// and casts continue with the new definitions.
+    pub fn set_library(&mut self, library: Arc<GameplayLibrary>) {
+        self.library = library;
+    }
+
+    /// Runs [`update_gameplay`] with the subsystem's assets.
+    pub fn update(&self, world: &mut World, delta: Duration) {
+        update_gameplay(world, &self.library, delta);
+    }
+}
+
+impl RustgineSystem for RustgineGameplay {
+    /// Initializes the gameplay subsystem.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the assets are invalid.
+    fn startup(&mut self) -> Result<(), RustgineError> {
+        self.library.validate()?;
+        info!(
+            abilities = self.library.abilities.len(),
+            effects = self.library.effects.len(),
+            "gameplay started"
+        );
+        Ok(())
+    }
+
+    /// Shuts down the gameplay subsystem.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if cleanup fails.
+    #[inline]
+    fn shutdown(&mut self) -> Result<(), RustgineError> {
+        Ok(())
+    }
+}
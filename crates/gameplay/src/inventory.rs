@@ -0,0 +1,786 @@
+//! Inventories of item stacks.
+//!
+//! An [`Inventory`] component holds stacks of items in a fixed number of
+//! slots, optionally under a weight limit, with some slots restricted to
+//! items with a tag (equipment slots, a quiver). Item properties come from
+//! the [`ItemDef`](crate::ItemDef)s in the [`GameplayLibrary`].
+//!
+//! Game code changes inventories directly, or through [`InventoryOp`]
+//! events, which [`update_inventories`] answers with [`InventoryEvent`]s for
+//! the UI and game rules.
+//!
+//! Inventories serialize with serde into save games, as slot indices with
+//! item ids and counts. Items may be removed from the game between saves, so
+//! call [`Inventory::retain_known`] after loading.
+
+use crate::library::GameplayLibrary;
+use ecs::{Entity, Events, World};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Copies of one item in a slot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemStack {
+    /// Item id in the library.
+    pub item: String,
+    /// Number of copies, at least 1.
+    pub count: u32,
+}
+
+impl ItemStack {
+    /// Creates a stack of `count` copies of `item`.
+    #[must_use]
+    pub fn new(item: impl Into<String>, count: u32) -> Self {
+        Self {
+            item: item.into(),
+            count,
+        }
+    }
+}
+
+/// Why an inventory change was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InventoryError {
+    /// The library has no such item.
+    UnknownItem(String),
+    /// The inventory has no slot with this index.
+    NoSlot(usize),
+    /// The slot is empty.
+    EmptySlot(usize),
+    /// The slot holds another item.
+    Occupied(usize),
+    /// The slot only accepts items with this tag.
+    Restricted {
+        /// Slot index.
+        slot: usize,
+        /// Required tag.
+        tag: String,
+    },
+    /// The count is 0 or more than the slot holds.
+    InvalidCount,
+    /// There are fewer copies of the item than requested.
+    NotEnough(String),
+    /// The items do not fit in the free slots and stacks.
+    Full,
+    /// The items would exceed the weight limit.
+    TooHeavy,
+    /// The stacks hold different items.
+    Mismatch,
+    /// The entity has no [`Inventory`] component.
+    NoInventory(Entity),
+    /// A transfer names the same inventory twice.
+    SameInventory,
+}
+
+impl fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownItem(item) => write!(f, "unknown item `{item}`"),
+            Self::NoSlot(slot) => write!(f, "no slot {slot}"),
+            Self::EmptySlot(slot) => write!(f, "slot {slot} is empty"),
+            Self::Occupied(slot) => write!(f, "slot {slot} holds another item"),
+            Self::Restricted { slot, tag } => write!(f, "slot {slot} only holds {tag} items"),
+            Self::InvalidCount => write!(f, "invalid item count"),
+            Self::NotEnough(item) => write!(f, "not enough `{item}`"),
+            Self::Full => write!(f, "inventory full"),
+            Self::TooHeavy => write!(f, "too heavy"),
+            Self::Mismatch => write!(f, "different items do not stack"),
+            Self::NoInventory(entity) => write!(f, "entity {} has no inventory", entity.0),
+            Self::SameInventory => write!(f, "cannot transfer within one inventory"),
+        }
+    }
+}
+
+impl std::error::Error for InventoryError {}
+
+/// Component holding an entity's items.
+///
+/// # Example
+///
+/// ```
+/// use gameplay::{GameplayLibrary, Inventory};
+///
+/// let library = GameplayLibrary::from_toml(r#"
+///     [items.arrow]
+///     name = "Arrow"
+///     max_stack = 50
+///     weight = 0.1
+/// "#)?;
+/// let mut quiver = Inventory::new(2).with_max_weight(20.0);
+/// quiver.add(&library, "arrow", 80)?;
+/// assert_eq!(quiver.slot(0).unwrap().count, 50);
+/// assert_eq!(quiver.slot(1).unwrap().count, 30);
+/// assert!(quiver.add(&library, "arrow", 30).is_err());
+///
+/// quiver.remove("arrow", 40)?;
+/// assert_eq!(quiver.count("arrow"), 40);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(into = "SavedInventory", try_from = "SavedInventory")]
+pub struct Inventory {
+    slots: Vec<Option<ItemStack>>,
+    max_weight: Option<f32>,
+    /// Tag required by each slot, if any.
+    restrictions: Vec<Option<String>>,
+}
+
+impl Inventory {
+    /// Creates an empty inventory with `slots` slots and no weight limit.
+    #[must_use]
+    pub fn new(slots: usize) -> Self {
+        Self {
+            slots: vec![None; slots],
+            max_weight: None,
+            restrictions: vec![None; slots],
+        }
+    }
+
+    /// Limits the total weight of the items.
+    #[must_use]
+    pub fn with_max_weight(mut self, max_weight: f32) -> Self {
+        self.max_weight = Some(max_weight);
+        self
+    }
+
+    /// Restricts `slot` to items tagged `tag`. Such slots are only filled
+    /// by moving items into them, never by [`add`](Self::add).
+    #[must_use]
+    pub fn with_restriction(mut self, slot: usize, tag: impl Into<String>) -> Self {
+        if let Some(restriction) = self.restrictions.get_mut(slot) {
+            *restriction = Some(tag.into());
+        }
+        self
+    }
+
+    /// Returns the number of slots.
+    #[must_use]
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the weight limit, if any.
+    #[must_use]
+    #[inline]
+    pub fn max_weight(&self) -> Option<f32> {
+        self.max_weight
+    }
+
+    /// Returns the tag `slot` requires, if any.
+    #[must_use]
+    pub fn restriction(&self, slot: usize) -> Option<&str> {
+        self.restrictions.get(slot)?.as_deref()
+    }
+
+    /// Returns the stack in `slot`, or `None` if it is empty or missing.
+    #[must_use]
+    pub fn slot(&self, slot: usize) -> Option<&ItemStack> {
+        self.slots.get(slot)?.as_ref()
+    }
+
+    /// Iterates over the occupied slots and their stacks.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &ItemStack)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, stack)| Some((slot, stack.as_ref()?)))
+    }
+
+    /// Returns `true` if every slot is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.slots.iter().all(Option::is_none)
+    }
+
+    /// Returns the copies of `item` across all slots.
+    #[must_use]
+    pub fn count(&self, item: &str) -> u32 {
+        self.iter()
+            .filter(|(_, stack)| stack.item == item)
+            .map(|(_, stack)| stack.count)
+            .sum()
+    }
+
+    /// Returns the total weight of the items; unknown items weigh nothing.
+    #[must_use]
+    pub fn weight(&self, library: &GameplayLibrary) -> f32 {
+        self.iter()
+            .filter_map(|(_, stack)| {
+                Some(item_weight(library.item(&stack.item)?.weight, stack.count))
+            })
+            .sum()
+    }
+
+    /// Adds `count` copies of `item`, topping up its stacks before using
+    /// empty unrestricted slots. Nothing is added unless all of it fits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the item is unknown, would exceed the weight
+    /// limit, or does not fit.
+    pub fn add(
+        &mut self,
+        library: &GameplayLibrary,
+        item: &str,
+        count: u32,
+    ) -> Result<(), InventoryError> {
+        let def = library
+            .item(item)
+            .ok_or_else(|| InventoryError::UnknownItem(item.to_owned()))?;
+        self.check_weight(library, item_weight(def.weight, count))?;
+
+        let space: u64 = self
+            .slots
+            .iter()
+            .zip(&self.restrictions)
+            .map(|(stack, restriction)| match stack {
+                Some(stack) if stack.item == item => def.max_stack.saturating_sub(stack.count),
+                None if restriction.is_none() => def.max_stack,
+                _ => 0,
+            })
+            .map(u64::from)
+            .sum();
+        if space < u64::from(count) {
+            return Err(InventoryError::Full);
+        }
+
+        let mut left = count;
+        for stack in self.slots.iter_mut().flatten() {
+            if stack.item == item {
+                let moved = left.min(def.max_stack.saturating_sub(stack.count));
+                stack.count += moved;
+                left -= moved;
+            }
+        }
+        for (slot, restriction) in self.slots.iter_mut().zip(&self.restrictions) {
+            if left == 0 {
+                break;
+            }
+            if slot.is_none() && restriction.is_none() {
+                let moved = left.min(def.max_stack);
+                *slot = Some(ItemStack::new(item, moved));
+                left -= moved;
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes `count` copies of `item`, from the last slots first. Nothing
+    /// is removed unless there are enough.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than `count` copies.
+    pub fn remove(&mut self, item: &str, count: u32) -> Result<(), InventoryError> {
+        if self.count(item) < count {
+            return Err(InventoryError::NotEnough(item.to_owned()));
+        }
+        let mut left = count;
+        for slot in self.slots.iter_mut().rev() {
+            if left == 0 {
+                break;
+            }
+            if let Some(stack) = slot.as_mut().filter(|stack| stack.item == item) {
+                let taken = left.min(stack.count);
+                stack.count -= taken;
+                left -= taken;
+                if stack.count == 0 {
+                    *slot = None;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes `count` copies from `slot` and returns them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slot is missing or empty, or `count` is 0 or
+    /// more than it holds.
+    pub fn take(&mut self, slot: usize, count: u32) -> Result<ItemStack, InventoryError> {
+        let stack = self.stack_mut(slot)?;
+        if count == 0 || count > stack.count {
+            return Err(InventoryError::InvalidCount);
+        }
+        stack.count -= count;
+        let taken = ItemStack::new(stack.item.clone(), count);
+        if stack.count == 0 {
+            self.slots[slot] = None;
+        }
+        Ok(taken)
+    }
+
+    /// Moves `count` copies from `slot` into the empty slot `to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either slot is missing, `slot` is empty, `to` is
+    /// occupied or does not accept the item, or `count` is 0 or more than
+    /// `slot` holds.
+    pub fn split(
+        &mut self,
+        library: &GameplayLibrary,
+        slot: usize,
+        count: u32,
+        to: usize,
+    ) -> Result<(), InventoryError> {
+        let item = self.stack_mut(slot)?.item.clone();
+        self.accepts(library, to, &item)?;
+        if self.slots[to].is_some() {
+            return Err(InventoryError::Occupied(to));
+        }
+        let taken = self.take(slot, count)?;
+        self.slots[to] = Some(taken);
+        Ok(())
+    }
+
+    /// Moves as many copies from `from` onto the stack in `to` as fit, or
+    /// the whole stack if `to` is empty, and returns how many moved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either slot is missing, `from` is empty, the
+    /// slots are the same or hold different items, `to` does not accept the
+    /// item, or its stack is full.
+    pub fn merge(
+        &mut self,
+        library: &GameplayLibrary,
+        from: usize,
+        to: usize,
+    ) -> Result<u32, InventoryError> {
+        let source = self.stack_mut(from)?.clone();
+        self.accepts(library, to, &source.item)?;
+        if from == to {
+            return Err(InventoryError::Mismatch);
+        }
+        let max_stack = library
+            .item(&source.item)
+            .map_or(u32::MAX, |def| def.max_stack);
+        let moved = match &self.slots[to] {
+            None => source.count,
+            Some(target) if target.item == source.item => {
+                source.count.min(max_stack.saturating_sub(target.count))
+            }
+            Some(_) => return Err(InventoryError::Mismatch),
+        };
+        if moved == 0 {
+            return Err(InventoryError::Full);
+        }
+        self.take(from, moved)?;
+        match &mut self.slots[to] {
+            Some(target) => target.count += moved,
+            empty => *empty = Some(ItemStack::new(source.item, moved)),
+        }
+        Ok(moved)
+    }
+
+    /// Moves `count` copies from `slot` of `from` into `to`, as
+    /// [`add`](Self::add) places them, and returns what moved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the slot is missing or empty, `count` is invalid,
+    /// or the items do not fit in `to`. Neither inventory changes then.
+    pub fn transfer(
+        library: &GameplayLibrary,
+        from: &mut Self,
+        slot: usize,
+        count: u32,
+        to: &mut Self,
+    ) -> Result<ItemStack, InventoryError> {
+        let stack = from.stack_mut(slot)?;
+        if count == 0 || count > stack.count {
+            return Err(InventoryError::InvalidCount);
+        }
+        let item = stack.item.clone();
+        to.add(library, &item, count)?;
+        from.take(slot, count)
+    }
+
+    /// Removes the stacks of items the library no longer has, e.g. after
+    /// loading an old save, and returns them.
+    pub fn retain_known(&mut self, library: &GameplayLibrary) -> Vec<ItemStack> {
+        self.slots
+            .iter_mut()
+            .filter(|slot| {
+                slot.as_ref()
+                    .is_some_and(|stack| library.item(&stack.item).is_none())
+            })
+            .filter_map(Option::take)
+            .collect()
+    }
+
+    /// Returns the stack in `slot`.
+    fn stack_mut(&mut self, slot: usize) -> Result<&mut ItemStack, InventoryError> {
+        self.slots
+            .get_mut(slot)
+            .ok_or(InventoryError::NoSlot(slot))?
+            .as_mut()
+            .ok_or(InventoryError::EmptySlot(slot))
+    }
+
+    /// Checks that `slot` exists and accepts `item`.
+    fn accepts(
+        &self,
+        library: &GameplayLibrary,
+        slot: usize,
+        item: &str,
+    ) -> Result<(), InventoryError> {
+        let restriction = self
+            .restrictions
+            .get(slot)
+            .ok_or(InventoryError::NoSlot(slot))?;
+        match restriction {
+            Some(tag) if !library.item(item).is_some_and(|def| def.has_tag(tag)) => {
+                Err(InventoryError::Restricted {
+                    slot,
+                    tag: tag.clone(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that `added` more weight stays within the limit.
+    fn check_weight(&self, library: &GameplayLibrary, added: f32) -> Result<(), InventoryError> {
+        match self.max_weight {
+            Some(max) if self.weight(library) + added > max => Err(InventoryError::TooHeavy),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn item_weight(weight: f32, count: u32) -> f32 {
+    weight * count as f32
+}
+
+/// An inventory as saved: its occupied slots and restrictions by index.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SavedInventory {
+    slots: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_weight: Option<f32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    restrictions: Vec<SavedRestriction>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    items: Vec<SavedStack>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SavedRestriction {
+    slot: usize,
+    tag: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SavedStack {
+    slot: usize,
+    item: String,
+    count: u32,
+}
+
+impl From<Inventory> for SavedInventory {
+    fn from(inventory: Inventory) -> Self {
+        Self {
+            slots: inventory.slots.len(),
+            max_weight: inventory.max_weight,
+            restrictions: inventory
+                .restrictions
+                .into_iter()
+                .enumerate()
+                .filter_map(|(slot, tag)| Some(SavedRestriction { slot, tag: tag? }))
+                .collect(),
+            items: inventory
+                .slots
+                .into_iter()
+                .enumerate()
+                .filter_map(|(slot, stack)| {
+                    let ItemStack { item, count } = stack?;
+                    Some(SavedStack { slot, item, count })
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<SavedInventory> for Inventory {
+    type Error = String;
+
+    fn try_from(saved: SavedInventory) -> Result<Self, Self::Error> {
+        let mut inventory = Self::new(saved.slots);
+        inventory.max_weight = saved.max_weight;
+        for SavedRestriction { slot, tag } in saved.restrictions {
+            *inventory
+                .restrictions
+                .get_mut(slot)
+                .ok_or_else(|| format!("restriction of missing slot {slot}"))? = Some(tag);
+        }
+        for SavedStack { slot, item, count } in saved.items {
+            let target = inventory
+                .slots
+                .get_mut(slot)
+                .ok_or_else(|| format!("item in missing slot {slot}"))?;
+            if target.is_some() {
+                return Err(format!("slot {slot} saved twice"));
+            }
+            if count == 0 {
+                return Err(format!("empty stack of `{item}` in slot {slot}"));
+            }
+            *target = Some(ItemStack { item, count });
+        }
+        Ok(inventory)
+    }
+}
+
+/// A change to the inventories of entities, answered with
+/// [`InventoryEvent`]s by [`update_inventories`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InventoryOp {
+    /// Adds items, e.g. a pickup or a reward.
+    Add {
+        /// Entity with the inventory.
+        entity: Entity,
+        /// Item id.
+        item: String,
+        /// Number of copies.
+        count: u32,
+    },
+    /// Removes items, e.g. used or sold ones.
+    Remove {
+        /// Entity with the inventory.
+        entity: Entity,
+        /// Item id.
+        item: String,
+        /// Number of copies.
+        count: u32,
+    },
+    /// Moves items from a slot to another entity's inventory.
+    Transfer {
+        /// Entity giving the items.
+        from: Entity,
+        /// Slot of `from` holding the items.
+        slot: usize,
+        /// Number of copies.
+        count: u32,
+        /// Entity receiving the items.
+        to: Entity,
+    },
+    /// Moves part of a stack into an empty slot.
+    Split {
+        /// Entity with the inventory.
+        entity: Entity,
+        /// Slot holding the stack.
+        slot: usize,
+        /// Number of copies moved.
+        count: u32,
+        /// Empty slot receiving them.
+        to: usize,
+    },
+    /// Moves a stack onto another of the same item.
+    Merge {
+        /// Entity with the inventory.
+        entity: Entity,
+        /// Slot holding the moved stack.
+        from: usize,
+        /// Slot receiving it.
+        to: usize,
+    },
+}
+
+/// Outcome of an [`InventoryOp`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InventoryEvent {
+    /// Items were added.
+    Added {
+        /// Entity with the inventory.
+        entity: Entity,
+        /// Item id.
+        item: String,
+        /// Number of copies.
+        count: u32,
+    },
+    /// Items were removed.
+    Removed {
+        /// Entity with the inventory.
+        entity: Entity,
+        /// Item id.
+        item: String,
+        /// Number of copies.
+        count: u32,
+    },
+    /// Items moved between entities.
+    Transferred {
+        /// Entity that gave the items.
+        from: Entity,
+        /// Entity that received them.
+        to: Entity,
+        /// Item id.
+        item: String,
+        /// Number of copies.
+        count: u32,
+    },
+    /// Part of a stack moved to another slot.
+    Split {
+        /// Entity with the inventory.
+        entity: Entity,
+        /// Item id.
+        item: String,
+        /// Slot the copies left.
+        from: usize,
+        /// Slot they moved to.
+        to: usize,
+        /// Number of copies.
+        count: u32,
+    },
+    /// Copies moved onto another stack.
+    Merged {
+        /// Entity with the inventory.
+        entity: Entity,
+        /// Item id.
+        item: String,
+        /// Slot the copies left.
+        from: usize,
+        /// Slot they moved to.
+        to: usize,
+        /// Number of copies.
+        count: u32,
+    },
+    /// The operation was refused and nothing changed.
+    Failed {
+        /// The refused operation.
+        op: InventoryOp,
+        /// Why.
+        error: InventoryError,
+    },
+}
+
+/// Applies the [`InventoryOp`]s sent since the last update, in order, and
+/// sends the outcome to the world's `Events<InventoryEvent>`, created if
+/// missing, which is advanced a frame first.
+pub fn update_inventories(world: &mut World, library: &GameplayLibrary) {
+    let ops: Vec<InventoryOp> = world
+        .resource_mut::<Events<InventoryOp>>()
+        .map(|events| events.drain().collect())
+        .unwrap_or_default();
+    let out: Vec<InventoryEvent> = ops
+        .into_iter()
+        .map(|op| {
+            apply(world, library, op.clone())
+                .unwrap_or_else(|error| InventoryEvent::Failed { op, error })
+        })
+        .collect();
+
+    if world.resource::<Events<InventoryEvent>>().is_none() {
+        world.insert_resource(Events::<InventoryEvent>::default());
+    }
+    if let Some(events) = world.resource_mut::<Events<InventoryEvent>>() {
+        events.update();
+        events.send_batch(out);
+    }
+}
+
+/// Returns the inventory of `entity`.
+fn inventory_of(world: &mut World, entity: Entity) -> Result<&mut Inventory, InventoryError> {
+    world
+        .get_mut::<Inventory>(entity)
+        .ok_or(InventoryError::NoInventory(entity))
+}
+
+/// Applies one operation.
+fn apply(
+    world: &mut World,
+    library: &GameplayLibrary,
+    op: InventoryOp,
+) -> Result<InventoryEvent, InventoryError> {
+    match op {
+        InventoryOp::Add {
+            entity,
+            item,
+            count,
+        } => {
+            inventory_of(world, entity)?.add(library, &item, count)?;
+            Ok(InventoryEvent::Added {
+                entity,
+                item,
+                count,
+            })
+        }
+        InventoryOp::Remove {
+            entity,
+            item,
+            count,
+        } => {
+            inventory_of(world, entity)?.remove(&item, count)?;
+            Ok(InventoryEvent::Removed {
+                entity,
+                item,
+                count,
+            })
+        }
+        InventoryOp::Transfer {
+            from,
+            slot,
+            count,
+            to,
+        } => {
+            if from == to {
+                return Err(InventoryError::SameInventory);
+            }
+            inventory_of(world, to)?;
+            let mut source = world
+                .remove::<Inventory>(from)
+                .ok_or(InventoryError::NoInventory(from))?;
+            let moved = inventory_of(world, to)
+                .and_then(|target| Inventory::transfer(library, &mut source, slot, count, target));
+            world.insert(from, source);
+            let ItemStack { item, count } = moved?;
+            Ok(InventoryEvent::Transferred {
+                from,
+                to,
+                item,
+                count,
+            })
+        }
+        InventoryOp::Split {
+            entity,
+            slot,
+            count,
+            to,
+        } => {
+            let inventory = inventory_of(world, entity)?;
+            inventory.split(library, slot, count, to)?;
+            let item = inventory
+                .slot(to)
+                .map(|stack| stack.item.clone())
+                .unwrap_or_default();
+            Ok(InventoryEvent::Split {
+                entity,
+                item,
+                from: slot,
+                to,
+                count,
+            })
+        }
+        InventoryOp::Merge { entity, from, to } => {
+            let inventory = inventory_of(world, entity)?;
+            let count = inventory.merge(library, from, to)?;
+            let item = inventory
+                .slot(to)
+                .map(|stack| stack.item.clone())
+                .unwrap_or_default();
+            Ok(InventoryEvent::Merged {
+                entity,
+                item,
+                from,
+                to,
+                count,
+            })
+        }
+    }
+}
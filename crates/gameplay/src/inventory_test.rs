@@ -0,0 +1,272 @@
+//! Unit tests for inventories.
+
+use crate::inventory::{
+    update_inventories, Inventory, InventoryError, InventoryEvent, InventoryOp, ItemStack,
+};
+use crate::library::GameplayLibrary;
+use ecs::{Events, World};
+
+const ITEMS: &str = r#"
+[items.arrow]
+name = "Arrow"
+max_stack = 50
+weight = 0.1
+
+[items.sword]
+name = "Iron Sword"
+icon = "icons/iron_sword.png"
+weight = 4.0
+tags = ["weapon"]
+stats = { damage = 12 }
+
+[items.potion]
+name = "Potion"
+max_stack = 5
+weight = 0.5
+tags = ["consumable"]
+"#;
+
+fn library() -> GameplayLibrary {
+    GameplayLibrary::from_toml(ITEMS).unwrap()
+}
+
+fn stacks(inventory: &Inventory) -> Vec<(usize, &str, u32)> {
+    inventory
+        .iter()
+        .map(|(slot, stack)| (slot, stack.item.as_str(), stack.count))
+        .collect()
+}
+
+/// Verifies adding tops up existing stacks before filling empty
+/// unrestricted slots, and adds nothing unless everything fits.
+#[test]
+fn add_tops_up_stacks_then_fills_slots() {
+    let library = library();
+    let mut inventory = Inventory::new(4).with_restriction(0, "weapon");
+    inventory.add(&library, "potion", 3).unwrap();
+    inventory.add(&library, "potion", 4).unwrap();
+    assert_eq!(stacks(&inventory), [(1, "potion", 5), (2, "potion", 2)]);
+
+    assert_eq!(
+        inventory.add(&library, "arrow", 51),
+        Err(InventoryError::Full)
+    );
+    assert_eq!(inventory.count("arrow"), 0);
+    assert_eq!(
+        inventory.add(&library, "bomb", 1),
+        Err(InventoryError::UnknownItem("bomb".to_owned()))
+    );
+    assert_eq!(library.item("sword").unwrap().stat("damage"), Some(12.0));
+}
+
+/// Verifies the weight limit refuses items that would exceed it.
+#[test]
+fn weight_limit_refuses_heavy_items() {
+    let library = library();
+    let mut inventory = Inventory::new(10).with_max_weight(10.0);
+    inventory.add(&library, "sword", 1).unwrap();
+    inventory.add(&library, "sword", 1).unwrap();
+    assert_eq!(
+        inventory.add(&library, "sword", 1),
+        Err(InventoryError::TooHeavy)
+    );
+    inventory.add(&library, "arrow", 20).unwrap();
+    assert!((inventory.weight(&library) - 10.0).abs() < 1e-4);
+}
+
+/// Verifies removing takes from the last slots and needs enough copies.
+#[test]
+fn remove_takes_from_last_slots() {
+    let library = library();
+    let mut inventory = Inventory::new(3);
+    inventory.add(&library, "potion", 12).unwrap();
+    inventory.remove("potion", 4).unwrap();
+    assert_eq!(stacks(&inventory), [(0, "potion", 5), (1, "potion", 3)]);
+    assert_eq!(
+        inventory.remove("potion", 9),
+        Err(InventoryError::NotEnough("potion".to_owned()))
+    );
+    inventory.remove("potion", 8).unwrap();
+    assert!(inventory.is_empty());
+}
+
+/// Verifies splitting and merging stacks within an inventory, and the slot
+/// checks they make.
+#[test]
+fn split_and_merge_stacks() {
+    let library = library();
+    let mut inventory = Inventory::new(4).with_restriction(3, "weapon");
+    inventory.add(&library, "potion", 5).unwrap();
+
+    inventory.split(&library, 0, 2, 2).unwrap();
+    assert_eq!(stacks(&inventory), [(0, "potion", 3), (2, "potion", 2)]);
+    assert_eq!(
+        inventory.split(&library, 0, 1, 2),
+        Err(InventoryError::Occupied(2))
+    );
+    assert_eq!(
+        inventory.split(&library, 0, 3, 3),
+        Err(InventoryError::Restricted {
+            slot: 3,
+            tag: "weapon".to_owned()
+        })
+    );
+    assert_eq!(
+        inventory.split(&library, 0, 4, 1),
+        Err(InventoryError::InvalidCount)
+    );
+    assert_eq!(
+        inventory.split(&library, 1, 1, 2),
+        Err(InventoryError::EmptySlot(1))
+    );
+
+    assert_eq!(inventory.merge(&library, 2, 0), Ok(2));
+    assert_eq!(stacks(&inventory), [(0, "potion", 5)]);
+    inventory.add(&library, "potion", 1).unwrap();
+    assert_eq!(inventory.merge(&library, 1, 0), Err(InventoryError::Full));
+    inventory.add(&library, "sword", 1).unwrap();
+    assert_eq!(
+        inventory.merge(&library, 2, 0),
+        Err(InventoryError::Mismatch)
+    );
+    assert_eq!(inventory.merge(&library, 2, 3), Ok(1));
+    assert_eq!(inventory.slot(3), Some(&ItemStack::new("sword", 1)));
+    assert_eq!(
+        inventory.merge(&library, 0, 9),
+        Err(InventoryError::NoSlot(9))
+    );
+}
+
+/// Verifies a transfer moves items only if they all fit.
+#[test]
+fn transfer_is_all_or_nothing() {
+    let library = library();
+    let mut chest = Inventory::new(2);
+    chest.add(&library, "arrow", 80).unwrap();
+    let mut bag = Inventory::new(1);
+
+    assert_eq!(
+        Inventory::transfer(&library, &mut chest, 0, 50, &mut bag),
+        Ok(ItemStack::new("arrow", 50))
+    );
+    assert_eq!(
+        Inventory::transfer(&library, &mut chest, 1, 30, &mut bag),
+        Err(InventoryError::Full)
+    );
+    assert_eq!(chest.count("arrow"), 30);
+    assert_eq!(bag.count("arrow"), 50);
+}
+
+/// Verifies an inventory survives a save through TOML, and items removed
+/// from the game are dropped after loading.
+#[test]
+fn saves_and_loads() {
+    let mut library = library();
+    let mut inventory = Inventory::new(4)
+        .with_max_weight(30.0)
+        .with_restriction(0, "weapon");
+    inventory.add(&library, "arrow", 60).unwrap();
+    inventory.add(&library, "potion", 2).unwrap();
+
+    let saved = toml::to_string(&inventory).unwrap();
+    let mut loaded: Inventory = toml::from_str(&saved).unwrap();
+    assert_eq!(loaded, inventory);
+    assert_eq!(loaded.restriction(0), Some("weapon"));
+
+    library.items.remove("potion");
+    assert_eq!(loaded.retain_known(&library), [ItemStack::new("potion", 2)]);
+    assert_eq!(stacks(&loaded), [(1, "arrow", 50), (2, "arrow", 10)]);
+}
+
+/// Verifies saves naming missing or repeated slots are refused.
+#[test]
+fn refuses_invalid_saves() {
+    for saved in [
+        "slots = 1\n[[items]]\nslot = 1\nitem = \"arrow\"\ncount = 1",
+        "slots = 2\n[[items]]\nslot = 0\nitem = \"arrow\"\ncount = 1\n[[items]]\nslot = 0\nitem = \"potion\"\ncount = 1",
+        "slots = 1\n[[items]]\nslot = 0\nitem = \"arrow\"\ncount = 0",
+        "slots = 1\n[[restrictions]]\nslot = 3\ntag = \"weapon\"",
+    ] {
+        assert!(toml::from_str::<Inventory>(saved).is_err(), "{saved}");
+    }
+}
+
+/// Verifies operations sent as events are applied in order and answered
+/// with events.
+#[test]
+fn ops_are_answered_with_events() {
+    let library = library();
+    let mut world = World::default();
+    let player = world.spawn();
+    world.insert(player, Inventory::new(2));
+    let chest = world.spawn();
+    world.insert(chest, Inventory::new(2));
+    let rock = world.spawn();
+
+    let ops = [
+        InventoryOp::Add {
+            entity: chest,
+            item: "potion".to_owned(),
+            count: 4,
+        },
+        InventoryOp::Transfer {
+            from: chest,
+            slot: 0,
+            count: 3,
+            to: player,
+        },
+        InventoryOp::Split {
+            entity: player,
+            slot: 0,
+            count: 1,
+            to: 1,
+        },
+        InventoryOp::Transfer {
+            from: chest,
+            slot: 0,
+            count: 1,
+            to: rock,
+        },
+    ];
+    let mut events = Events::default();
+    events.send_batch(ops.clone());
+    world.insert_resource(events);
+    update_inventories(&mut world, &library);
+
+    let events: Vec<InventoryEvent> = world
+        .resource::<Events<InventoryEvent>>()
+        .unwrap()
+        .iter()
+        .cloned()
+        .collect();
+    assert_eq!(
+        events,
+        [
+            InventoryEvent::Added {
+                entity: chest,
+                item: "potion".to_owned(),
+                count: 4,
+            },
+            InventoryEvent::Transferred {
+                from: chest,
+                to: player,
+                item: "potion".to_owned(),
+                count: 3,
+            },
+            InventoryEvent::Split {
+                entity: player,
+                item: "potion".to_owned(),
+                from: 0,
+                to: 1,
+                count: 1,
+            },
+            InventoryEvent::Failed {
+                op: ops[3].clone(),
+                error: InventoryError::NoInventory(rock),
+            },
+        ]
+    );
+    let inventory = world.get::<Inventory>(player).unwrap();
+    assert_eq!(stacks(inventory), [(0, "potion", 2), (1, "potion", 1)]);
+    assert_eq!(world.get::<Inventory>(chest).unwrap().count("potion"), 1);
+}
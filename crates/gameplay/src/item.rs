@@ -0,0 +1,58 @@
+//! Item definitions.
+//!
+//! An [`ItemDef`] asset describes everything that is the same for every
+//! copy of an item; inventories only hold item ids and counts.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// An item asset.
+///
+/// ```toml
+/// [items.health_potion]
+/// name = "Health Potion"
+/// icon = "icons/health_potion.png"
+/// max_stack = 20
+/// weight = 0.5
+/// tags = ["consumable"]
+/// stats = { heal = 50 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ItemDef {
+    /// Name shown to players, or a localization key the UI looks up.
+    pub name: String,
+    /// Asset path of the icon.
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// Most copies in one inventory slot.
+    #[serde(default = "one")]
+    pub max_stack: u32,
+    /// Weight of one copy, counted against inventory weight limits.
+    #[serde(default)]
+    pub weight: f32,
+    /// Tags for filtering and slot restrictions, e.g. `weapon`.
+    #[serde(default)]
+    pub tags: BTreeSet<String>,
+    /// Numbers game rules read, e.g. `damage` or `price`.
+    #[serde(default)]
+    pub stats: BTreeMap<String, f32>,
+}
+
+fn one() -> u32 {
+    1
+}
+
+impl ItemDef {
+    /// Returns `true` if the item has `tag`.
+    #[must_use]
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(tag)
+    }
+
+    /// Returns stat `name`, or `None` if the item has none.
+    #[must_use]
+    pub fn stat(&self, name: &str) -> Option<f32> {
+        self.stats.get(name).copied()
+    }
+}
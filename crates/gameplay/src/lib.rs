@@ -0,0 +1,86 @@
+//! Gameplay framework for the Rustgine game engine.
+//!
+//! This crate provides data-driven RPG scaffolding: designers describe
+//! abilities, status effects, items, dialogues, and quests in TOML assets,
+//! and game code drives them through ECS events.
+//!
+//! # Overview
+//!
+//! The gameplay crate handles:
+//! - Stats with flat and percent modifiers, timed or permanent, and pools
+//!   such as health and mana ([`Stats`])
+//! - Abilities with cast times, cooldowns, and costs ([`Abilities`])
+//! - Stacking status effects with stat modifiers and periodic changes
+//!   ([`StatusEffects`])
+//! - Item definitions with stats, stack sizes, icons, and tags ([`ItemDef`])
+//! - Inventories with slot and weight limits that save with serde
+//!   ([`Inventory`])
+//! - The ability, effect, item, and quest assets ([`GameplayLibrary`])
+//! - Branching dialogues with script commands, conditional choices, and
+//!   localized text ([`DialogueRunner`])
+//! - Quests with stages and objectives that count progress events or wait
+//!   for conditions, tracked in a saveable log ([`QuestLog`])
+//! - Handling cast, effect, inventory, dialogue, and quest events each
+//!   frame ([`update_gameplay`])
+//!
+//! # Example
+//!
+//! ```ignore
+//! use gameplay::RustgineGameplay;
+//! use rustgine_core::RustgineSystem;
+//!
+//! let mut gameplay = RustgineGameplay::new(Arc::new(library));
+//! gameplay.startup()?;
+//! ```
+
+#![warn(missing_docs)]
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+pub mod ability;
+#[cfg(test)]
+mod ability_test;
+pub mod dialogue;
+#[cfg(test)]
+mod dialogue_test;
+pub mod effect;
+pub mod gameplay;
+pub mod inventory;
+#[cfg(test)]
+mod inventory_test;
+pub mod item;
+pub mod library;
+#[cfg(test)]
+mod library_test;
+pub mod quest;
+pub mod quest_log;
+#[cfg(test)]
+mod quest_log_test;
+pub mod stats;
+#[cfg(test)]
+mod stats_test;
+pub mod update;
+#[cfg(test)]
+mod update_test;
+
+pub use ability::{Abilities, AbilityDef, Cast, CastError};
+pub use dialogue::{
+    update_dialogue, Dialogue, DialogueChoice, DialogueError, DialogueEvent, DialogueNode,
+    DialogueRequest, DialogueRunner,
+};
+pub use effect::{ActiveEffect, EffectDef, Periodic, StatusEffects};
+pub use gameplay::RustgineGameplay;
+pub use inventory::{
+    update_inventories, Inventory, InventoryError, InventoryEvent, InventoryOp, ItemStack,
+};
+pub use item::ItemDef;
+pub use library::GameplayLibrary;
+pub use quest::{Objective, QuestDef, QuestStage};
+pub use quest_log::{
+    update_quests, QuestConditions, QuestError, QuestEvent, QuestLog, QuestProgress, QuestRequest,
+    QuestState, QuestStatus,
+};
+pub use stats::{StatModifier, Stats};
+pub use update::{
+    update_gameplay, ApplyEffect, CastRequest, GameplayEvent, InterruptCast, RemoveEffect,
+};
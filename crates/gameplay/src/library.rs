@@ -0,0 +1,226 @@
+//! The ability, effect, item, and quest assets of a game.
+//!
+//! Designers describe abilities, status effects, items, and quests in TOML,
+//! and the
+//! game loads them into a [`GameplayLibrary`]:
+//!
+//! ```toml
+//! [abilities.fireball]
+//! cast_time = 1.5
+//! cooldown = 4.0
+//! cost = { mana = 20 }
+//! target_effects = ["burning"]
+//!
+//! [abilities.war_cry]
+//! cooldown = 30.0
+//! self_effects = ["enraged"]
+//!
+//! [effects.burning]
+//! duration = 3.0
+//! max_stacks = 3
+//! periodic = { stat = "health", amount = -5, interval = 1.0 }
+//!
+//! [effects.enraged]
+//! duration = 10.0
+//! modifiers = [{ stat = "damage", percent = 50 }, { stat = "armor", flat = -5 }]
+//!
+//! [items.iron_sword]
+//! name = "Iron Sword"
+//! icon = "icons/iron_sword.png"
+//! weight = 4.0
+//! tags = ["weapon"]
+//! stats = { damage = 12 }
+//!
+//! [quests.wolf_hunt]
+//! name = "Wolf Hunt"
+//! [[quests.wolf_hunt.stages]]
+//! objectives = [{ description = "Hunt wolves", event = "kill", subject = "wolf", count = 5 }]
+//! ```
+
+use crate::ability::AbilityDef;
+use crate::effect::EffectDef;
+use crate::item::ItemDef;
+use crate::quest::QuestDef;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Abilities, status effects, items, and quests by id.
+///
+/// # Example
+///
+/// ```
+/// use gameplay::GameplayLibrary;
+///
+/// let library = GameplayLibrary::from_toml(r#"
+///     [abilities.heal]
+///     cost = { mana = 10 }
+///     target_effects = ["mending"]
+///
+///     [effects.mending]
+///     duration = 5.0
+///     periodic = { stat = "health", amount = 4, interval = 1.0 }
+/// "#)?;
+/// assert_eq!(library.ability("heal").unwrap().cost["mana"], 10.0);
+/// assert!(library.effect("mending").is_some());
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GameplayLibrary {
+    /// Abilities by id.
+    #[serde(default)]
+    pub abilities: BTreeMap<String, AbilityDef>,
+    /// Status effects by id.
+    #[serde(default)]
+    pub effects: BTreeMap<String, EffectDef>,
+    /// Items by id.
+    #[serde(default)]
+    pub items: BTreeMap<String, ItemDef>,
+    /// Quests by id.
+    #[serde(default)]
+    pub quests: BTreeMap<String, QuestDef>,
+}
+
+impl GameplayLibrary {
+    /// Parses and validates a library.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a valid library, or it fails
+    /// [`validate`](Self::validate).
+    pub fn from_toml(text: &str) -> anyhow::Result<Self> {
+        let library: Self =
+            toml::from_str(text).map_err(|e| anyhow::anyhow!("invalid gameplay library: {e}"))?;
+        library.validate()?;
+        Ok(library)
+    }
+
+    /// Serializes the library to TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_toml(&self) -> anyhow::Result<String> {
+        toml::to_string(self)
+            .map_err(|e| anyhow::anyhow!("failed to serialize gameplay library: {e}"))
+    }
+
+    /// Adds every ability, effect, item, and quest of `other`, replacing those
+    /// with the same id, e.g. to combine one file per class.
+    pub fn merge(&mut self, other: Self) {
+        self.abilities.extend(other.abilities);
+        self.effects.extend(other.effects);
+        self.items.extend(other.items);
+        self.quests.extend(other.quests);
+    }
+
+    /// Returns ability `id`.
+    #[must_use]
+    pub fn ability(&self, id: &str) -> Option<&AbilityDef> {
+        self.abilities.get(id)
+    }
+
+    /// Returns effect `id`.
+    #[must_use]
+    pub fn effect(&self, id: &str) -> Option<&EffectDef> {
+        self.effects.get(id)
+    }
+
+    /// Returns item `id`.
+    #[must_use]
+    pub fn item(&self, id: &str) -> Option<&ItemDef> {
+        self.items.get(id)
+    }
+
+    /// Returns quest `id`.
+    #[must_use]
+    pub fn quest(&self, id: &str) -> Option<&QuestDef> {
+        self.quests.get(id)
+    }
+
+    /// Checks that times, costs, and weights are finite and not negative,
+    /// intervals and stack limits are positive, effect modifiers are not
+    /// timed, abilities only apply effects in the library, and quests have
+    /// stages, each with objectives that count an event or wait for a
+    /// condition.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first invalid ability, effect, item, or
+    /// quest.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let non_negative = |value: f32| value.is_finite() && value >= 0.0;
+        for (id, ability) in &self.abilities {
+            if !non_negative(ability.cast_time) || !non_negative(ability.cooldown) {
+                anyhow::bail!("ability `{id}` has a negative or infinite time");
+            }
+            if let Some((stat, _)) = ability.cost.iter().find(|(_, cost)| !non_negative(**cost)) {
+                anyhow::bail!("ability `{id}` has an invalid {stat} cost");
+            }
+            if let Some(effect) = ability
+                .target_effects
+                .iter()
+                .chain(&ability.self_effects)
+                .find(|effect| !self.effects.contains_key(*effect))
+            {
+                anyhow::bail!("ability `{id}` applies unknown effect `{effect}`");
+            }
+        }
+        for (id, effect) in &self.effects {
+            if effect
+                .duration
+                .is_some_and(|duration| !non_negative(duration))
+            {
+                anyhow::bail!("effect `{id}` has a negative or infinite duration");
+            }
+            if effect.max_stacks == 0 {
+                anyhow::bail!("effect `{id}` allows no stacks");
+            }
+            if effect
+                .modifiers
+                .iter()
+                .any(|modifier| modifier.duration.is_some())
+            {
+                anyhow::bail!("effect `{id}` has a timed modifier; effects time their modifiers");
+            }
+            if let Some(periodic) = &effect.periodic {
+                if !(periodic.interval.is_finite() && periodic.interval > 0.0) {
+                    anyhow::bail!("effect `{id}` has a periodic interval that is not positive");
+                }
+            }
+        }
+        for (id, item) in &self.items {
+            if item.max_stack == 0 {
+                anyhow::bail!("item `{id}` has a max stack of 0");
+            }
+            if !non_negative(item.weight) {
+                anyhow::bail!("item `{id}` has a negative or infinite weight");
+            }
+        }
+        for (id, quest) in &self.quests {
+            if quest.stages.is_empty() {
+                anyhow::bail!("quest `{id}` has no stages");
+            }
+            for (index, stage) in quest.stages.iter().enumerate() {
+                if stage.objectives.is_empty() {
+                    anyhow::bail!("quest `{id}` stage {index} has no objectives");
+                }
+                for objective in &stage.objectives {
+                    if objective.event.is_some() == objective.condition.is_some() {
+                        anyhow::bail!(
+                            "quest `{id}` objective `{}` needs either an event or a condition",
+                            objective.description
+                        );
+                    }
+                    if objective.count == 0 {
+                        anyhow::bail!(
+                            "quest `{id}` objective `{}` has a count of 0",
+                            objective.description
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+//! Unit tests for the gameplay asset library.
+
+use crate::library::GameplayLibrary;
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+}
+
+const LIBRARY: &str = r#"
+[abilities.fireball]
+cast_time = 1.5
+cooldown = 4.0
+cost = { mana = 20 }
+target_effects = ["burning"]
+
+[abilities.war_cry]
+self_effects = ["enraged"]
+
+[effects.burning]
+duration = 3.0
+max_stacks = 3
+periodic = { stat = "health", amount = -5, interval = 1.0 }
+
+[effects.enraged]
+modifiers = [{ stat = "damage", percent = 50 }]
+"#;
+
+/// Verifies abilities and effects are read with their defaults.
+#[test]
+fn reads_abilities_and_effects() {
+    let library = GameplayLibrary::from_toml(LIBRARY).unwrap();
+    let fireball = library.ability("fireball").unwrap();
+    assert_close(fireball.cast_time, 1.5);
+    assert_close(fireball.cost["mana"], 20.0);
+    assert_eq!(fireball.target_effects, ["burning"]);
+
+    let war_cry = library.ability("war_cry").unwrap();
+    assert_close(war_cry.cast_time, 0.0);
+    assert!(war_cry.cost.is_empty());
+
+    let burning = library.effect("burning").unwrap();
+    assert_eq!(burning.max_stacks, 3);
+    assert_close(burning.periodic.as_ref().unwrap().amount, -5.0);
+    let enraged = library.effect("enraged").unwrap();
+    assert_eq!(enraged.duration, None);
+    assert_eq!(enraged.max_stacks, 1);
+    assert_close(enraged.modifiers[0].percent, 50.0);
+}
+
+/// Verifies a library survives a round trip through TOML.
+#[test]
+fn round_trips_through_toml() {
+    let library = GameplayLibrary::from_toml(LIBRARY).unwrap();
+    let text = library.to_toml().unwrap();
+    assert_eq!(GameplayLibrary::from_toml(&text).unwrap(), library);
+}
+
+/// Verifies merging replaces entries with the same id.
+#[test]
+fn merge_replaces_same_ids() {
+    let mut library = GameplayLibrary::from_toml(LIBRARY).unwrap();
+    library.merge(
+        GameplayLibrary::from_toml("[abilities.fireball]\ncooldown = 1.0\n[abilities.blink]")
+            .unwrap(),
+    );
+    assert_eq!(library.abilities.len(), 3);
+    assert_close(library.ability("fireball").unwrap().cooldown, 1.0);
+    assert!(library
+        .ability("fireball")
+        .unwrap()
+        .target_effects
+        .is_empty());
+}
+
+/// Verifies invalid assets are refused with the offending id.
+#[test]
+fn refuses_invalid_assets() {
+    for (text, message) in [
+        (
+            "[abilities.a]\ncooldown = -1.0",
+            "ability `a` has a negative",
+        ),
+        ("[abilities.a]\ncost = { mana = -5 }", "invalid mana cost"),
+        (
+            "[abilities.a]\ntarget_effects = [\"nope\"]",
+            "unknown effect `nope`",
+        ),
+        ("[effects.e]\nmax_stacks = 0", "effect `e` allows no stacks"),
+        ("[effects.e]\nduration = -2.0", "effect `e` has a negative"),
+        (
+            "[effects.e]\nmodifiers = [{ stat = \"speed\", flat = 1, duration = 2.0 }]",
+            "timed modifier",
+        ),
+        (
+            "[effects.e]\nperiodic = { stat = \"health\", amount = 1, interval = 0 }",
+            "not positive",
+        ),
+        (
+            "[items.i]\nname = \"I\"\nmax_stack = 0",
+            "item `i` has a max stack of 0",
+        ),
+        (
+            "[items.i]\nname = \"I\"\nweight = -1.0",
+            "item `i` has a negative",
+        ),
+        ("[abilities.a]\nrange = 5", "invalid gameplay library"),
+    ] {
+        let error = GameplayLibrary::from_toml(text).unwrap_err().to_string();
+        assert!(error.contains(message), "{text}: {error}");
+    }
+}
+
+/// Verifies quests without stages or objectives, or with objectives that
+/// neither count events nor wait for a condition, are refused.
+#[test]
+fn refuses_invalid_quests() {
+    for quest in [
+        "[quests.q]\nname = \"Q\"\nstages = []",
+        "[quests.q]\nname = \"Q\"\n[[quests.q.stages]]\nobjectives = []",
+        "[quests.q]\nname = \"Q\"\n[[quests.q.stages]]\nobjectives = [{ description = \"d\" }]",
+        "[quests.q]\nname = \"Q\"\n[[quests.q.stages]]\nobjectives = [{ description = \"d\", event = \"kill\", condition = \"c\" }]",
+        "[quests.q]\nname = \"Q\"\n[[quests.q.stages]]\nobjectives = [{ description = \"d\", event = \"kill\", count = 0 }]",
+    ] {
+        assert!(GameplayLibrary::from_toml(quest).is_err(), "{quest}");
+    }
+}
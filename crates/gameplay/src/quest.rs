@@ -0,0 +1,87 @@
+//! Quest definitions.
+//!
+//! A [`QuestDef`] asset is a list of stages, each with objectives that must
+//! all be done before the next stage starts. An objective either counts
+//! [`QuestProgress`](crate::QuestProgress) events, e.g. five `kill` events
+//! about `wolf`, or waits for a condition game code registers by name in
+//! [`QuestConditions`](crate::QuestConditions). Progress is kept in the
+//! [`QuestLog`](crate::QuestLog).
+
+use serde::{Deserialize, Serialize};
+
+/// A quest asset.
+///
+/// ```toml
+/// [quests.wolf_hunt]
+/// name = "quest.wolf_hunt"
+///
+/// [[quests.wolf_hunt.stages]]
+/// description = "quest.wolf_hunt.hunt"
+/// objectives = [
+///     { description = "quest.wolf_hunt.wolves", event = "kill", subject = "wolf", count = 5 },
+///     { description = "quest.wolf_hunt.pelts", event = "collect", subject = "wolf_pelt", count = 3 },
+/// ]
+///
+/// [[quests.wolf_hunt.stages]]
+/// description = "quest.wolf_hunt.return"
+/// objectives = [{ description = "quest.wolf_hunt.smith", condition = "near smith" }]
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuestDef {
+    /// Name shown to players, or a localization key the UI looks up.
+    pub name: String,
+    /// Stages in order.
+    pub stages: Vec<QuestStage>,
+}
+
+/// A stage of a quest.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuestStage {
+    /// Text shown while the stage is active, or a localization key.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Objectives that complete the stage once all are done.
+    pub objectives: Vec<Objective>,
+}
+
+/// Something the player has to do.
+///
+/// Exactly one of `event` and `condition` is set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Objective {
+    /// Text shown to players, or a localization key.
+    pub description: String,
+    /// Kind of progress event counted, e.g. `kill` or `collect`.
+    #[serde(default)]
+    pub event: Option<String>,
+    /// Subject the counted events must have, or `None` for any.
+    #[serde(default)]
+    pub subject: Option<String>,
+    /// Amount of progress needed.
+    #[serde(default = "one")]
+    pub count: u32,
+    /// Condition that completes the objective once it holds, e.g.
+    /// `near smith`.
+    #[serde(default)]
+    pub condition: Option<String>,
+}
+
+fn one() -> u32 {
+    1
+}
+
+impl Objective {
+    /// Returns `true` if progress event `event` about `subject` counts
+    /// towards the objective.
+    #[must_use]
+    pub fn counts(&self, event: &str, subject: &str) -> bool {
+        self.event.as_deref() == Some(event)
+            && self
+                .subject
+                .as_deref()
+                .is_none_or(|wanted| wanted == subject)
+    }
+}
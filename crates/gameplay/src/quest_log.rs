@@ -0,0 +1,448 @@
+//! Quest progress and the per-frame quest update.
+//!
+//! The [`QuestLog`] resource holds the state of every quest the player has
+//! started and saves with serde. Game code starts and fails quests with
+//! [`QuestRequest`]s and reports what the player does with
+//! [`QuestProgress`] events; [`update_quests`] advances objectives and
+//! stages and answers with [`QuestEvent`]s for the UI.
+
+use crate::library::GameplayLibrary;
+use crate::quest::QuestDef;
+use crate::update::drain;
+use ecs::{Events, World};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use tracing::warn;
+
+/// Whether a quest is still going.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestStatus {
+    /// The quest is in progress.
+    Active,
+    /// Every stage was completed.
+    Completed,
+    /// The quest was failed and can be started again.
+    Failed,
+}
+
+/// Progress of one quest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuestState {
+    /// Whether the quest is still going.
+    pub status: QuestStatus,
+    /// Index of the current stage; the number of stages once completed.
+    pub stage: usize,
+    /// Progress of each objective of the current stage.
+    pub progress: Vec<u32>,
+}
+
+/// Why a quest request was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuestError {
+    /// No quest with this id is in the library.
+    UnknownQuest(String),
+    /// The quest is active or was completed.
+    AlreadyStarted(String),
+    /// The quest is not active.
+    NotActive(String),
+}
+
+impl fmt::Display for QuestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownQuest(id) => write!(f, "unknown quest `{id}`"),
+            Self::AlreadyStarted(id) => write!(f, "quest `{id}` was already started"),
+            Self::NotActive(id) => write!(f, "quest `{id}` is not active"),
+        }
+    }
+}
+
+impl std::error::Error for QuestError {}
+
+/// World resource with the state of every started quest.
+///
+/// # Example
+///
+/// ```
+/// use gameplay::{GameplayLibrary, QuestLog, QuestStatus};
+///
+/// let library = GameplayLibrary::from_toml(r#"
+///     [quests.delivery]
+///     name = "Delivery"
+///     [[quests.delivery.stages]]
+///     objectives = [{ description = "Bring the letter", event = "deliver", subject = "letter" }]
+/// "#)?;
+/// let mut log = QuestLog::default();
+/// log.start(&library, "delivery")?;
+/// assert_eq!(log.status("delivery"), Some(QuestStatus::Active));
+///
+/// let saved = toml::to_string(&log)?;
+/// let loaded: QuestLog = toml::from_str(&saved)?;
+/// assert_eq!(loaded, log);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuestLog {
+    quests: BTreeMap<String, QuestState>,
+}
+
+impl QuestLog {
+    /// Returns the state of quest `id`, or `None` if it was never started.
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&QuestState> {
+        self.quests.get(id)
+    }
+
+    /// Returns the status of quest `id`, or `None` if it was never started.
+    #[must_use]
+    pub fn status(&self, id: &str) -> Option<QuestStatus> {
+        self.quests.get(id).map(|state| state.status)
+    }
+
+    /// Iterates over started quests by id.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &QuestState)> {
+        self.quests.iter().map(|(id, state)| (id.as_str(), state))
+    }
+
+    /// Starts quest `id` at its first stage; failed quests start over.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the quest is not in `library`, or is active or
+    /// completed.
+    pub fn start(&mut self, library: &GameplayLibrary, id: &str) -> Result<(), QuestError> {
+        let quest = library
+            .quest(id)
+            .ok_or_else(|| QuestError::UnknownQuest(id.to_owned()))?;
+        if self
+            .status(id)
+            .is_some_and(|status| status != QuestStatus::Failed)
+        {
+            return Err(QuestError::AlreadyStarted(id.to_owned()));
+        }
+        self.quests.insert(
+            id.to_owned(),
+            QuestState {
+                status: QuestStatus::Active,
+                stage: 0,
+                progress: vec![0; quest.stages[0].objectives.len()],
+            },
+        );
+        Ok(())
+    }
+
+    /// Fails active quest `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the quest is not active.
+    pub fn fail(&mut self, id: &str) -> Result<(), QuestError> {
+        match self.quests.get_mut(id) {
+            Some(state) if state.status == QuestStatus::Active => {
+                state.status = QuestStatus::Failed;
+                Ok(())
+            }
+            _ => Err(QuestError::NotActive(id.to_owned())),
+        }
+    }
+
+    /// Drops quests missing from `library` or whose stage no longer exists,
+    /// e.g. after loading a save made with older assets, and returns their
+    /// ids. Progress of the kept quests is fitted to their objectives.
+    pub fn retain_known(&mut self, library: &GameplayLibrary) -> Vec<String> {
+        let mut dropped = Vec::new();
+        self.quests.retain(|id, state| {
+            let Some(quest) = library.quest(id) else {
+                dropped.push(id.clone());
+                return false;
+            };
+            if state.status != QuestStatus::Active {
+                return true;
+            }
+            if let Some(stage) = quest.stages.get(state.stage) {
+                state.progress.resize(stage.objectives.len(), 0);
+                true
+            } else {
+                dropped.push(id.clone());
+                false
+            }
+        });
+        dropped
+    }
+}
+
+/// A registered condition, called with its arguments.
+type ConditionFn = Box<dyn Fn(&[&str], &World) -> bool + Send + Sync>;
+
+/// World resource with the conditions quest objectives can wait for.
+#[derive(Default)]
+pub struct QuestConditions {
+    conditions: HashMap<String, ConditionFn>,
+}
+
+impl fmt::Debug for QuestConditions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<_> = self.conditions.keys().collect();
+        names.sort();
+        f.debug_struct("QuestConditions")
+            .field("conditions", &names)
+            .finish()
+    }
+}
+
+impl QuestConditions {
+    /// Registers condition `name`, replacing any condition of the same
+    /// name. It is called with the words after the name.
+    pub fn register(
+        &mut self,
+        name: &str,
+        condition: impl Fn(&[&str], &World) -> bool + Send + Sync + 'static,
+    ) {
+        self.conditions.insert(name.to_owned(), Box::new(condition));
+    }
+
+    /// Evaluates `condition`; unregistered conditions do not hold.
+    #[must_use]
+    pub fn check(&self, condition: &str, world: &World) -> bool {
+        let mut words = condition.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let args: Vec<&str> = words.collect();
+        if let Some(check) = self.conditions.get(name) {
+            check(&args, world)
+        } else {
+            warn!("Quest condition `{name}` is not registered");
+            false
+        }
+    }
+}
+
+/// Starts or fails a quest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuestRequest {
+    /// Starts quest `quest`.
+    Start {
+        /// Quest id.
+        quest: String,
+    },
+    /// Fails active quest `quest`.
+    Fail {
+        /// Quest id.
+        quest: String,
+    },
+}
+
+/// Something the player did that objectives may count.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuestProgress {
+    /// Kind of progress, e.g. `kill`.
+    pub event: String,
+    /// What it was about, e.g. `wolf`.
+    pub subject: String,
+    /// How much progress, e.g. the number of kills.
+    pub amount: u32,
+}
+
+/// Change to the quest log, for the UI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuestEvent {
+    /// A quest started.
+    Started {
+        /// Quest id.
+        quest: String,
+    },
+    /// An objective made progress.
+    Progressed {
+        /// Quest id.
+        quest: String,
+        /// Stage index.
+        stage: usize,
+        /// Objective index in the stage.
+        objective: usize,
+        /// Progress so far.
+        progress: u32,
+        /// Progress needed.
+        count: u32,
+    },
+    /// An objective is done.
+    ObjectiveCompleted {
+        /// Quest id.
+        quest: String,
+        /// Stage index.
+        stage: usize,
+        /// Objective index in the stage.
+        objective: usize,
+    },
+    /// Every objective of a stage is done, and the next stage, if any,
+    /// started.
+    StageCompleted {
+        /// Quest id.
+        quest: String,
+        /// Index of the completed stage.
+        stage: usize,
+    },
+    /// The last stage is done.
+    Completed {
+        /// Quest id.
+        quest: String,
+    },
+    /// A quest was failed.
+    Failed {
+        /// Quest id.
+        quest: String,
+    },
+    /// The request was refused and nothing changed.
+    Refused {
+        /// The refused request.
+        request: QuestRequest,
+        /// Why.
+        error: QuestError,
+    },
+}
+
+/// Handles the [`QuestRequest`]s and [`QuestProgress`] events sent since
+/// the last update and sends the outcome to the world's
+/// `Events<QuestEvent>`, created if missing, which is advanced a frame
+/// first.
+///
+/// Requests are handled first, then progress counts towards the current
+/// stage of each active quest, and condition objectives are checked with
+/// the world's [`QuestConditions`], if any. A completed stage starts the
+/// next at once, whose conditions are checked in the same update. The
+/// [`QuestLog`] resource is created if missing.
+pub fn update_quests(world: &mut World, library: &GameplayLibrary) {
+    let mut log = world.remove_resource::<QuestLog>().unwrap_or_default();
+    let conditions = world.remove_resource::<QuestConditions>();
+    let mut out = Vec::new();
+
+    for request in drain::<QuestRequest>(world) {
+        let result = match &request {
+            QuestRequest::Start { quest } => {
+                log.start(library, quest).map(|()| QuestEvent::Started {
+                    quest: quest.clone(),
+                })
+            }
+            QuestRequest::Fail { quest } => log.fail(quest).map(|()| QuestEvent::Failed {
+                quest: quest.clone(),
+            }),
+        };
+        out.push(result.unwrap_or_else(|error| QuestEvent::Refused { request, error }));
+    }
+
+    let progress = drain::<QuestProgress>(world);
+    for (id, state) in &mut log.quests {
+        let Some(quest) = library.quest(id) else {
+            continue;
+        };
+        if state.status != QuestStatus::Active {
+            continue;
+        }
+        count_progress(id, quest, state, &progress, &mut out);
+        advance(id, quest, state, world, conditions.as_ref(), &mut out);
+    }
+
+    world.insert_resource(log);
+    if let Some(conditions) = conditions {
+        world.insert_resource(conditions);
+    }
+    if world.resource::<Events<QuestEvent>>().is_none() {
+        world.insert_resource(Events::<QuestEvent>::default());
+    }
+    if let Some(events) = world.resource_mut::<Events<QuestEvent>>() {
+        events.update();
+        events.send_batch(out);
+    }
+}
+
+/// Counts progress events towards the objectives of the current stage.
+fn count_progress(
+    id: &str,
+    quest: &QuestDef,
+    state: &mut QuestState,
+    events: &[QuestProgress],
+    out: &mut Vec<QuestEvent>,
+) {
+    let Some(current) = quest.stages.get(state.stage) else {
+        return;
+    };
+    for event in events {
+        for (index, objective) in current.objectives.iter().enumerate() {
+            let Some(done) = state.progress.get_mut(index) else {
+                continue;
+            };
+            if *done >= objective.count || !objective.counts(&event.event, &event.subject) {
+                continue;
+            }
+            *done = done.saturating_add(event.amount).min(objective.count);
+            out.push(QuestEvent::Progressed {
+                quest: id.to_owned(),
+                stage: state.stage,
+                objective: index,
+                progress: *done,
+                count: objective.count,
+            });
+            if *done == objective.count {
+                out.push(QuestEvent::ObjectiveCompleted {
+                    quest: id.to_owned(),
+                    stage: state.stage,
+                    objective: index,
+                });
+            }
+        }
+    }
+}
+
+/// Checks condition objectives and moves through every completed stage.
+fn advance(
+    id: &str,
+    quest: &QuestDef,
+    state: &mut QuestState,
+    world: &World,
+    conditions: Option<&QuestConditions>,
+    out: &mut Vec<QuestEvent>,
+) {
+    while let Some(current) = quest.stages.get(state.stage) {
+        for (index, objective) in current.objectives.iter().enumerate() {
+            let Some(condition) = &objective.condition else {
+                continue;
+            };
+            let Some(done) = state.progress.get_mut(index) else {
+                continue;
+            };
+            if *done < objective.count
+                && conditions.is_some_and(|conditions| conditions.check(condition, world))
+            {
+                *done = objective.count;
+                out.push(QuestEvent::ObjectiveCompleted {
+                    quest: id.to_owned(),
+                    stage: state.stage,
+                    objective: index,
+                });
+            }
+        }
+        let complete = current
+            .objectives
+            .iter()
+            .zip(&state.progress)
+            .all(|(objective, done)| *done >= objective.count);
+        if !complete {
+            return;
+        }
+        out.push(QuestEvent::StageCompleted {
+            quest: id.to_owned(),
+            stage: state.stage,
+        });
+        state.stage += 1;
+        if let Some(next) = quest.stages.get(state.stage) {
+            state.progress = vec![0; next.objectives.len()];
+        } else {
+            state.progress.clear();
+            state.status = QuestStatus::Completed;
+            out.push(QuestEvent::Completed {
+                quest: id.to_owned(),
+            });
+        }
+    }
+}
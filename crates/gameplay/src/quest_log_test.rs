@@ -0,0 +1,258 @@
+//! Unit tests for quest tracking.
+
+use crate::library::GameplayLibrary;
+use crate::quest_log::{
+    update_quests, QuestConditions, QuestError, QuestEvent, QuestLog, QuestProgress, QuestRequest,
+    QuestStatus,
+};
+use ecs::{Events, World};
+
+const QUESTS: &str = r#"
+[quests.wolf_hunt]
+name = "Wolf Hunt"
+
+[[quests.wolf_hunt.stages]]
+objectives = [
+    { description = "Hunt wolves", event = "kill", subject = "wolf", count = 3 },
+    { description = "Collect pelts", event = "collect", subject = "wolf_pelt", count = 2 },
+]
+
+[[quests.wolf_hunt.stages]]
+objectives = [{ description = "Return to the smith", condition = "at smithy" }]
+
+[quests.errand]
+name = "Errand"
+
+[[quests.errand.stages]]
+objectives = [{ description = "Talk to anyone", event = "talk" }]
+"#;
+
+/// Where the player is, read by the `at` condition.
+struct Location(&'static str);
+
+fn library() -> GameplayLibrary {
+    GameplayLibrary::from_toml(QUESTS).unwrap()
+}
+
+fn world() -> World {
+    let mut conditions = QuestConditions::default();
+    conditions.register("at", |args, world| {
+        world
+            .resource::<Location>()
+            .is_some_and(|location| location.0 == args[0])
+    });
+    let mut world = World::default();
+    world.insert_resource(conditions);
+    world.insert_resource(Location("forest"));
+    world
+}
+
+fn progress(event: &str, subject: &str, amount: u32) -> QuestProgress {
+    QuestProgress {
+        event: event.to_owned(),
+        subject: subject.to_owned(),
+        amount,
+    }
+}
+
+fn start(quest: &str) -> QuestRequest {
+    QuestRequest::Start {
+        quest: quest.to_owned(),
+    }
+}
+
+/// Sends `requests` and `progress`, runs an update, and returns the events
+/// it sent.
+fn run(
+    world: &mut World,
+    library: &GameplayLibrary,
+    requests: impl IntoIterator<Item = QuestRequest>,
+    progress: impl IntoIterator<Item = QuestProgress>,
+) -> Vec<QuestEvent> {
+    let mut events = Events::default();
+    events.send_batch(requests);
+    world.insert_resource(events);
+    let mut events = Events::default();
+    events.send_batch(progress);
+    world.insert_resource(events);
+    update_quests(world, library);
+    world
+        .resource::<Events<QuestEvent>>()
+        .unwrap()
+        .iter_current()
+        .cloned()
+        .collect()
+}
+
+/// Verifies progress events count towards matching objectives of the
+/// current stage, capped at their count.
+#[test]
+fn progress_counts_towards_matching_objectives() {
+    let library = library();
+    let mut world = world();
+    let events = run(
+        &mut world,
+        &library,
+        [start("wolf_hunt")],
+        [
+            progress("kill", "wolf", 2),
+            progress("kill", "bear", 1),
+            progress("collect", "wolf_pelt", 5),
+        ],
+    );
+    assert_eq!(
+        events,
+        [
+            QuestEvent::Started {
+                quest: "wolf_hunt".to_owned()
+            },
+            QuestEvent::Progressed {
+                quest: "wolf_hunt".to_owned(),
+                stage: 0,
+                objective: 0,
+                progress: 2,
+                count: 3,
+            },
+            QuestEvent::Progressed {
+                quest: "wolf_hunt".to_owned(),
+                stage: 0,
+                objective: 1,
+                progress: 2,
+                count: 2,
+            },
+            QuestEvent::ObjectiveCompleted {
+                quest: "wolf_hunt".to_owned(),
+                stage: 0,
+                objective: 1,
+            },
+        ]
+    );
+    let log = world.resource::<QuestLog>().unwrap();
+    assert_eq!(log.get("wolf_hunt").unwrap().progress, [2, 2]);
+}
+
+/// Verifies completed stages advance, condition objectives complete once
+/// their condition holds, and the last stage completes the quest.
+#[test]
+fn stages_advance_until_the_quest_completes() {
+    let library = library();
+    let mut world = world();
+    run(
+        &mut world,
+        &library,
+        [start("wolf_hunt")],
+        [progress("collect", "wolf_pelt", 2)],
+    );
+
+    let events = run(&mut world, &library, [], [progress("kill", "wolf", 3)]);
+    assert!(events.contains(&QuestEvent::StageCompleted {
+        quest: "wolf_hunt".to_owned(),
+        stage: 0,
+    }));
+    let log = world.resource::<QuestLog>().unwrap();
+    assert_eq!(log.get("wolf_hunt").unwrap().stage, 1);
+
+    assert!(run(&mut world, &library, [], []).is_empty());
+    world.insert_resource(Location("smithy"));
+    let events = run(&mut world, &library, [], []);
+    assert_eq!(
+        events,
+        [
+            QuestEvent::ObjectiveCompleted {
+                quest: "wolf_hunt".to_owned(),
+                stage: 1,
+                objective: 0,
+            },
+            QuestEvent::StageCompleted {
+                quest: "wolf_hunt".to_owned(),
+                stage: 1,
+            },
+            QuestEvent::Completed {
+                quest: "wolf_hunt".to_owned()
+            },
+        ]
+    );
+    let log = world.resource::<QuestLog>().unwrap();
+    assert_eq!(log.status("wolf_hunt"), Some(QuestStatus::Completed));
+}
+
+/// Verifies refused requests, and that failed quests stop counting and can
+/// be started again.
+#[test]
+fn refuses_requests_and_restarts_failed_quests() {
+    let library = library();
+    let mut world = world();
+    let fail = QuestRequest::Fail {
+        quest: "errand".to_owned(),
+    };
+    let events = run(
+        &mut world,
+        &library,
+        [
+            fail.clone(),
+            start("errand"),
+            start("errand"),
+            start("heist"),
+        ],
+        [],
+    );
+    assert_eq!(
+        events,
+        [
+            QuestEvent::Refused {
+                request: fail.clone(),
+                error: QuestError::NotActive("errand".to_owned()),
+            },
+            QuestEvent::Started {
+                quest: "errand".to_owned()
+            },
+            QuestEvent::Refused {
+                request: start("errand"),
+                error: QuestError::AlreadyStarted("errand".to_owned()),
+            },
+            QuestEvent::Refused {
+                request: start("heist"),
+                error: QuestError::UnknownQuest("heist".to_owned()),
+            },
+        ]
+    );
+
+    let events = run(&mut world, &library, [fail], [progress("talk", "smith", 1)]);
+    assert_eq!(
+        events,
+        [QuestEvent::Failed {
+            quest: "errand".to_owned()
+        }]
+    );
+    let events = run(
+        &mut world,
+        &library,
+        [start("errand")],
+        [progress("talk", "smith", 1)],
+    );
+    assert!(events.contains(&QuestEvent::Completed {
+        quest: "errand".to_owned()
+    }));
+}
+
+/// Verifies the log survives a save through TOML, and quests removed from
+/// the game are dropped after loading.
+#[test]
+fn saves_and_loads() {
+    let mut library = library();
+    let mut log = QuestLog::default();
+    log.start(&library, "wolf_hunt").unwrap();
+    log.start(&library, "errand").unwrap();
+    log.fail("errand").unwrap();
+
+    let saved = toml::to_string(&log).unwrap();
+    let mut loaded: QuestLog = toml::from_str(&saved).unwrap();
+    assert_eq!(loaded, log);
+
+    library.quests.remove("errand");
+    library.quests.get_mut("wolf_hunt").unwrap().stages[0]
+        .objectives
+        .pop();
+    assert_eq!(loaded.retain_known(&library), ["errand"]);
+    assert_eq!(loaded.get("wolf_hunt").unwrap().progress, [0]);
+}
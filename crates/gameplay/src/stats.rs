@@ -0,0 +1,236 @@
+//! Numeric stats and the modifiers that change them.
+//!
+//! A [`Stats`] component holds each stat's base value and the modifiers on
+//! top of it. A stat's value is `(base + flat) * (1 + percent / 100)`, with
+//! `flat` and `percent` summed over its modifiers. Stats such as health or
+//! mana are also pools, with a current amount between zero and the stat's
+//! value that damage, healing, and ability costs change.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// A change to one stat.
+///
+/// Written in assets as e.g. `{ stat = "speed", percent = 25, duration = 4.0 }`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StatModifier {
+    /// Name of the changed stat.
+    pub stat: String,
+    /// Amount added to the base value.
+    #[serde(default)]
+    pub flat: f32,
+    /// Percentage added to the value, e.g. `-10` for 10% less.
+    #[serde(default)]
+    pub percent: f32,
+    /// Seconds until the modifier is removed, or `None` until removed by
+    /// its source.
+    #[serde(default)]
+    pub duration: Option<f32>,
+}
+
+impl StatModifier {
+    /// Creates a modifier adding `amount` to `stat`.
+    #[must_use]
+    pub fn flat(stat: impl Into<String>, amount: f32) -> Self {
+        Self {
+            stat: stat.into(),
+            flat: amount,
+            percent: 0.0,
+            duration: None,
+        }
+    }
+
+    /// Creates a modifier adding `percent` percent to `stat`.
+    #[must_use]
+    pub fn percent(stat: impl Into<String>, percent: f32) -> Self {
+        Self {
+            stat: stat.into(),
+            flat: 0.0,
+            percent,
+            duration: None,
+        }
+    }
+
+    /// Removes the modifier after `seconds`.
+    #[must_use]
+    pub fn for_seconds(mut self, seconds: f32) -> Self {
+        self.duration = Some(seconds);
+        self
+    }
+}
+
+/// A modifier applied to a [`Stats`] component.
+#[derive(Debug, Clone, PartialEq)]
+struct Applied {
+    modifier: StatModifier,
+    /// What applied the modifier, e.g. a status effect id.
+    source: String,
+    /// Time until removal, if timed.
+    remaining: Option<Duration>,
+}
+
+/// Component with an entity's stats.
+///
+/// # Example
+///
+/// ```
+/// use gameplay::{StatModifier, Stats};
+///
+/// let mut stats = Stats::default().with_pool("health", 100.0).with_base("armor", 10.0);
+/// stats.add_modifier(StatModifier::flat("armor", 5.0), "shield");
+/// stats.add_modifier(StatModifier::percent("armor", 100.0), "rage");
+/// assert_eq!(stats.value("armor"), 30.0);
+///
+/// stats.change("health", -30.0);
+/// assert_eq!(stats.current("health"), 70.0);
+/// stats.remove_source("rage");
+/// assert_eq!(stats.value("armor"), 15.0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stats {
+    base: BTreeMap<String, f32>,
+    modifiers: Vec<Applied>,
+    /// Current amounts of the pools, before clamping to their stat.
+    pools: BTreeMap<String, f32>,
+}
+
+impl Stats {
+    /// Sets the base value of `stat`.
+    #[must_use]
+    pub fn with_base(mut self, stat: impl Into<String>, value: f32) -> Self {
+        self.set_base(stat, value);
+        self
+    }
+
+    /// Sets the base value of `stat` and makes it a full pool.
+    #[must_use]
+    pub fn with_pool(mut self, stat: impl Into<String>, value: f32) -> Self {
+        let stat = stat.into();
+        self.pools.insert(stat.clone(), value);
+        self.set_base(stat, value);
+        self
+    }
+
+    /// Sets the base value of `stat`.
+    pub fn set_base(&mut self, stat: impl Into<String>, value: f32) {
+        self.base.insert(stat.into(), value);
+    }
+
+    /// Returns the base value of `stat`, or `None` if it has none.
+    #[must_use]
+    pub fn base(&self, stat: &str) -> Option<f32> {
+        self.base.get(stat).copied()
+    }
+
+    /// Returns the value of `stat` with its modifiers; a stat without a base
+    /// value starts at zero.
+    #[must_use]
+    pub fn value(&self, stat: &str) -> f32 {
+        let (flat, percent) = self
+            .modifiers
+            .iter()
+            .filter(|applied| applied.modifier.stat == stat)
+            .fold((0.0, 0.0), |(flat, percent), applied| {
+                (
+                    flat + applied.modifier.flat,
+                    percent + applied.modifier.percent,
+                )
+            });
+        (self.base(stat).unwrap_or(0.0) + flat) * (1.0 + percent / 100.0)
+    }
+
+    /// Returns `true` if `stat` is a pool.
+    #[must_use]
+    pub fn is_pool(&self, stat: &str) -> bool {
+        self.pools.contains_key(stat)
+    }
+
+    /// Returns the current amount of pool `stat`, at most its value, or
+    /// zero if it is not a pool.
+    #[must_use]
+    pub fn current(&self, stat: &str) -> f32 {
+        self.pools
+            .get(stat)
+            .map_or(0.0, |current| current.clamp(0.0, self.value(stat).max(0.0)))
+    }
+
+    /// Adds `amount` to pool `stat`, clamped between zero and its value, and
+    /// returns the new amount. Stats that are not pools are unchanged.
+    pub fn change(&mut self, stat: &str, amount: f32) -> f32 {
+        let current = self.current(stat);
+        let max = self.value(stat).max(0.0);
+        match self.pools.get_mut(stat) {
+            Some(pool) => {
+                *pool = (current + amount).clamp(0.0, max);
+                *pool
+            }
+            None => 0.0,
+        }
+    }
+
+    /// Returns the first pool in `costs` with less than its cost, or `None`
+    /// if the entity can pay them all.
+    #[must_use]
+    pub fn missing<'a>(&self, costs: &'a BTreeMap<String, f32>) -> Option<&'a str> {
+        costs
+            .iter()
+            .find(|(stat, cost)| self.current(stat) < **cost)
+            .map(|(stat, _)| stat.as_str())
+    }
+
+    /// Takes `costs` from their pools, or returns the first pool that cannot
+    /// pay without taking anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns the name of a pool with less than its cost.
+    pub fn spend<'a>(&mut self, costs: &'a BTreeMap<String, f32>) -> Result<(), &'a str> {
+        if let Some(stat) = self.missing(costs) {
+            return Err(stat);
+        }
+        for (stat, cost) in costs {
+            self.change(stat, -cost);
+        }
+        Ok(())
+    }
+
+    /// Applies `modifier` on behalf of `source`.
+    pub fn add_modifier(&mut self, modifier: StatModifier, source: impl Into<String>) {
+        let remaining = modifier
+            .duration
+            .map(|seconds| Duration::from_secs_f32(seconds.max(0.0)));
+        self.modifiers.push(Applied {
+            modifier,
+            source: source.into(),
+            remaining,
+        });
+    }
+
+    /// Removes every modifier applied by `source`, returning how many.
+    pub fn remove_source(&mut self, source: &str) -> usize {
+        let before = self.modifiers.len();
+        self.modifiers.retain(|applied| applied.source != source);
+        before - self.modifiers.len()
+    }
+
+    /// Iterates over the modifiers with their sources, in application order.
+    pub fn modifiers(&self) -> impl Iterator<Item = (&StatModifier, &str)> {
+        self.modifiers
+            .iter()
+            .map(|applied| (&applied.modifier, applied.source.as_str()))
+    }
+
+    /// Advances timed modifiers by `delta`, removing the expired ones.
+    pub fn tick(&mut self, delta: Duration) {
+        self.modifiers
+            .retain_mut(|applied| match &mut applied.remaining {
+                Some(remaining) => {
+                    *remaining = remaining.saturating_sub(delta);
+                    !remaining.is_zero()
+                }
+                None => true,
+            });
+    }
+}
@@ -0,0 +1,81 @@
+//! Unit tests for stats and modifiers.
+
+use crate::stats::{StatModifier, Stats};
+use std::time::Duration;
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+}
+
+/// Verifies flat modifiers add to the base before percent modifiers scale
+/// it.
+#[test]
+fn value_applies_flat_then_percent() {
+    let mut stats = Stats::default().with_base("damage", 20.0);
+    stats.add_modifier(StatModifier::flat("damage", 10.0), "sword");
+    stats.add_modifier(StatModifier::percent("damage", 50.0), "rage");
+    stats.add_modifier(StatModifier::percent("damage", -25.0), "weakness");
+    assert_close(stats.value("damage"), 37.5);
+    assert_eq!(stats.base("damage"), Some(20.0));
+    assert_close(stats.value("speed"), 0.0);
+    assert_eq!(stats.modifiers().count(), 3);
+}
+
+/// Verifies removing a source removes all of its modifiers.
+#[test]
+fn remove_source_removes_its_modifiers() {
+    let mut stats = Stats::default().with_base("armor", 10.0);
+    stats.add_modifier(StatModifier::flat("armor", 5.0), "shield");
+    stats.add_modifier(StatModifier::flat("armor", 5.0), "shield");
+    stats.add_modifier(StatModifier::flat("armor", 1.0), "ring");
+    assert_eq!(stats.remove_source("shield"), 2);
+    assert_close(stats.value("armor"), 11.0);
+    assert_eq!(stats.remove_source("shield"), 0);
+}
+
+/// Verifies timed modifiers expire and permanent ones stay.
+#[test]
+fn tick_expires_timed_modifiers() {
+    let mut stats = Stats::default().with_base("speed", 5.0);
+    stats.add_modifier(StatModifier::flat("speed", 2.0).for_seconds(1.0), "sprint");
+    stats.add_modifier(StatModifier::flat("speed", 1.0), "boots");
+    stats.tick(Duration::from_millis(600));
+    assert_close(stats.value("speed"), 8.0);
+    stats.tick(Duration::from_millis(400));
+    assert_close(stats.value("speed"), 6.0);
+}
+
+/// Verifies pools stay between zero and their stat's value.
+#[test]
+fn pools_clamp_to_their_value() {
+    let mut stats = Stats::default().with_pool("health", 100.0);
+    assert!(stats.is_pool("health"));
+    assert_close(stats.change("health", -130.0), 0.0);
+    assert_close(stats.change("health", 250.0), 100.0);
+
+    stats.add_modifier(StatModifier::flat("health", -40.0), "curse");
+    assert_close(stats.current("health"), 60.0);
+    stats.remove_source("curse");
+    assert_close(stats.current("health"), 100.0);
+
+    stats.set_base("armor", 3.0);
+    assert!(!stats.is_pool("armor"));
+    assert_close(stats.change("armor", 1.0), 0.0);
+    assert_close(stats.value("armor"), 3.0);
+}
+
+/// Verifies spending takes every cost, or nothing if one pool falls short.
+#[test]
+fn spend_is_all_or_nothing() {
+    let mut stats = Stats::default()
+        .with_pool("mana", 30.0)
+        .with_pool("rage", 5.0);
+    let costs = [("mana".to_owned(), 20.0), ("rage".to_owned(), 10.0)].into();
+    assert_eq!(stats.spend(&costs), Err("rage"));
+    assert_close(stats.current("mana"), 30.0);
+
+    let costs = [("mana".to_owned(), 20.0)].into();
+    assert_eq!(stats.missing(&costs), None);
+    stats.spend(&costs).unwrap();
+    assert_close(stats.current("mana"), 10.0);
+}
@@ -0,0 +1,509 @@
+//! Gameplay events and the per-frame update.
+//!
+//! Game code drives abilities and effects by sending events through the
+//! world's [`Events`] resources, and [`update_gameplay`] answers with
+//! [`GameplayEvent`]s:
+//!
+//! | Event | Effect |
+//! |-------|--------|
+//! | [`CastRequest`] | Starts a cast, or fails it with a [`CastError`] |
+//! | [`InterruptCast`] | Stops the cast in progress without cost or cooldown |
+//! | [`ApplyEffect`] | Applies a status effect or adds a stack |
+//! | [`RemoveEffect`] | Removes a status effect, e.g. a cleanse |
+//! | [`InventoryOp`](crate::InventoryOp) | Changes inventories, see [`update_inventories`] |
+//! | [`DialogueRequest`](crate::DialogueRequest) | Drives the conversation, see [`update_dialogue`] |
+//! | [`QuestRequest`](crate::QuestRequest), [`QuestProgress`](crate::QuestProgress) | Advance quests, see [`update_quests`] |
+
+use crate::ability::{Abilities, Cast, CastError};
+use crate::dialogue::update_dialogue;
+use crate::effect::{ActiveEffect, StatusEffects};
+use crate::inventory::update_inventories;
+use crate::library::GameplayLibrary;
+use crate::quest_log::update_quests;
+use crate::stats::Stats;
+use ecs::{Entity, Events, World};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tracing::warn;
+
+/// Asks `caster` to cast `ability` at `target`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CastRequest {
+    /// Entity casting, with an [`Abilities`] component.
+    pub caster: Entity,
+    /// Ability id in the library.
+    pub ability: String,
+    /// Entity the ability is cast at; the caster for self-cast abilities.
+    pub target: Entity,
+}
+
+/// Stops the cast of `caster`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InterruptCast {
+    /// Entity casting.
+    pub caster: Entity,
+}
+
+/// Applies effect `effect` to `target`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApplyEffect {
+    /// Entity receiving the effect.
+    pub target: Entity,
+    /// Effect id in the library.
+    pub effect: String,
+    /// Entity applying the effect, e.g. the caster of an ability.
+    pub source: Entity,
+}
+
+/// Removes effect `effect` from `target`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoveEffect {
+    /// Entity losing the effect.
+    pub target: Entity,
+    /// Effect id in the library.
+    pub effect: String,
+}
+
+/// Outcome of gameplay events, for UI, audio, and game rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameplayEvent {
+    /// A cast started.
+    CastStarted {
+        /// Entity casting.
+        caster: Entity,
+        /// Ability id.
+        ability: String,
+        /// Entity cast at.
+        target: Entity,
+    },
+    /// A cast completed, paid its costs, and applied its effects.
+    CastCompleted {
+        /// Entity that cast.
+        caster: Entity,
+        /// Ability id.
+        ability: String,
+        /// Entity cast at.
+        target: Entity,
+    },
+    /// A cast did not start, or stopped before completing.
+    CastFailed {
+        /// Entity casting.
+        caster: Entity,
+        /// Ability id.
+        ability: String,
+        /// Why it failed.
+        error: CastError,
+    },
+    /// An effect was applied or gained a stack.
+    EffectApplied {
+        /// Entity with the effect.
+        target: Entity,
+        /// Effect id.
+        effect: String,
+        /// Stacks after applying.
+        stacks: u32,
+    },
+    /// An effect ended.
+    EffectEnded {
+        /// Entity that had the effect.
+        target: Entity,
+        /// Effect id.
+        effect: String,
+        /// `true` if its duration ran out, `false` if it was removed.
+        expired: bool,
+    },
+}
+
+/// Advances stats, status effects, and casts by `delta` and handles the
+/// gameplay events sent since the last update.
+///
+/// Each frame, in order: inventory operations are applied with
+/// [`update_inventories`], dialogue requests are handled with
+/// [`update_dialogue`], quests advance with [`update_quests`], timed stat modifiers and status effects tick,
+/// interrupts are handled, casts in progress advance and complete,
+/// requested casts start (instant ones complete at once), and effects
+/// are applied and removed. The outcome is sent as [`GameplayEvent`]s to
+/// the world's `Events<GameplayEvent>`, created if missing, which is
+/// advanced a frame first.
+///
+/// Status effects apply their modifiers to the target's [`Stats`] with the
+/// effect id as the source.
+///
+/// # Example
+///
+/// ```
+/// use ecs::{Events, World};
+/// use gameplay::{update_gameplay, Abilities, CastRequest, GameplayEvent, GameplayLibrary, Stats};
+/// use std::time::Duration;
+///
+/// let library = GameplayLibrary::from_toml(r#"
+///     [abilities.bolt]
+///     cost = { mana = 10 }
+/// "#)?;
+/// let mut world = World::default();
+/// let mage = world.spawn();
+/// world.insert(mage, Abilities::default().with("bolt"));
+/// world.insert(mage, Stats::default().with_pool("mana", 25.0));
+///
+/// let mut requests = Events::default();
+/// requests.send(CastRequest { caster: mage, ability: "bolt".into(), target: mage });
+/// world.insert_resource(requests);
+/// update_gameplay(&mut world, &library, Duration::from_millis(16));
+///
+/// assert_eq!(world.get::<Stats>(mage).unwrap().current("mana"), 15.0);
+/// let events = world.resource::<Events<GameplayEvent>>().unwrap();
+/// assert!(matches!(events.iter().last(), Some(GameplayEvent::CastCompleted { .. })));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn update_gameplay(world: &mut World, library: &GameplayLibrary, delta: Duration) {
+    update_inventories(world, library);
+    update_dialogue(world);
+    update_quests(world, library);
+    let mut out = Vec::new();
+    for entity in entities_with::<Stats>(world) {
+        if let Some(stats) = world.get_mut::<Stats>(entity) {
+            stats.tick(delta);
+        }
+    }
+    tick_effects(world, library, delta, &mut out);
+
+    let mut apply = Vec::new();
+    for InterruptCast { caster } in drain::<InterruptCast>(world) {
+        let cast = world
+            .get_mut::<Abilities>(caster)
+            .and_then(|abilities| abilities.casting.take());
+        if let Some(cast) = cast {
+            out.push(GameplayEvent::CastFailed {
+                caster,
+                ability: cast.ability,
+                error: CastError::Interrupted,
+            });
+        }
+    }
+    advance_casts(world, library, delta, &mut apply, &mut out);
+    for request in drain::<CastRequest>(world) {
+        start_cast(world, library, request, &mut apply, &mut out);
+    }
+
+    apply.extend(drain::<ApplyEffect>(world));
+    for request in apply {
+        apply_effect(world, library, request, &mut out);
+    }
+    for RemoveEffect { target, effect } in drain::<RemoveEffect>(world) {
+        let removed = world
+            .get_mut::<StatusEffects>(target)
+            .is_some_and(|effects| {
+                let before = effects.active.len();
+                effects.active.retain(|active| active.id != effect);
+                effects.active.len() < before
+            });
+        if removed {
+            if let Some(stats) = world.get_mut::<Stats>(target) {
+                stats.remove_source(&effect);
+            }
+            out.push(GameplayEvent::EffectEnded {
+                target,
+                effect,
+                expired: false,
+            });
+        }
+    }
+
+    if world.resource::<Events<GameplayEvent>>().is_none() {
+        world.insert_resource(Events::<GameplayEvent>::default());
+    }
+    if let Some(events) = world.resource_mut::<Events<GameplayEvent>>() {
+        events.update();
+        events.send_batch(out);
+    }
+}
+
+/// Returns the entities with component `C`, in id order.
+fn entities_with<C: Send + Sync + 'static>(world: &World) -> Vec<Entity> {
+    let mut entities: Vec<Entity> = world.query::<C>().map(|(entity, _)| entity).collect();
+    entities.sort_unstable();
+    entities
+}
+
+/// Takes the events of type `T` sent since the last update.
+pub(crate) fn drain<T: Send + Sync + 'static>(world: &mut World) -> Vec<T> {
+    world
+        .resource_mut::<Events<T>>()
+        .map(|events| events.drain().collect())
+        .unwrap_or_default()
+}
+
+/// Runs periodic changes and ends expired effects.
+fn tick_effects(
+    world: &mut World,
+    library: &GameplayLibrary,
+    delta: Duration,
+    out: &mut Vec<GameplayEvent>,
+) {
+    for entity in entities_with::<StatusEffects>(world) {
+        let mut changes = Vec::new();
+        let mut expired = Vec::new();
+        let Some(effects) = world.get_mut::<StatusEffects>(entity) else {
+            continue;
+        };
+        effects.active.retain_mut(|active| {
+            let elapsed = active
+                .remaining
+                .map_or(delta, |remaining| remaining.min(delta));
+            if let Some(periodic) = library
+                .effect(&active.id)
+                .and_then(|effect| effect.periodic.as_ref())
+            {
+                let interval = Duration::from_secs_f32(periodic.interval);
+                active.since_periodic += elapsed;
+                while active.since_periodic >= interval {
+                    active.since_periodic -= interval;
+                    #[allow(clippy::cast_precision_loss)]
+                    changes.push((
+                        periodic.stat.as_str(),
+                        periodic.amount * active.stacks as f32,
+                    ));
+                }
+            }
+            let Some(remaining) = &mut active.remaining else {
+                return true;
+            };
+            *remaining = remaining.saturating_sub(delta);
+            if remaining.is_zero() {
+                expired.push(active.id.clone());
+                return false;
+            }
+            true
+        });
+
+        if let Some(stats) = world.get_mut::<Stats>(entity) {
+            for (stat, amount) in changes {
+                stats.change(stat, amount);
+            }
+            for effect in &expired {
+                stats.remove_source(effect);
+            }
+        }
+        out.extend(
+            expired
+                .into_iter()
+                .map(|effect| GameplayEvent::EffectEnded {
+                    target: entity,
+                    effect,
+                    expired: true,
+                }),
+        );
+    }
+}
+
+/// Counts down cooldowns and casts, completing the casts that finish.
+fn advance_casts(
+    world: &mut World,
+    library: &GameplayLibrary,
+    delta: Duration,
+    apply: &mut Vec<ApplyEffect>,
+    out: &mut Vec<GameplayEvent>,
+) {
+    for caster in entities_with::<Abilities>(world) {
+        let Some(abilities) = world.get_mut::<Abilities>(caster) else {
+            continue;
+        };
+        for cooldown in abilities.cooldowns.values_mut() {
+            *cooldown = cooldown.saturating_sub(delta);
+        }
+        let Some(cast) = &mut abilities.casting else {
+            continue;
+        };
+        cast.remaining = cast.remaining.saturating_sub(delta);
+        if !cast.remaining.is_zero() {
+            continue;
+        }
+        if let Some(cast) = abilities.casting.take() {
+            complete_cast(world, library, caster, cast, apply, out);
+        }
+    }
+}
+
+/// Starts a requested cast, completing it at once if it is instant.
+fn start_cast(
+    world: &mut World,
+    library: &GameplayLibrary,
+    request: CastRequest,
+    apply: &mut Vec<ApplyEffect>,
+    out: &mut Vec<GameplayEvent>,
+) {
+    let CastRequest {
+        caster,
+        ability,
+        target,
+    } = request;
+    let started = library
+        .ability(&ability)
+        .ok_or(CastError::UnknownAbility)
+        .and_then(|def| {
+            world
+                .get::<Abilities>(caster)
+                .ok_or(CastError::NotLearned)?
+                .can_start(&ability)?;
+            affordable(world.get::<Stats>(caster), &def.cost)?;
+            Ok(Duration::from_secs_f32(def.cast_time))
+        });
+    let remaining = match started {
+        Ok(remaining) => remaining,
+        Err(error) => {
+            out.push(GameplayEvent::CastFailed {
+                caster,
+                ability,
+                error,
+            });
+            return;
+        }
+    };
+
+    out.push(GameplayEvent::CastStarted {
+        caster,
+        ability: ability.clone(),
+        target,
+    });
+    let cast = Cast {
+        ability,
+        target,
+        remaining,
+    };
+    if remaining.is_zero() {
+        complete_cast(world, library, caster, cast, apply, out);
+    } else if let Some(abilities) = world.get_mut::<Abilities>(caster) {
+        abilities.casting = Some(cast);
+    }
+}
+
+/// Pays for a finished cast, starts its cooldown, and queues its effects.
+fn complete_cast(
+    world: &mut World,
+    library: &GameplayLibrary,
+    caster: Entity,
+    cast: Cast,
+    apply: &mut Vec<ApplyEffect>,
+    out: &mut Vec<GameplayEvent>,
+) {
+    let Cast {
+        ability, target, ..
+    } = cast;
+    let Some(def) = library.ability(&ability) else {
+        out.push(GameplayEvent::CastFailed {
+            caster,
+            ability,
+            error: CastError::UnknownAbility,
+        });
+        return;
+    };
+    let paid = match world.get_mut::<Stats>(caster) {
+        Some(stats) => stats
+            .spend(&def.cost)
+            .map_err(|stat| CastError::CannotAfford(stat.to_owned())),
+        None => affordable(None, &def.cost),
+    };
+    if let Err(error) = paid {
+        out.push(GameplayEvent::CastFailed {
+            caster,
+            ability,
+            error,
+        });
+        return;
+    }
+
+    if let Some(cooldown) = world
+        .get_mut::<Abilities>(caster)
+        .and_then(|abilities| abilities.cooldowns.get_mut(&ability))
+    {
+        *cooldown = Duration::from_secs_f32(def.cooldown);
+    }
+    let effects = def
+        .target_effects
+        .iter()
+        .map(|effect| (target, effect))
+        .chain(def.self_effects.iter().map(|effect| (caster, effect)));
+    apply.extend(effects.map(|(target, effect)| ApplyEffect {
+        target,
+        effect: effect.clone(),
+        source: caster,
+    }));
+    out.push(GameplayEvent::CastCompleted {
+        caster,
+        ability,
+        target,
+    });
+}
+
+/// Checks that `stats` can pay `cost`.
+fn affordable(stats: Option<&Stats>, cost: &BTreeMap<String, f32>) -> Result<(), CastError> {
+    let missing = match stats {
+        Some(stats) => stats.missing(cost),
+        None => cost.keys().next().map(String::as_str),
+    };
+    match missing {
+        Some(stat) => Err(CastError::CannotAfford(stat.to_owned())),
+        None => Ok(()),
+    }
+}
+
+/// Applies an effect or adds a stack, with its stat modifiers.
+fn apply_effect(
+    world: &mut World,
+    library: &GameplayLibrary,
+    request: ApplyEffect,
+    out: &mut Vec<GameplayEvent>,
+) {
+    let ApplyEffect {
+        target,
+        effect,
+        source,
+    } = request;
+    let Some(def) = library.effect(&effect) else {
+        warn!(effect = %effect, "unknown status effect ignored");
+        return;
+    };
+    if !world.contains(target) {
+        return;
+    }
+    if world.get::<StatusEffects>(target).is_none() {
+        world.insert(target, StatusEffects::default());
+    }
+    let Some(effects) = world.get_mut::<StatusEffects>(target) else {
+        return;
+    };
+
+    let remaining = def.duration.map(Duration::from_secs_f32);
+    let (stacks, added) =
+        if let Some(active) = effects.active.iter_mut().find(|active| active.id == effect) {
+            let added = active.stacks < def.max_stacks;
+            if added {
+                active.stacks += 1;
+            }
+            active.remaining = remaining;
+            active.source = source;
+            (active.stacks, added)
+        } else {
+            effects.active.push(ActiveEffect {
+                id: effect.clone(),
+                source,
+                stacks: 1,
+                remaining,
+                since_periodic: Duration::ZERO,
+            });
+            (1, true)
+        };
+
+    if added {
+        if let Some(stats) = world.get_mut::<Stats>(target) {
+            for modifier in &def.modifiers {
+                stats.add_modifier(modifier.clone(), effect.as_str());
+            }
+        }
+    }
+    out.push(GameplayEvent::EffectApplied {
+        target,
+        effect,
+        stacks,
+    });
+}
@@ -0,0 +1,288 @@
+//! Unit tests for the gameplay update.
+
+use crate::ability::{Abilities, CastError};
+use crate::effect::StatusEffects;
+use crate::library::GameplayLibrary;
+use crate::stats::Stats;
+use crate::update::{
+    update_gameplay, ApplyEffect, CastRequest, GameplayEvent, InterruptCast, RemoveEffect,
+};
+use ecs::{Entity, Events, World};
+use std::time::Duration;
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+}
+
+const LIBRARY: &str = r#"
+[abilities.fireball]
+cast_time = 1.0
+cooldown = 4.0
+cost = { mana = 20 }
+target_effects = ["burning"]
+
+[abilities.war_cry]
+cooldown = 10.0
+self_effects = ["enraged"]
+
+[effects.burning]
+duration = 3.0
+max_stacks = 2
+modifiers = [{ stat = "armor", flat = -2 }]
+periodic = { stat = "health", amount = -5, interval = 1.0 }
+
+[effects.enraged]
+modifiers = [{ stat = "damage", percent = 50 }]
+"#;
+
+struct Arena {
+    world: World,
+    library: GameplayLibrary,
+    mage: Entity,
+    target: Entity,
+}
+
+impl Arena {
+    fn new() -> Self {
+        let mut world = World::default();
+        let mage = world.spawn();
+        world.insert(mage, Abilities::default().with("fireball").with("war_cry"));
+        world.insert(
+            mage,
+            Stats::default()
+                .with_pool("mana", 50.0)
+                .with_base("damage", 10.0),
+        );
+        let target = world.spawn();
+        world.insert(
+            target,
+            Stats::default()
+                .with_pool("health", 100.0)
+                .with_base("armor", 10.0),
+        );
+        Self {
+            world,
+            library: GameplayLibrary::from_toml(LIBRARY).unwrap(),
+            mage,
+            target,
+        }
+    }
+
+    fn send<T: Send + Sync + 'static>(&mut self, event: T) {
+        if self.world.resource::<Events<T>>().is_none() {
+            self.world.insert_resource(Events::<T>::default());
+        }
+        self.world.resource_mut::<Events<T>>().unwrap().send(event);
+    }
+
+    fn cast(&mut self, ability: &str) {
+        self.send(CastRequest {
+            caster: self.mage,
+            ability: ability.to_owned(),
+            target: self.target,
+        });
+    }
+
+    /// Runs a frame and returns the events it sent.
+    fn frame(&mut self, millis: u64) -> Vec<GameplayEvent> {
+        update_gameplay(
+            &mut self.world,
+            &self.library,
+            Duration::from_millis(millis),
+        );
+        self.world
+            .resource::<Events<GameplayEvent>>()
+            .unwrap()
+            .iter_current()
+            .cloned()
+            .collect()
+    }
+
+    fn stats(&self, entity: Entity) -> &Stats {
+        self.world.get::<Stats>(entity).unwrap()
+    }
+
+    fn abilities(&self) -> &Abilities {
+        self.world.get::<Abilities>(self.mage).unwrap()
+    }
+}
+
+/// Verifies a cast pays its cost, starts its cooldown, and applies its
+/// effects when its cast time is over.
+#[test]
+fn cast_completes_after_cast_time() {
+    let mut arena = Arena::new();
+    arena.cast("fireball");
+    let events = arena.frame(16);
+    assert!(matches!(&events[..], [GameplayEvent::CastStarted { .. }]));
+    assert_close(arena.stats(arena.mage).current("mana"), 50.0);
+
+    assert!(arena.frame(500).is_empty());
+    let events = arena.frame(500);
+    assert_eq!(
+        events,
+        [
+            GameplayEvent::CastCompleted {
+                caster: arena.mage,
+                ability: "fireball".to_owned(),
+                target: arena.target,
+            },
+            GameplayEvent::EffectApplied {
+                target: arena.target,
+                effect: "burning".to_owned(),
+                stacks: 1,
+            },
+        ]
+    );
+    assert_close(arena.stats(arena.mage).current("mana"), 30.0);
+    assert_eq!(
+        arena.abilities().cooldown("fireball"),
+        Some(Duration::from_secs(4))
+    );
+    assert!(arena.abilities().casting().is_none());
+    assert_close(arena.stats(arena.target).value("armor"), 8.0);
+}
+
+/// Verifies casts fail while unknown, unlearned, cooling down, busy, or
+/// unaffordable.
+#[test]
+fn cast_failures_are_reported() {
+    let mut arena = Arena::new();
+    let failure = |arena: &mut Arena, ability: &str| {
+        arena.cast(ability);
+        arena.frame(0).into_iter().find_map(|event| match event {
+            GameplayEvent::CastFailed { error, .. } => Some(error),
+            _ => None,
+        })
+    };
+    assert_eq!(
+        failure(&mut arena, "meteor"),
+        Some(CastError::UnknownAbility)
+    );
+
+    let mut library = GameplayLibrary::from_toml(LIBRARY).unwrap();
+    library.merge(GameplayLibrary::from_toml("[abilities.blink]").unwrap());
+    arena.library = library;
+    assert_eq!(failure(&mut arena, "blink"), Some(CastError::NotLearned));
+
+    assert_eq!(failure(&mut arena, "war_cry"), None);
+    assert_eq!(
+        failure(&mut arena, "war_cry"),
+        Some(CastError::OnCooldown(Duration::from_secs(10)))
+    );
+
+    assert_eq!(failure(&mut arena, "fireball"), None);
+    assert_eq!(failure(&mut arena, "fireball"), Some(CastError::Busy));
+
+    let mut broke = Arena::new();
+    broke
+        .world
+        .get_mut::<Stats>(broke.mage)
+        .unwrap()
+        .change("mana", -45.0);
+    assert_eq!(
+        failure(&mut broke, "fireball"),
+        Some(CastError::CannotAfford("mana".to_owned()))
+    );
+}
+
+/// Verifies an interrupted cast costs nothing and starts no cooldown.
+#[test]
+fn interrupted_cast_is_free() {
+    let mut arena = Arena::new();
+    arena.cast("fireball");
+    arena.frame(500);
+    arena.send(InterruptCast { caster: arena.mage });
+    let events = arena.frame(16);
+    assert!(matches!(
+        &events[..],
+        [GameplayEvent::CastFailed {
+            error: CastError::Interrupted,
+            ..
+        }]
+    ));
+    assert_close(arena.stats(arena.mage).current("mana"), 50.0);
+    assert!(arena.abilities().is_ready("fireball"));
+}
+
+/// Verifies instant self-cast abilities apply their effect at once.
+#[test]
+fn instant_cast_applies_self_effects() {
+    let mut arena = Arena::new();
+    arena.cast("war_cry");
+    let events = arena.frame(16);
+    assert_eq!(events.len(), 3);
+    assert_close(arena.stats(arena.mage).value("damage"), 15.0);
+    assert!(arena
+        .world
+        .get::<StatusEffects>(arena.mage)
+        .unwrap()
+        .contains("enraged"));
+}
+
+/// Verifies stacks scale modifiers and periodic changes, and expiry removes
+/// them.
+#[test]
+fn effects_stack_tick_and_expire() {
+    let mut arena = Arena::new();
+    for _ in 0..3 {
+        arena.send(ApplyEffect {
+            target: arena.target,
+            effect: "burning".to_owned(),
+            source: arena.mage,
+        });
+    }
+    let events = arena.frame(0);
+    assert_eq!(events.len(), 3);
+    let effects = arena.world.get::<StatusEffects>(arena.target).unwrap();
+    assert_eq!(effects.stacks("burning"), 2);
+    assert_eq!(effects.get("burning").unwrap().source, arena.mage);
+    assert_close(arena.stats(arena.target).value("armor"), 6.0);
+
+    arena.frame(1000);
+    assert_close(arena.stats(arena.target).current("health"), 90.0);
+    let events = arena.frame(2500);
+    assert_close(arena.stats(arena.target).current("health"), 70.0);
+    assert_eq!(
+        events,
+        [GameplayEvent::EffectEnded {
+            target: arena.target,
+            effect: "burning".to_owned(),
+            expired: true,
+        }]
+    );
+    assert_close(arena.stats(arena.target).value("armor"), 10.0);
+    assert!(arena
+        .world
+        .get::<StatusEffects>(arena.target)
+        .unwrap()
+        .is_empty());
+}
+
+/// Verifies removing an effect drops its modifiers.
+#[test]
+fn remove_effect_drops_modifiers() {
+    let mut arena = Arena::new();
+    arena.send(ApplyEffect {
+        target: arena.mage,
+        effect: "enraged".to_owned(),
+        source: arena.mage,
+    });
+    arena.frame(0);
+    assert_close(arena.stats(arena.mage).value("damage"), 15.0);
+
+    arena.send(RemoveEffect {
+        target: arena.mage,
+        effect: "enraged".to_owned(),
+    });
+    let events = arena.frame(0);
+    assert_eq!(
+        events,
+        [GameplayEvent::EffectEnded {
+            target: arena.mage,
+            effect: "enraged".to_owned(),
+            expired: false,
+        }]
+    );
+    assert_close(arena.stats(arena.mage).value("damage"), 10.0);
+}
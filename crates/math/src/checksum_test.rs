@@ -0,0 +1,79 @@
+//! Unit tests for simulation checksums, including determinism tests of a
+//! fixed-point simulation.
+
+use crate::checksum::StateChecksum;
+use crate::fixed::{Fixed, FixedVec3};
+
+/// Checksum of [`simulate`] with seed 1 for 600 ticks, computed once and
+/// expected on every platform.
+const GOLDEN_CHECKSUM: u64 = 0x8b0b_5cd6_ddcc_af6e;
+
+/// Runs a small gameplay-like simulation: projectiles orbiting, bouncing,
+/// and homing on a target, returning the checksum of the final state.
+fn simulate(seed: i32, ticks: u32) -> u64 {
+    let dt = Fixed::from_ratio(1, 60);
+    let mut bodies: Vec<(FixedVec3, FixedVec3)> = (0..64)
+        .map(|i| {
+            let angle = Fixed::from_ratio(i * 7 + seed, 16);
+            let position = FixedVec3::new(angle.cos(), Fixed::from_int(i % 5), angle.sin())
+                * Fixed::from_int(10);
+            (position, FixedVec3::ZERO)
+        })
+        .collect();
+    let target = FixedVec3::new(Fixed::from_int(2), Fixed::ZERO, Fixed::from_int(-3));
+    let gravity = FixedVec3::new(Fixed::ZERO, Fixed::from_ratio(-981, 100), Fixed::ZERO);
+    for tick in 0..ticks {
+        let time = Fixed::from_int(i32::try_from(tick).unwrap()) * dt;
+        for (position, velocity) in &mut bodies {
+            let homing = (target - *position).normalize() * Fixed::from_int(5);
+            let swirl = FixedVec3::new(time.sin(), Fixed::ZERO, time.cos());
+            *velocity += (gravity + homing + swirl) * dt;
+            *position += *velocity * dt;
+            if position.y < Fixed::ZERO {
+                position.y = -position.y;
+                velocity.y = -velocity.y * Fixed::from_ratio(4, 5);
+            }
+        }
+    }
+    let mut checksum = StateChecksum::default();
+    for (position, velocity) in bodies {
+        checksum.write_fixed_vec3(position);
+        checksum.write_fixed_vec3(velocity);
+    }
+    checksum.finish()
+}
+
+/// Verifies repeated runs produce the same checksum.
+#[test]
+fn test_simulation_is_repeatable() {
+    assert_eq!(simulate(1, 300), simulate(1, 300));
+    assert_ne!(simulate(1, 300), simulate(2, 300));
+}
+
+/// Verifies the simulation matches the checksum recorded on another
+/// machine, which is what lockstep peers rely on.
+#[test]
+fn test_simulation_matches_golden_checksum() {
+    assert_eq!(simulate(1, 600), GOLDEN_CHECKSUM);
+}
+
+/// Verifies the checksum ignores the sign of zero and NaN payloads.
+#[test]
+fn test_float_normalization() {
+    let hash = |value: f32| {
+        let mut checksum = StateChecksum::default();
+        checksum.write_f32(value);
+        checksum.finish()
+    };
+    assert_eq!(hash(0.0), hash(-0.0));
+    assert_eq!(hash(f32::NAN), hash(-f32::NAN));
+    assert_ne!(hash(1.0), hash(-1.0));
+}
+
+/// Verifies the hash matches the FNV-1a reference value.
+#[test]
+fn test_fnv1a_reference() {
+    let mut checksum = StateChecksum::default();
+    checksum.write_bytes(b"a");
+    assert_eq!(checksum.finish(), 0xaf63_dc4c_8601_ec8c);
+}
@@ -0,0 +1,610 @@
+//! Colors and conversions between sRGB and linear light.
+//!
+//! Colors are authored in sRGB, the gamma-encoded space of images and
+//! color pickers, but lighting and blending must happen in linear light.
+//! [`Srgba`] and [`LinearRgba`] keep the two apart in the type system, so a
+//! color cannot be passed to a shader in the wrong space by accident.
+//!
+//! Engine APIs take a [`Color`], which stores linear light and converts
+//! from any space, including [`Hsva`] for pickers and [`Oklaba`] for
+//! perceptually even gradients.
+
+use glam::{Vec3, Vec4};
+use serde::{Deserialize, Serialize};
+
+/// Encodes a linear channel value with the sRGB transfer function.
+#[must_use]
+pub fn linear_to_srgb(linear: f32) -> f32 {
+    if linear <= 0.003_130_8 {
+        linear * 12.92
+    } else {
+        1.055 * linear.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Decodes an sRGB-encoded channel value to linear.
+#[must_use]
+pub fn srgb_to_linear(encoded: f32) -> f32 {
+    if encoded <= 0.040_45 {
+        encoded / 12.92
+    } else {
+        ((encoded + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// A color in the sRGB color space, gamma encoded, with linear alpha.
+///
+/// # Example
+///
+/// ```
+/// use math::color::{LinearRgba, Srgba};
+///
+/// let orange = Srgba::from_hex("#ff8000").unwrap();
+/// let linear = LinearRgba::from(orange);
+/// assert!((linear.green - 0.2158).abs() < 1e-4);
+/// assert_eq!(Srgba::from(linear).to_rgba8(), [255, 128, 0, 255]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Srgba {
+    /// Red channel, 0 to 1.
+    pub red: f32,
+    /// Green channel, 0 to 1.
+    pub green: f32,
+    /// Blue channel, 0 to 1.
+    pub blue: f32,
+    /// Opacity, 0 to 1.
+    pub alpha: f32,
+}
+
+impl Srgba {
+    /// Opaque white.
+    pub const WHITE: Self = Self::rgb(1.0, 1.0, 1.0);
+    /// Opaque black.
+    pub const BLACK: Self = Self::rgb(0.0, 0.0, 0.0);
+    /// Fully transparent black.
+    pub const TRANSPARENT: Self = Self::new(0.0, 0.0, 0.0, 0.0);
+
+    /// Creates a color.
+    #[must_use]
+    pub const fn new(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    /// Creates an opaque color.
+    #[must_use]
+    pub const fn rgb(red: f32, green: f32, blue: f32) -> Self {
+        Self::new(red, green, blue, 1.0)
+    }
+
+    /// Creates a color from 8-bit channels.
+    #[must_use]
+    pub fn from_rgba8([red, green, blue, alpha]: [u8; 4]) -> Self {
+        Self::new(
+            f32::from(red) / 255.0,
+            f32::from(green) / 255.0,
+            f32::from(blue) / 255.0,
+            f32::from(alpha) / 255.0,
+        )
+    }
+
+    /// Returns the channels as 8-bit values, rounded and clamped.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn to_rgba8(&self) -> [u8; 4] {
+        [self.red, self.green, self.blue, self.alpha]
+            .map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+
+    /// Parses `#rgb`, `#rgba`, `#rrggbb`, or `#rrggbbaa`, with or without
+    /// the `#`.
+    #[must_use]
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        let digits: Vec<u8> = hex
+            .chars()
+            .map(|c| c.to_digit(16).and_then(|d| u8::try_from(d).ok()))
+            .collect::<Option<_>>()?;
+        let channels: Vec<u8> = match digits.len() {
+            3 | 4 => digits.iter().map(|d| d * 17).collect(),
+            6 | 8 => digits
+                .chunks(2)
+                .map(|pair| pair[0] * 16 + pair[1])
+                .collect(),
+            _ => return None,
+        };
+        let alpha = channels.get(3).copied().unwrap_or(255);
+        Some(Self::from_rgba8([
+            channels[0],
+            channels[1],
+            channels[2],
+            alpha,
+        ]))
+    }
+
+    /// Returns the color with `alpha` replaced.
+    #[must_use]
+    pub fn with_alpha(self, alpha: f32) -> Self {
+        Self { alpha, ..self }
+    }
+}
+
+impl Default for Srgba {
+    fn default() -> Self {
+        Self::WHITE
+    }
+}
+
+/// A color in linear light with Rec. 709 primaries, the space shaders
+/// light and blend in.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LinearRgba {
+    /// Red channel; may exceed 1 for HDR.
+    pub red: f32,
+    /// Green channel; may exceed 1 for HDR.
+    pub green: f32,
+    /// Blue channel; may exceed 1 for HDR.
+    pub blue: f32,
+    /// Opacity, 0 to 1.
+    pub alpha: f32,
+}
+
+impl LinearRgba {
+    /// Opaque white.
+    pub const WHITE: Self = Self::rgb(1.0, 1.0, 1.0);
+    /// Opaque black.
+    pub const BLACK: Self = Self::rgb(0.0, 0.0, 0.0);
+
+    /// Creates a color.
+    #[must_use]
+    pub const fn new(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    /// Creates an opaque color.
+    #[must_use]
+    pub const fn rgb(red: f32, green: f32, blue: f32) -> Self {
+        Self::new(red, green, blue, 1.0)
+    }
+
+    /// Returns the relative luminance.
+    #[must_use]
+    pub fn luminance(&self) -> f32 {
+        0.2126 * self.red + 0.7152 * self.green + 0.0722 * self.blue
+    }
+
+    /// Blends linearly towards `other` by `t`, which is how light mixes.
+    #[must_use]
+    pub fn mix(&self, other: &Self, t: f32) -> Self {
+        Self::from(Vec4::from(*self).lerp(Vec4::from(*other), t))
+    }
+
+    /// Returns the color channels without alpha.
+    #[must_use]
+    pub fn rgb_vec(&self) -> Vec3 {
+        Vec3::new(self.red, self.green, self.blue)
+    }
+}
+
+impl Default for LinearRgba {
+    fn default() -> Self {
+        Self::WHITE
+    }
+}
+
+impl From<Srgba> for LinearRgba {
+    fn from(color: Srgba) -> Self {
+        Self::new(
+            srgb_to_linear(color.red),
+            srgb_to_linear(color.green),
+            srgb_to_linear(color.blue),
+            color.alpha,
+        )
+    }
+}
+
+impl From<LinearRgba> for Srgba {
+    fn from(color: LinearRgba) -> Self {
+        Self::new(
+            linear_to_srgb(color.red),
+            linear_to_srgb(color.green),
+            linear_to_srgb(color.blue),
+            color.alpha,
+        )
+    }
+}
+
+impl From<LinearRgba> for Vec4 {
+    fn from(color: LinearRgba) -> Self {
+        Self::new(color.red, color.green, color.blue, color.alpha)
+    }
+}
+
+impl From<Vec4> for LinearRgba {
+    fn from(v: Vec4) -> Self {
+        Self::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<LinearRgba> for [f32; 4] {
+    fn from(color: LinearRgba) -> Self {
+        [color.red, color.green, color.blue, color.alpha]
+    }
+}
+
+impl From<[f32; 4]> for LinearRgba {
+    fn from([red, green, blue, alpha]: [f32; 4]) -> Self {
+        Self::new(red, green, blue, alpha)
+    }
+}
+
+/// A color as hue, saturation, and value over sRGB, the model of most
+/// color pickers.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Hsva {
+    /// Hue in degrees, 0 to 360.
+    pub hue: f32,
+    /// Saturation, 0 to 1.
+    pub saturation: f32,
+    /// Value, 0 to 1.
+    pub value: f32,
+    /// Opacity, 0 to 1.
+    pub alpha: f32,
+}
+
+impl Hsva {
+    /// Creates a color.
+    #[must_use]
+    pub const fn new(hue: f32, saturation: f32, value: f32, alpha: f32) -> Self {
+        Self {
+            hue,
+            saturation,
+            value,
+            alpha,
+        }
+    }
+}
+
+impl From<Srgba> for Hsva {
+    fn from(color: Srgba) -> Self {
+        let Srgba {
+            red,
+            green,
+            blue,
+            alpha,
+        } = color;
+        let max = red.max(green).max(blue);
+        let chroma = max - red.min(green).min(blue);
+        let hue = if chroma <= 0.0 {
+            0.0
+        } else if (max - red).abs() <= f32::EPSILON {
+            60.0 * ((green - blue) / chroma).rem_euclid(6.0)
+        } else if (max - green).abs() <= f32::EPSILON {
+            60.0 * ((blue - red) / chroma + 2.0)
+        } else {
+            60.0 * ((red - green) / chroma + 4.0)
+        };
+        let saturation = if max <= 0.0 { 0.0 } else { chroma / max };
+        Self::new(hue, saturation, max, alpha)
+    }
+}
+
+impl From<Hsva> for Srgba {
+    fn from(color: Hsva) -> Self {
+        let chroma = color.value * color.saturation;
+        let sector = color.hue.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+        let (red, green, blue) = match sector {
+            s if s < 1.0 => (chroma, x, 0.0),
+            s if s < 2.0 => (x, chroma, 0.0),
+            s if s < 3.0 => (0.0, chroma, x),
+            s if s < 4.0 => (0.0, x, chroma),
+            s if s < 5.0 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let offset = color.value - chroma;
+        Self::new(red + offset, green + offset, blue + offset, color.alpha)
+    }
+}
+
+/// A color in the Oklab perceptual space, where equal distances look
+/// equally different, so gradients through it look even.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Oklaba {
+    /// Perceived lightness, 0 to 1.
+    pub lightness: f32,
+    /// Green to red axis.
+    pub a: f32,
+    /// Blue to yellow axis.
+    pub b: f32,
+    /// Opacity, 0 to 1.
+    pub alpha: f32,
+}
+
+impl Oklaba {
+    /// Creates a color.
+    #[must_use]
+    pub const fn new(lightness: f32, a: f32, b: f32, alpha: f32) -> Self {
+        Self {
+            lightness,
+            a,
+            b,
+            alpha,
+        }
+    }
+}
+
+// Matrices from Björn Ottosson's reference implementation.
+#[allow(clippy::excessive_precision)]
+impl From<LinearRgba> for Oklaba {
+    fn from(color: LinearRgba) -> Self {
+        let LinearRgba {
+            red,
+            green,
+            blue,
+            alpha,
+        } = color;
+        let l = (0.412_221_470_8 * red + 0.536_332_536_3 * green + 0.051_445_992_9 * blue).cbrt();
+        let m = (0.211_903_498_2 * red + 0.680_699_545_1 * green + 0.107_396_956_6 * blue).cbrt();
+        let s = (0.088_302_461_9 * red + 0.281_718_837_6 * green + 0.629_978_700_5 * blue).cbrt();
+        Self::new(
+            0.210_454_255_3 * l + 0.793_617_785_0 * m - 0.004_072_046_8 * s,
+            1.977_998_495_1 * l - 2.428_592_205_0 * m + 0.450_593_709_9 * s,
+            0.025_904_037_1 * l + 0.782_771_766_2 * m - 0.808_675_766_0 * s,
+            alpha,
+        )
+    }
+}
+
+#[allow(clippy::excessive_precision)]
+impl From<Oklaba> for LinearRgba {
+    fn from(color: Oklaba) -> Self {
+        let Oklaba {
+            lightness,
+            a,
+            b,
+            alpha,
+        } = color;
+        let l = (lightness + 0.396_337_777_4 * a + 0.215_803_757_3 * b).powi(3);
+        let m = (lightness - 0.105_561_345_8 * a - 0.063_854_172_8 * b).powi(3);
+        let s = (lightness - 0.089_484_177_5 * a - 1.291_485_548_0 * b).powi(3);
+        Self::new(
+            4.076_741_662_1 * l - 3.307_711_591_3 * m + 0.230_969_929_2 * s,
+            -1.268_438_004_6 * l + 2.609_757_401_1 * m - 0.341_319_396_5 * s,
+            -0.004_196_086_3 * l - 0.703_418_614_7 * m + 1.707_614_701_0 * s,
+            alpha,
+        )
+    }
+}
+
+/// The engine's color type: stored in linear light, ready for shaders, and
+/// convertible to and from every other space.
+///
+/// Serialized as a linear `[r, g, b, a]` array. Named colors live in
+/// [`palette`](crate::palette).
+///
+/// # Example
+///
+/// ```
+/// use math::{palette, Color};
+///
+/// let sky = Color::hex("#87ceeb").unwrap();
+/// assert_eq!(sky.to_hex(), "#87ceeb");
+///
+/// // Halfway between red and blue in Oklab is a purple, not a dark
+/// // muddy mix.
+/// let purple = palette::RED.lerp(palette::BLUE, 0.5);
+/// assert!(purple.to_oklaba().lightness > 0.5);
+///
+/// let hsv = Color::hsv(120.0, 1.0, 1.0);
+/// assert_eq!(hsv, palette::LIME);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(from = "[f32; 4]", into = "[f32; 4]")]
+pub struct Color(LinearRgba);
+
+impl Color {
+    /// Opaque white.
+    pub const WHITE: Self = Self::linear_rgb(1.0, 1.0, 1.0);
+    /// Opaque black.
+    pub const BLACK: Self = Self::linear_rgb(0.0, 0.0, 0.0);
+    /// Fully transparent black.
+    pub const TRANSPARENT: Self = Self::linear_rgba(0.0, 0.0, 0.0, 0.0);
+
+    /// Creates a color from linear channels.
+    #[must_use]
+    pub const fn linear_rgba(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Self(LinearRgba::new(red, green, blue, alpha))
+    }
+
+    /// Creates an opaque color from linear channels.
+    #[must_use]
+    pub const fn linear_rgb(red: f32, green: f32, blue: f32) -> Self {
+        Self::linear_rgba(red, green, blue, 1.0)
+    }
+
+    /// Creates a color from sRGB-encoded channels.
+    #[must_use]
+    pub fn srgba(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Self::from(Srgba::new(red, green, blue, alpha))
+    }
+
+    /// Creates an opaque color from sRGB-encoded channels.
+    #[must_use]
+    pub fn srgb(red: f32, green: f32, blue: f32) -> Self {
+        Self::srgba(red, green, blue, 1.0)
+    }
+
+    /// Creates an opaque color from 8-bit sRGB channels.
+    #[must_use]
+    pub fn srgb_u8(red: u8, green: u8, blue: u8) -> Self {
+        Self::from(Srgba::from_rgba8([red, green, blue, 255]))
+    }
+
+    /// Creates an opaque color from hue in degrees, saturation, and value.
+    #[must_use]
+    pub fn hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        Self::from(Hsva::new(hue, saturation, value, 1.0))
+    }
+
+    /// Creates an opaque color from Oklab coordinates.
+    #[must_use]
+    pub fn oklab(lightness: f32, a: f32, b: f32) -> Self {
+        Self::from(Oklaba::new(lightness, a, b, 1.0))
+    }
+
+    /// Parses an sRGB hex color; see [`Srgba::from_hex`].
+    #[must_use]
+    pub fn hex(hex: &str) -> Option<Self> {
+        Srgba::from_hex(hex).map(Self::from)
+    }
+
+    /// Returns the color as `#rrggbb`, or `#rrggbbaa` if translucent.
+    #[must_use]
+    pub fn to_hex(&self) -> String {
+        let [red, green, blue, alpha] = self.to_srgba().to_rgba8();
+        if alpha == 255 {
+            format!("#{red:02x}{green:02x}{blue:02x}")
+        } else {
+            format!("#{red:02x}{green:02x}{blue:02x}{alpha:02x}")
+        }
+    }
+
+    /// Returns the color in linear light.
+    #[must_use]
+    #[inline]
+    pub fn to_linear(&self) -> LinearRgba {
+        self.0
+    }
+
+    /// Returns the color in sRGB.
+    #[must_use]
+    pub fn to_srgba(&self) -> Srgba {
+        Srgba::from(self.0)
+    }
+
+    /// Returns the color as hue, saturation, and value.
+    #[must_use]
+    pub fn to_hsva(&self) -> Hsva {
+        Hsva::from(self.to_srgba())
+    }
+
+    /// Returns the color in Oklab.
+    #[must_use]
+    pub fn to_oklaba(&self) -> Oklaba {
+        Oklaba::from(self.0)
+    }
+
+    /// Returns the linear channels as `[r, g, b, a]`, as uploaded to the GPU.
+    #[must_use]
+    #[inline]
+    pub fn to_array(&self) -> [f32; 4] {
+        self.0.into()
+    }
+
+    /// Returns the opacity.
+    #[must_use]
+    #[inline]
+    pub fn alpha(&self) -> f32 {
+        self.0.alpha
+    }
+
+    /// Returns the color with `alpha` replaced.
+    #[must_use]
+    pub fn with_alpha(self, alpha: f32) -> Self {
+        Self(LinearRgba { alpha, ..self.0 })
+    }
+
+    /// Blends towards `other` by `t` in Oklab, so gradients keep an even
+    /// perceived brightness. Alpha blends linearly.
+    #[must_use]
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        let from = self.to_oklaba();
+        let to = other.to_oklaba();
+        Self::from(Oklaba::new(
+            from.lightness + (to.lightness - from.lightness) * t,
+            from.a + (to.a - from.a) * t,
+            from.b + (to.b - from.b) * t,
+            from.alpha + (to.alpha - from.alpha) * t,
+        ))
+    }
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Self::WHITE
+    }
+}
+
+/// Multiplies channel by channel in linear light, as a tint does.
+impl std::ops::Mul for Color {
+    type Output = Self;
+
+    fn mul(self, tint: Self) -> Self {
+        Self::from(Vec4::from(self.0) * Vec4::from(tint.0))
+    }
+}
+
+impl From<LinearRgba> for Color {
+    fn from(color: LinearRgba) -> Self {
+        Self(color)
+    }
+}
+
+impl From<Srgba> for Color {
+    fn from(color: Srgba) -> Self {
+        Self(color.into())
+    }
+}
+
+impl From<Hsva> for Color {
+    fn from(color: Hsva) -> Self {
+        Self::from(Srgba::from(color))
+    }
+}
+
+impl From<Oklaba> for Color {
+    fn from(color: Oklaba) -> Self {
+        Self(color.into())
+    }
+}
+
+impl From<Vec4> for Color {
+    fn from(v: Vec4) -> Self {
+        Self(v.into())
+    }
+}
+
+/// Treats the array as linear `[r, g, b, a]`.
+impl From<[f32; 4]> for Color {
+    fn from(channels: [f32; 4]) -> Self {
+        Self(channels.into())
+    }
+}
+
+impl From<Color> for [f32; 4] {
+    fn from(color: Color) -> Self {
+        color.to_array()
+    }
+}
+
+impl From<Color> for LinearRgba {
+    fn from(color: Color) -> Self {
+        color.0
+    }
+}
+
+impl From<Color> for Srgba {
+    fn from(color: Color) -> Self {
+        color.to_srgba()
+    }
+}
@@ -0,0 +1,144 @@
+//! Unit tests for colors.
+
+use crate::color::{linear_to_srgb, srgb_to_linear, Color, Hsva, LinearRgba, Oklaba, Srgba};
+use crate::palette;
+
+/// Verifies the transfer functions invert each other and hit the
+/// reference points.
+#[test]
+fn test_transfer_functions() {
+    for i in 0..=100u8 {
+        let value = f32::from(i) / 100.0;
+        assert!((srgb_to_linear(linear_to_srgb(value)) - value).abs() < 1e-5);
+    }
+    assert!((srgb_to_linear(0.5) - 0.214_041).abs() < 1e-5);
+    assert!((linear_to_srgb(1.0) - 1.0).abs() < 1e-6);
+    assert_eq!(linear_to_srgb(0.0).to_bits(), 0.0f32.to_bits());
+}
+
+/// Verifies hex parsing in all supported forms.
+#[test]
+fn test_from_hex() {
+    assert_eq!(Srgba::from_hex("#fff").unwrap().to_rgba8(), [255; 4]);
+    assert_eq!(
+        Srgba::from_hex("0f08").unwrap().to_rgba8(),
+        [0, 255, 0, 136]
+    );
+    assert_eq!(
+        Srgba::from_hex("#336699").unwrap().to_rgba8(),
+        [0x33, 0x66, 0x99, 255]
+    );
+    assert_eq!(
+        Srgba::from_hex("#33669980").unwrap().to_rgba8(),
+        [0x33, 0x66, 0x99, 0x80]
+    );
+    assert_eq!(Srgba::from_hex("#12345"), None);
+    assert_eq!(Srgba::from_hex("#zzzzzz"), None);
+}
+
+/// Verifies conversions keep alpha linear and round-trip 8-bit colors.
+#[test]
+fn test_round_trip() {
+    for rgba in [[0, 0, 0, 0], [12, 200, 99, 128], [255, 255, 255, 255]] {
+        let linear = LinearRgba::from(Srgba::from_rgba8(rgba));
+        assert_eq!(Srgba::from(linear).to_rgba8(), rgba);
+        assert!((linear.alpha - f32::from(rgba[3]) / 255.0).abs() < 1e-6);
+    }
+}
+
+/// Verifies mixing happens in linear light.
+#[test]
+fn test_mix_and_luminance() {
+    let grey = LinearRgba::BLACK.mix(&LinearRgba::WHITE, 0.5);
+    assert!((grey.luminance() - 0.5).abs() < 1e-6);
+    // Halfway in linear light is brighter than sRGB 0.5.
+    assert_eq!(Srgba::from(grey).to_rgba8()[0], 188);
+    assert_eq!(
+        <[f32; 4]>::from(grey).map(f32::to_bits),
+        [0.5f32, 0.5, 0.5, 1.0].map(f32::to_bits)
+    );
+}
+
+/// Verifies HSV conversion at the primaries and round trips.
+#[test]
+fn test_hsv() {
+    assert_eq!(Color::hsv(0.0, 1.0, 1.0).to_hex(), "#ff0000");
+    assert_eq!(Color::hsv(120.0, 1.0, 1.0).to_hex(), "#00ff00");
+    assert_eq!(Color::hsv(240.0, 1.0, 0.5).to_hex(), "#000080");
+    assert_eq!(Color::hsv(-60.0, 1.0, 1.0).to_hex(), "#ff00ff");
+    assert_eq!(Color::hsv(300.0, 0.0, 1.0), Color::WHITE);
+
+    let hsv = Hsva::from(Srgba::from_hex("#ffa500").unwrap());
+    assert!((hsv.hue - 38.823_53).abs() < 1e-3, "{hsv:?}");
+    assert!((hsv.saturation - 1.0).abs() < 1e-6);
+    assert_eq!(Color::from(hsv).to_hex(), "#ffa500");
+}
+
+/// Verifies Oklab against reference values and round trips.
+#[test]
+fn test_oklab() {
+    let white = Oklaba::from(LinearRgba::WHITE);
+    assert!((white.lightness - 1.0).abs() < 1e-4);
+    assert!(white.a.abs() < 1e-4 && white.b.abs() < 1e-4);
+
+    let red = palette::RED.to_oklaba();
+    assert!((red.lightness - 0.627_955).abs() < 1e-4, "{red:?}");
+    assert!((red.a - 0.224_863).abs() < 1e-4);
+    assert!((red.b - 0.125_846).abs() < 1e-4);
+
+    for color in [palette::ORANGE, palette::TEAL, palette::PINK] {
+        assert_eq!(Color::from(color.to_oklaba()).to_hex(), color.to_hex());
+    }
+}
+
+/// Verifies blending is perceptual and hits both ends exactly.
+#[test]
+fn test_lerp() {
+    let from = palette::BLUE.with_alpha(0.0);
+    assert_eq!(from.lerp(palette::YELLOW, 0.0).to_hex(), "#0000ff00");
+    assert_eq!(from.lerp(palette::YELLOW, 1.0).to_hex(), "#ffff00");
+
+    // Lightness moves evenly, unlike a linear-light mix.
+    let black_to_white = |t| Color::BLACK.lerp(Color::WHITE, t).to_oklaba().lightness;
+    assert!((black_to_white(0.25) - 0.25).abs() < 1e-3);
+    assert!((black_to_white(0.5) - 0.5).abs() < 1e-3);
+    assert!((Color::BLACK.lerp(Color::WHITE, 0.5).alpha() - 1.0).abs() < 1e-6);
+}
+
+/// Verifies hex output, tinting, and the linear array form.
+#[test]
+fn test_color_conversions() {
+    assert_eq!(Color::hex("#87ceeb").unwrap().to_hex(), "#87ceeb");
+    assert_eq!(
+        Color::srgb_u8(255, 165, 0).to_hex(),
+        palette::ORANGE.to_hex()
+    );
+    assert_eq!(Color::srgb(1.0, 1.0, 1.0), Color::WHITE);
+    assert_eq!(palette::RED.with_alpha(0.5).to_hex(), "#ff000080");
+    assert_eq!(Color::default(), palette::WHITE);
+
+    let tinted = Color::from([0.5, 1.0, 1.0, 1.0]) * palette::CYAN;
+    assert_eq!(
+        tinted.to_array().map(f32::to_bits),
+        [0.0f32, 1.0, 1.0, 1.0].map(f32::to_bits)
+    );
+    assert_eq!(
+        <[f32; 4]>::from(palette::RED).map(f32::to_bits),
+        [1.0f32, 0.0, 0.0, 1.0].map(f32::to_bits)
+    );
+}
+
+/// Verifies named lookups and that the palette matches its hex values.
+#[test]
+fn test_palette() {
+    assert_eq!(palette::named("GREY"), Some(palette::GRAY));
+    assert_eq!(palette::named("#008080").unwrap().to_hex(), "#008080");
+    assert_eq!(palette::named("nope"), None);
+    for (name, hex) in [
+        ("gold", "#ffd700"),
+        ("pink", "#ffc0cb"),
+        ("brown", "#a52a2a"),
+    ] {
+        assert_eq!(palette::named(name).unwrap().to_hex(), hex);
+    }
+}
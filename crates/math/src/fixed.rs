@@ -0,0 +1,456 @@
+//! Deterministic fixed-point arithmetic.
+//!
+//! [`Fixed`] stores a number as a 64-bit integer counting 2⁻³² steps,
+//! giving about nine decimal digits after the point and a range of ±2³¹.
+//! Every operation, including [`sqrt`](Fixed::sqrt) and the trigonometric
+//! functions, is computed with integer arithmetic only, so results are
+//! bit-identical across platforms, compilers, and optimization levels.
+//!
+//! Arithmetic saturates at the ends of the range rather than wrapping,
+//! division rounds towards negative infinity whatever the signs, and
+//! dividing by zero panics, like integer division.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Number of fractional bits.
+const FRAC_BITS: u32 = 32;
+
+/// A Q32.32 fixed-point number.
+///
+/// # Example
+///
+/// ```
+/// use math::Fixed;
+///
+/// let speed = Fixed::from_int(3) / Fixed::from_int(2);
+/// assert_eq!(speed, Fixed::from_ratio(3, 2));
+/// assert_eq!(speed.to_f32(), 1.5);
+/// assert_eq!(Fixed::from_int(16).sqrt(), Fixed::from_int(4));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Fixed(i64);
+
+impl Fixed {
+    /// Zero.
+    pub const ZERO: Self = Self(0);
+    /// One.
+    pub const ONE: Self = Self(1 << FRAC_BITS);
+    /// One half.
+    pub const HALF: Self = Self(1 << (FRAC_BITS - 1));
+    /// The smallest positive value, 2⁻³².
+    pub const EPSILON: Self = Self(1);
+    /// The largest value.
+    pub const MAX: Self = Self(i64::MAX);
+    /// The smallest value.
+    pub const MIN: Self = Self(i64::MIN);
+    /// π, rounded to the nearest step.
+    pub const PI: Self = Self(0x3_243f_6a89);
+    /// π / 2.
+    pub const FRAC_PI_2: Self = Self(0x1_921f_b544);
+    /// 2π.
+    pub const TAU: Self = Self(0x6_487e_d511);
+
+    /// Creates a number from its raw Q32.32 bits.
+    #[must_use]
+    pub const fn from_bits(bits: i64) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw Q32.32 bits.
+    #[must_use]
+    pub const fn to_bits(self) -> i64 {
+        self.0
+    }
+
+    /// Converts an integer.
+    #[must_use]
+    pub const fn from_int(value: i32) -> Self {
+        Self((value as i64) << FRAC_BITS)
+    }
+
+    /// Returns `numerator / denominator`, rounded towards negative infinity
+    /// and saturating.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `denominator` is zero.
+    #[must_use]
+    pub const fn from_ratio(numerator: i32, denominator: i32) -> Self {
+        floor_div((numerator as i128) << FRAC_BITS, denominator as i128)
+    }
+
+    /// Converts a float, rounding to the nearest step and saturating.
+    ///
+    /// The conversion is exact IEEE arithmetic, so it is deterministic for
+    /// the same input bits; use it for loading data, not inside simulation
+    /// steps that mix in float math.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_f32(value: f32) -> Self {
+        Self((f64::from(value) * f64::from(1u32 << 16) * f64::from(1u32 << 16)).round() as i64)
+    }
+
+    /// Converts to the nearest float, for rendering and debugging.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    pub fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
+    /// Converts to the nearest double.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / (f64::from(1u32 << 16) * f64::from(1u32 << 16))
+    }
+
+    /// Returns the largest integer less than or equal to `self`.
+    #[must_use]
+    pub const fn floor(self) -> Self {
+        Self(self.0 & !((1 << FRAC_BITS) - 1))
+    }
+
+    /// Returns the smallest integer greater than or equal to `self`.
+    #[must_use]
+    pub const fn ceil(self) -> Self {
+        Self(self.0.saturating_add((1 << FRAC_BITS) - 1)).floor()
+    }
+
+    /// Returns the nearest integer, rounding halves up.
+    #[must_use]
+    pub const fn round(self) -> Self {
+        Self(self.0.saturating_add(1 << (FRAC_BITS - 1))).floor()
+    }
+
+    /// Returns the integer part, rounded towards negative infinity.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn to_int(self) -> i32 {
+        (self.0 >> FRAC_BITS) as i32
+    }
+
+    /// Returns the absolute value, saturating at [`MAX`](Self::MAX).
+    #[must_use]
+    pub const fn abs(self) -> Self {
+        Self(self.0.saturating_abs())
+    }
+
+    /// Returns `-1`, `0`, or `1` matching the sign of `self`.
+    #[must_use]
+    pub const fn signum(self) -> Self {
+        Self::from_int(self.0.signum() as i32)
+    }
+
+    /// Returns `self / rhs`, or `None` if `rhs` is zero.
+    #[must_use]
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        (rhs.0 != 0).then(|| self / rhs)
+    }
+
+    /// Returns the square root, or zero for negative numbers.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            return Self::ZERO;
+        }
+        // sqrt(bits / 2^32) * 2^32 = sqrt(bits * 2^32)
+        Self(((self.0 as u128) << FRAC_BITS).isqrt() as i64)
+    }
+
+    /// Returns the sine of `self` radians.
+    #[must_use]
+    pub fn sin(self) -> Self {
+        // Reduce to [-π, π], then fold into [-π/2, π/2], where the series
+        // converges quickly.
+        let mut x = Self((self + Self::PI).0.rem_euclid(Self::TAU.0)) - Self::PI;
+        if x > Self::FRAC_PI_2 {
+            x = Self::PI - x;
+        } else if x < -Self::FRAC_PI_2 {
+            x = -Self::PI - x;
+        }
+        // Taylor series to x^15, evaluated in Horner form:
+        // x (1 - x²/(2·3) (1 - x²/(4·5) (1 - ...)))
+        let x2 = x * x;
+        let mut sum = Self::ONE;
+        for n in (1..=7).rev() {
+            let k = 2 * n;
+            sum = Self::ONE - x2 * sum / Self::from_int(k * (k + 1));
+        }
+        x * sum
+    }
+
+    /// Returns the cosine of `self` radians.
+    #[must_use]
+    pub fn cos(self) -> Self {
+        (self + Self::FRAC_PI_2).sin()
+    }
+
+    /// Returns the smaller of two numbers.
+    #[must_use]
+    pub fn min(self, other: Self) -> Self {
+        Ord::min(self, other)
+    }
+
+    /// Returns the larger of two numbers.
+    #[must_use]
+    pub fn max(self, other: Self) -> Self {
+        Ord::max(self, other)
+    }
+
+    /// Restricts `self` to `min..=max`.
+    #[must_use]
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        Ord::clamp(self, min, max)
+    }
+
+    /// Interpolates linearly from `self` to `other` by `t`.
+    #[must_use]
+    pub fn lerp(self, other: Self, t: Self) -> Self {
+        self + (other - self) * t
+    }
+}
+
+/// Saturates a wide intermediate result into the Q32.32 range.
+#[allow(clippy::cast_possible_truncation)]
+const fn saturate(value: i128) -> Fixed {
+    if value > i64::MAX as i128 {
+        Fixed::MAX
+    } else if value < i64::MIN as i128 {
+        Fixed::MIN
+    } else {
+        Fixed(value as i64)
+    }
+}
+
+/// Divides wide Q32.32 bits, rounding towards negative infinity for either
+/// sign of `denominator`, and saturates the quotient.
+const fn floor_div(numerator: i128, denominator: i128) -> Fixed {
+    let quotient = numerator / denominator;
+    if numerator % denominator != 0 && (numerator < 0) != (denominator < 0) {
+        saturate(quotient - 1)
+    } else {
+        saturate(quotient)
+    }
+}
+
+impl From<i32> for Fixed {
+    fn from(value: i32) -> Self {
+        Self::from_int(value)
+    }
+}
+
+impl Add for Fixed {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        saturate((i128::from(self.0) * i128::from(rhs.0)) >> FRAC_BITS)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Self;
+
+    /// Rounds towards negative infinity, so `a / -b == -a / b`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: Self) -> Self {
+        assert!(rhs.0 != 0, "fixed-point division by zero");
+        floor_div(i128::from(self.0) << FRAC_BITS, i128::from(rhs.0))
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(self.0.saturating_neg())
+    }
+}
+
+impl AddAssign for Fixed {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Fixed {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Fixed {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for Fixed {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_f64(), f)
+    }
+}
+
+/// A 3D vector of [`Fixed`] components.
+///
+/// # Example
+///
+/// ```
+/// use math::{Fixed, FixedVec3};
+///
+/// let v = FixedVec3::new(Fixed::from_int(3), Fixed::ZERO, Fixed::from_int(4));
+/// assert_eq!(v.length(), Fixed::from_int(5));
+/// assert_eq!(v.normalize().x, Fixed::from_ratio(3, 5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FixedVec3 {
+    /// X component.
+    pub x: Fixed,
+    /// Y component.
+    pub y: Fixed,
+    /// Z component.
+    pub z: Fixed,
+}
+
+impl FixedVec3 {
+    /// All zeroes.
+    pub const ZERO: Self = Self::new(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO);
+
+    /// Creates a vector.
+    #[must_use]
+    pub const fn new(x: Fixed, y: Fixed, z: Fixed) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Converts float components, as with [`Fixed::from_f32`].
+    #[must_use]
+    pub fn from_f32(v: [f32; 3]) -> Self {
+        Self::new(
+            Fixed::from_f32(v[0]),
+            Fixed::from_f32(v[1]),
+            Fixed::from_f32(v[2]),
+        )
+    }
+
+    /// Converts to float components, for rendering.
+    #[must_use]
+    pub fn to_f32(self) -> [f32; 3] {
+        [self.x.to_f32(), self.y.to_f32(), self.z.to_f32()]
+    }
+
+    /// Returns the dot product.
+    #[must_use]
+    pub fn dot(self, other: Self) -> Fixed {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Returns the cross product.
+    #[must_use]
+    pub fn cross(self, other: Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Returns the squared length.
+    #[must_use]
+    pub fn length_squared(self) -> Fixed {
+        self.dot(self)
+    }
+
+    /// Returns the length.
+    #[must_use]
+    pub fn length(self) -> Fixed {
+        self.length_squared().sqrt()
+    }
+
+    /// Returns the vector scaled to length one, or zero for a zero vector.
+    #[must_use]
+    pub fn normalize(self) -> Self {
+        let length = self.length();
+        if length == Fixed::ZERO {
+            return Self::ZERO;
+        }
+        self / length
+    }
+}
+
+impl Add for FixedVec3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for FixedVec3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<Fixed> for FixedVec3 {
+    type Output = Self;
+
+    fn mul(self, rhs: Fixed) -> Self {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Div<Fixed> for FixedVec3 {
+    type Output = Self;
+
+    fn div(self, rhs: Fixed) -> Self {
+        Self::new(self.x / rhs, self.y / rhs, self.z / rhs)
+    }
+}
+
+impl Neg for FixedVec3 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl AddAssign for FixedVec3 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for FixedVec3 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
@@ -0,0 +1,130 @@
+//! Unit tests for fixed-point arithmetic.
+
+use crate::fixed::{Fixed, FixedVec3};
+
+fn assert_near(actual: Fixed, expected: f64, tolerance: f64) {
+    assert!(
+        (actual.to_f64() - expected).abs() <= tolerance,
+        "{actual} is not within {tolerance} of {expected}"
+    );
+}
+
+/// Verifies basic arithmetic is exact for representable values.
+#[test]
+fn test_arithmetic() {
+    let a = Fixed::from_ratio(3, 2);
+    let b = Fixed::from_int(-4);
+    assert_eq!(a + b, Fixed::from_ratio(-5, 2));
+    assert_eq!(a - b, Fixed::from_ratio(11, 2));
+    assert_eq!(a * b, Fixed::from_int(-6));
+    assert_eq!(b / a, Fixed::from_ratio(-8, 3));
+    assert_eq!(-a, Fixed::from_ratio(-3, 2));
+    assert_eq!(Fixed::from_f32(0.25), Fixed::from_ratio(1, 4));
+    assert_eq!(
+        Fixed::from_ratio(1, 4).to_f32().to_bits(),
+        0.25f32.to_bits()
+    );
+}
+
+/// Verifies rounding helpers on positive and negative values.
+#[test]
+fn test_rounding() {
+    let x = Fixed::from_ratio(-5, 2);
+    assert_eq!(x.floor(), Fixed::from_int(-3));
+    assert_eq!(x.ceil(), Fixed::from_int(-2));
+    assert_eq!(x.round(), Fixed::from_int(-2));
+    assert_eq!(x.to_int(), -3);
+    assert_eq!(Fixed::from_ratio(7, 4).round(), Fixed::from_int(2));
+    assert_eq!(x.abs(), Fixed::from_ratio(5, 2));
+    assert_eq!(x.signum(), Fixed::from_int(-1));
+}
+
+/// Verifies arithmetic saturates instead of wrapping.
+#[test]
+fn test_saturation() {
+    assert_eq!(Fixed::MAX + Fixed::ONE, Fixed::MAX);
+    assert_eq!(Fixed::MIN - Fixed::ONE, Fixed::MIN);
+    assert_eq!(
+        Fixed::from_int(1 << 20) * Fixed::from_int(1 << 20),
+        Fixed::MAX
+    );
+    assert_eq!(-Fixed::MIN, Fixed::MAX);
+    assert_eq!(Fixed::ONE.checked_div(Fixed::ZERO), None);
+}
+
+/// Verifies division rounds towards negative infinity for either sign of
+/// the divisor.
+#[test]
+fn test_division_rounds_down() {
+    let one = Fixed::ONE;
+    let three = Fixed::from_int(3);
+    let third = one / three;
+    assert_eq!(third, Fixed::from_ratio(1, 3));
+    assert_eq!(-one / -three, third);
+    assert_eq!(one / -three, -third - Fixed::EPSILON);
+    assert_eq!(-one / three, one / -three);
+    assert_eq!(Fixed::from_ratio(1, -3), one / -three);
+    assert_eq!(Fixed::from_ratio(-1, -3), third);
+}
+
+/// Verifies ratios whose quotient is out of range saturate instead of
+/// overflowing.
+#[test]
+fn test_from_ratio_saturates() {
+    assert_eq!(Fixed::from_ratio(i32::MIN, -1), Fixed::MAX);
+    assert_eq!(Fixed::from_ratio(i32::MIN, 1), Fixed::MIN);
+    assert_eq!(Fixed::MIN / -Fixed::ONE, Fixed::MAX);
+}
+
+/// Verifies division by zero panics like integer division.
+#[test]
+#[should_panic(expected = "division by zero")]
+fn test_division_by_zero_panics() {
+    let _ = Fixed::ONE / Fixed::ZERO;
+}
+
+/// Verifies square roots are exact for perfect squares and accurate
+/// elsewhere.
+#[test]
+fn test_sqrt() {
+    assert_eq!(Fixed::from_int(81).sqrt(), Fixed::from_int(9));
+    assert_eq!(Fixed::from_ratio(1, 4).sqrt(), Fixed::HALF);
+    assert_near(Fixed::from_int(2).sqrt(), std::f64::consts::SQRT_2, 1e-9);
+    assert_eq!(Fixed::from_int(-1).sqrt(), Fixed::ZERO);
+}
+
+/// Verifies sine and cosine against the float functions over several
+/// periods.
+#[test]
+fn test_trigonometry() {
+    for step in -400..=400 {
+        let x = Fixed::from_ratio(step, 40);
+        assert_near(x.sin(), x.to_f64().sin(), 1e-8);
+        assert_near(x.cos(), x.to_f64().cos(), 1e-8);
+    }
+    assert_eq!(Fixed::ZERO.sin(), Fixed::ZERO);
+}
+
+/// Verifies vector operations.
+#[test]
+fn test_vector() {
+    let x = FixedVec3::new(Fixed::ONE, Fixed::ZERO, Fixed::ZERO);
+    let y = FixedVec3::new(Fixed::ZERO, Fixed::ONE, Fixed::ZERO);
+    assert_eq!(
+        x.cross(y),
+        FixedVec3::new(Fixed::ZERO, Fixed::ZERO, Fixed::ONE)
+    );
+    assert_eq!(x.dot(y), Fixed::ZERO);
+    assert_eq!(
+        (x + y) * Fixed::from_int(2) - y,
+        FixedVec3::new(Fixed::from_int(2), Fixed::ONE, Fixed::ZERO)
+    );
+    assert_eq!(FixedVec3::ZERO.normalize(), FixedVec3::ZERO);
+    assert_near((x + y).normalize().length(), 1.0, 1e-9);
+    assert_eq!(
+        FixedVec3::from_f32([1.5, -2.0, 0.0])
+            .to_f32()
+            .map(f32::to_bits),
+        [1.5f32, -2.0, 0.0].map(f32::to_bits)
+    );
+}
@@ -0,0 +1,95 @@
+//! Interpolation and smoothing helpers.
+//!
+//! Vectors and quaternions interpolate with glam's own `lerp` and `slerp`;
+//! these cover scalars and frame-rate independent smoothing.
+
+use std::ops::{Add, Mul, Sub};
+
+/// Returns the value `t` of the way from `from` to `to`; `t` outside 0..1
+/// extrapolates.
+#[must_use]
+pub fn lerp<T>(from: T, to: T, t: f32) -> T
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+{
+    from + (to - from) * t
+}
+
+/// Returns how far `value` is from `from` to `to`, the inverse of
+/// [`lerp`]; zero if the range is empty.
+#[must_use]
+pub fn inverse_lerp(from: f32, to: f32, value: f32) -> f32 {
+    let range = to - from;
+    if range.abs() <= f32::EPSILON {
+        0.0
+    } else {
+        (value - from) / range
+    }
+}
+
+/// Maps `value` from one range to another.
+///
+/// # Example
+///
+/// ```
+/// use math::interpolation::remap;
+///
+/// // A stick deflection of 0.625 past a 0.25 dead zone is half speed.
+/// assert_eq!(remap(0.625, (0.25, 1.0), (0.0, 1.0)), 0.5);
+/// ```
+#[must_use]
+pub fn remap(value: f32, from: (f32, f32), to: (f32, f32)) -> f32 {
+    lerp(to.0, to.1, inverse_lerp(from.0, from.1, value))
+}
+
+/// Hermite smoothstep of `value` between `edge0` and `edge1`, clamped to
+/// 0..1.
+#[must_use]
+pub fn smoothstep(edge0: f32, edge1: f32, value: f32) -> f32 {
+    let t = inverse_lerp(edge0, edge1, value).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Ken Perlin's smootherstep, with zero first and second derivatives at
+/// the edges.
+#[must_use]
+pub fn smootherstep(edge0: f32, edge1: f32, value: f32) -> f32 {
+    let t = inverse_lerp(edge0, edge1, value).clamp(0.0, 1.0);
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+/// Moves `current` towards `target` by at most `max_delta`, without
+/// overshooting.
+#[must_use]
+pub fn move_towards(current: f32, target: f32, max_delta: f32) -> f32 {
+    let difference = target - current;
+    if difference.abs() <= max_delta {
+        target
+    } else {
+        current + difference.signum() * max_delta
+    }
+}
+
+/// Smooths `current` towards `target` exponentially, closing the gap at
+/// `rate` per second whatever the frame rate.
+///
+/// Use instead of `lerp(current, target, k)` per frame, which converges
+/// faster at higher frame rates.
+///
+/// # Example
+///
+/// ```
+/// use math::interpolation::exp_decay;
+///
+/// // One 1/30 s frame equals two 1/60 s frames.
+/// let once = exp_decay(0.0, 10.0, 5.0, 1.0 / 30.0);
+/// let twice = exp_decay(exp_decay(0.0, 10.0, 5.0, 1.0 / 60.0), 10.0, 5.0, 1.0 / 60.0);
+/// assert!((once - twice).abs() < 1e-5);
+/// ```
+#[must_use]
+pub fn exp_decay<T>(current: T, target: T, rate: f32, seconds: f32) -> T
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T>,
+{
+    lerp(current, target, 1.0 - (-rate * seconds).exp())
+}
@@ -0,0 +1,56 @@
+//! Unit tests for interpolation helpers.
+
+use crate::interpolation::{
+    exp_decay, inverse_lerp, lerp, move_towards, remap, smootherstep, smoothstep,
+};
+use glam::Vec3;
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 1e-5, "{actual} != {expected}");
+}
+
+/// Verifies linear interpolation of scalars and vectors, and its inverse.
+#[test]
+fn test_lerp() {
+    assert_close(lerp(2.0, 6.0, 0.25), 3.0);
+    assert_close(lerp(2.0, 6.0, 1.5), 8.0);
+    assert_eq!(lerp(Vec3::ZERO, Vec3::splat(2.0), 0.5), Vec3::ONE);
+    assert_close(inverse_lerp(2.0, 6.0, 3.0), 0.25);
+    assert_close(inverse_lerp(1.0, 1.0, 3.0), 0.0);
+    assert_close(remap(5.0, (0.0, 10.0), (100.0, 200.0)), 150.0);
+}
+
+/// Verifies the smoothing curves are clamped and symmetric.
+#[test]
+fn test_smoothsteps() {
+    for f in [smoothstep, smootherstep] {
+        assert_close(f(0.0, 1.0, -1.0), 0.0);
+        assert_close(f(0.0, 1.0, 0.5), 0.5);
+        assert_close(f(0.0, 1.0, 2.0), 1.0);
+        assert_close(f(0.0, 1.0, 0.25) + f(0.0, 1.0, 0.75), 1.0);
+    }
+    assert!(smootherstep(0.0, 1.0, 0.1) < smoothstep(0.0, 1.0, 0.1));
+}
+
+/// Verifies stepping towards a target stops at it.
+#[test]
+fn test_move_towards() {
+    assert_close(move_towards(0.0, 10.0, 3.0), 3.0);
+    assert_close(move_towards(0.0, -10.0, 3.0), -3.0);
+    assert_close(move_towards(9.0, 10.0, 3.0), 10.0);
+}
+
+/// Verifies exponential smoothing is independent of the step size.
+#[test]
+fn test_exp_decay_is_frame_rate_independent() {
+    let mut at_30 = 0.0;
+    for _ in 0..30 {
+        at_30 = exp_decay(at_30, 1.0, 3.0, 1.0 / 30.0);
+    }
+    let mut at_144 = 0.0;
+    for _ in 0..144 {
+        at_144 = exp_decay(at_144, 1.0, 3.0, 1.0 / 144.0);
+    }
+    assert_close(at_30, at_144);
+    assert_close(at_30, 1.0 - (-3.0f32).exp());
+}
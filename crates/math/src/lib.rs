@@ -1 +1,72 @@
-// engine-math library root
+//! Math primitives for the Rustgine game engine.
+//!
+//! # Overview
+//!
+//! The math crate provides:
+//! - Vector, matrix, and quaternion types, re-exported from glam ([`Vec3`],
+//!   [`Mat4`], [`Quat`], ...)
+//! - Rectangles, bounding boxes, planes, and rays ([`Rect`], [`Aabb`],
+//!   [`Plane`], [`Ray`])
+//! - The engine-wide [`Color`] type with sRGB, linear, HSV, and Oklab
+//!   conversions, perceptual blending, and named colors ([`palette`])
+//! - Scalar interpolation and frame-rate independent smoothing
+//!   ([`interpolation`])
+//! - Q32.32 fixed-point numbers and vectors whose results are bit-identical
+//!   on every platform ([`Fixed`], [`FixedVec3`])
+//! - Checksums of simulation state for comparing lockstep peers and
+//!   replays ([`StateChecksum`])
+//!
+//! # Determinism
+//!
+//! Floating-point results can differ across CPUs, compilers, and math
+//! libraries, which desynchronizes lockstep multiplayer games. Gameplay
+//! code that must agree across machines can opt in by doing its math in
+//! [`Fixed`], which only uses integer arithmetic. Rigid-body physics stays
+//! in floats; enable the `deterministic` feature of the physics crate (or
+//! the app) to build it with cross-platform float determinism instead.
+//!
+//! ```
+//! use math::{Fixed, StateChecksum};
+//!
+//! let mut angle = Fixed::ZERO;
+//! let mut position = Fixed::ZERO;
+//! for _ in 0..60 {
+//!     angle += Fixed::from_ratio(1, 60);
+//!     position += angle.sin() * Fixed::from_ratio(1, 10);
+//! }
+//!
+//! let mut checksum = StateChecksum::default();
+//! checksum.write_fixed(position);
+//! // Every platform computes the same bits.
+//! assert_eq!(position.to_bits(), 0x0000_0002_ccd9_a28f);
+//! # let _ = checksum.finish();
+//! ```
+
+#![warn(missing_docs)]
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+pub mod checksum;
+#[cfg(test)]
+mod checksum_test;
+pub mod color;
+#[cfg(test)]
+mod color_test;
+pub mod fixed;
+#[cfg(test)]
+mod fixed_test;
+pub mod interpolation;
+#[cfg(test)]
+mod interpolation_test;
+pub mod palette;
+pub mod shapes;
+#[cfg(test)]
+mod shapes_test;
+
+pub use checksum::StateChecksum;
+pub use color::{Color, Hsva, LinearRgba, Oklaba, Srgba};
+pub use fixed::{Fixed, FixedVec3};
+pub use glam;
+pub use glam::{EulerRot, IVec2, IVec3, Mat3, Mat4, Quat, UVec2, UVec3, Vec2, Vec3, Vec3A, Vec4};
+pub use interpolation::{exp_decay, lerp};
+pub use shapes::{Aabb, Plane, Ray, Rect};
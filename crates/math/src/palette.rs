@@ -0,0 +1,107 @@
+//! Named colors.
+//!
+//! The CSS basic colors plus a few extras, defined by their sRGB hex
+//! values and stored in linear light like every [`Color`]. [`named`] looks
+//! one up by name, for colors written in config files and console commands.
+
+use crate::color::Color;
+
+/// `#ffffff`.
+pub const WHITE: Color = Color::WHITE;
+
+/// `#000000`.
+pub const BLACK: Color = Color::BLACK;
+
+/// `#ff0000`.
+pub const RED: Color = Color::linear_rgb(1.0, 0.0, 0.0);
+
+/// `#008000`.
+pub const GREEN: Color = Color::linear_rgb(0.0, 0.215_860_5, 0.0);
+
+/// `#00ff00`.
+pub const LIME: Color = Color::linear_rgb(0.0, 1.0, 0.0);
+
+/// `#0000ff`.
+pub const BLUE: Color = Color::linear_rgb(0.0, 0.0, 1.0);
+
+/// `#ffff00`.
+pub const YELLOW: Color = Color::linear_rgb(1.0, 1.0, 0.0);
+
+/// `#00ffff`.
+pub const CYAN: Color = Color::linear_rgb(0.0, 1.0, 1.0);
+
+/// `#ff00ff`.
+pub const MAGENTA: Color = Color::linear_rgb(1.0, 0.0, 1.0);
+
+/// `#ffa500`.
+pub const ORANGE: Color = Color::linear_rgb(1.0, 0.376_262_13, 0.0);
+
+/// `#800080`.
+pub const PURPLE: Color = Color::linear_rgb(0.215_860_5, 0.0, 0.215_860_5);
+
+/// `#ffc0cb`.
+pub const PINK: Color = Color::linear_rgb(1.0, 0.527_115_1, 0.597_201_76);
+
+/// `#a52a2a`.
+pub const BROWN: Color = Color::linear_rgb(0.376_262_13, 0.023_153_367, 0.023_153_367);
+
+/// `#808080`.
+pub const GRAY: Color = Color::linear_rgb(0.215_860_5, 0.215_860_5, 0.215_860_5);
+
+/// `#c0c0c0`.
+pub const SILVER: Color = Color::linear_rgb(0.527_115_1, 0.527_115_1, 0.527_115_1);
+
+/// `#000080`.
+pub const NAVY: Color = Color::linear_rgb(0.0, 0.0, 0.215_860_5);
+
+/// `#008080`.
+pub const TEAL: Color = Color::linear_rgb(0.0, 0.215_860_5, 0.215_860_5);
+
+/// `#800000`.
+pub const MAROON: Color = Color::linear_rgb(0.215_860_5, 0.0, 0.0);
+
+/// `#808000`.
+pub const OLIVE: Color = Color::linear_rgb(0.215_860_5, 0.215_860_5, 0.0);
+
+/// `#ffd700`.
+pub const GOLD: Color = Color::linear_rgb(1.0, 0.679_542_5, 0.0);
+
+/// Fully transparent black.
+pub const TRANSPARENT: Color = Color::TRANSPARENT;
+
+/// Returns the color named `name`, ignoring case, or parses it as hex.
+///
+/// ```
+/// use math::palette;
+///
+/// assert_eq!(palette::named("Orange"), Some(palette::ORANGE));
+/// assert_eq!(palette::named("#ff0000"), Some(palette::RED));
+/// assert_eq!(palette::named("chartreuse"), None);
+/// ```
+#[must_use]
+pub fn named(name: &str) -> Option<Color> {
+    Some(match name.to_ascii_lowercase().as_str() {
+        "white" => WHITE,
+        "black" => BLACK,
+        "red" => RED,
+        "green" => GREEN,
+        "lime" => LIME,
+        "blue" => BLUE,
+        "yellow" => YELLOW,
+        "cyan" => CYAN,
+        "magenta" => MAGENTA,
+        "orange" => ORANGE,
+        "purple" => PURPLE,
+        "pink" => PINK,
+        "brown" => BROWN,
+        "gray" | "grey" => GRAY,
+        "silver" => SILVER,
+        "navy" => NAVY,
+        "teal" => TEAL,
+        "maroon" => MAROON,
+        "olive" => OLIVE,
+        "gold" => GOLD,
+        "transparent" => TRANSPARENT,
+        _ => return Color::hex(name),
+    })
+}
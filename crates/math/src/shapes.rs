@@ -0,0 +1,357 @@
+//! Geometric primitives: rectangles, boxes, planes, and rays.
+
+use glam::{Mat4, Vec2, Vec3};
+use serde::{Deserialize, Serialize};
+
+/// An axis-aligned 2D rectangle.
+///
+/// # Example
+///
+/// ```
+/// use math::{Rect, Vec2};
+///
+/// let screen = Rect::new(Vec2::ZERO, Vec2::new(1920.0, 1080.0));
+/// let panel = Rect::from_center_size(Vec2::new(1900.0, 540.0), Vec2::splat(100.0));
+/// assert_eq!(screen.intersection(&panel).unwrap().width(), 70.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Rect {
+    /// Corner with the smallest coordinates.
+    pub min: Vec2,
+    /// Corner with the largest coordinates.
+    pub max: Vec2,
+}
+
+impl Rect {
+    /// Creates a rectangle spanning two corners, in either order.
+    #[must_use]
+    pub fn new(a: Vec2, b: Vec2) -> Self {
+        Self {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    /// Creates a rectangle of `size` centered on `center`.
+    #[must_use]
+    pub fn from_center_size(center: Vec2, size: Vec2) -> Self {
+        let half = size.abs() * 0.5;
+        Self {
+            min: center - half,
+            max: center + half,
+        }
+    }
+
+    /// Returns the width.
+    #[must_use]
+    pub fn width(&self) -> f32 {
+        self.max.x - self.min.x
+    }
+
+    /// Returns the height.
+    #[must_use]
+    pub fn height(&self) -> f32 {
+        self.max.y - self.min.y
+    }
+
+    /// Returns the width and height.
+    #[must_use]
+    pub fn size(&self) -> Vec2 {
+        self.max - self.min
+    }
+
+    /// Returns the center.
+    #[must_use]
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Returns `true` if `point` is inside or on the edge.
+    #[must_use]
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.cmpge(self.min).all() && point.cmple(self.max).all()
+    }
+
+    /// Returns `true` if the rectangles overlap or touch.
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.cmple(other.max).all() && other.min.cmple(self.max).all()
+    }
+
+    /// Returns the overlap of two rectangles, if any.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.intersects(other).then(|| Self {
+            min: self.min.max(other.min),
+            max: self.max.min(other.max),
+        })
+    }
+
+    /// Returns the smallest rectangle containing both.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+}
+
+/// An axis-aligned 3D bounding box.
+///
+/// # Example
+///
+/// ```
+/// use math::{Aabb, Mat4, Vec3};
+///
+/// let unit = Aabb::from_center_half_extents(Vec3::ZERO, Vec3::splat(0.5));
+/// let moved = unit.transformed(&Mat4::from_translation(Vec3::X * 10.0));
+/// assert_eq!(moved.center(), Vec3::X * 10.0);
+/// assert!(!unit.intersects(&moved));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Aabb {
+    /// Corner with the smallest coordinates.
+    pub min: Vec3,
+    /// Corner with the largest coordinates.
+    pub max: Vec3,
+}
+
+impl Aabb {
+    /// Creates a box spanning two corners, in either order.
+    #[must_use]
+    pub fn new(a: Vec3, b: Vec3) -> Self {
+        Self {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    /// Creates a box extending `half_extents` from `center` on each axis.
+    #[must_use]
+    pub fn from_center_half_extents(center: Vec3, half_extents: Vec3) -> Self {
+        let half = half_extents.abs();
+        Self {
+            min: center - half,
+            max: center + half,
+        }
+    }
+
+    /// Returns the smallest box containing every point, or `None` if there
+    /// are none.
+    #[must_use]
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Option<Self> {
+        points
+            .into_iter()
+            .fold(None, |bounds: Option<Self>, point| {
+                Some(bounds.map_or(
+                    Self {
+                        min: point,
+                        max: point,
+                    },
+                    |b| Self {
+                        min: b.min.min(point),
+                        max: b.max.max(point),
+                    },
+                ))
+            })
+    }
+
+    /// Returns the center.
+    #[must_use]
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Returns the distance from the center to the faces on each axis.
+    #[must_use]
+    pub fn half_extents(&self) -> Vec3 {
+        (self.max - self.min) * 0.5
+    }
+
+    /// Returns the eight corners.
+    #[must_use]
+    pub fn corners(&self) -> [Vec3; 8] {
+        std::array::from_fn(|i| {
+            Vec3::new(
+                if i & 1 == 0 { self.min.x } else { self.max.x },
+                if i & 2 == 0 { self.min.y } else { self.max.y },
+                if i & 4 == 0 { self.min.z } else { self.max.z },
+            )
+        })
+    }
+
+    /// Returns `true` if `point` is inside or on a face.
+    #[must_use]
+    pub fn contains(&self, point: Vec3) -> bool {
+        point.cmpge(self.min).all() && point.cmple(self.max).all()
+    }
+
+    /// Returns `true` if `other` lies entirely inside this box.
+    #[must_use]
+    pub fn contains_aabb(&self, other: &Self) -> bool {
+        self.min.cmple(other.min).all() && other.max.cmple(self.max).all()
+    }
+
+    /// Returns `true` if the boxes overlap or touch.
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.cmple(other.max).all() && other.min.cmple(self.max).all()
+    }
+
+    /// Returns the distance from `point` to the box, zero inside it.
+    #[must_use]
+    pub fn distance_to(&self, point: Vec3) -> f32 {
+        (self.min - point)
+            .max(point - self.max)
+            .max(Vec3::ZERO)
+            .length()
+    }
+
+    /// Returns the smallest box containing both.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// Returns this box grown by `margin` on every side.
+    #[must_use]
+    pub fn expanded(&self, margin: f32) -> Self {
+        Self {
+            min: self.min - Vec3::splat(margin),
+            max: self.max + Vec3::splat(margin),
+        }
+    }
+
+    /// Returns the box containing this one after `transform`.
+    #[must_use]
+    pub fn transformed(&self, transform: &Mat4) -> Self {
+        let center = transform.transform_point3(self.center());
+        let half = self.half_extents();
+        // Each world axis extent is the sum of the rotated extents.
+        let extent = transform.x_axis.truncate().abs() * half.x
+            + transform.y_axis.truncate().abs() * half.y
+            + transform.z_axis.truncate().abs() * half.z;
+        Self::from_center_half_extents(center, extent)
+    }
+}
+
+/// An infinite plane: the points `p` where `normal.dot(p) == distance`.
+///
+/// # Example
+///
+/// ```
+/// use math::{Plane, Vec3};
+///
+/// let ground = Plane::from_point_normal(Vec3::ZERO, Vec3::Y);
+/// assert_eq!(ground.signed_distance(Vec3::new(3.0, 2.0, 0.0)), 2.0);
+/// assert_eq!(ground.project(Vec3::new(3.0, 2.0, 0.0)), Vec3::new(3.0, 0.0, 0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Plane {
+    /// Unit normal, pointing to the positive side.
+    pub normal: Vec3,
+    /// Signed distance from the origin along the normal.
+    pub distance: f32,
+}
+
+impl Plane {
+    /// Creates the plane through `point` facing `normal`, which need not be
+    /// normalized.
+    #[must_use]
+    pub fn from_point_normal(point: Vec3, normal: Vec3) -> Self {
+        let normal = normal.normalize_or_zero();
+        Self {
+            normal,
+            distance: normal.dot(point),
+        }
+    }
+
+    /// Creates the plane through three points, facing the side from which
+    /// they wind counter-clockwise, or `None` if they are collinear.
+    #[must_use]
+    pub fn from_points(a: Vec3, b: Vec3, c: Vec3) -> Option<Self> {
+        let normal = (b - a).cross(c - a).try_normalize()?;
+        Some(Self {
+            normal,
+            distance: normal.dot(a),
+        })
+    }
+
+    /// Returns the distance of `point` from the plane, negative behind it.
+    #[must_use]
+    pub fn signed_distance(&self, point: Vec3) -> f32 {
+        self.normal.dot(point) - self.distance
+    }
+
+    /// Returns the closest point on the plane to `point`.
+    #[must_use]
+    pub fn project(&self, point: Vec3) -> Vec3 {
+        point - self.normal * self.signed_distance(point)
+    }
+}
+
+/// A half-line from an origin along a unit direction.
+///
+/// # Example
+///
+/// ```
+/// use math::{Aabb, Plane, Ray, Vec3};
+///
+/// let ray = Ray::new(Vec3::new(0.0, 5.0, 0.0), Vec3::NEG_Y);
+/// assert_eq!(ray.intersect_plane(&Plane::from_point_normal(Vec3::ZERO, Vec3::Y)), Some(5.0));
+///
+/// let crate_box = Aabb::from_center_half_extents(Vec3::new(0.0, 1.0, 0.0), Vec3::ONE);
+/// assert_eq!(ray.intersect_aabb(&crate_box), Some(3.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Ray {
+    /// Start point.
+    pub origin: Vec3,
+    /// Unit direction.
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// Creates a ray, normalizing `direction`.
+    #[must_use]
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Self {
+            origin,
+            direction: direction.normalize_or_zero(),
+        }
+    }
+
+    /// Returns the point `distance` along the ray.
+    #[must_use]
+    pub fn at(&self, distance: f32) -> Vec3 {
+        self.origin + self.direction * distance
+    }
+
+    /// Returns the distance along the ray to the plane, if the ray hits it
+    /// from either side.
+    #[must_use]
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<f32> {
+        let facing = plane.normal.dot(self.direction);
+        if facing.abs() <= f32::EPSILON {
+            return None;
+        }
+        let distance = -plane.signed_distance(self.origin) / facing;
+        (distance >= 0.0).then_some(distance)
+    }
+
+    /// Returns the distance along the ray to where it enters the box, or
+    /// zero if it starts inside.
+    #[must_use]
+    pub fn intersect_aabb(&self, aabb: &Aabb) -> Option<f32> {
+        let inverse = self.direction.recip();
+        let t1 = (aabb.min - self.origin) * inverse;
+        let t2 = (aabb.max - self.origin) * inverse;
+        let near = t1.min(t2).max_element().max(0.0);
+        let far = t1.max(t2).min_element();
+        (near <= far).then_some(near)
+    }
+}
@@ -0,0 +1,104 @@
+//! Unit tests for geometric primitives.
+
+use crate::shapes::{Aabb, Plane, Ray, Rect};
+use glam::{Mat4, Quat, Vec2, Vec3};
+
+/// Verifies rectangle construction, containment, and overlap.
+#[test]
+fn test_rect() {
+    let a = Rect::new(Vec2::new(4.0, 4.0), Vec2::ZERO);
+    assert_eq!(a.min, Vec2::ZERO);
+    assert_eq!(a.size(), Vec2::splat(4.0));
+    assert_eq!(a.center(), Vec2::splat(2.0));
+    assert!(a.contains(Vec2::new(4.0, 0.0)));
+    assert!(!a.contains(Vec2::new(4.1, 0.0)));
+
+    let b = Rect::from_center_size(Vec2::new(5.0, 2.0), Vec2::new(4.0, 2.0));
+    assert_eq!(
+        a.intersection(&b),
+        Some(Rect::new(Vec2::new(3.0, 1.0), Vec2::new(4.0, 3.0)))
+    );
+    assert_eq!(a.union(&b).max, Vec2::new(7.0, 4.0));
+    let far = Rect::from_center_size(Vec2::splat(100.0), Vec2::ONE);
+    assert!(!a.intersects(&far));
+    assert_eq!(a.intersection(&far), None);
+}
+
+/// Verifies bounding boxes from points and their overlap tests.
+#[test]
+fn test_aabb() {
+    let bounds = Aabb::from_points([Vec3::new(1.0, -2.0, 0.0), Vec3::new(-1.0, 3.0, 2.0)]).unwrap();
+    assert_eq!(
+        bounds,
+        Aabb::new(Vec3::new(-1.0, -2.0, 0.0), Vec3::new(1.0, 3.0, 2.0))
+    );
+    assert_eq!(bounds.half_extents(), Vec3::new(1.0, 2.5, 1.0));
+    assert!(bounds.contains(Vec3::new(0.0, 3.0, 1.0)));
+    assert!(Aabb::from_points([]).is_none());
+    assert!(bounds.intersects(&Aabb::from_center_half_extents(
+        Vec3::new(2.0, 0.0, 0.0),
+        Vec3::ONE
+    )));
+}
+
+/// Verifies box containment, growth, and point distances.
+#[test]
+fn test_aabb_containment_and_distance() {
+    let unit = Aabb::from_center_half_extents(Vec3::ZERO, Vec3::ONE);
+    let grown = unit.expanded(0.5);
+    assert_eq!(grown.half_extents(), Vec3::splat(1.5));
+    assert!(grown.contains_aabb(&unit));
+    assert!(!unit.contains_aabb(&grown));
+
+    assert!(unit.distance_to(Vec3::new(0.5, 0.0, 0.0)).abs() < 1e-6);
+    assert!((unit.distance_to(Vec3::new(4.0, 0.0, 0.0)) - 3.0).abs() < 1e-6);
+    assert!((unit.distance_to(Vec3::new(4.0, 5.0, 0.0)) - 5.0).abs() < 1e-6);
+}
+
+/// Verifies a rotated box is enclosed by its transformed bounds.
+#[test]
+fn test_aabb_transformed_by_rotation() {
+    let unit = Aabb::from_center_half_extents(Vec3::ZERO, Vec3::new(1.0, 0.5, 0.5));
+    let turned = unit.transformed(&Mat4::from_quat(Quat::from_rotation_z(
+        std::f32::consts::FRAC_PI_2,
+    )));
+    assert!(
+        (turned.half_extents() - Vec3::new(0.5, 1.0, 0.5))
+            .abs()
+            .max_element()
+            < 1e-6
+    );
+}
+
+/// Verifies plane construction and distances.
+#[test]
+fn test_plane() {
+    let plane = Plane::from_points(Vec3::ZERO, Vec3::X, Vec3::NEG_Z).unwrap();
+    assert!((plane.normal - Vec3::Y).length() < 1e-6);
+    assert!((plane.signed_distance(Vec3::new(0.0, -2.0, 0.0)) + 2.0).abs() < 1e-6);
+    assert!(Plane::from_points(Vec3::ZERO, Vec3::X, Vec3::X * 2.0).is_none());
+    let raised = Plane::from_point_normal(Vec3::new(0.0, 3.0, 0.0), Vec3::Y * 5.0);
+    assert_eq!(raised.distance.to_bits(), 3.0f32.to_bits());
+}
+
+/// Verifies ray hits and misses against planes and boxes.
+#[test]
+fn test_ray() {
+    let ray = Ray::new(Vec3::ZERO, Vec3::X * 3.0);
+    assert_eq!(ray.direction, Vec3::X);
+    assert_eq!(ray.at(2.0), Vec3::new(2.0, 0.0, 0.0));
+
+    let wall = Plane::from_point_normal(Vec3::new(5.0, 0.0, 0.0), Vec3::NEG_X);
+    assert_eq!(ray.intersect_plane(&wall), Some(5.0));
+    let behind = Plane::from_point_normal(Vec3::new(-5.0, 0.0, 0.0), Vec3::X);
+    assert_eq!(ray.intersect_plane(&behind), None);
+    let parallel = Plane::from_point_normal(Vec3::Y, Vec3::Y);
+    assert_eq!(ray.intersect_plane(&parallel), None);
+
+    let target = Aabb::from_center_half_extents(Vec3::new(10.0, 0.0, 0.0), Vec3::ONE);
+    assert_eq!(ray.intersect_aabb(&target), Some(9.0));
+    let inside = Aabb::from_center_half_extents(Vec3::ZERO, Vec3::ONE);
+    assert_eq!(ray.intersect_aabb(&inside), Some(0.0));
+    let above = Aabb::from_center_half_extents(Vec3::new(10.0, 5.0, 0.0), Vec3::ONE);
+    assert_eq!(ray.intersect_aabb(&above), None);
+}
@@ -0,0 +1,55 @@
+//! Sandboxed WebAssembly mods for the Rustgine game engine.
+//!
+//! Mods ship as WebAssembly modules with a `mod.toml` manifest and run in
+//! [wasmtime](https://wasmtime.dev) with only the host functions their
+//! manifest asks for.
+//!
+//! # Overview
+//!
+//! The mods crate handles:
+//! - Mod manifests declaring capabilities, limits, and dependencies
+//!   ([`ModManifest`]), and the load order they imply ([`load_order`])
+//! - Where mod files come from ([`ModSource`], [`DirSource`],
+//!   [`MemorySource`])
+//! - Instantiating mods against the capability-scoped host API and running
+//!   them every frame within their fuel and memory limits ([`ModRuntime`])
+//!
+//! # Example
+//!
+//! ```
+//! use ecs::{TypeRegistry, World};
+//! use mods::{MemorySource, ModRuntime};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! let source = MemorySource::default()
+//!     .with_file("spawner", "mod.toml", "id = \"spawner\"\nversion = \"1.0.0\"\n[capabilities]\nspawn = true\n")
+//!     .with_file("spawner", "mod.wat", r#"(module
+//!         (import "rustgine" "spawn" (func $spawn (result i64)))
+//!         (func (export "update") (param f32) (drop (call $spawn))))"#);
+//!
+//! let mut world = World::default();
+//! let mut mods = ModRuntime::new(Arc::new(TypeRegistry::default()))?;
+//! mods.load(&source, &mut world)?;
+//! mods.update(&mut world, Duration::from_millis(16));
+//! assert_eq!(world.len(), 1);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+#![warn(missing_docs)]
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+pub mod manifest;
+#[cfg(test)]
+mod manifest_test;
+pub mod runtime;
+#[cfg(test)]
+mod runtime_test;
+pub mod source;
+#[cfg(test)]
+mod source_test;
+
+pub use manifest::{load_order, Capabilities, ModLimits, ModManifest, MANIFEST_FILE};
+pub use runtime::{ModRuntime, ModStatus, HOST_MODULE};
+pub use source::{DirSource, MemorySource, ModSource};
@@ -0,0 +1,189 @@
+//! Mod manifests and load order.
+//!
+//! Every mod has a `mod.toml` naming its module and declaring everything it
+//! may do; the host API refuses the rest:
+//!
+//! ```toml
+//! id = "horde_mode"
+//! version = "1.2.0"
+//! module = "horde_mode.wasm"
+//! dependencies = ["base_rules"]
+//!
+//! [capabilities]
+//! spawn = true
+//! read_components = ["Health"]
+//! events = ["wave_started"]
+//!
+//! [limits]
+//! fuel_per_frame = 5_000_000
+//! memory_bytes = 8_388_608
+//! ```
+
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Name of the manifest file in every mod.
+pub const MANIFEST_FILE: &str = "mod.toml";
+
+/// Module loaded when a manifest names none.
+const DEFAULT_MODULE: &str = "mod.wat";
+
+/// Fuel a mod may burn per frame when its manifest sets no limit.
+///
+/// Roughly ten million simple instructions, a few milliseconds.
+const DEFAULT_FUEL_PER_FRAME: u64 = 10_000_000;
+
+/// Memory a mod may use when its manifest sets no limit.
+const DEFAULT_MEMORY_BYTES: usize = 16 * 1024 * 1024;
+
+/// A mod's `mod.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModManifest {
+    /// Unique name other mods depend on, of lowercase letters, digits, `_`,
+    /// and `-`.
+    pub id: String,
+    /// Mod version, shown to players.
+    pub version: String,
+    /// Path of the WebAssembly module within the mod, binary or text.
+    #[serde(default = "default_module")]
+    pub module: String,
+    /// Mods that must load first.
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    /// Host functions and data the mod may use.
+    #[serde(default)]
+    pub capabilities: Capabilities,
+    /// Per-frame resource limits.
+    #[serde(default)]
+    pub limits: ModLimits,
+}
+
+/// What a mod may do through the host API.
+///
+/// Logging is always allowed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Capabilities {
+    /// Whether the mod may spawn entities.
+    #[serde(default)]
+    pub spawn: bool,
+    /// Registered component types whose fields the mod may read.
+    #[serde(default)]
+    pub read_components: Vec<String>,
+    /// Events delivered to the mod's `on_event` export.
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+/// Resources a mod may use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModLimits {
+    /// Fuel available each frame, consumed by every instruction; a mod
+    /// running out is stopped.
+    #[serde(default = "default_fuel")]
+    pub fuel_per_frame: u64,
+    /// Largest size of the mod's linear memory, in bytes.
+    #[serde(default = "default_memory")]
+    pub memory_bytes: usize,
+}
+
+impl Default for ModLimits {
+    fn default() -> Self {
+        Self {
+            fuel_per_frame: DEFAULT_FUEL_PER_FRAME,
+            memory_bytes: DEFAULT_MEMORY_BYTES,
+        }
+    }
+}
+
+fn default_module() -> String {
+    DEFAULT_MODULE.to_owned()
+}
+
+fn default_fuel() -> u64 {
+    DEFAULT_FUEL_PER_FRAME
+}
+
+fn default_memory() -> usize {
+    DEFAULT_MEMORY_BYTES
+}
+
+impl ModManifest {
+    /// Parses and validates a manifest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a valid manifest, the id is empty
+    /// or has other characters than lowercase letters, digits, `_`, and
+    /// `-`, or the mod depends on itself.
+    pub fn from_toml(text: &str) -> anyhow::Result<Self> {
+        let manifest: Self = toml::from_str(text)?;
+        let valid = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-';
+        if manifest.id.is_empty() || !manifest.id.chars().all(valid) {
+            anyhow::bail!("invalid mod id `{}`", manifest.id);
+        }
+        if manifest.dependencies.contains(&manifest.id) {
+            anyhow::bail!("mod `{}` depends on itself", manifest.id);
+        }
+        Ok(manifest)
+    }
+}
+
+/// Orders `manifests` so every mod follows its dependencies, otherwise by
+/// id.
+///
+/// # Errors
+///
+/// Returns an error if two mods share an id, a dependency is missing, or
+/// dependencies form a cycle.
+pub fn load_order(manifests: &[ModManifest]) -> anyhow::Result<Vec<&ModManifest>> {
+    let mut by_id = BTreeMap::new();
+    for manifest in manifests {
+        if by_id.insert(manifest.id.as_str(), manifest).is_some() {
+            anyhow::bail!("more than one mod has id `{}`", manifest.id);
+        }
+    }
+    for manifest in manifests {
+        if let Some(missing) = manifest
+            .dependencies
+            .iter()
+            .find(|dependency| !by_id.contains_key(dependency.as_str()))
+        {
+            anyhow::bail!(
+                "mod `{}` depends on `{missing}`, which is not installed",
+                manifest.id
+            );
+        }
+    }
+
+    let mut order = Vec::with_capacity(manifests.len());
+    let mut loaded = BTreeSet::new();
+    while order.len() < by_id.len() {
+        let ready: Vec<&ModManifest> = by_id
+            .values()
+            .filter(|manifest| !loaded.contains(manifest.id.as_str()))
+            .filter(|manifest| {
+                manifest
+                    .dependencies
+                    .iter()
+                    .all(|dependency| loaded.contains(dependency.as_str()))
+            })
+            .copied()
+            .collect();
+        if ready.is_empty() {
+            let cycle: Vec<&str> = by_id
+                .keys()
+                .filter(|id| !loaded.contains(*id))
+                .copied()
+                .collect();
+            anyhow::bail!("mods {} depend on each other", cycle.join(", "));
+        }
+        for manifest in ready {
+            loaded.insert(manifest.id.as_str());
+            order.push(manifest);
+        }
+    }
+    Ok(order)
+}
@@ -0,0 +1,105 @@
+//! Unit tests for mod manifests and load order.
+
+use crate::manifest::{load_order, ModLimits, ModManifest};
+
+fn manifest(id: &str, dependencies: &[&str]) -> ModManifest {
+    let dependencies: Vec<String> = dependencies.iter().map(|d| format!("\"{d}\"")).collect();
+    ModManifest::from_toml(&format!(
+        "id = \"{id}\"\nversion = \"1.0.0\"\ndependencies = [{}]\n",
+        dependencies.join(", ")
+    ))
+    .unwrap()
+}
+
+fn ids(order: &[&ModManifest]) -> Vec<String> {
+    order.iter().map(|manifest| manifest.id.clone()).collect()
+}
+
+/// Verifies a minimal manifest gets the default module, no capabilities,
+/// and the default limits.
+#[test]
+fn minimal_manifest_uses_defaults() {
+    let manifest = ModManifest::from_toml("id = \"base_rules\"\nversion = \"0.1.0\"\n").unwrap();
+    assert_eq!(manifest.module, "mod.wat");
+    assert!(manifest.dependencies.is_empty());
+    assert!(!manifest.capabilities.spawn);
+    assert!(manifest.capabilities.read_components.is_empty());
+    assert_eq!(manifest.limits, ModLimits::default());
+}
+
+/// Verifies capabilities and limits are read.
+#[test]
+fn reads_capabilities_and_limits() {
+    let manifest = ModManifest::from_toml(
+        r#"
+        id = "horde_mode"
+        version = "1.2.0"
+        module = "horde_mode.wasm"
+
+        [capabilities]
+        spawn = true
+        read_components = ["Health"]
+        events = ["wave_started"]
+
+        [limits]
+        fuel_per_frame = 5_000
+        "#,
+    )
+    .unwrap();
+    assert_eq!(manifest.module, "horde_mode.wasm");
+    assert!(manifest.capabilities.spawn);
+    assert_eq!(manifest.capabilities.read_components, ["Health"]);
+    assert_eq!(manifest.capabilities.events, ["wave_started"]);
+    assert_eq!(manifest.limits.fuel_per_frame, 5_000);
+    assert_eq!(
+        manifest.limits.memory_bytes,
+        ModLimits::default().memory_bytes
+    );
+}
+
+/// Verifies invalid ids, unknown keys, and self-dependencies are refused.
+#[test]
+fn refuses_invalid_manifests() {
+    for text in [
+        "id = \"\"\nversion = \"1\"",
+        "id = \"Horde Mode\"\nversion = \"1\"",
+        "id = \"horde\"\nversion = \"1\"\nnetwork = true",
+        "id = \"horde\"\nversion = \"1\"\n[capabilities]\nfilesystem = true",
+        "id = \"horde\"\nversion = \"1\"\ndependencies = [\"horde\"]",
+    ] {
+        assert!(ModManifest::from_toml(text).is_err(), "{text}");
+    }
+}
+
+/// Verifies mods follow their dependencies and are otherwise ordered by id.
+#[test]
+fn load_order_follows_dependencies() {
+    let manifests = [
+        manifest("zombies", &["base"]),
+        manifest("horde", &["zombies", "base"]),
+        manifest("base", &[]),
+        manifest("art", &[]),
+    ];
+    let order = load_order(&manifests).unwrap();
+    assert_eq!(ids(&order), ["art", "base", "zombies", "horde"]);
+}
+
+/// Verifies duplicate ids, missing dependencies, and cycles are reported.
+#[test]
+fn load_order_reports_unresolvable_mods() {
+    let duplicate = [manifest("base", &[]), manifest("base", &[])];
+    let error = load_order(&duplicate).unwrap_err().to_string();
+    assert!(error.contains("more than one mod has id `base`"), "{error}");
+
+    let missing = [manifest("horde", &["zombies"])];
+    let error = load_order(&missing).unwrap_err().to_string();
+    assert!(error.contains("depends on `zombies`"), "{error}");
+
+    let cycle = [
+        manifest("a", &["b"]),
+        manifest("b", &["a"]),
+        manifest("base", &[]),
+    ];
+    let error = load_order(&cycle).unwrap_err().to_string();
+    assert!(error.contains("mods a, b depend on each other"), "{error}");
+}
@@ -0,0 +1,491 @@
+//! Running mods in the WebAssembly sandbox.
+//!
+//! Each mod gets its own wasmtime store, so mods share nothing but the
+//! [`World`] the host lends them during their calls. Mods import host
+//! functions from the [`HOST_MODULE`] module:
+//!
+//! | Import | Signature | Capability |
+//! |--------|-----------|------------|
+//! | `log` | `(ptr: i32, len: i32)` | Always |
+//! | `spawn` | `() -> i64` entity | `spawn` |
+//! | `read_field` | `(entity: i64, component_ptr: i32, component_len: i32, field_ptr: i32, field_len: i32, out_ptr: i32) -> i32` | `read_components` |
+//!
+//! `read_field` writes the field of a registered component as little-endian
+//! `f32`s at `out_ptr` (1 for a float, 3 for a vector, 4 for a color) and
+//! returns how many, or `-1` if the entity lacks the component. Strings are
+//! UTF-8 in the mod's exported `memory`.
+//!
+//! Mods may export:
+//!
+//! | Export | Signature | Called |
+//! |--------|-----------|--------|
+//! | `init` | `()` | Once, after loading |
+//! | `update` | `(delta_seconds: f32)` | Every frame |
+//! | `on_event` | `(name_ptr: i32, name_len: i32, payload_ptr: i32, payload_len: i32)` | For each subscribed event, before `update` |
+//! | `alloc` | `(len: i32) -> i32` | To place event data in the mod's memory; required with `on_event` |
+//!
+//! A mod that traps, uses a host function it did not declare, or runs out
+//! of fuel is stopped for the rest of the session, without affecting the
+//! others.
+
+use crate::manifest::{load_order, Capabilities, ModManifest, MANIFEST_FILE};
+use crate::source::ModSource;
+use anyhow::Context;
+use ecs::{Entity, FieldValue, TypeRegistry, World};
+use rustgine_core::{RustgineError, RustgineSystem};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, error, info};
+use wasmtime::{
+    Caller, Config, Engine, Extern, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder,
+    Trap, TypedFunc,
+};
+
+/// Module name of the host API imports.
+pub const HOST_MODULE: &str = "rustgine";
+
+/// Whether a loaded mod still runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModStatus {
+    /// Called every frame.
+    Running,
+    /// Stopped after the error it describes.
+    Faulted(String),
+}
+
+/// Data of a mod's store, reachable from host functions.
+struct HostState {
+    /// Mod id, for logs and errors.
+    id: String,
+    /// The host's world, lent for the duration of a call.
+    world: World,
+    registry: Arc<TypeRegistry>,
+    capabilities: Capabilities,
+    limits: StoreLimits,
+}
+
+/// Exports used to deliver events to a mod.
+#[derive(Clone)]
+struct EventExports {
+    on_event: TypedFunc<(i32, i32, i32, i32), ()>,
+    alloc: TypedFunc<i32, i32>,
+    memory: Memory,
+}
+
+/// A loaded mod.
+struct LoadedMod {
+    manifest: ModManifest,
+    store: Store<HostState>,
+    init: Option<TypedFunc<(), ()>>,
+    update: Option<TypedFunc<f32, ()>>,
+    on_event: Option<EventExports>,
+    status: ModStatus,
+}
+
+/// Loads mods and runs them every frame.
+pub struct ModRuntime {
+    engine: Engine,
+    linker: Linker<HostState>,
+    registry: Arc<TypeRegistry>,
+    mods: Vec<LoadedMod>,
+    /// Events sent since the last update.
+    events: Vec<(String, Vec<u8>)>,
+}
+
+impl fmt::Debug for ModRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ModRuntime")
+            .field("registry", &self.registry)
+            .field(
+                "mods",
+                &self
+                    .mods
+                    .iter()
+                    .map(|loaded| (&loaded.manifest.id, &loaded.status))
+                    .collect::<Vec<_>>(),
+            )
+            .field("events", &self.events.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ModRuntime {
+    /// Creates a runtime without mods, whose mods may read the components
+    /// registered in `registry`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the WebAssembly engine cannot be created.
+    pub fn new(registry: Arc<TypeRegistry>) -> anyhow::Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)?;
+        let mut linker = Linker::new(&engine);
+        define_host_api(&mut linker)?;
+        Ok(Self {
+            engine,
+            linker,
+            registry,
+            mods: Vec::new(),
+            events: Vec::new(),
+        })
+    }
+
+    /// Loads every mod in `source` in dependency order, replacing the mods
+    /// loaded before, and runs their `init` exports on `world`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a manifest or module cannot be read or is
+    /// invalid, the load order cannot be resolved, or a module imports a
+    /// host function its manifest does not declare. No mod is loaded then.
+    /// A mod that traps in `init` is loaded but [faulted](ModStatus::Faulted).
+    pub fn load(&mut self, source: &dyn ModSource, world: &mut World) -> anyhow::Result<()> {
+        let mut manifests = Vec::new();
+        let mut dirs = Vec::new();
+        for name in source.mods()? {
+            let text = source.read(&name, MANIFEST_FILE)?;
+            let text = String::from_utf8(text)
+                .with_context(|| format!("mod `{name}` has an invalid {MANIFEST_FILE}"))?;
+            let manifest = ModManifest::from_toml(&text)
+                .with_context(|| format!("mod `{name}` has an invalid {MANIFEST_FILE}"))?;
+            dirs.push((manifest.id.clone(), name));
+            manifests.push(manifest);
+        }
+
+        let mut mods = Vec::new();
+        for manifest in load_order(&manifests)? {
+            let dir = dirs
+                .iter()
+                .find(|(id, _)| *id == manifest.id)
+                .map_or(manifest.id.as_str(), |(_, dir)| dir.as_str());
+            mods.push(self.instantiate(source, dir, manifest.clone())?);
+        }
+
+        self.mods = mods;
+        for loaded in &mut self.mods {
+            if let Some(init) = loaded.init.clone() {
+                loaded.refuel();
+                loaded.enter(world, |store| init.call(store, ()));
+            }
+            info!(id = %loaded.manifest.id, version = %loaded.manifest.version, "mod loaded");
+        }
+        Ok(())
+    }
+
+    /// Queues an event for the mods subscribed to `name`, delivered on the
+    /// next [`update`](Self::update).
+    pub fn send_event(&mut self, name: &str, payload: &[u8]) {
+        self.events.push((name.to_owned(), payload.to_vec()));
+    }
+
+    /// Runs one frame: refuels every running mod, delivers the queued
+    /// events, then calls `update`.
+    pub fn update(&mut self, world: &mut World, delta: Duration) {
+        let events = std::mem::take(&mut self.events);
+        for loaded in &mut self.mods {
+            if loaded.status != ModStatus::Running {
+                continue;
+            }
+            loaded.refuel();
+            let subscribed: Vec<&(String, Vec<u8>)> = events
+                .iter()
+                .filter(|(name, _)| loaded.manifest.capabilities.events.contains(name))
+                .collect();
+            let on_event = loaded.on_event.clone();
+            let update = loaded.update.clone();
+            loaded.enter(world, |store| {
+                if let Some(exports) = on_event {
+                    for (name, payload) in subscribed {
+                        deliver(store, &exports, name, payload)?;
+                    }
+                }
+                match update {
+                    Some(update) => update.call(store, delta.as_secs_f32()),
+                    None => Ok(()),
+                }
+            });
+        }
+    }
+
+    /// Returns the loaded mods in load order, with their status.
+    pub fn mods(&self) -> impl Iterator<Item = (&ModManifest, &ModStatus)> {
+        self.mods
+            .iter()
+            .map(|loaded| (&loaded.manifest, &loaded.status))
+    }
+
+    /// Returns the status of the mod with `id`, or `None` if it is not
+    /// loaded.
+    #[must_use]
+    pub fn status(&self, id: &str) -> Option<&ModStatus> {
+        self.mods
+            .iter()
+            .find(|loaded| loaded.manifest.id == id)
+            .map(|loaded| &loaded.status)
+    }
+
+    /// Compiles and instantiates the mod in `dir`, without running it.
+    fn instantiate(
+        &self,
+        source: &dyn ModSource,
+        dir: &str,
+        manifest: ModManifest,
+    ) -> anyhow::Result<LoadedMod> {
+        let id = manifest.id.clone();
+        let bytes = source.read(dir, &manifest.module)?;
+        let module = Module::new(&self.engine, bytes)
+            .with_context(|| format!("mod `{id}` has an invalid module {}", manifest.module))?;
+        check_imports(&module, &manifest)?;
+
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(manifest.limits.memory_bytes)
+            .instances(1)
+            .build();
+        let mut store = Store::new(
+            &self.engine,
+            HostState {
+                id: id.clone(),
+                world: World::default(),
+                registry: Arc::clone(&self.registry),
+                capabilities: manifest.capabilities.clone(),
+                limits,
+            },
+        );
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(manifest.limits.fuel_per_frame)?;
+        let instance = self
+            .linker
+            .instantiate(&mut store, &module)
+            .with_context(|| format!("failed to instantiate mod `{id}`"))?;
+
+        let init = instance.get_typed_func(&mut store, "init").ok();
+        let update = instance.get_typed_func(&mut store, "update").ok();
+        let on_event = if manifest.capabilities.events.is_empty() {
+            None
+        } else {
+            let on_event = instance
+                .get_typed_func(&mut store, "on_event")
+                .with_context(|| format!("mod `{id}` subscribes to events without `on_event`"))?;
+            let alloc = instance
+                .get_typed_func(&mut store, "alloc")
+                .with_context(|| format!("mod `{id}` subscribes to events without `alloc`"))?;
+            let memory = instance
+                .get_memory(&mut store, "memory")
+                .with_context(|| format!("mod `{id}` subscribes to events without `memory`"))?;
+            Some(EventExports {
+                on_event,
+                alloc,
+                memory,
+            })
+        };
+        debug!(id = %id, "mod instantiated");
+        Ok(LoadedMod {
+            manifest,
+            store,
+            init,
+            update,
+            on_event,
+            status: ModStatus::Running,
+        })
+    }
+}
+
+impl RustgineSystem for ModRuntime {
+    /// Starts the mod subsystem; mods are loaded with
+    /// [`load`](ModRuntime::load).
+    ///
+    /// # Errors
+    ///
+    /// Never fails.
+    fn startup(&mut self) -> Result<(), RustgineError> {
+        info!(mods = self.mods.len(), "mods started");
+        Ok(())
+    }
+
+    /// Unloads every mod and drops the queued events.
+    ///
+    /// # Errors
+    ///
+    /// Never fails.
+    fn shutdown(&mut self) -> Result<(), RustgineError> {
+        self.mods.clear();
+        self.events.clear();
+        Ok(())
+    }
+}
+
+impl LoadedMod {
+    /// Resets the mod's fuel to its per-frame budget.
+    fn refuel(&mut self) {
+        // Only fails without fuel consumption, which the engine enables
+        let _ = self.store.set_fuel(self.manifest.limits.fuel_per_frame);
+    }
+
+    /// Lends `world` to the mod while `call` runs, faulting the mod if it
+    /// fails.
+    fn enter(
+        &mut self,
+        world: &mut World,
+        call: impl FnOnce(&mut Store<HostState>) -> anyhow::Result<()>,
+    ) {
+        if self.status != ModStatus::Running {
+            return;
+        }
+        self.store.data_mut().world = std::mem::take(world);
+        let result = call(&mut self.store);
+        *world = std::mem::take(&mut self.store.data_mut().world);
+
+        if let Err(e) = result {
+            let reason = if e.downcast_ref::<Trap>() == Some(&Trap::OutOfFuel) {
+                format!(
+                    "exceeded its budget of {} fuel per frame",
+                    self.manifest.limits.fuel_per_frame
+                )
+            } else {
+                format!("{e:#}")
+            };
+            error!(id = %self.manifest.id, reason = %reason, "mod faulted and was stopped");
+            self.status = ModStatus::Faulted(reason);
+        }
+    }
+}
+
+/// Copies `name` and `payload` into the mod's memory and calls `on_event`.
+fn deliver(
+    store: &mut Store<HostState>,
+    exports: &EventExports,
+    name: &str,
+    payload: &[u8],
+) -> anyhow::Result<()> {
+    let EventExports {
+        on_event,
+        alloc,
+        memory,
+    } = exports;
+    let name_len = i32::try_from(name.len())?;
+    let payload_len = i32::try_from(payload.len())?;
+    let len = name_len
+        .checked_add(payload_len)
+        .context("event too large for the mod's memory")?;
+    let ptr = alloc.call(&mut *store, len)?;
+    let offset = usize::try_from(ptr).context("`alloc` returned a negative pointer")?;
+    memory.write(&mut *store, offset, name.as_bytes())?;
+    memory.write(&mut *store, offset + name.len(), payload)?;
+    on_event.call(&mut *store, (ptr, name_len, ptr + name_len, payload_len))
+}
+
+/// Refuses imports the host does not provide or the manifest did not
+/// declare a capability for.
+fn check_imports(module: &Module, manifest: &ModManifest) -> anyhow::Result<()> {
+    let capabilities = &manifest.capabilities;
+    for import in module.imports() {
+        let (module_name, name) = (import.module(), import.name());
+        let capability = match (module_name, name) {
+            (HOST_MODULE, "log") => None,
+            (HOST_MODULE, "spawn") => Some(("spawn", capabilities.spawn)),
+            (HOST_MODULE, "read_field") => {
+                Some(("read_components", !capabilities.read_components.is_empty()))
+            }
+            _ => anyhow::bail!(
+                "mod `{}` imports `{module_name}::{name}`, which the host does not provide",
+                manifest.id
+            ),
+        };
+        if let Some((capability, false)) = capability {
+            anyhow::bail!(
+                "mod `{}` imports `{module_name}::{name}` without the `{capability}` capability",
+                manifest.id
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Defines the host API on `linker`.
+fn define_host_api(linker: &mut Linker<HostState>) -> anyhow::Result<()> {
+    linker.func_wrap(
+        HOST_MODULE,
+        "log",
+        |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| -> anyhow::Result<()> {
+            let text = guest_str(&mut caller, ptr, len)?;
+            info!(id = %caller.data().id, "{text}");
+            Ok(())
+        },
+    )?;
+
+    linker.func_wrap(
+        HOST_MODULE,
+        "spawn",
+        |mut caller: Caller<'_, HostState>| -> anyhow::Result<i64> {
+            let state = caller.data_mut();
+            anyhow::ensure!(
+                state.capabilities.spawn,
+                "mod `{}` may not spawn entities",
+                state.id
+            );
+            Ok(state.world.spawn().0.cast_signed())
+        },
+    )?;
+
+    linker.func_wrap(
+        HOST_MODULE,
+        "read_field",
+        |mut caller: Caller<'_, HostState>,
+         entity: i64,
+         component_ptr: i32,
+         component_len: i32,
+         field_ptr: i32,
+         field_len: i32,
+         out_ptr: i32|
+         -> anyhow::Result<i32> {
+            let component = guest_str(&mut caller, component_ptr, component_len)?;
+            let field = guest_str(&mut caller, field_ptr, field_len)?;
+            let state = caller.data();
+            anyhow::ensure!(
+                state.capabilities.read_components.contains(&component),
+                "mod `{}` may not read component `{component}`",
+                state.id
+            );
+            let value = state.registry.get(
+                &state.world,
+                Entity(entity.cast_unsigned()),
+                &component,
+                &field,
+            );
+            let floats: Vec<f32> = match value {
+                None => return Ok(-1),
+                Some(FieldValue::Float(value)) => vec![value],
+                Some(FieldValue::Vec3(value)) => value.to_vec(),
+                Some(FieldValue::Color(color)) => color.to_array().to_vec(),
+            };
+            let bytes: Vec<u8> = floats.iter().flat_map(|f| f.to_le_bytes()).collect();
+            let memory = guest_memory(&mut caller)?;
+            let offset = usize::try_from(out_ptr).context("negative output pointer")?;
+            memory.write(&mut caller, offset, &bytes)?;
+            Ok(i32::try_from(floats.len())?)
+        },
+    )?;
+    Ok(())
+}
+
+/// Returns the mod's exported memory.
+fn guest_memory(caller: &mut Caller<'_, HostState>) -> anyhow::Result<Memory> {
+    caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| anyhow::anyhow!("mod `{}` exports no memory", caller.data().id))
+}
+
+/// Reads a UTF-8 string from the mod's memory.
+fn guest_str(caller: &mut Caller<'_, HostState>, ptr: i32, len: i32) -> anyhow::Result<String> {
+    let memory = guest_memory(caller)?;
+    let offset = usize::try_from(ptr).context("negative string pointer")?;
+    let len = usize::try_from(len).context("negative string length")?;
+    let bytes = memory
+        .data(&*caller)
+        .get(offset..offset.saturating_add(len))
+        .ok_or_else(|| anyhow::anyhow!("string out of bounds"))?;
+    Ok(std::str::from_utf8(bytes)?.to_owned())
+}
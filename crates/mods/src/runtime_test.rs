@@ -0,0 +1,237 @@
+//! Unit tests for the mod runtime.
+
+use crate::runtime::{ModRuntime, ModStatus};
+use crate::source::MemorySource;
+use ecs::{TypeRegistry, World};
+use rustgine_core::RustgineSystem;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug, Default)]
+struct Health {
+    value: f32,
+}
+
+/// Mod spawning an entity every frame.
+const SPAWNER: &str = r#"(module
+    (import "rustgine" "spawn" (func $spawn (result i64)))
+    (func (export "update") (param f32) (drop (call $spawn))))"#;
+
+fn registry() -> Arc<TypeRegistry> {
+    let mut registry = TypeRegistry::default();
+    registry
+        .register::<Health>("Health")
+        .float("value", |h| h.value, |h, v| h.value = v);
+    Arc::new(registry)
+}
+
+/// Adds a mod with `manifest` lines after its id and version, and module
+/// `wat`.
+fn with_mod(source: MemorySource, id: &str, manifest: &str, wat: &str) -> MemorySource {
+    source
+        .with_file(
+            id,
+            "mod.toml",
+            format!("id = \"{id}\"\nversion = \"1.0.0\"\n{manifest}"),
+        )
+        .with_file(id, "mod.wat", wat)
+}
+
+fn load(source: &MemorySource, world: &mut World) -> anyhow::Result<ModRuntime> {
+    let mut mods = ModRuntime::new(registry())?;
+    mods.load(source, world)?;
+    Ok(mods)
+}
+
+fn frame(mods: &mut ModRuntime, world: &mut World) {
+    mods.update(world, Duration::from_millis(16));
+}
+
+/// Verifies mods run `init` once on load and `update` every frame.
+#[test]
+fn runs_init_and_update() {
+    let wat = r#"(module
+        (import "rustgine" "spawn" (func $spawn (result i64)))
+        (func (export "init") (drop (call $spawn)) (drop (call $spawn)))
+        (func (export "update") (param f32) (drop (call $spawn))))"#;
+    let source = with_mod(
+        MemorySource::default(),
+        "spawner",
+        "[capabilities]\nspawn = true",
+        wat,
+    );
+    let mut world = World::default();
+    let mut mods = load(&source, &mut world).unwrap();
+    assert_eq!(world.len(), 2);
+    frame(&mut mods, &mut world);
+    frame(&mut mods, &mut world);
+    assert_eq!(world.len(), 4);
+    assert_eq!(mods.status("spawner"), Some(&ModStatus::Running));
+}
+
+/// Verifies importing a host function without its capability, or one the
+/// host does not provide, refuses the load.
+#[test]
+fn refuses_imports_outside_capabilities() {
+    let ungranted = with_mod(MemorySource::default(), "spawner", "", SPAWNER);
+    let error = format!("{:#}", load(&ungranted, &mut World::default()).unwrap_err());
+    assert!(error.contains("without the `spawn` capability"), "{error}");
+
+    let wasi = r#"(module (import "wasi_snapshot_preview1" "fd_write"
+        (func (param i32 i32 i32 i32) (result i32))))"#;
+    let unknown = with_mod(MemorySource::default(), "printer", "", wasi);
+    let error = format!("{:#}", load(&unknown, &mut World::default()).unwrap_err());
+    assert!(error.contains("which the host does not provide"), "{error}");
+}
+
+/// Verifies a mod reads fields of components it declared, and faults when
+/// reading any other.
+#[test]
+fn reads_declared_components_only() {
+    let mut world = World::default();
+    let wounded = world.spawn();
+    world.insert(wounded, Health { value: 80.0 });
+    let reader = |component: &str| {
+        format!(
+            r#"(module
+            (import "rustgine" "read_field"
+                (func $read (param i64 i32 i32 i32 i32 i32) (result i32)))
+            (import "rustgine" "spawn" (func $spawn (result i64)))
+            (memory (export "memory") 1)
+            (data (i32.const 0) "{component}value")
+            (func (export "update") (param f32)
+                (if (i32.eq (call $read (i64.const {entity}) (i32.const 0) (i32.const {len})
+                        (i32.const {len}) (i32.const 5) (i32.const 64)) (i32.const 1))
+                    (then (if (f32.gt (f32.load (i32.const 64)) (f32.const 50))
+                        (then (drop (call $spawn))))))))"#,
+            entity = wounded.0,
+            len = component.len(),
+        )
+    };
+    let source = with_mod(
+        MemorySource::default(),
+        "healer",
+        "[capabilities]\nspawn = true\nread_components = [\"Health\"]",
+        &reader("Health"),
+    );
+    let source = with_mod(
+        source,
+        "snoop",
+        "[capabilities]\nspawn = true\nread_components = [\"Health\"]",
+        &reader("Secret"),
+    );
+
+    let mut mods = load(&source, &mut world).unwrap();
+    frame(&mut mods, &mut world);
+    assert_eq!(world.len(), 2);
+    assert_eq!(mods.status("healer"), Some(&ModStatus::Running));
+    assert!(matches!(
+        mods.status("snoop"),
+        Some(ModStatus::Faulted(reason)) if reason.contains("may not read component `Secret`")
+    ));
+}
+
+/// Verifies a mod exceeding its fuel is stopped while other mods keep
+/// running.
+#[test]
+fn stops_mod_out_of_fuel() {
+    let spinner = r#"(module (func (export "update") (param f32) (loop (br 0))))"#;
+    let source = with_mod(
+        MemorySource::default(),
+        "spinner",
+        "[limits]\nfuel_per_frame = 10_000",
+        spinner,
+    );
+    let source = with_mod(source, "spawner", "[capabilities]\nspawn = true", SPAWNER);
+    let mut world = World::default();
+    let mut mods = load(&source, &mut world).unwrap();
+
+    frame(&mut mods, &mut world);
+    frame(&mut mods, &mut world);
+    assert_eq!(
+        mods.status("spinner"),
+        Some(&ModStatus::Faulted(
+            "exceeded its budget of 10000 fuel per frame".to_owned()
+        ))
+    );
+    assert_eq!(world.len(), 2);
+}
+
+/// Verifies a module needing more memory than its limit is refused.
+#[test]
+fn refuses_module_over_memory_limit() {
+    let source = with_mod(
+        MemorySource::default(),
+        "hungry",
+        "[limits]\nmemory_bytes = 65_536",
+        "(module (memory 2))",
+    );
+    let error = format!("{:#}", load(&source, &mut World::default()).unwrap_err());
+    assert!(
+        error.contains("failed to instantiate mod `hungry`"),
+        "{error}"
+    );
+}
+
+/// Verifies events reach the mods subscribed to them, with their payload.
+#[test]
+fn delivers_subscribed_events() {
+    // Spawns one entity per payload byte.
+    let listener = r#"(module
+        (import "rustgine" "spawn" (func $spawn (result i64)))
+        (memory (export "memory") 1)
+        (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+        (func (export "on_event") (param i32 i32 i32 i32)
+            (loop $next
+                (if (i32.gt_s (local.get 3) (i32.const 0))
+                    (then
+                        (drop (call $spawn))
+                        (local.set 3 (i32.sub (local.get 3) (i32.const 1)))
+                        (br $next))))))"#;
+    let source = with_mod(
+        MemorySource::default(),
+        "listener",
+        "[capabilities]\nspawn = true\nevents = [\"wave_started\"]",
+        listener,
+    );
+    let mut world = World::default();
+    let mut mods = load(&source, &mut world).unwrap();
+
+    mods.send_event("wave_started", &[1, 2, 3]);
+    mods.send_event("game_over", &[1]);
+    frame(&mut mods, &mut world);
+    assert_eq!(world.len(), 3);
+    frame(&mut mods, &mut world);
+    assert_eq!(world.len(), 3);
+}
+
+/// Verifies subscribing to events requires the exports to deliver them.
+#[test]
+fn refuses_subscriber_without_on_event() {
+    let source = with_mod(
+        MemorySource::default(),
+        "deaf",
+        "[capabilities]\nevents = [\"wave_started\"]",
+        "(module)",
+    );
+    let error = format!("{:#}", load(&source, &mut World::default()).unwrap_err());
+    assert!(error.contains("without `on_event`"), "{error}");
+}
+
+/// Verifies shutdown unloads every mod.
+#[test]
+fn shutdown_unloads_mods() {
+    let source = with_mod(
+        MemorySource::default(),
+        "spawner",
+        "[capabilities]\nspawn = true",
+        SPAWNER,
+    );
+    let mut world = World::default();
+    let mut mods = load(&source, &mut world).unwrap();
+    mods.startup().unwrap();
+    mods.shutdown().unwrap();
+    assert_eq!(mods.mods().count(), 0);
+    frame(&mut mods, &mut world);
+    assert!(world.is_empty());
+}
@@ -0,0 +1,140 @@
+//! Where mod files are read from.
+//!
+//! A [`ModSource`] lists installed mods and reads files within one. Mods
+//! only ever name paths relative to their own root, and sources refuse
+//! paths that leave it, so a manifest cannot load another mod's module or
+//! arbitrary files.
+
+use anyhow::Context;
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+/// Installed mods and their files.
+pub trait ModSource {
+    /// Returns the names of the installed mods.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mods cannot be listed.
+    fn mods(&self) -> anyhow::Result<Vec<String>>;
+
+    /// Reads `path`, relative to the root of mod `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file does not exist, cannot be read, or
+    /// `path` leaves the mod's root.
+    fn read(&self, name: &str, path: &str) -> anyhow::Result<Vec<u8>>;
+}
+
+/// Mods installed as subdirectories of a directory, e.g. `mods/horde_mode`.
+#[derive(Debug, Clone)]
+pub struct DirSource {
+    root: PathBuf,
+}
+
+impl DirSource {
+    /// Creates a source for the mods in `root`.
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Returns the directory holding the mods.
+    #[must_use]
+    #[inline]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl ModSource for DirSource {
+    /// Returns the subdirectories of the root that hold a manifest, in
+    /// name order. A missing root has no mods.
+    fn mods(&self) -> anyhow::Result<Vec<String>> {
+        let entries = match std::fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("failed to list {}", self.root.display()))
+            }
+        };
+        let mut names = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            if entry.path().join(crate::MANIFEST_FILE).is_file() {
+                if let Ok(name) = entry.file_name().into_string() {
+                    names.push(name);
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    fn read(&self, name: &str, path: &str) -> anyhow::Result<Vec<u8>> {
+        let relative = contained(name, path)?;
+        let full = self.root.join(name).join(relative);
+        std::fs::read(&full).with_context(|| format!("failed to read {}", full.display()))
+    }
+}
+
+/// Mods held in memory, e.g. unpacked from an archive or built by tests.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySource {
+    files: BTreeMap<String, BTreeMap<PathBuf, Vec<u8>>>,
+}
+
+impl MemorySource {
+    /// Adds `path` with `contents` to mod `name`, replacing any earlier
+    /// contents.
+    #[must_use]
+    pub fn with_file(
+        mut self,
+        name: impl Into<String>,
+        path: impl Into<PathBuf>,
+        contents: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.files
+            .entry(name.into())
+            .or_default()
+            .insert(path.into(), contents.into());
+        self
+    }
+}
+
+impl ModSource for MemorySource {
+    fn mods(&self) -> anyhow::Result<Vec<String>> {
+        Ok(self
+            .files
+            .iter()
+            .filter(|(_, files)| files.contains_key(Path::new(crate::MANIFEST_FILE)))
+            .map(|(name, _)| name.clone())
+            .collect())
+    }
+
+    fn read(&self, name: &str, path: &str) -> anyhow::Result<Vec<u8>> {
+        let relative = contained(name, path)?;
+        self.files
+            .get(name)
+            .and_then(|files| files.get(&relative))
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("mod `{name}` has no file {path}"))
+    }
+}
+
+/// Returns `path` normalized, or an error if it is absolute or leaves the
+/// root of mod `name`.
+fn contained(name: &str, path: &str) -> anyhow::Result<PathBuf> {
+    let mut relative = PathBuf::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                anyhow::bail!("mod `{name}` refers to {path}, outside the mod");
+            }
+        }
+    }
+    Ok(relative)
+}
@@ -0,0 +1,53 @@
+//! Unit tests for mod sources.
+
+use crate::source::{DirSource, MemorySource, ModSource};
+
+/// Verifies only mods with a manifest are listed, and files are read
+/// relative to their mod.
+#[test]
+fn memory_source_lists_mods_with_manifest() {
+    let source = MemorySource::default()
+        .with_file("horde", "mod.toml", "id = \"horde\"")
+        .with_file("horde", "scripts/mod.wat", "(module)")
+        .with_file("stray", "readme.txt", "no manifest");
+    assert_eq!(source.mods().unwrap(), ["horde"]);
+    assert_eq!(
+        source.read("horde", "./scripts/mod.wat").unwrap(),
+        b"(module)"
+    );
+    assert!(source.read("horde", "missing.wat").is_err());
+}
+
+/// Verifies paths leaving the mod's root are refused.
+#[test]
+fn refuses_paths_outside_the_mod() {
+    let source = MemorySource::default()
+        .with_file("horde", "mod.toml", "")
+        .with_file("other", "mod.toml", "secret");
+    for path in [
+        "../other/mod.toml",
+        "/etc/passwd",
+        "scripts/../../other/mod.toml",
+    ] {
+        let error = source.read("horde", path).unwrap_err().to_string();
+        assert!(error.contains("outside the mod"), "{error}");
+    }
+}
+
+/// Verifies a directory source lists subdirectories with a manifest and
+/// reads their files.
+#[test]
+fn dir_source_reads_mod_directories() {
+    let root = std::env::temp_dir().join(format!("rustgine-mods-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("horde")).unwrap();
+    std::fs::create_dir_all(root.join("empty")).unwrap();
+    std::fs::write(root.join("horde/mod.toml"), "id = \"horde\"").unwrap();
+
+    let source = DirSource::new(&root);
+    assert_eq!(source.mods().unwrap(), ["horde"]);
+    assert_eq!(source.read("horde", "mod.toml").unwrap(), b"id = \"horde\"");
+    assert!(source.read("horde", "../horde/mod.toml").is_err());
+    std::fs::remove_dir_all(&root).unwrap();
+
+    assert!(source.mods().unwrap().is_empty());
+}
@@ -0,0 +1,378 @@
+//! Simulated network conditions for development.
+//!
+//! Games that only ever run over loopback hide their networking bugs until
+//! players hit them. A [`LinkConditioner`] sits on one direction of a link
+//! and delays, jitters, drops, and duplicates packets according to
+//! [`NetworkConditions`], so prediction, reliability, and interpolation can
+//! be tested against realistic connections on a development machine.
+//! [`LocalTransport`] is an in-memory link with a conditioner in each
+//! direction.
+//!
+//! Conditions are usually driven by cvars, so they can be changed from the
+//! console while the game runs:
+//!
+//! ```text
+//! net.sim.enabled true
+//! net.sim.latency_ms 80
+//! net.sim.jitter_ms 15
+//! net.sim.loss 0.02
+//! ```
+
+use rustgine_core::{CVar, CVars};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Network impairments applied to one direction of a link.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct NetworkConditions {
+    /// Delay added to every packet.
+    pub latency: Duration,
+    /// Largest random extra delay, which can reorder packets.
+    pub jitter: Duration,
+    /// Fraction of packets dropped, from 0 to 1.
+    pub loss: f32,
+    /// Fraction of packets delivered twice, from 0 to 1.
+    pub duplication: f32,
+}
+
+impl NetworkConditions {
+    /// A perfect link.
+    pub const IDEAL: Self = Self {
+        latency: Duration::ZERO,
+        jitter: Duration::ZERO,
+        loss: 0.0,
+        duplication: 0.0,
+    };
+
+    /// Returns `true` if packets pass through unchanged.
+    #[must_use]
+    pub fn is_ideal(&self) -> bool {
+        self.latency.is_zero()
+            && self.jitter.is_zero()
+            && self.loss <= 0.0
+            && self.duplication <= 0.0
+    }
+}
+
+/// Cvars controlling simulated network conditions.
+#[derive(Debug, Clone)]
+pub struct ConditionCVars {
+    /// `net.sim.enabled`: whether conditions are applied.
+    enabled: CVar<bool>,
+    /// `net.sim.latency_ms`: delay added to every packet.
+    latency_ms: CVar<i64>,
+    /// `net.sim.jitter_ms`: largest random extra delay.
+    jitter_ms: CVar<i64>,
+    /// `net.sim.loss`: fraction of packets dropped.
+    loss: CVar<f64>,
+    /// `net.sim.duplicate`: fraction of packets delivered twice.
+    duplicate: CVar<f64>,
+}
+
+impl ConditionCVars {
+    /// Registers the `net.sim.*` cvars, disabled by default.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cvars are already registered.
+    pub fn register(cvars: &CVars) -> anyhow::Result<Self> {
+        Ok(Self {
+            enabled: cvars.register(
+                "net.sim.enabled",
+                false,
+                "Apply simulated network conditions",
+            )?,
+            latency_ms: cvars.register(
+                "net.sim.latency_ms",
+                0_i64,
+                "Simulated one-way latency in milliseconds",
+            )?,
+            jitter_ms: cvars.register(
+                "net.sim.jitter_ms",
+                0_i64,
+                "Simulated random extra latency in milliseconds",
+            )?,
+            loss: cvars.register("net.sim.loss", 0.0, "Simulated packet loss, from 0 to 1")?,
+            duplicate: cvars.register(
+                "net.sim.duplicate",
+                0.0,
+                "Simulated packet duplication, from 0 to 1",
+            )?,
+        })
+    }
+
+    /// Returns the conditions the cvars currently describe.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn conditions(&self) -> NetworkConditions {
+        if !self.enabled.get() {
+            return NetworkConditions::IDEAL;
+        }
+        let millis = |cvar: &CVar<i64>| Duration::from_millis(cvar.get().max(0).unsigned_abs());
+        NetworkConditions {
+            latency: millis(&self.latency_ms),
+            jitter: millis(&self.jitter_ms),
+            loss: self.loss.get().clamp(0.0, 1.0) as f32,
+            duplication: self.duplicate.get().clamp(0.0, 1.0) as f32,
+        }
+    }
+}
+
+/// Counts of what a conditioner did to packets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ConditionerStats {
+    /// Packets sent into the conditioner.
+    pub sent: u64,
+    /// Packets dropped.
+    pub dropped: u64,
+    /// Extra copies delivered.
+    pub duplicated: u64,
+}
+
+/// Where a conditioner's conditions come from.
+#[derive(Debug, Clone)]
+enum Source {
+    /// Fixed conditions.
+    Fixed(NetworkConditions),
+    /// Conditions read from cvars on every send.
+    CVars(ConditionCVars),
+}
+
+/// Applies [`NetworkConditions`] to one direction of a link.
+///
+/// # Example
+///
+/// ```
+/// use net::conditioner::{LinkConditioner, NetworkConditions};
+/// use std::time::{Duration, Instant};
+///
+/// let latency = Duration::from_millis(50);
+/// let mut link = LinkConditioner::new(NetworkConditions { latency, ..NetworkConditions::IDEAL });
+///
+/// let now = Instant::now();
+/// link.send(now, "hello");
+/// assert!(link.receive(now).is_empty());
+/// assert_eq!(link.receive(now + latency), ["hello"]);
+/// ```
+#[derive(Debug)]
+pub struct LinkConditioner<T> {
+    /// Where conditions come from.
+    source: Source,
+    /// Packets in flight with their delivery time, in send order.
+    in_flight: VecDeque<(Instant, T)>,
+    /// State of the random number generator.
+    rng: u64,
+    /// What was done to packets.
+    stats: ConditionerStats,
+}
+
+impl<T: Clone> LinkConditioner<T> {
+    /// Creates a conditioner applying fixed conditions.
+    #[must_use]
+    pub fn new(conditions: NetworkConditions) -> Self {
+        Self::with_source(Source::Fixed(conditions))
+    }
+
+    /// Creates a conditioner applying the conditions `cvars` describe at
+    /// the time of each send.
+    #[must_use]
+    pub fn from_cvars(cvars: ConditionCVars) -> Self {
+        Self::with_source(Source::CVars(cvars))
+    }
+
+    fn with_source(source: Source) -> Self {
+        let mut seed = [0; 8];
+        // Without OS randomness the zero seed still gives usable, if
+        // repeatable, conditions.
+        let _ = getrandom::fill(&mut seed);
+        Self {
+            source,
+            in_flight: VecDeque::new(),
+            rng: 0,
+            stats: ConditionerStats::default(),
+        }
+        .with_seed(u64::from_le_bytes(seed))
+    }
+
+    /// Seeds the random number generator, so tests see the same drops and
+    /// delays on every run.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        // Xorshift would stay at zero forever.
+        self.rng = seed | 1;
+        self
+    }
+
+    /// Replaces the conditions, including cvar-driven ones.
+    pub fn set_conditions(&mut self, conditions: NetworkConditions) {
+        self.source = Source::Fixed(conditions);
+    }
+
+    /// Returns the conditions applied to the next packet.
+    #[must_use]
+    pub fn conditions(&self) -> NetworkConditions {
+        match &self.source {
+            Source::Fixed(conditions) => *conditions,
+            Source::CVars(cvars) => cvars.conditions(),
+        }
+    }
+
+    /// Returns what was done to packets so far.
+    #[must_use]
+    #[inline]
+    pub fn stats(&self) -> ConditionerStats {
+        self.stats
+    }
+
+    /// Returns the number of packets in flight.
+    #[must_use]
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Sends a packet at `now`.
+    pub fn send(&mut self, now: Instant, packet: T) {
+        let conditions = self.conditions();
+        self.stats.sent += 1;
+        if conditions.is_ideal() {
+            self.in_flight.push_back((now, packet));
+            return;
+        }
+        if self.chance(conditions.loss) {
+            self.stats.dropped += 1;
+            return;
+        }
+        if self.chance(conditions.duplication) {
+            self.stats.duplicated += 1;
+            let at = now + conditions.latency + self.jitter(conditions.jitter);
+            self.in_flight.push_back((at, packet.clone()));
+        }
+        let at = now + conditions.latency + self.jitter(conditions.jitter);
+        self.in_flight.push_back((at, packet));
+    }
+
+    /// Returns the packets delivered by `now`, in delivery order.
+    pub fn receive(&mut self, now: Instant) -> Vec<T> {
+        let mut due: Vec<(Instant, T)> = Vec::new();
+        let mut index = 0;
+        while index < self.in_flight.len() {
+            if self.in_flight[index].0 <= now {
+                due.extend(self.in_flight.remove(index));
+            } else {
+                index += 1;
+            }
+        }
+        // Stable, so packets due at the same time keep their send order.
+        due.sort_by_key(|(at, _)| *at);
+        due.into_iter().map(|(_, packet)| packet).collect()
+    }
+
+    fn chance(&mut self, probability: f32) -> bool {
+        probability > 0.0 && self.next_unit() < probability
+    }
+
+    fn jitter(&mut self, max: Duration) -> Duration {
+        max.mul_f32(self.next_unit())
+    }
+
+    /// Returns a random number in `0..1`.
+    #[allow(clippy::cast_precision_loss)]
+    fn next_unit(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 40) as f32 / (1_u64 << 24) as f32
+    }
+}
+
+/// One end of an in-memory link with simulated conditions.
+///
+/// # Example
+///
+/// ```
+/// use net::conditioner::{LocalTransport, NetworkConditions};
+/// use std::time::Instant;
+///
+/// let (mut client, mut server) = LocalTransport::pair(NetworkConditions::IDEAL);
+/// let now = Instant::now();
+/// client.send(now, vec![1, 2, 3]);
+/// assert_eq!(server.receive(now), [vec![1, 2, 3]]);
+/// ```
+#[derive(Debug)]
+pub struct LocalTransport<T> {
+    /// Packets this end sends.
+    outgoing: Arc<Mutex<LinkConditioner<T>>>,
+    /// Packets this end receives.
+    incoming: Arc<Mutex<LinkConditioner<T>>>,
+}
+
+impl<T: Clone> LocalTransport<T> {
+    /// Creates both ends of a link with the same conditions each way.
+    #[must_use]
+    pub fn pair(conditions: NetworkConditions) -> (Self, Self) {
+        Self::pair_from(
+            LinkConditioner::new(conditions),
+            LinkConditioner::new(conditions),
+        )
+    }
+
+    /// Creates both ends of a link whose conditions follow the cvars.
+    #[must_use]
+    pub fn pair_from_cvars(cvars: &ConditionCVars) -> (Self, Self) {
+        Self::pair_from(
+            LinkConditioner::from_cvars(cvars.clone()),
+            LinkConditioner::from_cvars(cvars.clone()),
+        )
+    }
+
+    /// Creates both ends of a link from the conditioners of each direction.
+    #[must_use]
+    pub fn pair_from(
+        first_to_second: LinkConditioner<T>,
+        second_to_first: LinkConditioner<T>,
+    ) -> (Self, Self) {
+        let forward = Arc::new(Mutex::new(first_to_second));
+        let backward = Arc::new(Mutex::new(second_to_first));
+        (
+            Self {
+                outgoing: Arc::clone(&forward),
+                incoming: Arc::clone(&backward),
+            },
+            Self {
+                outgoing: backward,
+                incoming: forward,
+            },
+        )
+    }
+
+    /// Sends a packet to the other end at `now`.
+    pub fn send(&mut self, now: Instant, packet: T) {
+        lock(&self.outgoing).send(now, packet);
+    }
+
+    /// Returns the packets from the other end delivered by `now`.
+    pub fn receive(&mut self, now: Instant) -> Vec<T> {
+        lock(&self.incoming).receive(now)
+    }
+
+    /// Replaces the conditions of packets this end sends.
+    pub fn set_conditions(&mut self, conditions: NetworkConditions) {
+        lock(&self.outgoing).set_conditions(conditions);
+    }
+
+    /// Returns what was done to packets this end sent.
+    #[must_use]
+    pub fn stats(&self) -> ConditionerStats {
+        lock(&self.outgoing).stats()
+    }
+}
+
+fn lock<T>(
+    conditioner: &Mutex<LinkConditioner<T>>,
+) -> std::sync::MutexGuard<'_, LinkConditioner<T>> {
+    // A panic mid-send leaves at worst a lost packet, which is the point.
+    conditioner
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
@@ -0,0 +1,102 @@
+//! Unit tests for simulated network conditions.
+
+use crate::conditioner::{ConditionCVars, LinkConditioner, LocalTransport, NetworkConditions};
+use rustgine_core::CVars;
+use std::time::{Duration, Instant};
+
+const MS: Duration = Duration::from_millis(1);
+
+/// Verifies that an ideal link delivers immediately and in order.
+#[test]
+fn ideal_link_passes_through() {
+    let now = Instant::now();
+    let mut link = LinkConditioner::new(NetworkConditions::IDEAL);
+    for n in 0..5 {
+        link.send(now, n);
+    }
+
+    assert_eq!(link.receive(now), [0, 1, 2, 3, 4]);
+    assert_eq!(link.in_flight(), 0);
+}
+
+/// Verifies that jitter keeps delays within latency plus jitter.
+#[test]
+fn delays_within_jitter() {
+    let now = Instant::now();
+    let mut link = LinkConditioner::new(NetworkConditions {
+        latency: 40 * MS,
+        jitter: 20 * MS,
+        ..NetworkConditions::IDEAL
+    })
+    .with_seed(7);
+    for n in 0..100 {
+        link.send(now, n);
+    }
+
+    assert!(link.receive(now + 39 * MS).is_empty());
+    let mut delivered = link.receive(now + 60 * MS);
+    assert_eq!(delivered.len(), 100);
+    // Jitter reorders packets, but loses none.
+    assert_ne!(delivered, (0..100).collect::<Vec<_>>());
+    delivered.sort_unstable();
+    assert_eq!(delivered, (0..100).collect::<Vec<_>>());
+}
+
+/// Verifies that loss and duplication happen at roughly their rates.
+#[test]
+fn drops_and_duplicates_at_rate() {
+    let now = Instant::now();
+    let mut link = LinkConditioner::new(NetworkConditions {
+        loss: 0.2,
+        duplication: 0.1,
+        ..NetworkConditions::IDEAL
+    })
+    .with_seed(42);
+    for n in 0..10_000 {
+        link.send(now, n);
+    }
+
+    let stats = link.stats();
+    assert_eq!(stats.sent, 10_000);
+    assert!((1_800..2_200).contains(&stats.dropped), "{stats:?}");
+    assert!((650..950).contains(&stats.duplicated), "{stats:?}");
+    let delivered = link.receive(now).len() as u64;
+    assert_eq!(delivered, stats.sent - stats.dropped + stats.duplicated);
+}
+
+/// Verifies that conditions follow the cvars and are off by default.
+#[test]
+fn conditions_follow_cvars() {
+    let cvars = CVars::new();
+    let conditions = ConditionCVars::register(&cvars).unwrap();
+    cvars.set("net.sim.latency_ms", "80").unwrap();
+    assert!(conditions.conditions().is_ideal());
+
+    cvars.set("net.sim.enabled", "true").unwrap();
+    cvars.set("net.sim.loss", "1.5").unwrap();
+
+    let applied = conditions.conditions();
+    assert_eq!(applied.latency, 80 * MS);
+    assert!((applied.loss - 1.0).abs() < f32::EPSILON);
+    assert!(ConditionCVars::register(&cvars).is_err());
+}
+
+/// Verifies that a local transport applies cvar changes to later packets.
+#[test]
+fn local_transport_applies_cvars() {
+    let now = Instant::now();
+    let cvars = CVars::new();
+    let conditions = ConditionCVars::register(&cvars).unwrap();
+    let (mut client, mut server) = LocalTransport::pair_from_cvars(&conditions);
+
+    client.send(now, "before");
+    cvars.set("net.sim.enabled", "true").unwrap();
+    cvars.set("net.sim.latency_ms", "100").unwrap();
+    client.send(now, "after");
+    server.send(now, "reply");
+
+    assert_eq!(server.receive(now), ["before"]);
+    assert_eq!(server.receive(now + 100 * MS), ["after"]);
+    assert_eq!(client.receive(now + 100 * MS), ["reply"]);
+    assert_eq!(client.stats().sent, 2);
+}
@@ -0,0 +1,72 @@
+//! Unit tests for packet encryption and replay protection.
+
+use crate::crypto::{generate_key, PacketCipher, ReplayProtection, REPLAY_WINDOW};
+
+fn pair(protocol_id: u64) -> (PacketCipher, PacketCipher) {
+    let (up, down) = (generate_key().unwrap(), generate_key().unwrap());
+    (
+        PacketCipher::new(protocol_id, up, down),
+        PacketCipher::new(protocol_id, down, up),
+    )
+}
+
+/// Verifies that sealed packets open to their kind and contents.
+#[test]
+fn round_trips_packets() {
+    let (mut client, mut server) = pair(7);
+
+    let packet = client.seal(4, b"state").unwrap();
+
+    assert!(!packet.windows(5).any(|window| window == b"state"));
+    assert_eq!(server.open(&packet).unwrap(), (4, b"state".to_vec()));
+    assert_eq!(client.next_sequence(), 1);
+}
+
+/// Verifies that altered packets, including a changed kind, are rejected.
+#[test]
+fn rejects_tampering() {
+    let (mut client, mut server) = pair(7);
+    let packet = client.seal(4, b"state").unwrap();
+
+    let mut flipped = packet.clone();
+    *flipped.last_mut().unwrap() ^= 1;
+    assert!(server.open(&flipped).is_err());
+
+    let mut rekinded = packet.clone();
+    rekinded[0] = 3;
+    assert!(server.open(&rekinded).is_err());
+
+    // Failed packets do not use up their sequence number.
+    assert!(server.open(&packet).is_ok());
+}
+
+/// Verifies that packets sealed for another protocol are rejected.
+#[test]
+fn rejects_other_protocols() {
+    let key = generate_key().unwrap();
+    let mut sender = PacketCipher::new(1, key, key);
+    let mut receiver = PacketCipher::new(2, key, key);
+
+    let packet = sender.seal(4, b"state").unwrap();
+
+    assert!(receiver.open(&packet).is_err());
+}
+
+/// Verifies that replayed and too-old packets are rejected while
+/// reordered ones are accepted.
+#[test]
+fn rejects_replays() {
+    let (mut client, mut server) = pair(7);
+    let packets: Vec<Vec<u8>> = (0..3).map(|_| client.seal(4, b"x").unwrap()).collect();
+
+    assert!(server.open(&packets[2]).is_ok());
+    assert!(server.open(&packets[0]).is_ok());
+    assert!(server.open(&packets[0]).is_err());
+    assert!(server.open(&packets[1]).is_ok());
+
+    let mut window = ReplayProtection::default();
+    let newest = REPLAY_WINDOW as u64 + 10;
+    window.record(newest);
+    assert!(window.is_replay(5));
+    assert!(!window.is_replay(newest - 1));
+}
@@ -0,0 +1,774 @@
+//! Connect tokens and the authenticated connection handshake.
+//!
+//! Only players the game's backend has authorized may join a server. After
+//! logging a player in, the backend issues a [`ConnectToken`] with a
+//! [`TokenIssuer`] holding a key it shares with its game servers, and hands
+//! the token to the client over its own secure channel, such as HTTPS. The
+//! token carries a private part encrypted with the shared key, which only
+//! servers can read, holding the client id and the connection's packet keys.
+//!
+//! The handshake follows netcode.io:
+//!
+//! 1. The client sends a connection request holding the private part.
+//! 2. The server decrypts it, rejecting expired, forged, or reused tokens,
+//!    and replies with a challenge sealed with the packet keys.
+//! 3. The client proves it holds the keys by returning the challenge.
+//! 4. The server admits the client and both ends switch to a
+//!    [`Connection`] that encrypts all further packets.
+//!
+//! Servers never answer a request they cannot decrypt, so spoofed packets
+//! get no reply to amplify.
+//!
+//! Tokens and servers are bound to a protocol id naming the game and its
+//! version. Deriving it with
+//! [`BuildInfo::protocol_id`](rustgine_core::BuildInfo::protocol_id) makes
+//! servers reject clients built from another engine version.
+
+use crate::crypto::{generate_key, Key, PacketCipher, KEY_BYTES, TAG_BYTES};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce, XChaCha20Poly1305, XNonce};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Handshake protocol version, checked before anything else is read.
+pub const VERSION: [u8; 8] = *b"RGNET1.0";
+
+/// Length of a connect token's nonce in bytes.
+pub const TOKEN_NONCE_BYTES: usize = 24;
+
+/// Length of the game data a token carries to the server.
+pub const USER_DATA_BYTES: usize = 32;
+
+/// Default time a connect token stays valid.
+pub const DEFAULT_TOKEN_LIFETIME: Duration = Duration::from_secs(30);
+
+/// Default time without packets before a connection times out.
+pub const DEFAULT_CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Length of a token's decrypted private part.
+const PRIVATE_BYTES: usize = 8 + 4 + KEY_BYTES * 2 + USER_DATA_BYTES;
+
+/// Length of a token's encrypted private part.
+const SEALED_PRIVATE_BYTES: usize = PRIVATE_BYTES + TAG_BYTES;
+
+/// Length of a serialized connect token.
+const TOKEN_BYTES: usize = 8 + 8 + 8 + TOKEN_NONCE_BYTES + SEALED_PRIVATE_BYTES + 4 + KEY_BYTES * 2;
+
+/// Length of a connection request.
+const REQUEST_BYTES: usize = 1 + VERSION.len() + 8 + 8 + TOKEN_NONCE_BYTES + SEALED_PRIVATE_BYTES;
+
+/// Length of a welcome: client id and user data.
+const WELCOME_BYTES: usize = 8 + USER_DATA_BYTES;
+
+/// Length of an encrypted challenge.
+const CHALLENGE_BYTES: usize = 8 + 8 + USER_DATA_BYTES + TAG_BYTES;
+
+/// Kinds of transport packets; the first byte of every packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum PacketKind {
+    /// Connection request, holding a token's private part.
+    Request = 0,
+    /// Challenge from the server.
+    Challenge = 1,
+    /// Challenge returned by the client.
+    Response = 2,
+    /// Admission of the client.
+    Welcome = 3,
+    /// Game data on an established connection.
+    Payload = 4,
+}
+
+/// Returns the current time in seconds since the Unix epoch.
+#[must_use]
+pub fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// A token authorizing one client to connect, issued by the backend.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ConnectToken {
+    /// Game and version the token is for.
+    pub protocol_id: u64,
+    /// When the token was issued, in Unix seconds.
+    pub create_timestamp: u64,
+    /// When the token stops being accepted, in Unix seconds.
+    pub expire_timestamp: u64,
+    /// Nonce the private part was encrypted with.
+    nonce: [u8; TOKEN_NONCE_BYTES],
+    /// Private part, readable only by servers.
+    private: Vec<u8>,
+    /// Seconds without packets before the connection times out.
+    pub timeout_seconds: u32,
+    /// Key for packets from client to server.
+    client_to_server_key: Key,
+    /// Key for packets from server to client.
+    server_to_client_key: Key,
+}
+
+impl std::fmt::Debug for ConnectToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Keys stay out of logs.
+        f.debug_struct("ConnectToken")
+            .field("protocol_id", &self.protocol_id)
+            .field("create_timestamp", &self.create_timestamp)
+            .field("expire_timestamp", &self.expire_timestamp)
+            .field("timeout_seconds", &self.timeout_seconds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ConnectToken {
+    /// Serializes the token for delivery to the client.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(TOKEN_BYTES);
+        bytes.extend_from_slice(&self.protocol_id.to_le_bytes());
+        bytes.extend_from_slice(&self.create_timestamp.to_le_bytes());
+        bytes.extend_from_slice(&self.expire_timestamp.to_le_bytes());
+        bytes.extend_from_slice(&self.nonce);
+        bytes.extend_from_slice(&self.private);
+        bytes.extend_from_slice(&self.timeout_seconds.to_le_bytes());
+        bytes.extend_from_slice(&self.client_to_server_key);
+        bytes.extend_from_slice(&self.server_to_client_key);
+        bytes
+    }
+
+    /// Parses a token received from the backend.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bytes` is not a serialized token.
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        if bytes.len() != TOKEN_BYTES {
+            anyhow::bail!(
+                "connect token is {} bytes, expected {TOKEN_BYTES}",
+                bytes.len()
+            );
+        }
+        let mut reader = Reader(bytes);
+        Ok(Self {
+            protocol_id: reader.u64(),
+            create_timestamp: reader.u64(),
+            expire_timestamp: reader.u64(),
+            nonce: reader.array(),
+            private: reader.take(SEALED_PRIVATE_BYTES).to_vec(),
+            timeout_seconds: reader.u32(),
+            client_to_server_key: reader.array(),
+            server_to_client_key: reader.array(),
+        })
+    }
+}
+
+/// The decrypted private part of a connect token.
+struct PrivateToken {
+    /// Client the token was issued to.
+    client_id: u64,
+    /// Seconds without packets before the connection times out.
+    timeout_seconds: u32,
+    /// Key for packets from client to server.
+    client_to_server_key: Key,
+    /// Key for packets from server to client.
+    server_to_client_key: Key,
+    /// Game data passed from the backend to the server.
+    user_data: [u8; USER_DATA_BYTES],
+}
+
+/// Issues connect tokens; runs on the game's backend.
+///
+/// # Example
+///
+/// ```
+/// use net::crypto::generate_key;
+/// use net::handshake::{unix_time, ClientHandshake, ServerAuth, ServerEvent, TokenIssuer};
+///
+/// let key = generate_key()?;
+/// let issuer = TokenIssuer::new(0x5247, key);
+/// let mut server = ServerAuth::new(0x5247, key)?;
+///
+/// let now = unix_time();
+/// let token = issuer.issue(42, [0; 32], now)?;
+/// let mut client = ClientHandshake::new(token);
+///
+/// let challenge = match server.handle(now, &"client", &client.request())? {
+///     ServerEvent::Reply(packet) => packet,
+///     ServerEvent::Connected { .. } => unreachable!(),
+/// };
+/// let response = client.handle(&challenge)?.unwrap();
+/// let ServerEvent::Connected { connection, reply } = server.handle(now, &"client", &response)? else {
+///     unreachable!();
+/// };
+/// assert_eq!(connection.client_id(), 42);
+/// assert!(client.handle(&reply)?.is_none());
+/// assert!(client.take_connection().is_some());
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct TokenIssuer {
+    /// Game and version tokens are for.
+    protocol_id: u64,
+    /// Key shared with game servers.
+    cipher: XChaCha20Poly1305,
+    /// Time tokens stay valid.
+    lifetime: Duration,
+    /// Time without packets before connections time out.
+    timeout: Duration,
+}
+
+impl std::fmt::Debug for TokenIssuer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenIssuer")
+            .field("protocol_id", &self.protocol_id)
+            .field("lifetime", &self.lifetime)
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TokenIssuer {
+    /// Creates an issuer for `protocol_id` with the key shared with servers.
+    #[must_use]
+    pub fn new(protocol_id: u64, key: Key) -> Self {
+        Self {
+            protocol_id,
+            cipher: XChaCha20Poly1305::new(&key.into()),
+            lifetime: DEFAULT_TOKEN_LIFETIME,
+            timeout: DEFAULT_CONNECTION_TIMEOUT,
+        }
+    }
+
+    /// Sets how long tokens stay valid; keep it short, since a stolen token
+    /// works until it expires.
+    #[must_use]
+    pub fn with_lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Sets the time without packets before connections time out.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Issues a token for `client_id` at `now`, in Unix seconds, carrying
+    /// `user_data` to the server.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if key generation or encryption fails.
+    pub fn issue(
+        &self,
+        client_id: u64,
+        user_data: [u8; USER_DATA_BYTES],
+        now: u64,
+    ) -> anyhow::Result<ConnectToken> {
+        let private = PrivateToken {
+            client_id,
+            timeout_seconds: u32::try_from(self.timeout.as_secs())?,
+            client_to_server_key: generate_key()?,
+            server_to_client_key: generate_key()?,
+            user_data,
+        };
+        let mut nonce = [0; TOKEN_NONCE_BYTES];
+        getrandom::fill(&mut nonce)
+            .map_err(|e| anyhow::anyhow!("failed to generate nonce: {e}"))?;
+        let expire_timestamp = now.saturating_add(self.lifetime.as_secs());
+
+        let mut plaintext = Vec::with_capacity(PRIVATE_BYTES);
+        plaintext.extend_from_slice(&private.client_id.to_le_bytes());
+        plaintext.extend_from_slice(&private.timeout_seconds.to_le_bytes());
+        plaintext.extend_from_slice(&private.client_to_server_key);
+        plaintext.extend_from_slice(&private.server_to_client_key);
+        plaintext.extend_from_slice(&private.user_data);
+        let sealed = self
+            .cipher
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: &plaintext,
+                    aad: &token_associated_data(self.protocol_id, expire_timestamp),
+                },
+            )
+            .map_err(|_| anyhow::anyhow!("failed to encrypt connect token"))?;
+
+        Ok(ConnectToken {
+            protocol_id: self.protocol_id,
+            create_timestamp: now,
+            expire_timestamp,
+            nonce,
+            private: sealed,
+            timeout_seconds: private.timeout_seconds,
+            client_to_server_key: private.client_to_server_key,
+            server_to_client_key: private.server_to_client_key,
+        })
+    }
+}
+
+/// An authenticated, encrypted connection.
+#[derive(Debug)]
+pub struct Connection {
+    /// Client the connection belongs to.
+    client_id: u64,
+    /// Game data from the connect token.
+    user_data: [u8; USER_DATA_BYTES],
+    /// Time without packets before the connection times out.
+    timeout: Duration,
+    /// Packet encryption for this end.
+    cipher: PacketCipher,
+}
+
+impl Connection {
+    /// Returns the client the connection belongs to.
+    #[must_use]
+    #[inline]
+    pub fn client_id(&self) -> u64 {
+        self.client_id
+    }
+
+    /// Returns the game data the backend put in the connect token.
+    #[must_use]
+    #[inline]
+    pub fn user_data(&self) -> &[u8; USER_DATA_BYTES] {
+        &self.user_data
+    }
+
+    /// Returns the time without packets from the other end after which the
+    /// connection should be dropped, as set in the connect token.
+    #[must_use]
+    #[inline]
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Encrypts game data into a packet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if encryption fails.
+    pub fn seal(&mut self, payload: &[u8]) -> anyhow::Result<Vec<u8>> {
+        self.cipher.seal(PacketKind::Payload as u8, payload)
+    }
+
+    /// Decrypts a packet of game data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packet is not authentic game data or was
+    /// already received.
+    pub fn open(&mut self, packet: &[u8]) -> anyhow::Result<Vec<u8>> {
+        match self.cipher.open(packet)? {
+            (kind, payload) if kind == PacketKind::Payload as u8 => Ok(payload),
+            (kind, _) => anyhow::bail!("unexpected packet kind {kind} on a connection"),
+        }
+    }
+}
+
+/// Client side of the handshake.
+#[derive(Debug)]
+pub struct ClientHandshake {
+    /// The token being presented.
+    token: ConnectToken,
+    /// Packet encryption, until handed to the connection.
+    cipher: Option<PacketCipher>,
+    /// The connection, once the server admits the client.
+    connection: Option<Connection>,
+}
+
+impl ClientHandshake {
+    /// Starts a handshake presenting `token`.
+    #[must_use]
+    pub fn new(token: ConnectToken) -> Self {
+        let cipher = PacketCipher::new(
+            token.protocol_id,
+            token.client_to_server_key,
+            token.server_to_client_key,
+        );
+        Self {
+            token,
+            cipher: Some(cipher),
+            connection: None,
+        }
+    }
+
+    /// Returns the connection request; resend it until a challenge arrives.
+    #[must_use]
+    pub fn request(&self) -> Vec<u8> {
+        let mut packet = Vec::with_capacity(REQUEST_BYTES);
+        packet.push(PacketKind::Request as u8);
+        packet.extend_from_slice(&VERSION);
+        packet.extend_from_slice(&self.token.protocol_id.to_le_bytes());
+        packet.extend_from_slice(&self.token.expire_timestamp.to_le_bytes());
+        packet.extend_from_slice(&self.token.nonce);
+        packet.extend_from_slice(&self.token.private);
+        packet
+    }
+
+    /// Handles a packet from the server, returning the reply to send, if
+    /// any. Once a welcome arrives the connection is ready to take.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the packet is not authentic.
+    pub fn handle(&mut self, packet: &[u8]) -> anyhow::Result<Option<Vec<u8>>> {
+        let Some(cipher) = self.cipher.as_mut() else {
+            anyhow::bail!("handshake already complete");
+        };
+        let (kind, body) = cipher.open(packet)?;
+        match kind {
+            kind if kind == PacketKind::Challenge as u8 => {
+                Ok(Some(cipher.seal(PacketKind::Response as u8, &body)?))
+            }
+            kind if kind == PacketKind::Welcome as u8 => {
+                if body.len() != WELCOME_BYTES {
+                    anyhow::bail!("welcome is {} bytes", body.len());
+                }
+                let mut reader = Reader(&body);
+                let (client_id, user_data) = (reader.u64(), reader.array());
+                if let Some(cipher) = self.cipher.take() {
+                    self.connection = Some(Connection {
+                        client_id,
+                        user_data,
+                        timeout: Duration::from_secs(self.token.timeout_seconds.into()),
+                        cipher,
+                    });
+                }
+                Ok(None)
+            }
+            kind => anyhow::bail!("unexpected packet kind {kind} during handshake"),
+        }
+    }
+
+    /// Takes the connection once the server has admitted the client.
+    pub fn take_connection(&mut self) -> Option<Connection> {
+        self.connection.take()
+    }
+}
+
+/// Result of a handshake packet handled by the server.
+#[derive(Debug)]
+pub enum ServerEvent {
+    /// A packet to send back to the sender.
+    Reply(Vec<u8>),
+    /// The sender was admitted.
+    Connected {
+        /// The new connection.
+        connection: Connection,
+        /// The welcome packet to send back.
+        reply: Vec<u8>,
+    },
+}
+
+/// A client that presented a valid token and has not answered the
+/// challenge yet.
+#[derive(Debug)]
+struct PendingClient {
+    /// Client the token was issued to.
+    client_id: u64,
+    /// Game data from the token.
+    user_data: [u8; USER_DATA_BYTES],
+    /// Tag of the token presented.
+    token_tag: [u8; TAG_BYTES],
+    /// The sealed challenge, sent again for resent requests so no nonce is
+    /// used twice with the packet key.
+    challenge: Vec<u8>,
+    /// Packet encryption for the connection.
+    cipher: PacketCipher,
+    /// When the token expires, in Unix seconds.
+    expire_timestamp: u64,
+    /// Seconds without packets before the client is dropped.
+    timeout_seconds: u32,
+    /// When the client last sent a packet, in Unix seconds.
+    last_packet: u64,
+}
+
+impl PendingClient {
+    /// Returns whether the client is still waited on at `now`.
+    fn is_alive(&self, now: u64) -> bool {
+        self.expire_timestamp > now
+            && self.last_packet.saturating_add(self.timeout_seconds.into()) > now
+    }
+}
+
+/// Server side of the handshake, keyed by transport address.
+pub struct ServerAuth<A> {
+    /// Game and version accepted.
+    protocol_id: u64,
+    /// Key shared with the backend.
+    token_cipher: XChaCha20Poly1305,
+    /// Key known only to this server, for challenges.
+    challenge_cipher: ChaCha20Poly1305,
+    /// Number of the next challenge.
+    challenge_sequence: u64,
+    /// Clients waiting to answer a challenge.
+    pending: HashMap<A, PendingClient>,
+    /// Tokens already presented, by tag, with the address and expiry.
+    used_tokens: HashMap<[u8; TAG_BYTES], (A, u64)>,
+}
+
+impl<A> std::fmt::Debug for ServerAuth<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServerAuth")
+            .field("protocol_id", &self.protocol_id)
+            .field("pending", &self.pending.len())
+            .field("used_tokens", &self.used_tokens.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Clone + Eq + Hash> ServerAuth<A> {
+    /// Creates the server side for `protocol_id` with the key shared with
+    /// the backend.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the challenge key cannot be generated.
+    pub fn new(protocol_id: u64, key: Key) -> anyhow::Result<Self> {
+        Ok(Self {
+            protocol_id,
+            token_cipher: XChaCha20Poly1305::new(&key.into()),
+            challenge_cipher: ChaCha20Poly1305::new(&generate_key()?.into()),
+            challenge_sequence: 0,
+            pending: HashMap::new(),
+            used_tokens: HashMap::new(),
+        })
+    }
+
+    /// Returns the number of clients waiting to answer a challenge.
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Handles a handshake packet from `from` at `now`, in Unix seconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and the packet should be dropped without reply, if
+    /// it is malformed, expired, forged, or replayed, or if its sender did
+    /// not answer the challenge within the token's timeout.
+    pub fn handle(&mut self, now: u64, from: &A, packet: &[u8]) -> anyhow::Result<ServerEvent> {
+        self.forget_expired(now);
+        match packet.first() {
+            Some(&kind) if kind == PacketKind::Request as u8 => {
+                self.handle_request(now, from, packet)
+            }
+            Some(_) => self.handle_response(now, from, packet),
+            None => anyhow::bail!("empty packet"),
+        }
+    }
+
+    fn handle_request(&mut self, now: u64, from: &A, packet: &[u8]) -> anyhow::Result<ServerEvent> {
+        if packet.len() != REQUEST_BYTES {
+            anyhow::bail!("connection request is {} bytes", packet.len());
+        }
+        let mut reader = Reader(&packet[1..]);
+        if reader.take(VERSION.len()) != VERSION {
+            anyhow::bail!("connection request has an unsupported version");
+        }
+        let protocol_id = reader.u64();
+        if protocol_id != self.protocol_id {
+            anyhow::bail!("connection request is for protocol {protocol_id:#x}");
+        }
+        let expire_timestamp = reader.u64();
+        if expire_timestamp <= now {
+            anyhow::bail!("connect token expired");
+        }
+        let nonce: [u8; TOKEN_NONCE_BYTES] = reader.array();
+        let sealed = reader.take(SEALED_PRIVATE_BYTES);
+
+        let plaintext = self
+            .token_cipher
+            .decrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: sealed,
+                    aad: &token_associated_data(protocol_id, expire_timestamp),
+                },
+            )
+            .map_err(|_| anyhow::anyhow!("connect token failed authentication"))?;
+        let private = PrivateToken::parse(&plaintext);
+
+        let mut tag = [0; TAG_BYTES];
+        tag.copy_from_slice(&sealed[sealed.len() - TAG_BYTES..]);
+        if let Some((address, _)) = self.used_tokens.get(&tag) {
+            if address != from {
+                anyhow::bail!("connect token already used from another address");
+            }
+            // A resent request gets the same challenge: sealing a new one
+            // would reuse the packet key's first nonce.
+            match self.pending.get_mut(from) {
+                Some(pending) if pending.token_tag == tag => {
+                    pending.last_packet = now;
+                    return Ok(ServerEvent::Reply(pending.challenge.clone()));
+                }
+                _ => anyhow::bail!("connect token already used"),
+            }
+        }
+        self.used_tokens
+            .insert(tag, (from.clone(), expire_timestamp));
+
+        let mut cipher = PacketCipher::new(
+            protocol_id,
+            private.server_to_client_key,
+            private.client_to_server_key,
+        );
+        let challenge = self.challenge(private.client_id, &private.user_data)?;
+        let reply = cipher.seal(PacketKind::Challenge as u8, &challenge)?;
+        self.pending.insert(
+            from.clone(),
+            PendingClient {
+                client_id: private.client_id,
+                user_data: private.user_data,
+                token_tag: tag,
+                challenge: reply.clone(),
+                cipher,
+                expire_timestamp,
+                timeout_seconds: private.timeout_seconds,
+                last_packet: now,
+            },
+        );
+        Ok(ServerEvent::Reply(reply))
+    }
+
+    fn handle_response(
+        &mut self,
+        now: u64,
+        from: &A,
+        packet: &[u8],
+    ) -> anyhow::Result<ServerEvent> {
+        let Some(pending) = self.pending.get_mut(from) else {
+            anyhow::bail!("handshake packet from an unknown address");
+        };
+        let (kind, body) = pending.cipher.open(packet)?;
+        pending.last_packet = now;
+        if kind != PacketKind::Response as u8 {
+            anyhow::bail!("unexpected packet kind {kind} during handshake");
+        }
+        let expected = pending.client_id;
+        let client_id = self.verify_challenge(&body)?;
+        if client_id != expected {
+            anyhow::bail!("challenge response is for another client");
+        }
+        let Some(mut pending) = self.pending.remove(from) else {
+            anyhow::bail!("handshake packet from an unknown address");
+        };
+        let mut welcome = Vec::with_capacity(WELCOME_BYTES);
+        welcome.extend_from_slice(&client_id.to_le_bytes());
+        welcome.extend_from_slice(&pending.user_data);
+        let reply = pending.cipher.seal(PacketKind::Welcome as u8, &welcome)?;
+        tracing::info!(client_id, "client authenticated");
+        Ok(ServerEvent::Connected {
+            connection: Connection {
+                client_id,
+                user_data: pending.user_data,
+                timeout: Duration::from_secs(pending.timeout_seconds.into()),
+                cipher: pending.cipher,
+            },
+            reply,
+        })
+    }
+
+    /// Encrypts a challenge only this server can read back.
+    fn challenge(
+        &mut self,
+        client_id: u64,
+        user_data: &[u8; USER_DATA_BYTES],
+    ) -> anyhow::Result<Vec<u8>> {
+        let sequence = self.challenge_sequence;
+        self.challenge_sequence += 1;
+        let mut plaintext = Vec::with_capacity(WELCOME_BYTES);
+        plaintext.extend_from_slice(&client_id.to_le_bytes());
+        plaintext.extend_from_slice(user_data);
+        let sealed = self
+            .challenge_cipher
+            .encrypt(&challenge_nonce(sequence), plaintext.as_slice())
+            .map_err(|_| anyhow::anyhow!("failed to encrypt challenge"))?;
+        let mut challenge = Vec::with_capacity(CHALLENGE_BYTES);
+        challenge.extend_from_slice(&sequence.to_le_bytes());
+        challenge.extend_from_slice(&sealed);
+        Ok(challenge)
+    }
+
+    /// Decrypts a returned challenge, returning the client it was for.
+    fn verify_challenge(&self, challenge: &[u8]) -> anyhow::Result<u64> {
+        if challenge.len() != CHALLENGE_BYTES {
+            anyhow::bail!("challenge response is {} bytes", challenge.len());
+        }
+        let sequence = Reader(challenge).u64();
+        let plaintext = self
+            .challenge_cipher
+            .decrypt(&challenge_nonce(sequence), &challenge[8..])
+            .map_err(|_| anyhow::anyhow!("challenge response failed authentication"))?;
+        Reader(&plaintext).checked_u64()
+    }
+
+    fn forget_expired(&mut self, now: u64) {
+        self.used_tokens.retain(|_, (_, expire)| *expire > now);
+        self.pending.retain(|_, pending| pending.is_alive(now));
+    }
+}
+
+impl PrivateToken {
+    /// Reads a decrypted private part, which is always [`PRIVATE_BYTES`]
+    /// long since it authenticated.
+    fn parse(bytes: &[u8]) -> Self {
+        let mut reader = Reader(bytes);
+        Self {
+            client_id: reader.u64(),
+            timeout_seconds: reader.u32(),
+            client_to_server_key: reader.array(),
+            server_to_client_key: reader.array(),
+            user_data: reader.array(),
+        }
+    }
+}
+
+fn token_associated_data(protocol_id: u64, expire_timestamp: u64) -> [u8; 24] {
+    let mut data = [0; 24];
+    data[..8].copy_from_slice(&VERSION);
+    data[8..16].copy_from_slice(&protocol_id.to_le_bytes());
+    data[16..].copy_from_slice(&expire_timestamp.to_le_bytes());
+    data
+}
+
+fn challenge_nonce(sequence: u64) -> Nonce {
+    let mut nonce = Nonce::default();
+    nonce[4..].copy_from_slice(&sequence.to_le_bytes());
+    nonce
+}
+
+/// Reads fixed-size fields from a buffer whose length was checked.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> &'a [u8] {
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        head
+    }
+
+    fn array<const N: usize>(&mut self) -> [u8; N] {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N));
+        array
+    }
+
+    fn u64(&mut self) -> u64 {
+        u64::from_le_bytes(self.array())
+    }
+
+    fn u32(&mut self) -> u32 {
+        u32::from_le_bytes(self.array())
+    }
+
+    fn checked_u64(&mut self) -> anyhow::Result<u64> {
+        if self.0.len() < 8 {
+            anyhow::bail!("packet too short");
+        }
+        Ok(self.u64())
+    }
+}
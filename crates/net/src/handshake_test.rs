@@ -0,0 +1,174 @@
+//! Unit tests for connect tokens and the connection handshake.
+
+use crate::crypto::generate_key;
+use crate::handshake::{
+    ClientHandshake, ConnectToken, Connection, ServerAuth, ServerEvent, TokenIssuer,
+};
+use std::time::Duration;
+
+const PROTOCOL: u64 = 0x5247_0001;
+const NOW: u64 = 1_700_000_000;
+
+fn setup() -> (TokenIssuer, ServerAuth<&'static str>) {
+    let key = generate_key().unwrap();
+    (
+        TokenIssuer::new(PROTOCOL, key),
+        ServerAuth::new(PROTOCOL, key).unwrap(),
+    )
+}
+
+fn reply(event: ServerEvent) -> Vec<u8> {
+    match event {
+        ServerEvent::Reply(packet) => packet,
+        ServerEvent::Connected { .. } => panic!("connected before the challenge"),
+    }
+}
+
+/// Runs the full handshake from `address`, returning both connections.
+fn connect(
+    server: &mut ServerAuth<&'static str>,
+    token: ConnectToken,
+    address: &'static str,
+) -> (Connection, Connection) {
+    let mut client = ClientHandshake::new(token);
+    let challenge = reply(server.handle(NOW, &address, &client.request()).unwrap());
+    let response = client.handle(&challenge).unwrap().unwrap();
+    let ServerEvent::Connected { connection, reply } =
+        server.handle(NOW, &address, &response).unwrap()
+    else {
+        panic!("challenge response was not accepted");
+    };
+    assert!(client.handle(&reply).unwrap().is_none());
+    (client.take_connection().unwrap(), connection)
+}
+
+/// Verifies that a valid token connects and both ends exchange encrypted data.
+#[test]
+fn authorized_client_connects() {
+    let (issuer, mut server) = setup();
+    let token = issuer.issue(42, [7; 32], NOW).unwrap();
+
+    let (mut client, mut host) = connect(&mut server, token, "10.0.0.1:4000");
+
+    assert_eq!(client.client_id(), 42);
+    assert_eq!(host.client_id(), 42);
+    assert_eq!(host.user_data(), &[7; 32]);
+    assert_eq!(server.pending(), 0);
+    let packet = client.seal(b"move").unwrap();
+    assert_eq!(host.open(&packet).unwrap(), b"move");
+    let packet = host.seal(b"snapshot").unwrap();
+    assert_eq!(client.open(&packet).unwrap(), b"snapshot");
+}
+
+/// Verifies that tokens survive serialization for delivery to clients.
+#[test]
+fn tokens_round_trip() {
+    let (issuer, mut server) = setup();
+    let token = issuer
+        .with_lifetime(Duration::from_secs(90))
+        .issue(3, [0; 32], NOW)
+        .unwrap();
+
+    let parsed = ConnectToken::from_bytes(&token.to_bytes()).unwrap();
+
+    assert_eq!(parsed, token);
+    assert_eq!(parsed.expire_timestamp, NOW + 90);
+    assert!(ConnectToken::from_bytes(&[0; 12]).is_err());
+    connect(&mut server, parsed, "client");
+}
+
+/// Verifies that expired, forged, and foreign tokens get no reply.
+#[test]
+fn rejects_invalid_tokens() {
+    let (issuer, mut server) = setup();
+    let token = issuer.issue(1, [0; 32], NOW).unwrap();
+    let request = ClientHandshake::new(token.clone()).request();
+
+    let later = token.expire_timestamp;
+    assert!(server.handle(later, &"client", &request).is_err());
+
+    let mut forged = request.clone();
+    let last = forged.len() - 1;
+    forged[last] ^= 1;
+    assert!(server.handle(NOW, &"client", &forged).is_err());
+
+    let other = TokenIssuer::new(PROTOCOL, generate_key().unwrap());
+    let foreign = ClientHandshake::new(other.issue(1, [0; 32], NOW).unwrap()).request();
+    assert!(server.handle(NOW, &"client", &foreign).is_err());
+
+    let wrong_protocol = TokenIssuer::new(PROTOCOL + 1, generate_key().unwrap());
+    let request = ClientHandshake::new(wrong_protocol.issue(1, [0; 32], NOW).unwrap()).request();
+    assert!(server.handle(NOW, &"client", &request).is_err());
+}
+
+/// Verifies that a token captured and replayed from another address is
+/// rejected, while the owner may resend its request.
+#[test]
+fn rejects_replayed_tokens() {
+    let (issuer, mut server) = setup();
+    let token = issuer.issue(1, [0; 32], NOW).unwrap();
+    let request = ClientHandshake::new(token).request();
+
+    assert!(server.handle(NOW, &"owner", &request).is_ok());
+    assert!(server.handle(NOW, &"owner", &request).is_ok());
+    assert!(server.handle(NOW, &"attacker", &request).is_err());
+}
+
+/// Verifies that a resent request gets the same challenge rather than a new
+/// one sealed under the same key and nonce, and that the token cannot be
+/// presented again once connected.
+#[test]
+fn resent_requests_reuse_the_challenge() {
+    let (issuer, mut server) = setup();
+    let mut client = ClientHandshake::new(issuer.issue(1, [0; 32], NOW).unwrap());
+    let request = client.request();
+
+    let first = reply(server.handle(NOW, &"owner", &request).unwrap());
+    let second = reply(server.handle(NOW + 1, &"owner", &request).unwrap());
+    assert_eq!(first, second);
+
+    let response = client.handle(&first).unwrap().unwrap();
+    let event = server.handle(NOW + 1, &"owner", &response).unwrap();
+    assert!(matches!(event, ServerEvent::Connected { .. }));
+    assert!(server.handle(NOW + 1, &"owner", &request).is_err());
+}
+
+/// Verifies that clients not answering the challenge within the token's
+/// timeout are dropped, and connections carry the timeout.
+#[test]
+fn pending_clients_time_out() {
+    let key = generate_key().unwrap();
+    let issuer = TokenIssuer::new(PROTOCOL, key)
+        .with_lifetime(Duration::from_secs(90))
+        .with_timeout(Duration::from_secs(5));
+    let mut server = ServerAuth::new(PROTOCOL, key).unwrap();
+    let token = issuer.issue(1, [0; 32], NOW).unwrap();
+    assert_eq!(token.timeout_seconds, 5);
+    let mut client = ClientHandshake::new(token);
+
+    let challenge = reply(server.handle(NOW, &"client", &client.request()).unwrap());
+    let response = client.handle(&challenge).unwrap().unwrap();
+    assert!(server.handle(NOW + 5, &"client", &response).is_err());
+    assert_eq!(server.pending(), 0);
+
+    let token = issuer.issue(2, [0; 32], NOW).unwrap();
+    let (client, host) = connect(&mut server, token, "other");
+    assert_eq!(client.timeout(), Duration::from_secs(5));
+    assert_eq!(host.timeout(), Duration::from_secs(5));
+}
+
+/// Verifies that a client without the token's keys cannot answer the
+/// challenge.
+#[test]
+fn rejects_responses_without_keys() {
+    let (issuer, mut server) = setup();
+    let token = issuer.issue(1, [0; 32], NOW).unwrap();
+    let mut eavesdropper = ClientHandshake::new(issuer.issue(1, [0; 32], NOW).unwrap());
+    let challenge = reply(
+        server
+            .handle(NOW, &"client", &ClientHandshake::new(token).request())
+            .unwrap(),
+    );
+
+    assert!(eavesdropper.handle(&challenge).is_err());
+}
@@ -0,0 +1,69 @@
+//! Networking subsystem for the Rustgine game engine.
+//!
+//! This crate provides multiplayer building blocks for games.
+//!
+//! # Overview
+//!
+//! The net crate handles:
+//! - Client-side prediction with input history and reconciliation against
+//!   authoritative server snapshots ([`Predictor`])
+//! - Sessions with a shared player list, reliable ordered messages, and
+//!   host migration, independent of matchmaking providers ([`Session`])
+//! - Connect-token authentication and encrypted, replay-protected packets
+//!   ([`ServerAuth`], [`Connection`])
+//! - Simulated latency, jitter, loss, and duplication for development,
+//!   toggled through cvars ([`LinkConditioner`])
+//! - Typed messages and server RPCs with automatic serialization and
+//!   dispatch into event queues ([`NetMessage`], [`server_rpc`])
+//!
+//! # Example
+//!
+//! ```ignore
+//! use net::RustgineNet;
+//! use rustgine_core::RustgineSystem;
+//!
+//! let mut net = RustgineNet::default();
+//! net.startup()?;
+//! ```
+
+#![warn(missing_docs)]
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+/// Re-exports for code generated by the message macros.
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+}
+
+pub mod conditioner;
+#[cfg(test)]
+mod conditioner_test;
+pub mod crypto;
+#[cfg(test)]
+mod crypto_test;
+pub mod handshake;
+#[cfg(test)]
+mod handshake_test;
+pub mod message;
+#[cfg(test)]
+mod message_test;
+pub mod net;
+pub mod prediction;
+#[cfg(test)]
+mod prediction_test;
+pub mod reliable;
+#[cfg(test)]
+mod reliable_test;
+pub mod session;
+#[cfg(test)]
+mod session_test;
+
+pub use conditioner::{ConditionCVars, LinkConditioner, LocalTransport, NetworkConditions};
+pub use crypto::PacketCipher;
+pub use handshake::{ClientHandshake, ConnectToken, Connection, ServerAuth, TokenIssuer};
+pub use message::{server_rpc, MessageDispatcher, NetMessage, Received, ServerRpc};
+pub use net::RustgineNet;
+pub use prediction::{Predicted, PredictionMetrics, Predictor, ServerSnapshot};
+pub use reliable::{Packet, ReliableChannel};
+pub use session::{Player, PlayerId, Session, SessionConfig, SessionEvent, SessionMessage};
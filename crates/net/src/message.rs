@@ -0,0 +1,305 @@
+//! Typed network messages and server RPCs.
+//!
+//! Games declare their messages as plain serde types and derive
+//! [`NetMessage`], which names the message and picks its channel. Messages
+//! are encoded with bincode behind a four-byte id derived from the name, so
+//! no game code handles bytes:
+//!
+//! ```
+//! use net::message::{self, Channel, NetMessage};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize, NetMessage)]
+//! #[net(channel = "unreliable")]
+//! struct PlayerMoved {
+//!     position: [f32; 3],
+//! }
+//!
+//! assert_eq!(PlayerMoved::CHANNEL, Channel::Unreliable);
+//! let bytes = message::encode(&PlayerMoved { position: [1.0, 0.0, 2.0] })?;
+//! let decoded: PlayerMoved = message::decode(&bytes)?;
+//! assert_eq!(decoded.position, [1.0, 0.0, 2.0]);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! A [`MessageDispatcher`] decodes incoming messages by id into per-type
+//! [`Events`] queues that systems read like any other event. Server RPCs,
+//! declared with [`server_rpc`], are messages whose
+//! handler the dispatcher calls directly.
+
+use crate::session::PlayerId;
+use ecs::Events;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::any::Any;
+use std::collections::HashMap;
+
+pub use net_macros::{server_rpc, NetMessage};
+
+/// Length of the message id in front of every encoded message.
+pub const ID_BYTES: usize = 4;
+
+/// How a message is delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Channel {
+    /// Delivered exactly once and in order, resent if lost.
+    #[default]
+    Reliable,
+    /// Sent once; may be lost or arrive out of order. Suits state that is
+    /// resent every tick anyway, such as positions.
+    Unreliable,
+}
+
+/// A message that can be sent over the network.
+///
+/// Derive it with `#[derive(NetMessage)]` rather than implementing it.
+pub trait NetMessage: Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Name identifying the message on both ends.
+    const NAME: &'static str;
+
+    /// Wire id, derived from the name.
+    const ID: u32 = message_id(Self::NAME);
+
+    /// Channel the message is sent on.
+    const CHANNEL: Channel = Channel::Reliable;
+}
+
+/// A message sent by a client that the server handles by calling a function.
+///
+/// Implemented by `#[server_rpc]` for the message it generates.
+///
+/// # Example
+///
+/// ```
+/// use net::message::{self, server_rpc, MessageDispatcher};
+/// use net::session::PlayerId;
+///
+/// #[derive(Default)]
+/// struct Scores(Vec<(PlayerId, u32)>);
+///
+/// #[server_rpc]
+/// fn submit_score(scores: &mut Scores, sender: PlayerId, score: u32) {
+///     scores.0.push((sender, score));
+/// }
+///
+/// let mut dispatcher = MessageDispatcher::default();
+/// dispatcher.register::<SubmitScore>()?;
+/// dispatcher.dispatch(PlayerId(2), &message::encode(&SubmitScore { score: 40 })?)?;
+///
+/// let mut scores = Scores::default();
+/// dispatcher.invoke::<SubmitScore>(&mut scores);
+/// assert_eq!(scores.0, [(PlayerId(2), 40)]);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub trait ServerRpc: NetMessage {
+    /// Server state the handler works on.
+    type Context;
+
+    /// Calls the handler with the message's arguments.
+    fn invoke(self, context: &mut Self::Context, sender: PlayerId);
+}
+
+/// Returns the wire id of a message name: its 32-bit FNV-1a hash.
+#[must_use]
+pub const fn message_id(name: &str) -> u32 {
+    let bytes = name.as_bytes();
+    let mut hash: u32 = 0x811c_9dc5;
+    let mut index = 0;
+    while index < bytes.len() {
+        hash ^= bytes[index] as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+        index += 1;
+    }
+    hash
+}
+
+/// Encodes a message with its id.
+///
+/// # Errors
+///
+/// Returns an error if the message cannot be serialized.
+pub fn encode<M: NetMessage>(message: &M) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = M::ID.to_le_bytes().to_vec();
+    bincode::serde::encode_into_std_write(message, &mut bytes, bincode::config::standard())
+        .map_err(|e| anyhow::anyhow!("failed to encode {}: {e}", M::NAME))?;
+    Ok(bytes)
+}
+
+/// Decodes a message encoded by [`encode`].
+///
+/// # Errors
+///
+/// Returns an error if the bytes hold another message or are malformed.
+pub fn decode<M: NetMessage>(bytes: &[u8]) -> anyhow::Result<M> {
+    match peek_id(bytes) {
+        Some(id) if id == M::ID => decode_body(&bytes[ID_BYTES..]),
+        Some(id) => anyhow::bail!("expected {} but got message {id:#010x}", M::NAME),
+        None => anyhow::bail!("message of {} bytes has no id", bytes.len()),
+    }
+}
+
+/// Returns the id of an encoded message.
+#[must_use]
+pub fn peek_id(bytes: &[u8]) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(..ID_BYTES)?.try_into().ok()?))
+}
+
+fn decode_body<M: NetMessage>(body: &[u8]) -> anyhow::Result<M> {
+    let (message, read) = bincode::serde::decode_from_slice(body, bincode::config::standard())
+        .map_err(|e| anyhow::anyhow!("failed to decode {}: {e}", M::NAME))?;
+    if read != body.len() {
+        anyhow::bail!("{} has {} trailing bytes", M::NAME, body.len() - read);
+    }
+    Ok(message)
+}
+
+/// A message received from a player.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Received<M> {
+    /// The sender.
+    pub sender: PlayerId,
+    /// The message.
+    pub message: M,
+}
+
+/// Type-erased event queue of one message type.
+trait Queue: Send + Sync {
+    /// Returns the message name.
+    fn name(&self) -> &'static str;
+    /// Decodes a message body into the queue.
+    fn push(&mut self, sender: PlayerId, body: &[u8]) -> anyhow::Result<()>;
+    /// Advances the queue by one frame.
+    fn update(&mut self);
+    /// Returns the queue for downcasting.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<M: NetMessage> Queue for Events<Received<M>> {
+    fn name(&self) -> &'static str {
+        M::NAME
+    }
+
+    fn push(&mut self, sender: PlayerId, body: &[u8]) -> anyhow::Result<()> {
+        let message = decode_body(body)?;
+        self.send(Received { sender, message });
+        Ok(())
+    }
+
+    fn update(&mut self) {
+        Events::update(self);
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Decodes incoming messages into per-type event queues.
+///
+/// # Example
+///
+/// ```
+/// use net::message::{self, MessageDispatcher, NetMessage};
+/// use net::session::PlayerId;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, NetMessage)]
+/// struct Chat {
+///     text: String,
+/// }
+///
+/// let mut dispatcher = MessageDispatcher::default();
+/// dispatcher.register::<Chat>()?;
+///
+/// let bytes = message::encode(&Chat { text: "gg".into() })?;
+/// dispatcher.dispatch(PlayerId(3), &bytes)?;
+///
+/// let chat = dispatcher.events::<Chat>().unwrap();
+/// let received = chat.iter().next().unwrap();
+/// assert_eq!((received.sender, received.message.text.as_str()), (PlayerId(3), "gg"));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Default)]
+pub struct MessageDispatcher {
+    /// Event queue of each registered message, by id.
+    queues: HashMap<u32, Box<dyn Queue>>,
+}
+
+impl std::fmt::Debug for MessageDispatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<&str> = self.queues.values().map(|queue| queue.name()).collect();
+        names.sort_unstable();
+        f.debug_struct("MessageDispatcher")
+            .field("messages", &names)
+            .finish()
+    }
+}
+
+impl MessageDispatcher {
+    /// Registers a message type, creating its event queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if another message has the same id; give one of
+    /// them a different name with `#[net(name = "...")]`.
+    pub fn register<M: NetMessage>(&mut self) -> anyhow::Result<()> {
+        if let Some(existing) = self.queues.get(&M::ID) {
+            if existing.name() == M::NAME {
+                return Ok(());
+            }
+            anyhow::bail!(
+                "messages {} and {} have the same id {:#010x}",
+                existing.name(),
+                M::NAME,
+                M::ID
+            );
+        }
+        self.queues
+            .insert(M::ID, Box::new(Events::<Received<M>>::default()));
+        Ok(())
+    }
+
+    /// Decodes a message from `sender` into its event queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message is unregistered or malformed; the
+    /// sender may be running a different game version, or be hostile.
+    pub fn dispatch(&mut self, sender: PlayerId, bytes: &[u8]) -> anyhow::Result<()> {
+        let id = peek_id(bytes)
+            .ok_or_else(|| anyhow::anyhow!("message of {} bytes has no id", bytes.len()))?;
+        let queue = self
+            .queues
+            .get_mut(&id)
+            .ok_or_else(|| anyhow::anyhow!("unregistered message {id:#010x} from {sender}"))?;
+        queue.push(sender, &bytes[ID_BYTES..])
+    }
+
+    /// Returns the event queue of a registered message type.
+    pub fn events<M: NetMessage>(&mut self) -> Option<&mut Events<Received<M>>> {
+        self.queues
+            .get_mut(&M::ID)?
+            .as_any_mut()
+            .downcast_mut::<Events<Received<M>>>()
+    }
+
+    /// Calls the handler of every received `R`, returning how many ran.
+    pub fn invoke<R: ServerRpc>(&mut self, context: &mut R::Context) -> usize {
+        let Some(events) = self.events::<R>() else {
+            return 0;
+        };
+        let mut count = 0;
+        for Received { sender, message } in events.drain() {
+            message.invoke(context, sender);
+            count += 1;
+        }
+        count
+    }
+
+    /// Advances every event queue by one frame.
+    pub fn update(&mut self) {
+        for queue in self.queues.values_mut() {
+            queue.update();
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Unit tests for typed network messages and server RPCs.
+
+use crate::message::{
+    self, message_id, server_rpc, Channel, MessageDispatcher, NetMessage, Received,
+};
+use crate::session::PlayerId;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, NetMessage)]
+#[net(crate = "crate")]
+struct Chat {
+    text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, NetMessage)]
+#[net(crate = "crate", channel = "unreliable", name = "player_moved")]
+struct PlayerMoved {
+    position: [f32; 3],
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, NetMessage)]
+#[net(crate = "crate")]
+enum Emote {
+    Wave,
+    Dance { seconds: u8 },
+}
+
+/// Server state the test RPC works on.
+#[derive(Debug, Default)]
+struct Arena {
+    shots: Vec<(PlayerId, u64, f32)>,
+}
+
+#[server_rpc(crate = "crate")]
+fn fire_weapon(arena: &mut Arena, sender: PlayerId, target: u64, power: f32) {
+    arena.shots.push((sender, target, power));
+}
+
+/// Verifies the names, ids, and channels the derive picks.
+#[test]
+fn derive_names_messages() {
+    assert_eq!(Chat::NAME, concat!(module_path!(), "::Chat"));
+    assert_eq!(Chat::CHANNEL, Channel::Reliable);
+    assert_eq!(PlayerMoved::NAME, "player_moved");
+    assert_eq!(PlayerMoved::ID, message_id("player_moved"));
+    assert_eq!(PlayerMoved::CHANNEL, Channel::Unreliable);
+    assert_ne!(Chat::ID, Emote::ID);
+}
+
+/// Verifies that messages round-trip and are checked on decode.
+#[test]
+fn encodes_and_decodes() {
+    let bytes = message::encode(&Emote::Dance { seconds: 3 }).unwrap();
+    assert_eq!(message::peek_id(&bytes), Some(Emote::ID));
+    assert_eq!(
+        message::decode::<Emote>(&bytes).unwrap(),
+        Emote::Dance { seconds: 3 }
+    );
+
+    assert!(message::decode::<Chat>(&bytes).is_err());
+    assert!(message::decode::<Emote>(&bytes[..2]).is_err());
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert!(message::decode::<Emote>(&trailing).is_err());
+}
+
+/// Verifies that dispatched messages land in their event queues.
+#[test]
+fn dispatches_into_events() {
+    let mut dispatcher = MessageDispatcher::default();
+    dispatcher.register::<Chat>().unwrap();
+    dispatcher.register::<Emote>().unwrap();
+
+    let chat = Chat {
+        text: "hi".to_owned(),
+    };
+    dispatcher
+        .dispatch(PlayerId(1), &message::encode(&chat).unwrap())
+        .unwrap();
+    dispatcher
+        .dispatch(PlayerId(2), &message::encode(&Emote::Wave).unwrap())
+        .unwrap();
+
+    let chats: Vec<_> = dispatcher
+        .events::<Chat>()
+        .unwrap()
+        .iter()
+        .cloned()
+        .collect();
+    assert_eq!(
+        chats,
+        [Received {
+            sender: PlayerId(1),
+            message: chat
+        }]
+    );
+    assert_eq!(dispatcher.events::<Emote>().unwrap().len(), 1);
+    assert!(dispatcher.events::<PlayerMoved>().is_none());
+
+    dispatcher.update();
+    dispatcher.update();
+    assert!(dispatcher.events::<Chat>().unwrap().is_empty());
+}
+
+/// Verifies that unregistered and malformed messages are rejected.
+#[test]
+fn rejects_unknown_messages() {
+    let mut dispatcher = MessageDispatcher::default();
+    dispatcher.register::<Chat>().unwrap();
+
+    let moved = message::encode(&PlayerMoved { position: [0.0; 3] }).unwrap();
+    assert!(dispatcher.dispatch(PlayerId(1), &moved).is_err());
+    assert!(dispatcher.dispatch(PlayerId(1), &[1, 2]).is_err());
+    let mut corrupt = message::encode(&Chat { text: "x".into() }).unwrap();
+    corrupt.truncate(corrupt.len() - 1);
+    assert!(dispatcher.dispatch(PlayerId(1), &corrupt).is_err());
+    assert!(dispatcher.register::<Chat>().is_ok());
+}
+
+/// Verifies that server RPCs call their handler with the sender and
+/// arguments.
+#[test]
+fn invokes_server_rpcs() {
+    let mut dispatcher = MessageDispatcher::default();
+    dispatcher.register::<FireWeapon>().unwrap();
+    let call = FireWeapon {
+        target: 9,
+        power: 0.5,
+    };
+
+    dispatcher
+        .dispatch(PlayerId(4), &message::encode(&call).unwrap())
+        .unwrap();
+    let mut arena = Arena::default();
+
+    assert_eq!(dispatcher.invoke::<FireWeapon>(&mut arena), 1);
+    assert_eq!(arena.shots, [(PlayerId(4), 9, 0.5)]);
+    assert_eq!(dispatcher.invoke::<FireWeapon>(&mut arena), 0);
+    assert_eq!(FireWeapon::CHANNEL, Channel::Reliable);
+}
@@ -0,0 +1,109 @@
+//! Networking subsystem implementation.
+//!
+//! Provides the [`RustgineNet`] system, which owns the network tick rate and
+//! the cvars controlling simulated network conditions.
+
+use crate::conditioner::{ConditionCVars, NetworkConditions};
+use rustgine_core::{CVars, RustgineError, RustgineSystem};
+use tracing::info;
+
+/// Default network simulation ticks per second.
+pub const DEFAULT_TICK_RATE: u32 = 60;
+
+/// Networking subsystem for the Rustgine engine.
+///
+/// Manages:
+/// - The simulation tick shared by clients and server
+/// - Client-side prediction of locally controlled entities
+/// - Simulated network conditions, toggled through `net.sim.*` cvars
+///
+/// # Example
+///
+/// ```ignore
+/// use net::RustgineNet;
+/// use rustgine_core::RustgineSystem;
+///
+/// let mut net = RustgineNet::default();
+/// net.startup()?;
+/// ```
+#[derive(Debug)]
+pub struct RustgineNet {
+    /// Simulation ticks per second.
+    tick_rate: u32,
+    /// Registry the condition cvars are registered in at startup.
+    cvars: Option<CVars>,
+    /// Condition cvars, once registered.
+    conditions: Option<ConditionCVars>,
+}
+
+impl Default for RustgineNet {
+    fn default() -> Self {
+        Self {
+            tick_rate: DEFAULT_TICK_RATE,
+            cvars: None,
+            conditions: None,
+        }
+    }
+}
+
+impl RustgineNet {
+    /// Sets how many simulation ticks run per second.
+    #[must_use]
+    pub fn with_tick_rate(mut self, tick_rate: u32) -> Self {
+        self.tick_rate = tick_rate.max(1);
+        self
+    }
+
+    /// Registers the `net.sim.*` cvars in `cvars` at startup.
+    #[must_use]
+    pub fn with_cvars(mut self, cvars: CVars) -> Self {
+        self.cvars = Some(cvars);
+        self
+    }
+
+    /// Returns how many simulation ticks run per second.
+    #[must_use]
+    #[inline]
+    pub fn tick_rate(&self) -> u32 {
+        self.tick_rate
+    }
+
+    /// Returns the condition cvars, once registered at startup.
+    #[must_use]
+    pub fn condition_cvars(&self) -> Option<&ConditionCVars> {
+        self.conditions.as_ref()
+    }
+
+    /// Returns the simulated network conditions currently configured.
+    #[must_use]
+    pub fn conditions(&self) -> NetworkConditions {
+        self.conditions
+            .as_ref()
+            .map_or(NetworkConditions::IDEAL, ConditionCVars::conditions)
+    }
+}
+
+impl RustgineSystem for RustgineNet {
+    /// Initializes the networking subsystem.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the condition cvars cannot be registered.
+    fn startup(&mut self) -> Result<(), RustgineError> {
+        if let Some(cvars) = &self.cvars {
+            self.conditions = Some(ConditionCVars::register(cvars)?);
+        }
+        info!(tick_rate = self.tick_rate, "net started");
+        Ok(())
+    }
+
+    /// Shuts down the networking subsystem.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if cleanup fails.
+    #[inline]
+    fn shutdown(&mut self) -> Result<(), RustgineError> {
+        Ok(())
+    }
+}
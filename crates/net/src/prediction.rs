@@ -0,0 +1,328 @@
+//! Client-side prediction and server reconciliation.
+//!
+//! Waiting a round trip for the server before moving the local player makes
+//! every game feel sluggish. Instead the client simulates its own player
+//! immediately with a [`Predictor`], sends each input to the server, and
+//! keeps the inputs the server has not confirmed yet.
+//!
+//! Every [`ServerSnapshot`] names the last input tick the server applied.
+//! The predictor drops the confirmed inputs and compares its own prediction
+//! for that tick with the authoritative state. If they diverge by more than
+//! the tolerance, it rewinds to the server's state and replays the
+//! unconfirmed inputs on top of it. [`PredictionStats`] and, optionally,
+//! [`PredictionMetrics`] report how often and how badly predictions miss,
+//! which is what tuning movement code for prediction is about.
+
+use rustgine_core::metrics::{Counter, Histogram};
+use rustgine_core::Metrics;
+use std::collections::VecDeque;
+use std::time::Duration;
+use tracing::warn;
+
+/// Default number of unconfirmed inputs kept: two seconds at 60 Hz.
+pub const DEFAULT_INPUT_HISTORY: usize = 120;
+
+/// Default divergence tolerated before a correction.
+pub const DEFAULT_TOLERANCE: f32 = 0.01;
+
+/// Histogram buckets for misprediction errors, in world units.
+const ERROR_BUCKETS: [f64; 6] = [0.01, 0.05, 0.1, 0.5, 1.0, 5.0];
+
+/// State a client can simulate ahead of the server.
+pub trait Predicted: Clone {
+    /// Player input applied once per tick.
+    type Input: Clone;
+
+    /// Advances the state by one tick of `step` with `input`.
+    ///
+    /// Must match the server's simulation for predictions to hold.
+    fn simulate(&mut self, input: &Self::Input, step: Duration);
+
+    /// Returns how far this predicted state is from the authoritative one,
+    /// in the state's own units, such as distance.
+    fn divergence(&self, authoritative: &Self) -> f32;
+}
+
+/// An input stamped with the tick it was applied at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputRecord<I> {
+    /// Tick the input was applied at.
+    pub tick: u32,
+    /// The input.
+    pub input: I,
+}
+
+/// Authoritative state received from the server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerSnapshot<S> {
+    /// Last input tick the server applied to produce `state`.
+    pub tick: u32,
+    /// The authoritative state.
+    pub state: S,
+}
+
+/// Running totals of prediction quality.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PredictionStats {
+    /// Snapshots reconciled.
+    pub reconciliations: u64,
+    /// Snapshots that diverged beyond the tolerance.
+    pub mispredictions: u64,
+    /// Inputs replayed after corrections.
+    pub replayed_inputs: u64,
+    /// Inputs dropped because the history was full.
+    pub dropped_inputs: u64,
+    /// Divergence found by the last reconciliation.
+    pub last_error: f32,
+    /// Largest divergence found.
+    pub max_error: f32,
+}
+
+impl PredictionStats {
+    /// Returns the fraction of reconciliations that needed a correction.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn misprediction_rate(&self) -> f32 {
+        if self.reconciliations == 0 {
+            0.0
+        } else {
+            self.mispredictions as f32 / self.reconciliations as f32
+        }
+    }
+}
+
+/// Prediction metrics exported through the engine's [`Metrics`] registry.
+#[derive(Debug, Clone)]
+pub struct PredictionMetrics {
+    /// Snapshots reconciled.
+    reconciliations: Counter,
+    /// Snapshots that needed a correction.
+    mispredictions: Counter,
+    /// Inputs replayed after corrections.
+    replayed_inputs: Counter,
+    /// Divergence of each reconciled snapshot.
+    error: Histogram,
+}
+
+impl PredictionMetrics {
+    /// Registers the prediction metrics.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a metric name is registered as another type.
+    pub fn register(metrics: &Metrics) -> anyhow::Result<Self> {
+        Ok(Self {
+            reconciliations: metrics.counter(
+                "rustgine_net_reconciliations_total",
+                "Server snapshots reconciled against predictions",
+            )?,
+            mispredictions: metrics.counter(
+                "rustgine_net_mispredictions_total",
+                "Server snapshots that diverged from predictions",
+            )?,
+            replayed_inputs: metrics.counter(
+                "rustgine_net_replayed_inputs_total",
+                "Inputs replayed after prediction corrections",
+            )?,
+            error: metrics.histogram(
+                "rustgine_net_prediction_error",
+                "Divergence between predicted and authoritative state",
+                &ERROR_BUCKETS,
+            )?,
+        })
+    }
+}
+
+/// A locally controlled entity simulated ahead of the server.
+///
+/// # Example
+///
+/// ```
+/// use net::prediction::{Predicted, Predictor, ServerSnapshot};
+/// use std::time::Duration;
+///
+/// #[derive(Clone)]
+/// struct Position(f32);
+///
+/// impl Predicted for Position {
+///     type Input = f32;
+///
+///     fn simulate(&mut self, speed: &f32, step: Duration) {
+///         self.0 += speed * step.as_secs_f32();
+///     }
+///
+///     fn divergence(&self, authoritative: &Self) -> f32 {
+///         (self.0 - authoritative.0).abs()
+///     }
+/// }
+///
+/// let mut player = Predictor::new(Position(0.0), Duration::from_millis(100));
+/// let first = player.predict(10.0);
+/// player.predict(10.0);
+/// assert_eq!(player.state().0, 2.0);
+///
+/// // The server was pushed back and only moved half as far.
+/// let corrected = player.reconcile(ServerSnapshot { tick: first.tick, state: Position(0.5) });
+/// assert!(corrected);
+/// assert_eq!(player.state().0, 1.5);
+/// ```
+#[derive(Debug)]
+pub struct Predictor<S: Predicted> {
+    /// Predicted current state.
+    state: S,
+    /// Length of one tick.
+    step: Duration,
+    /// Tick the next input is applied at.
+    next_tick: u32,
+    /// Last tick confirmed by the server.
+    acknowledged: Option<u32>,
+    /// Unconfirmed inputs with the state predicted after each.
+    history: VecDeque<(InputRecord<S::Input>, S)>,
+    /// Unconfirmed inputs kept at most.
+    capacity: usize,
+    /// Divergence tolerated before a correction.
+    tolerance: f32,
+    /// Running totals.
+    stats: PredictionStats,
+    /// Exported metrics, if registered.
+    metrics: Option<PredictionMetrics>,
+}
+
+impl<S: Predicted> Predictor<S> {
+    /// Creates a predictor starting from `state`, ticking every `step`.
+    #[must_use]
+    pub fn new(state: S, step: Duration) -> Self {
+        Self {
+            state,
+            step,
+            next_tick: 0,
+            acknowledged: None,
+            history: VecDeque::new(),
+            capacity: DEFAULT_INPUT_HISTORY,
+            tolerance: DEFAULT_TOLERANCE,
+            stats: PredictionStats::default(),
+            metrics: None,
+        }
+    }
+
+    /// Sets how many unconfirmed inputs are kept.
+    #[must_use]
+    pub fn with_history(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Sets the divergence tolerated before a correction.
+    #[must_use]
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance.max(0.0);
+        self
+    }
+
+    /// Exports prediction quality through `metrics`.
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: PredictionMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Returns the predicted current state.
+    #[must_use]
+    #[inline]
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Returns the tick the next input is applied at.
+    #[must_use]
+    #[inline]
+    pub fn next_tick(&self) -> u32 {
+        self.next_tick
+    }
+
+    /// Returns the number of inputs the server has not confirmed yet.
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Returns the running totals of prediction quality.
+    #[must_use]
+    #[inline]
+    pub fn stats(&self) -> &PredictionStats {
+        &self.stats
+    }
+
+    /// Applies `input` immediately and returns it stamped with its tick,
+    /// ready to send to the server.
+    pub fn predict(&mut self, input: S::Input) -> InputRecord<S::Input> {
+        self.state.simulate(&input, self.step);
+        let record = InputRecord {
+            tick: self.next_tick,
+            input,
+        };
+        self.next_tick = self.next_tick.wrapping_add(1);
+        if self.history.len() == self.capacity {
+            // The server stopped confirming; keep the newest inputs.
+            self.history.pop_front();
+            self.stats.dropped_inputs += 1;
+            warn!(capacity = self.capacity, "prediction input history full");
+        }
+        self.history.push_back((record.clone(), self.state.clone()));
+        record
+    }
+
+    /// Reconciles the prediction with an authoritative snapshot, rewinding
+    /// and replaying unconfirmed inputs if they diverge.
+    ///
+    /// Returns `true` if the predicted state was corrected. Snapshots older
+    /// than one already reconciled are ignored.
+    pub fn reconcile(&mut self, snapshot: ServerSnapshot<S>) -> bool {
+        if self
+            .acknowledged
+            .is_some_and(|acknowledged| snapshot.tick <= acknowledged)
+        {
+            return false;
+        }
+        self.acknowledged = Some(snapshot.tick);
+
+        let mut predicted = None;
+        while let Some((record, _)) = self.history.front() {
+            if record.tick > snapshot.tick {
+                break;
+            }
+            predicted = self.history.pop_front().map(|(_, state)| state);
+        }
+        // Without a prediction for the tick, such as after dropped history,
+        // the server's state is taken as a correction.
+        let error = predicted.map_or(f32::INFINITY, |predicted| {
+            predicted.divergence(&snapshot.state)
+        });
+
+        self.stats.reconciliations += 1;
+        let finite_error = if error.is_finite() { error } else { 0.0 };
+        self.stats.last_error = finite_error;
+        self.stats.max_error = self.stats.max_error.max(finite_error);
+        if let Some(metrics) = &self.metrics {
+            metrics.reconciliations.inc();
+            metrics.error.observe(f64::from(finite_error));
+        }
+        if error <= self.tolerance {
+            return false;
+        }
+
+        self.state = snapshot.state;
+        for (record, predicted) in &mut self.history {
+            self.state.simulate(&record.input, self.step);
+            predicted.clone_from(&self.state);
+        }
+        let replayed = self.history.len() as u64;
+        self.stats.mispredictions += 1;
+        self.stats.replayed_inputs += replayed;
+        if let Some(metrics) = &self.metrics {
+            metrics.mispredictions.inc();
+            metrics.replayed_inputs.add(replayed);
+        }
+        true
+    }
+}
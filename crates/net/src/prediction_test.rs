@@ -0,0 +1,172 @@
+//! Unit tests for client-side prediction.
+
+use crate::prediction::{Predicted, PredictionMetrics, Predictor, ServerSnapshot};
+use rustgine_core::Metrics;
+use std::time::Duration;
+
+const STEP: Duration = Duration::from_millis(100);
+
+/// A player moving along one axis at the input speed.
+#[derive(Debug, Clone, PartialEq)]
+struct Player {
+    x: f32,
+}
+
+impl Predicted for Player {
+    type Input = f32;
+
+    fn simulate(&mut self, speed: &f32, step: Duration) {
+        self.x += speed * step.as_secs_f32();
+    }
+
+    fn divergence(&self, authoritative: &Self) -> f32 {
+        (self.x - authoritative.x).abs()
+    }
+}
+
+fn assert_near(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+}
+
+/// Verifies that inputs apply immediately and are stamped with ticks.
+#[test]
+fn predicts_inputs_immediately() {
+    let mut predictor = Predictor::new(Player { x: 0.0 }, STEP);
+
+    let first = predictor.predict(10.0);
+    let second = predictor.predict(20.0);
+
+    assert_eq!((first.tick, second.tick), (0, 1));
+    assert_near(predictor.state().x, 3.0);
+    assert_eq!(predictor.pending(), 2);
+    assert_eq!(predictor.next_tick(), 2);
+}
+
+/// Verifies that a matching snapshot drops confirmed inputs without a correction.
+#[test]
+fn confirms_correct_predictions() {
+    let mut predictor = Predictor::new(Player { x: 0.0 }, STEP);
+    for _ in 0..3 {
+        predictor.predict(10.0);
+    }
+
+    let corrected = predictor.reconcile(ServerSnapshot {
+        tick: 1,
+        state: Player { x: 2.0 },
+    });
+
+    assert!(!corrected);
+    assert_eq!(predictor.pending(), 1);
+    assert_near(predictor.state().x, 3.0);
+    assert_eq!(predictor.stats().reconciliations, 1);
+    assert_eq!(predictor.stats().mispredictions, 0);
+}
+
+/// Verifies that a diverging snapshot rewinds and replays unconfirmed inputs.
+#[test]
+fn replays_inputs_after_misprediction() {
+    let mut predictor = Predictor::new(Player { x: 0.0 }, STEP);
+    for speed in [10.0, 10.0, 20.0, 30.0] {
+        predictor.predict(speed);
+    }
+
+    // The server blocked the first move.
+    let corrected = predictor.reconcile(ServerSnapshot {
+        tick: 0,
+        state: Player { x: 0.0 },
+    });
+
+    assert!(corrected);
+    assert_near(predictor.state().x, 6.0);
+    let stats = predictor.stats();
+    assert_eq!(stats.mispredictions, 1);
+    assert_eq!(stats.replayed_inputs, 3);
+    assert_near(stats.last_error, 1.0);
+
+    // Replayed predictions are what later snapshots are compared against.
+    let corrected = predictor.reconcile(ServerSnapshot {
+        tick: 2,
+        state: Player { x: 3.0 },
+    });
+    assert!(!corrected);
+    assert_near(predictor.stats().misprediction_rate(), 0.5);
+}
+
+/// Verifies that divergence within the tolerance is not corrected.
+#[test]
+fn tolerates_small_divergence() {
+    let mut predictor = Predictor::new(Player { x: 0.0 }, STEP).with_tolerance(0.5);
+    predictor.predict(10.0);
+
+    let corrected = predictor.reconcile(ServerSnapshot {
+        tick: 0,
+        state: Player { x: 1.25 },
+    });
+
+    assert!(!corrected);
+    assert_near(predictor.state().x, 1.0);
+    assert_near(predictor.stats().max_error, 0.25);
+}
+
+/// Verifies that snapshots arriving out of order are ignored.
+#[test]
+fn ignores_stale_snapshots() {
+    let mut predictor = Predictor::new(Player { x: 0.0 }, STEP);
+    for _ in 0..3 {
+        predictor.predict(10.0);
+    }
+    predictor.reconcile(ServerSnapshot {
+        tick: 1,
+        state: Player { x: 2.0 },
+    });
+
+    let corrected = predictor.reconcile(ServerSnapshot {
+        tick: 0,
+        state: Player { x: -50.0 },
+    });
+
+    assert!(!corrected);
+    assert_near(predictor.state().x, 3.0);
+    assert_eq!(predictor.stats().reconciliations, 1);
+}
+
+/// Verifies that a full history drops the oldest inputs and that the
+/// server's state then replaces the missing prediction.
+#[test]
+fn bounds_input_history() {
+    let mut predictor = Predictor::new(Player { x: 0.0 }, STEP).with_history(2);
+    for _ in 0..4 {
+        predictor.predict(10.0);
+    }
+    assert_eq!(predictor.pending(), 2);
+    assert_eq!(predictor.stats().dropped_inputs, 2);
+
+    let corrected = predictor.reconcile(ServerSnapshot {
+        tick: 1,
+        state: Player { x: 2.0 },
+    });
+
+    assert!(corrected);
+    assert_near(predictor.state().x, 4.0);
+}
+
+/// Verifies that reconciliations are exported as metrics.
+#[test]
+fn exports_metrics() {
+    let metrics = Metrics::new();
+    let mut predictor = Predictor::new(Player { x: 0.0 }, STEP)
+        .with_metrics(PredictionMetrics::register(&metrics).unwrap());
+    predictor.predict(10.0);
+    predictor.predict(10.0);
+
+    predictor.reconcile(ServerSnapshot {
+        tick: 0,
+        state: Player { x: 0.0 },
+    });
+
+    let text = metrics.render();
+    assert!(text.contains("rustgine_net_reconciliations_total 1"));
+    assert!(text.contains("rustgine_net_mispredictions_total 1"));
+    assert!(text.contains("rustgine_net_replayed_inputs_total 1"));
+    assert!(text.contains("rustgine_net_prediction_error_count 1"));
+}
@@ -0,0 +1,162 @@
+//! Reliable, ordered delivery over an unreliable transport.
+//!
+//! A [`ReliableChannel`] numbers every message it sends and resends it until
+//! the other end acknowledges it. The receiving end holds back messages that
+//! overtook an earlier one and delivers them once the gap is filled, so each
+//! message arrives exactly once and in the order it was sent.
+//!
+//! The channel does no I/O: the transport sends whatever
+//! [`ReliableChannel::poll_transmit`] returns and feeds every [`Packet`] it
+//! receives to [`ReliableChannel::receive`].
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// Default time before an unacknowledged message is sent again.
+pub const DEFAULT_RESEND_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A unit of data exchanged between the two ends of a channel.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Packet<T> {
+    /// A numbered message.
+    Message {
+        /// Position of the message in the sender's order.
+        seq: u32,
+        /// The message.
+        payload: T,
+    },
+    /// Acknowledges every message numbered below `next`.
+    Ack {
+        /// Number of the first message not yet received in order.
+        next: u32,
+    },
+}
+
+/// One end of a reliable, ordered message stream.
+///
+/// Sequence numbers do not wrap: at 60 messages per second a channel lasts
+/// over two years.
+///
+/// # Example
+///
+/// ```
+/// use net::reliable::ReliableChannel;
+/// use std::time::Instant;
+///
+/// let now = Instant::now();
+/// let mut client = ReliableChannel::default();
+/// let mut server = ReliableChannel::default();
+/// client.send("hello");
+/// client.send("world");
+///
+/// // The first packet is lost, so the second is held back.
+/// let mut packets = client.poll_transmit(now);
+/// let second = packets.pop().unwrap();
+/// assert!(server.receive(second).is_empty());
+///
+/// // The resent first packet releases both in order.
+/// let resent = client.poll_transmit(now + client.resend_interval());
+/// assert_eq!(server.receive(resent[0].clone()), ["hello", "world"]);
+/// ```
+#[derive(Debug)]
+pub struct ReliableChannel<T> {
+    /// Number of the next message sent.
+    next_send: u32,
+    /// Sent messages awaiting acknowledgement, with when each was last sent.
+    unacked: BTreeMap<u32, (T, Option<Instant>)>,
+    /// Number of the next message delivered.
+    next_receive: u32,
+    /// Received messages held back until earlier ones arrive.
+    held: BTreeMap<u32, T>,
+    /// Whether an acknowledgement is owed to the other end.
+    ack_pending: bool,
+    /// Time before an unacknowledged message is sent again.
+    resend_interval: Duration,
+}
+
+impl<T> Default for ReliableChannel<T> {
+    fn default() -> Self {
+        Self {
+            next_send: 0,
+            unacked: BTreeMap::new(),
+            next_receive: 0,
+            held: BTreeMap::new(),
+            ack_pending: false,
+            resend_interval: DEFAULT_RESEND_INTERVAL,
+        }
+    }
+}
+
+impl<T: Clone> ReliableChannel<T> {
+    /// Sets the time before an unacknowledged message is sent again,
+    /// usually a little over the round-trip time.
+    #[must_use]
+    pub fn with_resend_interval(mut self, interval: Duration) -> Self {
+        self.resend_interval = interval;
+        self
+    }
+
+    /// Returns the time before an unacknowledged message is sent again.
+    #[must_use]
+    #[inline]
+    pub fn resend_interval(&self) -> Duration {
+        self.resend_interval
+    }
+
+    /// Returns the number of sent messages not yet acknowledged.
+    #[must_use]
+    pub fn unacked(&self) -> usize {
+        self.unacked.len()
+    }
+
+    /// Queues a message for delivery.
+    pub fn send(&mut self, payload: T) {
+        self.unacked.insert(self.next_send, (payload, None));
+        self.next_send += 1;
+    }
+
+    /// Returns the packets to send at `now`: an acknowledgement if one is
+    /// owed, new messages, and messages due to be resent.
+    pub fn poll_transmit(&mut self, now: Instant) -> Vec<Packet<T>> {
+        let mut packets = Vec::new();
+        if std::mem::take(&mut self.ack_pending) {
+            packets.push(Packet::Ack {
+                next: self.next_receive,
+            });
+        }
+        for (&seq, (payload, sent)) in &mut self.unacked {
+            if sent.is_none_or(|sent| now.saturating_duration_since(sent) >= self.resend_interval) {
+                *sent = Some(now);
+                packets.push(Packet::Message {
+                    seq,
+                    payload: payload.clone(),
+                });
+            }
+        }
+        packets
+    }
+
+    /// Handles a packet from the other end, returning the messages it makes
+    /// deliverable, in order.
+    pub fn receive(&mut self, packet: Packet<T>) -> Vec<T> {
+        match packet {
+            Packet::Ack { next } => {
+                self.unacked = self.unacked.split_off(&next);
+                Vec::new()
+            }
+            Packet::Message { seq, payload } => {
+                // Duplicates are acknowledged again in case the ack was lost.
+                self.ack_pending = true;
+                if seq >= self.next_receive {
+                    self.held.entry(seq).or_insert(payload);
+                }
+                let mut delivered = Vec::new();
+                while let Some(payload) = self.held.remove(&self.next_receive) {
+                    delivered.push(payload);
+                    self.next_receive += 1;
+                }
+                delivered
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! Unit tests for reliable, ordered channels.
+
+use crate::reliable::{Packet, ReliableChannel};
+use std::time::{Duration, Instant};
+
+/// Verifies that messages arrive in order despite reordering.
+#[test]
+fn delivers_in_order() {
+    let now = Instant::now();
+    let mut sender = ReliableChannel::default();
+    let mut receiver = ReliableChannel::default();
+    for n in 0..4 {
+        sender.send(n);
+    }
+
+    let mut delivered = Vec::new();
+    for packet in sender.poll_transmit(now).into_iter().rev() {
+        delivered.extend(receiver.receive(packet));
+    }
+
+    assert_eq!(delivered, [0, 1, 2, 3]);
+}
+
+/// Verifies that duplicates are delivered once.
+#[test]
+fn drops_duplicates() {
+    let now = Instant::now();
+    let mut sender = ReliableChannel::default();
+    let mut receiver = ReliableChannel::default();
+    sender.send("once");
+
+    let packet = sender.poll_transmit(now).remove(0);
+    assert_eq!(receiver.receive(packet.clone()), ["once"]);
+    assert!(receiver.receive(packet).is_empty());
+}
+
+/// Verifies that unacknowledged messages are resent after the interval and
+/// that acknowledged ones are not.
+#[test]
+fn resends_until_acknowledged() {
+    let now = Instant::now();
+    let interval = Duration::from_millis(50);
+    let mut sender = ReliableChannel::default().with_resend_interval(interval);
+    let mut receiver = ReliableChannel::default();
+    sender.send(7);
+
+    assert_eq!(sender.poll_transmit(now).len(), 1);
+    assert!(sender.poll_transmit(now + interval / 2).is_empty());
+    let resent = sender.poll_transmit(now + interval);
+    assert_eq!(resent.len(), 1);
+
+    receiver.receive(resent[0].clone());
+    let acks = receiver.poll_transmit(now);
+    assert_eq!(acks, [Packet::Ack { next: 1 }]);
+    sender.receive(acks[0].clone());
+
+    assert_eq!(sender.unacked(), 0);
+    assert!(sender.poll_transmit(now + interval * 4).is_empty());
+}
@@ -0,0 +1,785 @@
+//! Multiplayer sessions independent of any matchmaking provider.
+//!
+//! A [`Session`] tracks who is in a game: the host creates it, other players
+//! join through the host, and every member keeps the same player list with
+//! each player's metadata, such as a chosen character or team. Members
+//! exchange reliable, ordered messages relayed by the host.
+//!
+//! Sessions do no I/O. A lobby or matchmaking provider finds the host's
+//! address, the transport connects to it, and the session is driven with the
+//! packets the transport receives and the packets
+//! [`Session::poll_transmit`] returns. Peers are identified by whatever
+//! address type the transport uses.
+//!
+//! In listen-server games the host is also a player, and the session would
+//! end when they quit. With host migration enabled, every member instead
+//! picks the earliest-joined remaining player as the new host; the others
+//! reconnect to that player with [`Session::reconnect`], and players who do
+//! not rejoin within the timeout are dropped.
+
+use crate::reliable::{Packet, ReliableChannel};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Default number of players a session holds.
+pub const DEFAULT_MAX_PLAYERS: usize = 8;
+
+/// Default time players have to reconnect after a host migration.
+pub const DEFAULT_REJOIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Identifies a player within a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PlayerId(pub u32);
+
+impl fmt::Display for PlayerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "player {}", self.0)
+    }
+}
+
+/// A member of a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    /// Identifier assigned by the host.
+    pub id: PlayerId,
+    /// Display name.
+    pub name: String,
+    /// Game-defined key-value data, such as a team or character choice.
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Settings the host creates a session with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionConfig {
+    /// Most players the session holds, the host included.
+    max_players: usize,
+    /// Whether a new host is chosen when the host leaves.
+    host_migration: bool,
+    /// Time players have to reconnect after a host migration.
+    rejoin_timeout: Duration,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            max_players: DEFAULT_MAX_PLAYERS,
+            host_migration: false,
+            rejoin_timeout: DEFAULT_REJOIN_TIMEOUT,
+        }
+    }
+}
+
+impl SessionConfig {
+    /// Sets the most players the session holds, the host included.
+    #[must_use]
+    pub fn with_max_players(mut self, max_players: usize) -> Self {
+        self.max_players = max_players.max(1);
+        self
+    }
+
+    /// Enables choosing a new host when the host leaves.
+    #[must_use]
+    pub fn with_host_migration(mut self, enabled: bool) -> Self {
+        self.host_migration = enabled;
+        self
+    }
+
+    /// Sets the time players have to reconnect after a host migration.
+    #[must_use]
+    pub fn with_rejoin_timeout(mut self, timeout: Duration) -> Self {
+        self.rejoin_timeout = timeout;
+        self
+    }
+
+    /// Returns the most players the session holds.
+    #[must_use]
+    #[inline]
+    pub fn max_players(&self) -> usize {
+        self.max_players
+    }
+
+    /// Returns whether a new host is chosen when the host leaves.
+    #[must_use]
+    #[inline]
+    pub fn host_migration(&self) -> bool {
+        self.host_migration
+    }
+}
+
+/// Messages exchanged by session members.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionMessage {
+    /// Asks the host to join.
+    Join {
+        /// Display name.
+        name: String,
+        /// Initial metadata.
+        metadata: BTreeMap<String, String>,
+    },
+    /// Asks the new host to keep an existing player after a migration.
+    Rejoin {
+        /// The player rejoining.
+        player: PlayerId,
+    },
+    /// Admits a player, with the session as the host sees it.
+    Accepted {
+        /// The admitted player.
+        player: PlayerId,
+        /// The host.
+        host: PlayerId,
+        /// Every player, in join order.
+        players: Vec<Player>,
+        /// Whether a new host is chosen when the host leaves.
+        host_migration: bool,
+    },
+    /// Refuses a join.
+    Rejected {
+        /// Why the join was refused.
+        reason: String,
+    },
+    /// Announces a new player.
+    PlayerJoined(Player),
+    /// Announces a player leaving.
+    PlayerLeft(PlayerId),
+    /// Replaces a player's metadata.
+    Metadata {
+        /// The player whose metadata changed.
+        player: PlayerId,
+        /// The new metadata.
+        metadata: BTreeMap<String, String>,
+    },
+    /// Game data from one player.
+    Data {
+        /// The sender.
+        from: PlayerId,
+        /// The recipient, or `None` for every other player.
+        to: Option<PlayerId>,
+        /// The data.
+        payload: Vec<u8>,
+    },
+    /// Announces that the sender is leaving.
+    Leave,
+}
+
+/// Something that happened in a session.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionEvent {
+    /// The local player was admitted.
+    Joined(PlayerId),
+    /// A player joined.
+    PlayerJoined(PlayerId),
+    /// A player left.
+    PlayerLeft(PlayerId),
+    /// A player's metadata changed.
+    MetadataChanged(PlayerId),
+    /// A new host took over.
+    HostMigrated(PlayerId),
+    /// Game data arrived.
+    Message {
+        /// The sender.
+        from: PlayerId,
+        /// The data.
+        payload: Vec<u8>,
+    },
+    /// The session ended for the local player.
+    Ended {
+        /// Why the session ended.
+        reason: String,
+    },
+}
+
+/// What the local player is doing in the session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Role<P> {
+    /// Hosting the session.
+    Host,
+    /// Waiting for the host to admit the local player.
+    Joining(P),
+    /// A member connected to the host.
+    Client(P),
+    /// Waiting to reconnect to a new host.
+    Migrating,
+    /// No longer in the session.
+    Ended,
+}
+
+/// A connection to another session member.
+#[derive(Debug)]
+struct Peer {
+    /// The player at the other end, once known.
+    player: Option<PlayerId>,
+    /// Messages to and from the peer.
+    channel: ReliableChannel<SessionMessage>,
+}
+
+impl Peer {
+    fn new(player: Option<PlayerId>) -> Self {
+        Self {
+            player,
+            channel: ReliableChannel::default(),
+        }
+    }
+}
+
+/// The local view of a multiplayer session.
+///
+/// # Example
+///
+/// ```
+/// use net::session::{Session, SessionConfig, SessionEvent};
+/// use std::collections::BTreeMap;
+/// use std::time::Instant;
+///
+/// let now = Instant::now();
+/// let mut host = Session::create(SessionConfig::default(), "ada", BTreeMap::new());
+/// let mut guest = Session::join("host-address", "bob", BTreeMap::new());
+///
+/// // A transport carries packets between the two, addressed by peer.
+/// for _ in 0..2 {
+///     for (_, packet) in guest.poll_transmit(now) {
+///         host.receive(&"guest-address", packet);
+///     }
+///     for (_, packet) in host.poll_transmit(now) {
+///         guest.receive(&"host-address", packet);
+///     }
+/// }
+///
+/// assert_eq!(host.players().len(), 2);
+/// assert!(guest.drain_events().any(|event| matches!(event, SessionEvent::Joined(_))));
+/// ```
+#[derive(Debug)]
+pub struct Session<P> {
+    /// Settings, as received from the host.
+    config: SessionConfig,
+    /// What the local player is doing.
+    role: Role<P>,
+    /// The local player, once admitted.
+    local: Option<PlayerId>,
+    /// The local player's name and metadata, sent when joining.
+    profile: (String, BTreeMap<String, String>),
+    /// The host, once known.
+    host: Option<PlayerId>,
+    /// Every player, in join order.
+    players: Vec<Player>,
+    /// Connections, by transport address.
+    peers: HashMap<P, Peer>,
+    /// Identifier of the next admitted player.
+    next_id: u32,
+    /// Players expected to reconnect after a migration.
+    awaiting_rejoin: HashSet<PlayerId>,
+    /// When players stop being waited for, once a migration starts.
+    rejoin_deadline: Option<Instant>,
+    /// Events not yet drained.
+    events: VecDeque<SessionEvent>,
+}
+
+impl<P: Clone + Eq + Hash + fmt::Debug> Session<P> {
+    /// Creates a session hosted by the local player.
+    #[must_use]
+    pub fn create(
+        config: SessionConfig,
+        name: impl Into<String>,
+        metadata: BTreeMap<String, String>,
+    ) -> Self {
+        let name = name.into();
+        let host = PlayerId(0);
+        let mut session = Self::new(config, Role::Host, name.clone(), metadata.clone());
+        session.local = Some(host);
+        session.host = Some(host);
+        session.next_id = 1;
+        session.players.push(Player {
+            id: host,
+            name,
+            metadata,
+        });
+        session
+    }
+
+    /// Starts joining the session hosted at `host`.
+    #[must_use]
+    pub fn join(host: P, name: impl Into<String>, metadata: BTreeMap<String, String>) -> Self {
+        let mut session = Self::new(
+            SessionConfig::default(),
+            Role::Joining(host.clone()),
+            name.into(),
+            metadata,
+        );
+        session.send_join(host);
+        session
+    }
+
+    fn new(
+        config: SessionConfig,
+        role: Role<P>,
+        name: String,
+        metadata: BTreeMap<String, String>,
+    ) -> Self {
+        Self {
+            config,
+            role,
+            local: None,
+            profile: (name, metadata),
+            host: None,
+            players: Vec::new(),
+            peers: HashMap::new(),
+            next_id: 0,
+            awaiting_rejoin: HashSet::new(),
+            rejoin_deadline: None,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Returns the session settings.
+    #[must_use]
+    #[inline]
+    pub fn config(&self) -> &SessionConfig {
+        &self.config
+    }
+
+    /// Returns the local player, once admitted.
+    #[must_use]
+    #[inline]
+    pub fn local_player(&self) -> Option<PlayerId> {
+        self.local
+    }
+
+    /// Returns the host, once known.
+    #[must_use]
+    #[inline]
+    pub fn host(&self) -> Option<PlayerId> {
+        self.host
+    }
+
+    /// Returns `true` if the local player hosts the session.
+    #[must_use]
+    pub fn is_host(&self) -> bool {
+        self.role == Role::Host
+    }
+
+    /// Returns `true` if the local player has left or been dropped.
+    #[must_use]
+    pub fn is_ended(&self) -> bool {
+        self.role == Role::Ended
+    }
+
+    /// Returns every player, in join order.
+    #[must_use]
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    /// Returns a player.
+    #[must_use]
+    pub fn player(&self, id: PlayerId) -> Option<&Player> {
+        self.players.iter().find(|player| player.id == id)
+    }
+
+    /// Drains the events that happened since the last call.
+    pub fn drain_events(&mut self) -> impl Iterator<Item = SessionEvent> + '_ {
+        self.events.drain(..)
+    }
+
+    /// Sets a metadata entry of the local player and shares it, once
+    /// admitted.
+    pub fn set_metadata(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.profile.1.insert(key.into(), value.into());
+        let Some(local) = self.local else {
+            return;
+        };
+        let metadata = self.profile.1.clone();
+        if let Some(player) = self.players.iter_mut().find(|player| player.id == local) {
+            player.metadata.clone_from(&metadata);
+        }
+        self.send_to_host_or_all(&SessionMessage::Metadata {
+            player: local,
+            metadata,
+        });
+    }
+
+    /// Sends game data to one player, or to every other player if `to` is
+    /// `None`. Data is delivered reliably and in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the local player is not in the session.
+    pub fn send(&mut self, to: Option<PlayerId>, payload: Vec<u8>) -> anyhow::Result<()> {
+        let Some(from) = self.local.filter(|_| !self.is_ended()) else {
+            anyhow::bail!("cannot send session data before joining");
+        };
+        let message = SessionMessage::Data { from, to, payload };
+        if self.is_host() {
+            self.relay(&message, from, to);
+        } else {
+            self.send_to_host_or_all(&message);
+        }
+        Ok(())
+    }
+
+    /// Leaves the session, telling the other members.
+    pub fn leave(&mut self) {
+        let peers: Vec<P> = self.peers.keys().cloned().collect();
+        for peer in peers {
+            self.send_to(&peer, SessionMessage::Leave);
+        }
+        self.end("left the session");
+    }
+
+    /// Connects to the new host after a migration. `host` is that player's
+    /// address, as found by the matchmaking provider.
+    pub fn reconnect(&mut self, host: P) {
+        if self.role != Role::Migrating {
+            return;
+        }
+        let Some(player) = self.local else {
+            return;
+        };
+        let mut peer = Peer::new(self.host);
+        peer.channel.send(SessionMessage::Rejoin { player });
+        self.peers.insert(host.clone(), peer);
+        self.role = Role::Joining(host);
+    }
+
+    /// Handles the loss of the connection to `peer`, as detected by the
+    /// transport.
+    pub fn disconnected(&mut self, peer: &P) {
+        let Some(state) = self.peers.remove(peer) else {
+            return;
+        };
+        match (&self.role, state.player) {
+            (Role::Host, Some(player)) => self.remove_player(player),
+            (Role::Client(host) | Role::Joining(host), _) if host == peer => self.host_lost(),
+            _ => {}
+        }
+    }
+
+    /// Drops players that did not reconnect in time after a migration.
+    pub fn update(&mut self, now: Instant) {
+        if self.awaiting_rejoin.is_empty() || self.role != Role::Host {
+            return;
+        }
+        let deadline = *self
+            .rejoin_deadline
+            .get_or_insert(now + self.config.rejoin_timeout);
+        if now >= deadline {
+            let mut missing: Vec<PlayerId> = self.awaiting_rejoin.drain().collect();
+            missing.sort_unstable();
+            for player in missing {
+                warn!(%player, "player did not rejoin after host migration");
+                self.remove_player(player);
+            }
+        }
+    }
+
+    /// Returns the packets to send at `now`, by peer address.
+    pub fn poll_transmit(&mut self, now: Instant) -> Vec<(P, Packet<SessionMessage>)> {
+        let mut packets = Vec::new();
+        for (address, peer) in &mut self.peers {
+            packets.extend(
+                peer.channel
+                    .poll_transmit(now)
+                    .into_iter()
+                    .map(|packet| (address.clone(), packet)),
+            );
+        }
+        packets
+    }
+
+    /// Handles a packet received from `peer`.
+    pub fn receive(&mut self, peer: &P, packet: Packet<SessionMessage>) {
+        if self.role == Role::Host && !self.peers.contains_key(peer) {
+            self.peers.insert(peer.clone(), Peer::new(None));
+        }
+        let Some(state) = self.peers.get_mut(peer) else {
+            return;
+        };
+        for message in state.channel.receive(packet) {
+            self.handle(peer, message);
+        }
+    }
+
+    fn handle(&mut self, peer: &P, message: SessionMessage) {
+        let sender = self.peers.get(peer).and_then(|state| state.player);
+        match (&self.role, message) {
+            (Role::Host, SessionMessage::Join { name, metadata }) if sender.is_none() => {
+                self.admit(peer, name, metadata);
+            }
+            (Role::Host, SessionMessage::Rejoin { player }) if sender.is_none() => {
+                self.readmit(peer, player);
+            }
+            (
+                Role::Joining(host),
+                SessionMessage::Accepted {
+                    player,
+                    host: host_player,
+                    players,
+                    host_migration,
+                },
+            ) if host == peer => {
+                let rejoined = self.local == Some(player);
+                self.role = Role::Client(peer.clone());
+                self.local = Some(player);
+                self.host = Some(host_player);
+                self.config.host_migration = host_migration;
+                if let Some(state) = self.peers.get_mut(peer) {
+                    state.player = Some(host_player);
+                }
+                if rejoined {
+                    self.sync_players(players);
+                } else {
+                    self.players = players;
+                    self.events.push_back(SessionEvent::Joined(player));
+                }
+                // Share metadata changed while the join was in flight.
+                if self.player(player).map(|entry| &entry.metadata) != Some(&self.profile.1) {
+                    let metadata = self.profile.1.clone();
+                    self.apply_metadata(player, metadata.clone());
+                    self.send_to_host_or_all(&SessionMessage::Metadata { player, metadata });
+                }
+            }
+            (Role::Joining(host), SessionMessage::Rejected { reason }) if host == peer => {
+                self.end(&reason);
+            }
+            (Role::Client(host), message) if host == peer => self.handle_from_host(message),
+            (Role::Host, SessionMessage::Metadata { player, metadata })
+                if sender == Some(player) =>
+            {
+                self.apply_metadata(player, metadata.clone());
+                self.broadcast(&SessionMessage::Metadata { player, metadata }, Some(player));
+            }
+            (Role::Host, SessionMessage::Data { from, to, payload }) if sender == Some(from) => {
+                self.relay(&SessionMessage::Data { from, to, payload }, from, to);
+            }
+            (Role::Host, SessionMessage::Leave) => {
+                self.peers.remove(peer);
+                if let Some(player) = sender {
+                    self.remove_player(player);
+                }
+            }
+            (_, message) => warn!(?peer, ?message, "unexpected session message"),
+        }
+    }
+
+    fn handle_from_host(&mut self, message: SessionMessage) {
+        match message {
+            SessionMessage::PlayerJoined(player) => {
+                let id = player.id;
+                if self.player(id).is_none() {
+                    self.players.push(player);
+                    self.events.push_back(SessionEvent::PlayerJoined(id));
+                }
+            }
+            SessionMessage::PlayerLeft(id) => {
+                if self.take_player(id) {
+                    self.events.push_back(SessionEvent::PlayerLeft(id));
+                }
+            }
+            SessionMessage::Metadata { player, metadata } => self.apply_metadata(player, metadata),
+            SessionMessage::Data { from, payload, .. } => {
+                self.events
+                    .push_back(SessionEvent::Message { from, payload });
+            }
+            SessionMessage::Leave => self.host_lost(),
+            message => warn!(?message, "unexpected session message from host"),
+        }
+    }
+
+    fn admit(&mut self, peer: &P, name: String, metadata: BTreeMap<String, String>) {
+        if self.players.len() >= self.config.max_players {
+            self.send_to(
+                peer,
+                SessionMessage::Rejected {
+                    reason: format!("session is full ({} players)", self.config.max_players),
+                },
+            );
+            return;
+        }
+        let player = Player {
+            id: PlayerId(self.next_id),
+            name,
+            metadata,
+        };
+        self.next_id += 1;
+        let id = player.id;
+        info!(%id, name = %player.name, "player joined session");
+        self.broadcast(&SessionMessage::PlayerJoined(player.clone()), None);
+        self.players.push(player);
+        self.accept(peer, id);
+        self.events.push_back(SessionEvent::PlayerJoined(id));
+    }
+
+    fn readmit(&mut self, peer: &P, player: PlayerId) {
+        if !self.awaiting_rejoin.remove(&player) {
+            self.send_to(
+                peer,
+                SessionMessage::Rejected {
+                    reason: format!("{player} is not awaited by the new host"),
+                },
+            );
+            return;
+        }
+        info!(%player, "player rejoined after host migration");
+        self.accept(peer, player);
+    }
+
+    fn accept(&mut self, peer: &P, player: PlayerId) {
+        if let Some(state) = self.peers.get_mut(peer) {
+            state.player = Some(player);
+        }
+        let accepted = SessionMessage::Accepted {
+            player,
+            host: self.local.unwrap_or(PlayerId(0)),
+            players: self.players.clone(),
+            host_migration: self.config.host_migration,
+        };
+        self.send_to(peer, accepted);
+    }
+
+    fn host_lost(&mut self) {
+        let Some(old_host) = self.host else {
+            self.end("lost connection to the host");
+            return;
+        };
+        if !self.config.host_migration {
+            self.end("the host left the session");
+            return;
+        }
+        self.take_player(old_host);
+        self.events.push_back(SessionEvent::PlayerLeft(old_host));
+        // Every member picks the same successor from the shared join order.
+        let Some(successor) = self.players.first().map(|player| player.id) else {
+            self.end("the host left the session");
+            return;
+        };
+        self.host = Some(successor);
+        self.peers.clear();
+        info!(host = %successor, "session host migrated");
+        self.events.push_back(SessionEvent::HostMigrated(successor));
+        if self.local == Some(successor) {
+            self.role = Role::Host;
+            self.next_id = self.players.iter().map(|p| p.id.0 + 1).max().unwrap_or(0);
+            self.awaiting_rejoin = self
+                .players
+                .iter()
+                .map(|player| player.id)
+                .filter(|&id| id != successor)
+                .collect();
+            self.rejoin_deadline = None;
+        } else {
+            self.role = Role::Migrating;
+        }
+    }
+
+    fn remove_player(&mut self, player: PlayerId) {
+        if !self.take_player(player) {
+            return;
+        }
+        self.awaiting_rejoin.remove(&player);
+        self.peers.retain(|_, state| state.player != Some(player));
+        info!(%player, "player left session");
+        self.broadcast(&SessionMessage::PlayerLeft(player), None);
+        self.events.push_back(SessionEvent::PlayerLeft(player));
+    }
+
+    fn take_player(&mut self, player: PlayerId) -> bool {
+        let before = self.players.len();
+        self.players.retain(|candidate| candidate.id != player);
+        self.players.len() != before
+    }
+
+    /// Replaces the player list after a rejoin, reporting the differences.
+    fn sync_players(&mut self, players: Vec<Player>) {
+        let known: HashSet<PlayerId> = self.players.iter().map(|player| player.id).collect();
+        let current: HashSet<PlayerId> = players.iter().map(|player| player.id).collect();
+        for id in known.difference(&current) {
+            self.events.push_back(SessionEvent::PlayerLeft(*id));
+        }
+        for player in &players {
+            if !known.contains(&player.id) && Some(player.id) != self.local {
+                self.events.push_back(SessionEvent::PlayerJoined(player.id));
+            }
+        }
+        self.players = players;
+    }
+
+    fn apply_metadata(&mut self, player: PlayerId, metadata: BTreeMap<String, String>) {
+        if let Some(entry) = self.players.iter_mut().find(|entry| entry.id == player) {
+            entry.metadata = metadata;
+            self.events.push_back(SessionEvent::MetadataChanged(player));
+        }
+    }
+
+    /// Delivers data on the host, locally and to its recipients.
+    fn relay(&mut self, message: &SessionMessage, from: PlayerId, to: Option<PlayerId>) {
+        let SessionMessage::Data { payload, .. } = message else {
+            return;
+        };
+        if from != self.local.unwrap_or(PlayerId(0)) && to.is_none_or(|to| Some(to) == self.local) {
+            self.events.push_back(SessionEvent::Message {
+                from,
+                payload: payload.clone(),
+            });
+        }
+        match to {
+            Some(to) => {
+                if let Some(address) = self.address_of(to) {
+                    self.send_to(&address, message.clone());
+                }
+            }
+            None => self.broadcast(message, Some(from)),
+        }
+    }
+
+    fn send_join(&mut self, host: P) {
+        let (name, metadata) = self.profile.clone();
+        let mut peer = Peer::new(None);
+        peer.channel.send(SessionMessage::Join { name, metadata });
+        self.peers.insert(host, peer);
+    }
+
+    fn send_to_host_or_all(&mut self, message: &SessionMessage) {
+        match &self.role {
+            Role::Host => self.broadcast(message, self.local),
+            Role::Client(host) => {
+                let host = host.clone();
+                self.send_to(&host, message.clone());
+            }
+            _ => {}
+        }
+    }
+
+    /// Sends to every admitted peer except `except`.
+    fn broadcast(&mut self, message: &SessionMessage, except: Option<PlayerId>) {
+        for peer in self.peers.values_mut() {
+            if peer.player.is_some() && peer.player != except {
+                peer.channel.send(message.clone());
+            }
+        }
+    }
+
+    fn send_to(&mut self, peer: &P, message: SessionMessage) {
+        if let Some(state) = self.peers.get_mut(peer) {
+            state.channel.send(message);
+        }
+    }
+
+    fn address_of(&self, player: PlayerId) -> Option<P> {
+        self.peers
+            .iter()
+            .find(|(_, state)| state.player == Some(player))
+            .map(|(address, _)| address.clone())
+    }
+
+    fn end(&mut self, reason: &str) {
+        if self.role == Role::Ended {
+            return;
+        }
+        info!(reason, "session ended");
+        self.role = Role::Ended;
+        self.events.push_back(SessionEvent::Ended {
+            reason: reason.to_owned(),
+        });
+    }
+}
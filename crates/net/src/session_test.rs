@@ -0,0 +1,241 @@
+//! Unit tests for multiplayer sessions.
+
+use crate::session::{PlayerId, Session, SessionConfig, SessionEvent};
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+/// Sessions connected by a lossless in-memory transport, addressed by name.
+struct Network {
+    now: Instant,
+    sessions: HashMap<&'static str, Session<&'static str>>,
+}
+
+impl Network {
+    fn new() -> Self {
+        Self {
+            now: Instant::now(),
+            sessions: HashMap::new(),
+        }
+    }
+
+    fn add(&mut self, address: &'static str, session: Session<&'static str>) {
+        self.sessions.insert(address, session);
+    }
+
+    fn session(&mut self, address: &str) -> &mut Session<&'static str> {
+        self.sessions.get_mut(address).unwrap()
+    }
+
+    /// Delivers packets until every session is idle.
+    fn settle(&mut self) {
+        for _ in 0..16 {
+            let mut in_flight = Vec::new();
+            for (&from, session) in &mut self.sessions {
+                for (to, packet) in session.poll_transmit(self.now) {
+                    in_flight.push((from, to, packet));
+                }
+            }
+            if in_flight.is_empty() {
+                return;
+            }
+            for (from, to, packet) in in_flight {
+                if let Some(session) = self.sessions.get_mut(to) {
+                    session.receive(&from, packet);
+                }
+            }
+        }
+        panic!("session traffic did not settle");
+    }
+
+    fn events(&mut self, address: &str) -> Vec<SessionEvent> {
+        self.session(address).drain_events().collect()
+    }
+}
+
+fn metadata(team: &str) -> BTreeMap<String, String> {
+    BTreeMap::from([("team".to_owned(), team.to_owned())])
+}
+
+/// Hosts a session at "host" joined by "a" and "b", in that order.
+fn lobby(config: SessionConfig) -> Network {
+    let mut network = Network::new();
+    network.add("host", Session::create(config, "host", metadata("red")));
+    network.add("a", Session::join("host", "a", metadata("blue")));
+    network.settle();
+    network.add("b", Session::join("host", "b", metadata("red")));
+    network.settle();
+    for address in ["host", "a", "b"] {
+        network.events(address);
+    }
+    network
+}
+
+/// Verifies that joining players share the same player list and metadata.
+#[test]
+fn joins_share_player_list() {
+    let mut network = Network::new();
+    network.add(
+        "host",
+        Session::create(SessionConfig::default(), "host", metadata("red")),
+    );
+    network.add("a", Session::join("host", "a", metadata("blue")));
+    network.settle();
+    network.add("b", Session::join("host", "b", metadata("red")));
+    network.settle();
+
+    assert_eq!(
+        network.events("a"),
+        [
+            SessionEvent::Joined(PlayerId(1)),
+            SessionEvent::PlayerJoined(PlayerId(2))
+        ]
+    );
+    let host_view = network.session("host").players().to_vec();
+    assert_eq!(host_view.len(), 3);
+    for address in ["a", "b"] {
+        assert_eq!(network.session(address).players(), host_view);
+        assert_eq!(network.session(address).host(), Some(PlayerId(0)));
+    }
+    assert_eq!(host_view[1].metadata, metadata("blue"));
+}
+
+/// Verifies that joins beyond the player limit are rejected.
+#[test]
+fn rejects_full_session() {
+    let mut network = lobby(SessionConfig::default().with_max_players(3));
+    network.add("c", Session::join("host", "c", BTreeMap::new()));
+    network.settle();
+
+    assert!(network.session("c").is_ended());
+    assert!(matches!(
+        network.events("c").as_slice(),
+        [SessionEvent::Ended { reason }] if reason.contains("full")
+    ));
+    assert_eq!(network.session("host").players().len(), 3);
+}
+
+/// Verifies that metadata changes reach every member.
+#[test]
+fn shares_metadata_changes() {
+    let mut network = lobby(SessionConfig::default());
+    network.session("a").set_metadata("team", "green");
+    network.settle();
+
+    for address in ["host", "b"] {
+        let player = network.session(address).player(PlayerId(1)).unwrap();
+        assert_eq!(player.metadata, metadata("green"));
+        assert_eq!(
+            network.events(address),
+            [SessionEvent::MetadataChanged(PlayerId(1))]
+        );
+    }
+}
+
+/// Verifies that data is relayed through the host in order.
+#[test]
+fn relays_messages_in_order() {
+    let mut network = lobby(SessionConfig::default());
+    for byte in 0..3 {
+        network.session("a").send(None, vec![byte]).unwrap();
+    }
+    network
+        .session("b")
+        .send(Some(PlayerId(1)), vec![9])
+        .unwrap();
+    network.settle();
+
+    let from_a = |payload: u8| SessionEvent::Message {
+        from: PlayerId(1),
+        payload: vec![payload],
+    };
+    assert_eq!(network.events("host"), [from_a(0), from_a(1), from_a(2)]);
+    assert_eq!(network.events("b"), [from_a(0), from_a(1), from_a(2)]);
+    assert_eq!(
+        network.events("a"),
+        [SessionEvent::Message {
+            from: PlayerId(2),
+            payload: vec![9]
+        }]
+    );
+}
+
+/// Verifies that players leaving are removed everywhere.
+#[test]
+fn removes_leaving_players() {
+    let mut network = lobby(SessionConfig::default());
+    network.session("b").leave();
+    network.settle();
+
+    assert!(network.session("b").is_ended());
+    for address in ["host", "a"] {
+        assert_eq!(network.session(address).players().len(), 2);
+        assert_eq!(
+            network.events(address),
+            [SessionEvent::PlayerLeft(PlayerId(2))]
+        );
+    }
+}
+
+/// Verifies that the session ends with the host when migration is off.
+#[test]
+fn ends_without_host_migration() {
+    let mut network = lobby(SessionConfig::default());
+    network.session("a").disconnected(&"host");
+
+    assert!(network.session("a").is_ended());
+}
+
+/// Verifies that the earliest-joined player takes over as host and the
+/// others rejoin them.
+#[test]
+fn migrates_host() {
+    let mut network = lobby(SessionConfig::default().with_host_migration(true));
+    network.sessions.remove("host");
+    network.session("a").disconnected(&"host");
+    network.session("b").disconnected(&"host");
+
+    assert!(network.session("a").is_host());
+    assert_eq!(
+        network.events("b"),
+        [
+            SessionEvent::PlayerLeft(PlayerId(0)),
+            SessionEvent::HostMigrated(PlayerId(1))
+        ]
+    );
+
+    network.session("b").reconnect("a");
+    network.settle();
+    network.session("b").send(None, vec![1]).unwrap();
+    network.settle();
+
+    assert_eq!(network.session("b").host(), Some(PlayerId(1)));
+    assert_eq!(network.session("b").players().len(), 2);
+    assert_eq!(
+        network.events("a"),
+        [
+            SessionEvent::PlayerLeft(PlayerId(0)),
+            SessionEvent::HostMigrated(PlayerId(1)),
+            SessionEvent::Message {
+                from: PlayerId(2),
+                payload: vec![1]
+            }
+        ]
+    );
+}
+
+/// Verifies that players who do not rejoin the new host are dropped.
+#[test]
+fn drops_players_missing_after_migration() {
+    let mut network = lobby(SessionConfig::default().with_host_migration(true));
+    network.sessions.remove("host");
+    network.session("a").disconnected(&"host");
+    network.events("a");
+
+    let now = network.now;
+    network.session("a").update(now);
+    assert_eq!(network.session("a").players().len(), 2);
+    network.session("a").update(now + Duration::from_secs(11));
+
+    assert_eq!(network.session("a").players().len(), 1);
+    assert_eq!(network.events("a"), [SessionEvent::PlayerLeft(PlayerId(2))]);
+}
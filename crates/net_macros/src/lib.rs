@@ -0,0 +1,268 @@
+//! Procedural macros for Rustgine network messages.
+//!
+//! These macros are re-exported by the `net` crate and documented there;
+//! depend on `net` rather than on this crate directly.
+//!
+//! # Overview
+//!
+//! - `#[derive(NetMessage)]` implements `net::message::NetMessage` for a
+//!   serde-serializable type
+//! - `#[server_rpc]` turns a server-side handler function into an RPC
+//!   message that clients send and the server dispatches to the handler
+
+#![warn(missing_docs)]
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_macro_input, DeriveInput, FnArg, Ident, ItemFn, LitStr, Meta, Pat, Token, Type,
+    TypeReference,
+};
+
+/// Message options given as `#[net(...)]` or `#[server_rpc(...)]`.
+#[derive(Default)]
+struct Options {
+    /// Channel variant name, such as `Reliable`.
+    channel: Option<Ident>,
+    /// Stable message name overriding the default.
+    name: Option<LitStr>,
+    /// Path of the `net` crate, for code that renames or is inside it.
+    krate: Option<syn::Path>,
+}
+
+impl Options {
+    /// Parses `key = "value"` pairs naming a `channel`, `name`, or `crate`.
+    fn parse(metas: Punctuated<Meta, Token![,]>) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for meta in metas {
+            let Meta::NameValue(pair) = &meta else {
+                return Err(syn::Error::new_spanned(meta, "expected `key = \"value\"`"));
+            };
+            let syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(value),
+                ..
+            }) = &pair.value
+            else {
+                return Err(syn::Error::new_spanned(&pair.value, "expected a string"));
+            };
+            if pair.path.is_ident("channel") {
+                let variant = match value.value().as_str() {
+                    "reliable" => "Reliable",
+                    "unreliable" => "Unreliable",
+                    other => {
+                        return Err(syn::Error::new_spanned(
+                            value,
+                            format!(
+                                "unknown channel `{other}`, expected `reliable` or `unreliable`"
+                            ),
+                        ))
+                    }
+                };
+                options.channel = Some(Ident::new(variant, value.span()));
+            } else if pair.path.is_ident("name") {
+                options.name = Some(value.clone());
+            } else if pair.path.is_ident("crate") {
+                options.krate = Some(value.parse()?);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    &pair.path,
+                    "unknown option, expected `channel`, `name`, or `crate`",
+                ));
+            }
+        }
+        Ok(options)
+    }
+
+    /// Returns the path of the `net` crate.
+    fn krate(&self) -> TokenStream2 {
+        self.krate
+            .as_ref()
+            .map_or_else(|| quote!(::net), |path| quote!(#path))
+    }
+
+    /// Generates the `NetMessage` implementation for `ident`.
+    fn implement(&self, ident: &Ident, generics: &syn::Generics) -> TokenStream2 {
+        let krate = self.krate();
+        let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+        let name = self.name.as_ref().map_or_else(
+            || {
+                quote!(::core::concat!(
+                    ::core::module_path!(),
+                    "::",
+                    ::core::stringify!(#ident)
+                ))
+            },
+            |name| quote!(#name),
+        );
+        let channel = self
+            .channel
+            .clone()
+            .unwrap_or_else(|| Ident::new("Reliable", Span::call_site()));
+        quote! {
+            impl #impl_generics #krate::message::NetMessage for #ident #type_generics #where_clause {
+                const NAME: &'static str = #name;
+                const CHANNEL: #krate::message::Channel = #krate::message::Channel::#channel;
+            }
+        }
+    }
+}
+
+/// Implements `NetMessage` for a type that also derives serde's
+/// `Serialize` and `Deserialize`.
+///
+/// Options go in a `#[net(...)]` attribute:
+/// - `channel = "reliable"` (default) or `channel = "unreliable"`
+/// - `name = "..."` to keep the message id stable across renames
+/// - `crate = "..."` to name the `net` crate when it is not `::net`
+#[proc_macro_derive(NetMessage, attributes(net))]
+pub fn derive_net_message(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let mut options = Options::default();
+    for attribute in input.attrs.iter().filter(|a| a.path().is_ident("net")) {
+        match attribute
+            .parse_args_with(Punctuated::parse_terminated)
+            .and_then(Options::parse)
+        {
+            Ok(parsed) => {
+                options.channel = parsed.channel.or(options.channel);
+                options.name = parsed.name.or(options.name);
+                options.krate = parsed.krate.or(options.krate);
+            }
+            Err(e) => return e.to_compile_error().into(),
+        }
+    }
+    options.implement(&input.ident, &input.generics).into()
+}
+
+/// Turns a server-side handler into an RPC message.
+///
+/// The handler takes the server's context as `&mut Context`, then the
+/// sending `PlayerId`, then the RPC's arguments. The macro keeps the
+/// function and generates a message struct named after it in `PascalCase`,
+/// with one public field per argument, implementing `NetMessage` and
+/// `ServerRpc`. Options are the same as for `#[derive(NetMessage)]`.
+#[proc_macro_attribute]
+pub fn server_rpc(args: TokenStream, item: TokenStream) -> TokenStream {
+    let function = parse_macro_input!(item as ItemFn);
+    let options = match Punctuated::<Meta, Token![,]>::parse_terminated
+        .parse(args)
+        .and_then(Options::parse)
+    {
+        Ok(options) => options,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    match expand_server_rpc(&function, &options) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand_server_rpc(function: &ItemFn, options: &Options) -> syn::Result<TokenStream2> {
+    let signature = &function.sig;
+    if !signature.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &signature.generics,
+            "server RPCs cannot be generic",
+        ));
+    }
+    let mut inputs = signature.inputs.iter();
+    let context = match inputs.next() {
+        Some(FnArg::Typed(arg)) => match &*arg.ty {
+            Type::Reference(TypeReference {
+                mutability: Some(_),
+                elem,
+                ..
+            }) => elem.clone(),
+            other => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    "the first argument must be the server context as `&mut Context`",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                signature,
+                "server RPCs take the server context, then the sender, then arguments",
+            ))
+        }
+    };
+    if !matches!(inputs.next(), Some(FnArg::Typed(_))) {
+        return Err(syn::Error::new_spanned(
+            signature,
+            "the second argument must be the sending `PlayerId`",
+        ));
+    }
+    let mut fields = Vec::new();
+    let mut types = Vec::new();
+    for input in inputs {
+        let FnArg::Typed(arg) = input else {
+            return Err(syn::Error::new_spanned(input, "unexpected receiver"));
+        };
+        let Pat::Ident(pattern) = &*arg.pat else {
+            return Err(syn::Error::new_spanned(
+                &arg.pat,
+                "RPC arguments must be plain names",
+            ));
+        };
+        fields.push(pattern.ident.clone());
+        types.push(arg.ty.clone());
+    }
+
+    let handler = &signature.ident;
+    let visibility = &function.vis;
+    let message = format_ident!(
+        "{}",
+        pascal_case(&handler.to_string()),
+        span = handler.span()
+    );
+    let struct_doc = format!("Arguments of the [`{handler}`] server RPC.");
+    let field_docs = fields
+        .iter()
+        .map(|field| format!("The `{field}` argument."));
+    let message_impl = options.implement(&message, &syn::Generics::default());
+    let krate = options.krate();
+    let serde_crate = quote!(#krate::__private::serde).to_string();
+
+    Ok(quote! {
+        #function
+
+        #[doc = #struct_doc]
+        #[derive(::core::fmt::Debug, ::core::clone::Clone, #krate::__private::serde::Serialize, #krate::__private::serde::Deserialize)]
+        #[serde(crate = #serde_crate)]
+        #visibility struct #message {
+            #(
+                #[doc = #field_docs]
+                pub #fields: #types,
+            )*
+        }
+
+        #message_impl
+
+        impl #krate::message::ServerRpc for #message {
+            type Context = #context;
+
+            fn invoke(self, context: &mut Self::Context, sender: #krate::session::PlayerId) {
+                #handler(context, sender, #(self.#fields),*);
+            }
+        }
+    })
+}
+
+/// Converts a `snake_case` function name to a `PascalCase` type name.
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,257 @@
+//! Kinematic character movement.
+//!
+//! A [`CharacterController`] moves a capsule through the world without
+//! being pushed around by the solver. Movement slides along walls, climbs
+//! steps up to the step offset, refuses slopes steeper than the slope limit,
+//! and snaps down stairs and slopes instead of launching off them. Standing
+//! on a moving body, such as an elevator or a ship deck, carries the
+//! character along with it.
+//!
+//! Gameplay code calls [`CharacterController::move_by`] with the wanted
+//! displacement, then [`CharacterController::update`] once per physics step
+//! before the world is stepped.
+
+use crate::world::PhysicsWorld;
+use rapier3d::control::{CharacterAutostep, CharacterLength, KinematicCharacterController};
+use rapier3d::prelude::*;
+use std::time::Duration;
+
+/// Default height of steps a character climbs without jumping.
+pub const DEFAULT_STEP_OFFSET: f32 = 0.3;
+
+/// Default steepest slope a character walks up, in radians (45 degrees).
+pub const DEFAULT_MAX_SLOPE: f32 = std::f32::consts::FRAC_PI_4;
+
+/// Gap kept between the capsule and the surfaces it touches.
+const SKIN_WIDTH: f32 = 0.01;
+
+/// Extra distance below the capsule searched for the ground collider.
+const GROUND_PROBE: f32 = 0.1;
+
+/// Component moving an entity as a kinematic capsule.
+///
+/// # Example
+///
+/// ```
+/// use physics::rapier3d::prelude::*;
+/// use physics::{CharacterController, PhysicsWorld};
+/// use std::time::Duration;
+///
+/// let mut world = PhysicsWorld::default();
+/// world.insert_collider(ColliderBuilder::cuboid(50.0, 0.5, 50.0).translation(vector![0.0, -0.5, 0.0]), None);
+///
+/// let mut player = CharacterController::new(0.4, 0.5);
+/// player.spawn(&mut world, [0.0, 0.91, 0.0]);
+///
+/// let step = Duration::from_secs_f32(1.0 / 60.0);
+/// player.move_by([0.1, 0.0, 0.0]);
+/// player.update(&mut world, step);
+/// world.step(step);
+/// assert!(player.is_grounded());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CharacterController {
+    /// Radius of the capsule.
+    pub radius: f32,
+    /// Half the height of the capsule's straight section.
+    pub half_height: f32,
+    /// Height of steps climbed automatically; 0 disables stepping.
+    pub step_offset: f32,
+    /// Steepest walkable slope in radians.
+    pub max_slope: f32,
+    /// Kinematic body created by [`spawn`](Self::spawn).
+    body: Option<RigidBodyHandle>,
+    /// Displacement requested since the last update.
+    displacement: Vector<Real>,
+    /// Speed along the up axis from gravity.
+    vertical_speed: f32,
+    /// Whether the capsule stood on the ground after the last update.
+    grounded: bool,
+    /// Collider the capsule stood on after the last update.
+    ground: Option<ColliderHandle>,
+}
+
+impl CharacterController {
+    /// Creates a controller for a capsule of the given size.
+    #[must_use]
+    pub fn new(radius: f32, half_height: f32) -> Self {
+        Self {
+            radius,
+            half_height,
+            step_offset: DEFAULT_STEP_OFFSET,
+            max_slope: DEFAULT_MAX_SLOPE,
+            body: None,
+            displacement: Vector::zeros(),
+            vertical_speed: 0.0,
+            grounded: false,
+            ground: None,
+        }
+    }
+
+    /// Sets the height of steps climbed automatically.
+    #[must_use]
+    pub fn with_step_offset(mut self, step_offset: f32) -> Self {
+        self.step_offset = step_offset.max(0.0);
+        self
+    }
+
+    /// Sets the steepest walkable slope in radians.
+    #[must_use]
+    pub fn with_max_slope(mut self, max_slope: f32) -> Self {
+        self.max_slope = max_slope.clamp(0.0, std::f32::consts::FRAC_PI_2);
+        self
+    }
+
+    /// Creates the character's kinematic body and capsule at `position`,
+    /// the capsule's center.
+    pub fn spawn(&mut self, world: &mut PhysicsWorld, position: [f32; 3]) -> RigidBodyHandle {
+        let body = world
+            .insert_body(RigidBodyBuilder::kinematic_position_based().translation(position.into()));
+        world.insert_collider(
+            ColliderBuilder::capsule_y(self.half_height, self.radius),
+            Some(body),
+        );
+        self.body = Some(body);
+        body
+    }
+
+    /// Returns the character's body, once spawned.
+    #[must_use]
+    #[inline]
+    pub fn body(&self) -> Option<RigidBodyHandle> {
+        self.body
+    }
+
+    /// Returns `true` if the character stood on walkable ground after the
+    /// last update.
+    #[must_use]
+    #[inline]
+    pub fn is_grounded(&self) -> bool {
+        self.grounded
+    }
+
+    /// Returns the collider the character stood on after the last update.
+    #[must_use]
+    #[inline]
+    pub fn ground(&self) -> Option<ColliderHandle> {
+        self.ground
+    }
+
+    /// Returns the falling or rising speed along the up axis.
+    #[must_use]
+    #[inline]
+    pub fn vertical_speed(&self) -> f32 {
+        self.vertical_speed
+    }
+
+    /// Requests a displacement, in world units, for the next update.
+    ///
+    /// Requests made between updates add up.
+    pub fn move_by(&mut self, displacement: [f32; 3]) {
+        self.displacement += Vector::from(displacement);
+    }
+
+    /// Moves the character by the requested displacement plus gravity and
+    /// the motion of the ground it stands on, sliding along obstacles.
+    ///
+    /// Returns the translation applied. The body reaches its new position
+    /// when the world is next stepped.
+    pub fn update(&mut self, world: &mut PhysicsWorld, delta: Duration) -> [f32; 3] {
+        let requested = std::mem::replace(&mut self.displacement, Vector::zeros());
+        let Some(handle) = self.body else {
+            return [0.0; 3];
+        };
+        let dt = delta.as_secs_f32();
+        let gravity = Vector::from(world.gravity()).y;
+        let up = Vector::y();
+
+        if self.grounded {
+            self.vertical_speed = 0.0;
+        } else {
+            self.vertical_speed += gravity * dt;
+        }
+        let carried = self.ground_velocity(world) * dt;
+        let desired = requested + carried + up * (self.vertical_speed * dt);
+
+        let shape = Capsule::new_y(self.half_height, self.radius);
+        let controller = self.kinematic_controller();
+        let (bodies, colliders, queries) = world.query_parts();
+        let Some(position) = bodies.get(handle).map(|body| *body.next_position()) else {
+            return [0.0; 3];
+        };
+        let movement = controller.move_shape(
+            dt,
+            bodies,
+            colliders,
+            queries,
+            &shape,
+            &position,
+            desired,
+            QueryFilter::default().exclude_rigid_body(handle),
+            |_| {},
+        );
+
+        let feet = position.translation.vector + movement.translation
+            - up * (self.half_height + self.radius);
+        let probe = Ray::new(Point::from(feet + up * SKIN_WIDTH), -up);
+        let ground = queries
+            .cast_ray(
+                bodies,
+                colliders,
+                &probe,
+                SKIN_WIDTH * 2.0 + GROUND_PROBE,
+                true,
+                QueryFilter::default()
+                    .exclude_rigid_body(handle)
+                    .exclude_sensors(),
+            )
+            .map(|(collider, _)| collider);
+
+        self.grounded = movement.grounded;
+        self.ground = ground.filter(|_| movement.grounded);
+        if self.grounded && self.vertical_speed < 0.0 {
+            self.vertical_speed = 0.0;
+        }
+        if let Some(body) = world.body_mut(handle) {
+            body.set_next_kinematic_translation(position.translation.vector + movement.translation);
+        }
+        movement.translation.into()
+    }
+
+    /// Returns the velocity of the ground under the character's feet.
+    fn ground_velocity(&self, world: &PhysicsWorld) -> Vector<Real> {
+        let (Some(ground), Some(handle)) = (self.ground, self.body) else {
+            return Vector::zeros();
+        };
+        let Some(platform) = world
+            .collider(ground)
+            .and_then(Collider::parent)
+            .and_then(|parent| world.body(parent))
+            .filter(|platform| !platform.is_fixed())
+        else {
+            return Vector::zeros();
+        };
+        let Some(character) = world.body(handle) else {
+            return Vector::zeros();
+        };
+        let feet = character.next_position().translation.vector
+            - Vector::y() * (self.half_height + self.radius);
+        platform.velocity_at_point(&Point::from(feet))
+    }
+
+    /// Returns rapier's controller configured from this component.
+    fn kinematic_controller(&self) -> KinematicCharacterController {
+        KinematicCharacterController {
+            offset: CharacterLength::Absolute(SKIN_WIDTH),
+            autostep: (self.step_offset > 0.0).then_some(CharacterAutostep {
+                max_height: CharacterLength::Absolute(self.step_offset),
+                min_width: CharacterLength::Absolute(self.radius * 0.5),
+                include_dynamic_bodies: false,
+            }),
+            max_slope_climb_angle: self.max_slope,
+            min_slope_slide_angle: self.max_slope,
+            snap_to_ground: Some(CharacterLength::Absolute(self.step_offset.max(SKIN_WIDTH))),
+            ..KinematicCharacterController::default()
+        }
+    }
+}
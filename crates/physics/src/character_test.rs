@@ -0,0 +1,157 @@
+//! Unit tests for the kinematic character controller.
+
+use crate::character::CharacterController;
+use crate::world::PhysicsWorld;
+use rapier3d::prelude::*;
+use std::time::Duration;
+
+const STEP: Duration = Duration::from_nanos(16_666_667);
+
+/// Capsule center height when standing on the floor.
+const STANDING: f32 = 0.9;
+
+fn floor() -> PhysicsWorld {
+    let mut world = PhysicsWorld::default();
+    world.insert_collider(
+        ColliderBuilder::cuboid(50.0, 0.5, 50.0).translation(vector![0.0, -0.5, 0.0]),
+        None,
+    );
+    world
+}
+
+fn player(world: &mut PhysicsWorld, position: [f32; 3]) -> CharacterController {
+    let mut player = CharacterController::new(0.4, 0.5);
+    player.spawn(world, position);
+    player
+}
+
+/// Moves `player` by `per_step` for `steps` physics steps.
+fn walk(
+    world: &mut PhysicsWorld,
+    player: &mut CharacterController,
+    per_step: [f32; 3],
+    steps: u32,
+) {
+    for _ in 0..steps {
+        player.move_by(per_step);
+        player.update(world, STEP);
+        world.step(STEP);
+    }
+}
+
+fn position(world: &PhysicsWorld, player: &CharacterController) -> [f32; 3] {
+    world.translation(player.body().unwrap()).unwrap()
+}
+
+/// Verifies that a character falls under gravity and lands on the ground.
+#[test]
+fn falls_and_lands() {
+    let mut world = floor();
+    let mut player = player(&mut world, [0.0, 3.0, 0.0]);
+    walk(&mut world, &mut player, [0.0; 3], 120);
+
+    let [_, y, _] = position(&world, &player);
+    assert!((y - STANDING).abs() < 0.05, "standing at {y}");
+    assert!(player.is_grounded());
+    assert!(player.ground().is_some());
+    assert!(player.vertical_speed().abs() < f32::EPSILON);
+}
+
+/// Verifies that movement into a wall slides along it.
+#[test]
+fn slides_along_walls() {
+    let mut world = floor();
+    world.insert_collider(
+        ColliderBuilder::cuboid(0.5, 2.0, 50.0).translation(vector![2.0, 2.0, 0.0]),
+        None,
+    );
+    let mut player = player(&mut world, [0.0, STANDING, 0.0]);
+    walk(&mut world, &mut player, [0.1, 0.0, 0.1], 60);
+
+    let [x, _, z] = position(&world, &player);
+    assert!(x < 1.1, "passed into the wall at x = {x}");
+    assert!(z > 5.0, "stuck at z = {z}");
+}
+
+/// Verifies that steps below the step offset are climbed and taller ones
+/// block.
+#[test]
+fn climbs_low_steps_only() {
+    let mut world = floor();
+    // A 0.2 step at x = 2 and a 1.0 ledge at x = 6.
+    world.insert_collider(
+        ColliderBuilder::cuboid(1.0, 0.1, 5.0).translation(vector![3.0, 0.1, 0.0]),
+        None,
+    );
+    world.insert_collider(
+        ColliderBuilder::cuboid(1.0, 0.5, 5.0).translation(vector![7.0, 0.5, 0.0]),
+        None,
+    );
+    let mut player = player(&mut world, [0.0, STANDING, 0.0]);
+    walk(&mut world, &mut player, [0.05, 0.0, 0.0], 60);
+    let [x, y, _] = position(&world, &player);
+    assert!(
+        x > 2.5 && (y - STANDING - 0.2).abs() < 0.05,
+        "at ({x}, {y})"
+    );
+
+    walk(&mut world, &mut player, [0.05, 0.0, 0.0], 120);
+    let [x, _, _] = position(&world, &player);
+    assert!(x < 6.0, "climbed the ledge to x = {x}");
+}
+
+/// Verifies that slopes steeper than the limit cannot be walked up.
+#[test]
+fn respects_slope_limit() {
+    let ramp = |angle: f32| {
+        let mut world = floor();
+        world.insert_collider(
+            ColliderBuilder::cuboid(5.0, 0.1, 5.0)
+                .translation(vector![5.0, 0.0, 0.0])
+                .rotation(vector![0.0, 0.0, angle]),
+            None,
+        );
+        let mut player = player(&mut world, [0.0, STANDING, 0.0]);
+        walk(&mut world, &mut player, [0.05, 0.0, 0.0], 180);
+        position(&world, &player)[1]
+    };
+
+    assert!(
+        ramp(20_f32.to_radians()) > 1.5,
+        "did not climb the gentle ramp"
+    );
+    assert!(ramp(60_f32.to_radians()) < 1.2, "climbed the steep ramp");
+}
+
+/// Verifies that a character standing on a moving platform is carried along.
+#[test]
+fn rides_moving_platforms() {
+    let mut world = floor();
+    let platform = world.insert_body(
+        RigidBodyBuilder::kinematic_velocity_based()
+            .translation(vector![0.0, 1.0, 0.0])
+            .linvel(vector![1.0, 0.0, 0.0]),
+    );
+    world.insert_collider(ColliderBuilder::cuboid(3.0, 0.1, 3.0), Some(platform));
+    let mut player = player(&mut world, [0.0, 2.0, 0.0]);
+    walk(&mut world, &mut player, [0.0; 3], 60);
+
+    let [platform_x, _, _] = world.translation(platform).unwrap();
+    let [x, _, _] = position(&world, &player);
+    assert!(player.ground().is_some());
+    assert!(
+        (x - platform_x).abs() < 0.1,
+        "player at {x}, platform at {platform_x}"
+    );
+}
+
+/// Verifies that an unspawned controller does not move.
+#[test]
+fn unspawned_controller_is_inert() {
+    let mut world = floor();
+    let mut player = CharacterController::new(0.4, 0.5);
+    player.move_by([1.0, 0.0, 0.0]);
+    let moved = player.update(&mut world, STEP);
+    assert_eq!(moved.map(f32::to_bits), [0.0_f32.to_bits(); 3]);
+    assert!(!player.is_grounded());
+}
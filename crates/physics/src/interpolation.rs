@@ -0,0 +1,170 @@
+//! Smoothing of physics motion between fixed steps.
+//!
+//! Physics steps at a fixed rate, usually 60 Hz, while frames render as
+//! fast as the display allows. Drawing bodies where the last step left them
+//! makes motion judder whenever the two rates disagree. A
+//! [`TransformInterpolation`] component keeps the transforms of the last two
+//! steps and blends them by the frame's alpha, the fraction of a step
+//! elapsed since the last one, as returned by `FixedTimestep::alpha`.
+//!
+//! Interpolation draws bodies up to one step in the past but never guesses
+//! wrong; extrapolation continues the last step's motion to the present,
+//! trading latency for overshoot when a body stops or turns.
+
+use crate::world::PhysicsWorld;
+use math::{Mat4, Quat, Vec3};
+use rapier3d::prelude::*;
+
+/// Position and orientation of a body.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BodyTransform {
+    /// World position.
+    pub translation: Vec3,
+    /// Orientation.
+    pub rotation: Quat,
+}
+
+impl From<&Isometry<Real>> for BodyTransform {
+    fn from(isometry: &Isometry<Real>) -> Self {
+        let vector = isometry.translation.vector;
+        let rotation = isometry.rotation.coords;
+        Self {
+            translation: Vec3::new(vector.x, vector.y, vector.z),
+            rotation: Quat::from_xyzw(rotation.x, rotation.y, rotation.z, rotation.w),
+        }
+    }
+}
+
+impl BodyTransform {
+    /// Returns the transform `t` of the way from `self` to `other`; `t`
+    /// above 1 continues past `other`.
+    #[must_use]
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        // Slerp falls back to nlerp for near-identical orientations, which
+        // covers the tiny angles of a single step.
+        Self {
+            translation: self.translation.lerp(other.translation, t),
+            rotation: self.rotation.slerp(other.rotation, t).normalize(),
+        }
+    }
+
+    /// Returns the transform as a matrix.
+    #[must_use]
+    pub fn to_matrix(&self) -> Mat4 {
+        Mat4::from_rotation_translation(self.rotation, self.translation)
+    }
+}
+
+/// How rendered transforms are derived from physics steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Smoothing {
+    /// Blend between the last two steps, rendering up to a step late.
+    #[default]
+    Interpolate,
+    /// Continue the last step's motion past the latest step.
+    Extrapolate,
+}
+
+/// Component holding a body's transforms from the last two physics steps.
+///
+/// # Example
+///
+/// ```
+/// use math::Vec3;
+/// use physics::interpolation::{BodyTransform, TransformInterpolation};
+///
+/// let at = |x| BodyTransform { translation: Vec3::new(x, 0.0, 0.0), ..BodyTransform::default() };
+/// let mut smoothing = TransformInterpolation::new(at(0.0));
+/// smoothing.record(at(1.0));
+///
+/// // A quarter of the way to the next step.
+/// assert_eq!(smoothing.sample(0.25).translation, Vec3::new(0.25, 0.0, 0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformInterpolation {
+    /// How rendered transforms are derived.
+    pub smoothing: Smoothing,
+    /// Transform after the second-to-last step.
+    previous: BodyTransform,
+    /// Transform after the last step.
+    current: BodyTransform,
+}
+
+impl TransformInterpolation {
+    /// Creates an interpolating component for a body at `transform`.
+    #[must_use]
+    pub fn new(transform: BodyTransform) -> Self {
+        Self {
+            smoothing: Smoothing::Interpolate,
+            previous: transform,
+            current: transform,
+        }
+    }
+
+    /// Sets how rendered transforms are derived.
+    #[must_use]
+    pub fn with_smoothing(mut self, smoothing: Smoothing) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Returns the transform after the second-to-last step.
+    #[must_use]
+    #[inline]
+    pub fn previous(&self) -> BodyTransform {
+        self.previous
+    }
+
+    /// Returns the transform after the last step.
+    #[must_use]
+    #[inline]
+    pub fn current(&self) -> BodyTransform {
+        self.current
+    }
+
+    /// Records the transform after a physics step.
+    pub fn record(&mut self, transform: BodyTransform) {
+        self.previous = self.current;
+        self.current = transform;
+    }
+
+    /// Moves the body without smoothing, so a teleport does not render as
+    /// a streak across the level.
+    pub fn teleport(&mut self, transform: BodyTransform) {
+        self.previous = transform;
+        self.current = transform;
+    }
+
+    /// Returns the transform to render at `alpha`, the fraction of a step
+    /// elapsed since the last one.
+    #[must_use]
+    pub fn sample(&self, alpha: f32) -> BodyTransform {
+        let alpha = alpha.clamp(0.0, 1.0);
+        match self.smoothing {
+            Smoothing::Interpolate => self.previous.lerp(&self.current, alpha),
+            Smoothing::Extrapolate => self.previous.lerp(&self.current, 1.0 + alpha),
+        }
+    }
+}
+
+impl PhysicsWorld {
+    /// Returns the transform of a rigid body.
+    #[must_use]
+    pub fn body_transform(&self, handle: RigidBodyHandle) -> Option<BodyTransform> {
+        self.body(handle)
+            .map(|body| BodyTransform::from(body.position()))
+    }
+
+    /// Records every listed body's transform after a step, for bodies that
+    /// still exist.
+    pub fn record_transforms<'a>(
+        &self,
+        bodies: impl IntoIterator<Item = (RigidBodyHandle, &'a mut TransformInterpolation)>,
+    ) {
+        for (handle, interpolation) in bodies {
+            if let Some(transform) = self.body_transform(handle) {
+                interpolation.record(transform);
+            }
+        }
+    }
+}
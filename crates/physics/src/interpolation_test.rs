@@ -0,0 +1,92 @@
+//! Unit tests for transform interpolation.
+
+use crate::interpolation::{BodyTransform, Smoothing, TransformInterpolation};
+use crate::world::PhysicsWorld;
+use math::{Quat, Vec3};
+use rapier3d::prelude::*;
+use std::time::Duration;
+
+fn at(x: f32) -> BodyTransform {
+    BodyTransform {
+        translation: Vec3::new(x, 0.0, 0.0),
+        ..BodyTransform::default()
+    }
+}
+
+fn assert_near(actual: Vec3, expected: [f32; 3]) {
+    let expected = Vec3::from(expected);
+    assert!(actual.abs_diff_eq(expected, 1e-5), "{actual} != {expected}");
+}
+
+/// Verifies that sampling blends the last two steps by alpha.
+#[test]
+fn interpolates_between_steps() {
+    let mut smoothing = TransformInterpolation::new(at(0.0));
+    smoothing.record(at(2.0));
+    smoothing.record(at(4.0));
+
+    assert_near(smoothing.sample(0.0).translation, [2.0, 0.0, 0.0]);
+    assert_near(smoothing.sample(0.5).translation, [3.0, 0.0, 0.0]);
+    assert_near(smoothing.sample(1.0).translation, [4.0, 0.0, 0.0]);
+    assert_near(smoothing.sample(7.0).translation, [4.0, 0.0, 0.0]);
+}
+
+/// Verifies that extrapolation continues the last step's motion.
+#[test]
+fn extrapolates_past_latest_step() {
+    let mut smoothing = TransformInterpolation::new(at(0.0)).with_smoothing(Smoothing::Extrapolate);
+    smoothing.record(at(2.0));
+
+    assert_near(smoothing.sample(0.0).translation, [2.0, 0.0, 0.0]);
+    assert_near(smoothing.sample(0.5).translation, [3.0, 0.0, 0.0]);
+}
+
+/// Verifies that rotations are blended along the shortest arc.
+#[test]
+fn slerps_rotations() {
+    let quarter_turn = BodyTransform {
+        rotation: Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+        ..BodyTransform::default()
+    };
+    let mut smoothing = TransformInterpolation::new(BodyTransform::default());
+    smoothing.record(quarter_turn);
+
+    let rotation = smoothing.sample(0.5).rotation;
+    let eighth_turn = Quat::from_rotation_y(std::f32::consts::FRAC_PI_4);
+    assert!(rotation.abs_diff_eq(eighth_turn, 1e-5), "{rotation}");
+}
+
+/// Verifies that teleporting skips smoothing.
+#[test]
+fn teleport_resets_history() {
+    let mut smoothing = TransformInterpolation::new(at(0.0));
+    smoothing.teleport(at(100.0));
+    assert_eq!(smoothing.previous(), smoothing.current());
+    assert_near(smoothing.sample(0.5).translation, [100.0, 0.0, 0.0]);
+}
+
+/// Verifies that matrices are column-major with the translation last.
+#[test]
+fn converts_to_matrix() {
+    let matrix = at(3.0).to_matrix();
+    assert_near(matrix.w_axis.truncate(), [3.0, 0.0, 0.0]);
+    assert_near(
+        Vec3::new(matrix.x_axis.x, matrix.y_axis.y, matrix.z_axis.z),
+        [1.0; 3],
+    );
+}
+
+/// Verifies that recorded world transforms render smoothly between steps.
+#[test]
+fn records_world_bodies() {
+    let mut world = PhysicsWorld::new([0.0; 3]);
+    let body = world.insert_body(RigidBodyBuilder::dynamic().linvel(vector![6.0, 0.0, 0.0]));
+    world.insert_collider(ColliderBuilder::ball(0.5), Some(body));
+    let mut smoothing = TransformInterpolation::new(world.body_transform(body).unwrap());
+
+    world.step(Duration::from_millis(100));
+    world.record_transforms([(body, &mut smoothing)]);
+
+    assert_near(smoothing.current().translation, [0.6, 0.0, 0.0]);
+    assert_near(smoothing.sample(0.5).translation, [0.3, 0.0, 0.0]);
+}
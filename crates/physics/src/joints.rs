@@ -0,0 +1,333 @@
+//! Joint components connecting rigid bodies.
+//!
+//! Doors hang on [`RevoluteJoint`]s, elevators and pistons slide on
+//! [`PrismaticJoint`]s, ragdoll bones and welded props hold together with
+//! [`FixedJoint`]s, and vehicle suspension bounces on [`SpringJoint`]s.
+//! Each component lives on one entity and names the entity it connects to;
+//! anchors are in each body's local space.
+//!
+//! Components are plain data, saved in scenes as a [`Joint`]:
+//!
+//! ```toml
+//! type = "revolute"
+//! connected = 12
+//! local_anchor1 = [0.5, 0.0, 0.0]
+//! axis = [0.0, 1.0, 0.0]
+//! limits = [-1.5, 1.5]
+//! ```
+//!
+//! [`JointSync`] creates the physics joints of new components, rebuilds the
+//! joints of changed ones, and removes the joints of despawned entities.
+
+use crate::world::PhysicsWorld;
+use rapier3d::prelude::{
+    FixedJointBuilder, GenericJoint, ImpulseJointHandle, PrismaticJointBuilder,
+    RevoluteJointBuilder, RigidBodyHandle, SpringJointBuilder, UnitVector, Vector,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A motor driving a joint towards a target position and velocity.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JointMotor {
+    /// Angle in radians, or distance, the motor drives towards.
+    #[serde(default)]
+    pub target_position: f32,
+    /// Speed the motor drives towards.
+    #[serde(default)]
+    pub target_velocity: f32,
+    /// Strength of the pull towards the target position.
+    #[serde(default)]
+    pub stiffness: f32,
+    /// Strength of the pull towards the target velocity.
+    #[serde(default)]
+    pub damping: f32,
+    /// Largest force or torque the motor applies; unlimited if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_force: Option<f32>,
+}
+
+/// Welds two bodies together.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FixedJoint {
+    /// Entity this entity is attached to.
+    pub connected: u64,
+    /// Attachment point on this entity's body.
+    #[serde(default)]
+    pub local_anchor1: [f32; 3],
+    /// Attachment point on the connected body.
+    #[serde(default)]
+    pub local_anchor2: [f32; 3],
+}
+
+/// Lets two bodies rotate about a shared axis, like a hinge.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RevoluteJoint {
+    /// Entity this entity is attached to.
+    pub connected: u64,
+    /// Attachment point on this entity's body.
+    #[serde(default)]
+    pub local_anchor1: [f32; 3],
+    /// Attachment point on the connected body.
+    #[serde(default)]
+    pub local_anchor2: [f32; 3],
+    /// Rotation axis.
+    pub axis: [f32; 3],
+    /// Smallest and largest angle in radians, if limited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<[f32; 2]>,
+    /// Motor turning the hinge, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub motor: Option<JointMotor>,
+}
+
+/// Lets two bodies slide along a shared axis.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PrismaticJoint {
+    /// Entity this entity is attached to.
+    pub connected: u64,
+    /// Attachment point on this entity's body.
+    #[serde(default)]
+    pub local_anchor1: [f32; 3],
+    /// Attachment point on the connected body.
+    #[serde(default)]
+    pub local_anchor2: [f32; 3],
+    /// Sliding axis.
+    pub axis: [f32; 3],
+    /// Smallest and largest offset along the axis, if limited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<[f32; 2]>,
+    /// Motor driving the slide, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub motor: Option<JointMotor>,
+}
+
+/// Pulls two anchor points towards a rest distance.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpringJoint {
+    /// Entity this entity is attached to.
+    pub connected: u64,
+    /// Attachment point on this entity's body.
+    #[serde(default)]
+    pub local_anchor1: [f32; 3],
+    /// Attachment point on the connected body.
+    #[serde(default)]
+    pub local_anchor2: [f32; 3],
+    /// Distance the spring settles at.
+    pub rest_length: f32,
+    /// Force per unit of stretch.
+    pub stiffness: f32,
+    /// Force per unit of stretching speed.
+    pub damping: f32,
+}
+
+/// Any joint component, as saved in scenes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Joint {
+    /// A [`FixedJoint`].
+    Fixed(FixedJoint),
+    /// A [`RevoluteJoint`].
+    Revolute(RevoluteJoint),
+    /// A [`PrismaticJoint`].
+    Prismatic(PrismaticJoint),
+    /// A [`SpringJoint`].
+    Spring(SpringJoint),
+}
+
+impl From<FixedJoint> for Joint {
+    fn from(joint: FixedJoint) -> Self {
+        Self::Fixed(joint)
+    }
+}
+
+impl From<RevoluteJoint> for Joint {
+    fn from(joint: RevoluteJoint) -> Self {
+        Self::Revolute(joint)
+    }
+}
+
+impl From<PrismaticJoint> for Joint {
+    fn from(joint: PrismaticJoint) -> Self {
+        Self::Prismatic(joint)
+    }
+}
+
+impl From<SpringJoint> for Joint {
+    fn from(joint: SpringJoint) -> Self {
+        Self::Spring(joint)
+    }
+}
+
+impl Joint {
+    /// Returns the entity the joint connects to.
+    #[must_use]
+    pub fn connected(&self) -> u64 {
+        match self {
+            Self::Fixed(joint) => joint.connected,
+            Self::Revolute(joint) => joint.connected,
+            Self::Prismatic(joint) => joint.connected,
+            Self::Spring(joint) => joint.connected,
+        }
+    }
+
+    /// Builds the rapier joint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a hinge or slide axis has zero length.
+    pub fn to_rapier(&self) -> anyhow::Result<GenericJoint> {
+        Ok(match *self {
+            Self::Fixed(joint) => FixedJointBuilder::new()
+                .local_anchor1(joint.local_anchor1.into())
+                .local_anchor2(joint.local_anchor2.into())
+                .build()
+                .into(),
+            Self::Revolute(joint) => {
+                let mut builder = RevoluteJointBuilder::new(unit_axis(joint.axis)?)
+                    .local_anchor1(joint.local_anchor1.into())
+                    .local_anchor2(joint.local_anchor2.into());
+                if let Some(limits) = joint.limits {
+                    builder = builder.limits(limits);
+                }
+                if let Some(motor) = joint.motor {
+                    builder = builder
+                        .motor(
+                            motor.target_position,
+                            motor.target_velocity,
+                            motor.stiffness,
+                            motor.damping,
+                        )
+                        .motor_max_force(motor.max_force.unwrap_or(f32::MAX));
+                }
+                builder.build().into()
+            }
+            Self::Prismatic(joint) => {
+                let mut builder = PrismaticJointBuilder::new(unit_axis(joint.axis)?)
+                    .local_anchor1(joint.local_anchor1.into())
+                    .local_anchor2(joint.local_anchor2.into());
+                if let Some(limits) = joint.limits {
+                    builder = builder.limits(limits);
+                }
+                if let Some(motor) = joint.motor {
+                    builder = builder
+                        .set_motor(
+                            motor.target_position,
+                            motor.target_velocity,
+                            motor.stiffness,
+                            motor.damping,
+                        )
+                        .motor_max_force(motor.max_force.unwrap_or(f32::MAX));
+                }
+                builder.build().into()
+            }
+            Self::Spring(joint) => {
+                SpringJointBuilder::new(joint.rest_length, joint.stiffness, joint.damping)
+                    .local_anchor1(joint.local_anchor1.into())
+                    .local_anchor2(joint.local_anchor2.into())
+                    .build()
+                    .into()
+            }
+        })
+    }
+}
+
+fn unit_axis(axis: [f32; 3]) -> anyhow::Result<UnitVector<f32>> {
+    UnitVector::try_new(Vector::from(axis), f32::EPSILON)
+        .ok_or_else(|| anyhow::anyhow!("joint axis {axis:?} has zero length"))
+}
+
+/// Keeps physics joints in step with joint components.
+///
+/// # Example
+///
+/// ```
+/// use physics::joints::{FixedJoint, Joint, JointSync};
+/// use physics::rapier3d::prelude::*;
+/// use physics::PhysicsWorld;
+///
+/// let mut world = PhysicsWorld::default();
+/// let bodies = [
+///     world.insert_body(RigidBodyBuilder::dynamic()),
+///     world.insert_body(RigidBodyBuilder::dynamic()),
+/// ];
+/// let weld = Joint::from(FixedJoint { connected: 1, local_anchor1: [0.0; 3], local_anchor2: [0.0; 3] });
+///
+/// let mut joints = JointSync::default();
+/// joints.sync(&mut world, [(0, &weld)], |entity| bodies.get(entity as usize).copied());
+/// assert!(joints.handle(0).is_some());
+///
+/// joints.sync(&mut world, [], |entity| bodies.get(entity as usize).copied());
+/// assert!(joints.handle(0).is_none());
+/// ```
+#[derive(Debug, Default)]
+pub struct JointSync {
+    /// Physics joint and the component it was built from, by entity.
+    joints: HashMap<u64, (ImpulseJointHandle, Joint)>,
+}
+
+impl JointSync {
+    /// Returns the physics joint built for an entity's component.
+    #[must_use]
+    pub fn handle(&self, entity: u64) -> Option<ImpulseJointHandle> {
+        self.joints.get(&entity).map(|(handle, _)| *handle)
+    }
+
+    /// Returns the number of physics joints built.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.joints.len()
+    }
+
+    /// Returns `true` if no physics joints are built.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.joints.is_empty()
+    }
+
+    /// Brings physics joints in line with `components`, every entity's
+    /// joint component.
+    ///
+    /// `body_of` returns an entity's rigid body. Joints whose bodies do not
+    /// exist yet are built on a later sync; invalid components are logged
+    /// and skipped.
+    pub fn sync<'a>(
+        &mut self,
+        world: &mut PhysicsWorld,
+        components: impl IntoIterator<Item = (u64, &'a Joint)>,
+        body_of: impl Fn(u64) -> Option<RigidBodyHandle>,
+    ) {
+        let mut stale = std::mem::take(&mut self.joints);
+        for (entity, joint) in components {
+            match stale.remove(&entity) {
+                // Removing either body also removes the joint.
+                Some((handle, built)) if built == *joint && world.joint(handle).is_some() => {
+                    self.joints.insert(entity, (handle, built));
+                    continue;
+                }
+                Some((handle, _)) => {
+                    world.remove_joint(handle);
+                }
+                None => {}
+            }
+            let (Some(body1), Some(body2)) = (body_of(entity), body_of(joint.connected())) else {
+                continue;
+            };
+            match joint.to_rapier() {
+                Ok(data) => {
+                    let handle = world.insert_joint(body1, body2, data);
+                    self.joints.insert(entity, (handle, *joint));
+                }
+                Err(e) => tracing::warn!(entity, error = %e, "invalid joint component"),
+            }
+        }
+        for (handle, _) in stale.into_values() {
+            world.remove_joint(handle);
+        }
+    }
+}
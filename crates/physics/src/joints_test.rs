@@ -0,0 +1,221 @@
+//! Unit tests for joint components.
+
+use crate::joints::{
+    FixedJoint, Joint, JointMotor, JointSync, PrismaticJoint, RevoluteJoint, SpringJoint,
+};
+use crate::world::PhysicsWorld;
+use rapier3d::prelude::*;
+use std::time::Duration;
+
+const STEP: Duration = Duration::from_nanos(16_666_667);
+
+/// A world with a fixed anchor body (entity 0) and a dynamic box (entity 1)
+/// hanging one unit to its right.
+fn pair() -> (PhysicsWorld, [RigidBodyHandle; 2]) {
+    let mut world = PhysicsWorld::default();
+    let anchor = world.insert_body(RigidBodyBuilder::fixed());
+    let weight = world.insert_body(RigidBodyBuilder::dynamic().translation(vector![1.0, 0.0, 0.0]));
+    world.insert_collider(ColliderBuilder::cuboid(0.2, 0.2, 0.2), Some(weight));
+    (world, [anchor, weight])
+}
+
+fn simulate(world: &mut PhysicsWorld, steps: u32) {
+    for _ in 0..steps {
+        world.step(STEP);
+    }
+}
+
+fn sync(
+    joints: &mut JointSync,
+    world: &mut PhysicsWorld,
+    bodies: [RigidBodyHandle; 2],
+    joint: Option<&Joint>,
+) {
+    let entity_body = |entity: u64| {
+        usize::try_from(entity)
+            .ok()
+            .and_then(|i| bodies.get(i).copied())
+    };
+    joints.sync(world, joint.map(|joint| (1, joint)), entity_body);
+}
+
+/// Verifies that a fixed joint holds a body against gravity and that
+/// removing the component releases it.
+#[test]
+fn fixed_joint_follows_component() {
+    let (mut world, bodies) = pair();
+    let weld = Joint::from(FixedJoint {
+        connected: 0,
+        local_anchor1: [-1.0, 0.0, 0.0],
+        local_anchor2: [0.0; 3],
+    });
+    let mut joints = JointSync::default();
+    sync(&mut joints, &mut world, bodies, Some(&weld));
+    assert_eq!(joints.len(), 1);
+
+    simulate(&mut world, 60);
+    assert!(world.translation(bodies[1]).unwrap()[1] > -0.05);
+
+    sync(&mut joints, &mut world, bodies, None);
+    assert!(joints.is_empty());
+    simulate(&mut world, 60);
+    assert!(world.translation(bodies[1]).unwrap()[1] < -1.0);
+}
+
+/// Verifies that a hinge swings within its limits.
+#[test]
+fn revolute_joint_respects_limits() {
+    let (mut world, bodies) = pair();
+    let hinge = Joint::from(RevoluteJoint {
+        connected: 0,
+        local_anchor1: [-1.0, 0.0, 0.0],
+        local_anchor2: [0.0; 3],
+        axis: [0.0, 0.0, 1.0],
+        limits: Some([-0.5, 0.5]),
+        motor: None,
+    });
+    let mut joints = JointSync::default();
+    sync(&mut joints, &mut world, bodies, Some(&hinge));
+    simulate(&mut world, 120);
+
+    let [x, y, _] = world.translation(bodies[1]).unwrap();
+    let angle = y.atan2(x);
+    assert!(angle < -0.3 && angle > -0.6, "swung to {angle}");
+    assert!(((x * x + y * y).sqrt() - 1.0).abs() < 0.05);
+}
+
+/// Verifies that a prismatic motor drives the body along its axis.
+#[test]
+fn prismatic_motor_drives_slide() {
+    let mut world = PhysicsWorld::new([0.0; 3]);
+    let anchor = world.insert_body(RigidBodyBuilder::fixed());
+    let slider = world.insert_body(RigidBodyBuilder::dynamic());
+    world.insert_collider(ColliderBuilder::cuboid(0.2, 0.2, 0.2), Some(slider));
+    let piston = Joint::from(PrismaticJoint {
+        connected: 0,
+        local_anchor1: [0.0; 3],
+        local_anchor2: [0.0; 3],
+        axis: [1.0, 0.0, 0.0],
+        limits: Some([-5.0, 5.0]),
+        motor: Some(JointMotor {
+            target_position: 2.0,
+            target_velocity: 0.0,
+            stiffness: 100.0,
+            damping: 20.0,
+            max_force: None,
+        }),
+    });
+    let mut joints = JointSync::default();
+    sync(&mut joints, &mut world, [anchor, slider], Some(&piston));
+    simulate(&mut world, 240);
+
+    let [x, y, z] = world.translation(slider).unwrap();
+    assert!((x.abs() - 2.0).abs() < 0.1, "slid to {x}");
+    assert!(y.abs() < 0.01 && z.abs() < 0.01);
+}
+
+/// Verifies that a spring hanging from a point above a body holds it near
+/// its rest length.
+#[test]
+fn spring_joint_settles_at_rest_length() {
+    let (mut world, [anchor, weight]) = pair();
+    // The spring belongs to the anchor and connects to the weight.
+    let spring = Joint::from(SpringJoint {
+        connected: 0,
+        local_anchor1: [1.0, 1.0, 0.0],
+        local_anchor2: [0.0; 3],
+        rest_length: 0.5,
+        stiffness: 500.0,
+        damping: 5.0,
+    });
+    let mut joints = JointSync::default();
+    sync(&mut joints, &mut world, [weight, anchor], Some(&spring));
+    simulate(&mut world, 600);
+
+    let [x, y, _] = world.translation(weight).unwrap();
+    assert!((x - 1.0).abs() < 0.05, "x = {x}");
+    assert!((y - 0.5).abs() < 0.05, "y = {y}");
+}
+
+/// Verifies that changed components rebuild their joint, invalid ones are
+/// skipped, and joints wait for both bodies to exist.
+#[test]
+fn sync_rebuilds_and_skips() {
+    let (mut world, bodies) = pair();
+    let mut hinge = RevoluteJoint {
+        connected: 0,
+        local_anchor1: [0.0; 3],
+        local_anchor2: [0.0; 3],
+        axis: [0.0, 0.0, 1.0],
+        limits: None,
+        motor: None,
+    };
+    let mut joints = JointSync::default();
+    sync(&mut joints, &mut world, bodies, Some(&hinge.into()));
+    let first = joints.handle(1).unwrap();
+    sync(&mut joints, &mut world, bodies, Some(&hinge.into()));
+    assert_eq!(joints.handle(1), Some(first));
+
+    hinge.limits = Some([0.0, 1.0]);
+    sync(&mut joints, &mut world, bodies, Some(&hinge.into()));
+    assert!(world.joint(first).is_none());
+    assert!(joints.handle(1).is_some());
+
+    hinge.axis = [0.0; 3];
+    sync(&mut joints, &mut world, bodies, Some(&hinge.into()));
+    assert!(joints.is_empty());
+
+    hinge.axis = [0.0, 0.0, 1.0];
+    hinge.connected = 7;
+    sync(&mut joints, &mut world, bodies, Some(&hinge.into()));
+    assert!(joints.is_empty());
+}
+
+/// Verifies that a joint removed along with its body is rebuilt once the
+/// body exists again.
+#[test]
+fn rebuilds_after_body_removal() {
+    let (mut world, mut bodies) = pair();
+    let weld = Joint::from(FixedJoint {
+        connected: 0,
+        local_anchor1: [0.0; 3],
+        local_anchor2: [0.0; 3],
+    });
+    let mut joints = JointSync::default();
+    sync(&mut joints, &mut world, bodies, Some(&weld));
+    let first = joints.handle(1).unwrap();
+
+    world.remove_body(bodies[1]);
+    assert!(world.joint(first).is_none());
+    bodies[1] = world.insert_body(RigidBodyBuilder::dynamic());
+    sync(&mut joints, &mut world, bodies, Some(&weld));
+    let second = joints.handle(1).unwrap();
+    assert!(world.joint(second).is_some());
+}
+
+/// Verifies that joints round-trip through scene data.
+#[test]
+fn serializes_in_scenes() {
+    let joint: Joint = toml::from_str(
+        r#"
+        type = "revolute"
+        connected = 12
+        local_anchor1 = [0.5, 0.0, 0.0]
+        axis = [0.0, 1.0, 0.0]
+        limits = [-1.5, 1.5]
+        motor = { target_velocity = 2.0, damping = 10.0 }
+        "#,
+    )
+    .unwrap();
+    let Joint::Revolute(hinge) = joint else {
+        panic!("expected a revolute joint, got {joint:?}");
+    };
+    assert_eq!(hinge.connected, 12);
+    assert_eq!(hinge.local_anchor2.map(f32::to_bits), [0; 3]);
+    assert_eq!(hinge.motor.unwrap().max_force, None);
+    assert_eq!(joint.connected(), 12);
+
+    let text = toml::to_string(&joint).unwrap();
+    assert_eq!(toml::from_str::<Joint>(&text).unwrap(), joint);
+    assert!(toml::from_str::<Joint>("type = \"rope\"\nconnected = 1").is_err());
+}
@@ -0,0 +1,284 @@
+//! Named collision layers and their interaction matrix.
+//!
+//! Projects name their collision layers in a data file and list which
+//! pairs of layers ignore each other and which only detect each other
+//! without a physical response, as sensors do. Every other pair collides:
+//!
+//! ```toml
+//! layers = ["world", "player", "enemy", "pickup"]
+//! ignore = [["enemy", "enemy"]]
+//! sensor = [["player", "pickup"]]
+//! ```
+//!
+//! Entities name their layers in a [`CollisionLayers`] component. The names
+//! are checked against the [`LayerMatrix`] when the entity is loaded, and
+//! resolved into rapier collision and solver groups for its colliders.
+//! Layer names are interned [`Label`]s, so looking one up compares
+//! pointers rather than strings.
+
+use rapier3d::prelude::*;
+use rustgine_core::Label;
+use serde::{Deserialize, Serialize};
+
+/// Most layers a matrix can hold: one per collision group bit.
+pub const MAX_LAYERS: usize = 32;
+
+/// Name of the only layer in the default matrix.
+pub const DEFAULT_LAYER: &str = "default";
+
+/// How colliders on two layers treat each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interaction {
+    /// Contacts are detected and resolved.
+    Collide,
+    /// Contacts are detected and reported as events, but not resolved.
+    Sensor,
+    /// Contacts are not detected.
+    Ignore,
+}
+
+/// The data file format of a [`LayerMatrix`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LayerConfig {
+    /// Layer names, in bit order.
+    layers: Vec<Label>,
+    /// Pairs that ignore each other.
+    #[serde(default)]
+    ignore: Vec<[Label; 2]>,
+    /// Pairs that only detect each other.
+    #[serde(default)]
+    sensor: Vec<[Label; 2]>,
+}
+
+/// Configured collision layers and how each pair interacts.
+///
+/// # Example
+///
+/// ```
+/// use physics::layers::{CollisionLayers, Interaction, LayerMatrix};
+///
+/// let matrix = LayerMatrix::from_toml(r#"
+///     layers = ["world", "player", "pickup"]
+///     sensor = [["player", "pickup"]]
+/// "#)?;
+/// assert_eq!(matrix.interaction("pickup", "player"), Some(Interaction::Sensor));
+///
+/// let layers = CollisionLayers::new(["player"]);
+/// assert!(matrix.resolve(&layers).is_ok());
+/// assert!(matrix.resolve(&CollisionLayers::new(["ghost"])).is_err());
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerMatrix {
+    /// Layer names, in bit order.
+    names: Vec<Label>,
+    /// Interaction of each pair, indexed by `a * MAX_LAYERS + b`.
+    interactions: Vec<Interaction>,
+}
+
+impl Default for LayerMatrix {
+    fn default() -> Self {
+        Self::new([DEFAULT_LAYER]).expect("default layer matrix is valid")
+    }
+}
+
+impl LayerMatrix {
+    /// Creates a matrix where every pair of the named layers collides.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a name repeats or there are more than
+    /// [`MAX_LAYERS`] names.
+    pub fn new<S: Into<Label>>(names: impl IntoIterator<Item = S>) -> anyhow::Result<Self> {
+        let names: Vec<Label> = names.into_iter().map(Into::into).collect();
+        if names.len() > MAX_LAYERS {
+            anyhow::bail!(
+                "{} collision layers configured, at most {MAX_LAYERS} are supported",
+                names.len()
+            );
+        }
+        for (index, name) in names.iter().enumerate() {
+            if names[..index].contains(name) {
+                anyhow::bail!("collision layer `{name}` is configured twice");
+            }
+        }
+        Ok(Self {
+            names,
+            interactions: vec![Interaction::Collide; MAX_LAYERS * MAX_LAYERS],
+        })
+    }
+
+    /// Parses a matrix from its data file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not a valid layer configuration or a
+    /// pair names an unknown layer.
+    pub fn from_toml(text: &str) -> anyhow::Result<Self> {
+        let config: LayerConfig =
+            toml::from_str(text).map_err(|e| anyhow::anyhow!("invalid collision layers: {e}"))?;
+        let mut matrix = Self::new(config.layers)?;
+        for ([a, b], interaction) in config
+            .ignore
+            .iter()
+            .map(|pair| (pair, Interaction::Ignore))
+            .chain(config.sensor.iter().map(|pair| (pair, Interaction::Sensor)))
+        {
+            matrix.set_interaction(*a, *b, interaction)?;
+        }
+        Ok(matrix)
+    }
+
+    /// Sets how colliders on layers `a` and `b` treat each other.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either layer is not configured.
+    pub fn set_interaction(
+        &mut self,
+        a: impl Into<Label>,
+        b: impl Into<Label>,
+        interaction: Interaction,
+    ) -> anyhow::Result<()> {
+        let a = self.require(a.into())?;
+        let b = self.require(b.into())?;
+        self.interactions[a * MAX_LAYERS + b] = interaction;
+        self.interactions[b * MAX_LAYERS + a] = interaction;
+        Ok(())
+    }
+
+    /// Returns the configured layer names, in bit order.
+    #[must_use]
+    pub fn names(&self) -> &[Label] {
+        &self.names
+    }
+
+    /// Returns the bit index of a layer.
+    #[must_use]
+    pub fn index(&self, name: impl Into<Label>) -> Option<usize> {
+        let name = name.into();
+        self.names.iter().position(|&candidate| candidate == name)
+    }
+
+    /// Returns how colliders on two layers treat each other, or `None` if
+    /// either is not configured.
+    #[must_use]
+    pub fn interaction(&self, a: impl Into<Label>, b: impl Into<Label>) -> Option<Interaction> {
+        Some(self.interactions[self.index(a)? * MAX_LAYERS + self.index(b)?])
+    }
+
+    /// Returns the bit mask of the named layers, as used by
+    /// [`QueryOptions::with_layers`](crate::QueryOptions::with_layers).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a layer is not configured.
+    pub fn mask<S: Into<Label>>(&self, names: impl IntoIterator<Item = S>) -> anyhow::Result<u32> {
+        names
+            .into_iter()
+            .try_fold(0, |mask, name| Ok(mask | 1 << self.require(name.into())?))
+    }
+
+    /// Checks that every layer named by `layers` is configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first unknown layer.
+    pub fn validate(&self, layers: &CollisionLayers) -> anyhow::Result<()> {
+        self.mask(layers.names.iter().copied()).map(|_| ())
+    }
+
+    /// Resolves a component's layer names into collider groups.
+    ///
+    /// A collider on several layers interacts with another layer in the
+    /// most permissive way any of its layers does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a layer is not configured.
+    pub fn resolve(&self, layers: &CollisionLayers) -> anyhow::Result<ResolvedLayers> {
+        let memberships = self.mask(layers.names.iter().copied())?;
+        let mut detect = 0;
+        let mut solve = 0;
+        for member in (0..self.names.len()).filter(|bit| memberships & 1 << bit != 0) {
+            for other in 0..self.names.len() {
+                match self.interactions[member * MAX_LAYERS + other] {
+                    Interaction::Collide => {
+                        detect |= 1 << other;
+                        solve |= 1 << other;
+                    }
+                    Interaction::Sensor => detect |= 1 << other,
+                    Interaction::Ignore => {}
+                }
+            }
+        }
+        let group = Group::from_bits_retain;
+        Ok(ResolvedLayers {
+            collision_groups: InteractionGroups::new(group(memberships), group(detect)),
+            solver_groups: InteractionGroups::new(group(memberships), group(solve)),
+        })
+    }
+
+    fn require(&self, name: Label) -> anyhow::Result<usize> {
+        self.index(name).ok_or_else(|| {
+            let names: Vec<&str> = self.names.iter().map(|name| name.as_str()).collect();
+            anyhow::anyhow!(
+                "unknown collision layer `{name}`, configured layers are: {}",
+                names.join(", ")
+            )
+        })
+    }
+}
+
+/// Component naming the collision layers of an entity's colliders.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CollisionLayers {
+    /// Names of the layers the entity is on.
+    pub names: Vec<Label>,
+}
+
+impl Default for CollisionLayers {
+    fn default() -> Self {
+        Self::new([DEFAULT_LAYER])
+    }
+}
+
+impl CollisionLayers {
+    /// Puts an entity on the named layers.
+    #[must_use]
+    pub fn new<S: Into<Label>>(names: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            names: names.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Collider groups resolved from a [`CollisionLayers`] component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolvedLayers {
+    /// Groups deciding which contacts are detected.
+    pub collision_groups: InteractionGroups,
+    /// Groups deciding which detected contacts are resolved.
+    pub solver_groups: InteractionGroups,
+}
+
+impl ResolvedLayers {
+    /// Returns `true` if some detected contacts are not resolved, so the
+    /// collider needs collision events to report them.
+    #[must_use]
+    pub fn has_sensor_pairs(&self) -> bool {
+        self.collision_groups.filter != self.solver_groups.filter
+    }
+
+    /// Applies the groups to a collider, enabling collision events when
+    /// some of its pairs only detect contacts.
+    pub fn apply(&self, collider: &mut Collider) {
+        collider.set_collision_groups(self.collision_groups);
+        collider.set_solver_groups(self.solver_groups);
+        if self.has_sensor_pairs() {
+            collider.set_active_events(collider.active_events() | ActiveEvents::COLLISION_EVENTS);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Unit tests for collision layers.
+
+use crate::layers::{CollisionLayers, Interaction, LayerMatrix, MAX_LAYERS};
+use crate::world::PhysicsWorld;
+use rapier3d::prelude::*;
+use std::time::Duration;
+
+const CONFIG: &str = r#"
+layers = ["world", "player", "enemy", "pickup"]
+ignore = [["enemy", "enemy"]]
+sensor = [["player", "pickup"]]
+"#;
+
+/// Verifies that the data file sets symmetric pair interactions.
+#[test]
+fn parses_interaction_matrix() {
+    let matrix = LayerMatrix::from_toml(CONFIG).unwrap();
+    assert_eq!(matrix.names(), ["world", "player", "enemy", "pickup"]);
+    assert_eq!(matrix.index("enemy"), Some(2));
+    assert_eq!(
+        matrix.interaction("world", "player"),
+        Some(Interaction::Collide)
+    );
+    assert_eq!(
+        matrix.interaction("enemy", "enemy"),
+        Some(Interaction::Ignore)
+    );
+    assert_eq!(
+        matrix.interaction("pickup", "player"),
+        Some(Interaction::Sensor)
+    );
+    assert_eq!(
+        matrix.interaction("player", "pickup"),
+        Some(Interaction::Sensor)
+    );
+    assert_eq!(matrix.interaction("player", "ghost"), None);
+    assert_eq!(matrix.mask(["player", "pickup"]).unwrap(), 0b1010);
+}
+
+/// Verifies that invalid configurations are rejected with the offending
+/// name.
+#[test]
+fn rejects_invalid_configurations() {
+    let error = LayerMatrix::from_toml("layers = [\"a\", \"a\"]").unwrap_err();
+    assert!(error.to_string().contains("`a`"), "{error}");
+
+    let error = LayerMatrix::from_toml("layers = [\"a\"]\nsensor = [[\"a\", \"b\"]]").unwrap_err();
+    assert!(
+        error.to_string().contains("unknown collision layer `b`"),
+        "{error}"
+    );
+
+    assert!(LayerMatrix::from_toml("layers = [\"a\"]\nsolid = []").is_err());
+    assert!(LayerMatrix::new((0..=MAX_LAYERS).map(|i| i.to_string())).is_err());
+    assert!(LayerMatrix::new((0..MAX_LAYERS).map(|i| i.to_string())).is_ok());
+}
+
+/// Verifies that components are validated against the configured names.
+#[test]
+fn validates_components() {
+    let matrix = LayerMatrix::from_toml(CONFIG).unwrap();
+    assert!(matrix
+        .validate(&CollisionLayers::new(["player", "enemy"]))
+        .is_ok());
+
+    let error = matrix
+        .validate(&CollisionLayers::new(["player", "npc"]))
+        .unwrap_err();
+    assert!(error.to_string().contains("`npc`"), "{error}");
+    assert!(
+        error.to_string().contains("world, player, enemy, pickup"),
+        "{error}"
+    );
+
+    assert!(LayerMatrix::default()
+        .validate(&CollisionLayers::default())
+        .is_ok());
+}
+
+/// Verifies that components deserialize from a plain list of names.
+#[test]
+fn deserializes_components() {
+    #[derive(serde::Deserialize)]
+    struct Entity {
+        layers: CollisionLayers,
+    }
+    let entity: Entity = toml::from_str("layers = [\"enemy\"]").unwrap();
+    assert_eq!(entity.layers, CollisionLayers::new(["enemy"]));
+}
+
+/// Verifies that resolved groups detect sensor pairs without resolving
+/// them.
+#[test]
+fn resolves_groups() {
+    let matrix = LayerMatrix::from_toml(CONFIG).unwrap();
+
+    let player = matrix.resolve(&CollisionLayers::new(["player"])).unwrap();
+    assert_eq!(player.collision_groups.memberships.bits(), 0b0010);
+    assert_eq!(player.collision_groups.filter.bits(), 0b1111);
+    assert_eq!(player.solver_groups.filter.bits(), 0b0111);
+    assert!(player.has_sensor_pairs());
+
+    let enemy = matrix.resolve(&CollisionLayers::new(["enemy"])).unwrap();
+    assert_eq!(enemy.collision_groups.filter.bits(), 0b1011);
+    assert!(!enemy.has_sensor_pairs());
+    assert!(!enemy.collision_groups.test(enemy.collision_groups));
+}
+
+/// Verifies that a body falls through colliders it only senses and lands
+/// on colliders it collides with.
+#[test]
+fn sensor_pairs_do_not_block() {
+    let matrix = LayerMatrix::from_toml(CONFIG).unwrap();
+    let fall_onto = |layer: &str| {
+        let mut world = PhysicsWorld::default();
+        let ground = world.insert_collider(ColliderBuilder::cuboid(5.0, 0.5, 5.0), None);
+        let ball =
+            world.insert_body(RigidBodyBuilder::dynamic().translation(vector![0.0, 2.0, 0.0]));
+        let shape = world.insert_collider(ColliderBuilder::ball(0.5), Some(ball));
+        let ground_layers = matrix.resolve(&CollisionLayers::new([layer])).unwrap();
+        let ball_layers = matrix.resolve(&CollisionLayers::new(["player"])).unwrap();
+        ground_layers.apply(world.collider_mut(ground).unwrap());
+        ball_layers.apply(world.collider_mut(shape).unwrap());
+        for _ in 0..120 {
+            world.step(Duration::from_secs_f32(1.0 / 60.0));
+        }
+        world.translation(ball).unwrap()[1]
+    };
+
+    assert!(fall_onto("world") > 0.9);
+    assert!(fall_onto("pickup") < -2.0);
+}
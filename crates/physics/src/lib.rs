@@ -0,0 +1,65 @@
+//! Physics subsystem for the Rustgine game engine.
+//!
+//! This crate integrates the rapier physics engine with the engine's fixed
+//! simulation step.
+//!
+//! # Overview
+//!
+//! The physics crate handles:
+//! - Rigid bodies and colliders stepped together ([`PhysicsWorld`])
+//! - Kinematic characters with step climbing, slope limits, and
+//!   moving-platform support ([`CharacterController`])
+//! - Raycasts, shape casts, and overlap queries filtered by collision layer,
+//!   with debug drawing of recent queries ([`QueryOptions`])
+//! - Smooth rendering of bodies between fixed steps
+//!   ([`TransformInterpolation`])
+//! - Fixed, revolute, prismatic, and spring joint components kept in step
+//!   with their entities ([`JointSync`])
+//! - Named collision layers with a data-driven interaction matrix
+//!   ([`LayerMatrix`])
+//! - State checksums for detecting lockstep desyncs
+//!   ([`PhysicsWorld::checksum`]), deterministic across platforms with the
+//!   `deterministic` feature
+//!
+//! # Example
+//!
+//! ```ignore
+//! use physics::RustginePhysics;
+//! use rustgine_core::RustgineSystem;
+//!
+//! let mut physics = RustginePhysics::default();
+//! physics.startup()?;
+//! ```
+
+#![warn(missing_docs)]
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+pub mod character;
+#[cfg(test)]
+mod character_test;
+pub mod interpolation;
+#[cfg(test)]
+mod interpolation_test;
+pub mod joints;
+#[cfg(test)]
+mod joints_test;
+pub mod layers;
+#[cfg(test)]
+mod layers_test;
+pub mod physics;
+pub mod query;
+#[cfg(test)]
+mod query_test;
+pub mod world;
+#[cfg(test)]
+mod world_test;
+
+pub use character::CharacterController;
+pub use interpolation::{BodyTransform, TransformInterpolation};
+pub use joints::{Joint, JointSync};
+pub use layers::{CollisionLayers, LayerMatrix};
+pub use physics::RustginePhysics;
+pub use query::{QueryHit, QueryOptions, QueryShape};
+pub use rapier3d;
+pub use world::PhysicsWorld;
@@ -0,0 +1,118 @@
+//! Physics subsystem implementation.
+//!
+//! Provides the [`RustginePhysics`] system, which owns the simulation rate,
+//! world gravity, and collision layers.
+
+use crate::layers::LayerMatrix;
+use crate::world::DEFAULT_GRAVITY;
+use rustgine_core::{RustgineError, RustgineSystem};
+use tracing::info;
+
+/// Default physics steps per second.
+pub const DEFAULT_TICK_RATE: u32 = 60;
+
+/// Physics subsystem for the Rustgine engine.
+///
+/// Manages:
+/// - Rigid body and collider simulation
+/// - Kinematic character movement
+/// - Fixed-rate stepping independent of frame rate
+///
+/// # Example
+///
+/// ```ignore
+/// use physics::RustginePhysics;
+/// use rustgine_core::RustgineSystem;
+///
+/// let mut physics = RustginePhysics::default();
+/// physics.startup()?;
+/// ```
+#[derive(Debug)]
+pub struct RustginePhysics {
+    /// Simulation steps per second.
+    tick_rate: u32,
+    /// Gravity acceleration in units per second squared.
+    gravity: [f32; 3],
+    /// Configured collision layers.
+    layers: LayerMatrix,
+}
+
+impl Default for RustginePhysics {
+    fn default() -> Self {
+        Self {
+            tick_rate: DEFAULT_TICK_RATE,
+            gravity: DEFAULT_GRAVITY,
+            layers: LayerMatrix::default(),
+        }
+    }
+}
+
+impl RustginePhysics {
+    /// Sets how many times per second the simulation steps.
+    #[must_use]
+    pub fn with_tick_rate(mut self, tick_rate: u32) -> Self {
+        self.tick_rate = tick_rate.max(1);
+        self
+    }
+
+    /// Sets the world gravity.
+    #[must_use]
+    pub fn with_gravity(mut self, gravity: [f32; 3]) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    /// Sets the configured collision layers.
+    #[must_use]
+    pub fn with_layers(mut self, layers: LayerMatrix) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Returns how many times per second the simulation steps.
+    #[must_use]
+    #[inline]
+    pub fn tick_rate(&self) -> u32 {
+        self.tick_rate
+    }
+
+    /// Returns the world gravity.
+    #[must_use]
+    #[inline]
+    pub fn gravity(&self) -> [f32; 3] {
+        self.gravity
+    }
+
+    /// Returns the configured collision layers.
+    #[must_use]
+    #[inline]
+    pub fn layers(&self) -> &LayerMatrix {
+        &self.layers
+    }
+}
+
+impl RustgineSystem for RustginePhysics {
+    /// Initializes the physics subsystem.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if physics initialization fails.
+    fn startup(&mut self) -> Result<(), RustgineError> {
+        info!(
+            tick_rate = self.tick_rate,
+            layers = self.layers.names().len(),
+            "physics started"
+        );
+        Ok(())
+    }
+
+    /// Shuts down the physics subsystem.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if cleanup fails.
+    #[inline]
+    fn shutdown(&mut self) -> Result<(), RustgineError> {
+        Ok(())
+    }
+}
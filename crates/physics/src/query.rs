@@ -0,0 +1,412 @@
+//! Scene queries against the physics world.
+//!
+//! Gameplay systems and scripts ask the [`PhysicsWorld`] what a ray or a
+//! moving shape hits first, or which colliders overlap a sphere. Queries
+//! only see colliders on the layers they ask for, and every result names
+//! the entity owning the collider that was found.
+//!
+//! Queries see the world as of the last step or
+//! [`PhysicsWorld::update_queries`]. While query recording is enabled, as it
+//! is by default in debug builds, the world keeps the queries made since the
+//! last step so they can be drawn with [`QueryRecord::debug_lines`].
+
+use crate::world::PhysicsWorld;
+use math::Color;
+use rapier3d::parry::query::ShapeCastOptions;
+use rapier3d::prelude::*;
+
+/// Queries recorded per step at most; older ones are dropped.
+pub const MAX_RECENT_QUERIES: usize = 256;
+
+/// Segments used to draw one debug circle.
+const CIRCLE_SEGMENTS: usize = 16;
+
+/// Debug color of queries that hit something.
+const HIT_COLOR: Color = Color::linear_rgb(1.0, 0.2, 0.2);
+
+/// Debug color of queries that hit nothing.
+const MISS_COLOR: Color = Color::linear_rgb(0.2, 1.0, 0.2);
+
+/// Which colliders a query considers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryOptions {
+    /// Bit mask of collision layers to search.
+    pub layers: u32,
+    /// Whether sensor colliders are reported.
+    pub include_sensors: bool,
+    /// Body whose colliders are skipped, usually the querying entity's own.
+    pub exclude_body: Option<RigidBodyHandle>,
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        Self {
+            layers: u32::MAX,
+            include_sensors: false,
+            exclude_body: None,
+        }
+    }
+}
+
+impl QueryOptions {
+    /// Searches only the layers in `layers`.
+    #[must_use]
+    pub fn with_layers(mut self, layers: u32) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Reports sensor colliders too.
+    #[must_use]
+    pub fn with_sensors(mut self) -> Self {
+        self.include_sensors = true;
+        self
+    }
+
+    /// Skips the colliders of `body`.
+    #[must_use]
+    pub fn excluding(mut self, body: RigidBodyHandle) -> Self {
+        self.exclude_body = Some(body);
+        self
+    }
+
+    /// Returns the equivalent rapier filter.
+    fn filter(&self) -> QueryFilter<'static> {
+        let mut filter = QueryFilter::default().groups(InteractionGroups::new(
+            Group::ALL,
+            Group::from_bits_retain(self.layers),
+        ));
+        if !self.include_sensors {
+            filter = filter.exclude_sensors();
+        }
+        if let Some(body) = self.exclude_body {
+            filter = filter.exclude_rigid_body(body);
+        }
+        filter
+    }
+}
+
+/// Shape swept by [`PhysicsWorld::shape_cast`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueryShape {
+    /// A sphere of the given radius.
+    Sphere(f32),
+    /// An axis-aligned box with the given half extents.
+    Box([f32; 3]),
+    /// A capsule along the `y` axis.
+    Capsule {
+        /// Half the height of the straight section.
+        half_height: f32,
+        /// Radius of the caps.
+        radius: f32,
+    },
+}
+
+impl QueryShape {
+    fn to_shape(self) -> SharedShape {
+        match self {
+            Self::Sphere(radius) => SharedShape::ball(radius),
+            Self::Box([x, y, z]) => SharedShape::cuboid(x, y, z),
+            Self::Capsule {
+                half_height,
+                radius,
+            } => SharedShape::capsule_y(half_height, radius),
+        }
+    }
+
+    /// Returns the radius of a sphere bounding the shape.
+    fn bounding_radius(self) -> f32 {
+        match self {
+            Self::Sphere(radius) => radius,
+            Self::Box(half_extents) => length(half_extents),
+            Self::Capsule {
+                half_height,
+                radius,
+            } => half_height + radius,
+        }
+    }
+}
+
+/// The first collider hit by a ray or shape cast.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueryHit {
+    /// Collider hit.
+    pub collider: ColliderHandle,
+    /// Entity owning the collider, if tagged.
+    pub entity: Option<u64>,
+    /// World position of the contact.
+    pub point: [f32; 3],
+    /// Outward surface normal of the collider at the contact.
+    pub normal: [f32; 3],
+    /// Distance travelled before the hit.
+    pub distance: f32,
+}
+
+/// A collider found by an overlap query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overlap {
+    /// Collider overlapping the query volume.
+    pub collider: ColliderHandle,
+    /// Entity owning the collider, if tagged.
+    pub entity: Option<u64>,
+}
+
+/// The volume a recorded query searched.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueryKind {
+    /// A ray from `origin` to `end`.
+    Ray {
+        /// Start of the ray.
+        origin: [f32; 3],
+        /// Farthest point searched.
+        end: [f32; 3],
+    },
+    /// A shape swept from `origin` to `end`.
+    ShapeCast {
+        /// Shape swept.
+        shape: QueryShape,
+        /// Start of the sweep.
+        origin: [f32; 3],
+        /// Farthest point searched.
+        end: [f32; 3],
+    },
+    /// A sphere overlap test.
+    Sphere {
+        /// Center of the sphere.
+        center: [f32; 3],
+        /// Radius of the sphere.
+        radius: f32,
+    },
+}
+
+/// A scene query kept for debug drawing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryRecord {
+    /// Volume searched.
+    pub kind: QueryKind,
+    /// Contact points found.
+    pub hits: Vec<[f32; 3]>,
+}
+
+/// A colored line segment for debug drawing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebugLine {
+    /// Start of the segment.
+    pub start: [f32; 3],
+    /// End of the segment.
+    pub end: [f32; 3],
+    /// Line color.
+    pub color: Color,
+}
+
+impl QueryRecord {
+    /// Returns line segments outlining the query: red where it hit
+    /// something, green where it did not, with a cross at each hit.
+    #[must_use]
+    pub fn debug_lines(&self) -> Vec<DebugLine> {
+        let color = if self.hits.is_empty() {
+            MISS_COLOR
+        } else {
+            HIT_COLOR
+        };
+        let mut lines = Vec::new();
+        match self.kind {
+            QueryKind::Ray { origin, end } => lines.push(DebugLine {
+                start: origin,
+                end,
+                color,
+            }),
+            QueryKind::ShapeCast { shape, origin, end } => {
+                let radius = shape.bounding_radius();
+                lines.push(DebugLine {
+                    start: origin,
+                    end,
+                    color,
+                });
+                circles(&mut lines, origin, radius, color);
+                circles(&mut lines, end, radius, color);
+            }
+            QueryKind::Sphere { center, radius } => circles(&mut lines, center, radius, color),
+        }
+        for &hit in &self.hits {
+            for axis in 0..3 {
+                let mut start = hit;
+                let mut end = hit;
+                start[axis] -= 0.1;
+                end[axis] += 0.1;
+                lines.push(DebugLine {
+                    start,
+                    end,
+                    color: HIT_COLOR,
+                });
+            }
+        }
+        lines
+    }
+}
+
+impl PhysicsWorld {
+    /// Returns the first collider hit by a ray from `origin` along
+    /// `direction`, within `max_distance`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use physics::query::QueryOptions;
+    /// use physics::rapier3d::prelude::*;
+    /// use physics::PhysicsWorld;
+    ///
+    /// let mut world = PhysicsWorld::default();
+    /// let wall = world.insert_collider(ColliderBuilder::cuboid(0.5, 5.0, 5.0).translation(vector![5.0, 0.0, 0.0]), None);
+    /// world.set_entity(wall, 42);
+    /// world.update_queries();
+    ///
+    /// let hit = world.raycast([0.0; 3], [1.0, 0.0, 0.0], 100.0, QueryOptions::default()).unwrap();
+    /// assert_eq!(hit.entity, Some(42));
+    /// assert!((hit.distance - 4.5).abs() < 1e-4);
+    /// ```
+    #[must_use]
+    pub fn raycast(
+        &self,
+        origin: [f32; 3],
+        direction: [f32; 3],
+        max_distance: f32,
+        options: QueryOptions,
+    ) -> Option<QueryHit> {
+        let direction = normalize(direction)?;
+        let (bodies, colliders, queries) = self.query_view();
+        let ray = Ray::new(origin.into(), direction);
+        let hit = queries
+            .cast_ray_and_get_normal(
+                bodies,
+                colliders,
+                &ray,
+                max_distance,
+                true,
+                options.filter(),
+            )
+            .map(|(collider, intersection)| QueryHit {
+                collider,
+                entity: self.entity(collider),
+                point: ray.point_at(intersection.time_of_impact).into(),
+                normal: intersection.normal.into(),
+                distance: intersection.time_of_impact,
+            });
+        self.record_query(|| QueryRecord {
+            kind: QueryKind::Ray {
+                origin,
+                end: ray.point_at(max_distance).into(),
+            },
+            hits: hit.iter().map(|hit| hit.point).collect(),
+        });
+        hit
+    }
+
+    /// Returns the first collider hit by `shape` swept from `origin` along
+    /// `direction`, within `max_distance`.
+    #[must_use]
+    pub fn shape_cast(
+        &self,
+        shape: QueryShape,
+        origin: [f32; 3],
+        direction: [f32; 3],
+        max_distance: f32,
+        options: QueryOptions,
+    ) -> Option<QueryHit> {
+        let direction = normalize(direction)?;
+        let (bodies, colliders, queries) = self.query_view();
+        let start = Isometry::translation(origin[0], origin[1], origin[2]);
+        let hit = queries
+            .cast_shape(
+                bodies,
+                colliders,
+                &start,
+                &direction,
+                shape.to_shape().as_ref(),
+                ShapeCastOptions::with_max_time_of_impact(max_distance),
+                options.filter(),
+            )
+            .map(|(collider, hit)| QueryHit {
+                collider,
+                entity: self.entity(collider),
+                point: hit.witness1.into(),
+                normal: hit.normal1.into_inner().into(),
+                distance: hit.time_of_impact,
+            });
+        self.record_query(|| QueryRecord {
+            kind: QueryKind::ShapeCast {
+                shape,
+                origin,
+                end: (Vector::from(origin) + direction * max_distance).into(),
+            },
+            hits: hit.iter().map(|hit| hit.point).collect(),
+        });
+        hit
+    }
+
+    /// Returns the colliders overlapping a sphere.
+    #[must_use]
+    pub fn overlap_sphere(
+        &self,
+        center: [f32; 3],
+        radius: f32,
+        options: QueryOptions,
+    ) -> Vec<Overlap> {
+        let (bodies, colliders, queries) = self.query_view();
+        let position = Isometry::translation(center[0], center[1], center[2]);
+        let mut overlaps = Vec::new();
+        queries.intersections_with_shape(
+            bodies,
+            colliders,
+            &position,
+            &Ball::new(radius),
+            options.filter(),
+            |collider| {
+                overlaps.push(Overlap {
+                    collider,
+                    entity: self.entity(collider),
+                });
+                true
+            },
+        );
+        self.record_query(|| QueryRecord {
+            kind: QueryKind::Sphere { center, radius },
+            hits: overlaps
+                .iter()
+                .filter_map(|overlap| colliders.get(overlap.collider))
+                .map(|collider| (*collider.translation()).into())
+                .collect(),
+        });
+        overlaps
+    }
+}
+
+/// Appends three axis-aligned circles outlining a sphere.
+#[allow(clippy::cast_precision_loss)]
+fn circles(lines: &mut Vec<DebugLine>, center: [f32; 3], radius: f32, color: Color) {
+    let point = |axes: (usize, usize), segment: usize| {
+        let angle = segment as f32 / CIRCLE_SEGMENTS as f32 * std::f32::consts::TAU;
+        let mut point = center;
+        point[axes.0] += radius * angle.cos();
+        point[axes.1] += radius * angle.sin();
+        point
+    };
+    for axes in [(0, 1), (1, 2), (2, 0)] {
+        for segment in 0..CIRCLE_SEGMENTS {
+            lines.push(DebugLine {
+                start: point(axes, segment),
+                end: point(axes, segment + 1),
+                color,
+            });
+        }
+    }
+}
+
+fn normalize(direction: [f32; 3]) -> Option<Vector<Real>> {
+    let length = length(direction);
+    (length > f32::EPSILON).then(|| Vector::from(direction) / length)
+}
+
+fn length(v: [f32; 3]) -> f32 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
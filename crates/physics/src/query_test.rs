@@ -0,0 +1,166 @@
+//! Unit tests for physics scene queries.
+
+use crate::query::{QueryKind, QueryOptions, QueryShape};
+use crate::world::PhysicsWorld;
+use rapier3d::prelude::*;
+
+const WALLS: u32 = 0b01;
+const PROPS: u32 = 0b10;
+
+/// Builds a world with a wall at `x = 5` and a crate at `x = 2`, on
+/// different layers and tagged as entities 1 and 2.
+fn scene() -> (PhysicsWorld, ColliderHandle, ColliderHandle) {
+    let mut world = PhysicsWorld::default();
+    let wall = world.insert_collider(
+        ColliderBuilder::cuboid(0.5, 5.0, 5.0)
+            .translation(vector![5.0, 0.0, 0.0])
+            .collision_groups(InteractionGroups::new(
+                Group::from_bits_retain(WALLS),
+                Group::ALL,
+            )),
+        None,
+    );
+    let prop = world.insert_collider(
+        ColliderBuilder::cuboid(0.5, 0.5, 0.5)
+            .translation(vector![2.0, 0.0, 0.0])
+            .collision_groups(InteractionGroups::new(
+                Group::from_bits_retain(PROPS),
+                Group::ALL,
+            )),
+        None,
+    );
+    world.set_entity(wall, 1);
+    world.set_entity(prop, 2);
+    world.update_queries();
+    (world, wall, prop)
+}
+
+/// Verifies that raycasts report the nearest hit on the requested layers.
+#[test]
+fn raycast_filters_layers() {
+    let (world, wall, prop) = scene();
+
+    let hit = world
+        .raycast([0.0; 3], [2.0, 0.0, 0.0], 100.0, QueryOptions::default())
+        .unwrap();
+    assert_eq!((hit.collider, hit.entity), (prop, Some(2)));
+    assert!((hit.distance - 1.5).abs() < 1e-4);
+    assert!((hit.normal[0] + 1.0).abs() < 1e-4);
+
+    let hit = world
+        .raycast(
+            [0.0; 3],
+            [1.0, 0.0, 0.0],
+            100.0,
+            QueryOptions::default().with_layers(WALLS),
+        )
+        .unwrap();
+    assert_eq!((hit.collider, hit.entity), (wall, Some(1)));
+    assert!((hit.point[0] - 4.5).abs() < 1e-4);
+
+    assert!(world
+        .raycast([0.0; 3], [1.0, 0.0, 0.0], 1.0, QueryOptions::default())
+        .is_none());
+    assert!(world
+        .raycast([0.0; 3], [0.0; 3], 100.0, QueryOptions::default())
+        .is_none());
+}
+
+/// Verifies that shape casts stop where the swept shape first touches.
+#[test]
+fn shape_cast_hits_first_contact() {
+    let (world, _, prop) = scene();
+
+    let hit = world
+        .shape_cast(
+            QueryShape::Sphere(0.5),
+            [0.0; 3],
+            [1.0, 0.0, 0.0],
+            10.0,
+            QueryOptions::default(),
+        )
+        .unwrap();
+    assert_eq!(hit.collider, prop);
+    assert!(
+        (hit.distance - 1.0).abs() < 1e-3,
+        "distance {}",
+        hit.distance
+    );
+
+    let passes_over = world.shape_cast(
+        QueryShape::Box([0.25; 3]),
+        [0.0, 1.0, 0.0],
+        [1.0, 0.0, 0.0],
+        3.0,
+        QueryOptions::default(),
+    );
+    assert!(passes_over.is_none());
+}
+
+/// Verifies that sphere overlaps find colliders in range and skip sensors
+/// and excluded bodies unless asked.
+#[test]
+fn overlap_sphere_finds_colliders() {
+    let (mut world, _, prop) = scene();
+    let sensor = world.insert_collider(ColliderBuilder::ball(0.5).sensor(true), None);
+    let body = world.insert_body(RigidBodyBuilder::fixed().translation(vector![0.0, 1.0, 0.0]));
+    world.insert_collider(ColliderBuilder::ball(0.5), Some(body));
+    world.update_queries();
+
+    let options = QueryOptions::default().excluding(body);
+    let found: Vec<_> = world
+        .overlap_sphere([0.0; 3], 2.0, options)
+        .into_iter()
+        .map(|overlap| (overlap.collider, overlap.entity))
+        .collect();
+    assert_eq!(found, [(prop, Some(2))]);
+
+    let mut found: Vec<_> = world
+        .overlap_sphere([0.0; 3], 2.0, options.with_sensors())
+        .into_iter()
+        .map(|overlap| overlap.collider)
+        .collect();
+    found.sort_by_key(|collider| collider.into_raw_parts());
+    assert_eq!(found, [prop, sensor]);
+
+    assert_eq!(
+        world
+            .overlap_sphere([0.0; 3], 2.0, QueryOptions::default())
+            .len(),
+        2
+    );
+}
+
+/// Verifies that removed colliders lose their entity tag.
+#[test]
+fn removal_clears_entities() {
+    let (mut world, wall, _) = scene();
+    world.remove_collider(wall);
+    assert_eq!(world.entity(wall), None);
+}
+
+/// Verifies that queries are recorded for debug drawing until the next step.
+#[test]
+fn records_recent_queries() {
+    let (mut world, _, _) = scene();
+    world.set_query_debug(true);
+    let _ = world.raycast([0.0; 3], [1.0, 0.0, 0.0], 100.0, QueryOptions::default());
+    let _ = world.raycast([0.0; 3], [-1.0, 0.0, 0.0], 100.0, QueryOptions::default());
+    let _ = world.overlap_sphere([0.0; 3], 1.0, QueryOptions::default());
+
+    let recent = world.recent_queries();
+    assert_eq!(recent.len(), 3);
+    assert!(matches!(recent[0].kind, QueryKind::Ray { .. }));
+    assert_eq!(recent[0].hits.len(), 1);
+    assert!(recent[1].hits.is_empty());
+    assert!(matches!(recent[2].kind, QueryKind::Sphere { .. }));
+    assert_eq!(recent[0].debug_lines().len(), 4);
+    assert_eq!(recent[2].debug_lines().len(), 48);
+
+    world.step(std::time::Duration::from_millis(16));
+    assert!(world.recent_queries().is_empty());
+
+    world.set_query_debug(false);
+    let _ = world.raycast([0.0; 3], [1.0, 0.0, 0.0], 100.0, QueryOptions::default());
+    assert!(world.recent_queries().is_empty());
+}
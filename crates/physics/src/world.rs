@@ -0,0 +1,343 @@
+//! The simulated physics world.
+//!
+//! [`PhysicsWorld`] bundles the rapier body, collider, and joint sets with
+//! the pipelines that step them. Gameplay code inserts bodies built with
+//! rapier's builders and advances the world once per fixed step.
+//!
+//! Colliders can be tagged with the id of the entity they belong to, so
+//! scene queries and contacts lead back to the owning entity.
+
+use crate::query::{QueryRecord, MAX_RECENT_QUERIES};
+use math::StateChecksum;
+use rapier3d::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+/// Default gravity: Earth's, pulling down the `y` axis.
+pub const DEFAULT_GRAVITY: [f32; 3] = [0.0, -9.81, 0.0];
+
+/// Rigid bodies and colliders simulated together.
+///
+/// # Example
+///
+/// ```
+/// use physics::rapier3d::prelude::*;
+/// use physics::PhysicsWorld;
+/// use std::time::Duration;
+///
+/// let mut world = PhysicsWorld::default();
+/// let ball = world.insert_body(RigidBodyBuilder::dynamic().translation(vector![0.0, 10.0, 0.0]));
+/// world.insert_collider(ColliderBuilder::ball(0.5), Some(ball));
+///
+/// world.step(Duration::from_secs_f32(1.0 / 60.0));
+/// assert!(world.translation(ball).unwrap()[1] < 10.0);
+/// ```
+pub struct PhysicsWorld {
+    /// Gravity acceleration.
+    gravity: Vector<Real>,
+    /// Solver settings, including the step length.
+    integration_parameters: IntegrationParameters,
+    /// Runs the simulation steps.
+    pipeline: PhysicsPipeline,
+    /// Tracks sleeping and awake body islands.
+    islands: IslandManager,
+    /// Finds potentially colliding pairs.
+    broad_phase: DefaultBroadPhase,
+    /// Computes contacts of colliding pairs.
+    narrow_phase: NarrowPhase,
+    /// All rigid bodies.
+    bodies: RigidBodySet,
+    /// All colliders.
+    colliders: ColliderSet,
+    /// Joints solved as impulses.
+    impulse_joints: ImpulseJointSet,
+    /// Joints solved in reduced coordinates.
+    multibody_joints: MultibodyJointSet,
+    /// Continuous collision detection for fast bodies.
+    ccd_solver: CCDSolver,
+    /// Acceleration structure for scene queries.
+    query_pipeline: QueryPipeline,
+    /// Whether colliders changed since the query pipeline was updated.
+    queries_dirty: bool,
+    /// Entity owning each tagged collider.
+    entities: HashMap<ColliderHandle, u64>,
+    /// Whether scene queries are recorded for debug drawing.
+    query_debug: bool,
+    /// Scene queries made since the last step.
+    recent_queries: Mutex<VecDeque<QueryRecord>>,
+}
+
+impl std::fmt::Debug for PhysicsWorld {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PhysicsWorld")
+            .field("gravity", &self.gravity)
+            .field("bodies", &self.bodies.len())
+            .field("colliders", &self.colliders.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for PhysicsWorld {
+    fn default() -> Self {
+        Self::new(DEFAULT_GRAVITY)
+    }
+}
+
+impl PhysicsWorld {
+    /// Creates an empty world with the given gravity.
+    #[must_use]
+    pub fn new(gravity: [f32; 3]) -> Self {
+        Self {
+            gravity: gravity.into(),
+            integration_parameters: IntegrationParameters::default(),
+            pipeline: PhysicsPipeline::new(),
+            islands: IslandManager::new(),
+            broad_phase: DefaultBroadPhase::new(),
+            narrow_phase: NarrowPhase::new(),
+            bodies: RigidBodySet::new(),
+            colliders: ColliderSet::new(),
+            impulse_joints: ImpulseJointSet::new(),
+            multibody_joints: MultibodyJointSet::new(),
+            ccd_solver: CCDSolver::new(),
+            query_pipeline: QueryPipeline::new(),
+            queries_dirty: false,
+            entities: HashMap::new(),
+            query_debug: cfg!(debug_assertions),
+            recent_queries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Returns the gravity acceleration.
+    #[must_use]
+    pub fn gravity(&self) -> [f32; 3] {
+        self.gravity.into()
+    }
+
+    /// Adds a rigid body.
+    pub fn insert_body(&mut self, body: impl Into<RigidBody>) -> RigidBodyHandle {
+        self.bodies.insert(body)
+    }
+
+    /// Adds a collider, attached to `parent` or fixed in the world.
+    pub fn insert_collider(
+        &mut self,
+        collider: impl Into<Collider>,
+        parent: Option<RigidBodyHandle>,
+    ) -> ColliderHandle {
+        self.queries_dirty = true;
+        match parent {
+            Some(parent) => self
+                .colliders
+                .insert_with_parent(collider, parent, &mut self.bodies),
+            None => self.colliders.insert(collider),
+        }
+    }
+
+    /// Removes a rigid body with its colliders and joints.
+    pub fn remove_body(&mut self, handle: RigidBodyHandle) -> Option<RigidBody> {
+        self.queries_dirty = true;
+        if let Some(body) = self.bodies.get(handle) {
+            for collider in body.colliders() {
+                self.entities.remove(collider);
+            }
+        }
+        self.bodies.remove(
+            handle,
+            &mut self.islands,
+            &mut self.colliders,
+            &mut self.impulse_joints,
+            &mut self.multibody_joints,
+            true,
+        )
+    }
+
+    /// Removes a collider.
+    pub fn remove_collider(&mut self, handle: ColliderHandle) -> Option<Collider> {
+        self.queries_dirty = true;
+        self.entities.remove(&handle);
+        self.colliders
+            .remove(handle, &mut self.islands, &mut self.bodies, true)
+    }
+
+    /// Connects two rigid bodies with a joint.
+    pub fn insert_joint(
+        &mut self,
+        body1: RigidBodyHandle,
+        body2: RigidBodyHandle,
+        joint: impl Into<GenericJoint>,
+    ) -> ImpulseJointHandle {
+        self.impulse_joints.insert(body1, body2, joint, true)
+    }
+
+    /// Removes a joint.
+    pub fn remove_joint(&mut self, handle: ImpulseJointHandle) -> Option<ImpulseJoint> {
+        self.impulse_joints.remove(handle, true)
+    }
+
+    /// Returns a joint.
+    #[must_use]
+    pub fn joint(&self, handle: ImpulseJointHandle) -> Option<&ImpulseJoint> {
+        self.impulse_joints.get(handle)
+    }
+
+    /// Tags a collider with the id of the entity it belongs to.
+    pub fn set_entity(&mut self, collider: ColliderHandle, entity: u64) {
+        if self.colliders.contains(collider) {
+            self.entities.insert(collider, entity);
+        }
+    }
+
+    /// Returns the id of the entity a collider belongs to.
+    #[must_use]
+    pub fn entity(&self, collider: ColliderHandle) -> Option<u64> {
+        self.entities.get(&collider).copied()
+    }
+
+    /// Returns a rigid body.
+    #[must_use]
+    pub fn body(&self, handle: RigidBodyHandle) -> Option<&RigidBody> {
+        self.bodies.get(handle)
+    }
+
+    /// Returns a rigid body for modification.
+    pub fn body_mut(&mut self, handle: RigidBodyHandle) -> Option<&mut RigidBody> {
+        self.bodies.get_mut(handle)
+    }
+
+    /// Returns a collider.
+    #[must_use]
+    pub fn collider(&self, handle: ColliderHandle) -> Option<&Collider> {
+        self.colliders.get(handle)
+    }
+
+    /// Returns a collider for modification.
+    pub fn collider_mut(&mut self, handle: ColliderHandle) -> Option<&mut Collider> {
+        self.queries_dirty = true;
+        self.colliders.get_mut(handle)
+    }
+
+    /// Returns all rigid bodies.
+    #[must_use]
+    pub fn bodies(&self) -> &RigidBodySet {
+        &self.bodies
+    }
+
+    /// Returns all colliders.
+    #[must_use]
+    pub fn colliders(&self) -> &ColliderSet {
+        &self.colliders
+    }
+
+    /// Returns the world position of a rigid body.
+    #[must_use]
+    pub fn translation(&self, handle: RigidBodyHandle) -> Option<[f32; 3]> {
+        self.bodies
+            .get(handle)
+            .map(|body| (*body.translation()).into())
+    }
+
+    /// Returns a checksum of every body's position, rotation, and velocity.
+    ///
+    /// Lockstep peers compare checksums to detect desyncs. Results only
+    /// agree across platforms when built with the `deterministic` feature.
+    #[must_use]
+    pub fn checksum(&self) -> u64 {
+        let mut checksum = StateChecksum::default();
+        for (handle, body) in self.bodies.iter() {
+            let (index, generation) = handle.into_raw_parts();
+            checksum.write_u64(u64::from(index) << 32 | u64::from(generation));
+            let rotation = body.rotation();
+            checksum.write_f32s(body.translation().as_slice());
+            checksum.write_f32s(&[rotation.i, rotation.j, rotation.k, rotation.w]);
+            checksum.write_f32s(body.linvel().as_slice());
+            checksum.write_f32s(body.angvel().as_slice());
+        }
+        checksum.finish()
+    }
+
+    /// Advances the simulation by `delta`, normally one fixed step.
+    pub fn step(&mut self, delta: Duration) {
+        self.recent_queries
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        self.integration_parameters.dt = delta.as_secs_f32();
+        self.pipeline.step(
+            &self.gravity,
+            &self.integration_parameters,
+            &mut self.islands,
+            &mut self.broad_phase,
+            &mut self.narrow_phase,
+            &mut self.bodies,
+            &mut self.colliders,
+            &mut self.impulse_joints,
+            &mut self.multibody_joints,
+            &mut self.ccd_solver,
+            Some(&mut self.query_pipeline),
+            &(),
+            &(),
+        );
+        self.queries_dirty = false;
+    }
+
+    /// Brings scene queries up to date with colliders added, moved, or
+    /// removed since the last step.
+    pub fn update_queries(&mut self) {
+        if self.queries_dirty {
+            self.query_pipeline.update(&self.colliders);
+            self.queries_dirty = false;
+        }
+    }
+
+    /// Enables or disables recording scene queries for debug drawing.
+    ///
+    /// Recording is enabled by default in debug builds.
+    pub fn set_query_debug(&mut self, enabled: bool) {
+        self.query_debug = enabled;
+        if !enabled {
+            self.recent_queries
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear();
+        }
+    }
+
+    /// Returns the scene queries made since the last step, oldest first,
+    /// while query recording is enabled.
+    #[must_use]
+    pub fn recent_queries(&self) -> Vec<QueryRecord> {
+        self.recent_queries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Records a scene query for debug drawing.
+    pub(crate) fn record_query(&self, record: impl FnOnce() -> QueryRecord) {
+        if !self.query_debug {
+            return;
+        }
+        let mut recent = self
+            .recent_queries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if recent.len() == MAX_RECENT_QUERIES {
+            recent.pop_front();
+        }
+        recent.push_back(record());
+    }
+
+    /// Returns the sets and the query pipeline as of the last update.
+    pub(crate) fn query_view(&self) -> (&RigidBodySet, &ColliderSet, &QueryPipeline) {
+        (&self.bodies, &self.colliders, &self.query_pipeline)
+    }
+
+    /// Returns the sets and an up-to-date query pipeline for scene queries.
+    pub(crate) fn query_parts(&mut self) -> (&RigidBodySet, &ColliderSet, &QueryPipeline) {
+        self.update_queries();
+        (&self.bodies, &self.colliders, &self.query_pipeline)
+    }
+}
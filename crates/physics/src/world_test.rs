@@ -0,0 +1,84 @@
+//! Unit tests for the physics world.
+
+use crate::world::PhysicsWorld;
+use rapier3d::prelude::*;
+use std::time::Duration;
+
+/// Verifies that dynamic bodies fall and come to rest on fixed colliders.
+#[test]
+fn bodies_fall_onto_ground() {
+    let mut world = PhysicsWorld::default();
+    world.insert_collider(
+        ColliderBuilder::cuboid(10.0, 0.5, 10.0).translation(vector![0.0, -0.5, 0.0]),
+        None,
+    );
+    let ball = world.insert_body(RigidBodyBuilder::dynamic().translation(vector![0.0, 2.0, 0.0]));
+    world.insert_collider(ColliderBuilder::ball(0.5), Some(ball));
+
+    for _ in 0..180 {
+        world.step(Duration::from_secs_f32(1.0 / 60.0));
+    }
+    let [_, y, _] = world.translation(ball).unwrap();
+    assert!((y - 0.5).abs() < 0.05, "ball rests at {y}");
+}
+
+/// Verifies that removing a body also removes its colliders.
+#[test]
+fn removes_body_with_colliders() {
+    let mut world = PhysicsWorld::default();
+    let body = world.insert_body(RigidBodyBuilder::dynamic());
+    let collider = world.insert_collider(ColliderBuilder::ball(0.5), Some(body));
+
+    assert!(world.remove_body(body).is_some());
+    assert!(world.body(body).is_none());
+    assert!(world.collider(collider).is_none());
+    assert_eq!(world.colliders().len(), 0);
+}
+
+/// Builds a small pile of bodies, steps it, and returns its checksum.
+#[cfg(feature = "deterministic")]
+fn pile_checksum(offset: f32) -> u64 {
+    let mut world = PhysicsWorld::default();
+    world.insert_collider(
+        ColliderBuilder::cuboid(10.0, 0.5, 10.0).translation(vector![0.0, -0.5, 0.0]),
+        None,
+    );
+    for i in 0..20u8 {
+        let height = 1.0 + f32::from(i) * 0.7;
+        let body = world.insert_body(RigidBodyBuilder::dynamic().translation(vector![
+            offset + f32::from(i % 3) * 0.2,
+            height,
+            0.0
+        ]));
+        world.insert_collider(ColliderBuilder::cuboid(0.3, 0.3, 0.3), Some(body));
+    }
+    for _ in 0..120 {
+        world.step(Duration::from_secs_f32(1.0 / 60.0));
+    }
+    world.checksum()
+}
+
+/// Verifies the checksum reflects the simulation state.
+#[test]
+fn checksum_tracks_state() {
+    let mut world = PhysicsWorld::default();
+    let empty = world.checksum();
+    let ball = world.insert_body(RigidBodyBuilder::dynamic().translation(vector![0.0, 2.0, 0.0]));
+    world.insert_collider(ColliderBuilder::ball(0.5), Some(ball));
+    let placed = world.checksum();
+    assert_ne!(empty, placed);
+    assert_eq!(placed, world.checksum());
+
+    world.step(Duration::from_secs_f32(1.0 / 60.0));
+    assert_ne!(placed, world.checksum());
+}
+
+/// Verifies identical scenes stepped identically have the same checksum,
+/// and different scenes do not. Without the `deterministic` feature rapier
+/// may order contacts differently from run to run.
+#[cfg(feature = "deterministic")]
+#[test]
+fn checksum_matches_across_runs() {
+    assert_eq!(pile_checksum(0.0), pile_checksum(0.0));
+    assert_ne!(pile_checksum(0.0), pile_checksum(0.1));
+}
@@ -0,0 +1,271 @@
+//! Persistent, hot-reloadable key bindings.
+//!
+//! [`BindingConfig`] keeps an [`InputMap`] in sync with a TOML file in the
+//! user's config directory. Defaults are baked in by the game and the file
+//! only overrides the actions it mentions, so actions added in an update
+//! still get their default keys. The file is re-read when it changes on
+//! disk, and in-game rebinding writes changes back.
+//!
+//! The file maps each action to a list of bindings:
+//!
+//! ```toml
+//! forward = [{ physical = "KeyW" }, { physical = "ArrowUp" }]
+//! undo = [{ logical = { Character = "z" } }]
+//! ```
+
+use super::{InputMap, KeyBinding, KeyboardEvent};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use winit::keyboard::KeyCode;
+
+/// File name of the bindings file inside the application config directory.
+pub const BINDINGS_FILE_NAME: &str = "input.toml";
+
+/// Returns the default bindings file path for `app_name`.
+///
+/// Resolves to `<config dir>/<app_name>/input.toml`, for example
+/// `~/.config/<app_name>/input.toml` on Linux and
+/// `%APPDATA%\<app_name>\input.toml` on Windows. Returns `None` on platforms
+/// without a user config directory, such as the web.
+#[must_use]
+pub fn default_bindings_path(app_name: &str) -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(app_name).join(BINDINGS_FILE_NAME))
+}
+
+/// An [`InputMap`] backed by a user bindings file.
+///
+/// # Example
+///
+/// ```no_run
+/// use platform::input::{BindingConfig, InputMap, KeyBinding};
+/// use winit::keyboard::KeyCode;
+///
+/// let mut defaults = InputMap::default();
+/// defaults.bind("jump", KeyBinding::Physical(KeyCode::Space));
+///
+/// let mut config = BindingConfig::load_default("my-game", defaults)?;
+///
+/// // Options menu: the next key the player presses becomes "jump".
+/// config.start_rebind("jump");
+///
+/// // Each frame, pick up edits made to the file while the game runs.
+/// config.poll_reload()?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct BindingConfig {
+    map: InputMap,
+    defaults: InputMap,
+    path: Option<PathBuf>,
+    modified: Option<SystemTime>,
+    rebinding: Option<String>,
+}
+
+impl BindingConfig {
+    /// Creates a configuration that is never read from or written to disk.
+    #[must_use]
+    pub fn in_memory(defaults: InputMap) -> Self {
+        Self {
+            map: defaults.clone(),
+            defaults,
+            path: None,
+            modified: None,
+            rebinding: None,
+        }
+    }
+
+    /// Loads bindings from `path`, overlaid on `defaults`.
+    ///
+    /// A missing file is not an error; the defaults are used until the first
+    /// save creates it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(path: impl Into<PathBuf>, defaults: InputMap) -> anyhow::Result<Self> {
+        let mut config = Self::in_memory(defaults);
+        config.path = Some(path.into());
+        config.reload()?;
+        Ok(config)
+    }
+
+    /// Loads bindings from [`default_bindings_path`].
+    ///
+    /// Falls back to [`in_memory`](Self::in_memory) when the platform has no
+    /// config directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load_default(app_name: &str, defaults: InputMap) -> anyhow::Result<Self> {
+        match default_bindings_path(app_name) {
+            Some(path) => Self::load(path, defaults),
+            None => Ok(Self::in_memory(defaults)),
+        }
+    }
+
+    /// Returns the active bindings.
+    #[must_use]
+    #[inline]
+    pub fn map(&self) -> &InputMap {
+        &self.map
+    }
+
+    /// Returns the backing file path, if any.
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Re-reads the file if it changed on disk since the last load or save.
+    ///
+    /// Returns `true` if the bindings were reloaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed. The previous
+    /// bindings stay active, so a half-saved edit does not unbind anything.
+    pub fn poll_reload(&mut self) -> anyhow::Result<bool> {
+        let Some(path) = &self.path else {
+            return Ok(false);
+        };
+        let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+        if modified == self.modified {
+            return Ok(false);
+        }
+        self.reload()?;
+        Ok(true)
+    }
+
+    /// Writes the active bindings to the backing file, creating parent
+    /// directories as needed. Does nothing for in-memory configurations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&mut self) -> anyhow::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let text = to_toml(&self.map)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                anyhow::anyhow!("failed to create directory {}: {e}", parent.display())
+            })?;
+        }
+        fs::write(path, text)
+            .map_err(|e| anyhow::anyhow!("failed to write {}: {e}", path.display()))?;
+        self.modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+        Ok(())
+    }
+
+    /// Replaces all bindings of `action` and saves.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if saving fails; the binding is still applied.
+    pub fn rebind(&mut self, action: &str, binding: KeyBinding) -> anyhow::Result<()> {
+        self.map.rebind(action, binding);
+        self.save()
+    }
+
+    /// Restores the baked-in defaults and saves.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if saving fails; the defaults are still applied.
+    pub fn reset_to_defaults(&mut self) -> anyhow::Result<()> {
+        self.map = self.defaults.clone();
+        self.save()
+    }
+
+    /// Starts capturing the next key press as the new binding of `action`.
+    ///
+    /// Replaces any rebind already in progress.
+    pub fn start_rebind(&mut self, action: &str) {
+        self.rebinding = Some(action.to_owned());
+    }
+
+    /// Cancels the rebind in progress, if any.
+    pub fn cancel_rebind(&mut self) {
+        self.rebinding = None;
+    }
+
+    /// Returns the action waiting for a key, if a rebind is in progress.
+    #[must_use]
+    pub fn rebinding(&self) -> Option<&str> {
+        self.rebinding.as_deref()
+    }
+
+    /// Feeds a key event to the rebind in progress.
+    ///
+    /// The first fresh key press is bound to the action by physical
+    /// position, and the bindings are saved. Escape cancels the rebind
+    /// instead. Releases and key repeats are ignored.
+    ///
+    /// Returns the captured binding, if this event completed a rebind.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if saving fails; the binding is still applied.
+    pub fn capture(&mut self, event: &KeyboardEvent) -> anyhow::Result<Option<KeyBinding>> {
+        if !event.pressed || event.repeat {
+            return Ok(None);
+        }
+        let Some(action) = self.rebinding.take() else {
+            return Ok(None);
+        };
+        if event.physical == KeyCode::Escape {
+            return Ok(None);
+        }
+        let binding = KeyBinding::Physical(event.physical);
+        self.rebind(&action, binding.clone())?;
+        Ok(Some(binding))
+    }
+
+    /// Rebuilds the active map from the defaults and the file.
+    fn reload(&mut self) -> anyhow::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                self.map = self.defaults.clone();
+                self.modified = None;
+                return Ok(());
+            }
+            Err(e) => return Err(anyhow::anyhow!("failed to read {}: {e}", path.display())),
+        };
+        let overrides: InputMap = toml::from_str(&text)
+            .map_err(|e| anyhow::anyhow!("invalid key bindings in {}: {e}", path.display()))?;
+        let mut map = self.defaults.clone();
+        map.merge(overrides);
+        self.map = map;
+        self.modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+        Ok(())
+    }
+}
+
+/// Serializes a map with one `action = [...]` line per action, which is
+/// easier to edit by hand than the array-of-tables layout `toml` emits.
+fn to_toml(map: &InputMap) -> anyhow::Result<String> {
+    let mut text = String::new();
+    for (action, bindings) in map.iter() {
+        let value = toml::Value::try_from(bindings)
+            .map_err(|e| anyhow::anyhow!("failed to serialize bindings of `{action}`: {e}"))?;
+        let bare = !action.is_empty()
+            && action
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        let key = if bare {
+            action.to_owned()
+        } else {
+            toml::Value::String(action.to_owned()).to_string()
+        };
+        writeln!(text, "{key} = {value}")?;
+    }
+    Ok(text)
+}
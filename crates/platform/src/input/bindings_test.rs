@@ -0,0 +1,154 @@
+//! Unit tests for persisted key bindings.
+
+use super::{BindingConfig, InputMap, KeyBinding, KeyboardEvent};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use winit::keyboard::{Key, KeyCode};
+
+/// Returns a fresh bindings path in a per-test temp directory.
+fn temp_path(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rustgine-bindings-{test}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir.join("input.toml")
+}
+
+/// Default bindings used by the tests.
+fn defaults() -> InputMap {
+    let mut map = InputMap::default();
+    map.bind("jump", KeyBinding::Physical(KeyCode::Space));
+    map.bind("forward", KeyBinding::Physical(KeyCode::KeyW));
+    map
+}
+
+/// Builds a key press event.
+fn press(physical: KeyCode) -> KeyboardEvent {
+    KeyboardEvent {
+        physical,
+        logical: Key::Unidentified(winit::keyboard::NativeKey::Unidentified),
+        pressed: true,
+        repeat: false,
+    }
+}
+
+/// Verifies that a missing file falls back to the defaults.
+#[test]
+fn missing_file_uses_defaults() {
+    let config = BindingConfig::load(temp_path("missing"), defaults()).unwrap();
+    assert_eq!(config.map(), &defaults());
+}
+
+/// Verifies that saved bindings round-trip through the file.
+#[test]
+fn save_and_load_round_trip() {
+    let path = temp_path("round-trip");
+    let mut config = BindingConfig::load(&path, defaults()).unwrap();
+    config
+        .rebind("jump", KeyBinding::Physical(KeyCode::KeyJ))
+        .unwrap();
+    config
+        .rebind("undo", KeyBinding::Logical(Key::Character("z".into())))
+        .unwrap();
+
+    config
+        .rebind("open map", KeyBinding::Physical(KeyCode::KeyM))
+        .unwrap();
+
+    let text = fs::read_to_string(&path).unwrap();
+    assert!(text.contains("jump = [{ physical = \"KeyJ\" }]"), "{text}");
+    assert!(text.contains("\"open map\" = "), "{text}");
+
+    let loaded = BindingConfig::load(&path, defaults()).unwrap();
+    assert_eq!(loaded.map(), config.map());
+}
+
+/// Verifies that the file only overrides the actions it lists.
+#[test]
+fn file_overrides_defaults_per_action() {
+    let path = temp_path("overlay");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "jump = [{ physical = \"KeyK\" }]\n").unwrap();
+
+    let config = BindingConfig::load(&path, defaults()).unwrap();
+    assert_eq!(
+        config.map().bindings("jump"),
+        [KeyBinding::Physical(KeyCode::KeyK)]
+    );
+    assert_eq!(
+        config.map().bindings("forward"),
+        [KeyBinding::Physical(KeyCode::KeyW)]
+    );
+}
+
+/// Verifies that external edits are picked up and bad edits are rejected.
+#[test]
+fn poll_reload_detects_changes() {
+    let path = temp_path("reload");
+    let mut config = BindingConfig::load(&path, defaults()).unwrap();
+    config.save().unwrap();
+    assert!(!config.poll_reload().unwrap());
+
+    fs::write(&path, "forward = [{ physical = \"ArrowUp\" }]\n").unwrap();
+    let later = SystemTime::now() + Duration::from_secs(5);
+    fs::File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(later)
+        .unwrap();
+    assert!(config.poll_reload().unwrap());
+    assert_eq!(
+        config.map().bindings("forward"),
+        [KeyBinding::Physical(KeyCode::ArrowUp)]
+    );
+
+    fs::write(&path, "forward = [{ nonsense = 1 }]\n").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&path)
+        .unwrap()
+        .set_modified(later + Duration::from_secs(5))
+        .unwrap();
+    assert!(config.poll_reload().is_err());
+    assert_eq!(
+        config.map().bindings("forward"),
+        [KeyBinding::Physical(KeyCode::ArrowUp)]
+    );
+}
+
+/// Verifies that a rebind captures the next fresh press and saves it.
+#[test]
+fn rebind_captures_next_press() {
+    let path = temp_path("capture");
+    let mut config = BindingConfig::load(&path, defaults()).unwrap();
+
+    // Without a rebind in progress, presses are ignored.
+    assert_eq!(config.capture(&press(KeyCode::KeyQ)).unwrap(), None);
+
+    config.start_rebind("jump");
+    assert_eq!(config.rebinding(), Some("jump"));
+
+    let mut repeat = press(KeyCode::KeyX);
+    repeat.repeat = true;
+    assert_eq!(config.capture(&repeat).unwrap(), None);
+
+    let captured = config.capture(&press(KeyCode::KeyF)).unwrap();
+    assert_eq!(captured, Some(KeyBinding::Physical(KeyCode::KeyF)));
+    assert_eq!(config.rebinding(), None);
+
+    let loaded = BindingConfig::load(&path, defaults()).unwrap();
+    assert_eq!(
+        loaded.map().bindings("jump"),
+        [KeyBinding::Physical(KeyCode::KeyF)]
+    );
+}
+
+/// Verifies that Escape cancels a rebind without changing bindings.
+#[test]
+fn escape_cancels_rebind() {
+    let mut config = BindingConfig::in_memory(defaults());
+    config.start_rebind("jump");
+    assert_eq!(config.capture(&press(KeyCode::Escape)).unwrap(), None);
+    assert_eq!(config.rebinding(), None);
+    assert_eq!(config.map(), &defaults());
+}
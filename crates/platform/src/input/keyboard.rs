@@ -0,0 +1,137 @@
+//! Keyboard input with physical and logical keys.
+//!
+//! Every key event carries both the physical key (the position on the
+//! keyboard, independent of layout) and the logical key (what the active
+//! layout produces). Gameplay bindings such as WASD should use physical keys
+//! so they work on AZERTY or Dvorak; text shortcuts and UI labels use
+//! logical keys.
+
+use ecs::Events;
+use std::collections::{HashMap, HashSet};
+use winit::keyboard::{Key, KeyCode, PhysicalKey};
+
+/// A keyboard event carrying both physical and logical keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyboardEvent {
+    /// Layout-independent key position (scancode).
+    pub physical: KeyCode,
+    /// Layout-dependent key meaning (keysym).
+    pub logical: Key,
+    /// `true` for a press, `false` for a release.
+    pub pressed: bool,
+    /// `true` if this press was generated by key repeat.
+    pub repeat: bool,
+}
+
+impl KeyboardEvent {
+    /// Converts a winit key event.
+    ///
+    /// Returns `None` for keys the platform could not identify.
+    #[must_use]
+    pub fn from_winit(event: &winit::event::KeyEvent) -> Option<Self> {
+        let PhysicalKey::Code(physical) = event.physical_key else {
+            return None;
+        };
+        Some(Self {
+            physical,
+            logical: event.logical_key.clone(),
+            pressed: event.state.is_pressed(),
+            repeat: event.repeat,
+        })
+    }
+}
+
+/// Keyboard input resource tracking held keys per frame.
+///
+/// # Example
+///
+/// ```
+/// use platform::input::{KeyboardEvent, KeyboardInput};
+/// use winit::keyboard::{Key, KeyCode};
+///
+/// let mut keyboard = KeyboardInput::default();
+/// // On an AZERTY layout, the key in the QWERTY "Q" position produces "a".
+/// keyboard.handle(KeyboardEvent {
+///     physical: KeyCode::KeyQ,
+///     logical: Key::Character("a".into()),
+///     pressed: true,
+///     repeat: false,
+/// });
+///
+/// assert!(keyboard.pressed(KeyCode::KeyQ));
+/// assert!(keyboard.logical_pressed(&Key::Character("a".into())));
+/// ```
+#[derive(Debug, Default)]
+pub struct KeyboardInput {
+    /// Raw keyboard events received this frame and last.
+    pub events: Events<KeyboardEvent>,
+    pressed: HashSet<KeyCode>,
+    logical_pressed: HashSet<Key>,
+    just_pressed: HashSet<KeyCode>,
+    just_released: HashSet<KeyCode>,
+    layout: HashMap<KeyCode, Key>,
+}
+
+impl KeyboardInput {
+    /// Applies a keyboard event.
+    pub fn handle(&mut self, event: KeyboardEvent) {
+        self.layout.insert(event.physical, event.logical.clone());
+        if event.pressed {
+            if self.pressed.insert(event.physical) {
+                self.just_pressed.insert(event.physical);
+            }
+            self.logical_pressed.insert(event.logical.clone());
+        } else {
+            if self.pressed.remove(&event.physical) {
+                self.just_released.insert(event.physical);
+            }
+            self.logical_pressed.remove(&event.logical);
+        }
+        self.events.send(event);
+    }
+
+    /// Advances to the next frame.
+    pub fn begin_frame(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+        self.events.update();
+    }
+
+    /// Releases all keys, e.g. when the window loses focus.
+    pub fn release_all(&mut self) {
+        self.just_released.extend(self.pressed.drain());
+        self.logical_pressed.clear();
+    }
+
+    /// Returns `true` while the physical key is held.
+    #[must_use]
+    pub fn pressed(&self, key: KeyCode) -> bool {
+        self.pressed.contains(&key)
+    }
+
+    /// Returns `true` if the physical key was pressed this frame.
+    #[must_use]
+    pub fn just_pressed(&self, key: KeyCode) -> bool {
+        self.just_pressed.contains(&key)
+    }
+
+    /// Returns `true` if the physical key was released this frame.
+    #[must_use]
+    pub fn just_released(&self, key: KeyCode) -> bool {
+        self.just_released.contains(&key)
+    }
+
+    /// Returns `true` while a key producing the logical key is held.
+    #[must_use]
+    pub fn logical_pressed(&self, key: &Key) -> bool {
+        self.logical_pressed.contains(key)
+    }
+
+    /// Returns the logical key last produced by a physical key.
+    ///
+    /// Learned from observed events, so it reflects the user's active layout.
+    #[must_use]
+    pub fn layout_key(&self, key: KeyCode) -> Option<&Key> {
+        self.layout.get(&key)
+    }
+}
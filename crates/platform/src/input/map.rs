@@ -0,0 +1,163 @@
+//! Action-based input bindings.
+//!
+//! Provides the [`InputMap`], which maps named actions (`"jump"`, `"move_left"`)
+//! to one or more key bindings so gameplay code never hard-codes keys.
+
+use super::KeyboardInput;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use winit::keyboard::{Key, KeyCode};
+
+/// Action that shows or hides the developer console.
+pub const TOGGLE_CONSOLE: &str = "console.toggle";
+
+/// A key binding, matched either by physical position or by logical key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyBinding {
+    /// Matches the key at this physical position on any layout.
+    Physical(KeyCode),
+    /// Matches whichever key produces this logical key on the active layout.
+    Logical(Key),
+}
+
+impl KeyBinding {
+    /// Returns `true` while the bound key is held.
+    #[must_use]
+    pub fn pressed(&self, keyboard: &KeyboardInput) -> bool {
+        match self {
+            Self::Physical(code) => keyboard.pressed(*code),
+            Self::Logical(key) => keyboard.logical_pressed(key),
+        }
+    }
+
+    /// Returns a human-readable label for the binding.
+    ///
+    /// Physical bindings are labelled with the key the user's layout
+    /// produces at that position when known, so a `KeyW` binding shows as
+    /// "Z" on AZERTY keyboards.
+    #[must_use]
+    pub fn display_name(&self, keyboard: &KeyboardInput) -> String {
+        match self {
+            Self::Physical(code) => keyboard
+                .layout_key(*code)
+                .and_then(logical_label)
+                .unwrap_or_else(|| physical_label(*code)),
+            Self::Logical(key) => logical_label(key).unwrap_or_else(|| format!("{key:?}")),
+        }
+    }
+}
+
+/// Maps named actions to key bindings.
+///
+/// # Example
+///
+/// ```
+/// use platform::input::{InputMap, KeyBinding, KeyboardInput};
+/// use winit::keyboard::KeyCode;
+///
+/// let mut map = InputMap::default();
+/// map.bind("forward", KeyBinding::Physical(KeyCode::KeyW));
+///
+/// let keyboard = KeyboardInput::default();
+/// assert!(!map.pressed("forward", &keyboard));
+/// assert_eq!(map.display_name("forward", &keyboard).as_deref(), Some("W"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct InputMap {
+    bindings: BTreeMap<String, Vec<KeyBinding>>,
+}
+
+impl InputMap {
+    /// Creates a map with the engine's own actions bound.
+    ///
+    /// Binds [`TOGGLE_CONSOLE`] to the key below Escape (`` ` `` on US
+    /// layouts), matched physically so it is reachable on every layout.
+    #[must_use]
+    pub fn with_engine_defaults() -> Self {
+        let mut map = Self::default();
+        map.bind(TOGGLE_CONSOLE, KeyBinding::Physical(KeyCode::Backquote));
+        map
+    }
+
+    /// Adds a binding to `action`, ignoring duplicates.
+    pub fn bind(&mut self, action: &str, binding: KeyBinding) {
+        let bindings = self.bindings.entry(action.to_owned()).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    /// Replaces all bindings of `action` with a single binding.
+    pub fn rebind(&mut self, action: &str, binding: KeyBinding) {
+        self.bindings.insert(action.to_owned(), vec![binding]);
+    }
+
+    /// Removes all bindings of `action`.
+    pub fn unbind(&mut self, action: &str) {
+        self.bindings.remove(action);
+    }
+
+    /// Returns the bindings of `action`.
+    #[must_use]
+    pub fn bindings(&self, action: &str) -> &[KeyBinding] {
+        self.bindings.get(action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterates over all actions and their bindings, sorted by action name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[KeyBinding])> {
+        self.bindings
+            .iter()
+            .map(|(action, bindings)| (action.as_str(), bindings.as_slice()))
+    }
+
+    /// Returns `true` while any binding of `action` is held.
+    #[must_use]
+    pub fn pressed(&self, action: &str, keyboard: &KeyboardInput) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|binding| binding.pressed(keyboard))
+    }
+
+    /// Returns `true` if a physical binding of `action` was pressed this frame.
+    #[must_use]
+    pub fn just_pressed(&self, action: &str, keyboard: &KeyboardInput) -> bool {
+        self.bindings(action).iter().any(|binding| match binding {
+            KeyBinding::Physical(code) => keyboard.just_pressed(*code),
+            KeyBinding::Logical(_) => false,
+        })
+    }
+
+    /// Overlays `other` onto this map, replacing the bindings of every
+    /// action `other` defines and keeping the rest.
+    pub fn merge(&mut self, other: InputMap) {
+        self.bindings.extend(other.bindings);
+    }
+
+    /// Returns the label of the first binding of `action`.
+    #[must_use]
+    pub fn display_name(&self, action: &str, keyboard: &KeyboardInput) -> Option<String> {
+        self.bindings(action)
+            .first()
+            .map(|binding| binding.display_name(keyboard))
+    }
+}
+
+/// Label for a logical key, if it is printable or named.
+fn logical_label(key: &Key) -> Option<String> {
+    match key {
+        Key::Character(text) if !text.trim().is_empty() => Some(text.to_uppercase()),
+        Key::Named(named) => Some(format!("{named:?}")),
+        _ => None,
+    }
+}
+
+/// Fallback label derived from the physical key code name.
+fn physical_label(code: KeyCode) -> String {
+    let name = format!("{code:?}");
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .unwrap_or(&name)
+        .to_owned()
+}
@@ -0,0 +1,93 @@
+//! Unit tests for keyboard state and action bindings.
+
+use super::{InputMap, KeyBinding, KeyboardEvent, KeyboardInput};
+use winit::keyboard::{Key, KeyCode, NamedKey};
+
+/// Builds a key event.
+fn key(physical: KeyCode, logical: &str, pressed: bool) -> KeyboardEvent {
+    KeyboardEvent {
+        physical,
+        logical: Key::Character(logical.into()),
+        pressed,
+        repeat: false,
+    }
+}
+
+/// Verifies that physical bindings work regardless of layout.
+#[test]
+fn physical_binding_ignores_layout() {
+    let mut map = InputMap::default();
+    map.bind("forward", KeyBinding::Physical(KeyCode::KeyW));
+
+    // AZERTY: the QWERTY "W" position produces "z".
+    let mut keyboard = KeyboardInput::default();
+    keyboard.handle(key(KeyCode::KeyW, "z", true));
+
+    assert!(map.pressed("forward", &keyboard));
+    assert!(map.just_pressed("forward", &keyboard));
+    assert_eq!(map.display_name("forward", &keyboard).as_deref(), Some("Z"));
+}
+
+/// Verifies that logical bindings follow the produced character.
+#[test]
+fn logical_binding_follows_layout() {
+    let mut map = InputMap::default();
+    map.bind("undo", KeyBinding::Logical(Key::Character("z".into())));
+
+    let mut keyboard = KeyboardInput::default();
+    keyboard.handle(key(KeyCode::KeyY, "z", true));
+    assert!(map.pressed("undo", &keyboard));
+
+    keyboard.handle(key(KeyCode::KeyY, "z", false));
+    assert!(!map.pressed("undo", &keyboard));
+}
+
+/// Verifies frame-edge tracking of presses and releases.
+#[test]
+fn just_pressed_resets_each_frame() {
+    let mut keyboard = KeyboardInput::default();
+    keyboard.handle(key(KeyCode::Space, " ", true));
+    assert!(keyboard.just_pressed(KeyCode::Space));
+
+    keyboard.begin_frame();
+    assert!(keyboard.pressed(KeyCode::Space));
+    assert!(!keyboard.just_pressed(KeyCode::Space));
+
+    keyboard.release_all();
+    assert!(keyboard.just_released(KeyCode::Space));
+    assert!(!keyboard.pressed(KeyCode::Space));
+}
+
+/// Verifies fallback labels when the layout is unknown.
+#[test]
+fn display_name_fallbacks() {
+    let keyboard = KeyboardInput::default();
+    assert_eq!(
+        KeyBinding::Physical(KeyCode::Digit1).display_name(&keyboard),
+        "1"
+    );
+    assert_eq!(
+        KeyBinding::Physical(KeyCode::ShiftLeft).display_name(&keyboard),
+        "ShiftLeft"
+    );
+    assert_eq!(
+        KeyBinding::Logical(Key::Named(NamedKey::Enter)).display_name(&keyboard),
+        "Enter"
+    );
+}
+
+/// Verifies bind, rebind, and unbind semantics.
+#[test]
+fn bind_rebind_unbind() {
+    let mut map = InputMap::default();
+    map.bind("jump", KeyBinding::Physical(KeyCode::Space));
+    map.bind("jump", KeyBinding::Physical(KeyCode::Space));
+    map.bind("jump", KeyBinding::Physical(KeyCode::KeyJ));
+    assert_eq!(map.bindings("jump").len(), 2);
+
+    map.rebind("jump", KeyBinding::Physical(KeyCode::KeyK));
+    assert_eq!(map.bindings("jump"), [KeyBinding::Physical(KeyCode::KeyK)]);
+
+    map.unbind("jump");
+    assert!(map.bindings("jump").is_empty());
+}
@@ -0,0 +1,37 @@
+//! Input collection and state.
+//!
+//! This module gathers platform input into engine-friendly types:
+//!
+//! - [`TouchInput`] - Touch points, raw touch events, and derived gestures
+//! - [`TextInput`] - Committed text and IME composition for focused fields
+//! - [`MouseInput`] - Cursor, buttons, wheel, and raw relative motion for
+//!   camera control
+//! - [`KeyboardInput`] - Held keys by physical position and logical meaning
+//! - [`InputMap`] - Named actions bound to physical or logical keys
+//! - [`BindingConfig`] - Key bindings persisted to a hot-reloaded user file
+
+mod bindings;
+#[cfg(test)]
+mod bindings_test;
+mod keyboard;
+mod map;
+#[cfg(test)]
+mod map_test;
+mod mouse;
+#[cfg(test)]
+mod mouse_test;
+mod text;
+#[cfg(test)]
+mod text_test;
+mod touch;
+#[cfg(test)]
+mod touch_test;
+
+pub use bindings::{default_bindings_path, BindingConfig, BINDINGS_FILE_NAME};
+pub use keyboard::{KeyboardEvent, KeyboardInput};
+pub use map::{InputMap, KeyBinding, TOGGLE_CONSOLE};
+pub use mouse::{MouseInput, MouseMotion, PIXELS_PER_LINE};
+pub use text::{ImeEvent, Preedit, ReceivedCharacter, TextInput};
+pub use touch::{
+    Gesture, GestureConfig, GestureRecognizer, Touch, TouchEvent, TouchInput, TouchPhase, Touches,
+};
@@ -0,0 +1,246 @@
+//! Mouse input and relative (raw) motion.
+//!
+//! Camera controllers need unbounded, unaccelerated mouse deltas rather than
+//! cursor positions clamped to the screen. Relative mode grabs and hides the
+//! cursor and reads raw device motion instead.
+
+use ecs::Events;
+use std::collections::HashSet;
+use winit::event::{DeviceEvent, MouseButton, MouseScrollDelta, WindowEvent};
+use winit::window::{CursorGrabMode, Window};
+
+/// Pixels of a touchpad scroll counted as one wheel line.
+pub const PIXELS_PER_LINE: f64 = 20.0;
+
+/// Raw mouse movement reported by the device, before OS acceleration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MouseMotion {
+    /// Movement in device units since the previous event.
+    pub delta: [f64; 2],
+}
+
+/// Mouse input resource with optional relative mode.
+///
+/// While relative mode is enabled, raw device motion is accumulated each
+/// frame and published as [`MouseMotion`] events; otherwise device motion
+/// is ignored and only cursor events apply. Buttons and the wheel are
+/// tracked in both modes.
+///
+/// # Example
+///
+/// ```
+/// use platform::input::MouseInput;
+///
+/// let mut mouse = MouseInput::default();
+/// mouse.set_relative_mode(true);
+/// mouse.handle_raw_motion((3.0, -2.0));
+/// mouse.handle_raw_motion((1.0, 0.0));
+/// assert_eq!(mouse.frame_delta(), [4.0, -2.0]);
+/// // The cursor grab follows when the event loop applies it.
+/// assert_eq!(mouse.pending_grab(), Some(true));
+/// ```
+#[derive(Debug, Default)]
+pub struct MouseInput {
+    /// Raw motion events received while in relative mode.
+    pub motion: Events<MouseMotion>,
+    relative_mode: bool,
+    pending_grab: Option<bool>,
+    frame_delta: [f64; 2],
+    wheel_delta: [f64; 2],
+    cursor_position: Option<[f64; 2]>,
+    pressed: HashSet<MouseButton>,
+    just_pressed: HashSet<MouseButton>,
+    just_released: HashSet<MouseButton>,
+}
+
+impl MouseInput {
+    /// Enables or disables relative mode.
+    ///
+    /// The cursor grab and visibility change with it: the next
+    /// [`apply_to_window`](Self::apply_to_window), which the event loop
+    /// calls every iteration, grabs and hides the cursor or releases it.
+    pub fn set_relative_mode(&mut self, enabled: bool) {
+        if self.relative_mode != enabled {
+            self.pending_grab = Some(enabled);
+        }
+        self.relative_mode = enabled;
+        if !enabled {
+            self.frame_delta = [0.0; 2];
+        }
+    }
+
+    /// Returns `true` if relative mode is enabled.
+    #[must_use]
+    #[inline]
+    pub fn relative_mode(&self) -> bool {
+        self.relative_mode
+    }
+
+    /// Returns the cursor grab not yet applied to the window, `true` to
+    /// grab and hide it and `false` to release it.
+    #[must_use]
+    #[inline]
+    pub fn pending_grab(&self) -> Option<bool> {
+        self.pending_grab
+    }
+
+    /// Applies a raw device motion delta (from `DeviceEvent::MouseMotion`).
+    pub fn handle_raw_motion(&mut self, delta: (f64, f64)) {
+        if !self.relative_mode {
+            return;
+        }
+        self.frame_delta[0] += delta.0;
+        self.frame_delta[1] += delta.1;
+        self.motion.send(MouseMotion {
+            delta: [delta.0, delta.1],
+        });
+    }
+
+    /// Applies a raw winit device event, ignoring non-motion events.
+    pub fn handle_device_event(&mut self, event: &DeviceEvent) {
+        if let DeviceEvent::MouseMotion { delta } = event {
+            self.handle_raw_motion(*delta);
+        }
+    }
+
+    /// Applies the cursor, button, and wheel events of a window, ignoring
+    /// the rest.
+    pub fn handle_window_event(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.handle_cursor_moved([position.x, position.y]);
+            }
+            WindowEvent::CursorLeft { .. } => self.handle_cursor_left(),
+            WindowEvent::MouseInput { state, button, .. } => {
+                self.handle_button(*button, state.is_pressed());
+            }
+            WindowEvent::MouseWheel { delta, .. } => self.handle_wheel(*delta),
+            _ => {}
+        }
+    }
+
+    /// Records the cursor position in window coordinates.
+    pub fn handle_cursor_moved(&mut self, position: [f64; 2]) {
+        self.cursor_position = Some(position);
+    }
+
+    /// Returns the cursor position, unless the cursor left the window.
+    ///
+    /// Meaningless while relative mode hides the cursor.
+    #[must_use]
+    #[inline]
+    pub fn cursor_position(&self) -> Option<[f64; 2]> {
+        self.cursor_position
+    }
+
+    /// Records that the cursor left the window.
+    pub fn handle_cursor_left(&mut self) {
+        self.cursor_position = None;
+    }
+
+    /// Records a button press or release.
+    pub fn handle_button(&mut self, button: MouseButton, pressed: bool) {
+        if pressed {
+            if self.pressed.insert(button) {
+                self.just_pressed.insert(button);
+            }
+        } else if self.pressed.remove(&button) {
+            self.just_released.insert(button);
+        }
+    }
+
+    /// Adds a wheel movement to the frame's total, converting touchpad
+    /// pixels to lines with [`PIXELS_PER_LINE`].
+    pub fn handle_wheel(&mut self, delta: MouseScrollDelta) {
+        let [x, y] = match delta {
+            MouseScrollDelta::LineDelta(x, y) => [f64::from(x), f64::from(y)],
+            MouseScrollDelta::PixelDelta(position) => {
+                [position.x / PIXELS_PER_LINE, position.y / PIXELS_PER_LINE]
+            }
+        };
+        self.wheel_delta[0] += x;
+        self.wheel_delta[1] += y;
+    }
+
+    /// Returns `true` while the button is held.
+    #[must_use]
+    pub fn pressed(&self, button: MouseButton) -> bool {
+        self.pressed.contains(&button)
+    }
+
+    /// Returns `true` if the button was pressed this frame.
+    #[must_use]
+    pub fn just_pressed(&self, button: MouseButton) -> bool {
+        self.just_pressed.contains(&button)
+    }
+
+    /// Returns `true` if the button was released this frame.
+    #[must_use]
+    pub fn just_released(&self, button: MouseButton) -> bool {
+        self.just_released.contains(&button)
+    }
+
+    /// Releases all buttons, e.g. when the window loses focus.
+    pub fn release_all(&mut self) {
+        self.just_released.extend(self.pressed.drain());
+    }
+
+    /// Total raw motion accumulated during the current frame.
+    #[must_use]
+    #[inline]
+    pub fn frame_delta(&self) -> [f64; 2] {
+        self.frame_delta
+    }
+
+    /// Total wheel movement during the current frame, in lines, positive
+    /// up and right.
+    #[must_use]
+    #[inline]
+    pub fn wheel_delta(&self) -> [f64; 2] {
+        self.wheel_delta
+    }
+
+    /// Advances to the next frame, resetting the accumulated deltas.
+    pub fn begin_frame(&mut self) {
+        self.frame_delta = [0.0; 2];
+        self.wheel_delta = [0.0; 2];
+        self.just_pressed.clear();
+        self.just_released.clear();
+        self.motion.update();
+    }
+
+    /// Grabs and hides the cursor, or releases it, if relative mode changed
+    /// since the last call.
+    ///
+    /// Prefers [`CursorGrabMode::Locked`] and falls back to
+    /// [`CursorGrabMode::Confined`] on platforms that only support one of
+    /// them (macOS supports only locking, Windows only confining).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the platform supports neither grab mode. Relative
+    /// mode is then turned off again, so it never runs without the grab.
+    pub fn apply_to_window(&mut self, window: &Window) -> anyhow::Result<()> {
+        match self.pending_grab.take() {
+            Some(true) => {
+                let grabbed = window
+                    .set_cursor_grab(CursorGrabMode::Locked)
+                    .or_else(|_| window.set_cursor_grab(CursorGrabMode::Confined));
+                if let Err(e) = grabbed {
+                    self.relative_mode = false;
+                    self.frame_delta = [0.0; 2];
+                    anyhow::bail!("failed to grab cursor: {e}");
+                }
+                window.set_cursor_visible(false);
+            }
+            Some(false) => {
+                window
+                    .set_cursor_grab(CursorGrabMode::None)
+                    .map_err(|e| anyhow::anyhow!("failed to release cursor: {e}"))?;
+                window.set_cursor_visible(true);
+            }
+            None => {}
+        }
+        Ok(())
+    }
+}
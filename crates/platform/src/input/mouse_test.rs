@@ -0,0 +1,91 @@
+//! Unit tests for mouse input.
+
+use super::{MouseInput, PIXELS_PER_LINE};
+use winit::dpi::PhysicalPosition;
+use winit::event::{MouseButton, MouseScrollDelta};
+
+fn assert_close(actual: [f64; 2], expected: [f64; 2]) {
+    assert!(
+        (actual[0] - expected[0]).abs() < 1e-9 && (actual[1] - expected[1]).abs() < 1e-9,
+        "{actual:?} != {expected:?}"
+    );
+}
+
+/// Verifies raw motion is only collected in relative mode and resets each
+/// frame.
+#[test]
+fn relative_mode_collects_raw_motion() {
+    let mut mouse = MouseInput::default();
+    mouse.handle_raw_motion((5.0, 5.0));
+    assert_close(mouse.frame_delta(), [0.0, 0.0]);
+    assert_eq!(mouse.motion.iter().count(), 0);
+
+    mouse.set_relative_mode(true);
+    mouse.handle_raw_motion((3.0, -2.0));
+    mouse.handle_raw_motion((-1.0, 0.5));
+    assert_close(mouse.frame_delta(), [2.0, -1.5]);
+    assert_eq!(mouse.motion.iter().count(), 2);
+
+    mouse.begin_frame();
+    assert_close(mouse.frame_delta(), [0.0, 0.0]);
+    mouse.handle_raw_motion((1.0, 1.0));
+    mouse.set_relative_mode(false);
+    assert_close(mouse.frame_delta(), [0.0, 0.0]);
+}
+
+/// Verifies toggling relative mode records the matching cursor grab, and
+/// that setting the current mode again records nothing.
+#[test]
+fn relative_mode_records_pending_grab() {
+    let mut mouse = MouseInput::default();
+    mouse.set_relative_mode(false);
+    assert_eq!(mouse.pending_grab(), None);
+
+    mouse.set_relative_mode(true);
+    assert_eq!(mouse.pending_grab(), Some(true));
+    mouse.set_relative_mode(false);
+    assert_eq!(mouse.pending_grab(), Some(false));
+}
+
+/// Verifies held, just pressed, and just released button state.
+#[test]
+fn tracks_button_state() {
+    let mut mouse = MouseInput::default();
+    mouse.handle_button(MouseButton::Left, true);
+    mouse.handle_button(MouseButton::Left, true);
+    assert!(mouse.pressed(MouseButton::Left));
+    assert!(mouse.just_pressed(MouseButton::Left));
+    assert!(!mouse.pressed(MouseButton::Right));
+
+    mouse.begin_frame();
+    assert!(mouse.pressed(MouseButton::Left));
+    assert!(!mouse.just_pressed(MouseButton::Left));
+
+    mouse.handle_button(MouseButton::Left, false);
+    mouse.handle_button(MouseButton::Right, false);
+    assert!(!mouse.pressed(MouseButton::Left));
+    assert!(mouse.just_released(MouseButton::Left));
+    assert!(!mouse.just_released(MouseButton::Right));
+
+    mouse.handle_button(MouseButton::Middle, true);
+    mouse.begin_frame();
+    mouse.release_all();
+    assert!(mouse.just_released(MouseButton::Middle));
+    assert!(!mouse.pressed(MouseButton::Middle));
+}
+
+/// Verifies wheel lines and touchpad pixels add up over a frame.
+#[test]
+fn accumulates_wheel_movement() {
+    let mut mouse = MouseInput::default();
+    mouse.handle_wheel(MouseScrollDelta::LineDelta(0.0, 1.0));
+    mouse.handle_wheel(MouseScrollDelta::LineDelta(0.0, 2.0));
+    mouse.handle_wheel(MouseScrollDelta::PixelDelta(PhysicalPosition::new(
+        PIXELS_PER_LINE,
+        -PIXELS_PER_LINE / 2.0,
+    )));
+    assert_close(mouse.wheel_delta(), [1.0, 2.5]);
+
+    mouse.begin_frame();
+    assert_close(mouse.wheel_delta(), [0.0, 0.0]);
+}
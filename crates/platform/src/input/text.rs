@@ -0,0 +1,181 @@
+//! Text input and IME composition.
+//!
+//! Raw key codes are not enough for text fields: layouts, dead keys, and
+//! input method editors (IMEs) for CJK languages all produce text that does
+//! not map 1:1 to key presses. This module surfaces the composed text stream
+//! and the IME's in-progress (preedit) state.
+
+use ecs::Events;
+
+/// IME composition event, mirroring [`winit::event::Ime`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImeEvent {
+    /// The IME was enabled for the window.
+    Enabled,
+    /// The in-progress composition changed.
+    Preedit {
+        /// Composition text; empty when the composition was cleared.
+        text: String,
+        /// Byte range of the cursor/selection within `text`, if shown.
+        cursor: Option<(usize, usize)>,
+    },
+    /// The composition was committed as final text.
+    Commit(String),
+    /// The IME was disabled for the window.
+    Disabled,
+}
+
+impl From<winit::event::Ime> for ImeEvent {
+    fn from(ime: winit::event::Ime) -> Self {
+        match ime {
+            winit::event::Ime::Enabled => Self::Enabled,
+            winit::event::Ime::Preedit(text, cursor) => Self::Preedit { text, cursor },
+            winit::event::Ime::Commit(text) => Self::Commit(text),
+            winit::event::Ime::Disabled => Self::Disabled,
+        }
+    }
+}
+
+/// A character of committed text, in the style of `ReceivedCharacter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReceivedCharacter {
+    /// Focus owner that receives the character.
+    pub owner: u64,
+    /// The Unicode scalar value typed.
+    pub char: char,
+}
+
+/// In-progress IME composition shown inline by the focused text field.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Preedit {
+    /// Composition text.
+    pub text: String,
+    /// Byte range of the cursor/selection within `text`, if shown.
+    pub cursor: Option<(usize, usize)>,
+}
+
+/// Text input resource routing typed text to the focused UI element.
+///
+/// Text is only produced while an element holds focus via
+/// [`focus`](Self::focus). The platform layer should enable the OS IME
+/// whenever [`ime_allowed`](Self::ime_allowed) returns `true`.
+///
+/// # Example
+///
+/// ```
+/// use platform::input::TextInput;
+///
+/// let mut text = TextInput::default();
+/// text.focus(42, true);
+/// text.handle_text("hé\u{8}");
+///
+/// let typed: String = text.characters.iter().map(|c| c.char).collect();
+/// assert_eq!(typed, "hé");
+/// ```
+#[derive(Debug, Default)]
+pub struct TextInput {
+    /// Committed characters for the focused element.
+    pub characters: Events<ReceivedCharacter>,
+    focus: Option<u64>,
+    ime_allowed: bool,
+    ime_active: bool,
+    preedit: Preedit,
+}
+
+impl TextInput {
+    /// Gives text focus to `owner`, optionally allowing IME composition.
+    ///
+    /// Any in-progress composition for the previous owner is discarded.
+    pub fn focus(&mut self, owner: u64, allow_ime: bool) {
+        if self.focus != Some(owner) {
+            self.preedit = Preedit::default();
+        }
+        self.focus = Some(owner);
+        self.ime_allowed = allow_ime;
+    }
+
+    /// Removes text focus; no further text is produced until refocused.
+    pub fn blur(&mut self) {
+        self.focus = None;
+        self.ime_allowed = false;
+        self.preedit = Preedit::default();
+    }
+
+    /// Returns the element that currently has text focus.
+    #[must_use]
+    #[inline]
+    pub fn focused(&self) -> Option<u64> {
+        self.focus
+    }
+
+    /// Returns `true` if the OS IME should be enabled for the window.
+    #[must_use]
+    #[inline]
+    pub fn ime_allowed(&self) -> bool {
+        self.focus.is_some() && self.ime_allowed
+    }
+
+    /// Returns `true` while the IME reports itself enabled.
+    #[must_use]
+    #[inline]
+    pub fn ime_active(&self) -> bool {
+        self.ime_active
+    }
+
+    /// Returns the current composition, if any.
+    #[must_use]
+    pub fn preedit(&self) -> Option<&Preedit> {
+        (!self.preedit.text.is_empty()).then_some(&self.preedit)
+    }
+
+    /// Applies an IME event.
+    pub fn handle_ime(&mut self, event: ImeEvent) {
+        match event {
+            ImeEvent::Enabled => self.ime_active = true,
+            ImeEvent::Disabled => {
+                self.ime_active = false;
+                self.preedit = Preedit::default();
+            }
+            ImeEvent::Preedit { text, cursor } => {
+                if self.ime_allowed() {
+                    self.preedit = Preedit { text, cursor };
+                }
+            }
+            ImeEvent::Commit(text) => {
+                self.preedit = Preedit::default();
+                if self.ime_allowed() {
+                    self.push_text(&text);
+                }
+            }
+        }
+    }
+
+    /// Applies text produced by a key press (outside IME composition).
+    ///
+    /// Control characters such as backspace and escape are dropped; text
+    /// fields handle those through key events instead.
+    pub fn handle_text(&mut self, text: &str) {
+        if self.ime_active && !self.preedit.text.is_empty() {
+            // The IME owns the keystrokes while composing.
+            return;
+        }
+        self.push_text(text);
+    }
+
+    /// Advances to the next frame.
+    pub fn begin_frame(&mut self) {
+        self.characters.update();
+    }
+
+    /// Sends each printable character of `text` to the focused element.
+    fn push_text(&mut self, text: &str) {
+        let Some(owner) = self.focus else {
+            return;
+        };
+        self.characters.send_batch(
+            text.chars()
+                .filter(|c| !c.is_control())
+                .map(|char| ReceivedCharacter { owner, char }),
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! Unit tests for text input and IME handling.
+
+use super::{ImeEvent, TextInput};
+
+/// Collects the committed text for the current frame.
+fn typed(input: &TextInput) -> String {
+    input.characters.iter().map(|c| c.char).collect()
+}
+
+/// Verifies that text is dropped when nothing has focus.
+#[test]
+fn text_requires_focus() {
+    let mut input = TextInput::default();
+    input.handle_text("abc");
+    assert!(input.characters.is_empty());
+}
+
+/// Verifies that multi-byte characters are delivered intact.
+#[test]
+fn unicode_text_is_preserved() {
+    let mut input = TextInput::default();
+    input.focus(1, false);
+    input.handle_text("日本🎮");
+    assert_eq!(typed(&input), "日本🎮");
+    assert!(input.characters.iter().all(|c| c.owner == 1));
+}
+
+/// Verifies the preedit/commit flow of an IME composition.
+#[test]
+fn ime_composition_commits_text() {
+    let mut input = TextInput::default();
+    input.focus(1, true);
+    assert!(input.ime_allowed());
+
+    input.handle_ime(ImeEvent::Enabled);
+    input.handle_ime(ImeEvent::Preedit {
+        text: "にほ".to_owned(),
+        cursor: Some((6, 6)),
+    });
+    assert_eq!(input.preedit().unwrap().text, "にほ");
+
+    // Raw key text is suppressed while composing.
+    input.handle_text("n");
+    assert!(input.characters.is_empty());
+
+    input.handle_ime(ImeEvent::Commit("日本".to_owned()));
+    assert!(input.preedit().is_none());
+    assert_eq!(typed(&input), "日本");
+}
+
+/// Verifies that elements without IME support ignore compositions.
+#[test]
+fn ime_disallowed_ignores_composition() {
+    let mut input = TextInput::default();
+    input.focus(1, false);
+    assert!(!input.ime_allowed());
+
+    input.handle_ime(ImeEvent::Preedit {
+        text: "x".to_owned(),
+        cursor: None,
+    });
+    input.handle_ime(ImeEvent::Commit("x".to_owned()));
+    assert!(input.preedit().is_none());
+    assert!(input.characters.is_empty());
+}
+
+/// Verifies that blurring clears focus and composition.
+#[test]
+fn blur_clears_state() {
+    let mut input = TextInput::default();
+    input.focus(1, true);
+    input.handle_ime(ImeEvent::Preedit {
+        text: "a".to_owned(),
+        cursor: None,
+    });
+    input.blur();
+    assert_eq!(input.focused(), None);
+    assert!(input.preedit().is_none());
+    assert!(!input.ime_allowed());
+}
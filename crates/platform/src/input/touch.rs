@@ -0,0 +1,376 @@
+//! Touch input and gesture recognition.
+//!
+//! Provides raw [`TouchEvent`]s, the per-frame [`Touches`] state, and a
+//! [`GestureRecognizer`] deriving taps, drags, pinches, and rotations.
+
+use ecs::Events;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Lifecycle phase of a single touch point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchPhase {
+    /// A finger touched the screen.
+    Started,
+    /// A finger moved while touching the screen.
+    Moved,
+    /// A finger was lifted from the screen.
+    Ended,
+    /// The OS cancelled the touch (e.g. a system gesture took over).
+    Cancelled,
+}
+
+/// A raw touch event from the platform.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchEvent {
+    /// Identifier of the touch point, stable from start to end.
+    pub id: u64,
+    /// Phase of the touch point.
+    pub phase: TouchPhase,
+    /// Position in physical window pixels.
+    pub position: [f32; 2],
+    /// Normalized pressure in `0.0..=1.0`, if the device reports it.
+    pub pressure: Option<f32>,
+    /// Time of the event, relative to an arbitrary fixed origin.
+    pub time: Duration,
+}
+
+/// State of an active touch point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Touch {
+    /// Identifier of the touch point.
+    pub id: u64,
+    /// Position where the touch started.
+    pub start_position: [f32; 2],
+    /// Time the touch started.
+    pub start_time: Duration,
+    /// Position at the end of the previous frame.
+    pub previous_position: [f32; 2],
+    /// Current position.
+    pub position: [f32; 2],
+    /// Most recent pressure reading.
+    pub pressure: Option<f32>,
+}
+
+impl Touch {
+    /// Movement since the previous frame.
+    #[must_use]
+    pub fn delta(&self) -> [f32; 2] {
+        sub(self.position, self.previous_position)
+    }
+
+    /// Total movement since the touch started.
+    #[must_use]
+    pub fn distance_from_start(&self) -> f32 {
+        length(sub(self.position, self.start_position))
+    }
+}
+
+/// Queryable per-frame touch state.
+///
+/// Feed every [`TouchEvent`] through [`handle`](Self::handle) and call
+/// [`begin_frame`](Self::begin_frame) at the start of each frame.
+#[derive(Debug, Clone, Default)]
+pub struct Touches {
+    active: HashMap<u64, Touch>,
+    just_pressed: Vec<u64>,
+    just_released: Vec<Touch>,
+}
+
+impl Touches {
+    /// Applies a raw touch event.
+    pub fn handle(&mut self, event: &TouchEvent) {
+        match event.phase {
+            TouchPhase::Started => {
+                self.active.insert(
+                    event.id,
+                    Touch {
+                        id: event.id,
+                        start_position: event.position,
+                        start_time: event.time,
+                        previous_position: event.position,
+                        position: event.position,
+                        pressure: event.pressure,
+                    },
+                );
+                self.just_pressed.push(event.id);
+            }
+            TouchPhase::Moved => {
+                if let Some(touch) = self.active.get_mut(&event.id) {
+                    touch.position = event.position;
+                    touch.pressure = event.pressure.or(touch.pressure);
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                if let Some(mut touch) = self.active.remove(&event.id) {
+                    touch.position = event.position;
+                    self.just_released.push(touch);
+                }
+            }
+        }
+    }
+
+    /// Clears per-frame state; call once at the start of each frame.
+    pub fn begin_frame(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+        for touch in self.active.values_mut() {
+            touch.previous_position = touch.position;
+        }
+    }
+
+    /// Returns the active touch with the given id.
+    #[must_use]
+    pub fn get(&self, id: u64) -> Option<&Touch> {
+        self.active.get(&id)
+    }
+
+    /// Iterates over all active touches.
+    pub fn iter(&self) -> impl Iterator<Item = &Touch> {
+        self.active.values()
+    }
+
+    /// Returns the number of active touches.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Returns `true` if the touch started during this frame.
+    #[must_use]
+    pub fn just_pressed(&self, id: u64) -> bool {
+        self.just_pressed.contains(&id)
+    }
+
+    /// Touches released during this frame, with their final state.
+    #[must_use]
+    pub fn just_released(&self) -> &[Touch] {
+        &self.just_released
+    }
+}
+
+/// A high-level gesture derived from touch input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    /// A short touch without significant movement.
+    Tap {
+        /// Position of the tap.
+        position: [f32; 2],
+    },
+    /// Single-finger movement.
+    Drag {
+        /// Current finger position.
+        position: [f32; 2],
+        /// Movement since the previous event.
+        delta: [f32; 2],
+    },
+    /// Two-finger pinch.
+    Pinch {
+        /// Ratio of the new finger distance to the previous one.
+        scale: f32,
+    },
+    /// Two-finger rotation.
+    Rotate {
+        /// Angle change in radians, counter-clockwise positive.
+        radians: f32,
+    },
+}
+
+/// Thresholds used by the [`GestureRecognizer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureConfig {
+    /// Maximum movement in pixels for a touch to still count as a tap.
+    pub tap_slop: f32,
+    /// Maximum duration of a tap.
+    pub tap_max_duration: Duration,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            tap_slop: 10.0,
+            tap_max_duration: Duration::from_millis(300),
+        }
+    }
+}
+
+/// Derives [`Gesture`]s from a stream of [`TouchEvent`]s.
+///
+/// # Example
+///
+/// ```
+/// use platform::input::{Gesture, GestureRecognizer, TouchEvent, TouchPhase};
+/// use std::time::Duration;
+///
+/// let mut recognizer = GestureRecognizer::default();
+/// let touch = |phase, ms| TouchEvent {
+///     id: 0,
+///     phase,
+///     position: [100.0, 100.0],
+///     pressure: None,
+///     time: Duration::from_millis(ms),
+/// };
+///
+/// recognizer.handle(&touch(TouchPhase::Started, 0));
+/// let gestures = recognizer.handle(&touch(TouchPhase::Ended, 80));
+/// assert_eq!(gestures, [Gesture::Tap { position: [100.0, 100.0] }]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GestureRecognizer {
+    config: GestureConfig,
+    touches: Touches,
+    dragging: bool,
+    multi_touch: bool,
+}
+
+impl GestureRecognizer {
+    /// Creates a recognizer with custom thresholds.
+    #[must_use]
+    pub fn new(config: GestureConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Processes a touch event and returns any gestures it completes.
+    pub fn handle(&mut self, event: &TouchEvent) -> Vec<Gesture> {
+        let mut gestures = Vec::new();
+        let pair_before = self.pair();
+        let previous = self.touches.get(event.id).map(|touch| touch.position);
+        self.touches.handle(event);
+
+        match event.phase {
+            TouchPhase::Started => {
+                if self.touches.count() > 1 {
+                    self.multi_touch = true;
+                    self.dragging = false;
+                }
+            }
+            TouchPhase::Moved => {
+                if let (Some(before), Some(after)) = (pair_before, self.pair()) {
+                    gestures.extend(two_finger_gestures(before, after));
+                } else if !self.multi_touch {
+                    let touch = self.touches.get(event.id).copied();
+                    if let (Some(touch), Some(previous)) = (touch, previous) {
+                        if self.dragging || touch.distance_from_start() > self.config.tap_slop {
+                            self.dragging = true;
+                            gestures.push(Gesture::Drag {
+                                position: touch.position,
+                                delta: sub(touch.position, previous),
+                            });
+                        }
+                    }
+                }
+            }
+            TouchPhase::Ended => {
+                let released = self.touches.just_released().last().copied();
+                if let Some(touch) = released {
+                    let is_tap = !self.multi_touch
+                        && !self.dragging
+                        && touch.distance_from_start() <= self.config.tap_slop
+                        && event.time.saturating_sub(touch.start_time)
+                            <= self.config.tap_max_duration;
+                    if is_tap {
+                        gestures.push(Gesture::Tap {
+                            position: touch.position,
+                        });
+                    }
+                }
+            }
+            TouchPhase::Cancelled => {}
+        }
+
+        if self.touches.count() == 0 {
+            self.dragging = false;
+            self.multi_touch = false;
+        }
+        self.touches.begin_frame();
+        gestures
+    }
+
+    /// Returns the positions of the two lowest-id touches, if exactly two are active.
+    fn pair(&self) -> Option<([f32; 2], [f32; 2])> {
+        if self.touches.count() != 2 {
+            return None;
+        }
+        let mut touches: Vec<_> = self.touches.iter().collect();
+        touches.sort_by_key(|touch| touch.id);
+        Some((touches[0].position, touches[1].position))
+    }
+}
+
+/// Computes pinch and rotation between two finger pairs.
+fn two_finger_gestures(before: ([f32; 2], [f32; 2]), after: ([f32; 2], [f32; 2])) -> Vec<Gesture> {
+    let mut gestures = Vec::new();
+    let span_before = sub(before.1, before.0);
+    let span_after = sub(after.1, after.0);
+
+    let distance_before = length(span_before);
+    if distance_before > f32::EPSILON {
+        let scale = length(span_after) / distance_before;
+        if (scale - 1.0).abs() > f32::EPSILON {
+            gestures.push(Gesture::Pinch { scale });
+        }
+    }
+
+    let mut radians = span_after[1].atan2(span_after[0]) - span_before[1].atan2(span_before[0]);
+    if radians > std::f32::consts::PI {
+        radians -= std::f32::consts::TAU;
+    } else if radians < -std::f32::consts::PI {
+        radians += std::f32::consts::TAU;
+    }
+    if radians.abs() > f32::EPSILON {
+        gestures.push(Gesture::Rotate { radians });
+    }
+    gestures
+}
+
+fn sub(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] - b[0], a[1] - b[1]]
+}
+
+fn length(v: [f32; 2]) -> f32 {
+    v[0].hypot(v[1])
+}
+
+/// Touch input resource combining frame state, raw events, and gestures.
+///
+/// The platform layer feeds window touch events through
+/// [`handle`](Self::handle); systems query [`touches`](Self::touches) for
+/// current state or read the [`Events`] queues for discrete input.
+#[derive(Debug, Default)]
+pub struct TouchInput {
+    /// Per-frame state of all touch points.
+    pub touches: Touches,
+    /// Raw touch events received this frame and last.
+    pub events: Events<TouchEvent>,
+    /// Gestures recognized this frame and last.
+    pub gestures: Events<Gesture>,
+    recognizer: GestureRecognizer,
+}
+
+impl TouchInput {
+    /// Creates a touch input resource with custom gesture thresholds.
+    #[must_use]
+    pub fn new(config: GestureConfig) -> Self {
+        Self {
+            recognizer: GestureRecognizer::new(config),
+            ..Self::default()
+        }
+    }
+
+    /// Applies a raw touch event, updating state and recognizing gestures.
+    pub fn handle(&mut self, event: TouchEvent) {
+        self.touches.handle(&event);
+        self.gestures.send_batch(self.recognizer.handle(&event));
+        self.events.send(event);
+    }
+
+    /// Advances to the next frame; call once before processing its events.
+    pub fn begin_frame(&mut self) {
+        self.touches.begin_frame();
+        self.events.update();
+        self.gestures.update();
+    }
+}
@@ -0,0 +1,105 @@
+//! Unit tests for touch input and gesture recognition.
+
+use super::{Gesture, GestureRecognizer, TouchEvent, TouchInput, TouchPhase, Touches};
+use std::time::Duration;
+
+/// Builds a touch event at the given time in milliseconds.
+fn touch(id: u64, phase: TouchPhase, position: [f32; 2], ms: u64) -> TouchEvent {
+    TouchEvent {
+        id,
+        phase,
+        position,
+        pressure: None,
+        time: Duration::from_millis(ms),
+    }
+}
+
+/// Verifies that frame state tracks presses, moves, and releases.
+#[test]
+fn touches_track_frame_state() {
+    let mut touches = Touches::default();
+    touches.handle(&touch(1, TouchPhase::Started, [0.0, 0.0], 0));
+    assert!(touches.just_pressed(1));
+    assert_eq!(touches.count(), 1);
+
+    touches.begin_frame();
+    touches.handle(&touch(1, TouchPhase::Moved, [3.0, 4.0], 16));
+    assert!(!touches.just_pressed(1));
+    let delta = touches.get(1).unwrap().delta();
+    assert!((delta[0] - 3.0).abs() < 1e-6 && (delta[1] - 4.0).abs() < 1e-6);
+    assert!((touches.get(1).unwrap().distance_from_start() - 5.0).abs() < 1e-6);
+
+    touches.handle(&touch(1, TouchPhase::Ended, [3.0, 4.0], 32));
+    assert_eq!(touches.count(), 0);
+    assert_eq!(touches.just_released().len(), 1);
+}
+
+/// Verifies that a long press is not reported as a tap.
+#[test]
+fn slow_press_is_not_tap() {
+    let mut recognizer = GestureRecognizer::default();
+    recognizer.handle(&touch(1, TouchPhase::Started, [0.0, 0.0], 0));
+    let gestures = recognizer.handle(&touch(1, TouchPhase::Ended, [0.0, 0.0], 1000));
+    assert!(gestures.is_empty());
+}
+
+/// Verifies that movement beyond the slop produces drags instead of a tap.
+#[test]
+fn movement_produces_drag() {
+    let mut recognizer = GestureRecognizer::default();
+    recognizer.handle(&touch(1, TouchPhase::Started, [0.0, 0.0], 0));
+    let gestures = recognizer.handle(&touch(1, TouchPhase::Moved, [20.0, 0.0], 16));
+    assert_eq!(
+        gestures,
+        [Gesture::Drag {
+            position: [20.0, 0.0],
+            delta: [20.0, 0.0]
+        }]
+    );
+    let gestures = recognizer.handle(&touch(1, TouchPhase::Ended, [20.0, 0.0], 50));
+    assert!(gestures.is_empty());
+}
+
+/// Verifies that spreading two fingers produces a pinch.
+#[test]
+fn two_fingers_pinch() {
+    let mut recognizer = GestureRecognizer::default();
+    recognizer.handle(&touch(1, TouchPhase::Started, [0.0, 0.0], 0));
+    recognizer.handle(&touch(2, TouchPhase::Started, [10.0, 0.0], 0));
+    let gestures = recognizer.handle(&touch(2, TouchPhase::Moved, [20.0, 0.0], 16));
+    assert_eq!(gestures, [Gesture::Pinch { scale: 2.0 }]);
+}
+
+/// Verifies that rotating two fingers produces a rotation.
+#[test]
+fn two_fingers_rotate() {
+    let mut recognizer = GestureRecognizer::default();
+    recognizer.handle(&touch(1, TouchPhase::Started, [0.0, 0.0], 0));
+    recognizer.handle(&touch(2, TouchPhase::Started, [10.0, 0.0], 0));
+    let gestures = recognizer.handle(&touch(2, TouchPhase::Moved, [0.0, 10.0], 16));
+    let Some(Gesture::Rotate { radians }) = gestures.last() else {
+        panic!("expected rotation, got {gestures:?}");
+    };
+    assert!((radians - std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+}
+
+/// Verifies that the input resource publishes raw events and gestures.
+#[test]
+fn touch_input_publishes_events() {
+    let mut input = TouchInput::default();
+    input.handle(touch(7, TouchPhase::Started, [5.0, 5.0], 0));
+    input.handle(touch(7, TouchPhase::Ended, [5.0, 5.0], 50));
+
+    assert_eq!(input.events.len(), 2);
+    assert_eq!(
+        input.gestures.iter().copied().collect::<Vec<_>>(),
+        [Gesture::Tap {
+            position: [5.0, 5.0]
+        }]
+    );
+
+    input.begin_frame();
+    input.begin_frame();
+    assert!(input.events.is_empty());
+    assert!(input.gestures.is_empty());
+}
@@ -7,8 +7,12 @@
 //!
 //! The platform crate handles:
 //! - Window creation and lifecycle management
-//! - Input event collection (keyboard, mouse, gamepad)
+//! - Input event collection (keyboard, mouse, gamepad, touch)
 //! - OS-level integration (clipboard, file dialogs, etc.)
+//! - Window visibility tracking (minimized, occluded)
+//! - Web targets: canvas-backed windows and browser-driven frame loops
+//!   (enable the `wasm` feature for `wasm32-unknown-unknown`)
+//! - Mobile lifecycle mapping (suspend/resume, surface loss, orientation)
 //!
 //! # Example
 //!
@@ -24,6 +28,23 @@
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+pub mod input;
+pub mod lifecycle;
+#[cfg(test)]
+mod lifecycle_test;
 pub mod platform;
+pub mod visibility;
+#[cfg(test)]
+mod visibility_test;
+pub mod web;
+#[cfg(test)]
+mod web_test;
 
+pub use lifecycle::{
+    LifecycleAction, LifecycleEvent, LifecycleState, LifecycleTracker, Orientation,
+};
 pub use platform::RustginePlatform;
+pub use visibility::{VisibilityTracker, WindowVisibility};
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub use web::run_animation_frames;
+pub use web::{CanvasConfig, FrameClock, LoopDriver, MAX_FRAME_DELTA};
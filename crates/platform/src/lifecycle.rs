@@ -0,0 +1,245 @@
+//! Mobile application lifecycle.
+//!
+//! Android and iOS suspend applications aggressively: the OS may pause the
+//! activity, destroy the native window (and with it the render surface), and
+//! later resume with a brand-new window. The [`LifecycleTracker`] maps these
+//! OS callbacks onto engine states and tells the caller which actions to take.
+//! winit reports them as [`Event::Suspended`] and [`Event::Resumed`], fed in
+//! through [`LifecycleTracker::handle_winit_event`].
+
+use winit::event::Event;
+
+/// Screen orientation reported by the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    /// Height greater than width.
+    #[default]
+    Portrait,
+    /// Portrait, rotated 180 degrees.
+    PortraitUpsideDown,
+    /// Width greater than height, rotated counter-clockwise.
+    LandscapeLeft,
+    /// Width greater than height, rotated clockwise.
+    LandscapeRight,
+}
+
+impl Orientation {
+    /// Returns `true` for either landscape orientation.
+    #[must_use]
+    #[inline]
+    pub fn is_landscape(self) -> bool {
+        matches!(self, Self::LandscapeLeft | Self::LandscapeRight)
+    }
+}
+
+/// Lifecycle callbacks delivered by the mobile OS.
+///
+/// Names follow Android's activity lifecycle; iOS scene callbacks map onto
+/// the same set (`willEnterForeground` → [`Started`](Self::Started),
+/// `didBecomeActive` → [`Resumed`](Self::Resumed), and so on).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleEvent {
+    /// The application process was created.
+    Created,
+    /// The application became visible.
+    Started,
+    /// The application gained input focus and is in the foreground.
+    Resumed,
+    /// The application lost focus (e.g. a dialog or the app switcher).
+    Paused,
+    /// The application is no longer visible.
+    Stopped,
+    /// The application is being destroyed.
+    Destroyed,
+    /// A native window is available for rendering.
+    SurfaceCreated,
+    /// The native window was destroyed; the render surface is invalid.
+    SurfaceDestroyed,
+    /// The device orientation changed.
+    OrientationChanged(Orientation),
+    /// The OS is low on memory and may kill the process.
+    LowMemory,
+}
+
+/// Engine-level state derived from lifecycle events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LifecycleState {
+    /// Created but not yet started.
+    #[default]
+    Created,
+    /// In the foreground with a valid surface; frames are rendered.
+    Running,
+    /// Visible or alive but not rendering (paused or no surface).
+    Paused,
+    /// Destroyed; the engine should shut down.
+    Terminated,
+}
+
+/// Action the engine must take in response to a lifecycle event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleAction {
+    /// Start the engine subsystems.
+    Startup,
+    /// Pause simulation and rendering.
+    Pause,
+    /// Resume simulation and rendering.
+    Resume,
+    /// Create (or recreate) the render surface for the new native window.
+    RecreateSurface,
+    /// Drop the render surface before the native window goes away.
+    DestroySurface,
+    /// Resize swapchain-dependent resources for the new orientation.
+    Reorient(Orientation),
+    /// Release caches and other reclaimable memory.
+    TrimMemory,
+    /// Shut down the engine subsystems.
+    Shutdown,
+}
+
+/// Maps mobile OS lifecycle events onto engine lifecycle actions.
+///
+/// The engine renders only while the application is both resumed and has a
+/// valid surface, since Android may deliver these in either order.
+///
+/// # Example
+///
+/// ```
+/// use platform::{LifecycleAction, LifecycleEvent, LifecycleState, LifecycleTracker};
+///
+/// let mut tracker = LifecycleTracker::default();
+/// tracker.handle(LifecycleEvent::Created);
+/// tracker.handle(LifecycleEvent::Resumed);
+/// let actions = tracker.handle(LifecycleEvent::SurfaceCreated);
+///
+/// assert!(actions.contains(&LifecycleAction::RecreateSurface));
+/// assert_eq!(tracker.state(), LifecycleState::Running);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LifecycleTracker {
+    state: LifecycleState,
+    started: bool,
+    resumed: bool,
+    has_surface: bool,
+    orientation: Orientation,
+}
+
+impl LifecycleTracker {
+    /// Returns the current engine lifecycle state.
+    #[must_use]
+    #[inline]
+    pub fn state(&self) -> LifecycleState {
+        self.state
+    }
+
+    /// Returns `true` if a render surface currently exists.
+    #[must_use]
+    #[inline]
+    pub fn has_surface(&self) -> bool {
+        self.has_surface
+    }
+
+    /// Returns the last reported orientation.
+    #[must_use]
+    #[inline]
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Processes a lifecycle event and returns the actions to perform, in order.
+    pub fn handle(&mut self, event: LifecycleEvent) -> Vec<LifecycleAction> {
+        let mut actions = Vec::new();
+        if self.state == LifecycleState::Terminated {
+            return actions;
+        }
+
+        match event {
+            LifecycleEvent::Created | LifecycleEvent::Started => {
+                if !self.started {
+                    self.started = true;
+                    actions.push(LifecycleAction::Startup);
+                }
+            }
+            LifecycleEvent::Resumed => self.resumed = true,
+            LifecycleEvent::Paused | LifecycleEvent::Stopped => self.resumed = false,
+            LifecycleEvent::SurfaceCreated => {
+                self.has_surface = true;
+                actions.push(LifecycleAction::RecreateSurface);
+            }
+            LifecycleEvent::SurfaceDestroyed => {
+                self.has_surface = false;
+                // Pause before the surface disappears so no frame targets it.
+                if self.state == LifecycleState::Running {
+                    self.state = LifecycleState::Paused;
+                    actions.push(LifecycleAction::Pause);
+                }
+                actions.push(LifecycleAction::DestroySurface);
+            }
+            LifecycleEvent::OrientationChanged(orientation) => {
+                if orientation != self.orientation {
+                    self.orientation = orientation;
+                    actions.push(LifecycleAction::Reorient(orientation));
+                }
+            }
+            LifecycleEvent::LowMemory => actions.push(LifecycleAction::TrimMemory),
+            LifecycleEvent::Destroyed => {
+                if self.state == LifecycleState::Running {
+                    actions.push(LifecycleAction::Pause);
+                }
+                if self.has_surface {
+                    self.has_surface = false;
+                    actions.push(LifecycleAction::DestroySurface);
+                }
+                if self.started {
+                    actions.push(LifecycleAction::Shutdown);
+                }
+                self.state = LifecycleState::Terminated;
+                return actions;
+            }
+        }
+
+        self.update_running(&mut actions);
+        actions
+    }
+
+    /// Processes a winit event loop event and returns the actions to
+    /// perform, in order, ignoring events unrelated to the lifecycle.
+    ///
+    /// winit folds the OS callbacks into two events: [`Event::Resumed`]
+    /// arrives once the application is in the foreground with a native
+    /// window, and [`Event::Suspended`] before that window is destroyed.
+    pub fn handle_winit_event<T>(&mut self, event: &Event<T>) -> Vec<LifecycleAction> {
+        let events: &[LifecycleEvent] = match event {
+            Event::Resumed => &[
+                LifecycleEvent::Started,
+                LifecycleEvent::Resumed,
+                LifecycleEvent::SurfaceCreated,
+            ],
+            Event::Suspended => &[LifecycleEvent::Paused, LifecycleEvent::SurfaceDestroyed],
+            Event::MemoryWarning => &[LifecycleEvent::LowMemory],
+            _ => &[],
+        };
+        events
+            .iter()
+            .flat_map(|&event| self.handle(event))
+            .collect()
+    }
+
+    /// Transitions between running and paused based on focus and surface.
+    fn update_running(&mut self, actions: &mut Vec<LifecycleAction>) {
+        let should_run = self.started && self.resumed && self.has_surface;
+        match (self.state, should_run) {
+            (LifecycleState::Running, false) => {
+                self.state = LifecycleState::Paused;
+                actions.push(LifecycleAction::Pause);
+            }
+            (LifecycleState::Created | LifecycleState::Paused, true) => {
+                self.state = LifecycleState::Running;
+                actions.push(LifecycleAction::Resume);
+            }
+            (LifecycleState::Created, false) if self.started => {
+                self.state = LifecycleState::Paused;
+            }
+            _ => {}
+        }
+    }
+}
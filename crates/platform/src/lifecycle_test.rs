@@ -0,0 +1,115 @@
+//! Unit tests for mobile lifecycle mapping.
+
+use super::{LifecycleAction, LifecycleEvent, LifecycleState, LifecycleTracker, Orientation};
+use winit::event::Event;
+
+/// Drives a tracker to the running state.
+fn running_tracker() -> LifecycleTracker {
+    let mut tracker = LifecycleTracker::default();
+    tracker.handle(LifecycleEvent::Created);
+    tracker.handle(LifecycleEvent::Started);
+    tracker.handle(LifecycleEvent::Resumed);
+    tracker.handle(LifecycleEvent::SurfaceCreated);
+    tracker
+}
+
+/// Verifies the normal launch sequence starts the engine once and runs.
+#[test]
+fn launch_sequence_runs() {
+    let mut tracker = LifecycleTracker::default();
+    assert_eq!(
+        tracker.handle(LifecycleEvent::Created),
+        [LifecycleAction::Startup]
+    );
+    assert!(tracker.handle(LifecycleEvent::Started).is_empty());
+    assert!(tracker.handle(LifecycleEvent::Resumed).is_empty());
+    assert_eq!(
+        tracker.handle(LifecycleEvent::SurfaceCreated),
+        [LifecycleAction::RecreateSurface, LifecycleAction::Resume]
+    );
+    assert_eq!(tracker.state(), LifecycleState::Running);
+}
+
+/// Verifies that losing the surface pauses before destroying it.
+#[test]
+fn surface_loss_pauses_then_destroys() {
+    let mut tracker = running_tracker();
+    assert_eq!(
+        tracker.handle(LifecycleEvent::SurfaceDestroyed),
+        [LifecycleAction::Pause, LifecycleAction::DestroySurface]
+    );
+    assert_eq!(tracker.state(), LifecycleState::Paused);
+    assert!(!tracker.has_surface());
+}
+
+/// Verifies that a suspend/resume cycle rebuilds the surface and resumes.
+#[test]
+fn resume_recreates_surface() {
+    let mut tracker = running_tracker();
+    tracker.handle(LifecycleEvent::Paused);
+    tracker.handle(LifecycleEvent::SurfaceDestroyed);
+    tracker.handle(LifecycleEvent::Stopped);
+
+    tracker.handle(LifecycleEvent::Started);
+    assert!(tracker.handle(LifecycleEvent::Resumed).is_empty());
+    assert_eq!(
+        tracker.handle(LifecycleEvent::SurfaceCreated),
+        [LifecycleAction::RecreateSurface, LifecycleAction::Resume]
+    );
+}
+
+/// Verifies that winit suspend and resume events drop and recreate the
+/// surface.
+#[test]
+fn winit_events_suspend_and_resume() {
+    let mut tracker = LifecycleTracker::default();
+    assert_eq!(
+        tracker.handle_winit_event(&Event::<()>::Resumed),
+        [
+            LifecycleAction::Startup,
+            LifecycleAction::RecreateSurface,
+            LifecycleAction::Resume
+        ]
+    );
+    assert_eq!(
+        tracker.handle_winit_event(&Event::<()>::Suspended),
+        [LifecycleAction::Pause, LifecycleAction::DestroySurface]
+    );
+    assert_eq!(tracker.state(), LifecycleState::Paused);
+    assert_eq!(
+        tracker.handle_winit_event(&Event::<()>::Resumed),
+        [LifecycleAction::RecreateSurface, LifecycleAction::Resume]
+    );
+    assert!(tracker
+        .handle_winit_event(&Event::<()>::AboutToWait)
+        .is_empty());
+}
+
+/// Verifies that orientation changes are reported only when they differ.
+#[test]
+fn orientation_changes() {
+    let mut tracker = running_tracker();
+    let landscape = LifecycleEvent::OrientationChanged(Orientation::LandscapeLeft);
+    assert_eq!(
+        tracker.handle(landscape),
+        [LifecycleAction::Reorient(Orientation::LandscapeLeft)]
+    );
+    assert!(tracker.handle(landscape).is_empty());
+    assert!(tracker.orientation().is_landscape());
+}
+
+/// Verifies that destruction tears everything down and ignores later events.
+#[test]
+fn destroy_shuts_down() {
+    let mut tracker = running_tracker();
+    assert_eq!(
+        tracker.handle(LifecycleEvent::Destroyed),
+        [
+            LifecycleAction::Pause,
+            LifecycleAction::DestroySurface,
+            LifecycleAction::Shutdown
+        ]
+    );
+    assert_eq!(tracker.state(), LifecycleState::Terminated);
+    assert!(tracker.handle(LifecycleEvent::Resumed).is_empty());
+}
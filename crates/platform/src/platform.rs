@@ -3,7 +3,7 @@
 //! Provides the [`RustginePlatform`] system for managing window creation,
 //! input handling, and OS-level interactions.
 
-use rustgine_core::RustgineSystem;
+use rustgine_core::{RustgineError, RustgineSystem};
 
 /// Platform abstraction layer for the Rustgine engine.
 ///
@@ -38,7 +38,7 @@ impl RustgineSystem for RustginePlatform {
     ///
     /// Returns an error if platform initialization fails (e.g., window creation fails).
     #[inline]
-    fn startup(&mut self) -> anyhow::Result<()> {
+    fn startup(&mut self) -> Result<(), RustgineError> {
         Ok(())
     }
 
@@ -48,7 +48,7 @@ impl RustgineSystem for RustginePlatform {
     ///
     /// Returns an error if cleanup fails.
     #[inline]
-    fn shutdown(&mut self) -> anyhow::Result<()> {
+    fn shutdown(&mut self) -> Result<(), RustgineError> {
         Ok(())
     }
 }
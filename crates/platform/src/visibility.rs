@@ -0,0 +1,106 @@
+//! Window visibility tracking.
+//!
+//! Provides the [`VisibilityTracker`], which folds minimize, occlusion, and
+//! resize notifications from the windowing system into a single
+//! [`WindowVisibility`] so the engine can suspend rendering while nothing
+//! is visible.
+
+use winit::event::WindowEvent;
+
+/// Whether the window's contents can currently be seen by the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WindowVisibility {
+    /// The window is at least partially visible.
+    #[default]
+    Visible,
+    /// The window is minimized or has a zero-sized client area.
+    Minimized,
+    /// The window is fully covered by other windows or on a hidden workspace.
+    Occluded,
+}
+
+impl WindowVisibility {
+    /// Returns `true` if rendering would produce visible output.
+    #[must_use]
+    #[inline]
+    pub fn is_visible(self) -> bool {
+        matches!(self, Self::Visible)
+    }
+}
+
+/// Tracks window visibility from platform window events.
+///
+/// Each setter returns the new [`WindowVisibility`] when it changes, so
+/// callers only need to react to transitions.
+///
+/// # Example
+///
+/// ```
+/// use platform::{VisibilityTracker, WindowVisibility};
+///
+/// let mut tracker = VisibilityTracker::default();
+/// assert_eq!(tracker.set_minimized(true), Some(WindowVisibility::Minimized));
+/// assert_eq!(tracker.set_minimized(true), None);
+/// assert_eq!(tracker.set_minimized(false), Some(WindowVisibility::Visible));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VisibilityTracker {
+    minimized: bool,
+    zero_sized: bool,
+    occluded: bool,
+}
+
+impl VisibilityTracker {
+    /// Returns the current window visibility.
+    ///
+    /// Minimization takes precedence over occlusion.
+    #[must_use]
+    pub fn visibility(&self) -> WindowVisibility {
+        if self.minimized || self.zero_sized {
+            WindowVisibility::Minimized
+        } else if self.occluded {
+            WindowVisibility::Occluded
+        } else {
+            WindowVisibility::Visible
+        }
+    }
+
+    /// Records a minimize or restore notification.
+    pub fn set_minimized(&mut self, minimized: bool) -> Option<WindowVisibility> {
+        self.update(|tracker| tracker.minimized = minimized)
+    }
+
+    /// Records an occlusion notification.
+    pub fn set_occluded(&mut self, occluded: bool) -> Option<WindowVisibility> {
+        self.update(|tracker| tracker.occluded = occluded)
+    }
+
+    /// Records a resize of the window's client area.
+    ///
+    /// Some platforms report minimization only as a resize to zero.
+    pub fn resized(&mut self, width: u32, height: u32) -> Option<WindowVisibility> {
+        self.update(|tracker| tracker.zero_sized = width == 0 || height == 0)
+    }
+
+    /// Records a window event, ignoring events that do not affect
+    /// visibility.
+    ///
+    /// winit reports minimization as [`Occluded`](WindowEvent::Occluded) on
+    /// some platforms and as a resize to zero on others; where it reports
+    /// neither, call [`set_minimized`](Self::set_minimized) as well.
+    pub fn handle_window_event(&mut self, event: &WindowEvent) -> Option<WindowVisibility> {
+        match event {
+            WindowEvent::Occluded(occluded) => self.set_occluded(*occluded),
+            WindowEvent::Resized(size) => self.resized(size.width, size.height),
+            _ => None,
+        }
+    }
+
+    /// Applies `change` and reports the new visibility if it differs.
+    fn update(&mut self, change: impl FnOnce(&mut Self)) -> Option<WindowVisibility> {
+        let before = self.visibility();
+        change(self);
+        let after = self.visibility();
+        (before != after).then_some(after)
+    }
+}
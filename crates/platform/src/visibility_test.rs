@@ -0,0 +1,72 @@
+//! Unit tests for window visibility tracking.
+
+use super::{VisibilityTracker, WindowVisibility};
+use winit::dpi::PhysicalSize;
+use winit::event::WindowEvent;
+
+/// Verifies that a new tracker reports a visible window.
+#[test]
+fn default_is_visible() {
+    let tracker = VisibilityTracker::default();
+    assert_eq!(tracker.visibility(), WindowVisibility::Visible);
+    assert!(tracker.visibility().is_visible());
+}
+
+/// Verifies that occlusion transitions are reported once.
+#[test]
+fn occlusion_transitions() {
+    let mut tracker = VisibilityTracker::default();
+    assert_eq!(tracker.set_occluded(true), Some(WindowVisibility::Occluded));
+    assert_eq!(tracker.set_occluded(true), None);
+    assert_eq!(tracker.set_occluded(false), Some(WindowVisibility::Visible));
+}
+
+/// Verifies that a zero-sized resize counts as minimized.
+#[test]
+fn zero_size_is_minimized() {
+    let mut tracker = VisibilityTracker::default();
+    assert_eq!(tracker.resized(0, 0), Some(WindowVisibility::Minimized));
+    assert_eq!(tracker.resized(800, 600), Some(WindowVisibility::Visible));
+}
+
+/// Verifies that minimization takes precedence over occlusion.
+#[test]
+fn minimized_overrides_occluded() {
+    let mut tracker = VisibilityTracker::default();
+    tracker.set_occluded(true);
+    assert_eq!(
+        tracker.set_minimized(true),
+        Some(WindowVisibility::Minimized)
+    );
+    assert_eq!(
+        tracker.set_minimized(false),
+        Some(WindowVisibility::Occluded)
+    );
+}
+
+/// Verifies that occlusion and resize window events update the tracker,
+/// and other events are ignored.
+#[test]
+fn window_events_update_visibility() {
+    let mut tracker = VisibilityTracker::default();
+    assert_eq!(
+        tracker.handle_window_event(&WindowEvent::Occluded(true)),
+        Some(WindowVisibility::Occluded)
+    );
+    assert_eq!(
+        tracker.handle_window_event(&WindowEvent::Resized(PhysicalSize::new(0, 0))),
+        Some(WindowVisibility::Minimized)
+    );
+    assert_eq!(
+        tracker.handle_window_event(&WindowEvent::Focused(true)),
+        None
+    );
+    assert_eq!(
+        tracker.handle_window_event(&WindowEvent::Resized(PhysicalSize::new(800, 600))),
+        Some(WindowVisibility::Occluded)
+    );
+    assert_eq!(
+        tracker.handle_window_event(&WindowEvent::Occluded(false)),
+        Some(WindowVisibility::Visible)
+    );
+}
@@ -0,0 +1,252 @@
+//! Web (`wasm32-unknown-unknown`) platform support.
+//!
+//! Browsers own the event loop: the engine cannot block a thread or listen
+//! for OS signals, and frames are driven by `requestAnimationFrame` into a
+//! canvas element instead of a native window. This module describes those
+//! differences so the rest of the platform layer can select the right path
+//! at compile time.
+//!
+//! With the `wasm` feature on `wasm32`, [`run_animation_frames`] drives a
+//! callback from `requestAnimationFrame` and [`CanvasConfig::find_canvas`]
+//! looks up the canvas to create the window on.
+
+use std::time::Duration;
+
+/// Longest frame delta reported by [`FrameClock`].
+///
+/// Browsers stop sending animation frames to hidden tabs, so the first frame
+/// after the tab comes back would otherwise step the simulation by the whole
+/// time it was hidden.
+pub const MAX_FRAME_DELTA: Duration = Duration::from_millis(250);
+
+/// How the main loop is driven on the current target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopDriver {
+    /// The engine owns the loop and ticks frames on a timer (desktop).
+    Native,
+    /// The browser drives frames through `requestAnimationFrame` callbacks.
+    AnimationFrame,
+}
+
+impl LoopDriver {
+    /// Returns the loop driver for the target being compiled.
+    ///
+    /// `wasm32` targets use [`AnimationFrame`](Self::AnimationFrame). The
+    /// `wasm` feature alone does not change the driver, so native builds with
+    /// it enabled keep their timer loop and Ctrl+C handling.
+    #[must_use]
+    pub const fn for_target() -> Self {
+        if cfg!(target_arch = "wasm32") {
+            Self::AnimationFrame
+        } else {
+            Self::Native
+        }
+    }
+
+    /// Returns `true` if the engine may install OS signal handlers
+    /// (e.g. Ctrl+C) on this target.
+    #[must_use]
+    #[inline]
+    pub const fn supports_os_signals(self) -> bool {
+        matches!(self, Self::Native)
+    }
+}
+
+/// Turns `requestAnimationFrame` timestamps into frame deltas.
+///
+/// # Example
+///
+/// ```
+/// use platform::FrameClock;
+/// use std::time::Duration;
+///
+/// let mut clock = FrameClock::new();
+/// assert_eq!(clock.tick(1000.0), Duration::ZERO);
+/// assert_eq!(clock.tick(1016.0), Duration::from_millis(16));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameClock {
+    last: Option<f64>,
+}
+
+impl FrameClock {
+    /// Creates a clock that has not seen a frame yet.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { last: None }
+    }
+
+    /// Returns the time since the previous frame for a callback at
+    /// `timestamp_ms`, the `DOMHighResTimeStamp` passed to the callback.
+    ///
+    /// The first frame, and a timestamp earlier than the previous one, report
+    /// zero. Deltas are clamped to [`MAX_FRAME_DELTA`].
+    pub fn tick(&mut self, timestamp_ms: f64) -> Duration {
+        let delta = self
+            .last
+            .map_or(0.0, |last| (timestamp_ms - last).max(0.0) / 1000.0);
+        self.last = Some(timestamp_ms);
+        if delta.is_finite() {
+            Duration::from_secs_f64(delta).min(MAX_FRAME_DELTA)
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+/// Calls `frame` from `requestAnimationFrame` with the time since the
+/// previous frame, until it returns `false`.
+///
+/// Returns once the first frame is requested; the browser drives the rest.
+///
+/// # Errors
+///
+/// Returns an error if there is no browser window or the frame cannot be
+/// requested.
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub fn run_animation_frames(
+    mut frame: impl FnMut(Duration) -> bool + 'static,
+) -> anyhow::Result<()> {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::JsCast;
+
+    type FrameCallback = Closure<dyn FnMut(f64)>;
+
+    let window = web_sys::window().ok_or_else(|| anyhow::anyhow!("no browser window"))?;
+    // The callback re-requests itself, so it has to own a handle to itself
+    let callback: Rc<RefCell<Option<FrameCallback>>> = Rc::new(RefCell::new(None));
+    let next = Rc::clone(&callback);
+    let browser = window.clone();
+    let mut clock = FrameClock::new();
+
+    *callback.borrow_mut() = Some(Closure::new(move |timestamp_ms: f64| {
+        let running = frame(clock.tick(timestamp_ms))
+            && next.borrow().as_ref().is_some_and(|callback| {
+                browser
+                    .request_animation_frame(callback.as_ref().unchecked_ref())
+                    .is_ok()
+            });
+        if !running {
+            // Dropping the closure ends the loop
+            next.borrow_mut().take();
+        }
+    }));
+
+    let requested = callback
+        .borrow()
+        .as_ref()
+        .map(|callback| window.request_animation_frame(callback.as_ref().unchecked_ref()));
+    match requested {
+        Some(Err(e)) => anyhow::bail!("failed to request an animation frame: {e:?}"),
+        _ => Ok(()),
+    }
+}
+
+/// Canvas-backed window configuration for web builds.
+///
+/// # Example
+///
+/// ```
+/// use platform::CanvasConfig;
+///
+/// let canvas = CanvasConfig::new("#game");
+/// assert!(canvas.fit_to_parent);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanvasConfig {
+    /// CSS selector of the `<canvas>` element to render into.
+    pub selector: String,
+
+    /// Resize the canvas to fill its parent element.
+    pub fit_to_parent: bool,
+
+    /// Scale the backing buffer by `window.devicePixelRatio` for sharp output
+    /// on high-DPI displays.
+    pub use_device_pixel_ratio: bool,
+}
+
+impl Default for CanvasConfig {
+    fn default() -> Self {
+        Self::new("canvas")
+    }
+}
+
+impl CanvasConfig {
+    /// Creates a configuration targeting the canvas matching `selector`.
+    #[must_use]
+    pub fn new(selector: &str) -> Self {
+        Self {
+            selector: selector.to_owned(),
+            fit_to_parent: true,
+            use_device_pixel_ratio: true,
+        }
+    }
+
+    /// Finds the `<canvas>` element matching [`selector`](Self::selector).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no document, nothing matches the
+    /// selector, or the matching element is not a canvas.
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    pub fn find_canvas(&self) -> anyhow::Result<web_sys::HtmlCanvasElement> {
+        use wasm_bindgen::JsCast;
+
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or_else(|| anyhow::anyhow!("no browser document"))?;
+        document
+            .query_selector(&self.selector)
+            .map_err(|e| anyhow::anyhow!("invalid canvas selector `{}`: {e:?}", self.selector))?
+            .ok_or_else(|| anyhow::anyhow!("no element matches `{}`", self.selector))?
+            .dyn_into::<web_sys::HtmlCanvasElement>()
+            .map_err(|_| anyhow::anyhow!("`{}` is not a canvas", self.selector))
+    }
+
+    /// Returns window attributes that create the window on the configured
+    /// canvas.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the canvas cannot be found; see
+    /// [`find_canvas`](Self::find_canvas).
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    pub fn window_attributes(&self) -> anyhow::Result<winit::window::WindowAttributes> {
+        use winit::platform::web::WindowAttributesExtWebSys;
+
+        Ok(winit::window::Window::default_attributes()
+            .with_canvas(Some(self.find_canvas()?))
+            .with_prevent_default(true))
+    }
+
+    /// Computes the backing buffer size for a canvas of the given CSS size.
+    ///
+    /// # Arguments
+    ///
+    /// * `css_width` / `css_height` - Canvas size in CSS pixels
+    /// * `device_pixel_ratio` - The browser's `devicePixelRatio`
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn physical_size(
+        &self,
+        css_width: u32,
+        css_height: u32,
+        device_pixel_ratio: f64,
+    ) -> (u32, u32) {
+        let scale = if self.use_device_pixel_ratio && device_pixel_ratio > 0.0 {
+            device_pixel_ratio
+        } else {
+            1.0
+        };
+        (
+            (f64::from(css_width) * scale).round() as u32,
+            (f64::from(css_height) * scale).round() as u32,
+        )
+    }
+}
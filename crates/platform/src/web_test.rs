@@ -0,0 +1,46 @@
+//! Unit tests for web platform support.
+
+use super::{CanvasConfig, FrameClock, LoopDriver, MAX_FRAME_DELTA};
+use std::time::Duration;
+
+/// Verifies that native builds keep the timer loop and OS signals, even with
+/// the `wasm` feature enabled.
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn native_targets_drive_their_own_loop() {
+    assert_eq!(LoopDriver::for_target(), LoopDriver::Native);
+    assert!(LoopDriver::for_target().supports_os_signals());
+    assert!(!LoopDriver::AnimationFrame.supports_os_signals());
+}
+
+/// Verifies that frame deltas follow the animation frame timestamps.
+#[test]
+fn frame_clock_measures_deltas() {
+    let mut clock = FrameClock::new();
+    assert_eq!(clock.tick(500.0), Duration::ZERO);
+    assert_eq!(clock.tick(516.0), Duration::from_millis(16));
+    assert_eq!(clock.tick(549.5), Duration::from_micros(33_500));
+}
+
+/// Verifies that long pauses are clamped and time never runs backwards.
+#[test]
+fn frame_clock_clamps_deltas() {
+    let mut clock = FrameClock::new();
+    clock.tick(0.0);
+    assert_eq!(clock.tick(60_000.0), MAX_FRAME_DELTA);
+    assert_eq!(clock.tick(59_000.0), Duration::ZERO);
+    assert_eq!(clock.tick(f64::NAN), Duration::ZERO);
+}
+
+/// Verifies that the backing buffer follows the device pixel ratio when
+/// enabled.
+#[test]
+fn physical_size_scales_by_pixel_ratio() {
+    let mut canvas = CanvasConfig::new("#game");
+    assert_eq!(canvas.physical_size(800, 600, 2.0), (1600, 1200));
+    assert_eq!(canvas.physical_size(800, 600, 1.25), (1000, 750));
+    assert_eq!(canvas.physical_size(800, 600, 0.0), (800, 600));
+
+    canvas.use_device_pixel_ratio = false;
+    assert_eq!(canvas.physical_size(800, 600, 2.0), (800, 600));
+}
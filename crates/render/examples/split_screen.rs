@@ -0,0 +1,41 @@
+//! Two-player split-screen.
+//!
+//! Sets up one camera per player, each with its own viewport and render
+//! layer for its HUD, and prints what each view draws for one frame.
+//!
+//! Run with `cargo run -p render --example split_screen`.
+
+use render::viewport::frame_views;
+use render::{Camera, RenderLayers, RenderLimits, RenderTextures, Viewport};
+
+/// Layer of the first player's HUD; the second player uses the next one.
+const HUD_LAYER: u8 = 1;
+
+fn main() {
+    let players = 2;
+    let cameras: Vec<Camera> = (0..players)
+        .map(|player| Camera {
+            order: i32::from(player),
+            layers: RenderLayers::default().with(HUD_LAYER + player),
+            viewport: Viewport::split_screen(usize::from(players), usize::from(player)),
+            ..Camera::default()
+        })
+        .collect();
+
+    let renderables = [
+        ("level", RenderLayers::default()),
+        ("player 1 health bar", RenderLayers::layer(HUD_LAYER)),
+        ("player 2 health bar", RenderLayers::layer(HUD_LAYER + 1)),
+    ];
+
+    let textures = RenderTextures::new(RenderLimits::default());
+    for view in frame_views(&cameras, (1920, 1080), &textures) {
+        let visible: Vec<_> = cameras[view.camera].visible(renderables).collect();
+        println!(
+            "player {}: viewport {:?} (aspect {:.2}) draws {visible:?}",
+            view.camera + 1,
+            view.rect,
+            view.rect.aspect_ratio()
+        );
+    }
+}
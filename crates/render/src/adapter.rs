@@ -0,0 +1,94 @@
+//! Asynchronous adapter setup.
+//!
+//! Requesting an adapter is asynchronous: native backends answer right away,
+//! but WebGPU resolves a JavaScript promise, and the browser's only thread
+//! must not block waiting for it. [`request_capabilities`] is therefore an
+//! `async fn` that the caller awaits on whatever executor the target has.
+
+use crate::capabilities::{GpuCapabilities, GpuFeatures};
+use crate::RenderLimits;
+use std::future::Future;
+use tracing::{info, warn};
+
+/// Which adapter to prefer when several are available.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PowerPreference {
+    /// The fastest adapter, usually a discrete GPU.
+    #[default]
+    HighPerformance,
+    /// The most power-efficient adapter, usually an integrated GPU.
+    LowPower,
+}
+
+/// Options for a single adapter request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AdapterRequest {
+    /// Which adapter to prefer.
+    pub power_preference: PowerPreference,
+    /// Only accept the backend's fallback (software) adapter.
+    pub force_fallback_adapter: bool,
+}
+
+/// What an adapter reports about itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdapterInfo {
+    /// Adapter name reported by the driver.
+    pub name: String,
+    /// Optional features the adapter supports.
+    pub features: GpuFeatures,
+    /// Limits the adapter supports.
+    pub limits: RenderLimits,
+}
+
+/// A graphics backend that hands out adapters, such as wgpu.
+pub trait GpuBackend {
+    /// Requests an adapter matching `request`.
+    ///
+    /// Resolves to `None` if no adapter matches.
+    fn request_adapter(&self, request: AdapterRequest)
+        -> impl Future<Output = Option<AdapterInfo>>;
+}
+
+/// Requests an adapter from `backend` and returns its capabilities.
+///
+/// A hardware adapter matching `power_preference` is tried first, then the
+/// backend's fallback adapter, so machines without a usable GPU driver still
+/// start, slowly.
+///
+/// # Example
+///
+/// ```ignore
+/// let caps = render::request_capabilities(&backend, PowerPreference::HighPerformance).await?;
+/// println!("{caps}");
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the backend has no adapter at all, for example in a
+/// browser without WebGPU.
+pub async fn request_capabilities<B: GpuBackend>(
+    backend: &B,
+    power_preference: PowerPreference,
+) -> anyhow::Result<GpuCapabilities> {
+    let hardware = AdapterRequest {
+        power_preference,
+        force_fallback_adapter: false,
+    };
+    let adapter = if let Some(adapter) = backend.request_adapter(hardware).await {
+        adapter
+    } else {
+        warn!("no hardware adapter available, trying the fallback adapter");
+        let fallback = AdapterRequest {
+            force_fallback_adapter: true,
+            ..hardware
+        };
+        backend
+            .request_adapter(fallback)
+            .await
+            .ok_or_else(|| anyhow::anyhow!("no graphics adapter available"))?
+    };
+
+    let caps = GpuCapabilities::from_adapter(adapter.name, adapter.features, adapter.limits);
+    info!(capabilities = %caps, "graphics adapter selected");
+    Ok(caps)
+}
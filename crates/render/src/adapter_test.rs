@@ -0,0 +1,114 @@
+//! Unit tests for asynchronous adapter setup.
+
+use crate::adapter::{
+    request_capabilities, AdapterInfo, AdapterRequest, GpuBackend, PowerPreference,
+};
+use crate::capabilities::GpuFeatures;
+use crate::RenderLimits;
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+/// Backend that answers each request on its second poll, like a promise
+/// resolving on a later tick, and records the requests it saw.
+struct FakeBackend {
+    hardware: Option<AdapterInfo>,
+    fallback: Option<AdapterInfo>,
+    requests: RefCell<Vec<AdapterRequest>>,
+}
+
+impl GpuBackend for FakeBackend {
+    fn request_adapter(
+        &self,
+        request: AdapterRequest,
+    ) -> impl Future<Output = Option<AdapterInfo>> {
+        self.requests.borrow_mut().push(request);
+        let adapter = if request.force_fallback_adapter {
+            self.fallback.clone()
+        } else {
+            self.hardware.clone()
+        };
+        let mut pending = true;
+        std::future::poll_fn(move |cx| {
+            if std::mem::take(&mut pending) {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            } else {
+                Poll::Ready(adapter.clone())
+            }
+        })
+    }
+}
+
+fn adapter(name: &str) -> AdapterInfo {
+    AdapterInfo {
+        name: name.to_owned(),
+        features: GpuFeatures::all(),
+        limits: RenderLimits::native_defaults(),
+    }
+}
+
+/// Polls `future` to completion on the current thread.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+/// Verifies that a hardware adapter is used when available.
+#[test]
+fn prefers_hardware_adapter() {
+    let backend = FakeBackend {
+        hardware: Some(adapter("Discrete GPU")),
+        fallback: Some(adapter("Software")),
+        requests: RefCell::default(),
+    };
+
+    let caps = block_on(request_capabilities(&backend, PowerPreference::LowPower)).unwrap();
+
+    assert_eq!(caps.adapter_name, "Discrete GPU");
+    assert_eq!(caps.limits, RenderLimits::for_target());
+    assert_eq!(
+        *backend.requests.borrow(),
+        [AdapterRequest {
+            power_preference: PowerPreference::LowPower,
+            force_fallback_adapter: false,
+        }]
+    );
+}
+
+/// Verifies that the fallback adapter is requested when there is no
+/// hardware adapter.
+#[test]
+fn falls_back_to_software_adapter() {
+    let backend = FakeBackend {
+        hardware: None,
+        fallback: Some(adapter("Software")),
+        requests: RefCell::default(),
+    };
+
+    let caps = block_on(request_capabilities(&backend, PowerPreference::default())).unwrap();
+
+    assert_eq!(caps.adapter_name, "Software");
+    let requests = backend.requests.borrow();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].force_fallback_adapter);
+}
+
+/// Verifies that setup fails without any adapter.
+#[test]
+fn fails_without_adapter() {
+    let backend = FakeBackend {
+        hardware: None,
+        fallback: None,
+        requests: RefCell::default(),
+    };
+
+    let err = block_on(request_capabilities(&backend, PowerPreference::default())).unwrap_err();
+    assert!(err.to_string().contains("no graphics adapter"));
+}
@@ -0,0 +1,25 @@
+//! Screen-space ambient occlusion.
+//!
+//! [`AmbientOcclusion`] estimates how much ambient light reaches each pixel
+//! from the view's depth and normals, with classic SSAO or ground-truth
+//! ambient occlusion ([`AoTechnique`]), optionally at half resolution with
+//! a depth-aware upsample. PBR materials sample the result to darken their
+//! ambient term ([`occlude_ambient`]), so creases and contact areas no
+//! longer look flat.
+//!
+//! [`AoQuality`] presets trade quality for speed, and the `r.ssao.*` cvars
+//! ([`AoCVars`]) toggle and tune the pass at runtime.
+
+mod pass;
+#[cfg(test)]
+mod pass_test;
+mod settings;
+#[cfg(test)]
+mod settings_test;
+mod upsample;
+#[cfg(test)]
+mod upsample_test;
+
+pub use pass::{occlude_ambient, AmbientOcclusion, AoInput, AoMap};
+pub use settings::{AoCVars, AoQuality, AoSettings, AoTechnique};
+pub use upsample::{bilateral_upsample, downsample_depth};
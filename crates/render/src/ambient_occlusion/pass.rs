@@ -0,0 +1,396 @@
+//! The ambient occlusion pass.
+
+use super::settings::{AoSettings, AoTechnique};
+use super::upsample::{bilateral_upsample, downsample_depth};
+use crate::material::Material;
+use crate::target::TextureHandle;
+use math::interpolation::smoothstep;
+use math::{Mat4, Vec2, Vec3};
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+/// The view's depth and normals occlusion is computed from.
+#[derive(Debug, Clone, Copy)]
+pub struct AoInput<'a> {
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+    /// Distance along the view direction per pixel, rows from the top;
+    /// infinite where only the sky shows.
+    pub depth: &'a [f32],
+    /// View-space unit normals per pixel.
+    pub normals: &'a [Vec3],
+    /// Symmetric perspective projection from view space, looking down
+    /// `-Z`, to clip space.
+    pub projection: Mat4,
+}
+
+impl AoInput<'_> {
+    /// Returns the view-space position at pixel `(x, y)`.
+    #[allow(clippy::cast_precision_loss)]
+    fn position(&self, x: u32, y: u32) -> Vec3 {
+        let depth = self.depth[self.index(x, y)];
+        let ndc = Vec2::new(
+            (x as f32 + 0.5) / self.width as f32 * 2.0 - 1.0,
+            1.0 - (y as f32 + 0.5) / self.height as f32 * 2.0,
+        );
+        Vec3::new(
+            ndc.x * depth / self.projection.x_axis.x,
+            ndc.y * depth / self.projection.y_axis.y,
+            -depth,
+        )
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+
+    /// Returns the continuous pixel coordinates `point` projects to, if it
+    /// is in front of the camera.
+    #[allow(clippy::cast_precision_loss)]
+    fn screen(&self, point: Vec3) -> Option<Vec2> {
+        let clip = self.projection * point.extend(1.0);
+        if clip.w <= f32::EPSILON {
+            return None;
+        }
+        Some(Vec2::new(
+            (clip.x / clip.w + 1.0) * 0.5 * self.width as f32,
+            (1.0 - clip.y / clip.w) * 0.5 * self.height as f32,
+        ))
+    }
+
+    /// Returns the pixel containing the continuous coordinates `screen`,
+    /// if it is on screen.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn pixel_at(&self, screen: Vec2) -> Option<(u32, u32)> {
+        if screen.x < 0.0
+            || screen.y < 0.0
+            || screen.x >= self.width as f32
+            || screen.y >= self.height as f32
+        {
+            return None;
+        }
+        Some((screen.x as u32, screen.y as u32))
+    }
+}
+
+/// Ambient visibility per pixel, 1 unoccluded and 0 fully occluded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AoMap {
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+    /// Visibility per pixel, rows from the top.
+    pub values: Vec<f32>,
+}
+
+impl AoMap {
+    /// Creates a map without any occlusion.
+    #[must_use]
+    pub fn unoccluded(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            values: vec![1.0; width as usize * height as usize],
+        }
+    }
+
+    /// Returns the visibility at pixel `(x, y)`, clamped to the map.
+    #[must_use]
+    pub fn get(&self, x: u32, y: u32) -> f32 {
+        let (x, y) = (x.min(self.width - 1), y.min(self.height - 1));
+        self.values[y as usize * self.width as usize + x as usize]
+    }
+}
+
+/// Ambient occlusion stage of the frame, run after the depth and normal
+/// pre-pass and before lighting, whose ambient term it darkens in creases
+/// and contact areas.
+///
+/// The GPU path runs the same computation in a compute shader, writing the
+/// render target PBR materials sample through
+/// [`bind`](Self::bind); the CPU implementation here is the reference the
+/// shader is tested against.
+///
+/// # Example
+///
+/// ```
+/// use math::{Mat4, Vec3, Vec4};
+/// use render::ambient_occlusion::{AmbientOcclusion, AoInput, AoQuality, AoSettings};
+///
+/// let pass = AmbientOcclusion::new(AoSettings::preset(AoQuality::High));
+/// // A wall facing the camera 5 units away hides nothing.
+/// let (depth, normals) = (vec![5.0; 64], vec![Vec3::Z; 64]);
+/// let input = AoInput {
+///     width: 8,
+///     height: 8,
+///     depth: &depth,
+///     normals: &normals,
+///     projection: Mat4::from_cols(Vec4::X, Vec4::Y, Vec4::new(0.0, 0.0, -1.0, -1.0), Vec4::new(0.0, 0.0, -0.1, 0.0)),
+/// };
+/// let map = pass.compute(&input);
+/// assert!(map.values.iter().all(|&visibility| visibility > 0.95));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmbientOcclusion {
+    settings: AoSettings,
+}
+
+impl AmbientOcclusion {
+    /// Creates the stage with `settings`.
+    #[must_use]
+    pub fn new(settings: AoSettings) -> Self {
+        Self { settings }
+    }
+
+    /// Returns the settings in use.
+    #[must_use]
+    pub fn settings(&self) -> &AoSettings {
+        &self.settings
+    }
+
+    /// Returns `true` if the pass runs.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.settings.enabled
+    }
+
+    /// Returns the size of the occlusion target for a view of `width` x
+    /// `height`.
+    #[must_use]
+    pub fn target_size(&self, width: u32, height: u32) -> (u32, u32) {
+        if self.settings.half_resolution {
+            (width.div_ceil(2).max(1), height.div_ceil(2).max(1))
+        } else {
+            (width, height)
+        }
+    }
+
+    /// Computes the visibility of every pixel of `input`, at full
+    /// resolution.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the view is empty or `depth` or `normals` does not hold
+    /// one value per pixel.
+    #[must_use]
+    pub fn compute(&self, input: &AoInput<'_>) -> AoMap {
+        let pixels = input.width as usize * input.height as usize;
+        assert!(pixels > 0, "ambient occlusion view is empty");
+        assert_eq!(input.depth.len(), pixels, "depth buffer size mismatch");
+        assert_eq!(input.normals.len(), pixels, "normal buffer size mismatch");
+        if !self.settings.enabled {
+            return AoMap::unoccluded(input.width, input.height);
+        }
+        if !self.settings.half_resolution {
+            return self.compute_at(input);
+        }
+        let (depth, normals, width, height) =
+            downsample_depth(input.width, input.height, input.depth, input.normals);
+        let half = AoInput {
+            width,
+            height,
+            depth: &depth,
+            normals: &normals,
+            projection: input.projection,
+        };
+        let low = self.compute_at(&half);
+        bilateral_upsample(&low, &depth, input.width, input.height, input.depth)
+    }
+
+    /// Sets the `ambient_occlusion` render target and `ao_enabled` float
+    /// (0 or 1) of a PBR `material`; without a `target` the material's
+    /// ambient light stays unoccluded.
+    #[must_use]
+    pub fn bind(&self, material: Material, target: Option<TextureHandle>) -> Material {
+        match target.filter(|_| self.settings.enabled) {
+            Some(target) => material
+                .with_render_target("ambient_occlusion", target)
+                .with_float("ao_enabled", 1.0),
+            None => material.with_float("ao_enabled", 0.0),
+        }
+    }
+
+    fn compute_at(&self, input: &AoInput<'_>) -> AoMap {
+        let mut values = Vec::with_capacity(input.depth.len());
+        for y in 0..input.height {
+            for x in 0..input.width {
+                let depth = input.depth[input.index(x, y)];
+                let visibility = if depth.is_finite() && depth > 0.0 {
+                    match self.settings.technique {
+                        AoTechnique::Ssao => self.ssao(input, x, y),
+                        AoTechnique::Gtao => self.gtao(input, x, y),
+                    }
+                } else {
+                    1.0
+                };
+                values.push(visibility.clamp(0.0, 1.0).powf(self.settings.intensity));
+            }
+        }
+        AoMap {
+            width: input.width,
+            height: input.height,
+            values,
+        }
+    }
+
+    /// Tests points in a hemisphere around the normal against the depth
+    /// buffer.
+    #[allow(clippy::cast_precision_loss)]
+    fn ssao(&self, input: &AoInput<'_>, x: u32, y: u32) -> f32 {
+        let position = input.position(x, y);
+        let normal = input.normals[input.index(x, y)].normalize_or(Vec3::Z);
+        let (tangent, bitangent) = basis(normal, noise(x, y) * TAU);
+        let samples = self.settings.directions.max(1);
+        let radius = self.settings.radius;
+        let bias = radius * 0.05;
+        let mut occlusion = 0.0;
+        for i in 0..samples {
+            let (u, v) = ((i as f32 + 0.5) / samples as f32, radical_inverse(i));
+            // Cosine-weighted, denser near the center.
+            let (sin_theta, cos_theta) = (u.sqrt(), (1.0 - u).sqrt());
+            let phi = v * TAU;
+            let direction = tangent * (sin_theta * phi.cos())
+                + bitangent * (sin_theta * phi.sin())
+                + normal * cos_theta;
+            let scale = 0.1 + 0.9 * u * u;
+            let sample = position + direction * (radius * scale);
+            let Some((sx, sy)) = input.screen(sample).and_then(|p| input.pixel_at(p)) else {
+                continue;
+            };
+            let scene = input.depth[input.index(sx, sy)];
+            if scene < -sample.z - bias {
+                let range = (radius / (-position.z - scene).abs().max(1e-4)).min(1.0);
+                occlusion += smoothstep(0.0, 1.0, range);
+            }
+        }
+        1.0 - occlusion / samples as f32
+    }
+
+    /// Integrates the visible arc between the horizons found on both sides
+    /// of each screen direction, as in "Practical Real-Time Strategies for
+    /// Accurate Indirect Occlusion" (2016).
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss
+    )]
+    fn gtao(&self, input: &AoInput<'_>, x: u32, y: u32) -> f32 {
+        let position = input.position(x, y);
+        let normal = input.normals[input.index(x, y)].normalize_or(Vec3::Z);
+        let view = (-position).normalize_or(Vec3::Z);
+        let radius = self.settings.radius;
+        let pixels_per_unit = input.projection.x_axis.x * input.width as f32 * 0.5 / -position.z;
+        let screen_radius = radius * pixels_per_unit;
+        if screen_radius < 1.0 {
+            return 1.0;
+        }
+        let falloff = radius * 0.6;
+        let (slices, steps) = (self.settings.directions.max(1), self.settings.steps.max(1));
+        let jitter = noise(x, y);
+        let center = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+        // Slices turn evenly about the view vector; turning them evenly on
+        // screen instead under-weights tilted surfaces.
+        let (tangent, bitangent) = view.any_orthonormal_pair();
+        let mut visibility = 0.0;
+        for slice in 0..slices {
+            let phi = (slice as f32 + jitter) / slices as f32 * PI;
+            let ortho = tangent * phi.cos() + bitangent * phi.sin();
+            let Some(ahead) = input.screen(position + ortho * (-position.z * 0.01)) else {
+                continue;
+            };
+            let step = (ahead - center).normalize_or_zero();
+            if step == Vec2::ZERO {
+                continue;
+            }
+            let axis = ortho.cross(view).normalize_or(Vec3::Y);
+            let projected = normal - axis * normal.dot(axis);
+            let projected_length = projected.length();
+            if projected_length <= 1e-4 {
+                continue;
+            }
+            let cos_n = (projected.dot(view) / projected_length).clamp(0.0, 1.0);
+            let n = projected.dot(ortho).signum() * cos_n.acos();
+
+            let mut horizons = [(n + FRAC_PI_2).cos(), (n - FRAC_PI_2).cos()];
+            let lowest = horizons;
+            for i in 0..steps {
+                let t = (i as f32 + 1.0) / steps as f32;
+                let offset = step * (t * t * screen_radius).max(1.0);
+                for (side, sign) in [(0, 1.0), (1, -1.0)] {
+                    let Some((sx, sy)) = input.pixel_at(center + offset * sign) else {
+                        continue;
+                    };
+                    if !input.depth[input.index(sx, sy)].is_finite() {
+                        continue;
+                    }
+                    let delta = input.position(sx, sy) - position;
+                    let distance = delta.length();
+                    if distance <= 1e-5 {
+                        continue;
+                    }
+                    let weight = ((radius - distance) / falloff).clamp(0.0, 1.0);
+                    let cos_h = lowest[side] + (delta.dot(view) / distance - lowest[side]) * weight;
+                    horizons[side] = horizons[side].max(cos_h);
+                }
+            }
+            let h1 = n + (horizons[0].clamp(-1.0, 1.0).acos() - n).clamp(-FRAC_PI_2, FRAC_PI_2);
+            let h0 = n + (-horizons[1].clamp(-1.0, 1.0).acos() - n).clamp(-FRAC_PI_2, FRAC_PI_2);
+            let arc = |h: f32| (cos_n + 2.0 * h * n.sin() - (2.0 * h - n).cos()) * 0.25;
+            visibility += projected_length * (arc(h0) + arc(h1));
+        }
+        visibility / slices as f32
+    }
+}
+
+/// Darkens the ambient `diffuse` and `specular` light of a surface by its
+/// ambient `visibility`, with `n_dot_v` the cosine between its normal and
+/// the view direction.
+///
+/// Specular occlusion follows Lagarde and de Rousiers' approximation,
+/// occluding glossy reflections less at grazing angles.
+#[must_use]
+pub fn occlude_ambient(
+    diffuse: Vec3,
+    specular: Vec3,
+    visibility: f32,
+    n_dot_v: f32,
+    roughness: f32,
+) -> Vec3 {
+    let visibility = visibility.clamp(0.0, 1.0);
+    let n_dot_v = n_dot_v.clamp(0.0, 1.0);
+    let exponent = (-16.0 * roughness.clamp(0.0, 1.0) - 1.0).exp2();
+    let specular_visibility =
+        ((n_dot_v + visibility).powf(exponent) - 1.0 + visibility).clamp(0.0, 1.0);
+    diffuse * visibility + specular * specular_visibility
+}
+
+/// Returns two unit vectors completing `normal` to a basis, turned by
+/// `angle` about it.
+fn basis(normal: Vec3, angle: f32) -> (Vec3, Vec3) {
+    let tangent = normal.any_orthonormal_vector();
+    let bitangent = normal.cross(tangent);
+    let (sin, cos) = angle.sin_cos();
+    (
+        tangent * cos + bitangent * sin,
+        bitangent * cos - tangent * sin,
+    )
+}
+
+/// Interleaved gradient noise in `0..1`, decorrelating neighboring pixels.
+#[allow(clippy::cast_precision_loss)]
+fn noise(x: u32, y: u32) -> f32 {
+    (52.982_918 * (0.067_110_56 * x as f32 + 0.005_837_15 * y as f32).fract()).fract()
+}
+
+/// Van der Corput radical inverse of `i` in base 2.
+#[allow(clippy::cast_precision_loss)]
+fn radical_inverse(i: u32) -> f32 {
+    i.reverse_bits() as f32 / 4_294_967_296.0
+}
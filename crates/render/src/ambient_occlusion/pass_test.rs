@@ -0,0 +1,178 @@
+//! Unit tests for the ambient occlusion pass.
+
+use super::pass::{occlude_ambient, AmbientOcclusion, AoInput, AoMap};
+use super::settings::{AoQuality, AoSettings};
+use crate::target::{RenderTextureDesc, RenderTextures};
+use crate::{Material, MaterialParam, RenderLimits};
+use math::{Mat4, Vec3, Vec4};
+
+const WIDTH: u32 = 64;
+const HEIGHT: u32 = 32;
+
+/// A 90 degree projection; the view is twice as wide as high.
+fn projection() -> Mat4 {
+    Mat4::from_cols(
+        Vec4::new(0.5, 0.0, 0.0, 0.0),
+        Vec4::Y,
+        Vec4::new(0.0, 0.0, -1.0, -1.0),
+        Vec4::new(0.0, 0.0, -0.1, 0.0),
+    )
+}
+
+/// A floor 3 units below the camera meeting a wall 6 units ahead, with
+/// the crease between rows 23 and 24.
+#[allow(clippy::cast_precision_loss)]
+fn room_scene() -> (Vec<f32>, Vec<Vec3>) {
+    let mut depth = Vec::new();
+    let mut normals = Vec::new();
+    for y in 0..HEIGHT {
+        let ndc_y = 1.0 - (y as f32 + 0.5) / HEIGHT as f32 * 2.0;
+        let floor = if ndc_y < 0.0 {
+            -3.0 / ndc_y
+        } else {
+            f32::INFINITY
+        };
+        for _ in 0..WIDTH {
+            if floor < 6.0 {
+                depth.push(floor);
+                normals.push(Vec3::Y);
+            } else {
+                depth.push(6.0);
+                normals.push(Vec3::Z);
+            }
+        }
+    }
+    (depth, normals)
+}
+
+fn input<'a>(depth: &'a [f32], normals: &'a [Vec3]) -> AoInput<'a> {
+    AoInput {
+        width: WIDTH,
+        height: HEIGHT,
+        depth,
+        normals,
+        projection: projection(),
+    }
+}
+
+fn settings(quality: AoQuality) -> AoSettings {
+    AoSettings {
+        radius: 1.5,
+        ..AoSettings::preset(quality)
+    }
+}
+
+/// Verifies open surfaces stay lit while the crease between floor and
+/// wall darkens, with both techniques.
+#[test]
+fn darkens_creases() {
+    let (depth, normals) = room_scene();
+    for quality in [AoQuality::Low, AoQuality::High, AoQuality::Ultra] {
+        let settings = AoSettings {
+            half_resolution: false,
+            ..settings(quality)
+        };
+        let map = AmbientOcclusion::new(settings).compute(&input(&depth, &normals));
+        let (wall, crease, floor) = (map.get(32, 4), map.get(32, 23), map.get(32, 31));
+        assert!(wall > 0.9, "{quality}: wall {wall}");
+        assert!(floor > 0.9, "{quality}: floor {floor}");
+        assert!(
+            crease < wall - 0.1,
+            "{quality}: crease {crease} wall {wall}"
+        );
+    }
+}
+
+/// Verifies the intensity exponent darkens occlusion and the sky is never
+/// occluded.
+#[test]
+fn applies_intensity_and_skips_sky() {
+    let (mut depth, normals) = room_scene();
+    depth[0] = f32::INFINITY;
+    let base = AmbientOcclusion::new(settings(AoQuality::High)).compute(&input(&depth, &normals));
+    let strong = AmbientOcclusion::new(AoSettings {
+        intensity: 2.0,
+        ..settings(AoQuality::High)
+    })
+    .compute(&input(&depth, &normals));
+    assert!((strong.get(32, 23) - base.get(32, 23).powi(2)).abs() < 1e-5);
+    assert!((base.get(0, 0) - 1.0).abs() < f32::EPSILON);
+}
+
+/// Verifies half resolution returns a full-size map close to the full
+/// resolution result, with the crease intact.
+#[test]
+fn computes_at_half_resolution() {
+    let (depth, normals) = room_scene();
+    let half = AmbientOcclusion::new(settings(AoQuality::Medium));
+    assert_eq!(half.target_size(WIDTH, HEIGHT), (32, 16));
+    assert_eq!(half.target_size(5, 3), (3, 2));
+    let map = half.compute(&input(&depth, &normals));
+    assert_eq!((map.width, map.height), (WIDTH, HEIGHT));
+    assert!(map.get(32, 4) > 0.9);
+    assert!(map.get(32, 23) < map.get(32, 4) - 0.1);
+    assert!(map.get(32, 31) > 0.9);
+
+    let full = AmbientOcclusion::new(settings(AoQuality::High));
+    assert_eq!(full.target_size(WIDTH, HEIGHT), (WIDTH, HEIGHT));
+}
+
+/// Verifies a disabled pass leaves everything unoccluded and unbinds its
+/// target.
+#[test]
+fn disabled_pass_is_unoccluded() {
+    let (depth, normals) = room_scene();
+    let pass = AmbientOcclusion::new(AoSettings::disabled());
+    assert!(!pass.is_enabled());
+    assert_eq!(
+        pass.compute(&input(&depth, &normals)),
+        AoMap::unoccluded(WIDTH, HEIGHT)
+    );
+
+    let mut textures = RenderTextures::new(RenderLimits::default());
+    let target = textures.create(RenderTextureDesc::new(16, 8)).unwrap();
+    let pbr = Material::new("pbr.wgsl");
+    let unbound = pass.bind(pbr.clone(), Some(target));
+    assert_eq!(
+        unbound.params.get("ao_enabled"),
+        Some(&MaterialParam::Float(0.0))
+    );
+    assert_eq!(unbound.render_target("ambient_occlusion"), None);
+
+    let enabled = AmbientOcclusion::new(AoSettings::default());
+    let bound = enabled.bind(pbr.clone(), Some(target));
+    assert_eq!(
+        bound.params.get("ao_enabled"),
+        Some(&MaterialParam::Float(1.0))
+    );
+    assert_eq!(bound.render_target("ambient_occlusion"), Some(target));
+    assert_eq!(
+        enabled.bind(pbr, None).params.get("ao_enabled"),
+        Some(&MaterialParam::Float(0.0))
+    );
+}
+
+/// Verifies a mismatched buffer is rejected.
+#[test]
+#[should_panic(expected = "normal buffer size mismatch")]
+fn rejects_mismatched_buffers() {
+    let (depth, _) = room_scene();
+    let _ = AmbientOcclusion::new(AoSettings::default()).compute(&input(&depth, &[Vec3::Z]));
+}
+
+/// Verifies occlusion darkens diffuse ambient light fully and specular
+/// reflections less at grazing angles.
+#[test]
+fn occludes_ambient_light() {
+    let (diffuse, specular) = (Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+    let lit = occlude_ambient(diffuse, specular, 1.0, 1.0, 0.5);
+    assert!(lit.abs_diff_eq(diffuse + specular, 1e-5));
+    let dark = occlude_ambient(diffuse, specular, 0.0, 0.5, 0.5);
+    assert_eq!(dark, Vec3::ZERO);
+
+    let half = occlude_ambient(diffuse, specular, 0.5, 1.0, 0.0);
+    assert!((half.x - 0.5).abs() < 1e-5);
+    assert!(half.y > 0.5, "{half}");
+    let grazing = occlude_ambient(diffuse, specular, 0.5, 0.0, 0.0);
+    assert!(grazing.y < half.y);
+}
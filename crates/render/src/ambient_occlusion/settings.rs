@@ -0,0 +1,190 @@
+//! Ambient occlusion quality presets and cvars.
+
+use rustgine_core::{CVar, CVars};
+use std::fmt;
+use std::str::FromStr;
+
+/// How occlusion is estimated from the depth buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AoTechnique {
+    /// Classic screen-space ambient occlusion: points in a hemisphere
+    /// around the normal are tested against the depth buffer. Cheap, but
+    /// noisier and darker than the ground truth.
+    Ssao,
+    /// Ground-truth ambient occlusion: horizons are searched along screen
+    /// directions and the visible cosine-weighted arc between them is
+    /// integrated analytically.
+    Gtao,
+}
+
+/// Ambient occlusion quality presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AoQuality {
+    /// Half-resolution SSAO with few samples, for integrated GPUs.
+    Low,
+    /// Half-resolution GTAO.
+    #[default]
+    Medium,
+    /// Full-resolution GTAO.
+    High,
+    /// Full-resolution GTAO with more directions and steps.
+    Ultra,
+}
+
+impl AoQuality {
+    /// Every preset, from cheapest to best.
+    pub const ALL: [Self; 4] = [Self::Low, Self::Medium, Self::High, Self::Ultra];
+
+    /// Returns the preset's name, as used by the `r.ssao.quality` cvar.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Ultra => "ultra",
+        }
+    }
+}
+
+impl fmt::Display for AoQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for AoQuality {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> anyhow::Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|quality| quality.name().eq_ignore_ascii_case(text.trim()))
+            .ok_or_else(|| {
+                anyhow::anyhow!("unknown ambient occlusion quality {text:?}, expected low, medium, high, or ultra")
+            })
+    }
+}
+
+/// Ambient occlusion settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AoSettings {
+    /// Whether the pass runs; when off, ambient light is unoccluded.
+    pub enabled: bool,
+    /// Estimation technique.
+    pub technique: AoTechnique,
+    /// World-space radius searched for occluders.
+    pub radius: f32,
+    /// Exponent darkening the result; 1 is physically based.
+    pub intensity: f32,
+    /// SSAO samples, or GTAO screen directions per pixel.
+    pub directions: u32,
+    /// GTAO steps per direction and side; unused by SSAO.
+    pub steps: u32,
+    /// Whether occlusion is computed at half resolution and upsampled
+    /// with a depth-aware filter.
+    pub half_resolution: bool,
+}
+
+impl Default for AoSettings {
+    fn default() -> Self {
+        Self::preset(AoQuality::default())
+    }
+}
+
+impl AoSettings {
+    /// Returns the settings of `quality`, with a half-meter radius.
+    #[must_use]
+    pub fn preset(quality: AoQuality) -> Self {
+        let (technique, directions, steps, half_resolution) = match quality {
+            AoQuality::Low => (AoTechnique::Ssao, 8, 0, true),
+            AoQuality::Medium => (AoTechnique::Gtao, 2, 4, true),
+            AoQuality::High => (AoTechnique::Gtao, 3, 6, false),
+            AoQuality::Ultra => (AoTechnique::Gtao, 4, 10, false),
+        };
+        Self {
+            enabled: true,
+            technique,
+            radius: 0.5,
+            intensity: 1.0,
+            directions,
+            steps,
+            half_resolution,
+        }
+    }
+
+    /// Returns disabled settings.
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+}
+
+/// Cvars controlling ambient occlusion.
+#[derive(Debug, Clone)]
+pub struct AoCVars {
+    /// `r.ssao.enabled`: whether the pass runs.
+    enabled: CVar<bool>,
+    /// `r.ssao.quality`: preset name.
+    quality: CVar<String>,
+    /// `r.ssao.half_res`: overrides the preset's resolution when set.
+    half_res: CVar<i64>,
+    /// `r.ssao.radius`: occluder search radius.
+    radius: CVar<f64>,
+    /// `r.ssao.intensity`: darkening exponent.
+    intensity: CVar<f64>,
+}
+
+impl AoCVars {
+    /// Registers the `r.ssao.*` cvars, enabled at medium quality by default.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cvars are already registered.
+    pub fn register(cvars: &CVars) -> anyhow::Result<Self> {
+        Ok(Self {
+            enabled: cvars.register("r.ssao.enabled", true, "Render ambient occlusion")?,
+            quality: cvars.register(
+                "r.ssao.quality",
+                AoQuality::default().name().to_owned(),
+                "Ambient occlusion quality: low, medium, high, or ultra",
+            )?,
+            half_res: cvars.register(
+                "r.ssao.half_res",
+                -1_i64,
+                "Ambient occlusion at half resolution: 1 on, 0 off, -1 from the quality preset",
+            )?,
+            radius: cvars.register(
+                "r.ssao.radius",
+                0.5,
+                "Ambient occlusion radius in world units",
+            )?,
+            intensity: cvars.register(
+                "r.ssao.intensity",
+                1.0,
+                "Ambient occlusion darkening exponent",
+            )?,
+        })
+    }
+
+    /// Returns the settings the cvars currently describe; an unknown
+    /// quality falls back to the default preset.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn settings(&self) -> AoSettings {
+        let quality = self.quality.get().parse().unwrap_or_default();
+        let mut settings = AoSettings::preset(quality);
+        settings.enabled = self.enabled.get();
+        match self.half_res.get() {
+            0 => settings.half_resolution = false,
+            1 => settings.half_resolution = true,
+            _ => {}
+        }
+        settings.radius = self.radius.get().max(0.0) as f32;
+        settings.intensity = self.intensity.get().max(0.0) as f32;
+        settings
+    }
+}
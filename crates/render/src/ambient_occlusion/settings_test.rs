@@ -0,0 +1,53 @@
+//! Unit tests for ambient occlusion settings.
+
+use super::settings::{AoCVars, AoQuality, AoSettings, AoTechnique};
+use rustgine_core::CVars;
+
+/// Verifies presets get costlier with quality and names round-trip.
+#[test]
+fn presets_scale_with_quality() {
+    let low = AoSettings::preset(AoQuality::Low);
+    assert_eq!(low.technique, AoTechnique::Ssao);
+    assert!(low.half_resolution);
+    let [_, medium, high, ultra] = AoQuality::ALL.map(AoSettings::preset);
+    assert!(medium.half_resolution && !high.half_resolution && !ultra.half_resolution);
+    assert!(medium.directions * medium.steps < high.directions * high.steps);
+    assert!(high.directions * high.steps < ultra.directions * ultra.steps);
+    assert_eq!(AoSettings::default(), medium);
+    assert!(!AoSettings::disabled().enabled);
+
+    for quality in AoQuality::ALL {
+        assert_eq!(quality.to_string().parse::<AoQuality>().unwrap(), quality);
+    }
+    assert_eq!(" High ".parse::<AoQuality>().unwrap(), AoQuality::High);
+    assert!("extreme".parse::<AoQuality>().is_err());
+}
+
+/// Verifies the cvars toggle the pass, pick presets, and override them.
+#[test]
+fn cvars_drive_settings() {
+    let cvars = CVars::new();
+    let ao = AoCVars::register(&cvars).unwrap();
+    assert_eq!(ao.settings(), AoSettings::default());
+
+    cvars.set("r.ssao.quality", "ultra").unwrap();
+    cvars.set("r.ssao.radius", "1.5").unwrap();
+    cvars.set("r.ssao.intensity", "2").unwrap();
+    let ultra = ao.settings();
+    assert_eq!(
+        ultra.directions,
+        AoSettings::preset(AoQuality::Ultra).directions
+    );
+    assert!(!ultra.half_resolution);
+    assert!((ultra.radius - 1.5).abs() < 1e-6);
+    assert!((ultra.intensity - 2.0).abs() < 1e-6);
+
+    cvars.set("r.ssao.half_res", "1").unwrap();
+    assert!(ao.settings().half_resolution);
+    cvars.set("r.ssao.enabled", "false").unwrap();
+    assert!(!ao.settings().enabled);
+
+    cvars.set("r.ssao.quality", "bogus").unwrap();
+    assert_eq!(ao.settings().technique, AoSettings::default().technique);
+    assert!(AoCVars::register(&cvars).is_err());
+}
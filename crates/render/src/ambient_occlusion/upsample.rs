@@ -0,0 +1,115 @@
+//! Half-resolution depth and depth-aware upsampling.
+
+use super::pass::AoMap;
+use math::Vec3;
+
+/// Halves a `width` x `height` depth and normal buffer, keeping the
+/// nearest pixel of each 2x2 block so thin foreground objects survive.
+///
+/// Returns the depth, normals, and size of the half-resolution buffers.
+#[must_use]
+pub fn downsample_depth(
+    width: u32,
+    height: u32,
+    depth: &[f32],
+    normals: &[Vec3],
+) -> (Vec<f32>, Vec<Vec3>, u32, u32) {
+    let (half_width, half_height) = (width.div_ceil(2), height.div_ceil(2));
+    let mut half_depth = Vec::with_capacity(half_width as usize * half_height as usize);
+    let mut half_normals = Vec::with_capacity(half_depth.capacity());
+    for y in 0..half_height {
+        for x in 0..half_width {
+            let nearest = [(0, 0), (1, 0), (0, 1), (1, 1)]
+                .into_iter()
+                .map(|(dx, dy)| {
+                    let (sx, sy) = ((x * 2 + dx).min(width - 1), (y * 2 + dy).min(height - 1));
+                    sy as usize * width as usize + sx as usize
+                })
+                .min_by(|&a, &b| depth[a].total_cmp(&depth[b]))
+                .unwrap_or_default();
+            half_depth.push(depth[nearest]);
+            half_normals.push(normals[nearest]);
+        }
+    }
+    (half_depth, half_normals, half_width, half_height)
+}
+
+/// Upsamples the half-resolution `low` map, computed from `low_depth`, to
+/// `width` x `height` with full-resolution `depth`.
+///
+/// Each pixel blends its four nearest low-resolution texels bilinearly,
+/// weighted down where their depth differs from its own, so occlusion
+/// does not bleed across silhouettes.
+///
+/// # Panics
+///
+/// Panics if `low_depth` does not match `low`, or `depth` does not hold
+/// `width * height` values.
+#[must_use]
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn bilateral_upsample(
+    low: &AoMap,
+    low_depth: &[f32],
+    width: u32,
+    height: u32,
+    depth: &[f32],
+) -> AoMap {
+    assert_eq!(low_depth.len(), low.values.len(), "low depth size mismatch");
+    assert_eq!(
+        depth.len(),
+        width as usize * height as usize,
+        "depth buffer size mismatch"
+    );
+    let low_texel = |x: u32, y: u32| {
+        let (x, y) = (x.min(low.width - 1), y.min(low.height - 1));
+        y as usize * low.width as usize + x as usize
+    };
+    let mut values = Vec::with_capacity(depth.len());
+    for y in 0..height {
+        for x in 0..width {
+            let center = depth[y as usize * width as usize + x as usize];
+            let fx = ((x as f32 + 0.5) * 0.5 - 0.5).max(0.0);
+            let fy = ((y as f32 + 0.5) * 0.5 - 0.5).max(0.0);
+            let (x0, y0) = (fx as u32, fy as u32);
+            let (tx, ty) = (fx - x0 as f32, fy - y0 as f32);
+            let mut total = 0.0;
+            let mut weights = 0.0;
+            let mut nearest = (f32::INFINITY, 1.0);
+            for (dx, dy, bilinear) in [
+                (0, 0, (1.0 - tx) * (1.0 - ty)),
+                (1, 0, tx * (1.0 - ty)),
+                (0, 1, (1.0 - tx) * ty),
+                (1, 1, tx * ty),
+            ] {
+                let texel = low_texel(x0 + dx, y0 + dy);
+                let difference = if center.is_finite() && low_depth[texel].is_finite() {
+                    (center - low_depth[texel]).abs() / center.max(1e-4)
+                } else if center.is_finite() == low_depth[texel].is_finite() {
+                    0.0
+                } else {
+                    f32::INFINITY
+                };
+                if difference < nearest.0 {
+                    nearest = (difference, low.values[texel]);
+                }
+                let weight = bilinear / (1e-3 + difference * 100.0);
+                total += low.values[texel] * weight;
+                weights += weight;
+            }
+            values.push(if weights > 1e-6 {
+                total / weights
+            } else {
+                nearest.1
+            });
+        }
+    }
+    AoMap {
+        width,
+        height,
+        values,
+    }
+}
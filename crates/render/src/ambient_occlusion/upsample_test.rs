@@ -0,0 +1,64 @@
+//! Unit tests for half-resolution ambient occlusion.
+
+use super::pass::AoMap;
+use super::upsample::{bilateral_upsample, downsample_depth};
+use math::Vec3;
+
+/// Verifies each 2x2 block keeps its nearest pixel, odd sizes rounding up.
+#[test]
+fn downsamples_to_nearest_depth() {
+    let depth = [4.0, 3.0, 9.0, 5.0, 6.0, 8.0];
+    let normals = [
+        Vec3::X,
+        Vec3::Y,
+        Vec3::Z,
+        Vec3::NEG_X,
+        Vec3::NEG_Y,
+        Vec3::NEG_Z,
+    ];
+    let (half, half_normals, width, height) = downsample_depth(3, 2, &depth, &normals);
+    assert_eq!((width, height), (2, 1));
+    assert_eq!(half, [3.0, 8.0]);
+    assert_eq!(half_normals, [Vec3::Y, Vec3::NEG_Z]);
+}
+
+/// Verifies upsampling interpolates smooth areas and keeps occlusion from
+/// bleeding across depth edges.
+#[test]
+fn upsamples_without_bleeding() {
+    // Left half far and open, right half near and occluded.
+    let low = AoMap {
+        width: 2,
+        height: 1,
+        values: vec![1.0, 0.2],
+    };
+    let low_depth = [10.0, 2.0];
+    let depth = [10.0, 10.0, 2.0, 2.0];
+    let map = bilateral_upsample(&low, &low_depth, 4, 1, &depth);
+    assert_eq!((map.width, map.height), (4, 1));
+    assert!(map.values[1] > 0.99, "{:?}", map.values);
+    assert!(map.values[2] < 0.21, "{:?}", map.values);
+
+    let flat = bilateral_upsample(&low, &[5.0, 5.0], 4, 1, &[5.0; 4]);
+    assert!((flat.values[1] - 0.8).abs() < 1e-5, "{:?}", flat.values);
+    assert!((flat.values[0] - 1.0).abs() < 1e-5);
+}
+
+/// Verifies sky pixels take values from the sky, not from geometry.
+#[test]
+fn keeps_sky_apart() {
+    let low = AoMap {
+        width: 2,
+        height: 1,
+        values: vec![0.5, 1.0],
+    };
+    let map = bilateral_upsample(
+        &low,
+        &[3.0, f32::INFINITY],
+        4,
+        1,
+        &[3.0, 3.0, f32::INFINITY, f32::INFINITY],
+    );
+    assert!((map.values[1] - 0.5).abs() < 1e-5);
+    assert!((map.values[2] - 1.0).abs() < 1e-5);
+}
@@ -0,0 +1,408 @@
+//! Bind group caching and texture batching.
+//!
+//! Creating a bind group for every draw churns descriptors and forces a
+//! rebind between draws that sample different textures. Two pieces keep
+//! that down:
+//!
+//! - [`BindGroupCache`] creates each distinct set of resources, its
+//!   [`BindGroupKey`], once and reuses it while it stays in use, dropping
+//!   groups unused for a number of frames. Its hit rate is reported in
+//!   [`RenderStats`](crate::RenderStats).
+//! - [`TextureArrays`] packs textures into a few shared bind groups, so
+//!   draws sampling different textures still batch together and pick their
+//!   texture by index. With [`TextureBinding::Bindless`] every texture goes
+//!   into one binding array; otherwise textures of the same size share 2D
+//!   texture arrays, one bind group per array.
+
+use crate::capabilities::{GpuCapabilities, TextureBinding};
+use crate::material::{BindingKind, Material, MaterialOverride};
+use rustgine_core::Label;
+use std::collections::HashMap;
+use tracing::trace;
+
+/// Frames an unused bind group is kept by [`BindGroupCache::default`].
+pub const DEFAULT_RETENTION_FRAMES: u64 = 120;
+
+/// The resources of a bind group, identifying it in a [`BindGroupCache`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BindGroupKey {
+    /// Layout the group is created with, e.g. the material's shader.
+    pub layout: Label,
+    /// Buffers by id, in binding order.
+    pub buffers: Vec<u64>,
+    /// Textures, in binding order.
+    pub textures: Vec<Label>,
+}
+
+impl BindGroupKey {
+    /// Creates a key for `layout` without resources.
+    #[must_use]
+    pub fn new(layout: impl Into<Label>) -> Self {
+        Self {
+            layout: layout.into(),
+            buffers: Vec::new(),
+            textures: Vec::new(),
+        }
+    }
+
+    /// Returns the key of `material`'s bind group, with its uniform block
+    /// in buffer `uniforms` and `overrides` applied to its textures.
+    #[must_use]
+    pub fn for_material(
+        material: &Material,
+        overrides: Option<&MaterialOverride>,
+        uniforms: u64,
+    ) -> Self {
+        let mut key = Self::new(material.shader);
+        for entry in material.bind_group_layout() {
+            match entry.kind {
+                BindingKind::Uniforms { .. } => key.buffers.push(uniforms),
+                BindingKind::Texture => key.textures.push(
+                    material
+                        .texture(&entry.param, overrides)
+                        .unwrap_or_default(),
+                ),
+                BindingKind::Sampler => {}
+            }
+        }
+        key
+    }
+
+    /// Adds a buffer binding.
+    #[must_use]
+    pub fn with_buffer(mut self, buffer: u64) -> Self {
+        self.buffers.push(buffer);
+        self
+    }
+
+    /// Adds a texture binding.
+    #[must_use]
+    pub fn with_texture(mut self, texture: impl Into<Label>) -> Self {
+        self.textures.push(texture.into());
+        self
+    }
+}
+
+/// Bind group cache counters of one frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BindGroupCacheStats {
+    /// Lookups answered by an existing group.
+    pub hits: u64,
+    /// Lookups that created a group.
+    pub misses: u64,
+    /// Groups dropped after going unused.
+    pub evicted: u64,
+}
+
+impl BindGroupCacheStats {
+    /// Returns the fraction of lookups that were hits, 1.0 without lookups.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            1.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// A cached group and the frame it was last used in.
+#[derive(Debug)]
+struct CachedGroup<G> {
+    group: G,
+    last_used: u64,
+}
+
+/// Bind groups by the resources they bind.
+///
+/// `G` is the backend's bind group object.
+///
+/// # Example
+///
+/// ```
+/// use render::bind_group::{BindGroupCache, BindGroupKey};
+///
+/// let mut cache = BindGroupCache::new(2);
+/// let brick = BindGroupKey::new("shaders/lit.wgsl").with_texture("brick.png");
+/// let mut created = 0;
+/// for _ in 0..3 {
+///     cache.get_or_create(&brick, |_| {
+///         created += 1;
+///         "brick group"
+///     });
+/// }
+/// let stats = cache.end_frame();
+/// assert_eq!(created, 1);
+/// assert_eq!((stats.hits, stats.misses), (2, 1));
+///
+/// // Unused for two frames, the group is dropped.
+/// cache.end_frame();
+/// assert_eq!(cache.end_frame().evicted, 1);
+/// assert!(cache.is_empty());
+/// ```
+#[derive(Debug)]
+pub struct BindGroupCache<G> {
+    groups: HashMap<BindGroupKey, CachedGroup<G>>,
+    /// Frames a group may go unused before it is dropped.
+    retention_frames: u64,
+    /// Number of the frame being recorded.
+    frame: u64,
+    /// Counters of the frame being recorded.
+    stats: BindGroupCacheStats,
+}
+
+impl<G> Default for BindGroupCache<G> {
+    fn default() -> Self {
+        Self::new(DEFAULT_RETENTION_FRAMES)
+    }
+}
+
+impl<G> BindGroupCache<G> {
+    /// Creates a cache dropping groups unused for `retention_frames`
+    /// frames.
+    #[must_use]
+    pub fn new(retention_frames: u64) -> Self {
+        Self {
+            groups: HashMap::new(),
+            retention_frames,
+            frame: 0,
+            stats: BindGroupCacheStats::default(),
+        }
+    }
+
+    /// Returns the group binding `key`, creating it with `create` if it is
+    /// not cached.
+    pub fn get_or_create(
+        &mut self,
+        key: &BindGroupKey,
+        create: impl FnOnce(&BindGroupKey) -> G,
+    ) -> &G {
+        let frame = self.frame;
+        if let Some(cached) = self.groups.get_mut(key) {
+            self.stats.hits += 1;
+            cached.last_used = frame;
+        } else {
+            self.stats.misses += 1;
+            let group = create(key);
+            self.groups.insert(
+                key.clone(),
+                CachedGroup {
+                    group,
+                    last_used: frame,
+                },
+            );
+        }
+        &self.groups[key].group
+    }
+
+    /// Drops every group binding `texture`, e.g. after it was reloaded or
+    /// resized, returning how many were dropped.
+    pub fn invalidate_texture(&mut self, texture: &Label) -> usize {
+        let before = self.groups.len();
+        self.groups.retain(|key, _| !key.textures.contains(texture));
+        before - self.groups.len()
+    }
+
+    /// Returns the number of cached groups.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Returns `true` if no groups are cached.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Ends the frame: drops groups unused for the retention period and
+    /// returns the frame's counters, also emitted as a `trace` event on the
+    /// `render::bind_group` target.
+    pub fn end_frame(&mut self) -> BindGroupCacheStats {
+        let before = self.groups.len();
+        let (frame, retention) = (self.frame, self.retention_frames);
+        self.groups
+            .retain(|_, cached| frame - cached.last_used < retention);
+        self.stats.evicted = (before - self.groups.len()) as u64;
+        self.frame += 1;
+        let stats = std::mem::take(&mut self.stats);
+        trace!(
+            target: "render::bind_group",
+            hits = stats.hits,
+            misses = stats.misses,
+            evicted = stats.evicted,
+            cached = self.groups.len(),
+            "bind group cache"
+        );
+        stats
+    }
+}
+
+/// Where a texture is bound by [`TextureArrays`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextureSlot {
+    /// Index of the shared bind group.
+    pub group: u32,
+    /// Layer of the texture array, or element of the binding array.
+    pub index: u32,
+}
+
+/// Textures sharing one bind group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextureGroup {
+    /// Size of every layer of a texture array, `None` for a binding array.
+    pub size: Option<[u32; 2]>,
+    /// Textures by [`TextureSlot::index`].
+    pub textures: Vec<Label>,
+}
+
+/// Consecutive draws sharing one texture group, drawn with one call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextureBatch<T> {
+    /// Index of the group bound for the draw.
+    pub group: u32,
+    /// The draws, each with its texture's [`TextureSlot::index`].
+    pub draws: Vec<(T, u32)>,
+}
+
+/// Packs textures into shared bind groups.
+///
+/// # Example
+///
+/// ```
+/// use render::bind_group::TextureArrays;
+/// use render::capabilities::{GpuCapabilities, GpuFeatures};
+/// use render::RenderLimits;
+///
+/// // Without bindless support, same-sized textures share texture arrays.
+/// let mut arrays = TextureArrays::new(&GpuCapabilities::baseline());
+/// let batches = arrays.batch([
+///     ("hero", "hero.png", [64, 64]),
+///     ("slime", "slime.png", [64, 64]),
+///     ("boss", "boss.png", [256, 256]),
+/// ]);
+/// assert_eq!(batches.len(), 2);
+/// assert_eq!(batches[0].draws, [("hero", 0), ("slime", 1)]);
+///
+/// // With it, every texture shares one binding array.
+/// let features = GpuFeatures { bindless_textures: true, ..GpuFeatures::default() };
+/// let caps = GpuCapabilities::from_adapter("GPU", features, RenderLimits::native_defaults());
+/// let mut bindless = TextureArrays::new(&caps);
+/// let batches = bindless.batch([("hero", "hero.png", [64, 64]), ("boss", "boss.png", [256, 256])]);
+/// assert_eq!(batches.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextureArrays {
+    binding: TextureBinding,
+    /// Textures per group.
+    capacity: u32,
+    slots: HashMap<Label, TextureSlot>,
+    groups: Vec<TextureGroup>,
+}
+
+impl TextureArrays {
+    /// Creates empty groups sized for `capabilities`.
+    #[must_use]
+    pub fn new(capabilities: &GpuCapabilities) -> Self {
+        let binding = capabilities.texture_binding();
+        let limits = &capabilities.limits;
+        let capacity = match binding {
+            TextureBinding::Bindless => limits.max_binding_array_elements_per_shader_stage,
+            TextureBinding::PerMaterial => limits.max_texture_array_layers,
+        };
+        Self {
+            binding,
+            capacity: capacity.max(1),
+            slots: HashMap::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    /// Returns how textures are bound.
+    #[must_use]
+    #[inline]
+    pub fn binding(&self) -> TextureBinding {
+        self.binding
+    }
+
+    /// Returns the groups, indexed by [`TextureSlot::group`].
+    #[must_use]
+    #[inline]
+    pub fn groups(&self) -> &[TextureGroup] {
+        &self.groups
+    }
+
+    /// Returns the slot of `texture`, if it has one.
+    #[must_use]
+    pub fn get(&self, texture: &Label) -> Option<TextureSlot> {
+        self.slots.get(texture).copied()
+    }
+
+    /// Returns the slot of `texture`, a `size` texture, adding it to the
+    /// first group with room for it.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn slot(&mut self, texture: Label, size: [u32; 2]) -> TextureSlot {
+        if let Some(slot) = self.slots.get(&texture) {
+            return *slot;
+        }
+        let size = (self.binding == TextureBinding::PerMaterial).then_some(size);
+        let capacity = self.capacity as usize;
+        let existing = self
+            .groups
+            .iter()
+            .position(|group| group.size == size && group.textures.len() < capacity);
+        let group = existing.unwrap_or_else(|| {
+            self.groups.push(TextureGroup {
+                size,
+                textures: Vec::new(),
+            });
+            self.groups.len() - 1
+        });
+        let textures = &mut self.groups[group].textures;
+        let slot = TextureSlot {
+            group: group as u32,
+            index: textures.len() as u32,
+        };
+        textures.push(texture);
+        self.slots.insert(texture, slot);
+        slot
+    }
+
+    /// Returns the bind group key of `group` for `layout`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `group` is not a group index.
+    #[must_use]
+    pub fn key(&self, group: u32, layout: impl Into<Label>) -> BindGroupKey {
+        BindGroupKey {
+            layout: layout.into(),
+            buffers: Vec::new(),
+            textures: self.groups[group as usize].textures.clone(),
+        }
+    }
+
+    /// Batches `(draw, texture, size)` draws in order, merging consecutive
+    /// draws whose textures share a group.
+    pub fn batch<T>(
+        &mut self,
+        draws: impl IntoIterator<Item = (T, impl Into<Label>, [u32; 2])>,
+    ) -> Vec<TextureBatch<T>> {
+        let mut batches: Vec<TextureBatch<T>> = Vec::new();
+        for (draw, texture, size) in draws {
+            let slot = self.slot(texture.into(), size);
+            match batches.last_mut() {
+                Some(batch) if batch.group == slot.group => batch.draws.push((draw, slot.index)),
+                _ => batches.push(TextureBatch {
+                    group: slot.group,
+                    draws: vec![(draw, slot.index)],
+                }),
+            }
+        }
+        batches
+    }
+}
@@ -0,0 +1,157 @@
+//! Unit tests for bind group caching and texture batching.
+
+use crate::bind_group::{BindGroupCache, BindGroupKey, TextureArrays, TextureSlot};
+use crate::capabilities::{GpuCapabilities, GpuFeatures, TextureBinding};
+use crate::material::{Material, MaterialOverride, MaterialParam};
+use crate::RenderLimits;
+use rustgine_core::Label;
+
+fn capabilities(bindless: bool, limits: RenderLimits) -> GpuCapabilities {
+    let features = GpuFeatures {
+        bindless_textures: bindless,
+        ..GpuFeatures::default()
+    };
+    GpuCapabilities::from_adapter("GPU", features, limits)
+}
+
+/// Verifies material keys follow the bound textures, overrides included,
+/// so materials sharing resources share a group.
+#[test]
+fn material_keys_follow_resources() {
+    let brick = Material::new("shaders/lit.wgsl")
+        .with_float("roughness", 0.5)
+        .with_texture("albedo", "brick.png");
+    let key = BindGroupKey::for_material(&brick, None, 7);
+    assert_eq!(key.layout, Label::from("shaders/lit.wgsl"));
+    assert_eq!(key.buffers, [7]);
+    assert_eq!(key.textures, [Label::from("brick.png")]);
+    assert_eq!(BindGroupKey::for_material(&brick.clone(), None, 7), key);
+
+    let mossy = MaterialOverride::default()
+        .with_param("albedo", MaterialParam::Texture(Some("moss.png".into())));
+    let overridden = BindGroupKey::for_material(&brick, Some(&mossy), 7);
+    assert_eq!(overridden.textures, [Label::from("moss.png")]);
+    assert_ne!(overridden, key);
+}
+
+/// Verifies lookups hit once a group exists, and groups in use are kept
+/// while unused ones are evicted.
+#[test]
+fn cache_counts_hits_and_evicts_unused() {
+    let mut cache = BindGroupCache::new(3);
+    let grass = BindGroupKey::new("terrain").with_texture("grass.png");
+    let rock = BindGroupKey::new("terrain").with_texture("rock.png");
+
+    let mut created = Vec::new();
+    for key in [&grass, &rock, &grass, &grass] {
+        let group = *cache.get_or_create(key, |key| {
+            created.push(key.textures[0]);
+            created.len()
+        });
+        assert_eq!(group, if key == &grass { 1 } else { 2 });
+    }
+    let stats = cache.end_frame();
+    assert_eq!((stats.hits, stats.misses, stats.evicted), (2, 2, 0));
+    assert!((stats.hit_rate() - 0.5).abs() < 1e-9);
+
+    for _ in 0..3 {
+        cache.get_or_create(&grass, |_| unreachable!("grass stays cached"));
+        cache.end_frame();
+    }
+    assert_eq!(cache.len(), 1);
+    assert_eq!(created.len(), 2);
+}
+
+/// Verifies invalidating a texture drops only the groups binding it.
+#[test]
+fn invalidate_texture_drops_its_groups() {
+    let mut cache = BindGroupCache::default();
+    let keys = [
+        BindGroupKey::new("lit").with_texture("brick.png"),
+        BindGroupKey::new("lit")
+            .with_texture("brick.png")
+            .with_texture("normal.png"),
+        BindGroupKey::new("lit")
+            .with_texture("moss.png")
+            .with_buffer(1),
+    ];
+    for key in &keys {
+        cache.get_or_create(key, |_| ());
+    }
+    assert_eq!(cache.invalidate_texture(&Label::from("brick.png")), 2);
+    assert_eq!(cache.len(), 1);
+}
+
+/// Verifies texture arrays group textures by size and open a new array
+/// when one is full.
+#[test]
+fn texture_arrays_group_by_size() {
+    let limits = RenderLimits {
+        max_texture_array_layers: 2,
+        ..RenderLimits::native_defaults()
+    };
+    let mut arrays = TextureArrays::new(&capabilities(false, limits));
+    assert_eq!(arrays.binding(), TextureBinding::PerMaterial);
+
+    let slots = ["a.png", "b.png", "big.png", "c.png", "a.png"].map(|name| {
+        let size = if name == "big.png" {
+            [512, 512]
+        } else {
+            [64, 64]
+        };
+        arrays.slot(name.into(), size)
+    });
+    assert_eq!(
+        slots,
+        [
+            TextureSlot { group: 0, index: 0 },
+            TextureSlot { group: 0, index: 1 },
+            TextureSlot { group: 1, index: 0 },
+            TextureSlot { group: 2, index: 0 },
+            TextureSlot { group: 0, index: 0 },
+        ]
+    );
+    assert_eq!(arrays.groups()[1].size, Some([512, 512]));
+    assert_eq!(arrays.get(&Label::from("c.png")), Some(slots[3]));
+    assert_eq!(
+        arrays.key(0, "sprites").textures,
+        [Label::from("a.png"), Label::from("b.png")]
+    );
+}
+
+/// Verifies bindless batching draws sprites of many atlases in one call,
+/// while texture arrays keep painter's order across sizes.
+#[test]
+fn bindless_batches_many_textures_into_one_draw() {
+    let draws: Vec<_> = (0..64)
+        .map(|index| {
+            let size = if index % 2 == 0 {
+                [128, 128]
+            } else {
+                [256, 256]
+            };
+            (index, format!("atlas{}.png", index % 8), size)
+        })
+        .collect();
+
+    let mut bindless = TextureArrays::new(&capabilities(true, RenderLimits::native_defaults()));
+    assert_eq!(bindless.binding(), TextureBinding::Bindless);
+    let batches = bindless.batch(
+        draws
+            .iter()
+            .map(|(i, name, size)| (*i, name.as_str(), *size)),
+    );
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].draws.len(), 64);
+    assert_eq!(batches[0].draws[9], (9, 1));
+    assert_eq!(bindless.groups()[0].textures.len(), 8);
+
+    let mut arrays = TextureArrays::new(&capabilities(false, RenderLimits::native_defaults()));
+    let batches = arrays.batch(
+        draws
+            .iter()
+            .map(|(i, name, size)| (*i, name.as_str(), *size)),
+    );
+    assert_eq!(batches.len(), 64);
+    assert_eq!(arrays.groups().len(), 2);
+}
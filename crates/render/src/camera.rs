@@ -0,0 +1,93 @@
+//! Cameras and the visibility stage.
+//!
+//! A [`Camera`] is the component that makes the renderer draw the world
+//! from an entity's point of view. Several cameras can be active at once;
+//! each runs its own visibility stage over the renderables and draws into
+//! its own [`Viewport`].
+
+use crate::layers::RenderLayers;
+use crate::target::RenderTarget;
+use crate::viewport::Viewport;
+
+/// Component marking an entity as a camera.
+///
+/// # Example
+///
+/// ```
+/// use render::{Camera, RenderLayers};
+///
+/// let minimap = Camera {
+///     layers: RenderLayers::layer(2),
+///     order: 1,
+///     ..Camera::default()
+/// };
+///
+/// let renderables = [("terrain", RenderLayers::default()), ("marker", RenderLayers::layer(2))];
+/// let visible: Vec<_> = minimap.visible(renderables).collect();
+/// assert_eq!(visible, ["marker"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Camera {
+    /// Whether the camera renders at all.
+    pub active: bool,
+    /// Cameras render in ascending order, so higher orders draw on top.
+    pub order: i32,
+    /// Layers the camera sees; renderables sharing none are culled.
+    pub layers: RenderLayers,
+    /// Where the camera draws.
+    pub target: RenderTarget,
+    /// Region of the target the camera covers.
+    pub viewport: Viewport,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            active: true,
+            order: 0,
+            layers: RenderLayers::default(),
+            target: RenderTarget::Window,
+            viewport: Viewport::FULL,
+        }
+    }
+}
+
+impl Camera {
+    /// Returns `true` if a renderable on `layers` is visible to the camera.
+    #[must_use]
+    pub fn sees(&self, layers: RenderLayers) -> bool {
+        self.active && self.layers.intersects(layers)
+    }
+
+    /// Visibility stage: yields the renderables the camera sees.
+    ///
+    /// `renderables` pairs each renderable with its layers; renderables
+    /// without a [`RenderLayers`] component use the default mask.
+    pub fn visible<T>(
+        &self,
+        renderables: impl IntoIterator<Item = (T, RenderLayers)>,
+    ) -> impl Iterator<Item = T> {
+        let camera = self.clone();
+        renderables
+            .into_iter()
+            .filter(move |(_, layers)| camera.sees(*layers))
+            .map(|(renderable, _)| renderable)
+    }
+}
+
+/// Returns the indices of the active `cameras` in the order they render.
+///
+/// Cameras drawing into textures go first so that materials sampling those
+/// textures see the current frame; within each group cameras are sorted by
+/// [`order`](Camera::order), keeping their original order on ties.
+#[must_use]
+pub fn render_order(cameras: &[Camera]) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..cameras.len())
+        .filter(|&index| cameras[index].active)
+        .collect();
+    indices.sort_by_key(|&index| {
+        let camera = &cameras[index];
+        (camera.target == RenderTarget::Window, camera.order)
+    });
+    indices
+}
@@ -0,0 +1,334 @@
+//! Standard camera behaviors.
+//!
+//! Three components move camera entities by writing their [`Transform`]:
+//!
+//! - [`OrbitCamera`] circles a point under mouse control, for editors,
+//!   model viewers, and strategy games.
+//! - [`FollowCamera`] trails another entity with damping and looks ahead
+//!   of its motion, for third-person and racing games.
+//! - [`CameraShake`] adds trauma-based shake on top of either, or on top of
+//!   a camera positioned by game code.
+//!
+//! Shake follows Squirrel Eiserloh's "Juicing Your Cameras With Math": hits
+//! add trauma, trauma decays over time, and the shake grows with the square
+//! of trauma, so small hits barely register and big ones rattle. Offsets
+//! come from smooth noise rather than random jumps, so the camera wobbles
+//! instead of teleporting.
+//!
+//! [`update_camera_controllers`] runs all three over a world once per
+//! frame.
+
+use crate::transform::Transform;
+use ecs::{Entity, World};
+use math::{exp_decay, Quat, Vec2, Vec3};
+use std::f32::consts::FRAC_PI_2;
+use std::time::Duration;
+
+/// Largest pitch an orbit camera reaches, just short of straight up or
+/// down, where yaw becomes meaningless.
+const PITCH_LIMIT: f32 = FRAC_PI_2 - 0.01;
+
+/// Mouse input driving orbit cameras for one frame.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct OrbitInput {
+    /// Mouse motion in pixels while the orbit button is held, +y down.
+    pub rotate: Vec2,
+    /// Scroll wheel lines, positive away from the user to zoom in.
+    pub zoom: f32,
+}
+
+/// Circles a focus point at a distance, steered by the mouse.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitCamera {
+    /// Point the camera circles and looks at.
+    pub focus: Vec3,
+    /// Angle around +Y in radians; 0 places the camera on +Z.
+    pub yaw: f32,
+    /// Angle above the horizon in radians.
+    pub pitch: f32,
+    /// Distance from the focus.
+    pub distance: f32,
+    /// Radians turned per pixel of mouse motion.
+    pub sensitivity: f32,
+    /// Fraction of the distance each scroll line zooms.
+    pub zoom_speed: f32,
+    /// Closest the camera gets to the focus.
+    pub min_distance: f32,
+    /// Farthest the camera gets from the focus.
+    pub max_distance: f32,
+}
+
+impl Default for OrbitCamera {
+    fn default() -> Self {
+        Self {
+            focus: Vec3::ZERO,
+            yaw: 0.0,
+            pitch: 0.3,
+            distance: 10.0,
+            sensitivity: 0.005,
+            zoom_speed: 0.1,
+            min_distance: 1.0,
+            max_distance: 100.0,
+        }
+    }
+}
+
+impl OrbitCamera {
+    /// Applies a frame of mouse input.
+    pub fn apply(&mut self, input: OrbitInput) {
+        self.yaw -= input.rotate.x * self.sensitivity;
+        self.pitch =
+            (self.pitch + input.rotate.y * self.sensitivity).clamp(-PITCH_LIMIT, PITCH_LIMIT);
+        // Multiplicative, so each line feels the same near and far.
+        let factor = (1.0 - self.zoom_speed).powf(input.zoom);
+        self.distance = (self.distance * factor).clamp(self.min_distance, self.max_distance);
+    }
+
+    /// Returns the camera transform.
+    #[must_use]
+    pub fn transform(&self) -> Transform {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        let offset = Vec3::new(cos_pitch * sin_yaw, sin_pitch, cos_pitch * cos_yaw) * self.distance;
+        Transform::from_euler(self.focus + offset, self.yaw, -self.pitch, 0.0)
+    }
+}
+
+/// Trails a target entity, smoothing its motion and looking ahead of it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FollowCamera {
+    /// Entity followed.
+    pub target: Entity,
+    /// Camera position relative to the target.
+    pub offset: Vec3,
+    /// How quickly the camera catches up, per second; higher is snappier.
+    pub damping: f32,
+    /// Seconds of the target's velocity to lead by.
+    pub look_ahead: f32,
+    /// Smoothed point the camera aims at, once following.
+    aim: Option<Vec3>,
+    /// Target position at the last update.
+    last_target: Option<Vec3>,
+}
+
+impl FollowCamera {
+    /// Creates a camera following `target` from `offset`.
+    #[must_use]
+    pub fn new(target: Entity, offset: Vec3) -> Self {
+        Self {
+            target,
+            offset,
+            damping: 5.0,
+            look_ahead: 0.3,
+            aim: None,
+            last_target: None,
+        }
+    }
+
+    /// Sets how quickly the camera catches up, per second.
+    #[must_use]
+    pub fn with_damping(mut self, damping: f32) -> Self {
+        self.damping = damping.max(0.0);
+        self
+    }
+
+    /// Sets how many seconds of the target's velocity to lead by.
+    #[must_use]
+    pub fn with_look_ahead(mut self, seconds: f32) -> Self {
+        self.look_ahead = seconds.max(0.0);
+        self
+    }
+
+    /// Advances by `delta` with the target at `target`, returning the
+    /// camera transform.
+    ///
+    /// The first update snaps to the target, so the camera does not sweep
+    /// in from the origin.
+    pub fn update(&mut self, target: Vec3, delta: Duration) -> Transform {
+        let seconds = delta.as_secs_f32();
+        let velocity = match self.last_target {
+            Some(last) if seconds > 0.0 => (target - last) / seconds,
+            _ => Vec3::ZERO,
+        };
+        self.last_target = Some(target);
+        let goal = target + velocity * self.look_ahead;
+        // Exponential smoothing, which behaves the same at any frame rate.
+        let aim = match self.aim {
+            Some(aim) => exp_decay(aim, goal, self.damping, seconds),
+            None => target,
+        };
+        self.aim = Some(aim);
+        Transform::from_translation(aim + self.offset).looking_at(aim)
+    }
+}
+
+/// Shake driven by trauma that hits add and time removes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraShake {
+    /// Current trauma, from 0 to 1.
+    trauma: f32,
+    /// Trauma removed per second.
+    pub decay: f32,
+    /// Largest offset in world units, at full trauma.
+    pub max_offset: f32,
+    /// Largest yaw, pitch, and roll in radians, at full trauma.
+    pub max_angle: f32,
+    /// Speed of the wobble, in noise cycles per second.
+    pub frequency: f32,
+    /// Time the noise is sampled at.
+    time: f32,
+    /// Offset and rotation applied last frame, to undo before the next.
+    applied: Option<(Vec3, Quat)>,
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            decay: 1.0,
+            max_offset: 0.3,
+            max_angle: 0.05,
+            frequency: 15.0,
+            time: 0.0,
+            applied: None,
+        }
+    }
+}
+
+impl CameraShake {
+    /// Adds trauma, such as 0.2 for a nearby footstep or 0.8 for an
+    /// explosion. Trauma is capped at 1.
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount.max(0.0)).min(1.0);
+    }
+
+    /// Returns the current trauma, from 0 to 1.
+    #[must_use]
+    #[inline]
+    pub fn trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    /// Returns how strongly the camera shakes, from 0 to 1: trauma
+    /// squared.
+    #[must_use]
+    pub fn intensity(&self) -> f32 {
+        self.trauma * self.trauma
+    }
+
+    /// Advances the noise and decays trauma by `delta`.
+    pub fn update(&mut self, delta: Duration) {
+        let seconds = delta.as_secs_f32();
+        self.time += seconds;
+        self.trauma = (self.trauma - self.decay * seconds).max(0.0);
+    }
+
+    /// Returns the shake offset and local rotation at the current time.
+    #[must_use]
+    pub fn offset(&self) -> (Vec3, Quat) {
+        let intensity = self.intensity();
+        let t = self.time * self.frequency;
+        let translation =
+            Vec3::from([0.0, 1.0, 2.0].map(|seed| noise(seed, t) * self.max_offset * intensity));
+        let [yaw, pitch, roll] =
+            [3.0, 4.0, 5.0].map(|seed| noise(seed, t) * self.max_angle * intensity);
+        let rotation = Transform::from_euler(Vec3::ZERO, yaw, pitch, roll).rotation;
+        (translation, rotation)
+    }
+
+    /// Removes the shake applied by the last [`apply`](Self::apply).
+    pub fn remove(&mut self, transform: &mut Transform) {
+        if let Some((offset, rotation)) = self.applied.take() {
+            transform.translation -= offset;
+            *transform = transform.rotated_locally(rotation.inverse());
+        }
+    }
+
+    /// Adds the current shake to `transform`, remembering it so
+    /// [`remove`](Self::remove) can undo it next frame.
+    pub fn apply(&mut self, transform: &mut Transform) {
+        let (offset, rotation) = self.offset();
+        transform.translation += offset;
+        *transform = transform.rotated_locally(rotation);
+        self.applied = Some((offset, rotation));
+    }
+}
+
+/// Smooth noise in -1..=1, different for each seed.
+fn noise(seed: f32, t: f32) -> f32 {
+    // Incommensurate frequencies never line up, so the sum never repeats
+    // visibly.
+    ((t + seed * 17.0).sin() * 0.6 + (t * 2.31 + seed * 5.0).sin() * 0.4).clamp(-1.0, 1.0)
+}
+
+/// Runs the camera controllers of a world for one frame.
+///
+/// Shake applied last frame is removed first, orbit and follow cameras then
+/// write their transforms, and finally the new shake is applied, so shake
+/// never accumulates into the camera's position.
+pub fn update_camera_controllers(world: &mut World, orbit: OrbitInput, delta: Duration) {
+    let shaking = entities::<CameraShake>(world);
+    for &entity in &shaking {
+        with_shake(world, entity, CameraShake::remove);
+    }
+
+    for entity in entities::<OrbitCamera>(world) {
+        let Some(camera) = world.get_mut::<OrbitCamera>(entity) else {
+            continue;
+        };
+        camera.apply(orbit);
+        let transform = camera.transform();
+        set_transform(world, entity, transform);
+    }
+
+    for entity in entities::<FollowCamera>(world) {
+        let Some(target) = world
+            .get::<FollowCamera>(entity)
+            .and_then(|camera| world.get::<Transform>(camera.target))
+            .map(|transform| transform.translation)
+        else {
+            continue;
+        };
+        let Some(camera) = world.get_mut::<FollowCamera>(entity) else {
+            continue;
+        };
+        let transform = camera.update(target, delta);
+        set_transform(world, entity, transform);
+    }
+
+    for &entity in &shaking {
+        if let Some(shake) = world.get_mut::<CameraShake>(entity) {
+            shake.update(delta);
+        }
+        with_shake(world, entity, CameraShake::apply);
+    }
+}
+
+fn entities<T: Send + Sync + 'static>(world: &World) -> Vec<Entity> {
+    let mut entities: Vec<Entity> = world.query::<T>().map(|(entity, _)| entity).collect();
+    entities.sort_unstable();
+    entities
+}
+
+fn set_transform(world: &mut World, entity: Entity, transform: Transform) {
+    match world.get_mut::<Transform>(entity) {
+        Some(existing) => *existing = transform,
+        None => {
+            world.insert(entity, transform);
+        }
+    }
+}
+
+fn with_shake(world: &mut World, entity: Entity, f: fn(&mut CameraShake, &mut Transform)) {
+    let (Some(mut shake), Some(mut transform)) = (
+        world.get::<CameraShake>(entity).copied(),
+        world.get::<Transform>(entity).copied(),
+    ) else {
+        return;
+    };
+    f(&mut shake, &mut transform);
+    set_transform(world, entity, transform);
+    if let Some(stored) = world.get_mut::<CameraShake>(entity) {
+        *stored = shake;
+    }
+}
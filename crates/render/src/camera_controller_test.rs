@@ -0,0 +1,170 @@
+//! Unit tests for camera controllers and transforms.
+
+use crate::camera_controller::{
+    update_camera_controllers, CameraShake, FollowCamera, OrbitCamera, OrbitInput,
+};
+use crate::transform::Transform;
+use ecs::World;
+use math::{Vec2, Vec3};
+use std::time::Duration;
+
+const FRAME: Duration = Duration::from_millis(16);
+
+fn assert_close(actual: Vec3, expected: Vec3) {
+    assert!(actual.abs_diff_eq(expected, 1e-4), "{actual} != {expected}");
+}
+
+fn direction(from: Vec3, to: Vec3) -> Vec3 {
+    (to - from).normalize()
+}
+
+/// Verifies an unrotated transform faces -Z and `looking_at` turns it
+/// toward the target.
+#[test]
+fn test_looking_at_faces_target() {
+    assert_close(Transform::default().forward(), Vec3::NEG_Z);
+
+    let eye = Vec3::new(3.0, 4.0, -2.0);
+    let target = Vec3::new(-1.0, 0.5, 6.0);
+    let transform = Transform::from_translation(eye).looking_at(target);
+    assert_close(transform.forward(), direction(eye, target));
+    // Upright: the camera's up axis still points above the horizon.
+    assert!(transform.rotate(Vec3::Y).y > 0.0);
+}
+
+/// Verifies an orbit camera sits `distance` from its focus and looks at it.
+#[test]
+fn test_orbit_looks_at_focus() {
+    let camera = OrbitCamera {
+        focus: Vec3::new(1.0, 2.0, 3.0),
+        yaw: 0.7,
+        pitch: 0.4,
+        distance: 5.0,
+        ..OrbitCamera::default()
+    };
+    let transform = camera.transform();
+    let offset = direction(camera.focus, transform.translation);
+    assert!((transform.translation.distance(camera.focus) - 5.0).abs() < 1e-4);
+    assert_close(transform.forward(), -offset);
+    assert!(transform.translation.y > camera.focus.y);
+}
+
+/// Verifies mouse input turns the orbit camera, clamping pitch and zoom.
+#[test]
+fn test_orbit_input_is_clamped() {
+    let mut camera = OrbitCamera::default();
+    camera.apply(OrbitInput {
+        rotate: Vec2::new(-100.0, 0.0),
+        zoom: 0.0,
+    });
+    assert!((camera.yaw - 0.5).abs() < 1e-6);
+
+    camera.apply(OrbitInput {
+        rotate: Vec2::new(0.0, 10_000.0),
+        zoom: 1_000.0,
+    });
+    assert!(camera.pitch < std::f32::consts::FRAC_PI_2);
+    assert!((camera.distance - camera.min_distance).abs() < 1e-6);
+
+    camera.apply(OrbitInput {
+        rotate: Vec2::new(0.0, -10_000.0),
+        zoom: -1_000.0,
+    });
+    assert!(camera.pitch > -std::f32::consts::FRAC_PI_2);
+    assert!((camera.distance - camera.max_distance).abs() < 1e-6);
+}
+
+/// Verifies a follow camera snaps on its first update, then lags behind a
+/// moving target and leads it once caught up.
+#[test]
+fn test_follow_damps_and_looks_ahead() {
+    let mut world = World::default();
+    let target = world.spawn();
+    let mut camera = FollowCamera::new(target, Vec3::new(0.0, 2.0, 5.0)).with_look_ahead(0.0);
+
+    let first = camera.update(Vec3::ZERO, FRAME);
+    assert_close(first.translation, Vec3::new(0.0, 2.0, 5.0));
+
+    // The target jumps; the camera covers only part of the way.
+    let second = camera.update(Vec3::new(10.0, 0.0, 0.0), FRAME);
+    assert!(second.translation.x > 0.0 && second.translation.x < 10.0);
+
+    let mut camera = FollowCamera::new(target, Vec3::new(0.0, 2.0, 5.0)).with_look_ahead(0.5);
+    let mut position = 0.0;
+    let mut transform = camera.update(Vec3::new(position, 0.0, 0.0), FRAME);
+    // Moving steadily along +X, the camera settles ahead of the target,
+    // by the look-ahead less the damping lag of speed / damping.
+    for _ in 0..600 {
+        position += 1.0 * FRAME.as_secs_f32();
+        transform = camera.update(Vec3::new(position, 0.0, 0.0), FRAME);
+    }
+    assert!((transform.translation.x - (position + 0.5 - 0.2)).abs() < 0.05);
+}
+
+/// Verifies trauma is capped, decays over time, and shakes with its square.
+#[test]
+fn test_shake_trauma_decays() {
+    let mut shake = CameraShake::default();
+    assert_close(shake.offset().0, Vec3::ZERO);
+
+    shake.add_trauma(0.5);
+    assert!((shake.intensity() - 0.25).abs() < 1e-6);
+    shake.add_trauma(2.0);
+    assert!((shake.trauma() - 1.0).abs() < 1e-6);
+
+    shake.update(Duration::from_millis(250));
+    assert!((shake.trauma() - 0.75).abs() < 1e-6);
+    shake.update(Duration::from_secs(1));
+    assert!(shake.trauma().abs() < 1e-6);
+    assert_close(shake.offset().0, Vec3::ZERO);
+}
+
+/// Verifies shake applied in a world update is removed again the next
+/// frame, so it never drifts the camera.
+#[test]
+fn test_world_update_restores_shaken_camera() {
+    let mut world = World::default();
+    let camera = world.spawn();
+    let base = Transform::from_translation(Vec3::new(1.0, 2.0, 3.0)).looking_at(Vec3::ZERO);
+    world.insert(camera, base);
+    let mut shake = CameraShake::default();
+    shake.add_trauma(1.0);
+    shake.decay = 0.0;
+    world.insert(camera, shake);
+
+    update_camera_controllers(&mut world, OrbitInput::default(), FRAME);
+    let shaken = *world.get::<Transform>(camera).unwrap();
+    assert!(shaken != base);
+
+    world.get_mut::<CameraShake>(camera).unwrap().decay = 100.0;
+    update_camera_controllers(&mut world, OrbitInput::default(), FRAME);
+    let settled = world.get::<Transform>(camera).unwrap();
+    assert_close(settled.translation, base.translation);
+    assert_close(settled.forward(), base.forward());
+}
+
+/// Verifies the world update moves orbit and follow cameras.
+#[test]
+fn test_world_update_moves_cameras() {
+    let mut world = World::default();
+    let player = world.spawn();
+    world.insert(
+        player,
+        Transform::from_translation(Vec3::new(4.0, 0.0, 0.0)),
+    );
+    let follow = world.spawn();
+    world.insert(follow, FollowCamera::new(player, Vec3::new(0.0, 3.0, 6.0)));
+    let orbit = world.spawn();
+    world.insert(orbit, OrbitCamera::default());
+
+    update_camera_controllers(&mut world, OrbitInput::default(), FRAME);
+
+    let transform = world.get::<Transform>(follow).unwrap();
+    assert_close(transform.translation, Vec3::new(4.0, 3.0, 6.0));
+    assert_close(
+        transform.forward(),
+        direction(Vec3::new(4.0, 3.0, 6.0), Vec3::new(4.0, 0.0, 0.0)),
+    );
+    let expected = world.get::<OrbitCamera>(orbit).unwrap().transform();
+    assert_eq!(world.get::<Transform>(orbit), Some(&expected));
+}
@@ -0,0 +1,283 @@
+//! Adapter capabilities and render path selection.
+//!
+//! The adapter's optional features and limits are queried once at startup
+//! and stored in [`GpuCapabilities`]. Optional techniques check it and fall
+//! back to paths every adapter supports, so the engine also runs on low-end
+//! integrated GPUs and older drivers.
+
+use crate::RenderLimits;
+use std::fmt;
+
+/// Optional adapter features the renderer can take advantage of.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct GpuFeatures {
+    /// Arrays of textures indexed dynamically in shaders
+    /// (`TEXTURE_BINDING_ARRAY` with non-uniform indexing).
+    pub bindless_textures: bool,
+    /// Many indirect draws issued by one call (`MULTI_DRAW_INDIRECT`).
+    pub multi_draw_indirect: bool,
+    /// GPU timestamps written between passes (`TIMESTAMP_QUERY`).
+    pub timestamp_query: bool,
+    /// GPU timestamps written inside passes
+    /// (`TIMESTAMP_QUERY_INSIDE_PASSES`).
+    pub timestamp_query_inside_passes: bool,
+    /// A compute queue separate from the graphics queue, so compute work
+    /// overlaps with rendering.
+    pub async_compute: bool,
+}
+
+impl GpuFeatures {
+    /// Returns the features present in both `self` and `other`.
+    #[must_use]
+    pub const fn intersection(self, other: Self) -> Self {
+        Self {
+            bindless_textures: self.bindless_textures && other.bindless_textures,
+            multi_draw_indirect: self.multi_draw_indirect && other.multi_draw_indirect,
+            timestamp_query: self.timestamp_query && other.timestamp_query,
+            timestamp_query_inside_passes: self.timestamp_query_inside_passes
+                && other.timestamp_query_inside_passes,
+            async_compute: self.async_compute && other.async_compute,
+        }
+    }
+
+    /// Every feature the renderer knows how to use.
+    #[must_use]
+    pub const fn all() -> Self {
+        Self {
+            bindless_textures: true,
+            multi_draw_indirect: true,
+            timestamp_query: true,
+            timestamp_query_inside_passes: true,
+            async_compute: true,
+        }
+    }
+}
+
+/// How materials bind their textures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureBinding {
+    /// One global texture array indexed per draw.
+    Bindless,
+    /// One bind group per material, rebound between draws.
+    PerMaterial,
+}
+
+/// How batched geometry is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndirectDraws {
+    /// One multi-draw-indirect call per batch.
+    MultiDraw,
+    /// One indirect draw call per mesh in the batch.
+    Loop,
+}
+
+/// How hidden geometry is culled beyond the view frustum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OcclusionCulling {
+    /// Draws are tested in compute against a hierarchical depth pyramid.
+    HiZ,
+    /// Only frustum culling is done.
+    Disabled,
+}
+
+/// How light probes are kept up to date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalIllumination {
+    /// Probes are re-lit every frame by tracing rays in compute.
+    Dynamic,
+    /// Probes keep the lighting baked with the level.
+    Baked,
+}
+
+/// Which queue compute passes, such as GPU particle simulation and
+/// skinning, are submitted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComputeQueue {
+    /// A dedicated compute queue, running alongside graphics work.
+    Async,
+    /// The graphics queue, in graph order with every other pass.
+    Graphics,
+}
+
+/// Compute invocations per workgroup the dynamic probe update is written
+/// for; each workgroup traces the rays of one probe.
+pub const DDGI_WORKGROUP_INVOCATIONS: u32 = 128;
+
+/// Compute invocations per workgroup the depth pyramid and occlusion test
+/// shaders are written for.
+pub const HIZ_WORKGROUP_INVOCATIONS: u32 = 64;
+
+/// Features and limits of the adapter the renderer runs on.
+///
+/// # Example
+///
+/// ```
+/// use render::capabilities::{GpuCapabilities, GpuFeatures, IndirectDraws, TextureBinding};
+/// use render::RenderLimits;
+///
+/// let features = GpuFeatures {
+///     multi_draw_indirect: true,
+///     ..GpuFeatures::default()
+/// };
+/// let caps = GpuCapabilities::from_adapter("Integrated GPU", features, RenderLimits::webgpu_defaults());
+///
+/// assert_eq!(caps.indirect_draws(), IndirectDraws::MultiDraw);
+/// assert_eq!(caps.texture_binding(), TextureBinding::PerMaterial);
+/// assert!(!caps.gpu_timing());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuCapabilities {
+    /// Adapter name reported by the driver.
+    pub adapter_name: String,
+    /// Optional features enabled on the device.
+    pub features: GpuFeatures,
+    /// Limits the renderer sizes its resources against.
+    pub limits: RenderLimits,
+}
+
+impl Default for GpuCapabilities {
+    fn default() -> Self {
+        Self::baseline()
+    }
+}
+
+impl GpuCapabilities {
+    /// Capabilities every adapter provides: no optional features and the
+    /// target's default limits.
+    ///
+    /// Used until an adapter has been queried.
+    #[must_use]
+    pub fn baseline() -> Self {
+        Self {
+            adapter_name: String::new(),
+            features: GpuFeatures::default(),
+            limits: RenderLimits::for_target(),
+        }
+    }
+
+    /// Builds capabilities from what an adapter reports.
+    ///
+    /// Features are limited to those the renderer uses, and limits are
+    /// clamped to the target's defaults so behavior stays predictable on
+    /// high-end adapters.
+    #[must_use]
+    pub fn from_adapter(
+        adapter_name: impl Into<String>,
+        features: GpuFeatures,
+        limits: RenderLimits,
+    ) -> Self {
+        let mut features = features.intersection(GpuFeatures::all());
+        // Timestamps inside passes are meaningless without timestamp queries
+        features.timestamp_query_inside_passes &= features.timestamp_query;
+        Self {
+            adapter_name: adapter_name.into(),
+            features,
+            limits: RenderLimits::for_target().min(limits),
+        }
+    }
+
+    /// Disables optional features, e.g. to rule them out when debugging.
+    #[must_use]
+    pub fn without(mut self, disabled: GpuFeatures) -> Self {
+        self.features = GpuFeatures {
+            bindless_textures: self.features.bindless_textures && !disabled.bindless_textures,
+            multi_draw_indirect: self.features.multi_draw_indirect && !disabled.multi_draw_indirect,
+            timestamp_query: self.features.timestamp_query && !disabled.timestamp_query,
+            timestamp_query_inside_passes: self.features.timestamp_query_inside_passes
+                && !disabled.timestamp_query_inside_passes
+                && !disabled.timestamp_query,
+            async_compute: self.features.async_compute && !disabled.async_compute,
+        };
+        self
+    }
+
+    /// Returns how materials should bind textures.
+    #[must_use]
+    pub fn texture_binding(&self) -> TextureBinding {
+        if self.features.bindless_textures {
+            TextureBinding::Bindless
+        } else {
+            TextureBinding::PerMaterial
+        }
+    }
+
+    /// Returns how batched geometry should be drawn.
+    #[must_use]
+    pub fn indirect_draws(&self) -> IndirectDraws {
+        if self.features.multi_draw_indirect {
+            IndirectDraws::MultiDraw
+        } else {
+            IndirectDraws::Loop
+        }
+    }
+
+    /// Returns how hidden geometry can be culled.
+    ///
+    /// Occlusion culling needs compute shaders with at least
+    /// [`HIZ_WORKGROUP_INVOCATIONS`] invocations and two storage buffers
+    /// (draw inputs and visible draws).
+    #[must_use]
+    pub fn occlusion_culling(&self) -> OcclusionCulling {
+        if self.limits.max_compute_invocations_per_workgroup >= HIZ_WORKGROUP_INVOCATIONS
+            && self.limits.max_storage_buffers_per_shader_stage >= 2
+        {
+            OcclusionCulling::HiZ
+        } else {
+            OcclusionCulling::Disabled
+        }
+    }
+
+    /// Returns how light probes can be lit.
+    ///
+    /// Dynamic probes need compute shaders with at least
+    /// [`DDGI_WORKGROUP_INVOCATIONS`] invocations and four storage buffers
+    /// (rays, probes, and the scene's geometry and lights).
+    #[must_use]
+    pub fn global_illumination(&self) -> GlobalIllumination {
+        if self.limits.max_compute_invocations_per_workgroup >= DDGI_WORKGROUP_INVOCATIONS
+            && self.limits.max_storage_buffers_per_shader_stage >= 4
+        {
+            GlobalIllumination::Dynamic
+        } else {
+            GlobalIllumination::Baked
+        }
+    }
+
+    /// Returns which queue compute passes should be submitted to.
+    #[must_use]
+    pub fn compute_queue(&self) -> ComputeQueue {
+        if self.features.async_compute {
+            ComputeQueue::Async
+        } else {
+            ComputeQueue::Graphics
+        }
+    }
+
+    /// Returns `true` if GPU pass timings can be measured.
+    #[must_use]
+    pub fn gpu_timing(&self) -> bool {
+        self.features.timestamp_query
+    }
+}
+
+impl fmt::Display for GpuCapabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = if self.adapter_name.is_empty() {
+            "unknown adapter"
+        } else {
+            &self.adapter_name
+        };
+        write!(
+            f,
+            "{name}: textures={:?}, draws={:?}, occlusion={:?}, gi={:?}, compute={:?}, gpu_timing={}, max_texture={}",
+            self.texture_binding(),
+            self.indirect_draws(),
+            self.occlusion_culling(),
+            self.global_illumination(),
+            self.compute_queue(),
+            self.gpu_timing(),
+            self.limits.max_texture_dimension_2d
+        )
+    }
+}
@@ -0,0 +1,95 @@
+//! Unit tests for adapter capabilities and render path selection.
+
+use crate::capabilities::{
+    ComputeQueue, GlobalIllumination, GpuCapabilities, GpuFeatures, IndirectDraws, TextureBinding,
+};
+use crate::RenderLimits;
+
+/// Verifies an adapter without optional features gets every fallback.
+#[test]
+fn baseline_uses_fallbacks() {
+    let caps = GpuCapabilities::baseline();
+
+    assert_eq!(caps.texture_binding(), TextureBinding::PerMaterial);
+    assert_eq!(caps.indirect_draws(), IndirectDraws::Loop);
+    assert_eq!(caps.compute_queue(), ComputeQueue::Graphics);
+    assert!(!caps.gpu_timing());
+    assert_eq!(caps.limits, RenderLimits::for_target());
+}
+
+/// Verifies a fully featured adapter enables every technique.
+#[test]
+fn full_adapter_enables_techniques() {
+    let caps = GpuCapabilities::from_adapter(
+        "Discrete GPU",
+        GpuFeatures::all(),
+        RenderLimits::native_defaults(),
+    );
+
+    assert_eq!(caps.texture_binding(), TextureBinding::Bindless);
+    assert_eq!(caps.indirect_draws(), IndirectDraws::MultiDraw);
+    assert_eq!(caps.compute_queue(), ComputeQueue::Async);
+    assert!(caps.gpu_timing());
+}
+
+/// Verifies adapter limits below the defaults are respected.
+#[test]
+fn limits_clamp_to_adapter() {
+    let limits = RenderLimits {
+        max_texture_dimension_2d: 4096,
+        ..RenderLimits::for_target()
+    };
+    let caps = GpuCapabilities::from_adapter("Old GPU", GpuFeatures::default(), limits);
+
+    assert_eq!(caps.limits.max_texture_dimension_2d, 4096);
+}
+
+/// Verifies in-pass timestamps require timestamp queries.
+#[test]
+fn inside_pass_timestamps_need_timestamp_query() {
+    let features = GpuFeatures {
+        timestamp_query_inside_passes: true,
+        ..GpuFeatures::default()
+    };
+    let caps = GpuCapabilities::from_adapter("GPU", features, RenderLimits::for_target());
+
+    assert!(!caps.features.timestamp_query_inside_passes);
+}
+
+/// Verifies features can be switched off after detection.
+#[test]
+fn without_disables_features() {
+    let caps = GpuCapabilities::from_adapter("GPU", GpuFeatures::all(), RenderLimits::for_target())
+        .without(GpuFeatures {
+            bindless_textures: true,
+            timestamp_query: true,
+            ..GpuFeatures::default()
+        });
+
+    assert_eq!(caps.texture_binding(), TextureBinding::PerMaterial);
+    assert_eq!(caps.indirect_draws(), IndirectDraws::MultiDraw);
+    assert!(!caps.gpu_timing());
+    assert!(!caps.features.timestamp_query_inside_passes);
+}
+
+/// Verifies dynamic light probes need enough compute invocations and
+/// storage buffers.
+#[test]
+fn dynamic_probes_need_compute() {
+    let native = GpuCapabilities::from_adapter(
+        "GPU",
+        GpuFeatures::default(),
+        RenderLimits::native_defaults(),
+    );
+    assert_eq!(native.global_illumination(), GlobalIllumination::Dynamic);
+
+    let limited = GpuCapabilities::from_adapter(
+        "GPU",
+        GpuFeatures::default(),
+        RenderLimits {
+            max_storage_buffers_per_shader_stage: 2,
+            ..RenderLimits::native_defaults()
+        },
+    );
+    assert_eq!(limited.global_illumination(), GlobalIllumination::Baked);
+}
@@ -0,0 +1,270 @@
+//! Color management: surface formats, HDR output, and tonemapping.
+//!
+//! Shading happens in linear light. What happens at the end of the frame
+//! depends on the surface:
+//!
+//! - SDR surfaces use an `*Srgb` format whenever one is offered, so the
+//!   hardware applies the sRGB transfer function on write. If only a
+//!   linear 8-bit format is available, [`OutputMode::shader_encodes_srgb`]
+//!   tells the final pass to encode manually; writing linear values to it
+//!   directly would make the image too dark.
+//! - HDR10 surfaces expect Rec. 2020 primaries encoded with the PQ curve.
+//! - scRGB surfaces take linear Rec. 709 values where 1.0 is 80 nits.
+//!
+//! [`tonemap`] is the reference for the tonemapping pass, which compresses
+//! scene luminance into the range of the active output and places SDR
+//! white at the configured paper-white level on HDR displays.
+
+use rustgine_core::{OutputConfig, SurfaceFormatPreference};
+use std::fmt;
+
+/// Luminance of scRGB 1.0, in nits.
+const SCRGB_WHITE_NITS: f32 = 80.0;
+
+/// Peak luminance the PQ curve encodes, in nits.
+const PQ_MAX_NITS: f32 = 10_000.0;
+
+/// Peak luminance assumed for HDR displays that do not report one.
+const DEFAULT_HDR_PEAK_NITS: f32 = 1000.0;
+
+/// Surface texture formats relevant to presentation, mirroring wgpu's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SurfaceFormat {
+    /// 8-bit RGBA, linear.
+    Rgba8Unorm,
+    /// 8-bit RGBA, sRGB encoded on write.
+    Rgba8UnormSrgb,
+    /// 8-bit BGRA, linear.
+    Bgra8Unorm,
+    /// 8-bit BGRA, sRGB encoded on write.
+    Bgra8UnormSrgb,
+    /// 10-bit RGB with 2-bit alpha, used for HDR10.
+    Rgb10a2Unorm,
+    /// 16-bit float RGBA, used for scRGB.
+    Rgba16Float,
+}
+
+impl SurfaceFormat {
+    /// Returns `true` if the hardware applies the sRGB transfer function
+    /// when writing.
+    #[must_use]
+    pub fn is_srgb(self) -> bool {
+        matches!(self, Self::Rgba8UnormSrgb | Self::Bgra8UnormSrgb)
+    }
+
+    /// Returns `true` for 8-bit formats suitable for SDR output.
+    #[must_use]
+    pub fn is_sdr(self) -> bool {
+        matches!(
+            self,
+            Self::Rgba8Unorm | Self::Rgba8UnormSrgb | Self::Bgra8Unorm | Self::Bgra8UnormSrgb
+        )
+    }
+}
+
+/// Color space the final pass writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// sRGB primaries and transfer function.
+    Srgb,
+    /// Rec. 2020 primaries with the PQ transfer function.
+    Hdr10,
+    /// Linear Rec. 709 primaries, extended range.
+    ScRgb,
+}
+
+impl ColorSpace {
+    /// Returns `true` for HDR color spaces.
+    #[must_use]
+    pub fn is_hdr(self) -> bool {
+        !matches!(self, Self::Srgb)
+    }
+}
+
+/// What the display reports about its HDR support.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DisplayInfo {
+    /// Whether HDR output is enabled on the display.
+    pub hdr: bool,
+    /// Peak luminance in nits, if known.
+    pub peak_nits: Option<f32>,
+}
+
+/// Output format and luminance levels chosen for the surface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutputMode {
+    /// Surface format to configure.
+    pub format: SurfaceFormat,
+    /// Color space the final pass writes.
+    pub color_space: ColorSpace,
+    /// Brightness of SDR white in nits; only used for HDR output.
+    pub paper_white_nits: f32,
+    /// Peak display brightness in nits; only used for HDR output.
+    pub peak_nits: f32,
+}
+
+impl OutputMode {
+    /// Chooses the output for a surface offering `supported` formats.
+    ///
+    /// HDR preferences fall back to SDR when the display has HDR disabled
+    /// or the surface lacks the format; [`SurfaceFormatPreference::Auto`]
+    /// tries scRGB, then HDR10. SDR output prefers an sRGB format.
+    ///
+    /// Returns `None` if `supported` holds no usable format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use render::color::{ColorSpace, DisplayInfo, OutputMode, SurfaceFormat};
+    /// use rustgine_core::{OutputConfig, SurfaceFormatPreference};
+    ///
+    /// let config = OutputConfig {
+    ///     surface_format: SurfaceFormatPreference::Auto,
+    ///     ..OutputConfig::default()
+    /// };
+    /// let formats = [SurfaceFormat::Bgra8UnormSrgb, SurfaceFormat::Rgb10a2Unorm];
+    ///
+    /// let sdr_display = OutputMode::select(&config, &formats, DisplayInfo::default()).unwrap();
+    /// assert_eq!(sdr_display.color_space, ColorSpace::Srgb);
+    ///
+    /// let hdr = DisplayInfo { hdr: true, peak_nits: Some(600.0) };
+    /// let hdr_display = OutputMode::select(&config, &formats, hdr).unwrap();
+    /// assert_eq!(hdr_display.color_space, ColorSpace::Hdr10);
+    /// ```
+    #[must_use]
+    pub fn select(
+        config: &OutputConfig,
+        supported: &[SurfaceFormat],
+        display: DisplayInfo,
+    ) -> Option<Self> {
+        let hdr_candidates: &[ColorSpace] = match config.surface_format {
+            _ if !display.hdr => &[],
+            SurfaceFormatPreference::Sdr => &[],
+            SurfaceFormatPreference::Hdr10 => &[ColorSpace::Hdr10],
+            SurfaceFormatPreference::ScRgb => &[ColorSpace::ScRgb],
+            SurfaceFormatPreference::Auto => &[ColorSpace::ScRgb, ColorSpace::Hdr10],
+        };
+        let hdr = hdr_candidates.iter().find_map(|&color_space| {
+            let format = match color_space {
+                ColorSpace::Hdr10 => SurfaceFormat::Rgb10a2Unorm,
+                ColorSpace::ScRgb => SurfaceFormat::Rgba16Float,
+                ColorSpace::Srgb => return None,
+            };
+            supported.contains(&format).then_some((format, color_space))
+        });
+
+        let (format, color_space) = if let Some(choice) = hdr {
+            choice
+        } else {
+            let format = supported
+                .iter()
+                .copied()
+                .find(|format| format.is_srgb())
+                .or_else(|| supported.iter().copied().find(|format| format.is_sdr()))?;
+            (format, ColorSpace::Srgb)
+        };
+        let paper_white_nits = config.paper_white_nits;
+        Some(Self {
+            format,
+            color_space,
+            paper_white_nits,
+            peak_nits: display
+                .peak_nits
+                .unwrap_or(DEFAULT_HDR_PEAK_NITS)
+                .max(paper_white_nits),
+        })
+    }
+
+    /// Returns `true` if the final pass must apply the sRGB transfer
+    /// function itself because the surface format does not.
+    #[must_use]
+    pub fn shader_encodes_srgb(&self) -> bool {
+        self.color_space == ColorSpace::Srgb && !self.format.is_srgb()
+    }
+
+    /// Returns the brightest scene value, relative to paper white, that the
+    /// output can show.
+    #[must_use]
+    pub fn headroom(&self) -> f32 {
+        if self.color_space.is_hdr() {
+            self.peak_nits / self.paper_white_nits
+        } else {
+            1.0
+        }
+    }
+}
+
+impl fmt::Display for OutputMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} ({:?})", self.format, self.color_space)?;
+        if self.color_space.is_hdr() {
+            write!(
+                f,
+                ", paper white {} nits, peak {} nits",
+                self.paper_white_nits, self.peak_nits
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Encodes a linear value with the sRGB transfer function.
+#[must_use]
+pub fn srgb_encode(linear: f32) -> f32 {
+    math::color::linear_to_srgb(linear)
+}
+
+/// Decodes an sRGB-encoded value to linear.
+#[must_use]
+pub fn srgb_decode(encoded: f32) -> f32 {
+    math::color::srgb_to_linear(encoded)
+}
+
+/// Encodes absolute luminance in nits with the SMPTE ST 2084 (PQ) curve.
+#[must_use]
+pub fn pq_encode(nits: f32) -> f32 {
+    const M1: f32 = 0.159_301_76;
+    const M2: f32 = 78.843_75;
+    const C1: f32 = 0.835_937_5;
+    const C2: f32 = 18.851_563;
+    const C3: f32 = 18.6875;
+    let y = (nits / PQ_MAX_NITS).clamp(0.0, 1.0).powf(M1);
+    ((C1 + C2 * y) / (1.0 + C3 * y)).powf(M2)
+}
+
+/// Converts linear Rec. 709 (sRGB primaries) to Rec. 2020.
+#[must_use]
+pub fn rec709_to_rec2020([r, g, b]: [f32; 3]) -> [f32; 3] {
+    [
+        0.627_404 * r + 0.329_283 * g + 0.043_313 * b,
+        0.069_097 * r + 0.919_540 * g + 0.011_362 * b,
+        0.016_391 * r + 0.088_013 * g + 0.895_595 * b,
+    ]
+}
+
+/// Tonemaps a linear scene color, where 1.0 is paper white, for `output`.
+///
+/// Uses the extended Reinhard curve with its white point at the output's
+/// [`headroom`](OutputMode::headroom), so SDR output compresses highlights
+/// into `0..=1` while HDR output keeps them up to the display's peak. The
+/// result is encoded for the output's color space and format: sRGB-encoded
+/// only if [`OutputMode::shader_encodes_srgb`], PQ for HDR10, and scaled
+/// to 80-nit units for scRGB.
+#[must_use]
+pub fn tonemap(color: [f32; 3], output: &OutputMode) -> [f32; 3] {
+    let white = output.headroom();
+    let curve = |value: f32| {
+        let value = value.max(0.0);
+        (value * (1.0 + value / (white * white)) / (1.0 + value)).min(white)
+    };
+    let mapped = color.map(curve);
+
+    match output.color_space {
+        ColorSpace::Srgb if output.shader_encodes_srgb() => mapped.map(srgb_encode),
+        ColorSpace::Srgb => mapped,
+        ColorSpace::Hdr10 => {
+            rec709_to_rec2020(mapped).map(|value| pq_encode(value * output.paper_white_nits))
+        }
+        ColorSpace::ScRgb => mapped.map(|value| value * output.paper_white_nits / SCRGB_WHITE_NITS),
+    }
+}
@@ -0,0 +1,113 @@
+//! Unit tests for color management.
+
+use crate::color::{
+    pq_encode, srgb_decode, srgb_encode, tonemap, ColorSpace, DisplayInfo, OutputMode,
+    SurfaceFormat,
+};
+use rustgine_core::{OutputConfig, SurfaceFormatPreference};
+
+const HDR_DISPLAY: DisplayInfo = DisplayInfo {
+    hdr: true,
+    peak_nits: Some(1000.0),
+};
+
+fn config(surface_format: SurfaceFormatPreference) -> OutputConfig {
+    OutputConfig {
+        surface_format,
+        paper_white_nits: 200.0,
+    }
+}
+
+/// Verifies SDR output prefers sRGB formats and flags manual encoding.
+#[test]
+fn sdr_prefers_srgb_formats() {
+    let sdr = config(SurfaceFormatPreference::Sdr);
+    let mode = OutputMode::select(
+        &sdr,
+        &[SurfaceFormat::Bgra8Unorm, SurfaceFormat::Bgra8UnormSrgb],
+        HDR_DISPLAY,
+    )
+    .unwrap();
+    assert_eq!(mode.format, SurfaceFormat::Bgra8UnormSrgb);
+    assert!(!mode.shader_encodes_srgb());
+
+    let linear_only = OutputMode::select(&sdr, &[SurfaceFormat::Rgba8Unorm], HDR_DISPLAY).unwrap();
+    assert!(linear_only.shader_encodes_srgb());
+
+    assert!(OutputMode::select(&sdr, &[SurfaceFormat::Rgba16Float], HDR_DISPLAY).is_none());
+}
+
+/// Verifies HDR preferences need both display support and the format.
+#[test]
+fn hdr_falls_back_to_sdr() {
+    let formats = [SurfaceFormat::Bgra8UnormSrgb, SurfaceFormat::Rgb10a2Unorm];
+
+    let hdr10 = config(SurfaceFormatPreference::Hdr10);
+    assert_eq!(
+        OutputMode::select(&hdr10, &formats, HDR_DISPLAY)
+            .unwrap()
+            .color_space,
+        ColorSpace::Hdr10
+    );
+    assert_eq!(
+        OutputMode::select(&hdr10, &formats, DisplayInfo::default())
+            .unwrap()
+            .color_space,
+        ColorSpace::Srgb
+    );
+
+    let scrgb = config(SurfaceFormatPreference::ScRgb);
+    assert_eq!(
+        OutputMode::select(&scrgb, &formats, HDR_DISPLAY)
+            .unwrap()
+            .format,
+        SurfaceFormat::Bgra8UnormSrgb
+    );
+
+    let auto = config(SurfaceFormatPreference::Auto);
+    let mut all = formats.to_vec();
+    all.push(SurfaceFormat::Rgba16Float);
+    assert_eq!(
+        OutputMode::select(&auto, &all, HDR_DISPLAY)
+            .unwrap()
+            .color_space,
+        ColorSpace::ScRgb
+    );
+}
+
+/// Verifies the transfer functions round-trip and hit known values.
+#[test]
+fn transfer_functions() {
+    for value in [0.0, 0.002, 0.18, 0.5, 1.0] {
+        assert!((srgb_decode(srgb_encode(value)) - value).abs() < 1e-5);
+    }
+    assert!((srgb_encode(0.18) - 0.461).abs() < 1e-3);
+    assert!(pq_encode(0.0) < 1e-6);
+    assert!((pq_encode(10_000.0) - 1.0).abs() < 1e-5);
+    assert!((pq_encode(100.0) - 0.508).abs() < 1e-3);
+}
+
+/// Verifies tonemapping adapts to the output's headroom.
+#[test]
+fn tonemapping_adapts_to_output() {
+    let formats = [SurfaceFormat::Bgra8UnormSrgb, SurfaceFormat::Rgba16Float];
+    let sdr =
+        OutputMode::select(&config(SurfaceFormatPreference::Sdr), &formats, HDR_DISPLAY).unwrap();
+    let scrgb = OutputMode::select(
+        &config(SurfaceFormatPreference::ScRgb),
+        &formats,
+        HDR_DISPLAY,
+    )
+    .unwrap();
+    assert!((scrgb.headroom() - 5.0).abs() < f32::EPSILON);
+
+    // SDR compresses highlights to white; HDR keeps them above paper white
+    let highlight = [20.0, 20.0, 20.0];
+    assert!((tonemap(highlight, &sdr)[0] - 1.0).abs() < 1e-6);
+    let hdr_nits = tonemap(highlight, &scrgb)[0] * 80.0;
+    assert!(hdr_nits > 200.0 && hdr_nits <= 1000.0);
+
+    // Paper white lands at the configured level
+    let white = tonemap([1.0, 1.0, 1.0], &scrgb)[0] * 80.0;
+    assert!(white > 0.5 * 200.0 && white <= 200.0);
+}
@@ -0,0 +1,210 @@
+//! Post-transform vertex cache and vertex fetch optimization.
+
+use crate::mesh::{Mesh, MeshVertex};
+
+/// Entries of the vertex cache modeled by [`optimize_vertex_cache`].
+const OPTIMIZE_CACHE_SIZE: usize = 32;
+
+/// FIFO entries of the vertex cache [`analyze_vertex_cache`] simulates by
+/// default, a conservative size for current GPUs.
+pub const DEFAULT_CACHE_SIZE: usize = 16;
+
+/// Efficiency of an index buffer in a post-transform vertex cache.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct VertexCacheStats {
+    /// Vertices shaded, counting each cache miss.
+    pub vertices_shaded: u32,
+    /// Average vertices shaded per triangle (ACMR), 0.5 at best and 3.0
+    /// without any reuse.
+    pub acmr: f32,
+    /// Average times each referenced vertex is shaded (ATVR), 1.0 at best.
+    pub atvr: f32,
+}
+
+/// Simulates a FIFO vertex cache of `cache_size` entries over `indices`.
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+pub fn analyze_vertex_cache(indices: &[u32], cache_size: usize) -> VertexCacheStats {
+    let mut cache = std::collections::VecDeque::with_capacity(cache_size + 1);
+    let mut referenced = std::collections::HashSet::new();
+    let mut misses = 0_u32;
+    for &index in indices {
+        referenced.insert(index);
+        if !cache.contains(&index) {
+            misses += 1;
+            cache.push_back(index);
+            if cache.len() > cache_size {
+                cache.pop_front();
+            }
+        }
+    }
+    let triangles = indices.len() / 3;
+    VertexCacheStats {
+        vertices_shaded: misses,
+        acmr: if triangles == 0 {
+            0.0
+        } else {
+            misses as f32 / triangles as f32
+        },
+        atvr: if referenced.is_empty() {
+            0.0
+        } else {
+            misses as f32 / referenced.len() as f32
+        },
+    }
+}
+
+/// Score of a vertex at `position` in the LRU cache with `remaining`
+/// triangles left to emit, after Forsyth's linear-speed optimizer.
+#[allow(clippy::cast_precision_loss)]
+fn vertex_score(position: Option<usize>, remaining: u32) -> f32 {
+    if remaining == 0 {
+        return -1.0;
+    }
+    let cache = match position {
+        None => 0.0,
+        // The last triangle's vertices score the same so the order of
+        // its corners does not matter.
+        Some(position) if position < 3 => 0.75,
+        Some(position) => {
+            let scaled = 1.0 - (position - 3) as f32 / (OPTIMIZE_CACHE_SIZE - 3) as f32;
+            scaled.powf(1.5)
+        }
+    };
+    cache + 2.0 * (remaining as f32).powf(-0.5)
+}
+
+/// Reorders the triangles of `indices`, referencing `vertex_count`
+/// vertices, to reuse recently shaded vertices.
+///
+/// Triangles are emitted greedily by the score of their vertices, which
+/// favors vertices still in an LRU cache and vertices with few triangles
+/// left, so fans are finished before moving on.
+///
+/// # Panics
+///
+/// Panics if an index is not below `vertex_count`.
+#[must_use]
+pub fn optimize_vertex_cache(indices: &[u32], vertex_count: usize) -> Vec<u32> {
+    let triangles = indices.len() / 3;
+    let mut remaining = vec![0_u32; vertex_count];
+    for &index in &indices[..triangles * 3] {
+        remaining[index as usize] += 1;
+    }
+    // Triangles using each vertex, as offsets into `adjacency`.
+    let mut offsets = Vec::with_capacity(vertex_count + 1);
+    let mut total = 0;
+    for &count in &remaining {
+        offsets.push(total);
+        total += count as usize;
+    }
+    offsets.push(total);
+    let mut filled = offsets.clone();
+    let mut adjacency = vec![0_usize; total];
+    for triangle in 0..triangles {
+        for &index in &indices[triangle * 3..triangle * 3 + 3] {
+            adjacency[filled[index as usize]] = triangle;
+            filled[index as usize] += 1;
+        }
+    }
+
+    let mut cache_position: Vec<Option<usize>> = vec![None; vertex_count];
+    let mut scores: Vec<f32> = remaining
+        .iter()
+        .map(|&remaining| vertex_score(None, remaining))
+        .collect();
+    let triangle_score = |triangle: usize, scores: &[f32]| -> f32 {
+        indices[triangle * 3..triangle * 3 + 3]
+            .iter()
+            .map(|&index| scores[index as usize])
+            .sum()
+    };
+    let mut triangle_scores: Vec<f32> = (0..triangles)
+        .map(|triangle| triangle_score(triangle, &scores))
+        .collect();
+    let mut emitted = vec![false; triangles];
+    let mut cache: Vec<u32> = Vec::with_capacity(OPTIMIZE_CACHE_SIZE + 3);
+    let mut output = Vec::with_capacity(triangles * 3);
+    let mut best = best_triangle(&triangle_scores, &emitted, 0..triangles);
+    let mut cursor = 0;
+
+    while let Some(triangle) = best {
+        emitted[triangle] = true;
+        triangle_scores[triangle] = f32::NEG_INFINITY;
+        let corners = &indices[triangle * 3..triangle * 3 + 3];
+        output.extend_from_slice(corners);
+        for &index in corners {
+            remaining[index as usize] -= 1;
+        }
+
+        let mut next: Vec<u32> = corners.to_vec();
+        next.extend(cache.iter().filter(|index| !corners.contains(index)));
+        for &evicted in next.iter().skip(OPTIMIZE_CACHE_SIZE) {
+            cache_position[evicted as usize] = None;
+        }
+        let touched = next.clone();
+        next.truncate(OPTIMIZE_CACHE_SIZE);
+        cache = next;
+        for (position, &index) in cache.iter().enumerate() {
+            cache_position[index as usize] = Some(position);
+        }
+
+        let mut candidates = Vec::new();
+        for &index in &touched {
+            let index = index as usize;
+            scores[index] = vertex_score(cache_position[index], remaining[index]);
+            candidates.extend(
+                adjacency[offsets[index]..offsets[index + 1]]
+                    .iter()
+                    .copied()
+                    .filter(|&triangle| !emitted[triangle]),
+            );
+        }
+        for &candidate in &candidates {
+            triangle_scores[candidate] = triangle_score(candidate, &scores);
+        }
+        // With no cached candidates left, continue with the first
+        // unemitted triangle, as rescoring every triangle is quadratic.
+        best = best_triangle(&triangle_scores, &emitted, candidates.into_iter()).or_else(|| {
+            while cursor < triangles && emitted[cursor] {
+                cursor += 1;
+            }
+            (cursor < triangles).then_some(cursor)
+        });
+    }
+    output
+}
+
+/// Returns the unemitted triangle among `candidates` with the best score.
+fn best_triangle(
+    scores: &[f32],
+    emitted: &[bool],
+    candidates: impl Iterator<Item = usize>,
+) -> Option<usize> {
+    candidates
+        .filter(|&triangle| !emitted[triangle])
+        .max_by(|&a, &b| scores[a].total_cmp(&scores[b]).then(b.cmp(&a)))
+}
+
+/// Reorders the vertices of `mesh` in the order its indices first use
+/// them, dropping unreferenced vertices, so vertex fetches stream through
+/// memory.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn optimize_vertex_fetch(mesh: &Mesh) -> Mesh {
+    let mut remap = vec![u32::MAX; mesh.vertices.len()];
+    let mut vertices: Vec<MeshVertex> = Vec::with_capacity(mesh.vertices.len());
+    let indices = mesh
+        .indices
+        .iter()
+        .map(|&index| {
+            let slot = &mut remap[index as usize];
+            if *slot == u32::MAX {
+                *slot = vertices.len() as u32;
+                vertices.push(mesh.vertices[index as usize]);
+            }
+            *slot
+        })
+        .collect();
+    Mesh { vertices, indices }
+}
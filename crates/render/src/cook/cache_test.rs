@@ -0,0 +1,69 @@
+//! Unit tests for vertex cache and vertex fetch optimization.
+
+use super::cache::{
+    analyze_vertex_cache, optimize_vertex_cache, optimize_vertex_fetch, DEFAULT_CACHE_SIZE,
+};
+use super::mesh_test::{grid, shuffled, sorted_triangles};
+use crate::mesh::{Mesh, MeshVertex};
+
+/// Verifies the cache simulation counts shaded vertices per triangle and
+/// per referenced vertex.
+#[test]
+fn analyze_counts_misses() {
+    let separate = analyze_vertex_cache(&[0, 1, 2, 3, 4, 5], DEFAULT_CACHE_SIZE);
+    assert_eq!(separate.vertices_shaded, 6);
+    assert!((separate.acmr - 3.0).abs() < 1e-6);
+    assert!((separate.atvr - 1.0).abs() < 1e-6);
+
+    let quad = analyze_vertex_cache(&[0, 1, 2, 2, 1, 3], DEFAULT_CACHE_SIZE);
+    assert!((quad.acmr - 2.0).abs() < 1e-6);
+
+    // A cache of 3 entries has evicted vertex 0 when it comes back.
+    let evicted = analyze_vertex_cache(&[0, 1, 2, 3, 4, 5, 0, 4, 5], 3);
+    assert!((evicted.atvr - 7.0 / 6.0).abs() < 1e-6);
+    assert!(analyze_vertex_cache(&[], DEFAULT_CACHE_SIZE).acmr.abs() < 1e-6);
+}
+
+/// Verifies optimization keeps every triangle and its winding while
+/// reusing far more cached vertices than a scattered order.
+#[test]
+fn optimize_improves_cache_reuse() {
+    let mesh = shuffled(&grid(16));
+    let before = analyze_vertex_cache(&mesh.indices, DEFAULT_CACHE_SIZE);
+
+    let indices = optimize_vertex_cache(&mesh.indices, mesh.vertices.len());
+    let after = analyze_vertex_cache(&indices, DEFAULT_CACHE_SIZE);
+    assert_eq!(sorted_triangles(&indices), sorted_triangles(&mesh.indices));
+    assert!(before.acmr > 2.0, "{before:?}");
+    assert!(after.acmr < 1.0, "{after:?}");
+}
+
+/// Verifies disconnected triangles are all emitted.
+#[test]
+fn optimize_handles_disconnected_triangles() {
+    let indices: Vec<u32> = (0..300).collect();
+    let optimized = optimize_vertex_cache(&indices, 300);
+    assert_eq!(sorted_triangles(&optimized), sorted_triangles(&indices));
+}
+
+/// Verifies vertices are reordered by first use and unreferenced ones
+/// dropped.
+#[test]
+fn vertex_fetch_follows_index_order() {
+    #[allow(clippy::cast_precision_loss)]
+    let vertex = |x: usize| MeshVertex {
+        position: [x as f32, 0.0, 0.0],
+        ..MeshVertex::default()
+    };
+    let mesh = Mesh {
+        vertices: (0..5).map(vertex).collect(),
+        indices: vec![3, 1, 4, 4, 1, 0],
+    };
+
+    let fetched = optimize_vertex_fetch(&mesh);
+    assert_eq!(fetched.indices, [0, 1, 2, 2, 1, 3]);
+    assert_eq!(
+        fetched.vertices,
+        [vertex(3), vertex(1), vertex(4), vertex(0)]
+    );
+}
@@ -0,0 +1,188 @@
+//! The mesh cook step and its report.
+
+use super::cache::{
+    analyze_vertex_cache, optimize_vertex_cache, optimize_vertex_fetch, VertexCacheStats,
+    DEFAULT_CACHE_SIZE,
+};
+use super::meshlet::{build_meshlets, Meshlet, MeshletLimits};
+use super::overdraw::optimize_overdraw;
+use crate::lod::{LodGeneration, LodLevel};
+use crate::mesh::Mesh;
+use std::fmt;
+
+/// What the cook does to each mesh.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeshCookSettings {
+    /// Reorders triangles for the post-transform vertex cache.
+    pub vertex_cache: bool,
+    /// Reorders triangle clusters to draw occluding surfaces first, after
+    /// the vertex cache pass.
+    pub overdraw: bool,
+    /// Generates detail levels below the source mesh, `None` to keep only
+    /// the source.
+    pub lods: Option<LodGeneration>,
+    /// Splits every level into meshlets for mesh-shader paths, `None` to
+    /// skip them.
+    pub meshlets: Option<MeshletLimits>,
+}
+
+impl Default for MeshCookSettings {
+    fn default() -> Self {
+        Self {
+            vertex_cache: true,
+            overdraw: true,
+            lods: Some(LodGeneration::default()),
+            meshlets: None,
+        }
+    }
+}
+
+/// Size and vertex cache efficiency of a mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MeshStats {
+    /// Vertices.
+    pub vertices: u32,
+    /// Triangles.
+    pub triangles: u32,
+    /// Efficiency in a [`DEFAULT_CACHE_SIZE`]-entry FIFO cache.
+    pub cache: VertexCacheStats,
+}
+
+impl MeshStats {
+    /// Measures `mesh`.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn of(mesh: &Mesh) -> Self {
+        Self {
+            vertices: mesh.vertices.len() as u32,
+            triangles: mesh.triangles() as u32,
+            cache: analyze_vertex_cache(&mesh.indices, DEFAULT_CACHE_SIZE),
+        }
+    }
+}
+
+/// A cooked detail level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CookedLod {
+    /// The optimized mesh and the screen size it is used from.
+    pub level: LodLevel<Mesh>,
+    /// Meshlets of the level, empty unless enabled.
+    pub meshlets: Vec<Meshlet>,
+}
+
+/// What the cook did to one mesh, for the pack report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshCookReport {
+    /// Asset name.
+    pub name: String,
+    /// The source mesh.
+    pub before: MeshStats,
+    /// Every cooked level, the full-detail one first.
+    pub levels: Vec<MeshStats>,
+    /// Meshlets across all levels.
+    pub meshlets: usize,
+}
+
+impl fmt::Display for MeshCookReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let after = self.levels.first().copied().unwrap_or_default();
+        let triangles: Vec<String> = self
+            .levels
+            .iter()
+            .map(|level| level.triangles.to_string())
+            .collect();
+        write!(
+            f,
+            "{}: {} vertices, ACMR {:.2} -> {:.2}, ATVR {:.2} -> {:.2}, LOD triangles {}",
+            self.name,
+            after.vertices,
+            self.before.cache.acmr,
+            after.cache.acmr,
+            self.before.cache.atvr,
+            after.cache.atvr,
+            triangles.join("/")
+        )?;
+        if self.meshlets > 0 {
+            write!(f, ", {} meshlets", self.meshlets)?;
+        }
+        Ok(())
+    }
+}
+
+/// A mesh ready to pack: its optimized levels and meshlets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CookedMesh {
+    /// Levels, full detail first, with their [`Lod`](crate::Lod)
+    /// thresholds.
+    pub lods: Vec<CookedLod>,
+    /// Before and after statistics.
+    pub report: MeshCookReport,
+}
+
+/// Cooks the mesh asset `name` with `settings`.
+///
+/// Levels are generated from the source mesh first, then each is optimized
+/// on its own: triangles for the vertex cache and overdraw, and vertices
+/// for fetch order, before meshlets are built from the final order.
+///
+/// # Example
+///
+/// ```
+/// use render::cook::{cook_mesh, MeshCookSettings};
+/// use render::mesh::{Mesh, MeshVertex};
+///
+/// let quad = Mesh {
+///     vertices: vec![MeshVertex::default(); 4],
+///     indices: vec![0, 1, 2, 2, 1, 3],
+/// };
+/// let cooked = cook_mesh("quad", &quad, &MeshCookSettings { lods: None, ..MeshCookSettings::default() });
+///
+/// assert_eq!(cooked.lods.len(), 1);
+/// assert_eq!(cooked.report.levels[0].triangles, 2);
+/// assert!(cooked.report.to_string().starts_with("quad: 4 vertices"));
+/// ```
+#[must_use]
+pub fn cook_mesh(name: &str, mesh: &Mesh, settings: &MeshCookSettings) -> CookedMesh {
+    let levels = match settings.lods {
+        Some(generation) => generation.generate(mesh),
+        None => vec![LodLevel::new(mesh.clone(), 0.0)],
+    };
+    let lods: Vec<CookedLod> = levels
+        .into_iter()
+        .map(|level| {
+            let mesh = optimize(level.mesh, settings);
+            let meshlets = settings
+                .meshlets
+                .map(|limits| build_meshlets(&mesh, limits))
+                .unwrap_or_default();
+            CookedLod {
+                level: LodLevel::new(mesh, level.min_screen_size),
+                meshlets,
+            }
+        })
+        .collect();
+    let report = MeshCookReport {
+        name: name.to_owned(),
+        before: MeshStats::of(mesh),
+        levels: lods
+            .iter()
+            .map(|lod| MeshStats::of(&lod.level.mesh))
+            .collect(),
+        meshlets: lods.iter().map(|lod| lod.meshlets.len()).sum(),
+    };
+    CookedMesh { lods, report }
+}
+
+/// Runs the enabled optimization passes over one level.
+fn optimize(mut mesh: Mesh, settings: &MeshCookSettings) -> Mesh {
+    if settings.vertex_cache {
+        mesh.indices = optimize_vertex_cache(&mesh.indices, mesh.vertices.len());
+    }
+    if settings.overdraw {
+        mesh.indices = optimize_overdraw(&mesh);
+    }
+    if settings.vertex_cache || settings.overdraw {
+        mesh = optimize_vertex_fetch(&mesh);
+    }
+    mesh
+}
@@ -0,0 +1,145 @@
+//! Unit tests for the mesh cook step.
+
+use super::mesh::{cook_mesh, MeshCookSettings};
+use super::meshlet::MeshletLimits;
+use super::overdraw::optimize_overdraw;
+use crate::lod::LodGeneration;
+use crate::mesh::{Mesh, MeshVertex};
+
+/// Flat grid of `n` x `n` quads in the XZ plane.
+pub(super) fn grid(n: u16) -> Mesh {
+    let mut mesh = Mesh::default();
+    for z in 0..=n {
+        for x in 0..=n {
+            mesh.vertices.push(MeshVertex {
+                position: [f32::from(x), 0.0, f32::from(z)],
+                normal: [0.0, 1.0, 0.0],
+                uv: [0.0, 0.0],
+            });
+        }
+    }
+    for z in 0..n {
+        for x in 0..n {
+            let (i, n) = (u32::from(z * (n + 1) + x), u32::from(n));
+            mesh.indices
+                .extend([i, i + n + 1, i + 1, i + 1, i + n + 1, i + n + 2]);
+        }
+    }
+    mesh
+}
+
+/// Returns `mesh` with its triangles in a scattered, deterministic order.
+pub(super) fn shuffled(mesh: &Mesh) -> Mesh {
+    let triangles: Vec<&[u32]> = mesh.indices.chunks_exact(3).collect();
+    let count = triangles.len();
+    Mesh {
+        vertices: mesh.vertices.clone(),
+        indices: (0..count)
+            .flat_map(|i| triangles[i * 7919 % count].iter().copied())
+            .collect(),
+    }
+}
+
+/// Returns the triangles of `indices`, each rotated to start at its
+/// smallest index so winding is kept, in sorted order.
+pub(super) fn sorted_triangles(indices: &[u32]) -> Vec<[u32; 3]> {
+    let mut triangles: Vec<[u32; 3]> = indices
+        .chunks_exact(3)
+        .map(|t| {
+            let start = (0..3).min_by_key(|&corner| t[corner]).unwrap_or(0);
+            [t[start], t[(start + 1) % 3], t[(start + 2) % 3]]
+        })
+        .collect();
+    triangles.sort_unstable();
+    triangles
+}
+
+/// Verifies cooking generates the LOD chain, optimizes every level, and
+/// reports the improvement.
+#[test]
+fn cook_optimizes_every_level() {
+    let mesh = shuffled(&grid(16));
+    let settings = MeshCookSettings {
+        lods: Some(LodGeneration {
+            levels: 2,
+            resolution: 8,
+        }),
+        meshlets: Some(MeshletLimits::default()),
+        ..MeshCookSettings::default()
+    };
+    let cooked = cook_mesh("floor", &mesh, &settings);
+
+    let thresholds: Vec<_> = cooked
+        .lods
+        .iter()
+        .map(|lod| lod.level.min_screen_size)
+        .collect();
+    assert_eq!(thresholds, [0.5, 0.25, 0.0]);
+    assert_eq!(
+        sorted_triangles(&cooked.lods[0].level.mesh.indices).len(),
+        mesh.triangles()
+    );
+
+    let report = &cooked.report;
+    assert_eq!(report.before.triangles, 512);
+    assert_eq!(report.levels.len(), 3);
+    assert!(report.levels[0].cache.acmr < report.before.cache.acmr);
+    assert!(report.levels[1].triangles < report.levels[0].triangles);
+    assert!(report.meshlets >= 512 / 124 + 2);
+    assert!(cooked.lods.iter().all(|lod| !lod.meshlets.is_empty()));
+    let line = report.to_string();
+    assert!(
+        line.starts_with("floor: 289 vertices, ACMR ") && line.contains("LOD triangles 512/"),
+        "{line}"
+    );
+    assert!(
+        line.ends_with(&format!("{} meshlets", report.meshlets)),
+        "{line}"
+    );
+}
+
+/// Verifies disabled passes leave the mesh untouched.
+#[test]
+fn cook_without_passes_keeps_mesh() {
+    let mesh = shuffled(&grid(4));
+    let cooked = cook_mesh(
+        "floor",
+        &mesh,
+        &MeshCookSettings {
+            vertex_cache: false,
+            overdraw: false,
+            lods: None,
+            meshlets: None,
+        },
+    );
+    assert_eq!(cooked.lods.len(), 1);
+    assert_eq!(cooked.lods[0].level.mesh, mesh);
+    assert_eq!(cooked.report.levels[0], cooked.report.before);
+    assert!(!cooked.report.to_string().contains("meshlets"));
+}
+
+/// Verifies clusters facing out of the mesh are drawn before those facing
+/// into it.
+#[test]
+fn overdraw_draws_outer_surfaces_first() {
+    let quad = |z: f32| {
+        [[-1.0, -1.0], [1.0, -1.0], [-1.0, 1.0], [1.0, 1.0]].map(|[x, y]| MeshVertex {
+            position: [x, y, z],
+            ..MeshVertex::default()
+        })
+    };
+    // Both quads face +z: the one at z = 1 faces out, the one at z = -1
+    // faces the mesh's center.
+    let mut vertices = quad(-1.0).to_vec();
+    vertices.extend(quad(1.0));
+    let mesh = Mesh {
+        vertices,
+        indices: vec![0, 1, 2, 2, 1, 3, 4, 5, 6, 6, 5, 7],
+    };
+
+    assert_eq!(
+        optimize_overdraw(&mesh),
+        [4, 5, 6, 6, 5, 7, 0, 1, 2, 2, 1, 3]
+    );
+    assert!(optimize_overdraw(&Mesh::default()).is_empty());
+}
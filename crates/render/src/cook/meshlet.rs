@@ -0,0 +1,112 @@
+//! Meshlet building for mesh-shader rendering.
+
+use crate::mesh::{Aabb, Mesh};
+use math::Vec3;
+
+/// Most vertices per meshlet, the limit common mesh-shader hardware favors.
+pub const DEFAULT_MESHLET_VERTICES: usize = 64;
+
+/// Most triangles per meshlet, leaving room in 128-entry primitive arrays.
+pub const DEFAULT_MESHLET_TRIANGLES: usize = 124;
+
+/// Size limits of the meshlets [`build_meshlets`] produces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MeshletLimits {
+    /// Most unique vertices per meshlet, at most 256.
+    pub max_vertices: usize,
+    /// Most triangles per meshlet.
+    pub max_triangles: usize,
+}
+
+impl Default for MeshletLimits {
+    fn default() -> Self {
+        Self {
+            max_vertices: DEFAULT_MESHLET_VERTICES,
+            max_triangles: DEFAULT_MESHLET_TRIANGLES,
+        }
+    }
+}
+
+/// A small group of triangles processed by one mesh-shader workgroup.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Meshlet {
+    /// Mesh vertices used, indexed by [`triangles`](Self::triangles).
+    pub vertices: Vec<u32>,
+    /// Triangles as indices into [`vertices`](Self::vertices).
+    pub triangles: Vec<[u8; 3]>,
+    /// Bounds of the vertices, for per-meshlet culling.
+    pub bounds: Aabb,
+}
+
+/// Splits the triangles of `mesh`, in index order, into meshlets within
+/// `limits`.
+///
+/// Triangles are added to the current meshlet until one would exceed a
+/// limit, so a cache-optimized index buffer yields meshlets of connected,
+/// vertex-sharing triangles.
+///
+/// # Panics
+///
+/// Panics if `limits` allows no triangle or more than 256 vertices.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn build_meshlets(mesh: &Mesh, limits: MeshletLimits) -> Vec<Meshlet> {
+    assert!(
+        limits.max_triangles > 0 && (3..=256).contains(&limits.max_vertices),
+        "meshlet limits {limits:?} cannot hold a triangle"
+    );
+    let mut meshlets = Vec::new();
+    let mut current = empty();
+    for triangle in mesh.indices.chunks_exact(3) {
+        let new_vertices = triangle
+            .iter()
+            .enumerate()
+            .filter(|&(corner, index)| {
+                !current.vertices.contains(index) && !triangle[..corner].contains(index)
+            })
+            .count();
+        if current.vertices.len() + new_vertices > limits.max_vertices
+            || current.triangles.len() == limits.max_triangles
+        {
+            meshlets.push(finish(mesh, std::mem::replace(&mut current, empty())));
+        }
+        let local = [0, 1, 2].map(|corner| {
+            let index = triangle[corner];
+            let slot = current
+                .vertices
+                .iter()
+                .position(|&vertex| vertex == index)
+                .unwrap_or_else(|| {
+                    current.vertices.push(index);
+                    current.vertices.len() - 1
+                });
+            slot as u8
+        });
+        current.triangles.push(local);
+    }
+    if !current.triangles.is_empty() {
+        meshlets.push(finish(mesh, current));
+    }
+    meshlets
+}
+
+/// Returns a meshlet without triangles.
+fn empty() -> Meshlet {
+    Meshlet {
+        vertices: Vec::new(),
+        triangles: Vec::new(),
+        bounds: Aabb::default(),
+    }
+}
+
+/// Computes the bounds of a filled meshlet.
+fn finish(mesh: &Mesh, mut meshlet: Meshlet) -> Meshlet {
+    meshlet.bounds = Aabb::from_points(
+        meshlet
+            .vertices
+            .iter()
+            .map(|&index| Vec3::from(mesh.vertices[index as usize].position)),
+    )
+    .unwrap_or_default();
+    meshlet
+}
@@ -0,0 +1,54 @@
+//! Unit tests for meshlet building.
+
+use super::mesh_test::grid;
+use super::meshlet::{build_meshlets, MeshletLimits};
+use math::Vec3;
+
+/// Verifies meshlets respect their limits and together hold every
+/// triangle in order.
+#[test]
+fn meshlets_cover_mesh_within_limits() {
+    let mesh = grid(16);
+    let limits = MeshletLimits {
+        max_vertices: 32,
+        max_triangles: 40,
+    };
+    let meshlets = build_meshlets(&mesh, limits);
+
+    assert!(meshlets.len() >= mesh.triangles() / 40);
+    let mut indices = Vec::new();
+    for meshlet in &meshlets {
+        assert!(meshlet.vertices.len() <= 32 && meshlet.triangles.len() <= 40);
+        for triangle in &meshlet.triangles {
+            indices.extend(triangle.map(|local| meshlet.vertices[usize::from(local)]));
+        }
+        for &vertex in &meshlet.vertices {
+            let position = Vec3::from(mesh.vertices[vertex as usize].position);
+            assert!(meshlet.bounds.contains(position));
+        }
+    }
+    assert_eq!(indices, mesh.indices);
+}
+
+/// Verifies the default limits fill meshlets up to the triangle limit on
+/// a well-connected mesh.
+#[test]
+fn default_limits() {
+    let meshlets = build_meshlets(&grid(4), MeshletLimits::default());
+    assert_eq!(meshlets.len(), 1);
+    assert_eq!(meshlets[0].triangles.len(), 32);
+    assert_eq!(meshlets[0].vertices.len(), 25);
+}
+
+/// Verifies limits that cannot hold a triangle are rejected.
+#[test]
+#[should_panic(expected = "cannot hold a triangle")]
+fn rejects_empty_limits() {
+    let _ = build_meshlets(
+        &grid(1),
+        MeshletLimits {
+            max_vertices: 2,
+            max_triangles: 1,
+        },
+    );
+}
@@ -0,0 +1,33 @@
+//! Cook-time mesh processing.
+//!
+//! The asset cook runs every mesh through [`cook_mesh`] before packing it.
+//! Following the passes of meshoptimizer, implemented natively here, it
+//! generates the [`Lod`](crate::Lod) chain, reorders each level's triangles
+//! for the post-transform vertex cache ([`optimize_vertex_cache`]) and then
+//! to draw occluding surfaces first ([`optimize_overdraw`]), reorders
+//! vertices for fetch locality ([`optimize_vertex_fetch`]), and optionally
+//! splits levels into [`Meshlet`]s for future mesh-shader paths.
+//!
+//! A [`MeshCookReport`] per mesh records the vertex cache efficiency before
+//! and after and the triangles of every level, for the pack report.
+
+mod cache;
+#[cfg(test)]
+mod cache_test;
+mod mesh;
+#[cfg(test)]
+mod mesh_test;
+mod meshlet;
+#[cfg(test)]
+mod meshlet_test;
+mod overdraw;
+
+pub use cache::{
+    analyze_vertex_cache, optimize_vertex_cache, optimize_vertex_fetch, VertexCacheStats,
+    DEFAULT_CACHE_SIZE,
+};
+pub use mesh::{cook_mesh, CookedLod, CookedMesh, MeshCookReport, MeshCookSettings, MeshStats};
+pub use meshlet::{
+    build_meshlets, Meshlet, MeshletLimits, DEFAULT_MESHLET_TRIANGLES, DEFAULT_MESHLET_VERTICES,
+};
+pub use overdraw::optimize_overdraw;
@@ -0,0 +1,86 @@
+//! Overdraw optimization by cluster ordering.
+
+use crate::mesh::Mesh;
+use math::Vec3;
+
+/// Entries of the FIFO cache used to find cluster boundaries.
+const CLUSTER_CACHE_SIZE: usize = 16;
+
+/// Reorders the triangles of `mesh` so surfaces likely to occlude the rest
+/// of the mesh are drawn first, reducing overdraw from any view.
+///
+/// The index buffer is expected to be cache-optimized already. It is cut
+/// into clusters where a triangle reuses none of the cached vertices, so
+/// the cache efficiency within each cluster is kept, and the clusters are
+/// sorted by how far their surface faces out of the mesh: the dot product
+/// of their area-weighted normal with the direction from the mesh centroid
+/// to the cluster centroid, outermost first.
+#[must_use]
+pub fn optimize_overdraw(mesh: &Mesh) -> Vec<u32> {
+    let triangles: Vec<&[u32]> = mesh.indices.chunks_exact(3).collect();
+    let position = |index: u32| Vec3::from(mesh.vertices[index as usize].position);
+
+    let mut clusters: Vec<std::ops::Range<usize>> = Vec::new();
+    let mut cache = std::collections::VecDeque::with_capacity(CLUSTER_CACHE_SIZE + 1);
+    let mut start = 0;
+    for (triangle, corners) in triangles.iter().enumerate() {
+        let misses = corners
+            .iter()
+            .filter(|index| !cache.contains(*index))
+            .count();
+        if misses == 3 && triangle > start {
+            clusters.push(start..triangle);
+            start = triangle;
+        }
+        for &index in *corners {
+            if !cache.contains(&index) {
+                cache.push_back(index);
+                if cache.len() > CLUSTER_CACHE_SIZE {
+                    cache.pop_front();
+                }
+            }
+        }
+    }
+    if start < triangles.len() {
+        clusters.push(start..triangles.len());
+    }
+
+    let centroid = |range: &std::ops::Range<usize>| -> (Vec3, Vec3, f32) {
+        let mut center = Vec3::ZERO;
+        let mut normal = Vec3::ZERO;
+        let mut area = 0.0;
+        for corners in &triangles[range.clone()] {
+            let [a, b, c] = [corners[0], corners[1], corners[2]].map(position);
+            let cross = (b - a).cross(c - a);
+            let weight = cross.length() * 0.5;
+            center += (a + b + c) / 3.0 * weight;
+            normal += cross;
+            area += weight;
+        }
+        (center, normal, area)
+    };
+    let measured: Vec<_> = clusters.iter().map(centroid).collect();
+    let total_area: f32 = measured.iter().map(|(_, _, area)| area).sum();
+    if total_area <= 0.0 {
+        return mesh.indices.clone();
+    }
+    let mesh_center = measured.iter().map(|(center, _, _)| *center).sum::<Vec3>() / total_area;
+
+    let mut order: Vec<(usize, f32)> = measured
+        .iter()
+        .enumerate()
+        .map(|(cluster, (center, normal, area))| {
+            let key = if *area > 0.0 {
+                (*center / *area - mesh_center).dot(normal.normalize_or_zero())
+            } else {
+                f32::NEG_INFINITY
+            };
+            (cluster, key)
+        })
+        .collect();
+    order.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    order
+        .into_iter()
+        .flat_map(|(cluster, _)| triangles[clusters[cluster].clone()].concat())
+        .collect()
+}
@@ -0,0 +1,270 @@
+//! The decal component and how it blends onto surfaces.
+
+use crate::layers::RenderLayers;
+use crate::material::Material;
+use crate::transform::Transform;
+use math::{Aabb, Color, Vec2, Vec3, Vec4};
+use rustgine_core::Label;
+
+/// How a decal changes the normals of the surfaces it covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NormalBlend {
+    /// Keeps the surface normals, e.g. for painted road markings.
+    Keep,
+    /// Adds the decal's bumps on top of the surface's, e.g. for bullet
+    /// holes in a brick wall.
+    #[default]
+    Blend,
+    /// Replaces the surface normals, e.g. for a metal plate.
+    Replace,
+}
+
+impl NormalBlend {
+    /// Returns the mode as the `normal_blend` material float.
+    fn shader_value(self) -> f32 {
+        match self {
+            Self::Keep => 0.0,
+            Self::Blend => 1.0,
+            Self::Replace => 2.0,
+        }
+    }
+}
+
+/// Which surface properties a decal overwrites.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecalBlend {
+    /// Whether the decal's color covers the surface's albedo.
+    pub albedo: bool,
+    /// How the decal's normal map combines with the surface's normals.
+    pub normal: NormalBlend,
+    /// Roughness the decal moves the surface towards, e.g. 0.1 for wet
+    /// blood; `None` keeps the surface's roughness.
+    pub roughness: Option<f32>,
+}
+
+impl Default for DecalBlend {
+    fn default() -> Self {
+        Self {
+            albedo: true,
+            normal: NormalBlend::Blend,
+            roughness: None,
+        }
+    }
+}
+
+/// Surface properties at a pixel, before or after decals.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecalSurface {
+    /// Linear albedo.
+    pub albedo: Vec3,
+    /// World-space unit normal.
+    pub normal: Vec3,
+    /// Perceptual roughness in `0..=1`.
+    pub roughness: f32,
+}
+
+/// Where a surface point falls in a decal's projection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecalHit {
+    /// Texture coordinates, `(0, 0)` at the top-left of the decal.
+    pub uv: Vec2,
+    /// Coverage in `0..=1`, from the opacity and the angle fade.
+    pub weight: f32,
+}
+
+/// A texture projected onto the surfaces inside a box, e.g. a bullet hole,
+/// a blood splat, or a road marking.
+///
+/// The box is [`size`](Self::size) large, centered on the entity's
+/// [`Transform`], and projects along the transform's forward (`-Z`) axis:
+/// the texture's right is the box's `+X` and its up the box's `+Y`.
+/// Surfaces facing away from the projection are not covered, and those
+/// turned by more than [`max_angle`](Self::max_angle) fade out, so the
+/// decal does not smear down the sides of objects.
+///
+/// # Example
+///
+/// ```
+/// use math::Vec3;
+/// use render::decal::Decal;
+/// use render::Transform;
+///
+/// // A 1 m road marking, projected straight down onto the ground.
+/// let marking = Decal::new("decals/arrow.png", Vec3::new(1.0, 1.0, 0.5));
+/// let transform = Transform::from_euler(Vec3::ZERO, 0.0, -std::f32::consts::FRAC_PI_2, 0.0);
+///
+/// let hit = marking.project(&transform, Vec3::new(0.25, 0.0, 0.0), Vec3::Y).unwrap();
+/// assert!((hit.uv.x - 0.75).abs() < 1e-5);
+/// assert_eq!(hit.weight, 1.0);
+/// assert!(marking.project(&transform, Vec3::new(2.0, 0.0, 0.0), Vec3::Y).is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decal {
+    /// Albedo texture asset path; its alpha is the decal's coverage.
+    pub albedo: Label,
+    /// Tangent-space normal map asset path.
+    pub normal: Option<Label>,
+    /// Tint multiplied with the albedo texture.
+    pub color: Color,
+    /// Coverage multiplier, faded out by pools as decals expire.
+    pub opacity: f32,
+    /// Width, height, and projection depth of the box, in world units.
+    pub size: Vec3,
+    /// Which surface properties the decal overwrites.
+    pub blend: DecalBlend,
+    /// Surfaces on these layers receive the decal.
+    pub layers: RenderLayers,
+    /// Decals with higher orders draw over lower ones.
+    pub order: i32,
+    /// Angle in degrees between a surface and the projection beyond which
+    /// the decal has faded out.
+    pub max_angle: f32,
+}
+
+impl Decal {
+    /// Creates a white, fully opaque decal of `albedo` in a box of `size`,
+    /// on every layer.
+    #[must_use]
+    pub fn new(albedo: impl Into<Label>, size: Vec3) -> Self {
+        Self {
+            albedo: albedo.into(),
+            normal: None,
+            color: Color::WHITE,
+            opacity: 1.0,
+            size,
+            blend: DecalBlend::default(),
+            layers: RenderLayers::ALL,
+            order: 0,
+            max_angle: 80.0,
+        }
+    }
+
+    /// Sets the normal map.
+    #[must_use]
+    pub fn with_normal(mut self, normal: impl Into<Label>) -> Self {
+        self.normal = Some(normal.into());
+        self
+    }
+
+    /// Sets which surface properties the decal overwrites.
+    #[must_use]
+    pub fn with_blend(mut self, blend: DecalBlend) -> Self {
+        self.blend = blend;
+        self
+    }
+
+    /// Sets the draw order among overlapping decals.
+    #[must_use]
+    pub fn with_order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Returns the world-space corners of the projection box.
+    #[must_use]
+    pub fn corners(&self, transform: &Transform) -> [Vec3; 8] {
+        let local = Aabb::from_center_half_extents(Vec3::ZERO, self.size * 0.5);
+        local
+            .corners()
+            .map(|corner| transform.translation + transform.rotate(corner))
+    }
+
+    /// Returns the world-space bounds of the projection box.
+    #[must_use]
+    pub fn bounds(&self, transform: &Transform) -> Aabb {
+        let local = Aabb::from_center_half_extents(Vec3::ZERO, self.size * 0.5);
+        local.transformed(&transform.to_matrix())
+    }
+
+    /// Projects the surface point `position` with unit normal `normal`
+    /// into the decal, or returns `None` if the decal does not cover it.
+    ///
+    /// This is what the decal shaders compute per pixel.
+    #[must_use]
+    pub fn project(&self, transform: &Transform, position: Vec3, normal: Vec3) -> Option<DecalHit> {
+        let local = transform.rotation.inverse() * (position - transform.translation);
+        let unit = local / self.size;
+        if unit.abs().max_element() > 0.5 || !unit.is_finite() {
+            return None;
+        }
+        // Surfaces facing the projector face the box's +Z.
+        let facing = normal.dot(transform.rotate(Vec3::Z));
+        let limit = self.max_angle.clamp(0.0, 90.0).to_radians().cos();
+        if facing <= limit || facing <= 0.0 {
+            return None;
+        }
+        // Fade over the last tenth of the cosine range before the limit.
+        let fade = ((facing - limit) / ((1.0 - limit) * 0.1).max(1e-4)).min(1.0);
+        let weight = self.opacity.clamp(0.0, 1.0) * fade;
+        Some(DecalHit {
+            uv: Vec2::new(unit.x + 0.5, 0.5 - unit.y),
+            weight,
+        })
+    }
+
+    /// Blends the decal's texels onto `surface` at a hit of `weight`:
+    /// `albedo` is the albedo texture's linear RGBA and `normal` the
+    /// tangent-space normal map sample, `+Z` being flat.
+    #[must_use]
+    pub fn blend(
+        &self,
+        transform: &Transform,
+        surface: DecalSurface,
+        albedo: Vec4,
+        normal: Vec3,
+        weight: f32,
+    ) -> DecalSurface {
+        let alpha = (albedo.w * self.color.alpha() * weight).clamp(0.0, 1.0);
+        let mut result = surface;
+        if self.blend.albedo {
+            let tint = self.color.to_linear().rgb_vec();
+            result.albedo = surface.albedo.lerp(albedo.truncate() * tint, alpha);
+        }
+        let projector = transform.rotate(Vec3::Z);
+        let decal_normal = (transform.rotate(Vec3::X) * normal.x
+            + transform.rotate(Vec3::Y) * normal.y
+            + projector * normal.z)
+            .normalize_or(projector);
+        result.normal = match self.blend.normal {
+            NormalBlend::Keep => surface.normal,
+            NormalBlend::Blend => {
+                (surface.normal + (decal_normal - projector) * alpha).normalize_or(surface.normal)
+            }
+            NormalBlend::Replace => surface
+                .normal
+                .lerp(decal_normal, alpha)
+                .normalize_or(surface.normal),
+        };
+        if let Some(roughness) = self.blend.roughness {
+            result.roughness = surface.roughness + (roughness - surface.roughness) * alpha;
+        }
+        result
+    }
+
+    /// Builds a material from `shader` for drawing the decal: the `albedo`
+    /// and optional `normal` textures, `color`, `opacity`, `normal_blend`
+    /// (0 keep, 1 blend, 2 replace), `albedo_blend` (0 or 1), `roughness`
+    /// and `roughness_blend` (0 or 1), and `max_angle_cos`.
+    #[must_use]
+    pub fn material(&self, shader: &str) -> Material {
+        let mut material = Material::new(shader)
+            .with_texture("albedo", self.albedo)
+            .with_color("color", self.color)
+            .with_float("opacity", self.opacity)
+            .with_float("albedo_blend", f32::from(u8::from(self.blend.albedo)))
+            .with_float("normal_blend", self.blend.normal.shader_value())
+            .with_float(
+                "roughness_blend",
+                f32::from(u8::from(self.blend.roughness.is_some())),
+            )
+            .with_float("roughness", self.blend.roughness.unwrap_or(0.0))
+            .with_float(
+                "max_angle_cos",
+                self.max_angle.clamp(0.0, 90.0).to_radians().cos(),
+            );
+        if let Some(normal) = self.normal {
+            material = material.with_texture("normal", normal);
+        }
+        material
+    }
+}
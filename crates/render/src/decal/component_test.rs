@@ -0,0 +1,199 @@
+//! Unit tests for the decal component.
+
+use super::component::{Decal, DecalBlend, DecalSurface, NormalBlend};
+use crate::{MaterialParam, Transform};
+use math::{Color, Vec2, Vec3, Vec4};
+use rustgine_core::Label;
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+}
+
+fn assert_vec_close(actual: Vec3, expected: Vec3) {
+    assert!(actual.abs_diff_eq(expected, 1e-4), "{actual} != {expected}");
+}
+
+fn wall() -> DecalSurface {
+    DecalSurface {
+        albedo: Vec3::splat(0.5),
+        normal: Vec3::Z,
+        roughness: 0.8,
+    }
+}
+
+/// Verifies points inside the box map to texture coordinates from the
+/// top-left, and points outside it are not covered.
+#[test]
+fn projects_points_inside_the_box() {
+    let decal = Decal::new("decals/hole.png", Vec3::new(2.0, 1.0, 1.0));
+    let transform = Transform::from_translation(Vec3::new(0.0, 1.0, 0.0));
+
+    let hit = decal
+        .project(&transform, Vec3::new(-1.0, 1.5, 0.0), Vec3::Z)
+        .unwrap();
+    assert_close(hit.uv.x, 0.0);
+    assert_close(hit.uv.y, 0.0);
+    assert_close(hit.weight, 1.0);
+    let center = decal.project(&transform, Vec3::Y, Vec3::Z).unwrap();
+    assert!(center.uv.abs_diff_eq(Vec2::splat(0.5), 1e-5));
+
+    assert!(decal
+        .project(&transform, Vec3::new(0.0, 1.0, 0.6), Vec3::Z)
+        .is_none());
+    assert!(decal
+        .project(&transform, Vec3::new(1.1, 1.0, 0.0), Vec3::Z)
+        .is_none());
+}
+
+/// Verifies surfaces facing away are skipped and steep ones fade out.
+#[test]
+fn fades_with_surface_angle() {
+    let decal = Decal {
+        opacity: 0.5,
+        ..Decal::new("decals/hole.png", Vec3::ONE)
+    };
+    let transform = Transform::default();
+    assert!(decal.project(&transform, Vec3::ZERO, Vec3::NEG_Z).is_none());
+    assert!(decal.project(&transform, Vec3::ZERO, Vec3::X).is_none());
+
+    let limit = 80f32.to_radians();
+    let edge = Vec3::new(limit.sin(), 0.0, limit.cos());
+    assert!(decal.project(&transform, Vec3::ZERO, edge).is_none());
+    let near_edge = (limit - 0.005).sin_cos();
+    let steep = decal
+        .project(
+            &transform,
+            Vec3::ZERO,
+            Vec3::new(near_edge.0, 0.0, near_edge.1),
+        )
+        .unwrap();
+    assert!(steep.weight > 0.0 && steep.weight < 0.5, "{}", steep.weight);
+    let flat = decal.project(&transform, Vec3::ZERO, Vec3::Z).unwrap();
+    assert_close(flat.weight, 0.5);
+}
+
+/// Verifies the box rotates and translates with the transform.
+#[test]
+fn bounds_follow_the_transform() {
+    let decal = Decal::new("decals/hole.png", Vec3::new(2.0, 1.0, 0.5));
+    let transform = Transform::from_euler(
+        Vec3::new(5.0, 0.0, 0.0),
+        std::f32::consts::FRAC_PI_2,
+        0.0,
+        0.0,
+    );
+    let bounds = decal.bounds(&transform);
+    assert_vec_close(bounds.min, Vec3::new(4.75, -0.5, -1.0));
+    assert_vec_close(bounds.max, Vec3::new(5.25, 0.5, 1.0));
+    for corner in decal.corners(&transform) {
+        assert!(bounds.min.cmple(corner + 1e-4).all() && bounds.max.cmpge(corner - 1e-4).all());
+    }
+}
+
+/// Verifies albedo and roughness blend by coverage, and only when enabled.
+#[test]
+fn blends_albedo_and_roughness() {
+    let decal = Decal {
+        color: Color::linear_rgb(1.0, 0.0, 0.0),
+        ..Decal::new("decals/blood.png", Vec3::ONE).with_blend(DecalBlend {
+            albedo: true,
+            normal: NormalBlend::Keep,
+            roughness: Some(0.1),
+        })
+    };
+    let result = decal.blend(&Transform::default(), wall(), Vec4::ONE, Vec3::Z, 0.5);
+    assert_vec_close(result.albedo, Vec3::new(0.75, 0.25, 0.25));
+    assert_close(result.roughness, 0.45);
+    assert_eq!(result.normal, Vec3::Z);
+
+    let clear = decal.blend(
+        &Transform::default(),
+        wall(),
+        Vec4::new(1.0, 1.0, 1.0, 0.0),
+        Vec3::Z,
+        1.0,
+    );
+    assert_eq!(clear, wall());
+
+    let gloss = Decal::new("decals/wet.png", Vec3::ONE).with_blend(DecalBlend {
+        albedo: false,
+        normal: NormalBlend::Keep,
+        roughness: Some(0.0),
+    });
+    let wet = gloss.blend(&Transform::default(), wall(), Vec4::ONE, Vec3::Z, 1.0);
+    assert_eq!(wet.albedo, wall().albedo);
+    assert_close(wet.roughness, 0.0);
+}
+
+/// Verifies each normal mode combines the decal's bumps with the surface.
+#[test]
+fn blends_normals() {
+    let bump = Vec3::new(0.6, 0.0, 0.8);
+    let tilted = DecalSurface {
+        normal: Vec3::new(0.0, 0.6, 0.8),
+        ..wall()
+    };
+    let with = |normal| {
+        Decal::new("decals/hole.png", Vec3::ONE)
+            .with_blend(DecalBlend {
+                albedo: false,
+                normal,
+                roughness: None,
+            })
+            .blend(&Transform::default(), tilted, Vec4::ONE, bump, 1.0)
+    };
+    assert_eq!(with(NormalBlend::Keep).normal, tilted.normal);
+    assert_vec_close(with(NormalBlend::Replace).normal, bump);
+    let blended = with(NormalBlend::Blend).normal;
+    assert_close(blended.length(), 1.0);
+    assert!(blended.x > 0.0 && blended.y > 0.0, "{blended}");
+
+    // A flat normal map leaves the surface untouched.
+    let flat = Decal::new("decals/hole.png", Vec3::ONE).blend(
+        &Transform::default(),
+        tilted,
+        Vec4::ONE,
+        Vec3::Z,
+        1.0,
+    );
+    assert_vec_close(flat.normal, tilted.normal);
+}
+
+/// Verifies decal materials carry the textures and blend options.
+#[test]
+fn builds_materials() {
+    let decal = Decal::new("decals/hole.png", Vec3::ONE)
+        .with_normal("decals/hole_n.png")
+        .with_blend(DecalBlend {
+            albedo: false,
+            normal: NormalBlend::Replace,
+            roughness: Some(0.3),
+        });
+    let material = decal.material("decal.wgsl");
+    assert_eq!(
+        material.texture("albedo", None),
+        Some(Label::new("decals/hole.png"))
+    );
+    assert_eq!(
+        material.texture("normal", None),
+        Some(Label::new("decals/hole_n.png"))
+    );
+    for (name, value) in [
+        ("albedo_blend", 0.0),
+        ("normal_blend", 2.0),
+        ("roughness_blend", 1.0),
+        ("roughness", 0.3),
+    ] {
+        assert_eq!(
+            material.params.get(name),
+            Some(&MaterialParam::Float(value)),
+            "{name}"
+        );
+    }
+    let plain = Decal::new("decals/hole.png", Vec3::ONE).material("decal.wgsl");
+    assert_eq!(plain.texture("normal", None), None);
+    assert_eq!(
+        plain.params.get("normal_blend"),
+        Some(&MaterialParam::Float(1.0))
+    );
+}
@@ -0,0 +1,27 @@
+//! Projected decals.
+//!
+//! A [`Decal`] projects a texture onto whatever surfaces lie inside its
+//! box, e.g. bullet holes, blood splats, and road markings, changing their
+//! albedo, normals, and roughness as its [`DecalBlend`] says.
+//!
+//! The [`DecalPass`] culls and orders a view's decals after opaque
+//! geometry. With [`DecalTechnique::Deferred`] each decal's box is drawn
+//! into the G-buffer; with [`DecalTechnique::ForwardClustered`] decals are
+//! binned into [`DecalClusters`] that forward shaders look up per pixel.
+//!
+//! Temporary decals live in a [`DecalPool`], which fades them out as they
+//! expire and recycles the oldest when it is full.
+
+mod component;
+#[cfg(test)]
+mod component_test;
+mod pass;
+#[cfg(test)]
+mod pass_test;
+mod pool;
+#[cfg(test)]
+mod pool_test;
+
+pub use component::{Decal, DecalBlend, DecalHit, DecalSurface, NormalBlend};
+pub use pass::{ClusterGrid, DecalClusters, DecalFrame, DecalPass, DecalTechnique};
+pub use pool::{DecalId, DecalPool};
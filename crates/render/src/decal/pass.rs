@@ -0,0 +1,302 @@
+//! The decal pass: culling, ordering, and clustering decals for a view.
+
+use super::component::Decal;
+use crate::transform::Transform;
+use math::{Mat4, Vec3, Vec4};
+
+/// How decals are applied to the scene.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecalTechnique {
+    /// Each decal's box is drawn after the G-buffer pass, blending into
+    /// the albedo, normal, and roughness targets it covers.
+    Deferred,
+    /// Decals are binned into view-space clusters that forward shaders look
+    /// up per pixel, blending every decal covering the pixel while shading.
+    ForwardClustered,
+}
+
+/// Cluster grid dimensions of the forward path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClusterGrid {
+    /// Screen tiles across.
+    pub tiles_x: u32,
+    /// Screen tiles down.
+    pub tiles_y: u32,
+    /// Depth slices.
+    pub slices: u32,
+}
+
+impl Default for ClusterGrid {
+    fn default() -> Self {
+        Self {
+            tiles_x: 16,
+            tiles_y: 9,
+            slices: 24,
+        }
+    }
+}
+
+impl ClusterGrid {
+    /// Returns the number of clusters.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tiles_x as usize * self.tiles_y as usize * self.slices as usize
+    }
+
+    /// Returns `true` if the grid has no clusters.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the index of cluster `(x, y, slice)`, `x` fastest.
+    #[must_use]
+    pub fn index(&self, x: u32, y: u32, slice: u32) -> usize {
+        (slice as usize * self.tiles_y as usize + y as usize) * self.tiles_x as usize + x as usize
+    }
+}
+
+/// Decal lists per cluster, flattened for upload to storage buffers.
+///
+/// Slices split normalized device depth evenly between the near (0) and
+/// far (1) planes; tiles run from the top-left of the screen.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DecalClusters {
+    /// The grid the lists were built for.
+    pub grid: ClusterGrid,
+    /// Per cluster, the first position in [`indices`](Self::indices) and
+    /// the number of decals.
+    pub offsets: Vec<[u32; 2]>,
+    /// Indices into the frame's [`DecalFrame::draws`], cluster by cluster,
+    /// in draw order.
+    pub indices: Vec<u32>,
+}
+
+impl DecalClusters {
+    /// Returns the decals of cluster `(x, y, slice)`.
+    #[must_use]
+    pub fn cluster(&self, x: u32, y: u32, slice: u32) -> &[u32] {
+        let [start, count] = self.offsets[self.grid.index(x, y, slice)];
+        &self.indices[start as usize..(start + count) as usize]
+    }
+
+    /// Returns the decals of the cluster containing the normalized device
+    /// coordinates `ndc`, or an empty list outside the view.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn at(&self, ndc: Vec3) -> &[u32] {
+        if ndc.x.abs() > 1.0 || ndc.y.abs() > 1.0 || !(0.0..=1.0).contains(&ndc.z) {
+            return &[];
+        }
+        let cell = |t: f32, count: u32| ((t * count as f32) as u32).min(count - 1);
+        let grid = self.grid;
+        self.cluster(
+            cell((ndc.x + 1.0) * 0.5, grid.tiles_x),
+            cell((1.0 - ndc.y) * 0.5, grid.tiles_y),
+            cell(ndc.z, grid.slices),
+        )
+    }
+}
+
+/// The decals to draw for one view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecalFrame<T> {
+    /// Visible decals in draw order: ascending [`Decal::order`], then
+    /// input order.
+    pub draws: Vec<T>,
+    /// Cluster lists for the forward path; `None` when deferred.
+    pub clusters: Option<DecalClusters>,
+}
+
+/// Decal stage of the frame, run after opaque geometry.
+///
+/// # Example
+///
+/// ```
+/// use math::{Mat4, Vec3};
+/// use render::decal::{Decal, DecalPass, DecalTechnique};
+/// use render::Transform;
+///
+/// let pass = DecalPass::new(DecalTechnique::Deferred);
+/// let splat = Decal::new("decals/blood.png", Vec3::splat(0.5)).with_order(1);
+/// let hole = Decal::new("decals/hole.png", Vec3::splat(0.1));
+/// let far_away = Transform::from_translation(Vec3::new(0.0, 0.0, 50.0));
+///
+/// let frame = pass.prepare(
+///     &Mat4::IDENTITY,
+///     [
+///         ("splat", &splat, &Transform::default()),
+///         ("hole", &hole, &Transform::default()),
+///         ("hidden", &hole, &far_away),
+///     ],
+/// );
+/// assert_eq!(frame.draws, ["hole", "splat"]);
+/// assert!(frame.clusters.is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecalPass {
+    technique: DecalTechnique,
+    grid: ClusterGrid,
+}
+
+impl DecalPass {
+    /// Creates the stage using `technique`, with the default cluster grid.
+    #[must_use]
+    pub fn new(technique: DecalTechnique) -> Self {
+        Self {
+            technique,
+            grid: ClusterGrid::default(),
+        }
+    }
+
+    /// Sets the cluster grid of the forward path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any dimension is 0.
+    #[must_use]
+    pub fn with_grid(mut self, grid: ClusterGrid) -> Self {
+        assert!(!grid.is_empty(), "decal cluster grid must not be empty");
+        self.grid = grid;
+        self
+    }
+
+    /// Returns the technique in use.
+    #[must_use]
+    pub fn technique(&self) -> DecalTechnique {
+        self.technique
+    }
+
+    /// Culls `decals` against the view-projection `view_proj`, orders the
+    /// visible ones, and for the forward path bins them into clusters.
+    pub fn prepare<'a, T>(
+        &self,
+        view_proj: &Mat4,
+        decals: impl IntoIterator<Item = (T, &'a Decal, &'a Transform)>,
+    ) -> DecalFrame<T> {
+        let mut visible: Vec<(T, i32, Option<ScreenBox>)> = decals
+            .into_iter()
+            .filter_map(|(item, decal, transform)| {
+                let clip = decal
+                    .corners(transform)
+                    .map(|corner| *view_proj * corner.extend(1.0));
+                if outside_frustum(&clip) {
+                    return None;
+                }
+                Some((item, decal.order, ScreenBox::from_clip(&clip)))
+            })
+            .collect();
+        visible.sort_by_key(|(_, order, _)| *order);
+
+        let clusters = match self.technique {
+            DecalTechnique::Deferred => None,
+            DecalTechnique::ForwardClustered => Some(
+                self.cluster(
+                    visible
+                        .iter()
+                        .map(|(_, _, screen)| screen.unwrap_or(ScreenBox::FULL)),
+                ),
+            ),
+        };
+        DecalFrame {
+            draws: visible.into_iter().map(|(item, _, _)| item).collect(),
+            clusters,
+        }
+    }
+
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn cluster(&self, screens: impl Iterator<Item = ScreenBox>) -> DecalClusters {
+        let grid = self.grid;
+        let mut lists = vec![Vec::new(); grid.len()];
+        let range = |min: f32, max: f32, count: u32| {
+            let first = ((min.clamp(0.0, 1.0) * count as f32) as u32).min(count - 1);
+            let last = ((max.clamp(0.0, 1.0) * count as f32) as u32).min(count - 1);
+            first..=last
+        };
+        for (index, screen) in screens.enumerate() {
+            let xs = range(
+                (screen.min.x + 1.0) * 0.5,
+                (screen.max.x + 1.0) * 0.5,
+                grid.tiles_x,
+            );
+            let ys = range(
+                (1.0 - screen.max.y) * 0.5,
+                (1.0 - screen.min.y) * 0.5,
+                grid.tiles_y,
+            );
+            let slices = range(screen.min.z, screen.max.z, grid.slices);
+            for slice in slices {
+                for y in ys.clone() {
+                    for x in xs.clone() {
+                        lists[grid.index(x, y, slice)].push(index as u32);
+                    }
+                }
+            }
+        }
+        let mut offsets = Vec::with_capacity(lists.len());
+        let mut indices = Vec::new();
+        for list in lists {
+            offsets.push([indices.len() as u32, list.len() as u32]);
+            indices.extend(list);
+        }
+        DecalClusters {
+            grid,
+            offsets,
+            indices,
+        }
+    }
+}
+
+/// Normalized device bounds of a decal box.
+#[derive(Debug, Clone, Copy)]
+struct ScreenBox {
+    min: Vec3,
+    max: Vec3,
+}
+
+impl ScreenBox {
+    /// Covers the whole view, for boxes crossing the camera plane.
+    const FULL: Self = Self {
+        min: Vec3::new(-1.0, -1.0, 0.0),
+        max: Vec3::new(1.0, 1.0, 1.0),
+    };
+
+    /// Returns the bounds of the projected corners, or `None` if any lies
+    /// behind the camera.
+    fn from_clip(clip: &[Vec4; 8]) -> Option<Self> {
+        let mut min = Vec3::splat(f32::INFINITY);
+        let mut max = Vec3::splat(f32::NEG_INFINITY);
+        for corner in clip {
+            if corner.w <= f32::EPSILON {
+                return None;
+            }
+            let ndc = corner.truncate() / corner.w;
+            min = min.min(ndc);
+            max = max.max(ndc);
+        }
+        Some(Self { min, max })
+    }
+}
+
+/// Returns `true` if every corner lies outside the same clip plane.
+fn outside_frustum(clip: &[Vec4; 8]) -> bool {
+    let planes: [fn(Vec4) -> bool; 6] = [
+        |c| c.x < -c.w,
+        |c| c.x > c.w,
+        |c| c.y < -c.w,
+        |c| c.y > c.w,
+        |c| c.z < 0.0,
+        |c| c.z > c.w,
+    ];
+    planes
+        .iter()
+        .any(|outside| clip.iter().all(|&corner| outside(corner)))
+}
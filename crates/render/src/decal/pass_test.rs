@@ -0,0 +1,115 @@
+//! Unit tests for the decal pass.
+
+use super::component::Decal;
+use super::pass::{ClusterGrid, DecalPass, DecalTechnique};
+use crate::Transform;
+use math::{Mat4, Vec3, Vec4};
+
+/// A camera at the origin looking down `-Z` with a 90 degree field of
+/// view, depth going from 0 at 0.1 to 1 at 100.
+fn view_proj() -> Mat4 {
+    let (near, far) = (0.1, 100.0);
+    let depth = far / (near - far);
+    Mat4::from_cols(
+        Vec4::X,
+        Vec4::Y,
+        Vec4::new(0.0, 0.0, depth, -1.0),
+        Vec4::new(0.0, 0.0, depth * near, 0.0),
+    )
+}
+
+fn at(x: f32, y: f32, z: f32) -> Transform {
+    Transform::from_translation(Vec3::new(x, y, z))
+}
+
+/// Verifies decals outside the view are culled and the rest draw by
+/// order, ties in input order.
+#[test]
+fn culls_and_orders_decals() {
+    let decal = Decal::new("decals/hole.png", Vec3::ONE);
+    let top = decal.clone().with_order(2);
+    let bottom = decal.clone().with_order(-1);
+    let transforms = [
+        at(0.0, 0.0, -5.0),
+        at(0.0, 0.0, 5.0),
+        at(30.0, 0.0, -5.0),
+        at(5.3, 0.0, -5.0),
+        at(0.0, 0.0, -200.0),
+    ];
+    let pass = DecalPass::new(DecalTechnique::Deferred);
+    let frame = pass.prepare(
+        &view_proj(),
+        [
+            ("top", &top, &transforms[0]),
+            ("behind", &decal, &transforms[1]),
+            ("right", &decal, &transforms[2]),
+            ("edge", &decal, &transforms[3]),
+            ("far", &decal, &transforms[4]),
+            ("first", &decal, &transforms[0]),
+            ("bottom", &bottom, &transforms[0]),
+        ],
+    );
+    assert_eq!(frame.draws, ["bottom", "edge", "first", "top"]);
+    assert!(frame.clusters.is_none());
+    assert_eq!(pass.technique(), DecalTechnique::Deferred);
+}
+
+/// Verifies the forward path bins decals into the clusters they cover.
+#[test]
+fn bins_decals_into_clusters() {
+    let pass = DecalPass::new(DecalTechnique::ForwardClustered).with_grid(ClusterGrid {
+        tiles_x: 2,
+        tiles_y: 2,
+        slices: 1,
+    });
+    let decal = Decal::new("decals/hole.png", Vec3::ONE);
+    let upper_left = at(-5.0, 5.0, -10.0);
+    let lower_right = at(5.0, -5.0, -10.0);
+    let frame = pass.prepare(
+        &view_proj(),
+        [(0, &decal, &upper_left), (1, &decal, &lower_right)],
+    );
+    let clusters = frame.clusters.unwrap();
+    assert_eq!(clusters.offsets.len(), 4);
+    assert_eq!(clusters.cluster(0, 0, 0), [0]);
+    assert_eq!(clusters.cluster(1, 1, 0), [1]);
+    assert!(clusters.cluster(1, 0, 0).is_empty());
+    assert!(clusters.cluster(0, 1, 0).is_empty());
+    assert_eq!(clusters.at(Vec3::new(-0.5, 0.5, 0.9)), [0]);
+    assert_eq!(clusters.at(Vec3::new(0.5, -0.5, 0.9)), [1]);
+    assert!(clusters.at(Vec3::new(0.0, 2.0, 0.5)).is_empty());
+}
+
+/// Verifies depth slices separate near and far decals, and a box around
+/// the camera conservatively covers every cluster.
+#[test]
+fn slices_by_depth() {
+    let pass = DecalPass::new(DecalTechnique::ForwardClustered).with_grid(ClusterGrid {
+        tiles_x: 1,
+        tiles_y: 1,
+        slices: 4,
+    });
+    let decal = Decal::new("decals/hole.png", Vec3::splat(0.01));
+    let around = Decal::new("decals/fog.png", Vec3::splat(4.0));
+    let (near, far, origin) = (at(0.0, 0.0, -0.11), at(0.0, 0.0, -50.0), at(0.0, 0.0, 0.0));
+    let frame = pass.prepare(
+        &view_proj(),
+        [(0, &decal, &near), (1, &decal, &far), (2, &around, &origin)],
+    );
+    let clusters = frame.clusters.unwrap();
+    assert_eq!(clusters.cluster(0, 0, 0), [0, 2]);
+    assert_eq!(clusters.cluster(0, 0, 3), [1, 2]);
+    assert_eq!(clusters.cluster(0, 0, 1), [2]);
+    assert_eq!(clusters.indices.len(), 6);
+}
+
+/// Verifies an empty cluster grid is rejected.
+#[test]
+#[should_panic(expected = "must not be empty")]
+fn rejects_empty_grids() {
+    let _ = DecalPass::new(DecalTechnique::ForwardClustered).with_grid(ClusterGrid {
+        tiles_x: 0,
+        tiles_y: 1,
+        slices: 1,
+    });
+}
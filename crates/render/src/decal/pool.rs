@@ -0,0 +1,218 @@
+//! Pooled temporary decals.
+
+use super::component::Decal;
+use crate::transform::Transform;
+use std::time::Duration;
+
+/// Handle to a decal in a [`DecalPool`]; stale once the decal expires or
+/// its slot is recycled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DecalId {
+    index: u32,
+    generation: u32,
+}
+
+#[derive(Debug, Clone)]
+struct Slot {
+    generation: u32,
+    entry: Option<Entry>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    decal: Decal,
+    transform: Transform,
+    remaining: Duration,
+    /// Spawn sequence number, to find the oldest decal.
+    spawned: u64,
+}
+
+/// A fixed number of temporary decals, e.g. bullet holes and footprints.
+///
+/// Decals live for the lifetime given when spawned and fade out over the
+/// pool's [`fade`](Self::with_fade) before they are removed. When the pool
+/// is full, spawning replaces the oldest decal, so the cost of decals in a
+/// firefight stays bounded.
+///
+/// # Example
+///
+/// ```
+/// use math::Vec3;
+/// use render::decal::{Decal, DecalPool};
+/// use render::Transform;
+/// use std::time::Duration;
+///
+/// let mut pool = DecalPool::new(64);
+/// let hole = Decal::new("decals/hole.png", Vec3::splat(0.1));
+/// let id = pool.spawn(hole, Transform::default(), Duration::from_secs(10));
+///
+/// pool.update(Duration::from_millis(9_500));
+/// assert!((pool.get(id).unwrap().0.opacity - 0.5).abs() < 1e-5);
+/// pool.update(Duration::from_secs(1));
+/// assert!(pool.get(id).is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct DecalPool {
+    slots: Vec<Slot>,
+    free: Vec<u32>,
+    fade: Duration,
+    spawned: u64,
+}
+
+impl DecalPool {
+    /// Creates a pool holding up to `capacity` decals, fading out over one
+    /// second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    #[must_use]
+    pub fn new(capacity: u32) -> Self {
+        assert!(capacity > 0, "decal pool capacity must be positive");
+        Self {
+            slots: (0..capacity)
+                .map(|_| Slot {
+                    generation: 0,
+                    entry: None,
+                })
+                .collect(),
+            free: (0..capacity).rev().collect(),
+            fade: Duration::from_secs(1),
+            spawned: 0,
+        }
+    }
+
+    /// Sets how long before expiring decals start fading out.
+    #[must_use]
+    pub fn with_fade(mut self, fade: Duration) -> Self {
+        self.fade = fade;
+        self
+    }
+
+    /// Returns the number of decals the pool holds at most.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the number of live decals.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    /// Returns `true` if no decals are live.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Places `decal` at `transform` for `lifetime`, replacing the oldest
+    /// decal if the pool is full.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn spawn(&mut self, decal: Decal, transform: Transform, lifetime: Duration) -> DecalId {
+        let index = self.free.pop().unwrap_or_else(|| {
+            let oldest = self
+                .slots
+                .iter()
+                .zip(0u32..)
+                .filter_map(|(slot, index)| Some((index, slot.entry.as_ref()?.spawned)))
+                .min_by_key(|&(_, spawned)| spawned)
+                .map(|(index, _)| index)
+                .expect("a full pool has live decals");
+            let slot = &mut self.slots[oldest as usize];
+            slot.entry = None;
+            slot.generation = slot.generation.wrapping_add(1);
+            oldest
+        });
+        self.spawned += 1;
+        let slot = &mut self.slots[index as usize];
+        slot.entry = Some(Entry {
+            decal,
+            transform,
+            remaining: lifetime,
+            spawned: self.spawned,
+        });
+        DecalId {
+            index,
+            generation: slot.generation,
+        }
+    }
+
+    /// Returns the decal, with its opacity faded, and where it is placed,
+    /// or `None` if `id` is stale.
+    #[must_use]
+    pub fn get(&self, id: DecalId) -> Option<(Decal, &Transform)> {
+        let entry = self.entry(id)?;
+        Some((self.faded(entry), &entry.transform))
+    }
+
+    /// Removes a decal early, returning it if `id` was live.
+    pub fn remove(&mut self, id: DecalId) -> Option<Decal> {
+        self.entry(id)?;
+        let slot = &mut self.slots[id.index as usize];
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(id.index);
+        slot.entry.take().map(|entry| entry.decal)
+    }
+
+    /// Removes every decal.
+    pub fn clear(&mut self) {
+        let live: Vec<_> = self.iter().map(|(id, _, _)| id).collect();
+        for id in live {
+            self.remove(id);
+        }
+    }
+
+    /// Ages every decal by `delta`, removing those that expire.
+    pub fn update(&mut self, delta: Duration) {
+        for (slot, index) in self.slots.iter_mut().zip(0u32..) {
+            let Some(entry) = &mut slot.entry else {
+                continue;
+            };
+            entry.remaining = entry.remaining.saturating_sub(delta);
+            if entry.remaining.is_zero() {
+                slot.entry = None;
+                slot.generation = slot.generation.wrapping_add(1);
+                self.free.push(index);
+            }
+        }
+    }
+
+    /// Iterates over the live decals, with their opacities faded, oldest
+    /// first, for [`DecalPass::prepare`](super::DecalPass::prepare).
+    pub fn iter(&self) -> impl Iterator<Item = (DecalId, Decal, &Transform)> + '_ {
+        let mut live: Vec<_> = self
+            .slots
+            .iter()
+            .zip(0u32..)
+            .filter_map(|(slot, index)| {
+                let entry = slot.entry.as_ref()?;
+                let id = DecalId {
+                    index,
+                    generation: slot.generation,
+                };
+                Some((id, entry))
+            })
+            .collect();
+        live.sort_by_key(|(_, entry)| entry.spawned);
+        live.into_iter()
+            .map(|(id, entry)| (id, self.faded(entry), &entry.transform))
+    }
+
+    fn entry(&self, id: DecalId) -> Option<&Entry> {
+        let slot = self.slots.get(id.index as usize)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        slot.entry.as_ref()
+    }
+
+    fn faded(&self, entry: &Entry) -> Decal {
+        let mut decal = entry.decal.clone();
+        if entry.remaining < self.fade {
+            decal.opacity *= entry.remaining.as_secs_f32() / self.fade.as_secs_f32();
+        }
+        decal
+    }
+}
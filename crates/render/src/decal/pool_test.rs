@@ -0,0 +1,76 @@
+//! Unit tests for pooled decals.
+
+use super::component::Decal;
+use super::pool::DecalPool;
+use crate::Transform;
+use math::Vec3;
+use std::time::Duration;
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+}
+
+fn hole() -> Decal {
+    Decal::new("decals/hole.png", Vec3::splat(0.1))
+}
+
+fn at(x: f32) -> Transform {
+    Transform::from_translation(Vec3::new(x, 0.0, 0.0))
+}
+
+/// Verifies decals fade out over the fade time and expire with their
+/// lifetime, freeing their slot.
+#[test]
+fn fades_and_expires_decals() {
+    let mut pool = DecalPool::new(4).with_fade(Duration::from_secs(2));
+    let short = pool.spawn(hole(), at(0.0), Duration::from_secs(3));
+    let long = pool.spawn(hole(), at(1.0), Duration::from_secs(30));
+    assert_eq!(pool.len(), 2);
+    assert_close(pool.get(short).unwrap().0.opacity, 1.0);
+
+    pool.update(Duration::from_millis(1_500));
+    assert_close(pool.get(short).unwrap().0.opacity, 0.75);
+    assert_close(pool.get(long).unwrap().0.opacity, 1.0);
+
+    pool.update(Duration::from_millis(1_500));
+    assert!(pool.get(short).is_none());
+    assert_eq!(pool.len(), 1);
+    assert_eq!(pool.get(long).unwrap().1, &at(1.0));
+}
+
+/// Verifies a full pool replaces its oldest decal and old handles go
+/// stale.
+#[test]
+fn recycles_the_oldest_decal() {
+    let mut pool = DecalPool::new(2);
+    let first = pool.spawn(hole(), at(0.0), Duration::from_secs(10));
+    let second = pool.spawn(hole(), at(1.0), Duration::from_secs(10));
+    let third = pool.spawn(hole(), at(2.0), Duration::from_secs(10));
+    assert_eq!(pool.len(), 2);
+    assert_eq!(pool.capacity(), 2);
+    assert!(pool.get(first).is_none());
+    assert_ne!(first, third);
+
+    let ids: Vec<_> = pool.iter().map(|(id, _, _)| id).collect();
+    assert_eq!(ids, [second, third]);
+    let fourth = pool.spawn(hole(), at(3.0), Duration::from_secs(10));
+    assert!(pool.get(second).is_none());
+    assert_eq!(pool.get(fourth).unwrap().1, &at(3.0));
+}
+
+/// Verifies decals can be removed early, once.
+#[test]
+fn removes_decals() {
+    let mut pool = DecalPool::new(3);
+    let id = pool.spawn(hole(), at(0.0), Duration::from_secs(10));
+    pool.spawn(hole(), at(1.0), Duration::from_secs(10));
+    assert_eq!(pool.remove(id), Some(hole()));
+    assert_eq!(pool.remove(id), None);
+    let reused = pool.spawn(hole(), at(2.0), Duration::from_secs(10));
+    assert!(pool.get(id).is_none());
+    assert!(pool.get(reused).is_some());
+
+    pool.clear();
+    assert!(pool.is_empty());
+    assert_eq!(pool.iter().count(), 0);
+}
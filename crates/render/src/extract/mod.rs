@@ -0,0 +1,25 @@
+//! The extract stage between the ECS world and the renderer.
+//!
+//! A frame is rendered in four [`RenderPhase`]s. Only the first, extract,
+//! reads the [`World`](ecs::World): a [`RenderExtractor`] copies the
+//! minimum the renderer needs, such as cameras, decals, and their
+//! transforms, into a renderer-owned [`RenderWorld`]. Prepare, queue, and
+//! render then work on that copy alone, so renderer internals never hold
+//! world borrows.
+//!
+//! A [`FramePipeline`] hands render worlds to the renderer. Serial, it
+//! renders each frame before the next is extracted; pipelined, the
+//! simulation runs frame `N + 1` while frame `N` is rendered, which is safe
+//! because the two share no data after extraction.
+
+mod pipeline;
+#[cfg(test)]
+mod pipeline_test;
+mod world;
+#[cfg(test)]
+mod world_test;
+
+pub use pipeline::{FramePipeline, RenderFrame, RenderPhase};
+pub use world::{
+    extract_components, extract_views, ExtractFn, Extracted, RenderExtractor, RenderWorld,
+};
@@ -0,0 +1,214 @@
+//! Frame phases and the hand-off of render worlds to the renderer.
+
+use super::world::{RenderExtractor, RenderWorld};
+use ecs::World;
+use std::collections::VecDeque;
+use std::fmt;
+use tracing::trace;
+
+/// Phase of a rendered frame, in execution order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum RenderPhase {
+    /// Copying render-relevant components out of the ECS world.
+    #[default]
+    Extract,
+    /// Uploading extracted data to GPU buffers and creating bind groups.
+    Prepare,
+    /// Culling, sorting, and batching draws per view.
+    Queue,
+    /// Recording and submitting command buffers.
+    Render,
+}
+
+impl RenderPhase {
+    /// Every phase, in execution order.
+    pub const ALL: [Self; 4] = [Self::Extract, Self::Prepare, Self::Queue, Self::Render];
+
+    /// Returns `true` if the phase borrows the ECS world, so the simulation
+    /// cannot run during it.
+    #[must_use]
+    pub fn reads_world(self) -> bool {
+        self == Self::Extract
+    }
+}
+
+impl fmt::Display for RenderPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Extract => "extract",
+            Self::Prepare => "prepare",
+            Self::Queue => "queue",
+            Self::Render => "render",
+        })
+    }
+}
+
+/// A frame's render world on its way through the phases after extraction.
+#[derive(Debug)]
+pub struct RenderFrame {
+    world: RenderWorld,
+    phase: RenderPhase,
+}
+
+impl RenderFrame {
+    /// Returns the frame's extracted data.
+    #[must_use]
+    #[inline]
+    pub fn world(&self) -> &RenderWorld {
+        &self.world
+    }
+
+    /// Returns the phase the frame is in.
+    #[must_use]
+    #[inline]
+    pub fn phase(&self) -> RenderPhase {
+        self.phase
+    }
+
+    /// Moves the frame on to `phase`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `phase` comes before the current phase, e.g. preparing a
+    /// frame that was already queued.
+    pub fn enter(&mut self, phase: RenderPhase) {
+        assert!(
+            phase >= self.phase,
+            "render frame {} cannot go back from {} to {phase}",
+            self.world.frame(),
+            self.phase
+        );
+        self.phase = phase;
+    }
+}
+
+/// Hands render worlds from the simulation to the renderer.
+///
+/// The simulation extracts each frame into a [`RenderWorld`] and carries on;
+/// the renderer takes the oldest extracted frame, runs the prepare, queue,
+/// and render phases over it, and gives it back so its allocations are
+/// reused. Render worlds own their data, so the renderer may run on another
+/// thread while the simulation mutates the world.
+///
+/// [`FramePipeline::serial`] allows one frame in flight: the simulation
+/// waits for every frame to finish rendering before extracting the next.
+/// [`FramePipeline::pipelined`] lets it extract ahead, rendering frame `N`
+/// while frame `N + 1` is simulated, at the cost of a frame of latency.
+///
+/// # Example
+///
+/// ```
+/// use ecs::World;
+/// use render::extract::{FramePipeline, RenderExtractor, RenderPhase};
+///
+/// let world = World::default();
+/// let mut pipeline = FramePipeline::pipelined(RenderExtractor::default(), 2);
+///
+/// assert_eq!(pipeline.extract(&world), Some(0));
+/// let mut frame = pipeline.begin_render().unwrap();
+/// // The simulation moves on while frame 0 renders.
+/// assert_eq!(pipeline.extract(&world), Some(1));
+/// assert_eq!(pipeline.extract(&world), None);
+///
+/// frame.enter(RenderPhase::Render);
+/// pipeline.finish_render(frame);
+/// assert_eq!(pipeline.extract(&world), Some(2));
+/// ```
+#[derive(Debug)]
+pub struct FramePipeline {
+    extractor: RenderExtractor,
+    /// Most frames extracted but not yet finished.
+    frames_in_flight: usize,
+    /// Number of the next extracted frame.
+    next_frame: u64,
+    /// Extracted frames waiting for the renderer, oldest first.
+    queued: VecDeque<RenderWorld>,
+    /// Frames taken by the renderer and not yet finished.
+    rendering: usize,
+    /// Finished render worlds, kept for their allocations.
+    free: Vec<RenderWorld>,
+}
+
+impl FramePipeline {
+    /// Creates a pipeline rendering each frame before the next is
+    /// extracted.
+    #[must_use]
+    pub fn serial(extractor: RenderExtractor) -> Self {
+        Self::pipelined(extractor, 1)
+    }
+
+    /// Creates a pipeline with up to `frames_in_flight` frames extracted
+    /// and not yet rendered, at least one.
+    #[must_use]
+    pub fn pipelined(extractor: RenderExtractor, frames_in_flight: usize) -> Self {
+        Self {
+            extractor,
+            frames_in_flight: frames_in_flight.max(1),
+            next_frame: 0,
+            queued: VecDeque::new(),
+            rendering: 0,
+            free: Vec::new(),
+        }
+    }
+
+    /// Returns the most frames extracted and not yet finished.
+    #[must_use]
+    #[inline]
+    pub fn frames_in_flight(&self) -> usize {
+        self.frames_in_flight
+    }
+
+    /// Returns `true` if the simulation may run ahead of the renderer.
+    #[must_use]
+    #[inline]
+    pub fn is_pipelined(&self) -> bool {
+        self.frames_in_flight > 1
+    }
+
+    /// Returns the number of frames extracted and not yet finished.
+    #[must_use]
+    #[inline]
+    pub fn in_flight(&self) -> usize {
+        self.queued.len() + self.rendering
+    }
+
+    /// Extract phase: copies `world` into a render world and queues it for
+    /// the renderer, returning its frame number, or `None` without reading
+    /// the world while [`frames_in_flight`](Self::frames_in_flight) frames
+    /// are unfinished.
+    pub fn extract(&mut self, world: &World) -> Option<u64> {
+        if self.in_flight() >= self.frames_in_flight {
+            return None;
+        }
+        let frame = self.next_frame;
+        self.next_frame += 1;
+        let mut render_world = self.free.pop().unwrap_or_default();
+        self.extractor.extract(world, frame, &mut render_world);
+        trace!(
+            target: "render::extract",
+            frame,
+            views = render_world.views().len(),
+            in_flight = self.in_flight() + 1,
+            "extracted frame"
+        );
+        self.queued.push_back(render_world);
+        Some(frame)
+    }
+
+    /// Takes the oldest extracted frame for rendering, starting in
+    /// [`RenderPhase::Prepare`].
+    pub fn begin_render(&mut self) -> Option<RenderFrame> {
+        let world = self.queued.pop_front()?;
+        self.rendering += 1;
+        Some(RenderFrame {
+            world,
+            phase: RenderPhase::Prepare,
+        })
+    }
+
+    /// Returns a rendered frame, letting the simulation extract another.
+    pub fn finish_render(&mut self, frame: RenderFrame) {
+        self.rendering = self.rendering.saturating_sub(1);
+        self.free.push(frame.world);
+    }
+}
@@ -0,0 +1,69 @@
+//! Unit tests for frame phases and the frame pipeline.
+
+use super::pipeline::{FramePipeline, RenderPhase};
+use super::world::RenderExtractor;
+use crate::Camera;
+use ecs::World;
+
+/// Verifies a serial pipeline extracts only after the previous frame
+/// finished rendering, and frames keep their numbers.
+#[test]
+fn serial_waits_for_each_frame() {
+    let world = World::default();
+    let mut pipeline = FramePipeline::serial(RenderExtractor::default());
+    assert!(!pipeline.is_pipelined());
+
+    assert_eq!(pipeline.extract(&world), Some(0));
+    assert_eq!(pipeline.extract(&world), None);
+    let frame = pipeline.begin_render().unwrap();
+    assert_eq!(frame.world().frame(), 0);
+    assert_eq!(frame.phase(), RenderPhase::Prepare);
+    assert_eq!(pipeline.extract(&world), None);
+    assert!(pipeline.begin_render().is_none());
+
+    pipeline.finish_render(frame);
+    assert_eq!(pipeline.in_flight(), 0);
+    assert_eq!(pipeline.extract(&world), Some(1));
+}
+
+/// Verifies a pipelined renderer sees the world as it was extracted while
+/// the simulation changes it.
+#[test]
+fn pipelined_frames_keep_their_copy() {
+    let mut world = World::default();
+    let mut pipeline = FramePipeline::pipelined(RenderExtractor::default(), 2);
+    assert!(pipeline.is_pipelined());
+
+    let main = world.spawn();
+    world.insert(main, Camera::default());
+    assert_eq!(pipeline.extract(&world), Some(0));
+    let rendering = pipeline.begin_render().unwrap();
+
+    world.despawn(main);
+    assert_eq!(pipeline.extract(&world), Some(1));
+    assert_eq!(pipeline.in_flight(), 2);
+    assert_eq!(rendering.world().views()[0].entity, main);
+
+    pipeline.finish_render(rendering);
+    let next = pipeline.begin_render().unwrap();
+    assert_eq!(next.world().frame(), 1);
+    assert!(next.world().views().is_empty());
+}
+
+/// Verifies phases only move forward and only extract reads the world.
+#[test]
+#[should_panic(expected = "cannot go back from queue to prepare")]
+fn phases_only_move_forward() {
+    assert!(RenderPhase::ALL.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(
+        RenderPhase::ALL.map(RenderPhase::reads_world),
+        [true, false, false, false]
+    );
+
+    let mut pipeline = FramePipeline::serial(RenderExtractor::default());
+    pipeline.extract(&World::default());
+    let mut frame = pipeline.begin_render().unwrap();
+    frame.enter(RenderPhase::Queue);
+    frame.enter(RenderPhase::Queue);
+    frame.enter(RenderPhase::Prepare);
+}
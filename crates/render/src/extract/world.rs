@@ -0,0 +1,263 @@
+//! The render world and the extractors that fill it.
+
+use crate::camera::{render_order, Camera};
+use crate::decal::Decal;
+use crate::layers::RenderLayers;
+use crate::transform::Transform;
+use ecs::{Disabled, Entity, Tick, World};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Render-relevant copy of one entity's component.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Extracted<T> {
+    /// Entity the component was copied from.
+    pub entity: Entity,
+    /// The component.
+    pub value: T,
+    /// The entity's transform, the identity if it has none.
+    pub transform: Transform,
+    /// The entity's layers, the default mask if it has none.
+    pub layers: RenderLayers,
+}
+
+impl<T: Clone + Send + Sync + 'static> Extracted<T> {
+    /// Copies `value` with the transform and layers of `entity`.
+    #[must_use]
+    pub fn copy(world: &World, entity: Entity, value: &T) -> Self {
+        Self {
+            entity,
+            value: value.clone(),
+            transform: world.get::<Transform>(entity).copied().unwrap_or_default(),
+            layers: world
+                .get::<RenderLayers>(entity)
+                .copied()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Type-erased list of one extracted component type.
+trait ExtractedStorage: Send + Sync {
+    /// Empties the list, keeping its allocation.
+    fn clear(&mut self);
+    /// Returns the list for downcasting.
+    fn as_any(&self) -> &dyn Any;
+    /// Returns the list for downcasting.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Send + Sync + 'static> ExtractedStorage for Vec<Extracted<T>> {
+    fn clear(&mut self) {
+        Vec::clear(self);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Renderer-owned data of one frame, copied from the ECS world.
+///
+/// The prepare, queue, and render phases only read this, never the
+/// [`World`], so the simulation can run the next frame while this one is
+/// drawn. Lists are cleared rather than dropped between frames, keeping
+/// their allocations.
+#[derive(Default)]
+pub struct RenderWorld {
+    /// Frame number the data was extracted for.
+    frame: u64,
+    /// World change tick at extraction.
+    tick: Tick,
+    /// Extracted cameras, in render order.
+    views: Vec<Extracted<Camera>>,
+    /// `Vec<Extracted<T>>` by component type.
+    components: HashMap<TypeId, Box<dyn ExtractedStorage>>,
+}
+
+impl fmt::Debug for RenderWorld {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderWorld")
+            .field("frame", &self.frame)
+            .field("tick", &self.tick)
+            .field("views", &self.views.len())
+            .field("component_types", &self.components.len())
+            .finish()
+    }
+}
+
+impl RenderWorld {
+    /// Returns the frame number the data was extracted for.
+    #[must_use]
+    #[inline]
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Returns the world's change tick at extraction.
+    #[must_use]
+    #[inline]
+    pub fn tick(&self) -> Tick {
+        self.tick
+    }
+
+    /// Returns the active cameras, in the order they render.
+    #[must_use]
+    #[inline]
+    pub fn views(&self) -> &[Extracted<Camera>] {
+        &self.views
+    }
+
+    /// Returns the extracted `T` components, sorted by entity.
+    #[must_use]
+    pub fn extracted<T: Send + Sync + 'static>(&self) -> &[Extracted<T>] {
+        self.components
+            .get(&TypeId::of::<T>())
+            .and_then(|items| items.as_any().downcast_ref::<Vec<Extracted<T>>>())
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the extracted `T` components for writing, e.g. by a custom
+    /// [`ExtractFn`].
+    #[allow(clippy::missing_panics_doc)]
+    pub fn extracted_mut<T: Send + Sync + 'static>(&mut self) -> &mut Vec<Extracted<T>> {
+        self.components
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(Vec::<Extracted<T>>::new()))
+            .as_any_mut()
+            .downcast_mut()
+            .expect("extracted storage is keyed by its type")
+    }
+
+    /// Returns the extracted `T` components on layers `view` sees, as
+    /// `(entity, component, transform)`, the input of passes such as
+    /// [`DecalPass::prepare`](crate::decal::DecalPass::prepare).
+    pub fn visible<'a, T: Send + Sync + 'static>(
+        &'a self,
+        view: &'a Extracted<Camera>,
+    ) -> impl Iterator<Item = (Entity, &'a T, &'a Transform)> + 'a {
+        self.extracted::<T>()
+            .iter()
+            .filter(|item| view.value.sees(item.layers))
+            .map(|item| (item.entity, &item.value, &item.transform))
+    }
+
+    /// Starts `frame`, emptying every list.
+    pub(crate) fn clear(&mut self, frame: u64, tick: Tick) {
+        self.frame = frame;
+        self.tick = tick;
+        self.views.clear();
+        for items in self.components.values_mut() {
+            items.clear();
+        }
+    }
+}
+
+/// Copies render-relevant data from the world into a [`RenderWorld`].
+pub type ExtractFn = fn(&World, &mut RenderWorld);
+
+/// The extract phase: the only part of a frame that reads the [`World`].
+///
+/// By default it copies active [`Camera`]s and [`Decal`]s; other component
+/// types are added with [`RenderExtractor::with_component`], or with a
+/// custom [`ExtractFn`]. Entities marked [`Disabled`] are skipped.
+///
+/// # Example
+///
+/// ```
+/// use ecs::World;
+/// use render::extract::{RenderExtractor, RenderWorld};
+/// use render::lod::{Lod, LodLevel};
+/// use render::Camera;
+///
+/// let mut world = World::default();
+/// let camera = world.spawn();
+/// world.insert(camera, Camera::default());
+/// let rock = world.spawn();
+/// world.insert(rock, Lod::new(vec![LodLevel::new("rock", 0.0)]));
+///
+/// let extractor = RenderExtractor::default().with_component::<Lod<&str>>();
+/// let mut render_world = RenderWorld::default();
+/// extractor.extract(&world, 1, &mut render_world);
+///
+/// // The world can change freely; the render world keeps its copy.
+/// world.despawn(rock);
+/// assert_eq!(render_world.views()[0].entity, camera);
+/// assert_eq!(render_world.extracted::<Lod<&str>>()[0].entity, rock);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RenderExtractor {
+    extractors: Vec<ExtractFn>,
+}
+
+impl Default for RenderExtractor {
+    fn default() -> Self {
+        Self {
+            extractors: vec![extract_views, extract_components::<Decal>],
+        }
+    }
+}
+
+impl RenderExtractor {
+    /// Also copies every `T` component.
+    #[must_use]
+    pub fn with_component<T: Clone + Send + Sync + 'static>(self) -> Self {
+        self.with(extract_components::<T>)
+    }
+
+    /// Also runs `extract`, after the extractors added before it.
+    #[must_use]
+    pub fn with(mut self, extract: ExtractFn) -> Self {
+        self.extractors.push(extract);
+        self
+    }
+
+    /// Replaces the contents of `render_world` with `frame`'s copy of
+    /// `world`.
+    pub fn extract(&self, world: &World, frame: u64, render_world: &mut RenderWorld) {
+        render_world.clear(frame, world.change_tick());
+        for extract in &self.extractors {
+            extract(world, render_world);
+        }
+    }
+}
+
+/// Copies the active cameras, in render order.
+pub fn extract_views(world: &World, render_world: &mut RenderWorld) {
+    let mut cameras = enabled::<Camera>(world);
+    cameras.sort_by_key(|camera| camera.entity);
+    let order = render_order(
+        &cameras
+            .iter()
+            .map(|camera| camera.value.clone())
+            .collect::<Vec<_>>(),
+    );
+    render_world
+        .views
+        .extend(order.into_iter().map(|index| cameras[index].clone()));
+}
+
+/// Copies every `T` component, sorted by entity.
+pub fn extract_components<T: Clone + Send + Sync + 'static>(
+    world: &World,
+    render_world: &mut RenderWorld,
+) {
+    let items = render_world.extracted_mut::<T>();
+    items.clear();
+    items.extend(enabled::<T>(world));
+    items.sort_by_key(|item| item.entity);
+}
+
+/// Copies the `T` components of entities not marked [`Disabled`].
+fn enabled<T: Clone + Send + Sync + 'static>(world: &World) -> Vec<Extracted<T>> {
+    world
+        .query::<T>()
+        .filter(|(entity, _)| world.get::<Disabled>(*entity).is_none())
+        .map(|(entity, value)| Extracted::copy(world, entity, value))
+        .collect()
+}
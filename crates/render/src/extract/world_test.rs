@@ -0,0 +1,146 @@
+//! Unit tests for the render world and its extractors.
+
+use super::world::{Extracted, RenderExtractor, RenderWorld};
+use crate::decal::Decal;
+use crate::target::{RenderTarget, TextureHandle};
+use crate::{Camera, RenderLayers, Transform};
+use ecs::{Disabled, Entity, World};
+use math::Vec3;
+
+fn camera(world: &mut World, camera: Camera) -> Entity {
+    let entity = world.spawn();
+    world.insert(entity, camera);
+    entity
+}
+
+/// Verifies active cameras are extracted in render order, texture targets
+/// first, and inactive or disabled cameras are skipped.
+#[test]
+fn extracts_views_in_render_order() {
+    let mut world = World::default();
+    let overlay = camera(
+        &mut world,
+        Camera {
+            order: 1,
+            ..Camera::default()
+        },
+    );
+    let main = camera(&mut world, Camera::default());
+    let mirror = camera(
+        &mut world,
+        Camera {
+            target: RenderTarget::Texture(TextureHandle::from_path("render-target:0").unwrap()),
+            order: 5,
+            ..Camera::default()
+        },
+    );
+    camera(
+        &mut world,
+        Camera {
+            active: false,
+            ..Camera::default()
+        },
+    );
+    let disabled = camera(&mut world, Camera::default());
+    world.insert(disabled, Disabled);
+
+    let mut render_world = RenderWorld::default();
+    RenderExtractor::default().extract(&world, 7, &mut render_world);
+    let views: Vec<_> = render_world
+        .views()
+        .iter()
+        .map(|view| view.entity)
+        .collect();
+    assert_eq!(views, [mirror, main, overlay]);
+    assert_eq!(render_world.frame(), 7);
+    assert_eq!(render_world.tick(), world.change_tick());
+}
+
+/// Verifies components are copied with their transform and layers, and
+/// later world changes do not reach the copy.
+#[test]
+fn copies_components_with_transform_and_layers() {
+    let mut world = World::default();
+    let hole = world.spawn();
+    world.insert(hole, Decal::new("decals/hole.png", Vec3::ONE));
+    world.insert(hole, Transform::from_translation(Vec3::X));
+    world.insert(hole, RenderLayers::layer(3));
+    let splat = world.spawn();
+    world.insert(splat, Decal::new("decals/splat.png", Vec3::ONE));
+
+    let mut render_world = RenderWorld::default();
+    RenderExtractor::default().extract(&world, 0, &mut render_world);
+    world.get_mut::<Transform>(hole).unwrap().translation = Vec3::Y;
+
+    let decals = render_world.extracted::<Decal>();
+    assert_eq!(decals.len(), 2);
+    assert_eq!(
+        decals[0],
+        Extracted {
+            entity: hole,
+            value: Decal::new("decals/hole.png", Vec3::ONE),
+            transform: Transform::from_translation(Vec3::X),
+            layers: RenderLayers::layer(3),
+        }
+    );
+    assert_eq!(decals[1].entity, splat);
+    assert_eq!(decals[1].transform, Transform::default());
+    assert_eq!(decals[1].layers, RenderLayers::default());
+}
+
+/// Verifies each view only sees the components on its layers.
+#[test]
+fn visible_filters_by_view_layers() {
+    let mut world = World::default();
+    camera(
+        &mut world,
+        Camera {
+            layers: RenderLayers::layer(2),
+            ..Camera::default()
+        },
+    );
+    let marker = world.spawn();
+    world.insert(marker, Decal::new("decals/marker.png", Vec3::ONE));
+    world.insert(marker, RenderLayers::layer(2));
+    let ground = world.spawn();
+    world.insert(ground, Decal::new("decals/ground.png", Vec3::ONE));
+
+    let mut render_world = RenderWorld::default();
+    RenderExtractor::default().extract(&world, 0, &mut render_world);
+    let view = &render_world.views()[0];
+    let visible: Vec<_> = render_world
+        .visible::<Decal>(view)
+        .map(|(entity, _, _)| entity)
+        .collect();
+    assert_eq!(visible, [marker]);
+}
+
+/// Verifies extracting again replaces the previous frame, including
+/// components added by custom extractors.
+#[test]
+fn extract_replaces_previous_frame() {
+    fn extract_markers(world: &World, render_world: &mut RenderWorld) {
+        for entity in world.entities() {
+            render_world
+                .extracted_mut::<u32>()
+                .push(Extracted::copy(world, entity, &1));
+        }
+    }
+
+    let mut world = World::default();
+    let decal = world.spawn();
+    world.insert(decal, Decal::new("decals/hole.png", Vec3::ONE));
+    let extractor = RenderExtractor::default()
+        .with_component::<f32>()
+        .with(extract_markers);
+    let mut render_world = RenderWorld::default();
+    extractor.extract(&world, 0, &mut render_world);
+    assert_eq!(render_world.extracted::<u32>().len(), 1);
+
+    world.despawn(decal);
+    extractor.extract(&world, 1, &mut render_world);
+    assert!(render_world.extracted::<Decal>().is_empty());
+    assert!(render_world.extracted::<u32>().is_empty());
+    assert!(render_world.extracted::<f32>().is_empty());
+    assert!(render_world.extracted::<i64>().is_empty());
+}
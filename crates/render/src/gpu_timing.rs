@@ -0,0 +1,207 @@
+//! GPU pass timing with timestamp queries.
+//!
+//! [`GpuTimer`] hands out timestamp query slots around each render pass and
+//! turns the resolved timestamps into per-pass GPU milliseconds. Readbacks
+//! arrive a few frames after submission, so each frame in flight keeps its
+//! own set of slots, and [`timings`](GpuTimer::timings) always reports the
+//! most recently resolved frame.
+//!
+//! Timing is only active when [`GpuCapabilities::gpu_timing`] is set; on
+//! other adapters every call is a cheap no-op.
+
+use crate::GpuCapabilities;
+
+/// Frames whose queries may be awaiting readback at the same time.
+pub const TIMING_FRAMES_IN_FLIGHT: usize = 3;
+
+/// Maximum passes timed per frame; later passes are not timed.
+pub const MAX_TIMED_PASSES: u32 = 32;
+
+/// Query indices to write timestamps to at the start and end of a pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PassQueries {
+    /// Index written when the pass begins.
+    pub begin: u32,
+    /// Index written when the pass ends.
+    pub end: u32,
+}
+
+/// GPU time spent in one pass.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassTiming {
+    /// Name the pass was timed under.
+    pub name: String,
+    /// GPU time in milliseconds.
+    pub gpu_ms: f64,
+}
+
+/// Passes recorded for one frame in flight.
+#[derive(Debug, Default)]
+struct FrameSlot {
+    /// Frame recorded into this slot, `None` once resolved or dropped.
+    frame: Option<u64>,
+    passes: Vec<String>,
+}
+
+/// Allocates timestamp queries per pass and resolves them into timings.
+///
+/// # Example
+///
+/// ```
+/// use render::capabilities::{GpuCapabilities, GpuFeatures};
+/// use render::gpu_timing::GpuTimer;
+/// use render::RenderLimits;
+///
+/// let features = GpuFeatures { timestamp_query: true, ..GpuFeatures::default() };
+/// let caps = GpuCapabilities::from_adapter("GPU", features, RenderLimits::for_target());
+/// // One tick per nanosecond, as reported by the queue's timestamp period
+/// let mut timer = GpuTimer::new(&caps, 1.0);
+///
+/// timer.begin_frame(0);
+/// let shadows = timer.begin_pass("shadows").unwrap();
+/// assert_eq!((shadows.begin, shadows.end), (0, 1));
+/// assert_eq!(timer.queries_used(), 2);
+///
+/// // Two frames later the readback for frame 0 arrives
+/// assert!(timer.resolve(0, &[1_000_000, 3_500_000]));
+/// assert_eq!(timer.timings()[0].name, "shadows");
+/// assert!((timer.timings()[0].gpu_ms - 2.5).abs() < 1e-9);
+/// ```
+#[derive(Debug)]
+pub struct GpuTimer {
+    enabled: bool,
+    /// Nanoseconds per timestamp tick.
+    period_ns: f64,
+    slots: [FrameSlot; TIMING_FRAMES_IN_FLIGHT],
+    current: Option<usize>,
+    latest: Vec<PassTiming>,
+    latest_frame: Option<u64>,
+}
+
+impl GpuTimer {
+    /// Creates a timer for an adapter.
+    ///
+    /// `timestamp_period_ns` is the queue's timestamp period: nanoseconds
+    /// per timestamp tick.
+    #[must_use]
+    pub fn new(capabilities: &GpuCapabilities, timestamp_period_ns: f32) -> Self {
+        Self {
+            enabled: capabilities.gpu_timing(),
+            period_ns: f64::from(timestamp_period_ns),
+            slots: Default::default(),
+            current: None,
+            latest: Vec::new(),
+            latest_frame: None,
+        }
+    }
+
+    /// Returns `true` if the adapter supports timestamp queries.
+    #[must_use]
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the number of queries each frame's query set must hold.
+    #[must_use]
+    pub const fn query_set_size() -> u32 {
+        MAX_TIMED_PASSES * 2
+    }
+
+    /// Starts recording `frame`.
+    ///
+    /// Returns the index of the frame's query set and readback buffer, or
+    /// `None` if timing is disabled. A frame still awaiting readback in that
+    /// slot is dropped.
+    pub fn begin_frame(&mut self, frame: u64) -> Option<usize> {
+        if !self.enabled {
+            return None;
+        }
+        let index = slot_index(frame);
+        let slot = &mut self.slots[index];
+        slot.frame = Some(frame);
+        slot.passes.clear();
+        self.current = Some(index);
+        Some(index)
+    }
+
+    /// Allocates queries for a pass in the current frame.
+    ///
+    /// Returns `None` if timing is disabled, no frame was begun, or the frame
+    /// already times [`MAX_TIMED_PASSES`] passes.
+    pub fn begin_pass(&mut self, name: &str) -> Option<PassQueries> {
+        let slot = &mut self.slots[self.current?];
+        let index = u32::try_from(slot.passes.len()).ok()?;
+        if index >= MAX_TIMED_PASSES {
+            return None;
+        }
+        slot.passes.push(name.to_owned());
+        Some(PassQueries {
+            begin: index * 2,
+            end: index * 2 + 1,
+        })
+    }
+
+    /// Returns the number of queries written in the current frame, i.e. the
+    /// range to resolve into the readback buffer when the frame ends.
+    #[must_use]
+    pub fn queries_used(&self) -> u32 {
+        self.current.map_or(0, |index| {
+            u32::try_from(self.slots[index].passes.len()).map_or(0, |passes| passes * 2)
+        })
+    }
+
+    /// Converts the timestamps read back for `frame` into pass timings.
+    ///
+    /// `timestamps` holds the raw query values in query order. Returns
+    /// `false` if the frame is unknown, was dropped, or is older than the
+    /// timings already reported.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn resolve(&mut self, frame: u64, timestamps: &[u64]) -> bool {
+        let slot = &mut self.slots[slot_index(frame)];
+        if slot.frame != Some(frame) || self.latest_frame.is_some_and(|latest| latest > frame) {
+            return false;
+        }
+        slot.frame = None;
+        if self.current == Some(slot_index(frame)) {
+            self.current = None;
+        }
+
+        let period_ns = self.period_ns;
+        self.latest = slot
+            .passes
+            .iter()
+            .zip(timestamps.chunks_exact(2))
+            .map(|(name, pair)| PassTiming {
+                name: name.clone(),
+                gpu_ms: pair[1].saturating_sub(pair[0]) as f64 * period_ns / 1_000_000.0,
+            })
+            .collect();
+        self.latest_frame = Some(frame);
+        true
+    }
+
+    /// Returns the pass timings of the most recently resolved frame.
+    #[must_use]
+    pub fn timings(&self) -> &[PassTiming] {
+        &self.latest
+    }
+
+    /// Returns the frame [`timings`](Self::timings) belong to.
+    #[must_use]
+    pub fn timings_frame(&self) -> Option<u64> {
+        self.latest_frame
+    }
+
+    /// Returns the total GPU time of the most recently resolved frame.
+    #[must_use]
+    pub fn total_ms(&self) -> f64 {
+        self.latest.iter().map(|timing| timing.gpu_ms).sum()
+    }
+}
+
+/// Returns the slot a frame records into.
+#[allow(clippy::cast_possible_truncation)]
+fn slot_index(frame: u64) -> usize {
+    (frame % TIMING_FRAMES_IN_FLIGHT as u64) as usize
+}
@@ -0,0 +1,86 @@
+//! Unit tests for GPU pass timing.
+
+use crate::capabilities::{GpuCapabilities, GpuFeatures};
+use crate::gpu_timing::{GpuTimer, MAX_TIMED_PASSES, TIMING_FRAMES_IN_FLIGHT};
+use crate::RenderLimits;
+
+fn timer() -> GpuTimer {
+    let features = GpuFeatures {
+        timestamp_query: true,
+        ..GpuFeatures::default()
+    };
+    let caps = GpuCapabilities::from_adapter("GPU", features, RenderLimits::for_target());
+    GpuTimer::new(&caps, 1.0)
+}
+
+/// Verifies adapters without timestamp queries never allocate queries.
+#[test]
+fn disabled_without_timestamp_queries() {
+    let mut timer = GpuTimer::new(&GpuCapabilities::baseline(), 1.0);
+
+    assert!(!timer.is_enabled());
+    assert_eq!(timer.begin_frame(0), None);
+    assert_eq!(timer.begin_pass("main"), None);
+    assert_eq!(timer.queries_used(), 0);
+}
+
+/// Verifies frames in flight resolve independently, a few frames later.
+#[test]
+fn resolves_frames_in_flight() {
+    let mut timer = timer();
+
+    timer.begin_frame(0);
+    timer.begin_pass("shadows");
+    timer.begin_pass("main");
+    timer.begin_frame(1);
+    timer.begin_pass("main");
+
+    assert!(timer.resolve(0, &[0, 2_000_000, 2_000_000, 6_000_000]));
+    assert_eq!(timer.timings().len(), 2);
+    assert!((timer.total_ms() - 6.0).abs() < 1e-9);
+
+    assert!(timer.resolve(1, &[0, 1_000_000]));
+    assert_eq!(timer.timings_frame(), Some(1));
+    assert_eq!(timer.timings()[0].name, "main");
+}
+
+/// Verifies a frame overwritten before its readback is not resolved.
+#[test]
+fn overwritten_frame_is_dropped() {
+    let mut timer = timer();
+
+    timer.begin_frame(0);
+    timer.begin_pass("main");
+    timer.begin_frame(TIMING_FRAMES_IN_FLIGHT as u64);
+
+    assert!(!timer.resolve(0, &[0, 1]));
+    assert!(timer.timings().is_empty());
+}
+
+/// Verifies late readbacks never replace newer timings.
+#[test]
+fn stale_readback_is_ignored() {
+    let mut timer = timer();
+
+    timer.begin_frame(0);
+    timer.begin_pass("main");
+    timer.begin_frame(1);
+    timer.begin_pass("main");
+
+    assert!(timer.resolve(1, &[0, 1_000_000]));
+    assert!(!timer.resolve(0, &[0, 9_000_000]));
+    assert!((timer.total_ms() - 1.0).abs() < 1e-9);
+}
+
+/// Verifies passes beyond the query budget are not timed.
+#[test]
+fn pass_budget_is_enforced() {
+    let mut timer = timer();
+    timer.begin_frame(0);
+
+    for i in 0..MAX_TIMED_PASSES {
+        assert!(timer.begin_pass(&format!("pass {i}")).is_some());
+    }
+    assert_eq!(timer.begin_pass("one too many"), None);
+    assert_eq!(timer.queries_used(), GpuTimer::query_set_size());
+}
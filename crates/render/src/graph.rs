@@ -0,0 +1,511 @@
+//! Render graph passes and parallel command recording.
+//!
+//! A [`RenderGraph`] lists the passes of a frame in submission order. Each
+//! pass records its own command buffer, e.g. from a per-thread
+//! `wgpu::CommandEncoder`, and may depend on earlier passes whose CPU-side
+//! results it reads. With [`RecordMode::Parallel`] passes are recorded as
+//! [`JobGraph`] jobs on the scheduler's workers, independent passes at the
+//! same time; the recorded buffers are still submitted in graph order, so
+//! the GPU sees the same frame either way.
+//!
+//! Compute passes, such as GPU particle simulation and skinning, are added
+//! with [`RenderGraph::add_compute_pass`]. [`RecordedGraph::submissions`]
+//! groups the recorded buffers into queue submissions: where the adapter
+//! has an async compute queue, compute passes go there and overlap with
+//! graphics work, and every dependency crossing queues becomes a fence
+//! wait. Otherwise everything is one submission to the graphics queue.
+//!
+//! [`RecordingStats`] compares the time the calling thread spent waiting
+//! for recording with the CPU time the passes took, showing how much
+//! render time parallel recording moved off the main thread.
+
+use crate::capabilities::ComputeQueue;
+use anyhow::Context as _;
+use scheduler::JobGraph;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::trace;
+
+/// Boxed pass body recording a command buffer.
+type Recorder<'a, C> = Box<dyn FnOnce() -> anyhow::Result<C> + Send + 'a>;
+
+/// Handle identifying a pass within a [`RenderGraph`].
+///
+/// A handle is only meaningful for the graph that created it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PassHandle(usize);
+
+/// Queue a pass's command buffer is submitted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PassQueue {
+    /// The graphics queue.
+    #[default]
+    Graphics,
+    /// The async compute queue, or the graphics queue on adapters without
+    /// one.
+    Compute,
+}
+
+/// A pass and the passes it depends on.
+struct Pass<'a, C> {
+    name: String,
+    queue: PassQueue,
+    dependencies: Vec<PassHandle>,
+    record: Recorder<'a, C>,
+}
+
+/// How a [`RenderGraph`] records its passes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecordMode {
+    /// One pass after another on the calling thread.
+    Serial,
+    /// On the scheduler's workers, independent passes in parallel.
+    #[default]
+    Parallel,
+}
+
+/// A recorded pass.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedPass<C> {
+    /// Pass name.
+    pub name: String,
+    /// Queue the pass was added for.
+    pub queue: PassQueue,
+    /// Passes whose results the pass reads.
+    pub dependencies: Vec<PassHandle>,
+    /// The recorded command buffer.
+    pub commands: C,
+    /// CPU time spent recording.
+    pub cpu_time: Duration,
+}
+
+/// Timing of one recording of a [`RenderGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RecordingStats {
+    /// Passes recorded.
+    pub passes: u32,
+    /// How the passes were recorded.
+    pub mode: RecordMode,
+    /// Time the calling thread spent recording or waiting for workers.
+    pub main_thread: Duration,
+    /// CPU time of all passes together.
+    pub recording: Duration,
+}
+
+impl RecordingStats {
+    /// Returns how many times longer recording would have blocked the
+    /// calling thread if done serially, 1.0 for an empty graph.
+    #[must_use]
+    pub fn speedup(&self) -> f64 {
+        if self.main_thread.is_zero() {
+            return 1.0;
+        }
+        self.recording.as_secs_f64() / self.main_thread.as_secs_f64()
+    }
+}
+
+/// The command buffers of a frame, in submission order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedGraph<C> {
+    /// Recorded passes, in graph order.
+    pub passes: Vec<RecordedPass<C>>,
+    /// Recording timing.
+    pub stats: RecordingStats,
+}
+
+impl<C> RecordedGraph<C> {
+    /// Returns the command buffers in graph order.
+    #[must_use]
+    pub fn into_command_buffers(self) -> Vec<C> {
+        self.passes.into_iter().map(|pass| pass.commands).collect()
+    }
+
+    /// Groups the command buffers into queue submissions, in the order they
+    /// are submitted.
+    ///
+    /// With [`ComputeQueue::Graphics`], or without compute passes, the whole
+    /// frame is one graphics submission in graph order. With
+    /// [`ComputeQueue::Async`], consecutive passes of a queue share a
+    /// submission, and a submission is cut where a pass reads the result of
+    /// a pass on the other queue, waiting for that pass's submission to
+    /// signal its fence.
+    #[must_use]
+    pub fn submissions(self, compute: ComputeQueue) -> Vec<Submission<C>> {
+        let single_queue = compute == ComputeQueue::Graphics
+            || self
+                .passes
+                .iter()
+                .all(|pass| pass.queue == PassQueue::Graphics);
+        let mut queues = SubmissionQueues::default();
+        // Fence value of the submission holding each pass.
+        let mut fences: Vec<FenceWait> = Vec::with_capacity(self.passes.len());
+        for pass in self.passes {
+            let queue = if single_queue {
+                PassQueue::Graphics
+            } else {
+                pass.queue
+            };
+            let mut waits: Vec<FenceWait> = Vec::new();
+            for dependency in &pass.dependencies {
+                let fence = fences[dependency.0];
+                if fence.queue == queue {
+                    continue;
+                }
+                queues.close_if(fence);
+                match waits.iter_mut().find(|wait| wait.queue == fence.queue) {
+                    Some(wait) => wait.value = wait.value.max(fence.value),
+                    None => waits.push(fence),
+                }
+            }
+            fences.push(queues.push(queue, waits, pass.name, pass.commands));
+        }
+        let submissions = queues.finish();
+        trace!(
+            target: "render::graph",
+            submissions = submissions.len(),
+            compute = submissions
+                .iter()
+                .filter(|submission| submission.queue == PassQueue::Compute)
+                .count(),
+            "grouped render graph submissions"
+        );
+        submissions
+    }
+}
+
+/// A submission's completion on a queue, as the fence value it signals.
+///
+/// Values count from 1 within a frame; a backend with one fence per queue
+/// offsets them by the values reached in earlier frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FenceWait {
+    /// Queue whose fence is waited on.
+    pub queue: PassQueue,
+    /// Fence value to wait for.
+    pub value: u64,
+}
+
+/// Command buffers submitted to a queue together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission<C> {
+    /// Queue submitted to.
+    pub queue: PassQueue,
+    /// Fences to wait for before the command buffers run.
+    pub waits: Vec<FenceWait>,
+    /// Value the queue's fence is signaled with once the command buffers
+    /// complete.
+    pub signal: u64,
+    /// Names of the passes, in submission order.
+    pub passes: Vec<String>,
+    /// The command buffers, in submission order.
+    pub command_buffers: Vec<C>,
+}
+
+/// Submissions being grouped by [`RecordedGraph::submissions`].
+struct SubmissionQueues<C> {
+    /// Closed submissions, in submission order.
+    closed: Vec<Submission<C>>,
+    /// Open submission of the graphics and of the compute queue.
+    open: [Option<Submission<C>>; 2],
+    /// Last fence value handed out per queue.
+    signaled: [u64; 2],
+}
+
+impl<C> Default for SubmissionQueues<C> {
+    fn default() -> Self {
+        Self {
+            closed: Vec::new(),
+            open: [None, None],
+            signaled: [0; 2],
+        }
+    }
+}
+
+impl<C> SubmissionQueues<C> {
+    /// Closes the open submission signaling `fence`, so work on the other
+    /// queue can wait for it.
+    fn close_if(&mut self, fence: FenceWait) {
+        let slot = &mut self.open[fence.queue as usize];
+        if slot.as_ref().is_some_and(|open| open.signal == fence.value) {
+            self.closed.extend(slot.take());
+        }
+    }
+
+    /// Adds a pass to `queue`, opening a new submission unless the open one
+    /// already waits for `waits`, and returns the fence the pass signals.
+    fn push(
+        &mut self,
+        queue: PassQueue,
+        waits: Vec<FenceWait>,
+        name: String,
+        commands: C,
+    ) -> FenceWait {
+        let index = queue as usize;
+        let covered = self.open[index].as_ref().is_some_and(|open| {
+            waits.iter().all(|wait| {
+                open.waits
+                    .iter()
+                    .any(|have| have.queue == wait.queue && have.value >= wait.value)
+            })
+        });
+        if !covered {
+            self.closed.extend(self.open[index].take());
+            self.signaled[index] += 1;
+            self.open[index] = Some(Submission {
+                queue,
+                waits,
+                signal: self.signaled[index],
+                passes: Vec::new(),
+                command_buffers: Vec::new(),
+            });
+        }
+        let open = self.open[index]
+            .as_mut()
+            .expect("a submission was opened above");
+        open.passes.push(name);
+        open.command_buffers.push(commands);
+        FenceWait {
+            queue,
+            value: open.signal,
+        }
+    }
+
+    /// Closes the open submissions, compute first, and returns them all.
+    fn finish(mut self) -> Vec<Submission<C>> {
+        let [graphics, compute] = self.open;
+        self.closed.extend(compute);
+        self.closed.extend(graphics);
+        self.closed
+    }
+}
+
+/// The passes of a frame, in submission order.
+///
+/// Passes declare dependencies on the handles of previously added passes,
+/// so the order they are added in is always a valid submission order.
+///
+/// # Example
+///
+/// ```
+/// use render::graph::{RecordMode, RenderGraph};
+///
+/// let mut graph = RenderGraph::new();
+/// let shadows = graph.add_pass("shadows", &[], || Ok(vec!["draw casters"])).unwrap();
+/// let reflection = graph.add_pass("reflection", &[], || Ok(vec!["draw mirror"])).unwrap();
+/// graph
+///     .add_pass("main", &[shadows, reflection], || Ok(vec!["draw scene"]))
+///     .unwrap();
+///
+/// let recorded = graph.record(RecordMode::Parallel).unwrap();
+/// assert_eq!(recorded.stats.passes, 3);
+/// assert_eq!(
+///     recorded.into_command_buffers(),
+///     [["draw casters"], ["draw mirror"], ["draw scene"]]
+/// );
+/// ```
+pub struct RenderGraph<'a, C> {
+    passes: Vec<Pass<'a, C>>,
+}
+
+impl<C> Default for RenderGraph<'_, C> {
+    fn default() -> Self {
+        Self { passes: Vec::new() }
+    }
+}
+
+impl<C> std::fmt::Debug for RenderGraph<'_, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RenderGraph")
+            .field(
+                "passes",
+                &self
+                    .passes
+                    .iter()
+                    .map(|pass| (&pass.name, &pass.dependencies))
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl<'a, C: Send + 'a> RenderGraph<'a, C> {
+    /// Creates an empty graph.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a pass submitted after every pass added before it, recorded by
+    /// `record` once all of `dependencies` are recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any dependency handle does not belong to a pass
+    /// previously added to this graph.
+    pub fn add_pass<F>(
+        &mut self,
+        name: &str,
+        dependencies: &[PassHandle],
+        record: F,
+    ) -> anyhow::Result<PassHandle>
+    where
+        F: FnOnce() -> anyhow::Result<C> + Send + 'a,
+    {
+        self.push_pass(name, PassQueue::Graphics, dependencies, Box::new(record))
+    }
+
+    /// Adds a compute pass, e.g. particle simulation or skinning, submitted
+    /// to the async compute queue where the adapter has one; see
+    /// [`add_pass`](Self::add_pass).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any dependency handle does not belong to a pass
+    /// previously added to this graph.
+    pub fn add_compute_pass<F>(
+        &mut self,
+        name: &str,
+        dependencies: &[PassHandle],
+        record: F,
+    ) -> anyhow::Result<PassHandle>
+    where
+        F: FnOnce() -> anyhow::Result<C> + Send + 'a,
+    {
+        self.push_pass(name, PassQueue::Compute, dependencies, Box::new(record))
+    }
+
+    fn push_pass(
+        &mut self,
+        name: &str,
+        queue: PassQueue,
+        dependencies: &[PassHandle],
+        record: Recorder<'a, C>,
+    ) -> anyhow::Result<PassHandle> {
+        if let Some(unknown) = dependencies.iter().find(|dep| dep.0 >= self.passes.len()) {
+            anyhow::bail!("pass `{name}` depends on unknown pass handle {}", unknown.0);
+        }
+        let handle = PassHandle(self.passes.len());
+        self.passes.push(Pass {
+            name: name.to_owned(),
+            queue,
+            dependencies: dependencies.to_vec(),
+            record,
+        });
+        Ok(handle)
+    }
+
+    /// Returns the number of passes.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.passes.len()
+    }
+
+    /// Returns `true` if the graph has no passes.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.passes.is_empty()
+    }
+
+    /// Records every pass, emitting the timing as a `trace` event on the
+    /// `render::graph` target.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of a pass, or an error describing its panic
+    /// when recording in parallel. No command buffers are returned then.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn record(self, mode: RecordMode) -> anyhow::Result<RecordedGraph<C>> {
+        let start = Instant::now();
+        let passes = match mode {
+            RecordMode::Serial => record_serial(self.passes)?,
+            RecordMode::Parallel => record_parallel(self.passes)?,
+        };
+        let stats = RecordingStats {
+            passes: passes.len() as u32,
+            mode,
+            main_thread: start.elapsed(),
+            recording: passes.iter().map(|pass| pass.cpu_time).sum(),
+        };
+        trace!(
+            target: "render::graph",
+            passes = stats.passes,
+            mode = ?stats.mode,
+            main_thread_us = stats.main_thread.as_micros(),
+            recording_us = stats.recording.as_micros(),
+            "recorded render graph"
+        );
+        Ok(RecordedGraph { passes, stats })
+    }
+}
+
+/// Records `passes` in order on the calling thread.
+fn record_serial<C>(passes: Vec<Pass<'_, C>>) -> anyhow::Result<Vec<RecordedPass<C>>> {
+    passes
+        .into_iter()
+        .map(|pass| {
+            let start = Instant::now();
+            let commands =
+                (pass.record)().with_context(|| format!("pass `{}` failed", pass.name))?;
+            Ok(RecordedPass {
+                name: pass.name,
+                queue: pass.queue,
+                dependencies: pass.dependencies,
+                commands,
+                cpu_time: start.elapsed(),
+            })
+        })
+        .collect()
+}
+
+/// Records `passes` as jobs of a [`JobGraph`], one per pass.
+fn record_parallel<'a, C: Send + 'a>(
+    passes: Vec<Pass<'a, C>>,
+) -> anyhow::Result<Vec<RecordedPass<C>>> {
+    let slots: Vec<Mutex<Option<(C, Duration)>>> =
+        passes.iter().map(|_| Mutex::new(None)).collect();
+    let mut infos = Vec::with_capacity(passes.len());
+    {
+        let mut jobs = JobGraph::new();
+        let mut handles = Vec::with_capacity(passes.len());
+        for (pass, slot) in passes.into_iter().zip(&slots) {
+            let dependencies: Vec<_> = pass
+                .dependencies
+                .iter()
+                .map(|dependency| handles[dependency.0])
+                .collect();
+            let record = pass.record;
+            handles.push(jobs.add_job(&pass.name, &dependencies, move || {
+                let start = Instant::now();
+                let commands = record()?;
+                let elapsed = start.elapsed();
+                *slot
+                    .lock()
+                    .map_err(|_| anyhow::anyhow!("command buffer slot poisoned"))? =
+                    Some((commands, elapsed));
+                Ok(())
+            })?);
+            infos.push((pass.name, pass.queue, pass.dependencies));
+        }
+        jobs.run()?;
+    }
+    infos
+        .into_iter()
+        .zip(slots)
+        .map(|((name, queue, dependencies), slot)| {
+            let (commands, cpu_time) = slot
+                .into_inner()
+                .ok()
+                .flatten()
+                .with_context(|| format!("pass `{name}` recorded nothing"))?;
+            Ok(RecordedPass {
+                name,
+                queue,
+                dependencies,
+                commands,
+                cpu_time,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,273 @@
+//! Unit tests for render graph recording.
+
+use crate::capabilities::ComputeQueue;
+use crate::graph::{FenceWait, PassQueue, RecordMode, RenderGraph};
+use std::sync::{Barrier, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Runs `f` on a pool of four workers, independent of the machine's cores.
+fn on_workers<R: Send>(f: impl FnOnce() -> R + Send) -> R {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .unwrap()
+        .install(f)
+}
+
+/// Builds a graph of `count` independent passes, each sleeping `work`.
+fn sleeping_passes(count: usize, work: Duration) -> RenderGraph<'static, usize> {
+    let mut graph = RenderGraph::new();
+    for index in 0..count {
+        graph
+            .add_pass(&format!("pass-{index}"), &[], move || {
+                thread::sleep(work);
+                Ok(index)
+            })
+            .unwrap();
+    }
+    graph
+}
+
+/// Verifies both modes return the command buffers in graph order.
+#[test]
+fn submits_in_graph_order() {
+    for mode in [RecordMode::Serial, RecordMode::Parallel] {
+        let recorded = sleeping_passes(8, Duration::ZERO).record(mode).unwrap();
+        assert_eq!(recorded.stats.passes, 8);
+        assert_eq!(recorded.stats.mode, mode);
+        assert_eq!(recorded.passes[3].name, "pass-3");
+        assert_eq!(recorded.into_command_buffers(), (0..8).collect::<Vec<_>>());
+    }
+}
+
+/// Verifies independent passes are recorded at the same time.
+#[test]
+fn records_independent_passes_in_parallel() {
+    let barrier = Barrier::new(2);
+    let mut graph = RenderGraph::new();
+    for name in ["shadows", "reflection"] {
+        let barrier = &barrier;
+        graph
+            .add_pass(name, &[], move || {
+                // Deadlocks unless both passes record concurrently.
+                barrier.wait();
+                Ok(name)
+            })
+            .unwrap();
+    }
+    let recorded = on_workers(|| graph.record(RecordMode::Parallel)).unwrap();
+    assert_eq!(recorded.into_command_buffers(), ["shadows", "reflection"]);
+}
+
+/// Verifies a pass records only after the passes it depends on.
+#[test]
+fn dependencies_record_first() {
+    let order = Mutex::new(Vec::new());
+    let mut graph = RenderGraph::new();
+    let culling = graph
+        .add_pass("culling", &[], || {
+            thread::sleep(Duration::from_millis(5));
+            order.lock().unwrap().push("culling");
+            Ok(())
+        })
+        .unwrap();
+    graph
+        .add_pass("main", &[culling], || {
+            order.lock().unwrap().push("main");
+            Ok(())
+        })
+        .unwrap();
+    graph.record(RecordMode::Parallel).unwrap();
+    assert_eq!(*order.lock().unwrap(), ["culling", "main"]);
+}
+
+/// Verifies parallel recording of many passes blocks the calling thread
+/// for less than their combined recording time.
+#[test]
+fn parallel_reduces_main_thread_time() {
+    let work = Duration::from_millis(10);
+    let serial = sleeping_passes(8, work)
+        .record(RecordMode::Serial)
+        .unwrap()
+        .stats;
+    assert!(serial.main_thread >= serial.recording);
+    assert!(serial.recording >= work * 8);
+
+    let parallel = on_workers(|| sleeping_passes(8, work).record(RecordMode::Parallel))
+        .unwrap()
+        .stats;
+    assert!(parallel.recording >= work * 8);
+    assert!(
+        parallel.main_thread < serial.main_thread,
+        "{parallel:?} vs {serial:?}"
+    );
+    assert!(parallel.speedup() > 1.0, "{parallel:?}");
+}
+
+/// Verifies failures name the pass and unknown handles are rejected.
+#[test]
+fn reports_failing_pass() {
+    for mode in [RecordMode::Serial, RecordMode::Parallel] {
+        let mut graph = RenderGraph::<()>::new();
+        graph
+            .add_pass("bloom", &[], || anyhow::bail!("no target"))
+            .unwrap();
+        let error = format!("{:#}", graph.record(mode).unwrap_err());
+        assert!(
+            error.contains("`bloom`") && error.contains("no target"),
+            "{error}"
+        );
+    }
+
+    let mut graph = RenderGraph::<()>::new();
+    let mut other = RenderGraph::<()>::new();
+    other.add_pass("a", &[], || Ok(())).unwrap();
+    let foreign = other.add_pass("b", &[], || Ok(())).unwrap();
+    assert!(graph.add_pass("main", &[foreign], || Ok(())).is_err());
+}
+
+/// Builds a frame simulating particles and skinning in compute before the
+/// main pass, with shadows independent of both.
+fn compute_frame() -> RenderGraph<'static, &'static str> {
+    let mut graph = RenderGraph::new();
+    let particles = graph
+        .add_compute_pass("particles", &[], || Ok("simulate particles"))
+        .unwrap();
+    let skinning = graph
+        .add_compute_pass("skinning", &[], || Ok("skin meshes"))
+        .unwrap();
+    let shadows = graph
+        .add_pass("shadows", &[], || Ok("draw casters"))
+        .unwrap();
+    let main = graph
+        .add_pass("main", &[particles, skinning, shadows], || Ok("draw scene"))
+        .unwrap();
+    graph.add_pass("post", &[main], || Ok("tonemap")).unwrap();
+    graph
+}
+
+/// Verifies adapters without an async compute queue submit the whole
+/// frame to the graphics queue in graph order.
+#[test]
+fn single_queue_fallback() {
+    let recorded = compute_frame().record(RecordMode::Serial).unwrap();
+    assert_eq!(recorded.passes[0].queue, PassQueue::Compute);
+
+    let submissions = recorded.submissions(ComputeQueue::Graphics);
+    assert_eq!(submissions.len(), 1);
+    assert_eq!(submissions[0].queue, PassQueue::Graphics);
+    assert!(submissions[0].waits.is_empty());
+    assert_eq!(
+        submissions[0].command_buffers,
+        [
+            "simulate particles",
+            "skin meshes",
+            "draw casters",
+            "draw scene",
+            "tonemap"
+        ]
+    );
+}
+
+/// Verifies compute passes overlap with independent graphics work and
+/// passes reading their results wait on the compute fence.
+#[test]
+fn async_compute_overlaps_graphics() {
+    let submissions = compute_frame()
+        .record(RecordMode::Serial)
+        .unwrap()
+        .submissions(ComputeQueue::Async);
+
+    let summary: Vec<_> = submissions
+        .iter()
+        .map(|s| (s.queue, s.signal, s.waits.clone(), s.passes.clone()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (
+                PassQueue::Compute,
+                1,
+                vec![],
+                vec!["particles".to_owned(), "skinning".to_owned()]
+            ),
+            (PassQueue::Graphics, 1, vec![], vec!["shadows".to_owned()]),
+            (
+                PassQueue::Graphics,
+                2,
+                vec![FenceWait {
+                    queue: PassQueue::Compute,
+                    value: 1
+                }],
+                vec!["main".to_owned(), "post".to_owned()]
+            ),
+        ]
+    );
+}
+
+/// Verifies dependencies crossing queues in both directions become fence
+/// waits on the submission of the pass depended on.
+#[test]
+fn async_compute_waits_both_ways() {
+    let mut graph = RenderGraph::new();
+    let depth = graph.add_pass("depth", &[], || Ok(0)).unwrap();
+    let culling = graph
+        .add_compute_pass("culling", &[depth], || Ok(1))
+        .unwrap();
+    let main = graph.add_pass("main", &[culling], || Ok(2)).unwrap();
+    graph.add_compute_pass("reduce", &[main], || Ok(3)).unwrap();
+
+    let submissions = graph
+        .record(RecordMode::Serial)
+        .unwrap()
+        .submissions(ComputeQueue::Async);
+    let waits: Vec<_> = submissions
+        .iter()
+        .map(|s| {
+            (
+                s.queue,
+                s.signal,
+                s.waits.clone(),
+                s.command_buffers.clone(),
+            )
+        })
+        .collect();
+    let fence = |queue, value| vec![FenceWait { queue, value }];
+    assert_eq!(
+        waits,
+        [
+            (PassQueue::Graphics, 1, vec![], vec![0]),
+            (
+                PassQueue::Compute,
+                1,
+                fence(PassQueue::Graphics, 1),
+                vec![1]
+            ),
+            (
+                PassQueue::Graphics,
+                2,
+                fence(PassQueue::Compute, 1),
+                vec![2]
+            ),
+            (
+                PassQueue::Compute,
+                2,
+                fence(PassQueue::Graphics, 2),
+                vec![3]
+            ),
+        ]
+    );
+}
+
+/// Verifies a frame without compute passes is one graphics submission even
+/// with an async compute queue.
+#[test]
+fn async_compute_without_compute_passes() {
+    let submissions = sleeping_passes(3, Duration::ZERO)
+        .record(RecordMode::Serial)
+        .unwrap()
+        .submissions(ComputeQueue::Async);
+    assert_eq!(submissions.len(), 1);
+    assert_eq!(submissions[0].command_buffers, [0, 1, 2]);
+}
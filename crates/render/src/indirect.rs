@@ -0,0 +1,354 @@
+//! GPU-driven indirect drawing with compute culling.
+//!
+//! Scenes with hundreds of thousands of instances cannot afford a CPU loop
+//! over every instance per frame. On the GPU-driven path the per-instance
+//! data ([`GpuInstance`]) and one set of draw arguments per mesh
+//! ([`DrawIndexedIndirect`]) live in storage buffers, uploaded when the
+//! scene changes rather than every frame. Each frame a compute pass of
+//! [`CULL_WORKGROUP_SIZE`] instances per workgroup resets the instance
+//! counts, tests every instance against the view frustum and, when a
+//! [`DepthPyramid`] was built, against the previous frame's depth, and
+//! appends the visible ones to their draw's range of the visible-instance
+//! buffer by atomically incrementing the draw's `instance_count`.
+//!
+//! The CPU then only issues the indirect calls: one multi-draw-indirect per
+//! [`MeshPool`] block where [`IndirectDraws::MultiDraw`] is supported,
+//! otherwise one `draw_indexed_indirect` per mesh. [`IndirectScene::cull`]
+//! is the CPU reference the culling shader is tested against.
+
+use crate::capabilities::IndirectDraws;
+use crate::mesh::Aabb;
+use crate::mesh_pool::{DrawIndexedIndirect, MeshHandle, MeshPool};
+use crate::occlusion::DepthPyramid;
+use anyhow::Context as _;
+use math::{Mat4, Vec4};
+use std::mem::size_of;
+
+/// Instances tested per workgroup of the culling compute pass.
+pub const CULL_WORKGROUP_SIZE: u32 = 64;
+
+/// Marks slots of the visible-instance buffer no instance was written to.
+pub const NO_INSTANCE: u32 = u32::MAX;
+
+/// Per-instance data in the instance storage buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[repr(C)]
+pub struct GpuInstance {
+    /// Local-to-world transform, column-major.
+    pub model: [f32; 16],
+    /// World-space bounds, smallest corner.
+    pub bounds_min: [f32; 3],
+    /// Index of the instance's draw in the draw argument buffer.
+    pub draw: u32,
+    /// World-space bounds, largest corner.
+    pub bounds_max: [f32; 3],
+    /// Caller-chosen identifier, e.g. for picking.
+    pub id: u32,
+}
+
+/// An instance to place in an [`IndirectScene`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InstanceDesc {
+    /// Mesh drawn.
+    pub mesh: MeshHandle,
+    /// Local-to-world transform.
+    pub model: Mat4,
+    /// Bounds of the mesh in local space.
+    pub bounds: Aabb,
+    /// Caller-chosen identifier, e.g. for picking.
+    pub id: u32,
+}
+
+/// Consecutive draws sharing one [`MeshPool`] block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndirectBatch {
+    /// Block whose vertex and index buffers are bound.
+    pub block: u32,
+    /// First draw in the draw argument buffer.
+    pub first_draw: u32,
+    /// Number of draws.
+    pub draw_count: u32,
+}
+
+/// One indirect call issued by the CPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndirectCall {
+    /// Block whose vertex and index buffers are bound.
+    pub block: u32,
+    /// First draw in the draw argument buffer.
+    pub first_draw: u32,
+    /// Draws read by the call, 1 for `draw_indexed_indirect`.
+    pub draw_count: u32,
+}
+
+/// Instance counts of one culling pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GpuCullStats {
+    /// Instances tested.
+    pub instances: u32,
+    /// Instances outside the view frustum.
+    pub frustum_culled: u32,
+    /// Instances hidden in the depth pyramid.
+    pub occlusion_culled: u32,
+}
+
+impl GpuCullStats {
+    /// Returns the instances drawn.
+    #[must_use]
+    pub fn visible(&self) -> u32 {
+        self.instances - self.frustum_culled - self.occlusion_culled
+    }
+}
+
+/// The buffers the culling pass writes for one view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndirectFrame {
+    /// Draw arguments with this view's instance counts.
+    pub draws: Vec<DrawIndexedIndirect>,
+    /// Visible instance indices; draw `d` reads slots
+    /// `first_instance..first_instance + instance_count`, the rest hold
+    /// [`NO_INSTANCE`].
+    pub visible: Vec<u32>,
+    /// Draws grouped by block.
+    pub batches: Vec<IndirectBatch>,
+    /// Culling counts.
+    pub stats: GpuCullStats,
+}
+
+impl IndirectFrame {
+    /// Returns the calls drawing the frame: one per batch with
+    /// [`IndirectDraws::MultiDraw`], one per draw with
+    /// [`IndirectDraws::Loop`].
+    #[must_use]
+    pub fn calls(&self, mode: IndirectDraws) -> Vec<IndirectCall> {
+        match mode {
+            IndirectDraws::MultiDraw => self
+                .batches
+                .iter()
+                .map(|batch| IndirectCall {
+                    block: batch.block,
+                    first_draw: batch.first_draw,
+                    draw_count: batch.draw_count,
+                })
+                .collect(),
+            IndirectDraws::Loop => self
+                .batches
+                .iter()
+                .flat_map(|batch| {
+                    (batch.first_draw..batch.first_draw + batch.draw_count).map(|draw| {
+                        IndirectCall {
+                            block: batch.block,
+                            first_draw: draw,
+                            draw_count: 1,
+                        }
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The instance and draw argument buffers of the GPU-driven path.
+///
+/// Instances are grouped by mesh into one draw each, and draws by mesh pool
+/// block so each block is drawn by a single multi-draw call.
+///
+/// # Example
+///
+/// ```
+/// use math::{Mat4, Vec3};
+/// use render::capabilities::IndirectDraws;
+/// use render::indirect::{IndirectScene, InstanceDesc};
+/// use render::mesh::Aabb;
+/// use render::mesh_pool::MeshPool;
+///
+/// let mut pool = MeshPool::default();
+/// let cube = pool.insert(24, 36).unwrap();
+/// let bounds = Aabb::new(Vec3::splat(-0.1), Vec3::splat(0.1));
+/// let instances = [-0.5, 0.5, 5.0].map(|x| InstanceDesc {
+///     mesh: cube,
+///     model: Mat4::from_translation(Vec3::new(x, 0.0, 0.5)),
+///     bounds,
+///     id: 0,
+/// });
+/// let scene = IndirectScene::build(&pool, instances).unwrap();
+///
+/// let frame = scene.cull(&Mat4::IDENTITY, None);
+/// assert_eq!(frame.stats.visible(), 2);
+/// assert_eq!(frame.draws[0].instance_count, 2);
+/// assert_eq!(frame.calls(IndirectDraws::MultiDraw).len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IndirectScene {
+    /// Instance storage buffer contents.
+    instances: Vec<GpuInstance>,
+    /// Local bounds of each instance, for transform updates.
+    local_bounds: Vec<Aabb>,
+    /// Draw arguments with zero instances.
+    draws: Vec<DrawIndexedIndirect>,
+    /// Draws grouped by block.
+    batches: Vec<IndirectBatch>,
+}
+
+impl IndirectScene {
+    /// Builds the buffers for `instances` of meshes stored in `pool`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an instance's mesh is not in `pool`.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn build(
+        pool: &MeshPool,
+        instances: impl IntoIterator<Item = InstanceDesc>,
+    ) -> anyhow::Result<Self> {
+        let mut instances: Vec<_> = instances.into_iter().collect();
+        let mut ranges = Vec::with_capacity(instances.len());
+        for instance in &instances {
+            let range = pool
+                .get(instance.mesh)
+                .with_context(|| format!("instance {} uses a removed mesh", instance.id))?;
+            ranges.push((range.block, instance.mesh, range));
+        }
+        let mut order: Vec<usize> = (0..instances.len()).collect();
+        order.sort_by_key(|&index| (ranges[index].0, ranges[index].1));
+        instances = order.iter().map(|&index| instances[index]).collect();
+
+        let mut scene = Self::default();
+        for (position, &index) in order.iter().enumerate() {
+            let (block, mesh, range) = ranges[index];
+            let first_instance = position as u32;
+            let new_draw = position == 0 || {
+                let (previous_block, previous_mesh, _) = ranges[order[position - 1]];
+                (previous_block, previous_mesh) != (block, mesh)
+            };
+            if new_draw {
+                scene.draws.push(range.draw(0, first_instance));
+                match scene.batches.last_mut() {
+                    Some(batch) if batch.block == block => batch.draw_count += 1,
+                    _ => scene.batches.push(IndirectBatch {
+                        block,
+                        first_draw: scene.draws.len() as u32 - 1,
+                        draw_count: 1,
+                    }),
+                }
+            }
+            let instance = &instances[position];
+            scene.local_bounds.push(instance.bounds);
+            scene.instances.push(GpuInstance {
+                draw: scene.draws.len() as u32 - 1,
+                id: instance.id,
+                ..GpuInstance::default()
+            });
+            scene.write_transform(position, &instance.model);
+        }
+        Ok(scene)
+    }
+
+    /// Returns the instance storage buffer contents, ordered by draw.
+    #[must_use]
+    #[inline]
+    pub fn instances(&self) -> &[GpuInstance] {
+        &self.instances
+    }
+
+    /// Returns the draw arguments the culling pass starts from, with zero
+    /// instances.
+    #[must_use]
+    #[inline]
+    pub fn draws(&self) -> &[DrawIndexedIndirect] {
+        &self.draws
+    }
+
+    /// Returns the bytes of the instance and visible-instance buffers.
+    #[must_use]
+    pub fn instance_buffer_bytes(&self) -> u64 {
+        self.instances.len() as u64 * (size_of::<GpuInstance>() + size_of::<u32>()) as u64
+    }
+
+    /// Returns the bytes of the draw argument buffer.
+    #[must_use]
+    pub fn draw_buffer_bytes(&self) -> u64 {
+        self.draws.len() as u64 * size_of::<DrawIndexedIndirect>() as u64
+    }
+
+    /// Returns the workgroups the culling pass dispatches.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn workgroups(&self) -> u32 {
+        (self.instances.len() as u32).div_ceil(CULL_WORKGROUP_SIZE)
+    }
+
+    /// Moves instance `index` of [`instances`](Self::instances), updating
+    /// its world bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range.
+    pub fn set_transform(&mut self, index: usize, model: &Mat4) {
+        self.write_transform(index, model);
+    }
+
+    fn write_transform(&mut self, index: usize, model: &Mat4) {
+        let bounds = self.local_bounds[index].transformed(model);
+        let instance = &mut self.instances[index];
+        instance.model = model.to_cols_array();
+        instance.bounds_min = bounds.min.to_array();
+        instance.bounds_max = bounds.max.to_array();
+    }
+
+    /// Culls every instance for a view, as the culling compute pass does:
+    /// against the frustum of `view_proj` and, if given, the depth
+    /// `pyramid`. Visible instances keep their relative order here; on the
+    /// GPU their order within a draw is unspecified.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn cull(&self, view_proj: &Mat4, pyramid: Option<&DepthPyramid>) -> IndirectFrame {
+        let mut draws = self.draws.clone();
+        let mut visible = vec![NO_INSTANCE; self.instances.len()];
+        let mut stats = GpuCullStats {
+            instances: self.instances.len() as u32,
+            ..GpuCullStats::default()
+        };
+        for (index, instance) in self.instances.iter().enumerate() {
+            let bounds = Aabb {
+                min: instance.bounds_min.into(),
+                max: instance.bounds_max.into(),
+            };
+            let clip = bounds
+                .corners()
+                .map(|corner| *view_proj * corner.extend(1.0));
+            if outside_frustum(&clip) {
+                stats.frustum_culled += 1;
+                continue;
+            }
+            if pyramid.is_some_and(|pyramid| pyramid.is_occluded(view_proj, &bounds)) {
+                stats.occlusion_culled += 1;
+                continue;
+            }
+            let draw = &mut draws[instance.draw as usize];
+            visible[(draw.first_instance + draw.instance_count) as usize] = index as u32;
+            draw.instance_count += 1;
+        }
+        IndirectFrame {
+            draws,
+            visible,
+            batches: self.batches.clone(),
+            stats,
+        }
+    }
+}
+
+/// Returns `true` if every corner lies outside the same clip plane.
+fn outside_frustum(clip: &[Vec4; 8]) -> bool {
+    let planes: [fn(Vec4) -> bool; 6] = [
+        |c| c.x < -c.w,
+        |c| c.x > c.w,
+        |c| c.y < -c.w,
+        |c| c.y > c.w,
+        |c| c.z < 0.0,
+        |c| c.z > c.w,
+    ];
+    planes
+        .iter()
+        .any(|outside| clip.iter().all(|&corner| outside(corner)))
+}
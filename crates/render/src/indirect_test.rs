@@ -0,0 +1,206 @@
+//! Unit tests for GPU-driven indirect drawing.
+
+use crate::capabilities::IndirectDraws;
+use crate::indirect::{
+    IndirectCall, IndirectScene, InstanceDesc, CULL_WORKGROUP_SIZE, NO_INSTANCE,
+};
+use crate::mesh::Aabb;
+use crate::mesh_pool::{MeshHandle, MeshPool};
+use crate::occlusion::DepthPyramid;
+use crate::stats::RenderStatsRecorder;
+use math::{Mat4, Vec3};
+
+fn instance(mesh: MeshHandle, x: f32, z: f32, id: u32) -> InstanceDesc {
+    InstanceDesc {
+        mesh,
+        model: Mat4::from_translation(Vec3::new(x, 0.0, z)),
+        bounds: Aabb::new(Vec3::splat(-0.05), Vec3::splat(0.05)),
+        id,
+    }
+}
+
+/// Builds a pool of two small meshes in block 0 and one filling block 1.
+fn pool() -> (MeshPool, [MeshHandle; 3]) {
+    let mut pool = MeshPool::new(100, 300);
+    let a = pool.insert(10, 30).unwrap();
+    let b = pool.insert(10, 60).unwrap();
+    let c = pool.insert(100, 300).unwrap();
+    (pool, [a, b, c])
+}
+
+/// Verifies instances are grouped into one draw per mesh, with draws of a
+/// block adjacent and each draw owning a range of instance slots.
+#[test]
+fn build_groups_instances_by_mesh() {
+    let (pool, [a, b, c]) = pool();
+    let scene = IndirectScene::build(
+        &pool,
+        [
+            instance(c, 0.0, 0.5, 0),
+            instance(b, 0.0, 0.5, 1),
+            instance(a, 0.0, 0.5, 2),
+            instance(b, 0.0, 0.5, 3),
+        ],
+    )
+    .unwrap();
+
+    let ids: Vec<_> = scene.instances().iter().map(|i| i.id).collect();
+    assert_eq!(ids, [2, 1, 3, 0]);
+    let draws: Vec<_> = scene
+        .draws()
+        .iter()
+        .map(|d| {
+            (
+                d.first_index,
+                d.index_count,
+                d.first_instance,
+                d.instance_count,
+            )
+        })
+        .collect();
+    assert_eq!(draws, [(0, 30, 0, 0), (30, 60, 1, 0), (0, 300, 3, 0)]);
+    let draw_of: Vec<_> = scene.instances().iter().map(|i| i.draw).collect();
+    assert_eq!(draw_of, [0, 1, 1, 2]);
+
+    let frame = scene.cull(&Mat4::IDENTITY, None);
+    assert_eq!(
+        frame.calls(IndirectDraws::MultiDraw),
+        [
+            IndirectCall {
+                block: 0,
+                first_draw: 0,
+                draw_count: 2
+            },
+            IndirectCall {
+                block: 1,
+                first_draw: 2,
+                draw_count: 1
+            },
+        ]
+    );
+    let loop_calls: Vec<_> = frame
+        .calls(IndirectDraws::Loop)
+        .iter()
+        .map(|call| (call.block, call.first_draw, call.draw_count))
+        .collect();
+    assert_eq!(loop_calls, [(0, 0, 1), (0, 1, 1), (1, 2, 1)]);
+}
+
+/// Verifies instances of removed meshes are rejected.
+#[test]
+fn build_rejects_removed_mesh() {
+    let (mut pool, [a, ..]) = pool();
+    pool.remove(a);
+    let error = IndirectScene::build(&pool, [instance(a, 0.0, 0.5, 7)]).unwrap_err();
+    assert!(error.to_string().contains("instance 7"), "{error}");
+}
+
+/// Verifies frustum culling counts instances per draw and writes the
+/// visible ones into the draw's slots.
+#[test]
+fn cull_writes_visible_instances() {
+    let (pool, [a, b, _]) = pool();
+    let scene = IndirectScene::build(
+        &pool,
+        [
+            instance(a, -0.5, 0.5, 0),
+            instance(a, 3.0, 0.5, 1),
+            instance(a, 0.5, 0.5, 2),
+            instance(b, 0.0, -2.0, 3),
+        ],
+    )
+    .unwrap();
+
+    let frame = scene.cull(&Mat4::IDENTITY, None);
+    assert_eq!(frame.stats.instances, 4);
+    assert_eq!(frame.stats.frustum_culled, 2);
+    assert_eq!(frame.stats.visible(), 2);
+    assert_eq!(frame.draws[0].instance_count, 2);
+    assert_eq!(frame.draws[1].instance_count, 0);
+    assert_eq!(frame.visible, [0, 2, NO_INSTANCE, NO_INSTANCE]);
+}
+
+/// Verifies instances hidden in the depth pyramid are culled.
+#[test]
+fn cull_uses_depth_pyramid() {
+    let (pool, [a, ..]) = pool();
+    let scene =
+        IndirectScene::build(&pool, [instance(a, 0.0, 0.2, 0), instance(a, 0.0, 0.8, 1)]).unwrap();
+    // A wall filling the screen at depth 0.5.
+    let pyramid = DepthPyramid::build(4, 4, &[0.5; 16]);
+
+    let frame = scene.cull(&Mat4::IDENTITY, Some(&pyramid));
+    assert_eq!(frame.stats.occlusion_culled, 1);
+    assert_eq!(frame.visible[0], 0);
+    assert_eq!(frame.draws[0].instance_count, 1);
+}
+
+/// Verifies moving an instance updates its world bounds.
+#[test]
+fn set_transform_moves_bounds() {
+    let (pool, [a, ..]) = pool();
+    let mut scene = IndirectScene::build(&pool, [instance(a, 5.0, 0.5, 0)]).unwrap();
+    assert_eq!(scene.cull(&Mat4::IDENTITY, None).stats.visible(), 0);
+
+    scene.set_transform(0, &Mat4::from_translation(Vec3::new(0.0, 0.0, 0.5)));
+    let min = Vec3::from(scene.instances()[0].bounds_min);
+    assert!(
+        min.abs_diff_eq(Vec3::new(-0.05, -0.05, 0.45), 1e-6),
+        "{min}"
+    );
+    assert_eq!(scene.cull(&Mat4::IDENTITY, None).stats.visible(), 1);
+}
+
+/// Verifies multi-draw-indirect issues one draw call per block while
+/// counting only visible instances.
+#[test]
+fn stats_count_indirect_calls() {
+    let (pool, [a, b, c]) = pool();
+    let scene = IndirectScene::build(
+        &pool,
+        [
+            instance(a, 0.0, 0.5, 0),
+            instance(b, 0.0, 0.5, 1),
+            instance(b, 9.0, 0.5, 2),
+            instance(c, 0.0, 0.5, 3),
+        ],
+    )
+    .unwrap();
+    let frame = scene.cull(&Mat4::IDENTITY, None);
+
+    for (mode, draw_calls) in [(IndirectDraws::MultiDraw, 2), (IndirectDraws::Loop, 3)] {
+        let mut recorder = RenderStatsRecorder::default();
+        recorder.begin_frame();
+        recorder.draw_indirect(1, &frame, mode);
+        let stats = recorder.end_frame();
+        assert_eq!(stats.draw_calls, draw_calls);
+        assert_eq!(stats.instances, 3);
+        assert_eq!(stats.triangles, 10 + 20 + 100);
+    }
+}
+
+/// Verifies a 100k-instance scene is culled in one dispatch of full
+/// workgroups with the instances outside the view dropped.
+#[test]
+fn culls_large_scene() {
+    let (pool, [a, b, _]) = pool();
+    let count = 100_000_u32;
+    #[allow(clippy::cast_precision_loss)]
+    let instances = (0..count).map(|i| {
+        let mesh = if i % 2 == 0 { a } else { b };
+        // Spread over x in [-2, 2); boxes within x in [-1.05, 1.05] touch
+        // the view, 210 of every 400.
+        instance(mesh, (i % 400) as f32 / 100.0 - 2.0 + 0.005, 0.5, i)
+    });
+    let scene = IndirectScene::build(&pool, instances).unwrap();
+    assert_eq!(scene.workgroups(), count.div_ceil(CULL_WORKGROUP_SIZE));
+
+    let frame = scene.cull(&Mat4::IDENTITY, None);
+    let visible = frame.stats.visible();
+    assert_eq!(visible, 210 * count / 400);
+    assert_eq!(
+        frame.draws.iter().map(|d| d.instance_count).sum::<u32>(),
+        visible
+    );
+    assert_eq!(frame.calls(IndirectDraws::MultiDraw).len(), 1);
+}
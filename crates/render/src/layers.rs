@@ -0,0 +1,118 @@
+//! Render layers for per-camera visibility.
+//!
+//! Every renderable carries a [`RenderLayers`] mask, and so does every
+//! [`Camera`](crate::camera::Camera). A camera only draws renderables whose
+//! mask shares at least one layer with its own, which lets split-screen
+//! players, minimaps, and first-person weapons each see a different subset
+//! of the world. Masks are compared during the visibility stage, before any
+//! draw calls are built.
+
+use std::fmt;
+
+/// Set of up to [`RenderLayers::COUNT`] render layers, stored as a bitmask.
+///
+/// The default mask holds only layer 0, so renderables and cameras that
+/// never touch layers see each other.
+///
+/// # Example
+///
+/// ```
+/// use render::RenderLayers;
+///
+/// const WEAPON: u8 = 1;
+///
+/// let weapon = RenderLayers::layer(WEAPON);
+/// let world_camera = RenderLayers::default();
+/// let weapon_camera = RenderLayers::default().with(WEAPON);
+///
+/// assert!(!world_camera.intersects(weapon));
+/// assert!(weapon_camera.intersects(weapon));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RenderLayers(u32);
+
+impl RenderLayers {
+    /// Number of available layers.
+    pub const COUNT: u8 = 32;
+
+    /// Mask with no layers; never visible to any camera.
+    pub const NONE: Self = Self(0);
+
+    /// Mask with every layer.
+    pub const ALL: Self = Self(u32::MAX);
+
+    /// Creates a mask holding only `layer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `layer` is not below [`COUNT`](Self::COUNT).
+    #[must_use]
+    pub const fn layer(layer: u8) -> Self {
+        Self::NONE.with(layer)
+    }
+
+    /// Creates a mask from raw bits, one bit per layer.
+    #[must_use]
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw bits, one bit per layer.
+    #[must_use]
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Adds `layer` to the mask.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `layer` is not below [`COUNT`](Self::COUNT).
+    #[must_use]
+    pub const fn with(self, layer: u8) -> Self {
+        assert!(layer < Self::COUNT, "render layer out of range");
+        Self(self.0 | 1 << layer)
+    }
+
+    /// Removes `layer` from the mask.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `layer` is not below [`COUNT`](Self::COUNT).
+    #[must_use]
+    pub const fn without(self, layer: u8) -> Self {
+        assert!(layer < Self::COUNT, "render layer out of range");
+        Self(self.0 & !(1 << layer))
+    }
+
+    /// Returns `true` if the mask holds `layer`.
+    #[must_use]
+    pub const fn contains(self, layer: u8) -> bool {
+        layer < Self::COUNT && self.0 & 1 << layer != 0
+    }
+
+    /// Returns `true` if the masks share at least one layer.
+    #[must_use]
+    pub const fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Returns the layers in the mask, in ascending order.
+    pub fn iter(self) -> impl Iterator<Item = u8> {
+        (0..Self::COUNT).filter(move |&layer| self.contains(layer))
+    }
+}
+
+impl Default for RenderLayers {
+    fn default() -> Self {
+        Self::layer(0)
+    }
+}
+
+impl fmt::Debug for RenderLayers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RenderLayers")
+            .field(&format_args!("{:?}", self.iter().collect::<Vec<_>>()))
+            .finish()
+    }
+}
@@ -0,0 +1,56 @@
+//! Unit tests for render layers and camera culling.
+
+use crate::camera::Camera;
+use crate::layers::RenderLayers;
+
+/// Verifies layers can be added, removed, and listed.
+#[test]
+fn layer_mask_operations() {
+    let layers = RenderLayers::layer(3).with(31).with(0).without(0);
+
+    assert!(layers.contains(3));
+    assert!(layers.contains(31));
+    assert!(!layers.contains(0));
+    assert!(!layers.contains(40));
+    assert_eq!(layers.iter().collect::<Vec<_>>(), [3, 31]);
+    assert_eq!(layers.bits(), 1 << 3 | 1 << 31);
+    assert_eq!(RenderLayers::default(), RenderLayers::layer(0));
+}
+
+/// Verifies out-of-range layers are rejected.
+#[test]
+#[should_panic(expected = "render layer out of range")]
+fn layer_out_of_range_panics() {
+    let _ = RenderLayers::layer(RenderLayers::COUNT);
+}
+
+/// Verifies each camera only sees renderables sharing one of its layers.
+#[test]
+fn cameras_cull_by_layers() {
+    let renderables = [
+        ("world", RenderLayers::default()),
+        ("weapon", RenderLayers::layer(1)),
+        ("player2_hud", RenderLayers::layer(2)),
+        ("hidden", RenderLayers::NONE),
+        ("everywhere", RenderLayers::ALL),
+    ];
+    let world = Camera::default();
+    let first_person = Camera {
+        layers: RenderLayers::default().with(1),
+        ..Camera::default()
+    };
+    let inactive = Camera {
+        active: false,
+        ..Camera::default()
+    };
+
+    assert_eq!(
+        world.visible(renderables).collect::<Vec<_>>(),
+        ["world", "everywhere"]
+    );
+    assert_eq!(
+        first_person.visible(renderables).collect::<Vec<_>>(),
+        ["world", "weapon", "everywhere"]
+    );
+    assert_eq!(inactive.visible(renderables).count(), 0);
+}
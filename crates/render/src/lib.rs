@@ -7,9 +7,56 @@
 //!
 //! The render crate handles:
 //! - Graphics device initialization and management
-//! - Render pipeline creation and configuration
-//! - Draw call submission and frame presentation
+//! - Render pipeline creation, cached and compiled in the background
+//!   ([`PipelineCache`])
+//! - Draw call submission and frame presentation, with render graph passes
+//!   recorded in parallel on scheduler workers and compute passes submitted
+//!   to an async compute queue where available ([`RenderGraph`])
+//! - An extract stage copying render data out of the ECS world, so frames
+//!   can render while the next is simulated ([`FramePipeline`])
+//! - Mesh geometry suballocated from pooled vertex and index buffers, drawn
+//!   with multi-draw-indirect, with a fragmentation debug view ([`MeshPool`])
+//! - GPU-driven rendering: compute culling writing indirect draw arguments,
+//!   drawn with multi-draw-indirect where supported ([`IndirectScene`])
+//! - Static batching of level geometry ([`StaticBatches`])
+//! - Hierarchical-Z occlusion culling ([`OcclusionCuller`])
+//! - Screen-space ambient occlusion, SSAO or GTAO, with quality presets and
+//!   `r.ssao.*` cvars ([`AmbientOcclusion`])
+//! - Level of detail switching with cross-fades ([`Lod`])
+//! - Mesh cooking: LOD chain generation, vertex cache, overdraw, and vertex
+//!   fetch optimization, and meshlet building ([`cook_mesh`])
+//! - Orbit, follow, and shake camera controllers ([`OrbitCamera`],
+//!   [`FollowCamera`], [`CameraShake`])
+//! - Per-camera visibility filtered by [`RenderLayers`]
+//! - Render-to-texture targets sampled by materials ([`RenderTarget`])
+//! - Split-screen and multi-viewport rendering ([`Viewport`])
 //! - GPU resource management (buffers, textures, shaders)
+//! - Sprite animation from texture atlas frames ([`SpriteAnimation`])
+//! - 9-slice scaling for sprites and UI panels ([`NineSlice`])
+//! - UI and text batching across widgets with nested clip rectangles
+//!   ([`UiBatcher`])
+//! - Chunked 2D tilemaps imported from Tiled and LDtk ([`Tilemap`])
+//! - Heightmap terrain with quadtree LOD, splat-map layer blending, and
+//!   collision meshes ([`Terrain`])
+//! - Per-scene skies: HDR skyboxes or a procedural sun-driven atmosphere,
+//!   with image-based ambient lighting baked from them ([`Sky`])
+//! - Light probes for diffuse global illumination, baked or updated
+//!   dynamically on capable adapters ([`LightProbes`])
+//! - Projected decals, deferred or forward-clustered, with pooled
+//!   temporary decals ([`Decal`])
+//! - Sorted or weighted blended order-independent transparency
+//!   ([`TransparencyPass`]) and a stock water material ([`Water`])
+//! - Materials with generated uniform and bind group layouts ([`Material`])
+//! - Bind group caching and texture arrays or bindless textures that batch
+//!   draws across textures ([`BindGroupCache`], [`TextureArrays`])
+//! - Linear/sRGB color management and HDR output ([`OutputMode`])
+//! - Adapter capability detection with fallbacks ([`GpuCapabilities`]),
+//!   requested asynchronously as WebGPU requires ([`request_capabilities`])
+//! - Per-pass GPU timing with timestamp queries ([`GpuTimer`])
+//! - Per-frame draw call, triangle, and GPU memory statistics
+//!   ([`RenderStats`])
+//! - Recovery from lost or outdated surfaces ([`SurfaceRecovery`])
+//! - Target-specific device limits, including WebGPU (`wasm` feature)
 //!
 //! # Example
 //!
@@ -25,6 +72,121 @@
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+pub mod adapter;
+#[cfg(test)]
+mod adapter_test;
+pub mod ambient_occlusion;
+pub mod bind_group;
+#[cfg(test)]
+mod bind_group_test;
+pub mod camera;
+pub mod camera_controller;
+#[cfg(test)]
+mod camera_controller_test;
+pub mod capabilities;
+#[cfg(test)]
+mod capabilities_test;
+pub mod color;
+#[cfg(test)]
+mod color_test;
+pub mod cook;
+pub mod decal;
+pub mod extract;
+pub mod gpu_timing;
+#[cfg(test)]
+mod gpu_timing_test;
+pub mod graph;
+#[cfg(test)]
+mod graph_test;
+pub mod indirect;
+#[cfg(test)]
+mod indirect_test;
+pub mod layers;
+#[cfg(test)]
+mod layers_test;
+pub mod light_probes;
+pub mod limits;
+#[cfg(test)]
+mod limits_test;
+pub mod lod;
+#[cfg(test)]
+mod lod_test;
+pub mod material;
+#[cfg(test)]
+mod material_test;
+pub mod mesh;
+pub mod mesh_pool;
+pub mod nine_slice;
+#[cfg(test)]
+mod nine_slice_test;
+pub mod occlusion;
+#[cfg(test)]
+mod occlusion_test;
+pub mod pipeline_cache;
+#[cfg(test)]
+mod pipeline_cache_test;
 pub mod render;
+pub mod sky;
+pub mod sprite;
+#[cfg(test)]
+mod sprite_test;
+pub mod static_batch;
+#[cfg(test)]
+mod static_batch_test;
+pub mod stats;
+#[cfg(test)]
+mod stats_test;
+pub mod surface;
+#[cfg(test)]
+mod surface_test;
+pub mod suspend;
+pub mod target;
+#[cfg(test)]
+mod target_test;
+pub mod terrain;
+pub mod tilemap;
+pub mod transform;
+pub mod transparency;
+pub mod ui_batch;
+#[cfg(test)]
+mod ui_batch_test;
+pub mod viewport;
+#[cfg(test)]
+mod viewport_test;
 
+pub use adapter::{request_capabilities, GpuBackend};
+pub use ambient_occlusion::AmbientOcclusion;
+pub use bind_group::{BindGroupCache, TextureArrays};
+pub use camera::Camera;
+pub use camera_controller::{CameraShake, FollowCamera, OrbitCamera};
+pub use capabilities::GpuCapabilities;
+pub use color::OutputMode;
+pub use cook::{cook_mesh, MeshCookSettings};
+pub use decal::Decal;
+pub use extract::{FramePipeline, RenderExtractor, RenderWorld};
+pub use gpu_timing::{GpuTimer, PassTiming};
+pub use graph::{RecordMode, RenderGraph};
+pub use indirect::IndirectScene;
+pub use layers::RenderLayers;
+pub use light_probes::LightProbes;
+pub use limits::RenderLimits;
+pub use lod::Lod;
+pub use material::{Material, MaterialOverride, MaterialParam};
+pub use mesh_pool::MeshPool;
+pub use nine_slice::{ImageScaling, NineSlice};
+pub use occlusion::OcclusionCuller;
+pub use pipeline_cache::{PipelineCache, PipelineCompiler, PipelineKey};
 pub use render::RustgineRender;
+pub use sky::Sky;
+pub use sprite::{SpriteAnimation, TextureAtlas};
+pub use static_batch::StaticBatches;
+pub use stats::{RenderStats, RenderStatsRecorder};
+pub use surface::{GpuDeviceLost, RenderSurface, SurfaceError, SurfaceRecovery};
+pub use suspend::RenderingPaused;
+pub use target::{RenderTarget, RenderTextures, TextureHandle};
+pub use terrain::Terrain;
+pub use tilemap::Tilemap;
+pub use transform::Transform;
+pub use transparency::{TransparencyPass, Water};
+pub use ui_batch::UiBatcher;
+pub use viewport::Viewport;
@@ -0,0 +1,153 @@
+//! Dynamic diffuse global illumination.
+
+use super::volume::{sphere_directions, IrradianceVolume};
+use crate::capabilities::DDGI_WORKGROUP_INVOCATIONS;
+use crate::sky::Irradiance;
+use math::{Quat, Vec3};
+
+/// What a probe ray found, as reported by the scene's ray tracer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProbeRay {
+    /// The ray left the level; holds the sky's radiance along it.
+    Miss(Vec3),
+    /// The ray hit a surface.
+    Hit {
+        /// World position of the hit.
+        position: Vec3,
+        /// Surface normal at the hit.
+        normal: Vec3,
+        /// Diffuse albedo of the surface.
+        albedo: Vec3,
+        /// Light the surface reflects from direct lights and emission.
+        direct: Vec3,
+    },
+}
+
+/// How quickly dynamic probes follow lighting changes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DdgiSettings {
+    /// Rays traced per probe update.
+    pub rays_per_probe: u32,
+    /// Probes updated per frame, cycling through the volume.
+    pub probes_per_frame: u32,
+    /// Share of a probe's previous lighting kept on each update; higher
+    /// values are less noisy but react more slowly.
+    pub hysteresis: f32,
+}
+
+impl Default for DdgiSettings {
+    fn default() -> Self {
+        Self {
+            rays_per_probe: DDGI_WORKGROUP_INVOCATIONS,
+            probes_per_frame: 512,
+            hysteresis: 0.95,
+        }
+    }
+}
+
+/// An irradiance volume re-lit at runtime by tracing rays from its probes,
+/// in the style of dynamic diffuse global illumination (DDGI).
+///
+/// Each frame a budget of probes traces rays in a freshly rotated set of
+/// directions. Rays hitting a surface return its direct light plus its
+/// albedo times the volume's current light there, so bounces accumulate
+/// over frames. New results are blended into the old with hysteresis.
+///
+/// This is a lite variant: probes store spherical harmonics rather than
+/// octahedral irradiance and depth maps, so there is no visibility test;
+/// [`IrradianceVolume::irradiance_at`]'s normal weighting limits leaks
+/// instead. The GPU update runs only when
+/// [`GpuCapabilities::global_illumination`](crate::GpuCapabilities::global_illumination)
+/// allows it; this CPU implementation is the reference the compute shader
+/// is tested against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DdgiVolume {
+    volume: IrradianceVolume,
+    settings: DdgiSettings,
+    /// Next probe to update.
+    cursor: usize,
+    /// Updates so far, which pick the ray rotation.
+    frame: u32,
+}
+
+impl DdgiVolume {
+    /// Creates a dynamic volume starting from `volume`'s lighting, e.g.
+    /// the level's baked probes, so the first frames are not black.
+    #[must_use]
+    pub fn new(volume: IrradianceVolume, settings: DdgiSettings) -> Self {
+        Self {
+            volume,
+            settings,
+            cursor: 0,
+            frame: 0,
+        }
+    }
+
+    /// Returns the settings.
+    #[must_use]
+    pub fn settings(&self) -> &DdgiSettings {
+        &self.settings
+    }
+
+    /// Returns the current lighting.
+    #[must_use]
+    pub fn volume(&self) -> &IrradianceVolume {
+        &self.volume
+    }
+
+    /// Updates the next probes of this frame's budget; `trace` casts a ray
+    /// from a probe position in a direction.
+    ///
+    /// Every probe updated in one call reads the lighting of the previous
+    /// call, as the compute shader does. Returns the number of probes
+    /// updated.
+    pub fn update(&mut self, mut trace: impl FnMut(Vec3, Vec3) -> ProbeRay) -> usize {
+        let probes = self.volume.probes().len();
+        let budget = (self.settings.probes_per_frame as usize).min(probes);
+        let rotation = ray_rotation(self.frame);
+        let hysteresis = self.settings.hysteresis.clamp(0.0, 1.0);
+        let updated: Vec<(usize, Irradiance)> = (0..budget)
+            .map(|offset| {
+                let index = (self.cursor + offset) % probes;
+                let origin = self.volume.grid().position(index);
+                let traced = Irradiance::from_samples(
+                    sphere_directions(self.settings.rays_per_probe, rotation).map(|direction| {
+                        let radiance = match trace(origin, direction) {
+                            ProbeRay::Miss(sky) => sky,
+                            ProbeRay::Hit {
+                                position,
+                                normal,
+                                albedo,
+                                direct,
+                            } => direct + albedo * self.volume.diffuse(position, normal),
+                        };
+                        (direction, radiance)
+                    }),
+                );
+                let previous = &self.volume.probes()[index];
+                (index, traced.lerp(previous, hysteresis))
+            })
+            .collect();
+        for &(index, irradiance) in &updated {
+            self.volume.probes_mut()[index] = irradiance;
+        }
+        self.cursor = (self.cursor + budget) % probes;
+        self.frame = self.frame.wrapping_add(1);
+        budget
+    }
+}
+
+/// Returns a pseudo-random rotation for the rays of update `frame`, so
+/// successive updates sample different directions.
+#[allow(clippy::cast_precision_loss)]
+fn ray_rotation(frame: u32) -> Quat {
+    if frame == 0 {
+        return Quat::IDENTITY;
+    }
+    let random = |seed: u32| seed.wrapping_mul(0x9E37_79B9).reverse_bits() as f32 / 4_294_967_296.0;
+    let z = random(frame) * 2.0 - 1.0;
+    let phi = random(frame ^ 0x5555_5555) * std::f32::consts::TAU;
+    let radius = (1.0 - z * z).sqrt();
+    let axis = Vec3::new(radius * phi.cos(), radius * phi.sin(), z);
+    Quat::from_axis_angle(axis, random(frame ^ 0xAAAA_AAAA) * std::f32::consts::TAU)
+}
@@ -0,0 +1,93 @@
+//! Unit tests for dynamic diffuse global illumination.
+
+use super::ddgi::{DdgiSettings, DdgiVolume, ProbeRay};
+use super::grid::ProbeGrid;
+use super::volume::IrradianceVolume;
+use math::{UVec3, Vec3};
+
+fn volume() -> IrradianceVolume {
+    IrradianceVolume::new(ProbeGrid::new(Vec3::ZERO, Vec3::ONE, UVec3::new(3, 2, 1)))
+}
+
+fn settings(probes_per_frame: u32, hysteresis: f32) -> DdgiSettings {
+    DdgiSettings {
+        rays_per_probe: 64,
+        probes_per_frame,
+        hysteresis,
+    }
+}
+
+/// Verifies probes cycle through the volume within the per-frame budget.
+#[test]
+fn updates_probes_within_budget() {
+    let mut ddgi = DdgiVolume::new(volume(), settings(4, 0.0));
+    assert_eq!(ddgi.update(|_, _| ProbeRay::Miss(Vec3::ONE)), 4);
+    let lit = |ddgi: &DdgiVolume| {
+        ddgi.volume()
+            .probes()
+            .iter()
+            .filter(|probe| probe.diffuse(Vec3::Y).x > 0.9)
+            .count()
+    };
+    assert_eq!(lit(&ddgi), 4);
+    assert_eq!(ddgi.update(|_, _| ProbeRay::Miss(Vec3::ONE)), 4);
+    assert_eq!(lit(&ddgi), 6);
+
+    let mut large = DdgiVolume::new(volume(), settings(100, 0.0));
+    assert_eq!(large.update(|_, _| ProbeRay::Miss(Vec3::ONE)), 6);
+}
+
+/// Verifies hysteresis blends new lighting into the old gradually.
+#[test]
+fn blends_with_hysteresis() {
+    let mut ddgi = DdgiVolume::new(volume(), settings(6, 0.75));
+    ddgi.update(|_, _| ProbeRay::Miss(Vec3::ONE));
+    let first = ddgi.volume().diffuse(Vec3::ZERO, Vec3::Y).x;
+    assert!((first - 0.25).abs() < 0.02, "{first}");
+    for _ in 0..40 {
+        ddgi.update(|_, _| ProbeRay::Miss(Vec3::ONE));
+    }
+    let settled = ddgi.volume().diffuse(Vec3::ZERO, Vec3::Y).x;
+    assert!((settled - 1.0).abs() < 0.02, "{settled}");
+}
+
+/// Verifies light bounces accumulate over updates inside a closed room.
+#[test]
+fn accumulates_bounces() {
+    let mut ddgi = DdgiVolume::new(volume(), settings(6, 0.0));
+    // Every ray hits a gray wall lit directly to 0.5, facing the probe.
+    let room = |origin: Vec3, direction: Vec3| ProbeRay::Hit {
+        position: origin + direction,
+        normal: -direction,
+        albedo: Vec3::splat(0.5),
+        direct: Vec3::splat(0.5),
+    };
+    ddgi.update(room);
+    let direct = ddgi.volume().diffuse(Vec3::ONE * 0.5, Vec3::Y).x;
+    assert!((direct - 0.5).abs() < 0.02, "{direct}");
+    for _ in 0..20 {
+        ddgi.update(room);
+    }
+    // 0.5 + 0.5 * 0.5 + ... converges to 1.
+    let bounced = ddgi.volume().diffuse(Vec3::ONE * 0.5, Vec3::Y).x;
+    assert!((bounced - 1.0).abs() < 0.05, "{bounced}");
+}
+
+/// Verifies successive updates trace rays in different directions.
+#[test]
+fn rotates_rays_between_updates() {
+    let mut ddgi = DdgiVolume::new(volume(), settings(1, 0.5));
+    let mut first = Vec::new();
+    ddgi.update(|_, direction| {
+        first.push(direction);
+        ProbeRay::Miss(Vec3::ZERO)
+    });
+    let mut second = Vec::new();
+    ddgi.update(|_, direction| {
+        second.push(direction);
+        ProbeRay::Miss(Vec3::ZERO)
+    });
+    assert_eq!(first.len(), 64);
+    assert_ne!(first, second);
+    assert!(second.iter().all(|d| (d.length() - 1.0).abs() < 1e-4));
+}
@@ -0,0 +1,132 @@
+//! Regular grids of light probes.
+
+use math::{Aabb, UVec3, Vec3};
+use serde::{Deserialize, Serialize};
+
+/// Light probes placed at the corners of a regular 3D grid.
+///
+/// Probes are indexed x first, then y, then z, which is also the order
+/// their data is stored in and uploaded to the GPU.
+///
+/// # Example
+///
+/// ```
+/// use math::{UVec3, Vec3};
+/// use render::light_probes::ProbeGrid;
+///
+/// let grid = ProbeGrid::new(Vec3::ZERO, Vec3::splat(2.0), UVec3::new(3, 2, 2));
+/// assert_eq!(grid.len(), 12);
+/// assert_eq!(grid.position(grid.index(UVec3::new(2, 1, 0))), Vec3::new(4.0, 2.0, 0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ProbeGrid {
+    /// Position of the probe at grid coordinates `(0, 0, 0)`.
+    pub origin: Vec3,
+    /// Distance between neighboring probes along each axis.
+    pub spacing: Vec3,
+    /// Number of probes along each axis.
+    pub counts: UVec3,
+}
+
+impl ProbeGrid {
+    /// Creates a grid of `counts` probes starting at `origin`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a count is 0 or a spacing is not positive.
+    #[must_use]
+    pub fn new(origin: Vec3, spacing: Vec3, counts: UVec3) -> Self {
+        assert!(counts.min_element() > 0, "probe grid must not be empty");
+        assert!(
+            spacing.min_element() > 0.0,
+            "probe spacing must be positive"
+        );
+        Self {
+            origin,
+            spacing,
+            counts,
+        }
+    }
+
+    /// Creates a grid covering `bounds` with probes at most `spacing`
+    /// apart, with probes on the bounds' faces.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spacing` is not positive.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn covering(bounds: &Aabb, spacing: f32) -> Self {
+        assert!(spacing > 0.0, "probe spacing must be positive");
+        let extent = bounds.max - bounds.min;
+        let cells = (extent / spacing).ceil().max(Vec3::ONE);
+        let counts = UVec3::new(cells.x as u32, cells.y as u32, cells.z as u32) + UVec3::ONE;
+        Self::new(
+            bounds.min,
+            (extent / cells).max(Vec3::splat(f32::EPSILON)),
+            counts,
+        )
+    }
+
+    /// Returns the number of probes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.counts.x as usize * self.counts.y as usize * self.counts.z as usize
+    }
+
+    /// Returns `true` if the grid has no probes, which [`ProbeGrid::new`]
+    /// rules out.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the index of the probe at grid `coords`.
+    #[must_use]
+    pub fn index(&self, coords: UVec3) -> usize {
+        let coords = coords.min(self.counts - UVec3::ONE);
+        (coords.z as usize * self.counts.y as usize + coords.y as usize) * self.counts.x as usize
+            + coords.x as usize
+    }
+
+    /// Returns the grid coordinates of probe `index`.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn coords(&self, index: usize) -> UVec3 {
+        let (x, y) = (self.counts.x as usize, self.counts.y as usize);
+        UVec3::new(
+            (index % x) as u32,
+            (index / x % y) as u32,
+            (index / (x * y)) as u32,
+        )
+    }
+
+    /// Returns the world position of probe `index`.
+    #[must_use]
+    pub fn position(&self, index: usize) -> Vec3 {
+        self.origin + self.coords(index).as_vec3() * self.spacing
+    }
+
+    /// Returns the eight probes around `point` with their trilinear
+    /// weights, which sum to 1.
+    ///
+    /// Points outside the grid use the nearest face, edge, or corner.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn cell(&self, point: Vec3) -> [(usize, f32); 8] {
+        let last = (self.counts - UVec3::ONE).as_vec3();
+        let local = ((point - self.origin) / self.spacing).clamp(Vec3::ZERO, last);
+        let base = local.floor().min((last - Vec3::ONE).max(Vec3::ZERO));
+        let t = (local - base).min(Vec3::ONE);
+        let base = UVec3::new(base.x as u32, base.y as u32, base.z as u32);
+        std::array::from_fn(|corner| {
+            let offset = UVec3::new(
+                corner as u32 & 1,
+                (corner as u32 >> 1) & 1,
+                (corner as u32 >> 2) & 1,
+            );
+            let weight = Vec3::select(offset.as_vec3().cmpeq(Vec3::ONE), t, Vec3::ONE - t);
+            (self.index(base + offset), weight.x * weight.y * weight.z)
+        })
+    }
+}
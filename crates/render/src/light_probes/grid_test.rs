@@ -0,0 +1,71 @@
+//! Unit tests for probe grids.
+
+use super::grid::ProbeGrid;
+use math::{Aabb, UVec3, Vec3};
+
+fn grid() -> ProbeGrid {
+    ProbeGrid::new(
+        Vec3::new(-1.0, 0.0, 2.0),
+        Vec3::new(1.0, 2.0, 0.5),
+        UVec3::new(4, 3, 2),
+    )
+}
+
+/// Verifies probe indices and grid coordinates round-trip in x, y, z order.
+#[test]
+fn indexes_probes_x_first() {
+    let grid = grid();
+    assert_eq!(grid.len(), 24);
+    assert!(!grid.is_empty());
+    assert_eq!(grid.index(UVec3::new(1, 0, 0)), 1);
+    assert_eq!(grid.index(UVec3::new(0, 1, 0)), 4);
+    assert_eq!(grid.index(UVec3::new(0, 0, 1)), 12);
+    for index in 0..grid.len() {
+        assert_eq!(grid.index(grid.coords(index)), index);
+    }
+    assert_eq!(grid.position(23), Vec3::new(2.0, 4.0, 2.5));
+}
+
+/// Verifies a covering grid spans the bounds with probes at most the
+/// requested spacing apart.
+#[test]
+fn covers_bounds() {
+    let bounds = Aabb::new(Vec3::ZERO, Vec3::new(10.0, 3.0, 0.0));
+    let grid = ProbeGrid::covering(&bounds, 4.0);
+    assert_eq!(grid.counts, UVec3::new(4, 2, 2));
+    assert_eq!(grid.origin, Vec3::ZERO);
+    let last = grid.position(grid.len() - 1);
+    assert!((last - Vec3::new(10.0, 3.0, 0.0)).length() < 1e-3, "{last}");
+    assert!(grid.spacing.x <= 4.0 && grid.spacing.y <= 4.0);
+}
+
+/// Verifies trilinear weights sum to 1 and reproduce the point inside the
+/// grid, and clamp to the nearest probes outside it.
+#[test]
+fn weights_cells_trilinearly() {
+    let grid = grid();
+    let point = Vec3::new(0.25, 3.0, 2.1);
+    let cell = grid.cell(point);
+    let total: f32 = cell.iter().map(|&(_, weight)| weight).sum();
+    assert!((total - 1.0).abs() < 1e-5);
+    let blended: Vec3 = cell
+        .iter()
+        .map(|&(index, weight)| grid.position(index) * weight)
+        .sum();
+    assert!((blended - point).length() < 1e-5, "{blended}");
+
+    let outside = grid.cell(Vec3::new(-5.0, -5.0, -5.0));
+    let weight_of_first: f32 = outside
+        .iter()
+        .filter(|&&(index, _)| index == 0)
+        .map(|&(_, weight)| weight)
+        .sum();
+    assert!((weight_of_first - 1.0).abs() < 1e-5);
+}
+
+/// Verifies an empty grid is rejected.
+#[test]
+#[should_panic(expected = "probe grid must not be empty")]
+fn rejects_empty_grid() {
+    let _ = ProbeGrid::new(Vec3::ZERO, Vec3::ONE, UVec3::new(2, 0, 2));
+}
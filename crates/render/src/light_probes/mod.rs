@@ -0,0 +1,32 @@
+//! Light probes for diffuse global illumination.
+//!
+//! A [`ProbeGrid`] places probes through a level. Each probe stores the
+//! light arriving from every direction as spherical harmonics, so
+//! surfaces pick up light bounced off walls and floors, not only the sky.
+//!
+//! An [`IrradianceVolume`] is baked by the asset cook tool or when the
+//! level loads and is blended per pixel by the PBR shader. On adapters
+//! that allow it ([`GlobalIllumination::Dynamic`]), a [`DdgiVolume`]
+//! re-lights the probes every frame by tracing rays, so moving lights and
+//! opened doors change the bounce light. [`LightProbes`] picks between
+//! the two.
+//!
+//! [`GlobalIllumination::Dynamic`]: crate::capabilities::GlobalIllumination::Dynamic
+
+mod ddgi;
+#[cfg(test)]
+mod ddgi_test;
+mod grid;
+#[cfg(test)]
+mod grid_test;
+mod probes;
+#[cfg(test)]
+mod probes_test;
+mod volume;
+#[cfg(test)]
+mod volume_test;
+
+pub use ddgi::{DdgiSettings, DdgiVolume, ProbeRay};
+pub use grid::ProbeGrid;
+pub use probes::LightProbes;
+pub use volume::IrradianceVolume;
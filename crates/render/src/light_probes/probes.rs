@@ -0,0 +1,92 @@
+//! Light probe selection between baked and dynamic lighting.
+
+use super::ddgi::{DdgiSettings, DdgiVolume, ProbeRay};
+use super::volume::IrradianceVolume;
+use crate::capabilities::{GlobalIllumination, GpuCapabilities};
+use crate::target::TextureHandle;
+use crate::Material;
+use math::Vec3;
+
+/// Lighting source of [`LightProbes`].
+#[derive(Debug, Clone, PartialEq)]
+enum Source {
+    Baked(IrradianceVolume),
+    Dynamic(DdgiVolume),
+}
+
+/// The light probes of a level, baked or updated every frame.
+///
+/// # Example
+///
+/// ```
+/// use math::{UVec3, Vec3};
+/// use render::capabilities::GlobalIllumination;
+/// use render::light_probes::{DdgiSettings, IrradianceVolume, LightProbes, ProbeGrid, ProbeRay};
+/// use render::GpuCapabilities;
+///
+/// let grid = ProbeGrid::new(Vec3::ZERO, Vec3::ONE, UVec3::splat(2));
+/// let baked = IrradianceVolume::new(grid);
+/// let mut probes = LightProbes::new(&GpuCapabilities::baseline(), baked, Some(DdgiSettings::default()));
+///
+/// if probes.mode() == GlobalIllumination::Dynamic {
+///     assert_eq!(probes.update(|_, _| ProbeRay::Miss(Vec3::ONE)), 8);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LightProbes {
+    source: Source,
+}
+
+impl LightProbes {
+    /// Creates the probes from the level's `baked` volume, updating them
+    /// dynamically with `dynamic` settings when given and the adapter
+    /// supports it.
+    #[must_use]
+    pub fn new(
+        capabilities: &GpuCapabilities,
+        baked: IrradianceVolume,
+        dynamic: Option<DdgiSettings>,
+    ) -> Self {
+        let source = match dynamic {
+            Some(settings) if capabilities.global_illumination() == GlobalIllumination::Dynamic => {
+                Source::Dynamic(DdgiVolume::new(baked, settings))
+            }
+            _ => Source::Baked(baked),
+        };
+        Self { source }
+    }
+
+    /// Returns how the probes are lit.
+    #[must_use]
+    pub fn mode(&self) -> GlobalIllumination {
+        match self.source {
+            Source::Baked(_) => GlobalIllumination::Baked,
+            Source::Dynamic(_) => GlobalIllumination::Dynamic,
+        }
+    }
+
+    /// Returns the current lighting.
+    #[must_use]
+    pub fn volume(&self) -> &IrradianceVolume {
+        match &self.source {
+            Source::Baked(volume) => volume,
+            Source::Dynamic(ddgi) => ddgi.volume(),
+        }
+    }
+
+    /// Updates dynamic probes, see [`DdgiVolume::update`]; baked probes
+    /// never change. Returns the number of probes updated.
+    pub fn update(&mut self, trace: impl FnMut(Vec3, Vec3) -> ProbeRay) -> usize {
+        match &mut self.source {
+            Source::Baked(_) => 0,
+            Source::Dynamic(ddgi) => ddgi.update(trace),
+        }
+    }
+
+    /// Binds the uploaded probe texture to `material`, see
+    /// [`IrradianceVolume::bind`].
+    #[must_use]
+    pub fn bind(&self, material: Material, probes: Option<TextureHandle>) -> Material {
+        self.volume().bind(material, probes)
+    }
+}
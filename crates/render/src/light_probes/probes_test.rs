@@ -0,0 +1,64 @@
+//! Unit tests for light probe selection.
+
+use super::ddgi::{DdgiSettings, ProbeRay};
+use super::grid::ProbeGrid;
+use super::probes::LightProbes;
+use super::volume::IrradianceVolume;
+use crate::capabilities::{GlobalIllumination, GpuCapabilities, GpuFeatures};
+use crate::{Material, MaterialParam, RenderLimits};
+use math::{UVec3, Vec3};
+
+fn baked() -> IrradianceVolume {
+    let grid = ProbeGrid::new(Vec3::ZERO, Vec3::ONE, UVec3::splat(2));
+    IrradianceVolume::bake(grid, 32, |_, _| Vec3::splat(0.25))
+}
+
+/// Verifies capable adapters update the probes dynamically, starting from
+/// the baked lighting.
+#[test]
+fn capable_adapter_updates_dynamically() {
+    let capabilities = GpuCapabilities::from_adapter(
+        "Discrete GPU",
+        GpuFeatures::default(),
+        RenderLimits::native_defaults(),
+    );
+    let mut probes = LightProbes::new(&capabilities, baked(), Some(DdgiSettings::default()));
+    assert_eq!(probes.mode(), GlobalIllumination::Dynamic);
+    assert_eq!(probes.volume(), &baked());
+    assert_eq!(probes.update(|_, _| ProbeRay::Miss(Vec3::ONE)), 8);
+    assert_ne!(probes.volume(), &baked());
+}
+
+/// Verifies baked lighting is kept on limited adapters or when dynamic
+/// probes are not requested.
+#[test]
+fn falls_back_to_baked() {
+    let limited = GpuCapabilities::from_adapter(
+        "Integrated GPU",
+        GpuFeatures::default(),
+        RenderLimits {
+            max_compute_invocations_per_workgroup: 64,
+            ..RenderLimits::webgpu_defaults()
+        },
+    );
+    let native = GpuCapabilities::from_adapter(
+        "Discrete GPU",
+        GpuFeatures::default(),
+        RenderLimits::native_defaults(),
+    );
+    for mut probes in [
+        LightProbes::new(&limited, baked(), Some(DdgiSettings::default())),
+        LightProbes::new(&native, baked(), None),
+    ] {
+        assert_eq!(probes.mode(), GlobalIllumination::Baked);
+        assert_eq!(probes.update(|_, _| ProbeRay::Miss(Vec3::ONE)), 0);
+        assert_eq!(probes.volume(), &baked());
+        assert_eq!(
+            probes
+                .bind(Material::new("pbr.wgsl"), None)
+                .params
+                .get("probes_enabled"),
+            Some(&MaterialParam::Float(0.0))
+        );
+    }
+}
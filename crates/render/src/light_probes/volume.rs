@@ -0,0 +1,239 @@
+//! Baked irradiance volumes.
+
+use super::grid::ProbeGrid;
+use crate::sky::{Cubemap, Irradiance};
+use crate::target::TextureHandle;
+use crate::Material;
+use math::{Color, Quat, Vec3, Vec4};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// Diffuse light stored as spherical harmonics at every probe of a
+/// [`ProbeGrid`].
+///
+/// Volumes are baked once, by the asset cook tool or when a level loads,
+/// and saved with the level ([`IrradianceVolume::to_json`]). At runtime
+/// the PBR shader blends the eight probes around each pixel, exactly as
+/// [`IrradianceVolume::diffuse`] does here.
+///
+/// # Example
+///
+/// ```
+/// use math::{UVec3, Vec3};
+/// use render::light_probes::{IrradianceVolume, ProbeGrid};
+///
+/// let grid = ProbeGrid::new(Vec3::ZERO, Vec3::ONE, UVec3::splat(2));
+/// // Bright light from above, dark ground below.
+/// let volume = IrradianceVolume::bake(grid, 64, |_, direction| {
+///     if direction.y > 0.0 { Vec3::ONE } else { Vec3::ZERO }
+/// });
+///
+/// let center = Vec3::splat(0.5);
+/// assert!(volume.diffuse(center, Vec3::Y).x > volume.diffuse(center, Vec3::NEG_Y).x);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IrradianceVolume {
+    grid: ProbeGrid,
+    probes: Vec<Irradiance>,
+}
+
+impl IrradianceVolume {
+    /// Creates an unlit volume.
+    #[must_use]
+    pub fn new(grid: ProbeGrid) -> Self {
+        Self {
+            grid,
+            probes: vec![Irradiance::default(); grid.len()],
+        }
+    }
+
+    /// Creates a volume from already baked `probes`, in grid order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is not exactly one probe per grid point.
+    pub fn from_probes(grid: ProbeGrid, probes: Vec<Irradiance>) -> anyhow::Result<Self> {
+        if probes.len() != grid.len() {
+            anyhow::bail!(
+                "probe volume has {} probes, but its grid needs {}",
+                probes.len(),
+                grid.len()
+            );
+        }
+        Ok(Self { grid, probes })
+    }
+
+    /// Bakes each probe from `rays` rays spread evenly over the sphere;
+    /// `trace` returns the radiance arriving at a probe position from a
+    /// direction.
+    ///
+    /// The cook tool traces the level's collision geometry lit by its
+    /// static lights.
+    #[must_use]
+    pub fn bake(grid: ProbeGrid, rays: u32, mut trace: impl FnMut(Vec3, Vec3) -> Vec3) -> Self {
+        let probes = (0..grid.len())
+            .map(|index| {
+                let origin = grid.position(index);
+                Irradiance::from_samples(
+                    sphere_directions(rays, Quat::IDENTITY)
+                        .map(|direction| (direction, trace(origin, direction))),
+                )
+            })
+            .collect();
+        Self { grid, probes }
+    }
+
+    /// Bakes each probe from a cubemap `capture` renders at its position,
+    /// for baking on the GPU when a level loads.
+    #[must_use]
+    pub fn bake_cubemaps(grid: ProbeGrid, mut capture: impl FnMut(Vec3) -> Cubemap) -> Self {
+        let probes = (0..grid.len())
+            .map(|index| Irradiance::from_cubemap(&capture(grid.position(index))))
+            .collect();
+        Self { grid, probes }
+    }
+
+    /// Returns the probe grid.
+    #[must_use]
+    pub fn grid(&self) -> &ProbeGrid {
+        &self.grid
+    }
+
+    /// Returns the probes, in grid order.
+    #[must_use]
+    pub fn probes(&self) -> &[Irradiance] {
+        &self.probes
+    }
+
+    /// Returns the probes for updating in place.
+    pub(super) fn probes_mut(&mut self) -> &mut [Irradiance] {
+        &mut self.probes
+    }
+
+    /// Returns the spherical harmonics at `position` for a surface facing
+    /// `normal`, blending the eight probes around it.
+    ///
+    /// Trilinear weights are scaled down for probes behind the surface,
+    /// which mostly see the inside of the wall it belongs to, so light
+    /// leaks less through thin geometry.
+    #[must_use]
+    pub fn irradiance_at(&self, position: Vec3, normal: Vec3) -> Irradiance {
+        let normal = normal.normalize_or_zero();
+        let mut coefficients = [Vec3::ZERO; 9];
+        let mut total = 0.0;
+        for (index, trilinear) in self.grid.cell(position) {
+            if trilinear <= 0.0 {
+                continue;
+            }
+            let towards = (self.grid.position(index) - position).normalize_or_zero();
+            let facing = (towards.dot(normal) + 1.0) * 0.5;
+            let weight = trilinear * (facing * facing + 0.2);
+            for (sum, coefficient) in coefficients.iter_mut().zip(self.probes[index].coefficients) {
+                *sum += coefficient * weight;
+            }
+            total += weight;
+        }
+        if total > 0.0 {
+            for sum in &mut coefficients {
+                *sum /= total;
+            }
+        }
+        Irradiance { coefficients }
+    }
+
+    /// Returns the diffuse light a white surface at `position` facing
+    /// `normal` reflects.
+    #[must_use]
+    pub fn diffuse(&self, position: Vec3, normal: Vec3) -> Vec3 {
+        self.irradiance_at(position, normal).diffuse(normal)
+    }
+
+    /// Returns the size of the texture the probes upload to: nine texels
+    /// per probe along x, one row per probe along y and z.
+    #[must_use]
+    pub fn texture_size(&self) -> (u32, u32) {
+        let counts = self.grid.counts;
+        (counts.x * 9, counts.y * counts.z)
+    }
+
+    /// Returns the texels of the probe texture, row by row; each holds one
+    /// coefficient in `xyz`.
+    #[must_use]
+    pub fn texels(&self) -> Vec<Vec4> {
+        // Probes are stored x first, so rows of the texture follow them.
+        self.probes
+            .iter()
+            .flat_map(|probe| probe.coefficients.map(|c| c.extend(0.0)))
+            .collect()
+    }
+
+    /// Sets the `light_probes` target of `material` to the uploaded probe
+    /// texture, with the grid's `probe_origin`, `probe_spacing`, and
+    /// `probe_counts` colors and a `probes_enabled` float of 1.
+    ///
+    /// Without a texture, `probes_enabled` is 0 and the shader falls back to
+    /// the sky's ambient light.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn bind(&self, material: Material, probes: Option<TextureHandle>) -> Material {
+        let Some(probes) = probes else {
+            return material.with_float("probes_enabled", 0.0);
+        };
+        let ProbeGrid {
+            origin,
+            spacing,
+            counts,
+        } = self.grid;
+        material
+            .with_render_target("light_probes", probes)
+            .with_color(
+                "probe_origin",
+                Color::linear_rgb(origin.x, origin.y, origin.z),
+            )
+            .with_color(
+                "probe_spacing",
+                Color::linear_rgb(spacing.x, spacing.y, spacing.z),
+            )
+            .with_color(
+                "probe_counts",
+                Color::linear_rgb(counts.x as f32, counts.y as f32, counts.z as f32),
+            )
+            .with_float("probes_enabled", 1.0)
+    }
+
+    /// Parses a volume saved by [`IrradianceVolume::to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is not a valid volume or its probe count
+    /// does not match its grid.
+    pub fn from_json(text: &str) -> anyhow::Result<Self> {
+        let volume: Self =
+            serde_json::from_str(text).map_err(|e| anyhow::anyhow!("invalid probe volume: {e}"))?;
+        Self::from_probes(volume.grid, volume.probes)
+    }
+
+    /// Serializes the volume for saving with a level.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the volume cannot be represented in JSON.
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        serde_json::to_string(self)
+            .map_err(|e| anyhow::anyhow!("failed to serialize probe volume: {e}"))
+    }
+}
+
+/// Returns `count` directions spread evenly over the sphere on a Fibonacci
+/// spiral, turned by `rotation`.
+#[allow(clippy::cast_precision_loss)]
+pub(super) fn sphere_directions(count: u32, rotation: Quat) -> impl Iterator<Item = Vec3> {
+    // The golden angle, in radians.
+    let golden = PI * (3.0 - 5.0_f32.sqrt());
+    (0..count).map(move |i| {
+        let y = 1.0 - (i as f32 + 0.5) / count as f32 * 2.0;
+        let radius = (1.0 - y * y).sqrt();
+        let phi = golden * i as f32;
+        rotation * Vec3::new(radius * phi.cos(), y, radius * phi.sin())
+    })
+}
@@ -0,0 +1,119 @@
+//! Unit tests for baked irradiance volumes.
+
+use super::grid::ProbeGrid;
+use super::volume::IrradianceVolume;
+use crate::sky::{Cubemap, Irradiance};
+use crate::target::{RenderTextureDesc, RenderTextures};
+use crate::{Material, MaterialParam, RenderLimits};
+use math::{Color, UVec3, Vec3};
+
+/// Two probes along x, 2 units apart.
+fn pair() -> ProbeGrid {
+    ProbeGrid::new(Vec3::ZERO, Vec3::new(2.0, 1.0, 1.0), UVec3::new(2, 1, 1))
+}
+
+/// Verifies a uniform environment bakes to uniform diffuse light with both
+/// ray tracing and cubemap captures.
+#[test]
+fn bakes_uniform_light() {
+    let light = Vec3::new(1.0, 0.5, 0.25);
+    let traced = IrradianceVolume::bake(pair(), 256, |_, _| light);
+    let captured = IrradianceVolume::bake_cubemaps(pair(), |_| Cubemap::uniform(8, light));
+    for volume in [&traced, &captured] {
+        for normal in [Vec3::Y, Vec3::NEG_X, Vec3::new(1.0, -1.0, 1.0)] {
+            let diffuse = volume.diffuse(Vec3::new(0.7, 0.0, 0.0), normal);
+            assert!((diffuse - light).length() < 0.02, "{diffuse}");
+        }
+    }
+}
+
+/// Verifies light is interpolated between probes.
+#[test]
+fn interpolates_between_probes() {
+    let volume = IrradianceVolume::bake(pair(), 128, |origin, _| {
+        Vec3::splat(if origin.x > 1.0 { 1.0 } else { 0.0 })
+    });
+    let at = |x: f32| volume.diffuse(Vec3::new(x, 0.0, 0.0), Vec3::Y).x;
+    assert!(at(0.0) < 0.02);
+    assert!((at(1.0) - 0.5).abs() < 0.02, "{}", at(1.0));
+    assert!((at(2.0) - 1.0).abs() < 0.02);
+    assert!(at(0.5) < at(1.5));
+}
+
+/// Verifies probes behind a surface count less than probes in front of it.
+#[test]
+fn favors_probes_in_front() {
+    let volume = IrradianceVolume::bake(pair(), 128, |origin, _| {
+        Vec3::splat(if origin.x > 1.0 { 1.0 } else { 0.0 })
+    });
+    let middle = Vec3::new(1.0, 0.0, 0.0);
+    let facing_lit = volume.diffuse(middle, Vec3::X).x;
+    let facing_dark = volume.diffuse(middle, Vec3::NEG_X).x;
+    assert!(facing_lit > 0.7, "{facing_lit}");
+    assert!(facing_dark < 0.3, "{facing_dark}");
+}
+
+/// Verifies the probe texture holds nine coefficients per probe.
+#[test]
+fn packs_probe_texture() {
+    let grid = ProbeGrid::new(Vec3::ZERO, Vec3::ONE, UVec3::new(2, 3, 2));
+    let volume = IrradianceVolume::bake(grid, 32, |origin, _| origin);
+    assert_eq!(volume.texture_size(), (18, 6));
+    let texels = volume.texels();
+    assert_eq!(texels.len(), 18 * 6);
+    let probe = grid.index(UVec3::new(1, 2, 1));
+    assert_eq!(
+        texels[probe * 9 + 4].truncate(),
+        volume.probes()[probe].coefficients[4]
+    );
+}
+
+/// Verifies volumes round-trip through JSON and mismatched probe counts are
+/// rejected.
+#[test]
+fn saves_and_loads() {
+    let volume = IrradianceVolume::bake(pair(), 16, |_, direction| direction.abs());
+    let json = volume.to_json().unwrap();
+    assert_eq!(IrradianceVolume::from_json(&json).unwrap(), volume);
+
+    let error = IrradianceVolume::from_probes(pair(), vec![Irradiance::default()]).unwrap_err();
+    assert!(error.to_string().contains("has 1 probes"), "{error}");
+    let three = json.replacen("\"probes\":[", "\"probes\":[{\"coefficients\":[[0,0,0],[0,0,0],[0,0,0],[0,0,0],[0,0,0],[0,0,0],[0,0,0],[0,0,0],[0,0,0]]},", 1);
+    assert!(IrradianceVolume::from_json(&three).is_err());
+    assert!(IrradianceVolume::from_json("{").is_err());
+}
+
+/// Verifies binding sets the grid and texture, or disables probes without
+/// a texture.
+#[test]
+fn binds_probes_to_materials() {
+    let volume = IrradianceVolume::new(pair());
+    let mut textures = RenderTextures::new(RenderLimits::default());
+    let (width, height) = volume.texture_size();
+    let texture = textures
+        .create(RenderTextureDesc::new(width, height))
+        .unwrap();
+    let pbr = Material::new("pbr.wgsl");
+
+    let bound = volume.bind(pbr.clone(), Some(texture));
+    assert_eq!(bound.render_target("light_probes"), Some(texture));
+    assert_eq!(
+        bound.params.get("probe_spacing"),
+        Some(&MaterialParam::Color(Color::linear_rgb(2.0, 1.0, 1.0)))
+    );
+    assert_eq!(
+        bound.params.get("probe_counts"),
+        Some(&MaterialParam::Color(Color::linear_rgb(2.0, 1.0, 1.0)))
+    );
+    assert_eq!(
+        bound.params.get("probes_enabled"),
+        Some(&MaterialParam::Float(1.0))
+    );
+
+    let unbound = volume.bind(pbr, None);
+    assert_eq!(unbound.render_target("light_probes"), None);
+    assert_eq!(
+        unbound.params.get("probes_enabled"),
+        Some(&MaterialParam::Float(0.0))
+    );
+}
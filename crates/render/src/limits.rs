@@ -0,0 +1,124 @@
+//! Device limit profiles.
+//!
+//! Provides [`RenderLimits`], the subset of GPU limits the renderer sizes its
+//! resources against. WebGPU in the browser guarantees far less than native
+//! backends, so web builds start from a conservative profile.
+
+/// GPU limits the renderer relies on when allocating resources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderLimits {
+    /// Maximum width/height of a 2D texture.
+    pub max_texture_dimension_2d: u32,
+    /// Maximum number of bind groups in a pipeline layout.
+    pub max_bind_groups: u32,
+    /// Maximum storage buffers visible to a single shader stage.
+    pub max_storage_buffers_per_shader_stage: u32,
+    /// Maximum size in bytes of a uniform buffer binding.
+    pub max_uniform_buffer_binding_size: u32,
+    /// Maximum size in bytes of a storage buffer binding.
+    pub max_storage_buffer_binding_size: u32,
+    /// Maximum compute workgroup invocations.
+    pub max_compute_invocations_per_workgroup: u32,
+    /// Maximum layers of a 2D texture array.
+    pub max_texture_array_layers: u32,
+    /// Maximum textures in the binding arrays of a single shader stage,
+    /// used with bindless textures.
+    pub max_binding_array_elements_per_shader_stage: u32,
+}
+
+impl Default for RenderLimits {
+    fn default() -> Self {
+        Self::for_target()
+    }
+}
+
+impl RenderLimits {
+    /// Limits typically available on desktop Vulkan, Metal, and DX12 devices.
+    #[must_use]
+    pub const fn native_defaults() -> Self {
+        Self {
+            max_texture_dimension_2d: 16384,
+            max_bind_groups: 8,
+            max_storage_buffers_per_shader_stage: 16,
+            max_uniform_buffer_binding_size: 64 << 10,
+            max_storage_buffer_binding_size: 1 << 30,
+            max_compute_invocations_per_workgroup: 1024,
+            max_texture_array_layers: 2048,
+            max_binding_array_elements_per_shader_stage: 16384,
+        }
+    }
+
+    /// Limits guaranteed by the WebGPU specification.
+    #[must_use]
+    pub const fn webgpu_defaults() -> Self {
+        Self {
+            max_texture_dimension_2d: 8192,
+            max_bind_groups: 4,
+            max_storage_buffers_per_shader_stage: 8,
+            max_uniform_buffer_binding_size: 64 << 10,
+            max_storage_buffer_binding_size: 128 << 20,
+            max_compute_invocations_per_workgroup: 256,
+            max_texture_array_layers: 256,
+            max_binding_array_elements_per_shader_stage: 0,
+        }
+    }
+
+    /// Returns the starting profile for the target being compiled.
+    ///
+    /// Only `wasm32` targets start from the WebGPU profile; the `wasm`
+    /// feature alone does not lower native limits.
+    #[must_use]
+    pub const fn for_target() -> Self {
+        if cfg!(target_arch = "wasm32") {
+            Self::webgpu_defaults()
+        } else {
+            Self::native_defaults()
+        }
+    }
+
+    /// Returns the component-wise minimum of two limit sets.
+    ///
+    /// Used to clamp the engine's requested limits to what an adapter reports.
+    #[must_use]
+    pub const fn min(self, other: Self) -> Self {
+        const fn min_u32(a: u32, b: u32) -> u32 {
+            if a < b {
+                a
+            } else {
+                b
+            }
+        }
+
+        Self {
+            max_texture_dimension_2d: min_u32(
+                self.max_texture_dimension_2d,
+                other.max_texture_dimension_2d,
+            ),
+            max_bind_groups: min_u32(self.max_bind_groups, other.max_bind_groups),
+            max_storage_buffers_per_shader_stage: min_u32(
+                self.max_storage_buffers_per_shader_stage,
+                other.max_storage_buffers_per_shader_stage,
+            ),
+            max_uniform_buffer_binding_size: min_u32(
+                self.max_uniform_buffer_binding_size,
+                other.max_uniform_buffer_binding_size,
+            ),
+            max_storage_buffer_binding_size: min_u32(
+                self.max_storage_buffer_binding_size,
+                other.max_storage_buffer_binding_size,
+            ),
+            max_compute_invocations_per_workgroup: min_u32(
+                self.max_compute_invocations_per_workgroup,
+                other.max_compute_invocations_per_workgroup,
+            ),
+            max_texture_array_layers: min_u32(
+                self.max_texture_array_layers,
+                other.max_texture_array_layers,
+            ),
+            max_binding_array_elements_per_shader_stage: min_u32(
+                self.max_binding_array_elements_per_shader_stage,
+                other.max_binding_array_elements_per_shader_stage,
+            ),
+        }
+    }
+}
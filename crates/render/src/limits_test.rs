@@ -0,0 +1,39 @@
+//! Unit tests for device limit profiles.
+
+use crate::RenderLimits;
+
+/// Verifies that native builds start from the native profile, even with the
+/// `wasm` feature enabled.
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn native_targets_use_native_defaults() {
+    assert_eq!(RenderLimits::for_target(), RenderLimits::native_defaults());
+    assert_eq!(RenderLimits::default(), RenderLimits::native_defaults());
+}
+
+/// Verifies that the WebGPU profile never exceeds the native one.
+#[test]
+fn webgpu_is_the_conservative_profile() {
+    let native = RenderLimits::native_defaults();
+    let web = RenderLimits::webgpu_defaults();
+    assert_eq!(native.min(web), web);
+    assert_eq!(web.min(native), web);
+}
+
+/// Verifies that clamping takes the smaller value of each limit.
+#[test]
+fn min_is_component_wise() {
+    let a = RenderLimits {
+        max_texture_dimension_2d: 4096,
+        ..RenderLimits::native_defaults()
+    };
+    let b = RenderLimits {
+        max_bind_groups: 2,
+        ..RenderLimits::native_defaults()
+    };
+
+    let clamped = a.min(b);
+    assert_eq!(clamped.max_texture_dimension_2d, 4096);
+    assert_eq!(clamped.max_bind_groups, 2);
+    assert_eq!(clamped.max_storage_buffers_per_shader_stage, 16);
+}
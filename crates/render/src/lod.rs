@@ -0,0 +1,326 @@
+//! Level of detail selection and generation.
+//!
+//! A [`Lod`] component holds one mesh per detail level with the minimum
+//! screen size each is used at, measured as the fraction of the viewport
+//! height covered by the bounding sphere ([`screen_size`]). Switching uses a
+//! hysteresis band around every threshold so objects hovering at a boundary
+//! do not pop back and forth, and can cross-fade the old and new level with
+//! a screen-door dither instead of switching in one frame.
+//!
+//! Meshes without hand-made levels can get them at cook time from
+//! [`LodGeneration`], which simplifies the source mesh by vertex clustering.
+
+use crate::mesh::{Aabb, Mesh, MeshVertex};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Default hysteresis band, as a fraction of each threshold.
+pub const DEFAULT_HYSTERESIS: f32 = 0.1;
+
+/// 4x4 ordered dither matrix used for cross-fading.
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Returns the fraction of the viewport height covered by the bounding
+/// sphere of `bounds` seen from `distance` with vertical field of view
+/// `fov_y` in radians.
+#[must_use]
+pub fn screen_size(bounds: &Aabb, distance: f32, fov_y: f32) -> f32 {
+    let radius = bounds.half_extents().length();
+    radius / (distance.max(radius) * (fov_y * 0.5).tan())
+}
+
+/// One detail level of a [`Lod`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LodLevel<M> {
+    /// Mesh drawn at this level.
+    pub mesh: M,
+    /// Smallest [`screen_size`] this level is used at.
+    pub min_screen_size: f32,
+}
+
+impl<M> LodLevel<M> {
+    /// Creates a level used from `min_screen_size` upwards.
+    #[must_use]
+    pub fn new(mesh: M, min_screen_size: f32) -> Self {
+        Self {
+            mesh,
+            min_screen_size,
+        }
+    }
+}
+
+/// A cross-fade between two levels in progress.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LodFade {
+    /// Level being faded out.
+    pub from: usize,
+    /// Fraction of the fade done, from 0 to 1.
+    pub progress: f32,
+}
+
+impl LodFade {
+    /// Returns `true` if the incoming level covers `pixel`; the outgoing
+    /// level covers exactly the other pixels, so the object never has
+    /// holes or double coverage.
+    #[must_use]
+    pub fn incoming_covers(&self, [x, y]: [u32; 2]) -> bool {
+        let threshold = f32::from(BAYER[(y % 4) as usize][(x % 4) as usize]);
+        (threshold + 0.5) / 16.0 < self.progress
+    }
+}
+
+/// Detail levels of an entity and the one currently drawn.
+///
+/// # Example
+///
+/// ```
+/// use render::lod::{Lod, LodLevel};
+/// use std::time::Duration;
+///
+/// let mut lod = Lod::new(vec![
+///     LodLevel::new("rock_high", 0.3),
+///     LodLevel::new("rock_mid", 0.1),
+///     LodLevel::new("rock_low", 0.0),
+/// ]);
+///
+/// lod.update(0.2, Duration::ZERO);
+/// assert_eq!(*lod.mesh(), "rock_mid");
+/// // Inside the hysteresis band above 0.3, so no switch yet.
+/// lod.update(0.31, Duration::ZERO);
+/// assert_eq!(*lod.mesh(), "rock_mid");
+/// lod.update(0.4, Duration::ZERO);
+/// assert_eq!(*lod.mesh(), "rock_high");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lod<M> {
+    /// Levels from most to least detailed.
+    levels: Vec<LodLevel<M>>,
+    /// Hysteresis band as a fraction of each threshold.
+    hysteresis: f32,
+    /// Cross-fade duration, or `None` to switch instantly.
+    cross_fade: Option<Duration>,
+    /// Index of the level drawn.
+    current: usize,
+    /// Fade from the previous level, if one is in progress.
+    fade: Option<LodFade>,
+}
+
+impl<M> Lod<M> {
+    /// Creates the component, starting at the most detailed level.
+    ///
+    /// Levels are ordered by descending threshold.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `levels` is empty.
+    #[must_use]
+    pub fn new(mut levels: Vec<LodLevel<M>>) -> Self {
+        assert!(!levels.is_empty(), "LOD needs at least one level");
+        levels.sort_by(|a, b| b.min_screen_size.total_cmp(&a.min_screen_size));
+        Self {
+            levels,
+            hysteresis: DEFAULT_HYSTERESIS,
+            cross_fade: None,
+            current: 0,
+            fade: None,
+        }
+    }
+
+    /// Sets the hysteresis band as a fraction of each threshold.
+    #[must_use]
+    pub fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis.max(0.0);
+        self
+    }
+
+    /// Cross-fades between levels over `duration` instead of switching
+    /// instantly.
+    #[must_use]
+    pub fn with_cross_fade(mut self, duration: Duration) -> Self {
+        self.cross_fade = Some(duration).filter(|duration| !duration.is_zero());
+        self
+    }
+
+    /// Returns the levels from most to least detailed.
+    #[must_use]
+    #[inline]
+    pub fn levels(&self) -> &[LodLevel<M>] {
+        &self.levels
+    }
+
+    /// Returns the index of the level drawn.
+    #[must_use]
+    #[inline]
+    pub fn level(&self) -> usize {
+        self.current
+    }
+
+    /// Returns the mesh of the level drawn.
+    #[must_use]
+    pub fn mesh(&self) -> &M {
+        &self.levels[self.current].mesh
+    }
+
+    /// Returns the fade in progress; while fading, the mesh of
+    /// [`LodFade::from`] is drawn as well, dithered out.
+    #[must_use]
+    #[inline]
+    pub fn fade(&self) -> Option<LodFade> {
+        self.fade
+    }
+
+    /// Picks the level for the current `screen_size` and advances the fade
+    /// by `delta`.
+    ///
+    /// Returns `true` if the level changed.
+    pub fn update(&mut self, screen_size: f32, delta: Duration) -> bool {
+        if let (Some(fade), Some(duration)) = (&mut self.fade, self.cross_fade) {
+            fade.progress += delta.as_secs_f32() / duration.as_secs_f32();
+            if fade.progress >= 1.0 {
+                self.fade = None;
+            }
+        }
+
+        let finer = self
+            .levels
+            .iter()
+            .position(|level| screen_size >= level.min_screen_size * (1.0 + self.hysteresis))
+            .filter(|&level| level < self.current);
+        let coarser = (screen_size
+            < self.levels[self.current].min_screen_size * (1.0 - self.hysteresis))
+            .then(|| {
+                self.levels
+                    .iter()
+                    .position(|level| screen_size >= level.min_screen_size)
+                    .unwrap_or(self.levels.len() - 1)
+            });
+        let Some(next) = finer.or(coarser).filter(|&next| next != self.current) else {
+            return false;
+        };
+
+        self.fade = self.cross_fade.map(|_| LodFade {
+            from: self.current,
+            progress: 0.0,
+        });
+        self.current = next;
+        true
+    }
+}
+
+/// Cook-time settings for generating detail levels from a single mesh.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LodGeneration {
+    /// Number of levels generated below the source mesh.
+    pub levels: usize,
+    /// Clustering grid cells along the longest axis for the first
+    /// generated level; each further level halves it.
+    pub resolution: u32,
+}
+
+impl Default for LodGeneration {
+    fn default() -> Self {
+        Self {
+            levels: 3,
+            resolution: 32,
+        }
+    }
+}
+
+impl LodGeneration {
+    /// Returns the source mesh followed by the generated levels, with
+    /// thresholds halving from 0.5 and the last level used down to zero.
+    #[must_use]
+    pub fn generate(&self, mesh: &Mesh) -> Vec<LodLevel<Mesh>> {
+        let mut levels = vec![LodLevel::new(mesh.clone(), 0.5)];
+        let mut resolution = self.resolution;
+        for _ in 0..self.levels {
+            if resolution == 0 {
+                break;
+            }
+            let threshold = levels[levels.len() - 1].min_screen_size * 0.5;
+            levels.push(LodLevel::new(simplify(mesh, resolution), threshold));
+            resolution /= 2;
+        }
+        let last = levels.len() - 1;
+        levels[last].min_screen_size = 0.0;
+        levels
+    }
+}
+
+/// Simplifies `mesh` by merging the vertices in each cell of a grid with
+/// `resolution` cells along the longest axis of its bounds.
+///
+/// Merged vertices average their attributes; triangles collapsing to a line
+/// or point are dropped.
+///
+/// # Panics
+///
+/// Panics if the simplified mesh has more than `u32::MAX` vertices.
+#[must_use]
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn simplify(mesh: &Mesh, resolution: u32) -> Mesh {
+    let Some(bounds) = mesh.bounds() else {
+        return Mesh::default();
+    };
+    let extent = (bounds.max - bounds.min).max_element();
+    let cell_size = extent / resolution.max(1) as f32;
+    if cell_size <= 0.0 {
+        return mesh.clone();
+    }
+
+    let mut clusters: HashMap<[u32; 3], u32> = HashMap::new();
+    let mut sums: Vec<(MeshVertex, f32)> = Vec::new();
+    let remap: Vec<u32> = mesh
+        .vertices
+        .iter()
+        .map(|vertex| {
+            let cell = std::array::from_fn(|axis| {
+                ((vertex.position[axis] - bounds.min[axis]) / cell_size) as u32
+            });
+            let index = *clusters.entry(cell).or_insert_with(|| {
+                sums.push((MeshVertex::default(), 0.0));
+                u32::try_from(sums.len() - 1).expect("mesh vertex count exceeds u32")
+            });
+            let (sum, count) = &mut sums[index as usize];
+            for axis in 0..3 {
+                sum.position[axis] += vertex.position[axis];
+                sum.normal[axis] += vertex.normal[axis];
+            }
+            sum.uv[0] += vertex.uv[0];
+            sum.uv[1] += vertex.uv[1];
+            *count += 1.0;
+            index
+        })
+        .collect();
+
+    let vertices = sums
+        .into_iter()
+        .map(|(sum, count)| {
+            let length = sum.normal.iter().map(|n| n * n).sum::<f32>().sqrt();
+            MeshVertex {
+                position: sum.position.map(|p| p / count),
+                normal: if length > 0.0 {
+                    sum.normal.map(|n| n / length)
+                } else {
+                    sum.normal
+                },
+                uv: sum.uv.map(|uv| uv / count),
+            }
+        })
+        .collect();
+    let indices = mesh
+        .indices
+        .chunks_exact(3)
+        .map(|triangle| triangle.iter().map(|&index| remap[index as usize]))
+        .filter_map(|mut triangle| {
+            let (a, b, c) = (triangle.next()?, triangle.next()?, triangle.next()?);
+            (a != b && b != c && a != c).then_some([a, b, c])
+        })
+        .flatten()
+        .collect();
+    Mesh { vertices, indices }
+}
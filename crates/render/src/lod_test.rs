@@ -0,0 +1,112 @@
+//! Unit tests for level of detail selection and generation.
+
+use crate::lod::{screen_size, simplify, Lod, LodFade, LodGeneration, LodLevel};
+use crate::mesh::{Aabb, Mesh, MeshVertex};
+use math::Vec3;
+use std::time::Duration;
+
+fn lod() -> Lod<&'static str> {
+    Lod::new(vec![
+        LodLevel::new("low", 0.0),
+        LodLevel::new("high", 0.4),
+        LodLevel::new("mid", 0.1),
+    ])
+}
+
+/// Flat grid of `n` x `n` quads in the XZ plane.
+fn grid(n: u16) -> Mesh {
+    let mut mesh = Mesh::default();
+    for z in 0..=n {
+        for x in 0..=n {
+            mesh.vertices.push(MeshVertex {
+                position: [f32::from(x), 0.0, f32::from(z)],
+                normal: [0.0, 1.0, 0.0],
+                uv: [0.0, 0.0],
+            });
+        }
+    }
+    for z in 0..n {
+        for x in 0..n {
+            let (i, n) = (u32::from(z * (n + 1) + x), u32::from(n));
+            mesh.indices
+                .extend([i, i + n + 1, i + 1, i + 1, i + n + 1, i + n + 2]);
+        }
+    }
+    mesh
+}
+
+/// Verifies screen size shrinks with distance and saturates up close.
+#[test]
+fn screen_size_follows_distance() {
+    let unit = Aabb {
+        min: Vec3::splat(-1.0),
+        max: Vec3::ONE,
+    };
+    let fov = std::f32::consts::FRAC_PI_2;
+
+    let near = screen_size(&unit, 10.0, fov);
+    let far = screen_size(&unit, 20.0, fov);
+    assert!((near - 2.0 * far).abs() < 1e-6);
+    assert!((screen_size(&unit, 0.0, fov) - 1.0).abs() < 1e-6);
+}
+
+/// Verifies levels are sorted and switched only outside the hysteresis band.
+#[test]
+fn switching_uses_hysteresis() {
+    let mut lod = lod();
+    assert_eq!(*lod.mesh(), "high");
+
+    assert!(!lod.update(0.38, Duration::ZERO));
+    assert!(lod.update(0.3, Duration::ZERO));
+    assert_eq!(*lod.mesh(), "mid");
+    assert!(!lod.update(0.42, Duration::ZERO));
+    assert!(!lod.update(0.095, Duration::ZERO));
+    assert!(lod.update(0.01, Duration::ZERO));
+    assert_eq!(*lod.mesh(), "low");
+    // Jumps straight back to the finest level that fits.
+    assert!(lod.update(1.0, Duration::ZERO));
+    assert_eq!(lod.level(), 0);
+}
+
+/// Verifies cross-fades dither complementary pixels and finish on time.
+#[test]
+fn cross_fade_dithers_between_levels() {
+    let mut lod = lod().with_cross_fade(Duration::from_millis(200));
+    lod.update(0.05, Duration::ZERO);
+
+    assert_eq!(
+        lod.fade(),
+        Some(LodFade {
+            from: 0,
+            progress: 0.0
+        })
+    );
+    lod.update(0.05, Duration::from_millis(100));
+    let fade = lod.fade().unwrap();
+    let covered = (0..4)
+        .flat_map(|y| (0..4).map(move |x| [x, y]))
+        .filter(|&pixel| fade.incoming_covers(pixel))
+        .count();
+    assert_eq!(covered, 8);
+    lod.update(0.05, Duration::from_millis(100));
+    assert_eq!(lod.fade(), None);
+}
+
+/// Verifies generated levels reduce triangles and end at a zero threshold.
+#[test]
+fn generation_simplifies_mesh() {
+    let mesh = grid(16);
+    let levels = LodGeneration {
+        levels: 2,
+        resolution: 4,
+    }
+    .generate(&mesh);
+
+    let thresholds: Vec<_> = levels.iter().map(|level| level.min_screen_size).collect();
+    assert_eq!(thresholds, [0.5, 0.25, 0.0]);
+    assert_eq!(levels[0].mesh, mesh);
+    assert!(levels[1].mesh.triangles() < mesh.triangles());
+    assert!(levels[2].mesh.triangles() < levels[1].mesh.triangles());
+    assert!(levels[2].mesh.triangles() > 0);
+    assert_eq!(simplify(&Mesh::default(), 4), Mesh::default());
+}
@@ -0,0 +1,388 @@
+//! Materials with user-defined shader parameters.
+//!
+//! A [`Material`] names a base shader and a set of named parameters: colors
+//! and floats packed into one uniform block, and texture slots bound next to
+//! it. The uniform layout and bind group layout are derived from the
+//! parameters, so shaders and materials agree without hand-written layouts.
+//!
+//! Materials are assets stored as TOML:
+//!
+//! ```toml
+//! shader = "shaders/lit.wgsl"
+//!
+//! [params]
+//! base_color = { color = [1.0, 0.5, 0.5, 1.0] }
+//! roughness = { float = 0.5 }
+//! albedo = { texture = "textures/brick.png" }
+//! ```
+//!
+//! Entities sharing a material can still differ through a
+//! [`MaterialOverride`], e.g. to tint individual instances.
+
+use crate::target::TextureHandle;
+use math::Color;
+use rustgine_core::Label;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Size in bytes of a packed float parameter.
+const FLOAT_SIZE: u32 = 4;
+
+/// Size in bytes of a packed color parameter.
+const COLOR_SIZE: u32 = 16;
+
+/// A material parameter value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaterialParam {
+    /// A scalar, such as roughness.
+    Float(f32),
+    /// A color, stored as a linear RGBA array.
+    Color(Color),
+    /// A texture asset path, or `None` for the shader's default texture.
+    Texture(Option<Label>),
+}
+
+impl MaterialParam {
+    /// Returns the parameter's kind.
+    #[must_use]
+    pub fn kind(&self) -> ParamKind {
+        match self {
+            Self::Float(_) => ParamKind::Float,
+            Self::Color(_) => ParamKind::Color,
+            Self::Texture(_) => ParamKind::Texture,
+        }
+    }
+}
+
+/// Kind of a material parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParamKind {
+    /// `f32` in the uniform block.
+    Float,
+    /// `vec4<f32>` in the uniform block.
+    Color,
+    /// A texture and sampler binding pair.
+    Texture,
+}
+
+impl fmt::Display for ParamKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Float => "float",
+            Self::Color => "color",
+            Self::Texture => "texture",
+        })
+    }
+}
+
+/// A parameter's place in the uniform block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UniformField {
+    /// Parameter name.
+    pub name: String,
+    /// Parameter kind, either [`Float`](ParamKind::Float) or
+    /// [`Color`](ParamKind::Color).
+    pub kind: ParamKind,
+    /// Byte offset from the start of the block.
+    pub offset: u32,
+}
+
+/// Layout of a material's uniform block.
+///
+/// Colors come first so every field is naturally aligned, followed by
+/// floats; fields of the same kind are ordered by name. The size is padded
+/// to 16 bytes as uniform buffers require.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UniformLayout {
+    /// Fields in offset order.
+    pub fields: Vec<UniformField>,
+    /// Block size in bytes.
+    pub size: u32,
+}
+
+/// What a bind group entry holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingKind {
+    /// The uniform block, with its size in bytes.
+    Uniforms {
+        /// Block size in bytes.
+        size: u32,
+    },
+    /// A sampled 2D texture.
+    Texture,
+    /// The sampler for the preceding texture.
+    Sampler,
+}
+
+/// One entry of a material's bind group layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindingEntry {
+    /// Binding index in the group.
+    pub binding: u32,
+    /// What is bound.
+    pub kind: BindingKind,
+    /// Parameter the binding belongs to; empty for the uniform block.
+    pub param: String,
+}
+
+/// A shader and the parameters it is rendered with.
+///
+/// # Example
+///
+/// ```
+/// use math::Color;
+/// use render::material::{BindingKind, Material};
+///
+/// let material = Material::new("shaders/lit.wgsl")
+///     .with_color("base_color", Color::hex("#ffbcbc").unwrap())
+///     .with_float("roughness", 0.5)
+///     .with_texture("albedo", "textures/brick.png");
+///
+/// let layout = material.uniform_layout();
+/// assert_eq!(layout.size, 32);
+///
+/// let bindings = material.bind_group_layout();
+/// assert_eq!(bindings[0].kind, BindingKind::Uniforms { size: 32 });
+/// assert_eq!(bindings[1].kind, BindingKind::Texture);
+/// assert_eq!(bindings[2].kind, BindingKind::Sampler);
+///
+/// let text = material.to_toml().unwrap();
+/// assert_eq!(Material::from_toml(&text).unwrap(), material);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Material {
+    /// Base shader asset path.
+    pub shader: Label,
+    /// Parameters by name.
+    #[serde(default)]
+    pub params: BTreeMap<String, MaterialParam>,
+}
+
+impl Material {
+    /// Creates a material for `shader` without parameters.
+    #[must_use]
+    pub fn new(shader: impl Into<Label>) -> Self {
+        Self {
+            shader: shader.into(),
+            params: BTreeMap::new(),
+        }
+    }
+
+    /// Sets a float parameter.
+    #[must_use]
+    pub fn with_float(self, name: &str, value: f32) -> Self {
+        self.with_param(name, MaterialParam::Float(value))
+    }
+
+    /// Sets a color parameter.
+    #[must_use]
+    pub fn with_color(self, name: &str, value: impl Into<Color>) -> Self {
+        self.with_param(name, MaterialParam::Color(value.into()))
+    }
+
+    /// Sets a texture parameter.
+    #[must_use]
+    pub fn with_texture(self, name: &str, path: impl Into<Label>) -> Self {
+        self.with_param(name, MaterialParam::Texture(Some(path.into())))
+    }
+
+    /// Sets a texture parameter to an off-screen render texture.
+    #[must_use]
+    pub fn with_render_target(self, name: &str, handle: TextureHandle) -> Self {
+        self.with_texture(name, handle.to_string())
+    }
+
+    /// Sets a parameter.
+    #[must_use]
+    pub fn with_param(mut self, name: &str, value: MaterialParam) -> Self {
+        self.params.insert(name.to_owned(), value);
+        self
+    }
+
+    /// Parses a material asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is not a valid material.
+    pub fn from_toml(text: &str) -> anyhow::Result<Self> {
+        toml::from_str(text).map_err(|e| anyhow::anyhow!("invalid material: {e}"))
+    }
+
+    /// Serializes the material as an asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a parameter cannot be represented in TOML.
+    pub fn to_toml(&self) -> anyhow::Result<String> {
+        toml::to_string(self).map_err(|e| anyhow::anyhow!("failed to serialize material: {e}"))
+    }
+
+    /// Returns the layout of the uniform block holding colors and floats.
+    #[must_use]
+    pub fn uniform_layout(&self) -> UniformLayout {
+        let mut fields = Vec::new();
+        let mut offset = 0;
+        for (kind, size) in [
+            (ParamKind::Color, COLOR_SIZE),
+            (ParamKind::Float, FLOAT_SIZE),
+        ] {
+            for (name, param) in &self.params {
+                if param.kind() == kind {
+                    fields.push(UniformField {
+                        name: name.clone(),
+                        kind,
+                        offset,
+                    });
+                    offset += size;
+                }
+            }
+        }
+        UniformLayout {
+            fields,
+            size: offset.next_multiple_of(16),
+        }
+    }
+
+    /// Returns the bind group layout: the uniform block at binding 0, if
+    /// any, then a texture and sampler pair per texture parameter.
+    #[must_use]
+    pub fn bind_group_layout(&self) -> Vec<BindingEntry> {
+        let mut entries = Vec::new();
+        let size = self.uniform_layout().size;
+        if size > 0 {
+            entries.push(BindingEntry {
+                binding: 0,
+                kind: BindingKind::Uniforms { size },
+                param: String::new(),
+            });
+        }
+        for (name, param) in &self.params {
+            if param.kind() == ParamKind::Texture {
+                for kind in [BindingKind::Texture, BindingKind::Sampler] {
+                    entries.push(BindingEntry {
+                        binding: u32::try_from(entries.len()).unwrap_or(u32::MAX),
+                        kind,
+                        param: name.clone(),
+                    });
+                }
+            }
+        }
+        entries
+    }
+
+    /// Packs the uniform block, applying `overrides` if given.
+    #[must_use]
+    pub fn uniform_bytes(&self, overrides: Option<&MaterialOverride>) -> Vec<u8> {
+        let layout = self.uniform_layout();
+        let mut bytes = vec![0; layout.size as usize];
+        for field in &layout.fields {
+            let value = overrides.map_or_else(
+                || self.params.get(&field.name).cloned(),
+                |overrides| overrides.resolve(&field.name, self.params.get(&field.name)),
+            );
+            let offset = field.offset as usize;
+            match value {
+                Some(MaterialParam::Float(value)) => {
+                    bytes[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+                }
+                Some(MaterialParam::Color(color)) => {
+                    for (i, channel) in color.to_array().iter().enumerate() {
+                        let start = offset + i * 4;
+                        bytes[start..start + 4].copy_from_slice(&channel.to_le_bytes());
+                    }
+                }
+                Some(MaterialParam::Texture(_)) | None => {}
+            }
+        }
+        bytes
+    }
+
+    /// Returns the texture bound to parameter `name`, applying `overrides`.
+    #[must_use]
+    pub fn texture(&self, name: &str, overrides: Option<&MaterialOverride>) -> Option<Label> {
+        let value = overrides.map_or_else(
+            || self.params.get(name).cloned(),
+            |overrides| overrides.resolve(name, self.params.get(name)),
+        );
+        match value {
+            Some(MaterialParam::Texture(path)) => path,
+            _ => None,
+        }
+    }
+
+    /// Returns the render texture bound to parameter `name`, if the
+    /// parameter samples one rather than a texture asset.
+    #[must_use]
+    pub fn render_target(&self, name: &str) -> Option<TextureHandle> {
+        TextureHandle::from_path(&self.texture(name, None)?)
+    }
+}
+
+/// Per-entity changes to a shared [`Material`].
+///
+/// Overrides only replace parameters the material declares with the same
+/// kind, so they never change its layout. The tint multiplies every color.
+///
+/// # Example
+///
+/// ```
+/// use math::{palette, Color};
+/// use render::material::{Material, MaterialOverride, MaterialParam};
+///
+/// let material = Material::new("shaders/unlit.wgsl").with_color("color", Color::WHITE);
+/// let red = MaterialOverride::tinted(palette::RED);
+///
+/// let bytes = material.uniform_bytes(Some(&red));
+/// assert_eq!(&bytes[4..8], &0.0_f32.to_le_bytes());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaterialOverride {
+    /// Multiplier applied to every color parameter.
+    pub tint: Color,
+    /// Replacement parameter values by name.
+    pub params: BTreeMap<String, MaterialParam>,
+}
+
+impl Default for MaterialOverride {
+    fn default() -> Self {
+        Self {
+            tint: Color::WHITE,
+            params: BTreeMap::new(),
+        }
+    }
+}
+
+impl MaterialOverride {
+    /// Creates an override that only tints the material.
+    #[must_use]
+    pub fn tinted(tint: impl Into<Color>) -> Self {
+        Self {
+            tint: tint.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Replaces a parameter.
+    #[must_use]
+    pub fn with_param(mut self, name: &str, value: MaterialParam) -> Self {
+        self.params.insert(name.to_owned(), value);
+        self
+    }
+
+    /// Returns the effective value of a parameter whose material value is
+    /// `base`.
+    fn resolve(&self, name: &str, base: Option<&MaterialParam>) -> Option<MaterialParam> {
+        let base = base?;
+        let value = self
+            .params
+            .get(name)
+            .filter(|value| value.kind() == base.kind())
+            .unwrap_or(base);
+        Some(match value {
+            MaterialParam::Color(color) => MaterialParam::Color(*color * self.tint),
+            other => other.clone(),
+        })
+    }
+}
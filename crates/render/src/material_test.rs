@@ -0,0 +1,134 @@
+//! Unit tests for materials and their generated layouts.
+
+use crate::material::{
+    BindingKind, Material, MaterialOverride, MaterialParam, ParamKind, UniformField,
+};
+
+fn floats(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+        .collect()
+}
+
+/// Verifies colors are laid out before floats and the block is padded.
+#[test]
+fn uniform_layout_aligns_fields() {
+    let material = Material::new("lit.wgsl")
+        .with_float("roughness", 0.5)
+        .with_color("base_color", [1.0; 4])
+        .with_float("metallic", 0.0)
+        .with_texture("albedo", "brick.png");
+
+    let layout = material.uniform_layout();
+    assert_eq!(
+        layout.fields,
+        [
+            UniformField {
+                name: "base_color".to_owned(),
+                kind: ParamKind::Color,
+                offset: 0,
+            },
+            UniformField {
+                name: "metallic".to_owned(),
+                kind: ParamKind::Float,
+                offset: 16,
+            },
+            UniformField {
+                name: "roughness".to_owned(),
+                kind: ParamKind::Float,
+                offset: 20,
+            },
+        ]
+    );
+    assert_eq!(layout.size, 32);
+}
+
+/// Verifies texture-only materials have no uniform binding.
+#[test]
+fn bind_group_layout_without_uniforms() {
+    let material = Material::new("sprite.wgsl")
+        .with_texture("albedo", "a.png")
+        .with_texture("mask", "b.png");
+
+    let kinds: Vec<_> = material
+        .bind_group_layout()
+        .into_iter()
+        .map(|entry| (entry.binding, entry.kind, entry.param))
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            (0, BindingKind::Texture, "albedo".to_owned()),
+            (1, BindingKind::Sampler, "albedo".to_owned()),
+            (2, BindingKind::Texture, "mask".to_owned()),
+            (3, BindingKind::Sampler, "mask".to_owned()),
+        ]
+    );
+}
+
+/// Verifies parameters are packed at their layout offsets.
+#[test]
+fn uniform_bytes_pack_values() {
+    let material = Material::new("lit.wgsl")
+        .with_color("base_color", [0.1, 0.2, 0.3, 1.0])
+        .with_float("roughness", 0.5);
+
+    assert_eq!(
+        floats(&material.uniform_bytes(None)),
+        [0.1, 0.2, 0.3, 1.0, 0.5, 0.0, 0.0, 0.0]
+    );
+}
+
+/// Verifies overrides tint colors and replace parameters of the same kind.
+#[test]
+fn overrides_tint_and_replace() {
+    let material = Material::new("lit.wgsl")
+        .with_color("base_color", [1.0, 1.0, 1.0, 1.0])
+        .with_float("roughness", 0.5)
+        .with_texture("albedo", "brick.png");
+    let overrides = MaterialOverride::tinted([0.5, 1.0, 1.0, 1.0])
+        .with_param("roughness", MaterialParam::Float(0.25))
+        .with_param("albedo", MaterialParam::Texture(Some("moss.png".into())))
+        // Wrong kind, ignored so the layout never changes
+        .with_param("base_color", MaterialParam::Float(0.0))
+        // Not declared by the material, ignored
+        .with_param("emissive", MaterialParam::Float(1.0));
+
+    assert_eq!(
+        floats(&material.uniform_bytes(Some(&overrides))),
+        [0.5, 1.0, 1.0, 1.0, 0.25, 0.0, 0.0, 0.0]
+    );
+    assert_eq!(
+        material.texture("albedo", Some(&overrides)).as_deref(),
+        Some("moss.png")
+    );
+    assert_eq!(
+        material.texture("albedo", None).as_deref(),
+        Some("brick.png")
+    );
+}
+
+/// Verifies materials load from their TOML asset form.
+#[test]
+fn parses_material_asset() {
+    let material = Material::from_toml(
+        r#"
+        shader = "shaders/lit.wgsl"
+
+        [params]
+        base_color = { color = [1.0, 0.5, 0.5, 1.0] }
+        roughness = { float = 0.5 }
+        albedo = { texture = "textures/brick.png" }
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(material.shader, "shaders/lit.wgsl");
+    assert_eq!(material.params["roughness"], MaterialParam::Float(0.5));
+    assert_eq!(
+        material.texture("albedo", None).as_deref(),
+        Some("textures/brick.png")
+    );
+    assert!(Material::from_toml("shader = 1").is_err());
+}
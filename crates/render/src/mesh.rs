@@ -0,0 +1,64 @@
+//! CPU-side triangle meshes.
+
+pub use math::Aabb;
+use math::{Mat4, Vec3};
+
+/// Mesh vertex.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MeshVertex {
+    /// Position.
+    pub position: [f32; 3],
+    /// Unit normal.
+    pub normal: [f32; 3],
+    /// Texture coordinates.
+    pub uv: [f32; 2],
+}
+
+/// Indexed triangle list.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Mesh {
+    /// Vertices.
+    pub vertices: Vec<MeshVertex>,
+    /// Triangle list indices into [`vertices`](Self::vertices).
+    pub indices: Vec<u32>,
+}
+
+impl Mesh {
+    /// Returns the bounds of the vertices, or `None` for an empty mesh.
+    #[must_use]
+    pub fn bounds(&self) -> Option<Aabb> {
+        Aabb::from_points(
+            self.vertices
+                .iter()
+                .map(|vertex| Vec3::from(vertex.position)),
+        )
+    }
+
+    /// Returns the number of triangles.
+    #[must_use]
+    pub fn triangles(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Appends `other` transformed by `matrix`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the combined mesh has more than `u32::MAX` vertices.
+    pub fn append_transformed(&mut self, other: &Self, matrix: &Mat4) {
+        let base = u32::try_from(self.vertices.len()).expect("mesh vertex count exceeds u32");
+        self.vertices.extend(other.vertices.iter().map(|vertex| {
+            MeshVertex {
+                position: matrix.transform_point3(vertex.position.into()).into(),
+                // Exact for rotations and uniform scales.
+                normal: matrix
+                    .transform_vector3(vertex.normal.into())
+                    .normalize_or_zero()
+                    .into(),
+                uv: vertex.uv,
+            }
+        }));
+        self.indices
+            .extend(other.indices.iter().map(|index| base + index));
+    }
+}
@@ -0,0 +1,235 @@
+//! Free-list range allocation within one buffer.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Occupancy of a [`RangeAllocator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FreeListStats {
+    /// Elements the buffer holds.
+    pub capacity: u64,
+    /// Elements allocated.
+    pub used: u64,
+    /// Live allocations.
+    pub allocations: u32,
+    /// Separate free ranges.
+    pub free_ranges: u32,
+    /// Elements in the largest free range.
+    pub largest_free: u64,
+}
+
+impl FreeListStats {
+    /// Returns the elements not allocated.
+    #[must_use]
+    pub fn free(&self) -> u64 {
+        self.capacity - self.used
+    }
+
+    /// Returns the share of free space outside the largest free range, 0.0
+    /// when the free space is contiguous or there is none.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fragmentation(&self) -> f64 {
+        let free = self.free();
+        if free == 0 {
+            0.0
+        } else {
+            1.0 - self.largest_free as f64 / free as f64
+        }
+    }
+}
+
+impl fmt::Display for FreeListStats {
+    #[allow(clippy::cast_precision_loss)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let used = if self.capacity == 0 {
+            0.0
+        } else {
+            self.used as f64 / self.capacity as f64
+        };
+        write!(
+            f,
+            "{:.0}% used, {:.0}% fragmented ({} allocations, {} free ranges)",
+            used * 100.0,
+            self.fragmentation() * 100.0,
+            self.allocations,
+            self.free_ranges
+        )
+    }
+}
+
+/// A range moved by [`RangeAllocator::compact`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeMove {
+    /// Offset before compaction.
+    pub from: u64,
+    /// Offset after compaction.
+    pub to: u64,
+    /// Elements moved.
+    pub len: u64,
+}
+
+/// Hands out ranges of a fixed-size buffer, in elements.
+///
+/// Allocation is best fit over a free list; freed ranges merge with free
+/// neighbors, and [`compact`](Self::compact) slides every allocation to the
+/// front when the free space has splintered.
+///
+/// # Example
+///
+/// ```
+/// use render::mesh_pool::RangeAllocator;
+///
+/// let mut buffer = RangeAllocator::new(100);
+/// let a = buffer.allocate(40).unwrap();
+/// let b = buffer.allocate(40).unwrap();
+/// assert_eq!((a, b), (0, 40));
+/// assert_eq!(buffer.allocate(40), None);
+///
+/// buffer.free(a);
+/// let moves = buffer.compact();
+/// assert_eq!(moves[0].from, 40);
+/// assert_eq!(buffer.allocate(60), Some(40));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeAllocator {
+    capacity: u64,
+    /// Free ranges as offset to length.
+    free: BTreeMap<u64, u64>,
+    /// Allocations as offset to length.
+    used: BTreeMap<u64, u64>,
+}
+
+impl RangeAllocator {
+    /// Creates an allocator over `capacity` free elements.
+    #[must_use]
+    pub fn new(capacity: u64) -> Self {
+        let mut free = BTreeMap::new();
+        if capacity > 0 {
+            free.insert(0, capacity);
+        }
+        Self {
+            capacity,
+            free,
+            used: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the elements the buffer holds.
+    #[must_use]
+    #[inline]
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// Allocates `len` elements, returning their offset, or `None` if no
+    /// free range is large enough. Empty allocations are not tracked and
+    /// always return offset 0.
+    pub fn allocate(&mut self, len: u64) -> Option<u64> {
+        if len == 0 {
+            return Some(0);
+        }
+        let (&offset, &free) = self
+            .free
+            .iter()
+            .filter(|(_, &free)| free >= len)
+            .min_by_key(|(_, &free)| free)?;
+        self.free.remove(&offset);
+        if free > len {
+            self.free.insert(offset + len, free - len);
+        }
+        self.used.insert(offset, len);
+        Some(offset)
+    }
+
+    /// Frees the allocation at `offset`, returning its length, or `None` if
+    /// nothing is allocated there.
+    pub fn free(&mut self, offset: u64) -> Option<u64> {
+        let len = self.used.remove(&offset)?;
+        let mut start = offset;
+        let mut end = offset + len;
+        if let Some((&before, &before_len)) = self.free.range(..offset).next_back() {
+            if before + before_len == offset {
+                self.free.remove(&before);
+                start = before;
+            }
+        }
+        if let Some(after_len) = self.free.remove(&end) {
+            end += after_len;
+        }
+        self.free.insert(start, end - start);
+        Some(len)
+    }
+
+    /// Moves every allocation to the front of the buffer, in offset order,
+    /// leaving one free range at the end. Returns the moves to apply to the
+    /// buffer's contents, in order; each copies to a lower offset, so
+    /// applying them in order never overwrites data not yet moved. A move
+    /// whose source and destination overlap has to go through a staging
+    /// buffer, since a GPU cannot copy a buffer range onto itself.
+    pub fn compact(&mut self) -> Vec<RangeMove> {
+        let mut moves = Vec::new();
+        let mut used = BTreeMap::new();
+        let mut next = 0;
+        for (&from, &len) in &self.used {
+            if from != next {
+                moves.push(RangeMove {
+                    from,
+                    to: next,
+                    len,
+                });
+            }
+            used.insert(next, len);
+            next += len;
+        }
+        self.used = used;
+        self.free.clear();
+        if next < self.capacity {
+            self.free.insert(next, self.capacity - next);
+        }
+        moves
+    }
+
+    /// Returns the allocations as `(offset, len)`, in offset order.
+    pub fn allocations(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.used.iter().map(|(&offset, &len)| (offset, len))
+    }
+
+    /// Returns the occupancy.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn stats(&self) -> FreeListStats {
+        FreeListStats {
+            capacity: self.capacity,
+            used: self.used.values().sum(),
+            allocations: self.used.len() as u32,
+            free_ranges: self.free.len() as u32,
+            largest_free: self.free.values().copied().max().unwrap_or(0),
+        }
+    }
+
+    /// Draws the buffer as `width` cells, `#` where a cell is mostly
+    /// allocated and `.` where it is mostly free.
+    #[must_use]
+    pub fn occupancy_map(&self, width: usize) -> String {
+        let width = width.max(1) as u64;
+        (0..width)
+            .map(|cell| {
+                let start = cell * self.capacity / width;
+                let end = ((cell + 1) * self.capacity / width).max(start + 1);
+                let used: u64 = self
+                    .used
+                    .iter()
+                    .map(|(&offset, &len)| {
+                        (offset + len).min(end).saturating_sub(offset.max(start))
+                    })
+                    .sum();
+                if used * 2 >= end - start {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,129 @@
+//! Unit tests for free-list range allocation.
+
+use super::allocator::{RangeAllocator, RangeMove};
+
+/// Verifies allocation picks the smallest free range that fits.
+#[test]
+fn allocate_is_best_fit() {
+    let mut buffer = RangeAllocator::new(100);
+    let a = buffer.allocate(30).unwrap();
+    let b = buffer.allocate(10).unwrap();
+    let c = buffer.allocate(20).unwrap();
+    buffer.allocate(40).unwrap();
+    buffer.free(a);
+    buffer.free(c);
+
+    // The 20-element hole at 40 fits better than the 30-element one at 0.
+    assert_eq!(buffer.allocate(15), Some(40));
+    assert_eq!(buffer.allocate(25), Some(0));
+    assert_eq!(buffer.allocate(10), None);
+    assert_eq!(b, 30);
+}
+
+/// Verifies freed ranges merge with free neighbors on both sides.
+#[test]
+fn free_merges_neighbors() {
+    let mut buffer = RangeAllocator::new(90);
+    let a = buffer.allocate(30).unwrap();
+    let b = buffer.allocate(30).unwrap();
+    let c = buffer.allocate(30).unwrap();
+    buffer.free(a);
+    buffer.free(c);
+    assert_eq!(buffer.stats().free_ranges, 2);
+
+    assert_eq!(buffer.free(b), Some(30));
+    let stats = buffer.stats();
+    assert_eq!(
+        (stats.free_ranges, stats.largest_free, stats.used),
+        (1, 90, 0)
+    );
+    assert_eq!(buffer.allocate(90), Some(0));
+}
+
+/// Verifies freeing an offset that is not allocated changes nothing.
+#[test]
+fn free_unknown_offset() {
+    let mut buffer = RangeAllocator::new(10);
+    buffer.allocate(4).unwrap();
+    assert_eq!(buffer.free(2), None);
+    assert_eq!(buffer.stats().used, 4);
+}
+
+/// Verifies empty allocations always succeed and are not tracked.
+#[test]
+fn empty_allocation() {
+    let mut buffer = RangeAllocator::new(0);
+    assert_eq!(buffer.allocate(0), Some(0));
+    assert_eq!(buffer.allocate(1), None);
+    assert_eq!(buffer.stats().allocations, 0);
+}
+
+/// Verifies compaction slides allocations to the front in offset order and
+/// reports only those that moved.
+#[test]
+fn compact_moves_allocations_down() {
+    let mut buffer = RangeAllocator::new(100);
+    let offsets: Vec<_> = (0..5).map(|_| buffer.allocate(10).unwrap()).collect();
+    buffer.free(offsets[0]);
+    buffer.free(offsets[2]);
+
+    let moves = buffer.compact();
+    assert_eq!(
+        moves,
+        [
+            RangeMove {
+                from: 10,
+                to: 0,
+                len: 10
+            },
+            RangeMove {
+                from: 30,
+                to: 10,
+                len: 10
+            },
+            RangeMove {
+                from: 40,
+                to: 20,
+                len: 10
+            },
+        ]
+    );
+    assert_eq!(
+        buffer.allocations().collect::<Vec<_>>(),
+        [(0, 10), (10, 10), (20, 10)]
+    );
+    let stats = buffer.stats();
+    assert_eq!((stats.free_ranges, stats.largest_free), (1, 70));
+    assert!(buffer.compact().is_empty());
+}
+
+/// Verifies fragmentation is the share of free space outside the largest
+/// free range.
+#[test]
+fn fragmentation() {
+    let mut buffer = RangeAllocator::new(100);
+    assert!(buffer.stats().fragmentation().abs() < 1e-9);
+
+    let offsets: Vec<_> = (0..4).map(|_| buffer.allocate(20).unwrap()).collect();
+    buffer.free(offsets[1]);
+    // Free: 20 at 20, 20 at 80.
+    let stats = buffer.stats();
+    assert!((stats.fragmentation() - 0.5).abs() < 1e-9);
+    assert_eq!(
+        stats.to_string(),
+        "60% used, 50% fragmented (3 allocations, 2 free ranges)"
+    );
+
+    buffer.compact();
+    assert!(buffer.stats().fragmentation().abs() < 1e-9);
+}
+
+/// Verifies the occupancy map marks mostly allocated cells.
+#[test]
+fn occupancy_map() {
+    let mut buffer = RangeAllocator::new(100);
+    let offsets: Vec<_> = (0..4).map(|_| buffer.allocate(25).unwrap()).collect();
+    buffer.free(offsets[1]);
+    assert_eq!(buffer.occupancy_map(8), "##..####");
+    assert_eq!(buffer.occupancy_map(4), "#.##");
+}
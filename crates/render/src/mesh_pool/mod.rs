@@ -0,0 +1,21 @@
+//! Vertex and index buffer suballocation.
+//!
+//! Meshes are not given buffers of their own. A [`MeshPool`] creates large
+//! vertex and index buffers in blocks and places each mesh in a range of
+//! them, tracked by a best-fit [`RangeAllocator`] per buffer. Meshes of one
+//! block share their buffers, so they are drawn together with
+//! multi-draw-indirect, and a debug view ([`MeshPool::debug_lines`]) shows
+//! how fragmented each block's free space is.
+
+mod allocator;
+#[cfg(test)]
+mod allocator_test;
+mod pool;
+#[cfg(test)]
+mod pool_test;
+
+pub use allocator::{FreeListStats, RangeAllocator, RangeMove};
+pub use pool::{
+    BlockStats, BufferCopy, DrawIndexedIndirect, MeshHandle, MeshPool, MeshRange, MultiDraw,
+    PoolBuffer, DEFAULT_BLOCK_INDICES, DEFAULT_BLOCK_VERTICES,
+};
@@ -0,0 +1,383 @@
+//! Mesh geometry suballocated from shared vertex and index buffers.
+
+use super::allocator::{FreeListStats, RangeAllocator, RangeMove};
+use crate::mesh::{Mesh, MeshVertex};
+use anyhow::bail;
+use std::mem::size_of;
+use tracing::debug;
+
+/// Vertices per block of [`MeshPool::default`].
+pub const DEFAULT_BLOCK_VERTICES: u32 = 1 << 20;
+
+/// Indices per block of [`MeshPool::default`].
+pub const DEFAULT_BLOCK_INDICES: u32 = 3 << 20;
+
+/// Handle to a mesh stored in a [`MeshPool`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MeshHandle(u32);
+
+/// Where a mesh's geometry lives in the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MeshRange {
+    /// Block whose vertex and index buffers hold the mesh.
+    pub block: u32,
+    /// First vertex, added to every index.
+    pub base_vertex: u32,
+    /// Number of vertices.
+    pub vertex_count: u32,
+    /// First index.
+    pub first_index: u32,
+    /// Number of indices.
+    pub index_count: u32,
+}
+
+impl MeshRange {
+    /// Returns the indirect draw arguments for `instances` instances,
+    /// starting at instance `first_instance`.
+    #[must_use]
+    pub fn draw(&self, instances: u32, first_instance: u32) -> DrawIndexedIndirect {
+        DrawIndexedIndirect {
+            index_count: self.index_count,
+            instance_count: instances,
+            first_index: self.first_index,
+            base_vertex: i32::try_from(self.base_vertex).unwrap_or(i32::MAX),
+            first_instance,
+        }
+    }
+}
+
+/// Arguments of one indexed indirect draw, laid out as the GPU reads them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(C)]
+pub struct DrawIndexedIndirect {
+    /// Indices per instance.
+    pub index_count: u32,
+    /// Instances drawn.
+    pub instance_count: u32,
+    /// First index in the index buffer.
+    pub first_index: u32,
+    /// Added to every index before reading the vertex buffer.
+    pub base_vertex: i32,
+    /// First instance.
+    pub first_instance: u32,
+}
+
+/// Draws sharing one block's buffers, issued by one multi-draw call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiDraw {
+    /// Block whose buffers are bound.
+    pub block: u32,
+    /// The draws, in submission order.
+    pub draws: Vec<DrawIndexedIndirect>,
+}
+
+/// Which buffer of a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolBuffer {
+    /// The vertex buffer.
+    Vertex,
+    /// The index buffer.
+    Index,
+}
+
+/// Geometry copy within a block's buffer, from [`MeshPool::defragment`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferCopy {
+    /// Block of the buffer.
+    pub block: u32,
+    /// Buffer copied within.
+    pub buffer: PoolBuffer,
+    /// The range moved, in vertices or indices.
+    pub range: RangeMove,
+}
+
+/// One vertex and one index buffer, suballocated.
+#[derive(Debug, Clone)]
+struct Block {
+    vertices: RangeAllocator,
+    indices: RangeAllocator,
+}
+
+/// Occupancy of one block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockStats {
+    /// The vertex buffer, in vertices.
+    pub vertices: FreeListStats,
+    /// The index buffer, in indices.
+    pub indices: FreeListStats,
+}
+
+/// Mesh geometry in a few large vertex and index buffers.
+///
+/// Creating buffers per mesh costs an allocation each and forces a rebind
+/// between draws. The pool instead creates buffers in blocks of a fixed
+/// size and hands out ranges of them: a [`MeshHandle`] resolves to the
+/// block and the vertex and index offsets, so every mesh of a block can be
+/// drawn with one multi-draw call ([`MeshPool::multi_draw`]). Removed
+/// meshes return their ranges to the block's free lists, and
+/// [`MeshPool::defragment`] compacts blocks whose free space splintered.
+///
+/// # Example
+///
+/// ```
+/// use render::mesh_pool::MeshPool;
+///
+/// let mut pool = MeshPool::new(1000, 3000);
+/// let rock = pool.insert(24, 36).unwrap();
+/// let tree = pool.insert(500, 1200).unwrap();
+///
+/// let range = pool.get(tree).unwrap();
+/// assert_eq!((range.block, range.base_vertex, range.first_index), (0, 24, 36));
+///
+/// let draws = pool.multi_draw([(rock, 10), (tree, 3)]);
+/// assert_eq!(draws.len(), 1);
+/// assert_eq!(draws[0].draws[1].instance_count, 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MeshPool {
+    block_vertices: u32,
+    block_indices: u32,
+    blocks: Vec<Block>,
+    /// Ranges by handle, `None` for removed meshes.
+    meshes: Vec<Option<MeshRange>>,
+    /// Handles of removed meshes, reused first.
+    free_handles: Vec<u32>,
+}
+
+impl Default for MeshPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_BLOCK_VERTICES, DEFAULT_BLOCK_INDICES)
+    }
+}
+
+impl MeshPool {
+    /// Creates an empty pool whose blocks hold `block_vertices` vertices
+    /// and `block_indices` indices.
+    #[must_use]
+    pub fn new(block_vertices: u32, block_indices: u32) -> Self {
+        Self {
+            block_vertices,
+            block_indices,
+            blocks: Vec::new(),
+            meshes: Vec::new(),
+            free_handles: Vec::new(),
+        }
+    }
+
+    /// Returns the number of blocks, each a vertex and an index buffer.
+    #[must_use]
+    #[inline]
+    pub fn blocks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns the bytes of one block's vertex buffer.
+    #[must_use]
+    pub fn block_vertex_bytes(&self) -> u64 {
+        u64::from(self.block_vertices) * size_of::<MeshVertex>() as u64
+    }
+
+    /// Returns the bytes of one block's index buffer.
+    #[must_use]
+    pub fn block_index_bytes(&self) -> u64 {
+        u64::from(self.block_indices) * size_of::<u32>() as u64
+    }
+
+    /// Returns the bytes of every block's buffers.
+    #[must_use]
+    pub fn allocated_bytes(&self) -> u64 {
+        self.blocks.len() as u64 * (self.block_vertex_bytes() + self.block_index_bytes())
+    }
+
+    /// Reserves room for a mesh of `vertices` vertices and `indices`
+    /// indices in the first block with space, creating a block if none has.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mesh is larger than a block.
+    #[allow(clippy::cast_possible_truncation, clippy::missing_panics_doc)]
+    pub fn insert(&mut self, vertices: u32, indices: u32) -> anyhow::Result<MeshHandle> {
+        if vertices > self.block_vertices || indices > self.block_indices {
+            bail!(
+                "mesh of {vertices} vertices and {indices} indices exceeds the pool's blocks of {} and {}",
+                self.block_vertices,
+                self.block_indices
+            );
+        }
+        let placed = self
+            .blocks
+            .iter_mut()
+            .enumerate()
+            .find_map(|(index, block)| Some((index, block.reserve(vertices, indices)?)));
+        let (block, (base_vertex, first_index)) = if let Some(placed) = placed {
+            placed
+        } else {
+            let mut block = Block {
+                vertices: RangeAllocator::new(self.block_vertices.into()),
+                indices: RangeAllocator::new(self.block_indices.into()),
+            };
+            let offsets = block
+                .reserve(vertices, indices)
+                .expect("an empty block fits any mesh within the block size");
+            self.blocks.push(block);
+            debug!(
+                target: "render::mesh_pool",
+                blocks = self.blocks.len(),
+                bytes = self.allocated_bytes(),
+                "created mesh pool block"
+            );
+            (self.blocks.len() - 1, offsets)
+        };
+        let range = MeshRange {
+            block: block as u32,
+            base_vertex,
+            vertex_count: vertices,
+            first_index,
+            index_count: indices,
+        };
+        let handle = if let Some(handle) = self.free_handles.pop() {
+            self.meshes[handle as usize] = Some(range);
+            handle
+        } else {
+            self.meshes.push(Some(range));
+            (self.meshes.len() - 1) as u32
+        };
+        Ok(MeshHandle(handle))
+    }
+
+    /// Reserves room for `mesh`; see [`MeshPool::insert`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mesh is larger than a block.
+    pub fn insert_mesh(&mut self, mesh: &Mesh) -> anyhow::Result<MeshHandle> {
+        let vertices = u32::try_from(mesh.vertices.len())?;
+        let indices = u32::try_from(mesh.indices.len())?;
+        self.insert(vertices, indices)
+    }
+
+    /// Returns where a mesh lives.
+    #[must_use]
+    pub fn get(&self, handle: MeshHandle) -> Option<MeshRange> {
+        self.meshes.get(handle.0 as usize).copied().flatten()
+    }
+
+    /// Frees a mesh's ranges, returning where it lived.
+    pub fn remove(&mut self, handle: MeshHandle) -> Option<MeshRange> {
+        let range = self.meshes.get_mut(handle.0 as usize)?.take()?;
+        let block = &mut self.blocks[range.block as usize];
+        if range.vertex_count > 0 {
+            block.vertices.free(range.base_vertex.into());
+        }
+        if range.index_count > 0 {
+            block.indices.free(range.first_index.into());
+        }
+        self.free_handles.push(handle.0);
+        Some(range)
+    }
+
+    /// Groups `(mesh, instances)` draws by block, keeping their order within
+    /// each block and skipping removed meshes. Instances are numbered
+    /// consecutively across all draws.
+    pub fn multi_draw(&self, draws: impl IntoIterator<Item = (MeshHandle, u32)>) -> Vec<MultiDraw> {
+        let mut groups: Vec<MultiDraw> = Vec::new();
+        let mut first_instance = 0;
+        for (handle, instances) in draws {
+            let Some(range) = self.get(handle) else {
+                continue;
+            };
+            let draw = range.draw(instances, first_instance);
+            first_instance += instances;
+            match groups.iter_mut().find(|group| group.block == range.block) {
+                Some(group) => group.draws.push(draw),
+                None => groups.push(MultiDraw {
+                    block: range.block,
+                    draws: vec![draw],
+                }),
+            }
+        }
+        groups
+    }
+
+    /// Compacts every block, updating mesh ranges, and returns the copies
+    /// to apply to the blocks' buffers, in order.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn defragment(&mut self) -> Vec<BufferCopy> {
+        let mut copies = Vec::new();
+        for (index, block) in self.blocks.iter_mut().enumerate() {
+            let index = index as u32;
+            for (buffer, allocator) in [
+                (PoolBuffer::Vertex, &mut block.vertices),
+                (PoolBuffer::Index, &mut block.indices),
+            ] {
+                copies.extend(allocator.compact().into_iter().map(|range| BufferCopy {
+                    block: index,
+                    buffer,
+                    range,
+                }));
+            }
+        }
+        for range in self.meshes.iter_mut().flatten() {
+            for copy in copies.iter().filter(|copy| copy.block == range.block) {
+                let offset = match copy.buffer {
+                    PoolBuffer::Vertex if range.vertex_count > 0 => &mut range.base_vertex,
+                    PoolBuffer::Index if range.index_count > 0 => &mut range.first_index,
+                    _ => continue,
+                };
+                if u64::from(*offset) == copy.range.from {
+                    *offset = copy.range.to as u32;
+                }
+            }
+        }
+        debug!(
+            target: "render::mesh_pool",
+            copies = copies.len(),
+            "defragmented mesh pool"
+        );
+        copies
+    }
+
+    /// Returns the occupancy of each block.
+    #[must_use]
+    pub fn stats(&self) -> Vec<BlockStats> {
+        self.blocks
+            .iter()
+            .map(|block| BlockStats {
+                vertices: block.vertices.stats(),
+                indices: block.indices.stats(),
+            })
+            .collect()
+    }
+
+    /// Returns a debug view of the pool: per block and buffer, an occupancy
+    /// map `width` cells wide and its fragmentation.
+    #[must_use]
+    pub fn debug_lines(&self, width: usize) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (index, block) in self.blocks.iter().enumerate() {
+            for (name, allocator) in [("vertices", &block.vertices), ("indices", &block.indices)] {
+                lines.push(format!(
+                    "block {index} {name:<8} [{}] {}",
+                    allocator.occupancy_map(width),
+                    allocator.stats()
+                ));
+            }
+        }
+        lines
+    }
+}
+
+impl Block {
+    /// Allocates both ranges, or neither if either does not fit.
+    #[allow(clippy::cast_possible_truncation)]
+    fn reserve(&mut self, vertices: u32, indices: u32) -> Option<(u32, u32)> {
+        let base_vertex = self.vertices.allocate(vertices.into())?;
+        let Some(first_index) = self.indices.allocate(indices.into()) else {
+            if vertices > 0 {
+                self.vertices.free(base_vertex);
+            }
+            return None;
+        };
+        Some((base_vertex as u32, first_index as u32))
+    }
+}
@@ -0,0 +1,157 @@
+//! Unit tests for the mesh pool.
+
+use super::pool::{MeshPool, PoolBuffer};
+use crate::mesh::{Mesh, MeshVertex};
+
+/// Verifies meshes share a block until it is full, then spill into a new one.
+#[test]
+fn insert_suballocates_blocks() {
+    let mut pool = MeshPool::new(100, 300);
+    let a = pool.insert(60, 180).unwrap();
+    let b = pool.insert(40, 90).unwrap();
+    let c = pool.insert(10, 30).unwrap();
+
+    let (a, b, c) = (
+        pool.get(a).unwrap(),
+        pool.get(b).unwrap(),
+        pool.get(c).unwrap(),
+    );
+    assert_eq!((a.block, a.base_vertex, a.first_index), (0, 0, 0));
+    assert_eq!((b.block, b.base_vertex, b.first_index), (0, 60, 180));
+    assert_eq!((c.block, c.base_vertex, c.first_index), (1, 0, 0));
+    assert_eq!(pool.blocks(), 2);
+    assert_eq!(
+        pool.allocated_bytes(),
+        2 * (100 * std::mem::size_of::<MeshVertex>() as u64 + 300 * 4)
+    );
+}
+
+/// Verifies a mesh fitting one buffer of a block but not the other leaves
+/// the block untouched.
+#[test]
+fn insert_rolls_back_partial_fit() {
+    let mut pool = MeshPool::new(100, 100);
+    pool.insert(10, 90).unwrap();
+    let spilled = pool.insert(10, 20).unwrap();
+    assert_eq!(pool.get(spilled).unwrap().block, 1);
+    assert_eq!(pool.stats()[0].vertices.used, 10);
+}
+
+/// Verifies meshes larger than a block are rejected.
+#[test]
+fn insert_larger_than_block() {
+    let mut pool = MeshPool::new(100, 300);
+    let error = pool.insert(101, 3).unwrap_err();
+    assert!(error.to_string().contains("exceeds"), "{error}");
+    assert_eq!(pool.blocks(), 0);
+}
+
+/// Verifies mesh sizes come from the mesh's vertices and indices.
+#[test]
+fn insert_mesh() {
+    let mesh = Mesh {
+        vertices: vec![MeshVertex::default(); 3],
+        indices: vec![0, 1, 2],
+    };
+    let mut pool = MeshPool::default();
+    let handle = pool.insert_mesh(&mesh).unwrap();
+    let range = pool.get(handle).unwrap();
+    assert_eq!((range.vertex_count, range.index_count), (3, 3));
+}
+
+/// Verifies removed meshes free their ranges and handles for reuse.
+#[test]
+fn remove_frees_ranges() {
+    let mut pool = MeshPool::new(100, 300);
+    let a = pool.insert(50, 150).unwrap();
+    pool.insert(50, 150).unwrap();
+
+    assert_eq!(pool.remove(a).unwrap().base_vertex, 0);
+    assert_eq!(pool.get(a), None);
+    assert_eq!(pool.remove(a), None);
+
+    let c = pool.insert(50, 150).unwrap();
+    assert_eq!(c, a);
+    let range = pool.get(c).unwrap();
+    assert_eq!((range.block, range.base_vertex), (0, 0));
+    assert_eq!(pool.blocks(), 1);
+}
+
+/// Verifies draws are grouped into one multi-draw per block with the
+/// meshes' offsets and consecutive instances.
+#[test]
+fn multi_draw_groups_by_block() {
+    let mut pool = MeshPool::new(100, 300);
+    let a = pool.insert(80, 240).unwrap();
+    let b = pool.insert(80, 240).unwrap();
+    let c = pool.insert(20, 60).unwrap();
+    let removed = pool.insert(10, 10).unwrap();
+    pool.remove(removed);
+
+    let groups = pool.multi_draw([(a, 2), (b, 1), (c, 5), (removed, 1)]);
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].block, 0);
+    assert_eq!(groups[1].block, 1);
+
+    let draws = &groups[0].draws;
+    assert_eq!(draws.len(), 2);
+    assert_eq!((draws[0].first_index, draws[0].instance_count), (0, 2));
+    assert_eq!(
+        (
+            draws[1].first_index,
+            draws[1].base_vertex,
+            draws[1].first_instance
+        ),
+        (240, 80, 3)
+    );
+    assert_eq!(groups[1].draws[0].first_instance, 2);
+}
+
+/// Verifies defragmentation compacts blocks and updates mesh offsets to
+/// match the returned copies.
+#[test]
+fn defragment_updates_handles() {
+    let mut pool = MeshPool::new(100, 300);
+    let handles: Vec<_> = (0..4).map(|_| pool.insert(20, 60).unwrap()).collect();
+    pool.remove(handles[0]);
+    pool.remove(handles[2]);
+    assert!(pool.stats()[0].vertices.fragmentation() > 0.0);
+
+    let copies = pool.defragment();
+    let vertex_copies = copies
+        .iter()
+        .filter(|copy| copy.buffer == PoolBuffer::Vertex)
+        .map(|copy| (copy.range.from, copy.range.to))
+        .collect::<Vec<_>>();
+    assert_eq!(vertex_copies, [(20, 0), (60, 20)]);
+    assert_eq!(copies.len(), 4);
+
+    let b = pool.get(handles[1]).unwrap();
+    let d = pool.get(handles[3]).unwrap();
+    assert_eq!((b.base_vertex, b.first_index), (0, 0));
+    assert_eq!((d.base_vertex, d.first_index), (20, 60));
+    assert!(pool.stats()[0].vertices.fragmentation().abs() < 1e-9);
+    assert_eq!(
+        pool.insert(60, 180)
+            .map(|h| pool.get(h).unwrap().block)
+            .ok(),
+        Some(0)
+    );
+}
+
+/// Verifies the debug view shows each block's buffers and fragmentation.
+#[test]
+fn debug_lines() {
+    let mut pool = MeshPool::new(100, 100);
+    let a = pool.insert(50, 50).unwrap();
+    pool.insert(25, 25).unwrap();
+    pool.remove(a);
+
+    assert_eq!(
+        pool.debug_lines(4),
+        [
+            "block 0 vertices [..#.] 25% used, 33% fragmented (1 allocations, 2 free ranges)",
+            "block 0 indices  [..#.] 25% used, 33% fragmented (1 allocations, 2 free ranges)",
+        ]
+    );
+}
@@ -0,0 +1,166 @@
+//! 9-slice scaling for sprites and UI images.
+//!
+//! A 9-slice image is cut by four borders into corners, edges, and a
+//! center. When drawn at another size the corners keep their pixel size,
+//! the edges stretch along one axis, and the center stretches along both,
+//! so panels and buttons scale without distorting their frames.
+//!
+//! Borders are stored on the texture, per atlas frame in
+//! [`TextureAtlas::slices`](crate::TextureAtlas::slices), or on the
+//! component through [`ImageScaling::Sliced`], which takes precedence.
+
+use math::{Rect, Vec2};
+use serde::{Deserialize, Serialize};
+
+/// Border widths, in texture pixels, of a 9-slice image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct NineSlice {
+    /// Left border.
+    pub left: u32,
+    /// Right border.
+    pub right: u32,
+    /// Top border.
+    pub top: u32,
+    /// Bottom border.
+    pub bottom: u32,
+}
+
+/// How an image is fitted to a size other than its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageScaling {
+    /// Uses the texture's borders if it has any, otherwise stretches.
+    #[default]
+    Auto,
+    /// Stretches the whole image.
+    Stretch,
+    /// Scales as a 9-slice image with these borders.
+    Sliced(NineSlice),
+}
+
+impl ImageScaling {
+    /// Returns the borders to draw with, given the texture's own.
+    #[must_use]
+    pub fn borders(self, texture: Option<NineSlice>) -> Option<NineSlice> {
+        match self {
+            Self::Auto => texture,
+            Self::Stretch => None,
+            Self::Sliced(borders) => Some(borders),
+        }
+    }
+}
+
+/// One textured quad of a scaled image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SliceQuad {
+    /// Destination rectangle.
+    pub position: Rect,
+    /// Texture coordinates.
+    pub uv: Rect,
+}
+
+impl NineSlice {
+    /// Creates borders of the same width on every side.
+    #[must_use]
+    pub const fn uniform(border: u32) -> Self {
+        Self {
+            left: border,
+            right: border,
+            top: border,
+            bottom: border,
+        }
+    }
+
+    /// Splits an image into the quads that draw it into `target`.
+    ///
+    /// `uv` is the image's region of the texture and `source_size` its size
+    /// in pixels. Corners keep their pixel size unless `target` is too small
+    /// for the borders, in which case opposite borders shrink in proportion
+    /// and the middle row or column disappears. Quads with no area are
+    /// skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use math::{Rect, Vec2};
+    /// use render::nine_slice::NineSlice;
+    ///
+    /// let panel = NineSlice::uniform(8);
+    /// let uv = Rect::new(Vec2::ZERO, Vec2::ONE);
+    /// let quads = panel.quads(uv, (32, 32), Rect::new(Vec2::ZERO, Vec2::new(200.0, 100.0)));
+    ///
+    /// assert_eq!(quads.len(), 9);
+    /// // The top-left corner keeps its 8 pixels
+    /// assert_eq!(quads[0].position, Rect::new(Vec2::ZERO, Vec2::splat(8.0)));
+    /// // The center stretches
+    /// assert_eq!(quads[4].position.size(), Vec2::new(184.0, 84.0));
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn quads(self, uv: Rect, source_size: (u32, u32), target: Rect) -> Vec<SliceQuad> {
+        let (source_width, source_height) =
+            (source_size.0.max(1) as f32, source_size.1.max(1) as f32);
+        let xs = cuts(
+            [target.min.x, target.max.x],
+            [uv.min.x, uv.max.x],
+            (self.left as f32, self.right as f32),
+            source_width,
+        );
+        let ys = cuts(
+            [target.min.y, target.max.y],
+            [uv.min.y, uv.max.y],
+            (self.top as f32, self.bottom as f32),
+            source_height,
+        );
+
+        let mut quads = Vec::with_capacity(9);
+        for row in 0..3 {
+            for column in 0..3 {
+                let (x0, x1) = (xs[column], xs[column + 1]);
+                let (y0, y1) = (ys[row], ys[row + 1]);
+                if x1.0 - x0.0 > 0.0 && y1.0 - y0.0 > 0.0 {
+                    quads.push(SliceQuad {
+                        position: Rect::new(Vec2::new(x0.0, y0.0), Vec2::new(x1.0, y1.0)),
+                        uv: Rect::new(Vec2::new(x0.1, y0.1), Vec2::new(x1.1, y1.1)),
+                    });
+                }
+            }
+        }
+        quads
+    }
+}
+
+/// Returns the four cuts along one axis as `(position, uv)` pairs.
+fn cuts(target: [f32; 2], uv: [f32; 2], (start, end): (f32, f32), source: f32) -> [(f32, f32); 4] {
+    let length = (target[1] - target[0]).max(0.0);
+    let borders = (start + end).min(source);
+    let scale = if borders > length && borders > 0.0 {
+        length / borders
+    } else {
+        1.0
+    };
+    let uv_per_pixel = (uv[1] - uv[0]) / source;
+    [
+        (target[0], uv[0]),
+        (target[0] + start * scale, uv[0] + start * uv_per_pixel),
+        (target[1] - end * scale, uv[1] - end * uv_per_pixel),
+        (target[1], uv[1]),
+    ]
+}
+
+/// Returns the quads that draw an image into `target` with `borders`, or a
+/// single stretched quad without them.
+#[must_use]
+pub fn scaled_quads(
+    borders: Option<NineSlice>,
+    uv: Rect,
+    source_size: (u32, u32),
+    target: Rect,
+) -> Vec<SliceQuad> {
+    match borders {
+        Some(borders) => borders.quads(uv, source_size, target),
+        None => vec![SliceQuad {
+            position: target,
+            uv,
+        }],
+    }
+}
@@ -0,0 +1,110 @@
+//! Unit tests for 9-slice scaling.
+
+use crate::nine_slice::{scaled_quads, ImageScaling, NineSlice, SliceQuad};
+use crate::sprite::TextureAtlas;
+use math::{Rect, Vec2};
+
+fn rect(x0: f32, y0: f32, x1: f32, y1: f32) -> Rect {
+    Rect::new(Vec2::new(x0, y0), Vec2::new(x1, y1))
+}
+
+fn quad(position: [f32; 4], uv: [f32; 4]) -> SliceQuad {
+    SliceQuad {
+        position: rect(position[0], position[1], position[2], position[3]),
+        uv: rect(uv[0], uv[1], uv[2], uv[3]),
+    }
+}
+
+/// Verifies corners keep their size while edges and center stretch.
+#[test]
+fn corners_keep_their_size() {
+    let borders = NineSlice {
+        left: 4,
+        right: 8,
+        top: 2,
+        bottom: 6,
+    };
+    let quads = borders.quads(
+        rect(0.0, 0.0, 1.0, 1.0),
+        (16, 16),
+        rect(10.0, 20.0, 110.0, 70.0),
+    );
+
+    assert_eq!(quads.len(), 9);
+    assert_eq!(
+        quads[0],
+        quad([10.0, 20.0, 14.0, 22.0], [0.0, 0.0, 0.25, 0.125])
+    );
+    assert_eq!(
+        quads[4],
+        quad([14.0, 22.0, 102.0, 64.0], [0.25, 0.125, 0.5, 0.625])
+    );
+    assert_eq!(
+        quads[8],
+        quad([102.0, 64.0, 110.0, 70.0], [0.5, 0.625, 1.0, 1.0])
+    );
+}
+
+/// Verifies borders shrink in proportion when the target is too small.
+#[test]
+fn small_targets_shrink_borders() {
+    let quads = NineSlice::uniform(8).quads(
+        rect(0.0, 0.0, 1.0, 1.0),
+        (32, 32),
+        rect(0.0, 0.0, 8.0, 100.0),
+    );
+
+    // The middle column is gone; each side gets half the width
+    assert_eq!(quads.len(), 6);
+    assert_eq!(quads[0], quad([0.0, 0.0, 4.0, 8.0], [0.0, 0.0, 0.25, 0.25]));
+    assert_eq!(quads[1], quad([4.0, 0.0, 8.0, 8.0], [0.75, 0.0, 1.0, 0.25]));
+}
+
+/// Verifies images without borders are drawn as one stretched quad.
+#[test]
+fn stretch_without_borders() {
+    let uv = rect(0.5, 0.0, 1.0, 0.5);
+    let target = rect(0.0, 0.0, 300.0, 40.0);
+
+    assert_eq!(
+        scaled_quads(None, uv, (16, 16), target),
+        [SliceQuad {
+            position: target,
+            uv
+        }]
+    );
+    assert_eq!(
+        ImageScaling::Stretch.borders(Some(NineSlice::uniform(2))),
+        None
+    );
+    assert_eq!(
+        ImageScaling::Auto.borders(Some(NineSlice::uniform(2))),
+        Some(NineSlice::uniform(2))
+    );
+}
+
+/// Verifies atlas frames use their stored borders unless overridden.
+#[test]
+fn atlas_frames_use_stored_borders() {
+    let atlas = TextureAtlas::grid("ui.png", (16, 16), 2, 1).with_slice(1, NineSlice::uniform(4));
+    let target = rect(0.0, 0.0, 64.0, 64.0);
+
+    assert_eq!(atlas.frame_quads(0, ImageScaling::Auto, target).len(), 1);
+    let panel = atlas.frame_quads(1, ImageScaling::Auto, target);
+    assert_eq!(panel.len(), 9);
+    assert_eq!(
+        panel[0],
+        quad([0.0, 0.0, 4.0, 4.0], [0.5, 0.0, 0.625, 0.25])
+    );
+    assert_eq!(atlas.frame_quads(1, ImageScaling::Stretch, target).len(), 1);
+    assert_eq!(
+        atlas
+            .frame_quads(0, ImageScaling::Sliced(NineSlice::uniform(2)), target)
+            .len(),
+        9
+    );
+    assert!(atlas.frame_quads(5, ImageScaling::Auto, target).is_empty());
+
+    let text = atlas.to_toml().unwrap();
+    assert_eq!(TextureAtlas::from_toml(&text).unwrap(), atlas);
+}
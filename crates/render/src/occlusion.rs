@@ -0,0 +1,267 @@
+//! Hierarchical-Z occlusion culling.
+//!
+//! After frustum culling, opaque occluders are drawn into a low-resolution
+//! depth pre-pass (1/[`DEPTH_PREPASS_SCALE`] of the view size). A
+//! [`DepthPyramid`] is built from it where every texel of a level holds the
+//! farthest depth of the 2x2 texels below it, and each remaining draw's
+//! bounding box is tested against the level where it covers at most 2x2
+//! texels. A box whose nearest point is behind the farthest depth over its
+//! screen rectangle is hidden and its draw call is skipped.
+//!
+//! The GPU path runs the reduction and the test in compute shaders, so it is
+//! only enabled when [`GpuCapabilities::occlusion_culling`] allows it; the
+//! CPU implementation here is the reference the shaders are tested against.
+//!
+//! Depth follows wgpu's convention: 0 at the near plane, 1 at the far plane.
+
+use crate::capabilities::{GpuCapabilities, OcclusionCulling};
+use crate::mesh::Aabb;
+use math::Mat4;
+
+/// Divisor applied to the view size for the depth pre-pass.
+pub const DEPTH_PREPASS_SCALE: u32 = 4;
+
+/// A max-reduced depth mip chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepthPyramid {
+    /// Levels from full pre-pass resolution down to 1x1, row-major.
+    levels: Vec<Vec<f32>>,
+    /// Width of level 0 in texels.
+    width: u32,
+    /// Height of level 0 in texels.
+    height: u32,
+}
+
+impl DepthPyramid {
+    /// Builds the pyramid from a `width` x `height` depth buffer.
+    ///
+    /// Odd sizes round up, so the last row and column of a level also cover
+    /// the texels past the edge of the level below.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `depth` does not hold `width * height` values or either
+    /// size is zero.
+    #[must_use]
+    pub fn build(width: u32, height: u32, depth: &[f32]) -> Self {
+        assert!(width > 0 && height > 0, "depth buffer is empty");
+        assert_eq!(
+            depth.len(),
+            width as usize * height as usize,
+            "depth buffer size mismatch"
+        );
+        let mut levels = vec![depth.to_vec()];
+        let (mut w, mut h) = (width, height);
+        while w > 1 || h > 1 {
+            let (next_w, next_h) = (w.div_ceil(2), h.div_ceil(2));
+            let below = &levels[levels.len() - 1];
+            let texel = |x: u32, y: u32| below[(y.min(h - 1) * w + x.min(w - 1)) as usize];
+            let mut level = Vec::with_capacity(next_w as usize * next_h as usize);
+            for y in 0..next_h {
+                for x in 0..next_w {
+                    let (x, y) = (x * 2, y * 2);
+                    level.push(
+                        texel(x, y)
+                            .max(texel(x + 1, y))
+                            .max(texel(x, y + 1))
+                            .max(texel(x + 1, y + 1)),
+                    );
+                }
+            }
+            levels.push(level);
+            (w, h) = (next_w, next_h);
+        }
+        Self {
+            levels,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the number of mip levels.
+    #[must_use]
+    #[inline]
+    pub fn level_count(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Returns the size of `level` in texels.
+    #[must_use]
+    pub fn level_size(&self, level: usize) -> (u32, u32) {
+        let shrink = |size: u32| (0..level).fold(size, |size, _| size.div_ceil(2));
+        (shrink(self.width), shrink(self.height))
+    }
+
+    /// Returns the farthest depth over the inclusive texel rectangle
+    /// `[x0, y0]..=[x1, y1]` of `level`, clamped to the level.
+    #[must_use]
+    pub fn max_depth(&self, level: usize, [x0, y0]: [u32; 2], [x1, y1]: [u32; 2]) -> f32 {
+        let (w, h) = self.level_size(level);
+        let texels = &self.levels[level];
+        let mut depth = 0.0_f32;
+        for y in y0.min(h - 1)..=y1.min(h - 1) {
+            for x in x0.min(w - 1)..=x1.min(w - 1) {
+                depth = depth.max(texels[(y * w + x) as usize]);
+            }
+        }
+        depth
+    }
+
+    /// Returns `true` if `bounds` is certainly hidden behind the depth the
+    /// pyramid was built from.
+    ///
+    /// Boxes crossing the near plane or lying outside the view are never
+    /// reported hidden; frustum culling handles the latter.
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn is_occluded(&self, view_proj: &Mat4, bounds: &Aabb) -> bool {
+        let mut min = [f32::INFINITY; 2];
+        let mut max = [f32::NEG_INFINITY; 2];
+        let mut nearest = f32::INFINITY;
+        for corner in bounds.corners() {
+            let [x, y, z, w] = (*view_proj * corner.extend(1.0)).to_array();
+            if w <= f32::EPSILON {
+                return false;
+            }
+            let ndc = [x / w, y / w];
+            for axis in 0..2 {
+                min[axis] = min[axis].min(ndc[axis]);
+                max[axis] = max[axis].max(ndc[axis]);
+            }
+            nearest = nearest.min(z / w);
+        }
+        if max[0] < -1.0 || min[0] > 1.0 || max[1] < -1.0 || min[1] > 1.0 || nearest < 0.0 {
+            return false;
+        }
+
+        // NDC y points up, texel rows go down.
+        let (width, height) = (self.width as f32, self.height as f32);
+        let to_texel = |ndc: f32, size: f32| (ndc.clamp(-1.0, 1.0) + 1.0) * 0.5 * size;
+        let x0 = to_texel(min[0], width).floor() as u32;
+        let x1 = (to_texel(max[0], width).ceil() as u32)
+            .saturating_sub(1)
+            .max(x0);
+        let y0 = to_texel(-max[1], height).floor() as u32;
+        let y1 = (to_texel(-min[1], height).ceil() as u32)
+            .saturating_sub(1)
+            .max(y0);
+
+        // Finest level at which the rectangle spans at most 2x2 texels.
+        let extent = (x1 - x0 + 1).max(y1 - y0 + 1);
+        let level =
+            (extent.next_power_of_two().trailing_zeros() as usize).min(self.level_count() - 1);
+        let occluder = self.max_depth(
+            level,
+            [x0 >> level, y0 >> level],
+            [x1 >> level, y1 >> level],
+        );
+        nearest > occluder
+    }
+}
+
+/// Draw counts of one occlusion pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OcclusionStats {
+    /// Draws tested against the pyramid.
+    pub tested: usize,
+    /// Draws skipped because they were hidden.
+    pub culled: usize,
+}
+
+/// Occlusion stage of the culling pipeline.
+///
+/// # Example
+///
+/// ```
+/// use math::{Mat4, Vec3};
+/// use render::mesh::Aabb;
+/// use render::occlusion::{DepthPyramid, OcclusionCuller};
+/// use render::GpuCapabilities;
+///
+/// let culler = OcclusionCuller::new(&GpuCapabilities::baseline(), true);
+/// // A wall filling the screen at depth 0.25.
+/// let pyramid = DepthPyramid::build(4, 4, &[0.25; 16]);
+/// let behind = Aabb::new(Vec3::new(-0.5, -0.5, 0.5), Vec3::new(0.5, 0.5, 0.6));
+/// let before = Aabb::new(Vec3::new(-0.5, -0.5, 0.1), Vec3::new(0.5, 0.5, 0.2));
+///
+/// let (visible, stats) = culler.cull(Some(&pyramid), &Mat4::IDENTITY, [("behind", behind), ("before", before)]);
+/// assert_eq!(visible, ["before"]);
+/// assert_eq!(stats.culled, 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OcclusionCuller {
+    /// Technique in use.
+    mode: OcclusionCulling,
+}
+
+impl OcclusionCuller {
+    /// Creates the stage for the adapter; `enabled` lets platforms opt out
+    /// even when the adapter supports it.
+    #[must_use]
+    pub fn new(capabilities: &GpuCapabilities, enabled: bool) -> Self {
+        let mode = if enabled {
+            capabilities.occlusion_culling()
+        } else {
+            OcclusionCulling::Disabled
+        };
+        Self { mode }
+    }
+
+    /// Returns the technique in use.
+    #[must_use]
+    #[inline]
+    pub fn mode(&self) -> OcclusionCulling {
+        self.mode
+    }
+
+    /// Returns `true` if a depth pre-pass and pyramid are needed.
+    #[must_use]
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.mode == OcclusionCulling::HiZ
+    }
+
+    /// Returns the depth pre-pass size for a view of `width` x `height`.
+    #[must_use]
+    pub fn prepass_size(width: u32, height: u32) -> (u32, u32) {
+        (
+            width.div_ceil(DEPTH_PREPASS_SCALE).max(1),
+            height.div_ceil(DEPTH_PREPASS_SCALE).max(1),
+        )
+    }
+
+    /// Returns the draws of frustum-visible `items` not hidden in
+    /// `pyramid`, in input order.
+    ///
+    /// Everything is kept when the stage is disabled or no pyramid was
+    /// built this frame.
+    pub fn cull<T>(
+        &self,
+        pyramid: Option<&DepthPyramid>,
+        view_proj: &Mat4,
+        items: impl IntoIterator<Item = (T, Aabb)>,
+    ) -> (Vec<T>, OcclusionStats) {
+        let mut stats = OcclusionStats::default();
+        let pyramid = pyramid.filter(|_| self.is_enabled());
+        let visible = items
+            .into_iter()
+            .filter_map(|(item, bounds)| {
+                let Some(pyramid) = pyramid else {
+                    return Some(item);
+                };
+                stats.tested += 1;
+                if pyramid.is_occluded(view_proj, &bounds) {
+                    stats.culled += 1;
+                    None
+                } else {
+                    Some(item)
+                }
+            })
+            .collect();
+        (visible, stats)
+    }
+}
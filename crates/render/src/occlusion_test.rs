@@ -0,0 +1,105 @@
+//! Unit tests for the depth pyramid and occlusion culling.
+
+use crate::capabilities::{GpuCapabilities, GpuFeatures, OcclusionCulling};
+use crate::mesh::Aabb;
+use crate::occlusion::{DepthPyramid, OcclusionCuller};
+use crate::RenderLimits;
+use math::{Mat4, Vec3, Vec4};
+
+/// Box spanning `[x0, x1]` x `[y0, y1]` in NDC between depths `near` and `far`.
+fn ndc_box([x0, x1]: [f32; 2], [y0, y1]: [f32; 2], [near, far]: [f32; 2]) -> Aabb {
+    Aabb {
+        min: Vec3::new(x0, y0, near),
+        max: Vec3::new(x1, y1, far),
+    }
+}
+
+/// Verifies every level keeps the farthest depth of the texels below.
+#[test]
+fn pyramid_reduces_to_max_depth() {
+    #[rustfmt::skip]
+    let depth = [
+        0.1, 0.2, 0.3,
+        0.4, 0.5, 0.6,
+        0.7, 0.8, 0.9,
+    ];
+    let pyramid = DepthPyramid::build(3, 3, &depth);
+
+    assert_eq!(pyramid.level_count(), 3);
+    assert_eq!(pyramid.level_size(1), (2, 2));
+    assert_eq!(
+        pyramid.max_depth(1, [0, 0], [0, 0]).to_bits(),
+        0.5_f32.to_bits()
+    );
+    // Odd edge texels cover only the last column and row.
+    assert_eq!(
+        pyramid.max_depth(1, [1, 0], [1, 0]).to_bits(),
+        0.6_f32.to_bits()
+    );
+    assert_eq!(
+        pyramid.max_depth(2, [0, 0], [0, 0]).to_bits(),
+        0.9_f32.to_bits()
+    );
+}
+
+/// Verifies boxes behind an occluder are hidden and boxes in front are not.
+#[test]
+fn boxes_behind_occluders_are_hidden() {
+    // Left half of the screen is covered by a wall at depth 0.3.
+    let depth: Vec<f32> = (0..64).map(|i| if i % 8 < 4 { 0.3 } else { 1.0 }).collect();
+    let pyramid = DepthPyramid::build(8, 8, &depth);
+
+    let behind_wall = ndc_box([-0.9, -0.2], [-0.5, 0.5], [0.5, 0.6]);
+    let before_wall = ndc_box([-0.9, -0.2], [-0.5, 0.5], [0.1, 0.2]);
+    let beside_wall = ndc_box([0.2, 0.9], [-0.5, 0.5], [0.5, 0.6]);
+    let straddling = ndc_box([-0.5, 0.5], [-0.5, 0.5], [0.5, 0.6]);
+
+    assert!(pyramid.is_occluded(&Mat4::IDENTITY, &behind_wall));
+    assert!(!pyramid.is_occluded(&Mat4::IDENTITY, &before_wall));
+    assert!(!pyramid.is_occluded(&Mat4::IDENTITY, &beside_wall));
+    assert!(!pyramid.is_occluded(&Mat4::IDENTITY, &straddling));
+}
+
+/// Verifies boxes behind the camera or off screen are left to frustum culling.
+#[test]
+fn boxes_outside_view_are_kept() {
+    let pyramid = DepthPyramid::build(4, 4, &[0.0; 16]);
+    let mut behind_camera = Mat4::IDENTITY;
+    behind_camera.z_axis = Vec4::new(0.0, 0.0, 1.0, -1.0);
+    behind_camera.w_axis = Vec4::ZERO;
+
+    assert!(!pyramid.is_occluded(
+        &behind_camera,
+        &ndc_box([-0.5, 0.5], [-0.5, 0.5], [1.0, 2.0])
+    ));
+    assert!(!pyramid.is_occluded(
+        &Mat4::IDENTITY,
+        &ndc_box([2.0, 3.0], [-0.5, 0.5], [0.5, 0.6])
+    ));
+}
+
+/// Verifies occlusion culling needs compute support and can be opted out of.
+#[test]
+fn culler_follows_capabilities() {
+    let limits = RenderLimits {
+        max_compute_invocations_per_workgroup: 0,
+        ..RenderLimits::for_target()
+    };
+    let no_compute = GpuCapabilities::from_adapter("GL", GpuFeatures::default(), limits);
+    let pyramid = DepthPyramid::build(1, 1, &[0.0]);
+    let hidden = ndc_box([-0.5, 0.5], [-0.5, 0.5], [0.5, 0.6]);
+
+    assert_eq!(no_compute.occlusion_culling(), OcclusionCulling::Disabled);
+    assert!(!OcclusionCuller::new(&no_compute, true).is_enabled());
+    assert!(!OcclusionCuller::new(&GpuCapabilities::baseline(), false).is_enabled());
+
+    let culler = OcclusionCuller::new(&GpuCapabilities::baseline(), true);
+    assert_eq!(culler.mode(), OcclusionCulling::HiZ);
+    let (visible, stats) = culler.cull(Some(&pyramid), &Mat4::IDENTITY, [(1, hidden)]);
+    assert!(visible.is_empty());
+    assert_eq!((stats.tested, stats.culled), (1, 1));
+    let (visible, stats) = culler.cull(None, &Mat4::IDENTITY, [(1, hidden)]);
+    assert_eq!(visible, [1]);
+    assert_eq!(stats.tested, 0);
+    assert_eq!(OcclusionCuller::prepass_size(1920, 1080), (480, 270));
+}
@@ -0,0 +1,384 @@
+//! Render pipeline cache with background compilation.
+//!
+//! Creating a pipeline compiles its shaders, which can take long enough to
+//! drop frames. [`PipelineCache`] compiles each distinct [`PipelineKey`] once
+//! on a scheduler worker thread and hands out a fallback pipeline, such as
+//! a flat "loading" material, until the real one is ready.
+//!
+//! Backends that can serialize compiled pipelines return the bytes from
+//! [`PipelineCompiler::compile`]; with a disk cache configured they are
+//! written to disk and passed back on the next run to skip recompilation.
+//! Artifacts are stored under the key and the compiler's
+//! [`cache_version`](PipelineCompiler::cache_version), so an edited shader
+//! misses the cache.
+
+use scheduler::spawn_background;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::hash::Hash;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tracing::{debug, warn};
+
+/// How fragments are blended into the target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BlendMode {
+    /// Fragments replace the target.
+    #[default]
+    Opaque,
+    /// Standard alpha blending.
+    Alpha,
+    /// Fragments are added to the target.
+    Additive,
+}
+
+/// Which triangle faces are culled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CullMode {
+    /// Nothing is culled.
+    None,
+    /// Back faces are culled.
+    #[default]
+    Back,
+    /// Front faces are culled.
+    Front,
+}
+
+/// Fixed-function state baked into a pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PipelineState {
+    /// Blending with the color target.
+    pub blend: BlendMode,
+    /// Face culling.
+    pub cull: CullMode,
+    /// Whether fragments are depth tested and written.
+    pub depth: bool,
+}
+
+/// Identifies a pipeline: the shader, its preprocessor defines, and state.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PipelineKey {
+    /// Shader asset path.
+    pub shader: String,
+    /// Preprocessor defines, kept sorted so equivalent keys compare equal.
+    pub defines: Vec<String>,
+    /// Fixed-function state.
+    pub state: PipelineState,
+}
+
+impl PipelineKey {
+    /// Creates a key for `shader` with default state and no defines.
+    #[must_use]
+    pub fn new(shader: impl Into<String>) -> Self {
+        Self {
+            shader: shader.into(),
+            defines: Vec::new(),
+            state: PipelineState::default(),
+        }
+    }
+
+    /// Adds a preprocessor define.
+    #[must_use]
+    pub fn define(mut self, define: impl Into<String>) -> Self {
+        let define = define.into();
+        if let Err(index) = self.defines.binary_search(&define) {
+            self.defines.insert(index, define);
+        }
+        self
+    }
+
+    /// Sets the fixed-function state.
+    #[must_use]
+    pub fn with_state(mut self, state: PipelineState) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// Returns a file name for the key compiled from inputs at `version`
+    /// that is stable across runs.
+    fn cache_file_name(&self, version: u64) -> String {
+        // FNV-1a over the display form; `DefaultHasher` is not stable
+        let hash = self
+            .to_string()
+            .bytes()
+            .chain(version.to_le_bytes())
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            });
+        format!("{hash:016x}.bin")
+    }
+}
+
+impl fmt::Display for PipelineKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.shader)?;
+        for define in &self.defines {
+            write!(f, " +{define}")?;
+        }
+        write!(
+            f,
+            " [{:?}, cull {:?}, depth {}]",
+            self.state.blend, self.state.cull, self.state.depth
+        )
+    }
+}
+
+/// A compiled pipeline and, if the backend supports it, its serialized form.
+#[derive(Debug)]
+pub struct Compiled<P> {
+    /// The pipeline, ready to bind.
+    pub pipeline: P,
+    /// Bytes to store in the disk cache.
+    pub artifact: Option<Vec<u8>>,
+}
+
+/// Backend that turns pipeline keys into pipelines.
+pub trait PipelineCompiler: Send + Sync + 'static {
+    /// The backend's pipeline object.
+    type Pipeline: Send + Sync + 'static;
+
+    /// Returns a value that changes whenever the inputs of `key` that the
+    /// key does not name change, such as a hash of the shader source and
+    /// the compiler version.
+    ///
+    /// Disk cache artifacts are stored under it, so that a changed shader
+    /// is compiled again instead of loaded from a stale artifact. Defaults
+    /// to 0, for backends without a disk cache.
+    fn cache_version(&self, key: &PipelineKey) -> u64 {
+        let _ = key;
+        0
+    }
+
+    /// Compiles the pipeline for `key`.
+    ///
+    /// `cached` holds the artifact stored by a previous run, if any; the
+    /// backend may use it to skip shader compilation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the shader fails to compile or link.
+    fn compile(
+        &self,
+        key: &PipelineKey,
+        cached: Option<&[u8]>,
+    ) -> anyhow::Result<Compiled<Self::Pipeline>>;
+}
+
+/// Compilation state of a pipeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PipelineStatus {
+    /// Not requested yet.
+    Missing,
+    /// Compiling in the background; the fallback is used meanwhile.
+    Compiling,
+    /// Compiled and in use.
+    Ready,
+    /// Compilation failed; the fallback stays in use.
+    Failed(String),
+}
+
+/// Cache entry.
+enum Entry<P> {
+    /// Compiling under the given request number.
+    Compiling(u64),
+    Ready(Arc<P>),
+    Failed(String),
+}
+
+/// Shared state of a [`PipelineCache`].
+struct Shared<C: PipelineCompiler> {
+    compiler: C,
+    fallback: Arc<C::Pipeline>,
+    disk_cache: Option<PathBuf>,
+    entries: Mutex<HashMap<PipelineKey, Entry<C::Pipeline>>>,
+    /// Request number of the next compilation.
+    next_request: AtomicU64,
+}
+
+/// Cache of pipelines compiled in the background.
+///
+/// `PipelineCache` is a cheap handle; clones share the same cache.
+///
+/// # Example
+///
+/// ```ignore
+/// use render::pipeline_cache::{PipelineCache, PipelineKey};
+///
+/// let cache = PipelineCache::new(compiler, loading_material_pipeline)
+///     .with_disk_cache(cache_dir);
+///
+/// // Returns the loading pipeline until "lit.wgsl" has compiled
+/// let pipeline = cache.get(&PipelineKey::new("lit.wgsl").define("SHADOWS"));
+/// ```
+pub struct PipelineCache<C: PipelineCompiler> {
+    shared: Arc<Shared<C>>,
+}
+
+impl<C: PipelineCompiler> Clone for PipelineCache<C> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<C: PipelineCompiler> PipelineCache<C> {
+    /// Creates a cache compiling with `compiler` and serving `fallback`
+    /// while pipelines compile or after they fail.
+    #[must_use]
+    pub fn new(compiler: C, fallback: C::Pipeline) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                compiler,
+                fallback: Arc::new(fallback),
+                disk_cache: None,
+                entries: Mutex::new(HashMap::new()),
+                next_request: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Stores compiled artifacts in `dir` and reuses them on later runs.
+    ///
+    /// Must be called before the cache is cloned or used.
+    #[must_use]
+    pub fn with_disk_cache(mut self, dir: impl Into<PathBuf>) -> Self {
+        if let Some(shared) = Arc::get_mut(&mut self.shared) {
+            shared.disk_cache = Some(dir.into());
+        }
+        self
+    }
+
+    /// Returns the pipeline for `key`, or the fallback until it is ready.
+    ///
+    /// The first request for a key starts its compilation in the background.
+    #[must_use]
+    pub fn get(&self, key: &PipelineKey) -> Arc<C::Pipeline> {
+        let request = {
+            let mut entries = self.shared.entries();
+            match entries.get(key) {
+                Some(Entry::Ready(pipeline)) => return Arc::clone(pipeline),
+                Some(Entry::Compiling(_) | Entry::Failed(_)) => {
+                    return Arc::clone(&self.shared.fallback)
+                }
+                None => {
+                    let request = self.shared.next_request.fetch_add(1, Ordering::Relaxed);
+                    entries.insert(key.clone(), Entry::Compiling(request));
+                    request
+                }
+            }
+        };
+
+        debug!(pipeline = %key, "compiling pipeline in the background");
+        let shared = Arc::clone(&self.shared);
+        let key = key.clone();
+        spawn_background("pipeline compile", move || shared.compile(&key, request));
+        Arc::clone(&self.shared.fallback)
+    }
+
+    /// Returns the compilation state of `key`.
+    #[must_use]
+    pub fn status(&self, key: &PipelineKey) -> PipelineStatus {
+        match self.shared.entries().get(key) {
+            None => PipelineStatus::Missing,
+            Some(Entry::Compiling(_)) => PipelineStatus::Compiling,
+            Some(Entry::Ready(_)) => PipelineStatus::Ready,
+            Some(Entry::Failed(error)) => PipelineStatus::Failed(error.clone()),
+        }
+    }
+
+    /// Returns the number of pipelines still compiling.
+    #[must_use]
+    pub fn compiling(&self) -> usize {
+        self.shared
+            .entries()
+            .values()
+            .filter(|entry| matches!(entry, Entry::Compiling(_)))
+            .count()
+    }
+
+    /// Drops `key` so the next [`get`](Self::get) recompiles it, e.g. after
+    /// its shader changed on disk, and deletes its disk cache artifact.
+    pub fn invalidate(&self, key: &PipelineKey) {
+        self.shared.entries().remove(key);
+        if let Some(path) = self.shared.cache_file(key) {
+            if let Err(e) = fs::remove_file(&path) {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    warn!(pipeline = %key, error = %e, "failed to delete cached pipeline");
+                }
+            }
+        }
+    }
+}
+
+impl<C: PipelineCompiler> Shared<C> {
+    /// Returns the disk cache artifact path of `key`, if caching to disk.
+    fn cache_file(&self, key: &PipelineKey) -> Option<PathBuf> {
+        self.disk_cache
+            .as_ref()
+            .map(|dir| dir.join(key.cache_file_name(self.compiler.cache_version(key))))
+    }
+
+    /// Compiles `key` and stores the result if `request` is still current.
+    ///
+    /// A panicking compiler fails the pipeline rather than leaving it
+    /// compiling forever.
+    fn compile(&self, key: &PipelineKey, request: u64) {
+        let cache_file = self.cache_file(key);
+        let cached = cache_file.as_ref().and_then(|path| fs::read(path).ok());
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            self.compiler.compile(key, cached.as_deref())
+        }))
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("pipeline compiler panicked")));
+        let entry = match result {
+            Ok(compiled) => {
+                if let (Some(path), Some(artifact)) = (&cache_file, &compiled.artifact) {
+                    if cached.as_ref() != Some(artifact) {
+                        if let Err(e) = write_artifact(path, artifact) {
+                            warn!(pipeline = %key, error = %e, "failed to cache pipeline");
+                        }
+                    }
+                }
+                debug!(pipeline = %key, "pipeline ready");
+                Entry::Ready(Arc::new(compiled.pipeline))
+            }
+            Err(e) => {
+                warn!(pipeline = %key, error = %e, "pipeline failed to compile");
+                Entry::Failed(e.to_string())
+            }
+        };
+
+        // A key invalidated meanwhile is left alone so it recompiles on next use
+        let mut entries = self.entries();
+        if let Some(slot) = entries.get_mut(key) {
+            if matches!(slot, Entry::Compiling(current) if *current == request) {
+                *slot = entry;
+            }
+        }
+    }
+
+    fn entries(&self) -> MutexGuard<'_, HashMap<PipelineKey, Entry<C::Pipeline>>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Writes a compiled artifact, creating the cache directory if needed.
+fn write_artifact(path: &std::path::Path, artifact: &[u8]) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, artifact)
+}
+
+impl<C: PipelineCompiler> fmt::Debug for PipelineCache<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PipelineCache")
+            .field("pipelines", &self.shared.entries().len())
+            .field("disk_cache", &self.shared.disk_cache)
+            .finish_non_exhaustive()
+    }
+}
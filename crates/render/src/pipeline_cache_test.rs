@@ -0,0 +1,199 @@
+//! Unit tests for the pipeline cache.
+
+use crate::pipeline_cache::{
+    BlendMode, Compiled, PipelineCache, PipelineCompiler, PipelineKey, PipelineState,
+    PipelineStatus,
+};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Compiler producing the shader name, counting compilations and cache hits.
+#[derive(Debug, Default)]
+struct FakeCompiler {
+    compiles: Arc<AtomicUsize>,
+    cache_hits: Arc<AtomicUsize>,
+    /// Stands in for a hash of the shader sources.
+    version: Arc<AtomicU64>,
+}
+
+impl PipelineCompiler for FakeCompiler {
+    type Pipeline = String;
+
+    fn cache_version(&self, _key: &PipelineKey) -> u64 {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    fn compile(
+        &self,
+        key: &PipelineKey,
+        cached: Option<&[u8]>,
+    ) -> anyhow::Result<Compiled<String>> {
+        self.compiles.fetch_add(1, Ordering::SeqCst);
+        if cached.is_some() {
+            self.cache_hits.fetch_add(1, Ordering::SeqCst);
+        }
+        if key.shader.starts_with("broken") {
+            anyhow::bail!("syntax error");
+        }
+        assert!(!key.shader.starts_with("panic"), "compiler bug");
+        Ok(Compiled {
+            pipeline: key.shader.clone(),
+            artifact: Some(key.shader.as_bytes().to_vec()),
+        })
+    }
+}
+
+/// Polls until `key` leaves the compiling state.
+fn wait_for(cache: &PipelineCache<FakeCompiler>, key: &PipelineKey) -> PipelineStatus {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        let status = cache.status(key);
+        if status != PipelineStatus::Compiling || Instant::now() > deadline {
+            return status;
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
+
+/// Verifies the fallback is served until the background compile finishes.
+#[test]
+fn serves_fallback_until_compiled() {
+    let cache = PipelineCache::new(FakeCompiler::default(), "loading".to_owned());
+    let key = PipelineKey::new("lit.wgsl");
+
+    assert_eq!(cache.status(&key), PipelineStatus::Missing);
+    assert_eq!(*cache.get(&key), "loading");
+    assert_eq!(wait_for(&cache, &key), PipelineStatus::Ready);
+    assert_eq!(*cache.get(&key), "lit.wgsl");
+    assert_eq!(cache.compiling(), 0);
+}
+
+/// Verifies each key compiles once, however often it is requested.
+#[test]
+fn compiles_each_key_once() {
+    let compiler = FakeCompiler::default();
+    let compile_count = Arc::clone(&compiler.compiles);
+    let cache = PipelineCache::new(compiler, "loading".to_owned());
+    let key = PipelineKey::new("lit.wgsl").define("SHADOWS");
+
+    for _ in 0..10 {
+        let _ = cache.get(&key);
+    }
+    wait_for(&cache, &key);
+    let _ = cache.get(&key);
+
+    assert_eq!(compile_count.load(Ordering::SeqCst), 1);
+}
+
+/// Verifies keys differing only in define order are the same pipeline.
+#[test]
+fn define_order_does_not_matter() {
+    let a = PipelineKey::new("lit.wgsl").define("A").define("B");
+    let b = PipelineKey::new("lit.wgsl")
+        .define("B")
+        .define("A")
+        .define("A");
+    let c = PipelineKey::new("lit.wgsl").with_state(PipelineState {
+        blend: BlendMode::Alpha,
+        ..PipelineState::default()
+    });
+
+    assert_eq!(a, b);
+    assert_ne!(PipelineKey::new("lit.wgsl"), c);
+}
+
+/// Verifies a failed compile keeps serving the fallback.
+#[test]
+fn failed_compile_keeps_fallback() {
+    let cache = PipelineCache::new(FakeCompiler::default(), "loading".to_owned());
+    let key = PipelineKey::new("broken.wgsl");
+
+    let _ = cache.get(&key);
+    assert_eq!(
+        wait_for(&cache, &key),
+        PipelineStatus::Failed("syntax error".to_owned())
+    );
+    assert_eq!(*cache.get(&key), "loading");
+}
+
+/// Verifies invalidated pipelines recompile on next use.
+#[test]
+fn invalidate_recompiles() {
+    let compiler = FakeCompiler::default();
+    let compile_count = Arc::clone(&compiler.compiles);
+    let cache = PipelineCache::new(compiler, "loading".to_owned());
+    let key = PipelineKey::new("lit.wgsl");
+
+    let _ = cache.get(&key);
+    wait_for(&cache, &key);
+    cache.invalidate(&key);
+    assert_eq!(*cache.get(&key), "loading");
+    assert_eq!(wait_for(&cache, &key), PipelineStatus::Ready);
+
+    assert_eq!(compile_count.load(Ordering::SeqCst), 2);
+}
+
+/// Verifies compiled artifacts are written to and read from the disk cache.
+#[test]
+fn disk_cache_round_trip() {
+    let dir = std::env::temp_dir().join(format!("rustgine-pipelines-{}", std::process::id()));
+    let key = PipelineKey::new("lit.wgsl");
+
+    let cache =
+        PipelineCache::new(FakeCompiler::default(), "loading".to_owned()).with_disk_cache(&dir);
+    let _ = cache.get(&key);
+    wait_for(&cache, &key);
+
+    let compiler = FakeCompiler::default();
+    let cache_hits = Arc::clone(&compiler.cache_hits);
+    let cache = PipelineCache::new(compiler, "loading".to_owned()).with_disk_cache(&dir);
+    let _ = cache.get(&key);
+    wait_for(&cache, &key);
+
+    assert_eq!(cache_hits.load(Ordering::SeqCst), 1);
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+/// Verifies a panicking compiler fails the pipeline instead of leaving it
+/// compiling.
+#[test]
+fn panicking_compile_fails() {
+    let cache = PipelineCache::new(FakeCompiler::default(), "loading".to_owned());
+    let key = PipelineKey::new("panic.wgsl");
+
+    let _ = cache.get(&key);
+    assert_eq!(
+        wait_for(&cache, &key),
+        PipelineStatus::Failed("pipeline compiler panicked".to_owned())
+    );
+    assert_eq!(cache.compiling(), 0);
+}
+
+/// Verifies invalidated keys and changed shader sources miss the disk
+/// cache.
+#[test]
+fn stale_artifacts_are_not_reused() {
+    let dir = std::env::temp_dir().join(format!("rustgine-stale-pipelines-{}", std::process::id()));
+    let key = PipelineKey::new("lit.wgsl");
+    let compiler = FakeCompiler::default();
+    let cache_hits = Arc::clone(&compiler.cache_hits);
+    let cache = PipelineCache::new(compiler, "loading".to_owned()).with_disk_cache(&dir);
+    let _ = cache.get(&key);
+    wait_for(&cache, &key);
+
+    cache.invalidate(&key);
+    let _ = cache.get(&key);
+    wait_for(&cache, &key);
+    assert_eq!(cache_hits.load(Ordering::SeqCst), 0);
+
+    // The next run sees an edited shader.
+    let compiler = FakeCompiler::default();
+    let cache_hits = Arc::clone(&compiler.cache_hits);
+    compiler.version.store(1, Ordering::SeqCst);
+    let cache = PipelineCache::new(compiler, "loading".to_owned()).with_disk_cache(&dir);
+    let _ = cache.get(&key);
+    wait_for(&cache, &key);
+    assert_eq!(cache_hits.load(Ordering::SeqCst), 0);
+    let _ = std::fs::remove_dir_all(dir);
+}
@@ -2,7 +2,9 @@
 //!
 //! Provides the [`RustgineRender`] system for GPU-accelerated graphics rendering.
 
-use rustgine_core::RustgineSystem;
+use crate::{GpuCapabilities, RenderingPaused};
+use rustgine_core::{OutputConfig, RustgineError, RustgineSystem};
+use tracing::info;
 
 /// GPU rendering subsystem for the Rustgine engine.
 ///
@@ -11,6 +13,8 @@ use rustgine_core::RustgineSystem;
 /// - Render pipeline configuration
 /// - Frame submission and presentation
 /// - GPU resource allocation
+/// - Frame suspension while the window is hidden ([`RenderingPaused`])
+/// - Technique selection from adapter capabilities ([`GpuCapabilities`])
 ///
 /// # Thread Safety
 ///
@@ -23,13 +27,81 @@ use rustgine_core::RustgineSystem;
 /// use render::RustgineRender;
 /// use rustgine_core::RustgineSystem;
 ///
-/// let mut renderer = RustgineRender;
+/// let mut renderer = RustgineRender::default();
 /// renderer.startup()?;
 /// // ... render frames ...
 /// renderer.shutdown()?;
 /// ```
 #[derive(Debug, Default)]
-pub struct RustgineRender;
+pub struct RustgineRender {
+    /// Shared flag set by the platform layer while the window is hidden.
+    paused: RenderingPaused,
+    /// Features and limits of the adapter in use.
+    capabilities: GpuCapabilities,
+    /// Surface format and HDR settings.
+    output: OutputConfig,
+}
+
+impl RustgineRender {
+    /// Creates a renderer that observes the given suspension flag.
+    #[must_use]
+    pub fn new(paused: RenderingPaused) -> Self {
+        Self {
+            paused,
+            capabilities: GpuCapabilities::baseline(),
+            output: OutputConfig::default(),
+        }
+    }
+
+    /// Sets the capabilities queried from the adapter.
+    ///
+    /// Until called, the renderer assumes [`GpuCapabilities::baseline`] and
+    /// uses the fallback path for every optional technique.
+    #[must_use]
+    pub fn with_capabilities(mut self, capabilities: GpuCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Sets the surface format preference and HDR paper-white level.
+    ///
+    /// The surface format itself is chosen with
+    /// [`OutputMode::select`](crate::OutputMode::select) once the surface's
+    /// supported formats are known.
+    #[must_use]
+    pub fn with_output_config(mut self, output: OutputConfig) -> Self {
+        self.output = output;
+        self
+    }
+
+    /// Returns the surface format and HDR settings.
+    #[must_use]
+    #[inline]
+    pub fn output_config(&self) -> &OutputConfig {
+        &self.output
+    }
+
+    /// Returns the capabilities of the adapter in use.
+    #[must_use]
+    #[inline]
+    pub fn capabilities(&self) -> &GpuCapabilities {
+        &self.capabilities
+    }
+
+    /// Returns the renderer's suspension flag.
+    #[must_use]
+    #[inline]
+    pub fn paused(&self) -> &RenderingPaused {
+        &self.paused
+    }
+
+    /// Returns `true` if a frame should be rendered and presented.
+    #[must_use]
+    #[inline]
+    pub fn should_render(&self) -> bool {
+        !self.paused.is_paused()
+    }
+}
 
 impl RustgineSystem for RustgineRender {
     /// Initializes the rendering subsystem and acquires GPU resources.
@@ -37,8 +109,12 @@ impl RustgineSystem for RustgineRender {
     /// # Errors
     ///
     /// Returns an error if GPU initialization fails (e.g., no compatible device found).
-    #[inline]
-    fn startup(&mut self) -> anyhow::Result<()> {
+    fn startup(&mut self) -> Result<(), RustgineError> {
+        info!(
+            capabilities = %self.capabilities,
+            surface_format = ?self.output.surface_format,
+            "renderer started"
+        );
         Ok(())
     }
 
@@ -48,7 +124,7 @@ impl RustgineSystem for RustgineRender {
     ///
     /// Returns an error if GPU resource cleanup fails.
     #[inline]
-    fn shutdown(&mut self) -> anyhow::Result<()> {
+    fn shutdown(&mut self) -> Result<(), RustgineError> {
         Ok(())
     }
 }
@@ -0,0 +1,155 @@
+//! Procedural atmospheric scattering.
+
+use super::cubemap::Cubemap;
+use math::{Color, Vec3};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// A procedural sky lit by the sun, approximating single Rayleigh and Mie
+/// scattering through an atmosphere of exponentially thinning density.
+///
+/// The sky is blue overhead at noon and reddens towards the horizon as
+/// sunlight travels through more air at sunrise and sunset. Once the sun
+/// sets, the sky fades to [`night`](Self::night).
+///
+/// # Example
+///
+/// ```
+/// use math::Vec3;
+/// use render::sky::Atmosphere;
+///
+/// let atmosphere = Atmosphere::default();
+/// let morning_sun = Vec3::new(1.0, 1.0, 0.0).normalize();
+/// let zenith = atmosphere.radiance(Vec3::Y, morning_sun);
+/// assert!(zenith.z > zenith.x, "the sky is blue");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Atmosphere {
+    /// Rayleigh scattering coefficients per color channel at sea level, in
+    /// inverse megameters.
+    pub rayleigh: Vec3,
+    /// Height over which Rayleigh scattering thins by `e`, in kilometers.
+    pub rayleigh_height: f32,
+    /// Mie scattering coefficient at sea level, in inverse megameters;
+    /// raise it for haze.
+    pub mie: f32,
+    /// Height over which Mie scattering thins by `e`, in kilometers.
+    pub mie_height: f32,
+    /// How strongly Mie scattering favors the forward direction, in
+    /// `0..1`; brightens the glow around the sun.
+    pub mie_anisotropy: f32,
+    /// Radiance of the sun above the atmosphere.
+    pub sun_intensity: f32,
+    /// Angular radius of the sun's disc, in degrees.
+    pub sun_radius: f32,
+    /// Color of the ground seen below the horizon.
+    pub ground: Color,
+    /// Radiance of the sky without sunlight, e.g. moon- and starlight.
+    pub night: Color,
+}
+
+impl Default for Atmosphere {
+    fn default() -> Self {
+        Self {
+            rayleigh: Vec3::new(5.8, 13.5, 33.1),
+            rayleigh_height: 8.0,
+            mie: 21.0,
+            mie_height: 1.2,
+            mie_anisotropy: 0.76,
+            sun_intensity: 20.0,
+            sun_radius: 0.27,
+            ground: Color::linear_rgb(0.1, 0.1, 0.1),
+            night: Color::linear_rgb(0.001, 0.0015, 0.003),
+        }
+    }
+}
+
+impl Atmosphere {
+    /// Returns the sunlight reaching the ground from `sun`, the direction
+    /// towards the sun, for lighting with a directional light.
+    #[must_use]
+    pub fn sunlight(&self, sun: Vec3) -> Vec3 {
+        let sun = sun.normalize_or(Vec3::Y);
+        let transmittance = (-self.optical_depth() * air_mass(sun.y)).exp();
+        self.sun_intensity * transmittance * daylight(sun.y)
+    }
+
+    /// Returns the sky radiance seen looking along `view` with the sun in
+    /// direction `sun`, including the sun's disc.
+    #[must_use]
+    pub fn radiance(&self, view: Vec3, sun: Vec3) -> Vec3 {
+        let view = view.normalize_or(Vec3::NEG_Z);
+        let sun = sun.normalize_or(Vec3::Y);
+        let night = self.night.to_linear().rgb_vec();
+        let horizon = Vec3::new(view.x, 0.0, view.z).normalize_or(Vec3::NEG_Z);
+        let sky = self.scattering(if view.y > 0.0 { view } else { horizon }, sun) + night;
+        if view.y >= 0.0 {
+            let disc = (self.sun_radius.to_radians()).cos();
+            if view.dot(sun) >= disc {
+                return sky + self.sunlight(sun);
+            }
+            return sky;
+        }
+        let ground =
+            self.ground.to_linear().rgb_vec() * (self.sunlight(sun) * sun.y.max(0.0) / PI + night);
+        // A narrow band below the horizon hides the seam.
+        let t = smoothstep(0.0, 0.05, -view.y);
+        sky.lerp(ground, t)
+    }
+
+    /// Bakes the sky for the sun in direction `sun` into a cubemap of
+    /// `size`, to draw and to bake lighting from.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    #[must_use]
+    pub fn to_cubemap(&self, sun: Vec3, size: u32) -> Cubemap {
+        Cubemap::from_fn(size, |view| self.radiance(view, sun))
+    }
+
+    /// Returns the light scattered towards the viewer along `view`, above
+    /// the horizon.
+    fn scattering(&self, view: Vec3, sun: Vec3) -> Vec3 {
+        let cos = view.dot(sun);
+        let rayleigh = self.rayleigh * self.rayleigh_height * 1e-3;
+        let mie = Vec3::splat(self.mie * self.mie_height * 1e-3);
+        let g = self.mie_anisotropy.clamp(0.0, 0.99);
+        let rayleigh_phase = 3.0 / (16.0 * PI) * (1.0 + cos * cos);
+        let mie_phase =
+            (1.0 - g * g) / (4.0 * PI * (1.0 + g * g - 2.0 * g * cos).max(1e-4).powf(1.5));
+        let depth = rayleigh + mie;
+        let scattered = (rayleigh * rayleigh_phase + mie * mie_phase) / depth;
+        let in_view = Vec3::ONE - (-depth * air_mass(view.y)).exp();
+        // Light entering the view ray has crossed the atmosphere towards
+        // the sun; below the horizon, twilight still lights the upper air.
+        let sunlight =
+            self.sun_intensity * (-depth * air_mass(sun.y.max(0.0))).exp() * daylight(sun.y);
+        sunlight * scattered * in_view * 4.0 * PI
+    }
+
+    /// Returns the vertical optical depth per color channel.
+    fn optical_depth(&self) -> Vec3 {
+        (self.rayleigh * self.rayleigh_height + Vec3::splat(self.mie * self.mie_height)) * 1e-3
+    }
+}
+
+/// Returns how many times more air a ray crosses at elevation sine
+/// `sin_elevation` than straight up (Kasten and Young).
+fn air_mass(sin_elevation: f32) -> f32 {
+    let sin_elevation = sin_elevation.clamp(0.0, 1.0);
+    let zenith = 90.0 - sin_elevation.asin().to_degrees();
+    1.0 / (sin_elevation + 0.505_72 * (96.079_95 - zenith).powf(-1.636_4))
+}
+
+/// Returns how much sunlight remains as the sun dips below the horizon,
+/// fading out through civil twilight.
+fn daylight(sun_y: f32) -> f32 {
+    smoothstep(-0.1, 0.02, sun_y)
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
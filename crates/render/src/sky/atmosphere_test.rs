@@ -0,0 +1,66 @@
+//! Unit tests for the procedural atmosphere.
+
+use super::atmosphere::Atmosphere;
+use math::Vec3;
+
+fn elevated(degrees: f32, azimuth: Vec3) -> Vec3 {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    (azimuth * cos + Vec3::Y * sin).normalize()
+}
+
+/// Verifies the day sky is blue overhead and the sunset horizon towards
+/// the sun is red.
+#[test]
+fn colors_follow_the_sun() {
+    let atmosphere = Atmosphere::default();
+    let noon = elevated(60.0, Vec3::Z);
+    let zenith = atmosphere.radiance(Vec3::Y, noon);
+    assert!(zenith.z > zenith.y && zenith.y > zenith.x, "{zenith}");
+
+    let sunset = elevated(1.0, Vec3::NEG_X);
+    let glow = atmosphere.radiance(elevated(3.0, Vec3::NEG_X), sunset);
+    assert!(glow.x > glow.z, "{glow}");
+    let overhead = atmosphere.radiance(Vec3::Y, sunset);
+    assert!(overhead.length() < zenith.length());
+}
+
+/// Verifies the sky darkens to the night color once the sun has set.
+#[test]
+fn fades_to_night() {
+    let atmosphere = Atmosphere::default();
+    let night = atmosphere.night.to_linear().rgb_vec();
+    let midnight = elevated(-40.0, Vec3::Z);
+    assert!((atmosphere.radiance(Vec3::Y, midnight) - night).length() < 1e-5);
+    assert_eq!(atmosphere.sunlight(midnight), Vec3::ZERO);
+    let twilight = atmosphere.radiance(Vec3::Y, elevated(-3.0, Vec3::Z));
+    assert!(twilight.z > night.z);
+}
+
+/// Verifies sunlight weakens and reddens towards the horizon, and looking
+/// at the sun shows its disc.
+#[test]
+fn sunlight_and_disc() {
+    let atmosphere = Atmosphere::default();
+    let high = atmosphere.sunlight(Vec3::Y);
+    let low = atmosphere.sunlight(elevated(5.0, Vec3::X));
+    assert!(low.length() < high.length());
+    assert!(low.x / low.z > high.x / high.z);
+
+    let sun = elevated(30.0, Vec3::X);
+    let disc = atmosphere.radiance(sun, sun);
+    let beside = atmosphere.radiance(elevated(35.0, Vec3::X), sun);
+    assert!(disc.x > beside.x + atmosphere.sunlight(sun).x * 0.9);
+}
+
+/// Verifies the ground below the horizon is lit by the sun.
+#[test]
+fn draws_the_ground() {
+    let atmosphere = Atmosphere::default();
+    let ground = atmosphere.radiance(Vec3::NEG_Y, Vec3::Y);
+    let expected = atmosphere.ground.to_linear().rgb_vec()
+        * (atmosphere.sunlight(Vec3::Y) / std::f32::consts::PI
+            + atmosphere.night.to_linear().rgb_vec());
+    assert!((ground - expected).length() < 1e-5);
+    let cubemap = atmosphere.to_cubemap(Vec3::Y, 4);
+    assert!(cubemap.sample(Vec3::Y).z > cubemap.sample(Vec3::NEG_Y).z);
+}
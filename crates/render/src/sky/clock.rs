@@ -0,0 +1,191 @@
+//! Time of day driving the sun.
+
+use ecs::Events;
+use math::Vec3;
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+/// Sun elevation, in degrees, between night and day; the civil twilight
+/// band of dawn and dusk lies between its negative and positive.
+const TWILIGHT_DEGREES: f32 = 6.0;
+
+/// Part of the day, by the sun's elevation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DayPhase {
+    /// The sun is more than 6° below the horizon.
+    Night,
+    /// The sun is within 6° of the horizon, rising.
+    Dawn,
+    /// The sun is more than 6° above the horizon.
+    Day,
+    /// The sun is within 6° of the horizon, setting.
+    Dusk,
+}
+
+/// Sent by [`SkyClock::advance`] when the [`DayPhase`] changes, e.g. to
+/// switch street lights on at dusk or change ambient sounds at dawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DayPhaseChanged {
+    /// The phase before.
+    pub from: DayPhase,
+    /// The phase now.
+    pub to: DayPhase,
+    /// The time of day, in hours.
+    pub time_of_day: f32,
+}
+
+/// Time of day moving the sun across the sky.
+///
+/// The sun rises in the east (`+X`), culminates towards the south (`+Z`)
+/// in the northern hemisphere, and sets in the west (`-X`); its elevation
+/// follows the latitude and the season's solar declination.
+///
+/// # Example
+///
+/// ```
+/// use ecs::Events;
+/// use render::sky::{DayPhase, SkyClock};
+/// use std::time::Duration;
+///
+/// // A day lasting 24 real minutes, starting just before sunrise.
+/// let mut clock = SkyClock::new(5.0).with_day_length(Duration::from_secs(24 * 60));
+/// let mut events = Events::default();
+/// assert_eq!(clock.phase(), DayPhase::Night);
+///
+/// clock.advance(Duration::from_secs(60), &mut events);
+/// assert_eq!(clock.phase(), DayPhase::Dawn);
+/// assert_eq!(events.iter().next().unwrap().to, DayPhase::Dawn);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkyClock {
+    /// Hours since midnight, in `0..24`.
+    time_of_day: f32,
+    /// Real seconds per day; 0 stops the clock.
+    day_length: f32,
+    /// Degrees north of the equator.
+    latitude: f32,
+    /// Degrees the sun stands north of the equator, from -23.44 at the
+    /// December solstice to 23.44 at the June solstice.
+    declination: f32,
+    /// Phase at the last advance.
+    phase: DayPhase,
+}
+
+impl SkyClock {
+    /// Creates a clock at `time_of_day` hours, with 20 minute days at a
+    /// latitude of 40° around an equinox.
+    #[must_use]
+    pub fn new(time_of_day: f32) -> Self {
+        let mut clock = Self {
+            time_of_day: 0.0,
+            day_length: 20.0 * 60.0,
+            latitude: 40.0,
+            declination: 0.0,
+            phase: DayPhase::Night,
+        };
+        clock.set_time(time_of_day);
+        clock
+    }
+
+    /// Sets how long a day lasts in real time; zero stops the clock.
+    #[must_use]
+    pub fn with_day_length(mut self, day_length: Duration) -> Self {
+        self.day_length = day_length.as_secs_f32();
+        self
+    }
+
+    /// Sets the latitude in degrees, negative for the southern hemisphere.
+    #[must_use]
+    pub fn with_latitude(mut self, latitude: f32) -> Self {
+        self.latitude = latitude.clamp(-90.0, 90.0);
+        self.phase = self.compute_phase();
+        self
+    }
+
+    /// Sets the sun's declination in degrees, for the season.
+    #[must_use]
+    pub fn with_declination(mut self, declination: f32) -> Self {
+        self.declination = declination.clamp(-90.0, 90.0);
+        self.phase = self.compute_phase();
+        self
+    }
+
+    /// Returns the hours since midnight.
+    #[must_use]
+    pub fn time_of_day(&self) -> f32 {
+        self.time_of_day
+    }
+
+    /// Jumps to `time_of_day` hours, wrapped into `0..24`, without sending
+    /// a [`DayPhaseChanged`].
+    pub fn set_time(&mut self, time_of_day: f32) {
+        self.time_of_day = time_of_day.rem_euclid(24.0);
+        self.phase = self.compute_phase();
+    }
+
+    /// Returns the current part of the day.
+    #[must_use]
+    pub fn phase(&self) -> DayPhase {
+        self.phase
+    }
+
+    /// Returns the direction towards the sun; below the horizon at night.
+    #[must_use]
+    pub fn sun_direction(&self) -> Vec3 {
+        let hour_angle = (self.time_of_day - 12.0) / 24.0 * TAU;
+        let (sin_lat, cos_lat) = self.latitude.to_radians().sin_cos();
+        let (sin_dec, cos_dec) = self.declination.to_radians().sin_cos();
+        let (sin_hour, cos_hour) = hour_angle.sin_cos();
+        let east = -cos_dec * sin_hour;
+        let up = sin_lat * sin_dec + cos_lat * cos_dec * cos_hour;
+        let north = cos_lat * sin_dec - sin_lat * cos_dec * cos_hour;
+        Vec3::new(east, up, -north).normalize()
+    }
+
+    /// Returns the sun's elevation above the horizon, in degrees.
+    #[must_use]
+    pub fn sun_elevation(&self) -> f32 {
+        self.sun_direction().y.clamp(-1.0, 1.0).asin().to_degrees()
+    }
+
+    /// Advances the clock by the real time `delta`, sending a
+    /// [`DayPhaseChanged`] if the phase changed.
+    ///
+    /// Steps longer than a phase report only the phase they end in.
+    pub fn advance(&mut self, delta: Duration, events: &mut Events<DayPhaseChanged>) {
+        if self.day_length <= 0.0 {
+            return;
+        }
+        let hours = delta.as_secs_f32() / self.day_length * 24.0;
+        self.time_of_day = (self.time_of_day + hours).rem_euclid(24.0);
+        let from = self.phase;
+        self.phase = self.compute_phase();
+        if self.phase != from {
+            events.send(DayPhaseChanged {
+                from,
+                to: self.phase,
+                time_of_day: self.time_of_day,
+            });
+        }
+    }
+
+    fn compute_phase(&self) -> DayPhase {
+        let elevation = self.sun_elevation();
+        if elevation > TWILIGHT_DEGREES {
+            DayPhase::Day
+        } else if elevation < -TWILIGHT_DEGREES {
+            DayPhase::Night
+        } else if self.time_of_day < 12.0 {
+            DayPhase::Dawn
+        } else {
+            DayPhase::Dusk
+        }
+    }
+}
+
+impl Default for SkyClock {
+    /// Noon.
+    fn default() -> Self {
+        Self::new(12.0)
+    }
+}
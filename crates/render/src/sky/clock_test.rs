@@ -0,0 +1,64 @@
+//! Unit tests for the sky clock.
+
+use super::clock::{DayPhase, DayPhaseChanged, SkyClock};
+use ecs::Events;
+use std::time::Duration;
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 1e-3, "{actual} != {expected}");
+}
+
+/// Verifies the sun rises in the east, culminates in the south at the
+/// latitude's complement, and sets in the west.
+#[test]
+fn sun_crosses_the_sky() {
+    let mut clock = SkyClock::new(6.0);
+    let sunrise = clock.sun_direction();
+    assert!(sunrise.x > 0.99, "{sunrise}");
+    clock.set_time(12.0);
+    assert_close(clock.sun_elevation(), 50.0);
+    assert!(clock.sun_direction().z > 0.0);
+    clock.set_time(18.0);
+    assert!(clock.sun_direction().x < -0.99);
+    clock.set_time(-1.0);
+    assert_close(clock.time_of_day(), 23.0);
+    assert_eq!(clock.phase(), DayPhase::Night);
+
+    let summer = SkyClock::new(12.0).with_declination(23.44);
+    assert_close(summer.sun_elevation(), 73.44);
+    let south = SkyClock::new(12.0).with_latitude(-40.0);
+    assert!(south.sun_direction().z < 0.0);
+}
+
+/// Verifies advancing through a day sends each phase change once.
+#[test]
+fn sends_phase_changes() {
+    let mut clock = SkyClock::new(0.0).with_day_length(Duration::from_secs(24));
+    let mut events = Events::<DayPhaseChanged>::default();
+    let mut phases = Vec::new();
+    for _ in 0..24 * 4 {
+        events.update();
+        clock.advance(Duration::from_millis(250), &mut events);
+        phases.extend(events.iter_current().map(|event| (event.from, event.to)));
+    }
+    assert_eq!(
+        phases,
+        [
+            (DayPhase::Night, DayPhase::Dawn),
+            (DayPhase::Dawn, DayPhase::Day),
+            (DayPhase::Day, DayPhase::Dusk),
+            (DayPhase::Dusk, DayPhase::Night),
+        ]
+    );
+}
+
+/// Verifies a clock without a day length stands still.
+#[test]
+fn stopped_clock_stays() {
+    let mut clock = SkyClock::new(9.5).with_day_length(Duration::ZERO);
+    let mut events = Events::default();
+    clock.advance(Duration::from_hours(1), &mut events);
+    assert_close(clock.time_of_day(), 9.5);
+    assert_eq!(clock.phase(), DayPhase::Day);
+    assert_eq!(events.iter().count(), 0);
+}
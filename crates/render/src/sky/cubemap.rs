@@ -0,0 +1,206 @@
+//! Cubemaps of sky radiance.
+
+use super::hdr::HdrImage;
+use math::Vec3;
+
+/// A face of a cubemap, in GPU layer order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CubeFace {
+    /// Facing `+X`.
+    PositiveX,
+    /// Facing `-X`.
+    NegativeX,
+    /// Facing `+Y`.
+    PositiveY,
+    /// Facing `-Y`.
+    NegativeY,
+    /// Facing `+Z`.
+    PositiveZ,
+    /// Facing `-Z`.
+    NegativeZ,
+}
+
+impl CubeFace {
+    /// All faces, in layer order.
+    pub const ALL: [Self; 6] = [
+        Self::PositiveX,
+        Self::NegativeX,
+        Self::PositiveY,
+        Self::NegativeY,
+        Self::PositiveZ,
+        Self::NegativeZ,
+    ];
+
+    /// Returns the face's texture array layer.
+    #[must_use]
+    pub fn layer(self) -> usize {
+        self as usize
+    }
+
+    /// Returns the direction through face coordinates `s` and `t`, both in
+    /// `-1..=1` with `t` increasing downwards, unnormalized.
+    fn direction(self, s: f32, t: f32) -> Vec3 {
+        match self {
+            Self::PositiveX => Vec3::new(1.0, -t, -s),
+            Self::NegativeX => Vec3::new(-1.0, -t, s),
+            Self::PositiveY => Vec3::new(s, 1.0, t),
+            Self::NegativeY => Vec3::new(s, -1.0, -t),
+            Self::PositiveZ => Vec3::new(s, -t, 1.0),
+            Self::NegativeZ => Vec3::new(-s, -t, -1.0),
+        }
+    }
+
+    /// Returns the face `direction` points through and its face
+    /// coordinates there.
+    fn project(direction: Vec3) -> (Self, f32, f32) {
+        let Vec3 { x, y, z } = direction;
+        let (ax, ay, az) = (x.abs(), y.abs(), z.abs());
+        if ax >= ay && ax >= az {
+            if x > 0.0 {
+                (Self::PositiveX, -z / ax, -y / ax)
+            } else {
+                (Self::NegativeX, z / ax, -y / ax)
+            }
+        } else if ay >= az {
+            if y > 0.0 {
+                (Self::PositiveY, x / ay, z / ay)
+            } else {
+                (Self::NegativeY, x / ay, -z / ay)
+            }
+        } else if z > 0.0 {
+            (Self::PositiveZ, x / az, -y / az)
+        } else {
+            (Self::NegativeZ, -x / az, -y / az)
+        }
+    }
+}
+
+/// Six square faces of linear RGB radiance, for drawing a skybox and
+/// baking image-based lighting.
+///
+/// Faces follow the WebGPU cube texture convention; see [`CubeFace`].
+///
+/// # Example
+///
+/// ```
+/// use math::Vec3;
+/// use render::sky::{Cubemap, HdrImage};
+///
+/// let panorama = HdrImage::new(2, 1, vec![Vec3::ONE, Vec3::ONE])?;
+/// let cubemap = Cubemap::from_equirect(&panorama, 16);
+/// assert_eq!(cubemap.sample(Vec3::Y), Vec3::ONE);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cubemap {
+    size: u32,
+    faces: [Vec<Vec3>; 6],
+}
+
+impl Cubemap {
+    /// Creates a cubemap of `size` x `size` texels per face, all
+    /// `radiance`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    #[must_use]
+    pub fn uniform(size: u32, radiance: Vec3) -> Self {
+        assert!(size > 0, "cubemap size must be positive");
+        let texels = size as usize * size as usize;
+        Self {
+            size,
+            faces: std::array::from_fn(|_| vec![radiance; texels]),
+        }
+    }
+
+    /// Creates a cubemap by evaluating `radiance` in the direction of each
+    /// texel's center.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    #[must_use]
+    pub fn from_fn(size: u32, mut radiance: impl FnMut(Vec3) -> Vec3) -> Self {
+        let mut cubemap = Self::uniform(size, Vec3::ZERO);
+        for face in CubeFace::ALL {
+            for y in 0..size {
+                for x in 0..size {
+                    let texel = y as usize * size as usize + x as usize;
+                    cubemap.faces[face.layer()][texel] = radiance(cubemap.direction(face, x, y));
+                }
+            }
+        }
+        cubemap
+    }
+
+    /// Resamples an equirectangular panorama, see [`HdrImage::sample`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    #[must_use]
+    pub fn from_equirect(image: &HdrImage, size: u32) -> Self {
+        Self::from_fn(size, |direction| image.sample(direction))
+    }
+
+    /// Returns the width and height of each face in texels.
+    #[must_use]
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Returns the texels of `face`, row by row.
+    #[must_use]
+    pub fn face(&self, face: CubeFace) -> &[Vec3] {
+        &self.faces[face.layer()]
+    }
+
+    /// Returns the normalized direction through the center of texel
+    /// `(x, y)` of `face`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn direction(&self, face: CubeFace, x: u32, y: u32) -> Vec3 {
+        let size = self.size as f32;
+        let s = (x as f32 + 0.5) / size * 2.0 - 1.0;
+        let t = (y as f32 + 0.5) / size * 2.0 - 1.0;
+        face.direction(s, t).normalize()
+    }
+
+    /// Samples the radiance in `direction`, filtering bilinearly within the
+    /// face it points through.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn sample(&self, direction: Vec3) -> Vec3 {
+        let (face, s, t) = CubeFace::project(direction.normalize_or(Vec3::NEG_Z));
+        let size = self.size as f32;
+        let last = (self.size - 1) as f32;
+        let fx = ((s + 1.0) * 0.5 * size - 0.5).clamp(0.0, last);
+        let fy = ((t + 1.0) * 0.5 * size - 0.5).clamp(0.0, last);
+        let (x0, y0) = (fx.floor(), fy.floor());
+        let (tx, ty) = (fx - x0, fy - y0);
+        let (x0, y0) = (x0 as u32, y0 as u32);
+        let (x1, y1) = ((x0 + 1).min(self.size - 1), (y0 + 1).min(self.size - 1));
+        let texel = |x: u32, y: u32| self.faces[face.layer()][(y * self.size + x) as usize];
+        let top = texel(x0, y0).lerp(texel(x1, y0), tx);
+        let bottom = texel(x0, y1).lerp(texel(x1, y1), tx);
+        top.lerp(bottom, ty)
+    }
+
+    /// Returns the solid angle texel `(x, y)` covers on any face, in
+    /// steradians; the six faces cover `4π`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn texel_solid_angle(&self, x: u32, y: u32) -> f32 {
+        // Integral of the solid angle from the face center to (s, t).
+        let corner = |s: f32, t: f32| (s * t).atan2((s * s + t * t + 1.0).sqrt());
+        let step = 2.0 / self.size as f32;
+        let (s0, t0) = (x as f32 * step - 1.0, y as f32 * step - 1.0);
+        let (s1, t1) = (s0 + step, t0 + step);
+        corner(s0, t0) - corner(s0, t1) - corner(s1, t0) + corner(s1, t1)
+    }
+}
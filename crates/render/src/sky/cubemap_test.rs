@@ -0,0 +1,59 @@
+//! Unit tests for cubemaps.
+
+use super::cubemap::{CubeFace, Cubemap};
+use super::hdr::HdrImage;
+use math::Vec3;
+use std::f32::consts::PI;
+
+/// Verifies texel directions point out of their face and sampling finds
+/// the texel again.
+#[test]
+fn directions_round_trip() {
+    let cubemap = Cubemap::from_fn(4, |direction| direction);
+    let axes = [
+        Vec3::X,
+        Vec3::NEG_X,
+        Vec3::Y,
+        Vec3::NEG_Y,
+        Vec3::Z,
+        Vec3::NEG_Z,
+    ];
+    for (face, axis) in CubeFace::ALL.into_iter().zip(axes) {
+        for (x, y) in [(0, 0), (3, 1), (2, 3)] {
+            let direction = cubemap.direction(face, x, y);
+            assert!(direction.dot(axis) > 0.5, "{face:?} {x} {y}");
+            let sampled = cubemap.sample(direction);
+            assert!((sampled - direction).length() < 1e-5, "{face:?} {x} {y}");
+        }
+    }
+    // Face rows run downwards.
+    let top = cubemap.direction(CubeFace::NegativeZ, 1, 0);
+    let bottom = cubemap.direction(CubeFace::NegativeZ, 1, 3);
+    assert!(top.y > 0.0 && bottom.y < 0.0);
+}
+
+/// Verifies texel solid angles cover the sphere, largest at face centers.
+#[test]
+fn solid_angles_cover_the_sphere() {
+    let cubemap = Cubemap::uniform(8, Vec3::ZERO);
+    let mut total = 0.0;
+    for y in 0..8 {
+        for x in 0..8 {
+            total += cubemap.texel_solid_angle(x, y);
+        }
+    }
+    assert!((total * 6.0 - 4.0 * PI).abs() < 1e-4, "{total}");
+    assert!(cubemap.texel_solid_angle(3, 3) > cubemap.texel_solid_angle(0, 0));
+}
+
+/// Verifies panoramas are resampled onto the faces.
+#[test]
+fn resamples_panoramas() {
+    // Bright sky over dark ground.
+    let pixels = vec![Vec3::ONE, Vec3::ONE, Vec3::ZERO, Vec3::ZERO];
+    let image = HdrImage::new(1, 4, pixels).unwrap();
+    let cubemap = Cubemap::from_equirect(&image, 4);
+    assert_eq!(cubemap.size(), 4);
+    assert_eq!(cubemap.face(CubeFace::PositiveY), [Vec3::ONE; 16]);
+    assert_eq!(cubemap.face(CubeFace::NegativeY), [Vec3::ZERO; 16]);
+}
@@ -0,0 +1,218 @@
+//! Radiance HDR images.
+
+use math::Vec3;
+use std::f32::consts::{PI, TAU};
+
+/// A high dynamic range image of linear RGB radiance, rows from the top.
+///
+/// Skyboxes are equirectangular panoramas: `x` goes once around the
+/// horizon and `y` from straight up to straight down.
+///
+/// # Example
+///
+/// ```
+/// use math::Vec3;
+/// use render::sky::HdrImage;
+///
+/// // A 1 x 1 image, its pixel stored as mantissas sharing an exponent.
+/// let file = b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y 1 +X 1\n\x80\x40\x00\x81";
+/// let image = HdrImage::from_radiance(file)?;
+/// assert_eq!(image.get(0, 0), Vec3::new(1.0, 0.5, 0.0));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HdrImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<Vec3>,
+}
+
+impl HdrImage {
+    /// Creates an image from `width` x `height` pixels, row by row.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image is empty or `pixels` has the wrong
+    /// length.
+    pub fn new(width: u32, height: u32, pixels: Vec<Vec3>) -> anyhow::Result<Self> {
+        if width == 0 || height == 0 {
+            anyhow::bail!("HDR image must not be empty, got {width}x{height}");
+        }
+        if pixels.len() != width as usize * height as usize {
+            anyhow::bail!(
+                "{width}x{height} HDR image needs {} pixels, got {}",
+                width as usize * height as usize,
+                pixels.len()
+            );
+        }
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Reads a Radiance `.hdr` file with flat or run-length encoded RGBE
+    /// scanlines.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file is not a Radiance image, uses another
+    /// pixel format or orientation than `-Y height +X width`, or is
+    /// truncated.
+    pub fn from_radiance(bytes: &[u8]) -> anyhow::Result<Self> {
+        let invalid = |reason: &str| anyhow::anyhow!("invalid Radiance HDR image: {reason}");
+        let mut pos = 0;
+        let mut line = || -> Option<&[u8]> {
+            let rest = bytes.get(pos..)?;
+            let end = rest.iter().position(|&b| b == b'\n')?;
+            pos += end + 1;
+            Some(&rest[..end])
+        };
+        let magic = line().ok_or_else(|| invalid("truncated header"))?;
+        if !magic.starts_with(b"#?") {
+            return Err(invalid("missing #? signature"));
+        }
+        loop {
+            let header = line().ok_or_else(|| invalid("truncated header"))?;
+            if header.is_empty() {
+                break;
+            }
+            if let Some(format) = header.strip_prefix(b"FORMAT=") {
+                if format != b"32-bit_rle_rgbe" {
+                    return Err(invalid("only the 32-bit_rle_rgbe format is supported"));
+                }
+            }
+        }
+        let resolution = line().ok_or_else(|| invalid("missing resolution"))?;
+        let resolution = std::str::from_utf8(resolution).map_err(|_| invalid("bad resolution"))?;
+        let (height, width) = match resolution.split_whitespace().collect::<Vec<_>>()[..] {
+            ["-Y", height, "+X", width] => (height.parse::<u32>(), width.parse::<u32>()),
+            _ => return Err(invalid("only -Y height +X width images are supported")),
+        };
+        let (Ok(width), Ok(height)) = (width, height) else {
+            return Err(invalid("bad resolution"));
+        };
+        if width == 0 || height == 0 {
+            return Err(invalid("empty image"));
+        }
+
+        let mut data = bytes.get(pos..).unwrap_or_default();
+        let mut pixels = Vec::with_capacity(width as usize * height as usize);
+        let mut scanline = vec![[0u8; 4]; width as usize];
+        for _ in 0..height {
+            data = read_scanline(data, &mut scanline).ok_or_else(|| invalid("truncated pixels"))?;
+            pixels.extend(scanline.iter().map(|&rgbe| rgbe_to_linear(rgbe)));
+        }
+        Self::new(width, height, pixels)
+    }
+
+    /// Returns the width in pixels.
+    #[must_use]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height in pixels.
+    #[must_use]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the pixels, row by row.
+    #[must_use]
+    pub fn pixels(&self) -> &[Vec3] {
+        &self.pixels
+    }
+
+    /// Returns pixel `(x, y)`, clamped to the image.
+    #[must_use]
+    pub fn get(&self, x: u32, y: u32) -> Vec3 {
+        let (x, y) = (x.min(self.width - 1), y.min(self.height - 1));
+        self.pixels[y as usize * self.width as usize + x as usize]
+    }
+
+    /// Samples the panorama in `direction` with bilinear filtering,
+    /// wrapping around the horizon.
+    ///
+    /// The center column faces `-Z`, and `x` increases towards `+X`.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_possible_wrap
+    )]
+    pub fn sample(&self, direction: Vec3) -> Vec3 {
+        let direction = direction.normalize_or(Vec3::NEG_Z);
+        let u = 0.5 + direction.x.atan2(-direction.z) / TAU;
+        let v = direction.y.clamp(-1.0, 1.0).acos() / PI;
+        let fx = u * self.width as f32 - 0.5;
+        let fy = (v * self.height as f32 - 0.5).clamp(0.0, (self.height - 1) as f32);
+        let (x0, y0) = (fx.floor(), fy.floor());
+        let (tx, ty) = (fx - x0, fy - y0);
+        let width = i64::from(self.width);
+        let column = |x: f32| (x as i64).rem_euclid(width) as u32;
+        let (x0, x1) = (column(x0), column(x0 + 1.0));
+        let (y0, y1) = (y0 as u32, y0 as u32 + 1);
+        let top = self.get(x0, y0).lerp(self.get(x1, y0), tx);
+        let bottom = self.get(x0, y1).lerp(self.get(x1, y1), tx);
+        top.lerp(bottom, ty)
+    }
+}
+
+/// Reads one scanline into `pixels`, returning the remaining bytes.
+fn read_scanline<'a>(data: &'a [u8], pixels: &mut [[u8; 4]]) -> Option<&'a [u8]> {
+    let width = pixels.len();
+    let run_length = (8..0x8000).contains(&width)
+        && data.len() >= 4
+        && data[0] == 2
+        && data[1] == 2
+        && usize::from(data[2]) << 8 | usize::from(data[3]) == width;
+    if !run_length {
+        let bytes = data.get(..width * 4)?;
+        for (pixel, rgbe) in pixels.iter_mut().zip(bytes.chunks_exact(4)) {
+            pixel.copy_from_slice(rgbe);
+        }
+        return data.get(width * 4..);
+    }
+    // Each channel is stored separately, as runs of one repeated byte or
+    // of literal bytes.
+    let mut data = &data[4..];
+    for channel in 0..4 {
+        let mut x = 0;
+        while x < width {
+            let (&count, rest) = data.split_first()?;
+            if count > 128 {
+                let count = usize::from(count - 128);
+                let (&value, rest) = rest.split_first()?;
+                for pixel in pixels.get_mut(x..x + count)? {
+                    pixel[channel] = value;
+                }
+                x += count;
+                data = rest;
+            } else {
+                let count = usize::from(count);
+                if count == 0 {
+                    return None;
+                }
+                let values = rest.get(..count)?;
+                for (pixel, &value) in pixels.get_mut(x..x + count)?.iter_mut().zip(values) {
+                    pixel[channel] = value;
+                }
+                x += count;
+                data = &rest[count..];
+            }
+        }
+    }
+    Some(data)
+}
+
+/// Decodes mantissas sharing an exponent into linear RGB.
+fn rgbe_to_linear([r, g, b, e]: [u8; 4]) -> Vec3 {
+    if e == 0 {
+        return Vec3::ZERO;
+    }
+    let scale = 2f32.powi(i32::from(e) - 136);
+    Vec3::new(f32::from(r), f32::from(g), f32::from(b)) * scale
+}
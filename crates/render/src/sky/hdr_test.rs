@@ -0,0 +1,78 @@
+//! Unit tests for HDR images.
+
+use super::hdr::HdrImage;
+use math::Vec3;
+
+fn assert_near(actual: Vec3, expected: Vec3) {
+    assert!(
+        (actual - expected).length() < 1e-4,
+        "{actual} != {expected}"
+    );
+}
+
+fn header(width: u32, height: u32) -> Vec<u8> {
+    format!("#?RADIANCE\n# exported by hand\nFORMAT=32-bit_rle_rgbe\nEXPOSURE=1.0\n\n-Y {height} +X {width}\n")
+        .into_bytes()
+}
+
+/// Verifies flat scanlines decode shared exponents, with exponent 0 as
+/// black.
+#[test]
+fn reads_flat_scanlines() {
+    let mut file = header(2, 1);
+    file.extend([128, 64, 0, 129, 255, 255, 255, 0]);
+    let image = HdrImage::from_radiance(&file).unwrap();
+    assert_eq!((image.width(), image.height()), (2, 1));
+    assert_near(image.get(0, 0), Vec3::new(1.0, 0.5, 0.0));
+    assert_near(image.get(1, 0), Vec3::ZERO);
+}
+
+/// Verifies run-length encoded scanlines, with runs and literals per
+/// channel.
+#[test]
+fn reads_run_length_scanlines() {
+    let mut file = header(8, 1);
+    file.extend([2, 2, 0, 8]);
+    // Red: a run of eight 128s.
+    file.extend([128 + 8, 128]);
+    // Green: four literals, then a run of four 0s.
+    file.extend([4, 0, 64, 128, 192, 128 + 4, 0]);
+    // Blue and exponent: runs.
+    file.extend([128 + 8, 0, 128 + 8, 129]);
+    let image = HdrImage::from_radiance(&file).unwrap();
+    assert_near(image.get(0, 0), Vec3::new(1.0, 0.0, 0.0));
+    assert_near(image.get(3, 0), Vec3::new(1.0, 1.5, 0.0));
+    assert_near(image.get(7, 0), Vec3::new(1.0, 0.0, 0.0));
+}
+
+/// Verifies malformed files are refused.
+#[test]
+fn refuses_invalid_files() {
+    assert!(HdrImage::from_radiance(b"P5 1 1 255\n\x00").is_err());
+    let mut flipped = b"#?RADIANCE\n\n+Y 1 +X 1\n".to_vec();
+    flipped.extend([0, 0, 0, 0]);
+    assert!(HdrImage::from_radiance(&flipped).is_err());
+    let mut truncated = header(2, 2);
+    truncated.extend([0; 12]);
+    assert!(HdrImage::from_radiance(&truncated).is_err());
+    let mut xyz = b"#?RADIANCE\nFORMAT=32-bit_rle_xyze\n\n-Y 1 +X 1\n".to_vec();
+    xyz.extend([0; 4]);
+    assert!(HdrImage::from_radiance(&xyz).is_err());
+}
+
+/// Verifies panoramas map directions to longitude and latitude, wrapping
+/// around the horizon.
+#[test]
+fn samples_panoramas() {
+    let red = Vec3::X;
+    let blue = Vec3::Z;
+    // Top row red getting brighter towards +X; bottom row blue.
+    let pixels = vec![red, red * 2.0, red * 3.0, red * 4.0, blue, blue, blue, blue];
+    let image = HdrImage::new(4, 2, pixels).unwrap();
+    assert_near(image.sample(Vec3::NEG_Y), blue);
+    assert_near(image.sample(Vec3::X), (red * 3.5 + blue) * 0.5);
+    assert_near(image.sample(Vec3::NEG_X), (red * 1.5 + blue) * 0.5);
+    // Straight back lies on the seam between the last and first columns.
+    assert_near(image.sample(Vec3::Z), (red * 2.5 + blue) * 0.5);
+    assert!(HdrImage::new(2, 2, vec![Vec3::ZERO; 3]).is_err());
+}
@@ -0,0 +1,283 @@
+//! Image-based lighting baked from sky cubemaps.
+
+use super::cubemap::{CubeFace, Cubemap};
+use math::Vec3;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{PI, TAU};
+
+/// Second-order spherical harmonics of a sky, for diffuse ambient light.
+///
+/// Nine coefficients per color channel are enough to light rough
+/// surfaces; shaders evaluate them per pixel with [`Irradiance::diffuse`]'s
+/// formula instead of sampling a texture.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Irradiance {
+    /// Radiance projected onto the basis functions, in band order
+    /// `L00, L1-1, L10, L11, L2-2, L2-1, L20, L21, L22`.
+    pub coefficients: [Vec3; 9],
+}
+
+impl Irradiance {
+    /// Projects the radiance of `cubemap` onto spherical harmonics,
+    /// weighting each texel by its solid angle.
+    #[must_use]
+    pub fn from_cubemap(cubemap: &Cubemap) -> Self {
+        let mut coefficients = [Vec3::ZERO; 9];
+        let size = cubemap.size();
+        for face in CubeFace::ALL {
+            let texels = cubemap.face(face);
+            for y in 0..size {
+                for x in 0..size {
+                    let radiance = texels[(y * size + x) as usize];
+                    let weight = cubemap.texel_solid_angle(x, y);
+                    let basis = sh_basis(cubemap.direction(face, x, y));
+                    for (coefficient, basis) in coefficients.iter_mut().zip(basis) {
+                        *coefficient += radiance * basis * weight;
+                    }
+                }
+            }
+        }
+        Self { coefficients }
+    }
+
+    /// Projects radiance sampled along directions spread uniformly over the
+    /// sphere, given as `(direction, radiance)` pairs.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_samples(samples: impl IntoIterator<Item = (Vec3, Vec3)>) -> Self {
+        let mut coefficients = [Vec3::ZERO; 9];
+        let mut count = 0_usize;
+        for (direction, radiance) in samples {
+            let basis = sh_basis(direction.normalize_or(Vec3::Y));
+            for (coefficient, basis) in coefficients.iter_mut().zip(basis) {
+                *coefficient += radiance * basis;
+            }
+            count += 1;
+        }
+        if count > 0 {
+            let weight = 4.0 * PI / count as f32;
+            for coefficient in &mut coefficients {
+                *coefficient *= weight;
+            }
+        }
+        Self { coefficients }
+    }
+
+    /// Blends the coefficients linearly towards `other` by `t`.
+    #[must_use]
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            coefficients: std::array::from_fn(|i| {
+                self.coefficients[i].lerp(other.coefficients[i], t)
+            }),
+        }
+    }
+
+    /// Returns the light a white Lambertian surface facing `normal`
+    /// reflects, i.e. irradiance over `π`.
+    #[must_use]
+    pub fn diffuse(&self, normal: Vec3) -> Vec3 {
+        // Convolution with the clamped cosine lobe scales each band.
+        const BANDS: [f32; 9] = [
+            PI,
+            TAU / 3.0,
+            TAU / 3.0,
+            TAU / 3.0,
+            PI / 4.0,
+            PI / 4.0,
+            PI / 4.0,
+            PI / 4.0,
+            PI / 4.0,
+        ];
+        let basis = sh_basis(normal.normalize_or(Vec3::Y));
+        let irradiance: Vec3 = self
+            .coefficients
+            .iter()
+            .zip(basis)
+            .zip(BANDS)
+            .map(|((&coefficient, basis), band)| coefficient * basis * band)
+            .sum();
+        (irradiance / PI).max(Vec3::ZERO)
+    }
+}
+
+/// Evaluates the nine real spherical harmonic basis functions.
+fn sh_basis(direction: Vec3) -> [f32; 9] {
+    let Vec3 { x, y, z } = direction;
+    [
+        0.282_095,
+        0.488_603 * y,
+        0.488_603 * z,
+        0.488_603 * x,
+        1.092_548 * x * y,
+        1.092_548 * y * z,
+        0.315_392 * (3.0 * z * z - 1.0),
+        1.092_548 * x * z,
+        0.546_274 * (x * x - y * y),
+    ]
+}
+
+/// How finely [`EnvironmentLighting::bake`] prefilters specular light.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IblSettings {
+    /// Face size of the sharpest mip, for mirror-like surfaces.
+    pub specular_size: u32,
+    /// Number of mips, from roughness 0 to 1; each halves the face size.
+    pub mip_levels: u32,
+    /// GGX samples per texel of rough mips.
+    pub samples: u32,
+}
+
+impl Default for IblSettings {
+    fn default() -> Self {
+        Self {
+            specular_size: 128,
+            mip_levels: 6,
+            samples: 128,
+        }
+    }
+}
+
+/// Ambient lighting for physically based materials, baked from a sky.
+///
+/// Diffuse light comes from spherical harmonics; specular reflections from
+/// a chain of cubemap mips prefiltered with the GGX distribution for
+/// increasing roughness, sampled at the mip matching a surface's
+/// roughness. Both are baked on the CPU whenever the sky changes, e.g.
+/// after loading a skybox or once the sun of an atmosphere has moved far
+/// enough.
+///
+/// # Example
+///
+/// ```
+/// use math::Vec3;
+/// use render::sky::{Cubemap, EnvironmentLighting, IblSettings};
+///
+/// let sky = Cubemap::uniform(8, Vec3::splat(0.5));
+/// let settings = IblSettings { specular_size: 8, mip_levels: 3, samples: 16 };
+/// let lighting = EnvironmentLighting::bake(&sky, settings);
+///
+/// assert!((lighting.diffuse(Vec3::Y) - Vec3::splat(0.5)).length() < 0.01);
+/// assert!((lighting.specular(Vec3::X, 0.7) - Vec3::splat(0.5)).length() < 0.01);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvironmentLighting {
+    irradiance: Irradiance,
+    specular: Vec<Cubemap>,
+}
+
+impl EnvironmentLighting {
+    /// Bakes the lighting of `sky`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the specular size or mip count is 0.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn bake(sky: &Cubemap, settings: IblSettings) -> Self {
+        assert!(settings.mip_levels > 0, "IBL needs at least one mip");
+        let last = (settings.mip_levels - 1).max(1) as f32;
+        let specular = (0..settings.mip_levels)
+            .map(|level| {
+                let size = (settings.specular_size >> level).max(1);
+                let roughness = level as f32 / last;
+                prefilter(sky, size, roughness, settings.samples)
+            })
+            .collect();
+        Self {
+            irradiance: Irradiance::from_cubemap(sky),
+            specular,
+        }
+    }
+
+    /// Returns the diffuse spherical harmonics.
+    #[must_use]
+    pub fn irradiance(&self) -> &Irradiance {
+        &self.irradiance
+    }
+
+    /// Returns the prefiltered specular mips, sharpest first.
+    #[must_use]
+    pub fn specular_mips(&self) -> &[Cubemap] {
+        &self.specular
+    }
+
+    /// Returns the diffuse light a white surface facing `normal` reflects.
+    #[must_use]
+    pub fn diffuse(&self, normal: Vec3) -> Vec3 {
+        self.irradiance.diffuse(normal)
+    }
+
+    /// Returns the prefiltered light reflected along `direction` by a
+    /// surface of `roughness`, blending the two nearest mips.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn specular(&self, direction: Vec3, roughness: f32) -> Vec3 {
+        let last = self.specular.len() - 1;
+        let level = roughness.clamp(0.0, 1.0) * last as f32;
+        let lower = level.floor() as usize;
+        let upper = (lower + 1).min(last);
+        let sharp = self.specular[lower].sample(direction);
+        let blurry = self.specular[upper].sample(direction);
+        sharp.lerp(blurry, level - lower as f32)
+    }
+}
+
+/// Convolves `sky` with the GGX lobe of `roughness` into a cubemap of
+/// `size`, assuming the view direction equals the normal.
+#[allow(clippy::cast_precision_loss)]
+fn prefilter(sky: &Cubemap, size: u32, roughness: f32, samples: u32) -> Cubemap {
+    if roughness <= 0.0 || samples == 0 {
+        return Cubemap::from_fn(size, |direction| sky.sample(direction));
+    }
+    let alpha = roughness * roughness;
+    let offsets: Vec<(f32, f32)> = (0..samples)
+        .map(|i| {
+            let (u, v) = hammersley(i, samples);
+            let phi = TAU * u;
+            let cos_theta = ((1.0 - v) / (1.0 + (alpha * alpha - 1.0) * v)).sqrt();
+            (phi, cos_theta)
+        })
+        .collect();
+    Cubemap::from_fn(size, |normal| {
+        let up = if normal.z.abs() < 0.999 {
+            Vec3::Z
+        } else {
+            Vec3::X
+        };
+        let tangent = up.cross(normal).normalize();
+        let bitangent = normal.cross(tangent);
+        let mut total = Vec3::ZERO;
+        let mut weight = 0.0;
+        for &(phi, cos_theta) in &offsets {
+            let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+            let half = tangent * (sin_theta * phi.cos())
+                + bitangent * (sin_theta * phi.sin())
+                + normal * cos_theta;
+            let light = half * (2.0 * normal.dot(half)) - normal;
+            let n_dot_l = normal.dot(light);
+            if n_dot_l > 0.0 {
+                total += sky.sample(light) * n_dot_l;
+                weight += n_dot_l;
+            }
+        }
+        if weight > 0.0 {
+            total / weight
+        } else {
+            sky.sample(normal)
+        }
+    })
+}
+
+/// Returns point `i` of `count` of the Hammersley low-discrepancy set.
+#[allow(clippy::cast_precision_loss)]
+fn hammersley(i: u32, count: u32) -> (f32, f32) {
+    (
+        i as f32 / count as f32,
+        i.reverse_bits() as f32 * 2.328_306_4e-10,
+    )
+}
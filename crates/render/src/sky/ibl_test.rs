@@ -0,0 +1,77 @@
+//! Unit tests for image-based lighting.
+
+use super::cubemap::Cubemap;
+use super::ibl::{EnvironmentLighting, IblSettings, Irradiance};
+use math::Vec3;
+
+fn settings() -> IblSettings {
+    IblSettings {
+        specular_size: 8,
+        mip_levels: 4,
+        samples: 64,
+    }
+}
+
+/// Verifies a uniform sky lights every direction equally.
+#[test]
+fn uniform_sky_lights_evenly() {
+    let sky = Cubemap::uniform(8, Vec3::new(1.0, 0.5, 0.25));
+    let irradiance = Irradiance::from_cubemap(&sky);
+    for normal in [Vec3::Y, Vec3::NEG_Y, Vec3::new(1.0, 1.0, -1.0)] {
+        let diffuse = irradiance.diffuse(normal);
+        assert!(
+            (diffuse - Vec3::new(1.0, 0.5, 0.25)).length() < 0.01,
+            "{diffuse}"
+        );
+    }
+}
+
+/// Verifies surfaces facing a bright sky receive more diffuse light than
+/// those facing dark ground.
+#[test]
+fn diffuse_follows_the_sky() {
+    let sky = Cubemap::from_fn(8, |direction| {
+        if direction.y > 0.0 {
+            Vec3::ONE
+        } else {
+            Vec3::ZERO
+        }
+    });
+    let irradiance = Irradiance::from_cubemap(&sky);
+    let up = irradiance.diffuse(Vec3::Y);
+    let side = irradiance.diffuse(Vec3::X);
+    let down = irradiance.diffuse(Vec3::NEG_Y);
+    assert!(up.x > 0.9 && down.x < 0.1, "{up} {down}");
+    assert!((side.x - 0.5).abs() < 0.05, "{side}");
+}
+
+/// Verifies specular mips halve in size and blur with roughness, keeping
+/// mirror reflections sharp.
+#[test]
+fn specular_blurs_with_roughness() {
+    // A bright spot straight up.
+    let sky = Cubemap::from_fn(8, |direction| {
+        if direction.y > 0.95 {
+            Vec3::splat(10.0)
+        } else {
+            Vec3::ZERO
+        }
+    });
+    let lighting = EnvironmentLighting::bake(&sky, settings());
+    let sizes: Vec<u32> = lighting.specular_mips().iter().map(Cubemap::size).collect();
+    assert_eq!(sizes, [8, 4, 2, 1]);
+    assert_eq!(
+        lighting.specular_mips()[0],
+        Cubemap::from_fn(8, |d| sky.sample(d))
+    );
+
+    let sharp = lighting.specular(Vec3::Y, 0.0).x;
+    let rough = lighting.specular(Vec3::Y, 1.0).x;
+    let sideways = Vec3::new(1.0, 1.0, 0.0);
+    assert!(sharp > rough, "{sharp} {rough}");
+    assert!(lighting.specular(sideways, 0.0).x < lighting.specular(sideways, 1.0).x);
+    assert_eq!(
+        lighting.diffuse(Vec3::Y),
+        lighting.irradiance().diffuse(Vec3::Y)
+    );
+}
@@ -0,0 +1,41 @@
+//! Sky rendering and image-based ambient lighting.
+//!
+//! Each scene picks a [`Sky`]: a flat color, a [`Skybox`] from an HDR
+//! panorama, or a procedural [`Atmosphere`] lit by the sun. Any of them
+//! bakes into a [`Cubemap`], from which [`EnvironmentLighting`] bakes the
+//! diffuse and specular ambient light physically based materials receive.
+//!
+//! A [`SkyClock`] moves the sun through the day and sends
+//! [`DayPhaseChanged`] events at dawn, day, dusk, and night, so games can
+//! hook lights, sounds, and spawns to the time of day.
+//!
+//! HDR panoramas are imported from:
+//!
+//! - [`HdrImage::from_radiance`] - Radiance `.hdr` files, flat or
+//!   run-length encoded
+
+mod atmosphere;
+#[cfg(test)]
+mod atmosphere_test;
+mod clock;
+#[cfg(test)]
+mod clock_test;
+mod cubemap;
+#[cfg(test)]
+mod cubemap_test;
+mod hdr;
+#[cfg(test)]
+mod hdr_test;
+mod ibl;
+#[cfg(test)]
+mod ibl_test;
+mod scene;
+#[cfg(test)]
+mod scene_test;
+
+pub use atmosphere::Atmosphere;
+pub use clock::{DayPhase, DayPhaseChanged, SkyClock};
+pub use cubemap::{CubeFace, Cubemap};
+pub use hdr::HdrImage;
+pub use ibl::{EnvironmentLighting, IblSettings, Irradiance};
+pub use scene::{Sky, Skybox};
@@ -0,0 +1,188 @@
+//! Per-scene sky selection.
+
+use super::atmosphere::Atmosphere;
+use super::cubemap::Cubemap;
+use super::hdr::HdrImage;
+use crate::material::Material;
+use math::{Color, Quat, Vec3};
+use rustgine_core::Label;
+use serde::{Deserialize, Serialize};
+
+/// A skybox drawn from an equirectangular HDR panorama.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Skybox {
+    /// Radiance `.hdr` asset path.
+    pub texture: Label,
+    /// Multiplier for the image's radiance.
+    #[serde(default = "one")]
+    pub intensity: f32,
+    /// Rotation about the up axis, in degrees.
+    #[serde(default)]
+    pub rotation: f32,
+}
+
+impl Skybox {
+    /// Creates a skybox of the panorama at `texture`, unscaled and
+    /// unrotated.
+    #[must_use]
+    pub fn new(texture: impl Into<Label>) -> Self {
+        Self {
+            texture: texture.into(),
+            intensity: 1.0,
+            rotation: 0.0,
+        }
+    }
+
+    /// Resamples the loaded panorama `image` into a cubemap of `size`,
+    /// scaled and rotated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    #[must_use]
+    pub fn to_cubemap(&self, image: &HdrImage, size: u32) -> Cubemap {
+        let rotation = Quat::from_rotation_y(-self.rotation.to_radians());
+        Cubemap::from_fn(size, |direction| {
+            image.sample(rotation * direction) * self.intensity
+        })
+    }
+}
+
+fn one() -> f32 {
+    1.0
+}
+
+/// The sky of a scene, stored as a TOML asset each scene refers to.
+///
+/// ```toml
+/// kind = "skybox"
+/// texture = "skies/harbor.hdr"
+/// intensity = 1.5
+/// ```
+///
+/// ```toml
+/// kind = "atmosphere"
+/// mie = 40.0  # hazy
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use math::Vec3;
+/// use render::sky::Sky;
+///
+/// let sky = Sky::from_toml("kind = \"atmosphere\"")?;
+/// let cubemap = sky.to_cubemap(Vec3::Y, 4, None)?;
+/// assert!(cubemap.sample(Vec3::Y).z > 0.0);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Sky {
+    /// One color in every direction.
+    Color {
+        /// Linear radiance.
+        color: Color,
+    },
+    /// An HDR panorama.
+    Skybox(Skybox),
+    /// A procedural atmosphere following the sun.
+    Atmosphere(Atmosphere),
+}
+
+impl Default for Sky {
+    fn default() -> Self {
+        Self::Atmosphere(Atmosphere::default())
+    }
+}
+
+impl Sky {
+    /// Parses a sky asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is not a valid sky.
+    pub fn from_toml(text: &str) -> anyhow::Result<Self> {
+        toml::from_str(text).map_err(|e| anyhow::anyhow!("invalid sky: {e}"))
+    }
+
+    /// Serializes the sky as an asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sky cannot be represented in TOML.
+    pub fn to_toml(&self) -> anyhow::Result<String> {
+        toml::to_string(self).map_err(|e| anyhow::anyhow!("failed to serialize sky: {e}"))
+    }
+
+    /// Bakes the sky into a cubemap of `size` for the sun in direction
+    /// `sun`; a skybox needs its loaded panorama `image`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sky is a skybox and `image` is `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn to_cubemap(
+        &self,
+        sun: Vec3,
+        size: u32,
+        image: Option<&HdrImage>,
+    ) -> anyhow::Result<Cubemap> {
+        match self {
+            Self::Color { color } => Ok(Cubemap::uniform(size, color.to_linear().rgb_vec())),
+            Self::Skybox(skybox) => {
+                let image = image.ok_or_else(|| {
+                    anyhow::anyhow!("skybox {} needs its image loaded", skybox.texture)
+                })?;
+                Ok(skybox.to_cubemap(image, size))
+            }
+            Self::Atmosphere(atmosphere) => Ok(atmosphere.to_cubemap(sun, size)),
+        }
+    }
+
+    /// Builds a material from `shader` for drawing the sky behind the
+    /// scene, for the sun in direction `sun`.
+    ///
+    /// Every sky sets a `sky_kind` float, 0 for a color, 1 for a skybox,
+    /// and 2 for an atmosphere, and a `sun_direction` color holding the
+    /// direction. A color sets `sky_color`; a skybox sets the `skybox`
+    /// texture, `intensity`, and `rotation` in radians; an atmosphere sets
+    /// its parameters by field name, Rayleigh coefficients as a color.
+    #[must_use]
+    pub fn material(&self, shader: &str, sun: Vec3) -> Material {
+        let sun = sun.normalize_or(Vec3::Y);
+        let material = Material::new(shader)
+            .with_color("sun_direction", Color::linear_rgb(sun.x, sun.y, sun.z));
+        match self {
+            Self::Color { color } => material
+                .with_float("sky_kind", 0.0)
+                .with_color("sky_color", *color),
+            Self::Skybox(skybox) => material
+                .with_float("sky_kind", 1.0)
+                .with_texture("skybox", skybox.texture)
+                .with_float("intensity", skybox.intensity)
+                .with_float("rotation", skybox.rotation.to_radians()),
+            Self::Atmosphere(atmosphere) => {
+                let rayleigh = atmosphere.rayleigh;
+                material
+                    .with_float("sky_kind", 2.0)
+                    .with_color(
+                        "rayleigh",
+                        Color::linear_rgb(rayleigh.x, rayleigh.y, rayleigh.z),
+                    )
+                    .with_float("rayleigh_height", atmosphere.rayleigh_height)
+                    .with_float("mie", atmosphere.mie)
+                    .with_float("mie_height", atmosphere.mie_height)
+                    .with_float("mie_anisotropy", atmosphere.mie_anisotropy)
+                    .with_float("sun_intensity", atmosphere.sun_intensity)
+                    .with_float("sun_radius", atmosphere.sun_radius.to_radians())
+                    .with_color("ground", atmosphere.ground)
+                    .with_color("night", atmosphere.night)
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Unit tests for per-scene skies.
+
+use super::atmosphere::Atmosphere;
+use super::hdr::HdrImage;
+use super::scene::{Sky, Skybox};
+use crate::MaterialParam;
+use math::{Color, Vec3};
+use rustgine_core::Label;
+
+/// Verifies each kind of sky round-trips through TOML with defaults
+/// filled in.
+#[test]
+fn skies_round_trip() {
+    let skybox = Sky::from_toml("kind = \"skybox\"\ntexture = \"skies/harbor.hdr\"").unwrap();
+    assert_eq!(skybox, Sky::Skybox(Skybox::new("skies/harbor.hdr")));
+
+    let hazy = Sky::from_toml("kind = \"atmosphere\"\nmie = 40.0").unwrap();
+    assert_eq!(
+        hazy,
+        Sky::Atmosphere(Atmosphere {
+            mie: 40.0,
+            ..Atmosphere::default()
+        })
+    );
+
+    let color = Sky::Color {
+        color: Color::linear_rgb(0.2, 0.3, 0.4),
+    };
+    for sky in [skybox, hazy, color] {
+        assert_eq!(Sky::from_toml(&sky.to_toml().unwrap()).unwrap(), sky);
+    }
+    assert!(Sky::from_toml("kind = \"stars\"").is_err());
+    assert!(Sky::from_toml("kind = \"skybox\"\ntexture = \"a.hdr\"\nexposure = 2.0").is_err());
+}
+
+/// Verifies skyboxes are scaled and rotated into cubemaps, and need their
+/// image loaded.
+#[test]
+fn bakes_skyboxes() {
+    // Brightest towards +X.
+    let pixels = vec![Vec3::ONE, Vec3::ONE, Vec3::splat(4.0), Vec3::splat(4.0)];
+    let image = HdrImage::new(4, 1, pixels).unwrap();
+    let sky = Sky::Skybox(Skybox {
+        intensity: 2.0,
+        rotation: 90.0,
+        ..Skybox::new("skies/test.hdr")
+    });
+    assert!(sky.to_cubemap(Vec3::Y, 4, None).is_err());
+    let cubemap = sky.to_cubemap(Vec3::Y, 4, Some(&image)).unwrap();
+    // Turned a quarter counterclockwise seen from above, +X now faces -Z.
+    assert!(cubemap.sample(Vec3::NEG_Z).x > cubemap.sample(Vec3::X).x);
+    assert!(cubemap.sample(Vec3::NEG_Z).x > 7.0);
+}
+
+/// Verifies sky materials carry what their shader needs.
+#[test]
+fn builds_materials() {
+    let skybox = Sky::Skybox(Skybox::new("skies/harbor.hdr")).material("sky.wgsl", Vec3::Y);
+    assert_eq!(
+        skybox.params.get("sky_kind"),
+        Some(&MaterialParam::Float(1.0))
+    );
+    assert_eq!(
+        skybox.texture("skybox", None),
+        Some(Label::new("skies/harbor.hdr"))
+    );
+
+    let atmosphere = Sky::default().material("sky.wgsl", Vec3::new(0.0, 2.0, 0.0));
+    assert_eq!(
+        atmosphere.params.get("sun_direction"),
+        Some(&MaterialParam::Color(Color::linear_rgb(0.0, 1.0, 0.0)))
+    );
+    assert_eq!(
+        atmosphere.params.get("mie"),
+        Some(&MaterialParam::Float(21.0))
+    );
+
+    let color = Sky::Color {
+        color: Color::WHITE,
+    };
+    let cubemap = color.to_cubemap(Vec3::Y, 2, None).unwrap();
+    assert_eq!(cubemap.sample(Vec3::X), Vec3::ONE);
+}
@@ -0,0 +1,413 @@
+//! Sprite animation from texture atlas frames.
+//!
+//! A [`TextureAtlas`] asset cuts one texture into frames and names
+//! [`AnimationClip`]s, sequences of those frames played at a fixed rate. A
+//! [`SpriteAnimation`] component plays one clip on a sprite, and
+//! [`animate_sprites`] advances every sprite by the simulation step,
+//! emitting an [`AnimationEvent`] whenever a clip reaches a frame with an
+//! event attached, e.g. a footstep sound on the frame a foot lands. Clip,
+//! event, and texture names are interned [`Label`]s, so thousands of
+//! animated sprites share them without cloning strings.
+//!
+//! Atlases are assets stored as TOML:
+//!
+//! ```toml
+//! texture = "sprites/hero.png"
+//! width = 256
+//! height = 64
+//! frames = [
+//!     { x = 0, y = 0, width = 64, height = 64 },
+//!     { x = 64, y = 0, width = 64, height = 64 },
+//! ]
+//!
+//! [clips.walk]
+//! frames = [0, 1]
+//! fps = 8.0
+//! mode = "loop"
+//! events = { 1 = "footstep" }
+//!
+//! # Frames drawn as 9-slice panels
+//! [slices]
+//! 1 = { left = 4, right = 4, top = 4, bottom = 4 }
+//! ```
+
+use crate::nine_slice::{scaled_quads, ImageScaling, NineSlice, SliceQuad};
+use ecs::Events;
+use math::{Rect, Vec2};
+use rustgine_core::Label;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Region of an atlas texture, in pixels from the top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AtlasRect {
+    /// Left edge.
+    pub x: u32,
+    /// Top edge.
+    pub y: u32,
+    /// Width.
+    pub width: u32,
+    /// Height.
+    pub height: u32,
+}
+
+/// How a clip continues after its last frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaybackMode {
+    /// Stops on the last frame.
+    Once,
+    /// Starts over from the first frame.
+    #[default]
+    Loop,
+    /// Plays backwards to the first frame, then forwards again.
+    PingPong,
+}
+
+/// A named sequence of atlas frames.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnimationClip {
+    /// Atlas frame indices, in playback order.
+    pub frames: Vec<usize>,
+    /// Frames per second at speed 1.0.
+    pub fps: f32,
+    /// What happens after the last frame.
+    #[serde(default)]
+    pub mode: PlaybackMode,
+    /// Event names by position in [`frames`](Self::frames), sent when
+    /// playback reaches that position.
+    #[serde(default, with = "frame_keys")]
+    pub events: BTreeMap<usize, Label>,
+}
+
+impl AnimationClip {
+    /// Creates a looping clip.
+    #[must_use]
+    pub fn new(frames: impl Into<Vec<usize>>, fps: f32) -> Self {
+        Self {
+            frames: frames.into(),
+            fps,
+            mode: PlaybackMode::Loop,
+            events: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the playback mode.
+    #[must_use]
+    pub fn with_mode(mut self, mode: PlaybackMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sends event `name` when playback reaches position `position`.
+    #[must_use]
+    pub fn with_event(mut self, position: usize, name: impl Into<Label>) -> Self {
+        self.events.insert(position, name.into());
+        self
+    }
+}
+
+/// A texture cut into frames, with the clips that animate them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextureAtlas {
+    /// Texture asset path.
+    pub texture: Label,
+    /// Texture width in pixels.
+    pub width: u32,
+    /// Texture height in pixels.
+    pub height: u32,
+    /// Frame regions, indexed by frame number.
+    pub frames: Vec<AtlasRect>,
+    /// Clips by name.
+    #[serde(default)]
+    pub clips: BTreeMap<Label, AnimationClip>,
+    /// 9-slice borders of frames that scale as panels, by frame index.
+    #[serde(default, with = "frame_keys")]
+    pub slices: BTreeMap<usize, NineSlice>,
+}
+
+impl TextureAtlas {
+    /// Creates an atlas of `columns` x `rows` equally sized cells, numbered
+    /// left to right, then top to bottom.
+    #[must_use]
+    pub fn grid(
+        texture: impl Into<Label>,
+        (cell_width, cell_height): (u32, u32),
+        columns: u32,
+        rows: u32,
+    ) -> Self {
+        let frames = (0..rows)
+            .flat_map(|row| {
+                (0..columns).map(move |column| AtlasRect {
+                    x: column * cell_width,
+                    y: row * cell_height,
+                    width: cell_width,
+                    height: cell_height,
+                })
+            })
+            .collect();
+        Self {
+            texture: texture.into(),
+            width: columns * cell_width,
+            height: rows * cell_height,
+            frames,
+            clips: BTreeMap::new(),
+            slices: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a clip.
+    #[must_use]
+    pub fn with_clip(mut self, name: impl Into<Label>, clip: AnimationClip) -> Self {
+        self.clips.insert(name.into(), clip);
+        self
+    }
+
+    /// Sets the 9-slice borders of `frame`.
+    #[must_use]
+    pub fn with_slice(mut self, frame: usize, borders: NineSlice) -> Self {
+        self.slices.insert(frame, borders);
+        self
+    }
+
+    /// Returns the quads that draw `frame` into `target`, as a 9-slice
+    /// image if `scaling` calls for it.
+    #[must_use]
+    pub fn frame_quads(&self, frame: usize, scaling: ImageScaling, target: Rect) -> Vec<SliceQuad> {
+        let (Some(rect), Some(uv)) = (self.frames.get(frame), self.uv(frame)) else {
+            return Vec::new();
+        };
+        let borders = scaling.borders(self.slices.get(&frame).copied());
+        scaled_quads(borders, uv, (rect.width, rect.height), target)
+    }
+
+    /// Parses an atlas asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is not a valid atlas or a clip refers to a
+    /// frame the atlas does not have.
+    pub fn from_toml(text: &str) -> anyhow::Result<Self> {
+        let atlas: Self =
+            toml::from_str(text).map_err(|e| anyhow::anyhow!("invalid texture atlas: {e}"))?;
+        for (name, clip) in &atlas.clips {
+            if let Some(frame) = clip.frames.iter().find(|&&f| f >= atlas.frames.len()) {
+                anyhow::bail!(
+                    "clip {name} uses frame {frame}, but the atlas has {}",
+                    atlas.frames.len()
+                );
+            }
+        }
+        Ok(atlas)
+    }
+
+    /// Serializes the atlas as an asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the atlas cannot be represented in TOML.
+    pub fn to_toml(&self) -> anyhow::Result<String> {
+        toml::to_string(self).map_err(|e| anyhow::anyhow!("failed to serialize texture atlas: {e}"))
+    }
+
+    /// Returns the texture coordinates of `frame`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn uv(&self, frame: usize) -> Option<Rect> {
+        let rect = self.frames.get(frame)?;
+        let size = Vec2::new(self.width.max(1) as f32, self.height.max(1) as f32);
+        let min = Vec2::new(rect.x as f32, rect.y as f32);
+        let max = Vec2::new((rect.x + rect.width) as f32, (rect.y + rect.height) as f32);
+        Some(Rect::new(min / size, max / size))
+    }
+}
+
+/// Sent when a sprite's clip reaches a frame with an event attached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnimationEvent<E> {
+    /// The animated entity.
+    pub entity: E,
+    /// Name of the playing clip.
+    pub clip: Label,
+    /// Event name from [`AnimationClip::events`].
+    pub name: Label,
+}
+
+/// Component playing an [`AnimationClip`] of a [`TextureAtlas`].
+///
+/// # Example
+///
+/// ```
+/// use ecs::Events;
+/// use render::sprite::{animate_sprites, AnimationClip, SpriteAnimation, TextureAtlas};
+/// use std::time::Duration;
+///
+/// let atlas = TextureAtlas::grid("hero.png", (32, 32), 4, 1)
+///     .with_clip("walk", AnimationClip::new([0, 1, 2, 3], 10.0).with_event(2, "footstep"));
+/// let mut hero = SpriteAnimation::new("walk");
+/// let mut events = Events::default();
+///
+/// animate_sprites(&atlas, [("hero", &mut hero)], Duration::from_millis(250), &mut events);
+///
+/// assert_eq!(hero.frame(&atlas), Some(2));
+/// assert_eq!(events.iter().next().unwrap().name, "footstep");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpriteAnimation {
+    /// Name of the clip being played.
+    pub clip: Label,
+    /// Playback speed multiplier; 0.0 pauses.
+    pub speed: f32,
+    /// Position in the clip's frame list.
+    position: usize,
+    /// Time since the current frame was shown, in seconds.
+    elapsed: f32,
+    /// Whether a ping-pong clip is playing backwards.
+    reversed: bool,
+    /// Whether a [`PlaybackMode::Once`] clip reached its end.
+    finished: bool,
+}
+
+impl SpriteAnimation {
+    /// Creates a component playing `clip` from the start at normal speed.
+    #[must_use]
+    pub fn new(clip: impl Into<Label>) -> Self {
+        Self {
+            clip: clip.into(),
+            speed: 1.0,
+            position: 0,
+            elapsed: 0.0,
+            reversed: false,
+            finished: false,
+        }
+    }
+
+    /// Switches to `clip` from its start, unless it is already playing.
+    pub fn play(&mut self, clip: impl Into<Label>) {
+        let clip = clip.into();
+        if self.clip != clip {
+            *self = Self {
+                speed: self.speed,
+                ..Self::new(clip)
+            };
+        }
+    }
+
+    /// Returns the atlas frame to draw, or `None` if the clip is unknown or
+    /// empty.
+    #[must_use]
+    pub fn frame(&self, atlas: &TextureAtlas) -> Option<usize> {
+        atlas
+            .clips
+            .get(&self.clip)?
+            .frames
+            .get(self.position)
+            .copied()
+    }
+
+    /// Returns `true` once a [`PlaybackMode::Once`] clip showed its last
+    /// frame.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Advances by `delta` of simulation time, returning the events of the
+    /// frames reached.
+    fn advance(&mut self, clip: &AnimationClip, delta: Duration) -> Vec<Label> {
+        let mut reached = Vec::new();
+        if clip.frames.is_empty() || clip.fps <= 0.0 || self.finished {
+            return reached;
+        }
+        let frame_time = clip.fps.recip();
+        self.elapsed += delta.as_secs_f32() * self.speed.max(0.0);
+        while self.elapsed >= frame_time && !self.finished {
+            self.elapsed -= frame_time;
+            self.step(clip);
+            reached.extend(clip.events.get(&self.position).copied());
+        }
+        reached
+    }
+
+    /// Moves to the next position according to the clip's mode.
+    fn step(&mut self, clip: &AnimationClip) {
+        let last = clip.frames.len() - 1;
+        match clip.mode {
+            PlaybackMode::Once if self.position >= last => {
+                self.finished = true;
+                self.elapsed = 0.0;
+            }
+            PlaybackMode::Once => self.position += 1,
+            PlaybackMode::Loop => self.position = (self.position + 1) % clip.frames.len(),
+            PlaybackMode::PingPong if last == 0 => {}
+            PlaybackMode::PingPong => {
+                if self.position == last {
+                    self.reversed = true;
+                } else if self.position == 0 {
+                    self.reversed = false;
+                }
+                if self.reversed {
+                    self.position -= 1;
+                } else {
+                    self.position += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Advances every sprite by the simulation step `delta`, sending an
+/// [`AnimationEvent`] for each event frame reached.
+///
+/// Sprites playing a clip the atlas does not have are left unchanged.
+pub fn animate_sprites<'s, E: Clone>(
+    atlas: &TextureAtlas,
+    sprites: impl IntoIterator<Item = (E, &'s mut SpriteAnimation)>,
+    delta: Duration,
+    events: &mut Events<AnimationEvent<E>>,
+) {
+    for (entity, sprite) in sprites {
+        let Some(clip) = atlas.clips.get(&sprite.clip) else {
+            continue;
+        };
+        for name in sprite.advance(clip, delta) {
+            events.send(AnimationEvent {
+                entity: entity.clone(),
+                clip: sprite.clip,
+                name,
+            });
+        }
+    }
+}
+
+/// TOML table keys are strings, so maps keyed by frame or position store
+/// their keys as such.
+mod frame_keys {
+    use serde::de::{DeserializeOwned, Error};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::BTreeMap;
+
+    pub(super) fn serialize<S: Serializer, V: Serialize>(
+        map: &BTreeMap<usize, V>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        map.iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect::<BTreeMap<_, _>>()
+            .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>, V: DeserializeOwned>(
+        deserializer: D,
+    ) -> Result<BTreeMap<usize, V>, D::Error> {
+        BTreeMap::<String, V>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(key, value)| {
+                key.parse()
+                    .map(|key| (key, value))
+                    .map_err(|_| D::Error::custom(format!("invalid frame key {key}")))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,153 @@
+//! Unit tests for sprite animation.
+
+use crate::sprite::{
+    animate_sprites, AnimationClip, AnimationEvent, PlaybackMode, SpriteAnimation, TextureAtlas,
+};
+use ecs::Events;
+use math::{Rect, Vec2};
+use rustgine_core::Label;
+use std::time::Duration;
+
+/// One frame at 10 fps.
+const FRAME: Duration = Duration::from_millis(100);
+
+fn atlas(mode: PlaybackMode) -> TextureAtlas {
+    TextureAtlas::grid("hero.png", (16, 16), 4, 1)
+        .with_clip("run", AnimationClip::new([0, 1, 2], 10.0).with_mode(mode))
+}
+
+/// Plays `frames` steps and returns the atlas frame shown after each.
+fn play(atlas: &TextureAtlas, sprite: &mut SpriteAnimation, frames: usize) -> Vec<usize> {
+    let mut events = Events::default();
+    (0..frames)
+        .map(|_| {
+            animate_sprites(atlas, [((), &mut *sprite)], FRAME, &mut events);
+            sprite.frame(atlas).unwrap()
+        })
+        .collect()
+}
+
+/// Verifies looping, once, and ping-pong playback orders.
+#[test]
+fn playback_modes() {
+    let looping = atlas(PlaybackMode::Loop);
+    assert_eq!(
+        play(&looping, &mut SpriteAnimation::new("run"), 5),
+        [1, 2, 0, 1, 2]
+    );
+
+    let once = atlas(PlaybackMode::Once);
+    let mut sprite = SpriteAnimation::new("run");
+    assert_eq!(play(&once, &mut sprite, 4), [1, 2, 2, 2]);
+    assert!(sprite.is_finished());
+
+    let ping_pong = atlas(PlaybackMode::PingPong);
+    assert_eq!(
+        play(&ping_pong, &mut SpriteAnimation::new("run"), 6),
+        [1, 2, 1, 0, 1, 2]
+    );
+}
+
+/// Verifies speed scales playback and partial frames accumulate.
+#[test]
+fn speed_scales_playback() {
+    let atlas = atlas(PlaybackMode::Loop);
+    let mut events = Events::default();
+    let mut sprite = SpriteAnimation::new("run");
+    sprite.speed = 2.0;
+
+    animate_sprites(&atlas, [((), &mut sprite)], FRAME, &mut events);
+    assert_eq!(sprite.frame(&atlas), Some(2));
+
+    sprite.speed = 0.5;
+    animate_sprites(&atlas, [((), &mut sprite)], FRAME, &mut events);
+    assert_eq!(sprite.frame(&atlas), Some(2));
+    animate_sprites(&atlas, [((), &mut sprite)], FRAME, &mut events);
+    assert_eq!(sprite.frame(&atlas), Some(0));
+}
+
+/// Verifies events are sent for each event frame reached.
+#[test]
+fn sends_frame_events() {
+    let atlas = TextureAtlas::grid("hero.png", (16, 16), 4, 1).with_clip(
+        "walk",
+        AnimationClip::new([0, 1, 2, 3], 10.0)
+            .with_event(1, "left_foot")
+            .with_event(3, "right_foot"),
+    );
+    let mut events = Events::default();
+    let mut hero = SpriteAnimation::new("walk");
+    let mut idle = SpriteAnimation::new("missing");
+
+    animate_sprites(
+        &atlas,
+        [(7, &mut hero), (8, &mut idle)],
+        FRAME * 5,
+        &mut events,
+    );
+
+    let names: Vec<_> = events.iter().map(|event| event.name.as_str()).collect();
+    assert_eq!(names, ["left_foot", "right_foot", "left_foot"]);
+    assert_eq!(
+        events.iter().next(),
+        Some(&AnimationEvent {
+            entity: 7,
+            clip: "walk".into(),
+            name: "left_foot".into(),
+        })
+    );
+}
+
+/// Verifies switching clips restarts playback only for a new clip.
+#[test]
+fn play_switches_clips() {
+    let atlas = atlas(PlaybackMode::Loop).with_clip("idle", AnimationClip::new([3], 1.0));
+    let mut sprite = SpriteAnimation::new("run");
+    play(&atlas, &mut sprite, 1);
+
+    sprite.play("run");
+    assert_eq!(sprite.frame(&atlas), Some(1));
+    sprite.play("idle");
+    assert_eq!(sprite.frame(&atlas), Some(3));
+}
+
+/// Verifies atlases load from TOML and reject clips with missing frames.
+#[test]
+fn parses_atlas_asset() {
+    let atlas = TextureAtlas::from_toml(
+        r#"
+        texture = "sprites/hero.png"
+        width = 128
+        height = 64
+        frames = [
+            { x = 0, y = 0, width = 64, height = 64 },
+            { x = 64, y = 0, width = 64, height = 64 },
+        ]
+
+        [clips.walk]
+        frames = [0, 1]
+        fps = 8.0
+        mode = "ping_pong"
+        events = { 1 = "footstep" }
+        "#,
+    )
+    .unwrap();
+
+    let walk = &atlas.clips[&Label::new("walk")];
+    assert_eq!(walk.mode, PlaybackMode::PingPong);
+    assert_eq!(walk.events[&1], "footstep");
+    assert_eq!(atlas.uv(1), Some(Rect::new(Vec2::new(0.5, 0.0), Vec2::ONE)));
+    assert_eq!(
+        TextureAtlas::from_toml(&atlas.to_toml().unwrap()).unwrap(),
+        atlas
+    );
+
+    let broken = r#"
+        texture = "a.png"
+        width = 16
+        height = 16
+        frames = []
+        clips = { idle = { frames = [0], fps = 1.0 } }
+    "#;
+    assert!(TextureAtlas::from_toml(broken).is_err());
+}
@@ -0,0 +1,188 @@
+//! Static batching of level geometry.
+//!
+//! Big levels are built from many small static meshes, each costing a draw
+//! call. At scene load, [`StaticBatches::build`] merges meshes that share a
+//! material and a spatial cell into one combined vertex and index buffer,
+//! pre-transformed into world space. The original entities remain for game
+//! logic, but the renderer skips those that
+//! [`is_batched`](StaticBatches::is_batched) and draws the batches instead.
+//!
+//! Batching by cell rather than merging the whole level keeps each batch
+//! small enough to still be culled, so the result works with any camera
+//! frustum.
+
+use crate::mesh::{Aabb, Mesh};
+use math::Mat4;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Default edge length of a batching cell, in world units.
+pub const DEFAULT_CELL_SIZE: f32 = 64.0;
+
+/// Default maximum number of vertices in one batch.
+pub const DEFAULT_MAX_BATCH_VERTICES: usize = 1 << 18;
+
+/// Static batching settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StaticBatchConfig {
+    /// Edge length of a batching cell, in world units.
+    pub cell_size: f32,
+    /// Batches are split once they would exceed this many vertices.
+    pub max_vertices: usize,
+}
+
+impl Default for StaticBatchConfig {
+    fn default() -> Self {
+        Self {
+            cell_size: DEFAULT_CELL_SIZE,
+            max_vertices: DEFAULT_MAX_BATCH_VERTICES,
+        }
+    }
+}
+
+/// A static mesh placed in the level.
+#[derive(Debug, Clone, Copy)]
+pub struct StaticMesh<'a, E, M> {
+    /// Entity the mesh belongs to.
+    pub entity: E,
+    /// Mesh in local space.
+    pub mesh: &'a Mesh,
+    /// Material the mesh is drawn with.
+    pub material: M,
+    /// Local-to-world transform.
+    pub transform: Mat4,
+}
+
+/// Meshes merged into one draw call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaticBatch<E, M> {
+    /// Material shared by every merged mesh.
+    pub material: M,
+    /// Cell the merged meshes are centered in.
+    pub cell: [i32; 3],
+    /// Combined mesh in world space.
+    pub mesh: Mesh,
+    /// World-space bounds, for culling.
+    pub bounds: Aabb,
+    /// Entities whose meshes were merged.
+    pub entities: Vec<E>,
+}
+
+/// Result of static batching.
+///
+/// # Example
+///
+/// ```
+/// use math::{Mat4, Vec3};
+/// use render::mesh::{Mesh, MeshVertex};
+/// use render::static_batch::{StaticBatchConfig, StaticBatches, StaticMesh};
+///
+/// let crate_mesh = Mesh {
+///     vertices: vec![MeshVertex::default(); 3],
+///     indices: vec![0, 1, 2],
+/// };
+/// let level = (0..100).map(|i| StaticMesh {
+///     entity: i,
+///     mesh: &crate_mesh,
+///     material: "wood",
+///     transform: Mat4::from_translation(Vec3::X * i as f32),
+/// });
+///
+/// let batches = StaticBatches::build(level, StaticBatchConfig::default());
+/// assert_eq!(batches.draw_calls(), 2);
+/// assert!(batches.is_batched(&42));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaticBatches<E, M> {
+    batches: Vec<StaticBatch<E, M>>,
+    batched: BTreeSet<E>,
+}
+
+impl<E: Ord + Clone, M: Ord + Clone> StaticBatches<E, M> {
+    /// Merges `meshes` by material and cell.
+    ///
+    /// Meshes without vertices are ignored and stay unbatched.
+    #[must_use]
+    pub fn build<'a>(
+        meshes: impl IntoIterator<Item = StaticMesh<'a, E, M>>,
+        config: StaticBatchConfig,
+    ) -> Self {
+        let mut groups: BTreeMap<_, Vec<StaticMesh<'a, E, M>>> = BTreeMap::new();
+        for mesh in meshes {
+            let Some(bounds) = mesh.mesh.bounds() else {
+                continue;
+            };
+            let cell = cell_of(
+                bounds.transformed(&mesh.transform).center().to_array(),
+                config.cell_size,
+            );
+            groups
+                .entry((mesh.material.clone(), cell))
+                .or_default()
+                .push(mesh);
+        }
+
+        let mut batches = Vec::new();
+        let mut merged_entities = BTreeSet::new();
+        for ((material, cell), meshes) in groups {
+            let mut current: Option<StaticBatch<E, M>> = None;
+            for placed in meshes {
+                let full = current.as_ref().is_some_and(|batch| {
+                    batch.mesh.vertices.len() + placed.mesh.vertices.len() > config.max_vertices
+                });
+                if full {
+                    batches.extend(current.take());
+                }
+                let bounds = placed
+                    .mesh
+                    .bounds()
+                    .map(|bounds| bounds.transformed(&placed.transform));
+                let batch = current.get_or_insert_with(|| StaticBatch {
+                    material: material.clone(),
+                    cell,
+                    mesh: Mesh::default(),
+                    bounds: bounds.unwrap_or_default(),
+                    entities: Vec::new(),
+                });
+                batch
+                    .mesh
+                    .append_transformed(placed.mesh, &placed.transform);
+                if let Some(bounds) = bounds {
+                    batch.bounds = batch.bounds.union(&bounds);
+                }
+                merged_entities.insert(placed.entity.clone());
+                batch.entities.push(placed.entity);
+            }
+            batches.extend(current);
+        }
+        Self {
+            batches,
+            batched: merged_entities,
+        }
+    }
+
+    /// Returns the batches to draw.
+    #[must_use]
+    pub fn batches(&self) -> &[StaticBatch<E, M>] {
+        &self.batches
+    }
+
+    /// Returns the number of draw calls the batches need.
+    #[must_use]
+    pub fn draw_calls(&self) -> usize {
+        self.batches.len()
+    }
+
+    /// Returns `true` if `entity` is drawn as part of a batch and must not
+    /// be drawn on its own.
+    #[must_use]
+    pub fn is_batched(&self, entity: &E) -> bool {
+        self.batched.contains(entity)
+    }
+}
+
+/// Returns the cell holding `point`.
+#[allow(clippy::cast_possible_truncation)]
+fn cell_of(point: [f32; 3], cell_size: f32) -> [i32; 3] {
+    let size = cell_size.max(f32::EPSILON);
+    point.map(|coordinate| (coordinate / size).floor() as i32)
+}
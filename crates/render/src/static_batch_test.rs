@@ -0,0 +1,119 @@
+//! Unit tests for static batching.
+
+use crate::mesh::{Aabb, Mesh, MeshVertex};
+use crate::static_batch::{StaticBatchConfig, StaticBatches, StaticMesh};
+use math::{Mat4, Vec3};
+
+/// A unit triangle.
+fn triangle() -> Mesh {
+    let vertex = |position| MeshVertex {
+        position,
+        normal: [0.0, 0.0, 1.0],
+        uv: [0.0, 0.0],
+    };
+    Mesh {
+        vertices: vec![
+            vertex([0.0, 0.0, 0.0]),
+            vertex([1.0, 0.0, 0.0]),
+            vertex([0.0, 1.0, 0.0]),
+        ],
+        indices: vec![0, 1, 2],
+    }
+}
+
+fn placed<'a>(
+    entity: u32,
+    mesh: &'a Mesh,
+    material: &'static str,
+    x: f32,
+) -> StaticMesh<'a, u32, &'static str> {
+    StaticMesh {
+        entity,
+        mesh,
+        material,
+        transform: Mat4::from_translation(Vec3::new(x, 0.0, 0.0)),
+    }
+}
+
+/// Verifies meshes merge by material and cell into world space.
+#[test]
+fn merges_by_material_and_cell() {
+    let mesh = triangle();
+    let config = StaticBatchConfig {
+        cell_size: 10.0,
+        ..StaticBatchConfig::default()
+    };
+    let batches = StaticBatches::build(
+        [
+            placed(1, &mesh, "stone", 0.0),
+            placed(2, &mesh, "stone", 5.0),
+            placed(3, &mesh, "wood", 5.0),
+            placed(4, &mesh, "stone", 25.0),
+        ],
+        config,
+    );
+
+    assert_eq!(batches.draw_calls(), 3);
+    let stone = &batches.batches()[0];
+    assert_eq!((stone.material, stone.cell), ("stone", [0, 0, 0]));
+    assert_eq!(stone.entities, [1, 2]);
+    assert_eq!(stone.mesh.indices, [0, 1, 2, 3, 4, 5]);
+    assert_eq!(
+        stone.mesh.vertices[4],
+        MeshVertex {
+            position: [6.0, 0.0, 0.0],
+            normal: [0.0, 0.0, 1.0],
+            uv: [0.0, 0.0],
+        }
+    );
+    assert_eq!(
+        stone.bounds,
+        Aabb {
+            min: Vec3::ZERO,
+            max: Vec3::new(6.0, 1.0, 0.0),
+        }
+    );
+    assert_eq!(batches.batches()[1].cell, [2, 0, 0]);
+    assert_eq!(batches.batches()[2].material, "wood");
+}
+
+/// Verifies batches split at the vertex limit.
+#[test]
+fn splits_large_batches() {
+    let mesh = triangle();
+    let config = StaticBatchConfig {
+        max_vertices: 7,
+        ..StaticBatchConfig::default()
+    };
+    let batches = StaticBatches::build((0..5).map(|i| placed(i, &mesh, "stone", 0.0)), config);
+
+    let sizes: Vec<_> = batches
+        .batches()
+        .iter()
+        .map(|batch| batch.entities.len())
+        .collect();
+    assert_eq!(sizes, [2, 2, 1]);
+}
+
+/// Verifies batched entities are flagged while empty meshes are left out.
+#[test]
+fn tracks_batched_entities() {
+    let mesh = triangle();
+    let empty = Mesh::default();
+    let batches = StaticBatches::build(
+        [
+            placed(1, &mesh, "stone", 0.0),
+            StaticMesh {
+                entity: 2,
+                mesh: &empty,
+                material: "stone",
+                transform: Mat4::IDENTITY,
+            },
+        ],
+        StaticBatchConfig::default(),
+    );
+
+    assert!(batches.is_batched(&1));
+    assert!(!batches.is_batched(&2));
+    assert!(!batches.is_batched(&3));
+}
@@ -0,0 +1,256 @@
+//! Per-frame renderer statistics.
+//!
+//! The renderer reports each draw and each GPU allocation to a
+//! [`RenderStatsRecorder`], which sums them into a [`RenderStats`] snapshot
+//! when the frame ends. The snapshot is shown in the debug overlay, and is
+//! also emitted as a `trace` event on the `render::stats` target so headless
+//! runs can capture it with `RUST_LOG=render::stats=trace`.
+
+use crate::bind_group::BindGroupCacheStats;
+use crate::capabilities::IndirectDraws;
+use crate::indirect::IndirectFrame;
+use crate::ui_batch::UiFrame;
+use serde::Serialize;
+use std::fmt;
+use tracing::trace;
+
+/// Counters of one rendered frame, plus GPU memory in use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct RenderStats {
+    /// Draw calls submitted.
+    pub draw_calls: u64,
+    /// Instances drawn across all draw calls.
+    pub instances: u64,
+    /// Triangles submitted, counting every instance.
+    pub triangles: u64,
+    /// Times consecutive draws used different pipelines.
+    pub pipeline_switches: u64,
+    /// Draw calls of the UI, also counted in `draw_calls`.
+    pub ui_draw_calls: u64,
+    /// UI quads drawn.
+    pub ui_quads: u64,
+    /// Bind group lookups answered from the cache.
+    pub bind_group_hits: u64,
+    /// Bind groups created because the cache missed.
+    pub bind_group_misses: u64,
+    /// Bytes of GPU buffers allocated.
+    pub buffer_bytes: u64,
+    /// Bytes of GPU textures allocated.
+    pub texture_bytes: u64,
+}
+
+impl RenderStats {
+    /// Returns the GPU memory allocated in buffers and textures.
+    #[must_use]
+    pub fn memory_bytes(&self) -> u64 {
+        self.buffer_bytes + self.texture_bytes
+    }
+
+    /// Returns the fraction of bind group lookups answered from the cache,
+    /// 1.0 without lookups.
+    #[must_use]
+    pub fn bind_group_hit_rate(&self) -> f64 {
+        BindGroupCacheStats {
+            hits: self.bind_group_hits,
+            misses: self.bind_group_misses,
+            evicted: 0,
+        }
+        .hit_rate()
+    }
+
+    /// Returns the lines shown in the debug overlay.
+    #[must_use]
+    pub fn overlay_lines(&self) -> Vec<String> {
+        vec![
+            format!(
+                "draw calls  {} ({} instances)",
+                self.draw_calls, self.instances
+            ),
+            format!("triangles   {}", self.triangles),
+            format!("pipelines   {} switches", self.pipeline_switches),
+            format!(
+                "ui          {} draw calls ({} quads)",
+                self.ui_draw_calls, self.ui_quads
+            ),
+            format!(
+                "bind groups {:.1}% hits ({} created)",
+                self.bind_group_hit_rate() * 100.0,
+                self.bind_group_misses
+            ),
+            format!(
+                "gpu memory  {} ({} buffers, {} textures)",
+                Bytes(self.memory_bytes()),
+                Bytes(self.buffer_bytes),
+                Bytes(self.texture_bytes)
+            ),
+        ]
+    }
+}
+
+impl fmt::Display for RenderStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.overlay_lines().join("\n"))
+    }
+}
+
+/// Formats a byte count with a binary unit, such as `1.5 MiB`.
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+    #[allow(clippy::cast_precision_loss)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut value = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while value >= 1024.0 && unit + 1 < UNITS.len() {
+            value /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// Collects [`RenderStats`] while a frame is recorded.
+///
+/// Draw counters reset every frame; memory counters persist until the
+/// allocation is freed.
+///
+/// # Example
+///
+/// ```
+/// use render::stats::RenderStatsRecorder;
+///
+/// let mut recorder = RenderStatsRecorder::default();
+/// recorder.allocate_texture(4 * 1024 * 1024);
+///
+/// recorder.begin_frame();
+/// recorder.draw(1, 100, 12); // 100 cubes
+/// recorder.draw(1, 1, 2);
+/// recorder.draw(2, 1, 2);
+/// let stats = recorder.end_frame();
+///
+/// assert_eq!(stats.draw_calls, 3);
+/// assert_eq!(stats.triangles, 1_204);
+/// assert_eq!(stats.pipeline_switches, 1);
+/// assert_eq!(stats.texture_bytes, 4 * 1024 * 1024);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RenderStatsRecorder {
+    /// Counters of the frame being recorded.
+    current: RenderStats,
+    /// Pipeline of the previous draw this frame.
+    pipeline: Option<u64>,
+    /// Counters of the last completed frame.
+    last: RenderStats,
+}
+
+impl RenderStatsRecorder {
+    /// Starts a frame, clearing the draw counters.
+    pub fn begin_frame(&mut self) {
+        self.current = RenderStats {
+            buffer_bytes: self.current.buffer_bytes,
+            texture_bytes: self.current.texture_bytes,
+            ..RenderStats::default()
+        };
+        self.pipeline = None;
+    }
+
+    /// Records a draw call of `instances` instances of a mesh with
+    /// `triangles` triangles, using the pipeline identified by `pipeline`.
+    pub fn draw(&mut self, pipeline: u64, instances: u32, triangles: u32) {
+        if self.pipeline.is_some_and(|previous| previous != pipeline) {
+            self.current.pipeline_switches += 1;
+        }
+        self.pipeline = Some(pipeline);
+        self.current.draw_calls += 1;
+        self.current.instances += u64::from(instances);
+        self.current.triangles += u64::from(instances) * u64::from(triangles);
+    }
+
+    /// Records the draw calls of a batched UI `frame`, all using the
+    /// pipeline identified by `pipeline`.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn draw_ui(&mut self, pipeline: u64, frame: &UiFrame) {
+        for batch in &frame.batches {
+            self.draw(pipeline, 1, batch.indices.len() as u32 / 3);
+        }
+        self.current.ui_draw_calls += frame.batches.len() as u64;
+        self.current.ui_quads += u64::from(frame.stats.quads);
+    }
+
+    /// Records the indirect calls drawing a culled `frame` with `mode`, all
+    /// using the pipeline identified by `pipeline`. Instances and triangles
+    /// are those the culling pass left visible.
+    pub fn draw_indirect(&mut self, pipeline: u64, frame: &IndirectFrame, mode: IndirectDraws) {
+        for call in frame.calls(mode) {
+            let first = call.first_draw as usize;
+            let draws = &frame.draws[first..first + call.draw_count as usize];
+            if self.pipeline.is_some_and(|previous| previous != pipeline) {
+                self.current.pipeline_switches += 1;
+            }
+            self.pipeline = Some(pipeline);
+            self.current.draw_calls += 1;
+            for draw in draws {
+                self.current.instances += u64::from(draw.instance_count);
+                self.current.triangles +=
+                    u64::from(draw.instance_count) * u64::from(draw.index_count / 3);
+            }
+        }
+    }
+
+    /// Records the lookups of a bind group cache over the frame.
+    pub fn record_bind_groups(&mut self, cache: &BindGroupCacheStats) {
+        self.current.bind_group_hits += cache.hits;
+        self.current.bind_group_misses += cache.misses;
+    }
+
+    /// Records a buffer allocation.
+    pub fn allocate_buffer(&mut self, bytes: u64) {
+        self.current.buffer_bytes += bytes;
+    }
+
+    /// Records a buffer being freed.
+    pub fn free_buffer(&mut self, bytes: u64) {
+        self.current.buffer_bytes = self.current.buffer_bytes.saturating_sub(bytes);
+    }
+
+    /// Records a texture allocation.
+    pub fn allocate_texture(&mut self, bytes: u64) {
+        self.current.texture_bytes += bytes;
+    }
+
+    /// Records a texture being freed.
+    pub fn free_texture(&mut self, bytes: u64) {
+        self.current.texture_bytes = self.current.texture_bytes.saturating_sub(bytes);
+    }
+
+    /// Ends the frame, emitting and returning its statistics.
+    pub fn end_frame(&mut self) -> RenderStats {
+        self.last = self.current;
+        let stats = self.last;
+        trace!(
+            target: "render::stats",
+            draw_calls = stats.draw_calls,
+            instances = stats.instances,
+            triangles = stats.triangles,
+            pipeline_switches = stats.pipeline_switches,
+            ui_draw_calls = stats.ui_draw_calls,
+            bind_group_hits = stats.bind_group_hits,
+            bind_group_misses = stats.bind_group_misses,
+            buffer_bytes = stats.buffer_bytes,
+            texture_bytes = stats.texture_bytes,
+            "frame render stats"
+        );
+        stats
+    }
+
+    /// Returns the statistics of the last completed frame.
+    #[must_use]
+    #[inline]
+    pub fn last(&self) -> &RenderStats {
+        &self.last
+    }
+}
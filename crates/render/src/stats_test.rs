@@ -0,0 +1,94 @@
+//! Unit tests for renderer statistics.
+
+use crate::bind_group::BindGroupCacheStats;
+use crate::stats::{RenderStats, RenderStatsRecorder};
+
+/// Verifies draw counters reset each frame while memory persists.
+#[test]
+fn test_frames_reset_draw_counters() {
+    let mut recorder = RenderStatsRecorder::default();
+    recorder.allocate_buffer(1_000);
+    recorder.begin_frame();
+    recorder.draw(7, 10, 2);
+    let first = recorder.end_frame();
+    assert_eq!(
+        (first.draw_calls, first.instances, first.triangles),
+        (1, 10, 20)
+    );
+
+    recorder.free_buffer(400);
+    recorder.begin_frame();
+    let second = recorder.end_frame();
+    assert_eq!(second.draw_calls, 0);
+    assert_eq!(second.buffer_bytes, 600);
+    assert_eq!(recorder.last(), &second);
+
+    recorder.free_buffer(10_000);
+    recorder.free_texture(1);
+    assert_eq!(recorder.end_frame().memory_bytes(), 0);
+}
+
+/// Verifies only changes of pipeline between consecutive draws count as
+/// switches, starting fresh each frame.
+#[test]
+fn test_pipeline_switches() {
+    let mut recorder = RenderStatsRecorder::default();
+    recorder.begin_frame();
+    for pipeline in [1, 1, 2, 2, 1, 3] {
+        recorder.draw(pipeline, 1, 1);
+    }
+    assert_eq!(recorder.end_frame().pipeline_switches, 3);
+
+    recorder.begin_frame();
+    recorder.draw(5, 1, 1);
+    assert_eq!(recorder.end_frame().pipeline_switches, 0);
+}
+
+/// Verifies the overlay lines format counts and memory units.
+#[test]
+fn test_overlay_lines() {
+    let stats = RenderStats {
+        draw_calls: 42,
+        instances: 1_000,
+        triangles: 250_000,
+        pipeline_switches: 6,
+        ui_draw_calls: 2,
+        ui_quads: 120,
+        bind_group_hits: 195,
+        bind_group_misses: 5,
+        buffer_bytes: 512,
+        texture_bytes: 3 * 1024 * 1024,
+    };
+    assert_eq!(
+        stats.overlay_lines(),
+        [
+            "draw calls  42 (1000 instances)",
+            "triangles   250000",
+            "pipelines   6 switches",
+            "ui          2 draw calls (120 quads)",
+            "bind groups 97.5% hits (5 created)",
+            "gpu memory  3.0 MiB (512 B buffers, 3.0 MiB textures)",
+        ]
+    );
+    assert_eq!(stats.to_string().lines().count(), 6);
+}
+
+/// Verifies bind group cache lookups add up over a frame into a hit rate.
+#[test]
+fn test_bind_group_hit_rate() {
+    let mut recorder = RenderStatsRecorder::default();
+    recorder.begin_frame();
+    assert!((recorder.end_frame().bind_group_hit_rate() - 1.0).abs() < 1e-9);
+
+    recorder.begin_frame();
+    for (hits, misses) in [(6, 2), (0, 2)] {
+        recorder.record_bind_groups(&BindGroupCacheStats {
+            hits,
+            misses,
+            evicted: 0,
+        });
+    }
+    let stats = recorder.end_frame();
+    assert_eq!((stats.bind_group_hits, stats.bind_group_misses), (6, 4));
+    assert!((stats.bind_group_hit_rate() - 0.6).abs() < 1e-9);
+}
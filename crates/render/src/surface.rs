@@ -0,0 +1,192 @@
+//! Surface error recovery.
+//!
+//! Swapchain surfaces become outdated on resizes and mode switches and get
+//! lost on driver resets. [`SurfaceRecovery`] turns those errors into a
+//! skipped frame plus the work needed to get presenting again, and raises
+//! [`GpuDeviceLost`] when the device itself has to be recreated, instead of
+//! letting the error abort the engine. The application converts it into
+//! [`RustgineError::DeviceLost`] if restarting the renderer cannot recover.
+//!
+//! The renderer reaches the GPU through the [`RenderSurface`] trait, so the
+//! recovery path can be exercised without a GPU.
+
+use ecs::Events;
+use rustgine_core::RustgineError;
+use tracing::{debug, warn};
+
+/// Consecutive lost surfaces tolerated before the device is considered lost.
+pub const MAX_SURFACE_LOSSES: u32 = 3;
+
+/// Error acquiring the next swapchain texture.
+///
+/// Mirrors `wgpu::SurfaceError`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurfaceError {
+    /// Acquiring the texture took too long.
+    Timeout,
+    /// The surface no longer matches the window and must be reconfigured.
+    Outdated,
+    /// The surface was lost, e.g. by a driver reset.
+    Lost,
+    /// There is not enough memory left to acquire a texture.
+    OutOfMemory,
+    /// Any other backend error.
+    Other,
+}
+
+/// Access to a presentable surface and the resources sized against it.
+pub trait RenderSurface {
+    /// A frame ready to be rendered into.
+    type Frame;
+
+    /// Acquires the next frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason no frame could be acquired.
+    fn acquire(&mut self) -> Result<Self::Frame, SurfaceError>;
+
+    /// Reconfigures the surface for the current window size.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the surface cannot be reconfigured.
+    fn reconfigure(&mut self) -> anyhow::Result<()>;
+
+    /// Recreates resources that depend on the swapchain, such as depth
+    /// buffers and render targets sized to the surface.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a resource cannot be recreated.
+    fn recreate_resources(&mut self) -> anyhow::Result<()>;
+}
+
+/// Raised when the GPU device must be recreated before rendering resumes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuDeviceLost {
+    /// Why the device was given up on.
+    pub reason: String,
+}
+
+impl From<GpuDeviceLost> for RustgineError {
+    #[inline]
+    fn from(_: GpuDeviceLost) -> Self {
+        Self::DeviceLost
+    }
+}
+
+/// Result of [`SurfaceRecovery::acquire`].
+#[derive(Debug)]
+pub enum FrameOutcome<F> {
+    /// A frame was acquired and should be rendered.
+    Render(F),
+    /// The frame should be skipped; the surface was recovered if needed.
+    Skip,
+    /// The device is lost; a [`GpuDeviceLost`] event was raised and frames
+    /// are skipped until [`SurfaceRecovery::device_recreated`] is called.
+    DeviceLost,
+}
+
+/// Recovers from surface errors while acquiring frames.
+///
+/// # Example
+///
+/// ```ignore
+/// use render::surface::{FrameOutcome, SurfaceRecovery};
+///
+/// match recovery.acquire(&mut surface, &mut device_lost_events) {
+///     FrameOutcome::Render(frame) => renderer.draw(frame),
+///     FrameOutcome::Skip | FrameOutcome::DeviceLost => {}
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct SurfaceRecovery {
+    /// Consecutive frames that hit [`SurfaceError::Lost`].
+    losses: u32,
+    device_lost: bool,
+}
+
+impl SurfaceRecovery {
+    /// Creates a recovery tracker for a healthy surface.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` while waiting for the device to be recreated.
+    #[must_use]
+    #[inline]
+    pub fn is_device_lost(&self) -> bool {
+        self.device_lost
+    }
+
+    /// Clears the device-lost state once a new device and surface exist.
+    pub fn device_recreated(&mut self) {
+        self.device_lost = false;
+        self.losses = 0;
+    }
+
+    /// Acquires the next frame from `surface`, recovering from errors.
+    ///
+    /// | Error | Recovery |
+    /// |-------|----------|
+    /// | [`Timeout`](SurfaceError::Timeout), [`Other`](SurfaceError::Other) | Skip the frame |
+    /// | [`Outdated`](SurfaceError::Outdated) | Reconfigure, skip the frame |
+    /// | [`Lost`](SurfaceError::Lost) | Reconfigure and recreate resources, skip the frame |
+    /// | [`OutOfMemory`](SurfaceError::OutOfMemory) | Device lost |
+    ///
+    /// Failing to recover, or losing the surface [`MAX_SURFACE_LOSSES`]
+    /// frames in a row, raises [`GpuDeviceLost`] on `events`.
+    pub fn acquire<S: RenderSurface>(
+        &mut self,
+        surface: &mut S,
+        events: &mut Events<GpuDeviceLost>,
+    ) -> FrameOutcome<S::Frame> {
+        if self.device_lost {
+            return FrameOutcome::DeviceLost;
+        }
+
+        let error = match surface.acquire() {
+            Ok(frame) => {
+                self.losses = 0;
+                return FrameOutcome::Render(frame);
+            }
+            Err(error) => error,
+        };
+
+        let recovered = match error {
+            SurfaceError::Timeout | SurfaceError::Other => {
+                debug!(?error, "skipping frame after surface error");
+                Ok(())
+            }
+            SurfaceError::Outdated => surface.reconfigure(),
+            SurfaceError::Lost => {
+                self.losses += 1;
+                if self.losses >= MAX_SURFACE_LOSSES {
+                    Err(anyhow::anyhow!(
+                        "surface lost {} frames in a row",
+                        self.losses
+                    ))
+                } else {
+                    surface
+                        .reconfigure()
+                        .and_then(|()| surface.recreate_resources())
+                }
+            }
+            SurfaceError::OutOfMemory => Err(anyhow::anyhow!("out of GPU memory")),
+        };
+
+        match recovered {
+            Ok(()) => FrameOutcome::Skip,
+            Err(e) => {
+                warn!(surface_error = ?error, error = %e, "GPU device lost");
+                self.device_lost = true;
+                events.send(GpuDeviceLost {
+                    reason: e.to_string(),
+                });
+                FrameOutcome::DeviceLost
+            }
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! Unit tests for surface error recovery.
+
+use crate::surface::{
+    FrameOutcome, GpuDeviceLost, RenderSurface, SurfaceError, SurfaceRecovery, MAX_SURFACE_LOSSES,
+};
+use ecs::Events;
+use std::collections::VecDeque;
+
+/// Surface that replays scripted acquire results.
+#[derive(Debug, Default)]
+struct ScriptedSurface {
+    results: VecDeque<Result<u32, SurfaceError>>,
+    reconfigures: u32,
+    recreated: u32,
+    fail_reconfigure: bool,
+}
+
+impl ScriptedSurface {
+    fn new(results: impl IntoIterator<Item = Result<u32, SurfaceError>>) -> Self {
+        Self {
+            results: results.into_iter().collect(),
+            ..Self::default()
+        }
+    }
+}
+
+impl RenderSurface for ScriptedSurface {
+    type Frame = u32;
+
+    fn acquire(&mut self) -> Result<u32, SurfaceError> {
+        self.results.pop_front().unwrap_or(Ok(0))
+    }
+
+    fn reconfigure(&mut self) -> anyhow::Result<()> {
+        self.reconfigures += 1;
+        if self.fail_reconfigure {
+            anyhow::bail!("surface gone");
+        }
+        Ok(())
+    }
+
+    fn recreate_resources(&mut self) -> anyhow::Result<()> {
+        self.recreated += 1;
+        Ok(())
+    }
+}
+
+/// Verifies a lost surface is reconfigured, its resources recreated, and
+/// the frame skipped.
+#[test]
+fn lost_surface_recovers_and_skips_frame() {
+    let mut surface = ScriptedSurface::new([Err(SurfaceError::Lost), Ok(7)]);
+    let mut recovery = SurfaceRecovery::new();
+    let mut events = Events::default();
+
+    assert!(matches!(
+        recovery.acquire(&mut surface, &mut events),
+        FrameOutcome::Skip
+    ));
+    assert_eq!((surface.reconfigures, surface.recreated), (1, 1));
+    assert!(matches!(
+        recovery.acquire(&mut surface, &mut events),
+        FrameOutcome::Render(7)
+    ));
+    assert!(events.is_empty());
+}
+
+/// Verifies an outdated surface is only reconfigured.
+#[test]
+fn outdated_surface_reconfigures() {
+    let mut surface = ScriptedSurface::new([Err(SurfaceError::Outdated)]);
+    let mut recovery = SurfaceRecovery::new();
+    let mut events = Events::default();
+
+    assert!(matches!(
+        recovery.acquire(&mut surface, &mut events),
+        FrameOutcome::Skip
+    ));
+    assert_eq!((surface.reconfigures, surface.recreated), (1, 0));
+}
+
+/// Verifies timeouts skip the frame without touching the surface.
+#[test]
+fn timeout_skips_frame() {
+    let mut surface = ScriptedSurface::new([Err(SurfaceError::Timeout)]);
+    let mut recovery = SurfaceRecovery::new();
+    let mut events = Events::default();
+
+    assert!(matches!(
+        recovery.acquire(&mut surface, &mut events),
+        FrameOutcome::Skip
+    ));
+    assert_eq!(surface.reconfigures, 0);
+}
+
+/// Verifies repeated losses raise a device-lost event instead of looping.
+#[test]
+fn repeated_losses_raise_device_lost() {
+    let mut surface = ScriptedSurface::new(std::iter::repeat_n(
+        Err(SurfaceError::Lost),
+        MAX_SURFACE_LOSSES as usize,
+    ));
+    let mut recovery = SurfaceRecovery::new();
+    let mut events = Events::default();
+
+    for _ in 1..MAX_SURFACE_LOSSES {
+        assert!(matches!(
+            recovery.acquire(&mut surface, &mut events),
+            FrameOutcome::Skip
+        ));
+    }
+    assert!(matches!(
+        recovery.acquire(&mut surface, &mut events),
+        FrameOutcome::DeviceLost
+    ));
+    assert!(recovery.is_device_lost());
+    assert_eq!(events.len(), 1);
+
+    // Frames are skipped without acquiring until the device is recreated
+    assert!(matches!(
+        recovery.acquire(&mut surface, &mut events),
+        FrameOutcome::DeviceLost
+    ));
+    recovery.device_recreated();
+    assert!(matches!(
+        recovery.acquire(&mut surface, &mut events),
+        FrameOutcome::Render(0)
+    ));
+}
+
+/// Verifies a failed reconfigure raises a device-lost event.
+#[test]
+fn failed_reconfigure_raises_device_lost() {
+    let mut surface = ScriptedSurface::new([Err(SurfaceError::Lost)]);
+    surface.fail_reconfigure = true;
+    let mut recovery = SurfaceRecovery::new();
+    let mut events: Events<GpuDeviceLost> = Events::default();
+
+    assert!(matches!(
+        recovery.acquire(&mut surface, &mut events),
+        FrameOutcome::DeviceLost
+    ));
+    assert_eq!(events.iter().next().unwrap().reason, "surface gone");
+}
+
+/// Verifies running out of memory is treated as a lost device.
+#[test]
+fn out_of_memory_raises_device_lost() {
+    let mut surface = ScriptedSurface::new([Err(SurfaceError::OutOfMemory)]);
+    let mut recovery = SurfaceRecovery::new();
+    let mut events = Events::default();
+
+    assert!(matches!(
+        recovery.acquire(&mut surface, &mut events),
+        FrameOutcome::DeviceLost
+    ));
+    assert_eq!(events.len(), 1);
+}
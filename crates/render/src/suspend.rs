@@ -0,0 +1,54 @@
+//! Rendering suspension state.
+//!
+//! Provides the [`RenderingPaused`] flag shared between the platform layer,
+//! which knows when the window is hidden, and the renderer, which skips
+//! frame submission while paused.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Shared flag indicating that frame rendering is suspended.
+///
+/// Set while the window is minimized or fully occluded so the renderer can
+/// skip acquiring and presenting frames. Resuming is a single atomic store,
+/// so rendering restarts on the very next frame.
+///
+/// # Thread Safety
+///
+/// `RenderingPaused` is [`Clone`] and [`Send`] + [`Sync`]; clones share the
+/// same flag.
+///
+/// # Example
+///
+/// ```
+/// use render::RenderingPaused;
+///
+/// let paused = RenderingPaused::default();
+/// assert!(paused.set(true));
+/// assert!(paused.is_paused());
+/// assert!(!paused.set(true));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RenderingPaused {
+    paused: Arc<AtomicBool>,
+}
+
+impl RenderingPaused {
+    /// Returns `true` if rendering is currently suspended.
+    #[must_use]
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Acquire)
+    }
+
+    /// Suspends or resumes rendering.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the state changed.
+    #[allow(clippy::must_use_candidate)]
+    #[inline]
+    pub fn set(&self, paused: bool) -> bool {
+        self.paused.swap(paused, Ordering::AcqRel) != paused
+    }
+}
@@ -0,0 +1,190 @@
+//! Render targets: the window or off-screen textures.
+//!
+//! A [`Camera`](crate::Camera) draws into its [`RenderTarget`]. Off-screen
+//! targets are allocated from [`RenderTextures`] and identified by a
+//! [`TextureHandle`], which materials and UI images can sample like any other
+//! texture. This is how mirrors, security monitors, portals, and minimaps
+//! are built.
+
+use crate::RenderLimits;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Prefix identifying render textures in material texture paths.
+const TEXTURE_PATH_PREFIX: &str = "render-target:";
+
+/// Handle to an off-screen render texture.
+///
+/// In a [`Material`](crate::Material) a render texture is referenced by its
+/// path form, `render-target:<id>`, so materials keep a single texture
+/// parameter type for assets and render textures alike.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TextureHandle(u32);
+
+impl TextureHandle {
+    /// Parses the path form of a handle, returning `None` for other paths.
+    #[must_use]
+    pub fn from_path(path: &str) -> Option<Self> {
+        path.strip_prefix(TEXTURE_PATH_PREFIX)?
+            .parse()
+            .ok()
+            .map(Self)
+    }
+
+    /// Returns the raw id.
+    #[must_use]
+    pub fn id(self) -> u32 {
+        self.0
+    }
+}
+
+impl fmt::Display for TextureHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{TEXTURE_PATH_PREFIX}{}", self.0)
+    }
+}
+
+/// Where a camera renders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderTarget {
+    /// The window's surface.
+    #[default]
+    Window,
+    /// An off-screen texture from [`RenderTextures`].
+    Texture(TextureHandle),
+}
+
+/// Size and format of a render texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderTextureDesc {
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+    /// Whether the texture has a depth attachment.
+    pub depth: bool,
+}
+
+impl RenderTextureDesc {
+    /// Creates a description of a `width` x `height` texture with depth.
+    #[must_use]
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            depth: true,
+        }
+    }
+}
+
+/// Registry of off-screen render textures.
+///
+/// The renderer creates a GPU texture for each entry, usable both as a
+/// color attachment and as a sampled binding.
+///
+/// # Example
+///
+/// ```
+/// use render::target::{RenderTarget, RenderTextureDesc, RenderTextures};
+/// use render::{Camera, Material, RenderLimits};
+///
+/// let mut textures = RenderTextures::new(RenderLimits::default());
+/// let monitor = textures.create(RenderTextureDesc::new(512, 512))?;
+///
+/// let security_camera = Camera {
+///     target: RenderTarget::Texture(monitor),
+///     ..Camera::default()
+/// };
+/// let screen = Material::new("shaders/unlit.wgsl").with_render_target("albedo", monitor);
+/// assert_eq!(screen.render_target("albedo"), Some(monitor));
+/// # anyhow::Ok(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RenderTextures {
+    limits: RenderLimits,
+    textures: BTreeMap<TextureHandle, RenderTextureDesc>,
+    next_id: u32,
+}
+
+impl RenderTextures {
+    /// Creates an empty registry validating sizes against `limits`.
+    #[must_use]
+    pub fn new(limits: RenderLimits) -> Self {
+        Self {
+            limits,
+            textures: BTreeMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Allocates a render texture.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either dimension is zero or exceeds the device's
+    /// maximum texture size.
+    pub fn create(&mut self, desc: RenderTextureDesc) -> anyhow::Result<TextureHandle> {
+        self.validate(desc)?;
+        let handle = TextureHandle(self.next_id);
+        self.next_id += 1;
+        self.textures.insert(handle, desc);
+        Ok(handle)
+    }
+
+    /// Changes the size of an existing render texture, e.g. after the
+    /// window it mirrors was resized.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `handle` is unknown or the size is invalid.
+    pub fn resize(&mut self, handle: TextureHandle, width: u32, height: u32) -> anyhow::Result<()> {
+        let desc = self
+            .textures
+            .get(&handle)
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("unknown render texture {handle}"))?;
+        let desc = RenderTextureDesc {
+            width,
+            height,
+            ..desc
+        };
+        self.validate(desc)?;
+        self.textures.insert(handle, desc);
+        Ok(())
+    }
+
+    /// Frees a render texture, returning its description if it existed.
+    pub fn remove(&mut self, handle: TextureHandle) -> Option<RenderTextureDesc> {
+        self.textures.remove(&handle)
+    }
+
+    /// Returns the description of a render texture.
+    #[must_use]
+    pub fn get(&self, handle: TextureHandle) -> Option<RenderTextureDesc> {
+        self.textures.get(&handle).copied()
+    }
+
+    /// Returns the number of render textures.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.textures.len()
+    }
+
+    /// Returns `true` if no render textures exist.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.textures.is_empty()
+    }
+
+    fn validate(&self, desc: RenderTextureDesc) -> anyhow::Result<()> {
+        let max = self.limits.max_texture_dimension_2d;
+        if desc.width == 0 || desc.height == 0 || desc.width > max || desc.height > max {
+            anyhow::bail!(
+                "render texture size {}x{} is outside 1..={max}",
+                desc.width,
+                desc.height
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+//! Unit tests for render targets.
+
+use crate::camera::{render_order, Camera};
+use crate::target::{RenderTarget, RenderTextureDesc, RenderTextures, TextureHandle};
+use crate::{Material, MaterialOverride, MaterialParam, RenderLimits};
+
+/// Verifies render textures are allocated, resized, and freed.
+#[test]
+fn render_texture_lifecycle() {
+    let mut textures = RenderTextures::new(RenderLimits::default());
+    let mirror = textures.create(RenderTextureDesc::new(256, 128)).unwrap();
+    let minimap = textures.create(RenderTextureDesc::new(64, 64)).unwrap();
+    assert_ne!(mirror, minimap);
+    assert_eq!(textures.len(), 2);
+
+    textures.resize(mirror, 512, 256).unwrap();
+    assert_eq!(textures.get(mirror), Some(RenderTextureDesc::new(512, 256)));
+
+    assert!(textures.remove(mirror).is_some());
+    assert!(textures.resize(mirror, 1, 1).is_err());
+    assert_eq!(textures.len(), 1);
+}
+
+/// Verifies sizes outside the device limits are rejected.
+#[test]
+fn rejects_invalid_sizes() {
+    let limits = RenderLimits::default();
+    let mut textures = RenderTextures::new(limits);
+
+    assert!(textures.create(RenderTextureDesc::new(0, 64)).is_err());
+    let too_wide = limits.max_texture_dimension_2d + 1;
+    assert!(textures
+        .create(RenderTextureDesc::new(too_wide, 64))
+        .is_err());
+    assert!(textures.is_empty());
+}
+
+/// Verifies materials reference render textures through their path form.
+#[test]
+fn materials_sample_render_textures() {
+    let mut textures = RenderTextures::new(RenderLimits::default());
+    let portal = textures.create(RenderTextureDesc::new(256, 256)).unwrap();
+    let other = textures.create(RenderTextureDesc::new(256, 256)).unwrap();
+
+    let material = Material::new("portal.wgsl")
+        .with_render_target("view", portal)
+        .with_texture("frame", "textures/frame.png");
+    assert_eq!(material.render_target("view"), Some(portal));
+    assert_eq!(material.render_target("frame"), None);
+    assert_eq!(TextureHandle::from_path(&portal.to_string()), Some(portal));
+    assert_eq!(TextureHandle::from_path("render-target:x"), None);
+
+    // A per-entity override can point the same material at another texture
+    let overrides = MaterialOverride::default().with_param(
+        "view",
+        MaterialParam::Texture(Some(other.to_string().into())),
+    );
+    let path = material.texture("view", Some(&overrides)).unwrap();
+    assert_eq!(TextureHandle::from_path(&path), Some(other));
+}
+
+/// Verifies texture cameras render before window cameras.
+#[test]
+fn texture_targets_render_first() {
+    let mut textures = RenderTextures::new(RenderLimits::default());
+    let minimap = textures.create(RenderTextureDesc::new(128, 128)).unwrap();
+    let cameras = [
+        Camera::default(),
+        Camera {
+            order: 5,
+            target: RenderTarget::Texture(minimap),
+            ..Camera::default()
+        },
+        Camera {
+            order: -1,
+            ..Camera::default()
+        },
+        Camera {
+            active: false,
+            target: RenderTarget::Texture(minimap),
+            ..Camera::default()
+        },
+    ];
+
+    assert_eq!(render_order(&cameras), [1, 2, 0]);
+}
@@ -0,0 +1,277 @@
+//! Heightmap samples and their import formats.
+
+/// A rectangle of heightmap or splat map samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SampleRect {
+    /// First sample along `x`.
+    pub x: u32,
+    /// First sample along `z`.
+    pub z: u32,
+    /// Number of samples along `x`.
+    pub width: u32,
+    /// Number of samples along `z`.
+    pub depth: u32,
+}
+
+impl SampleRect {
+    /// Returns the smallest rectangle covering both.
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        let x = self.x.min(other.x);
+        let z = self.z.min(other.z);
+        Self {
+            x,
+            z,
+            width: (self.x + self.width).max(other.x + other.width) - x,
+            depth: (self.z + self.depth).max(other.z + other.depth) - z,
+        }
+    }
+
+    /// Returns the rectangle grown by `amount` samples on every side,
+    /// clipped to a map of `width` x `depth` samples.
+    #[must_use]
+    pub fn grow(self, amount: u32, width: u32, depth: u32) -> Self {
+        let x = self.x.saturating_sub(amount);
+        let z = self.z.saturating_sub(amount);
+        Self {
+            x,
+            z,
+            width: (self.x + self.width + amount).min(width) - x,
+            depth: (self.z + self.depth + amount).min(depth) - z,
+        }
+    }
+}
+
+/// A grid of terrain heights.
+///
+/// Samples are addressed by `(x, z)`, with `x` along the width and `z`
+/// along the depth; heights are in world units.
+///
+/// # Example
+///
+/// ```
+/// use render::terrain::Heightmap;
+///
+/// // A 2 x 2 RAW file of little-endian 16-bit samples.
+/// let raw = [0, 0, 255, 255, 0, 0, 255, 255];
+/// let heightmap = Heightmap::from_r16(&raw, 2, 2, 100.0)?;
+/// assert_eq!(heightmap.get(1, 0), 100.0);
+/// assert_eq!(heightmap.sample(0.5, 0.5), 50.0);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heightmap {
+    width: u32,
+    depth: u32,
+    heights: Vec<f32>,
+}
+
+impl Heightmap {
+    /// Creates a flat heightmap of `width` x `depth` samples at height zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either side has fewer than two samples.
+    pub fn new(width: u32, depth: u32) -> anyhow::Result<Self> {
+        Self::from_heights(width, depth, vec![0.0; width as usize * depth as usize])
+    }
+
+    /// Creates a heightmap from `heights` in row-major order, `x` fastest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either side has fewer than two samples, or the
+    /// number of heights does not match.
+    pub fn from_heights(width: u32, depth: u32, heights: Vec<f32>) -> anyhow::Result<Self> {
+        if width < 2 || depth < 2 {
+            anyhow::bail!("heightmap must be at least 2 x 2 samples, got {width} x {depth}");
+        }
+        if heights.len() != width as usize * depth as usize {
+            anyhow::bail!(
+                "heightmap of {width} x {depth} samples needs {} heights, got {}",
+                width as usize * depth as usize,
+                heights.len()
+            );
+        }
+        Ok(Self {
+            width,
+            depth,
+            heights,
+        })
+    }
+
+    /// Imports a RAW heightmap of little-endian unsigned 16-bit samples, as
+    /// exported by most terrain tools, mapping the full range to
+    /// `0..=height_scale`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not `width * depth` samples.
+    pub fn from_r16(
+        bytes: &[u8],
+        width: u32,
+        depth: u32,
+        height_scale: f32,
+    ) -> anyhow::Result<Self> {
+        if bytes.len() != width as usize * depth as usize * 2 {
+            anyhow::bail!(
+                "invalid RAW heightmap: {} bytes is not {width} x {depth} 16-bit samples",
+                bytes.len()
+            );
+        }
+        let heights = bytes
+            .chunks_exact(2)
+            .map(|pair| f32::from(u16::from_le_bytes([pair[0], pair[1]])) / 65535.0 * height_scale)
+            .collect();
+        Self::from_heights(width, depth, heights)
+    }
+
+    /// Imports a binary PGM (`P5`) image with 8- or 16-bit samples, mapping
+    /// its maximum value to `height_scale`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not a binary PGM image.
+    pub fn from_pgm(bytes: &[u8], height_scale: f32) -> anyhow::Result<Self> {
+        let invalid = |reason: &str| anyhow::anyhow!("invalid PGM heightmap: {reason}");
+        let mut header = Vec::new();
+        let mut pos = 0;
+        while header.len() < 4 {
+            while pos < bytes.len() && (bytes[pos].is_ascii_whitespace() || bytes[pos] == b'#') {
+                if bytes[pos] == b'#' {
+                    while pos < bytes.len() && bytes[pos] != b'\n' {
+                        pos += 1;
+                    }
+                } else {
+                    pos += 1;
+                }
+            }
+            let start = pos;
+            while pos < bytes.len() && !bytes[pos].is_ascii_whitespace() {
+                pos += 1;
+            }
+            if start == pos {
+                return Err(invalid("truncated header"));
+            }
+            header
+                .push(std::str::from_utf8(&bytes[start..pos]).map_err(|_| invalid("bad header"))?);
+        }
+        if header[0] != "P5" {
+            return Err(invalid("not a binary (P5) image"));
+        }
+        let number = |text: &str| {
+            text.parse::<u32>()
+                .map_err(|_| invalid(&format!("bad number `{text}`")))
+        };
+        let (width, depth, max) = (number(header[1])?, number(header[2])?, number(header[3])?);
+        if max == 0 || max > 65535 {
+            return Err(invalid("maximum value must be 1 to 65535"));
+        }
+        // A single whitespace byte separates the header from the samples.
+        let data = bytes.get(pos + 1..).unwrap_or_default();
+        let samples = width as usize * depth as usize;
+        #[allow(clippy::cast_precision_loss)]
+        let scale = height_scale / max as f32;
+        let heights: Vec<f32> = if max < 256 {
+            if data.len() < samples {
+                return Err(invalid("truncated samples"));
+            }
+            data[..samples]
+                .iter()
+                .map(|&v| f32::from(v) * scale)
+                .collect()
+        } else {
+            if data.len() < samples * 2 {
+                return Err(invalid("truncated samples"));
+            }
+            data[..samples * 2]
+                .chunks_exact(2)
+                .map(|pair| f32::from(u16::from_be_bytes([pair[0], pair[1]])) * scale)
+                .collect()
+        };
+        Self::from_heights(width, depth, heights)
+    }
+
+    /// Returns the number of samples along `x`.
+    #[must_use]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the number of samples along `z`.
+    #[must_use]
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Returns all heights in row-major order, `x` fastest.
+    #[must_use]
+    pub fn heights(&self) -> &[f32] {
+        &self.heights
+    }
+
+    /// Returns the height at sample `(x, z)`, clamped to the edges.
+    #[must_use]
+    pub fn get(&self, x: u32, z: u32) -> f32 {
+        let x = x.min(self.width - 1) as usize;
+        let z = z.min(self.depth - 1) as usize;
+        self.heights[z * self.width as usize + x]
+    }
+
+    /// Sets the height at sample `(x, z)`; samples outside are ignored.
+    pub fn set(&mut self, x: u32, z: u32, height: f32) {
+        if x < self.width && z < self.depth {
+            self.heights[z as usize * self.width as usize + x as usize] = height;
+        }
+    }
+
+    /// Returns the height at fractional sample coordinates, interpolated
+    /// bilinearly and clamped to the edges.
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn sample(&self, x: f32, z: f32) -> f32 {
+        let x = x.clamp(0.0, (self.width - 1) as f32);
+        let z = z.clamp(0.0, (self.depth - 1) as f32);
+        let (x0, z0) = (x.floor() as u32, z.floor() as u32);
+        let (tx, tz) = (x - x0 as f32, z - z0 as f32);
+        let top = self.get(x0, z0) + (self.get(x0 + 1, z0) - self.get(x0, z0)) * tx;
+        let bottom = self.get(x0, z0 + 1) + (self.get(x0 + 1, z0 + 1) - self.get(x0, z0 + 1)) * tx;
+        top + (bottom - top) * tz
+    }
+
+    /// Returns the unit surface normal at sample `(x, z)` for samples
+    /// `spacing` world units apart.
+    #[must_use]
+    pub fn normal(&self, x: u32, z: u32, spacing: f32) -> [f32; 3] {
+        let left = self.get(x.saturating_sub(1), z);
+        let right = self.get(x + 1, z);
+        let back = self.get(x, z.saturating_sub(1));
+        let front = self.get(x, z + 1);
+        let normal = math::Vec3::new(left - right, 2.0 * spacing, back - front).normalize_or_zero();
+        normal.into()
+    }
+
+    /// Returns the heights in `rect` in row-major order, for uploading only
+    /// the edited part of a height texture.
+    #[must_use]
+    pub fn region(&self, rect: SampleRect) -> Vec<f32> {
+        (rect.z..rect.z + rect.depth)
+            .flat_map(|z| (rect.x..rect.x + rect.width).map(move |x| (x, z)))
+            .map(|(x, z)| self.get(x, z))
+            .collect()
+    }
+
+    /// Returns the lowest and highest heights.
+    #[must_use]
+    pub fn range(&self) -> (f32, f32) {
+        self.heights
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &h| {
+                (min.min(h), max.max(h))
+            })
+    }
+}
@@ -0,0 +1,92 @@
+//! Unit tests for heightmaps.
+
+use super::heightmap::{Heightmap, SampleRect};
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+}
+
+/// Verifies RAW files map the 16-bit range to the height scale.
+#[test]
+fn imports_raw_16_bit() {
+    let raw: Vec<u8> = [0u16, 32768, 65535, 0, 0, 0]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+    let heightmap = Heightmap::from_r16(&raw, 3, 2, 200.0).unwrap();
+    assert_eq!((heightmap.width(), heightmap.depth()), (3, 2));
+    assert_close(heightmap.get(1, 0), 200.0 * 32768.0 / 65535.0);
+    assert_close(heightmap.get(2, 0), 200.0);
+    assert!(Heightmap::from_r16(&raw, 2, 2, 1.0).is_err());
+}
+
+/// Verifies 8- and 16-bit binary PGM images are read, skipping comments.
+#[test]
+fn imports_pgm() {
+    let mut pgm = b"P5\n# made by hand\n2 2\n255\n".to_vec();
+    pgm.extend([0, 51, 102, 255]);
+    let heightmap = Heightmap::from_pgm(&pgm, 10.0).unwrap();
+    assert_close(heightmap.get(1, 0), 2.0);
+    assert_close(heightmap.get(1, 1), 10.0);
+
+    let mut pgm = b"P5 2 2 1000 ".to_vec();
+    for value in [0u16, 250, 500, 1000] {
+        pgm.extend(value.to_be_bytes());
+    }
+    let heightmap = Heightmap::from_pgm(&pgm, 4.0).unwrap();
+    assert_close(heightmap.get(0, 1), 2.0);
+
+    assert!(Heightmap::from_pgm(b"P2 2 2 255 0 0 0 0", 1.0).is_err());
+    assert!(Heightmap::from_pgm(b"P5 2 2 255\n\x00", 1.0).is_err());
+}
+
+/// Verifies sampling interpolates bilinearly and clamps to the edges, and
+/// normals tilt away from higher ground.
+#[test]
+fn samples_and_normals() {
+    let heightmap = Heightmap::from_heights(2, 2, vec![0.0, 4.0, 0.0, 4.0]).unwrap();
+    assert_close(heightmap.sample(0.25, 0.5), 1.0);
+    assert_close(heightmap.sample(-3.0, 9.0), 0.0);
+    let normal = heightmap.normal(0, 0, 1.0);
+    assert!(normal[0] < 0.0 && normal[1] > 0.0);
+    assert_close(normal[2], 0.0);
+    assert!(Heightmap::from_heights(1, 4, vec![0.0; 4]).is_err());
+}
+
+/// Verifies regions are read row by row and rectangles merge and grow
+/// within the map.
+#[test]
+fn regions_and_rectangles() {
+    let heightmap = Heightmap::from_heights(3, 3, (0..9u8).map(f32::from).collect()).unwrap();
+    let rect = SampleRect {
+        x: 1,
+        z: 1,
+        width: 2,
+        depth: 2,
+    };
+    assert_eq!(heightmap.region(rect), [4.0, 5.0, 7.0, 8.0]);
+    assert_eq!(
+        rect.grow(1, 3, 3),
+        SampleRect {
+            x: 0,
+            z: 0,
+            width: 3,
+            depth: 3
+        }
+    );
+    let other = SampleRect {
+        x: 0,
+        z: 2,
+        width: 1,
+        depth: 1,
+    };
+    assert_eq!(
+        rect.union(other),
+        SampleRect {
+            x: 0,
+            z: 1,
+            width: 3,
+            depth: 2
+        }
+    );
+}
@@ -0,0 +1,395 @@
+//! The terrain component.
+
+use super::heightmap::{Heightmap, SampleRect};
+use super::quadtree::{node_grid, node_mesh, rect_bounds, root_level, TerrainNode};
+use super::splat::{brush_falloff, brush_rect, SplatMap, TerrainLayer};
+use crate::material::Material;
+use crate::mesh::{Aabb, Mesh};
+use crate::target::TextureHandle;
+use math::Vec3;
+use std::collections::BTreeSet;
+
+/// Triangles for a physics collider, e.g. rapier's
+/// `ColliderBuilder::trimesh`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CollisionMesh {
+    /// Vertex positions.
+    pub vertices: Vec<[f32; 3]>,
+    /// Triangles as indices into [`vertices`](Self::vertices).
+    pub indices: Vec<[u32; 3]>,
+}
+
+/// What changed since the last [`Terrain::take_changes`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TerrainChanges {
+    /// Nodes whose meshes must be rebuilt, at every level.
+    pub nodes: Vec<TerrainNode>,
+    /// Level 0 chunks whose colliders must be rebuilt.
+    pub colliders: Vec<TerrainNode>,
+    /// Heightmap samples to re-upload to the height texture.
+    pub heights: Option<SampleRect>,
+    /// Splat map samples to re-upload to the splat textures.
+    pub splat: Option<SampleRect>,
+}
+
+/// Component for a heightmap terrain.
+///
+/// The terrain spans the heightmap in terrain-local space, with sample
+/// `(x, z)` at `(x * spacing, height, z * spacing)`; the entity's transform
+/// places it in the world. It is drawn as a quadtree of nodes picked by
+/// distance to the camera ([`select`](Self::select)), each a mesh of
+/// [`TERRAIN_CHUNK_QUADS`](super::TERRAIN_CHUNK_QUADS) quads per side
+/// ([`node_mesh`](Self::node_mesh)), textured by blending up to
+/// [`MAX_TERRAIN_LAYERS`](super::MAX_TERRAIN_LAYERS) layers with a
+/// [`SplatMap`].
+///
+/// Edits mark the affected node meshes, chunk colliders, and texture
+/// regions dirty; the renderer and physics rebuild and re-upload only
+/// those, once per frame, from [`take_changes`](Self::take_changes).
+///
+/// # Example
+///
+/// ```
+/// use math::Vec3;
+/// use render::terrain::{Heightmap, Terrain, TerrainLayer};
+///
+/// let heightmap = Heightmap::new(129, 129)?;
+/// let mut terrain = Terrain::new(heightmap, 2.0, vec![TerrainLayer::new("grass.png")])?;
+/// terrain.take_changes();
+///
+/// terrain.raise(100.0, 100.0, 8.0, 5.0);
+/// assert_eq!(terrain.height_at(100.0, 100.0), 5.0);
+/// let changes = terrain.take_changes();
+/// assert!(!changes.nodes.is_empty());
+///
+/// // Nodes near the camera are finer than those far away.
+/// let nodes = terrain.select(Vec3::new(0.0, 10.0, 0.0));
+/// assert!(nodes.iter().any(|node| node.level == 0));
+/// assert!(nodes.iter().any(|node| node.level > 0));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Terrain {
+    heightmap: Heightmap,
+    splat: SplatMap,
+    layers: Vec<TerrainLayer>,
+    spacing: f32,
+    lod_distance: f32,
+    /// Lowest and highest heights, widened by edits.
+    heights: (f32, f32),
+    dirty_nodes: BTreeSet<TerrainNode>,
+    dirty_colliders: BTreeSet<TerrainNode>,
+    dirty_heights: Option<SampleRect>,
+    dirty_splat: Option<SampleRect>,
+}
+
+impl Terrain {
+    /// Creates a terrain from `heightmap` with samples `spacing` world
+    /// units apart, covered by the first of `layers`. Everything starts
+    /// dirty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `spacing` is not positive, or there are no
+    /// layers or more than [`MAX_TERRAIN_LAYERS`](super::MAX_TERRAIN_LAYERS).
+    pub fn new(
+        heightmap: Heightmap,
+        spacing: f32,
+        layers: Vec<TerrainLayer>,
+    ) -> anyhow::Result<Self> {
+        if !(spacing.is_finite() && spacing > 0.0) {
+            anyhow::bail!("terrain sample spacing must be positive, got {spacing}");
+        }
+        let splat = SplatMap::new(heightmap.width(), heightmap.depth(), layers.len())?;
+        let all = SampleRect {
+            x: 0,
+            z: 0,
+            width: heightmap.width(),
+            depth: heightmap.depth(),
+        };
+        let mut terrain = Self {
+            heights: heightmap.range(),
+            heightmap,
+            splat,
+            layers,
+            spacing,
+            lod_distance: 1.0,
+            dirty_nodes: BTreeSet::new(),
+            dirty_colliders: BTreeSet::new(),
+            dirty_heights: None,
+            dirty_splat: Some(all),
+        };
+        terrain.mark_heights(all);
+        Ok(terrain)
+    }
+
+    /// Sets how close, in multiples of a node's size, the camera must be for
+    /// the node to split into finer ones. Defaults to 1.
+    #[must_use]
+    pub fn with_lod_distance(mut self, lod_distance: f32) -> Self {
+        self.lod_distance = lod_distance;
+        self
+    }
+
+    /// Returns the heightmap.
+    #[must_use]
+    pub fn heightmap(&self) -> &Heightmap {
+        &self.heightmap
+    }
+
+    /// Returns the splat map.
+    #[must_use]
+    pub fn splat(&self) -> &SplatMap {
+        &self.splat
+    }
+
+    /// Returns the material layers.
+    #[must_use]
+    pub fn layers(&self) -> &[TerrainLayer] {
+        &self.layers
+    }
+
+    /// Returns the world units between samples.
+    #[must_use]
+    pub fn spacing(&self) -> f32 {
+        self.spacing
+    }
+
+    /// Returns the terrain-local bounds.
+    #[must_use]
+    pub fn bounds(&self) -> Aabb {
+        rect_bounds(self.all(), self.spacing, self.heights)
+    }
+
+    /// Returns the height at terrain-local `(x, z)`, interpolated between
+    /// samples and clamped to the edges.
+    #[must_use]
+    pub fn height_at(&self, x: f32, z: f32) -> f32 {
+        self.heightmap.sample(x / self.spacing, z / self.spacing)
+    }
+
+    /// Returns the root of the quadtree, covering the whole terrain.
+    #[must_use]
+    pub fn root(&self) -> TerrainNode {
+        TerrainNode {
+            level: root_level(self.heightmap.width(), self.heightmap.depth()),
+            x: 0,
+            z: 0,
+        }
+    }
+
+    /// Returns the nodes to draw for a camera at terrain-local `camera`,
+    /// covering the terrain once, in order.
+    ///
+    /// Starting at the root, a node splits into its children while the
+    /// camera is closer to it than its size times the LOD distance.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn select(&self, camera: Vec3) -> Vec<TerrainNode> {
+        let mut selected = Vec::new();
+        let mut pending = vec![self.root()];
+        while let Some(node) = pending.pop() {
+            let Some(rect) = node.samples(self.heightmap.width(), self.heightmap.depth()) else {
+                continue;
+            };
+            let bounds = rect_bounds(rect, self.spacing, self.heights);
+            let distance = camera.clamp(bounds.min, bounds.max).distance(camera);
+            let size = node.span() as f32 * self.spacing;
+            match node.children() {
+                Some(children) if distance < size * self.lod_distance => pending.extend(children),
+                _ => selected.push(node),
+            }
+        }
+        selected.sort_unstable();
+        selected
+    }
+
+    /// Returns the terrain-local bounds of `node`, or `None` if it lies
+    /// outside the terrain.
+    #[must_use]
+    pub fn node_bounds(&self, node: TerrainNode) -> Option<Aabb> {
+        let rect = node.samples(self.heightmap.width(), self.heightmap.depth())?;
+        Some(rect_bounds(rect, self.spacing, self.heights))
+    }
+
+    /// Builds the mesh of `node`, with skirts hiding cracks between
+    /// neighbours of different levels. Texture coordinates span the whole
+    /// terrain, for sampling the splat textures.
+    #[must_use]
+    pub fn node_mesh(&self, node: TerrainNode) -> Mesh {
+        node_mesh(&self.heightmap, self.spacing, node)
+    }
+
+    /// Builds a collision mesh for `node`; level 0 nodes match the
+    /// rendered surface exactly, coarser ones skip samples.
+    #[must_use]
+    pub fn collision_mesh(&self, node: TerrainNode) -> CollisionMesh {
+        let (mesh, _, _) = node_grid(&self.heightmap, self.spacing, node);
+        CollisionMesh {
+            vertices: mesh.vertices.iter().map(|vertex| vertex.position).collect(),
+            indices: mesh
+                .indices
+                .chunks_exact(3)
+                .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+                .collect(),
+        }
+    }
+
+    /// Returns the level 0 chunks, for building one collider each.
+    #[must_use]
+    pub fn chunks(&self) -> Vec<TerrainNode> {
+        self.nodes_in(0, self.all())
+    }
+
+    /// Builds a material from `shader` for drawing the terrain: a
+    /// `layer_count` float, `layer{i}_albedo`, `layer{i}_normal`, and
+    /// `layer{i}_uv_scale` parameters per layer, and the splat textures,
+    /// uploaded from [`SplatMap::texture`], bound as `splat{i}`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn material(&self, shader: &str, splat_textures: &[TextureHandle]) -> Material {
+        let mut material =
+            Material::new(shader).with_float("layer_count", self.layers.len() as f32);
+        for (index, layer) in self.layers.iter().enumerate() {
+            material = material
+                .with_texture(&format!("layer{index}_albedo"), layer.albedo.as_str())
+                .with_float(&format!("layer{index}_uv_scale"), layer.uv_scale);
+            if let Some(normal) = &layer.normal {
+                material = material.with_texture(&format!("layer{index}_normal"), normal.as_str());
+            }
+        }
+        for (index, handle) in splat_textures.iter().enumerate() {
+            material = material.with_render_target(&format!("splat{index}"), *handle);
+        }
+        material
+    }
+
+    /// Sets the height of sample `(x, z)`.
+    pub fn set_height(&mut self, x: u32, z: u32, height: f32) {
+        if x < self.heightmap.width() && z < self.heightmap.depth() {
+            self.heightmap.set(x, z, height);
+            self.widen_heights(height);
+            self.mark_heights(SampleRect {
+                x,
+                z,
+                width: 1,
+                depth: 1,
+            });
+        }
+    }
+
+    /// Raises the ground around terrain-local `(x, z)` within `radius` by
+    /// `amount` at the center, falling off smoothly to the edge; negative
+    /// amounts lower it.
+    pub fn raise(&mut self, x: f32, z: f32, radius: f32, amount: f32) {
+        self.brush(x, z, radius, |height, falloff| height + amount * falloff);
+    }
+
+    /// Moves the ground around terrain-local `(x, z)` within `radius`
+    /// towards `height`, all the way at the center with `strength` 1.
+    pub fn flatten(&mut self, x: f32, z: f32, radius: f32, height: f32, strength: f32) {
+        let strength = strength.clamp(0.0, 1.0);
+        self.brush(x, z, radius, |current, falloff| {
+            current + (height - current) * strength * falloff
+        });
+    }
+
+    /// Paints `layer` around terrain-local `(x, z)` within `radius`, see
+    /// [`SplatMap::paint`].
+    pub fn paint(&mut self, x: f32, z: f32, radius: f32, layer: usize, strength: f32) {
+        let spacing = self.spacing;
+        if let Some(rect) =
+            self.splat
+                .paint(x / spacing, z / spacing, radius / spacing, layer, strength)
+        {
+            self.dirty_splat = Some(self.dirty_splat.map_or(rect, |dirty| dirty.union(rect)));
+        }
+    }
+
+    /// Returns and clears what changed since the last call.
+    pub fn take_changes(&mut self) -> TerrainChanges {
+        TerrainChanges {
+            nodes: std::mem::take(&mut self.dirty_nodes).into_iter().collect(),
+            colliders: std::mem::take(&mut self.dirty_colliders)
+                .into_iter()
+                .collect(),
+            heights: self.dirty_heights.take(),
+            splat: self.dirty_splat.take(),
+        }
+    }
+
+    /// Applies `edit` to the heights under a brush, in world units.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn brush(&mut self, x: f32, z: f32, radius: f32, edit: impl Fn(f32, f32) -> f32) {
+        let (x, z, radius) = (x / self.spacing, z / self.spacing, radius / self.spacing);
+        if radius <= 0.0 {
+            return;
+        }
+        let (width, depth) = (self.heightmap.width(), self.heightmap.depth());
+        let Some(rect) = brush_rect(x, z, radius, width, depth) else {
+            return;
+        };
+        for sz in rect.z..rect.z + rect.depth {
+            for sx in rect.x..rect.x + rect.width {
+                let falloff = brush_falloff(sx as f32 - x, sz as f32 - z, radius);
+                if falloff > 0.0 {
+                    let height = edit(self.heightmap.get(sx, sz), falloff);
+                    self.heightmap.set(sx, sz, height);
+                    self.widen_heights(height);
+                }
+            }
+        }
+        self.mark_heights(rect);
+    }
+
+    fn widen_heights(&mut self, height: f32) {
+        self.heights = (self.heights.0.min(height), self.heights.1.max(height));
+    }
+
+    /// Marks the nodes, colliders, and texture region reading `rect` dirty.
+    fn mark_heights(&mut self, rect: SampleRect) {
+        let (width, depth) = (self.heightmap.width(), self.heightmap.depth());
+        // Normals read the neighbouring samples.
+        let rect = rect.grow(1, width, depth);
+        self.dirty_heights = Some(self.dirty_heights.map_or(rect, |dirty| dirty.union(rect)));
+        for level in 0..=self.root().level {
+            let nodes = self.nodes_in(level, rect);
+            if level == 0 {
+                self.dirty_colliders.extend(nodes.iter().copied());
+            }
+            self.dirty_nodes.extend(nodes);
+        }
+    }
+
+    /// Returns the nodes of `level` whose meshes read samples in `rect`.
+    fn nodes_in(&self, level: u32, rect: SampleRect) -> Vec<TerrainNode> {
+        let (width, depth) = (self.heightmap.width(), self.heightmap.depth());
+        let span = TerrainNode { level, x: 0, z: 0 }.span();
+        // A sample on a node boundary belongs to the nodes on both sides.
+        let first = |start: u32| start.saturating_sub(1) / span;
+        let last = |start: u32, len: u32| (start + len - 1) / span;
+        let mut nodes = Vec::new();
+        for z in first(rect.z)..=last(rect.z, rect.depth) {
+            for x in first(rect.x)..=last(rect.x, rect.width) {
+                let node = TerrainNode { level, x, z };
+                if node.samples(width, depth).is_some() {
+                    nodes.push(node);
+                }
+            }
+        }
+        nodes
+    }
+
+    fn all(&self) -> SampleRect {
+        SampleRect {
+            x: 0,
+            z: 0,
+            width: self.heightmap.width(),
+            depth: self.heightmap.depth(),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Unit tests for the terrain component.
+
+use super::heightmap::{Heightmap, SampleRect};
+use super::map::{Terrain, TerrainChanges};
+use super::quadtree::TerrainNode;
+use super::splat::TerrainLayer;
+use crate::target::{RenderTextureDesc, RenderTextures};
+use crate::{MaterialParam, RenderLimits};
+use math::Vec3;
+use rustgine_core::Label;
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+}
+
+fn assert_position(actual: [f32; 3], expected: [f32; 3]) {
+    let close = actual
+        .iter()
+        .zip(expected)
+        .all(|(a, e)| (a - e).abs() < 1e-4);
+    assert!(close, "{actual:?} != {expected:?}");
+}
+
+fn node(level: u32, x: u32, z: u32) -> TerrainNode {
+    TerrainNode { level, x, z }
+}
+
+fn terrain() -> Terrain {
+    let layers = vec![
+        TerrainLayer::new("grass.png"),
+        TerrainLayer {
+            normal: Some("rock_n.png".into()),
+            uv_scale: 4.0,
+            ..TerrainLayer::new("rock.png")
+        },
+    ];
+    Terrain::new(Heightmap::new(129, 129).unwrap(), 1.0, layers).unwrap()
+}
+
+/// Verifies nodes near the camera split into finer ones while distant
+/// ones stay coarse, covering the terrain once.
+#[test]
+fn selects_nodes_by_distance() {
+    let terrain = terrain();
+    assert_eq!(terrain.root(), node(2, 0, 0));
+    assert_eq!(
+        terrain.select(Vec3::new(1000.0, 0.0, 1000.0)),
+        [node(2, 0, 0)]
+    );
+    assert_eq!(
+        terrain.select(Vec3::ZERO),
+        [
+            node(0, 0, 0),
+            node(0, 0, 1),
+            node(0, 1, 0),
+            node(0, 1, 1),
+            node(1, 0, 1),
+            node(1, 1, 0),
+            node(1, 1, 1),
+        ]
+    );
+    let coarse = terrain.with_lod_distance(0.0);
+    assert_eq!(coarse.select(Vec3::ZERO), [node(2, 0, 0)]);
+}
+
+/// Verifies a new terrain is dirty everywhere, and edits then mark only
+/// the nodes, colliders, and regions they touch, including neighbours
+/// whose normals change.
+#[test]
+fn tracks_changes() {
+    let mut terrain = terrain();
+    let changes = terrain.take_changes();
+    assert_eq!(changes.nodes.len(), 1 + 4 + 16);
+    let mut chunks = terrain.chunks();
+    chunks.sort_unstable();
+    assert_eq!(changes.colliders, chunks);
+    assert_eq!(changes.colliders.len(), 16);
+    assert!(changes.heights.is_some() && changes.splat.is_some());
+    assert_eq!(terrain.take_changes(), TerrainChanges::default());
+
+    terrain.set_height(40, 10, 3.0);
+    let changes = terrain.take_changes();
+    assert_eq!(changes.nodes, [node(0, 1, 0), node(1, 0, 0), node(2, 0, 0)]);
+    assert_eq!(changes.colliders, [node(0, 1, 0)]);
+    assert_eq!(
+        changes.heights,
+        Some(SampleRect {
+            x: 39,
+            z: 9,
+            width: 3,
+            depth: 3
+        })
+    );
+    assert_eq!(changes.splat, None);
+
+    terrain.set_height(32, 5, 1.0);
+    let changes = terrain.take_changes();
+    assert_eq!(changes.colliders, [node(0, 0, 0), node(0, 1, 0)]);
+
+    terrain.paint(10.0, 10.0, 2.0, 1, 1.0);
+    let changes = terrain.take_changes();
+    assert!(changes.nodes.is_empty());
+    assert!(changes.splat.is_some());
+    assert!(terrain.splat().weights(10, 10)[1] > 0.99);
+}
+
+/// Verifies brushes raise and flatten the ground, widening the bounds.
+#[test]
+fn edits_heights() {
+    let mut terrain = Terrain::new(
+        Heightmap::new(65, 65).unwrap(),
+        2.0,
+        vec![TerrainLayer::new("grass.png")],
+    )
+    .unwrap();
+    terrain.raise(64.0, 64.0, 8.0, 5.0);
+    assert_close(terrain.height_at(64.0, 64.0), 5.0);
+    assert_close(terrain.height_at(80.0, 64.0), 0.0);
+    assert!(terrain.height_at(66.0, 64.0) > 0.0);
+    assert_close(terrain.bounds().max.y, 5.0);
+
+    terrain.flatten(64.0, 64.0, 8.0, 1.0, 1.0);
+    assert_close(terrain.height_at(64.0, 64.0), 1.0);
+    terrain.raise(64.0, 64.0, 0.0, 5.0);
+    assert_close(terrain.height_at(64.0, 64.0), 1.0);
+}
+
+/// Verifies collision meshes follow the heights without skirts.
+#[test]
+fn builds_collision_meshes() {
+    let mut terrain = terrain();
+    terrain.set_height(33, 1, 2.0);
+    let collider = terrain.collision_mesh(node(0, 1, 0));
+    assert_eq!(collider.vertices.len(), 33 * 33);
+    assert_eq!(collider.indices.len(), 2 * 32 * 32);
+    assert_position(collider.vertices[33 + 1], [33.0, 2.0, 1.0]);
+    assert!(terrain.collision_mesh(node(0, 4, 0)).vertices.is_empty());
+    assert!(terrain.node_mesh(node(0, 1, 0)).vertices.len() > collider.vertices.len());
+}
+
+/// Verifies the material describes every layer and binds the splat
+/// textures.
+#[test]
+fn builds_materials() {
+    let terrain = terrain();
+    let mut textures = RenderTextures::new(RenderLimits::default());
+    let splat = textures.create(RenderTextureDesc::new(129, 129)).unwrap();
+    let material = terrain.material("terrain.wgsl", &[splat]);
+    assert_eq!(
+        material.params.get("layer_count"),
+        Some(&MaterialParam::Float(2.0))
+    );
+    assert_eq!(
+        material.texture("layer1_albedo", None),
+        Some(Label::new("rock.png"))
+    );
+    assert_eq!(
+        material.texture("layer1_normal", None),
+        Some(Label::new("rock_n.png"))
+    );
+    assert_eq!(material.texture("layer0_normal", None), None);
+    assert_eq!(
+        material.params.get("layer1_uv_scale"),
+        Some(&MaterialParam::Float(4.0))
+    );
+    assert_eq!(material.render_target("splat0"), Some(splat));
+}
+
+/// Verifies invalid spacings and layer counts are refused.
+#[test]
+fn refuses_invalid_terrain() {
+    let heightmap = Heightmap::new(33, 33).unwrap();
+    let layers = vec![TerrainLayer::new("grass.png")];
+    assert!(Terrain::new(heightmap.clone(), 0.0, layers).is_err());
+    assert!(Terrain::new(heightmap, 1.0, Vec::new()).is_err());
+}
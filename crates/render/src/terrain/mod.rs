@@ -0,0 +1,35 @@
+//! Heightmap terrain with quadtree level of detail.
+//!
+//! A [`Terrain`] component turns a [`Heightmap`] into meshes of
+//! [`TERRAIN_CHUNK_QUADS`] x [`TERRAIN_CHUNK_QUADS`] quads. Far from the
+//! camera, nodes of a quadtree cover more ground with the same number of
+//! quads by skipping samples, and skirts hide the cracks where levels meet.
+//! Up to [`MAX_TERRAIN_LAYERS`] material layers are blended by a
+//! [`SplatMap`], and each chunk yields a [`CollisionMesh`] for physics.
+//!
+//! Heights and splat weights can be edited at runtime; the terrain tracks
+//! which node meshes, colliders, and texture regions the edits touched, so
+//! only those are rebuilt and re-uploaded ([`Terrain::take_changes`]).
+//!
+//! Heightmaps are imported from:
+//!
+//! - [`Heightmap::from_r16`] - RAW little-endian 16-bit samples
+//! - [`Heightmap::from_pgm`] - Binary PGM images with 8- or 16-bit samples
+
+mod heightmap;
+#[cfg(test)]
+mod heightmap_test;
+mod map;
+#[cfg(test)]
+mod map_test;
+mod quadtree;
+#[cfg(test)]
+mod quadtree_test;
+mod splat;
+#[cfg(test)]
+mod splat_test;
+
+pub use heightmap::{Heightmap, SampleRect};
+pub use map::{CollisionMesh, Terrain, TerrainChanges};
+pub use quadtree::{TerrainNode, TERRAIN_CHUNK_QUADS};
+pub use splat::{SplatMap, TerrainLayer, MAX_TERRAIN_LAYERS};
@@ -0,0 +1,178 @@
+//! Quadtree level of detail for terrain meshes.
+
+use super::heightmap::{Heightmap, SampleRect};
+use crate::mesh::{Mesh, MeshVertex};
+use math::{Aabb, Vec3};
+
+/// Quads along each side of a terrain node's mesh, at every level.
+pub const TERRAIN_CHUNK_QUADS: u32 = 32;
+
+/// A node of a terrain's quadtree.
+///
+/// Level 0 nodes are chunks of [`TERRAIN_CHUNK_QUADS`] heightmap quads;
+/// each level up covers twice the distance on each side with the same
+/// number of quads, skipping samples. Nodes order by level, then position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TerrainNode {
+    /// Level of detail, 0 being the finest.
+    pub level: u32,
+    /// Position along `x`, in nodes of this level.
+    pub x: u32,
+    /// Position along `z`, in nodes of this level.
+    pub z: u32,
+}
+
+impl TerrainNode {
+    /// Returns the number of heightmap samples between mesh vertices.
+    #[must_use]
+    pub fn stride(self) -> u32 {
+        1 << self.level
+    }
+
+    /// Returns the number of heightmap quads along each side.
+    #[must_use]
+    pub fn span(self) -> u32 {
+        TERRAIN_CHUNK_QUADS << self.level
+    }
+
+    /// Returns the four nodes one level finer covering this one, or `None`
+    /// at level 0.
+    #[must_use]
+    pub fn children(self) -> Option<[Self; 4]> {
+        let level = self.level.checked_sub(1)?;
+        let (x, z) = (self.x * 2, self.z * 2);
+        Some([(0, 0), (1, 0), (0, 1), (1, 1)].map(|(dx, dz)| Self {
+            level,
+            x: x + dx,
+            z: z + dz,
+        }))
+    }
+
+    /// Returns the heightmap samples the node's mesh reads, clipped to a
+    /// heightmap of `width` x `depth` samples, or `None` if the node lies
+    /// outside it.
+    #[must_use]
+    pub fn samples(self, width: u32, depth: u32) -> Option<SampleRect> {
+        let (x, z) = (self.x * self.span(), self.z * self.span());
+        if x >= width - 1 || z >= depth - 1 {
+            return None;
+        }
+        Some(SampleRect {
+            x,
+            z,
+            width: (self.span() + 1).min(width - x),
+            depth: (self.span() + 1).min(depth - z),
+        })
+    }
+}
+
+/// Returns the level of the quadtree's root for a heightmap of `width` x
+/// `depth` samples.
+pub(crate) fn root_level(width: u32, depth: u32) -> u32 {
+    let chunks = (width - 1).max(depth - 1).div_ceil(TERRAIN_CHUNK_QUADS);
+    chunks.next_power_of_two().trailing_zeros()
+}
+
+/// Returns the terrain-local bounds of the samples in `rect`, between the
+/// heights `heights`.
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn rect_bounds(rect: SampleRect, spacing: f32, heights: (f32, f32)) -> Aabb {
+    Aabb::new(
+        Vec3::new(rect.x as f32 * spacing, heights.0, rect.z as f32 * spacing),
+        Vec3::new(
+            (rect.x + rect.width - 1) as f32 * spacing,
+            heights.1,
+            (rect.z + rect.depth - 1) as f32 * spacing,
+        ),
+    )
+}
+
+/// Builds the grid of `node` without skirts, in terrain-local space with
+/// `spacing` world units between samples, and returns it with its number of
+/// vertex columns and rows.
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+pub(crate) fn node_grid(
+    heightmap: &Heightmap,
+    spacing: f32,
+    node: TerrainNode,
+) -> (Mesh, u32, u32) {
+    let (width, depth) = (heightmap.width(), heightmap.depth());
+    let Some(rect) = node.samples(width, depth) else {
+        return (Mesh::default(), 0, 0);
+    };
+    // Vertex sample coordinates along each axis, the last on the node's or
+    // heightmap's edge.
+    let axis = |start: u32, samples: u32| -> Vec<u32> {
+        let last = start + samples - 1;
+        let mut coords: Vec<u32> = (start..last).step_by(node.stride() as usize).collect();
+        coords.push(last);
+        coords
+    };
+    let xs = axis(rect.x, rect.width);
+    let zs = axis(rect.z, rect.depth);
+    let (uv_x, uv_z) = ((width - 1) as f32, (depth - 1) as f32);
+
+    let mut mesh = Mesh::default();
+    for &z in &zs {
+        for &x in &xs {
+            mesh.vertices.push(MeshVertex {
+                position: [x as f32 * spacing, heightmap.get(x, z), z as f32 * spacing],
+                normal: heightmap.normal(x, z, spacing),
+                uv: [x as f32 / uv_x, z as f32 / uv_z],
+            });
+        }
+    }
+    let (columns, rows) = (xs.len() as u32, zs.len() as u32);
+    for row in 0..rows - 1 {
+        for column in 0..columns - 1 {
+            let a = row * columns + column;
+            let (b, c, d) = (a + 1, a + columns, a + columns + 1);
+            mesh.indices.extend([a, c, b, b, c, d]);
+        }
+    }
+    (mesh, columns, rows)
+}
+
+/// Builds the mesh of `node`: its grid, with a skirt hanging down from each
+/// edge by the node's vertex spacing to hide cracks between neighbours
+/// drawn at different levels.
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+pub(crate) fn node_mesh(heightmap: &Heightmap, spacing: f32, node: TerrainNode) -> Mesh {
+    let (mut mesh, columns, rows) = node_grid(heightmap, spacing, node);
+    if mesh.vertices.is_empty() {
+        return mesh;
+    }
+    let skirt = node.stride() as f32 * spacing;
+    let last_row = (rows - 1) * columns;
+    let edges: [(Vec<u32>, Vec3); 4] = [
+        ((0..columns).collect(), Vec3::NEG_Z),
+        ((last_row..last_row + columns).collect(), Vec3::Z),
+        ((0..rows).map(|row| row * columns).collect(), Vec3::NEG_X),
+        (
+            (0..rows).map(|row| row * columns + columns - 1).collect(),
+            Vec3::X,
+        ),
+    ];
+    for (top, outward) in edges {
+        let base = mesh.vertices.len() as u32;
+        for &index in &top {
+            let mut dropped = mesh.vertices[index as usize];
+            dropped.position[1] -= skirt;
+            mesh.vertices.push(dropped);
+        }
+        for (i, pair) in top.windows(2).enumerate() {
+            let (p0, p1) = (pair[0], pair[1]);
+            let (q0, q1) = (base + i as u32, base + i as u32 + 1);
+            // Wind both triangles to face away from the node.
+            let a = Vec3::from(mesh.vertices[p0 as usize].position);
+            let b = Vec3::from(mesh.vertices[q0 as usize].position);
+            let c = Vec3::from(mesh.vertices[p1 as usize].position);
+            if (b - a).cross(c - a).dot(outward) >= 0.0 {
+                mesh.indices.extend([p0, q0, p1, p1, q0, q1]);
+            } else {
+                mesh.indices.extend([p0, p1, q0, p1, q1, q0]);
+            }
+        }
+    }
+    mesh
+}
@@ -0,0 +1,93 @@
+//! Unit tests for terrain quadtree nodes and meshes.
+
+use super::heightmap::{Heightmap, SampleRect};
+use super::quadtree::{node_mesh, root_level, TerrainNode, TERRAIN_CHUNK_QUADS};
+use math::Vec3;
+
+fn assert_position(actual: [f32; 3], expected: [f32; 3]) {
+    let close = actual
+        .iter()
+        .zip(expected)
+        .all(|(a, e)| (a - e).abs() < 1e-4);
+    assert!(close, "{actual:?} != {expected:?}");
+}
+
+/// Verifies nodes cover twice the ground per level and clip to the map.
+#[test]
+fn nodes_cover_the_heightmap() {
+    let node = TerrainNode {
+        level: 1,
+        x: 1,
+        z: 0,
+    };
+    assert_eq!((node.stride(), node.span()), (2, 64));
+    assert_eq!(
+        node.samples(97, 200),
+        Some(SampleRect {
+            x: 64,
+            z: 0,
+            width: 33,
+            depth: 65
+        })
+    );
+    assert_eq!(node.samples(65, 200), None);
+    let children = node.children().unwrap();
+    assert_eq!(
+        children[3],
+        TerrainNode {
+            level: 0,
+            x: 3,
+            z: 1
+        }
+    );
+    assert_eq!(children[0].children(), None);
+
+    assert_eq!(root_level(33, 33), 0);
+    assert_eq!(root_level(97, 65), 2);
+}
+
+/// Verifies a mesh has a grid of vertices with skirts around it, all
+/// triangles facing up or outward.
+#[test]
+fn meshes_have_skirts() {
+    let size = TERRAIN_CHUNK_QUADS + 1;
+    let heightmap = Heightmap::new(size, size).unwrap();
+    let node = TerrainNode {
+        level: 0,
+        x: 0,
+        z: 0,
+    };
+    let mesh = node_mesh(&heightmap, 1.0, node);
+    let quads = TERRAIN_CHUNK_QUADS as usize;
+    let grid = (quads + 1) * (quads + 1);
+    assert_eq!(mesh.vertices.len(), grid + 4 * (quads + 1));
+    assert_eq!(mesh.triangles(), 2 * quads * quads + 4 * 2 * quads);
+
+    let center = Vec3::new(16.0, 0.0, 16.0);
+    for triangle in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| Vec3::from(mesh.vertices[triangle[i] as usize].position));
+        let normal = (b - a).cross(c - a);
+        let outward = (a + b + c) / 3.0 - center;
+        assert!(normal.y > 0.0 || normal.dot(outward) > 0.0, "{triangle:?}");
+    }
+}
+
+/// Verifies coarser levels skip samples, and the last row and column end
+/// on the heightmap's edge.
+#[test]
+fn coarse_meshes_skip_samples() {
+    let heightmap = Heightmap::new(49, 33).unwrap();
+    let node = TerrainNode {
+        level: 1,
+        x: 0,
+        z: 0,
+    };
+    let mesh = node_mesh(&heightmap, 2.0, node);
+    let grid: Vec<[f32; 3]> = mesh.vertices[..25 * 17]
+        .iter()
+        .map(|vertex| vertex.position)
+        .collect();
+    assert_position(grid[1], [4.0, 0.0, 0.0]);
+    assert_position(grid[24], [96.0, 0.0, 0.0]);
+    assert_position(grid[25 * 17 - 1], [96.0, 0.0, 64.0]);
+}
@@ -0,0 +1,236 @@
+//! Splat maps blending terrain material layers.
+
+use super::heightmap::SampleRect;
+use serde::{Deserialize, Serialize};
+
+/// Most material layers a terrain blends, packed four per splat texture.
+pub const MAX_TERRAIN_LAYERS: usize = 8;
+
+/// A material layer of a terrain, e.g. grass or rock.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TerrainLayer {
+    /// Albedo texture path.
+    pub albedo: String,
+    /// Normal map texture path.
+    #[serde(default)]
+    pub normal: Option<String>,
+    /// Texture repeats per world unit.
+    #[serde(default = "one")]
+    pub uv_scale: f32,
+}
+
+fn one() -> f32 {
+    1.0
+}
+
+impl TerrainLayer {
+    /// Creates a layer with an albedo texture repeating once per world unit.
+    #[must_use]
+    pub fn new(albedo: impl Into<String>) -> Self {
+        Self {
+            albedo: albedo.into(),
+            normal: None,
+            uv_scale: 1.0,
+        }
+    }
+}
+
+/// Per-sample blend weights of a terrain's layers.
+///
+/// Weights at each sample sum to one. The map has one sample per heightmap
+/// sample, and uploads to the GPU as RGBA8 textures holding four layers
+/// each ([`texture`](Self::texture)).
+///
+/// # Example
+///
+/// ```
+/// use render::terrain::SplatMap;
+///
+/// let mut splat = SplatMap::new(16, 16, 2)?;
+/// assert_eq!(splat.weights(4, 4), [1.0, 0.0]);
+/// splat.paint(4.0, 4.0, 2.0, 1, 1.0);
+/// assert_eq!(splat.weights(4, 4), [0.0, 1.0]);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplatMap {
+    width: u32,
+    depth: u32,
+    layers: usize,
+    weights: Vec<f32>,
+}
+
+impl SplatMap {
+    /// Creates a map of `width` x `depth` samples covered by layer 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no layers or more than
+    /// [`MAX_TERRAIN_LAYERS`].
+    pub fn new(width: u32, depth: u32, layers: usize) -> anyhow::Result<Self> {
+        if layers == 0 || layers > MAX_TERRAIN_LAYERS {
+            anyhow::bail!("terrain needs 1 to {MAX_TERRAIN_LAYERS} layers, got {layers}");
+        }
+        let mut weights = vec![0.0; width as usize * depth as usize * layers];
+        for sample in weights.chunks_exact_mut(layers) {
+            sample[0] = 1.0;
+        }
+        Ok(Self {
+            width,
+            depth,
+            layers,
+            weights,
+        })
+    }
+
+    /// Returns the number of samples along `x`.
+    #[must_use]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the number of samples along `z`.
+    #[must_use]
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Returns the number of layers.
+    #[must_use]
+    pub fn layers(&self) -> usize {
+        self.layers
+    }
+
+    /// Returns the number of RGBA splat textures the layers need.
+    #[must_use]
+    pub fn textures(&self) -> usize {
+        self.layers.div_ceil(4)
+    }
+
+    /// Returns the layer weights at sample `(x, z)`, clamped to the edges.
+    #[must_use]
+    pub fn weights(&self, x: u32, z: u32) -> &[f32] {
+        let x = x.min(self.width - 1) as usize;
+        let z = z.min(self.depth - 1) as usize;
+        let start = (z * self.width as usize + x) * self.layers;
+        &self.weights[start..start + self.layers]
+    }
+
+    /// Paints `layer` around sample `(x, z)` within `radius` samples,
+    /// moving weights towards that layer alone by `strength` (0 to 1) at the
+    /// center, falling off smoothly to zero at the edge. Returns the
+    /// samples changed, or `None` if the brush missed the map.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn paint(
+        &mut self,
+        x: f32,
+        z: f32,
+        radius: f32,
+        layer: usize,
+        strength: f32,
+    ) -> Option<SampleRect> {
+        if layer >= self.layers || radius <= 0.0 {
+            return None;
+        }
+        let rect = brush_rect(x, z, radius, self.width, self.depth)?;
+        for sz in rect.z..rect.z + rect.depth {
+            for sx in rect.x..rect.x + rect.width {
+                let falloff = brush_falloff(sx as f32 - x, sz as f32 - z, radius);
+                if falloff <= 0.0 {
+                    continue;
+                }
+                let start = (sz as usize * self.width as usize + sx as usize) * self.layers;
+                let amount = strength.clamp(0.0, 1.0) * falloff;
+                let sample = &mut self.weights[start..start + self.layers];
+                for (index, weight) in sample.iter_mut().enumerate() {
+                    let target = if index == layer { 1.0 } else { 0.0 };
+                    *weight += (target - *weight) * amount;
+                }
+            }
+        }
+        Some(rect)
+    }
+
+    /// Returns splat texture `index` as RGBA8 texels in row-major order,
+    /// with layers `4 * index` to `4 * index + 3` in the channels.
+    #[must_use]
+    pub fn texture(&self, index: usize) -> Vec<[u8; 4]> {
+        self.texture_region(
+            index,
+            SampleRect {
+                x: 0,
+                z: 0,
+                width: self.width,
+                depth: self.depth,
+            },
+        )
+    }
+
+    /// Returns the texels of splat texture `index` in a rectangle, for
+    /// uploading only the painted part.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn texture_region(&self, index: usize, rect: SampleRect) -> Vec<[u8; 4]> {
+        let mut texels = Vec::with_capacity(rect.width as usize * rect.depth as usize);
+        for sz in rect.z..rect.z + rect.depth {
+            for sx in rect.x..rect.x + rect.width {
+                let weights = self.weights(sx, sz);
+                let mut texel = [0; 4];
+                for (channel, value) in texel.iter_mut().enumerate() {
+                    if let Some(weight) = weights.get(index * 4 + channel) {
+                        *value = (weight.clamp(0.0, 1.0) * 255.0).round() as u8;
+                    }
+                }
+                texels.push(texel);
+            }
+        }
+        texels
+    }
+}
+
+/// Returns the samples a brush of `radius` samples around `(x, z)` touches,
+/// or `None` if it misses the map.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+pub(crate) fn brush_rect(
+    x: f32,
+    z: f32,
+    radius: f32,
+    width: u32,
+    depth: u32,
+) -> Option<SampleRect> {
+    let (max_x, max_z) = ((width - 1) as f32, (depth - 1) as f32);
+    if x + radius < 0.0 || z + radius < 0.0 || x - radius > max_x || z - radius > max_z {
+        return None;
+    }
+    let (x0, z0) = (
+        (x - radius).clamp(0.0, max_x).ceil() as u32,
+        (z - radius).clamp(0.0, max_z).ceil() as u32,
+    );
+    let (x1, z1) = (
+        (x + radius).clamp(0.0, max_x).floor() as u32,
+        (z + radius).clamp(0.0, max_z).floor() as u32,
+    );
+    (x0 <= x1 && z0 <= z1).then(|| SampleRect {
+        x: x0,
+        z: z0,
+        width: x1 - x0 + 1,
+        depth: z1 - z0 + 1,
+    })
+}
+
+/// Returns a brush's weight at offset `(dx, dz)` from its center: one at
+/// the center, easing to zero at `radius`.
+pub(crate) fn brush_falloff(dx: f32, dz: f32, radius: f32) -> f32 {
+    let t = (dx.hypot(dz) / radius).clamp(0.0, 1.0);
+    let t = 1.0 - t;
+    t * t * (3.0 - 2.0 * t)
+}
@@ -0,0 +1,57 @@
+//! Unit tests for splat maps.
+
+use super::heightmap::SampleRect;
+use super::splat::{SplatMap, MAX_TERRAIN_LAYERS};
+
+/// Verifies painting blends towards the painted layer with a falloff, keeps
+/// weights summing to one, and reports the samples changed.
+#[test]
+fn painting_blends_layers() {
+    let mut splat = SplatMap::new(9, 9, 3).unwrap();
+    let rect = splat.paint(4.0, 4.0, 2.0, 2, 0.5).unwrap();
+    assert_eq!(
+        rect,
+        SampleRect {
+            x: 2,
+            z: 2,
+            width: 5,
+            depth: 5
+        }
+    );
+    assert_eq!(splat.weights(4, 4), [0.5, 0.0, 0.5]);
+    let edge = splat.weights(5, 4);
+    assert!(edge[2] > 0.0 && edge[2] < 0.5);
+    assert!((edge.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+    assert_eq!(splat.weights(8, 8), [1.0, 0.0, 0.0]);
+
+    assert_eq!(splat.paint(40.0, 40.0, 2.0, 1, 1.0), None);
+    assert_eq!(splat.paint(4.0, 4.0, 2.0, 3, 1.0), None);
+}
+
+/// Verifies layers pack four to an RGBA8 texture.
+#[test]
+fn packs_layers_into_textures() {
+    let mut splat = SplatMap::new(2, 2, 5).unwrap();
+    assert_eq!(splat.textures(), 2);
+    splat.paint(0.0, 0.0, 0.5, 4, 1.0);
+    assert_eq!(splat.texture(0)[0], [0, 0, 0, 0]);
+    assert_eq!(splat.texture(1)[0], [255, 0, 0, 0]);
+    assert_eq!(splat.texture(0)[1], [255, 0, 0, 0]);
+    let region = splat.texture_region(
+        1,
+        SampleRect {
+            x: 0,
+            z: 0,
+            width: 1,
+            depth: 1,
+        },
+    );
+    assert_eq!(region, [[255, 0, 0, 0]]);
+}
+
+/// Verifies the layer count is limited.
+#[test]
+fn limits_layers() {
+    assert!(SplatMap::new(2, 2, 0).is_err());
+    assert!(SplatMap::new(2, 2, MAX_TERRAIN_LAYERS + 1).is_err());
+}
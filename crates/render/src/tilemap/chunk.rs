@@ -0,0 +1,114 @@
+//! Chunked tile meshes.
+
+use super::map::{TileFlags, Tilemap};
+
+/// Width and height of a chunk, in tiles.
+pub const CHUNK_SIZE: u32 = 16;
+
+/// Position of a chunk in its layer, in chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ChunkCoord {
+    /// Column of the chunk.
+    pub x: u32,
+    /// Row of the chunk.
+    pub y: u32,
+}
+
+impl ChunkCoord {
+    /// Returns the chunk holding tile `(x, y)`.
+    #[must_use]
+    pub const fn containing(x: u32, y: u32) -> Self {
+        Self {
+            x: x / CHUNK_SIZE,
+            y: y / CHUNK_SIZE,
+        }
+    }
+}
+
+/// Vertex of a tile mesh.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileVertex {
+    /// Position in pixels, with y pointing down.
+    pub position: [f32; 2],
+    /// Texture coordinates in the tileset.
+    pub uv: [f32; 2],
+}
+
+/// Mesh of one chunk of a layer: four vertices and six indices per tile.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TileChunkMesh {
+    /// Vertices, four per tile.
+    pub vertices: Vec<TileVertex>,
+    /// Triangle list indices into [`vertices`](Self::vertices).
+    pub indices: Vec<u32>,
+}
+
+impl TileChunkMesh {
+    /// Returns `true` if the chunk has no visible tiles.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+impl Tilemap {
+    /// Builds the mesh of `chunk` in `layer` at the current animation time.
+    ///
+    /// Hidden or missing layers and chunks give an empty mesh.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn build_chunk(&self, layer: usize, chunk: ChunkCoord) -> TileChunkMesh {
+        let mut mesh = TileChunkMesh::default();
+        let Some(layer) = self.layers().get(layer).filter(|layer| layer.visible) else {
+            return mesh;
+        };
+        let tileset = &self.tileset;
+        let (tile_width, tile_height) = (tileset.tile_width as f32, tileset.tile_height as f32);
+        let x_end = ((chunk.x + 1) * CHUNK_SIZE).min(layer.width());
+        let y_end = ((chunk.y + 1) * CHUNK_SIZE).min(layer.height());
+
+        for y in chunk.y * CHUNK_SIZE..y_end {
+            for x in chunk.x * CHUNK_SIZE..x_end {
+                let Some(tile) = layer.tile(x, y) else {
+                    continue;
+                };
+                let Some([mut u0, mut v0, mut u1, mut v1]) =
+                    tileset.uv(tileset.display_index(tile, self.time()))
+                else {
+                    continue;
+                };
+                if tile.flags.contains(TileFlags::FLIP_X) {
+                    std::mem::swap(&mut u0, &mut u1);
+                }
+                if tile.flags.contains(TileFlags::FLIP_Y) {
+                    std::mem::swap(&mut v0, &mut v1);
+                }
+
+                let (left, top) = (x as f32 * tile_width, y as f32 * tile_height);
+                let (right, bottom) = (left + tile_width, top + tile_height);
+                let base = u32::try_from(mesh.vertices.len()).unwrap_or(u32::MAX);
+                mesh.vertices.extend([
+                    TileVertex {
+                        position: [left, top],
+                        uv: [u0, v0],
+                    },
+                    TileVertex {
+                        position: [right, top],
+                        uv: [u1, v0],
+                    },
+                    TileVertex {
+                        position: [right, bottom],
+                        uv: [u1, v1],
+                    },
+                    TileVertex {
+                        position: [left, bottom],
+                        uv: [u0, v1],
+                    },
+                ]);
+                mesh.indices
+                    .extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+            }
+        }
+        mesh
+    }
+}
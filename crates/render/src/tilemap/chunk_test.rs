@@ -0,0 +1,79 @@
+//! Unit tests for tile chunk meshing.
+
+use super::{ChunkCoord, Tile, TileFlags, TileLayer, TileVertex, Tilemap, Tileset, CHUNK_SIZE};
+
+fn vertex(position: [f32; 2], uv: [f32; 2]) -> TileVertex {
+    TileVertex { position, uv }
+}
+
+fn map() -> (Tilemap, usize) {
+    let mut map = Tilemap::new(Tileset {
+        texture: "tiles.png".into(),
+        tile_width: 16,
+        tile_height: 16,
+        columns: 2,
+        tile_count: 4,
+        ..Tileset::default()
+    });
+    let layer = map.add_layer(TileLayer::new("ground", 0, CHUNK_SIZE + 4, 4));
+    (map, layer)
+}
+
+/// Verifies each tile becomes a textured quad in pixel space.
+#[test]
+fn builds_quads_for_tiles() {
+    let (mut map, layer) = map();
+    map.set_tile(layer, 1, 2, Some(Tile::new(3)));
+
+    let mesh = map.build_chunk(layer, ChunkCoord { x: 0, y: 0 });
+    assert_eq!(mesh.vertices.len(), 4);
+    assert_eq!(mesh.indices, [0, 1, 2, 0, 2, 3]);
+    assert_eq!(mesh.vertices[0], vertex([16.0, 32.0], [0.5, 0.5]));
+    assert_eq!(mesh.vertices[2], vertex([32.0, 48.0], [1.0, 1.0]));
+}
+
+/// Verifies chunks only hold their own tiles and respect the layer edge.
+#[test]
+fn chunks_partition_layer() {
+    let (mut map, layer) = map();
+    map.set_tile(layer, 0, 0, Some(Tile::new(0)));
+    map.set_tile(layer, CHUNK_SIZE, 0, Some(Tile::new(0)));
+    map.set_tile(layer, CHUNK_SIZE + 3, 3, Some(Tile::new(0)));
+
+    assert_eq!(
+        map.build_chunk(layer, ChunkCoord { x: 0, y: 0 })
+            .vertices
+            .len(),
+        4
+    );
+    assert_eq!(
+        map.build_chunk(layer, ChunkCoord { x: 1, y: 0 })
+            .vertices
+            .len(),
+        8
+    );
+    assert!(map.build_chunk(layer, ChunkCoord { x: 2, y: 0 }).is_empty());
+}
+
+/// Verifies flips mirror texture coordinates and hidden layers are empty.
+#[test]
+fn flips_and_hidden_layers() {
+    let (mut map, layer) = map();
+    map.set_tile(
+        layer,
+        0,
+        0,
+        Some(Tile::new(0).with_flags(TileFlags::FLIP_X | TileFlags::FLIP_Y)),
+    );
+    let mesh = map.build_chunk(layer, ChunkCoord { x: 0, y: 0 });
+    assert_eq!(mesh.vertices[0], vertex([0.0, 0.0], [0.5, 0.5]));
+    assert_eq!(mesh.vertices[2], vertex([16.0, 16.0], [0.0, 0.0]));
+
+    let mut hidden = TileLayer::new("collision", 1, 4, 4);
+    hidden.visible = false;
+    let hidden = map.add_layer(hidden);
+    map.set_tile(hidden, 0, 0, Some(Tile::new(0)));
+    assert!(map
+        .build_chunk(hidden, ChunkCoord { x: 0, y: 0 })
+        .is_empty());
+}
@@ -0,0 +1,150 @@
+//! Unit tests for Tiled and LDtk import.
+
+use super::{Tile, TileFlags, Tilemap};
+use std::time::Duration;
+
+const TMX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="3" height="2" tilewidth="16" tileheight="16" infinite="0">
+ <tileset firstgid="1" name="terrain" tilewidth="16" tileheight="16" tilecount="8" columns="4">
+  <image source="terrain.png" width="64" height="32"/>
+  <tile id="2">
+   <properties>
+    <property name="collision" type="bool" value="true"/>
+   </properties>
+  </tile>
+  <tile id="5">
+   <animation>
+    <frame tileid="5" duration="200"/>
+    <frame tileid="6" duration="200"/>
+   </animation>
+  </tile>
+ </tileset>
+ <layer id="1" name="ground" width="3" height="2">
+  <data encoding="csv">
+1,2,3,
+0,6,2147483649
+</data>
+ </layer>
+ <layer id="2" name="markers" width="3" height="2" visible="0">
+  <data encoding="csv">
+0,0,0,
+0,0,0
+</data>
+ </layer>
+</map>
+"#;
+
+/// Verifies TMX maps import tiles, flags, animations, and layer order.
+#[test]
+fn imports_tmx() {
+    let map = Tilemap::from_tmx(TMX).unwrap();
+
+    assert_eq!(map.tileset.texture, "terrain.png");
+    assert_eq!(map.tileset.tile_count, 8);
+    let layers = map.layers();
+    assert_eq!(layers.len(), 2);
+    assert_eq!((layers[0].name.as_str(), layers[0].order), ("ground", 0));
+    assert!(!layers[1].visible);
+
+    let ground = &layers[0];
+    assert_eq!(ground.tile(0, 0), Some(Tile::new(0)));
+    assert_eq!(ground.tile(0, 1), None);
+    assert_eq!(
+        ground.tile(2, 0),
+        Some(Tile::new(2).with_flags(TileFlags::COLLISION))
+    );
+    assert_eq!(
+        ground.tile(1, 1),
+        Some(Tile::new(5).with_flags(TileFlags::ANIMATED))
+    );
+    assert_eq!(
+        ground.tile(2, 1),
+        Some(Tile::new(0).with_flags(TileFlags::FLIP_X))
+    );
+    assert!(map.is_solid(2, 0));
+    assert_eq!(
+        map.tileset.animations[&5].index_at(Duration::from_millis(300)),
+        Some(6)
+    );
+}
+
+/// Verifies unsupported TMX features are reported.
+#[test]
+fn rejects_unsupported_tmx() {
+    let base64 = TMX.replace(r#"encoding="csv""#, r#"encoding="base64""#);
+    let external = TMX.replace(r#"name="terrain""#, r#"source="terrain.tsx""#);
+    let isometric = TMX.replace("orthogonal", "isometric");
+
+    for text in [&base64, &external, &isometric, "<tileset/>", "not xml"] {
+        assert!(Tilemap::from_tmx(text).is_err(), "{text}");
+    }
+}
+
+const LDTK: &str = r#"{
+  "defs": {
+    "tilesets": [
+      { "uid": 7, "relPath": "tiles.png", "tileGridSize": 8, "__cWid": 4, "__cHei": 4 }
+    ]
+  },
+  "levels": [
+    { "identifier": "Level_0", "layerInstances": [] },
+    {
+      "identifier": "Level_1",
+      "layerInstances": [
+        {
+          "__identifier": "Entities", "__type": "Entities",
+          "__cWid": 2, "__cHei": 2, "__gridSize": 8, "__tilesetDefUid": null
+        },
+        {
+          "__identifier": "Walls", "__type": "IntGrid",
+          "__cWid": 2, "__cHei": 2, "__gridSize": 8, "__tilesetDefUid": null,
+          "intGridCsv": [1, 0, 0, 1]
+        },
+        {
+          "__identifier": "Ground", "__type": "Tiles",
+          "__cWid": 2, "__cHei": 2, "__gridSize": 8, "__tilesetDefUid": 7,
+          "visible": true,
+          "gridTiles": [
+            { "px": [0, 0], "t": 3, "f": 0 },
+            { "px": [8, 8], "t": 9, "f": 2 }
+          ]
+        }
+      ]
+    }
+  ]
+}"#;
+
+/// Verifies LDtk levels import tile layers and IntGrid collision.
+#[test]
+fn imports_ldtk() {
+    let map = Tilemap::from_ldtk(LDTK, Some("Level_1")).unwrap();
+
+    assert_eq!(map.tileset.texture, "tiles.png");
+    assert_eq!(map.tileset.tile_count, 16);
+    let names: Vec<_> = map
+        .layers()
+        .iter()
+        .map(|layer| layer.name.as_str())
+        .collect();
+    assert_eq!(names, ["Ground", "Walls collision"]);
+    assert_eq!(map.draw_order(), [0, 1]);
+
+    let ground = &map.layers()[0];
+    assert_eq!(ground.tile(0, 0), Some(Tile::new(3)));
+    assert_eq!(
+        ground.tile(1, 1),
+        Some(Tile::new(9).with_flags(TileFlags::FLIP_Y))
+    );
+    assert!(!map.layers()[1].visible);
+    assert!(map.is_solid(0, 0));
+    assert!(!map.is_solid(1, 0));
+    assert!(map.is_solid(1, 1));
+}
+
+/// Verifies LDtk import errors for missing levels and tilesets.
+#[test]
+fn rejects_invalid_ldtk() {
+    assert!(Tilemap::from_ldtk(LDTK, Some("Level_9")).is_err());
+    assert!(Tilemap::from_ldtk(LDTK, None).is_err());
+    assert!(Tilemap::from_ldtk("{}", None).is_err());
+}
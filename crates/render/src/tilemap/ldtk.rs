@@ -0,0 +1,193 @@
+//! Import from LDtk projects.
+
+use super::map::{Tile, TileFlags, TileLayer, Tilemap, Tileset};
+use anyhow::{bail, Context};
+use serde::Deserialize;
+
+/// LDtk flip bit for horizontally mirrored tiles.
+const FLIP_X: u8 = 1;
+
+/// LDtk flip bit for vertically mirrored tiles.
+const FLIP_Y: u8 = 2;
+
+#[derive(Deserialize)]
+struct Project {
+    defs: Definitions,
+    levels: Vec<Level>,
+}
+
+#[derive(Deserialize)]
+struct Definitions {
+    tilesets: Vec<TilesetDef>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TilesetDef {
+    uid: i64,
+    rel_path: Option<String>,
+    tile_grid_size: u32,
+    #[serde(rename = "__cWid")]
+    columns: u32,
+    #[serde(rename = "__cHei")]
+    rows: u32,
+}
+
+#[derive(Deserialize)]
+struct Level {
+    identifier: String,
+    #[serde(rename = "layerInstances", default)]
+    layers: Option<Vec<LayerInstance>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LayerInstance {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__type")]
+    kind: String,
+    #[serde(rename = "__cWid")]
+    width: u32,
+    #[serde(rename = "__cHei")]
+    height: u32,
+    #[serde(rename = "__gridSize")]
+    grid_size: u32,
+    #[serde(rename = "__tilesetDefUid")]
+    tileset_uid: Option<i64>,
+    #[serde(default = "visible")]
+    visible: bool,
+    #[serde(default)]
+    grid_tiles: Vec<TileInstance>,
+    #[serde(default)]
+    auto_layer_tiles: Vec<TileInstance>,
+    #[serde(default)]
+    int_grid_csv: Vec<i64>,
+}
+
+fn visible() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct TileInstance {
+    px: [u32; 2],
+    t: u32,
+    #[serde(default)]
+    f: u8,
+}
+
+impl Tilemap {
+    /// Imports the level named `level` from an LDtk project, or the first
+    /// level if `None`.
+    ///
+    /// Tile and auto layers become tile layers; all of them must use the
+    /// same tileset. Each IntGrid layer also adds a hidden layer whose
+    /// tiles carry [`TileFlags::COLLISION`] on the non-zero cells. LDtk lists layers top first,
+    /// so the last one in the file draws first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is not an LDtk project, the level does not
+    /// exist, or no layer references a tileset.
+    pub fn from_ldtk(text: &str, level: Option<&str>) -> anyhow::Result<Self> {
+        let project: Project = serde_json::from_str(text).context("invalid LDtk project")?;
+        let level = match level {
+            Some(name) => project
+                .levels
+                .iter()
+                .find(|candidate| candidate.identifier == name)
+                .with_context(|| format!("LDtk project has no level {name}"))?,
+            None => project
+                .levels
+                .first()
+                .context("LDtk project has no levels")?,
+        };
+        let layers = level
+            .layers
+            .as_deref()
+            .with_context(|| format!("level {} is stored externally", level.identifier))?;
+
+        let tileset_uid = layers
+            .iter()
+            .find_map(|layer| layer.tileset_uid)
+            .context("no LDtk layer uses a tileset")?;
+        let tileset = project
+            .defs
+            .tilesets
+            .iter()
+            .find(|tileset| tileset.uid == tileset_uid)
+            .with_context(|| format!("LDtk tileset {tileset_uid} is not defined"))?;
+        let mut tilemap = Tilemap::new(Tileset {
+            texture: tileset.rel_path.as_deref().unwrap_or_default().into(),
+            tile_width: tileset.tile_grid_size,
+            tile_height: tileset.tile_grid_size,
+            columns: tileset.columns,
+            tile_count: tileset.columns * tileset.rows,
+            ..Tileset::default()
+        });
+
+        for (order, instance) in (0..).zip(layers.iter().rev()) {
+            if !matches!(instance.kind.as_str(), "Tiles" | "AutoLayer" | "IntGrid") {
+                continue;
+            }
+            if instance.tileset_uid.is_some_and(|uid| uid != tileset_uid) {
+                bail!("layer {} uses a second tileset", instance.identifier);
+            }
+            let has_tiles =
+                !instance.grid_tiles.is_empty() || !instance.auto_layer_tiles.is_empty();
+            if instance.kind != "IntGrid" || has_tiles {
+                let mut layer =
+                    TileLayer::new(&instance.identifier, order, instance.width, instance.height);
+                layer.visible = instance.visible;
+                place_tiles(&mut layer, instance)?;
+                tilemap.add_layer(layer);
+            }
+            if instance.kind == "IntGrid" {
+                tilemap.add_layer(collision_layer(instance, order));
+            }
+        }
+        Ok(tilemap)
+    }
+}
+
+fn place_tiles(layer: &mut TileLayer, instance: &LayerInstance) -> anyhow::Result<()> {
+    let grid = instance.grid_size.max(1);
+    for tile in instance.grid_tiles.iter().chain(&instance.auto_layer_tiles) {
+        let mut placed = Tile::new(tile.t);
+        if tile.f & FLIP_X != 0 {
+            placed = placed.with_flags(TileFlags::FLIP_X);
+        }
+        if tile.f & FLIP_Y != 0 {
+            placed = placed.with_flags(TileFlags::FLIP_Y);
+        }
+        let (x, y) = (tile.px[0] / grid, tile.px[1] / grid);
+        if !layer.put(x, y, Some(placed)) {
+            bail!(
+                "layer {} has a tile outside its bounds",
+                instance.identifier
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Builds a hidden layer whose tiles mark the non-zero cells of an IntGrid
+/// layer as colliding.
+fn collision_layer(instance: &LayerInstance, order: i32) -> TileLayer {
+    let mut layer = TileLayer::new(
+        format!("{} collision", instance.identifier),
+        order,
+        instance.width,
+        instance.height,
+    );
+    layer.visible = false;
+    let width = instance.width.max(1);
+    for (position, value) in (0..).zip(&instance.int_grid_csv) {
+        if *value != 0 {
+            let tile = Tile::new(0).with_flags(TileFlags::COLLISION);
+            layer.put(position % width, position / width, Some(tile));
+        }
+    }
+    layer
+}
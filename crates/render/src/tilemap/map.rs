@@ -0,0 +1,360 @@
+//! Tilemap, layer, and tileset types.
+
+use super::chunk::{ChunkCoord, CHUNK_SIZE};
+use rustgine_core::Label;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+/// Per-tile flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TileFlags(u8);
+
+impl TileFlags {
+    /// No flags.
+    pub const NONE: Self = Self(0);
+    /// The tile blocks movement.
+    pub const COLLISION: Self = Self(1);
+    /// The tile cycles through its [`TileAnimation`].
+    pub const ANIMATED: Self = Self(1 << 1);
+    /// The tile is mirrored horizontally.
+    pub const FLIP_X: Self = Self(1 << 2);
+    /// The tile is mirrored vertically.
+    pub const FLIP_Y: Self = Self(1 << 3);
+
+    /// Returns `true` if all flags in `other` are set.
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns the union of both flag sets.
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl std::ops::BitOr for TileFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+/// A placed tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tile {
+    /// Index of the tile in the tileset.
+    pub index: u32,
+    /// Per-tile flags.
+    pub flags: TileFlags,
+}
+
+impl Tile {
+    /// Creates a tile without flags.
+    #[must_use]
+    pub const fn new(index: u32) -> Self {
+        Self {
+            index,
+            flags: TileFlags::NONE,
+        }
+    }
+
+    /// Adds flags to the tile.
+    #[must_use]
+    pub const fn with_flags(mut self, flags: TileFlags) -> Self {
+        self.flags = self.flags.union(flags);
+        self
+    }
+}
+
+/// Frames an animated tile cycles through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileAnimation {
+    /// Tileset indices and how long each is shown.
+    pub frames: Vec<(u32, Duration)>,
+}
+
+impl TileAnimation {
+    /// Returns the tileset index shown at `time`.
+    #[must_use]
+    pub fn index_at(&self, time: Duration) -> Option<u32> {
+        let total: Duration = self.frames.iter().map(|(_, duration)| *duration).sum();
+        if total.is_zero() {
+            return self.frames.first().map(|(index, _)| *index);
+        }
+        let mut remaining = Duration::from_nanos(
+            u64::try_from(time.as_nanos() % total.as_nanos()).unwrap_or_default(),
+        );
+        for &(index, duration) in &self.frames {
+            if remaining < duration {
+                return Some(index);
+            }
+            remaining -= duration;
+        }
+        None
+    }
+}
+
+/// Texture holding the tiles of a map, laid out in a grid.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Tileset {
+    /// Texture asset path.
+    pub texture: Label,
+    /// Tile width in pixels.
+    pub tile_width: u32,
+    /// Tile height in pixels.
+    pub tile_height: u32,
+    /// Number of tile columns in the texture.
+    pub columns: u32,
+    /// Number of tiles in the texture.
+    pub tile_count: u32,
+    /// Animations by the tileset index of the animated tile.
+    pub animations: BTreeMap<u32, TileAnimation>,
+}
+
+impl Tileset {
+    /// Returns the texture coordinates of tile `index` as
+    /// `[u0, v0, u1, v1]`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn uv(&self, index: u32) -> Option<[f32; 4]> {
+        if index >= self.tile_count || self.columns == 0 {
+            return None;
+        }
+        let rows = self.tile_count.div_ceil(self.columns);
+        let (column, row) = (index % self.columns, index / self.columns);
+        let (columns, rows) = (self.columns as f32, rows as f32);
+        Some([
+            column as f32 / columns,
+            row as f32 / rows,
+            (column + 1) as f32 / columns,
+            (row + 1) as f32 / rows,
+        ])
+    }
+
+    /// Returns the tileset index to draw for `tile` at animation time `at`.
+    #[must_use]
+    pub fn display_index(&self, tile: Tile, at: Duration) -> u32 {
+        if tile.flags.contains(TileFlags::ANIMATED) {
+            if let Some(index) = self
+                .animations
+                .get(&tile.index)
+                .and_then(|animation| animation.index_at(at))
+            {
+                return index;
+            }
+        }
+        tile.index
+    }
+}
+
+/// One grid of tiles, drawn in [`order`](Self::order).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileLayer {
+    /// Layer name from the editor.
+    pub name: String,
+    /// Layers draw in ascending order, so higher orders are on top.
+    pub order: i32,
+    /// Whether the layer is drawn; hidden layers still hold flags such as
+    /// collision.
+    pub visible: bool,
+    width: u32,
+    height: u32,
+    tiles: Vec<Option<Tile>>,
+}
+
+impl TileLayer {
+    /// Creates an empty visible layer of `width` x `height` tiles.
+    #[must_use]
+    pub fn new(name: impl Into<String>, order: i32, width: u32, height: u32) -> Self {
+        Self {
+            name: name.into(),
+            order,
+            visible: true,
+            width,
+            height,
+            tiles: vec![None; width as usize * height as usize],
+        }
+    }
+
+    /// Returns the width in tiles.
+    #[must_use]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height in tiles.
+    #[must_use]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the tile at `(x, y)`, if any.
+    #[must_use]
+    pub fn tile(&self, x: u32, y: u32) -> Option<Tile> {
+        self.index(x, y).and_then(|index| self.tiles[index])
+    }
+
+    /// Returns the number of chunks across and down.
+    #[must_use]
+    pub fn chunks(&self) -> (u32, u32) {
+        (
+            self.width.div_ceil(CHUNK_SIZE),
+            self.height.div_ceil(CHUNK_SIZE),
+        )
+    }
+
+    /// Replaces the tile at `(x, y)`, returning `false` if out of bounds.
+    ///
+    /// Used by importers; [`Tilemap::set_tile`] also tracks dirty chunks.
+    pub(super) fn put(&mut self, x: u32, y: u32, tile: Option<Tile>) -> bool {
+        match self.index(x, y) {
+            Some(index) => {
+                self.tiles[index] = tile;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y as usize * self.width as usize + x as usize)
+    }
+}
+
+/// A tile grid with its tileset and layers.
+///
+/// # Example
+///
+/// ```
+/// use render::tilemap::{ChunkCoord, Tile, TileFlags, TileLayer, Tilemap, Tileset};
+///
+/// let tileset = Tileset {
+///     texture: "tiles.png".into(),
+///     tile_width: 16,
+///     tile_height: 16,
+///     columns: 8,
+///     tile_count: 64,
+///     ..Tileset::default()
+/// };
+/// let mut map = Tilemap::new(tileset);
+/// let ground = map.add_layer(TileLayer::new("ground", 0, 64, 64));
+/// map.take_dirty_chunks();
+///
+/// // Only the chunk holding the edited tile is rebuilt
+/// map.set_tile(ground, 40, 3, Some(Tile::new(5).with_flags(TileFlags::COLLISION)));
+/// let dirty = map.take_dirty_chunks();
+/// assert_eq!(dirty, [(ground, ChunkCoord { x: 2, y: 0 })]);
+/// assert!(map.is_solid(40, 3));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tilemap {
+    /// Tileset all layers draw from.
+    pub tileset: Tileset,
+    layers: Vec<TileLayer>,
+    dirty: BTreeSet<(usize, ChunkCoord)>,
+    time: Duration,
+}
+
+impl Tilemap {
+    /// Creates a map without layers.
+    #[must_use]
+    pub fn new(tileset: Tileset) -> Self {
+        Self {
+            tileset,
+            layers: Vec::new(),
+            dirty: BTreeSet::new(),
+            time: Duration::ZERO,
+        }
+    }
+
+    /// Adds a layer, marking all its chunks dirty, and returns its index.
+    pub fn add_layer(&mut self, layer: TileLayer) -> usize {
+        let index = self.layers.len();
+        let (columns, rows) = layer.chunks();
+        self.layers.push(layer);
+        for y in 0..rows {
+            for x in 0..columns {
+                self.dirty.insert((index, ChunkCoord { x, y }));
+            }
+        }
+        index
+    }
+
+    /// Returns the layers in insertion order.
+    #[must_use]
+    pub fn layers(&self) -> &[TileLayer] {
+        &self.layers
+    }
+
+    /// Returns layer indices in draw order: ascending
+    /// [`order`](TileLayer::order), then insertion order.
+    #[must_use]
+    pub fn draw_order(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.layers.len()).collect();
+        indices.sort_by_key(|&index| self.layers[index].order);
+        indices
+    }
+
+    /// Replaces the tile at `(x, y)` of `layer` and marks its chunk dirty.
+    ///
+    /// Returns `false` if the layer or position does not exist.
+    pub fn set_tile(&mut self, layer: usize, x: u32, y: u32, tile: Option<Tile>) -> bool {
+        let Some(target) = self.layers.get_mut(layer) else {
+            return false;
+        };
+        if target.tile(x, y) == tile {
+            return target.index(x, y).is_some();
+        }
+        if !target.put(x, y, tile) {
+            return false;
+        }
+        self.dirty.insert((layer, ChunkCoord::containing(x, y)));
+        true
+    }
+
+    /// Returns `true` if any layer has a colliding tile at `(x, y)`.
+    #[must_use]
+    pub fn is_solid(&self, x: u32, y: u32) -> bool {
+        self.layers.iter().any(|layer| {
+            layer
+                .tile(x, y)
+                .is_some_and(|tile| tile.flags.contains(TileFlags::COLLISION))
+        })
+    }
+
+    /// Advances tile animations to `time`, marking chunks whose animated
+    /// tiles changed frame dirty.
+    pub fn set_time(&mut self, time: Duration) {
+        let previous = std::mem::replace(&mut self.time, time);
+        for (index, layer) in self.layers.iter().enumerate() {
+            for (position, tile) in layer.tiles.iter().enumerate() {
+                let Some(animated) = tile.filter(|tile| tile.flags.contains(TileFlags::ANIMATED))
+                else {
+                    continue;
+                };
+                if self.tileset.display_index(animated, previous)
+                    != self.tileset.display_index(animated, time)
+                {
+                    let position = u32::try_from(position).unwrap_or(u32::MAX);
+                    let (x, y) = (position % layer.width, position / layer.width);
+                    self.dirty.insert((index, ChunkCoord::containing(x, y)));
+                }
+            }
+        }
+    }
+
+    /// Returns the current animation time.
+    #[must_use]
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    /// Returns and clears the chunks changed since the last call, as
+    /// `(layer, chunk)` pairs.
+    pub fn take_dirty_chunks(&mut self) -> Vec<(usize, ChunkCoord)> {
+        std::mem::take(&mut self.dirty).into_iter().collect()
+    }
+}
@@ -0,0 +1,106 @@
+//! Unit tests for tilemap editing and animation.
+
+use super::{ChunkCoord, Tile, TileAnimation, TileFlags, TileLayer, Tilemap, Tileset};
+use std::time::Duration;
+
+fn tileset() -> Tileset {
+    Tileset {
+        texture: "tiles.png".into(),
+        tile_width: 16,
+        tile_height: 16,
+        columns: 4,
+        tile_count: 16,
+        ..Tileset::default()
+    }
+}
+
+/// Verifies new layers are fully dirty and edits only dirty their chunk.
+#[test]
+fn edits_dirty_only_their_chunk() {
+    let mut map = Tilemap::new(tileset());
+    let layer = map.add_layer(TileLayer::new("ground", 0, 20, 40));
+    assert_eq!(map.take_dirty_chunks().len(), 2 * 3);
+    assert!(map.take_dirty_chunks().is_empty());
+
+    assert!(map.set_tile(layer, 17, 33, Some(Tile::new(1))));
+    assert!(map.set_tile(layer, 18, 34, Some(Tile::new(2))));
+    // Unchanged tiles and out-of-bounds edits dirty nothing
+    assert!(map.set_tile(layer, 0, 0, None));
+    assert!(!map.set_tile(layer, 20, 0, Some(Tile::new(1))));
+    assert!(!map.set_tile(5, 0, 0, Some(Tile::new(1))));
+
+    assert_eq!(
+        map.take_dirty_chunks(),
+        [(layer, ChunkCoord { x: 1, y: 2 })]
+    );
+    assert_eq!(map.layers()[layer].tile(17, 33), Some(Tile::new(1)));
+}
+
+/// Verifies layers draw by order, keeping insertion order on ties.
+#[test]
+fn layers_draw_in_order() {
+    let mut map = Tilemap::new(tileset());
+    map.add_layer(TileLayer::new("decor", 2, 1, 1));
+    map.add_layer(TileLayer::new("ground", 0, 1, 1));
+    map.add_layer(TileLayer::new("walls", 2, 1, 1));
+
+    assert_eq!(map.draw_order(), [1, 0, 2]);
+}
+
+/// Verifies collision is checked across layers.
+#[test]
+fn collision_across_layers() {
+    let mut map = Tilemap::new(tileset());
+    let ground = map.add_layer(TileLayer::new("ground", 0, 4, 4));
+    let walls = map.add_layer(TileLayer::new("walls", 1, 4, 4));
+    map.set_tile(ground, 1, 1, Some(Tile::new(0)));
+    map.set_tile(
+        walls,
+        2,
+        1,
+        Some(Tile::new(3).with_flags(TileFlags::COLLISION)),
+    );
+
+    assert!(!map.is_solid(1, 1));
+    assert!(map.is_solid(2, 1));
+    assert!(!map.is_solid(9, 9));
+}
+
+/// Verifies animated tiles change frame and dirty their chunk only then.
+#[test]
+fn animated_tiles_rebuild_on_frame_change() {
+    let mut tileset = tileset();
+    tileset.animations.insert(
+        4,
+        TileAnimation {
+            frames: vec![
+                (4, Duration::from_millis(100)),
+                (5, Duration::from_millis(100)),
+            ],
+        },
+    );
+    let mut map = Tilemap::new(tileset);
+    let water = map.add_layer(TileLayer::new("water", 0, 40, 4));
+    map.set_tile(
+        water,
+        35,
+        0,
+        Some(Tile::new(4).with_flags(TileFlags::ANIMATED)),
+    );
+    map.take_dirty_chunks();
+
+    map.set_time(Duration::from_millis(50));
+    assert!(map.take_dirty_chunks().is_empty());
+
+    map.set_time(Duration::from_millis(150));
+    assert_eq!(
+        map.take_dirty_chunks(),
+        [(water, ChunkCoord { x: 2, y: 0 })]
+    );
+    let tile = map.layers()[water].tile(35, 0).unwrap();
+    assert_eq!(map.tileset.display_index(tile, map.time()), 5);
+    assert_eq!(
+        map.tileset.display_index(tile, Duration::from_millis(250)),
+        4
+    );
+}
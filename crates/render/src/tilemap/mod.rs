@@ -0,0 +1,27 @@
+//! 2D tilemaps rendered as chunked meshes.
+//!
+//! A [`Tilemap`] is a grid of tiles from one [`Tileset`], split into ordered
+//! [`TileLayer`]s. Layers are meshed in [`CHUNK_SIZE`] x [`CHUNK_SIZE`]
+//! chunks so that changing a tile at runtime only rebuilds the chunk that
+//! holds it: edits mark chunks dirty, and the renderer re-uploads the
+//! meshes returned by [`Tilemap::take_dirty_chunks`] once per frame.
+//!
+//! Maps are imported from the [Tiled](https://www.mapeditor.org/) TMX and
+//! [LDtk](https://ldtk.io/) formats:
+//!
+//! - [`Tilemap::from_tmx`] - Orthogonal TMX maps with CSV layer data
+//! - [`Tilemap::from_ldtk`] - One level of an LDtk project
+
+mod chunk;
+#[cfg(test)]
+mod chunk_test;
+#[cfg(test)]
+mod import_test;
+mod ldtk;
+mod map;
+#[cfg(test)]
+mod map_test;
+mod tiled;
+
+pub use chunk::{ChunkCoord, TileChunkMesh, TileVertex, CHUNK_SIZE};
+pub use map::{Tile, TileAnimation, TileFlags, TileLayer, Tilemap, Tileset};
@@ -0,0 +1,181 @@
+//! Import from Tiled TMX maps.
+
+use super::map::{Tile, TileAnimation, TileFlags, TileLayer, Tilemap, Tileset};
+use anyhow::{bail, Context};
+use roxmltree::{Document, Node};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Bit set in a TMX global tile id when the tile is flipped horizontally.
+const FLIPPED_HORIZONTALLY: u32 = 0x8000_0000;
+
+/// Bit set in a TMX global tile id when the tile is flipped vertically.
+const FLIPPED_VERTICALLY: u32 = 0x4000_0000;
+
+/// Bits of a TMX global tile id that hold the tile id.
+const GID_MASK: u32 = 0x0FFF_FFFF;
+
+/// Flags of a tileset tile from its properties and animation.
+type TileProperties = BTreeMap<u32, TileFlags>;
+
+impl Tilemap {
+    /// Imports an orthogonal Tiled map.
+    ///
+    /// Supports one embedded tileset and CSV-encoded, finite tile layers.
+    /// Tiles with a boolean `collision` property get
+    /// [`TileFlags::COLLISION`]; tiles with an animation get
+    /// [`TileFlags::ANIMATED`]. Layers are ordered as in the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is not a TMX map or uses an unsupported
+    /// feature, such as external tilesets or compressed layer data.
+    pub fn from_tmx(text: &str) -> anyhow::Result<Self> {
+        let document = Document::parse(text).context("invalid TMX document")?;
+        let map = document.root_element();
+        if !map.has_tag_name("map") {
+            bail!(
+                "TMX root element is <{}>, expected <map>",
+                map.tag_name().name()
+            );
+        }
+        if map.attribute("orientation").unwrap_or("orthogonal") != "orthogonal" {
+            bail!("only orthogonal TMX maps are supported");
+        }
+        if map.attribute("infinite") == Some("1") {
+            bail!("infinite TMX maps are not supported");
+        }
+
+        let tileset_node = map
+            .children()
+            .find(|node| node.has_tag_name("tileset"))
+            .context("TMX map has no tileset")?;
+        let first_gid = attribute(tileset_node, "firstgid")?;
+        let (tileset, properties) = parse_tileset(tileset_node)?;
+
+        let mut tilemap = Tilemap::new(tileset);
+        let layers = map.children().filter(|node| node.has_tag_name("layer"));
+        for (order, node) in (0..).zip(layers) {
+            let layer = parse_layer(node, order, first_gid, &properties)?;
+            tilemap.add_layer(layer);
+        }
+        Ok(tilemap)
+    }
+}
+
+fn parse_tileset(node: Node) -> anyhow::Result<(Tileset, TileProperties)> {
+    if let Some(source) = node.attribute("source") {
+        bail!("external tileset {source} is not supported, embed it in the map");
+    }
+    let image = node
+        .children()
+        .find(|child| child.has_tag_name("image"))
+        .context("tileset has no image")?;
+
+    let mut tileset = Tileset {
+        texture: image
+            .attribute("source")
+            .context("tileset image has no source")?
+            .into(),
+        tile_width: attribute(node, "tilewidth")?,
+        tile_height: attribute(node, "tileheight")?,
+        columns: attribute(node, "columns")?,
+        tile_count: attribute(node, "tilecount")?,
+        animations: BTreeMap::new(),
+    };
+
+    let mut properties = TileProperties::new();
+    for tile in node.children().filter(|child| child.has_tag_name("tile")) {
+        let id: u32 = attribute(tile, "id")?;
+        let mut flags = TileFlags::NONE;
+        let collision = tile
+            .descendants()
+            .filter(|child| child.has_tag_name("property"))
+            .any(|property| {
+                property.attribute("name") == Some("collision")
+                    && property.attribute("value") == Some("true")
+            });
+        if collision {
+            flags = flags | TileFlags::COLLISION;
+        }
+        if let Some(animation) = tile
+            .children()
+            .find(|child| child.has_tag_name("animation"))
+        {
+            let frames = animation
+                .children()
+                .filter(|child| child.has_tag_name("frame"))
+                .map(|frame| {
+                    Ok((
+                        attribute(frame, "tileid")?,
+                        Duration::from_millis(attribute(frame, "duration")?),
+                    ))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            tileset.animations.insert(id, TileAnimation { frames });
+            flags = flags | TileFlags::ANIMATED;
+        }
+        properties.insert(id, flags);
+    }
+    Ok((tileset, properties))
+}
+
+fn parse_layer(
+    node: Node,
+    order: i32,
+    first_gid: u32,
+    properties: &TileProperties,
+) -> anyhow::Result<TileLayer> {
+    let name = node.attribute("name").unwrap_or_default();
+    let mut layer = TileLayer::new(
+        name,
+        order,
+        attribute(node, "width")?,
+        attribute(node, "height")?,
+    );
+    layer.visible = node.attribute("visible") != Some("0");
+
+    let data = node
+        .children()
+        .find(|child| child.has_tag_name("data"))
+        .with_context(|| format!("layer {name} has no data"))?;
+    if data.attribute("encoding") != Some("csv") {
+        bail!("layer {name} is not CSV encoded");
+    }
+
+    let width = layer.width();
+    let gids = data.text().unwrap_or_default().split(',');
+    for (position, gid) in (0..).zip(gids.map(str::trim).filter(|gid| !gid.is_empty())) {
+        let gid: u32 = gid
+            .parse()
+            .with_context(|| format!("layer {name} has invalid tile {gid}"))?;
+        let id = gid & GID_MASK;
+        if id < first_gid {
+            continue;
+        }
+        let index = id - first_gid;
+        let mut tile =
+            Tile::new(index).with_flags(properties.get(&index).copied().unwrap_or_default());
+        if gid & FLIPPED_HORIZONTALLY != 0 {
+            tile = tile.with_flags(TileFlags::FLIP_X);
+        }
+        if gid & FLIPPED_VERTICALLY != 0 {
+            tile = tile.with_flags(TileFlags::FLIP_Y);
+        }
+        if !layer.put(position % width, position / width, Some(tile)) {
+            bail!("layer {name} has more tiles than its size");
+        }
+    }
+    Ok(layer)
+}
+
+/// Parses a required numeric attribute.
+fn attribute<T: std::str::FromStr>(node: Node, name: &str) -> anyhow::Result<T> {
+    let value = node
+        .attribute(name)
+        .with_context(|| format!("<{}> has no {name}", node.tag_name().name()))?;
+    value
+        .parse()
+        .ok()
+        .with_context(|| format!("<{}> has invalid {name} {value:?}", node.tag_name().name()))
+}
@@ -0,0 +1,92 @@
+//! Entity transforms.
+//!
+//! A [`Transform`] places an entity in the world. Coordinates are
+//! right-handed with +Y up, and an unrotated camera looks down -Z, matching
+//! the projection the renderer uses.
+
+use math::{Mat4, Quat, Vec3};
+
+/// Position and orientation of an entity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    /// World position.
+    pub translation: Vec3,
+    /// Orientation as a unit quaternion.
+    pub rotation: Quat,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self {
+            translation: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+        }
+    }
+}
+
+impl Transform {
+    /// Creates an unrotated transform at `translation`.
+    #[must_use]
+    pub fn from_translation(translation: Vec3) -> Self {
+        Self {
+            translation,
+            rotation: Quat::IDENTITY,
+        }
+    }
+
+    /// Creates a transform at `translation` rotated by `yaw` about +Y, then
+    /// `pitch` about the rotated +X, then `roll` about the rotated -Z, in
+    /// radians.
+    #[must_use]
+    pub fn from_euler(translation: Vec3, yaw: f32, pitch: f32, roll: f32) -> Self {
+        Self {
+            translation,
+            rotation: Quat::from_rotation_y(yaw)
+                * Quat::from_rotation_x(pitch)
+                * Quat::from_rotation_z(-roll),
+        }
+    }
+
+    /// Returns this transform turned so its forward axis points at
+    /// `target`, keeping +Y up. Unchanged if `target` is the translation.
+    #[must_use]
+    pub fn looking_at(self, target: Vec3) -> Self {
+        let Some(direction) = (target - self.translation).try_normalize() else {
+            return self;
+        };
+        Self::from_euler(
+            self.translation,
+            (-direction.x).atan2(-direction.z),
+            direction.y.clamp(-1.0, 1.0).asin(),
+            0.0,
+        )
+    }
+
+    /// Returns the direction the transform faces: its -Z axis.
+    #[must_use]
+    pub fn forward(&self) -> Vec3 {
+        self.rotate(Vec3::NEG_Z)
+    }
+
+    /// Returns `vector` rotated by the transform's rotation.
+    #[must_use]
+    pub fn rotate(&self, vector: Vec3) -> Vec3 {
+        self.rotation * vector
+    }
+
+    /// Returns this transform rotated further by `rotation`, applied in the
+    /// transform's own frame.
+    #[must_use]
+    pub fn rotated_locally(self, rotation: Quat) -> Self {
+        Self {
+            rotation: (self.rotation * rotation).normalize(),
+            ..self
+        }
+    }
+
+    /// Returns the local-to-world matrix.
+    #[must_use]
+    pub fn to_matrix(&self) -> Mat4 {
+        Mat4::from_rotation_translation(self.rotation, self.translation)
+    }
+}
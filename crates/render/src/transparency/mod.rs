@@ -0,0 +1,25 @@
+//! Transparent surfaces and water.
+//!
+//! The [`TransparencyPass`] runs after opaque geometry and orders the
+//! view's transparent draws by queue. With [`TransparencyMode::Sorted`]
+//! each queue is drawn back to front with alpha blending; with
+//! [`TransparencyMode::WeightedBlended`] fragments accumulate in any order
+//! and are composited over the scene per pixel ([`OitPixel`]).
+//!
+//! [`Water`] is the stock material drawn in the first transparency queue:
+//! scrolling normal maps, screen-space reflections, and depth-based color
+//! and shore fades read from the opaque scene behind it.
+
+mod oit;
+#[cfg(test)]
+mod oit_test;
+mod pass;
+#[cfg(test)]
+mod pass_test;
+mod water;
+#[cfg(test)]
+mod water_test;
+
+pub use oit::{oit_weight, OitPixel};
+pub use pass::{TransparencyMode, TransparencyPass, TransparentDraw};
+pub use water::{ReflectionHit, Water};
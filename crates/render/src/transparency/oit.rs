@@ -0,0 +1,81 @@
+//! Weighted blended order-independent transparency.
+
+use math::{Vec3, Vec4};
+
+/// Returns the weight of a fragment of coverage `alpha` at normalized
+/// device depth `depth` (0 near, 1 far).
+///
+/// Nearer fragments weigh more, so they dominate the averaged color as
+/// they would when sorted. This is equation 9 of "Weighted Blended
+/// Order-Independent Transparency" paper (JCGT, 2013), clamped so
+/// 16-bit float targets neither underflow nor overflow.
+#[must_use]
+pub fn oit_weight(alpha: f32, depth: f32) -> f32 {
+    let near = 1.0 - depth.clamp(0.0, 1.0);
+    alpha * (3.0e3 * near * near * near).clamp(1.0e-2, 3.0e3)
+}
+
+/// One pixel of the weighted blended accumulation and revealage targets.
+///
+/// Transparent fragments are added in any order with additive blending
+/// into `accumulation` and multiplicative blending into `revealage`; the
+/// composite pass then resolves each pixel over the opaque scene. This is
+/// the CPU reference of what the shaders compute.
+///
+/// # Example
+///
+/// ```
+/// use math::Vec3;
+/// use render::transparency::OitPixel;
+///
+/// let (red, blue) = (Vec3::X, Vec3::Z);
+/// let mut a = OitPixel::default();
+/// a.add(red, 0.5, 0.2);
+/// a.add(blue, 0.5, 0.4);
+/// let mut b = OitPixel::default();
+/// b.add(blue, 0.5, 0.4);
+/// b.add(red, 0.5, 0.2);
+///
+/// let color = a.resolve(Vec3::ZERO);
+/// assert!(color.abs_diff_eq(b.resolve(Vec3::ZERO), 1e-6));
+/// assert!(color.x > color.z, "the nearer red layer dominates");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OitPixel {
+    /// Weighted premultiplied color in `xyz` and weighted coverage in `w`.
+    pub accumulation: Vec4,
+    /// Product of `1 - alpha` over every fragment: how much of the scene
+    /// behind still shows.
+    pub revealage: f32,
+}
+
+impl Default for OitPixel {
+    fn default() -> Self {
+        Self {
+            accumulation: Vec4::ZERO,
+            revealage: 1.0,
+        }
+    }
+}
+
+impl OitPixel {
+    /// Adds a fragment of linear `color` and coverage `alpha` at
+    /// normalized device depth `depth`.
+    pub fn add(&mut self, color: Vec3, alpha: f32, depth: f32) {
+        let alpha = alpha.clamp(0.0, 1.0);
+        let weight = oit_weight(alpha, depth);
+        self.accumulation += (color * alpha).extend(alpha) * weight;
+        self.revealage *= 1.0 - alpha;
+    }
+
+    /// Composites the accumulated fragments over the opaque `background`.
+    #[must_use]
+    pub fn resolve(&self, background: Vec3) -> Vec3 {
+        let coverage = 1.0 - self.revealage;
+        if coverage <= f32::EPSILON {
+            return background;
+        }
+        let average = self.accumulation.truncate() / self.accumulation.w.max(1.0e-5);
+        average * coverage + background * self.revealage
+    }
+}
@@ -0,0 +1,75 @@
+//! Unit tests for weighted blended transparency.
+
+use super::oit::{oit_weight, OitPixel};
+use math::Vec3;
+
+fn assert_vec_close(actual: Vec3, expected: Vec3, tolerance: f32) {
+    assert!(
+        actual.abs_diff_eq(expected, tolerance),
+        "{actual} != {expected}"
+    );
+}
+
+/// Composites `layers` of (color, alpha), farthest first, with alpha
+/// blending, as the sorted path does.
+fn sorted(background: Vec3, layers: &[(Vec3, f32)]) -> Vec3 {
+    layers.iter().fold(background, |below, &(color, alpha)| {
+        below.lerp(color, alpha)
+    })
+}
+
+/// Verifies nearer fragments weigh more and weights stay in the range
+/// half-float targets hold.
+#[test]
+fn weights_favor_near_fragments() {
+    assert!(oit_weight(1.0, 0.1) > oit_weight(1.0, 0.5));
+    assert!(oit_weight(1.0, 0.5) > oit_weight(1.0, 0.9));
+    assert!((oit_weight(1.0, -1.0) - 3.0e3).abs() < 1e-3);
+    assert!((oit_weight(1.0, 1.0) - 1.0e-2).abs() < 1e-6);
+    assert!((oit_weight(0.5, 1.0) - 0.5e-2).abs() < 1e-6);
+}
+
+/// Verifies a single layer resolves exactly as alpha blending would, and
+/// an empty pixel shows the background.
+#[test]
+fn single_layers_match_alpha_blending() {
+    let background = Vec3::new(0.2, 0.4, 0.6);
+    assert_eq!(OitPixel::default().resolve(background), background);
+
+    let mut pixel = OitPixel::default();
+    pixel.add(Vec3::X, 0.25, 0.5);
+    assert_vec_close(
+        pixel.resolve(background),
+        sorted(background, &[(Vec3::X, 0.25)]),
+        1e-6,
+    );
+
+    let mut opaque = OitPixel::default();
+    opaque.add(Vec3::Y, 1.0, 0.5);
+    assert_vec_close(opaque.resolve(background), Vec3::Y, 1e-6);
+}
+
+/// Verifies stacked layers resolve the same in any order and close to
+/// sorted blending, with the nearer layer dominating.
+#[test]
+fn stacked_layers_are_order_independent() {
+    let background = Vec3::ZERO;
+    let (far, near) = ((Vec3::Z, 0.4, 0.6), (Vec3::X, 0.4, 0.2));
+    let mut forward = OitPixel::default();
+    let mut backward = OitPixel::default();
+    for (color, alpha, depth) in [far, near] {
+        forward.add(color, alpha, depth);
+    }
+    for (color, alpha, depth) in [near, far] {
+        backward.add(color, alpha, depth);
+    }
+    let resolved = forward.resolve(background);
+    assert_vec_close(resolved, backward.resolve(background), 1e-6);
+    assert!((forward.revealage - 0.36).abs() < 1e-6);
+
+    let reference = sorted(background, &[(far.0, far.1), (near.0, near.1)]);
+    assert_vec_close(resolved, reference, 0.2);
+    assert!(resolved.x > resolved.z);
+    // Total coverage is exact; only the mix of colors is approximate.
+    assert!((resolved.element_sum() - reference.element_sum()).abs() < 1e-5);
+}
@@ -0,0 +1,131 @@
+//! The transparency pass: ordering transparent draws for a view.
+
+use math::{Aabb, Mat4, Vec3, Vec4};
+
+/// How overlapping transparent surfaces are composited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransparencyMode {
+    /// Draws are sorted back to front and alpha blended over the scene.
+    /// Exact for non-intersecting surfaces, but sorts per draw, so
+    /// intersecting or interleaved surfaces can pop as the camera moves.
+    #[default]
+    Sorted,
+    /// Draws accumulate into weighted color and revealage targets in any
+    /// order, then one full-screen pass composites them over the scene
+    /// ([`OitPixel`](super::OitPixel)). Stable under any overlap, at the
+    /// cost of approximate colors where many layers stack.
+    WeightedBlended,
+}
+
+/// One transparent draw to order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransparentDraw<T> {
+    /// What the caller draws, e.g. an entity or a batch index.
+    pub item: T,
+    /// World-space bounds; their center is the sort depth.
+    pub bounds: Aabb,
+    /// Queue the draw belongs to: lower queues draw first regardless of
+    /// depth, e.g. water before particles.
+    pub queue: i32,
+}
+
+impl<T> TransparentDraw<T> {
+    /// Creates a draw of `item` in queue 0.
+    #[must_use]
+    pub fn new(item: T, bounds: Aabb) -> Self {
+        Self {
+            item,
+            bounds,
+            queue: 0,
+        }
+    }
+
+    /// Sets the draw's queue.
+    #[must_use]
+    pub fn with_queue(mut self, queue: i32) -> Self {
+        self.queue = queue;
+        self
+    }
+}
+
+/// Transparent stage of the frame, run after opaque geometry and the
+/// sky, reading the depth buffer but not writing it.
+///
+/// # Example
+///
+/// ```
+/// use math::{Aabb, Mat4, Vec3, Vec4};
+/// use render::transparency::{TransparencyMode, TransparencyPass, TransparentDraw};
+///
+/// // A camera at the origin looking down -Z, depth 0 near and 1 far.
+/// let view_proj = Mat4::from_cols(Vec4::X, Vec4::Y, Vec4::new(0.0, 0.0, -1.0, -1.0), Vec4::new(0.0, 0.0, -0.1, 0.0));
+/// let glass = |z: f32| Aabb::from_center_half_extents(Vec3::new(0.0, 0.0, z), Vec3::splat(0.5));
+///
+/// let pass = TransparencyPass::new(TransparencyMode::Sorted);
+/// let order = pass.order(
+///     &view_proj,
+///     [TransparentDraw::new("near", glass(-2.0)), TransparentDraw::new("far", glass(-8.0))],
+/// );
+/// assert_eq!(order, ["far", "near"]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransparencyPass {
+    mode: TransparencyMode,
+}
+
+impl TransparencyPass {
+    /// Creates the stage compositing with `mode`.
+    #[must_use]
+    pub fn new(mode: TransparencyMode) -> Self {
+        Self { mode }
+    }
+
+    /// Returns the compositing mode in use.
+    #[must_use]
+    pub fn mode(&self) -> TransparencyMode {
+        self.mode
+    }
+
+    /// Returns `true` if the pass needs the accumulation and revealage
+    /// targets and a composite pass.
+    #[must_use]
+    pub fn needs_oit_targets(&self) -> bool {
+        self.mode == TransparencyMode::WeightedBlended
+    }
+
+    /// Orders `draws` for the view-projection `view_proj`: by ascending
+    /// queue, then, when sorted, from the farthest bounds center to the
+    /// nearest. Weighted blending keeps input order within a queue, which
+    /// keeps batches of the same material together.
+    pub fn order<T>(
+        &self,
+        view_proj: &Mat4,
+        draws: impl IntoIterator<Item = TransparentDraw<T>>,
+    ) -> Vec<T> {
+        let mut keyed: Vec<(i32, f32, T)> = draws
+            .into_iter()
+            .map(|draw| {
+                let depth = match self.mode {
+                    TransparencyMode::Sorted => view_depth(view_proj, draw.bounds.center()),
+                    TransparencyMode::WeightedBlended => 0.0,
+                };
+                (draw.queue, depth, draw.item)
+            })
+            .collect();
+        keyed.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.total_cmp(&a.1)));
+        keyed.into_iter().map(|(_, _, item)| item).collect()
+    }
+}
+
+/// Returns the distance along the view direction of `point`, the clip
+/// `w` of perspective projections; orthographic projections fall back to
+/// normalized device depth.
+fn view_depth(view_proj: &Mat4, point: Vec3) -> f32 {
+    let clip: Vec4 = *view_proj * point.extend(1.0);
+    let perspective = view_proj.row(3).truncate() != Vec3::ZERO;
+    if perspective {
+        clip.w
+    } else {
+        clip.z / clip.w
+    }
+}
@@ -0,0 +1,76 @@
+//! Unit tests for the transparency pass.
+
+use super::pass::{TransparencyMode, TransparencyPass, TransparentDraw};
+use super::water::Water;
+use math::{Aabb, Mat4, Vec3, Vec4};
+
+/// A camera at the origin looking down `-Z`, depth 0 at 0.1 and 1 at
+/// infinity.
+fn perspective() -> Mat4 {
+    Mat4::from_cols(
+        Vec4::X,
+        Vec4::Y,
+        Vec4::new(0.0, 0.0, -1.0, -1.0),
+        Vec4::new(0.0, 0.0, -0.1, 0.0),
+    )
+}
+
+fn at(z: f32) -> Aabb {
+    Aabb::from_center_half_extents(Vec3::new(0.0, 0.0, z), Vec3::splat(0.5))
+}
+
+/// Verifies sorted draws go back to front within each queue, and lower
+/// queues first.
+#[test]
+fn sorts_back_to_front_by_queue() {
+    let pass = TransparencyPass::new(TransparencyMode::Sorted);
+    assert!(!pass.needs_oit_targets());
+    let order = pass.order(
+        &perspective(),
+        [
+            TransparentDraw::new("glass near", at(-2.0)),
+            TransparentDraw::new("smoke", at(-5.0)).with_queue(10),
+            TransparentDraw::new("glass far", at(-9.0)),
+            Water::draw("lake", at(-1.0)),
+            TransparentDraw::new("behind", at(3.0)),
+        ],
+    );
+    assert_eq!(
+        order,
+        ["lake", "glass far", "glass near", "behind", "smoke"]
+    );
+}
+
+/// Verifies orthographic views sort by device depth.
+#[test]
+fn sorts_orthographic_views() {
+    let orthographic = Mat4::from_cols(Vec4::X, Vec4::Y, Vec4::new(0.0, 0.0, -0.01, 0.0), Vec4::W);
+    let pass = TransparencyPass::new(TransparencyMode::Sorted);
+    let order = pass.order(
+        &orthographic,
+        [
+            TransparentDraw::new(1, at(-10.0)),
+            TransparentDraw::new(2, at(-50.0)),
+        ],
+    );
+    assert_eq!(order, [2, 1]);
+}
+
+/// Verifies weighted blending only orders by queue, keeping input order
+/// within one.
+#[test]
+fn weighted_blending_keeps_input_order() {
+    let pass = TransparencyPass::new(TransparencyMode::WeightedBlended);
+    assert_eq!(pass.mode(), TransparencyMode::WeightedBlended);
+    assert!(pass.needs_oit_targets());
+    let order = pass.order(
+        &perspective(),
+        [
+            TransparentDraw::new(0, at(-2.0)),
+            TransparentDraw::new(1, at(-9.0)).with_queue(1),
+            TransparentDraw::new(2, at(-9.0)),
+            Water::draw(3, at(-4.0)),
+        ],
+    );
+    assert_eq!(order, [3, 0, 2, 1]);
+}
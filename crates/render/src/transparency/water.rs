@@ -0,0 +1,245 @@
+//! The stock water material.
+
+use super::pass::TransparentDraw;
+use crate::material::Material;
+use crate::target::TextureHandle;
+use math::interpolation::smoothstep;
+use math::{Aabb, Color, Mat4, Vec2, Vec3, Vec4};
+use rustgine_core::Label;
+
+/// Reflectance of water seen head-on.
+const WATER_F0: f32 = 0.02;
+
+/// Where a screen-space reflection ray hit the scene.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReflectionHit {
+    /// Screen coordinates of the reflected point, `(0, 0)` at the top-left.
+    pub uv: Vec2,
+    /// How much to trust the hit in `0..=1`, falling off towards the end of
+    /// the ray and the screen edges, where the fallback reflection takes
+    /// over.
+    pub confidence: f32,
+}
+
+/// A water surface drawn in the transparency pass.
+///
+/// Two scrolling copies of [`normal_map`](Self::normal_map) make the
+/// ripples. The shader reads the opaque scene's color and depth behind
+/// the surface: the water darkens from [`shallow`](Self::shallow) to
+/// [`deep`](Self::deep) with the depth of water the view ray crosses, and
+/// fades out over [`shore_fade`](Self::shore_fade) where it meets the
+/// ground. Reflections are traced in screen space along the rippled
+/// normal, falling back to the sky where the ray leaves the screen.
+///
+/// # Example
+///
+/// ```
+/// use math::Vec3;
+/// use render::transparency::Water;
+///
+/// let water = Water::new("water/ripples.png");
+/// assert_eq!(water.shore_alpha(0.0), 0.0);
+/// assert_eq!(water.shore_alpha(2.0), 1.0);
+///
+/// // Looking straight down into deep water, little of the ground shows.
+/// let ground = Vec3::ONE;
+/// let color = water.shade(20.0, ground, Vec3::ZERO, 1.0);
+/// assert!(color.x < 0.1);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Water {
+    /// Tangent-space normal map asset path.
+    pub normal_map: Label,
+    /// Normal map repeats per world unit.
+    pub tiling: f32,
+    /// Scroll velocities of the two normal map layers, in texture widths
+    /// per second.
+    pub scroll: [Vec2; 2],
+    /// How strongly the ripples tilt the surface normal.
+    pub normal_strength: f32,
+    /// Color of shallow water.
+    pub shallow: Color,
+    /// Color of deep water.
+    pub deep: Color,
+    /// Depth of water over which the ground fades out, in world units.
+    pub depth_fade: f32,
+    /// Depth of water over which the surface fades in from the shore, in
+    /// world units.
+    pub shore_fade: f32,
+    /// Scales the reflections.
+    pub reflection: f32,
+    /// Furthest distance reflection rays are traced, in world units.
+    pub reflection_distance: f32,
+    /// Steps along each reflection ray.
+    pub reflection_steps: u32,
+    /// Depth behind the scene a ray may be and still hit it, in world units.
+    pub reflection_thickness: f32,
+}
+
+impl Water {
+    /// Transparency queue of water surfaces, before other transparent
+    /// draws: water reads the opaque scene behind it, so glass and
+    /// particles in front must blend over the finished water.
+    pub const QUEUE: i32 = -100;
+
+    /// Creates water rippled by `normal_map`, clear blue-green and
+    /// reflective.
+    #[must_use]
+    pub fn new(normal_map: impl Into<Label>) -> Self {
+        Self {
+            normal_map: normal_map.into(),
+            tiling: 0.1,
+            scroll: [Vec2::new(0.02, 0.01), Vec2::new(-0.013, 0.017)],
+            normal_strength: 0.5,
+            shallow: Color::linear_rgb(0.1, 0.45, 0.45),
+            deep: Color::linear_rgb(0.0, 0.06, 0.12),
+            depth_fade: 4.0,
+            shore_fade: 0.5,
+            reflection: 1.0,
+            reflection_distance: 50.0,
+            reflection_steps: 32,
+            reflection_thickness: 0.5,
+        }
+    }
+
+    /// Returns the transparent draw of a water surface `item` with world
+    /// `bounds`, in the water [`QUEUE`](Self::QUEUE).
+    #[must_use]
+    pub fn draw<T>(item: T, bounds: Aabb) -> TransparentDraw<T> {
+        TransparentDraw::new(item, bounds).with_queue(Self::QUEUE)
+    }
+
+    /// Returns the normal map coordinates of both layers at the world
+    /// position `xz` after `time` seconds, wrapped to `0..1`.
+    #[must_use]
+    pub fn normal_uvs(&self, xz: Vec2, time: f32) -> [Vec2; 2] {
+        let base = xz * self.tiling;
+        // The second layer is tiled differently so the layers never align.
+        [
+            (base + self.scroll[0] * time).fract_gl(),
+            (base * 1.37 + self.scroll[1] * time).fract_gl(),
+        ]
+    }
+
+    /// Combines the tangent-space samples `a` and `b` of the two layers
+    /// into a world-space normal of the surface, which faces `+Y`.
+    #[must_use]
+    pub fn surface_normal(&self, a: Vec3, b: Vec3) -> Vec3 {
+        // Whiteout blending: sum the slopes, multiply the heights.
+        let slope = (a.truncate() + b.truncate()) * self.normal_strength;
+        Vec3::new(slope.x, a.z * b.z, -slope.y).normalize_or(Vec3::Y)
+    }
+
+    /// Returns the surface's opacity at `depth` world units of water over
+    /// the opaque scene, fading out towards the shore.
+    #[must_use]
+    pub fn shore_alpha(&self, depth: f32) -> f32 {
+        smoothstep(0.0, self.shore_fade.max(1e-4), depth)
+    }
+
+    /// Returns the fraction of light reflected towards the viewer, with
+    /// `n_dot_v` the cosine between the surface normal and the direction
+    /// to the viewer.
+    #[must_use]
+    pub fn fresnel(&self, n_dot_v: f32) -> f32 {
+        let grazing = (1.0 - n_dot_v.clamp(0.0, 1.0)).powi(5);
+        ((WATER_F0 + (1.0 - WATER_F0) * grazing) * self.reflection).clamp(0.0, 1.0)
+    }
+
+    /// Shades the surface over `depth` world units of water: `refracted` is
+    /// the scene color behind the surface, `reflected` the traced or
+    /// fallback reflection, and `n_dot_v` as in [`fresnel`](Self::fresnel).
+    #[must_use]
+    pub fn shade(&self, depth: f32, refracted: Vec3, reflected: Vec3, n_dot_v: f32) -> Vec3 {
+        let transmittance = (-depth.max(0.0) / self.depth_fade.max(1e-4)).exp();
+        let body = self
+            .shallow
+            .to_linear()
+            .rgb_vec()
+            .lerp(self.deep.to_linear().rgb_vec(), 1.0 - transmittance);
+        let underwater = body.lerp(refracted, transmittance);
+        underwater.lerp(reflected, self.fresnel(n_dot_v))
+    }
+
+    /// Traces a reflection ray from the view-space `origin` along the
+    /// view-space `direction` through the scene's depth, returning where it
+    /// hit on screen.
+    ///
+    /// `projection` maps view space, looking down `-Z`, to clip space, and
+    /// `scene_depth` returns the view distance of the opaque scene at
+    /// screen coordinates. Rays leaving the screen or passing behind
+    /// surfaces thicker than [`reflection_thickness`](Self::reflection_thickness)
+    /// miss.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn trace_reflection(
+        &self,
+        projection: &Mat4,
+        origin: Vec3,
+        direction: Vec3,
+        scene_depth: impl Fn(Vec2) -> f32,
+    ) -> Option<ReflectionHit> {
+        let direction = direction.normalize_or_zero();
+        let steps = self.reflection_steps.max(1);
+        for step in 1..=steps {
+            let travelled = step as f32 / steps as f32;
+            let point = origin + direction * (self.reflection_distance * travelled);
+            let clip: Vec4 = *projection * point.extend(1.0);
+            if clip.w <= f32::EPSILON {
+                return None;
+            }
+            let ndc = clip.truncate() / clip.w;
+            if ndc.x.abs() > 1.0 || ndc.y.abs() > 1.0 {
+                return None;
+            }
+            let uv = Vec2::new((ndc.x + 1.0) * 0.5, (1.0 - ndc.y) * 0.5);
+            let behind = -point.z - scene_depth(uv);
+            if behind >= 0.0 {
+                if behind > self.reflection_thickness {
+                    return None;
+                }
+                let edge = 1.0 - smoothstep(0.8, 1.0, ndc.x.abs().max(ndc.y.abs()));
+                return Some(ReflectionHit {
+                    uv,
+                    confidence: edge * (1.0 - travelled * travelled),
+                });
+            }
+        }
+        None
+    }
+
+    /// Builds a material from `shader` for drawing the water at `time`
+    /// seconds, reading the opaque scene's `scene_color` and `scene_depth`
+    /// render targets.
+    ///
+    /// Sets the `normal_map` texture, the `scene_color` and `scene_depth`
+    /// targets, the `scroll` color holding both layers' velocities as
+    /// `(x0, y0, x1, y1)`, the `shallow` and `deep` colors, and the
+    /// remaining fields and `time` as floats of the same name.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn material(
+        &self,
+        shader: &str,
+        scene_color: TextureHandle,
+        scene_depth: TextureHandle,
+        time: f32,
+    ) -> Material {
+        let [a, b] = self.scroll;
+        Material::new(shader)
+            .with_texture("normal_map", self.normal_map)
+            .with_render_target("scene_color", scene_color)
+            .with_render_target("scene_depth", scene_depth)
+            .with_float("time", time)
+            .with_float("tiling", self.tiling)
+            .with_color("scroll", Color::linear_rgba(a.x, a.y, b.x, b.y))
+            .with_float("normal_strength", self.normal_strength)
+            .with_color("shallow", self.shallow)
+            .with_color("deep", self.deep)
+            .with_float("depth_fade", self.depth_fade)
+            .with_float("shore_fade", self.shore_fade)
+            .with_float("reflection", self.reflection)
+            .with_float("reflection_distance", self.reflection_distance)
+            .with_float("reflection_steps", self.reflection_steps as f32)
+            .with_float("reflection_thickness", self.reflection_thickness)
+    }
+}
@@ -0,0 +1,159 @@
+//! Unit tests for the water material.
+
+use super::water::Water;
+use crate::target::{RenderTextureDesc, RenderTextures};
+use crate::{MaterialParam, RenderLimits};
+use math::{Color, Mat4, Vec2, Vec3, Vec4};
+use rustgine_core::Label;
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+}
+
+fn water() -> Water {
+    Water::new("water/ripples.png")
+}
+
+/// Verifies both normal layers scroll at their own speed and wrap.
+#[test]
+fn scrolls_normal_layers() {
+    let water = Water {
+        tiling: 0.5,
+        scroll: [Vec2::new(0.1, 0.0), Vec2::new(0.0, -0.2)],
+        ..water()
+    };
+    let [a, b] = water.normal_uvs(Vec2::new(1.0, 0.0), 0.0);
+    assert!(a.abs_diff_eq(Vec2::new(0.5, 0.0), 1e-6));
+    assert!(b.abs_diff_eq(Vec2::new(0.685, 0.0), 1e-6));
+
+    let [a, b] = water.normal_uvs(Vec2::new(1.0, 0.0), 10.0);
+    assert!(a.abs_diff_eq(Vec2::new(0.5, 0.0), 1e-5), "{a}");
+    assert!(b.abs_diff_eq(Vec2::new(0.685, 0.0), 1e-5), "{b}");
+    let [a, _] = water.normal_uvs(Vec2::ZERO, 2.5);
+    assert!(a.abs_diff_eq(Vec2::new(0.25, 0.0), 1e-6));
+}
+
+/// Verifies flat samples leave the surface facing up and ripples tilt it
+/// by the normal strength.
+#[test]
+fn combines_ripple_normals() {
+    let water = water();
+    assert_eq!(water.surface_normal(Vec3::Z, Vec3::Z), Vec3::Y);
+    let tilt = Vec3::new(0.6, 0.0, 0.8);
+    let normal = water.surface_normal(tilt, Vec3::Z);
+    assert_close(normal.length(), 1.0);
+    assert!(normal.x > 0.0 && normal.y > 0.9, "{normal}");
+    let strong = Water {
+        normal_strength: 1.0,
+        ..water
+    };
+    assert!(strong.surface_normal(tilt, Vec3::Z).x > normal.x);
+    assert!(strong.surface_normal(tilt, tilt).x > strong.surface_normal(tilt, Vec3::Z).x);
+}
+
+/// Verifies the water fades in from the shore and darkens with depth,
+/// reflecting more at grazing angles.
+#[test]
+fn shades_by_depth_and_angle() {
+    let water = water();
+    assert_close(water.shore_alpha(0.25), 0.5);
+    assert_close(water.shore_alpha(-1.0), 0.0);
+
+    let ground = Vec3::ONE;
+    let shallow = water.shade(0.0, ground, Vec3::ZERO, 1.0);
+    assert!(shallow.abs_diff_eq(ground * 0.98, 1e-5), "{shallow}");
+    let deep = water.shade(100.0, ground, Vec3::ZERO, 1.0);
+    assert!(
+        deep.abs_diff_eq(Vec3::new(0.0, 0.06, 0.12) * 0.98, 1e-4),
+        "{deep}"
+    );
+
+    assert_close(water.fresnel(1.0), 0.02);
+    assert_close(water.fresnel(0.0), 1.0);
+    let sky = Vec3::new(0.5, 0.7, 1.0);
+    let grazing = water.shade(100.0, ground, sky, 0.0);
+    assert!(grazing.abs_diff_eq(sky, 1e-5));
+    let matte = Water {
+        reflection: 0.0,
+        ..water
+    };
+    assert_close(matte.fresnel(0.0), 0.0);
+}
+
+/// Verifies reflection rays hit the scene they pass behind, and miss when
+/// leaving the screen or passing behind thick geometry.
+#[test]
+fn traces_screen_space_reflections() {
+    let projection = Mat4::from_cols(
+        Vec4::X,
+        Vec4::Y,
+        Vec4::new(0.0, 0.0, -1.0, -1.0),
+        Vec4::new(0.0, 0.0, -0.1, 0.0),
+    );
+    let water = Water {
+        reflection_distance: 10.0,
+        reflection_steps: 10,
+        ..water()
+    };
+    // A wall 8 units away in the lower half of the screen, sky above.
+    let wall = |uv: Vec2| if uv.y > 0.5 { 8.0 } else { f32::INFINITY };
+    let origin = Vec3::new(0.0, -1.0, -2.0);
+
+    let hit = water
+        .trace_reflection(&projection, origin, Vec3::NEG_Z, wall)
+        .unwrap();
+    assert!(
+        hit.uv.y > 0.5 && (hit.uv.x - 0.5).abs() < 1e-5,
+        "{:?}",
+        hit.uv
+    );
+    assert!(hit.confidence > 0.0 && hit.confidence < 1.0);
+
+    let upwards = Vec3::new(0.0, 1.0, -1.0);
+    assert!(water
+        .trace_reflection(&projection, origin, upwards, wall)
+        .is_none());
+    let sideways = Vec3::new(1.0, 0.0, -0.1);
+    assert!(water
+        .trace_reflection(&projection, origin, sideways, wall)
+        .is_none());
+    let thin = Water {
+        reflection_thickness: 0.1,
+        reflection_steps: 2,
+        ..water
+    };
+    assert!(thin
+        .trace_reflection(&projection, origin, Vec3::NEG_Z, wall)
+        .is_none());
+}
+
+/// Verifies water materials carry the normal map, scene targets, and
+/// settings.
+#[test]
+fn builds_materials() {
+    let mut textures = RenderTextures::new(RenderLimits::default());
+    let color = textures.create(RenderTextureDesc::new(64, 64)).unwrap();
+    let depth = textures.create(RenderTextureDesc::new(64, 64)).unwrap();
+    let material = water().material("water.wgsl", color, depth, 12.5);
+
+    assert_eq!(
+        material.texture("normal_map", None),
+        Some(Label::new("water/ripples.png"))
+    );
+    assert_eq!(material.render_target("scene_color"), Some(color));
+    assert_eq!(material.render_target("scene_depth"), Some(depth));
+    assert_eq!(
+        material.params.get("time"),
+        Some(&MaterialParam::Float(12.5))
+    );
+    assert_eq!(
+        material.params.get("scroll"),
+        Some(&MaterialParam::Color(Color::linear_rgba(
+            0.02, 0.01, -0.013, 0.017
+        )))
+    );
+    assert_eq!(
+        material.params.get("reflection_steps"),
+        Some(&MaterialParam::Float(32.0))
+    );
+}
@@ -0,0 +1,371 @@
+//! Batched drawing of UI and text quads.
+//!
+//! Widgets emit quads in painter's order: images from a UI atlas, solid
+//! panels, and text glyphs from a font atlas. [`UiBatcher`] clips them to
+//! nested clip rectangles and merges them into as few draw calls as the
+//! order allows:
+//!
+//! - Clipping trims quads and their texture coordinates on the CPU, so
+//!   scroll views never split a batch or change the scissor rectangle.
+//! - Untextured quads sample the shader's built-in white and join batches
+//!   of any texture.
+//! - A quad joins the latest earlier batch of its texture when it overlaps
+//!   nothing drawn after that batch, so painter's order is only kept where
+//!   quads actually cover each other.
+//!
+//! A typical HUD of atlas images, panels, and text in one font draws in two
+//! calls; [`RenderStatsRecorder::draw_ui`](crate::RenderStatsRecorder::draw_ui)
+//! adds them to the frame's statistics.
+
+use crate::nine_slice::SliceQuad;
+use math::{Color, Rect, Vec2};
+use rustgine_core::Label;
+use std::ops::Range;
+use tracing::trace;
+
+/// How a UI quad is shaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiQuadKind {
+    /// A flat color.
+    Solid,
+    /// A texture tinted by the color.
+    Image,
+    /// A glyph whose coverage is read from the font atlas's red channel.
+    Text,
+}
+
+impl UiQuadKind {
+    /// Returns the value of the vertex's `mode` attribute.
+    #[must_use]
+    pub fn shader_value(self) -> f32 {
+        match self {
+            Self::Solid => 0.0,
+            Self::Image => 1.0,
+            Self::Text => 2.0,
+        }
+    }
+}
+
+/// A rectangle drawn by a widget.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UiQuad {
+    /// Screen rectangle in pixels.
+    pub rect: Rect,
+    /// Texture coordinates.
+    pub uv: Rect,
+    /// Tint, or the fill of solid quads.
+    pub color: Color,
+    /// Texture asset, `None` for solid quads.
+    pub texture: Option<Label>,
+    /// Shading.
+    pub kind: UiQuadKind,
+}
+
+impl UiQuad {
+    /// Creates a quad filled with `color`.
+    #[must_use]
+    pub fn solid(rect: Rect, color: Color) -> Self {
+        Self {
+            rect,
+            uv: Rect::new(Vec2::ZERO, Vec2::ONE),
+            color,
+            texture: None,
+            kind: UiQuadKind::Solid,
+        }
+    }
+
+    /// Creates a quad showing the `uv` region of `texture`.
+    #[must_use]
+    pub fn image(rect: Rect, texture: impl Into<Label>, uv: Rect) -> Self {
+        Self {
+            rect,
+            uv,
+            color: Color::WHITE,
+            texture: Some(texture.into()),
+            kind: UiQuadKind::Image,
+        }
+    }
+
+    /// Creates one quad of a 9-slice image of `texture`.
+    #[must_use]
+    pub fn slice(texture: impl Into<Label>, slice: &SliceQuad) -> Self {
+        Self::image(slice.position, texture, slice.uv)
+    }
+
+    /// Creates a glyph quad showing the `uv` region of the `font` atlas in
+    /// `color`.
+    #[must_use]
+    pub fn glyph(rect: Rect, font: impl Into<Label>, uv: Rect, color: Color) -> Self {
+        Self {
+            rect,
+            uv,
+            color,
+            texture: Some(font.into()),
+            kind: UiQuadKind::Text,
+        }
+    }
+
+    /// Returns the quad tinted with `color`.
+    #[must_use]
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+/// One vertex of the UI vertex buffer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UiVertex {
+    /// Screen position in pixels.
+    pub position: Vec2,
+    /// Texture coordinates.
+    pub uv: Vec2,
+    /// Linear RGBA tint.
+    pub color: [f32; 4],
+    /// [`UiQuadKind::shader_value`] of the quad.
+    pub mode: f32,
+}
+
+/// One draw call of a [`UiFrame`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct UiBatch {
+    /// Texture bound for the draw, `None` if every quad is solid.
+    pub texture: Option<Label>,
+    /// Range of [`UiFrame::indices`] drawn.
+    pub indices: Range<u32>,
+}
+
+/// Counters of one batched UI frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct UiBatchStats {
+    /// Quads drawn.
+    pub quads: u32,
+    /// Quads trimmed by a clip rectangle.
+    pub clipped: u32,
+    /// Quads skipped because they were fully clipped.
+    pub culled: u32,
+    /// Draw calls.
+    pub batches: u32,
+}
+
+/// Vertex and index data of the UI, with the draw calls that render it.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UiFrame {
+    /// Four vertices per quad.
+    pub vertices: Vec<UiVertex>,
+    /// Two triangles per quad.
+    pub indices: Vec<u32>,
+    /// Draw calls, in submission order.
+    pub batches: Vec<UiBatch>,
+    /// Counters of the frame.
+    pub stats: UiBatchStats,
+}
+
+/// A batch while the frame is collected.
+#[derive(Debug, Clone)]
+struct PendingBatch {
+    texture: Option<Label>,
+    bounds: Rect,
+    quads: Vec<UiQuad>,
+}
+
+/// Collects a frame of UI quads into batches.
+///
+/// # Example
+///
+/// ```
+/// use math::{Color, Rect, Vec2};
+/// use render::ui_batch::{UiBatcher, UiQuad};
+///
+/// let rect = |x0, y0, x1, y1| Rect::new(Vec2::new(x0, y0), Vec2::new(x1, y1));
+/// let mut batcher = UiBatcher::new(rect(0.0, 0.0, 1280.0, 720.0));
+/// // Three rows of an icon and a label, alternating textures.
+/// for y in [0.0, 40.0, 80.0] {
+///     batcher.add(UiQuad::image(rect(0.0, y, 32.0, y + 32.0), "ui/atlas.png", rect(0.0, 0.0, 0.25, 0.25)));
+///     batcher.add(UiQuad::glyph(rect(40.0, y, 48.0, y + 16.0), "fonts/body.png", rect(0.0, 0.0, 0.1, 0.1), Color::WHITE));
+/// }
+/// // A scroll view showing only part of its content.
+/// batcher.push_clip(rect(0.0, 200.0, 300.0, 300.0));
+/// batcher.add(UiQuad::solid(rect(0.0, 250.0, 300.0, 400.0), Color::BLACK));
+/// batcher.add(UiQuad::solid(rect(0.0, 500.0, 300.0, 600.0), Color::BLACK));
+/// batcher.pop_clip();
+///
+/// let frame = batcher.finish();
+/// assert_eq!(frame.batches.len(), 2);
+/// assert_eq!((frame.stats.quads, frame.stats.clipped, frame.stats.culled), (7, 1, 1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct UiBatcher {
+    /// Clip rectangles, the viewport first.
+    clips: Vec<Rect>,
+    batches: Vec<PendingBatch>,
+    stats: UiBatchStats,
+}
+
+impl UiBatcher {
+    /// Creates a batcher clipping to `viewport`.
+    #[must_use]
+    pub fn new(viewport: Rect) -> Self {
+        Self {
+            clips: vec![viewport],
+            batches: Vec::new(),
+            stats: UiBatchStats::default(),
+        }
+    }
+
+    /// Returns the current clip rectangle.
+    #[must_use]
+    pub fn clip(&self) -> Rect {
+        self.clips[self.clips.len() - 1]
+    }
+
+    /// Clips following quads to `rect` within the current clip rectangle,
+    /// e.g. for the content of a scroll view.
+    pub fn push_clip(&mut self, rect: Rect) {
+        let clip = self.clip().intersection(&rect).unwrap_or(Rect {
+            min: rect.min,
+            max: rect.min,
+        });
+        self.clips.push(clip);
+    }
+
+    /// Restores the clip rectangle before the last [`UiBatcher::push_clip`];
+    /// the viewport is never popped.
+    pub fn pop_clip(&mut self) {
+        if self.clips.len() > 1 {
+            self.clips.pop();
+        }
+    }
+
+    /// Adds a quad above everything added so far.
+    pub fn add(&mut self, quad: UiQuad) {
+        let Some(quad) = self.clipped(quad) else {
+            self.stats.culled += 1;
+            return;
+        };
+        self.stats.quads += 1;
+        for index in (0..self.batches.len()).rev() {
+            let batch = &mut self.batches[index];
+            let shares_texture = match (&batch.texture, &quad.texture) {
+                (Some(batch), Some(quad)) => batch == quad,
+                _ => true,
+            };
+            if shares_texture {
+                batch.texture = batch.texture.or(quad.texture);
+                batch.bounds = batch.bounds.union(&quad.rect);
+                batch.quads.push(quad);
+                return;
+            }
+            let covered = overlaps(&batch.bounds, &quad.rect)
+                && batch
+                    .quads
+                    .iter()
+                    .any(|other| overlaps(&other.rect, &quad.rect));
+            if covered {
+                break;
+            }
+        }
+        self.batches.push(PendingBatch {
+            texture: quad.texture,
+            bounds: quad.rect,
+            quads: vec![quad],
+        });
+    }
+
+    /// Adds quads in order, see [`UiBatcher::add`].
+    pub fn extend(&mut self, quads: impl IntoIterator<Item = UiQuad>) {
+        for quad in quads {
+            self.add(quad);
+        }
+    }
+
+    /// Returns the frame's geometry and draw calls, emitting its counters
+    /// as a `trace` event on the `render::ui` target, and starts the next
+    /// frame with only the viewport clip.
+    #[must_use]
+    pub fn finish(&mut self) -> UiFrame {
+        let mut frame = UiFrame::default();
+        for batch in self.batches.drain(..) {
+            let start = index(frame.indices.len());
+            for quad in &batch.quads {
+                let base = index(frame.vertices.len());
+                let (rect, uv) = (quad.rect, quad.uv);
+                let color = quad.color.to_array();
+                let mode = quad.kind.shader_value();
+                for (position, uv) in [
+                    (rect.min, uv.min),
+                    (
+                        Vec2::new(rect.max.x, rect.min.y),
+                        Vec2::new(uv.max.x, uv.min.y),
+                    ),
+                    (rect.max, uv.max),
+                    (
+                        Vec2::new(rect.min.x, rect.max.y),
+                        Vec2::new(uv.min.x, uv.max.y),
+                    ),
+                ] {
+                    frame.vertices.push(UiVertex {
+                        position,
+                        uv,
+                        color,
+                        mode,
+                    });
+                }
+                frame
+                    .indices
+                    .extend([0, 1, 2, 0, 2, 3].map(|corner| base + corner));
+            }
+            frame.batches.push(UiBatch {
+                texture: batch.texture,
+                indices: start..index(frame.indices.len()),
+            });
+        }
+        self.stats.batches = index(frame.batches.len());
+        frame.stats = std::mem::take(&mut self.stats);
+        self.clips.truncate(1);
+        trace!(
+            target: "render::ui",
+            quads = frame.stats.quads,
+            clipped = frame.stats.clipped,
+            culled = frame.stats.culled,
+            batches = frame.stats.batches,
+            "ui batches"
+        );
+        frame
+    }
+
+    /// Trims `quad` and its texture coordinates to the clip rectangle, or
+    /// returns `None` if nothing of it remains.
+    // Unclipped edges are copied exactly, so comparing detects trimming.
+    #[allow(clippy::float_cmp)]
+    fn clipped(&mut self, mut quad: UiQuad) -> Option<UiQuad> {
+        let clip = self.clip().intersection(&quad.rect)?;
+        if clip.width() <= 0.0 || clip.height() <= 0.0 {
+            return None;
+        }
+        if clip != quad.rect {
+            // Maps a point of the quad to its texture coordinates.
+            let uv = |point: Vec2| {
+                quad.uv.min + quad.uv.size() * (point - quad.rect.min) / quad.rect.size()
+            };
+            quad.uv = Rect {
+                min: uv(clip.min),
+                max: uv(clip.max),
+            };
+            quad.rect = clip;
+            self.stats.clipped += 1;
+        }
+        Some(quad)
+    }
+}
+
+/// Converts a buffer length to a `u32` index.
+#[allow(clippy::cast_possible_truncation)]
+fn index(len: usize) -> u32 {
+    len as u32
+}
+
+/// Returns `true` if two rectangles share area, not only an edge.
+fn overlaps(a: &Rect, b: &Rect) -> bool {
+    a.min.cmplt(b.max).all() && b.min.cmplt(a.max).all()
+}
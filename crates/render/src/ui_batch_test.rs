@@ -0,0 +1,200 @@
+//! Unit tests for UI batching.
+
+use crate::nine_slice::NineSlice;
+use crate::stats::RenderStatsRecorder;
+use crate::ui_batch::{UiBatcher, UiQuad, UiQuadKind};
+use math::{Color, Rect, Vec2};
+use rustgine_core::Label;
+
+const ATLAS: &str = "ui/hud.png";
+const FONT: &str = "fonts/hud.png";
+
+fn rect(x0: f32, y0: f32, x1: f32, y1: f32) -> Rect {
+    Rect::new(Vec2::new(x0, y0), Vec2::new(x1, y1))
+}
+
+fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert!(
+        actual.len() == expected.len()
+            && actual
+                .iter()
+                .zip(expected)
+                .all(|(a, b)| (a - b).abs() < 1e-5),
+        "{actual:?} != {expected:?}"
+    );
+}
+
+fn icon(x: f32, y: f32) -> UiQuad {
+    UiQuad::image(
+        rect(x, y, x + 32.0, y + 32.0),
+        ATLAS,
+        rect(0.0, 0.0, 0.5, 0.5),
+    )
+}
+
+fn text(x: f32, y: f32, characters: usize) -> Vec<UiQuad> {
+    (0..characters)
+        .map(|i| {
+            #[allow(clippy::cast_precision_loss)]
+            let left = x + i as f32 * 10.0;
+            UiQuad::glyph(
+                rect(left, y, left + 10.0, y + 16.0),
+                FONT,
+                rect(0.0, 0.0, 0.1, 0.1),
+                Color::WHITE,
+            )
+        })
+        .collect()
+}
+
+/// Verifies a typical HUD, with widgets each drawing a panel, an icon, and
+/// a label, draws in two calls.
+#[test]
+fn typical_hud_draws_in_two_calls() {
+    let mut batcher = UiBatcher::new(rect(0.0, 0.0, 1920.0, 1080.0));
+    for (x, y) in [(16.0, 16.0), (16.0, 64.0), (1700.0, 16.0), (900.0, 1000.0)] {
+        // Panel frame, icon on it, then its label over the panel.
+        let frame = NineSlice::uniform(4).quads(
+            rect(0.5, 0.5, 1.0, 1.0),
+            (16, 16),
+            rect(x, y, x + 200.0, y + 40.0),
+        );
+        batcher.extend(frame.iter().map(|slice| UiQuad::slice(ATLAS, slice)));
+        batcher.add(icon(x + 4.0, y + 4.0));
+        batcher.extend(text(x + 40.0, y + 12.0, 12));
+        // Health bar fill.
+        batcher.add(UiQuad::solid(
+            rect(x + 40.0, y + 30.0, x + 190.0, y + 36.0),
+            Color::linear_rgb(0.8, 0.1, 0.1),
+        ));
+    }
+    let frame = batcher.finish();
+    assert_eq!(frame.batches.len(), 2);
+    assert_eq!(frame.batches[0].texture, Some(Label::from(ATLAS)));
+    assert_eq!(frame.batches[1].texture, Some(Label::from(FONT)));
+    assert_eq!(frame.stats.quads, 4 * (9 + 1 + 12 + 1));
+    assert_eq!(frame.stats.batches, 2);
+    assert_eq!(frame.vertices.len(), frame.stats.quads as usize * 4);
+    assert_eq!(frame.indices.len(), frame.stats.quads as usize * 6);
+    let drawn: u32 = frame
+        .batches
+        .iter()
+        .map(|b| b.indices.end - b.indices.start)
+        .sum();
+    assert_eq!(drawn as usize, frame.indices.len());
+}
+
+/// Verifies quads covering a quad of another texture keep painter's order.
+#[test]
+fn keeps_order_where_quads_overlap() {
+    let mut batcher = UiBatcher::new(rect(0.0, 0.0, 800.0, 600.0));
+    batcher.add(icon(0.0, 0.0));
+    batcher.extend(text(0.0, 0.0, 2));
+    // A tooltip icon over the label must be drawn after it.
+    batcher.add(icon(5.0, 5.0));
+    // Away from the tooltip, so it moves back into the label's batch.
+    batcher.extend(text(400.0, 400.0, 1));
+    let frame = batcher.finish();
+    let textures: Vec<_> = frame.batches.iter().map(|b| b.texture).collect();
+    assert_eq!(
+        textures,
+        [Some(ATLAS.into()), Some(FONT.into()), Some(ATLAS.into())]
+    );
+    assert_eq!(frame.batches[1].indices, 6..24);
+    assert_eq!(frame.batches[2].indices, 24..30);
+}
+
+/// Verifies nested clip rectangles trim quads and their texture
+/// coordinates, cull hidden ones, and never split batches.
+#[test]
+fn clips_nested_scroll_views() {
+    let mut batcher = UiBatcher::new(rect(0.0, 0.0, 800.0, 600.0));
+    batcher.push_clip(rect(100.0, 100.0, 300.0, 300.0));
+    batcher.push_clip(rect(0.0, 150.0, 250.0, 500.0));
+    assert_eq!(batcher.clip(), rect(100.0, 150.0, 250.0, 300.0));
+    batcher.add(UiQuad::image(
+        rect(50.0, 100.0, 150.0, 200.0),
+        ATLAS,
+        rect(0.0, 0.0, 1.0, 1.0),
+    ));
+    batcher.add(icon(400.0, 400.0));
+    batcher.pop_clip();
+    batcher.add(icon(100.0, 100.0));
+    batcher.pop_clip();
+    batcher.pop_clip();
+    assert_eq!(batcher.clip(), rect(0.0, 0.0, 800.0, 600.0));
+    batcher.add(icon(700.0, 500.0));
+
+    let frame = batcher.finish();
+    assert_eq!(frame.batches.len(), 1);
+    assert_eq!(
+        (frame.stats.quads, frame.stats.clipped, frame.stats.culled),
+        (3, 1, 1)
+    );
+    let trimmed = &frame.vertices[..4];
+    assert_eq!(trimmed[0].position, Vec2::new(100.0, 150.0));
+    assert_eq!(trimmed[2].position, Vec2::new(150.0, 200.0));
+    assert_eq!(trimmed[0].uv, Vec2::new(0.5, 0.5));
+    assert_eq!(trimmed[2].uv, Vec2::new(1.0, 1.0));
+
+    batcher.push_clip(rect(0.0, 0.0, 10.0, 10.0));
+    batcher.push_clip(rect(20.0, 20.0, 30.0, 30.0));
+    batcher.add(icon(0.0, 0.0));
+    let empty = batcher.finish();
+    assert_eq!((empty.stats.quads, empty.stats.culled), (0, 1));
+    assert!(empty.batches.is_empty());
+    assert_eq!(batcher.clip(), rect(0.0, 0.0, 800.0, 600.0));
+}
+
+/// Verifies solid quads join textured batches and all-solid batches bind
+/// no texture.
+#[test]
+fn solid_quads_join_any_batch() {
+    let mut batcher = UiBatcher::new(rect(0.0, 0.0, 800.0, 600.0));
+    batcher.add(UiQuad::solid(rect(0.0, 0.0, 800.0, 600.0), Color::BLACK));
+    let solid = batcher.finish();
+    assert_eq!(solid.batches[0].texture, None);
+    assert_close(
+        &[solid.vertices[0].mode],
+        &[UiQuadKind::Solid.shader_value()],
+    );
+
+    batcher.add(UiQuad::solid(rect(0.0, 0.0, 800.0, 600.0), Color::BLACK));
+    batcher.extend(text(10.0, 10.0, 3));
+    batcher.add(UiQuad::solid(
+        rect(0.0, 0.0, 50.0, 50.0),
+        Color::WHITE.with_alpha(0.5),
+    ));
+    let mixed = batcher.finish();
+    assert_eq!(mixed.batches.len(), 1);
+    assert_eq!(mixed.batches[0].texture, Some(FONT.into()));
+    assert_close(
+        &[mixed.vertices[4].mode],
+        &[UiQuadKind::Text.shader_value()],
+    );
+    assert_close(
+        &mixed.vertices[16].color,
+        &Color::WHITE.with_alpha(0.5).to_array(),
+    );
+}
+
+/// Verifies UI draws are counted in the frame's render statistics.
+#[test]
+fn records_render_stats() {
+    let mut batcher = UiBatcher::new(rect(0.0, 0.0, 800.0, 600.0));
+    batcher.add(icon(0.0, 0.0));
+    batcher.extend(text(0.0, 0.0, 4));
+    let frame = batcher.finish();
+
+    let mut recorder = RenderStatsRecorder::default();
+    recorder.begin_frame();
+    recorder.draw(1, 1, 100);
+    recorder.draw_ui(2, &frame);
+    let stats = recorder.end_frame();
+    assert_eq!(
+        (stats.draw_calls, stats.ui_draw_calls, stats.ui_quads),
+        (3, 2, 5)
+    );
+    assert_eq!(stats.triangles, 100 + 5 * 2);
+    assert_eq!(stats.pipeline_switches, 1);
+}
@@ -0,0 +1,179 @@
+//! Viewports for split-screen and multi-view rendering.
+//!
+//! Each [`Camera`] covers a [`Viewport`] of its target, given as a fraction
+//! of the target size so layouts survive window resizes. Every frame,
+//! [`frame_views`] turns the active cameras into [`View`]s with pixel
+//! rectangles used for the viewport and scissor state, for culling, and as
+//! the root rectangle of the owning player's UI overlay.
+
+use crate::camera::{render_order, Camera};
+use crate::target::{RenderTarget, RenderTextures};
+
+/// Region of a render target, as fractions of its size.
+///
+/// `x` and `y` are measured from the top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    /// Left edge, from 0.0 to 1.0.
+    pub x: f32,
+    /// Top edge, from 0.0 to 1.0.
+    pub y: f32,
+    /// Width, from 0.0 to 1.0.
+    pub width: f32,
+    /// Height, from 0.0 to 1.0.
+    pub height: f32,
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+impl Viewport {
+    /// The whole target.
+    pub const FULL: Self = Self {
+        x: 0.0,
+        y: 0.0,
+        width: 1.0,
+        height: 1.0,
+    };
+
+    /// Returns the viewport of player `index` in a split-screen layout for
+    /// `players` players.
+    ///
+    /// Two players are stacked top and bottom; three or four share
+    /// quadrants, with the fourth left empty for three players. More than
+    /// four players, or an `index` outside the layout, get the whole target.
+    #[must_use]
+    pub fn split_screen(players: usize, index: usize) -> Self {
+        let half = |second: bool| if second { 0.5 } else { 0.0 };
+        match (players, index) {
+            (2, 0 | 1) => Self {
+                y: half(index == 1),
+                height: 0.5,
+                ..Self::FULL
+            },
+            (3 | 4, index) if index < players => Self {
+                x: half(index % 2 == 1),
+                y: half(index >= 2),
+                width: 0.5,
+                height: 0.5,
+            },
+            _ => Self::FULL,
+        }
+    }
+
+    /// Converts the viewport to pixels of a `width` x `height` target.
+    ///
+    /// Edges are rounded independently, so viewports sharing an edge tile
+    /// the target without gaps or overlap. The result is clamped to the
+    /// target.
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn to_pixels(&self, width: u32, height: u32) -> PixelRect {
+        let edge =
+            |fraction: f32, size: u32| (fraction.clamp(0.0, 1.0) * size as f32).round() as u32;
+        let left = edge(self.x, width);
+        let top = edge(self.y, height);
+        let right = edge(self.x + self.width, width).max(left);
+        let bottom = edge(self.y + self.height, height).max(top);
+        PixelRect {
+            x: left,
+            y: top,
+            width: right - left,
+            height: bottom - top,
+        }
+    }
+}
+
+/// Rectangle in pixels, measured from the top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PixelRect {
+    /// Left edge.
+    pub x: u32,
+    /// Top edge.
+    pub y: u32,
+    /// Width.
+    pub width: u32,
+    /// Height.
+    pub height: u32,
+}
+
+impl PixelRect {
+    /// Returns `true` if the rectangle covers no pixels.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Returns the width divided by the height, for the camera projection.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn aspect_ratio(&self) -> f32 {
+        if self.height == 0 {
+            1.0
+        } else {
+            self.width as f32 / self.height as f32
+        }
+    }
+}
+
+/// One camera's pass over its viewport this frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct View {
+    /// Index of the camera in the slice given to [`frame_views`].
+    pub camera: usize,
+    /// Viewport and scissor rectangle; also the root of the UI overlay
+    /// drawn for this view.
+    pub rect: PixelRect,
+}
+
+/// Returns the views to render this frame, in render order.
+///
+/// Window cameras are sized against `window`; texture cameras against their
+/// [`RenderTextures`] entry. Cameras with a missing texture or an empty
+/// viewport are skipped.
+///
+/// # Example
+///
+/// ```
+/// use render::viewport::{frame_views, Viewport};
+/// use render::{Camera, RenderLimits, RenderTextures};
+///
+/// let players: Vec<Camera> = (0..2)
+///     .map(|player| Camera {
+///         viewport: Viewport::split_screen(2, player),
+///         ..Camera::default()
+///     })
+///     .collect();
+///
+/// let views = frame_views(&players, (1280, 720), &RenderTextures::new(RenderLimits::default()));
+/// assert_eq!(views[0].rect.height, 360);
+/// assert_eq!(views[1].rect.y, 360);
+/// ```
+#[must_use]
+pub fn frame_views(cameras: &[Camera], window: (u32, u32), textures: &RenderTextures) -> Vec<View> {
+    render_order(cameras)
+        .into_iter()
+        .filter_map(|index| {
+            let camera = &cameras[index];
+            let (width, height) = match camera.target {
+                RenderTarget::Window => window,
+                RenderTarget::Texture(handle) => {
+                    let desc = textures.get(handle)?;
+                    (desc.width, desc.height)
+                }
+            };
+            let rect = camera.viewport.to_pixels(width, height);
+            (!rect.is_empty()).then_some(View {
+                camera: index,
+                rect,
+            })
+        })
+        .collect()
+}
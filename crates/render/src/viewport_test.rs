@@ -0,0 +1,112 @@
+//! Unit tests for viewports and per-frame views.
+
+use crate::camera::Camera;
+use crate::target::{RenderTarget, RenderTextureDesc, RenderTextures};
+use crate::viewport::{frame_views, PixelRect, View, Viewport};
+use crate::RenderLimits;
+
+/// Verifies split-screen layouts tile the window without gaps.
+#[test]
+fn split_screen_tiles_window() {
+    for players in 1..=4 {
+        let covered: u32 = (0..players)
+            .map(|index| {
+                let rect = Viewport::split_screen(players, index).to_pixels(1279, 719);
+                rect.width * rect.height
+            })
+            .sum();
+        let empty = Viewport::split_screen(4, 3).to_pixels(1279, 719);
+        let expected = if players == 3 {
+            1279 * 719 - empty.width * empty.height
+        } else {
+            1279 * 719
+        };
+        assert_eq!(covered, expected, "{players} players");
+    }
+}
+
+/// Verifies viewports are clamped to the target.
+#[test]
+fn viewport_clamped_to_target() {
+    let viewport = Viewport {
+        x: 0.75,
+        y: -0.5,
+        width: 0.5,
+        height: 1.0,
+    };
+
+    assert_eq!(
+        viewport.to_pixels(100, 100),
+        PixelRect {
+            x: 75,
+            y: 0,
+            width: 25,
+            height: 50,
+        }
+    );
+    assert!((viewport.to_pixels(100, 100).aspect_ratio() - 0.5).abs() < f32::EPSILON);
+}
+
+/// Verifies each camera gets a view sized against its own target.
+#[test]
+fn frame_views_per_camera() {
+    let mut textures = RenderTextures::new(RenderLimits::default());
+    let minimap = textures.create(RenderTextureDesc::new(200, 200)).unwrap();
+    let cameras = [
+        Camera {
+            viewport: Viewport::split_screen(2, 0),
+            ..Camera::default()
+        },
+        Camera {
+            viewport: Viewport::split_screen(2, 1),
+            ..Camera::default()
+        },
+        Camera {
+            target: RenderTarget::Texture(minimap),
+            ..Camera::default()
+        },
+        Camera {
+            viewport: Viewport {
+                width: 0.0,
+                ..Viewport::FULL
+            },
+            ..Camera::default()
+        },
+    ];
+
+    assert_eq!(
+        frame_views(&cameras, (800, 600), &textures),
+        [
+            View {
+                camera: 2,
+                rect: PixelRect {
+                    x: 0,
+                    y: 0,
+                    width: 200,
+                    height: 200,
+                },
+            },
+            View {
+                camera: 0,
+                rect: PixelRect {
+                    x: 0,
+                    y: 0,
+                    width: 800,
+                    height: 300,
+                },
+            },
+            View {
+                camera: 1,
+                rect: PixelRect {
+                    x: 0,
+                    y: 300,
+                    width: 800,
+                    height: 300,
+                },
+            },
+        ]
+    );
+
+    textures.remove(minimap);
+    assert_eq!(frame_views(&cameras, (800, 600), &textures).len(), 2);
+}
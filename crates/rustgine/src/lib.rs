@@ -0,0 +1,69 @@
+//! The Rustgine game engine.
+//!
+//! This crate re-exports the engine crates under one dependency, with
+//! consistent names (`rustgine::core`, `rustgine::app`, ...) and a
+//! [`prelude`] of the most used types. Heavy subsystems are Cargo features,
+//! all enabled by default:
+//!
+//! | Feature   | Enables                                             |
+//! |-----------|-----------------------------------------------------|
+//! | `render`  | [`render`] and the renderer subsystem               |
+//! | `audio`   | [`audio`] and the audio subsystem                   |
+//! | `physics` | [`physics`] and the physics subsystem               |
+//! | `net`     | [`net`] and the networking subsystem                |
+//! | `devui`   | The development-only HTTP remote control server     |
+//!
+//! Optional crates are off by default:
+//!
+//! | Feature    | Enables                                            |
+//! |------------|----------------------------------------------------|
+//! | `gameplay` | [`gameplay`] stats, abilities, and status effects  |
+//!
+//! A headless server disables the defaults and picks what it needs:
+//!
+//! ```toml
+//! [dependencies]
+//! rustgine = { version = "0.1", default-features = false, features = ["physics", "net"] }
+//! ```
+//!
+//! [`AppBuilder::add_default_systems`](app::resources::AppBuilder::add_default_systems)
+//! only registers the subsystems that were compiled in.
+//!
+//! # Example
+//!
+//! ```
+//! use rustgine::prelude::*;
+//!
+//! let state = AppBuilder::new(&Config::default())?
+//!     .add_default_systems()?
+//!     .build();
+//! assert!(state.system_count() >= 5);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+#![warn(missing_docs)]
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+pub mod prelude;
+
+pub use ai;
+pub use animation;
+pub use app;
+pub use assets;
+#[cfg(feature = "audio")]
+pub use audio;
+pub use ecs;
+#[cfg(feature = "gameplay")]
+pub use gameplay;
+pub use math;
+#[cfg(feature = "net")]
+pub use net;
+#[cfg(feature = "physics")]
+pub use physics;
+pub use platform;
+#[cfg(feature = "render")]
+pub use render;
+pub use rustgine_core as core;
+pub use scheduler;
+pub use ui;
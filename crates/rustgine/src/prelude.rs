@@ -0,0 +1,40 @@
+//! The types most games use, importable with one line.
+//!
+//! ```
+//! use rustgine::prelude::*;
+//!
+//! let mut world = World::default();
+//! let player = world.spawn();
+//! world.insert(player, Name::new("player"));
+//! world.insert(player, Vec3::new(0.0, 1.0, 0.0));
+//! assert_eq!(world.get::<Vec3>(player), Some(&Vec3::Y));
+//! ```
+//!
+//! Subsystem types are only included when their feature is enabled. Less
+//! common types stay in their crate, e.g. [`crate::scheduler::JobGraph`].
+
+pub use crate::app::resources::{AppBuilder, AppState, Shutdown};
+pub use crate::assets::{Asset, AssetCache, AssetId, Handle, WeakHandle};
+pub use crate::core::{CVars, Config, Console, Label, RustgineError, RustgineSystem};
+pub use crate::ecs::{Entity, EntityPool, Events, Name, Parent, World};
+pub use crate::math::{
+    lerp, palette, Aabb, Color, Mat3, Mat4, Plane, Quat, Ray, Rect, Vec2, Vec3, Vec4,
+};
+pub use crate::scheduler::{FixedTimestep, Schedule, SystemDescriptor};
+
+pub use crate::animation::{Ease, Repeat, Tweener};
+pub use crate::ui::{FocusManager, Focusable, WidgetId};
+
+#[cfg(feature = "audio")]
+pub use crate::audio::{Emitter, Listener, Mixer};
+#[cfg(feature = "gameplay")]
+pub use crate::gameplay::{
+    Abilities, DialogueRunner, GameplayLibrary, Inventory, QuestLog, StatModifier, Stats,
+    StatusEffects,
+};
+#[cfg(feature = "net")]
+pub use crate::net::{NetMessage, PlayerId, Session};
+#[cfg(feature = "physics")]
+pub use crate::physics::{CharacterController, CollisionLayers, PhysicsWorld};
+#[cfg(feature = "render")]
+pub use crate::render::{Camera, Material, Transform};
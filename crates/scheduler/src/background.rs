@@ -0,0 +1,37 @@
+//! Fire-and-forget background tasks.
+//!
+//! Long-running work that must not stall a frame, such as shader and
+//! pipeline compilation or asset decoding, runs on the scheduler's worker
+//! threads via [`spawn_background`]. Results are handed back through
+//! whatever channel or shared state the task captures.
+
+use crate::job_graph::panic_message;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use tracing::warn;
+
+/// Runs `task` on a scheduler worker thread without waiting for it.
+///
+/// A panic inside the task is caught and logged with `name` instead of
+/// tearing down the worker.
+///
+/// # Example
+///
+/// ```
+/// use scheduler::spawn_background;
+/// use std::sync::mpsc;
+///
+/// let (tx, rx) = mpsc::channel();
+/// spawn_background("answer", move || tx.send(42).unwrap());
+/// assert_eq!(rx.recv().unwrap(), 42);
+/// ```
+pub fn spawn_background<F>(name: &str, task: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    let name = name.to_owned();
+    rayon::spawn(move || {
+        if let Err(payload) = catch_unwind(AssertUnwindSafe(task)) {
+            warn!(task = %name, panic = %panic_message(&*payload), "background task panicked");
+        }
+    });
+}
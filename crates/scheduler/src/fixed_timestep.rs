@@ -0,0 +1,97 @@
+//! Fixed-rate simulation stepping.
+//!
+//! Provides the [`FixedTimestep`] accumulator, which turns variable frame
+//! times into a whole number of constant-length simulation steps so
+//! gameplay logic such as AI runs at the same rate regardless of frame rate.
+
+use std::time::Duration;
+
+/// Default number of steps run in one frame before time is dropped.
+const DEFAULT_MAX_STEPS: u32 = 8;
+
+/// Accumulates frame time and releases it in fixed steps.
+///
+/// # Example
+///
+/// ```
+/// use scheduler::FixedTimestep;
+/// use std::time::Duration;
+///
+/// let mut timestep = FixedTimestep::from_hz(50);
+/// assert_eq!(timestep.advance(Duration::from_millis(50)), 2);
+/// assert_eq!(timestep.advance(Duration::from_millis(5)), 0);
+/// assert_eq!(timestep.advance(Duration::from_millis(5)), 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedTimestep {
+    /// Length of one step.
+    step: Duration,
+    /// Time not yet consumed by a step.
+    accumulator: Duration,
+    /// Steps run per frame at most.
+    max_steps: u32,
+}
+
+impl FixedTimestep {
+    /// Creates a timestep of `step` per simulation step.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    #[must_use]
+    pub fn new(step: Duration) -> Self {
+        assert!(!step.is_zero(), "fixed timestep must be positive");
+        Self {
+            step,
+            accumulator: Duration::ZERO,
+            max_steps: DEFAULT_MAX_STEPS,
+        }
+    }
+
+    /// Creates a timestep running `hz` steps per second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hz` is zero.
+    #[must_use]
+    pub fn from_hz(hz: u32) -> Self {
+        assert!(hz > 0, "fixed timestep rate must be positive");
+        Self::new(Duration::from_secs(1) / hz)
+    }
+
+    /// Sets how many steps one frame may run; time beyond that is dropped
+    /// so a slow frame cannot make the next one slower still.
+    #[must_use]
+    pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    /// Returns the length of one step.
+    #[must_use]
+    #[inline]
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// Adds a frame's time and returns the number of steps to run.
+    pub fn advance(&mut self, delta: Duration) -> u32 {
+        self.accumulator += delta;
+        let mut steps = 0;
+        while self.accumulator >= self.step {
+            if steps == self.max_steps {
+                self.accumulator = Duration::ZERO;
+                break;
+            }
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+        steps
+    }
+
+    /// Returns how far the simulation is into the next step, from 0 to 1.
+    #[must_use]
+    pub fn alpha(&self) -> f32 {
+        self.accumulator.as_secs_f32() / self.step.as_secs_f32()
+    }
+}
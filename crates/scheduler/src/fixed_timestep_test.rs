@@ -0,0 +1,24 @@
+//! Unit tests for fixed-rate simulation stepping.
+
+use super::FixedTimestep;
+use std::time::Duration;
+
+/// Verifies leftover time carries over into later frames.
+#[test]
+fn accumulates_partial_steps() {
+    let mut timestep = FixedTimestep::new(Duration::from_millis(10));
+
+    assert_eq!(timestep.advance(Duration::from_millis(25)), 2);
+    assert!((timestep.alpha() - 0.5).abs() < 1e-6);
+    assert_eq!(timestep.advance(Duration::from_millis(5)), 1);
+    assert_eq!(timestep.alpha().to_bits(), 0.0_f32.to_bits());
+}
+
+/// Verifies long frames are capped and the excess dropped.
+#[test]
+fn caps_steps_per_frame() {
+    let mut timestep = FixedTimestep::new(Duration::from_millis(10)).with_max_steps(3);
+
+    assert_eq!(timestep.advance(Duration::from_secs(1)), 3);
+    assert_eq!(timestep.advance(Duration::ZERO), 0);
+}
@@ -0,0 +1,70 @@
+//! Unit tests for the frame arena.
+
+use super::FrameArena;
+use rayon::prelude::*;
+
+/// Verifies a reset reports the frame's usage and releases it.
+#[test]
+fn reset_reports_and_releases_usage() {
+    let mut arena = FrameArena::new();
+    let mut list = arena.vec_with_capacity::<u64>(100);
+    list.extend(0..100);
+    assert_eq!(list.iter().sum::<u64>(), 4950);
+    // Allocated after the list, so dropping the list cannot reclaim it
+    let copy = arena.alloc_slice_copy(&[1u8; 24]);
+    assert_eq!(copy.len(), 24);
+    drop(list);
+
+    let stats = arena.reset();
+    assert!(stats.used_bytes >= 100 * 8 + 24, "{stats:?}");
+    assert!(stats.capacity_bytes >= stats.used_bytes);
+    assert_eq!(stats.threads, 1);
+
+    let next = arena.stats();
+    assert_eq!(next.used_bytes, 0);
+    assert!(next.capacity_bytes > 0);
+}
+
+/// Verifies a frame as large as the last reuses the retained memory.
+#[test]
+fn steady_frames_do_not_grow() {
+    let mut arena = FrameArena::new();
+    let grew: Vec<bool> = (0..4)
+        .map(|_| {
+            let mut batch = arena.vec();
+            batch.extend((0..1000u32).map(|i| i * 2));
+            let sort_keys = arena.alloc_slice_copy(&batch);
+            sort_keys.reverse();
+            drop(batch);
+            arena.reset().grew()
+        })
+        .collect();
+    assert!(grew[0]);
+    assert_eq!(grew[2..], [false, false]);
+}
+
+/// Verifies parallel systems can share the arena.
+#[test]
+fn allocates_from_parallel_threads() {
+    let mut arena = FrameArena::new();
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .unwrap();
+    let sums: Vec<u32> = pool.install(|| {
+        (0..64u32)
+            .into_par_iter()
+            .map(|chunk| {
+                let mut visible = arena.vec_with_capacity(16);
+                visible.extend((0..16).map(|i| chunk * 16 + i));
+                arena.alloc(visible.iter().sum::<u32>());
+                visible.iter().sum()
+            })
+            .collect()
+    });
+    assert_eq!(sums.iter().sum::<u32>(), (0..1024).sum());
+
+    let stats = arena.reset();
+    assert!((1..=5).contains(&stats.threads), "{stats:?}");
+    assert!(stats.used_bytes >= 64 * 4, "{stats:?}");
+}
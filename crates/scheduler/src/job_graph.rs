@@ -0,0 +1,279 @@
+//! Fork-join job graphs with explicit dependencies.
+//!
+//! Provides the [`JobGraph`] builder, which lets engine code describe a set of
+//! jobs and the handles they depend on, then execute every ready job in
+//! parallel on the Rayon thread pool.
+
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Boxed job body executed by the graph.
+type Task<'a> = Box<dyn FnOnce() -> anyhow::Result<()> + Send + 'a>;
+
+/// Source of [`JobGraph`] ids, so handles can be traced to their graph.
+static NEXT_GRAPH_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Opaque handle identifying a job within a [`JobGraph`].
+///
+/// Handles are returned by [`JobGraph::add_job`] and passed as dependencies
+/// to later jobs. A handle is only meaningful for the graph that created it,
+/// and other graphs reject it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JobHandle {
+    /// Id of the graph that created the handle.
+    graph: u64,
+    /// Index of the job within that graph.
+    index: usize,
+}
+
+/// A single job and its position in the dependency graph.
+struct Job<'a> {
+    name: String,
+    dependencies: Vec<JobHandle>,
+    task: Task<'a>,
+}
+
+/// Builder for a directed acyclic graph of parallel jobs.
+///
+/// Jobs declare dependencies on the handles of previously added jobs, which
+/// makes the graph acyclic by construction. When [`run`](Self::run) is called,
+/// every job whose dependencies have completed is dispatched to the Rayon
+/// thread pool, so independent branches execute in parallel.
+///
+/// # Failure Propagation
+///
+/// If a job returns an error or panics, no further jobs are started and
+/// [`run`](Self::run) returns the first failure, annotated with the job name.
+/// Jobs already in flight are allowed to finish.
+///
+/// # Example
+///
+/// ```
+/// use scheduler::JobGraph;
+///
+/// let mut graph = JobGraph::new();
+/// let culling = graph.add_job("culling", &[], || Ok(())).unwrap();
+/// let batching = graph.add_job("batching", &[culling], || Ok(())).unwrap();
+/// graph.add_job("submission", &[batching], || Ok(())).unwrap();
+///
+/// graph.run().expect("job graph failed");
+/// ```
+pub struct JobGraph<'a> {
+    /// Id stamped on the handles this graph creates.
+    id: u64,
+    jobs: Vec<Job<'a>>,
+}
+
+impl Default for JobGraph<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for JobGraph<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobGraph")
+            .field(
+                "jobs",
+                &self
+                    .jobs
+                    .iter()
+                    .map(|job| (&job.name, &job.dependencies))
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl<'a> JobGraph<'a> {
+    /// Creates an empty job graph.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            id: NEXT_GRAPH_ID.fetch_add(1, Ordering::Relaxed),
+            jobs: Vec::new(),
+        }
+    }
+
+    /// Adds a job that runs after all of `dependencies` have completed.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Human-readable job name used in logs and error messages
+    /// * `dependencies` - Handles of jobs that must finish before this one starts
+    /// * `task` - The job body
+    ///
+    /// # Returns
+    ///
+    /// A [`JobHandle`] that later jobs can depend on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any dependency handle was created by another
+    /// graph or does not belong to a job previously added to this graph.
+    pub fn add_job<F>(
+        &mut self,
+        name: &str,
+        dependencies: &[JobHandle],
+        task: F,
+    ) -> anyhow::Result<JobHandle>
+    where
+        F: FnOnce() -> anyhow::Result<()> + Send + 'a,
+    {
+        if let Some(unknown) = dependencies
+            .iter()
+            .find(|dep| dep.graph != self.id || dep.index >= self.jobs.len())
+        {
+            anyhow::bail!(
+                "job `{name}` depends on job handle {} of another graph",
+                unknown.index
+            );
+        }
+
+        let handle = JobHandle {
+            graph: self.id,
+            index: self.jobs.len(),
+        };
+        self.jobs.push(Job {
+            name: name.to_owned(),
+            dependencies: dependencies.to_vec(),
+            task: Box::new(task),
+        });
+        Ok(handle)
+    }
+
+    /// Returns the number of jobs in the graph.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    /// Returns `true` if the graph contains no jobs.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Executes the graph on the current Rayon thread pool.
+    ///
+    /// Blocks until every job has completed or the graph has been aborted
+    /// because of a failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error produced by a job, or an error describing the
+    /// panic if a job panicked.
+    pub fn run(self) -> anyhow::Result<()> {
+        let mut dependents = vec![Vec::new(); self.jobs.len()];
+        let mut nodes = Vec::with_capacity(self.jobs.len());
+
+        for (index, job) in self.jobs.into_iter().enumerate() {
+            for dep in &job.dependencies {
+                dependents[dep.index].push(index);
+            }
+            nodes.push(Node {
+                name: job.name,
+                pending: AtomicUsize::new(job.dependencies.len()),
+                task: Mutex::new(Some(job.task)),
+            });
+        }
+
+        let context = RunContext {
+            nodes,
+            dependents,
+            aborted: AtomicBool::new(false),
+            failure: Mutex::new(None),
+        };
+
+        rayon::scope(|scope| {
+            for (index, node) in context.nodes.iter().enumerate() {
+                if node.pending.load(Ordering::Acquire) == 0 {
+                    spawn_job(scope, &context, index);
+                }
+            }
+        });
+
+        match context.failure.into_inner() {
+            Ok(Some(error)) => Err(error),
+            Ok(None) => Ok(()),
+            Err(_) => Err(anyhow::anyhow!("job graph failure lock poisoned")),
+        }
+    }
+}
+
+/// Runtime state for a single job during graph execution.
+struct Node<'a> {
+    name: String,
+    pending: AtomicUsize,
+    task: Mutex<Option<Task<'a>>>,
+}
+
+/// Shared state for one execution of a [`JobGraph`].
+struct RunContext<'a> {
+    nodes: Vec<Node<'a>>,
+    dependents: Vec<Vec<usize>>,
+    aborted: AtomicBool,
+    failure: Mutex<Option<anyhow::Error>>,
+}
+
+impl RunContext<'_> {
+    /// Records a job failure and aborts scheduling of further jobs.
+    fn fail(&self, error: anyhow::Error) {
+        self.aborted.store(true, Ordering::Release);
+        if let Ok(mut failure) = self.failure.lock() {
+            failure.get_or_insert(error);
+        }
+    }
+}
+
+/// Spawns the job at `index` and, on success, any dependents it unblocks.
+fn spawn_job<'scope, 'a: 'scope>(
+    scope: &rayon::Scope<'scope>,
+    context: &'scope RunContext<'a>,
+    index: usize,
+) {
+    scope.spawn(move |scope| {
+        if context.aborted.load(Ordering::Acquire) {
+            return;
+        }
+
+        let node = &context.nodes[index];
+        let Some(task) = node.task.lock().ok().and_then(|mut task| task.take()) else {
+            context.fail(anyhow::anyhow!("job `{}` could not be claimed", node.name));
+            return;
+        };
+
+        let result = match catch_unwind(AssertUnwindSafe(task)) {
+            Ok(result) => result,
+            Err(payload) => Err(anyhow::anyhow!("panicked: {}", panic_message(&*payload))),
+        };
+
+        if let Err(e) = result {
+            context.fail(e.context(format!("job `{}` failed", node.name)));
+            return;
+        }
+
+        for &dependent in &context.dependents[index] {
+            if context.nodes[dependent]
+                .pending
+                .fetch_sub(1, Ordering::AcqRel)
+                == 1
+            {
+                spawn_job(scope, context, dependent);
+            }
+        }
+    });
+}
+
+/// Extracts a readable message from a panic payload.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| (*s).to_owned())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".to_owned())
+}
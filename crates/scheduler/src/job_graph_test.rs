@@ -0,0 +1,150 @@
+//! Unit tests for the fork-join job graph.
+
+use super::{JobGraph, JobHandle};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Verifies that an empty graph runs successfully.
+#[test]
+fn empty_graph_runs() {
+    let graph = JobGraph::new();
+    assert!(graph.is_empty());
+    assert!(graph.run().is_ok());
+}
+
+/// Verifies that every job in the graph is executed exactly once.
+#[test]
+fn runs_all_jobs() {
+    let counter = AtomicUsize::new(0);
+    let mut graph = JobGraph::new();
+    for i in 0..16 {
+        graph
+            .add_job(&format!("job-{i}"), &[], || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    assert_eq!(graph.len(), 16);
+    graph.run().unwrap();
+    assert_eq!(counter.load(Ordering::SeqCst), 16);
+}
+
+/// Verifies that dependencies complete before their dependents start.
+#[test]
+fn respects_dependency_order() {
+    let order = Mutex::new(Vec::new());
+    let mut graph = JobGraph::new();
+
+    let culling = graph
+        .add_job("culling", &[], || {
+            order.lock().unwrap().push("culling");
+            Ok(())
+        })
+        .unwrap();
+    let shadows = graph
+        .add_job("shadows", &[culling], || {
+            order.lock().unwrap().push("shadows");
+            Ok(())
+        })
+        .unwrap();
+    let batching = graph
+        .add_job("batching", &[culling], || {
+            order.lock().unwrap().push("batching");
+            Ok(())
+        })
+        .unwrap();
+    graph
+        .add_job("submission", &[shadows, batching], || {
+            order.lock().unwrap().push("submission");
+            Ok(())
+        })
+        .unwrap();
+
+    graph.run().unwrap();
+
+    let order = order.into_inner().unwrap();
+    assert_eq!(order.len(), 4);
+    assert_eq!(order[0], "culling");
+    assert_eq!(order[3], "submission");
+}
+
+/// Verifies that a job error is returned and dependents are skipped.
+#[test]
+fn error_propagates_and_skips_dependents() {
+    let ran_dependent = AtomicUsize::new(0);
+    let mut graph = JobGraph::new();
+
+    let failing = graph
+        .add_job("failing", &[], || Err(anyhow::anyhow!("boom")))
+        .unwrap();
+    graph
+        .add_job("dependent", &[failing], || {
+            ran_dependent.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        })
+        .unwrap();
+
+    let error = graph.run().unwrap_err();
+    assert!(format!("{error:#}").contains("job `failing` failed"));
+    assert_eq!(ran_dependent.load(Ordering::SeqCst), 0);
+}
+
+/// Verifies that a panicking job is reported as an error instead of unwinding.
+#[test]
+fn panic_is_reported_as_error() {
+    let mut graph = JobGraph::new();
+    graph
+        .add_job("panicking", &[], || panic!("job exploded"))
+        .unwrap();
+
+    let error = graph.run().unwrap_err();
+    let message = format!("{error:#}");
+    assert!(message.contains("panicking"));
+    assert!(message.contains("job exploded"));
+}
+
+/// Verifies that handles from another graph are rejected.
+#[test]
+fn rejects_unknown_handle() {
+    let mut other = JobGraph::new();
+    other.add_job("a", &[], || Ok(())).unwrap();
+    let foreign: JobHandle = other.add_job("b", &[], || Ok(())).unwrap();
+
+    let mut graph = JobGraph::new();
+    assert!(graph.add_job("c", &[foreign], || Ok(())).is_err());
+}
+
+/// Verifies that a handle from another graph is rejected even when its
+/// index names a job this graph has.
+#[test]
+fn rejects_foreign_handle_in_range() {
+    let mut other = JobGraph::new();
+    let foreign = other.add_job("a", &[], || Ok(())).unwrap();
+
+    let mut graph = JobGraph::new();
+    let own = graph.add_job("b", &[], || Ok(())).unwrap();
+    assert!(graph.add_job("c", &[foreign], || Ok(())).is_err());
+    assert!(graph.add_job("d", &[own], || Ok(())).is_ok());
+    assert_eq!(graph.len(), 2);
+}
+
+/// Verifies that jobs may borrow data from the enclosing scope.
+#[test]
+fn jobs_can_borrow_local_data() {
+    let values = vec![1, 2, 3, 4];
+    let sum = AtomicUsize::new(0);
+    let mut graph = JobGraph::new();
+    for value in &values {
+        graph
+            .add_job("sum", &[], || {
+                sum.fetch_add(*value, Ordering::SeqCst);
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    graph.run().unwrap();
+    assert_eq!(sum.load(Ordering::SeqCst), 10);
+}
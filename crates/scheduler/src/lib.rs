@@ -11,6 +11,16 @@
 //! - Work stealing for optimal load distribution
 //! - Frame-synchronized task scheduling
 //!
+//! Dependent work is expressed as a [`JobGraph`]: jobs declare the
+//! [`JobHandle`]s they depend on and ready jobs run in parallel.
+//! Per-system CPU time is tracked against budgets by the [`SystemProfiler`],
+//! and work that may span frames runs via [`spawn_background`]. Gameplay
+//! logic that must run at a constant rate is stepped by a [`FixedTimestep`].
+//! Per-frame temporaries are bump-allocated from a [`FrameArena`], reset at
+//! the start of each frame.
+//! A [`Schedule`] of systems with declared data access resolves into a
+//! [`SchedulePlan`] of parallel batches that renders as DOT or Mermaid.
+//!
 //! # Example
 //!
 //! ```ignore
@@ -25,6 +35,28 @@
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+pub mod background;
+pub mod fixed_timestep;
+#[cfg(test)]
+mod fixed_timestep_test;
+pub mod frame_arena;
+#[cfg(test)]
+mod frame_arena_test;
+pub mod job_graph;
+#[cfg(test)]
+mod job_graph_test;
+pub mod profiler;
+#[cfg(test)]
+mod profiler_test;
+pub mod schedule;
+#[cfg(test)]
+mod schedule_test;
 pub mod scheduler;
 
+pub use background::spawn_background;
+pub use fixed_timestep::FixedTimestep;
+pub use frame_arena::{ArenaStats, BumpVec, FrameArena};
+pub use job_graph::{JobGraph, JobHandle};
+pub use profiler::{ArenaUsage, SystemProfiler, SystemTiming, TimingSortKey};
+pub use schedule::{Schedule, ScheduleFormat, SchedulePlan, SystemDescriptor};
 pub use scheduler::RustgineScheduler;
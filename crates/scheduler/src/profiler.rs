@@ -0,0 +1,359 @@
+//! Per-system and per-stage CPU time budgets.
+//!
+//! Provides the [`SystemProfiler`], which measures how long each system runs
+//! per frame, keeps rolling averages, and warns when a system or stage
+//! exceeds its configured budget. It also tracks how much of the
+//! [`FrameArena`](crate::FrameArena) each frame used.
+
+use crate::frame_arena::ArenaStats;
+use rustgine_core::Label;
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Default number of frames kept for rolling averages.
+const DEFAULT_WINDOW: usize = 120;
+
+/// Column used to order the rows returned by [`SystemProfiler::table`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimingSortKey {
+    /// Alphabetical by stage, then system name.
+    Name,
+    /// Time spent in the most recently completed frame.
+    Last,
+    /// Rolling average over the sample window.
+    #[default]
+    Average,
+    /// Worst frame within the sample window.
+    Max,
+}
+
+/// A single row of the per-system timing table.
+///
+/// Rows are intended for display in the debug overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SystemTiming {
+    /// Stage the system runs in.
+    pub stage: Label,
+    /// System name.
+    pub system: Label,
+    /// Time spent in the most recently completed frame.
+    pub last: Duration,
+    /// Rolling average over the sample window.
+    pub average: Duration,
+    /// Worst frame within the sample window.
+    pub max: Duration,
+    /// Budget applied to this system, if any.
+    pub budget: Option<Duration>,
+    /// Number of frames in the window that exceeded the budget.
+    pub over_budget_frames: usize,
+}
+
+/// Frame arena usage over the sample window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ArenaUsage {
+    /// Bytes held by the arena in the most recently completed frame.
+    pub capacity_bytes: usize,
+    /// Most bytes held in a single frame.
+    pub peak_capacity_bytes: usize,
+    /// Average bytes still allocated at the end of a frame.
+    pub average_used_bytes: usize,
+    /// Frames that outgrew the arena and allocated from the global
+    /// allocator; this settles at zero once the arena fits the workload.
+    pub grown_frames: usize,
+}
+
+/// Rolling sample history for one system or stage.
+#[derive(Debug, Default)]
+struct Samples {
+    current: Duration,
+    history: VecDeque<Duration>,
+    over_budget: VecDeque<bool>,
+}
+
+impl Samples {
+    fn push(&mut self, window: usize, over_budget: bool) {
+        if self.history.len() == window {
+            self.history.pop_front();
+            self.over_budget.pop_front();
+        }
+        self.history.push_back(std::mem::take(&mut self.current));
+        self.over_budget.push_back(over_budget);
+    }
+
+    fn last(&self) -> Duration {
+        self.history.back().copied().unwrap_or_default()
+    }
+
+    fn average(&self) -> Duration {
+        let count = u32::try_from(self.history.len()).unwrap_or(u32::MAX);
+        if count == 0 {
+            return Duration::ZERO;
+        }
+        self.history.iter().sum::<Duration>() / count
+    }
+
+    fn max(&self) -> Duration {
+        self.history.iter().max().copied().unwrap_or_default()
+    }
+}
+
+/// Mutable profiler state guarded by a single lock.
+#[derive(Debug, Default)]
+struct ProfilerState {
+    systems: HashMap<(Label, Label), Samples>,
+    stages: HashMap<Label, Samples>,
+    system_budgets: HashMap<Label, Duration>,
+    stage_budgets: HashMap<Label, Duration>,
+    default_system_budget: Option<Duration>,
+    arena: VecDeque<ArenaStats>,
+}
+
+/// Measures per-system CPU time and enforces frame budgets.
+///
+/// Systems are timed with [`time_system`](Self::time_system) (or reported
+/// with [`record`](Self::record)) during a frame. At the end of the frame,
+/// [`end_frame`](Self::end_frame) folds the measurements into rolling
+/// averages and logs a warning for every system or stage over budget.
+///
+/// # Thread Safety
+///
+/// All methods take `&self`, so a single profiler can be shared across the
+/// worker threads that execute systems in parallel.
+///
+/// Stages and systems are keyed by [`Label`], so recording a frame does not
+/// allocate. Callers on a hot path can intern their names once and pass the
+/// labels; plain `&str` names are interned on each call.
+///
+/// # Example
+///
+/// ```
+/// use scheduler::SystemProfiler;
+/// use std::time::Duration;
+///
+/// let profiler = SystemProfiler::new();
+/// profiler.set_system_budget("physics", Duration::from_millis(4));
+///
+/// profiler.time_system("update", "physics", || {
+///     // step the simulation
+/// });
+/// profiler.end_frame();
+///
+/// let table = profiler.table(scheduler::TimingSortKey::Average);
+/// assert_eq!(table[0].system, "physics");
+/// ```
+#[derive(Debug)]
+pub struct SystemProfiler {
+    window: usize,
+    state: Mutex<ProfilerState>,
+}
+
+impl Default for SystemProfiler {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemProfiler {
+    /// Creates a profiler with the default rolling window.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_window(DEFAULT_WINDOW)
+    }
+
+    /// Creates a profiler that averages over the last `window` frames.
+    ///
+    /// A window of `0` is treated as `1`.
+    #[must_use]
+    pub fn with_window(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            state: Mutex::new(ProfilerState::default()),
+        }
+    }
+
+    /// Sets the budget applied to systems without an explicit budget.
+    pub fn set_default_system_budget(&self, budget: Option<Duration>) {
+        self.with_state(|state| state.default_system_budget = budget);
+    }
+
+    /// Sets the per-frame budget for the named system.
+    pub fn set_system_budget(&self, system: impl Into<Label>, budget: Duration) {
+        let system = system.into();
+        self.with_state(|state| {
+            state.system_budgets.insert(system, budget);
+        });
+    }
+
+    /// Sets the per-frame budget for the named stage.
+    pub fn set_stage_budget(&self, stage: impl Into<Label>, budget: Duration) {
+        let stage = stage.into();
+        self.with_state(|state| {
+            state.stage_budgets.insert(stage, budget);
+        });
+    }
+
+    /// Runs `f`, attributing its wall-clock time to `system` in `stage`.
+    pub fn time_system<R>(
+        &self,
+        stage: impl Into<Label>,
+        system: impl Into<Label>,
+        f: impl FnOnce() -> R,
+    ) -> R {
+        let start = Instant::now();
+        let result = f();
+        self.record(stage, system, start.elapsed());
+        result
+    }
+
+    /// Adds `elapsed` to the current frame's total for `system` in `stage`.
+    ///
+    /// A system that runs several times in one frame accumulates its time.
+    pub fn record(&self, stage: impl Into<Label>, system: impl Into<Label>, elapsed: Duration) {
+        let (stage, system) = (stage.into(), system.into());
+        self.with_state(|state| {
+            state.systems.entry((stage, system)).or_default().current += elapsed;
+            state.stages.entry(stage).or_default().current += elapsed;
+        });
+    }
+
+    /// Completes the current frame.
+    ///
+    /// Pushes the accumulated frame times into the rolling history and logs a
+    /// warning for each system or stage that exceeded its budget.
+    pub fn end_frame(&self) {
+        let window = self.window;
+        self.with_state(|state| {
+            for ((stage, system), samples) in &mut state.systems {
+                let budget = state
+                    .system_budgets
+                    .get(system)
+                    .copied()
+                    .or(state.default_system_budget);
+                let elapsed = samples.current;
+                let over = budget.is_some_and(|budget| elapsed > budget);
+                if over {
+                    warn!(
+                        stage = %stage,
+                        system = %system,
+                        elapsed_us = elapsed.as_micros(),
+                        budget_us = budget.unwrap_or_default().as_micros(),
+                        "system exceeded CPU time budget"
+                    );
+                }
+                samples.push(window, over);
+            }
+
+            for (stage, samples) in &mut state.stages {
+                let budget = state.stage_budgets.get(stage).copied();
+                let elapsed = samples.current;
+                let over = budget.is_some_and(|budget| elapsed > budget);
+                if over {
+                    warn!(
+                        stage = %stage,
+                        elapsed_us = elapsed.as_micros(),
+                        budget_us = budget.unwrap_or_default().as_micros(),
+                        "stage exceeded CPU time budget"
+                    );
+                }
+                samples.push(window, over);
+            }
+        });
+    }
+
+    /// Records the frame arena usage of a completed frame, as returned by
+    /// [`FrameArena::reset`](crate::FrameArena::reset).
+    pub fn record_arena(&self, stats: ArenaStats) {
+        let window = self.window;
+        self.with_state(|state| {
+            if state.arena.len() == window {
+                state.arena.pop_front();
+            }
+            state.arena.push_back(stats);
+        });
+    }
+
+    /// Returns the frame arena usage over the sample window.
+    #[must_use]
+    pub fn arena_usage(&self) -> ArenaUsage {
+        self.with_state(|state| {
+            let Some(last) = state.arena.back() else {
+                return ArenaUsage::default();
+            };
+            let used: usize = state.arena.iter().map(|stats| stats.used_bytes).sum();
+            ArenaUsage {
+                capacity_bytes: last.capacity_bytes,
+                peak_capacity_bytes: state
+                    .arena
+                    .iter()
+                    .map(|stats| stats.capacity_bytes)
+                    .max()
+                    .unwrap_or_default(),
+                average_used_bytes: used / state.arena.len(),
+                grown_frames: state.arena.iter().filter(|stats| stats.grew()).count(),
+            }
+        })
+    }
+
+    /// Returns the rolling average frame time for a stage.
+    #[must_use]
+    pub fn stage_average(&self, stage: impl Into<Label>) -> Option<Duration> {
+        let stage = stage.into();
+        self.with_state(|state| state.stages.get(&stage).map(Samples::average))
+    }
+
+    /// Returns the per-system timing table sorted by `sort`.
+    ///
+    /// Time-based sort keys order rows from slowest to fastest.
+    #[must_use]
+    pub fn table(&self, sort: TimingSortKey) -> Vec<SystemTiming> {
+        let mut rows = self.with_state(|state| {
+            state
+                .systems
+                .iter()
+                .map(|(&(stage, system), samples)| SystemTiming {
+                    stage,
+                    system,
+                    last: samples.last(),
+                    average: samples.average(),
+                    max: samples.max(),
+                    budget: state
+                        .system_budgets
+                        .get(&system)
+                        .copied()
+                        .or(state.default_system_budget),
+                    over_budget_frames: samples.over_budget.iter().filter(|over| **over).count(),
+                })
+                .collect::<Vec<_>>()
+        });
+
+        match sort {
+            TimingSortKey::Name => rows.sort_by_key(|row| (row.stage, row.system)),
+            TimingSortKey::Last => rows.sort_by_key(|row| Reverse(row.last)),
+            TimingSortKey::Average => rows.sort_by_key(|row| Reverse(row.average)),
+            TimingSortKey::Max => rows.sort_by_key(|row| Reverse(row.max)),
+        }
+        rows
+    }
+
+    /// Discards all recorded samples while keeping configured budgets.
+    pub fn reset(&self) {
+        self.with_state(|state| {
+            state.systems.clear();
+            state.stages.clear();
+            state.arena.clear();
+        });
+    }
+
+    /// Runs `f` with the profiler state, recovering from a poisoned lock.
+    fn with_state<R>(&self, f: impl FnOnce(&mut ProfilerState) -> R) -> R {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        f(&mut state)
+    }
+}
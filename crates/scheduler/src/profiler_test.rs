@@ -0,0 +1,126 @@
+//! Unit tests for the system time profiler.
+
+use super::{ArenaUsage, SystemProfiler, TimingSortKey};
+use crate::ArenaStats;
+use std::time::Duration;
+
+/// Verifies that a frame's samples appear in the table after `end_frame`.
+#[test]
+fn records_system_time() {
+    let profiler = SystemProfiler::new();
+    profiler.record("update", "physics", Duration::from_millis(3));
+    profiler.end_frame();
+
+    let table = profiler.table(TimingSortKey::Name);
+    assert_eq!(table.len(), 1);
+    assert_eq!(table[0].stage, "update");
+    assert_eq!(table[0].system, "physics");
+    assert_eq!(table[0].last, Duration::from_millis(3));
+}
+
+/// Verifies that multiple invocations within a frame accumulate.
+#[test]
+fn accumulates_within_frame() {
+    let profiler = SystemProfiler::new();
+    profiler.record("update", "ai", Duration::from_millis(1));
+    profiler.record("update", "ai", Duration::from_millis(2));
+    profiler.end_frame();
+
+    assert_eq!(
+        profiler.table(TimingSortKey::Last)[0].last,
+        Duration::from_millis(3)
+    );
+    assert_eq!(
+        profiler.stage_average("update"),
+        Some(Duration::from_millis(3))
+    );
+}
+
+/// Verifies the rolling average only covers the configured window.
+#[test]
+fn rolling_average_uses_window() {
+    let profiler = SystemProfiler::with_window(2);
+    for ms in [10, 2, 4] {
+        profiler.record("update", "physics", Duration::from_millis(ms));
+        profiler.end_frame();
+    }
+
+    let row = &profiler.table(TimingSortKey::Average)[0];
+    assert_eq!(row.average, Duration::from_millis(3));
+    assert_eq!(row.max, Duration::from_millis(4));
+}
+
+/// Verifies that frames over budget are counted.
+#[test]
+fn counts_over_budget_frames() {
+    let profiler = SystemProfiler::new();
+    profiler.set_system_budget("physics", Duration::from_millis(4));
+
+    for ms in [2, 5, 6] {
+        profiler.record("update", "physics", Duration::from_millis(ms));
+        profiler.end_frame();
+    }
+
+    let row = &profiler.table(TimingSortKey::Name)[0];
+    assert_eq!(row.budget, Some(Duration::from_millis(4)));
+    assert_eq!(row.over_budget_frames, 2);
+}
+
+/// Verifies that time-based sorting puts the slowest system first.
+#[test]
+fn table_sorts_slowest_first() {
+    let profiler = SystemProfiler::new();
+    profiler.record("update", "fast", Duration::from_millis(1));
+    profiler.record("update", "slow", Duration::from_millis(9));
+    profiler.record("render", "medium", Duration::from_millis(5));
+    profiler.end_frame();
+
+    let names: Vec<_> = profiler
+        .table(TimingSortKey::Average)
+        .into_iter()
+        .map(|row| row.system)
+        .collect();
+    assert_eq!(names, ["slow", "medium", "fast"]);
+}
+
+/// Verifies that reset clears samples.
+#[test]
+fn reset_clears_samples() {
+    let profiler = SystemProfiler::new();
+    profiler.time_system("update", "physics", || ());
+    profiler.end_frame();
+    profiler.reset();
+
+    assert!(profiler.table(TimingSortKey::Name).is_empty());
+    assert_eq!(profiler.stage_average("update"), None);
+}
+
+/// Verifies arena usage is summarized over the window.
+#[test]
+fn tracks_arena_usage() {
+    let profiler = SystemProfiler::with_window(3);
+    assert_eq!(profiler.arena_usage(), ArenaUsage::default());
+
+    for (used_bytes, capacity_bytes, grown_bytes) in [
+        (100, 1024, 1024),
+        (1000, 8192, 4096),
+        (2000, 16384, 0),
+        (3000, 8192, 0),
+    ] {
+        profiler.record_arena(ArenaStats {
+            used_bytes,
+            capacity_bytes,
+            grown_bytes,
+            threads: 2,
+        });
+    }
+
+    let usage = profiler.arena_usage();
+    assert_eq!(usage.capacity_bytes, 8192);
+    assert_eq!(usage.peak_capacity_bytes, 16384);
+    assert_eq!(usage.average_used_bytes, 2000);
+    assert_eq!(usage.grown_frames, 1);
+
+    profiler.reset();
+    assert_eq!(profiler.arena_usage(), ArenaUsage::default());
+}
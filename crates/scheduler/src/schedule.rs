@@ -0,0 +1,537 @@
+//! System schedules and their resolved execution order.
+//!
+//! A [`Schedule`] describes the systems of each stage: which data they read
+//! and write, and which systems they must run after or before. Resolving it
+//! yields a [`SchedulePlan`]: per stage, batches of systems that may run in
+//! parallel, plus every pair of systems whose data access conflicts. The
+//! plan renders as Graphviz DOT or Mermaid, annotated with timings from the
+//! [`SystemProfiler`](crate::SystemProfiler), so users can see why systems
+//! run in the order they do and where parallelism is lost.
+
+use crate::profiler::SystemTiming;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::time::Duration;
+
+/// A system's place in the schedule.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SystemDescriptor {
+    /// System name, unique across the schedule.
+    pub name: String,
+    /// Stage the system runs in.
+    pub stage: String,
+    /// Data the system reads, such as component or resource names.
+    pub reads: Vec<String>,
+    /// Data the system writes.
+    pub writes: Vec<String>,
+    /// Systems of the same stage this one runs after.
+    pub after: Vec<String>,
+    /// Systems of the same stage this one runs before.
+    pub before: Vec<String>,
+}
+
+impl SystemDescriptor {
+    /// Describes system `name` in `stage`.
+    #[must_use]
+    pub fn new(stage: &str, name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            stage: stage.to_owned(),
+            ..Self::default()
+        }
+    }
+
+    /// Declares that the system reads `data`.
+    #[must_use]
+    pub fn reads(mut self, data: &str) -> Self {
+        self.reads.push(data.to_owned());
+        self
+    }
+
+    /// Declares that the system writes `data`.
+    #[must_use]
+    pub fn writes(mut self, data: &str) -> Self {
+        self.writes.push(data.to_owned());
+        self
+    }
+
+    /// Runs the system after `system`.
+    #[must_use]
+    pub fn after(mut self, system: &str) -> Self {
+        self.after.push(system.to_owned());
+        self
+    }
+
+    /// Runs the system before `system`.
+    #[must_use]
+    pub fn before(mut self, system: &str) -> Self {
+        self.before.push(system.to_owned());
+        self
+    }
+
+    /// Returns the data both systems touch with at least one writing it.
+    fn conflicts_with(&self, other: &Self) -> Vec<String> {
+        let mut data: Vec<String> = self
+            .writes
+            .iter()
+            .filter(|d| other.writes.contains(d) || other.reads.contains(d))
+            .chain(self.reads.iter().filter(|d| other.writes.contains(d)))
+            .cloned()
+            .collect();
+        data.sort_unstable();
+        data.dedup();
+        data
+    }
+}
+
+/// Stages and the systems in them.
+///
+/// # Example
+///
+/// ```
+/// use scheduler::schedule::{Schedule, SystemDescriptor};
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_stage("update");
+/// schedule.add_system(SystemDescriptor::new("update", "input").writes("Intent"))?;
+/// schedule.add_system(SystemDescriptor::new("update", "movement").reads("Intent").writes("Transform"))?;
+/// schedule.add_system(SystemDescriptor::new("update", "audio").reads("Listener"))?;
+///
+/// let plan = schedule.plan()?;
+/// // Movement waits for input; audio shares nothing and runs alongside.
+/// assert_eq!(plan.stages[0].batches, [vec!["input", "audio"], vec!["movement"]]);
+/// assert!(plan.to_mermaid(&[]).contains("Intent"));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Schedule {
+    /// Stage names in execution order.
+    stages: Vec<String>,
+    /// Systems in registration order.
+    systems: Vec<SystemDescriptor>,
+}
+
+impl Schedule {
+    /// Appends a stage, run after the stages added before it.
+    pub fn add_stage(&mut self, name: &str) -> &mut Self {
+        if !self.stages.iter().any(|stage| stage == name) {
+            self.stages.push(name.to_owned());
+        }
+        self
+    }
+
+    /// Adds a system.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if its stage is unknown or its name is taken.
+    pub fn add_system(&mut self, system: SystemDescriptor) -> anyhow::Result<()> {
+        if !self.stages.contains(&system.stage) {
+            anyhow::bail!(
+                "system {} is in unknown stage {}",
+                system.name,
+                system.stage
+            );
+        }
+        if self
+            .systems
+            .iter()
+            .any(|existing| existing.name == system.name)
+        {
+            anyhow::bail!("system {} is already scheduled", system.name);
+        }
+        self.systems.push(system);
+        Ok(())
+    }
+
+    /// Returns the stage names in execution order.
+    #[must_use]
+    pub fn stages(&self) -> &[String] {
+        &self.stages
+    }
+
+    /// Returns the systems in registration order.
+    #[must_use]
+    pub fn systems(&self) -> &[SystemDescriptor] {
+        &self.systems
+    }
+
+    /// Resolves the execution order.
+    ///
+    /// Within a stage, a system joins the earliest batch after every system
+    /// it is ordered after. Conflicting systems without an explicit ordering
+    /// run in registration order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an ordering names a system missing from the
+    /// stage, or the orderings form a cycle.
+    pub fn plan(&self) -> anyhow::Result<SchedulePlan> {
+        let mut plan = SchedulePlan::default();
+        for stage in &self.stages {
+            let systems: Vec<&SystemDescriptor> =
+                self.systems.iter().filter(|s| &s.stage == stage).collect();
+            let index: HashMap<&str, usize> = systems
+                .iter()
+                .enumerate()
+                .map(|(i, s)| (s.name.as_str(), i))
+                .collect();
+            let lookup = |system: &SystemDescriptor, other: &str| {
+                index.get(other).copied().ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "{} is ordered against {other}, which is not in stage {stage}",
+                        system.name
+                    )
+                })
+            };
+
+            // Explicit orderings as edges from earlier to later.
+            let mut predecessors = vec![HashSet::new(); systems.len()];
+            for (i, system) in systems.iter().enumerate() {
+                for other in &system.after {
+                    let j = lookup(system, other)?;
+                    predecessors[i].insert(j);
+                    plan.orderings
+                        .push((systems[j].name.clone(), system.name.clone()));
+                }
+                for other in &system.before {
+                    let j = lookup(system, other)?;
+                    predecessors[j].insert(i);
+                    plan.orderings
+                        .push((system.name.clone(), systems[j].name.clone()));
+                }
+            }
+            let ordered = transitive_closure(&predecessors);
+
+            // Conflicts without an explicit ordering fall back to
+            // registration order.
+            let mut constraints = predecessors.clone();
+            for i in 0..systems.len() {
+                for j in 0..i {
+                    let data = systems[j].conflicts_with(systems[i]);
+                    if data.is_empty() {
+                        continue;
+                    }
+                    let explicit = ordered[i].contains(&j) || ordered[j].contains(&i);
+                    if !explicit {
+                        constraints[i].insert(j);
+                    }
+                    plan.conflicts.push(AccessConflict {
+                        stage: stage.clone(),
+                        first: systems[j].name.clone(),
+                        second: systems[i].name.clone(),
+                        data,
+                        ordered: explicit,
+                    });
+                }
+            }
+
+            let levels = levels(&constraints)
+                .ok_or_else(|| anyhow::anyhow!("system orderings in stage {stage} form a cycle"))?;
+            let mut batches: Vec<Vec<String>> = Vec::new();
+            for (i, &level) in levels.iter().enumerate() {
+                if batches.len() <= level {
+                    batches.resize(level + 1, Vec::new());
+                }
+                batches[level].push(systems[i].name.clone());
+            }
+            plan.stages.push(StagePlan {
+                name: stage.clone(),
+                batches,
+            });
+        }
+        Ok(plan)
+    }
+}
+
+/// Returns, for each node, every node that must come before it.
+fn transitive_closure(predecessors: &[HashSet<usize>]) -> Vec<HashSet<usize>> {
+    (0..predecessors.len())
+        .map(|start| {
+            let mut seen = HashSet::new();
+            let mut pending: Vec<usize> = predecessors[start].iter().copied().collect();
+            while let Some(node) = pending.pop() {
+                if seen.insert(node) {
+                    pending.extend(predecessors[node].iter().copied());
+                }
+            }
+            seen
+        })
+        .collect()
+}
+
+/// Returns each node's batch: one past its latest predecessor's, or `None`
+/// on a cycle.
+fn levels(predecessors: &[HashSet<usize>]) -> Option<Vec<usize>> {
+    let mut levels: Vec<Option<usize>> = vec![None; predecessors.len()];
+    for _ in 0..predecessors.len() {
+        let mut progressed = false;
+        for node in 0..predecessors.len() {
+            if levels[node].is_some() {
+                continue;
+            }
+            let before: Option<Vec<usize>> =
+                predecessors[node].iter().map(|&p| levels[p]).collect();
+            if let Some(before) = before {
+                levels[node] = Some(before.into_iter().max().map_or(0, |level| level + 1));
+                progressed = true;
+            }
+        }
+        if !progressed {
+            break;
+        }
+    }
+    levels.into_iter().collect()
+}
+
+/// Two systems of a stage touching the same data, at least one writing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessConflict {
+    /// Stage of both systems.
+    pub stage: String,
+    /// The system registered first.
+    pub first: String,
+    /// The system registered second.
+    pub second: String,
+    /// The data both touch.
+    pub data: Vec<String>,
+    /// Whether an explicit ordering decides which runs first; if not, they
+    /// run in registration order.
+    pub ordered: bool,
+}
+
+/// Resolved batches of one stage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StagePlan {
+    /// Stage name.
+    pub name: String,
+    /// Systems in each batch, in execution order; systems of a batch may
+    /// run in parallel.
+    pub batches: Vec<Vec<String>>,
+}
+
+/// Text format a [`SchedulePlan`] renders to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScheduleFormat {
+    /// Graphviz DOT, for `dot -Tsvg`.
+    Dot,
+    /// Mermaid flowchart, for Markdown viewers.
+    #[default]
+    Mermaid,
+}
+
+impl std::str::FromStr for ScheduleFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "dot" | "graphviz" => Ok(Self::Dot),
+            "mermaid" => Ok(Self::Mermaid),
+            other => {
+                anyhow::bail!("unknown schedule format `{other}`, expected `dot` or `mermaid`")
+            }
+        }
+    }
+}
+
+/// A single row of the schedule panel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleRow {
+    /// Stage the system runs in.
+    pub stage: String,
+    /// Batch within the stage, from 0.
+    pub batch: usize,
+    /// System name.
+    pub system: String,
+    /// Measured average time, if profiled.
+    pub average: Option<Duration>,
+    /// Systems it conflicts with and is serialized against only by
+    /// registration order.
+    pub conflicts: Vec<String>,
+}
+
+/// A resolved [`Schedule`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SchedulePlan {
+    /// Stages in execution order.
+    pub stages: Vec<StagePlan>,
+    /// Explicit orderings as `(earlier, later)` system pairs.
+    pub orderings: Vec<(String, String)>,
+    /// Conflicting data access between systems of a stage.
+    pub conflicts: Vec<AccessConflict>,
+}
+
+impl SchedulePlan {
+    /// Returns the conflicts that serialize systems without an explicit
+    /// ordering, which is where parallelism is usually lost by accident.
+    pub fn implicit_conflicts(&self) -> impl Iterator<Item = &AccessConflict> {
+        self.conflicts.iter().filter(|conflict| !conflict.ordered)
+    }
+
+    /// Returns one row per system in execution order, for the debug
+    /// overlay's schedule panel.
+    #[must_use]
+    pub fn rows(&self, timings: &[SystemTiming]) -> Vec<ScheduleRow> {
+        let mut rows = Vec::new();
+        for stage in &self.stages {
+            for (batch, systems) in stage.batches.iter().enumerate() {
+                for system in systems {
+                    let conflicts = self
+                        .implicit_conflicts()
+                        .filter_map(|conflict| {
+                            if &conflict.second == system {
+                                Some(conflict.first.clone())
+                            } else if &conflict.first == system {
+                                Some(conflict.second.clone())
+                            } else {
+                                None
+                            }
+                        })
+                        .collect();
+                    rows.push(ScheduleRow {
+                        stage: stage.name.clone(),
+                        batch,
+                        system: system.clone(),
+                        average: timings
+                            .iter()
+                            .find(|timing| &timing.system == system)
+                            .map(|timing| timing.average),
+                        conflicts,
+                    });
+                }
+            }
+        }
+        rows
+    }
+
+    /// Renders the plan in `format`.
+    #[must_use]
+    pub fn render(&self, format: ScheduleFormat, timings: &[SystemTiming]) -> String {
+        match format {
+            ScheduleFormat::Dot => self.to_dot(timings),
+            ScheduleFormat::Mermaid => self.to_mermaid(timings),
+        }
+    }
+
+    /// Renders the plan as a Graphviz DOT digraph: one cluster per stage,
+    /// one row per batch, solid edges for orderings, and dashed red edges
+    /// for conflicts. Systems with `timings` show their average time.
+    #[must_use]
+    pub fn to_dot(&self, timings: &[SystemTiming]) -> String {
+        let ids = self.node_ids();
+        let mut out = String::from("digraph schedule {\n    rankdir=LR;\n    node [shape=box];\n");
+        for (s, stage) in self.stages.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    subgraph cluster_{s} {{\n        label=\"{}\";",
+                escape(&stage.name)
+            );
+            for (b, batch) in stage.batches.iter().enumerate() {
+                let nodes: Vec<&str> = batch
+                    .iter()
+                    .map(|name| ids[name.as_str()].as_str())
+                    .collect();
+                let _ = writeln!(
+                    out,
+                    "        // batch {b}\n        {{ rank=same; {}; }}",
+                    nodes.join("; ")
+                );
+                for name in batch {
+                    let label = match average_ms(name, timings) {
+                        Some(time) => format!("{}\\n{time}", escape(name)),
+                        None => escape(name),
+                    };
+                    let _ = writeln!(out, "        {} [label=\"{label}\"];", ids[name.as_str()]);
+                }
+            }
+            out.push_str("    }\n");
+        }
+        for (earlier, later) in &self.orderings {
+            let _ = writeln!(
+                out,
+                "    {} -> {};",
+                ids[earlier.as_str()],
+                ids[later.as_str()]
+            );
+        }
+        for conflict in &self.conflicts {
+            let _ = writeln!(
+                out,
+                "    {} -> {} [style=dashed, color=red, dir=none, label=\"{}\"];",
+                ids[conflict.first.as_str()],
+                ids[conflict.second.as_str()],
+                escape(&conflict.data.join(", "))
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Renders the plan as a Mermaid flowchart, laid out like
+    /// [`to_dot`](Self::to_dot).
+    #[must_use]
+    pub fn to_mermaid(&self, timings: &[SystemTiming]) -> String {
+        let ids = self.node_ids();
+        let mut out = String::from("flowchart LR\n");
+        for (s, stage) in self.stages.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    subgraph stage{s}[\"{}\"]",
+                mermaid_text(&stage.name)
+            );
+            for name in stage.batches.iter().flatten() {
+                let label = match average_ms(name, timings) {
+                    Some(time) => format!("{}<br/>{time}", mermaid_text(name)),
+                    None => mermaid_text(name),
+                };
+                let _ = writeln!(out, "        {}[\"{label}\"]", ids[name.as_str()]);
+            }
+            out.push_str("    end\n");
+        }
+        for (earlier, later) in &self.orderings {
+            let _ = writeln!(
+                out,
+                "    {} --> {}",
+                ids[earlier.as_str()],
+                ids[later.as_str()]
+            );
+        }
+        for conflict in &self.conflicts {
+            let _ = writeln!(
+                out,
+                "    {} -. \"{}\" .- {}",
+                ids[conflict.first.as_str()],
+                mermaid_text(&conflict.data.join(", ")),
+                ids[conflict.second.as_str()]
+            );
+        }
+        out
+    }
+
+    /// Assigns systems identifiers safe in both formats.
+    fn node_ids(&self) -> HashMap<&str, String> {
+        let mut ids = HashMap::new();
+        for (s, stage) in self.stages.iter().enumerate() {
+            for (n, name) in stage.batches.iter().flatten().enumerate() {
+                ids.insert(name.as_str(), format!("s{s}_{n}"));
+            }
+        }
+        ids
+    }
+}
+
+/// Returns a system's measured average time, formatted for a label.
+fn average_ms(system: &str, timings: &[SystemTiming]) -> Option<String> {
+    timings
+        .iter()
+        .find(|timing| timing.system == system)
+        .map(|timing| format!("{:.2} ms", timing.average.as_secs_f64() * 1000.0))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn mermaid_text(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
@@ -0,0 +1,158 @@
+//! Unit tests for schedules and their plans.
+
+use crate::profiler::SystemTiming;
+use crate::schedule::{Schedule, ScheduleFormat, SystemDescriptor};
+use rustgine_core::Label;
+use std::time::Duration;
+
+fn schedule() -> Schedule {
+    let mut schedule = Schedule::default();
+    schedule.add_stage("update").add_stage("render");
+    for system in [
+        SystemDescriptor::new("update", "input").writes("Intent"),
+        SystemDescriptor::new("update", "ai")
+            .reads("Transform")
+            .writes("Intent"),
+        SystemDescriptor::new("update", "movement")
+            .reads("Intent")
+            .writes("Transform")
+            .after("ai"),
+        SystemDescriptor::new("update", "audio").reads("Listener"),
+        SystemDescriptor::new("render", "extract").reads("Transform"),
+        SystemDescriptor::new("render", "ui")
+            .reads("Score")
+            .before("extract"),
+    ] {
+        schedule.add_system(system).unwrap();
+    }
+    schedule
+}
+
+/// Verifies systems are batched by orderings and conflicts, per stage.
+#[test]
+fn test_plan_batches() {
+    let plan = schedule().plan().unwrap();
+    assert_eq!(plan.stages.len(), 2);
+    assert_eq!(plan.stages[0].name, "update");
+    assert_eq!(
+        plan.stages[0].batches,
+        [vec!["input", "audio"], vec!["ai"], vec!["movement"]]
+    );
+    assert_eq!(plan.stages[1].batches, [vec!["ui"], vec!["extract"]]);
+    assert_eq!(
+        plan.orderings,
+        [
+            ("ai".to_owned(), "movement".to_owned()),
+            ("ui".to_owned(), "extract".to_owned()),
+        ]
+    );
+}
+
+/// Verifies conflicts are reported with their data and whether an ordering
+/// decides them.
+#[test]
+fn test_plan_reports_conflicts() {
+    let plan = schedule().plan().unwrap();
+    let summary: Vec<(&str, &str, Vec<String>, bool)> = plan
+        .conflicts
+        .iter()
+        .map(|c| {
+            (
+                c.first.as_str(),
+                c.second.as_str(),
+                c.data.clone(),
+                c.ordered,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("input", "ai", vec!["Intent".to_owned()], false),
+            ("input", "movement", vec!["Intent".to_owned()], false),
+            (
+                "ai",
+                "movement",
+                vec!["Intent".to_owned(), "Transform".to_owned()],
+                true
+            ),
+        ]
+    );
+    assert_eq!(plan.implicit_conflicts().count(), 2);
+
+    let rows = plan.rows(&[]);
+    let ai = rows.iter().find(|row| row.system == "ai").unwrap();
+    assert_eq!(
+        (ai.batch, ai.conflicts.as_slice()),
+        (1, ["input".to_owned()].as_slice())
+    );
+}
+
+/// Verifies invalid schedules are rejected.
+#[test]
+fn test_invalid_schedules() {
+    let mut schedule = Schedule::default();
+    schedule.add_stage("update");
+    assert!(schedule
+        .add_system(SystemDescriptor::new("physics", "step"))
+        .is_err());
+    schedule
+        .add_system(SystemDescriptor::new("update", "a").after("b"))
+        .unwrap();
+    assert!(schedule
+        .add_system(SystemDescriptor::new("update", "a"))
+        .is_err());
+    assert!(schedule.plan().is_err());
+
+    schedule
+        .add_system(SystemDescriptor::new("update", "b").after("a"))
+        .unwrap();
+    let error = schedule.plan().unwrap_err().to_string();
+    assert!(error.contains("cycle"), "{error}");
+}
+
+/// Verifies DOT and Mermaid output name every system, edge, and timing.
+#[test]
+fn test_graph_exports() {
+    let plan = schedule().plan().unwrap();
+    let timings = [SystemTiming {
+        stage: Label::new("update"),
+        system: Label::new("movement"),
+        last: Duration::from_micros(500),
+        average: Duration::from_micros(420),
+        max: Duration::from_micros(900),
+        budget: None,
+        over_budget_frames: 0,
+    }];
+
+    let dot = plan.to_dot(&timings);
+    assert!(dot.starts_with("digraph schedule {"));
+    assert!(dot.contains("label=\"movement\\n0.42 ms\""));
+    assert!(dot.contains("subgraph cluster_1"));
+    assert_eq!(dot.matches(" -> ").count(), 2 + 3);
+    assert!(dot.contains("style=dashed"));
+
+    let mermaid = plan.to_mermaid(&timings);
+    assert!(mermaid.starts_with("flowchart LR"));
+    assert!(mermaid.contains("[\"movement<br/>0.42 ms\"]"));
+    assert!(mermaid.contains("subgraph stage1[\"render\"]"));
+    assert_eq!(mermaid.matches(" --> ").count(), 2);
+    assert!(mermaid.contains("-. \"Intent, Transform\" .-"));
+}
+
+/// Verifies format names parse case-insensitively and render accordingly.
+#[test]
+fn test_schedule_format() {
+    assert_eq!(
+        "DOT".parse::<ScheduleFormat>().unwrap(),
+        ScheduleFormat::Dot
+    );
+    assert_eq!(
+        "mermaid".parse::<ScheduleFormat>().unwrap(),
+        ScheduleFormat::Mermaid
+    );
+    assert!("svg".parse::<ScheduleFormat>().is_err());
+
+    let plan = schedule().plan().unwrap();
+    assert_eq!(plan.render(ScheduleFormat::Dot, &[]), plan.to_dot(&[]));
+}
@@ -2,7 +2,7 @@
 //!
 //! Provides the [`RustgineScheduler`] system for managing concurrent task execution.
 
-use rustgine_core::RustgineSystem;
+use rustgine_core::{RustgineError, RustgineSystem};
 
 /// Task scheduling subsystem for the Rustgine engine.
 ///
@@ -38,7 +38,7 @@ impl RustgineSystem for RustgineScheduler {
     ///
     /// Returns an error if thread pool creation fails.
     #[inline]
-    fn startup(&mut self) -> anyhow::Result<()> {
+    fn startup(&mut self) -> Result<(), RustgineError> {
         Ok(())
     }
 
@@ -48,7 +48,7 @@ impl RustgineSystem for RustgineScheduler {
     ///
     /// Returns an error if worker thread shutdown fails.
     #[inline]
-    fn shutdown(&mut self) -> anyhow::Result<()> {
+    fn shutdown(&mut self) -> Result<(), RustgineError> {
         Ok(())
     }
 }
@@ -0,0 +1,233 @@
+//! UI values bound to ECS components and resources.
+//!
+//! Health bars and score labels usually need a system that copies game
+//! state into widgets every frame. A binding replaces it: it names a widget,
+//! a component or resource, and a function from that data to the widget's
+//! text or progress. [`UiBindings::update`] re-runs a binding only when its
+//! data was written since the last update, using the world's change ticks,
+//! and reports which widgets got new values:
+//!
+//! ```
+//! use ecs::World;
+//! use ui::{UiBindings, WidgetId};
+//!
+//! struct Health(u32);
+//!
+//! let mut world = World::default();
+//! let player = world.spawn();
+//! world.insert(player, Health(100));
+//!
+//! let label = WidgetId(1);
+//! let mut bindings = UiBindings::default();
+//! bindings.bind_text(label, player, |health: &Health| format!("{} HP", health.0));
+//!
+//! assert_eq!(bindings.update(&world), [label]);
+//! assert_eq!(bindings.text(label), Some("100 HP"));
+//!
+//! // Nothing was written, so nothing is formatted.
+//! assert!(bindings.update(&world).is_empty());
+//!
+//! world.get_mut::<Health>(player).unwrap().0 = 75;
+//! bindings.update(&world);
+//! assert_eq!(bindings.text(label), Some("75 HP"));
+//! ```
+
+use crate::focus::WidgetId;
+use ecs::{Entity, Tick, World};
+use std::collections::HashMap;
+
+/// A value shown by a widget.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoundValue {
+    /// Text of a label.
+    Text(String),
+    /// Fill of a progress bar, from 0 to 1.
+    Progress(f32),
+}
+
+/// Computes a binding's value if its data changed after the given tick, or
+/// always if there is no tick yet.
+type Evaluate = Box<dyn Fn(&World, Option<Tick>) -> Option<BoundValue> + Send + Sync>;
+
+/// A widget bound to data.
+struct Binding {
+    /// Widget that shows the value.
+    widget: WidgetId,
+    /// World tick at the last update, if any.
+    seen: Option<Tick>,
+    /// Computes the value.
+    evaluate: Evaluate,
+}
+
+/// Widgets bound to components and resources, with their current values.
+#[derive(Default)]
+pub struct UiBindings {
+    /// Bindings in the order they were made.
+    bindings: Vec<Binding>,
+    /// Latest value of each bound widget.
+    values: HashMap<WidgetId, BoundValue>,
+}
+
+impl std::fmt::Debug for UiBindings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UiBindings")
+            .field("bindings", &self.bindings.len())
+            .field("values", &self.values)
+            .finish()
+    }
+}
+
+impl UiBindings {
+    /// Binds a label's text to a component of `entity`.
+    pub fn bind_text<C: Send + Sync + 'static>(
+        &mut self,
+        widget: WidgetId,
+        entity: Entity,
+        text: impl Fn(&C) -> String + Send + Sync + 'static,
+    ) {
+        self.bind(
+            widget,
+            component(entity, move |c| BoundValue::Text(text(c))),
+        );
+    }
+
+    /// Binds a progress bar's fill to a component of `entity`.
+    ///
+    /// The fill is clamped to 0..=1.
+    pub fn bind_progress<C: Send + Sync + 'static>(
+        &mut self,
+        widget: WidgetId,
+        entity: Entity,
+        fill: impl Fn(&C) -> f32 + Send + Sync + 'static,
+    ) {
+        self.bind(widget, component(entity, move |c| progress(fill(c))));
+    }
+
+    /// Binds a label's text to a resource.
+    pub fn bind_resource_text<R: Send + Sync + 'static>(
+        &mut self,
+        widget: WidgetId,
+        text: impl Fn(&R) -> String + Send + Sync + 'static,
+    ) {
+        self.bind(widget, resource(move |r| BoundValue::Text(text(r))));
+    }
+
+    /// Binds a progress bar's fill to a resource.
+    ///
+    /// The fill is clamped to 0..=1.
+    pub fn bind_resource_progress<R: Send + Sync + 'static>(
+        &mut self,
+        widget: WidgetId,
+        fill: impl Fn(&R) -> f32 + Send + Sync + 'static,
+    ) {
+        self.bind(widget, resource(move |r| progress(fill(r))));
+    }
+
+    /// Removes a widget's binding and value.
+    pub fn unbind(&mut self, widget: WidgetId) {
+        self.bindings.retain(|binding| binding.widget != widget);
+        self.values.remove(&widget);
+    }
+
+    /// Returns the number of bindings.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.bindings.len()
+    }
+
+    /// Returns `true` if nothing is bound.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    /// Re-evaluates the bindings whose data changed since the last update,
+    /// returning the widgets whose values changed.
+    ///
+    /// Widgets keep their last value while their entity or resource is
+    /// missing.
+    pub fn update(&mut self, world: &World) -> Vec<WidgetId> {
+        let now = world.change_tick();
+        let mut updated = Vec::new();
+        for binding in &mut self.bindings {
+            let value = (binding.evaluate)(world, binding.seen);
+            binding.seen = Some(now);
+            let Some(value) = value else {
+                continue;
+            };
+            if self.values.get(&binding.widget) != Some(&value) {
+                self.values.insert(binding.widget, value);
+                updated.push(binding.widget);
+            }
+        }
+        updated
+    }
+
+    /// Returns a widget's current value.
+    #[must_use]
+    pub fn value(&self, widget: WidgetId) -> Option<&BoundValue> {
+        self.values.get(&widget)
+    }
+
+    /// Returns a label's current text.
+    #[must_use]
+    pub fn text(&self, widget: WidgetId) -> Option<&str> {
+        match self.values.get(&widget)? {
+            BoundValue::Text(text) => Some(text),
+            BoundValue::Progress(_) => None,
+        }
+    }
+
+    /// Returns a progress bar's current fill.
+    #[must_use]
+    pub fn progress(&self, widget: WidgetId) -> Option<f32> {
+        match self.values.get(&widget)? {
+            BoundValue::Progress(fill) => Some(*fill),
+            BoundValue::Text(_) => None,
+        }
+    }
+
+    /// Replaces a widget's binding.
+    fn bind(&mut self, widget: WidgetId, evaluate: Evaluate) {
+        self.unbind(widget);
+        self.bindings.push(Binding {
+            widget,
+            seen: None,
+            evaluate,
+        });
+    }
+}
+
+fn component<C: Send + Sync + 'static>(
+    entity: Entity,
+    value: impl Fn(&C) -> BoundValue + Send + Sync + 'static,
+) -> Evaluate {
+    Box::new(move |world, seen| {
+        let changed = world.changed_tick::<C>(entity)?;
+        if seen.is_some_and(|seen| changed <= seen) {
+            return None;
+        }
+        world.get::<C>(entity).map(&value)
+    })
+}
+
+fn resource<R: Send + Sync + 'static>(
+    value: impl Fn(&R) -> BoundValue + Send + Sync + 'static,
+) -> Evaluate {
+    Box::new(move |world, seen| {
+        let changed = world.resource_changed_tick::<R>()?;
+        if seen.is_some_and(|seen| changed <= seen) {
+            return None;
+        }
+        world.resource::<R>().map(&value)
+    })
+}
+
+fn progress(fill: f32) -> BoundValue {
+    // NaN, say from dividing by a zero maximum, shows as empty.
+    BoundValue::Progress(if fill.is_nan() {
+        0.0
+    } else {
+        fill.clamp(0.0, 1.0)
+    })
+}
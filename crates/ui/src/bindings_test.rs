@@ -0,0 +1,118 @@
+//! Unit tests for UI data bindings.
+
+use crate::bindings::{BoundValue, UiBindings};
+use crate::focus::WidgetId;
+use ecs::World;
+
+struct Health {
+    current: u32,
+    max: u32,
+}
+
+struct Score(u64);
+
+/// Verifies that bindings only re-run when their data is written.
+#[test]
+fn updates_only_on_change() {
+    let mut world = World::default();
+    let player = world.spawn();
+    let enemy = world.spawn();
+    world.insert(
+        player,
+        Health {
+            current: 80,
+            max: 100,
+        },
+    );
+    world.insert(
+        enemy,
+        Health {
+            current: 10,
+            max: 40,
+        },
+    );
+
+    let (label, bar) = (WidgetId(1), WidgetId(2));
+    let mut bindings = UiBindings::default();
+    bindings.bind_text(label, player, |h: &Health| format!("{} HP", h.current));
+    #[allow(clippy::cast_precision_loss)]
+    bindings.bind_progress(bar, player, |h: &Health| h.current as f32 / h.max as f32);
+
+    assert_eq!(bindings.update(&world), [label, bar]);
+    assert_eq!(bindings.text(label), Some("80 HP"));
+    assert_eq!(
+        bindings.progress(bar).map(f32::to_bits),
+        Some(0.8_f32.to_bits())
+    );
+
+    world.get_mut::<Health>(enemy).unwrap().current = 0;
+    assert!(bindings.update(&world).is_empty());
+
+    world.get_mut::<Health>(player).unwrap().current = 50;
+    assert_eq!(bindings.update(&world), [label, bar]);
+    assert_eq!(bindings.text(label), Some("50 HP"));
+}
+
+/// Verifies that a write producing the same value reports no update.
+#[test]
+fn skips_unchanged_values() {
+    let mut world = World::default();
+    let player = world.spawn();
+    world.insert(player, Health { current: 3, max: 3 });
+
+    let label = WidgetId(1);
+    let mut bindings = UiBindings::default();
+    bindings.bind_text(label, player, |h: &Health| {
+        format!("{}/{}", h.current, h.max)
+    });
+    bindings.update(&world);
+
+    world.get_mut::<Health>(player).unwrap().current = 3;
+    assert!(bindings.update(&world).is_empty());
+}
+
+/// Verifies resource bindings and progress clamping.
+#[test]
+fn binds_resources() {
+    let mut world = World::default();
+    world.insert_resource(Score(7));
+
+    let (label, bar) = (WidgetId(1), WidgetId(2));
+    let mut bindings = UiBindings::default();
+    bindings.bind_resource_text(label, |score: &Score| format!("Score {}", score.0));
+    bindings.bind_resource_progress(bar, |_: &Score| f32::NAN);
+    bindings.update(&world);
+    assert_eq!(bindings.text(label), Some("Score 7"));
+    assert_eq!(
+        bindings.progress(bar).map(f32::to_bits),
+        Some(0.0_f32.to_bits())
+    );
+
+    bindings.bind_resource_progress(bar, |_: &Score| 3.0);
+    world.resource_mut::<Score>().unwrap().0 = 9;
+    assert_eq!(bindings.update(&world), [label, bar]);
+    assert_eq!(bindings.value(bar), Some(&BoundValue::Progress(1.0)));
+    assert_eq!(bindings.len(), 2);
+}
+
+/// Verifies that widgets keep their value when the data disappears, and
+/// that unbinding drops it.
+#[test]
+fn keeps_value_when_source_missing() {
+    let mut world = World::default();
+    let player = world.spawn();
+    world.insert(player, Health { current: 1, max: 1 });
+
+    let label = WidgetId(1);
+    let mut bindings = UiBindings::default();
+    bindings.bind_text(label, player, |h: &Health| h.current.to_string());
+    bindings.update(&world);
+
+    world.despawn(player);
+    assert!(bindings.update(&world).is_empty());
+    assert_eq!(bindings.text(label), Some("1"));
+
+    bindings.unbind(label);
+    assert!(bindings.is_empty());
+    assert_eq!(bindings.text(label), None);
+}
@@ -0,0 +1,418 @@
+//! Keyboard and gamepad focus between widgets.
+//!
+//! Exactly one widget at a time has focus and receives confirm input. The
+//! [`FocusManager`] tracks the focusable widgets of the active screen and
+//! moves focus in response to [`NavCommand`]s: through tab order with
+//! Tab and Shift+Tab or the shoulder buttons, or to the nearest widget in
+//! a direction with the arrow keys, d-pad, or stick.
+//!
+//! A screen may name a default button, activated by confirm when the
+//! focused widget does not take it (a text field, say), and a cancel
+//! button, activated by back or Escape from anywhere on the screen.
+//!
+//! Focus rings follow the input device: they are shown while the player
+//! navigates with a keyboard or gamepad and hidden while they point with a
+//! mouse or finger, so clicking a button does not leave a ring around it.
+
+use crate::navigation::{NavCommand, NavDirection};
+use crate::prompt::InputDevice;
+use ecs::Events;
+use math::{Color, Rect, Vec2};
+use std::cmp::Ordering;
+
+/// Identifies a widget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WidgetId(pub u64);
+
+/// A widget that can take focus.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Focusable {
+    /// Screen rectangle, with y down, used for directional navigation and
+    /// focus rings.
+    pub rect: Rect,
+    /// Position in tab order; ties keep insertion order.
+    pub tab_index: i32,
+    /// Whether the widget can take focus.
+    pub enabled: bool,
+    /// Whether confirm activates the widget. Widgets such as text fields
+    /// set this to `false` so confirm activates the default button instead.
+    pub activatable: bool,
+}
+
+impl Focusable {
+    /// Creates an enabled, activatable widget at `rect`.
+    #[must_use]
+    pub fn new(rect: Rect) -> Self {
+        Self {
+            rect,
+            tab_index: 0,
+            enabled: true,
+            activatable: true,
+        }
+    }
+}
+
+/// A change in focus or a widget activation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusEvent {
+    /// Focus moved between widgets.
+    Changed {
+        /// Widget that lost focus.
+        previous: Option<WidgetId>,
+        /// Widget that gained focus.
+        current: Option<WidgetId>,
+    },
+    /// A widget was activated by confirm, or as the cancel button.
+    Activated(WidgetId),
+    /// Cancel was pressed on a screen without a cancel button.
+    Cancelled,
+}
+
+/// How focus rings are drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FocusStyle {
+    /// Ring color.
+    pub color: Color,
+    /// Line width in pixels.
+    pub width: f32,
+    /// Gap between the widget and the ring in pixels.
+    pub padding: f32,
+}
+
+impl Default for FocusStyle {
+    fn default() -> Self {
+        Self {
+            color: Color::linear_rgb(1.0, 0.8, 0.2),
+            width: 2.0,
+            padding: 2.0,
+        }
+    }
+}
+
+/// A focus ring to draw.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FocusRing {
+    /// Widget the ring surrounds.
+    pub widget: WidgetId,
+    /// Outer rectangle of the ring.
+    pub rect: Rect,
+    /// Ring color.
+    pub color: Color,
+    /// Line width in pixels.
+    pub width: f32,
+}
+
+/// Tracks focus between the widgets of a screen.
+///
+/// # Example
+///
+/// ```
+/// use math::{Rect, Vec2};
+/// use ui::{FocusManager, Focusable, NavCommand, NavDirection, WidgetId};
+///
+/// let mut focus = FocusManager::default();
+/// let top = Rect::new(Vec2::ZERO, Vec2::new(100.0, 40.0));
+/// let bottom = Rect::new(Vec2::new(0.0, 50.0), Vec2::new(100.0, 90.0));
+/// focus.insert(WidgetId(1), Focusable::new(top));
+/// focus.insert(WidgetId(2), Focusable::new(bottom));
+///
+/// focus.handle(NavCommand::Next);
+/// assert_eq!(focus.focused(), Some(WidgetId(1)));
+/// focus.handle(NavCommand::Move(NavDirection::Down));
+/// assert_eq!(focus.focused(), Some(WidgetId(2)));
+/// ```
+#[derive(Debug)]
+pub struct FocusManager {
+    /// Focusable widgets in insertion order.
+    widgets: Vec<(WidgetId, Focusable)>,
+    /// Widget with focus.
+    focused: Option<WidgetId>,
+    /// Widget activated by confirm when the focused one does not take it.
+    default_button: Option<WidgetId>,
+    /// Widget activated by cancel.
+    cancel_button: Option<WidgetId>,
+    /// Whether the focus ring is shown.
+    focus_visible: bool,
+    /// How the focus ring is drawn.
+    style: FocusStyle,
+    /// Focus changes and activations.
+    events: Events<FocusEvent>,
+}
+
+impl Default for FocusManager {
+    fn default() -> Self {
+        Self {
+            widgets: Vec::new(),
+            focused: None,
+            default_button: None,
+            cancel_button: None,
+            focus_visible: !InputDevice::default().is_pointer(),
+            style: FocusStyle::default(),
+            events: Events::default(),
+        }
+    }
+}
+
+impl FocusManager {
+    /// Sets how focus rings are drawn.
+    #[must_use]
+    pub fn with_style(mut self, style: FocusStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Adds a widget, or updates one added before.
+    ///
+    /// A focused widget that becomes disabled loses focus.
+    pub fn insert(&mut self, id: WidgetId, focusable: Focusable) {
+        match self.widgets.iter_mut().find(|(widget, _)| *widget == id) {
+            Some((_, existing)) => *existing = focusable,
+            None => self.widgets.push((id, focusable)),
+        }
+        if !focusable.enabled && self.focused == Some(id) {
+            self.set_focus(None);
+        }
+    }
+
+    /// Removes a widget, clearing focus and button roles that named it.
+    pub fn remove(&mut self, id: WidgetId) {
+        self.widgets.retain(|(widget, _)| *widget != id);
+        if self.focused == Some(id) {
+            self.set_focus(None);
+        }
+        if self.default_button == Some(id) {
+            self.default_button = None;
+        }
+        if self.cancel_button == Some(id) {
+            self.cancel_button = None;
+        }
+    }
+
+    /// Removes every widget, e.g. when the screen changes.
+    pub fn clear(&mut self) {
+        self.set_focus(None);
+        self.widgets.clear();
+        self.default_button = None;
+        self.cancel_button = None;
+    }
+
+    /// Returns a widget.
+    #[must_use]
+    pub fn get(&self, id: WidgetId) -> Option<&Focusable> {
+        self.widgets
+            .iter()
+            .find(|(widget, _)| *widget == id)
+            .map(|(_, focusable)| focusable)
+    }
+
+    /// Returns the widget with focus.
+    #[must_use]
+    #[inline]
+    pub fn focused(&self) -> Option<WidgetId> {
+        self.focused
+    }
+
+    /// Focuses a widget, returning `false` if it is missing or disabled.
+    pub fn focus(&mut self, id: WidgetId) -> bool {
+        if !self.is_enabled(id) {
+            return false;
+        }
+        self.set_focus(Some(id));
+        true
+    }
+
+    /// Removes focus from every widget.
+    pub fn blur(&mut self) {
+        self.set_focus(None);
+    }
+
+    /// Sets the button confirm activates when the focused widget does not
+    /// take it, or when nothing has focus.
+    pub fn set_default_button(&mut self, id: Option<WidgetId>) {
+        self.default_button = id;
+    }
+
+    /// Returns the default button.
+    #[must_use]
+    #[inline]
+    pub fn default_button(&self) -> Option<WidgetId> {
+        self.default_button
+    }
+
+    /// Sets the button cancel activates.
+    pub fn set_cancel_button(&mut self, id: Option<WidgetId>) {
+        self.cancel_button = id;
+    }
+
+    /// Returns the cancel button.
+    #[must_use]
+    #[inline]
+    pub fn cancel_button(&self) -> Option<WidgetId> {
+        self.cancel_button
+    }
+
+    /// Shows focus rings for keyboard and gamepad input and hides them for
+    /// pointer input.
+    pub fn set_input_device(&mut self, device: InputDevice) {
+        self.focus_visible = !device.is_pointer();
+    }
+
+    /// Returns `true` if the focus ring is shown.
+    #[must_use]
+    #[inline]
+    pub fn focus_visible(&self) -> bool {
+        self.focus_visible
+    }
+
+    /// Returns the focus ring to draw this frame, if any.
+    #[must_use]
+    pub fn focus_ring(&self) -> Option<FocusRing> {
+        if !self.focus_visible {
+            return None;
+        }
+        let widget = self.focused?;
+        let rect = self.get(widget)?.rect;
+        let inset = Vec2::splat(self.style.padding + self.style.width);
+        Some(FocusRing {
+            widget,
+            rect: Rect {
+                min: rect.min - inset,
+                max: rect.max + inset,
+            },
+            color: self.style.color,
+            width: self.style.width,
+        })
+    }
+
+    /// Applies a navigation command, returning `true` if focus changed or a
+    /// widget was activated.
+    pub fn handle(&mut self, command: NavCommand) -> bool {
+        match command {
+            NavCommand::Next => self.step(true),
+            NavCommand::Previous => self.step(false),
+            NavCommand::Move(direction) => self.move_towards(direction),
+            NavCommand::Submit => self.submit(),
+            NavCommand::Cancel => self.cancel(),
+        }
+    }
+
+    /// Returns focus changes and activations from this frame and last.
+    #[must_use]
+    #[inline]
+    pub fn events(&self) -> &Events<FocusEvent> {
+        &self.events
+    }
+
+    /// Advances the event queue by one frame.
+    pub fn update(&mut self) {
+        self.events.update();
+    }
+
+    fn is_enabled(&self, id: WidgetId) -> bool {
+        self.get(id).is_some_and(|focusable| focusable.enabled)
+    }
+
+    fn set_focus(&mut self, current: Option<WidgetId>) {
+        let previous = self.focused;
+        if previous != current {
+            self.focused = current;
+            self.events.send(FocusEvent::Changed { previous, current });
+        }
+    }
+
+    /// Returns the enabled widgets in tab order.
+    fn tab_order(&self) -> Vec<(WidgetId, &Focusable)> {
+        let mut order: Vec<_> = self
+            .widgets
+            .iter()
+            .filter(|(_, focusable)| focusable.enabled)
+            .map(|(id, focusable)| (*id, focusable))
+            .collect();
+        // Stable, so equal tab indices keep insertion order.
+        order.sort_by_key(|(_, focusable)| focusable.tab_index);
+        order
+    }
+
+    /// Focuses the next or previous widget in tab order, wrapping around.
+    fn step(&mut self, forward: bool) -> bool {
+        let order = self.tab_order();
+        if order.is_empty() {
+            return false;
+        }
+        let position = self
+            .focused
+            .and_then(|focused| order.iter().position(|(id, _)| *id == focused));
+        let next = match (position, forward) {
+            (None, true) => 0,
+            (None, false) => order.len() - 1,
+            (Some(index), true) => (index + 1) % order.len(),
+            (Some(index), false) => (index + order.len() - 1) % order.len(),
+        };
+        let id = order[next].0;
+        let changed = self.focused != Some(id);
+        self.set_focus(Some(id));
+        changed
+    }
+
+    /// Focuses the nearest widget in `direction` from the focused one.
+    ///
+    /// Without focus, the default button or else the first widget in tab
+    /// order takes it, so the first press of a d-pad lands somewhere useful.
+    fn move_towards(&mut self, direction: NavDirection) -> bool {
+        let Some(current) = self.focused.and_then(|id| self.get(id)) else {
+            let start = self
+                .default_button
+                .filter(|id| self.is_enabled(*id))
+                .or_else(|| self.tab_order().first().map(|(id, _)| *id));
+            return start.is_some_and(|id| self.focus(id));
+        };
+        let Vec2 { x: cx, y: cy } = current.rect.center();
+        let best = self
+            .tab_order()
+            .into_iter()
+            .filter(|(id, _)| Some(*id) != self.focused)
+            .filter_map(|(id, focusable)| {
+                let Vec2 { x, y } = focusable.rect.center();
+                let (along, across) = match direction {
+                    NavDirection::Up => (cy - y, x - cx),
+                    NavDirection::Down => (y - cy, x - cx),
+                    NavDirection::Left => (cx - x, y - cy),
+                    NavDirection::Right => (x - cx, y - cy),
+                };
+                // Widgets off to the side count double, so a widget straight
+                // ahead beats a closer one diagonally.
+                (along > 0.0).then(|| (id, along + 2.0 * across.abs()))
+            })
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        match best {
+            Some((id, _)) => {
+                self.set_focus(Some(id));
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn submit(&mut self) -> bool {
+        let focused = self
+            .focused
+            .filter(|id| self.get(*id).is_some_and(|focusable| focusable.activatable));
+        let default = self.default_button.filter(|id| self.is_enabled(*id));
+        match focused.or(default) {
+            Some(id) => {
+                self.events.send(FocusEvent::Activated(id));
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn cancel(&mut self) -> bool {
+        let event = match self.cancel_button {
+            Some(id) => FocusEvent::Activated(id),
+            None => FocusEvent::Cancelled,
+        };
+        self.events.send(event);
+        true
+    }
+}
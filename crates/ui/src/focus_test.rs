@@ -0,0 +1,175 @@
+//! Unit tests for widget focus.
+
+use crate::focus::{FocusEvent, FocusManager, Focusable, WidgetId};
+use crate::navigation::{NavCommand, NavDirection};
+use crate::prompt::{GamepadFamily, InputDevice};
+use math::{Rect, Vec2};
+
+fn rect(x0: f32, y0: f32, x1: f32, y1: f32) -> Rect {
+    Rect::new(Vec2::new(x0, y0), Vec2::new(x1, y1))
+}
+
+/// Lays out a 2x2 grid of buttons: 1 2 / 3 4.
+fn grid() -> FocusManager {
+    let mut focus = FocusManager::default();
+    for (id, x, y) in [
+        (1, 0.0, 0.0),
+        (2, 100.0, 0.0),
+        (3, 0.0, 50.0),
+        (4, 100.0, 50.0),
+    ] {
+        focus.insert(WidgetId(id), Focusable::new(rect(x, y, x + 80.0, y + 40.0)));
+    }
+    focus
+}
+
+fn activated(focus: &FocusManager) -> Vec<FocusEvent> {
+    focus
+        .events()
+        .iter()
+        .filter(|event| !matches!(event, FocusEvent::Changed { .. }))
+        .copied()
+        .collect()
+}
+
+/// Verifies that Tab order follows tab indices, wraps, and skips disabled
+/// widgets.
+#[test]
+fn tabs_through_widgets() {
+    let mut focus = grid();
+    focus.insert(
+        WidgetId(4),
+        Focusable {
+            tab_index: -1,
+            ..Focusable::new(rect(100.0, 50.0, 180.0, 90.0))
+        },
+    );
+    focus.insert(
+        WidgetId(2),
+        Focusable {
+            enabled: false,
+            ..Focusable::new(rect(100.0, 0.0, 180.0, 40.0))
+        },
+    );
+
+    let mut visited = Vec::new();
+    for _ in 0..4 {
+        focus.handle(NavCommand::Next);
+        visited.push(focus.focused().unwrap().0);
+    }
+    assert_eq!(visited, [4, 1, 3, 4]);
+
+    focus.handle(NavCommand::Previous);
+    assert_eq!(focus.focused(), Some(WidgetId(3)));
+}
+
+/// Verifies that directional moves pick the nearest widget that way.
+#[test]
+fn moves_spatially() {
+    let mut focus = grid();
+    assert!(focus.focus(WidgetId(1)));
+
+    assert!(focus.handle(NavCommand::Move(NavDirection::Right)));
+    assert_eq!(focus.focused(), Some(WidgetId(2)));
+    assert!(focus.handle(NavCommand::Move(NavDirection::Down)));
+    assert_eq!(focus.focused(), Some(WidgetId(4)));
+    assert!(!focus.handle(NavCommand::Move(NavDirection::Down)));
+    assert_eq!(focus.focused(), Some(WidgetId(4)));
+    assert!(focus.handle(NavCommand::Move(NavDirection::Left)));
+    assert_eq!(focus.focused(), Some(WidgetId(3)));
+    assert!(focus.handle(NavCommand::Move(NavDirection::Up)));
+    assert_eq!(focus.focused(), Some(WidgetId(1)));
+}
+
+/// Verifies that the first move without focus lands on the default button.
+#[test]
+fn first_move_focuses_default_button() {
+    let mut focus = grid();
+    focus.set_default_button(Some(WidgetId(3)));
+
+    focus.handle(NavCommand::Move(NavDirection::Up));
+    assert_eq!(focus.focused(), Some(WidgetId(3)));
+    assert_eq!(
+        focus.events().iter().copied().collect::<Vec<_>>(),
+        [FocusEvent::Changed {
+            previous: None,
+            current: Some(WidgetId(3))
+        }]
+    );
+}
+
+/// Verifies default and cancel button semantics.
+#[test]
+fn submits_and_cancels() {
+    let mut focus = grid();
+    focus.insert(
+        WidgetId(5),
+        Focusable {
+            activatable: false,
+            ..Focusable::new(rect(0.0, 100.0, 180.0, 120.0))
+        },
+    );
+    focus.set_default_button(Some(WidgetId(4)));
+
+    assert!(focus.handle(NavCommand::Cancel));
+    focus.set_cancel_button(Some(WidgetId(3)));
+    focus.handle(NavCommand::Cancel);
+    focus.focus(WidgetId(2));
+    focus.handle(NavCommand::Submit);
+    focus.focus(WidgetId(5));
+    focus.handle(NavCommand::Submit);
+
+    assert_eq!(
+        activated(&focus),
+        [
+            FocusEvent::Cancelled,
+            FocusEvent::Activated(WidgetId(3)),
+            FocusEvent::Activated(WidgetId(2)),
+            FocusEvent::Activated(WidgetId(4)),
+        ]
+    );
+
+    focus.remove(WidgetId(4));
+    focus.update();
+    focus.update();
+    assert!(!focus.handle(NavCommand::Submit));
+    assert!(focus.events().is_empty());
+}
+
+/// Verifies that focus rings follow the input device.
+#[test]
+fn focus_ring_follows_device() {
+    let mut focus = grid();
+    assert!(focus.focus_ring().is_none());
+    focus.focus(WidgetId(1));
+
+    let ring = focus.focus_ring().unwrap();
+    assert_eq!(ring.widget, WidgetId(1));
+    assert_eq!(ring.rect, rect(-4.0, -4.0, 84.0, 44.0));
+
+    focus.set_input_device(InputDevice::Mouse);
+    assert!(focus.focus_ring().is_none());
+    focus.set_input_device(InputDevice::Gamepad(GamepadFamily::Xbox));
+    assert!(focus.focus_ring().is_some());
+}
+
+/// Verifies that disabling or removing the focused widget clears focus.
+#[test]
+fn losing_widget_clears_focus() {
+    let mut focus = grid();
+    focus.focus(WidgetId(2));
+    focus.insert(
+        WidgetId(2),
+        Focusable {
+            enabled: false,
+            ..Focusable::new(rect(100.0, 0.0, 180.0, 40.0))
+        },
+    );
+    assert_eq!(focus.focused(), None);
+    assert!(!focus.focus(WidgetId(2)));
+
+    focus.focus(WidgetId(1));
+    focus.remove(WidgetId(1));
+    assert_eq!(focus.focused(), None);
+    assert!(!focus.focus(WidgetId(1)));
+}
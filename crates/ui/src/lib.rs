@@ -0,0 +1,47 @@
+//! User interface subsystem for the Rustgine game engine.
+//!
+//! This crate provides the engine's UI building blocks: widget focus,
+//! navigation with keyboard and gamepad, and input prompts.
+//!
+//! # Overview
+//!
+//! The UI crate handles:
+//! - Focus between widgets, moved with Tab, arrow keys, or a gamepad stick
+//! - Focus rings shown only while navigating without a pointer
+//! - Default and cancel buttons triggered by confirm and back inputs
+//! - Button prompts matching the last-used input device
+//! - Labels and progress bars bound to ECS components and resources
+//!
+//! # Example
+//!
+//! ```ignore
+//! use ui::RustgineUi;
+//! use rustgine_core::RustgineSystem;
+//!
+//! let mut ui = RustgineUi::default();
+//! ui.startup()?;
+//! ```
+
+#![warn(missing_docs)]
+#![warn(clippy::all, clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+
+pub mod bindings;
+#[cfg(test)]
+mod bindings_test;
+pub mod focus;
+#[cfg(test)]
+mod focus_test;
+pub mod navigation;
+#[cfg(test)]
+mod navigation_test;
+pub mod prompt;
+#[cfg(test)]
+mod prompt_test;
+pub mod ui;
+
+pub use bindings::{BoundValue, UiBindings};
+pub use focus::{FocusEvent, FocusManager, FocusRing, FocusStyle, Focusable, WidgetId};
+pub use navigation::{keyboard_commands, NavCommand, NavDirection, StickNavigator};
+pub use prompt::{GamepadFamily, InputDevice, InputDeviceTracker, PromptGlyphs};
+pub use ui::RustgineUi;
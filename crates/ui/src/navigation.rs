@@ -0,0 +1,178 @@
+//! Navigation commands from keyboard and gamepad input.
+//!
+//! The focus model only understands [`NavCommand`]s. This module turns
+//! device input into commands: [`keyboard_commands`] maps Tab, the arrow
+//! keys, Enter, and Escape, and a [`StickNavigator`] turns an analog stick
+//! or d-pad into steps that repeat while held, like a held arrow key.
+//! Gamepad face buttons map directly to [`NavCommand::Submit`] and
+//! [`NavCommand::Cancel`].
+
+use platform::input::KeyboardInput;
+use std::time::Duration;
+use winit::keyboard::KeyCode;
+
+/// Default stick deflection below which the stick counts as centered.
+pub const DEFAULT_DEADZONE: f32 = 0.5;
+
+/// Default time a direction is held before it starts repeating.
+pub const DEFAULT_REPEAT_DELAY: Duration = Duration::from_millis(400);
+
+/// Default time between repeated steps while a direction is held.
+pub const DEFAULT_REPEAT_INTERVAL: Duration = Duration::from_millis(120);
+
+/// A screen direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NavDirection {
+    /// Towards the top of the screen.
+    Up,
+    /// Towards the bottom of the screen.
+    Down,
+    /// Towards the left of the screen.
+    Left,
+    /// Towards the right of the screen.
+    Right,
+}
+
+/// A UI navigation command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NavCommand {
+    /// Focuses the next widget in tab order.
+    Next,
+    /// Focuses the previous widget in tab order.
+    Previous,
+    /// Focuses the nearest widget in a direction.
+    Move(NavDirection),
+    /// Activates the focused widget, or the default button.
+    Submit,
+    /// Activates the cancel button.
+    Cancel,
+}
+
+/// Returns the navigation commands for keys pressed this frame.
+///
+/// Tab moves to the next widget and Shift+Tab to the previous one, the
+/// arrow keys move spatially, Enter submits, and Escape cancels.
+#[must_use]
+pub fn keyboard_commands(keyboard: &KeyboardInput) -> Vec<NavCommand> {
+    let shift = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
+    let bindings = [
+        (
+            KeyCode::Tab,
+            if shift {
+                NavCommand::Previous
+            } else {
+                NavCommand::Next
+            },
+        ),
+        (KeyCode::ArrowUp, NavCommand::Move(NavDirection::Up)),
+        (KeyCode::ArrowDown, NavCommand::Move(NavDirection::Down)),
+        (KeyCode::ArrowLeft, NavCommand::Move(NavDirection::Left)),
+        (KeyCode::ArrowRight, NavCommand::Move(NavDirection::Right)),
+        (KeyCode::Enter, NavCommand::Submit),
+        (KeyCode::NumpadEnter, NavCommand::Submit),
+        (KeyCode::Escape, NavCommand::Cancel),
+    ];
+    bindings
+        .into_iter()
+        .filter(|(key, _)| keyboard.just_pressed(*key))
+        .map(|(_, command)| command)
+        .collect()
+}
+
+/// Turns an analog stick or d-pad into repeating navigation steps.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use ui::{NavCommand, NavDirection, StickNavigator};
+///
+/// let mut stick = StickNavigator::default();
+/// let frame = Duration::from_millis(16);
+///
+/// // Pushing the stick up steps once, then waits before repeating.
+/// assert_eq!(stick.update([0.0, 1.0], frame), Some(NavCommand::Move(NavDirection::Up)));
+/// assert_eq!(stick.update([0.0, 1.0], frame), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StickNavigator {
+    /// Deflection below which the stick counts as centered.
+    deadzone: f32,
+    /// Time a direction is held before it starts repeating.
+    repeat_delay: Duration,
+    /// Time between repeated steps.
+    repeat_interval: Duration,
+    /// Direction held, and the time until its next step.
+    held: Option<(NavDirection, Duration)>,
+}
+
+impl Default for StickNavigator {
+    fn default() -> Self {
+        Self {
+            deadzone: DEFAULT_DEADZONE,
+            repeat_delay: DEFAULT_REPEAT_DELAY,
+            repeat_interval: DEFAULT_REPEAT_INTERVAL,
+            held: None,
+        }
+    }
+}
+
+impl StickNavigator {
+    /// Sets the deflection, from 0 to 1, below which the stick counts as
+    /// centered.
+    #[must_use]
+    pub fn with_deadzone(mut self, deadzone: f32) -> Self {
+        self.deadzone = deadzone.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets how long a direction is held before repeating, and how often it
+    /// repeats after that.
+    #[must_use]
+    pub fn with_repeat(mut self, delay: Duration, interval: Duration) -> Self {
+        self.repeat_delay = delay;
+        self.repeat_interval = interval.max(Duration::from_millis(1));
+        self
+    }
+
+    /// Advances by `delta` with the stick at `stick`, where +y is up, and
+    /// returns the step to take this frame.
+    pub fn update(&mut self, stick: [f32; 2], delta: Duration) -> Option<NavCommand> {
+        let Some(direction) = self.direction(stick) else {
+            self.held = None;
+            return None;
+        };
+        match &mut self.held {
+            Some((held, remaining)) if *held == direction => {
+                if delta < *remaining {
+                    *remaining -= delta;
+                    return None;
+                }
+                *remaining = self.repeat_interval;
+                Some(NavCommand::Move(direction))
+            }
+            _ => {
+                self.held = Some((direction, self.repeat_delay));
+                Some(NavCommand::Move(direction))
+            }
+        }
+    }
+
+    /// Returns the direction the stick points, if outside the deadzone.
+    fn direction(&self, [x, y]: [f32; 2]) -> Option<NavDirection> {
+        if x.hypot(y) < self.deadzone.max(f32::EPSILON) {
+            return None;
+        }
+        Some(if x.abs() > y.abs() {
+            if x > 0.0 {
+                NavDirection::Right
+            } else {
+                NavDirection::Left
+            }
+        } else if y > 0.0 {
+            NavDirection::Up
+        } else {
+            NavDirection::Down
+        })
+    }
+}
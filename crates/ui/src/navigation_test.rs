@@ -0,0 +1,83 @@
+//! Unit tests for navigation input.
+
+use crate::navigation::{keyboard_commands, NavCommand, NavDirection, StickNavigator};
+use platform::input::{KeyboardEvent, KeyboardInput};
+use std::time::Duration;
+use winit::keyboard::{Key, KeyCode, NamedKey};
+
+fn press(keyboard: &mut KeyboardInput, physical: KeyCode, logical: NamedKey) {
+    keyboard.handle(KeyboardEvent {
+        physical,
+        logical: Key::Named(logical),
+        pressed: true,
+        repeat: false,
+    });
+}
+
+/// Verifies the keyboard mapping, including Shift+Tab.
+#[test]
+fn maps_keyboard() {
+    let mut keyboard = KeyboardInput::default();
+    press(&mut keyboard, KeyCode::Tab, NamedKey::Tab);
+    press(&mut keyboard, KeyCode::ArrowLeft, NamedKey::ArrowLeft);
+    assert_eq!(
+        keyboard_commands(&keyboard),
+        [NavCommand::Next, NavCommand::Move(NavDirection::Left)]
+    );
+
+    keyboard.begin_frame();
+    assert!(keyboard_commands(&keyboard).is_empty());
+    press(&mut keyboard, KeyCode::ShiftLeft, NamedKey::Shift);
+    press(&mut keyboard, KeyCode::Enter, NamedKey::Enter);
+    keyboard.handle(KeyboardEvent {
+        physical: KeyCode::Tab,
+        logical: Key::Named(NamedKey::Tab),
+        pressed: false,
+        repeat: false,
+    });
+    press(&mut keyboard, KeyCode::Tab, NamedKey::Tab);
+    assert_eq!(
+        keyboard_commands(&keyboard),
+        [NavCommand::Previous, NavCommand::Submit]
+    );
+}
+
+/// Verifies that a held stick steps, waits, then repeats.
+#[test]
+fn stick_repeats_while_held() {
+    let mut stick = StickNavigator::default()
+        .with_repeat(Duration::from_millis(300), Duration::from_millis(100));
+    let frame = Duration::from_millis(50);
+    let right = Some(NavCommand::Move(NavDirection::Right));
+
+    let steps: Vec<_> = (0..12).map(|_| stick.update([0.9, 0.2], frame)).collect();
+    let fired: Vec<usize> = steps
+        .iter()
+        .enumerate()
+        .filter(|(_, step)| **step == right)
+        .map(|(frame, _)| frame)
+        .collect();
+    assert_eq!(fired, [0, 6, 8, 10]);
+}
+
+/// Verifies the deadzone and that changing direction steps at once.
+#[test]
+fn stick_deadzone_and_direction_change() {
+    let mut stick = StickNavigator::default().with_deadzone(0.3);
+    let frame = Duration::from_millis(16);
+
+    assert_eq!(stick.update([0.2, 0.1], frame), None);
+    assert_eq!(
+        stick.update([0.0, -0.8], frame),
+        Some(NavCommand::Move(NavDirection::Down))
+    );
+    assert_eq!(
+        stick.update([-0.8, 0.1], frame),
+        Some(NavCommand::Move(NavDirection::Left))
+    );
+    assert_eq!(stick.update([0.0, 0.0], frame), None);
+    assert_eq!(
+        stick.update([-0.8, 0.1], frame),
+        Some(NavCommand::Move(NavDirection::Left))
+    );
+}
@@ -0,0 +1,248 @@
+//! Input prompts that follow the last-used input device.
+//!
+//! Prompts such as "press A to confirm" must show the glyph of the device
+//! the player is holding. An [`InputDeviceTracker`] remembers which device
+//! produced input last, and [`PromptGlyphs`] looks up the glyph of a UI
+//! action for that device, so prompts switch between keyboard and gamepad
+//! glyphs as soon as the player switches devices.
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// Action that activates the focused widget.
+pub const SUBMIT: &str = "ui.submit";
+
+/// Action that activates the cancel button.
+pub const CANCEL: &str = "ui.cancel";
+
+/// Action that focuses the next widget.
+pub const NEXT: &str = "ui.next";
+
+/// Action that focuses the previous widget.
+pub const PREVIOUS: &str = "ui.previous";
+
+/// Action that moves focus spatially.
+pub const NAVIGATE: &str = "ui.navigate";
+
+/// Gamepad families with distinct button glyphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GamepadFamily {
+    /// Xbox and `XInput` controllers: A, B, X, Y.
+    Xbox,
+    /// `PlayStation` controllers: cross, circle, square, triangle.
+    PlayStation,
+    /// Nintendo controllers: B, A, Y, X.
+    Nintendo,
+    /// Unknown controllers, labeled by button position.
+    #[default]
+    Generic,
+}
+
+impl GamepadFamily {
+    /// Guesses the family from a controller's reported name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Self {
+        let name = name.to_ascii_lowercase();
+        let contains = |patterns: &[&str]| patterns.iter().any(|p| name.contains(p));
+        if contains(&["xbox", "xinput"]) {
+            Self::Xbox
+        } else if contains(&["playstation", "dualshock", "dualsense", "ps4", "ps5"]) {
+            Self::PlayStation
+        } else if contains(&["nintendo", "switch", "joy-con", "pro controller"]) {
+            Self::Nintendo
+        } else {
+            Self::Generic
+        }
+    }
+}
+
+/// A kind of input device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum InputDevice {
+    /// A keyboard.
+    #[default]
+    Keyboard,
+    /// A mouse or other pointer.
+    Mouse,
+    /// A gamepad of the given family.
+    Gamepad(GamepadFamily),
+    /// A touch screen.
+    Touch,
+}
+
+impl InputDevice {
+    /// Returns `true` if the device points at widgets directly, rather than
+    /// moving focus between them.
+    #[must_use]
+    pub fn is_pointer(self) -> bool {
+        matches!(self, Self::Mouse | Self::Touch)
+    }
+}
+
+impl fmt::Display for InputDevice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Keyboard => f.write_str("keyboard"),
+            Self::Mouse => f.write_str("mouse"),
+            Self::Gamepad(family) => write!(f, "{family:?} gamepad"),
+            Self::Touch => f.write_str("touch"),
+        }
+    }
+}
+
+/// Remembers which input device was used last.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InputDeviceTracker {
+    /// Device that produced input last.
+    current: InputDevice,
+}
+
+impl InputDeviceTracker {
+    /// Returns the device that produced input last.
+    #[must_use]
+    #[inline]
+    pub fn current(&self) -> InputDevice {
+        self.current
+    }
+
+    /// Records input from `device`, returning `true` if it differs from the
+    /// previous device.
+    ///
+    /// Callers should only report deliberate input: a stick resting just
+    /// outside its center or a mouse nudged by the desk would otherwise
+    /// flip prompts back and forth.
+    pub fn observe(&mut self, device: InputDevice) -> bool {
+        let changed = self.current != device;
+        self.current = device;
+        changed
+    }
+}
+
+/// Which glyphs a device shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum GlyphSet {
+    /// Keyboard keys, also shown for mouse users.
+    Keyboard,
+    /// Gamepad buttons.
+    Gamepad(GamepadFamily),
+}
+
+impl GlyphSet {
+    fn of(device: InputDevice) -> Option<Self> {
+        match device {
+            InputDevice::Keyboard | InputDevice::Mouse => Some(Self::Keyboard),
+            InputDevice::Gamepad(family) => Some(Self::Gamepad(family)),
+            InputDevice::Touch => None,
+        }
+    }
+}
+
+/// Glyph names of actions per input device.
+///
+/// Glyph names identify images in the game's prompt atlas. The defaults
+/// cover the UI actions; games add their own actions with [`set`](Self::set).
+///
+/// # Example
+///
+/// ```
+/// use ui::prompt::{self, GamepadFamily, InputDevice, PromptGlyphs};
+///
+/// let glyphs = PromptGlyphs::default();
+/// assert_eq!(glyphs.glyph(InputDevice::Keyboard, prompt::SUBMIT), Some("key_enter"));
+/// let xbox = InputDevice::Gamepad(GamepadFamily::Xbox);
+/// assert_eq!(glyphs.glyph(xbox, prompt::SUBMIT), Some("xbox_a"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PromptGlyphs {
+    /// Glyph name by glyph set and action.
+    glyphs: HashMap<(GlyphSet, String), String>,
+}
+
+impl Default for PromptGlyphs {
+    fn default() -> Self {
+        let mut glyphs = Self {
+            glyphs: HashMap::new(),
+        };
+        let defaults = [
+            (
+                InputDevice::Keyboard,
+                [
+                    "key_enter",
+                    "key_escape",
+                    "key_tab",
+                    "key_shift_tab",
+                    "key_arrows",
+                ],
+            ),
+            (
+                InputDevice::Gamepad(GamepadFamily::Xbox),
+                ["xbox_a", "xbox_b", "xbox_rb", "xbox_lb", "xbox_dpad"],
+            ),
+            (
+                InputDevice::Gamepad(GamepadFamily::PlayStation),
+                ["ps_cross", "ps_circle", "ps_r1", "ps_l1", "ps_dpad"],
+            ),
+            (
+                InputDevice::Gamepad(GamepadFamily::Nintendo),
+                [
+                    "switch_a",
+                    "switch_b",
+                    "switch_r",
+                    "switch_l",
+                    "switch_dpad",
+                ],
+            ),
+            (
+                InputDevice::Gamepad(GamepadFamily::Generic),
+                [
+                    "gamepad_south",
+                    "gamepad_east",
+                    "gamepad_right_shoulder",
+                    "gamepad_left_shoulder",
+                    "gamepad_dpad",
+                ],
+            ),
+        ];
+        for (device, names) in defaults {
+            for (action, name) in [SUBMIT, CANCEL, NEXT, PREVIOUS, NAVIGATE]
+                .into_iter()
+                .zip(names)
+            {
+                glyphs.set(device, action, name);
+            }
+        }
+        glyphs
+    }
+}
+
+impl PromptGlyphs {
+    /// Sets the glyph of `action` on `device`.
+    ///
+    /// Mouse users see keyboard glyphs, so setting a glyph for either sets
+    /// it for both. Touch devices show no prompts and are ignored.
+    pub fn set(&mut self, device: InputDevice, action: &str, glyph: &str) {
+        if let Some(set) = GlyphSet::of(device) {
+            self.glyphs
+                .insert((set, action.to_owned()), glyph.to_owned());
+        }
+    }
+
+    /// Returns the glyph of `action` on `device`.
+    ///
+    /// Gamepads without a glyph of their own fall back to the generic
+    /// gamepad glyph. Returns `None` for touch devices, which show no
+    /// prompts.
+    #[must_use]
+    pub fn glyph(&self, device: InputDevice, action: &str) -> Option<&str> {
+        let set = GlyphSet::of(device)?;
+        let lookup = |set| {
+            self.glyphs
+                .get(&(set, action.to_owned()))
+                .map(String::as_str)
+        };
+        lookup(set).or_else(|| match set {
+            GlyphSet::Gamepad(_) => lookup(GlyphSet::Gamepad(GamepadFamily::Generic)),
+            GlyphSet::Keyboard => None,
+        })
+    }
+}
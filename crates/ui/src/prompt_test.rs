@@ -0,0 +1,59 @@
+//! Unit tests for input prompts.
+
+use crate::prompt::{self, GamepadFamily, InputDevice, InputDeviceTracker, PromptGlyphs};
+
+/// Verifies that the tracker reports device switches.
+#[test]
+fn tracks_last_device() {
+    let mut tracker = InputDeviceTracker::default();
+    assert_eq!(tracker.current(), InputDevice::Keyboard);
+
+    let xbox = InputDevice::Gamepad(GamepadFamily::Xbox);
+    assert!(tracker.observe(xbox));
+    assert!(!tracker.observe(xbox));
+    assert_eq!(tracker.current(), xbox);
+    assert!(tracker.observe(InputDevice::Mouse));
+}
+
+/// Verifies glyph lookup per device, with fallbacks.
+#[test]
+fn looks_up_glyphs() {
+    let mut glyphs = PromptGlyphs::default();
+    let ps = InputDevice::Gamepad(GamepadFamily::PlayStation);
+
+    assert_eq!(glyphs.glyph(ps, prompt::CANCEL), Some("ps_circle"));
+    assert_eq!(
+        glyphs.glyph(InputDevice::Mouse, prompt::CANCEL),
+        Some("key_escape")
+    );
+    assert_eq!(glyphs.glyph(InputDevice::Touch, prompt::SUBMIT), None);
+
+    glyphs.set(InputDevice::Keyboard, "jump", "key_space");
+    glyphs.set(
+        InputDevice::Gamepad(GamepadFamily::Generic),
+        "jump",
+        "gamepad_south",
+    );
+    glyphs.set(InputDevice::Touch, "jump", "ignored");
+    assert_eq!(glyphs.glyph(InputDevice::Mouse, "jump"), Some("key_space"));
+    assert_eq!(glyphs.glyph(ps, "jump"), Some("gamepad_south"));
+    assert_eq!(glyphs.glyph(InputDevice::Touch, "jump"), None);
+    assert_eq!(glyphs.glyph(InputDevice::Keyboard, "crouch"), None);
+}
+
+/// Verifies gamepad family detection from controller names.
+#[test]
+fn detects_gamepad_family() {
+    let cases = [
+        ("Xbox Wireless Controller", GamepadFamily::Xbox),
+        (
+            "Sony DualSense Wireless Controller",
+            GamepadFamily::PlayStation,
+        ),
+        ("Nintendo Switch Pro Controller", GamepadFamily::Nintendo),
+        ("8BitDo SN30", GamepadFamily::Generic),
+    ];
+    for (name, family) in cases {
+        assert_eq!(GamepadFamily::from_name(name), family, "{name}");
+    }
+}
@@ -0,0 +1,101 @@
+//! User interface subsystem implementation.
+//!
+//! Provides the [`RustgineUi`] system, which owns the state shared by all
+//! widgets: focus, the last-used input device, and data bindings.
+
+use crate::bindings::UiBindings;
+use crate::focus::FocusManager;
+use crate::prompt::{InputDevice, InputDeviceTracker};
+use rustgine_core::{RustgineError, RustgineSystem};
+use tracing::info;
+
+/// User interface subsystem for the Rustgine engine.
+///
+/// Manages:
+/// - Which widget has focus, and navigation between widgets
+/// - The last-used input device, which decides focus rings and prompts
+/// - Widgets bound to ECS components and resources
+///
+/// # Example
+///
+/// ```ignore
+/// use ui::RustgineUi;
+/// use rustgine_core::RustgineSystem;
+///
+/// let mut ui = RustgineUi::default();
+/// ui.startup()?;
+/// ```
+#[derive(Debug, Default)]
+pub struct RustgineUi {
+    /// Widget focus.
+    focus: FocusManager,
+    /// Last-used input device.
+    devices: InputDeviceTracker,
+    /// Widgets bound to ECS data.
+    bindings: UiBindings,
+}
+
+impl RustgineUi {
+    /// Returns the focus state.
+    #[must_use]
+    #[inline]
+    pub fn focus(&self) -> &FocusManager {
+        &self.focus
+    }
+
+    /// Returns the focus state for changes.
+    #[inline]
+    pub fn focus_mut(&mut self) -> &mut FocusManager {
+        &mut self.focus
+    }
+
+    /// Returns the data bindings.
+    #[must_use]
+    #[inline]
+    pub fn bindings(&self) -> &UiBindings {
+        &self.bindings
+    }
+
+    /// Returns the data bindings for changes.
+    #[inline]
+    pub fn bindings_mut(&mut self) -> &mut UiBindings {
+        &mut self.bindings
+    }
+
+    /// Returns the last-used input device.
+    #[must_use]
+    #[inline]
+    pub fn input_device(&self) -> InputDevice {
+        self.devices.current()
+    }
+
+    /// Records input from `device`, updating focus ring visibility when the
+    /// player switches between pointer and keyboard or gamepad.
+    pub fn observe_input(&mut self, device: InputDevice) {
+        if self.devices.observe(device) {
+            self.focus.set_input_device(device);
+        }
+    }
+}
+
+impl RustgineSystem for RustgineUi {
+    /// Initializes the UI subsystem.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if initialization fails.
+    fn startup(&mut self) -> Result<(), RustgineError> {
+        info!(device = %self.devices.current(), "ui started");
+        Ok(())
+    }
+
+    /// Shuts down the UI subsystem.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if cleanup fails.
+    #[inline]
+    fn shutdown(&mut self) -> Result<(), RustgineError> {
+        Ok(())
+    }
+}